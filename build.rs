@@ -0,0 +1,13 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto/monitor.proto");
+    // Only the gRPC API (`api` and `monitor` features) uses the generated code
+    if std::env::var_os("CARGO_FEATURE_API").is_none() || std::env::var_os("CARGO_FEATURE_MONITOR").is_none() {
+        return Ok(());
+    }
+    // A protoc named by PROTOC wins, otherwise the vendored one is used
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    }
+    tonic_build::compile_protos("proto/monitor.proto")?;
+    Ok(())
+}
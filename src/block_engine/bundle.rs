@@ -0,0 +1,282 @@
+//! Jito bundles: the snipe buy, the tip and an optional guard are sent as one atomic unit, so a
+//! buy that lands with a bad fill (or lands without its tip) cannot happen. Either every
+//! transaction of the bundle lands in order or none does.
+//!
+//! The guard is the buy's `MinOutGuard` in a transaction of its own: when the buy delivered less
+//! than expected it fails and takes the whole bundle with it. The tip rides in the last
+//! transaction so it is only paid together with the checks.
+//!
+//! With `JITO_BUNDLES_ENABLED=true` buys go to `JITO_BLOCK_ENGINE_URL` as bundles tipping
+//! `JITO_TIP_VALUE` SOL to one of `JITO_TIP_ACCOUNTS`, or to the block engine's own tip accounts
+//! (`getTipAccounts`) when that is empty.
+//!
+//! Given the bot's address lookup table, the transactions are compiled as v0 messages resolving
+//! the accounts the table holds through it; without one they carry legacy messages.
+
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_sdk::{
+    hash::Hash, instruction::Instruction, message::AddressLookupTableAccount, pubkey::Pubkey,
+    signature::{Keypair, Signature}, signer::Signer, system_instruction, transaction::VersionedTransaction,
+};
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use rand::{seq::SliceRandom, thread_rng};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::block_engine::guard::MinOutGuard;
+use crate::block_engine::lookup_table::compile_message;
+use crate::block_engine::preflight;
+use crate::error::{BotError, ClientError};
+use crate::library::circuit_breaker::{self, CircuitBreaker};
+
+lazy_static! {
+    static ref SHARED_JITO: Option<Arc<JitoClient>> = JitoClient::from_env().map(Arc::new);
+}
+
+/// Transactions a block engine accepts per bundle
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 5;
+
+/// Signed transactions landing together, buy first
+#[derive(Clone, Debug)]
+pub struct Bundle {
+    pub transactions: Vec<VersionedTransaction>,
+}
+
+impl Bundle {
+    /// Signature of each transaction, in bundle order
+    pub fn signatures(&self) -> Vec<Signature> {
+        self.transactions.iter().map(|tx| tx.signatures[0]).collect()
+    }
+}
+
+/// Packs a buy, a tip and an optional guard into a bundle
+pub struct BundleBuilder<'a> {
+    payer: &'a Keypair,
+    recent_blockhash: Hash,
+    buy: Vec<Instruction>,
+    tip: Option<(Pubkey, u64)>,
+    guard: Option<MinOutGuard>,
+    lookup_table: Option<AddressLookupTableAccount>,
+}
+
+impl<'a> BundleBuilder<'a> {
+    pub fn new(payer: &'a Keypair, recent_blockhash: Hash) -> Self {
+        Self { payer, recent_blockhash, buy: Vec::new(), tip: None, guard: None, lookup_table: None }
+    }
+
+    pub fn buy(mut self, instructions: Vec<Instruction>) -> Self {
+        self.buy = instructions;
+        self
+    }
+
+    pub fn tip(mut self, account: Pubkey, lamports: u64) -> Self {
+        self.tip = Some((account, lamports));
+        self
+    }
+
+    pub fn guard(mut self, guard: Option<MinOutGuard>) -> Self {
+        self.guard = guard;
+        self
+    }
+
+    /// Compile v0 transactions resolving accounts through `table`
+    pub fn lookup_table(mut self, table: Option<AddressLookupTableAccount>) -> Self {
+        self.lookup_table = table;
+        self
+    }
+
+    /// The buy, then the guard; the tip is added to the last transaction. A buy too large for
+    /// one transaction takes several
+    pub fn build(self) -> Result<Bundle> {
+        if self.buy.is_empty() {
+            return Err(anyhow!("A bundle needs a buy"));
+        }
+        let (tip_account, tip_lamports) = self.tip.ok_or_else(|| anyhow!("A bundle needs a tip"))?;
+        let tip = system_instruction::transfer(&self.payer.pubkey(), &tip_account, tip_lamports);
+
+        let mut groups = vec![self.buy];
+        if let Some(guard) = &self.guard {
+            if guard.owner != self.payer.pubkey() {
+                return Err(anyhow!("The guard must check the payer's token account"));
+            }
+            groups.push(vec![guard.instruction()?]);
+        }
+        groups.last_mut().expect("the buy is always present").push(tip);
+
+        // Groups too large for one transaction are split by the preflight check; the bundle
+        // keeps the parts atomic
+        let mut fitted = Vec::new();
+        for group in groups {
+            let parts = preflight::split(&self.payer.pubkey(), group, &[], self.recent_blockhash, self.lookup_table.as_ref())
+                .map_err(|e| anyhow!("Bundle transaction exceeds protocol limits: {}", e))?;
+            fitted.extend(parts);
+        }
+        if fitted.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(anyhow!("The bundle needs {} transactions, at most {} fit", fitted.len(), MAX_BUNDLE_TRANSACTIONS));
+        }
+
+        let transactions = fitted.iter()
+            .map(|instructions| {
+                let message = compile_message(
+                    &self.payer.pubkey(),
+                    instructions,
+                    self.recent_blockhash,
+                    self.lookup_table.as_ref(),
+                )?;
+                Ok(VersionedTransaction::try_new(message, &[self.payer])?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Bundle { transactions })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct JitoConfig {
+    pub url: String,
+    /// Sent as `x-jito-auth` when the block engine has granted a UUID with a higher rate limit
+    pub auth_uuid: Option<String>,
+    pub tip_lamports: u64,
+    /// Fetched from the block engine when empty
+    pub tip_accounts: Vec<Pubkey>,
+}
+
+impl JitoConfig {
+    pub const DEFAULT_URL: &'static str = "https://mainnet.block-engine.jito.wtf";
+
+    /// None unless JITO_BUNDLES_ENABLED is true
+    pub fn from_env() -> Option<Result<Self>> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let enabled = var("JITO_BUNDLES_ENABLED")
+            .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+        let parse = || -> Result<Self> {
+            let tip: f64 = var("JITO_TIP_VALUE").unwrap_or_else(|| "0.001".to_string())
+                .parse()
+                .map_err(|_| anyhow!("Invalid JITO_TIP_VALUE"))?;
+            if tip <= 0.0 {
+                return Err(anyhow!("JITO_TIP_VALUE must be positive, bundles without a tip are dropped"));
+            }
+            let tip_accounts = var("JITO_TIP_ACCOUNTS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|a| !a.is_empty())
+                .map(|a| Pubkey::from_str(a).map_err(|_| anyhow!("Invalid tip account in JITO_TIP_ACCOUNTS: {}", a)))
+                .collect::<Result<_>>()?;
+            Ok(Self {
+                url: var("JITO_BLOCK_ENGINE_URL").unwrap_or_else(|| Self::DEFAULT_URL.to_string()).trim_end_matches('/').to_string(),
+                auth_uuid: var("JITO_AUTH_UUID"),
+                tip_lamports: (tip * 1_000_000_000.0).round() as u64,
+                tip_accounts,
+            })
+        };
+        Some(parse())
+    }
+
+    /// JSON-RPC `sendBundle` body for base64 wire transactions
+    pub fn send_bundle_body(&self, encoded_txs: &[String]) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendBundle",
+            "params": [encoded_txs, { "encoding": "base64" }],
+        })
+    }
+}
+
+pub struct JitoClient {
+    config: JitoConfig,
+    client: reqwest::Client,
+    breaker: Arc<CircuitBreaker>,
+    /// Tip accounts from the configuration, or fetched once from the block engine
+    tip_accounts: Mutex<Vec<Pubkey>>,
+}
+
+impl JitoClient {
+    pub fn new(config: JitoConfig) -> Self {
+        let tip_accounts = Mutex::new(config.tip_accounts.clone());
+        Self { config, client: reqwest::Client::new(), breaker: circuit_breaker::breaker("jito"), tip_accounts }
+    }
+
+    /// None unless bundles are enabled, or (with an error printed) when misconfigured
+    pub fn from_env() -> Option<Self> {
+        match JitoConfig::from_env()? {
+            Ok(config) => Some(Self::new(config)),
+            Err(e) => {
+                eprintln!("Jito bundles disabled: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Process-wide client, None when bundles are disabled
+    pub fn shared() -> Option<Arc<JitoClient>> {
+        SHARED_JITO.clone()
+    }
+
+    pub fn config(&self) -> &JitoConfig {
+        &self.config
+    }
+
+    /// One of the tip accounts, picked at random to spread write locks
+    pub async fn tip_account(&self) -> Result<Pubkey, BotError> {
+        let mut accounts = self.tip_accounts.lock().await;
+        if accounts.is_empty() {
+            let result = self.rpc("getTipAccounts", json!([])).await?;
+            *accounts = result.as_array()
+                .map(|list| list.iter().filter_map(|a| a.as_str()).filter_map(|a| Pubkey::from_str(a).ok()).collect())
+                .unwrap_or_default();
+        }
+        accounts.choose(&mut thread_rng())
+            .copied()
+            .ok_or_else(|| ClientError::Jito("No tip account".to_string(), "getTipAccounts returned none".to_string()).into())
+    }
+
+    /// Submit a bundle, returning its bundle id
+    pub async fn send_bundle(&self, bundle: &Bundle) -> Result<String, BotError> {
+        if bundle.transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(BotError::InvalidConfig(format!("A bundle holds at most {} transactions", MAX_BUNDLE_TRANSACTIONS)));
+        }
+        let encoded = bundle.transactions.iter()
+            .map(|tx| bincode::serialize(tx).map(|wire| bs64::encode(&wire)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ClientError::Parse("Transaction serialization failed".to_string(), e.to_string()))?;
+        let body = self.config.send_bundle_body(&encoded);
+        let result = self.breaker.call(self.post(body)).await?;
+        result.as_str()
+            .map(str::to_string)
+            .ok_or_else(|| ClientError::Parse("Invalid response format".to_string(), "Missing bundle id".to_string()).into())
+    }
+
+    async fn rpc(&self, method: &str, params: Value) -> Result<Value, BotError> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        self.breaker.call(self.post(body)).await
+    }
+
+    async fn post(&self, body: Value) -> Result<Value, BotError> {
+        let mut request = self.client
+            .post(format!("{}/api/v1/bundles", self.config.url))
+            .header("Content-Type", "application/json")
+            .json(&body);
+        if let Some(uuid) = &self.config.auth_uuid {
+            request = request.header("x-jito-auth", uuid);
+        }
+        let response: Value = request
+            .send()
+            .await
+            .map_err(|e| ClientError::Jito("Request failed".to_string(), e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| ClientError::Parse("Invalid JSON response".to_string(), e.to_string()))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(ClientError::Jito("Block engine error".to_string(), error.to_string()).into());
+        }
+        Ok(response["result"].clone())
+    }
+}
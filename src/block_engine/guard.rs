@@ -0,0 +1,135 @@
+//! On-chain assertions appended to swap transactions, so a swap reverts when the expectations it
+//! was built on no longer hold at execution time.
+//!
+//! The assertion is a self-transfer (`transferChecked` from the wallet's token account to itself)
+//! of the amount the swap must deliver. The token program moves nothing, but fails the
+//! transaction when the account:
+//! - holds less than the expected amount (a worse fill than quoted),
+//! - is not owned by the wallet or is for another mint (wrong authority or account),
+//! - has been frozen by the mint's freeze authority.
+//!
+//! Only the standard token programs are used, so no extra program has to be trusted. Buys on
+//! pump.fun and PumpSwap are guarded; sells are already bounded by the program's minimum out.
+//! Enabled with `TRADE_GUARDS_ENABLED=true`.
+
+use std::str::FromStr;
+use anchor_client::solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+
+use crate::common::cluster::DexProgram;
+use crate::dex::pump_fun::PUMP_BUY_METHOD;
+use crate::dex::pump_swap::BUY_DISCRIMINATOR as PUMP_SWAP_BUY_DISCRIMINATOR;
+
+lazy_static! {
+    static ref GUARDS_ENABLED: bool = std::env::var("TRADE_GUARDS_ENABLED")
+        .ok()
+        .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+        .unwrap_or(false);
+}
+
+/// Pump.fun tokens all have 6 decimals
+const PUMP_FUN_DECIMALS: u8 = 6;
+
+/// Whether swap transactions carry guards (TRADE_GUARDS_ENABLED)
+pub fn guards_enabled() -> bool {
+    *GUARDS_ENABLED
+}
+
+/// Asserts that the swap delivered at least `min_amount` tokens to the owner's `token_account`
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinOutGuard {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub token_program: Pubkey,
+    /// Checked against the mint when known; required for Token-2022 accounts
+    pub decimals: Option<u8>,
+    /// Raw token amount the account must hold after the swap
+    pub min_amount: u64,
+}
+
+impl MinOutGuard {
+    /// Guard for the first guarded swap among `instructions`
+    pub fn for_swap(instructions: &[Instruction]) -> Option<Self> {
+        Self::from_pump_fun_buy(instructions).or_else(|| Self::from_pump_swap_buy(instructions))
+    }
+
+    /// Guard for a pump.fun buy, expecting the exact token amount it buys
+    pub fn from_pump_fun_buy(instructions: &[Instruction]) -> Option<Self> {
+        let program = DexProgram::PumpFun.id();
+        let buy = instructions.iter().find(|ix| {
+            ix.program_id == program && ix.data.get(..8) == Some(&PUMP_BUY_METHOD.to_le_bytes()[..])
+        })?;
+        Some(Self {
+            mint: buy.accounts.get(2)?.pubkey,
+            token_account: buy.accounts.get(5)?.pubkey,
+            owner: buy.accounts.get(6)?.pubkey,
+            token_program: buy.accounts.get(8)?.pubkey,
+            decimals: Some(PUMP_FUN_DECIMALS),
+            min_amount: read_amount(&buy.data)?,
+        })
+    }
+
+    /// Guard for a PumpSwap buy, expecting the exact base amount it buys
+    pub fn from_pump_swap_buy(instructions: &[Instruction]) -> Option<Self> {
+        let program = DexProgram::PumpSwap.id();
+        let buy = instructions.iter().find(|ix| {
+            ix.program_id == program && ix.data.get(..8) == Some(&PUMP_SWAP_BUY_DISCRIMINATOR[..])
+        })?;
+        Some(Self {
+            owner: buy.accounts.get(1)?.pubkey,
+            mint: buy.accounts.get(3)?.pubkey,
+            token_account: buy.accounts.get(5)?.pubkey,
+            token_program: buy.accounts.get(11)?.pubkey,
+            decimals: None,
+            min_amount: read_amount(&buy.data)?,
+        })
+    }
+
+    /// Self-transfer of `min_amount`: the token program checks the balance, authority and frozen
+    /// state before it notices source and destination are the same account
+    pub fn instruction(&self) -> Result<Instruction> {
+        match self.decimals {
+            Some(decimals) => Ok(spl_token_2022::instruction::transfer_checked(
+                &self.token_program,
+                &self.token_account,
+                &self.mint,
+                &self.token_account,
+                &self.owner,
+                &[],
+                self.min_amount,
+                decimals,
+            )?),
+            None if self.token_program == spl_token::id() => Ok(spl_token::instruction::transfer(
+                &self.token_program,
+                &self.token_account,
+                &self.token_account,
+                &self.owner,
+                &[],
+                self.min_amount,
+            )?),
+            None => Err(anyhow!("Guarding a {} account needs the mint's decimals", self.token_program)),
+        }
+    }
+}
+
+/// Append the guard of the swap among `instructions` when guards are enabled; unguarded and
+/// unrecognised transactions are returned as they are
+pub fn with_guard(mut instructions: Vec<Instruction>) -> Vec<Instruction> {
+    if !guards_enabled() {
+        return instructions;
+    }
+    if let Some(guard) = MinOutGuard::for_swap(&instructions) {
+        match guard.instruction() {
+            Ok(instruction) => instructions.push(instruction),
+            Err(e) => eprintln!("Swap of {} sent without a guard: {}", guard.mint, e),
+        }
+    }
+    instructions
+}
+
+/// Amount following the 8 byte discriminator
+fn read_amount(data: &[u8]) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(8..16)?.try_into().ok()?))
+}
@@ -0,0 +1,340 @@
+//! The bot's own address lookup table, listing the accounts its transactions use most: DEX
+//! programs with their config, fee and event accounts, the token programs, tip accounts, and the
+//! pool vaults the bot keeps trading through. Bundles are compiled as v0 transactions
+//! referencing the table, so each listed account costs one byte instead of 32, which leaves room
+//! for more instructions per transaction.
+//!
+//! With `LOOKUP_TABLE_ENABLED=true` the table at `LOOKUP_TABLE_ADDRESS` is used, or the one saved
+//! at `LOOKUP_TABLE_PATH` by a previous run; without either, a table is created on startup and
+//! its address saved there. Every `LOOKUP_TABLE_REFRESH_SECS` the table is extended with the
+//! accounts it is missing: the fixed set above, plus any account (other than a signer) that sent
+//! swaps used at least `LOOKUP_TABLE_MIN_USES` times. Only the table's authority can extend it;
+//! a table owned by another wallet is used as it is.
+//!
+//! A table holds at most 256 addresses, and an address added to it can be referenced from the
+//! next slot on, so transactions only use the addresses read back from the chain.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::{
+    address_lookup_table::{instruction as alt_instruction, state::AddressLookupTable},
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, AddressLookupTableAccount, Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use lazy_static::lazy_static;
+use tokio_util::sync::CancellationToken;
+
+use crate::block_engine::bundle::JitoClient;
+use crate::common::cluster::DexProgram;
+use crate::common::logger::Logger;
+use crate::dex::{pump_fun, pump_swap, raydium_launchpad};
+use crate::library::priority_lane::PriorityLaneClient;
+use crate::library::zeroslot;
+
+lazy_static! {
+    static ref SHARED_TABLE: Option<Arc<LookupTableManager>> = LookupTableConfig::from_env().map(|config| Arc::new(LookupTableManager::new(config)));
+}
+
+/// Addresses a lookup table can hold
+pub const MAX_ADDRESSES: usize = 256;
+/// Addresses added per extend transaction, keeping it under the packet size
+pub const EXTEND_CHUNK: usize = 20;
+
+#[derive(Clone, Debug)]
+pub struct LookupTableConfig {
+    /// Table to use; created when neither this nor a saved address is set
+    pub address: Option<Pubkey>,
+    /// Where the address of a created table is saved for later runs
+    pub path: Option<PathBuf>,
+    /// Uses by sent swaps after which an account is added to the table
+    pub min_uses: u32,
+    pub refresh: Duration,
+}
+
+impl Default for LookupTableConfig {
+    fn default() -> Self {
+        Self {
+            address: None,
+            path: Some(PathBuf::from("lookup_table.txt")),
+            min_uses: 3,
+            refresh: Duration::from_secs(300),
+        }
+    }
+}
+
+impl LookupTableConfig {
+    /// None unless LOOKUP_TABLE_ENABLED is true
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let enabled = var("LOOKUP_TABLE_ENABLED")
+            .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+        let defaults = Self::default();
+        let path = match std::env::var("LOOKUP_TABLE_PATH") {
+            Ok(path) => Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty()),
+            Err(_) => defaults.path,
+        };
+        Some(Self {
+            address: var("LOOKUP_TABLE_ADDRESS").and_then(|v| match Pubkey::from_str(v.trim()) {
+                Ok(address) => Some(address),
+                Err(_) => {
+                    eprintln!("Ignoring invalid LOOKUP_TABLE_ADDRESS '{}'", v);
+                    None
+                }
+            }),
+            path,
+            min_uses: var("LOOKUP_TABLE_MIN_USES").and_then(|v| v.parse().ok()).unwrap_or(defaults.min_uses).max(1),
+            refresh: var("LOOKUP_TABLE_REFRESH_SECS")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.refresh),
+        })
+    }
+
+    /// The configured table, else the one a previous run saved
+    pub fn table_address(&self) -> Option<Pubkey> {
+        self.address.or_else(|| {
+            let path = self.path.as_ref()?;
+            Pubkey::from_str(std::fs::read_to_string(path).ok()?.trim()).ok()
+        })
+    }
+}
+
+/// Accounts every run uses: DEX programs with their global, fee and event accounts, system and
+/// token programs, and the tip accounts of the configured senders
+pub fn core_accounts() -> Vec<Pubkey> {
+    let parsed = [
+        pump_fun::PUMP_GLOBAL,
+        pump_fun::PUMP_FEE_RECIPIENT,
+        pump_fun::PUMP_EVENT_AUTHORITY,
+        pump_fun::RENT_PROGRAM,
+    ]
+    .iter()
+    .filter_map(|a| Pubkey::from_str(a).ok());
+    let mut accounts: Vec<Pubkey> = [DexProgram::PumpFun, DexProgram::PumpSwap, DexProgram::RaydiumLaunchpad]
+        .iter()
+        .map(|dex| dex.id())
+        .chain(parsed)
+        .chain([
+            pump_swap::PUMP_GLOBAL_CONFIG,
+            pump_swap::PUMP_SWAP_FEE_RECIPIENT,
+            pump_swap::PUMP_EVENT_AUTHORITY,
+            raydium_launchpad::RAYDIUM_LAUNCHPAD_AUTHORITY,
+            raydium_launchpad::RAYDIUM_GLOBAL_CONFIG,
+            raydium_launchpad::RAYDIUM_PLATFORM_CONFIG,
+            raydium_launchpad::EVENT_AUTHORITY,
+            pump_swap::TOKEN_PROGRAM,
+            pump_swap::TOKEN_2022_PROGRAM,
+            pump_swap::ASSOCIATED_TOKEN_PROGRAM,
+            pump_swap::SOL_MINT,
+            solana_sdk::system_program::id(),
+            solana_sdk::compute_budget::id(),
+        ])
+        .chain(zeroslot::tip_accounts())
+        .chain(JitoClient::shared().map(|jito| jito.config().tip_accounts.clone()).unwrap_or_default())
+        .chain(PriorityLaneClient::shared().map(|lane| lane.config().tip_accounts.clone()).unwrap_or_default())
+        .collect();
+    let mut seen = HashSet::new();
+    accounts.retain(|a| seen.insert(*a));
+    accounts
+}
+
+/// Addresses of `wanted` the table lacks, in extend-sized chunks; stops once the table would be full
+pub fn plan_extension(table: &[Pubkey], wanted: &[Pubkey]) -> Vec<Vec<Pubkey>> {
+    let mut present: HashSet<Pubkey> = table.iter().copied().collect();
+    let room = MAX_ADDRESSES.saturating_sub(table.len());
+    let missing: Vec<Pubkey> = wanted.iter().copied().filter(|a| present.insert(*a)).take(room).collect();
+    missing.chunks(EXTEND_CHUNK).map(|chunk| chunk.to_vec()).collect()
+}
+
+/// A v0 message resolving accounts through `table` when there is one, a legacy message otherwise
+pub fn compile_message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    recent_blockhash: Hash,
+    table: Option<&AddressLookupTableAccount>,
+) -> Result<VersionedMessage> {
+    match table {
+        Some(table) => Ok(VersionedMessage::V0(v0::Message::try_compile(
+            payer,
+            instructions,
+            std::slice::from_ref(table),
+            recent_blockhash,
+        )?)),
+        None => Ok(VersionedMessage::Legacy(Message::new_with_blockhash(instructions, Some(payer), &recent_blockhash))),
+    }
+}
+
+/// How often sent swaps used each account
+#[derive(Default)]
+pub struct AccountUsage {
+    counts: Mutex<HashMap<Pubkey, u32>>,
+}
+
+impl AccountUsage {
+    /// Count the programs and non-signer accounts of `instructions`, once per transaction
+    pub fn record(&self, instructions: &[Instruction]) {
+        let used: HashSet<Pubkey> = instructions.iter()
+            .flat_map(|ix| {
+                ix.accounts.iter()
+                    .filter(|meta| !meta.is_signer)
+                    .map(|meta| meta.pubkey)
+                    .chain(std::iter::once(ix.program_id))
+            })
+            .collect();
+        let mut counts = self.counts.lock().unwrap();
+        for account in used {
+            *counts.entry(account).or_insert(0) += 1;
+        }
+    }
+
+    /// Accounts used at least `min_uses` times, most used first
+    pub fn frequent(&self, min_uses: u32) -> Vec<Pubkey> {
+        let counts = self.counts.lock().unwrap();
+        let mut frequent: Vec<(Pubkey, u32)> = counts.iter()
+            .filter(|(_, uses)| **uses >= min_uses)
+            .map(|(account, uses)| (*account, *uses))
+            .collect();
+        frequent.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        frequent.into_iter().map(|(account, _)| account).collect()
+    }
+}
+
+pub struct LookupTableManager {
+    config: LookupTableConfig,
+    /// The table as last read from the chain
+    table: RwLock<Option<AddressLookupTableAccount>>,
+    usage: AccountUsage,
+}
+
+impl LookupTableManager {
+    pub fn new(config: LookupTableConfig) -> Self {
+        Self { config, table: RwLock::new(None), usage: AccountUsage::default() }
+    }
+
+    /// Process-wide table, None when lookup tables are disabled
+    pub fn shared() -> Option<Arc<LookupTableManager>> {
+        SHARED_TABLE.clone()
+    }
+
+    /// The table transactions may reference, None until it has been read from the chain
+    pub fn current(&self) -> Option<AddressLookupTableAccount> {
+        self.table.read().unwrap().clone()
+    }
+
+    pub fn set_current(&self, table: AddressLookupTableAccount) {
+        *self.table.write().unwrap() = Some(table);
+    }
+
+    pub fn record_use(&self, instructions: &[Instruction]) {
+        self.usage.record(instructions);
+    }
+
+    /// Addresses the table should hold, fixed accounts first
+    pub fn wanted(&self) -> Vec<Pubkey> {
+        core_accounts().into_iter().chain(self.usage.frequent(self.config.min_uses)).collect()
+    }
+
+    /// Read the table back from the chain
+    pub async fn refresh(&self, rpc_client: &RpcClient, address: &Pubkey) -> Result<AddressLookupTableAccount> {
+        let account = rpc_client.get_account(address).await?;
+        let state = AddressLookupTable::deserialize(&account.data)
+            .map_err(|e| anyhow!("{} is not a lookup table: {}", address, e))?;
+        let table = AddressLookupTableAccount { key: *address, addresses: state.addresses.to_vec() };
+        self.set_current(table.clone());
+        Ok(table)
+    }
+
+    /// The configured or saved table, created (and its address saved) when there is none
+    async fn ensure_table(&self, rpc_client: &RpcClient, payer: &Keypair, logger: &Logger) -> Result<Pubkey> {
+        if let Some(address) = self.config.table_address() {
+            return Ok(address);
+        }
+        let slot = rpc_client.get_slot().await?;
+        let (create, address) = alt_instruction::create_lookup_table(payer.pubkey(), payer.pubkey(), slot);
+        send(rpc_client, payer, &[create]).await?;
+        logger.log(format!("Created lookup table {}", address).green().to_string());
+        if let Some(path) = &self.config.path {
+            if let Err(e) = std::fs::write(path, address.to_string()) {
+                logger.log(format!("Failed to save the lookup table address to {}: {}", path.display(), e).red().to_string());
+            }
+        }
+        Ok(address)
+    }
+
+    /// Create the table if needed, add the accounts it is missing, and read it back
+    pub async fn maintain(&self, rpc_client: &RpcClient, payer: &Keypair, logger: &Logger) -> Result<usize> {
+        let address = self.ensure_table(rpc_client, payer, logger).await?;
+        let account = rpc_client.get_account(&address).await?;
+        let state = AddressLookupTable::deserialize(&account.data)
+            .map_err(|e| anyhow!("{} is not a lookup table: {}", address, e))?;
+        if state.meta.authority != Some(payer.pubkey()) {
+            self.refresh(rpc_client, &address).await?;
+            return Ok(0);
+        }
+
+        let mut added = 0;
+        for chunk in plan_extension(&state.addresses, &self.wanted()) {
+            let extend = alt_instruction::extend_lookup_table(address, payer.pubkey(), Some(payer.pubkey()), chunk.clone());
+            send(rpc_client, payer, &[extend]).await?;
+            added += chunk.len();
+        }
+        if added > 0 {
+            // Addresses become usable in the slot after they were added
+            tokio::time::sleep(Duration::from_millis(800)).await;
+        }
+        self.refresh(rpc_client, &address).await?;
+        Ok(added)
+    }
+}
+
+async fn send(rpc_client: &RpcClient, payer: &Keypair, instructions: &[Instruction]) -> Result<()> {
+    let blockhash = rpc_client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &[payer], blockhash);
+    rpc_client.send_and_confirm_transaction(&transaction).await?;
+    Ok(())
+}
+
+/// Keep the process-wide table up to date every `LOOKUP_TABLE_REFRESH_SECS`; None when lookup
+/// tables are disabled
+pub fn start_lookup_table_maintenance(
+    rpc_client: Arc<RpcClient>,
+    payer: Arc<Keypair>,
+    cancel_token: CancellationToken,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let manager = LookupTableManager::shared()?;
+    let logger = Logger::new("[LOOKUP-TABLE] => ".cyan().to_string());
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(manager.config.refresh);
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            match manager.maintain(&rpc_client, &payer, &logger).await {
+                Ok(added) => {
+                    let size = manager.current().map_or(0, |table| table.addresses.len());
+                    if added > 0 {
+                        logger.log(format!("Added {} accounts, the table holds {}", added, size));
+                    }
+                }
+                Err(e) => logger.log(format!("Lookup table maintenance failed: {}", e).red().to_string()),
+            }
+        }
+        logger.log("Lookup table maintenance shut down.".yellow().to_string());
+    }))
+}
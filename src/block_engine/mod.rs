@@ -1,2 +1,7 @@
+pub mod bundle;
+pub mod guard;
+pub mod lookup_table;
+pub mod preflight;
+pub mod relay;
 pub mod token;
 pub mod tx;
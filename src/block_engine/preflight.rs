@@ -0,0 +1,169 @@
+//! Preflight checks against the protocol's transaction limits, run before anything is sent, so
+//! an oversized transaction is restructured here instead of being rejected by the RPC with an
+//! opaque error.
+//!
+//! A transaction must serialize to at most 1232 bytes (one packet), lock at most 64 accounts,
+//! and request at most 1.4M compute units. A compute unit limit above the maximum is lowered to
+//! it. Instructions that do not fit one transaction are compiled through the bot's address
+//! lookup table when one is given, and otherwise split into several transactions: the trailing
+//! instructions (the swap, its guard and the tip) stay together in the last one, the compute
+//! budget instructions are repeated in each, and the leading setup instructions move to the
+//! transactions before it.
+
+use std::fmt;
+use anchor_client::solana_sdk::{
+    compute_budget::{self, ComputeBudgetInstruction},
+    hash::Hash,
+    instruction::Instruction,
+    message::{AddressLookupTableAccount, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+};
+
+use crate::block_engine::lookup_table::compile_message;
+
+/// Largest serialized transaction, signatures included
+pub const MAX_TRANSACTION_SIZE: usize = PACKET_DATA_SIZE;
+/// Accounts a transaction may lock
+pub const MAX_ACCOUNT_LOCKS: usize = 64;
+/// Compute units a transaction may request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Discriminant of `ComputeBudgetInstruction::SetComputeUnitLimit`
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+
+/// A limit a transaction breaks
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    TooLarge { size: usize },
+    TooManyAccounts { count: usize },
+    ComputeUnitLimit { units: u32 },
+    /// The instructions cannot be compiled into a message at all
+    Invalid(String),
+    /// One instruction breaks a limit even in a transaction of its own
+    Unsplittable { index: usize, violation: Box<Violation> },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge { size } => write!(f, "transaction is {} bytes, the limit is {}", size, MAX_TRANSACTION_SIZE),
+            Self::TooManyAccounts { count } => write!(f, "transaction locks {} accounts, the limit is {}", count, MAX_ACCOUNT_LOCKS),
+            Self::ComputeUnitLimit { units } => write!(f, "transaction requests {} compute units, the limit is {}", units, MAX_COMPUTE_UNIT_LIMIT),
+            Self::Invalid(e) => write!(f, "instructions do not compile: {}", e),
+            Self::Unsplittable { index, violation } => write!(f, "instruction {} does not fit a transaction: {}", index, violation),
+        }
+    }
+}
+
+/// Serialized size of a transaction carrying `message` and its signatures
+pub fn transaction_size(message: &VersionedMessage) -> usize {
+    let signatures = message.header().num_required_signatures as usize;
+    let length_prefix = if signatures < 0x80 { 1 } else { 2 };
+    let message_size = bincode::serialized_size(message).map(|size| size as usize).unwrap_or(usize::MAX / 2);
+    length_prefix + signatures * 64 + message_size
+}
+
+/// Accounts `message` locks, whether listed in it or loaded through lookup tables
+pub fn account_count(message: &VersionedMessage) -> usize {
+    let looked_up: usize = message.address_table_lookups()
+        .map(|lookups| lookups.iter().map(|l| l.writable_indexes.len() + l.readonly_indexes.len()).sum())
+        .unwrap_or(0);
+    message.static_account_keys().len() + looked_up
+}
+
+/// Compute units requested by a `SetComputeUnitLimit` among `instructions`
+pub fn requested_units(instructions: &[Instruction]) -> Option<u32> {
+    instructions.iter()
+        .filter(|ix| ix.program_id == compute_budget::id() && ix.data.first() == Some(&SET_COMPUTE_UNIT_LIMIT))
+        .find_map(|ix| ix.data.get(1..5).and_then(|units| units.try_into().ok()).map(u32::from_le_bytes))
+}
+
+/// The first limit `message` breaks
+pub fn check(message: &VersionedMessage) -> Result<(), Violation> {
+    let size = transaction_size(message);
+    if size > MAX_TRANSACTION_SIZE {
+        return Err(Violation::TooLarge { size });
+    }
+    let count = account_count(message);
+    if count > MAX_ACCOUNT_LOCKS {
+        return Err(Violation::TooManyAccounts { count });
+    }
+    Ok(())
+}
+
+/// `instructions` with a compute unit limit above the maximum lowered to it
+pub fn clamp_compute_units(instructions: Vec<Instruction>) -> Vec<Instruction> {
+    instructions.into_iter()
+        .map(|ix| match requested_units(std::slice::from_ref(&ix)) {
+            Some(units) if units > MAX_COMPUTE_UNIT_LIMIT => ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT),
+            _ => ix,
+        })
+        .collect()
+}
+
+fn compile_and_check(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    recent_blockhash: Hash,
+    table: Option<&AddressLookupTableAccount>,
+) -> Result<VersionedMessage, Violation> {
+    if let Some(units) = requested_units(instructions).filter(|units| *units > MAX_COMPUTE_UNIT_LIMIT) {
+        return Err(Violation::ComputeUnitLimit { units });
+    }
+    let message = compile_message(payer, instructions, recent_blockhash, table)
+        .map_err(|e| Violation::Invalid(e.to_string()))?;
+    check(&message)?;
+    Ok(message)
+}
+
+/// Split `instructions` into groups that each fit a transaction, trailing instructions last.
+/// `extra` stands for instructions the sender adds to every transaction later (compute budget,
+/// tip) and is counted against each group without being part of it.
+pub fn split(
+    payer: &Pubkey,
+    instructions: Vec<Instruction>,
+    extra: &[Instruction],
+    recent_blockhash: Hash,
+    table: Option<&AddressLookupTableAccount>,
+) -> Result<Vec<Vec<Instruction>>, Violation> {
+    let instructions = clamp_compute_units(instructions);
+    let fits = |group: &[Instruction]| {
+        let with_extra: Vec<Instruction> = extra.iter().chain(group).cloned().collect();
+        compile_and_check(payer, &with_extra, recent_blockhash, table).map(|_| ())
+    };
+    match fits(&instructions) {
+        Ok(()) => return Ok(vec![instructions]),
+        // Splitting cannot help an instruction set that does not compile
+        Err(violation @ Violation::Invalid(_)) => return Err(violation),
+        Err(_) => {}
+    }
+
+    let budget: Vec<Instruction> = instructions.iter()
+        .filter(|ix| ix.program_id == compute_budget::id())
+        .cloned()
+        .collect();
+    let rest: Vec<(usize, Instruction)> = instructions.into_iter()
+        .enumerate()
+        .filter(|(_, ix)| ix.program_id != compute_budget::id())
+        .collect();
+    let mut groups: Vec<Vec<Instruction>> = Vec::new();
+    let mut current: Vec<Instruction> = Vec::new();
+    for (index, ix) in rest.into_iter().rev() {
+        let mut candidate = budget.clone();
+        candidate.push(ix.clone());
+        candidate.extend(current.iter().skip(budget.len()).cloned());
+        if fits(&candidate).is_ok() {
+            current = candidate;
+            continue;
+        }
+        let alone: Vec<Instruction> = budget.iter().cloned().chain(std::iter::once(ix)).collect();
+        if let Err(violation) = fits(&alone) {
+            return Err(Violation::Unsplittable { index, violation: Box::new(violation) });
+        }
+        groups.push(std::mem::replace(&mut current, alone));
+    }
+    groups.push(current);
+    groups.reverse();
+    Ok(groups)
+}
@@ -0,0 +1,412 @@
+//! Fast-relay backends (bloXroute Trader API, NextBlock) behind a common `Relay` trait.
+//!
+//! `RELAYS` lists the routes non-urgent sends rotate through, one per transaction, e.g.
+//! `zeroslot,bloxroute,nextblock`; empty keeps every send on ZeroSlot. Each relay has its own
+//! auth and tip settings (`BLOXROUTE_*`, `NEXTBLOCK_*`); tip accounts come from the provider's docs
+//! and are never built in. Sent and landed transactions are counted per route so the daily summary
+//! can compare landed rates.
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use anchor_client::solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signature, system_instruction,
+    transaction::{Transaction, VersionedTransaction},
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use rand::{seq::SliceRandom, thread_rng};
+use serde_json::{json, Value};
+
+use crate::error::{BotError, ClientError};
+use crate::library::circuit_breaker::{self, CircuitBreaker};
+
+lazy_static! {
+    static ref SHARED_ROUTER: Arc<RelayRouter> = Arc::new(RelayRouter::from_env());
+    static ref SHARED_STATS: Arc<RelayStats> = Arc::new(RelayStats::new());
+}
+
+/// Route name of the built-in ZeroSlot sender in `RELAYS` and the statistics
+pub const ZEROSLOT_ROUTE: &str = "zeroslot";
+
+/// Sends awaiting confirmation remembered for the landed counts; the map starts over once full
+const MAX_PENDING_SIGNATURES: usize = 10_000;
+
+/// A fast relay that forwards signed transactions to the leader
+#[async_trait]
+pub trait Relay: Send + Sync {
+    /// Name used in `RELAYS` and the statistics
+    fn name(&self) -> &'static str;
+
+    /// Tip transfer the relay requires, None when it takes no tip
+    fn tip_instruction(&self, payer: &Pubkey) -> Option<Instruction>;
+
+    /// Lamports paid by `tip_instruction`
+    fn tip_lamports(&self) -> u64;
+
+    /// Submit once; resending the same signed transaction is left to the caller's retry policy
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, BotError>;
+
+    /// Submit a v0 transaction (accounts resolved through an address lookup table) once
+    async fn send_versioned_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, BotError>;
+}
+
+/// Endpoint, credentials and tip of one relay
+#[derive(Clone, Debug)]
+pub struct RelayConfig {
+    pub url: String,
+    /// Value of the `Authorization` header
+    pub auth: String,
+    pub tip_lamports: u64,
+    pub tip_accounts: Vec<Pubkey>,
+}
+
+impl RelayConfig {
+    pub fn new(url: &str, auth: &str) -> Self {
+        Self { url: url.trim_end_matches('/').to_string(), auth: auth.to_string(), tip_lamports: 0, tip_accounts: Vec::new() }
+    }
+
+    /// Read `<PREFIX>_URL` (default `default_url`), `<PREFIX>_<auth_var>`, `<PREFIX>_TIP_SOL` and
+    /// `<PREFIX>_TIP_ACCOUNTS`
+    pub fn from_env(prefix: &str, auth_var: &str, default_url: &str) -> Result<Self> {
+        let var = |name: &str| std::env::var(format!("{}_{}", prefix, name)).ok().filter(|v| !v.trim().is_empty());
+        let auth = var(auth_var).ok_or_else(|| anyhow!("{}_{} is not set", prefix, auth_var))?;
+        let mut config = Self::new(&var("URL").unwrap_or_else(|| default_url.to_string()), auth.trim());
+        if let Some(tip) = var("TIP_SOL") {
+            let tip: f64 = tip.parse().map_err(|_| anyhow!("Invalid {}_TIP_SOL: {}", prefix, tip))?;
+            config.tip_lamports = (tip * 1_000_000_000.0).round() as u64;
+        }
+        config.tip_accounts = var("TIP_ACCOUNTS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(|a| Pubkey::from_str(a).map_err(|_| anyhow!("Invalid tip account in {}_TIP_ACCOUNTS: {}", prefix, a)))
+            .collect::<Result<_>>()?;
+        config.validate(prefix)?;
+        Ok(config)
+    }
+
+    /// Both relays only forward transactions that tip them
+    pub fn validate(&self, prefix: &str) -> Result<()> {
+        if self.tip_lamports == 0 {
+            return Err(anyhow!("{} requires a tip: set {}_TIP_SOL and {}_TIP_ACCOUNTS", prefix, prefix, prefix));
+        }
+        if self.tip_accounts.is_empty() {
+            return Err(anyhow!("{}_TIP_SOL is set but {}_TIP_ACCOUNTS is empty", prefix, prefix));
+        }
+        Ok(())
+    }
+
+    fn tip_instruction(&self, payer: &Pubkey) -> Option<Instruction> {
+        if self.tip_lamports == 0 {
+            return None;
+        }
+        let account = self.tip_accounts.choose(&mut thread_rng())?;
+        Some(system_instruction::transfer(payer, account, self.tip_lamports))
+    }
+}
+
+/// HTTP plumbing shared by the relays: both take a base64 transaction at `/api/v2/submit` and
+/// answer with its signature
+struct RelayHttp {
+    config: RelayConfig,
+    client: reqwest::Client,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl RelayHttp {
+    fn new(name: &str, config: RelayConfig) -> Self {
+        Self { config, client: reqwest::Client::new(), breaker: circuit_breaker::breaker(name) }
+    }
+
+    async fn submit<T: serde::Serialize + Sync>(&self, transaction: &T, body: impl Fn(&str) -> Value) -> Result<Signature, BotError> {
+        self.breaker.call(self.try_submit(transaction, body)).await
+    }
+
+    async fn try_submit<T: serde::Serialize + Sync>(&self, transaction: &T, body: impl Fn(&str) -> Value) -> Result<Signature, BotError> {
+        let wire_transaction = bincode::serialize(transaction)
+            .map_err(|e| ClientError::Parse("Transaction serialization failed".to_string(), e.to_string()))?;
+
+        let response: Value = self.client
+            .post(format!("{}/api/v2/submit", self.config.url))
+            .header("Content-Type", "application/json")
+            .header("Authorization", &self.config.auth)
+            .json(&body(&bs64::encode(&wire_transaction)))
+            .send()
+            .await
+            .map_err(|e| ClientError::Solana("Request failed".to_string(), e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| ClientError::Parse("Invalid JSON response".to_string(), e.to_string()))?;
+
+        parse_submit_response(&response)
+    }
+}
+
+/// Signature from a `/api/v2/submit` response
+pub fn parse_submit_response(response: &Value) -> Result<Signature, BotError> {
+    if let Some(error) = response.get("error") {
+        return Err(ClientError::Solana("Relay error".to_string(), error.to_string()).into());
+    }
+    let signature = response["signature"].as_str()
+        .ok_or_else(|| ClientError::Parse("Invalid response format".to_string(), "Missing signature field".to_string()))?;
+    Ok(Signature::from_str(signature)
+        .map_err(|e| ClientError::Parse("Invalid signature".to_string(), e.to_string()))?)
+}
+
+/// bloXroute Trader API
+pub struct BloxrouteRelay {
+    http: RelayHttp,
+    /// Have bloXroute hold the transaction back from validators known to sandwich
+    front_running_protection: bool,
+}
+
+impl BloxrouteRelay {
+    pub const DEFAULT_URL: &'static str = "https://ny.solana.dex.blxrbdn.com";
+
+    pub fn new(config: RelayConfig, front_running_protection: bool) -> Self {
+        Self { http: RelayHttp::new("bloxroute", config), front_running_protection }
+    }
+
+    /// BLOXROUTE_AUTH_HEADER is required; BLOXROUTE_FRONT_RUNNING_PROTECTION defaults to false
+    pub fn from_env() -> Result<Self> {
+        let config = RelayConfig::from_env("BLOXROUTE", "AUTH_HEADER", Self::DEFAULT_URL)?;
+        let protection = std::env::var("BLOXROUTE_FRONT_RUNNING_PROTECTION")
+            .ok()
+            .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+            .unwrap_or(false);
+        Ok(Self::new(config, protection))
+    }
+
+    pub fn request_body(&self, encoded_tx: &str) -> Value {
+        json!({
+            "transaction": { "content": encoded_tx },
+            "frontRunningProtection": self.front_running_protection,
+            "useStakedRPCs": true,
+        })
+    }
+}
+
+#[async_trait]
+impl Relay for BloxrouteRelay {
+    fn name(&self) -> &'static str {
+        "bloxroute"
+    }
+
+    fn tip_instruction(&self, payer: &Pubkey) -> Option<Instruction> {
+        self.http.config.tip_instruction(payer)
+    }
+
+    fn tip_lamports(&self) -> u64 {
+        self.http.config.tip_lamports
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, BotError> {
+        self.http.submit(transaction, |encoded| self.request_body(encoded)).await
+    }
+
+    async fn send_versioned_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, BotError> {
+        self.http.submit(transaction, |encoded| self.request_body(encoded)).await
+    }
+}
+
+/// NextBlock
+pub struct NextBlockRelay {
+    http: RelayHttp,
+}
+
+impl NextBlockRelay {
+    pub const DEFAULT_URL: &'static str = "https://ny.nextblock.io";
+
+    pub fn new(config: RelayConfig) -> Self {
+        Self { http: RelayHttp::new("nextblock", config) }
+    }
+
+    /// NEXTBLOCK_API_KEY is required
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(RelayConfig::from_env("NEXTBLOCK", "API_KEY", Self::DEFAULT_URL)?))
+    }
+
+    pub fn request_body(&self, encoded_tx: &str) -> Value {
+        json!({
+            "transaction": { "content": encoded_tx },
+            "frontRunningProtection": false,
+        })
+    }
+}
+
+#[async_trait]
+impl Relay for NextBlockRelay {
+    fn name(&self) -> &'static str {
+        "nextblock"
+    }
+
+    fn tip_instruction(&self, payer: &Pubkey) -> Option<Instruction> {
+        self.http.config.tip_instruction(payer)
+    }
+
+    fn tip_lamports(&self) -> u64 {
+        self.http.config.tip_lamports
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, BotError> {
+        self.http.submit(transaction, |encoded| self.request_body(encoded)).await
+    }
+
+    async fn send_versioned_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, BotError> {
+        self.http.submit(transaction, |encoded| self.request_body(encoded)).await
+    }
+}
+
+/// Where a transaction is sent
+#[derive(Clone)]
+pub enum Route {
+    ZeroSlot,
+    Relay(Arc<dyn Relay>),
+}
+
+impl Route {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Route::ZeroSlot => ZEROSLOT_ROUTE,
+            Route::Relay(relay) => relay.name(),
+        }
+    }
+}
+
+/// Rotates sends through the configured routes
+pub struct RelayRouter {
+    routes: Vec<Route>,
+    next: AtomicUsize,
+}
+
+impl RelayRouter {
+    /// Without routes every send goes through ZeroSlot
+    pub fn new(routes: Vec<Route>) -> Self {
+        Self { routes, next: AtomicUsize::new(0) }
+    }
+
+    /// Routes listed in RELAYS; misconfigured relays are left out with an error printed
+    pub fn from_env() -> Self {
+        let names = std::env::var("RELAYS").unwrap_or_default();
+        let routes = names.split(',')
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .filter_map(|name| {
+                let relay: Result<Arc<dyn Relay>> = match name.as_str() {
+                    ZEROSLOT_ROUTE => return Some(Route::ZeroSlot),
+                    "bloxroute" => BloxrouteRelay::from_env().map(|r| Arc::new(r) as Arc<dyn Relay>),
+                    "nextblock" => NextBlockRelay::from_env().map(|r| Arc::new(r) as Arc<dyn Relay>),
+                    _ => Err(anyhow!("unknown relay, use 'zeroslot', 'bloxroute' or 'nextblock'")),
+                };
+                match relay {
+                    Ok(relay) => Some(Route::Relay(relay)),
+                    Err(e) => {
+                        eprintln!("Relay {} disabled: {}", name, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        Self::new(routes)
+    }
+
+    /// Process-wide router
+    pub fn shared() -> Arc<RelayRouter> {
+        SHARED_ROUTER.clone()
+    }
+
+    pub fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+
+    /// Route of the next transaction
+    pub fn next_route(&self) -> Route {
+        if self.routes.is_empty() {
+            return Route::ZeroSlot;
+        }
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.routes.len();
+        self.routes[index].clone()
+    }
+}
+
+/// Sent and landed transactions of one route
+#[derive(Debug, Default)]
+pub struct RouteCounts {
+    pub sent: AtomicU64,
+    pub landed: AtomicU64,
+}
+
+impl RouteCounts {
+    /// Share of sent transactions that landed (%), None before the first send
+    pub fn landed_pct(&self) -> Option<f64> {
+        let sent = self.sent.load(Ordering::Relaxed);
+        (sent > 0).then(|| self.landed.load(Ordering::Relaxed) as f64 / sent as f64 * 100.0)
+    }
+}
+
+/// Landed rate per route since startup
+#[derive(Default)]
+pub struct RelayStats {
+    counts: DashMap<&'static str, RouteCounts>,
+    /// Signature -> route it was sent through, until it is confirmed
+    pending: DashMap<Signature, &'static str>,
+}
+
+impl RelayStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn shared() -> Arc<RelayStats> {
+        SHARED_STATS.clone()
+    }
+
+    pub fn record_sent(&self, route: &'static str, signature: Signature) {
+        self.counts.entry(route).or_default().sent.fetch_add(1, Ordering::Relaxed);
+        if self.pending.len() >= MAX_PENDING_SIGNATURES {
+            self.pending.clear();
+        }
+        self.pending.insert(signature, route);
+    }
+
+    /// Count a confirmed transaction towards the route it was sent through; signatures not sent
+    /// through a tracked route are ignored
+    pub fn record_landed(&self, signature: &Signature) {
+        if let Some((_, route)) = self.pending.remove(signature) {
+            self.counts.entry(route).or_default().landed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// (sent, landed) of a route
+    pub fn counts(&self, route: &str) -> (u64, u64) {
+        self.counts.get(route)
+            .map(|c| (c.sent.load(Ordering::Relaxed), c.landed.load(Ordering::Relaxed)))
+            .unwrap_or((0, 0))
+    }
+
+    /// One line per route, best landed rate first, for the daily summary
+    pub fn format(&self) -> String {
+        let mut lines: Vec<(f64, String)> = self.counts.iter()
+            .map(|entry| {
+                let sent = entry.value().sent.load(Ordering::Relaxed);
+                let landed = entry.value().landed.load(Ordering::Relaxed);
+                let pct = entry.value().landed_pct().unwrap_or(0.0);
+                (pct, format!("{}: {}/{} landed ({:.1}%)", entry.key(), landed, sent, pct))
+            })
+            .collect();
+        if lines.is_empty() {
+            return "No transaction sent yet".to_string();
+        }
+        lines.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        lines.into_iter().map(|(_, line)| line).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Landed rates of the process-wide statistics
+pub fn format_relay_stats() -> String {
+    RelayStats::shared().format()
+}
@@ -4,10 +4,12 @@ use anyhow::{Result, anyhow};
 use colored::Colorize;
 use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
 use anchor_client::solana_sdk::{
+    hash::Hash,
     instruction::Instruction,
-    signature::Keypair,
+    message::AddressLookupTableAccount,
+    signature::{Keypair, Signature},
     system_instruction,
-    transaction::Transaction,
+    transaction::{Transaction, VersionedTransaction},
 };
 use std::env;
 use anchor_client::solana_sdk::pubkey::Pubkey;
@@ -21,11 +23,22 @@ use base64;
 use bs58;
 use std::time::Duration;
 use crate::{
+    block_engine::{
+        bundle::{BundleBuilder, JitoClient},
+        guard::{self, MinOutGuard},
+        lookup_table::{compile_message, LookupTableManager},
+        preflight,
+        relay::{self, Relay, RelayRouter, RelayStats, Route},
+    },
+    error::{retry, BotError},
     common::{
         logger::Logger,
         config::TransactionLandingMode,
+        trade_costs::{self, TransactionCost},
     },
     library::{
+        audit_log::{self, AuditAction},
+        priority_lane::{PriorityLaneClient, Urgency},
         zeroslot::{self, ZeroSlotClient},
     },
 };
@@ -60,19 +73,22 @@ static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
    client
 });
 
+/// Send through ZeroSlot with its tip; with a lookup `table` the transaction goes out as v0
 pub async fn new_signed_and_send_zeroslot(
-    zeroslot_rpc_client: Arc<crate::library::zeroslot::ZeroSlotClient>,
+    rpc_api: Arc<dyn crate::library::rpc_api::RpcApi>,
     recent_blockhash: solana_sdk::hash::Hash,
     keypair: &Keypair,
     mut instructions: Vec<Instruction>,
+    table: Option<&AddressLookupTableAccount>,
     logger: &Logger,
-) -> Result<Vec<String>> {
-    let tip_account = zeroslot::get_tip_account()?;
+) -> Result<Vec<String>, BotError> {
+    crate::library::trading_lockdown::ensure_trading_enabled()?;
+    let tip_account = zeroslot::get_tip_account().map_err(|e| BotError::InvalidConfig(e.to_string()))?;
     let start_time = Instant::now();
     let mut txs: Vec<String> = vec![];
     
     // zeroslot tip, the upper limit is 0.1
-    let tip = zeroslot::get_tip_value().await?;
+    let tip = zeroslot::get_tip_value().await.map_err(|e| BotError::InvalidConfig(e.to_string()))?;
     let tip_lamports = ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS);
 
     let zeroslot_tip_instruction = 
@@ -89,33 +105,346 @@ pub async fn new_signed_and_send_zeroslot(
         
         instructions.push(zeroslot_tip_instruction); // zeroslot is different with others.
     // send init tx
-    let txn = Transaction::new_signed_with_payer(
-        &instructions,
-        Some(&keypair.pubkey()),
-        &vec![keypair],
-        recent_blockhash,
-    );
+    let txn = SignedTransaction::new(keypair, &instructions, recent_blockhash, table)?;
 
-    let tx_result = zeroslot_rpc_client.send_transaction(&txn).await;
+    // Resending the same signed transaction is idempotent, so transient send failures are retried
+    let tx_result = retry("zeroslot send", logger, |_| txn.send_rpc(rpc_api.as_ref())).await;
     
     match tx_result {
         Ok(signature) => {
             txs.push(signature.to_string());
+            RelayStats::shared().record_sent(relay::ZEROSLOT_ROUTE, signature);
+            audit_submission(relay::ZEROSLOT_ROUTE, keypair, &txs);
+            trade_costs::record_sent(&txs, &instructions, TransactionCost::sent(unit_limit, unit_price, tip_lamports));
             logger.log(
                 format!("[TXN-ELAPSED(ZEROSLOT)]: {:?}", start_time.elapsed())
                     .yellow()
                     .to_string(),
             );
         }
-        Err(_) => {
-            // Convert the error to a Send-compatible form
-            return Err(anyhow::anyhow!("zeroslot send_transaction failed"));
+        Err(e) => {
+            return Err(e);
         }
     };
 
     Ok(txs)
 }
 
+/// Audit a transaction or bundle a route accepted
+fn audit_submission(route: &str, keypair: &Keypair, signatures: &[String]) {
+    audit_log::record(
+        AuditAction::TradeSubmitted,
+        route,
+        serde_json::json!({ "signer": keypair.pubkey().to_string(), "signatures": signatures }),
+    );
+}
+
+/// A transaction signed for a route: legacy, or v0 when it is compiled through the lookup table
+enum SignedTransaction {
+    Legacy(Transaction),
+    V0(VersionedTransaction),
+}
+
+impl SignedTransaction {
+    fn new(
+        keypair: &Keypair,
+        instructions: &[Instruction],
+        recent_blockhash: Hash,
+        table: Option<&AddressLookupTableAccount>,
+    ) -> Result<Self, BotError> {
+        if table.is_none() {
+            return Ok(Self::Legacy(Transaction::new_signed_with_payer(
+                instructions,
+                Some(&keypair.pubkey()),
+                &[keypair],
+                recent_blockhash,
+            )));
+        }
+        let message = compile_message(&keypair.pubkey(), instructions, recent_blockhash, table)
+            .map_err(|e| BotError::InvalidInput(format!("Instructions do not compile: {}", e)))?;
+        VersionedTransaction::try_new(message, &[keypair])
+            .map(Self::V0)
+            .map_err(|e| BotError::InvalidInput(format!("Failed to sign v0 transaction: {}", e)))
+    }
+
+    async fn send_rpc(&self, rpc_api: &dyn crate::library::rpc_api::RpcApi) -> Result<Signature, BotError> {
+        match self {
+            Self::Legacy(txn) => rpc_api.send_transaction(txn).await,
+            Self::V0(txn) => rpc_api.send_versioned_transaction(txn).await,
+        }
+    }
+
+    async fn send_lane(&self, lane: &PriorityLaneClient) -> Result<Signature, BotError> {
+        match self {
+            Self::Legacy(txn) => lane.send_transaction(txn).await,
+            Self::V0(txn) => lane.send_transaction(txn).await,
+        }
+    }
+
+    async fn send_relay(&self, relay: &dyn Relay) -> Result<Signature, BotError> {
+        match self {
+            Self::Legacy(txn) => relay.send_transaction(txn).await,
+            Self::V0(txn) => relay.send_versioned_transaction(txn).await,
+        }
+    }
+}
+
+/// Stand-ins for what every route adds to a transaction: the compute budget and a tip transfer
+fn route_additions(payer: &Pubkey) -> Vec<Instruction> {
+    vec![
+        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(get_unit_limit()),
+        solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(get_unit_price()),
+        // Any account other than the ones already used, sized like the route's tip account
+        system_instruction::transfer(payer, &Pubkey::new_from_array([0xff; 32]), 0),
+    ]
+}
+
+/// Send through the priority lane (a staked connection) when one is configured for `urgency`,
+/// otherwise through the next route of the relay rotation (ZeroSlot unless `RELAYS` is set).
+/// Swaps get their on-chain guard first when `TRADE_GUARDS_ENABLED` is set.
+///
+/// Instructions too large for one legacy transaction go out as a single v0 transaction through
+/// the bot's address lookup table when that fits. Only when it does not are they split by the
+/// preflight check; each leading transaction must confirm before the next one is sent, so the
+/// swap only goes out once its setup has landed.
+pub async fn new_signed_and_send_by_urgency(
+    urgency: Urgency,
+    rpc_api: Arc<dyn crate::library::rpc_api::RpcApi>,
+    recent_blockhash: solana_sdk::hash::Hash,
+    keypair: &Keypair,
+    instructions: Vec<Instruction>,
+    logger: &Logger,
+) -> Result<Vec<String>, BotError> {
+    crate::library::trading_lockdown::ensure_trading_enabled()?;
+    let instructions = guard::with_guard(instructions);
+    if let Some(table) = LookupTableManager::shared() {
+        table.record_use(&instructions);
+    }
+    let payer = keypair.pubkey();
+    let additions = route_additions(&payer);
+    let too_large = |e: preflight::Violation| BotError::InvalidInput(format!("Transaction exceeds protocol limits: {}", e));
+    let legacy = preflight::split(&payer, instructions.clone(), &additions, recent_blockhash, None);
+    let (parts, table) = match (legacy, LookupTableManager::shared().and_then(|table| table.current())) {
+        (Ok(parts), _) if parts.len() == 1 => (parts, None),
+        (legacy, None) => (legacy.map_err(too_large)?, None),
+        (_, Some(table)) => {
+            let parts = preflight::split(&payer, instructions, &additions, recent_blockhash, Some(&table)).map_err(too_large)?;
+            (parts, Some(table))
+        }
+    };
+    if parts.len() > 1 {
+        logger.log(format!("Transaction too large, sending it as {} transactions", parts.len()).yellow().to_string());
+    } else if table.is_some() {
+        logger.log("Transaction too large for a legacy message, sending it as v0 through the lookup table".yellow().to_string());
+    }
+
+    let last = parts.len() - 1;
+    let mut signatures = Vec::new();
+    for (i, part) in parts.into_iter().enumerate() {
+        let sent = send_by_urgency(urgency, rpc_api.clone(), recent_blockhash, keypair, part, table.as_ref(), logger).await?;
+        if i < last {
+            for signature in &sent {
+                wait_for_confirmation(rpc_api.as_ref(), signature, logger).await?;
+            }
+        }
+        signatures.extend(sent);
+    }
+    Ok(signatures)
+}
+
+/// Wait until `signature` is confirmed; an on-chain failure is returned at once
+async fn wait_for_confirmation(
+    rpc_api: &dyn crate::library::rpc_api::RpcApi,
+    signature: &str,
+    logger: &Logger,
+) -> Result<(), BotError> {
+    let signature = solana_sdk::signature::Signature::from_str(signature)
+        .map_err(|e| BotError::InvalidInput(format!("Invalid signature {}: {}", signature, e)))?;
+    crate::library::rpc_api::wait_for_confirmation(rpc_api, &signature, logger).await
+}
+
+async fn send_by_urgency(
+    urgency: Urgency,
+    rpc_api: Arc<dyn crate::library::rpc_api::RpcApi>,
+    recent_blockhash: solana_sdk::hash::Hash,
+    keypair: &Keypair,
+    instructions: Vec<Instruction>,
+    table: Option<&AddressLookupTableAccount>,
+    logger: &Logger,
+) -> Result<Vec<String>, BotError> {
+    let lane = match PriorityLaneClient::shared().filter(|lane| lane.handles(urgency)) {
+        Some(lane) => lane,
+        None => {
+            return match RelayRouter::shared().next_route() {
+                Route::ZeroSlot => new_signed_and_send_zeroslot(rpc_api, recent_blockhash, keypair, instructions, table, logger).await,
+                Route::Relay(relay) => {
+                    new_signed_and_send_relay(relay.as_ref(), rpc_api, recent_blockhash, keypair, instructions, table, logger).await
+                }
+            };
+        }
+    };
+    let start_time = Instant::now();
+
+    let unit_limit = get_unit_limit();
+    let unit_price = get_unit_price();
+    let mut lane_instructions = instructions.clone();
+    lane_instructions.insert(1, solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(unit_limit));
+    lane_instructions.insert(2, solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(unit_price));
+    let tip = lane.tip_instruction(&keypair.pubkey());
+    let tip_lamports = if tip.is_some() { lane.config().tip_lamports } else { 0 };
+    lane_instructions.extend(tip);
+
+    let txn = SignedTransaction::new(keypair, &lane_instructions, recent_blockhash, table)?;
+
+    let signature = match txn.send_lane(&lane).await {
+        // Shed by the lane's circuit breaker before anything was sent, so ZeroSlot cannot
+        // double-submit the trade
+        Err(BotError::CircuitOpen(_)) => {
+            logger.log("Priority lane unavailable, sending through zeroslot".yellow().to_string());
+            return new_signed_and_send_zeroslot(rpc_api, recent_blockhash, keypair, instructions, table, logger).await;
+        }
+        // Resending the same signed transaction is idempotent
+        Err(e) if e.is_transient() => retry("priority lane send", logger, |_| txn.send_lane(&lane)).await?,
+        result => result?,
+    };
+
+    RelayStats::shared().record_sent("priority_lane", signature);
+    audit_submission("priority_lane", keypair, &[signature.to_string()]);
+    trade_costs::record_sent(&[signature.to_string()], &lane_instructions, TransactionCost::sent(unit_limit, unit_price, tip_lamports));
+    logger.log(
+        format!("[TXN-ELAPSED(PRIORITY-LANE, {:?})]: {:?}", urgency, start_time.elapsed())
+            .yellow()
+            .to_string(),
+    );
+    Ok(vec![signature.to_string()])
+}
+
+/// Send a buy: as a Jito bundle (buy, guard and tip landing together or not at all) when
+/// `JITO_BUNDLES_ENABLED` is set, otherwise like any other `Urgency::High` transaction
+pub async fn new_signed_and_send_buy(
+    rpc_api: Arc<dyn crate::library::rpc_api::RpcApi>,
+    recent_blockhash: solana_sdk::hash::Hash,
+    keypair: &Keypair,
+    instructions: Vec<Instruction>,
+    logger: &Logger,
+) -> Result<Vec<String>, BotError> {
+    crate::library::trading_lockdown::ensure_trading_enabled()?;
+    let jito = match JitoClient::shared() {
+        Some(jito) => jito,
+        None => return new_signed_and_send_by_urgency(Urgency::High, rpc_api, recent_blockhash, keypair, instructions, logger).await,
+    };
+    let start_time = Instant::now();
+
+    let tip_account = match jito.tip_account().await {
+        Ok(account) => account,
+        Err(e) => {
+            logger.log(format!("No Jito tip account ({}), sending the buy without a bundle", e).yellow().to_string());
+            return new_signed_and_send_by_urgency(Urgency::High, rpc_api, recent_blockhash, keypair, instructions, logger).await;
+        }
+    };
+    let unit_limit = get_unit_limit();
+    let unit_price = get_unit_price();
+    let guard = MinOutGuard::for_swap(&instructions);
+    let mut buy_instructions = instructions.clone();
+    buy_instructions.insert(1, solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(unit_limit));
+    buy_instructions.insert(2, solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(unit_price));
+
+    let lookup_table = LookupTableManager::shared();
+    if let Some(table) = &lookup_table {
+        table.record_use(&buy_instructions);
+    }
+    let bundle = BundleBuilder::new(keypair, recent_blockhash)
+        .buy(buy_instructions.clone())
+        .guard(guard.clone())
+        .tip(tip_account, jito.config().tip_lamports)
+        .lookup_table(lookup_table.and_then(|table| table.current()))
+        .build()
+        .map_err(|e| BotError::InvalidConfig(e.to_string()))?;
+
+    let bundle_id = match jito.send_bundle(&bundle).await {
+        // Nothing was sent, so the fallback cannot double-submit the buy
+        Err(BotError::CircuitOpen(_)) => {
+            logger.log("Jito block engine unavailable, sending the buy without a bundle".yellow().to_string());
+            return new_signed_and_send_by_urgency(Urgency::High, rpc_api, recent_blockhash, keypair, instructions, logger).await;
+        }
+        // The same signed bundle is accepted at most once
+        Err(e) if e.is_transient() => retry("jito bundle send", logger, |_| jito.send_bundle(&bundle)).await?,
+        result => result?,
+    };
+
+    let signatures = bundle.signatures();
+    RelayStats::shared().record_sent("jito_bundle", signatures[0]);
+    let sent: Vec<String> = signatures.iter().map(|s| s.to_string()).collect();
+    audit_submission("jito_bundle", keypair, &sent);
+    let mut paid_instructions = buy_instructions;
+    if let Some(guard) = &guard {
+        paid_instructions.push(guard.instruction().map_err(|e| BotError::InvalidConfig(e.to_string()))?);
+    }
+    paid_instructions.push(system_instruction::transfer(&keypair.pubkey(), &tip_account, jito.config().tip_lamports));
+    // One record for the whole bundle, with a base fee per transaction
+    let mut cost = TransactionCost::sent(unit_limit, unit_price, jito.config().tip_lamports);
+    cost.base_fee = trade_costs::BASE_FEE_LAMPORTS * signatures.len() as u64;
+    trade_costs::record_sent(&sent, &paid_instructions, cost);
+    logger.log(
+        format!(
+            "[TXN-ELAPSED(JITO-BUNDLE {}, {} txs{})]: {:?}",
+            bundle_id,
+            signatures.len(),
+            if guard.is_some() { ", guarded" } else { "" },
+            start_time.elapsed()
+        )
+            .yellow()
+            .to_string(),
+    );
+    Ok(sent)
+}
+
+/// Send through a fast relay (bloXroute, NextBlock) with the relay's own tip, falling back to
+/// ZeroSlot only when the relay's circuit breaker sheds the send
+pub async fn new_signed_and_send_relay(
+    relay: &dyn Relay,
+    rpc_api: Arc<dyn crate::library::rpc_api::RpcApi>,
+    recent_blockhash: solana_sdk::hash::Hash,
+    keypair: &Keypair,
+    instructions: Vec<Instruction>,
+    table: Option<&AddressLookupTableAccount>,
+    logger: &Logger,
+) -> Result<Vec<String>, BotError> {
+    crate::library::trading_lockdown::ensure_trading_enabled()?;
+    let start_time = Instant::now();
+
+    let unit_limit = get_unit_limit();
+    let unit_price = get_unit_price();
+    let mut relay_instructions = instructions.clone();
+    relay_instructions.insert(1, solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(unit_limit));
+    relay_instructions.insert(2, solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(unit_price));
+    let tip = relay.tip_instruction(&keypair.pubkey());
+    let tip_lamports = if tip.is_some() { relay.tip_lamports() } else { 0 };
+    relay_instructions.extend(tip);
+
+    let txn = SignedTransaction::new(keypair, &relay_instructions, recent_blockhash, table)?;
+
+    let signature = match txn.send_relay(relay).await {
+        // Nothing was sent, so ZeroSlot cannot double-submit the trade
+        Err(BotError::CircuitOpen(_)) => {
+            logger.log(format!("Relay {} unavailable, sending through zeroslot", relay.name()).yellow().to_string());
+            return new_signed_and_send_zeroslot(rpc_api, recent_blockhash, keypair, instructions, table, logger).await;
+        }
+        Err(e) if e.is_transient() => retry("relay send", logger, |_| txn.send_relay(relay)).await?,
+        result => result?,
+    };
+
+    RelayStats::shared().record_sent(relay.name(), signature);
+    audit_submission(relay.name(), keypair, &[signature.to_string()]);
+    trade_costs::record_sent(&[signature.to_string()], &relay_instructions, TransactionCost::sent(unit_limit, unit_price, tip_lamports));
+    logger.log(
+        format!("[TXN-ELAPSED({})]: {:?}", relay.name().to_uppercase(), start_time.elapsed())
+            .yellow()
+            .to_string(),
+    );
+    Ok(vec![signature.to_string()])
+}
+
 
 pub async fn new_signed_and_send_zeroslot_fast(
     compute_unit_limit: u32,
@@ -127,6 +456,7 @@ pub async fn new_signed_and_send_zeroslot_fast(
     mut instructions: Vec<Instruction>,
     logger: &Logger,
 ) -> Result<Vec<String>> {
+    crate::library::trading_lockdown::ensure_trading_enabled()?;
     let tip_account = zeroslot::get_tip_account()?;
     let start_time = Instant::now();
     let mut txs: Vec<String> = vec![];
@@ -161,6 +491,8 @@ pub async fn new_signed_and_send_zeroslot_fast(
     match tx_result {
         Ok(signature) => {
             txs.push(signature.to_string());
+            audit_submission(relay::ZEROSLOT_ROUTE, keypair, &txs);
+            trade_costs::record_sent(&txs, &instructions, TransactionCost::sent(unit_limit, unit_price, tip_lamports));
             logger.log(
                 format!("[TXN-ELAPSED(ZEROSLOT)]: {:?}", start_time.elapsed())
                     .yellow()
@@ -184,6 +516,7 @@ pub async fn new_signed_and_send_normal(
     mut instructions: Vec<Instruction>,
     logger: &Logger,
 ) -> Result<Vec<String>> {
+    crate::library::trading_lockdown::ensure_trading_enabled()?;
     let start_time = Instant::now();
     
     // Add compute budget instructions for priority fee
@@ -206,6 +539,8 @@ pub async fn new_signed_and_send_normal(
 
     match rpc_client.send_transaction(&txn).await {
         Ok(signature) => {
+            audit_submission("normal", keypair, &[signature.to_string()]);
+            trade_costs::record_sent(&[signature.to_string()], &instructions, TransactionCost::sent(0, 0, 0));
             logger.log(
                 format!("[TXN-ELAPSED(NORMAL)]: {:?}", start_time.elapsed())
                     .yellow()
@@ -226,17 +561,19 @@ pub async fn new_signed_and_send_with_landing_mode(
     instructions: Vec<Instruction>,
     logger: &Logger,
 ) -> Result<Vec<String>> {
+    crate::library::trading_lockdown::ensure_trading_enabled()?;
     // Route to the appropriate service
     match transaction_landing_mode {
         TransactionLandingMode::Zeroslot => {
             logger.log("Using Zeroslot for transaction landing".green().to_string());
-            new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
+            Ok(new_signed_and_send_zeroslot(
+                app_state.rpc_api.clone(),
                 recent_blockhash,
                 keypair,
                 instructions,
+                None,
                 logger,
-            ).await
+            ).await?)
         },
         TransactionLandingMode::Normal => {
             logger.log("Using Normal RPC for transaction landing".green().to_string());
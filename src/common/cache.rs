@@ -118,6 +118,55 @@ impl TokenMintCache {
     }
 }
 
+/// Address lookup table cache: table address -> addresses it holds
+pub struct LookupTableCache {
+    tables: RwLock<HashMap<Pubkey, CacheEntry<Vec<Pubkey>>>>,
+    default_ttl: u64,
+}
+
+impl LookupTableCache {
+    pub fn new(default_ttl: u64) -> Self {
+        Self {
+            tables: RwLock::new(HashMap::new()),
+            default_ttl,
+        }
+    }
+
+    pub fn get(&self, key: &Pubkey) -> Option<Vec<Pubkey>> {
+        let tables = self.tables.read().unwrap();
+        if let Some(entry) = tables.get(key) {
+            if !entry.is_expired() {
+                return Some(entry.value.clone());
+            }
+        }
+        None
+    }
+
+    /// Whether the cached table holds an address at `index` (tables are append-only)
+    pub fn covers(&self, key: &Pubkey, index: u8) -> bool {
+        let tables = self.tables.read().unwrap();
+        tables.get(key)
+            .map(|entry| !entry.is_expired() && (index as usize) < entry.value.len())
+            .unwrap_or(false)
+    }
+
+    pub fn insert(&self, key: Pubkey, value: Vec<Pubkey>, ttl: Option<u64>) {
+        let ttl = ttl.unwrap_or(self.default_ttl);
+        let mut tables = self.tables.write().unwrap();
+        tables.insert(key, CacheEntry::new(value, ttl));
+    }
+
+    pub fn clear_expired(&self) {
+        let mut tables = self.tables.write().unwrap();
+        tables.retain(|_, entry| !entry.is_expired());
+    }
+
+    pub fn size(&self) -> usize {
+        let tables = self.tables.read().unwrap();
+        tables.len()
+    }
+}
+
 /// Simple wallet token account tracker
 pub struct WalletTokenAccounts {
     accounts: RwLock<HashSet<Pubkey>>,
@@ -161,9 +210,130 @@ impl WalletTokenAccounts {
     }
 }
 
+/// Raw account data kept fresh by an account-update subscription instead of a TTL
+struct SubscribedAccount {
+    data: Option<Vec<u8>>,
+    /// Slot of the last update, 0 when the data came from an RPC read
+    slot: u64,
+    last_requested: Instant,
+}
+
+/// Accounts that readers asked for are subscribed to; while the subscription is live their
+/// cached data is served without RPC calls and replaced by every update. Entries are dropped
+/// when nobody read them for `idle` and everything is invalidated when the subscription drops.
+pub struct SubscribedAccountCache {
+    accounts: RwLock<HashMap<Pubkey, SubscribedAccount>>,
+    live: RwLock<HashSet<Pubkey>>,
+    max_accounts: usize,
+    idle: Duration,
+}
+
+impl SubscribedAccountCache {
+    pub fn new(max_accounts: usize, idle: Duration) -> Self {
+        Self {
+            accounts: RwLock::new(HashMap::new()),
+            live: RwLock::new(HashSet::new()),
+            max_accounts,
+            idle,
+        }
+    }
+
+    /// Ask for `key` to be subscribed; false when the cache is full
+    pub fn watch(&self, key: &Pubkey, now: Instant) -> bool {
+        let mut accounts = self.accounts.write().unwrap();
+        if let Some(entry) = accounts.get_mut(key) {
+            entry.last_requested = now;
+            return true;
+        }
+        if accounts.len() >= self.max_accounts {
+            return false;
+        }
+        accounts.insert(*key, SubscribedAccount { data: None, slot: 0, last_requested: now });
+        true
+    }
+
+    /// Cached data of a subscribed account, None when it is not live or not loaded yet
+    pub fn get(&self, key: &Pubkey) -> Option<Vec<u8>> {
+        if !self.live.read().unwrap().contains(key) {
+            return None;
+        }
+        let accounts = self.accounts.read().unwrap();
+        accounts.get(key).and_then(|entry| entry.data.clone())
+    }
+
+    /// Data read over RPC for a live account; kept unless an update already arrived
+    pub fn store_fetched(&self, key: &Pubkey, data: Vec<u8>) {
+        if !self.live.read().unwrap().contains(key) {
+            return;
+        }
+        let mut accounts = self.accounts.write().unwrap();
+        if let Some(entry) = accounts.get_mut(key) {
+            if entry.data.is_none() {
+                entry.data = Some(data);
+            }
+        }
+    }
+
+    /// Account update from the subscription; older slots than the cached one are ignored
+    pub fn apply_update(&self, key: &Pubkey, data: Vec<u8>, slot: u64) {
+        let mut accounts = self.accounts.write().unwrap();
+        if let Some(entry) = accounts.get_mut(key) {
+            if entry.data.is_none() || slot >= entry.slot {
+                entry.data = Some(data);
+                entry.slot = slot;
+            }
+        }
+    }
+
+    /// Accounts that should be subscribed, after dropping the ones nobody read lately
+    pub fn wanted(&self, now: Instant) -> Vec<Pubkey> {
+        let mut accounts = self.accounts.write().unwrap();
+        accounts.retain(|_, entry| now.saturating_duration_since(entry.last_requested) <= self.idle);
+        let mut keys: Vec<Pubkey> = accounts.keys().copied().collect();
+        keys.sort();
+        keys
+    }
+
+    /// The subscription now covers exactly `keys`. Data of newly covered accounts is dropped,
+    /// since updates before this point were missed.
+    pub fn mark_live(&self, keys: &[Pubkey]) {
+        let mut live = self.live.write().unwrap();
+        let mut accounts = self.accounts.write().unwrap();
+        for key in keys {
+            if !live.contains(key) {
+                if let Some(entry) = accounts.get_mut(key) {
+                    entry.data = None;
+                    entry.slot = 0;
+                }
+            }
+        }
+        *live = keys.iter().copied().collect();
+    }
+
+    /// The subscription dropped: nothing cached can be trusted anymore
+    pub fn invalidate_all(&self) {
+        self.live.write().unwrap().clear();
+        let mut accounts = self.accounts.write().unwrap();
+        for entry in accounts.values_mut() {
+            entry.data = None;
+            entry.slot = 0;
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        let accounts = self.accounts.read().unwrap();
+        accounts.len()
+    }
+}
+
 // Global cache instances with reasonable TTL values
 lazy_static! {
     pub static ref TOKEN_ACCOUNT_CACHE: TokenAccountCache = TokenAccountCache::new(60); // 60 seconds TTL
     pub static ref TOKEN_MINT_CACHE: TokenMintCache = TokenMintCache::new(300); // 5 minutes TTL
     pub static ref WALLET_TOKEN_ACCOUNTS: WalletTokenAccounts = WalletTokenAccounts::new();
+    pub static ref LOOKUP_TABLE_CACHE: LookupTableCache = LookupTableCache::new(3600); // 1 hour TTL
+    pub static ref SUBSCRIBED_ACCOUNTS: SubscribedAccountCache = SubscribedAccountCache::new(
+        std::env::var("ACCOUNT_CACHE_MAX_ACCOUNTS").ok().and_then(|v| v.parse().ok()).unwrap_or(500),
+        Duration::from_secs(std::env::var("ACCOUNT_CACHE_IDLE_SECS").ok().and_then(|v| v.parse().ok()).unwrap_or(600)),
+    );
 } 
\ No newline at end of file
@@ -0,0 +1,245 @@
+use std::fmt;
+use std::str::FromStr;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use lazy_static::lazy_static;
+
+/// Solana cluster the bot runs against, from `CLUSTER` (default mainnet).
+///
+/// Switches the DEX program ids, the default RPC endpoints and explorer links so the whole
+/// pipeline can be dry-run against devnet deployments. Services that only exist on mainnet
+/// (ZeroSlot, Jupiter) are disabled on other clusters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cluster {
+    Mainnet,
+    Devnet,
+    Testnet,
+}
+
+lazy_static! {
+    static ref CURRENT: Cluster = Cluster::from_env();
+}
+
+impl Cluster {
+    pub fn from_env() -> Self {
+        std::env::var("CLUSTER")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(Cluster::Mainnet)
+    }
+
+    /// The cluster configured for this process
+    pub fn current() -> Self {
+        *CURRENT
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Cluster::Mainnet => "mainnet",
+            Cluster::Devnet => "devnet",
+            Cluster::Testnet => "testnet",
+        }
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        *self == Cluster::Mainnet
+    }
+
+    pub fn default_rpc_http(&self) -> &'static str {
+        match self {
+            Cluster::Mainnet => "https://api.mainnet-beta.solana.com",
+            Cluster::Devnet => "https://api.devnet.solana.com",
+            Cluster::Testnet => "https://api.testnet.solana.com",
+        }
+    }
+
+    pub fn default_rpc_wss(&self) -> &'static str {
+        match self {
+            Cluster::Mainnet => "wss://api.mainnet-beta.solana.com",
+            Cluster::Devnet => "wss://api.devnet.solana.com",
+            Cluster::Testnet => "wss://api.testnet.solana.com",
+        }
+    }
+
+    /// RPC_HTTP when set; other clusters than mainnet fall back to their public endpoint
+    pub fn rpc_http(&self) -> Option<String> {
+        self.endpoint("RPC_HTTP", self.default_rpc_http())
+    }
+
+    /// RPC_WSS when set; other clusters than mainnet fall back to their public endpoint
+    pub fn rpc_wss(&self) -> Option<String> {
+        self.endpoint("RPC_WSS", self.default_rpc_wss())
+    }
+
+    fn endpoint(&self, var: &str, default: &str) -> Option<String> {
+        std::env::var(var)
+            .ok()
+            .filter(|v| !v.is_empty())
+            .or_else(|| (!self.is_mainnet()).then(|| default.to_string()))
+    }
+
+    /// ZeroSlot and Jupiter only serve mainnet
+    pub fn supports_mainnet_services(&self) -> bool {
+        self.is_mainnet()
+    }
+
+    fn explorer_suffix(&self) -> &'static str {
+        match self {
+            Cluster::Mainnet => "",
+            Cluster::Devnet => "?cluster=devnet",
+            Cluster::Testnet => "?cluster=testnet",
+        }
+    }
+
+    pub fn tx_url(&self, signature: &str) -> String {
+        format!("https://solscan.io/tx/{}{}", signature, self.explorer_suffix())
+    }
+
+    pub fn account_url(&self, address: &str) -> String {
+        format!("https://solscan.io/account/{}{}", address, self.explorer_suffix())
+    }
+
+    pub fn token_url(&self, mint: &str) -> String {
+        format!("https://solscan.io/token/{}{}", mint, self.explorer_suffix())
+    }
+}
+
+impl FromStr for Cluster {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "mainnet" | "mainnet-beta" => Ok(Cluster::Mainnet),
+            "devnet" => Ok(Cluster::Devnet),
+            "testnet" => Ok(Cluster::Testnet),
+            other => Err(format!("Invalid cluster: {}. Use 'mainnet', 'devnet' or 'testnet'", other)),
+        }
+    }
+}
+
+impl fmt::Display for Cluster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// DEX programs the bot trades, resolved for the configured cluster
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DexProgram {
+    PumpFun,
+    PumpSwap,
+    RaydiumLaunchpad,
+}
+
+lazy_static! {
+    static ref PUMP_FUN_ID: Pubkey = DexProgram::PumpFun.resolve(Cluster::current());
+    static ref PUMP_SWAP_ID: Pubkey = DexProgram::PumpSwap.resolve(Cluster::current());
+    static ref RAYDIUM_LAUNCHPAD_ID: Pubkey = DexProgram::RaydiumLaunchpad.resolve(Cluster::current());
+    static ref ENABLED_DEXES: Vec<DexProgram> = dex_list_var("ENABLED_DEXES").unwrap_or_else(|| DexProgram::ALL.to_vec());
+    static ref TRADE_DEXES: Vec<DexProgram> = dex_list_var("TRADE_DEXES")
+        .unwrap_or_else(|| ENABLED_DEXES.clone())
+        .into_iter()
+        .filter(|dex| ENABLED_DEXES.contains(dex))
+        .collect();
+}
+
+impl DexProgram {
+    pub const ALL: [DexProgram; 3] = [DexProgram::PumpFun, DexProgram::PumpSwap, DexProgram::RaydiumLaunchpad];
+
+    /// By name: `pumpfun`, `pumpswap` or `raydium_launchpad` (case, `-` and `_` ignored)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace(['-', '_'], "").as_str() {
+            "pumpfun" | "pump" => Some(DexProgram::PumpFun),
+            "pumpswap" | "pumpamm" => Some(DexProgram::PumpSwap),
+            "raydiumlaunchpad" | "launchpad" => Some(DexProgram::RaydiumLaunchpad),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DexProgram::PumpFun => "pumpfun",
+            DexProgram::PumpSwap => "pumpswap",
+            DexProgram::RaydiumLaunchpad => "raydium_launchpad",
+        }
+    }
+
+    /// Whether the DEX is subscribed to and parsed (`ENABLED_DEXES`, default all)
+    pub fn is_enabled(&self) -> bool {
+        ENABLED_DEXES.contains(self)
+    }
+
+    /// Whether strategies may buy on the DEX (`TRADE_DEXES`, default the enabled ones; never a
+    /// DEX that is not enabled)
+    pub fn is_tradeable(&self) -> bool {
+        TRADE_DEXES.contains(self)
+    }
+
+    /// Program id on the configured cluster
+    pub fn id(&self) -> Pubkey {
+        match self {
+            DexProgram::PumpFun => *PUMP_FUN_ID,
+            DexProgram::PumpSwap => *PUMP_SWAP_ID,
+            DexProgram::RaydiumLaunchpad => *RAYDIUM_LAUNCHPAD_ID,
+        }
+    }
+
+    /// Env var that overrides the program id, for deployments not in the table
+    pub fn override_var(&self) -> &'static str {
+        match self {
+            DexProgram::PumpFun => "PUMP_FUN_PROGRAM_ID",
+            DexProgram::PumpSwap => "PUMP_SWAP_PROGRAM_ID",
+            DexProgram::RaydiumLaunchpad => "RAYDIUM_LAUNCHPAD_PROGRAM_ID",
+        }
+    }
+
+    /// Known deployment on a cluster. Pump.fun and PumpSwap use the same address everywhere;
+    /// clusters without a known deployment fall back to the mainnet address.
+    pub fn default_id(&self, cluster: Cluster) -> Pubkey {
+        match (self, cluster) {
+            (DexProgram::PumpFun, _) => Pubkey::from_str(crate::dex::pump_fun::PUMP_FUN_PROGRAM).unwrap(),
+            (DexProgram::PumpSwap, _) => crate::dex::pump_swap::PUMP_SWAP_PROGRAM,
+            (DexProgram::RaydiumLaunchpad, Cluster::Devnet) => {
+                solana_sdk::pubkey!("LanD8FpTBBvzZFXjTxsAoipkFsxPUCDB4qAqKxYDiNP")
+            }
+            (DexProgram::RaydiumLaunchpad, _) => crate::dex::raydium_launchpad::RAYDIUM_LAUNCHPAD_PROGRAM,
+        }
+    }
+
+    fn resolve(&self, cluster: Cluster) -> Pubkey {
+        std::env::var(self.override_var())
+            .ok()
+            .and_then(|v| Pubkey::from_str(v.trim()).ok())
+            .unwrap_or_else(|| self.default_id(cluster))
+    }
+}
+
+/// Comma separated DEX names; an empty list or an unknown name is an error
+pub fn parse_dex_list(value: &str) -> Result<Vec<DexProgram>, String> {
+    let mut dexes = Vec::new();
+    for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let dex = DexProgram::from_name(entry)
+            .ok_or_else(|| format!("Unknown DEX '{}': use pumpfun, pumpswap or raydium_launchpad", entry))?;
+        if !dexes.contains(&dex) {
+            dexes.push(dex);
+        }
+    }
+    if dexes.is_empty() {
+        return Err("No DEX listed".to_string());
+    }
+    Ok(dexes)
+}
+
+/// Check `ENABLED_DEXES` and `TRADE_DEXES`; invalid lists are otherwise ignored (every DEX stays
+/// enabled), so startup calls this to refuse a typo instead
+pub fn validate_dex_toggles() -> Result<(), String> {
+    for name in ["ENABLED_DEXES", "TRADE_DEXES"] {
+        if let Some(value) = std::env::var(name).ok().filter(|v| !v.trim().is_empty()) {
+            parse_dex_list(&value).map_err(|e| format!("{}: {}", name, e))?;
+        }
+    }
+    Ok(())
+}
+
+fn dex_list_var(name: &str) -> Option<Vec<DexProgram>> {
+    std::env::var(name).ok().and_then(|v| parse_dex_list(&v).ok())
+}
@@ -1,18 +1,23 @@
 use anyhow::Result;
+#[cfg(feature = "trading")]
 use bs58;
 use colored::Colorize;
 use dotenv::dotenv;
 use reqwest::Error;
 use serde::Deserialize;
-use anchor_client::solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair, signer::Signer};
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+#[cfg(feature = "trading")]
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
 use tokio::sync::{Mutex, OnceCell};
 use tokio_tungstenite::tungstenite::http::request;
 use std::{env, sync::Arc};
 use crate::processor::swap::SwapProtocol;
 use crate::{
-    common::{constants::INIT_MSG, logger::Logger},
+    common::{cluster::Cluster, constants::INIT_MSG, logger::Logger},
     processor::swap::{SwapDirection, SwapInType},
 };
+#[cfg(feature = "trading")]
+use crate::error::BotError;
 use std::time::Duration;
 
 static GLOBAL_CONFIG: OnceCell<Mutex<Config>> = OnceCell::const_new();
@@ -43,6 +48,199 @@ impl FromStr for TransactionLandingMode {
 
 use std::str::FromStr;
 
+/// A percentage in percent points: `Percent::from_points(15.0)` is 15%.
+///
+/// Parses `15` or `15%` as 15%. Settings that historically took a fraction of 1 parse through
+/// [`Percent::parse_fraction`] instead, where `0.15` and `15%` both mean 15%.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Percent(f64);
+
+impl Percent {
+    pub const fn from_points(points: f64) -> Self {
+        Percent(points)
+    }
+
+    pub fn from_fraction(fraction: f64) -> Self {
+        Percent(fraction * 100.0)
+    }
+
+    /// 15.0 for 15%
+    pub fn points(&self) -> f64 {
+        self.0
+    }
+
+    /// 0.15 for 15%
+    pub fn fraction(&self) -> f64 {
+        self.0 / 100.0
+    }
+
+    /// A fraction of 1 (`0.15`), or percent points with an explicit `%` (`15%`). A bare value
+    /// above 1 is rejected rather than guessed at.
+    pub fn parse_fraction(raw: &str) -> Result<Self, String> {
+        let raw = raw.trim();
+        if raw.ends_with('%') {
+            return raw.parse();
+        }
+        let fraction = parse_number(raw)?;
+        if fraction > 1.0 {
+            return Err(format!("{} is read as a fraction of 1; write {}% for {} percent", raw, raw, raw));
+        }
+        Ok(Percent::from_fraction(fraction))
+    }
+}
+
+impl FromStr for Percent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_number(s.trim().trim_end_matches('%')).map(Percent)
+    }
+}
+
+impl std::fmt::Display for Percent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+/// An amount of SOL. Parses `1.5`, `1.5 SOL` or `1500000000 lamports`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct SolAmount(f64);
+
+impl SolAmount {
+    pub const fn from_sol(sol: f64) -> Self {
+        SolAmount(sol)
+    }
+
+    pub fn sol(&self) -> f64 {
+        self.0
+    }
+
+    pub fn lamports(&self) -> u64 {
+        (self.0 * 1_000_000_000.0).round() as u64
+    }
+}
+
+impl FromStr for SolAmount {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        match lower.strip_suffix("lamports") {
+            Some(lamports) => lamports.trim().parse::<u64>()
+                .map(|lamports| SolAmount(lamports as f64 / 1_000_000_000.0))
+                .map_err(|_| format!("{} is not a whole number of lamports", s.trim())),
+            None => parse_number(lower.trim_end_matches("sol").trim()).map(SolAmount),
+        }
+    }
+}
+
+impl std::fmt::Display for SolAmount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} SOL", self.0)
+    }
+}
+
+/// A number of slots. Parses `10` or `10 slots`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
+pub struct Slots(u64);
+
+impl Slots {
+    pub const fn new(slots: u64) -> Self {
+        Slots(slots)
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for Slots {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let raw = s.trim().to_lowercase();
+        let count = raw.strip_suffix("slots").or_else(|| raw.strip_suffix("slot")).unwrap_or(&raw).trim();
+        count.parse::<u64>().map(Slots).map_err(|_| format!("{} is not a whole number of slots", s.trim()))
+    }
+}
+
+impl std::fmt::Display for Slots {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} slots", self.0)
+    }
+}
+
+/// A finite, non-negative number
+fn parse_number(raw: &str) -> Result<f64, String> {
+    match raw.parse::<f64>() {
+        Ok(value) if value.is_finite() && value >= 0.0 => Ok(value),
+        Ok(_) => Err(format!("{} must be a non-negative number", raw)),
+        Err(_) => Err(format!("{} is not a number", raw)),
+    }
+}
+
+/// A threshold setting that is set but unusable
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThresholdError {
+    pub name: String,
+    pub value: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for ThresholdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}: {}", self.name, self.value, self.reason)
+    }
+}
+
+impl std::error::Error for ThresholdError {}
+
+/// Parse `raw` as the threshold `name` and check it lies within `range`
+pub fn parse_threshold<T: PartialOrd + std::fmt::Display>(
+    name: &str,
+    raw: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+    range: std::ops::RangeInclusive<T>,
+) -> Result<T, ThresholdError> {
+    let error = |reason: String| ThresholdError { name: name.to_string(), value: raw.to_string(), reason };
+    let value = parse(raw).map_err(error)?;
+    if !range.contains(&value) {
+        return Err(error(format!("must be between {} and {}", range.start(), range.end())));
+    }
+    Ok(value)
+}
+
+/// The threshold `name` from the environment; None when unset or empty
+pub fn threshold_from_env<T: PartialOrd + std::fmt::Display>(
+    name: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+    range: std::ops::RangeInclusive<T>,
+) -> Result<Option<T>, ThresholdError> {
+    match env::var(name) {
+        Ok(raw) if !raw.trim().is_empty() => parse_threshold(name, raw.trim(), parse, range).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// The threshold `name` from the environment, or `default` when unset. An unusable value is
+/// reported and the default used, so one bad setting does not stop the monitor.
+pub fn threshold_or<T: PartialOrd + std::fmt::Display>(
+    name: &str,
+    default: T,
+    parse: impl Fn(&str) -> Result<T, String>,
+    range: std::ops::RangeInclusive<T>,
+) -> T {
+    match threshold_from_env(name, parse, range) {
+        Ok(value) => value.unwrap_or(default),
+        Err(e) => {
+            eprintln!("{} (using {})", e, default);
+            default
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Config {
     pub yellowstone_grpc_http: String,
     pub yellowstone_grpc_token: String,
@@ -55,8 +253,8 @@ pub struct Config {
     pub selling_unit_limit: u32,  // New: Compute units for selling transactions
     pub zero_slot_tip_value: f64, // New: Tip value for zeroslot selling
     // Sniper configuration
-    pub focus_drop_threshold_pct: f64, // percentage drop from initial to flag "dropped"
-    pub focus_trigger_sol: f64,        // SOL size to trigger buy after drop
+    pub focus_drop_threshold: Percent, // drop from initial to flag "dropped"
+    pub focus_trigger_sol: SolAmount,  // SOL size to trigger buy after drop
 }
 
 impl Config {
@@ -74,9 +272,15 @@ impl Config {
             let yellowstone_grpc_token = import_env_var("YELLOWSTONE_GRPC_TOKEN");
             let slippage_input = import_env_var("SLIPPAGE").parse::<u64>().unwrap_or(5000);
             let counter_limit = import_env_var("COUNTER_LIMIT").parse::<u32>().unwrap_or(0_u32);
-            let transaction_landing_mode = import_env_var("TRANSACTION_LANDING_SERVICE")
+            let cluster = Cluster::current();
+            let transaction_landing_mode = match import_env_var("TRANSACTION_LANDING_SERVICE")
                 .parse::<TransactionLandingMode>()
-                .unwrap_or(TransactionLandingMode::default());
+                .unwrap_or(TransactionLandingMode::default())
+            {
+                // ZeroSlot only lands on mainnet
+                TransactionLandingMode::Zeroslot if !cluster.supports_mainnet_services() => TransactionLandingMode::Normal,
+                mode => mode,
+            };
             // Read COPY_SELLING_LIMIT from env (default 1.5)
             let copy_selling_limit = import_env_var("COPY_SELLING_LIMIT").parse::<f64>().unwrap_or(1.5);
             
@@ -85,8 +289,27 @@ impl Config {
             let selling_unit_limit = import_env_var("SELLING_UNIT_LIMIT").parse::<u32>().unwrap_or(2000000);
             let zero_slot_tip_value = import_env_var("ZERO_SLOT_TIP_VALUE").parse::<f64>().unwrap_or(0.0025);
             // Sniper thresholds
-            let focus_drop_threshold_pct = import_env_var("FOCUS_DROP_THRESHOLD_PCT").parse::<f64>().unwrap_or(0.15);
-            let focus_trigger_sol = import_env_var("FOCUS_TRIGGER_SOL").parse::<f64>().unwrap_or(1.0);
+            // A trading bot does not start on a threshold it cannot read
+            let focus_drop_threshold = threshold_from_env(
+                "FOCUS_DROP_THRESHOLD_PCT",
+                Percent::parse_fraction,
+                Percent::from_points(0.01)..=Percent::from_points(100.0),
+            );
+            let focus_trigger_sol = threshold_from_env(
+                "FOCUS_TRIGGER_SOL",
+                SolAmount::from_str,
+                SolAmount::from_sol(0.0)..=SolAmount::from_sol(1_000.0),
+            );
+            let (focus_drop_threshold, focus_trigger_sol) = match (focus_drop_threshold, focus_trigger_sol) {
+                (Ok(drop), Ok(trigger)) => (
+                    drop.unwrap_or(Percent::from_points(15.0)),
+                    trigger.unwrap_or(SolAmount::from_sol(1.0)),
+                ),
+                (Err(e), _) | (_, Err(e)) => {
+                    eprintln!("{}", e.to_string().red());
+                    std::process::exit(1);
+                }
+            };
             
             let max_slippage: u64 = 10000 ; 
             let slippage = if slippage_input > max_slippage {
@@ -94,22 +317,48 @@ impl Config {
             } else {
                 slippage_input
             };
-            let solana_price = create_coingecko_proxy().await.unwrap_or(200_f64);
+            let solana_price = create_coingecko_proxy().await;
+            if let Ok(price) = &solana_price {
+                crate::processor::market_regime::MarketRegime::shared().record_sol_price(*price);
+            }
+            crate::common::quote_asset::set_sol_usd_price(solana_price.unwrap_or(200_f64));
             let rpc_client = create_rpc_client().unwrap();
             let rpc_nonblocking_client = create_nonblocking_rpc_client().await.unwrap();
+            #[cfg(feature = "trading")]
             let zeroslot_rpc_client = create_zeroslot_rpc_client().await.unwrap();
-            let wallet: std::sync::Arc<anchor_client::solana_sdk::signature::Keypair> = import_wallet().unwrap();
-            let balance = match rpc_nonblocking_client
-                .get_account(&wallet.pubkey())
-                .await {
-                    Ok(account) => account.lamports,
-                    Err(err) => {
-                        logger.log(format!("Failed to get wallet balance: {}", err).red().to_string());
-                        0 // Default to zero if we can't get the balance
+            #[cfg(feature = "trading")]
+            let (wallet, wallet_summary) = if crate::library::trading_lockdown::trading_enabled() {
+                let wallet: std::sync::Arc<anchor_client::solana_sdk::signature::Keypair> = match import_wallet() {
+                    Ok(wallet) => wallet,
+                    Err(e) => {
+                        eprintln!("{}", e.to_string().red());
+                        std::process::exit(1);
                     }
                 };
-
-            let wallet_cloned = wallet.clone();
+                let balance = match rpc_nonblocking_client
+                    .get_account(&wallet.pubkey())
+                    .await {
+                        Ok(account) => account.lamports,
+                        Err(err) => {
+                            logger.log(format!("Failed to get wallet balance: {}", err).red().to_string());
+                            0 // Default to zero if we can't get the balance
+                        }
+                    };
+
+                let wallet_summary = format!(
+                    "* [Wallet]: {:?}, * [Balance]: {} Sol",
+                    wallet.pubkey(),
+                    balance as f64 / 1_000_000_000_f64,
+                );
+                (wallet, wallet_summary)
+            } else {
+                // Read-only: a throwaway key holding nothing; every signing and sending path refuses
+                // anyway, and the wallet balance monitors do not start
+                (Arc::new(Keypair::new()), "* [Wallet]: none (TRADING_ENABLED=false)".to_string())
+            };
+            // Alerts-only builds have no wallet to load
+            #[cfg(not(feature = "trading"))]
+            let wallet_summary = "* [Wallet]: none (built without trading)".to_string();
             let swap_direction = SwapDirection::Buy; //SwapDirection::Sell
             let in_type = SwapInType::Qty; //SwapInType::Pct
             let amount_in = import_env_var("TOKEN_AMOUNT")
@@ -126,21 +375,35 @@ impl Config {
             };
 
             let rpc_client = create_rpc_client().unwrap();
+            #[cfg(feature = "trading")]
+            let rpc_api: Arc<dyn crate::library::rpc_api::RpcApi> = Arc::new(crate::library::rpc_api::SolanaRpc::new(
+                rpc_nonblocking_client.clone(),
+                zeroslot_rpc_client.clone(),
+            ));
+            let account_batcher = Arc::new(crate::library::account_batcher::AccountBatcher::new(
+                rpc_nonblocking_client.clone(),
+                crate::library::account_batcher::BatchConfig::from_env(),
+            ));
             let app_state = AppState {
                 rpc_client,
                 rpc_nonblocking_client,
+                #[cfg(feature = "trading")]
                 zeroslot_rpc_client,
+                #[cfg(feature = "trading")]
+                rpc_api,
+                account_batcher,
+                #[cfg(feature = "trading")]
                 wallet,
                 protocol_preference: SwapProtocol::default(),
             };
            logger.log(
                     format!(
-                    "[SNIPER ENVIRONMENT]: \n\t\t\t\t [Yellowstone gRpc]: {},
-                    \n\t\t\t\t * [Wallet]: {:?}, * [Balance]: {} Sol, 
+                    "[SNIPER ENVIRONMENT]: \n\t\t\t\t [Cluster]: {}, [Yellowstone gRpc]: {},
+                    \n\t\t\t\t {}, 
                     \n\t\t\t\t * [Slippage]: {}, * [Solana]: {}, * [Amount]: {}",
+                    cluster,
                     yellowstone_grpc_http,
-                    wallet_cloned.pubkey(),
-                    balance as f64 / 1_000_000_000_f64,
+                    wallet_summary,
                     slippage_input,
                     solana_price,
                     amount_in,
@@ -160,7 +423,7 @@ impl Config {
                 selling_unit_price,
                 selling_unit_limit,
                 zero_slot_tip_value,
-                focus_drop_threshold_pct,
+                focus_drop_threshold,
                 focus_trigger_sol,
             })
         })
@@ -243,7 +506,14 @@ struct SolanaData {
 pub struct AppState {
     pub rpc_client: Arc<anchor_client::solana_client::rpc_client::RpcClient>,
     pub rpc_nonblocking_client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+    #[cfg(feature = "trading")]
     pub zeroslot_rpc_client: Arc<crate::library::zeroslot::ZeroSlotClient>,
+    /// Sends and confirmations of the trading path; mocked in tests
+    #[cfg(feature = "trading")]
+    pub rpc_api: Arc<dyn crate::library::rpc_api::RpcApi>,
+    /// Coalesces single account reads of enrichment tasks into getMultipleAccounts calls
+    pub account_batcher: Arc<crate::library::account_batcher::AccountBatcher>,
+    #[cfg(feature = "trading")]
     pub wallet: Arc<Keypair>,
     pub protocol_preference: SwapProtocol,
 }
@@ -267,6 +537,7 @@ pub fn import_env_var(key: &str) -> String {
 }
 
 // Zero slot health check URL
+#[cfg(feature = "trading")]
 pub fn get_zero_slot_health_url() -> String {
     std::env::var("ZERO_SLOT_HEALTH").unwrap_or_else(|_| {
         eprintln!("ZERO_SLOT_HEALTH environment variable not set, using default");
@@ -274,8 +545,13 @@ pub fn get_zero_slot_health_url() -> String {
     })
 }
 
+/// RPC_HTTP, or the public endpoint of the configured cluster when that is not mainnet
+pub fn rpc_http_url() -> String {
+    Cluster::current().rpc_http().unwrap_or_else(|| import_env_var("RPC_HTTP"))
+}
+
 pub fn create_rpc_client() -> Result<Arc<anchor_client::solana_client::rpc_client::RpcClient>> {
-    let rpc_http = import_env_var("RPC_HTTP");
+    let rpc_http = rpc_http_url();
     let timeout = Duration::from_secs(30); // 30 second timeout
     let rpc_client = anchor_client::solana_client::rpc_client::RpcClient::new_with_timeout_and_commitment(
         rpc_http,
@@ -287,7 +563,7 @@ pub fn create_rpc_client() -> Result<Arc<anchor_client::solana_client::rpc_clien
 
 pub async fn create_nonblocking_rpc_client(
 ) -> Result<Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>> {
-    let rpc_http = import_env_var("RPC_HTTP");
+    let rpc_http = rpc_http_url();
     let timeout = Duration::from_secs(30); // 30 second timeout
     let rpc_client = anchor_client::solana_client::nonblocking::rpc_client::RpcClient::new_with_timeout_and_commitment(
         rpc_http,
@@ -297,6 +573,7 @@ pub async fn create_nonblocking_rpc_client(
     Ok(Arc::new(rpc_client))
 }
 
+#[cfg(feature = "trading")]
 pub async fn create_zeroslot_rpc_client() -> Result<Arc<crate::library::zeroslot::ZeroSlotClient>> {
     let client = crate::library::zeroslot::ZeroSlotClient::new(
         crate::library::zeroslot::ZERO_SLOT_URL.as_str()
@@ -318,13 +595,55 @@ pub async fn create_coingecko_proxy() -> Result<f64, Error> {
     Ok(sol_price)
 }
 
+#[cfg(feature = "trading")]
 pub fn import_wallet() -> Result<Arc<Keypair>> {
-    let priv_key = import_env_var("PRIVATE_KEY");
-    if priv_key.len() < 85 {
-        println!("{}", format!("Please check wallet priv key: Invalid length => {}", priv_key.len()).red().to_string());
-        loop{}
+    // Read-only processes never hold a signing key
+    crate::library::trading_lockdown::ensure_trading_enabled()?;
+    // Without a PRIVATE_KEY, sign with the first wallet registered in the keystore
+    if std::env::var("PRIVATE_KEY").map(|k| k.trim().is_empty()).unwrap_or(true)
+        && !crate::library::keystore::registered_wallets().is_empty()
+    {
+        let wallet = crate::library::keystore::load_primary_wallet()
+            .map_err(|e| BotError::InvalidKeypair(e.to_string()))?;
+        audit_key_load("keystore", &wallet);
+        return Ok(Arc::new(wallet));
     }
-    let wallet: Keypair = Keypair::from_base58_string(priv_key.as_str());
+    let priv_key = import_env_var("PRIVATE_KEY");
+    let bytes = bs58::decode(priv_key.trim())
+        .into_vec()
+        .map_err(|e| BotError::InvalidKeypair(format!("PRIVATE_KEY is not base58: {}", e)))?;
+    let wallet = Keypair::from_bytes(&bytes)
+        .map_err(|e| BotError::InvalidKeypair(format!("PRIVATE_KEY is not a 64-byte keypair: {}", e)))?;
+    audit_key_load("private_key", &wallet);
 
     Ok(Arc::new(wallet))
+}
+
+/// Address of the wallet `import_wallet` would load, read from KEYSTORE_WALLETS or the public half
+/// of PRIVATE_KEY without decrypting or loading any key; works while trading is locked down
+#[cfg(feature = "trading")]
+pub fn wallet_pubkey() -> Result<anchor_client::solana_sdk::pubkey::Pubkey> {
+    use anchor_client::solana_sdk::pubkey::Pubkey;
+
+    if std::env::var("PRIVATE_KEY").map(|k| k.trim().is_empty()).unwrap_or(true) {
+        let address = crate::library::keystore::registered_wallets()
+            .into_iter()
+            .next()
+            .ok_or_else(|| BotError::InvalidKeypair("No PRIVATE_KEY and no wallet in KEYSTORE_WALLETS".to_string()))?;
+        return Pubkey::from_str(&address)
+            .map_err(|e| BotError::InvalidKeypair(format!("KEYSTORE_WALLETS entry {} is not an address: {}", address, e)).into());
+    }
+    let bytes = bs58::decode(import_env_var("PRIVATE_KEY").trim())
+        .into_vec()
+        .map_err(|e| BotError::InvalidKeypair(format!("PRIVATE_KEY is not base58: {}", e)))?;
+    if bytes.len() != 64 {
+        return Err(BotError::InvalidKeypair("PRIVATE_KEY is not a 64-byte keypair".to_string()).into());
+    }
+    Pubkey::try_from(&bytes[32..]).map_err(|_| BotError::InvalidKeypair("PRIVATE_KEY has no public key".to_string()).into())
+}
+
+#[cfg(feature = "trading")]
+fn audit_key_load(source: &str, wallet: &Keypair) {
+    use crate::library::audit_log::{self, AuditAction};
+    audit_log::record(AuditAction::KeyUsed, source, serde_json::json!({ "pubkey": wallet.pubkey().to_string(), "use": "load" }));
 }
\ No newline at end of file
@@ -0,0 +1,206 @@
+use std::str::FromStr;
+use crate::common::cluster::Cluster;
+
+/// Sites alerts link tokens, wallets and transactions to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Explorer {
+    Solscan,
+    SolanaExplorer,
+    Birdeye,
+    DexScreener,
+    PumpFun,
+}
+
+impl Explorer {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Explorer::Solscan => "Solscan",
+            Explorer::SolanaExplorer => "Explorer",
+            Explorer::Birdeye => "Birdeye",
+            Explorer::DexScreener => "DexScreener",
+            Explorer::PumpFun => "Pump.fun",
+        }
+    }
+
+    /// Birdeye, DexScreener and Pump.fun only index mainnet
+    fn serves(&self, cluster: Cluster) -> bool {
+        cluster.is_mainnet() || matches!(self, Explorer::Solscan | Explorer::SolanaExplorer)
+    }
+
+    fn cluster_query(cluster: Cluster) -> String {
+        if cluster.is_mainnet() {
+            String::new()
+        } else {
+            format!("?cluster={}", cluster)
+        }
+    }
+
+    pub fn token_url(&self, mint: &str, cluster: Cluster) -> Option<String> {
+        if !self.serves(cluster) {
+            return None;
+        }
+        Some(match self {
+            Explorer::Solscan => cluster.token_url(mint),
+            Explorer::SolanaExplorer => format!("https://explorer.solana.com/address/{}{}", mint, Self::cluster_query(cluster)),
+            Explorer::Birdeye => format!("https://birdeye.so/token/{}?chain=solana", mint),
+            Explorer::DexScreener => format!("https://dexscreener.com/solana/{}", mint),
+            Explorer::PumpFun => format!("https://pump.fun/coin/{}", mint),
+        })
+    }
+
+    pub fn wallet_url(&self, address: &str, cluster: Cluster) -> Option<String> {
+        if !self.serves(cluster) {
+            return None;
+        }
+        match self {
+            Explorer::Solscan => Some(cluster.account_url(address)),
+            Explorer::SolanaExplorer => Some(format!("https://explorer.solana.com/address/{}{}", address, Self::cluster_query(cluster))),
+            Explorer::Birdeye => Some(format!("https://birdeye.so/profile/{}?chain=solana", address)),
+            Explorer::PumpFun => Some(format!("https://pump.fun/profile/{}", address)),
+            Explorer::DexScreener => None,
+        }
+    }
+
+    pub fn tx_url(&self, signature: &str, cluster: Cluster) -> Option<String> {
+        match self {
+            Explorer::Solscan => Some(cluster.tx_url(signature)),
+            Explorer::SolanaExplorer => Some(format!("https://explorer.solana.com/tx/{}{}", signature, Self::cluster_query(cluster))),
+            Explorer::Birdeye | Explorer::DexScreener | Explorer::PumpFun => None,
+        }
+    }
+}
+
+impl FromStr for Explorer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "solscan" => Ok(Explorer::Solscan),
+            "explorer" | "solana_explorer" | "solana-explorer" => Ok(Explorer::SolanaExplorer),
+            "birdeye" => Ok(Explorer::Birdeye),
+            "dexscreener" => Ok(Explorer::DexScreener),
+            "pumpfun" | "pump.fun" | "pump_fun" => Ok(Explorer::PumpFun),
+            other => Err(format!("Unknown explorer: {}", other)),
+        }
+    }
+}
+
+/// Markdown links for alerts. The preferred explorer comes first and is the target of inline
+/// wallet and transaction links; sites that cannot show an entity (or the cluster) fall back to
+/// Solscan.
+#[derive(Clone, Debug)]
+pub struct ExplorerLinks {
+    preferred: Explorer,
+    cluster: Cluster,
+}
+
+impl Default for ExplorerLinks {
+    fn default() -> Self {
+        Self::new(Explorer::Solscan, Cluster::Mainnet)
+    }
+}
+
+impl ExplorerLinks {
+    pub fn new(preferred: Explorer, cluster: Cluster) -> Self {
+        Self { preferred, cluster }
+    }
+
+    /// ALERT_EXPLORER (default solscan) on the configured cluster
+    pub fn from_env() -> Self {
+        let preferred = std::env::var("ALERT_EXPLORER")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(Explorer::Solscan);
+        Self::new(preferred, Cluster::current())
+    }
+
+    /// Preferred explorer first, then the remaining ones in a fixed order
+    fn ordered(&self) -> Vec<Explorer> {
+        let mut explorers = vec![self.preferred];
+        explorers.extend(
+            [Explorer::Solscan, Explorer::Birdeye, Explorer::DexScreener, Explorer::PumpFun]
+                .into_iter()
+                .filter(|explorer| *explorer != self.preferred),
+        );
+        explorers
+    }
+
+    fn join(links: Vec<(Explorer, String)>) -> String {
+        links.into_iter()
+            .map(|(explorer, url)| format!("[{}]({})", explorer.label(), url))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// All sites that can show the token
+    pub fn token(&self, mint: &str) -> String {
+        Self::join(self.ordered()
+            .into_iter()
+            .filter_map(|explorer| explorer.token_url(mint, self.cluster).map(|url| (explorer, url)))
+            .collect())
+    }
+
+    /// The wallet, shortened to its first 8 characters, linked to the preferred explorer
+    pub fn wallet(&self, address: &str) -> String {
+        let url = self.preferred.wallet_url(address, self.cluster)
+            .unwrap_or_else(|| self.cluster.account_url(address));
+        format!("[{}]({})", address.get(..8).unwrap_or(address), url)
+    }
+
+    /// Link to the transaction on the preferred explorer
+    pub fn transaction(&self, signature: &str) -> String {
+        let (explorer, url) = match self.preferred.tx_url(signature, self.cluster) {
+            Some(url) => (self.preferred, url),
+            None => (Explorer::Solscan, self.cluster.tx_url(signature)),
+        };
+        format!("[View on {}]({})", explorer.label(), url)
+    }
+}
+
+/// One-tap links into trading UIs with the token and a SOL amount prefilled, for users who
+/// decide to act on an opportunity alert manually. Off unless TRADE_DEEP_LINKS_ENABLED is set,
+/// so purely educational deployments never show them.
+#[derive(Clone, Debug)]
+pub struct TradeLinks {
+    /// SOL amount prefilled in the swap form
+    pub amount_sol: f64,
+}
+
+impl TradeLinks {
+    pub fn new(amount_sol: f64) -> Self {
+        Self { amount_sol }
+    }
+
+    /// Enabled by TRADE_DEEP_LINKS_ENABLED (default false) with TRADE_DEEP_LINK_AMOUNT_SOL
+    /// (default 0.1); never on clusters other than mainnet
+    pub fn from_env() -> Option<Self> {
+        let enabled = std::env::var("TRADE_DEEP_LINKS_ENABLED")
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(false);
+        if !enabled || !Cluster::current().is_mainnet() {
+            return None;
+        }
+        let amount_sol = std::env::var("TRADE_DEEP_LINK_AMOUNT_SOL")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(0.1);
+        Some(Self::new(amount_sol))
+    }
+
+    pub fn jupiter(&self, mint: &str) -> String {
+        format!("https://jup.ag/swap/SOL-{}?inAmount={}", mint, self.amount_sol)
+    }
+
+    pub fn photon(&self, mint: &str) -> String {
+        format!("https://photon-sol.tinyastro.io/en/lp/{}", mint)
+    }
+
+    /// Button label and URL for each trading UI
+    pub fn buttons(&self, mint: &str) -> Vec<(String, String)> {
+        vec![
+            (format!("🪐 Jupiter ({} SOL)", self.amount_sol), self.jupiter(mint)),
+            ("⚡ Photon".to_string(), self.photon(mint)),
+        ]
+    }
+}
@@ -3,3 +3,9 @@ pub mod constants;
 pub mod logger;
 pub mod cache;
 pub mod timeseries;
+pub mod price_history;
+pub mod trade_costs;
+pub mod clock;
+pub mod cluster;
+pub mod explorer;
+pub mod quote_asset;
@@ -0,0 +1,115 @@
+//! Timestamped price and volume history per token, kept for charting (see the Grafana routes of
+//! the status server). Trades are aggregated into buckets of PRICE_HISTORY_RESOLUTION_SECS: the
+//! last price of the bucket and the SOL bought and sold in it. Each token keeps its
+//! PRICE_HISTORY_POINTS most recent buckets; 0 disables recording.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref SHARED_PRICE_HISTORY: Arc<PriceHistory> = Arc::new(PriceHistory::from_env());
+}
+
+/// One bucket of a token's trades
+#[derive(Clone, Debug, PartialEq)]
+pub struct PricePoint {
+    /// Start of the bucket, unix milliseconds
+    pub timestamp_ms: i64,
+    /// Price of the last trade in the bucket
+    pub price: f64,
+    pub buy_volume: f64,   // volume in SOL
+    pub sell_volume: f64,  // volume in SOL
+}
+
+pub struct PriceHistory {
+    resolution_ms: i64,
+    capacity: usize,
+    series: DashMap<String, VecDeque<PricePoint>>,
+}
+
+impl PriceHistory {
+    pub fn new(resolution_secs: u64, capacity: usize) -> Self {
+        Self {
+            resolution_ms: (resolution_secs.max(1) * 1000) as i64,
+            capacity,
+            series: DashMap::new(),
+        }
+    }
+
+    /// PRICE_HISTORY_RESOLUTION_SECS (default 10) and PRICE_HISTORY_POINTS (default 360)
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok();
+        Self::new(
+            var("PRICE_HISTORY_RESOLUTION_SECS").and_then(|v| v.parse().ok()).unwrap_or(10),
+            var("PRICE_HISTORY_POINTS").and_then(|v| v.parse().ok()).unwrap_or(360),
+        )
+    }
+
+    pub fn shared() -> Arc<PriceHistory> {
+        SHARED_PRICE_HISTORY.clone()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Width of one bucket
+    pub fn resolution(&self) -> chrono::Duration {
+        chrono::Duration::milliseconds(self.resolution_ms)
+    }
+
+    /// Add a trade at `at`; trades older than the token's last bucket are ignored
+    pub fn record(&self, mint: &str, at: DateTime<Utc>, price: f64, is_buy: bool, sol_volume: f64) {
+        if !self.is_enabled() || price <= 0.0 {
+            return;
+        }
+        let bucket = at.timestamp_millis().div_euclid(self.resolution_ms) * self.resolution_ms;
+        let mut points = self.series.entry(mint.to_string()).or_default();
+        match points.back_mut() {
+            Some(back) if back.timestamp_ms > bucket => return,
+            Some(back) if back.timestamp_ms == bucket => {
+                back.price = price;
+                if is_buy { back.buy_volume += sol_volume; } else { back.sell_volume += sol_volume; }
+                return;
+            }
+            _ => {}
+        }
+        let (buy_volume, sell_volume) = if is_buy { (sol_volume, 0.0) } else { (0.0, sol_volume) };
+        points.push_back(PricePoint { timestamp_ms: bucket, price, buy_volume, sell_volume });
+        while points.len() > self.capacity {
+            points.pop_front();
+        }
+    }
+
+    /// Buckets of `mint` starting within [from_ms, to_ms], oldest first
+    pub fn points(&self, mint: &str, from_ms: i64, to_ms: i64) -> Vec<PricePoint> {
+        self.series.get(mint)
+            .map(|points| points.iter()
+                .filter(|p| p.timestamp_ms >= from_ms && p.timestamp_ms <= to_ms)
+                .cloned()
+                .collect())
+            .unwrap_or_default()
+    }
+
+    /// Tokens with recorded history, sorted
+    pub fn mints(&self) -> Vec<String> {
+        let mut mints: Vec<String> = self.series.iter().map(|entry| entry.key().clone()).collect();
+        mints.sort();
+        mints
+    }
+
+    pub fn len(&self) -> usize {
+        self.series.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+
+    pub fn retain(&self, keep: impl Fn(&str) -> bool) {
+        self.series.retain(|mint, _| keep(mint));
+    }
+}
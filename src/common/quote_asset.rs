@@ -0,0 +1,181 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+
+/// Asset a pool quotes its token in.
+///
+/// Most pools pair a token with WSOL, but PumpSwap and Raydium Launchpad also list tokens against
+/// USDC and USDT. Stablecoin amounts have 6 decimals and are worth a dollar, so the parser
+/// converts them to SOL at the current SOL/USD price: thresholds, PnL and the trade ledger stay
+/// in SOL whatever the pool, while alerts name the asset actually traded and buys in such pools
+/// spend the stablecoin instead of wrapping SOL.
+///
+/// Stable-quoted pools are parsed unless `STABLE_QUOTES_ENABLED=false`, which drops their trades
+/// instead. `STABLE_BUY_AMOUNT` sets a fixed stablecoin amount per buy; by default a buy spends
+/// the SOL buy amount's worth. The SOL/USD price is fetched at startup and refreshed every
+/// `SOL_USD_REFRESH_SECS` seconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum QuoteAsset {
+    #[default]
+    Sol,
+    Usdc,
+    Usdt,
+}
+
+pub const USDC_MINT: Pubkey = anchor_client::solana_sdk::pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+pub const USDT_MINT: Pubkey = anchor_client::solana_sdk::pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
+pub const WSOL_MINT: Pubkey = anchor_client::solana_sdk::pubkey!("So11111111111111111111111111111111111111112");
+
+/// Used until the first price fetch succeeds, like the startup fallback
+const DEFAULT_SOL_USD: f64 = 200.0;
+
+lazy_static! {
+    static ref SOL_USD_BITS: AtomicU64 = AtomicU64::new(DEFAULT_SOL_USD.to_bits());
+    /// Tokens last seen trading against a stablecoin; everything else is SOL-quoted
+    static ref STABLE_QUOTED_MINTS: DashMap<String, QuoteAsset> = DashMap::new();
+    static ref STABLE_QUOTES_ENABLED: bool = std::env::var("STABLE_QUOTES_ENABLED")
+        .ok()
+        .and_then(|v| v.to_lowercase().parse().ok())
+        .unwrap_or(true);
+}
+
+impl QuoteAsset {
+    pub fn from_mint(mint: &str) -> Option<Self> {
+        match mint {
+            "So11111111111111111111111111111111111111112" => Some(QuoteAsset::Sol),
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v" => Some(QuoteAsset::Usdc),
+            "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB" => Some(QuoteAsset::Usdt),
+            _ => None,
+        }
+    }
+
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            QuoteAsset::Sol => WSOL_MINT,
+            QuoteAsset::Usdc => USDC_MINT,
+            QuoteAsset::Usdt => USDT_MINT,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            QuoteAsset::Sol => "SOL",
+            QuoteAsset::Usdc => "USDC",
+            QuoteAsset::Usdt => "USDT",
+        }
+    }
+
+    pub fn decimals(&self) -> u8 {
+        match self {
+            QuoteAsset::Sol => 9,
+            QuoteAsset::Usdc | QuoteAsset::Usdt => 6,
+        }
+    }
+
+    pub fn is_stable(&self) -> bool {
+        *self != QuoteAsset::Sol
+    }
+
+    /// Raw quote amount as lamports worth the same
+    pub fn raw_to_lamports(&self, raw: u64) -> u64 {
+        match self {
+            QuoteAsset::Sol => raw,
+            _ => (self.to_sol(raw as f64 / 10f64.powi(self.decimals() as i32)) * 1_000_000_000.0) as u64,
+        }
+    }
+
+    /// Quote units (UI amount) to SOL
+    pub fn to_sol(&self, amount: f64) -> f64 {
+        match self {
+            QuoteAsset::Sol => amount,
+            _ => amount / sol_usd_price(),
+        }
+    }
+
+    /// SOL to quote units (UI amount)
+    pub fn from_sol(&self, sol: f64) -> f64 {
+        match self {
+            QuoteAsset::Sol => sol,
+            _ => sol * sol_usd_price(),
+        }
+    }
+
+    /// Quote units (UI amount) to the raw on-chain amount
+    pub fn to_raw(&self, amount: f64) -> u64 {
+        (amount * 10f64.powi(self.decimals() as i32)) as u64
+    }
+
+    /// `sol` (a SOL-equivalent amount) labeled in this asset, e.g. `1.5 SOL` or `300.00 USDC (≈1.5 SOL)`
+    pub fn format_sol_amount(&self, sol: f64) -> String {
+        match self {
+            QuoteAsset::Sol => format!("{} SOL", sol),
+            _ => format!("{:.2} {} (≈{:.4} SOL)", self.from_sol(sol), self.symbol(), sol),
+        }
+    }
+}
+
+impl fmt::Display for QuoteAsset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+pub fn sol_usd_price() -> f64 {
+    f64::from_bits(SOL_USD_BITS.load(Ordering::Relaxed))
+}
+
+pub fn set_sol_usd_price(price: f64) {
+    if price.is_finite() && price > 0.0 {
+        SOL_USD_BITS.store(price.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Whether trades in stablecoin-quoted pools are handled
+pub fn stable_quotes_enabled() -> bool {
+    *STABLE_QUOTES_ENABLED
+}
+
+/// Stablecoin amount to spend per buy in a stable-quoted pool, given the SOL buy amount
+pub fn stable_buy_amount(sol_amount: f64, quote: QuoteAsset) -> f64 {
+    std::env::var("STABLE_BUY_AMOUNT")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|amount| *amount > 0.0)
+        .unwrap_or_else(|| quote.from_sol(sol_amount))
+}
+
+/// Remember that a token trades against a stablecoin, for code paths that only know the mint
+pub fn remember(mint: &str, quote: QuoteAsset) {
+    if quote.is_stable() {
+        STABLE_QUOTED_MINTS.insert(mint.to_string(), quote);
+    }
+}
+
+/// Quote asset `mint` was last seen trading against, SOL when unknown
+pub fn for_mint(mint: &str) -> QuoteAsset {
+    STABLE_QUOTED_MINTS.get(mint).map(|q| *q).unwrap_or_default()
+}
+
+/// Keep the SOL/USD price current for stablecoin conversions
+pub fn start_sol_price_refresh(cancel_token: tokio_util::sync::CancellationToken) -> tokio::task::JoinHandle<()> {
+    let secs = std::env::var("SOL_USD_REFRESH_SECS").ok().and_then(|v| v.parse().ok()).unwrap_or(300u64);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(secs.max(30)));
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = ticker.tick() => {
+                    match crate::common::config::create_coingecko_proxy().await {
+                        Ok(price) => {
+                            set_sol_usd_price(price);
+                            crate::processor::market_regime::MarketRegime::shared().record_sol_price(price);
+                        }
+                        Err(e) => eprintln!("Failed to refresh the SOL/USD price: {}", e),
+                    }
+                }
+            }
+        }
+    })
+}
@@ -38,6 +38,33 @@ impl TokenTimeseries {
         while self.samples.len() > self.capacity { self.samples.pop_front(); }
     }
 
+    /// Add samples from before the first recorded slot, e.g. backfilled from chain. Samples
+    /// of the same slot are aggregated; the newest samples are kept within capacity.
+    pub fn backfill(&mut self, mut samples: Vec<SlotSample>) {
+        let first_slot = self.samples.front().map(|s| s.slot).unwrap_or(u64::MAX);
+        samples.retain(|s| s.slot < first_slot);
+        samples.sort_by_key(|s| s.slot);
+        let mut older: VecDeque<SlotSample> = VecDeque::with_capacity(samples.len());
+        for sample in samples {
+            match older.back_mut() {
+                Some(back) if back.slot == sample.slot => {
+                    back.price = sample.price;
+                    back.buy_volume += sample.buy_volume;
+                    back.sell_volume += sample.sell_volume;
+                }
+                _ => older.push_back(sample),
+            }
+        }
+        while let Some(sample) = older.pop_back() {
+            if self.samples.len() >= self.capacity { break; }
+            self.samples.push_front(sample);
+        }
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &SlotSample> {
+        self.samples.iter()
+    }
+
     pub fn lowest_price(&self) -> Option<f64> {
         self.samples.iter().map(|s| s.price).fold(None, |acc, p| match acc {
             None => Some(p),
@@ -120,6 +147,11 @@ pub fn update_for_mint(mint: &str, slot: u64, price: f64, is_buy: bool, sol_volu
     entry.update(slot, price, is_buy, sol_volume);
 }
 
+pub fn backfill_for_mint(mint: &str, samples: Vec<SlotSample>) {
+    let mut entry = TOKEN_TIMESERIES.entry(mint.to_string()).or_insert_with(|| TokenTimeseries::new(20));
+    entry.backfill(samples);
+}
+
 pub fn analyze_bottom(mint: &str, min_drop_pct: f64, sell_decline_pct: f64, stabilize_slots: usize) -> BottomSignal {
     if let Some(ts) = TOKEN_TIMESERIES.get(mint) {
         ts.detect_bottom_after_drop(min_drop_pct, sell_decline_pct, stabilize_slots)
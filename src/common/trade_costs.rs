@@ -0,0 +1,172 @@
+use chrono::{NaiveDate, Utc};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::common::cluster::DexProgram;
+
+/// Base fee charged per signature
+pub const BASE_FEE_LAMPORTS: u64 = 5_000;
+/// Rent-exempt minimum for a 165 byte SPL token account
+pub const TOKEN_ACCOUNT_RENT_LAMPORTS: u64 = 2_039_280;
+
+/// Sent transactions not confirmed within this long are dropped from the pending costs
+const PENDING_TTL_SECS: i64 = 300;
+
+/// Infrastructure cost of a single submitted transaction, in lamports
+#[derive(Clone, Debug, Default)]
+pub struct TransactionCost {
+    pub base_fee: u64,
+    pub priority_fee: u64,
+    pub tip: u64,
+    pub ata_rent: u64,
+    pub rent_refund: u64,
+}
+
+impl TransactionCost {
+    /// Fees known when a transaction is sent; its rent is settled once it is confirmed
+    pub fn sent(unit_limit: u32, unit_price: u64, tip: u64) -> Self {
+        Self {
+            base_fee: BASE_FEE_LAMPORTS,
+            // unit price is in micro-lamports per compute unit
+            priority_fee: (unit_limit as u64).saturating_mul(unit_price) / 1_000_000,
+            tip,
+            ..Default::default()
+        }
+    }
+
+    /// Rent of the token accounts the confirmed transaction opened and refunds of those it
+    /// closed. An idempotent create of an existing account opens nothing and costs no rent.
+    pub fn settle(&mut self, accounts_opened: u32, accounts_closed: u32) {
+        self.ata_rent = accounts_opened as u64 * TOKEN_ACCOUNT_RENT_LAMPORTS;
+        self.rent_refund = accounts_closed as u64 * TOKEN_ACCOUNT_RENT_LAMPORTS;
+    }
+
+    /// Net lamports spent (rent refunds from closed accounts are credited back)
+    pub fn net_lamports(&self) -> i64 {
+        (self.base_fee + self.priority_fee + self.tip + self.ata_rent) as i64 - self.rent_refund as i64
+    }
+
+    fn add(&mut self, other: &TransactionCost) {
+        self.base_fee += other.base_fee;
+        self.priority_fee += other.priority_fee;
+        self.tip += other.tip;
+        self.ata_rent += other.ata_rent;
+        self.rent_refund += other.rent_refund;
+    }
+}
+
+/// Accumulated costs for one position or one day
+#[derive(Clone, Debug, Default)]
+pub struct CostSummary {
+    pub transactions: u64,
+    pub total: TransactionCost,
+}
+
+impl CostSummary {
+    pub fn net_sol(&self) -> f64 {
+        self.total.net_lamports() as f64 / 1_000_000_000.0
+    }
+}
+
+/// A sent transaction (or bundle) whose cost is recorded once it confirms
+#[derive(Clone)]
+struct PendingCost {
+    /// Every signature of the bundle, so confirming one settles the whole cost once
+    signatures: Vec<String>,
+    mint: Option<Pubkey>,
+    cost: TransactionCost,
+    sent_at: i64,
+}
+
+lazy_static! {
+    static ref POSITION_COSTS: DashMap<String, CostSummary> = DashMap::new();
+    static ref DAILY_COSTS: DashMap<NaiveDate, CostSummary> = DashMap::new();
+    static ref PENDING_COSTS: DashMap<String, PendingCost> = DashMap::new();
+}
+
+/// Find the token mint a swap transaction trades, from the DEX swap instruction's account list
+pub fn swap_mint(instructions: &[Instruction]) -> Option<Pubkey> {
+    instructions.iter().find_map(|ix| {
+        let mint_index = if ix.program_id == DexProgram::PumpFun.id() {
+            2
+        } else if ix.program_id == DexProgram::PumpSwap.id() {
+            3
+        } else if ix.program_id == DexProgram::RaydiumLaunchpad.id() {
+            9
+        } else {
+            return None;
+        };
+        ix.accounts.get(mint_index).map(|meta| meta.pubkey)
+    })
+}
+
+/// Hold the cost of a sent transaction or bundle until `record_confirmed` settles it; a send
+/// that never confirms costs nothing and is dropped after a few minutes
+pub fn record_sent(signatures: &[String], instructions: &[Instruction], cost: TransactionCost) {
+    let now = Utc::now().timestamp();
+    PENDING_COSTS.retain(|_, pending| now - pending.sent_at < PENDING_TTL_SECS);
+    let pending = PendingCost { signatures: signatures.to_vec(), mint: swap_mint(instructions), cost, sent_at: now };
+    for signature in signatures {
+        PENDING_COSTS.insert(signature.clone(), pending.clone());
+    }
+}
+
+/// Record the cost of a confirmed transaction against its position and today's totals, with the
+/// rent of the token accounts it actually opened and closed. Returns the cost, None when
+/// `signature` was not sent through `record_sent` or was already recorded.
+pub fn record_confirmed(signature: &str, accounts_opened: u32, accounts_closed: u32) -> Option<TransactionCost> {
+    let (_, pending) = PENDING_COSTS.remove(signature)?;
+    for other in &pending.signatures {
+        PENDING_COSTS.remove(other);
+    }
+    let mut cost = pending.cost;
+    cost.settle(accounts_opened, accounts_closed);
+
+    if let Some(mint) = pending.mint {
+        let mut position = POSITION_COSTS.entry(mint.to_string()).or_default();
+        position.transactions += 1;
+        position.total.add(&cost);
+    }
+
+    let mut day = DAILY_COSTS.entry(Utc::now().date_naive()).or_default();
+    day.transactions += 1;
+    day.total.add(&cost);
+    Some(cost)
+}
+
+/// Costs paid so far for a position
+pub fn position_costs(mint: &str) -> CostSummary {
+    POSITION_COSTS.get(mint).map(|c| c.clone()).unwrap_or_default()
+}
+
+/// Drop the cost record of a closed position, returning its final totals
+pub fn close_position(mint: &str) -> Option<CostSummary> {
+    POSITION_COSTS.remove(mint).map(|(_, costs)| costs)
+}
+
+/// Costs paid on a given (UTC) day
+pub fn daily_costs(date: NaiveDate) -> CostSummary {
+    DAILY_COSTS.get(&date).map(|c| c.clone()).unwrap_or_default()
+}
+
+/// Human-readable breakdown of today's infrastructure costs for reports
+pub fn format_daily_costs() -> String {
+    let today = daily_costs(Utc::now().date_naive());
+    let to_sol = |lamports: u64| lamports as f64 / 1_000_000_000.0;
+    format!(
+        "Transactions: {}\n\
+        Base fees: {:.6} SOL\n\
+        Priority fees: {:.6} SOL\n\
+        Tips: {:.6} SOL\n\
+        ATA rent: {:.6} SOL (refunded {:.6} SOL)\n\
+        Net infrastructure cost: {:.6} SOL",
+        today.transactions,
+        to_sol(today.total.base_fee),
+        to_sol(today.total.priority_fee),
+        to_sol(today.total.tip),
+        to_sol(today.total.ata_rent),
+        to_sol(today.total.rent_refund),
+        today.net_sol(),
+    )
+}
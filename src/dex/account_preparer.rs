@@ -0,0 +1,146 @@
+use std::sync::Arc;
+use anyhow::Result;
+use colored::Colorize;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    system_instruction,
+};
+use spl_associated_token_account::{
+    get_associated_token_address,
+    instruction::create_associated_token_account_idempotent,
+};
+
+use crate::common::{logger::Logger, cache::WALLET_TOKEN_ACCOUNTS};
+
+/// Pre-trade account preparation shared by the swap builders.
+///
+/// Collects the setup instructions (ATA creation, SOL -> WSOL wrapping) that must run before the
+/// swap and the cleanup instructions (closing emptied ATAs, unwrapping WSOL) that run after it, so
+/// every swap transaction is self-contained and recovers its rent.
+///
+/// Behaviour is controlled by `AUTO_WRAP_SOL`, `AUTO_UNWRAP_WSOL` and `CLOSE_EMPTY_ATA`
+/// (all default to `true`).
+pub struct AccountPreparer {
+    owner: Pubkey,
+    rpc_nonblocking_client: Option<Arc<solana_client::nonblocking::rpc_client::RpcClient>>,
+    auto_wrap_sol: bool,
+    auto_unwrap_wsol: bool,
+    close_empty_ata: bool,
+    pre_instructions: Vec<Instruction>,
+    post_instructions: Vec<Instruction>,
+    logger: Logger,
+}
+
+fn env_flag(name: &str, default: bool) -> bool {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.to_lowercase().parse::<bool>().ok())
+        .unwrap_or(default)
+}
+
+impl AccountPreparer {
+    pub fn new(
+        owner: Pubkey,
+        rpc_nonblocking_client: Option<Arc<solana_client::nonblocking::rpc_client::RpcClient>>,
+    ) -> Self {
+        Self {
+            owner,
+            rpc_nonblocking_client,
+            auto_wrap_sol: env_flag("AUTO_WRAP_SOL", true),
+            auto_unwrap_wsol: env_flag("AUTO_UNWRAP_WSOL", true),
+            close_empty_ata: env_flag("CLOSE_EMPTY_ATA", true),
+            pre_instructions: Vec::new(),
+            post_instructions: Vec::new(),
+            logger: Logger::new("[ACCOUNT-PREP] => ".yellow().to_string()),
+        }
+    }
+
+    /// Make sure the owner's ATA for `mint` exists, adding an idempotent create if it is not
+    /// known to exist. Returns the ATA address.
+    pub fn ensure_ata(&mut self, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+        let ata = get_associated_token_address(&self.owner, mint);
+        if !WALLET_TOKEN_ACCOUNTS.contains(&ata) {
+            self.logger.log(format!("Creating ATA for mint {} at address {}", mint, ata));
+            self.pre_instructions.push(create_associated_token_account_idempotent(
+                &self.owner,
+                &self.owner,
+                mint,
+                token_program,
+            ));
+            WALLET_TOKEN_ACCOUNTS.insert(ata);
+        }
+        ata
+    }
+
+    /// Make sure the WSOL ATA holds at least `lamports`, wrapping only the shortfall.
+    /// Returns the WSOL ATA address.
+    pub async fn wrap_sol(&mut self, lamports: u64) -> Result<Pubkey> {
+        let wsol_ata = get_associated_token_address(&self.owner, &spl_token::native_mint::ID);
+        if !self.auto_wrap_sol {
+            return Ok(wsol_ata);
+        }
+
+        let current_balance = self.get_wsol_balance(&wsol_ata).await;
+        if current_balance.is_none() {
+            // Account does not exist on chain (or could not be fetched): create it idempotently
+            WALLET_TOKEN_ACCOUNTS.remove(&wsol_ata);
+        }
+        self.ensure_ata(&spl_token::native_mint::ID, &spl_token::ID);
+
+        let shortfall = lamports.saturating_sub(current_balance.unwrap_or(0));
+        if shortfall > 0 {
+            self.logger.log(format!("Wrapping {} lamports into WSOL account {}", shortfall, wsol_ata));
+            self.pre_instructions.push(system_instruction::transfer(&self.owner, &wsol_ata, shortfall));
+            self.pre_instructions.push(spl_token::instruction::sync_native(&spl_token::ID, &wsol_ata)?);
+        }
+        Ok(wsol_ata)
+    }
+
+    /// Close the WSOL ATA after the swap, returning wrapped SOL and rent to the owner
+    pub fn unwrap_wsol_after(&mut self) -> Result<()> {
+        if !self.auto_unwrap_wsol {
+            return Ok(());
+        }
+        let wsol_ata = get_associated_token_address(&self.owner, &spl_token::native_mint::ID);
+        self.post_instructions.push(spl_token::instruction::close_account(
+            &spl_token::ID,
+            &wsol_ata,
+            &self.owner,
+            &self.owner,
+            &[&self.owner],
+        )?);
+        WALLET_TOKEN_ACCOUNTS.remove(&wsol_ata);
+        Ok(())
+    }
+
+    /// Close a token account that the swap leaves empty to recover its rent
+    pub fn close_ata_after(&mut self, ata: &Pubkey, token_program: &Pubkey) -> Result<()> {
+        if !self.close_empty_ata {
+            return Ok(());
+        }
+        self.post_instructions.push(spl_token_2022::instruction::close_account(
+            token_program,
+            ata,
+            &self.owner,
+            &self.owner,
+            &[&self.owner],
+        )?);
+        WALLET_TOKEN_ACCOUNTS.remove(ata);
+        Ok(())
+    }
+
+    /// Wrap the swap instructions with the collected setup and cleanup instructions
+    pub fn finish(self, swap_instructions: Vec<Instruction>) -> Vec<Instruction> {
+        let mut instructions = self.pre_instructions;
+        instructions.extend(swap_instructions);
+        instructions.extend(self.post_instructions);
+        instructions
+    }
+
+    async fn get_wsol_balance(&self, wsol_ata: &Pubkey) -> Option<u64> {
+        let client = self.rpc_nonblocking_client.as_ref()?;
+        let balance = client.get_token_account_balance(wsol_ata).await.ok()?;
+        balance.amount.parse::<u64>().ok()
+    }
+}
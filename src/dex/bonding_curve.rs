@@ -0,0 +1,194 @@
+//! Exact Pump.fun bonding curve math over a snapshot of the curve's reserves.
+//!
+//! The curve is a constant product over its virtual reserves. Quotes follow the program's integer
+//! arithmetic: a buy of `t` tokens costs `t * vsol / (vtok - t) + 1` lamports, a sell of `t` tokens
+//! returns `t * vsol / (vtok + t)`, and the fee (`PUMP_FUN_FEE_BPS`, protocol and creator fee
+//! together) is charged on top of a buy and taken out of a sell, rounded up. Buys are capped at the
+//! real token reserves left on the curve. Each quote carries the curve as it would be after the
+//! trade, so trades can be chained to simulate a sequence of fills.
+//!
+//! `price()` is virtual SOL over virtual tokens (lamports per raw token unit), the price the
+//! transaction parser reports for Pump.fun trades.
+
+use crate::dex::pump_fun::{
+    bonding_curve_progress, BondingCurveAccount, INITIAL_REAL_TOKEN_RESERVES, INITIAL_VIRTUAL_SOL_RESERVES,
+    INITIAL_VIRTUAL_TOKEN_RESERVES,
+};
+
+/// Protocol (95 bps) and creator (5 bps) fee of a curve trade
+pub const PUMP_FUN_FEE_BPS: u64 = 100;
+
+/// Reserves of a bonding curve at one point in time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurveSnapshot {
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub complete: bool,
+}
+
+/// A quoted trade
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CurveQuote {
+    /// Raw tokens bought or sold
+    pub tokens: u64,
+    /// Lamports paid for a buy or received from a sell, fee included
+    pub sol: u64,
+    /// Lamports of fee
+    pub fee: u64,
+    pub price_before: f64,
+    /// The curve once the trade executed
+    pub after: CurveSnapshot,
+}
+
+impl CurveQuote {
+    /// Move of the price caused by the trade (%)
+    pub fn impact_pct(&self) -> f64 {
+        if self.price_before <= 0.0 {
+            return 0.0;
+        }
+        ((self.after.price() - self.price_before) / self.price_before * 100.0).abs()
+    }
+
+    /// Lamports per raw token actually paid or received
+    pub fn average_price(&self) -> f64 {
+        if self.tokens == 0 {
+            return 0.0;
+        }
+        self.sol as f64 / self.tokens as f64
+    }
+}
+
+fn fee_of(lamports: u64, fee_bps: u64) -> u64 {
+    (lamports as u128 * fee_bps as u128).div_ceil(10_000) as u64
+}
+
+impl CurveSnapshot {
+    /// A freshly created curve
+    pub fn initial() -> Self {
+        Self::from_virtual(INITIAL_VIRTUAL_SOL_RESERVES, INITIAL_VIRTUAL_TOKEN_RESERVES)
+    }
+
+    /// The curve from its virtual reserves alone, as trade events report them; the real reserves
+    /// follow from the fixed initial offsets
+    pub fn from_virtual(virtual_sol_reserves: u64, virtual_token_reserves: u64) -> Self {
+        let token_offset = INITIAL_VIRTUAL_TOKEN_RESERVES - INITIAL_REAL_TOKEN_RESERVES;
+        let real_token_reserves = virtual_token_reserves.saturating_sub(token_offset);
+        Self {
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            real_sol_reserves: virtual_sol_reserves.saturating_sub(INITIAL_VIRTUAL_SOL_RESERVES),
+            real_token_reserves,
+            complete: real_token_reserves == 0,
+        }
+    }
+
+    pub fn from_account(account: &BondingCurveAccount) -> Self {
+        Self {
+            virtual_sol_reserves: account.virtual_sol_reserves,
+            virtual_token_reserves: account.virtual_token_reserves,
+            real_sol_reserves: account.real_sol_reserves,
+            real_token_reserves: account.real_token_reserves,
+            complete: account.complete,
+        }
+    }
+
+    /// Lamports per raw token unit at the margin
+    pub fn price(&self) -> f64 {
+        if self.virtual_token_reserves == 0 {
+            return 0.0;
+        }
+        self.virtual_sol_reserves as f64 / self.virtual_token_reserves as f64
+    }
+
+    /// `price()` scaled by 1e9 in integer math, as carried in parsed trade info
+    pub fn scaled_price(&self) -> u64 {
+        if self.virtual_token_reserves == 0 {
+            return 0;
+        }
+        (self.virtual_sol_reserves as u128 * 1_000_000_000 / self.virtual_token_reserves as u128) as u64
+    }
+
+    /// Curve completion (0-100%)
+    pub fn progress(&self) -> f64 {
+        bonding_curve_progress(self.virtual_token_reserves)
+    }
+
+    /// Lamports before fee the program charges for exactly `tokens`; None past the real reserves
+    pub fn buy_cost_before_fee(&self, tokens: u64) -> Option<u64> {
+        if tokens == 0 || tokens > self.real_token_reserves || tokens >= self.virtual_token_reserves {
+            return None;
+        }
+        let cost = tokens as u128 * self.virtual_sol_reserves as u128 / (self.virtual_token_reserves - tokens) as u128 + 1;
+        u64::try_from(cost).ok()
+    }
+
+    /// Buy exactly `tokens`
+    pub fn buy_exact_tokens(&self, tokens: u64, fee_bps: u64) -> Option<CurveQuote> {
+        if self.complete {
+            return None;
+        }
+        let cost = self.buy_cost_before_fee(tokens)?;
+        let fee = fee_of(cost, fee_bps);
+        Some(CurveQuote {
+            tokens,
+            sol: cost + fee,
+            fee,
+            price_before: self.price(),
+            after: Self {
+                virtual_sol_reserves: self.virtual_sol_reserves + cost,
+                virtual_token_reserves: self.virtual_token_reserves - tokens,
+                real_sol_reserves: self.real_sol_reserves + cost,
+                real_token_reserves: self.real_token_reserves - tokens,
+                complete: tokens == self.real_token_reserves,
+            },
+        })
+    }
+
+    /// Most tokens `lamports` buys, fee included; the quote never costs more than `lamports`
+    pub fn buy_with_sol(&self, lamports: u64, fee_bps: u64) -> Option<CurveQuote> {
+        if self.complete || lamports == 0 || self.virtual_token_reserves == 0 {
+            return None;
+        }
+        // Largest cost before fee the budget covers
+        let mut budget = (lamports as u128 * 10_000 / (10_000 + fee_bps as u128)) as u64;
+        while budget > 0 && budget + fee_of(budget, fee_bps) > lamports {
+            budget -= 1;
+        }
+        if budget == 0 {
+            return None;
+        }
+        // cost(t) = floor(t * vsol / (vtok - t)) + 1 <= budget  <=>  t < budget * vtok / (vsol + budget)
+        let budget = budget as u128;
+        let tokens = (budget * self.virtual_token_reserves as u128).div_ceil(self.virtual_sol_reserves as u128 + budget) - 1;
+        let tokens = (tokens as u64).min(self.real_token_reserves);
+        if tokens == 0 {
+            return None;
+        }
+        self.buy_exact_tokens(tokens, fee_bps)
+    }
+
+    /// Sell `tokens`; the fee is taken out of the proceeds
+    pub fn sell(&self, tokens: u64, fee_bps: u64) -> Option<CurveQuote> {
+        if self.complete || tokens == 0 {
+            return None;
+        }
+        let gross = (tokens as u128 * self.virtual_sol_reserves as u128 / (self.virtual_token_reserves as u128 + tokens as u128)) as u64;
+        let gross = gross.min(self.real_sol_reserves);
+        let fee = fee_of(gross, fee_bps).min(gross);
+        Some(CurveQuote {
+            tokens,
+            sol: gross - fee,
+            fee,
+            price_before: self.price(),
+            after: Self {
+                virtual_sol_reserves: self.virtual_sol_reserves - gross,
+                virtual_token_reserves: self.virtual_token_reserves + tokens,
+                real_sol_reserves: self.real_sol_reserves - gross,
+                real_token_reserves: self.real_token_reserves + tokens,
+                complete: false,
+            },
+        })
+    }
+}
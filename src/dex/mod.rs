@@ -1,3 +1,6 @@
 pub mod pump_fun;
+pub mod bonding_curve;
 pub mod pump_swap;
 pub mod raydium_launchpad;
+#[cfg(feature = "trading")]
+pub mod account_preparer;
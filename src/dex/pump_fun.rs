@@ -1,37 +1,46 @@
 use std::{str::FromStr, sync::Arc};
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use borsh::from_slice;
-use tokio::time::Instant;
 use borsh_derive::{BorshDeserialize, BorshSerialize};
-use colored::Colorize;
 use serde::{Deserialize, Serialize};
-use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
-    pubkey::Pubkey,
-    signature::Keypair,
-    signer::Signer,
-    system_program,
-};
-use spl_associated_token_account::{
-    get_associated_token_address, instruction::create_associated_token_account,
-};
-use spl_token::{ui_amount_to_amount};
-use tokio::sync::OnceCell;
-use lru::LruCache;
-use std::num::NonZeroUsize;
-
-use crate::{
-    common::{config::SwapConfig, logger::Logger, cache::WALLET_TOKEN_ACCOUNTS},
-    block_engine::token,
-    processor::{monitor::BondingCurveInfo, swap::{SwapDirection, SwapInType}},
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::processor::monitor::BondingCurveInfo;
+
+// Swap building signs with the wallet, so it only exists in trading builds
+#[cfg(feature = "trading")]
+use {
+    anyhow::anyhow,
+    colored::Colorize,
+    lru::LruCache,
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        signature::Keypair,
+        signer::Signer,
+        system_program,
+    },
+    spl_token::ui_amount_to_amount,
+    std::num::NonZeroUsize,
+    tokio::{sync::OnceCell, time::Instant},
+    crate::{
+        common::{config::SwapConfig, logger::Logger, cache::WALLET_TOKEN_ACCOUNTS, cluster::DexProgram},
+        block_engine::token,
+        processor::swap::{SwapDirection, SwapInType},
+        dex::account_preparer::AccountPreparer,
+        dex::bonding_curve::{CurveSnapshot, PUMP_FUN_FEE_BPS},
+    },
 };
 
 // Constants for cache
+#[cfg(feature = "trading")]
 const CACHE_SIZE: usize = 1000;
 
 // Thread-safe cache with LRU eviction policy
+#[cfg(feature = "trading")]
 static TOKEN_ACCOUNT_CACHE: OnceCell<LruCache<Pubkey, bool>> = OnceCell::const_new();
 
+#[cfg(feature = "trading")]
 async fn init_caches() {
     TOKEN_ACCOUNT_CACHE.get_or_init(|| async {
         LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())
@@ -50,17 +59,42 @@ pub const PUMP_EVENT_AUTHORITY: &str = "Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxX
 pub const PUMP_BUY_METHOD: u64 = 16927863322537952870;
 pub const PUMP_SELL_METHOD: u64 = 12502976635542562355;
 pub const PUMP_FUN_CREATE_IX_DISCRIMINATOR: &[u8] = &[24, 30, 200, 40, 5, 28, 7, 119];
+pub const PUMP_FUN_MIGRATE_IX_DISCRIMINATOR: &[u8] = &[155, 234, 231, 146, 236, 158, 162, 30];
+pub const POOL_AUTHORITY_SEED: &[u8] = b"pool-authority";
 pub const INITIAL_VIRTUAL_SOL_RESERVES: u64 = 30_000_000_000;
 pub const INITIAL_VIRTUAL_TOKEN_RESERVES: u64 = 1_073_000_000_000_000;
+pub const INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
 pub const TOKEN_TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
 
 // Volume accumulator seeds
 pub const GLOBAL_VOLUME_ACCUMULATOR_SEED: &[u8] = b"global_volume_accumulator";
 pub const USER_VOLUME_ACCUMULATOR_SEED: &[u8] = b"user_volume_accumulator";
 
+/// Bonding curve completion (0-100%) from the curve's virtual token reserves.
+/// The curve completes once all real (sellable) token reserves have been bought.
+pub fn bonding_curve_progress(virtual_token_reserves: u64) -> f64 {
+    let reserve_offset = INITIAL_VIRTUAL_TOKEN_RESERVES - INITIAL_REAL_TOKEN_RESERVES;
+    let real_token_reserves = virtual_token_reserves.saturating_sub(reserve_offset);
+    let progress = 100.0 - (real_token_reserves as f64 * 100.0 / INITIAL_REAL_TOKEN_RESERVES as f64);
+    progress.clamp(0.0, 100.0)
+}
+
+/// PumpSwap pool reserves (token base, SOL quote) expected right after a completed curve migrates:
+/// the tokens held back from the curve and the SOL it raised
+pub fn expected_migration_reserves() -> (u64, u64) {
+    let final_virtual_tokens = INITIAL_VIRTUAL_TOKEN_RESERVES - INITIAL_REAL_TOKEN_RESERVES;
+    let invariant = INITIAL_VIRTUAL_SOL_RESERVES as u128 * INITIAL_VIRTUAL_TOKEN_RESERVES as u128;
+    let final_virtual_sol = (invariant / final_virtual_tokens as u128) as u64;
+    (
+        TOKEN_TOTAL_SUPPLY - INITIAL_REAL_TOKEN_RESERVES,
+        final_virtual_sol - INITIAL_VIRTUAL_SOL_RESERVES,
+    )
+}
+
 // Minimum SOL output for selling to ensure transactions always build
 pub const MIN_SOL_OUTPUT_SELLING: u64 = 10;
 
+#[cfg(feature = "trading")]
 #[derive(Clone)]
 pub struct Pump {
     pub rpc_nonblocking_client: Arc<solana_client::nonblocking::rpc_client::RpcClient>,
@@ -68,6 +102,7 @@ pub struct Pump {
     pub rpc_client: Option<Arc<solana_client::rpc_client::RpcClient>>,
 }
 
+#[cfg(feature = "trading")]
 impl Pump {
     pub fn new(
         rpc_nonblocking_client: Arc<solana_client::nonblocking::rpc_client::RpcClient>,
@@ -94,84 +129,15 @@ impl Pump {
         }
     }
 
-    async fn cache_token_account(&self, account: Pubkey) {
-        WALLET_TOKEN_ACCOUNTS.insert(account);
-    }
-
     // Removed get_token_price method as it requires RPC calls
 
-    /// Calculate token amount out for buy using virtual reserves
-    pub fn calculate_buy_token_amount(
-        sol_amount_in: u64,
-        virtual_sol_reserves: u64,
-        virtual_token_reserves: u64,
-    ) -> u64 {
-        if sol_amount_in == 0 || virtual_sol_reserves == 0 || virtual_token_reserves == 0 {
-            return 0;
-        }
-        
-        // PumpFun bonding curve formula for buy:
-        // tokens_out = (sol_in * virtual_token_reserves) / (virtual_sol_reserves + sol_in)
-        let sol_amount_in_u128 = sol_amount_in as u128;
-        let virtual_sol_reserves_u128 = virtual_sol_reserves as u128;
-        let virtual_token_reserves_u128 = virtual_token_reserves as u128;
-        
-        let numerator = sol_amount_in_u128.saturating_mul(virtual_token_reserves_u128);
-        let denominator = virtual_sol_reserves_u128.saturating_add(sol_amount_in_u128);
-        
-        if denominator == 0 {
-            return 0;
-        }
-        
-        numerator.checked_div(denominator).unwrap_or(0) as u64
-    }
-
-    /// Calculate SOL amount out for sell using virtual reserves
-    pub fn calculate_sell_sol_amount(
-        token_amount_in: u64,
-        virtual_sol_reserves: u64,
-        virtual_token_reserves: u64,
-    ) -> u64 {
-        if token_amount_in == 0 || virtual_sol_reserves == 0 || virtual_token_reserves == 0 {
-            return 0;
-        }
-        
-        // PumpFun bonding curve formula for sell:
-        // sol_out = (token_in * virtual_sol_reserves) / (virtual_token_reserves + token_in)
-        let token_amount_in_u128 = token_amount_in as u128;
-        let virtual_sol_reserves_u128 = virtual_sol_reserves as u128;
-        let virtual_token_reserves_u128 = virtual_token_reserves as u128;
-        
-        let numerator = token_amount_in_u128.saturating_mul(virtual_sol_reserves_u128);
-        let denominator = virtual_token_reserves_u128.saturating_add(token_amount_in_u128);
-        
-        if denominator == 0 {
-            return 0;
-        }
-        
-        numerator.checked_div(denominator).unwrap_or(0) as u64
-    }
-
-    /// Calculate price using virtual reserves with consistent scaling
-    pub fn calculate_price_from_virtual_reserves(
-        virtual_sol_reserves: u64,
-        virtual_token_reserves: u64,
-    ) -> f64 {
-        if virtual_token_reserves == 0 {
-            return 0.0;
-        }
-        
-        // Price = (virtual_sol_reserves * 1_000_000_000) / virtual_token_reserves  
-        // This matches the scaling used in transaction_parser.rs for consistency
-        ((virtual_sol_reserves as f64) * 1_000_000_000.0) / (virtual_token_reserves as f64)
-    }
-
     // Updated build_swap_from_parsed_data method - now only uses TradeInfoFromToken data
     pub async fn build_swap_from_parsed_data(
         &self,
         trade_info: &crate::processor::transaction_parser::TradeInfoFromToken,
         swap_config: SwapConfig,
     ) -> Result<(Arc<Keypair>, Vec<Instruction>, f64)> {
+        crate::library::trading_lockdown::ensure_trading_enabled()?;
         let started_time = Instant::now();
         let _logger = Logger::new("[PUMPFUN-SWAP-FROM-PARSED] => ".blue().to_string());
         _logger.log(format!("Building PumpFun swap from parsed transaction data"));
@@ -187,7 +153,7 @@ impl Pump {
         let owner = self.keypair.pubkey();
         let token_program_id = Pubkey::from_str(TOKEN_PROGRAM)?;
         let native_mint = spl_token::native_mint::ID;
-        let pump_program = Pubkey::from_str(PUMP_FUN_PROGRAM)?;
+        let pump_program = DexProgram::PumpFun.id();
 
         // Use trade_info data directly - no RPC calls for buying, but need RPC for selling to get actual balance
         _logger.log("Using trade_info data with real balance for selling".to_string());
@@ -206,20 +172,17 @@ impl Pump {
             SwapDirection::Sell => (Pubkey::from_str(mint_str)?, native_mint, PUMP_SELL_METHOD),
         };
         
-        // Calculate price using virtual reserves from trade_info
-        let price_in_sol = Self::calculate_price_from_virtual_reserves(
-            trade_info.virtual_sol_reserves,
-            trade_info.virtual_token_reserves,
-        );
-        _logger.log(format!("Calculated price from virtual reserves: {} (scaled) -> {} SOL (Virtual SOL: {}, Virtual Tokens: {})", 
-            price_in_sol, price_in_sol / 1_000_000_000.0, trade_info.virtual_sol_reserves, trade_info.virtual_token_reserves));
+        // Quote against the curve as the trade left it
+        let curve = CurveSnapshot::from_virtual(trade_info.virtual_sol_reserves, trade_info.virtual_token_reserves);
+        let price_in_sol = curve.price();
+        _logger.log(format!("Price from virtual reserves: {} (Virtual SOL: {}, Virtual Tokens: {})",
+            price_in_sol, trade_info.virtual_sol_reserves, trade_info.virtual_token_reserves));
         
         // Use slippage directly as basis points (already u64)
         let slippage_bps = swap_config.slippage;
         
-        // Create instructions as needed
-        let mut create_instruction = None;
-        let mut close_instruction = None;
+        // Setup/cleanup instructions around the swap (pump.fun trades native SOL, no wrapping)
+        let mut preparer = AccountPreparer::new(owner, Some(self.rpc_nonblocking_client.clone()));
         
         // Handle token accounts based on direction (buy or sell)
         let in_ata = get_associated_token_address(&owner, &token_in);
@@ -229,18 +192,7 @@ impl Pump {
         if swap_config.swap_direction == SwapDirection::Buy {
             // Check if token account exists and create if needed
             if !self.check_token_account_cache(out_ata).await {
-                let logger = Logger::new("[PUMPFUN-ATA-CREATE] => ".yellow().to_string());
-                logger.log(format!("Creating ATA for mint {} at address {}", token_out, out_ata));
-                
-                create_instruction = Some(create_associated_token_account(
-                    &owner,
-                    &owner,
-                    &token_out,
-                    &token_program_id,
-                ));
-                // Cache the new account
-                self.cache_token_account(out_ata).await;
-                logger.log(format!("ATA creation instruction added for {}", out_ata));
+                preparer.ensure_ata(&token_out, &token_program_id);
             }
         } else {
             // For sell, check if we have tokens to sell using cache first
@@ -253,13 +205,7 @@ impl Pump {
             // For sell transactions, determine if it's a full sell
             if swap_config.in_type == SwapInType::Pct && swap_config.amount_in >= 1.0 {
                 // Close ATA for full sells
-                close_instruction = Some(spl_token::instruction::close_account(
-                    &token_program_id,
-                    &in_ata,
-                    &owner,
-                    &owner,
-                    &[&owner],
-                )?);
+                preparer.close_ata_after(&in_ata, &token_program_id)?;
             }
         }
         
@@ -278,12 +224,8 @@ impl Pump {
                 let amount_specified = ui_amount_to_amount(swap_config.amount_in, spl_token::native_mint::DECIMALS);
                 let max_sol_cost = max_amount_with_slippage(amount_specified, 20000);
                 
-                // Use virtual reserves from trade_info for accurate calculation
-                let tokens_out = Self::calculate_buy_token_amount(
-                    amount_specified,
-                    trade_info.virtual_sol_reserves,
-                    trade_info.virtual_token_reserves,
-                );
+                // Tokens the amount buys once the fee is paid
+                let tokens_out = curve.buy_with_sol(amount_specified, PUMP_FUN_FEE_BPS).map(|q| q.tokens).unwrap_or(0);
                 
                 _logger.log(format!("Buy calculation - SOL in: {}, Tokens out: {}, Virtual SOL: {}, Virtual Tokens: {}", 
                     amount_specified, tokens_out, trade_info.virtual_sol_reserves, trade_info.virtual_token_reserves));
@@ -374,29 +316,18 @@ impl Pump {
             input_accounts,
         );
         
-        // Combine all instructions
-        let mut instructions = vec![];
-        if let Some(create_instruction) = create_instruction {
-            instructions.push(create_instruction);
-        }
-        if token_amount > 0 {
-            instructions.push(swap_instruction);
-        }
-        if let Some(close_instruction) = close_instruction {
-            instructions.push(close_instruction);
-        }
-        
-        // Validate we have instructions
-        if instructions.is_empty() {
+        // Validate we have a swap to perform
+        if token_amount == 0 {
             return Err(anyhow!("Instructions is empty, no txn required."));
         }
         
-        // Use price from trade_info directly - convert back to unscaled for consistency with external usage
-        let token_price = price_in_sol / 1_000_000_000.0;
+        // Combine all instructions
+        let instructions = preparer.finish(vec![swap_instruction]);
+        
         println!("time taken for build_swap_from_parsed_data: {:?}", started_time.elapsed());
         
-        // Return the keypair, instructions, and the token price (unscaled f64)
-        Ok((self.keypair.clone(), instructions, token_price))
+        // Return the keypair, instructions, and the token price
+        Ok((self.keypair.clone(), instructions, price_in_sol))
     }
 }
 
@@ -543,6 +474,7 @@ pub async fn get_bonding_curve_account(
     ))
 }
 
+#[cfg(feature = "trading")]
 fn max_amount_with_slippage(input_amount: u64, slippage_bps: u64) -> u64 {
     input_amount
         .checked_mul(slippage_bps.checked_add(TEN_THOUSAND).unwrap())
@@ -557,6 +489,13 @@ pub fn get_pda(mint: &Pubkey, program_id: &Pubkey ) -> Result<Pubkey> {
     Ok(bonding_curve)
 }
 
+/// Get the pool authority PDA that creates a token's PumpSwap pool on migration
+pub fn get_pool_authority_pda(mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let seeds = [POOL_AUTHORITY_SEED, mint.as_ref()];
+    let (pda, _bump) = Pubkey::find_program_address(&seeds, program_id);
+    pda
+}
+
 /// Get the global volume accumulator PDA
 pub fn get_global_volume_accumulator_pda(program_id: &Pubkey) -> Result<Pubkey> {
     let seeds = [GLOBAL_VOLUME_ACCUMULATOR_SEED];
@@ -1,34 +1,38 @@
-use std::{str::FromStr, sync::Arc};
-use solana_program_pack::Pack;
-use anchor_client::solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
-use anchor_client::solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
-use solana_account_decoder::UiAccountEncoding;
-use anyhow::{anyhow, Result};
-use colored::Colorize;
-use anchor_client::solana_sdk::{
-    instruction::{AccountMeta, Instruction},
-    pubkey::Pubkey,
-    signature::Keypair,
-    system_program,
-    signer::Signer,
-};
-use crate::processor::transaction_parser::DexType;
-use spl_associated_token_account::{
-    get_associated_token_address,
-    instruction::create_associated_token_account_idempotent
-};
-use spl_token::ui_amount_to_amount;
-use tokio::sync::OnceCell;
-use lru::LruCache;
-use std::num::NonZeroUsize;
-
-use crate::{
-    common::{config::SwapConfig, logger::Logger, cache::WALLET_TOKEN_ACCOUNTS},
-    block_engine::token,
-    processor::swap::{SwapDirection, SwapInType},
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use crate::common::cluster::DexProgram;
+
+// Swap building signs with the wallet, so it only exists in trading builds
+#[cfg(feature = "trading")]
+use {
+    std::{num::NonZeroUsize, str::FromStr, sync::Arc},
+    solana_program_pack::Pack,
+    anchor_client::solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    anchor_client::solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+    solana_account_decoder::UiAccountEncoding,
+    anyhow::{anyhow, Result},
+    colored::Colorize,
+    anchor_client::solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        signature::Keypair,
+        system_program,
+        signer::Signer,
+    },
+    spl_associated_token_account::get_associated_token_address,
+    spl_token::ui_amount_to_amount,
+    tokio::sync::OnceCell,
+    lru::LruCache,
+    crate::processor::transaction_parser::DexType,
+    crate::dex::account_preparer::AccountPreparer,
+    crate::processor::route_split::{SellVenue, SplitLeg},
+    crate::{
+        common::{config::SwapConfig, logger::Logger, cache::WALLET_TOKEN_ACCOUNTS, quote_asset},
+        block_engine::token,
+        processor::swap::{SwapDirection, SwapInType},
+    },
 };
 
 // Import the volume accumulator structures from pump_fun
+#[cfg(feature = "trading")]
 use crate::dex::pump_fun::{GlobalVolumeAccumulator, UserVolumeAccumulator};
 
 // PUMP SWAP FIXES:
@@ -50,41 +54,70 @@ pub const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
 pub const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
 
 // Volume accumulator seed constants
+#[cfg(feature = "trading")]
 const GLOBAL_VOLUME_ACCUMULATOR_SEED: &[u8] = b"global_volume_accumulator";
+#[cfg(feature = "trading")]
 const USER_VOLUME_ACCUMULATOR_SEED: &[u8] = b"user_volume_accumulator";
 
+#[cfg(feature = "trading")]
 /// Get the global volume accumulator PDA for PumpSwap
 fn get_global_volume_accumulator_pda() -> Result<Pubkey> {
     let seeds = [GLOBAL_VOLUME_ACCUMULATOR_SEED];
-    let (pda, _bump) = Pubkey::find_program_address(&seeds, &PUMP_SWAP_PROGRAM);
+    let (pda, _bump) = Pubkey::find_program_address(&seeds, &DexProgram::PumpSwap.id());
     Ok(pda)
 }
 
+#[cfg(feature = "trading")]
 /// Get the user volume accumulator PDA for a specific user for PumpSwap
 fn get_user_volume_accumulator_pda(user: &Pubkey) -> Result<Pubkey> {
     let seeds = [USER_VOLUME_ACCUMULATOR_SEED, user.as_ref()];
-    let (pda, _bump) = Pubkey::find_program_address(&seeds, &PUMP_SWAP_PROGRAM);
+    let (pda, _bump) = Pubkey::find_program_address(&seeds, &DexProgram::PumpSwap.id());
     Ok(pda)
 }
 
+/// Get the canonical PumpSwap pool a pump.fun token migrates into
+pub fn get_canonical_pool_pda(mint: &Pubkey) -> Pubkey {
+    let pump_program = DexProgram::PumpFun.id();
+    let pool_authority = crate::dex::pump_fun::get_pool_authority_pda(mint, &pump_program);
+    let index: u16 = 0;
+    let seeds = [
+        b"pool".as_ref(),
+        &index.to_le_bytes(),
+        pool_authority.as_ref(),
+        mint.as_ref(),
+        SOL_MINT.as_ref(),
+    ];
+    let (pda, _bump) = Pubkey::find_program_address(&seeds, &DexProgram::PumpSwap.id());
+    pda
+}
+
 // Thread-safe cache with LRU eviction policy
+#[cfg(feature = "trading")]
 static TOKEN_ACCOUNT_CACHE: OnceCell<LruCache<Pubkey, bool>> = OnceCell::const_new();
 
+#[cfg(feature = "trading")]
 const TEN_THOUSAND: u64 = 10000;
+#[cfg(feature = "trading")]
+/// LP plus protocol fee charged on every PumpSwap swap
+const POOL_FEE_BPS: u64 = 25;
+#[cfg(feature = "trading")]
 const CACHE_SIZE: usize = 1000;
 
+#[cfg(feature = "trading")]
 async fn init_caches() {
     TOKEN_ACCOUNT_CACHE.get_or_init(|| async {
         LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())
     }).await;
 }
 
+#[cfg(feature = "trading")]
 pub struct PumpSwap {
     pub keypair: Arc<Keypair>,
     pub rpc_client: Option<Arc<anchor_client::solana_client::rpc_client::RpcClient>>,
     pub rpc_nonblocking_client: Option<Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>>,
 }
 
+#[cfg(feature = "trading")]
 impl PumpSwap {
     pub fn new(
         keypair: Arc<Keypair>,
@@ -145,6 +178,7 @@ impl PumpSwap {
         trade_info: &crate::processor::transaction_parser::TradeInfoFromToken,
         swap_config: SwapConfig,
     ) -> Result<(Arc<Keypair>, Vec<Instruction>, f64)> {
+        crate::library::trading_lockdown::ensure_trading_enabled()?;
         let logger = Logger::new("[PUMPSWAP-FROM-PARSED] => ".blue().to_string());
         let start_time = std::time::Instant::now();
         
@@ -164,9 +198,9 @@ impl PumpSwap {
             return Err(anyhow!("Coin creator not found in trade info"));
         };
         
-        // Use virtual reserves from trade_info for calculations
+        // Use virtual reserves from trade_info for calculations, with stablecoin reserves in SOL terms
         let token_price = Self::calculate_price_from_virtual_reserves(
-            trade_info.virtual_sol_reserves,
+            trade_info.quote_asset.raw_to_lamports(trade_info.virtual_sol_reserves),
             trade_info.virtual_token_reserves,
         );
         
@@ -175,11 +209,11 @@ impl PumpSwap {
         
         // Prepare swap parameters
         let (_token_in, _token_out, discriminator) = match swap_config.swap_direction {
-            SwapDirection::Buy => (SOL_MINT, mint, BUY_DISCRIMINATOR),
-            SwapDirection::Sell => (mint, SOL_MINT, SELL_DISCRIMINATOR),
+            SwapDirection::Buy => (trade_info.quote_asset.mint(), mint, BUY_DISCRIMINATOR),
+            SwapDirection::Sell => (mint, trade_info.quote_asset.mint(), SELL_DISCRIMINATOR),
         };
         
-        let mut instructions = Vec::with_capacity(3); // Pre-allocate for typical case
+        let mut preparer = AccountPreparer::new(owner, self.rpc_nonblocking_client.clone());
         
         // Process swap direction using only parsed data
         let (base_amount, quote_amount, accounts) = match swap_config.swap_direction {
@@ -191,7 +225,7 @@ impl PumpSwap {
                 coin_creator,
                 swap_config.amount_in,
                 swap_config.slippage as u64,
-                &mut instructions,
+                &mut preparer,
             ).await?,
             SwapDirection::Sell => self.prepare_sell_swap_from_parsed(
                 trade_info,
@@ -202,22 +236,21 @@ impl PumpSwap {
                 swap_config.amount_in,
                 swap_config.in_type,
                 swap_config.slippage as u64,
-                &mut instructions,
+                &mut preparer,
             ).await?,
         };
         
         // Add swap instruction if amount is valid
-        if base_amount > 0 {
-            instructions.push(create_swap_instruction(
-                PUMP_SWAP_PROGRAM,
-                discriminator,
-                base_amount,
-                quote_amount,
-                accounts,
-            ));
-        } else {
+        if base_amount == 0 {
             return Err(anyhow!("Invalid swap amount"));
         }
+        let instructions = preparer.finish(vec![create_swap_instruction(
+            DexProgram::PumpSwap.id(),
+            discriminator,
+            base_amount,
+            quote_amount,
+            accounts,
+        )]);
         
         logger.log(format!("Built swap instruction in {:?}", start_time.elapsed()));
         Ok((self.keypair.clone(), instructions, token_price))
@@ -233,9 +266,15 @@ impl PumpSwap {
         coin_creator: Pubkey,
         amount_in: f64,
         slippage_bps: u64,
-        instructions: &mut Vec<Instruction>,
+        preparer: &mut AccountPreparer,
     ) -> Result<(u64, u64, Vec<AccountMeta>)> {
-        let amount_specified = ui_amount_to_amount(amount_in, 9);
+        // Stable-quoted pools are paid in the stablecoin: size the buy in quote terms
+        let quote = trade_info.quote_asset;
+        let amount_specified = if quote.is_stable() {
+            quote.to_raw(quote_asset::stable_buy_amount(amount_in, quote))
+        } else {
+            ui_amount_to_amount(amount_in, 9)
+        };
         
         // Use virtual reserves for calculation
         let base_amount_out = Self::calculate_buy_token_amount(
@@ -245,28 +284,21 @@ impl PumpSwap {
         );
         
         let max_quote_amount_in = max_amount_with_slippage(amount_specified, slippage_bps);
-        let out_ata = get_associated_token_address(&owner, &mint);
         
-        // Check token account existence and create if needed
+        // Create the token ATA if needed and wrap enough SOL to cover the max quote amount
+        let out_ata = get_associated_token_address(&owner, &mint);
         if !self.check_token_account_cache(out_ata).await {
-            let logger = Logger::new("[PUMPSWAP-ATA-CREATE] => ".yellow().to_string());
-            logger.log(format!("Creating ATA for mint {} at address {}", mint, out_ata));
-            
-            instructions.push(create_associated_token_account_idempotent(
-                &owner,
-                &owner,
-                &mint,
-                &TOKEN_PROGRAM,
-            ));
-            
-            // Cache the account immediately since we're creating it
-            self.cache_token_account(out_ata).await;
-            logger.log(format!("ATA creation instruction added for {}", out_ata));
+            preparer.ensure_ata(&mint, &TOKEN_PROGRAM);
         }
+        let quote_ata = if quote.is_stable() {
+            preparer.ensure_ata(&quote.mint(), &TOKEN_PROGRAM)
+        } else {
+            preparer.wrap_sol(max_quote_amount_in).await?
+        };
         
         // Create accounts using parsed pool_id and coin_creator
         let pool_base_account = get_associated_token_address(&pool_id, &mint);
-        let pool_quote_account = get_associated_token_address(&pool_id, &SOL_MINT);
+        let pool_quote_account = get_associated_token_address(&pool_id, &quote.mint());
         
         // Get volume accumulator PDAs
         let global_volume_accumulator = get_global_volume_accumulator_pda()?;
@@ -276,9 +308,9 @@ impl PumpSwap {
             pool_id,
             owner,
             mint,
-            SOL_MINT,
+            quote.mint(),
             out_ata,
-            get_associated_token_address(&owner, &SOL_MINT),
+            quote_ata,
             pool_base_account,
             pool_quote_account,
             coin_creator,
@@ -300,7 +332,7 @@ impl PumpSwap {
         amount_in: f64,
         in_type: SwapInType,
         slippage_bps: u64,
-        instructions: &mut Vec<Instruction>,
+        preparer: &mut AccountPreparer,
     ) -> Result<(u64, u64, Vec<AccountMeta>)> {
         let in_ata = get_associated_token_address(&owner, &mint);
         
@@ -325,14 +357,8 @@ impl PumpSwap {
             SwapInType::Pct => {
                 let pct = amount_in.min(1.0);
                 if pct == 1.0 {
-                    // Close account if selling 100%
-                    instructions.push(spl_token::instruction::close_account(
-                        &TOKEN_PROGRAM,
-                        &in_ata,
-                        &owner,
-                        &owner,
-                        &[&owner],
-                    )?);
+                    // Close the emptied account after the swap if selling 100%
+                    preparer.close_ata_after(&in_ata, &TOKEN_PROGRAM)?;
                     account_info.base.amount
                 } else {
                     (pct * account_info.base.amount as f64) as u64
@@ -355,9 +381,17 @@ impl PumpSwap {
         println!("Sell calculation - Tokens in: {}, Expected SOL out: {}, Virtual SOL: {}, Virtual Tokens: {}", 
             amount, quote_amount_out, trade_info.virtual_sol_reserves, trade_info.virtual_token_reserves);
 
+        // Proceeds land in WSOL (or the pool's stablecoin); make sure the account exists and
+        // unwrap WSOL afterwards
+        let quote = trade_info.quote_asset;
+        let quote_ata = preparer.ensure_ata(&quote.mint(), &TOKEN_PROGRAM);
+        if !quote.is_stable() {
+            preparer.unwrap_wsol_after()?;
+        }
+
         // Create accounts using parsed pool_id and coin_creator
         let pool_base_account = get_associated_token_address(&pool_id, &mint);
-        let pool_quote_account = get_associated_token_address(&pool_id, &SOL_MINT);
+        let pool_quote_account = get_associated_token_address(&pool_id, &quote.mint());
 
         // Get volume accumulator PDAs
         let global_volume_accumulator = get_global_volume_accumulator_pda()?;
@@ -367,9 +401,9 @@ impl PumpSwap {
             pool_id,
             owner,
             mint,
-            SOL_MINT,
+            quote.mint(),
             in_ata,
-            get_associated_token_address(&owner, &SOL_MINT),
+            quote_ata,
             pool_base_account,
             pool_quote_account,
             coin_creator,
@@ -417,10 +451,6 @@ impl PumpSwap {
             false
         }
     }
-    
-    async fn cache_token_account(&self, account: Pubkey) {
-        WALLET_TOKEN_ACCOUNTS.insert(account);
-    }
 
     /// Calculate token amount out for buy using virtual reserves (PumpSwap AMM formula)
     pub fn calculate_buy_token_amount(
@@ -488,6 +518,147 @@ impl PumpSwap {
     }
 }
 
+#[cfg(feature = "trading")]
+/// A PumpSwap pool pairing a token with WSOL, with its current vault balances
+#[derive(Clone, Debug)]
+pub struct PumpSwapPool {
+    pub pool_id: Pubkey,
+    pub coin_creator: Pubkey,
+    pub pool_base_token_account: Pubkey,
+    pub pool_quote_token_account: Pubkey,
+    pub base_reserve: u64,
+    pub quote_reserve: u64,
+}
+
+#[cfg(feature = "trading")]
+impl PumpSwapPool {
+    pub fn to_venue(&self) -> SellVenue {
+        SellVenue {
+            pool_id: self.pool_id,
+            sol_reserve: self.quote_reserve,
+            token_reserve: self.base_reserve,
+            fee_bps: POOL_FEE_BPS,
+        }
+    }
+}
+
+#[cfg(feature = "trading")]
+impl PumpSwap {
+    /// Every WSOL pool of `mint`, with vault balances. Pools with an empty vault are left out.
+    pub async fn find_pools(&self, mint: &Pubkey) -> Result<Vec<PumpSwapPool>> {
+        let rpc_client = self.rpc_nonblocking_client.clone()
+            .ok_or_else(|| anyhow!("RPC client not initialized"))?;
+
+        let accounts = rpc_client.get_program_accounts_with_config(
+            &DexProgram::PumpSwap.id(),
+            RpcProgramAccountsConfig {
+                filters: Some(vec![
+                    RpcFilterType::DataSize(300),
+                    RpcFilterType::Memcmp(Memcmp::new(43, MemcmpEncodedBytes::Base64(base64::encode(mint.to_bytes())))),
+                    RpcFilterType::Memcmp(Memcmp::new(75, MemcmpEncodedBytes::Base64(base64::encode(SOL_MINT.to_bytes())))),
+                ]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ).await.map_err(|e| anyhow!("Error getting program accounts: {}", e))?;
+
+        // Pool layout: base_mint @43, quote_mint @75, pool_base_token_account @139,
+        // pool_quote_token_account @171, coin_creator @211
+        let mut pools: Vec<PumpSwapPool> = accounts.iter()
+            .filter(|(_, account)| account.data.len() >= 243)
+            .filter_map(|(pool_id, account)| {
+                let key = |offset: usize| Pubkey::try_from(&account.data[offset..offset + 32]).ok();
+                Some(PumpSwapPool {
+                    pool_id: *pool_id,
+                    coin_creator: key(211)?,
+                    pool_base_token_account: key(139)?,
+                    pool_quote_token_account: key(171)?,
+                    base_reserve: 0,
+                    quote_reserve: 0,
+                })
+            })
+            .collect();
+        if pools.is_empty() {
+            return Ok(pools);
+        }
+
+        let vaults: Vec<Pubkey> = pools.iter()
+            .flat_map(|p| [p.pool_base_token_account, p.pool_quote_token_account])
+            .collect();
+        let balances = rpc_client.get_multiple_accounts(&vaults).await?;
+        let amount = |index: usize| balances.get(index)
+            .and_then(|a| a.as_ref())
+            .and_then(|a| spl_token::state::Account::unpack(&a.data).ok())
+            .map(|a| a.amount)
+            .unwrap_or(0);
+        for (i, pool) in pools.iter_mut().enumerate() {
+            pool.base_reserve = amount(2 * i);
+            pool.quote_reserve = amount(2 * i + 1);
+        }
+        pools.retain(|p| p.base_reserve > 0 && p.quote_reserve > 0);
+        Ok(pools)
+    }
+
+    /// One transaction selling `legs` into their pools: a shared WSOL account, one sell
+    /// instruction per leg, then the unwrap (and the token ATA close when `close_ata`).
+    pub fn build_split_sell(
+        &self,
+        mint: &Pubkey,
+        pools: &[PumpSwapPool],
+        legs: &[SplitLeg],
+        slippage_bps: u64,
+        close_ata: bool,
+    ) -> Result<(Arc<Keypair>, Vec<Instruction>)> {
+        crate::library::trading_lockdown::ensure_trading_enabled()?;
+        let owner = self.keypair.pubkey();
+        let in_ata = get_associated_token_address(&owner, mint);
+        let mut preparer = AccountPreparer::new(owner, self.rpc_nonblocking_client.clone());
+        let wsol_ata = preparer.ensure_ata(&SOL_MINT, &TOKEN_PROGRAM);
+        preparer.unwrap_wsol_after()?;
+        if close_ata {
+            preparer.close_ata_after(&in_ata, &TOKEN_PROGRAM)?;
+        }
+
+        let global_volume_accumulator = get_global_volume_accumulator_pda()?;
+        let user_volume_accumulator = get_user_volume_accumulator_pda(&owner)?;
+        let mut swap_instructions = Vec::with_capacity(legs.len());
+        for leg in legs {
+            let pool = pools.iter()
+                .find(|p| p.pool_id == leg.venue.pool_id)
+                .ok_or_else(|| anyhow!("Pool {} not found for split leg", leg.venue.pool_id))?;
+            let quote_out = calculate_sell_quote_amount(leg.token_amount, pool.base_reserve, pool.quote_reserve);
+            let accounts = create_sell_accounts(
+                pool.pool_id,
+                owner,
+                *mint,
+                SOL_MINT,
+                in_ata,
+                wsol_ata,
+                pool.pool_base_token_account,
+                pool.pool_quote_token_account,
+                pool.coin_creator,
+                global_volume_accumulator,
+                user_volume_accumulator,
+            )?;
+            swap_instructions.push(create_swap_instruction(
+                DexProgram::PumpSwap.id(),
+                SELL_DISCRIMINATOR,
+                leg.token_amount,
+                min_amount_with_slippage(quote_out, slippage_bps),
+                accounts,
+            ));
+        }
+        if swap_instructions.is_empty() {
+            return Err(anyhow!("No legs to sell"));
+        }
+        Ok((self.keypair.clone(), preparer.finish(swap_instructions)))
+    }
+}
+
+#[cfg(feature = "trading")]
 /// Minimal pool info for price queries only (returns pool_id, base_reserve, quote_reserve)
 async fn get_pool_info_for_price(
     rpc_client: Arc<anchor_client::solana_client::rpc_client::RpcClient>,
@@ -497,7 +668,7 @@ async fn get_pool_info_for_price(
     
     // Initialize
     let sol_mint = SOL_MINT;
-    let pump_program = PUMP_SWAP_PROGRAM;
+    let pump_program = DexProgram::PumpSwap.id();
     
     // Find the pool
     let mut pool_id = Pubkey::default();
@@ -566,6 +737,7 @@ async fn get_pool_info_for_price(
 }
 
 // Optimized math functions with overflow protection
+#[cfg(feature = "trading")]
 #[inline]
 fn calculate_buy_base_amount(quote_amount_in: u64, quote_reserve: u64, base_reserve: u64) -> u64 {
     if quote_amount_in == 0 || base_reserve == 0 || quote_reserve == 0 {
@@ -584,6 +756,7 @@ fn calculate_buy_base_amount(quote_amount_in: u64, quote_reserve: u64, base_rese
     base_reserve.saturating_sub(base_reserve_after as u64)
 }
 
+#[cfg(feature = "trading")]
 #[inline]
 fn calculate_sell_quote_amount(base_amount_in: u64, base_reserve: u64, quote_reserve: u64) -> u64 {
     if base_amount_in == 0 || base_reserve == 0 || quote_reserve == 0 {
@@ -602,6 +775,7 @@ fn calculate_sell_quote_amount(base_amount_in: u64, base_reserve: u64, quote_res
     quote_reserve.saturating_sub(quote_reserve_after as u64)
 }
 
+#[cfg(feature = "trading")]
 #[inline]
 fn min_amount_with_slippage(input_amount: u64, slippage_bps: u64) -> u64 {
     input_amount
@@ -610,6 +784,7 @@ fn min_amount_with_slippage(input_amount: u64, slippage_bps: u64) -> u64 {
         .unwrap_or(0)
 }
 
+#[cfg(feature = "trading")]
 #[inline]
 fn max_amount_with_slippage(input_amount: u64, slippage_bps: u64) -> u64 {
     input_amount
@@ -619,6 +794,7 @@ fn max_amount_with_slippage(input_amount: u64, slippage_bps: u64) -> u64 {
 }
 
 // Optimized account creation with const pubkeys
+#[cfg(feature = "trading")]
 fn create_buy_accounts(
     pool_id: Pubkey,
     user: Pubkey,
@@ -634,7 +810,7 @@ fn create_buy_accounts(
 ) -> Result<Vec<AccountMeta>> {
     let (coin_creator_vault_authority, _) = Pubkey::find_program_address(
         &[b"creator_vault", coin_creator.as_ref()],
-        &PUMP_SWAP_PROGRAM,
+        &DexProgram::PumpSwap.id(),
     );
     let coin_creator_vault_ata = get_associated_token_address(&coin_creator_vault_authority, &quote_mint);
     
@@ -657,7 +833,7 @@ fn create_buy_accounts(
         AccountMeta::new_readonly(system_program::id(), false),
         AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM, false),
         AccountMeta::new_readonly(PUMP_EVENT_AUTHORITY, false),
-        AccountMeta::new_readonly(PUMP_SWAP_PROGRAM, false),
+        AccountMeta::new_readonly(DexProgram::PumpSwap.id(), false),
         AccountMeta::new(coin_creator_vault_ata, false),
         AccountMeta::new_readonly(coin_creator_vault_authority, false),
         AccountMeta::new(global_volume_accumulator, false),
@@ -666,6 +842,7 @@ fn create_buy_accounts(
 }
 
 // Similar optimization for sell accounts
+#[cfg(feature = "trading")]
 fn create_sell_accounts(
     pool_id: Pubkey,
     user: Pubkey,
@@ -682,7 +859,7 @@ fn create_sell_accounts(
 
     let (coin_creator_vault_authority, _) = Pubkey::find_program_address(
         &[b"creator_vault", coin_creator.as_ref()],
-        &PUMP_SWAP_PROGRAM,
+        &DexProgram::PumpSwap.id(),
     );
     let coin_creator_vault_ata = get_associated_token_address(&coin_creator_vault_authority, &quote_mint);
 
@@ -705,7 +882,7 @@ fn create_sell_accounts(
         AccountMeta::new_readonly(system_program::id(), false),
         AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM, false),
         AccountMeta::new_readonly(PUMP_EVENT_AUTHORITY, false),
-        AccountMeta::new_readonly(PUMP_SWAP_PROGRAM, false),
+        AccountMeta::new_readonly(DexProgram::PumpSwap.id(), false),
         AccountMeta::new(coin_creator_vault_ata, false),
         AccountMeta::new_readonly(coin_creator_vault_authority, false),
         AccountMeta::new(global_volume_accumulator, false),
@@ -714,6 +891,7 @@ fn create_sell_accounts(
 }
 
 // Optimized instruction creation
+#[cfg(feature = "trading")]
 fn create_swap_instruction(
     program_id: Pubkey,
     discriminator: [u8; 8],
@@ -1,29 +1,31 @@
-use std::{str::FromStr, sync::Arc, time::Instant};
-use solana_program_pack::Pack;
-use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
-use solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
-use solana_account_decoder::UiAccountEncoding;
-use anyhow::{anyhow, Result};
-use colored::Colorize;
-use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
-    pubkey::Pubkey,
-    signature::Keypair,
-    system_program,
-    signer::Signer,
-};
-use crate::processor::transaction_parser::DexType;
-use spl_associated_token_account::{
-    get_associated_token_address,
-    instruction::create_associated_token_account_idempotent
-};
-use spl_token::ui_amount_to_amount;
+use solana_sdk::pubkey::Pubkey;
+use crate::common::cluster::DexProgram;
 
-
-use crate::{
-    common::{config::SwapConfig, logger::Logger, cache::WALLET_TOKEN_ACCOUNTS},
-    block_engine::token,
-    processor::swap::{SwapDirection, SwapInType},
+// Swap building signs with the wallet, so it only exists in trading builds
+#[cfg(feature = "trading")]
+use {
+    std::{str::FromStr, sync::Arc, time::Instant},
+    solana_program_pack::Pack,
+    solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+    solana_account_decoder::UiAccountEncoding,
+    anyhow::{anyhow, Result},
+    colored::Colorize,
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        signature::Keypair,
+        system_program,
+        signer::Signer,
+    },
+    crate::processor::transaction_parser::DexType,
+    crate::dex::account_preparer::AccountPreparer,
+    spl_associated_token_account::get_associated_token_address,
+    spl_token::ui_amount_to_amount,
+    crate::{
+        common::{config::SwapConfig, logger::Logger, cache::WALLET_TOKEN_ACCOUNTS},
+        block_engine::token,
+        processor::swap::{SwapDirection, SwapInType},
+    },
 };
 
 pub const TOKEN_PROGRAM: Pubkey = solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
@@ -38,11 +40,20 @@ pub const SOL_MINT: Pubkey = solana_sdk::pubkey!("So1111111111111111111111111111
 pub const BUY_DISCRIMINATOR: [u8; 8] = [250, 234, 13, 123, 213, 156, 19, 236]; // buy_exact_in discriminator
 pub const SELL_DISCRIMINATOR: [u8; 8] = [149, 39, 222, 155, 211, 124, 152, 26]; // sell_exact_in discriminator
 
+#[cfg(feature = "trading")]
 const TEN_THOUSAND: u64 = 10000;
 const POOL_VAULT_SEED: &[u8] = b"pool_vault";
 
+/// Vault holding `mint` for a Raydium Launchpad pool
+pub fn get_pool_vault_pda(pool_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let seeds = [POOL_VAULT_SEED, pool_id.as_ref(), mint.as_ref()];
+    let (pda, _bump) = Pubkey::find_program_address(&seeds, &DexProgram::RaydiumLaunchpad.id());
+    pda
+}
 
 
+
+#[cfg(feature = "trading")]
 /// A struct to represent the Raydium pool which uses constant product AMM
 #[derive(Debug, Clone)]
 pub struct RaydiumPool {
@@ -53,12 +64,14 @@ pub struct RaydiumPool {
     pub pool_quote_account: Pubkey,
 }
 
+#[cfg(feature = "trading")]
 pub struct Raydium {
     pub keypair: Arc<Keypair>,
     pub rpc_client: Option<Arc<solana_client::rpc_client::RpcClient>>,
     pub rpc_nonblocking_client: Option<Arc<solana_client::nonblocking::rpc_client::RpcClient>>,
 }
 
+#[cfg(feature = "trading")]
 impl Raydium {
     pub fn new(
         keypair: Arc<Keypair>,
@@ -105,7 +118,7 @@ impl Raydium {
             .map_err(|e| anyhow!("Invalid pool_id in trade_info: {}", e))?;
         
         // For Raydium Launchpad, derive pool vault addresses using PDA (Program Derived Address)
-        let pump_program = RAYDIUM_LAUNCHPAD_PROGRAM;
+        let pump_program = DexProgram::RaydiumLaunchpad.id();
         let sol_mint = SOL_MINT;
         
         // Derive pool vault addresses using PDA with specific seeds
@@ -156,8 +169,13 @@ impl Raydium {
         trade_info: &crate::processor::transaction_parser::TradeInfoFromToken,
         swap_config: SwapConfig,
     ) -> Result<(Arc<Keypair>, Vec<Instruction>, f64)> {
+        crate::library::trading_lockdown::ensure_trading_enabled()?;
         let owner = self.keypair.pubkey();
         let mint = Pubkey::from_str(&trade_info.mint)?;
+        // Stable-quoted launchpads use their own global config, which this builder does not know
+        if trade_info.quote_asset.is_stable() {
+            return Err(anyhow!("{}-quoted Raydium Launchpad pools are not supported for trading", trade_info.quote_asset));
+        }
         
         // Get token program for the mint
         let token_program = self.get_token_program(&mint).await?;
@@ -168,73 +186,20 @@ impl Raydium {
             SwapDirection::Sell => (mint, SOL_MINT, SELL_DISCRIMINATOR),
         };
         
-        let mut instructions = Vec::with_capacity(3); // Pre-allocate for typical case
+        let mut preparer = AccountPreparer::new(owner, self.rpc_nonblocking_client.clone());
         
         // Check and create token accounts if needed
         let token_ata = get_associated_token_address(&owner, &mint);
-        let wsol_ata = get_associated_token_address(&owner, &SOL_MINT);
-        
-        // Check if token account exists and create if needed
         if !WALLET_TOKEN_ACCOUNTS.contains(&token_ata) {
             // Double-check with RPC to see if the account actually exists
-            let account_exists = if let Some(rpc_client) = &self.rpc_client {
-                match rpc_client.get_account(&token_ata) {
-                    Ok(_) => {
-                        // Account exists, add to cache
-                        WALLET_TOKEN_ACCOUNTS.insert(token_ata);
-                        true
-                    },
-                    Err(_) => false
-                }
-            } else {
-                false // No RPC client, assume account doesn't exist
+            let account_exists = match &self.rpc_client {
+                Some(rpc_client) => rpc_client.get_account(&token_ata).is_ok(),
+                None => false, // No RPC client, assume account doesn't exist
             };
-            
-            if !account_exists {
-                let logger = Logger::new("[RAYDIUM-ATA-CREATE] => ".yellow().to_string());
-                logger.log(format!("Creating token ATA for mint {} at address {}", mint, token_ata));
-                
-                instructions.push(create_associated_token_account_idempotent(
-                    &owner,
-                    &owner,
-                    &mint,
-                    &TOKEN_PROGRAM, // Always use legacy token program for ATA creation
-                ));
-                
-                // Cache the account since we're creating it
+            if account_exists {
                 WALLET_TOKEN_ACCOUNTS.insert(token_ata);
-            }
-        }
-        
-        // Check if WSOL account exists and create if needed
-        if !WALLET_TOKEN_ACCOUNTS.contains(&wsol_ata) {
-            // Double-check with RPC to see if the account actually exists
-            let account_exists = if let Some(rpc_client) = &self.rpc_client {
-                match rpc_client.get_account(&wsol_ata) {
-                    Ok(_) => {
-                        // Account exists, add to cache
-                        WALLET_TOKEN_ACCOUNTS.insert(wsol_ata);
-                        true
-                    },
-                    Err(_) => false
-                }
             } else {
-                false // No RPC client, assume account doesn't exist
-            };
-            
-            if !account_exists {
-                let logger = Logger::new("[RAYDIUM-WSOL-CREATE] => ".yellow().to_string());
-                logger.log(format!("Creating WSOL ATA at address {}", wsol_ata));
-                
-                instructions.push(create_associated_token_account_idempotent(
-                    &owner,
-                    &owner,
-                    &SOL_MINT,
-                    &TOKEN_PROGRAM, // WSOL always uses legacy token program
-                ));
-                
-                // Cache the account since we're creating it
-                WALLET_TOKEN_ACCOUNTS.insert(wsol_ata);
+                preparer.ensure_ata(&mint, &TOKEN_PROGRAM); // Always use legacy token program for ATA creation
             }
         }
         
@@ -308,6 +273,19 @@ impl Raydium {
             }
         };
         
+        // Buys spend WSOL, so wrap what is needed; sells receive WSOL, unwrapped after the swap
+        let wsol_ata = match swap_config.swap_direction {
+            SwapDirection::Buy => preparer.wrap_sol(amount_in).await?,
+            SwapDirection::Sell => {
+                if swap_config.in_type == SwapInType::Pct && swap_config.amount_in >= 1.0 {
+                    preparer.close_ata_after(&token_ata, &token_program)?;
+                }
+                let wsol_ata = preparer.ensure_ata(&SOL_MINT, &TOKEN_PROGRAM);
+                preparer.unwrap_wsol_after()?;
+                wsol_ata
+            }
+        };
+        
         // Calculate the actual quote amount using virtual reserves from trade_info
         let minimum_amount_out: u64 = 1; // to ignore slippage
         
@@ -345,13 +323,13 @@ impl Raydium {
             }
         };
         
-        instructions.push(create_swap_instruction(
-            RAYDIUM_LAUNCHPAD_PROGRAM,
+        let instructions = preparer.finish(vec![create_swap_instruction(
+            DexProgram::RaydiumLaunchpad.id(),
             discriminator,
             amount_in,
             minimum_amount_out,
             accounts,
-        ));
+        )]);
         
         // Return the actual price from trade_info (convert from lamports to SOL)
         let price_in_sol = trade_info.price as f64 / 1_000_000_000.0;
@@ -362,6 +340,7 @@ impl Raydium {
 
 }
 
+#[cfg(feature = "trading")]
 /// Get the Raydium pool information for a specific token mint
 pub async fn get_pool_info(
     rpc_client: Arc<solana_client::rpc_client::RpcClient>,
@@ -371,7 +350,7 @@ pub async fn get_pool_info(
     
     // Initialize
     let sol_mint = SOL_MINT;
-    let pump_program = RAYDIUM_LAUNCHPAD_PROGRAM;
+    let pump_program = DexProgram::RaydiumLaunchpad.id();
     
     // Use getProgramAccounts with config for better efficiency
     let mut pool_id = Pubkey::default();
@@ -445,6 +424,7 @@ pub async fn get_pool_info(
 }
 
 // Optimized account creation with const pubkeys
+#[cfg(feature = "trading")]
 fn create_buy_accounts(
     pool_id: Pubkey,
     user: Pubkey,
@@ -472,11 +452,12 @@ fn create_buy_accounts(
         AccountMeta::new_readonly(*token_program, false), // Use detected token program for base mint
         AccountMeta::new_readonly(TOKEN_PROGRAM, false), // Use legacy token program for WSOL
         AccountMeta::new_readonly(EVENT_AUTHORITY, false),
-        AccountMeta::new_readonly(RAYDIUM_LAUNCHPAD_PROGRAM, false),
+        AccountMeta::new_readonly(DexProgram::RaydiumLaunchpad.id(), false),
         ])
 }
 
 // Similar optimization for sell accounts
+#[cfg(feature = "trading")]
 fn create_sell_accounts(
     pool_id: Pubkey,
     user: Pubkey,
@@ -505,10 +486,11 @@ fn create_sell_accounts(
         AccountMeta::new_readonly(*token_program, false), // Use detected token program for base mint
         AccountMeta::new_readonly(TOKEN_PROGRAM, false), // Use legacy token program for WSOL
         AccountMeta::new_readonly(EVENT_AUTHORITY, false),
-        AccountMeta::new_readonly(RAYDIUM_LAUNCHPAD_PROGRAM, false),
+        AccountMeta::new_readonly(DexProgram::RaydiumLaunchpad.id(), false),
 ])
 }
 
+#[cfg(feature = "trading")]
 #[inline]
 fn calculate_raydium_sell_amount_out(
     base_amount_in: u64,
@@ -544,6 +526,7 @@ fn calculate_raydium_sell_amount_out(
 }
 
 // Optimized instruction creation
+#[cfg(feature = "trading")]
 fn create_swap_instruction(
     program_id: Pubkey,
     discriminator: [u8; 8],
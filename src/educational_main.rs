@@ -4,17 +4,20 @@ use anyhow::Result;
 use dotenv::dotenv;
 use std::sync::Arc;
 use tokio::time::{sleep, Duration};
+use tokio_util::sync::CancellationToken;
 
 mod common;
 mod processor;
 mod library;
 mod dex;
 mod error;
+#[cfg(feature = "trading")]
 mod block_engine;
 
 use crate::processor::telegram_alerts::{TelegramAlertSystem, AlertSettings};
 use crate::processor::educational_monitor::EducationalMonitor;
-use crate::common::config::Config;
+use crate::processor::report_export::{self, Report};
+use crate::common::config::{Config, Percent};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -30,6 +33,10 @@ async fn main() -> Result<()> {
     let config = Config::from_env().await?;
     println!("✅ Configuration loaded");
 
+    // With TRADING_ENABLED=false nothing in this process may sign or send
+    let trading_mode = library::trading_lockdown::self_check()?;
+    println!("✅ Trading lockdown checked: {}", trading_mode);
+
     // Initialize Telegram alerts if configured
     let telegram = match processor::telegram_alerts::init_from_env()? {
         Some(mut system) => {
@@ -64,9 +71,102 @@ async fn main() -> Result<()> {
     };
 
     // Initialize educational monitor
-    let monitor = EducationalMonitor::new(config.clone(), telegram.clone());
+    let monitor = Arc::new(EducationalMonitor::new(config.clone(), telegram.clone()));
     println!("✅ Educational monitor initialized");
 
+    // Optional gRPC API for querying state and adjusting thresholds
+    let cancel_token = CancellationToken::new();
+    #[cfg(feature = "api")]
+    let grpc_handle = library::grpc_api::start_grpc_api(monitor.clone(), cancel_token.clone()).await;
+    #[cfg(not(feature = "api"))]
+    let grpc_handle: Option<tokio::task::JoinHandle<()>> = None;
+    if grpc_handle.is_some() {
+        println!("✅ gRPC monitor API started");
+    }
+
+    // Deliver alerts queued while Telegram was unreachable
+    let outbox_handle = telegram.clone()
+        .map(|tg| processor::telegram_alerts::start_outbox_retry(tg, cancel_token.clone()));
+
+    // Health and circuit breaker state for probes and scrapers
+    #[cfg(feature = "api")]
+    let status_handle = library::status_server::start_status_server(cancel_token.clone()).await;
+    #[cfg(not(feature = "api"))]
+    let status_handle: Option<tokio::task::JoinHandle<()>> = None;
+    if status_handle.is_some() {
+        println!("✅ Status endpoints (/healthz, /metrics) started");
+    }
+
+    // Telegram commands (watchlist import/export)
+    #[cfg(feature = "telegram")]
+    let commands_handle = processor::telegram_commands::start_telegram_commands(Some(monitor.clone()), cancel_token.clone()).await;
+    #[cfg(not(feature = "telegram"))]
+    let commands_handle: Option<tokio::task::JoinHandle<()>> = None;
+    if commands_handle.is_some() {
+        println!("✅ Telegram commands enabled");
+    }
+
+    // Pool and vault accounts kept fresh by subscription instead of per-read RPC calls
+    let account_cache_handle = library::account_subscription::start_account_cache_subscription(
+        config.yellowstone_grpc_http.clone(),
+        config.yellowstone_grpc_token.clone(),
+        cancel_token.clone(),
+    );
+    if account_cache_handle.is_some() {
+        println!("✅ Account cache subscription started");
+    }
+
+    // Alerts built from a lagging stream are flagged as delayed
+    let slot_lag_handle = library::slot_lag::start_slot_lag_monitor(
+        config.app_state.rpc_nonblocking_client.clone(),
+        cancel_token.clone(),
+    );
+    if slot_lag_handle.is_some() {
+        println!("✅ Slot lag monitoring started");
+    }
+
+    // Heartbeat when alerts have been quiet for hours, with the stream's health
+    let heartbeat_handle = library::heartbeat::start_heartbeat(cancel_token.clone());
+    if heartbeat_handle.is_some() {
+        println!("✅ Quiet heartbeat started");
+    }
+
+    // Community scam lists merged into the blacklist, so alerts name the list flagging a token
+    let scam_list_handle = processor::scam_lists::start_scam_list_sync(cancel_token.clone());
+    if scam_list_handle.is_some() {
+        println!("✅ Scam list sync started");
+    }
+
+    // Pool reserve polling for depth estimates and liquidity drop alerts
+    let reserve_reader = Arc::new(processor::liquidity_depth::RpcReserveReader::new(
+        config.app_state.account_batcher.clone(),
+    ));
+    let depth_handle = processor::liquidity_depth::start_liquidity_depth_polling(
+        monitor.clone(),
+        reserve_reader,
+        cancel_token.clone(),
+    );
+    if depth_handle.is_some() {
+        println!("✅ Liquidity depth polling started");
+    }
+
+    // Optional config-driven pipeline (PIPELINE / PIPELINE_PATH); PIPELINE=monitor feeds the
+    // monitor from the live stream
+    let pipeline_ctx = processor::pipeline::PipelineContext {
+        monitor: Some(monitor.clone()),
+        telegram: telegram.clone(),
+        rpc: Some(config.app_state.rpc_nonblocking_client.clone()),
+        ..Default::default()
+    };
+    let source_config = library::transaction_source::SourceConfig::new(
+        config.yellowstone_grpc_http.clone(),
+        config.yellowstone_grpc_token.clone(),
+    );
+    let pipeline_handle = processor::pipeline::start_from_env(pipeline_ctx, source_config, cancel_token.clone())?;
+    if pipeline_handle.is_some() {
+        println!("✅ Pipeline started");
+    }
+
     // Display monitoring configuration
     println!("\n📊 Monitoring Configuration:");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -89,8 +189,8 @@ async fn main() -> Result<()> {
     }
 
     if let Ok(drop_threshold) = std::env::var("FOCUS_DROP_THRESHOLD_PCT") {
-        if let Ok(threshold) = drop_threshold.parse::<f64>() {
-            println!("📉 Price Drop Alert Threshold: {:.1}%", threshold * 100.0);
+        if let Ok(threshold) = Percent::parse_fraction(&drop_threshold) {
+            println!("📉 Price Drop Alert Threshold: {:.1}%", threshold.points());
         }
     }
 
@@ -112,9 +212,13 @@ async fn main() -> Result<()> {
     println!("• Wallet activity tracking");
     println!("• Price movement analysis");
     println!("• Volume spike detection");
+    println!("• Liquidity depth and price impact alerts");
     println!("• Pattern recognition");
     println!("• Hypothetical PnL tracking");
     println!("• Daily educational reports");
+    println!("• Weekly signal accuracy reports");
+    println!("• Launch replays when tokens graduate or die");
+    println!("• Trending token ranking (/trending)");
 
     println!("\n⚠️  IMPORTANT REMINDERS:");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -129,6 +233,11 @@ async fn main() -> Result<()> {
 
     // Main monitoring loop
     let mut report_timer = tokio::time::interval(Duration::from_secs(3600)); // Hourly reports
+    let mut weekly_timer = tokio::time::interval(Duration::from_secs(7 * 24 * 3600)); // Weekly signal accuracy
+    weekly_timer.tick().await;
+    let mut memory_timer = tokio::time::interval(Duration::from_secs(60)); // Memory budget checks
+    let mut replay_timer = tokio::time::interval(Duration::from_secs(60)); // Finished launch replays
+    let mut trending_timer = tokio::time::interval(Duration::from_secs(60)); // Trending ranking
 
     loop {
         tokio::select! {
@@ -143,6 +252,8 @@ async fn main() -> Result<()> {
                                 &report
                             ).await;
                         }
+                        let document = Report::from_text("Hourly Educational Report", chrono::Utc::now(), &report);
+                        report_export::export(&document, telegram.as_deref()).await;
                     },
                     Err(e) => {
                         eprintln!("Error generating report: {}", e);
@@ -150,6 +261,34 @@ async fn main() -> Result<()> {
                 }
             }
 
+            _ = weekly_timer.tick() => {
+                let report = monitor.generate_weekly_report().await;
+                println!("{}", report);
+                if let Some(tg) = &telegram {
+                    let _ = tg.send_custom_alert("Weekly Signal Report", &report).await;
+                }
+                let document = Report::from_text("Weekly Signal Report", chrono::Utc::now(), &report);
+                report_export::export(&document, telegram.as_deref()).await;
+            }
+
+            _ = memory_timer.tick() => {
+                let evicted = monitor.enforce_memory_budget().await;
+                if evicted > 0 {
+                    println!("🧠 Memory budget exceeded: evicted {} inactive tokens", evicted);
+                }
+            }
+
+            _ = replay_timer.tick() => {
+                let reported = monitor.report_finished_launches().await;
+                if reported > 0 {
+                    println!("🎬 Sent {} launch replays", reported);
+                }
+            }
+
+            _ = trending_timer.tick() => {
+                monitor.refresh_trending().await;
+            }
+
             _ = tokio::signal::ctrl_c() => {
                 println!("\n📛 Shutdown signal received");
 
@@ -161,6 +300,38 @@ async fn main() -> Result<()> {
                     ).await;
                 }
 
+                cancel_token.cancel();
+                if let Some(handle) = grpc_handle {
+                    let _ = handle.await;
+                }
+                if let Some(handle) = commands_handle {
+                    let _ = handle.await;
+                }
+                if let Some(handle) = status_handle {
+                    let _ = handle.await;
+                }
+                if let Some(handle) = outbox_handle {
+                    let _ = handle.await;
+                }
+                if let Some(handle) = depth_handle {
+                    let _ = handle.await;
+                }
+                if let Some(handle) = account_cache_handle {
+                    let _ = handle.await;
+                }
+                if let Some(handle) = slot_lag_handle {
+                    let _ = handle.await;
+                }
+                if let Some(handle) = heartbeat_handle {
+                    let _ = handle.await;
+                }
+                if let Some(handle) = scam_list_handle {
+                    let _ = handle.await;
+                }
+                if let Some(handle) = pipeline_handle {
+                    let _ = handle.await;
+                }
+
                 println!("✅ Educational monitoring stopped");
                 println!("Thank you for using the educational monitoring system!");
                 break;
@@ -0,0 +1,202 @@
+//! Error taxonomy of the trading and alert hot paths.
+//!
+//! Every failure is either transient (retrying the same operation may succeed: RPC timeouts,
+//! expired blockhashes, rate limits) or fatal (retrying cannot help: bad configuration, an
+//! invalid keypair, a transaction the program rejected). `RetryPolicy::for_error` picks the
+//! retry/backoff behaviour for each kind.
+
+use std::time::Duration;
+use anchor_client::solana_client::client_error::{ClientError as SolanaClientError, ClientErrorKind};
+use solana_sdk::transaction::TransactionError;
+
+use super::ClientError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorClass {
+    Transient,
+    Fatal,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BotError {
+    /// RPC or relay request timed out
+    RpcTimeout(String),
+    /// Connection-level failure talking to a remote service
+    Network(String),
+    /// The transaction's blockhash is no longer valid; rebuild with a fresh one
+    BlockhashExpired,
+    /// No recent blockhash observed yet
+    BlockhashUnavailable,
+    /// Remote service asked us to slow down
+    RateLimited { retry_after: Option<Duration> },
+    /// Sent but not confirmed within the verification window
+    NotConfirmed(String),
+    /// Missing or invalid configuration value
+    InvalidConfig(String),
+    /// Wallet private key missing or malformed
+    InvalidKeypair(String),
+    /// Bad input to an operation (mint, amount, trade data)
+    InvalidInput(String),
+    InsufficientFunds,
+    /// Landed but failed on-chain, or rejected by the remote service
+    Rejected(String),
+    /// The named service's circuit breaker is open; the call was shed without being attempted
+    CircuitOpen(String),
+    /// TRADING_ENABLED=false: the process is read-only and refuses to sign or send
+    TradingDisabled,
+    /// Unclassified failure; retried conservatively
+    Other(String),
+}
+
+impl BotError {
+    pub fn class(&self) -> ErrorClass {
+        match self {
+            Self::RpcTimeout(_)
+            | Self::Network(_)
+            | Self::BlockhashExpired
+            | Self::BlockhashUnavailable
+            | Self::RateLimited { .. }
+            | Self::NotConfirmed(_)
+            | Self::CircuitOpen(_)
+            | Self::Other(_) => ErrorClass::Transient,
+            Self::InvalidConfig(_)
+            | Self::InvalidKeypair(_)
+            | Self::InvalidInput(_)
+            | Self::InsufficientFunds
+            | Self::Rejected(_)
+            | Self::TradingDisabled => ErrorClass::Fatal,
+        }
+    }
+
+    pub fn is_transient(&self) -> bool {
+        self.class() == ErrorClass::Transient
+    }
+
+    /// Classify an error from its message when no typed source is available
+    pub fn from_message(message: &str) -> Self {
+        let lower = message.to_lowercase();
+        if lower.contains("blockhash not found") || lower.contains("blockhash expired") || lower.contains("block height exceeded") {
+            Self::BlockhashExpired
+        } else if lower.contains("timed out") || lower.contains("timeout") {
+            Self::RpcTimeout(message.to_string())
+        } else if lower.contains("429") || lower.contains("too many requests") || lower.contains("rate limit") {
+            Self::RateLimited { retry_after: None }
+        } else if lower.contains("insufficient funds") || lower.contains("insufficient lamports") {
+            Self::InsufficientFunds
+        } else if lower.contains("connection") || lower.contains("dns") || lower.contains("broken pipe") {
+            Self::Network(message.to_string())
+        } else {
+            Self::Other(message.to_string())
+        }
+    }
+
+    /// Classify an error of unknown type, keeping typed sources where they can be recovered
+    pub fn classify(error: &anyhow::Error) -> Self {
+        if let Some(e) = error.downcast_ref::<BotError>() {
+            e.clone()
+        } else if let Some(e) = error.downcast_ref::<SolanaClientError>() {
+            Self::from(e)
+        } else if let Some(e) = error.downcast_ref::<teloxide::RequestError>() {
+            Self::from(e)
+        } else {
+            Self::from_message(&error.to_string())
+        }
+    }
+}
+
+impl std::fmt::Display for BotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RpcTimeout(msg) => write!(f, "RPC timeout: {}", msg),
+            Self::Network(msg) => write!(f, "Network error: {}", msg),
+            Self::BlockhashExpired => write!(f, "Blockhash expired"),
+            Self::BlockhashUnavailable => write!(f, "No recent blockhash available"),
+            Self::RateLimited { retry_after: Some(after) } => write!(f, "Rate limited, retry after {:?}", after),
+            Self::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            Self::NotConfirmed(msg) => write!(f, "Transaction not confirmed: {}", msg),
+            Self::InvalidConfig(msg) => write!(f, "Invalid configuration: {}", msg),
+            Self::InvalidKeypair(msg) => write!(f, "Invalid keypair: {}", msg),
+            Self::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            Self::InsufficientFunds => write!(f, "Insufficient funds for transaction"),
+            Self::Rejected(msg) => write!(f, "Rejected: {}", msg),
+            Self::CircuitOpen(service) => write!(f, "{} circuit breaker open", service),
+            Self::TradingDisabled => write!(f, "Trading disabled (TRADING_ENABLED=false)"),
+            Self::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BotError {}
+
+impl From<&SolanaClientError> for BotError {
+    fn from(error: &SolanaClientError) -> Self {
+        match error.kind() {
+            ClientErrorKind::Reqwest(e) if e.is_timeout() => Self::RpcTimeout(e.to_string()),
+            ClientErrorKind::Reqwest(e) => Self::Network(e.to_string()),
+            ClientErrorKind::Io(e) => Self::Network(e.to_string()),
+            ClientErrorKind::TransactionError(TransactionError::BlockhashNotFound) => Self::BlockhashExpired,
+            ClientErrorKind::TransactionError(TransactionError::InsufficientFundsForFee)
+            | ClientErrorKind::TransactionError(TransactionError::InsufficientFundsForRent { .. }) => Self::InsufficientFunds,
+            ClientErrorKind::TransactionError(e) => Self::Rejected(e.to_string()),
+            _ => Self::from_message(&error.to_string()),
+        }
+    }
+}
+
+impl From<SolanaClientError> for BotError {
+    fn from(error: SolanaClientError) -> Self {
+        Self::from(&error)
+    }
+}
+
+impl From<&ClientError> for BotError {
+    fn from(error: &ClientError) -> Self {
+        match error {
+            ClientError::Timeout(msg, details) => Self::RpcTimeout(format!("{}: {}", msg, details)),
+            ClientError::RateLimitExceeded => Self::RateLimited { retry_after: None },
+            ClientError::Send(msg, details) => Self::Network(format!("{}: {}", msg, details)),
+            ClientError::InsufficientFunds => Self::InsufficientFunds,
+            ClientError::SolanaClientError(e) => Self::from(e),
+            ClientError::InvalidInput(msg) => Self::InvalidInput(msg.to_string()),
+            other => Self::from_message(&other.to_string()),
+        }
+    }
+}
+
+impl From<ClientError> for BotError {
+    fn from(error: ClientError) -> Self {
+        Self::from(&error)
+    }
+}
+
+impl From<&teloxide::RequestError> for BotError {
+    fn from(error: &teloxide::RequestError) -> Self {
+        use teloxide::RequestError;
+        match error {
+            RequestError::RetryAfter(after) => Self::RateLimited { retry_after: Some(after.duration()) },
+            RequestError::Network(e) if e.is_timeout() => Self::RpcTimeout(e.to_string()),
+            RequestError::Network(e) => Self::Network(e.to_string()),
+            RequestError::Io(e) => Self::Network(e.to_string()),
+            RequestError::Api(e) => Self::Rejected(e.to_string()),
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<anyhow::Error> for BotError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::classify(&error)
+    }
+}
+
+impl From<&anyhow::Error> for BotError {
+    fn from(error: &anyhow::Error) -> Self {
+        Self::classify(error)
+    }
+}
+
+impl From<&BotError> for BotError {
+    fn from(error: &BotError) -> Self {
+        error.clone()
+    }
+}
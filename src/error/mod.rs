@@ -17,6 +17,12 @@
 //! - `SimulationError`: Transaction simulation failed.
 //! - `RateLimitExceeded`: Rate limit exceeded.
 
+pub mod bot_error;
+pub mod retry;
+
+pub use bot_error::{BotError, ErrorClass};
+pub use retry::{retry, RetryPolicy};
+
 use serde_json::Error;
 use anchor_client::solana_client::{
     client_error::ClientError as SolanaClientError, pubsub_client::PubsubClientError,
@@ -0,0 +1,102 @@
+use std::future::Future;
+use std::time::Duration;
+use colored::Colorize;
+
+use super::{BotError, ErrorClass};
+use crate::common::logger::Logger;
+
+/// How often and how fast to retry an operation that failed with a given kind of error
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+}
+
+impl RetryPolicy {
+    pub const fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            multiplier: 1.0,
+        }
+    }
+
+    /// Policy for the error an attempt failed with
+    pub fn for_error(error: &BotError) -> Self {
+        // An open breaker sheds the call; waiting here would only hold up the caller
+        if error.class() == ErrorClass::Fatal || matches!(error, BotError::CircuitOpen(_)) {
+            return Self::no_retry();
+        }
+        match error {
+            // A fresh blockhash is usually a slot away
+            BotError::BlockhashExpired | BotError::BlockhashUnavailable => Self {
+                max_attempts: 3,
+                initial_backoff: Duration::from_millis(400),
+                max_backoff: Duration::from_millis(400),
+                multiplier: 1.0,
+            },
+            BotError::RateLimited { retry_after } => Self {
+                max_attempts: 5,
+                initial_backoff: retry_after.unwrap_or(Duration::from_secs(1)),
+                max_backoff: Duration::from_secs(30),
+                multiplier: 2.0,
+            },
+            BotError::NotConfirmed(_) => Self {
+                max_attempts: 5,
+                initial_backoff: Duration::from_millis(500),
+                max_backoff: Duration::from_millis(500),
+                multiplier: 1.0,
+            },
+            BotError::RpcTimeout(_) | BotError::Network(_) => Self {
+                max_attempts: 4,
+                initial_backoff: Duration::from_millis(250),
+                max_backoff: Duration::from_secs(2),
+                multiplier: 2.0,
+            },
+            _ => Self {
+                max_attempts: 3,
+                initial_backoff: Duration::from_secs(2),
+                max_backoff: Duration::from_secs(2),
+                multiplier: 1.0,
+            },
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.powi(retry.saturating_sub(1) as i32);
+        self.initial_backoff.mul_f64(factor).min(self.max_backoff.max(self.initial_backoff))
+    }
+}
+
+/// Run `operation` until it succeeds, fails fatally, or exhausts the retry policy of the error
+/// it last failed with. The closure receives the 1-based attempt number.
+pub async fn retry<T, F, Fut>(label: &str, logger: &Logger, mut operation: F) -> Result<T, BotError>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, BotError>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(error) => {
+                let policy = RetryPolicy::for_error(&error);
+                if attempt >= policy.max_attempts {
+                    return Err(error);
+                }
+                let delay = policy.backoff(attempt);
+                logger.log(format!(
+                    "{} attempt {}/{} failed: {}; retrying in {:?}",
+                    label, attempt, policy.max_attempts, error, delay
+                ).yellow().to_string());
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod common;
+#[cfg(feature = "trading")]
 pub mod block_engine;
 pub mod dex;
 pub mod processor;
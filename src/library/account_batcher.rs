@@ -0,0 +1,180 @@
+//! Coalesces single account reads into `getMultipleAccounts` calls.
+//!
+//! The first read of a batch waits `ACCOUNT_BATCH_WINDOW_MS` for others to join, then fetches
+//! every pending key in one call (100 keys per call at most) and hands each caller its account.
+//! A batch that reaches `ACCOUNT_BATCH_MAX` keys before the window ends is fetched right away. During launch
+//! storms this turns dozens of enrichment reads into a handful of RPC calls.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use tokio::sync::oneshot;
+
+/// getMultipleAccounts accepts at most 100 keys
+const RPC_MAX_KEYS: usize = 100;
+
+/// Bulk account source behind the batcher
+#[async_trait]
+pub trait AccountFetcher: Send + Sync {
+    /// Accounts in the order of `keys`, None for accounts that do not exist
+    async fn get_multiple_accounts(&self, keys: &[Pubkey]) -> Result<Vec<Option<Account>>>;
+}
+
+#[async_trait]
+impl AccountFetcher for RpcClient {
+    async fn get_multiple_accounts(&self, keys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        RpcClient::get_multiple_accounts(self, keys).await.map_err(|e| anyhow!("getMultipleAccounts failed: {}", e))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BatchConfig {
+    /// How long the first read of a batch waits for others
+    pub window: Duration,
+    /// Keys per batch; a full batch is fetched without waiting for the window
+    pub max_batch: usize,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self { window: Duration::from_millis(5), max_batch: RPC_MAX_KEYS }
+    }
+}
+
+impl BatchConfig {
+    /// ACCOUNT_BATCH_WINDOW_MS (default 5) and ACCOUNT_BATCH_MAX (default 100)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            window: std::env::var("ACCOUNT_BATCH_WINDOW_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.window),
+            max_batch: std::env::var("ACCOUNT_BATCH_MAX")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(defaults.max_batch)
+                .max(1),
+        }
+    }
+}
+
+/// Reads requested and RPC calls made, to see how well reads coalesce
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BatchStats {
+    pub requests: u64,
+    pub rpc_calls: u64,
+}
+
+type Waiter = oneshot::Sender<Result<Option<Account>, String>>;
+
+struct Batch {
+    id: u64,
+    waiters: HashMap<Pubkey, Vec<Waiter>>,
+}
+
+struct Shared {
+    fetcher: Arc<dyn AccountFetcher>,
+    pending: Mutex<Option<Batch>>,
+    rpc_calls: AtomicU64,
+}
+
+pub struct AccountBatcher {
+    shared: Arc<Shared>,
+    config: BatchConfig,
+    next_id: AtomicU64,
+    requests: AtomicU64,
+}
+
+impl AccountBatcher {
+    pub fn new(fetcher: Arc<dyn AccountFetcher>, config: BatchConfig) -> Self {
+        Self {
+            shared: Arc::new(Shared { fetcher, pending: Mutex::new(None), rpc_calls: AtomicU64::new(0) }),
+            config,
+            next_id: AtomicU64::new(0),
+            requests: AtomicU64::new(0),
+        }
+    }
+
+    /// Read one account, batched with the reads of other callers. None when it does not exist.
+    pub async fn get_account(&self, key: Pubkey) -> Result<Option<Account>> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+
+        let (lead, full) = {
+            let mut pending = self.shared.pending.lock().unwrap();
+            let batch = pending.get_or_insert_with(|| Batch {
+                id: self.next_id.fetch_add(1, Ordering::Relaxed),
+                waiters: HashMap::new(),
+            });
+            let lead = batch.waiters.is_empty().then_some(batch.id);
+            batch.waiters.entry(key).or_default().push(tx);
+            let full = if batch.waiters.len() >= self.config.max_batch { pending.take() } else { None };
+            (lead, full)
+        };
+
+        // Fetches run detached so a caller giving up does not strand the others in its batch
+        let shared = self.shared.clone();
+        if let Some(batch) = full {
+            tokio::spawn(async move { shared.flush(batch).await });
+        } else if let Some(id) = lead {
+            let window = self.config.window;
+            tokio::spawn(async move {
+                tokio::time::sleep(window).await;
+                let batch = {
+                    let mut pending = shared.pending.lock().unwrap();
+                    match pending.as_ref() {
+                        Some(batch) if batch.id == id => pending.take(),
+                        // Already fetched because it filled up
+                        _ => None,
+                    }
+                };
+                if let Some(batch) = batch {
+                    shared.flush(batch).await;
+                }
+            });
+        }
+
+        rx.await
+            .map_err(|_| anyhow!("Account batch for {} was dropped", key))?
+            .map_err(|e| anyhow!(e))
+    }
+
+    /// Read several accounts at once, in order of `keys`
+    pub async fn get_accounts(&self, keys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        futures::future::try_join_all(keys.iter().map(|key| self.get_account(*key))).await
+    }
+
+    pub fn stats(&self) -> BatchStats {
+        BatchStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            rpc_calls: self.shared.rpc_calls.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Shared {
+    async fn flush(&self, batch: Batch) {
+        let mut waiters = batch.waiters;
+        let keys: Vec<Pubkey> = waiters.keys().copied().collect();
+        for chunk in keys.chunks(RPC_MAX_KEYS) {
+            self.rpc_calls.fetch_add(1, Ordering::Relaxed);
+            let result = self.fetcher.get_multiple_accounts(chunk).await;
+            for (index, key) in chunk.iter().enumerate() {
+                let reply = match &result {
+                    Ok(accounts) => Ok(accounts.get(index).cloned().flatten()),
+                    Err(e) => Err(e.to_string()),
+                };
+                for waiter in waiters.remove(key).unwrap_or_default() {
+                    let _ = waiter.send(reply.clone());
+                }
+            }
+        }
+    }
+}
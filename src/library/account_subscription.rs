@@ -0,0 +1,118 @@
+/*!
+# Account Cache Subscription
+
+Keeps `SUBSCRIBED_ACCOUNTS` fresh through a Yellowstone account subscription. Readers register
+the accounts they need (pool vaults, bonding curves); the subscription is updated to cover
+them, and every account update replaces the cached data, so price and liquidity reads do not
+need an RPC call per swap. When the stream drops, the whole cache is invalidated until the
+subscription is back.
+
+## Environment Variables
+
+- `ACCOUNT_CACHE_ENABLED`: Run the account subscription (default: `true`)
+- `ACCOUNT_CACHE_MAX_ACCOUNTS`: Most accounts subscribed at once (default: `500`)
+- `ACCOUNT_CACHE_IDLE_SECS`: Accounts nobody read for this long are unsubscribed (default: `600`)
+*/
+
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use colored::Colorize;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use tokio_util::sync::CancellationToken;
+use yellowstone_grpc_proto::geyser::{
+    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts,
+};
+
+use crate::common::cache::SUBSCRIBED_ACCOUNTS;
+use crate::common::logger::Logger;
+use crate::library::geyser_subscription::{run_reconnecting, GeyserSubscription};
+
+/// How often the subscribed account set is compared with what readers asked for
+const RESUBSCRIBE_INTERVAL: Duration = Duration::from_secs(5);
+
+fn subscription_request(keys: &[Pubkey]) -> SubscribeRequest {
+    // An account filter without accounts would match every account, so send none instead
+    let accounts = if keys.is_empty() {
+        Default::default()
+    } else {
+        maplit::hashmap! {
+            "CachedAccounts".to_owned() => SubscribeRequestFilterAccounts {
+                account: keys.iter().map(|k| k.to_string()).collect(),
+                owner: vec![],
+                filters: vec![],
+                nonempty_txn_signature: None,
+            }
+        }
+    };
+    SubscribeRequest {
+        accounts,
+        commitment: Some(CommitmentLevel::Confirmed as i32),
+        ..Default::default()
+    }
+}
+
+async fn run_subscription(
+    yellowstone_grpc_http: &str,
+    yellowstone_grpc_token: &str,
+    logger: &Logger,
+) -> Result<(), String> {
+    let mut subscription = GeyserSubscription::open(yellowstone_grpc_http, yellowstone_grpc_token).await?;
+
+    let mut subscribed: Option<Vec<Pubkey>> = None;
+    let mut resubscribe = tokio::time::interval(RESUBSCRIBE_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = resubscribe.tick() => {
+                let wanted = SUBSCRIBED_ACCOUNTS.wanted(Instant::now());
+                if subscribed.as_ref() != Some(&wanted) {
+                    subscription.update(subscription_request(&wanted)).await?;
+                    SUBSCRIBED_ACCOUNTS.mark_live(&wanted);
+                    logger.log(format!("Subscribed to {} cached accounts", wanted.len()));
+                    subscribed = Some(wanted);
+                }
+            }
+            update = subscription.next() => {
+                if let UpdateOneof::Account(update) = update? {
+                    crate::library::slot_lag::observe_stream_slot(update.slot);
+                    if let Some(account) = update.account {
+                        if let Ok(key) = Pubkey::try_from(account.pubkey.as_slice()) {
+                            SUBSCRIBED_ACCOUNTS.apply_update(&key, account.data, update.slot);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Run the subscription, invalidating the cache once it drops: updates are missed until the
+/// stream is back
+async fn run_until_dropped(yellowstone_grpc_http: &str, yellowstone_grpc_token: &str, logger: &Logger) -> Result<(), String> {
+    let result = run_subscription(yellowstone_grpc_http, yellowstone_grpc_token, logger).await;
+    SUBSCRIBED_ACCOUNTS.invalidate_all();
+    result
+}
+
+/// Start the account cache subscription unless disabled via ACCOUNT_CACHE_ENABLED=false
+pub fn start_account_cache_subscription(
+    yellowstone_grpc_http: String,
+    yellowstone_grpc_token: String,
+    cancel_token: CancellationToken,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let enabled = std::env::var("ACCOUNT_CACHE_ENABLED")
+        .ok()
+        .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+        .unwrap_or(true);
+    if !enabled {
+        return None;
+    }
+
+    let logger = Logger::new("[ACCOUNT-CACHE] => ".bright_cyan().to_string());
+    Some(tokio::spawn(async move {
+        run_reconnecting("Account cache subscription", &logger, &cancel_token, || {
+            run_until_dropped(&yellowstone_grpc_http, &yellowstone_grpc_token, &logger)
+        }).await;
+        SUBSCRIBED_ACCOUNTS.invalidate_all();
+        logger.log("Account cache subscription shut down.".yellow().to_string());
+    }))
+}
@@ -0,0 +1,346 @@
+/*!
+# Audit Log
+
+Append-only record of every privileged action, so operators can reconstruct what the bot did and
+when:
+
+- `trade_submitted`: a signed transaction or bundle accepted by a route, with its signer and signatures
+- `config_changed`: a runtime configuration change (thresholds, mutes), with the old and new values
+- `paused` / `resumed`: new buys paused or resumed by the operator
+- `key_used`: a wallet key loaded for signing or generated into the keystore
+- `lock_handed_over`: the trading lock handed to another instance, or released, by `instance takeover`
+- `log_recovered`: lines found unreadable at startup (e.g. a write cut short by a crash), with
+  their line numbers; the chain continues from the last readable entry
+
+Each entry is one JSON line carrying the hash of the entry before it (SHA-256 over the previous
+hash and the entry's fields), so an edited, dropped or reordered line breaks the chain.
+`audit verify [PATH]` walks the file and reports the first break; unreadable lines only pass when
+a `log_recovered` entry right after them names them. A restarted bot continues the chain where
+the file ends. Lines are written by a dedicated thread holding the file open, so recording never
+blocks the async paths that call it. The process-wide log lives in a static that is never
+dropped, so the binary calls `shutdown()` before it exits to let the writer drain its queue.
+
+## Environment Variables
+
+- `AUDIT_LOG_PATH`: Audit log file (default: `audit_log.jsonl`, empty disables writing)
+*/
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use solana_sdk::hash::hashv;
+
+use crate::common::clock::{system_clock, Clock};
+
+lazy_static! {
+    static ref SHARED_AUDIT_LOG: Arc<AuditLog> = Arc::new(AuditLog::from_env());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    TradeSubmitted,
+    ConfigChanged,
+    Paused,
+    Resumed,
+    KeyUsed,
+    LockHandedOver,
+    LogRecovered,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the chain, from 0
+    pub seq: u64,
+    /// Unix milliseconds
+    pub at: i64,
+    pub action: AuditAction,
+    /// Who or what acted, e.g. `telegram`, `api`, `zeroslot`
+    pub source: String,
+    pub details: Value,
+    /// Hash of the previous entry, empty for the first
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Hash of the entry's fields chained onto `prev_hash`
+    pub fn compute_hash(&self) -> String {
+        let fields = json!([self.seq, self.at, self.action, self.source, self.details]).to_string();
+        hashv(&[self.prev_hash.as_bytes(), fields.as_bytes()]).to_string()
+    }
+}
+
+/// Where and why a chain stopped verifying
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChainBreak {
+    /// 1-based line in the log
+    pub line: usize,
+    pub reason: String,
+}
+
+impl std::fmt::Display for ChainBreak {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Audit chain broken at line {}: {}", self.line, self.reason)
+    }
+}
+
+#[derive(Default)]
+struct ChainHead {
+    next_seq: u64,
+    last_hash: String,
+}
+
+/// What a previous run left in the log: where the chain continues, and unreadable lines no
+/// `log_recovered` entry accounts for yet
+#[derive(Default)]
+struct LogTail {
+    head: ChainHead,
+    /// The file ends in a partial line the next entry must not be appended to
+    needs_newline: bool,
+    unrecorded: Vec<usize>,
+    reason: Option<String>,
+}
+
+fn read_tail(path: &Path) -> LogTail {
+    let mut tail = LogTail::default();
+    let content = match std::fs::read(path) {
+        Ok(content) => content,
+        Err(_) => return tail,
+    };
+    tail.needs_newline = !content.is_empty() && !content.ends_with(b"\n");
+    let mut recorded = Vec::new();
+    for (index, line) in content.split(|b| *b == b'\n').enumerate() {
+        let line = String::from_utf8_lossy(line);
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<AuditEntry>(&line) {
+            Ok(entry) => {
+                if entry.action == AuditAction::LogRecovered {
+                    recorded.extend(recovered_lines(&entry));
+                }
+                tail.head.next_seq = entry.seq + 1;
+                tail.head.last_hash = entry.hash;
+            }
+            Err(e) => {
+                tail.unrecorded.push(index + 1);
+                tail.reason.get_or_insert_with(|| e.to_string());
+            }
+        }
+    }
+    tail.unrecorded.retain(|line| !recorded.contains(line));
+    tail
+}
+
+/// Line numbers a `log_recovered` entry accounts for
+fn recovered_lines(entry: &AuditEntry) -> Vec<usize> {
+    entry.details["lines"]
+        .as_array()
+        .map(|lines| lines.iter().filter_map(|l| l.as_u64()).map(|l| l as usize).collect())
+        .unwrap_or_default()
+}
+
+/// Appends lines to `path` on its own thread, holding the file open, so recording never blocks
+/// the caller on disk. A failed write is retried before the next line, keeping the chain gapless.
+fn spawn_writer(path: PathBuf, needs_newline: bool) -> (mpsc::Sender<String>, std::thread::JoinHandle<()>) {
+    let (sender, receiver) = mpsc::channel::<String>();
+    let handle = std::thread::spawn(move || {
+        let mut file: Option<std::fs::File> = None;
+        let mut pending: VecDeque<String> = VecDeque::new();
+        let mut needs_newline = needs_newline;
+        for line in receiver {
+            pending.push_back(line);
+            while let Some(line) = pending.front() {
+                let opened = match file.as_mut() {
+                    Some(file) => Ok(file),
+                    None => std::fs::OpenOptions::new().create(true).append(true).open(&path).map(|f| file.insert(f)),
+                };
+                let separator = if needs_newline { "\n" } else { "" };
+                match opened.and_then(|f| writeln!(f, "{}{}", separator, line)) {
+                    Ok(()) => {
+                        needs_newline = false;
+                        pending.pop_front();
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to append to audit log {} ({} entries pending): {}", path.display(), pending.len(), e);
+                        file = None;
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    (sender, handle)
+}
+
+pub struct AuditLog {
+    path: Option<PathBuf>,
+    clock: Arc<dyn Clock>,
+    head: Mutex<ChainHead>,
+    writer: Mutex<Option<mpsc::Sender<String>>>,
+    writer_thread: Mutex<Option<std::thread::JoinHandle<()>>>,
+}
+
+impl AuditLog {
+    /// Log appended to `path` (if any), continuing the chain a previous run left there. Unreadable
+    /// lines are reported and recorded in a `log_recovered` entry, and the chain continues from
+    /// the last readable entry.
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let tail = path.as_deref().map(read_tail).unwrap_or_default();
+        let (writer, writer_thread) = match &path {
+            Some(path) => {
+                let (writer, thread) = spawn_writer(path.clone(), tail.needs_newline);
+                (Some(writer), Some(thread))
+            }
+            None => (None, None),
+        };
+        let log = Self {
+            path,
+            clock: system_clock(),
+            head: Mutex::new(tail.head),
+            writer: Mutex::new(writer),
+            writer_thread: Mutex::new(writer_thread),
+        };
+        if !tail.unrecorded.is_empty() {
+            let reason = tail.reason.unwrap_or_default();
+            eprintln!(
+                "Audit log {} has unreadable lines {:?} ({}), continuing the chain from the last readable entry",
+                log.path.as_deref().map(|p| p.display().to_string()).unwrap_or_default(),
+                tail.unrecorded,
+                reason
+            );
+            log.record(AuditAction::LogRecovered, "audit_log", json!({ "lines": tail.unrecorded, "reason": reason }));
+        }
+        log
+    }
+
+    pub fn from_env() -> Self {
+        let path = std::env::var("AUDIT_LOG_PATH").unwrap_or_else(|_| "audit_log.jsonl".to_string());
+        Self::new(Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty()))
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Process-wide log
+    pub fn shared() -> Arc<AuditLog> {
+        SHARED_AUDIT_LOG.clone()
+    }
+
+    /// Chain an entry and queue it for the writer thread; returns without waiting for the disk
+    pub fn record(&self, action: AuditAction, source: &str, details: Value) -> AuditEntry {
+        let mut head = self.head.lock().unwrap();
+        let mut entry = AuditEntry {
+            seq: head.next_seq,
+            at: self.clock.now().timestamp_millis(),
+            action,
+            source: source.to_string(),
+            details,
+            prev_hash: head.last_hash.clone(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+
+        // Sent under the head lock, so lines reach the file in chain order
+        if let Some(writer) = self.writer.lock().unwrap().as_ref() {
+            let _ = writer.send(serde_json::to_string(&entry).unwrap_or_default());
+        }
+        head.next_seq = entry.seq + 1;
+        head.last_hash = entry.hash.clone();
+        entry
+    }
+}
+
+    /// Stop the writer once it has written every queued line; entries recorded afterwards are
+    /// chained but no longer written
+    pub fn close(&self) {
+        self.writer.lock().unwrap().take();
+        let thread = self.writer_thread.lock().unwrap().take();
+        if let Some(thread) = thread {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for AuditLog {
+    /// Let the writer finish the queued lines
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// Record a privileged action in the process-wide log
+pub fn record(action: AuditAction, source: &str, details: Value) {
+    SHARED_AUDIT_LOG.record(action, source, details);
+}
+
+/// Write out everything recorded in the process-wide log; call before the process exits
+pub fn shutdown() {
+    SHARED_AUDIT_LOG.close();
+}
+
+/// Entries of the log at `path`, in file order
+pub fn read_entries(path: &Path) -> anyhow::Result<Vec<AuditEntry>> {
+    let file = std::fs::File::open(path)?;
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            entries.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(entries)
+}
+
+/// Check every entry of the log at `path` links to the one before it; returns the number of entries.
+/// Unreadable lines pass only when the next entry is a `log_recovered` naming them.
+pub fn verify(path: &Path) -> Result<usize, ChainBreak> {
+    let content = std::fs::read(path).map_err(|e| ChainBreak { line: 0, reason: e.to_string() })?;
+    let mut expected_seq = 0;
+    let mut last_hash = String::new();
+    let mut unreadable: Vec<(usize, String)> = Vec::new();
+    for (index, line) in content.split(|b| *b == b'\n').enumerate() {
+        let broken = |reason: String| ChainBreak { line: index + 1, reason };
+        let line = String::from_utf8_lossy(line);
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: AuditEntry = match serde_json::from_str(&line) {
+            Ok(entry) => entry,
+            Err(e) => {
+                unreadable.push((index + 1, format!("not an audit entry: {}", e)));
+                continue;
+            }
+        };
+        if let Some((line, reason)) = unreadable.first() {
+            let recovered = recovered_lines(&entry);
+            let accounted = entry.action == AuditAction::LogRecovered && unreadable.iter().all(|(l, _)| recovered.contains(l));
+            if !accounted {
+                return Err(ChainBreak { line: *line, reason: reason.clone() });
+            }
+            unreadable.clear();
+        }
+        if entry.seq != expected_seq {
+            return Err(broken(format!("sequence {} where {} was expected", entry.seq, expected_seq)));
+        }
+        if entry.prev_hash != last_hash {
+            return Err(broken("does not link to the previous entry".to_string()));
+        }
+        if entry.compute_hash() != entry.hash {
+            return Err(broken("contents do not match the entry hash".to_string()));
+        }
+        expected_seq += 1;
+        last_hash = entry.hash;
+    }
+    if let Some((line, reason)) = unreadable.into_iter().next() {
+        return Err(ChainBreak { line, reason });
+    }
+    Ok(expected_seq as usize)
+}
@@ -1,6 +1,17 @@
+//! Recent blockhash kept warm for the hot path.
+//!
+//! A background task refreshes the blockhash and its last valid block height every
+//! `BLOCKHASH_REFRESH_MS`, so building a transaction only reads the cache and never waits on an
+//! RPC round trip. A cached blockhash older than `BLOCKHASH_MAX_AGE_MS` counts as stale and is
+//! not handed out. Hits, stale reads, refresh failures and the cache age are exported on
+//! `/metrics`.
+
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::hash::Hash;
 use solana_client::rpc_client::RpcClient;
 use anyhow::{Result, anyhow};
@@ -8,27 +19,136 @@ use colored::Colorize;
 use lazy_static::lazy_static;
 use crate::common::logger::Logger;
 
-// Global state for latest blockhash and timestamp
-lazy_static! {
-    static ref LATEST_BLOCKHASH: Arc<RwLock<Option<Hash>>> = Arc::new(RwLock::new(None));
-    static ref BLOCKHASH_LAST_UPDATED: Arc<RwLock<Option<Instant>>> = Arc::new(RwLock::new(None));
+/// A blockhash as fetched, with the last block height at which transactions using it land
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CachedBlockhash {
+    pub hash: Hash,
+    pub last_valid_block_height: u64,
+    pub fetched_at: Instant,
+}
+
+/// Counters of the blockhash cache
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BlockhashStats {
+    /// Reads served from the cache
+    pub hits: u64,
+    /// Reads that found the cache stale or empty
+    pub stale_reads: u64,
+    pub refreshes: u64,
+    pub refresh_failures: u64,
+    /// Blockhashes fetched directly because the cache was stale
+    pub rpc_fallbacks: u64,
+    /// Age of the cached blockhash, None before the first refresh
+    pub age_ms: Option<u64>,
+    pub last_valid_block_height: Option<u64>,
+    /// Duration of the last successful refresh
+    pub last_refresh_ms: u64,
+}
+
+/// Cache state with its counters, independent of the RPC client so it can be exercised alone
+pub struct BlockhashCache {
+    max_age: Duration,
+    current: std::sync::RwLock<Option<CachedBlockhash>>,
+    hits: AtomicU64,
+    stale_reads: AtomicU64,
+    refreshes: AtomicU64,
+    refresh_failures: AtomicU64,
+    rpc_fallbacks: AtomicU64,
+    last_refresh_ms: AtomicU64,
+}
+
+impl BlockhashCache {
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            current: std::sync::RwLock::new(None),
+            hits: AtomicU64::new(0),
+            stale_reads: AtomicU64::new(0),
+            refreshes: AtomicU64::new(0),
+            refresh_failures: AtomicU64::new(0),
+            rpc_fallbacks: AtomicU64::new(0),
+            last_refresh_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Store a freshly fetched blockhash
+    pub fn store(&self, hash: Hash, last_valid_block_height: u64, fetched_at: Instant) {
+        *self.current.write().unwrap() = Some(CachedBlockhash { hash, last_valid_block_height, fetched_at });
+    }
+
+    /// The cached blockhash if it is younger than the max age at `now`
+    pub fn get(&self, now: Instant) -> Option<CachedBlockhash> {
+        let current = *self.current.read().unwrap();
+        match current {
+            Some(cached) if now.saturating_duration_since(cached.fetched_at) <= self.max_age => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(cached)
+            }
+            _ => {
+                self.stale_reads.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    pub fn record_refresh(&self, took: Duration) {
+        self.refreshes.fetch_add(1, Ordering::Relaxed);
+        self.last_refresh_ms.store(took.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_refresh_failure(&self) {
+        self.refresh_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_rpc_fallback(&self) {
+        self.rpc_fallbacks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self, now: Instant) -> BlockhashStats {
+        let current = *self.current.read().unwrap();
+        BlockhashStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            stale_reads: self.stale_reads.load(Ordering::Relaxed),
+            refreshes: self.refreshes.load(Ordering::Relaxed),
+            refresh_failures: self.refresh_failures.load(Ordering::Relaxed),
+            rpc_fallbacks: self.rpc_fallbacks.load(Ordering::Relaxed),
+            age_ms: current.map(|c| now.saturating_duration_since(c.fetched_at).as_millis() as u64),
+            last_valid_block_height: current.map(|c| c.last_valid_block_height),
+            last_refresh_ms: self.last_refresh_ms.load(Ordering::Relaxed),
+        }
+    }
 }
 
-const BLOCKHASH_STALENESS_THRESHOLD: Duration = Duration::from_secs(10);
-const UPDATE_INTERVAL: Duration = Duration::from_millis(300);
+fn env_millis(name: &str, default: u64) -> Duration {
+    Duration::from_millis(
+        std::env::var(name)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default),
+    )
+}
+
+// Global blockhash cache shared by every transaction builder
+lazy_static! {
+    static ref CACHE: BlockhashCache = BlockhashCache::new(env_millis("BLOCKHASH_MAX_AGE_MS", 10_000));
+    static ref UPDATE_INTERVAL: Duration = env_millis("BLOCKHASH_REFRESH_MS", 300);
+}
 
 pub struct BlockhashProcessor {
     rpc_client: Arc<RpcClient>,
     logger: Logger,
+    /// Serializes direct fetches so a burst of stale reads costs one RPC call
+    fallback_lock: Mutex<()>,
 }
 
 impl BlockhashProcessor {
     pub async fn new(rpc_client: Arc<RpcClient>) -> Result<Self> {
         let logger = Logger::new("[BLOCKHASH-PROCESSOR] => ".cyan().to_string());
-        
+
         Ok(Self {
             rpc_client,
             logger,
+            fallback_lock: Mutex::new(()),
         })
     }
 
@@ -40,61 +160,60 @@ impl BlockhashProcessor {
         let logger = self.logger.clone();
 
         tokio::spawn(async move {
+            let mut was_failing = false;
             loop {
-                tokio::select! {
-                    _ = cancel_token.cancelled() => {
-                        logger.log("Blockhash processor received shutdown signal.".yellow().to_string());
-                        break;
-                    }
-                    _ = tokio::time::sleep(UPDATE_INTERVAL) => {
-                        match Self::update_blockhash_from_rpc(&rpc_client).await {
-                    Ok(blockhash) => {
-                        // Update global blockhash
-                        let mut latest = LATEST_BLOCKHASH.write().await;
-                        *latest = Some(blockhash);
-                        
-                        // Update timestamp
-                        let mut last_updated = BLOCKHASH_LAST_UPDATED.write().await;
-                        *last_updated = Some(Instant::now());
-                        
-                        // logger.log(format!("Updated latest blockhash: {}", blockhash));
+                let started = Instant::now();
+                match Self::fetch_blockhash(rpc_client.clone()).await {
+                    Ok((blockhash, last_valid_block_height)) => {
+                        CACHE.store(blockhash, last_valid_block_height, Instant::now());
+                        CACHE.record_refresh(started.elapsed());
+                        if was_failing {
+                            logger.log("Blockhash refresh recovered".green().to_string());
+                            was_failing = false;
+                        }
                     }
                     Err(e) => {
+                        CACHE.record_refresh_failure();
+                        was_failing = true;
                         logger.log(format!("Error getting latest blockhash: {}", e).red().to_string());
                     }
                 }
+
+                tokio::select! {
+                    _ = cancel_token.cancelled() => {
+                        logger.log("Blockhash processor received shutdown signal.".yellow().to_string());
+                        break;
                     }
+                    _ = tokio::time::sleep(UPDATE_INTERVAL.saturating_sub(started.elapsed())) => {}
                 }
+            }
             logger.log("Blockhash processor shut down.".yellow().to_string());
         })
     }
 
-    async fn update_blockhash_from_rpc(rpc_client: &RpcClient) -> Result<Hash> {
-        rpc_client.get_latest_blockhash()
-            .map_err(|e| anyhow!("Failed to get blockhash from RPC: {}", e))
+    /// Blockhash and last valid block height from the RPC node, off the async workers
+    async fn fetch_blockhash(rpc_client: Arc<RpcClient>) -> Result<(Hash, u64)> {
+        tokio::task::spawn_blocking(move || {
+            rpc_client.get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+        })
+        .await
+        .map_err(|e| anyhow!("Blockhash fetch task failed: {}", e))?
+        .map_err(|e| anyhow!("Failed to get blockhash from RPC: {}", e))
     }
 
-    /// Update the latest blockhash and its timestamp
-    async fn update_blockhash(hash: Hash) {
-        let mut latest = LATEST_BLOCKHASH.write().await;
-        *latest = Some(hash);
-        
-        let mut last_updated = BLOCKHASH_LAST_UPDATED.write().await;
-        *last_updated = Some(Instant::now());
+    /// Get the latest cached blockhash, None when it is stale or was never fetched
+    pub async fn get_latest_blockhash() -> Option<Hash> {
+        CACHE.get(Instant::now()).map(|cached| cached.hash)
     }
 
-    /// Get the latest cached blockhash with freshness check
-    pub async fn get_latest_blockhash() -> Option<Hash> {
-        // Check if blockhash is stale
-        let last_updated = BLOCKHASH_LAST_UPDATED.read().await;
-        if let Some(instant) = *last_updated {
-            if instant.elapsed() > BLOCKHASH_STALENESS_THRESHOLD {
-                return None;
-            }
-        }
-        
-        let latest = LATEST_BLOCKHASH.read().await;
-        *latest
+    /// Latest cached blockhash with its last valid block height, None when stale
+    pub fn get_latest_blockhash_with_height() -> Option<(Hash, u64)> {
+        CACHE.get(Instant::now()).map(|cached| (cached.hash, cached.last_valid_block_height))
+    }
+
+    /// Counters and age of the global cache
+    pub fn stats() -> BlockhashStats {
+        CACHE.stats(Instant::now())
     }
 
     /// Get a fresh blockhash, falling back to RPC if necessary
@@ -102,13 +221,18 @@ impl BlockhashProcessor {
         if let Some(hash) = Self::get_latest_blockhash().await {
             return Ok(hash);
         }
-        
+
+        let _guard = self.fallback_lock.lock().await;
+        // Another caller may have fetched one while this one waited
+        if let Some(hash) = Self::get_latest_blockhash().await {
+            return Ok(hash);
+        }
+
         // Fallback to RPC if cached blockhash is stale or missing
         self.logger.log("Cached blockhash is stale or missing, falling back to RPC...".yellow().to_string());
-        let new_hash = self.rpc_client.get_latest_blockhash()
-            .map_err(|e| anyhow!("Failed to get blockhash from RPC: {}", e))?;
-        
-        Self::update_blockhash(new_hash).await;
+        CACHE.record_rpc_fallback();
+        let (new_hash, last_valid_block_height) = Self::fetch_blockhash(self.rpc_client.clone()).await?;
+        CACHE.store(new_hash, last_valid_block_height, Instant::now());
         Ok(new_hash)
     }
-} 
\ No newline at end of file
+}
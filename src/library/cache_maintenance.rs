@@ -3,7 +3,7 @@ use tokio::time;
 use colored::Colorize;
 
 use crate::common::logger::Logger;
-use crate::common::cache::{TOKEN_ACCOUNT_CACHE, TOKEN_MINT_CACHE};
+use crate::common::cache::{LOOKUP_TABLE_CACHE, TOKEN_ACCOUNT_CACHE, TOKEN_MINT_CACHE};
 
 /// CacheMaintenanceService handles periodic cleanup of expired cache entries
 pub struct CacheMaintenanceService {
@@ -53,11 +53,17 @@ impl CacheMaintenanceService {
         TOKEN_MINT_CACHE.clear_expired();
         let token_mint_count_after = TOKEN_MINT_CACHE.size();
         
+        // Clean up address lookup table cache
+        let lookup_table_count_before = LOOKUP_TABLE_CACHE.size();
+        LOOKUP_TABLE_CACHE.clear_expired();
+        let lookup_table_count_after = LOOKUP_TABLE_CACHE.size();
+        
         // Log cleanup results
         self.logger.log(format!(
-            "Cache cleanup complete - Token accounts: {} -> {}, Token mints: {} -> {}",
+            "Cache cleanup complete - Token accounts: {} -> {}, Token mints: {} -> {}, Lookup tables: {} -> {}",
             token_account_count_before, token_account_count_after,
-            token_mint_count_before, token_mint_count_after
+            token_mint_count_before, token_mint_count_after,
+            lookup_table_count_before, lookup_table_count_after
         ));
     }
 }
@@ -0,0 +1,174 @@
+//! Candlestick charts of the recorded price history (see `price_history`), rendered to PNG on
+//! demand for `/chart`. The history keeps the last price and the volume of each bucket, so
+//! candles are built from bucket prices and their wicks only span prices seen at bucket ends.
+//!
+//! Charts are drawn without text so no font has to be installed; the Telegram caption carries
+//! the numbers.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::common::price_history::{PriceHistory, PricePoint};
+use crate::library::ohlcv::{Candle, CandleSeries};
+
+pub const CHART_WIDTH: u32 = 800;
+pub const CHART_HEIGHT: u32 = 450;
+/// Candles a chart aims for, whatever its window
+const TARGET_CANDLES: i64 = 60;
+/// Longest window `/chart` accepts
+pub const MAX_WINDOW_DAYS: i64 = 7;
+
+const BACKGROUND: [u8; 3] = [0x13, 0x17, 0x22];
+const GRID: [u8; 3] = [0x2a, 0x2e, 0x39];
+const UP: [u8; 3] = [0x26, 0xa6, 0x9a];
+const DOWN: [u8; 3] = [0xef, 0x53, 0x50];
+const UP_VOLUME: [u8; 3] = [0x1d, 0x4f, 0x4b];
+const DOWN_VOLUME: [u8; 3] = [0x6b, 0x2c, 0x2f];
+
+/// Candle width for a window: about TARGET_CANDLES candles, never finer than the history
+pub fn candle_interval(window: Duration, resolution: Duration) -> Duration {
+    Duration::seconds((window.num_seconds() / TARGET_CANDLES).max(resolution.num_seconds()).max(1))
+}
+
+/// Aggregate history buckets, oldest first, into candles
+pub fn candles_from_points(points: &[PricePoint], interval: Duration) -> Vec<Candle> {
+    let mut series = CandleSeries::new(interval, points.len().max(1));
+    for point in points {
+        if let Some(at) = DateTime::from_timestamp_millis(point.timestamp_ms) {
+            series.update(at, point.price, point.buy_volume + point.sell_volume);
+        }
+    }
+    series.candles().cloned().collect()
+}
+
+/// `15m`, `4h`, `1d` as written in commands
+pub fn format_window(window: Duration) -> String {
+    let (seconds, minutes) = (window.num_seconds(), window.num_minutes());
+    if seconds % 60 != 0 {
+        format!("{}s", seconds)
+    } else if minutes % (24 * 60) == 0 {
+        format!("{}d", minutes / (24 * 60))
+    } else if minutes % 60 == 0 {
+        format!("{}h", minutes / 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// The chart of a mint's last `window` of history, with a caption summarizing it
+pub fn render_history_chart(history: &PriceHistory, mint: &str, window: Duration, now: DateTime<Utc>) -> Result<(Vec<u8>, String)> {
+    let window = window.min(Duration::days(MAX_WINDOW_DAYS));
+    let points = history.points(mint, (now - window).timestamp_millis(), now.timestamp_millis());
+    let interval = candle_interval(window, history.resolution());
+    let candles = candles_from_points(&points, interval);
+    let (first, last) = match (candles.first(), candles.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Err(anyhow!("No price history recorded for {} in the last {}", mint, format_window(window))),
+    };
+    let high = candles.iter().map(|c| c.high).fold(f64::NEG_INFINITY, f64::max);
+    let low = candles.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
+    let volume: f64 = candles.iter().map(|c| c.volume).sum();
+    let caption = format!(
+        "📈 {} ({})\nO {:.10} H {:.10} L {:.10} C {:.10} ({:+.1}%)\n{:.2} SOL volume, {} candles of {}",
+        mint,
+        format_window(window),
+        first.open,
+        high,
+        low,
+        last.close,
+        (last.close / first.open - 1.0) * 100.0,
+        volume,
+        candles.len(),
+        format_window(interval),
+    );
+    Ok((render_candles(&candles, CHART_WIDTH, CHART_HEIGHT)?, caption))
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        let pixels = BACKGROUND.iter().copied().cycle().take((width * height * 3) as usize).collect();
+        Self { width, height, pixels }
+    }
+
+    /// Fill the rectangle between two corners, clipped to the canvas
+    fn fill(&mut self, x0: i64, y0: i64, x1: i64, y1: i64, color: [u8; 3]) {
+        let (x0, x1) = (x0.min(x1).max(0), x0.max(x1).min(self.width as i64 - 1));
+        let (y0, y1) = (y0.min(y1).max(0), y0.max(y1).min(self.height as i64 - 1));
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let i = ((y as u32 * self.width + x as u32) * 3) as usize;
+                self.pixels[i..i + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        let mut png = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut png, self.width, self.height);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&self.pixels)?;
+            writer.finish()?;
+        }
+        Ok(png)
+    }
+}
+
+/// Candles over a volume panel, as PNG
+pub fn render_candles(candles: &[Candle], width: u32, height: u32) -> Result<Vec<u8>> {
+    if candles.is_empty() {
+        return Err(anyhow!("no candles to draw"));
+    }
+    if width < 100 || height < 100 {
+        return Err(anyhow!("chart too small"));
+    }
+    let mut canvas = Canvas::new(width, height);
+    let pad = 12i64;
+    let (w, h) = (width as i64, height as i64);
+    let price_bottom = pad + (h - 2 * pad) * 3 / 4;
+    let volume_top = price_bottom + pad;
+
+    let mut low = candles.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
+    let mut high = candles.iter().map(|c| c.high).fold(f64::NEG_INFINITY, f64::max);
+    if high - low <= high.abs() * 1e-9 {
+        // A flat chart still needs a range
+        low *= 0.99;
+        high *= 1.01;
+    }
+    let max_volume = candles.iter().map(|c| c.volume).fold(0.0, f64::max);
+    let price_y = |price: f64| price_bottom - ((price - low) / (high - low) * (price_bottom - pad) as f64).round() as i64;
+    let volume_y = |volume: f64| {
+        if max_volume > 0.0 {
+            h - pad - (volume / max_volume * (h - pad - volume_top) as f64).round() as i64
+        } else {
+            h - pad
+        }
+    };
+
+    for line in 0..=4 {
+        let y = pad + (price_bottom - pad) * line / 4;
+        canvas.fill(pad, y, w - pad, y, GRID);
+    }
+    canvas.fill(pad, volume_top - pad / 2, w - pad, volume_top - pad / 2, GRID);
+
+    let slot = (w - 2 * pad) as f64 / candles.len() as f64;
+    let body = ((slot * 0.6).round() as i64).max(1);
+    for (i, candle) in candles.iter().enumerate() {
+        let center = pad + (slot * (i as f64 + 0.5)).round() as i64;
+        let left = center - body / 2;
+        let rising = candle.close >= candle.open;
+        let (color, volume_color) = if rising { (UP, UP_VOLUME) } else { (DOWN, DOWN_VOLUME) };
+        canvas.fill(center, price_y(candle.high), center, price_y(candle.low), color);
+        canvas.fill(left, price_y(candle.open), left + body - 1, price_y(candle.close), color);
+        canvas.fill(left, volume_y(candle.volume), left + body - 1, h - pad, volume_color);
+    }
+    canvas.encode()
+}
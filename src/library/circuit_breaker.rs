@@ -0,0 +1,208 @@
+//! Circuit breakers around external services (RPC, ZeroSlot, Telegram, Jupiter).
+//!
+//! After `CIRCUIT_BREAKER_FAILURE_THRESHOLD` consecutive failures a breaker opens and calls fail
+//! fast with `BotError::CircuitOpen` instead of waiting on a dead service. Once
+//! `CIRCUIT_BREAKER_OPEN_SECS` have passed a single probe call is let through: success closes the
+//! breaker, failure re-opens it for another period. State is reported by `/healthz` and
+//! `/metrics` on the status server.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use serde::Serialize;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::error::BotError;
+
+lazy_static! {
+    static ref BREAKERS: DashMap<String, Arc<CircuitBreaker>> = DashMap::new();
+    static ref FAILURE_THRESHOLD: u32 = std::env::var("CIRCUIT_BREAKER_FAILURE_THRESHOLD")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .unwrap_or(5);
+    static ref OPEN_DURATION: Duration = Duration::from_secs(
+        std::env::var("CIRCUIT_BREAKER_OPEN_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(30),
+    );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Calls pass through
+    Closed,
+    /// Calls are rejected until the open period ends
+    Open,
+    /// A single recovery probe is in flight
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+
+    /// Numeric value for the metrics gauge
+    pub fn as_gauge(&self) -> u8 {
+        match self {
+            CircuitState::Closed => 0,
+            CircuitState::HalfOpen => 1,
+            CircuitState::Open => 2,
+        }
+    }
+}
+
+/// Point-in-time view of a breaker for health and metrics reporting
+#[derive(Clone, Debug, Serialize)]
+pub struct BreakerSnapshot {
+    pub name: String,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    pub total_failures: u64,
+    /// Calls shed while the breaker was open
+    pub rejected: u64,
+    pub times_opened: u64,
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<DateTime<Utc>>,
+    total_failures: u64,
+    rejected: u64,
+    times_opened: u64,
+}
+
+pub struct CircuitBreaker {
+    name: String,
+    failure_threshold: u32,
+    open_duration: Duration,
+    clock: Arc<dyn Clock>,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: &str, failure_threshold: u32, open_duration: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            name: name.to_string(),
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            clock,
+            inner: Mutex::new(BreakerInner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                total_failures: 0,
+                rejected: 0,
+                times_opened: 0,
+            }),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Whether a call may go through now. An open breaker whose period has ended admits one
+    /// probe and moves to half-open; everything else while open or probing is rejected. A probe
+    /// that never reports back (its future was dropped) is replaced after another period.
+    pub fn try_acquire(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state == CircuitState::Closed {
+            return true;
+        }
+        let now = self.clock.now();
+        let elapsed = inner.opened_at
+            .and_then(|since| (now - since).to_std().ok())
+            .unwrap_or_default();
+        if elapsed >= self.open_duration {
+            inner.state = CircuitState::HalfOpen;
+            inner.opened_at = Some(now);
+            true
+        } else {
+            inner.rejected += 1;
+            false
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+    }
+
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+        inner.total_failures += 1;
+        let trip = inner.state == CircuitState::HalfOpen
+            || (inner.state == CircuitState::Closed && inner.consecutive_failures >= self.failure_threshold);
+        if trip {
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(self.clock.now());
+            inner.times_opened += 1;
+        }
+    }
+
+    /// Run `operation` through the breaker, failing fast with `BotError::CircuitOpen` while open.
+    /// Only transient failures count against the service; a fatal error (bad input, rejected
+    /// transaction) means the service answered.
+    pub async fn call<T, E, Fut>(&self, operation: Fut) -> Result<T, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+        E: From<BotError>,
+        for<'a> &'a E: Into<BotError>,
+    {
+        if !self.try_acquire() {
+            return Err(E::from(BotError::CircuitOpen(self.name.clone())));
+        }
+        let result = operation.await;
+        match &result {
+            Err(e) if Into::<BotError>::into(e).is_transient() => self.record_failure(),
+            _ => self.record_success(),
+        }
+        result
+    }
+
+    pub fn snapshot(&self) -> BreakerSnapshot {
+        let inner = self.inner.lock().unwrap();
+        BreakerSnapshot {
+            name: self.name.clone(),
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+            total_failures: inner.total_failures,
+            rejected: inner.rejected,
+            times_opened: inner.times_opened,
+        }
+    }
+}
+
+/// Shared breaker for a service, created with the configured threshold on first use
+pub fn breaker(name: &str) -> Arc<CircuitBreaker> {
+    BREAKERS
+        .entry(name.to_string())
+        .or_insert_with(|| Arc::new(CircuitBreaker::new(name, *FAILURE_THRESHOLD, *OPEN_DURATION, system_clock())))
+        .clone()
+}
+
+/// Snapshots of every registered breaker, sorted by name
+pub fn snapshots() -> Vec<BreakerSnapshot> {
+    let mut snapshots: Vec<BreakerSnapshot> = BREAKERS.iter().map(|entry| entry.value().snapshot()).collect();
+    snapshots.sort_by(|a, b| a.name.cmp(&b.name));
+    snapshots
+}
@@ -0,0 +1,293 @@
+//! Streams normalized swap events to Kafka or NATS so downstream data pipelines
+//! can consume the bot's firehose without touching the gRPC stream themselves.
+//!
+//! Events are published as versioned JSON documents (see `SWAP_EVENT_SCHEMA`).
+//! Publishing never blocks the hot path: events go through a bounded channel and
+//! are dropped (and counted) when the exporter falls behind.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use serde::Serialize;
+use tokio::sync::{mpsc, OnceCell};
+use tokio_util::sync::CancellationToken;
+
+use crate::common::logger::Logger;
+use crate::processor::transaction_parser::{ParsedData, SwapType};
+
+pub const SWAP_EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// JSON schema describing the payload of every exported swap event
+pub const SWAP_EVENT_SCHEMA: &str = r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "title": "SwapEvent",
+  "type": "object",
+  "required": ["schema_version", "signature", "slot", "timestamp", "dex", "side", "mint", "signer"],
+  "properties": {
+    "schema_version": { "type": "integer" },
+    "signature": { "type": "string" },
+    "slot": { "type": "integer" },
+    "timestamp": { "type": "integer", "description": "unix seconds" },
+    "dex": { "type": "string" },
+    "side": { "type": "string", "enum": ["buy", "sell", "add_liquidity", "remove_liquidity", "pool_create"] },
+    "mint": { "type": "string" },
+    "signer": { "type": "string" },
+    "pool": { "type": ["string", "null"] },
+    "sol_amount": { "type": ["number", "null"] },
+    "token_amount": { "type": ["number", "null"] },
+    "price_sol": { "type": ["number", "null"] },
+    "liquidity_sol": { "type": ["number", "null"] },
+    "bonding_curve_progress": { "type": ["number", "null"], "description": "pump.fun curve completion %" }
+  }
+}"#;
+
+const CHANNEL_CAPACITY: usize = 10_000;
+const BATCH_SIZE: usize = 100;
+const FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+static EXPORTER: OnceCell<mpsc::Sender<SwapEvent>> = OnceCell::const_new();
+static DROPPED_EVENTS: AtomicU64 = AtomicU64::new(0);
+static EXPORTED_EVENTS: AtomicU64 = AtomicU64::new(0);
+
+/// Wire format of an exported swap
+#[derive(Debug, Clone, Serialize)]
+pub struct SwapEvent {
+    pub schema_version: u32,
+    pub signature: String,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub dex: String,
+    pub side: String,
+    pub mint: String,
+    pub signer: String,
+    pub pool: Option<String>,
+    pub sol_amount: Option<f64>,
+    pub token_amount: Option<f64>,
+    pub price_sol: Option<f64>,
+    pub liquidity_sol: Option<f64>,
+    pub bonding_curve_progress: Option<f64>,
+}
+
+impl From<&ParsedData> for SwapEvent {
+    fn from(data: &ParsedData) -> Self {
+        let side = match data.swap_type {
+            SwapType::Buy => "buy",
+            SwapType::Sell => "sell",
+            SwapType::AddLiquidity => "add_liquidity",
+            SwapType::RemoveLiquidity => "remove_liquidity",
+            SwapType::PoolCreate => "pool_create",
+        };
+        Self {
+            schema_version: SWAP_EVENT_SCHEMA_VERSION,
+            signature: data.signature.clone(),
+            slot: data.slot,
+            timestamp: data.timestamp,
+            dex: data.dex_name.clone(),
+            side: side.to_string(),
+            mint: data.token_mint.to_string(),
+            signer: data.signer.to_string(),
+            pool: data.pool_id.clone(),
+            sol_amount: data.sol_amount,
+            token_amount: data.token_amount,
+            price_sol: data.token_price,
+            liquidity_sol: data.liquidity,
+            bonding_curve_progress: data.bonding_curve_progress,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportBackend {
+    Kafka,
+    Nats,
+    Disabled,
+}
+
+/// Event export configuration
+#[derive(Debug, Clone)]
+pub struct EventExportConfig {
+    pub backend: ExportBackend,
+    /// Comma separated broker list (Kafka) or server URL (NATS)
+    pub servers: String,
+    /// Kafka topic or NATS subject
+    pub topic: String,
+}
+
+impl EventExportConfig {
+    pub fn set_from_env() -> Self {
+        let backend = match std::env::var("EVENT_EXPORT_BACKEND")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "kafka" => ExportBackend::Kafka,
+            "nats" => ExportBackend::Nats,
+            _ => ExportBackend::Disabled,
+        };
+        let servers = std::env::var("EVENT_EXPORT_SERVERS").unwrap_or_default();
+        let topic = std::env::var("EVENT_EXPORT_TOPIC").unwrap_or_else(|_| "solana.swaps".to_string());
+
+        Self { backend, servers, topic }
+    }
+}
+
+enum EventSink {
+    Kafka(rskafka::client::partition::PartitionClient),
+    Nats(async_nats::Client, String),
+}
+
+impl EventSink {
+    async fn connect(config: &EventExportConfig) -> Result<Self> {
+        match config.backend {
+            ExportBackend::Kafka => {
+                let brokers: Vec<String> = config.servers
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                if brokers.is_empty() {
+                    return Err(anyhow!("EVENT_EXPORT_SERVERS must list at least one Kafka broker"));
+                }
+                let client = rskafka::client::ClientBuilder::new(brokers).build().await?;
+                let partition = client
+                    .partition_client(
+                        config.topic.clone(),
+                        0,
+                        rskafka::client::partition::UnknownTopicHandling::Retry,
+                    )
+                    .await?;
+                Ok(EventSink::Kafka(partition))
+            }
+            ExportBackend::Nats => {
+                let client = async_nats::connect(config.servers.as_str()).await?;
+                Ok(EventSink::Nats(client, config.topic.clone()))
+            }
+            ExportBackend::Disabled => Err(anyhow!("Event export is disabled")),
+        }
+    }
+
+    async fn send_batch(&self, events: &[SwapEvent]) -> Result<()> {
+        match self {
+            EventSink::Kafka(partition) => {
+                let mut records = Vec::with_capacity(events.len());
+                for event in events {
+                    records.push(rskafka::record::Record {
+                        key: Some(event.mint.as_bytes().to_vec()),
+                        value: Some(serde_json::to_vec(event)?),
+                        headers: BTreeMap::from([(
+                            "schema_version".to_string(),
+                            SWAP_EVENT_SCHEMA_VERSION.to_string().into_bytes(),
+                        )]),
+                        timestamp: chrono::Utc::now(),
+                    });
+                }
+                partition
+                    .produce(records, rskafka::client::partition::Compression::default())
+                    .await?;
+            }
+            EventSink::Nats(client, subject) => {
+                for event in events {
+                    let payload = serde_json::to_vec(event)?;
+                    client.publish(subject.clone(), payload.into()).await?;
+                }
+                client.flush().await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Queue a parsed swap for export. No-op when the exporter is not running.
+pub fn publish(data: &ParsedData) {
+    if let Some(tx) = EXPORTER.get() {
+        if tx.try_send(SwapEvent::from(data)).is_err() {
+            DROPPED_EVENTS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Events queued and not yet taken by the exporter
+pub fn queue_depth() -> usize {
+    EXPORTER.get().map(|tx| tx.max_capacity() - tx.capacity()).unwrap_or(0)
+}
+
+/// (exported, dropped) event counters since startup
+pub fn export_stats() -> (u64, u64) {
+    (
+        EXPORTED_EVENTS.load(Ordering::Relaxed),
+        DROPPED_EVENTS.load(Ordering::Relaxed),
+    )
+}
+
+/// Start the event exporter in a background task if a backend is configured
+pub async fn start_event_export(cancel_token: CancellationToken) -> Option<tokio::task::JoinHandle<()>> {
+    let logger = Logger::new("[EVENT-EXPORT] => ".bright_blue().to_string());
+    let config = EventExportConfig::set_from_env();
+    if config.backend == ExportBackend::Disabled {
+        return None;
+    }
+
+    let sink = match EventSink::connect(&config).await {
+        Ok(sink) => sink,
+        Err(e) => {
+            logger.log(format!("Failed to connect event export backend: {}", e).red().to_string());
+            return None;
+        }
+    };
+
+    let (tx, mut rx) = mpsc::channel::<SwapEvent>(CHANNEL_CAPACITY);
+    if EXPORTER.set(tx).is_err() {
+        logger.log("Event exporter already running".yellow().to_string());
+        return None;
+    }
+
+    logger.log(format!(
+        "Exporting swap events to {:?} topic '{}' (schema v{})",
+        config.backend, config.topic, SWAP_EVENT_SCHEMA_VERSION
+    ).green().to_string());
+
+    Some(tokio::spawn(async move {
+        let mut batch: Vec<SwapEvent> = Vec::with_capacity(BATCH_SIZE);
+        let mut flush_interval = tokio::time::interval(FLUSH_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    if !batch.is_empty() {
+                        let _ = sink.send_batch(&batch).await;
+                    }
+                    logger.log("Event exporter shut down.".yellow().to_string());
+                    break;
+                }
+                maybe_event = rx.recv() => {
+                    match maybe_event {
+                        Some(event) => {
+                            batch.push(event);
+                            if batch.len() < BATCH_SIZE {
+                                continue;
+                            }
+                        }
+                        None => break,
+                    }
+                }
+                _ = flush_interval.tick() => {}
+            }
+
+            if batch.is_empty() {
+                continue;
+            }
+            match sink.send_batch(&batch).await {
+                Ok(_) => {
+                    EXPORTED_EVENTS.fetch_add(batch.len() as u64, Ordering::Relaxed);
+                }
+                Err(e) => {
+                    DROPPED_EVENTS.fetch_add(batch.len() as u64, Ordering::Relaxed);
+                    logger.log(format!("Failed to export {} events: {}", batch.len(), e).red().to_string());
+                }
+            }
+            batch.clear();
+        }
+    }))
+}
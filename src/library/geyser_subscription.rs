@@ -0,0 +1,110 @@
+/*!
+# Geyser Subscription
+
+One Yellowstone gRPC subscription with the connect, ping and reconnect handling shared by the
+stream consumers that send their own filters: the balance tracker (wallet token accounts), the
+token program monitor (tracked mints), the graduation sniper (pump.fun) and the account cache.
+Consumers that only need transactions for a filter use a `TransactionSource` instead.
+
+A subscription answers server pings itself, so `next` only yields the updates a consumer asked
+for. `run_reconnecting` reopens a dropped subscription after a short delay until cancelled.
+*/
+
+use std::any::Any;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use colored::Colorize;
+use futures::channel::mpsc::SendError;
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use tokio_util::sync::CancellationToken;
+use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient};
+use yellowstone_grpc_proto::geyser::{
+    subscribe_update::UpdateOneof, SubscribeRequest, SubscribeRequestPing, SubscribeUpdate,
+};
+
+use crate::common::logger::Logger;
+
+/// Wait before a dropped subscription is opened again
+pub const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+pub struct GeyserSubscription {
+    requests: Pin<Box<dyn Sink<SubscribeRequest, Error = SendError> + Send>>,
+    updates: Pin<Box<dyn Stream<Item = Result<SubscribeUpdate, tonic::Status>> + Send>>,
+    /// Kept alive for as long as the stream is consumed
+    _client: Box<dyn Any + Send>,
+}
+
+impl GeyserSubscription {
+    /// Connect to `endpoint` and open a subscription; nothing is delivered until `update` sends
+    /// the first filters
+    pub async fn open(endpoint: &str, x_token: &str) -> Result<Self, String> {
+        let mut client = GeyserGrpcClient::build_from_shared(endpoint.to_string())
+            .map_err(|e| format!("Failed to build client: {}", e))?
+            .x_token::<String>(Some(x_token.to_string()))
+            .map_err(|e| format!("Failed to set x_token: {}", e))?
+            .tls_config(ClientTlsConfig::new().with_native_roots())
+            .map_err(|e| format!("Failed to set tls config: {}", e))?
+            .connect()
+            .await
+            .map_err(|e| format!("Failed to connect: {}", e))?;
+
+        let (requests, updates) = client.subscribe().await
+            .map_err(|e| format!("Failed to subscribe: {}", e))?;
+        Ok(Self { requests: Box::pin(requests), updates: Box::pin(updates), _client: Box::new(client) })
+    }
+
+    /// Send filters; a request on an open subscription replaces the previous filters
+    pub async fn update(&mut self, request: SubscribeRequest) -> Result<(), String> {
+        self.requests.send(request).await
+            .map_err(|e| format!("Failed to send subscribe request: {}", e))
+    }
+
+    /// Next update other than a ping, answering pings on the way. Errors once the stream fails
+    /// or ends.
+    pub async fn next(&mut self) -> Result<UpdateOneof, String> {
+        loop {
+            let update = match self.updates.next().await {
+                Some(Ok(update)) => update,
+                Some(Err(e)) => return Err(format!("Stream error: {:?}", e)),
+                None => return Err("Stream ended".to_string()),
+            };
+            match update.update_oneof {
+                Some(UpdateOneof::Ping(_)) => {
+                    let _ = self.update(SubscribeRequest {
+                        ping: Some(SubscribeRequestPing { id: 1 }),
+                        ..Default::default()
+                    }).await;
+                }
+                Some(update) => return Ok(update),
+                None => {}
+            }
+        }
+    }
+}
+
+/// Run `subscription` until `cancel_token` fires, logging why it dropped (as "`what` error") and
+/// reopening it after `RECONNECT_DELAY`
+pub async fn run_reconnecting<F, Fut>(what: &str, logger: &Logger, cancel_token: &CancellationToken, mut subscription: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    loop {
+        tokio::select! {
+            _ = cancel_token.cancelled() => return,
+            result = subscription() => {
+                if let Err(e) = result {
+                    logger.log(format!("{} error: {}", what, e).red().to_string());
+                }
+            }
+        }
+
+        tokio::select! {
+            _ = cancel_token.cancelled() => return,
+            _ = tokio::time::sleep(RECONNECT_DELAY) => {
+                logger.log(format!("Reconnecting {}...", what.to_lowercase()).yellow().to_string());
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Grafana JSON datasource (the "JSON" / SimpleJSON plugin protocol) over the recorded token
+//! price history, served by the status server:
+//!
+//! - `GET /` answers the datasource connection test
+//! - `POST /search` lists the metrics, `<mint>:price`, `<mint>:buy_volume`, `<mint>:sell_volume`
+//!   and `<mint>:volume`, filtered by the `target` text of the request
+//! - `POST /query` returns `[{"target", "datapoints": [[value, unix_ms], ...]}]` for each
+//!   requested target within `range`, merged down to `maxDataPoints`
+
+use chrono::DateTime;
+use serde_json::{json, Value};
+
+use crate::common::price_history::{PriceHistory, PricePoint};
+
+const METRICS: [&str; 4] = ["price", "buy_volume", "sell_volume", "volume"];
+
+fn metric_value(metric: &str, point: &PricePoint) -> Option<f64> {
+    match metric {
+        "price" => Some(point.price),
+        "buy_volume" => Some(point.buy_volume),
+        "sell_volume" => Some(point.sell_volume),
+        "volume" => Some(point.buy_volume + point.sell_volume),
+        _ => None,
+    }
+}
+
+/// Merge consecutive points so at most `max` remain: the last price, summed volumes
+fn downsample(points: Vec<PricePoint>, max: usize) -> Vec<PricePoint> {
+    if max == 0 || points.len() <= max {
+        return points;
+    }
+    points.chunks(points.len().div_ceil(max))
+        .map(|chunk| {
+            let last = chunk.last().expect("chunks are never empty");
+            PricePoint {
+                timestamp_ms: last.timestamp_ms,
+                price: last.price,
+                buy_volume: chunk.iter().map(|p| p.buy_volume).sum(),
+                sell_volume: chunk.iter().map(|p| p.sell_volume).sum(),
+            }
+        })
+        .collect()
+}
+
+/// Metric names matching the `target` text of a search request (all of them when it is empty)
+pub fn search_body(request_body: &str, history: &PriceHistory) -> String {
+    let filter = serde_json::from_str::<Value>(request_body).ok()
+        .and_then(|request| request["target"].as_str().map(str::to_lowercase))
+        .unwrap_or_default();
+    let targets: Vec<String> = history.mints().iter()
+        .flat_map(|mint| METRICS.iter().map(move |metric| format!("{}:{}", mint, metric)))
+        .filter(|target| target.to_lowercase().contains(&filter))
+        .collect();
+    json!(targets).to_string()
+}
+
+fn range_ms(request: &Value, bound: &str) -> Result<i64, String> {
+    let text = request["range"][bound].as_str().ok_or_else(|| format!("missing range.{}", bound))?;
+    DateTime::parse_from_rfc3339(text)
+        .map(|at| at.timestamp_millis())
+        .map_err(|e| format!("invalid range.{}: {}", bound, e))
+}
+
+/// Series for the targets of a query request; Err holds why the request was rejected
+pub fn query_body(request_body: &str, history: &PriceHistory) -> Result<String, String> {
+    let request: Value = serde_json::from_str(request_body).map_err(|e| format!("invalid JSON: {}", e))?;
+    let from_ms = range_ms(&request, "from")?;
+    let to_ms = range_ms(&request, "to")?;
+    let max_points = request["maxDataPoints"].as_u64().unwrap_or(0) as usize;
+
+    let mut series = Vec::new();
+    for target in request["targets"].as_array().into_iter().flatten() {
+        let (name, mint, metric) = match target["target"].as_str().and_then(|name| Some((name, name.rsplit_once(':')?))) {
+            Some((name, (mint, metric))) if METRICS.contains(&metric) => (name, mint, metric),
+            _ => continue,
+        };
+        let datapoints: Vec<Value> = downsample(history.points(mint, from_ms, to_ms), max_points)
+            .iter()
+            .filter_map(|point| metric_value(metric, point).map(|value| json!([value, point.timestamp_ms])))
+            .collect();
+        series.push(json!({"target": name, "datapoints": datapoints}));
+    }
+    Ok(Value::Array(series).to_string())
+}
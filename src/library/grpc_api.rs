@@ -0,0 +1,286 @@
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use colored::Colorize;
+use solana_sdk::pubkey::Pubkey;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use tokio_util::sync::CancellationToken;
+use tonic::{Request, Response, Status};
+
+use crate::common::logger::Logger;
+use crate::library::audit_log::{self, AuditAction};
+use crate::processor::educational_monitor::{EducationalMonitor, MonitorThresholds};
+use crate::processor::telegram_alerts::subscribe_alerts;
+use crate::processor::watchlist::{export_watchlist, parse_watchlist, WatchlistFormat};
+
+pub mod pb {
+    tonic::include_proto!("monitor");
+}
+
+use pb::monitor_service_server::{MonitorService, MonitorServiceServer};
+
+/// gRPC front-end for querying and configuring the educational monitor
+pub struct MonitorGrpcService {
+    monitor: Arc<EducationalMonitor>,
+    api_token: Option<String>,
+}
+
+impl MonitorGrpcService {
+    pub fn new(monitor: Arc<EducationalMonitor>, api_token: Option<String>) -> Self {
+        Self { monitor, api_token }
+    }
+
+    /// Control calls require the `x-api-token` metadata, and are refused while GRPC_API_TOKEN is unset
+    fn authorize<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        let expected = match &self.api_token {
+            Some(token) => token,
+            None => return Err(Status::permission_denied("control calls are disabled, set GRPC_API_TOKEN")),
+        };
+        let provided = request
+            .metadata()
+            .get("x-api-token")
+            .and_then(|v| v.to_str().ok());
+        if provided == Some(expected.as_str()) {
+            Ok(())
+        } else {
+            Err(Status::unauthenticated("missing or invalid x-api-token"))
+        }
+    }
+}
+
+impl From<&MonitorThresholds> for pb::Thresholds {
+    fn from(t: &MonitorThresholds) -> Self {
+        Self {
+            price_change_pct: t.price_change_pct,
+            buy_sell_ratio: t.buy_sell_ratio,
+            whale_sol: t.whale_sol,
+            recovery_drop_pct: t.recovery_drop_pct,
+            graduation_pct: t.graduation_pct,
+            liquidity_removal_pct: t.liquidity_removal_pct,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl MonitorService for MonitorGrpcService {
+    async fn get_token(
+        &self,
+        request: Request<pb::GetTokenRequest>,
+    ) -> Result<Response<pb::TokenInfo>, Status> {
+        let mint = Pubkey::from_str(&request.get_ref().mint)
+            .map_err(|e| Status::invalid_argument(format!("invalid mint: {}", e)))?;
+        let metrics = self.monitor
+            .get_token(&mint)
+            .await
+            .ok_or_else(|| Status::not_found("token is not tracked"))?;
+
+        Ok(Response::new(pb::TokenInfo {
+            mint: metrics.address.to_string(),
+            name: metrics.name.unwrap_or_default(),
+            symbol: metrics.symbol.unwrap_or_default(),
+            initial_price: metrics.initial_price.unwrap_or_default(),
+            current_price: metrics.current_price.unwrap_or_default(),
+            volume_24h: metrics.volume_24h,
+            liquidity: metrics.liquidity,
+            buy_count: metrics.buy_count,
+            sell_count: metrics.sell_count,
+            largest_buy_sol: metrics.largest_buy_sol,
+            largest_sell_sol: metrics.largest_sell_sol,
+            first_seen: metrics.first_seen.timestamp(),
+            last_updated: metrics.last_updated.timestamp(),
+            bonding_curve_progress: metrics.bonding_curve_progress.unwrap_or_default(),
+            fresh_wallet_pct: metrics.fresh_wallet_pct.unwrap_or(-1.0),
+            buy_volume_24h: metrics.buy_volume_24h,
+            sell_volume_24h: metrics.sell_volume_24h,
+            volume_1h: metrics.volume_1h,
+        }))
+    }
+
+    async fn list_wallets(
+        &self,
+        request: Request<pb::ListWalletsRequest>,
+    ) -> Result<Response<pb::ListWalletsResponse>, Status> {
+        let limit = request.get_ref().limit as usize;
+        let wallets = self.monitor
+            .list_wallets()
+            .await
+            .into_iter()
+            .take(if limit == 0 { usize::MAX } else { limit })
+            .map(|w| pb::WalletInfo {
+                address: w.address.to_string(),
+                total_buys: w.total_buys,
+                total_sells: w.total_sells,
+                tokens_traded: w.tokens_traded.len() as u32,
+                total_volume_sol: w.total_volume_sol,
+                hypothetical_pnl: w.hypothetical_pnl,
+            })
+            .collect();
+
+        Ok(Response::new(pb::ListWalletsResponse { wallets }))
+    }
+
+    type StreamAlertsStream = Pin<Box<dyn Stream<Item = Result<pb::Alert, Status>> + Send>>;
+
+    async fn stream_alerts(
+        &self,
+        request: Request<pb::StreamAlertsRequest>,
+    ) -> Result<Response<Self::StreamAlertsStream>, Status> {
+        let categories = request.into_inner().categories;
+        let stream = BroadcastStream::new(subscribe_alerts()).filter_map(move |item| {
+            // Lagged receivers just skip the alerts they missed
+            let alert = item.ok()?;
+            if !categories.is_empty() && !categories.contains(&alert.category) {
+                return None;
+            }
+            Some(Ok(pb::Alert {
+                category: alert.category,
+                message: alert.message,
+                timestamp: alert.timestamp.timestamp(),
+            }))
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn set_thresholds(
+        &self,
+        request: Request<pb::Thresholds>,
+    ) -> Result<Response<pb::Thresholds>, Status> {
+        self.authorize(&request)?;
+        let mut new = request.into_inner();
+        let thresholds = self.monitor.thresholds();
+        let mut guard = thresholds.write().await;
+        // Added after the first release: proto3 sends them as 0 from older clients, which keeps the current value
+        if new.graduation_pct == 0.0 {
+            new.graduation_pct = guard.graduation_pct;
+        }
+        if new.liquidity_removal_pct == 0.0 {
+            new.liquidity_removal_pct = guard.liquidity_removal_pct;
+        }
+        // NaN compares false against everything, so test for what is valid rather than what is not
+        let valid = |x: f64| x.is_finite() && x > 0.0;
+        if ![
+            new.price_change_pct,
+            new.buy_sell_ratio,
+            new.whale_sol,
+            new.recovery_drop_pct,
+            new.graduation_pct,
+            new.liquidity_removal_pct,
+        ].into_iter().all(valid)
+        {
+            return Err(Status::invalid_argument("all thresholds must be positive and finite"));
+        }
+        if new.graduation_pct > 100.0 {
+            return Err(Status::invalid_argument("graduation_pct must be at most 100"));
+        }
+        if new.liquidity_removal_pct > 100.0 {
+            return Err(Status::invalid_argument("liquidity_removal_pct must be at most 100"));
+        }
+
+        let old = serde_json::to_value(&*guard).unwrap_or_default();
+        guard.price_change_pct = new.price_change_pct;
+        guard.buy_sell_ratio = new.buy_sell_ratio;
+        guard.whale_sol = new.whale_sol;
+        guard.recovery_drop_pct = new.recovery_drop_pct;
+        guard.graduation_pct = new.graduation_pct;
+        guard.liquidity_removal_pct = new.liquidity_removal_pct;
+        audit_log::record(
+            AuditAction::ConfigChanged,
+            "grpc",
+            serde_json::json!({ "setting": "thresholds", "old": old, "new": serde_json::to_value(&*guard).unwrap_or_default() }),
+        );
+
+        Ok(Response::new(pb::Thresholds::from(&*guard)))
+    }
+
+    async fn import_watchlist(
+        &self,
+        request: Request<pb::ImportWatchlistRequest>,
+    ) -> Result<Response<pb::ImportWatchlistResponse>, Status> {
+        self.authorize(&request)?;
+        let parsed = parse_watchlist(&request.get_ref().content);
+        if parsed.entries.is_empty() && !parsed.invalid.is_empty() {
+            return Err(Status::invalid_argument(format!("no valid mints: {}", parsed.invalid.join(", "))));
+        }
+
+        let total = parsed.entries.len();
+        let added = self.monitor.import_watchlist(parsed.entries).await;
+        Ok(Response::new(pb::ImportWatchlistResponse {
+            added: added as u32,
+            pinned: (total - added) as u32,
+            invalid: parsed.invalid,
+        }))
+    }
+
+    async fn export_watchlist(
+        &self,
+        request: Request<pb::ExportWatchlistRequest>,
+    ) -> Result<Response<pb::ExportWatchlistResponse>, Status> {
+        let format: WatchlistFormat = request.get_ref().format.parse()
+            .map_err(|e: anyhow::Error| Status::invalid_argument(e.to_string()))?;
+        let tokens = self.monitor.list_tokens().await;
+        let content = export_watchlist(&tokens, format)
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(pb::ExportWatchlistResponse {
+            content,
+            count: tokens.len() as u32,
+        }))
+    }
+
+    async fn list_trending(
+        &self,
+        request: Request<pb::ListTrendingRequest>,
+    ) -> Result<Response<pb::ListTrendingResponse>, Status> {
+        let limit = Some(request.get_ref().limit as usize).filter(|l| *l > 0);
+        let tokens = self.monitor
+            .trending(limit)
+            .await
+            .into_iter()
+            .map(|t| pb::TrendingToken {
+                mint: t.mint.to_string(),
+                name: t.name.unwrap_or_default(),
+                score: t.score,
+                volume_sol: t.volume_sol,
+                volume_acceleration: t.volume_acceleration,
+                unique_buyers: t.unique_buyers as u32,
+                holder_growth: t.holder_growth,
+                price_change_pct: t.price_change_pct.unwrap_or_default(),
+            })
+            .collect();
+
+        Ok(Response::new(pb::ListTrendingResponse { tokens }))
+    }
+}
+
+/// Serve the monitor gRPC API on GRPC_API_ADDR (e.g. 127.0.0.1:50051) if configured
+pub async fn start_grpc_api(
+    monitor: Arc<EducationalMonitor>,
+    cancel_token: CancellationToken,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let logger = Logger::new("[GRPC-API] => ".bright_cyan().to_string());
+    let addr: SocketAddr = match std::env::var("GRPC_API_ADDR").ok()?.parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            logger.log(format!("Invalid GRPC_API_ADDR: {}", e).red().to_string());
+            return None;
+        }
+    };
+    let api_token = std::env::var("GRPC_API_TOKEN").ok().filter(|t| !t.is_empty());
+    if api_token.is_none() {
+        logger.log("GRPC_API_TOKEN is not set, SetThresholds and ImportWatchlist are disabled".yellow().to_string());
+    }
+    let service = MonitorGrpcService::new(monitor, api_token);
+
+    logger.log(format!("Serving monitor gRPC API on {}", addr).green().to_string());
+    Some(tokio::spawn(async move {
+        let result = tonic::transport::Server::builder()
+            .add_service(MonitorServiceServer::new(service))
+            .serve_with_shutdown(addr, cancel_token.cancelled())
+            .await;
+        if let Err(e) = result {
+            logger.log(format!("gRPC API server error: {}", e).red().to_string());
+        }
+    }))
+}
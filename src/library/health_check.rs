@@ -151,6 +151,11 @@ impl HealthCheckManager {
         HEALTH_STATUS.get(service_name).map(|status| status.clone())
     }
 
+    /// Health status of every monitored service
+    pub fn all_health_status() -> Vec<HealthStatus> {
+        HEALTH_STATUS.iter().map(|entry| entry.value().clone()).collect()
+    }
+
     /// Get the healthiest service for a given transaction landing mode
     pub fn get_healthiest_service(landing_mode: &TransactionLandingMode) -> Option<String> {
         match landing_mode {
@@ -0,0 +1,225 @@
+/*!
+# Quiet Heartbeat
+
+When no alert has been sent for a while, a quiet market and a broken stream look the same from
+the chat. After `HEARTBEAT_QUIET_HOURS` without alerts a heartbeat is sent with the stream's lag,
+the time of its last update and how many updates arrived during the quiet period, so a silent
+bot can be told apart from a silent market. Further heartbeats follow every
+`HEARTBEAT_QUIET_HOURS` while it stays quiet.
+
+Operator notices (delayed data, heartbeats themselves) do not count as alerts.
+
+## Environment Variables
+
+- `HEARTBEAT_QUIET_HOURS`: Hours without alerts before a heartbeat is sent; `0` disables
+  (default: `3`)
+*/
+
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use lazy_static::lazy_static;
+use tokio_util::sync::CancellationToken;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::common::logger::Logger;
+use crate::library::slot_lag::{self, SlotLagStatus};
+
+/// How often the quiet period is checked
+const CHECK_SECS: u64 = 60;
+
+lazy_static! {
+    static ref HEARTBEAT: Heartbeat = Heartbeat::new(HeartbeatConfig::from_env(), system_clock());
+}
+
+#[derive(Clone, Debug)]
+pub struct HeartbeatConfig {
+    /// None disables heartbeats
+    pub quiet_after: Option<Duration>,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self { quiet_after: Some(Duration::hours(3)) }
+    }
+}
+
+impl HeartbeatConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            quiet_after: match std::env::var("HEARTBEAT_QUIET_HOURS").ok().and_then(|v| v.parse::<f64>().ok()) {
+                Some(hours) if hours > 0.0 => Some(Duration::seconds((hours * 3600.0) as i64)),
+                Some(_) => None,
+                None => defaults.quiet_after,
+            },
+        }
+    }
+}
+
+/// What a heartbeat reports about the quiet period
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuietReport {
+    /// Time since the last alert (or since start, when none was sent)
+    pub quiet_for: Duration,
+    pub last_alert: Option<DateTime<Utc>>,
+    /// Stream updates received since the last alert
+    pub stream_updates: u64,
+    pub lag_slots: Option<u64>,
+    pub seconds_since_update: Option<i64>,
+    pub delayed: bool,
+    /// Alerts sent since start
+    pub alerts_sent: u64,
+}
+
+impl QuietReport {
+    /// Whether the silence looks like a quiet market rather than a stalled stream
+    pub fn stream_flowing(&self, quiet_after: Duration) -> bool {
+        !self.delayed
+            && self.stream_updates > 0
+            && self.seconds_since_update.is_some_and(|secs| secs < quiet_after.num_seconds())
+    }
+
+    pub fn describe(&self, quiet_after: Duration) -> String {
+        let last_update = match self.seconds_since_update {
+            Some(secs) => format!("{} ago", format_age(secs)),
+            None => "never".to_string(),
+        };
+        let lag = match self.lag_slots {
+            Some(slots) => format!("{} slots", slots),
+            None => "unknown".to_string(),
+        };
+        let verdict = if self.stream_flowing(quiet_after) {
+            "The stream is delivering data: the market is just quiet."
+        } else if self.stream_updates == 0 {
+            "No stream data arrived in this period: the bot may be disconnected, check the stream and logs."
+        } else {
+            "The stream is stalled or behind: check the stream and logs."
+        };
+        format!(
+            "No alerts for {}.\n\n\
+            Last stream update: {}\n\
+            Stream lag: {}{}\n\
+            Stream updates since the last alert: {}\n\
+            Alerts sent since start: {}\n\n\
+            {}",
+            format_age(self.quiet_for.num_seconds()),
+            last_update,
+            lag,
+            if self.delayed { " (delayed)" } else { "" },
+            self.stream_updates,
+            self.alerts_sent,
+            verdict
+        )
+    }
+}
+
+fn format_age(secs: i64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s => format!("{}h {}m", s / 3600, s % 3600 / 60),
+    }
+}
+
+struct QuietState {
+    last_alert: Option<DateTime<Utc>>,
+    alerts: u64,
+    /// Last alert, heartbeat or start; the next heartbeat is due a full period after it
+    since: DateTime<Utc>,
+    /// Stream updates counted when the last alert was sent
+    updates_at_alert: u64,
+    /// Last alert or start
+    quiet_start: DateTime<Utc>,
+}
+
+pub struct Heartbeat {
+    config: HeartbeatConfig,
+    clock: Arc<dyn Clock>,
+    state: Mutex<QuietState>,
+}
+
+impl Heartbeat {
+    pub fn new(config: HeartbeatConfig, clock: Arc<dyn Clock>) -> Self {
+        let now = clock.now();
+        Self {
+            config,
+            clock,
+            state: Mutex::new(QuietState {
+                last_alert: None,
+                alerts: 0,
+                since: now,
+                updates_at_alert: 0,
+                quiet_start: now,
+            }),
+        }
+    }
+
+    pub fn config(&self) -> &HeartbeatConfig {
+        &self.config
+    }
+
+    /// An alert went out; `stream_updates` is the stream's update count at the time
+    pub fn record_alert(&self, stream_updates: u64) {
+        let now = self.clock.now();
+        let mut state = self.state.lock().unwrap();
+        state.last_alert = Some(now);
+        state.alerts += 1;
+        state.since = now;
+        state.quiet_start = now;
+        state.updates_at_alert = stream_updates;
+    }
+
+    /// The report to send when the quiet period since the last alert or heartbeat reached the
+    /// threshold; the next one is due a full period later
+    pub fn check(&self, stream: &SlotLagStatus) -> Option<QuietReport> {
+        let quiet_after = self.config.quiet_after?;
+        let now = self.clock.now();
+        let mut state = self.state.lock().unwrap();
+        if now - state.since < quiet_after {
+            return None;
+        }
+        state.since = now;
+        Some(QuietReport {
+            quiet_for: now - state.quiet_start,
+            last_alert: state.last_alert,
+            stream_updates: stream.updates.saturating_sub(state.updates_at_alert),
+            lag_slots: stream.lag_slots,
+            seconds_since_update: stream.seconds_since_update,
+            delayed: stream.delayed,
+            alerts_sent: state.alerts,
+        })
+    }
+}
+
+/// Count an alert sent on the process-wide heartbeat
+pub fn record_alert() {
+    HEARTBEAT.record_alert(slot_lag::status().updates);
+}
+
+/// Send a heartbeat to Telegram whenever alerts have been quiet for HEARTBEAT_QUIET_HOURS,
+/// unless disabled or Telegram is not configured
+pub fn start_heartbeat(cancel_token: CancellationToken) -> Option<tokio::task::JoinHandle<()>> {
+    let quiet_after = HEARTBEAT.config().quiet_after?;
+    let telegram = crate::processor::telegram_alerts::init_from_env().ok().flatten()?;
+    let logger = Logger::new("[HEARTBEAT] => ".bright_blue().to_string());
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_SECS));
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            let report = match HEARTBEAT.check(&slot_lag::status()) {
+                Some(report) => report,
+                None => continue,
+            };
+            let text = report.describe(quiet_after);
+            logger.log(text.replace('\n', " ").yellow().to_string());
+            if let Err(e) = telegram.send_heartbeat(&text).await {
+                logger.log(format!("Failed to send heartbeat: {}", e).red().to_string());
+            }
+        }
+        logger.log("Heartbeat shut down.".yellow().to_string());
+    }))
+}
@@ -0,0 +1,321 @@
+/*!
+# Instance Lock
+
+Two bots started against the same wallet would both trade. Instances coordinate through a lease
+kept in a lock file: the holder renews it every few seconds and is the only instance that buys,
+sells or moves profits. Any other instance keeps monitoring and alerting but refuses trades, and
+takes the lease over once the holder has not renewed it for `INSTANCE_LOCK_TTL_SECS` (crashed or
+stopped without releasing). A holder that shuts down cleanly releases the lease right away.
+
+Each check reads and rewrites the lease while holding an exclusive file lock on a guard file next
+to it (`<lock file>.guard`), so two instances finding the lease free at the same moment cannot
+both take it. Instances on different machines must point `INSTANCE_LOCK_PATH` at storage they
+share and that supports file locks. The lock is advisory: it keeps well-behaved instances apart,
+it does not stop a process that ignores it.
+
+Taking over from a running holder:
+
+- `--takeover`: start and claim the lease immediately
+- `instance takeover [INSTANCE_ID]`: hand the lease to the named instance (or release it to
+  whichever standby renews first); the holder drops to monitor-only on its next renewal
+- `instance status`: print the current holder
+
+## Environment Variables
+
+- `INSTANCE_LOCK_ENABLED`: Coordinate trading through the lock (default: `true`)
+- `INSTANCE_LOCK_PATH`: Lock file (default: `instance_lock_<wallet>.json`)
+- `INSTANCE_ID`: Name of this instance in the lock and alerts (default: `<hostname>-<pid>`)
+- `INSTANCE_LOCK_TTL_SECS`: Seconds without renewal before the lease counts as abandoned (default: `60`)
+- `INSTANCE_LOCK_RENEW_SECS`: How often the holder renews and standbys check the lease (default: `15`)
+*/
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::Result;
+use colored::Colorize;
+use fs2::FileExt;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::common::logger::Logger;
+
+lazy_static! {
+    /// Holder of the lease while this instance is monitor-only, None while it may trade
+    static ref STANDBY_HOLDER: RwLock<Option<String>> = RwLock::new(None);
+}
+
+#[derive(Clone, Debug)]
+pub struct InstanceLockConfig {
+    pub enabled: bool,
+    /// None: derived from the wallet
+    pub path: Option<PathBuf>,
+    pub instance_id: String,
+    pub ttl_secs: i64,
+    pub renew_interval: Duration,
+}
+
+impl Default for InstanceLockConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: None,
+            instance_id: default_instance_id(),
+            ttl_secs: 60,
+            renew_interval: Duration::from_secs(15),
+        }
+    }
+}
+
+impl InstanceLockConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        Self {
+            enabled: var("INSTANCE_LOCK_ENABLED")
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(defaults.enabled),
+            path: var("INSTANCE_LOCK_PATH").map(PathBuf::from),
+            instance_id: var("INSTANCE_ID").map(|v| v.trim().to_string()).unwrap_or(defaults.instance_id),
+            ttl_secs: var("INSTANCE_LOCK_TTL_SECS").and_then(|v| v.parse().ok()).unwrap_or(defaults.ttl_secs),
+            renew_interval: var("INSTANCE_LOCK_RENEW_SECS")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.renew_interval),
+        }
+    }
+
+    /// Lock file for `wallet`
+    pub fn lock_path(&self, wallet: &Pubkey) -> PathBuf {
+        self.path.clone().unwrap_or_else(|| PathBuf::from(format!("instance_lock_{}.json", wallet)))
+    }
+}
+
+fn default_instance_id() -> String {
+    let host = std::env::var("HOSTNAME").ok().filter(|h| !h.is_empty()).unwrap_or_else(|| "instance".to_string());
+    format!("{}-{}", host, std::process::id())
+}
+
+/// Contents of the lock file
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Lease {
+    pub holder: String,
+    pub acquired_at: i64,
+    pub renewed_at: i64,
+}
+
+/// Change of this instance's role, reported to the operator
+#[derive(Clone, Debug, PartialEq)]
+pub enum LockTransition {
+    /// This instance holds the lease and trades; `previous` is the holder it replaced
+    Acquired { previous: Option<String> },
+    /// Another instance holds the lease; this one is monitor-only
+    Lost { holder: String },
+}
+
+pub fn read_lease(path: &Path) -> Option<Lease> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Replace the lease atomically, so readers never see a half-written file
+pub fn write_lease(path: &Path, lease: &Lease) -> Result<()> {
+    let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+    std::fs::write(&tmp, serde_json::to_string(lease)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Run `f` holding the exclusive lock of the lease at `path`, so no other instance reads or writes
+/// the lease in between
+fn with_lease_locked<T>(path: &Path, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let mut guard_path = path.as_os_str().to_owned();
+    guard_path.push(".guard");
+    let guard = std::fs::OpenOptions::new().create(true).write(true).open(PathBuf::from(guard_path))?;
+    guard.lock_exclusive()?;
+    let result = f();
+    let _ = guard.unlock();
+    result
+}
+
+pub struct InstanceLock {
+    config: InstanceLockConfig,
+    path: PathBuf,
+    clock: Arc<dyn Clock>,
+    /// Whether this instance held the lease after the last check; None before the first
+    active: Mutex<Option<bool>>,
+}
+
+impl InstanceLock {
+    pub fn new(config: InstanceLockConfig, path: PathBuf) -> Self {
+        Self { config, path, clock: system_clock(), active: Mutex::new(None) }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.config.instance_id
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.lock().unwrap().unwrap_or(false)
+    }
+
+    /// Renew the lease if this instance holds it, take it if it is free or abandoned (or
+    /// `force`), otherwise stay monitor-only. Returns the role change, if any.
+    pub fn check(&self, force: bool) -> Result<Option<LockTransition>> {
+        let now = self.clock.now().timestamp();
+        let (lease, holder) = with_lease_locked(&self.path, || {
+            let lease = read_lease(&self.path);
+            let ours = lease.as_ref().is_some_and(|l| l.holder == self.config.instance_id);
+            let abandoned = lease.as_ref().map_or(true, |l| now - l.renewed_at > self.config.ttl_secs);
+            if !(ours || abandoned || force) {
+                let holder = lease.as_ref().map(|l| l.holder.clone()).unwrap_or_default();
+                return Ok((lease, holder));
+            }
+            let acquired_at = lease.as_ref().filter(|_| ours).map_or(now, |l| l.acquired_at);
+            write_lease(&self.path, &Lease { holder: self.config.instance_id.clone(), acquired_at, renewed_at: now })?;
+            Ok((lease, self.config.instance_id.clone()))
+        })?;
+
+        let active = holder == self.config.instance_id;
+        let previous = lease.map(|l| l.holder).filter(|h| *h != self.config.instance_id);
+        let mut state = self.active.lock().unwrap();
+        let transition = match (*state, active) {
+            (Some(true), true) | (Some(false), false) => None,
+            (_, true) => Some(LockTransition::Acquired { previous }),
+            (_, false) => Some(LockTransition::Lost { holder }),
+        };
+        *state = Some(active);
+        Ok(transition)
+    }
+
+    /// Give the lease up so a standby can take over without waiting for it to expire
+    pub fn release(&self) -> Result<()> {
+        with_lease_locked(&self.path, || {
+            if read_lease(&self.path).is_some_and(|l| l.holder == self.config.instance_id) {
+                std::fs::remove_file(&self.path)?;
+            }
+            Ok(())
+        })?;
+        *self.active.lock().unwrap() = Some(false);
+        Ok(())
+    }
+}
+
+/// Hand the lease to `instance_id`, or release it to the first standby when None; used by
+/// `instance takeover`
+pub fn hand_over(path: &Path, instance_id: Option<&str>, now: i64) -> Result<()> {
+    with_lease_locked(path, || match instance_id {
+        Some(id) => write_lease(path, &Lease { holder: id.to_string(), acquired_at: now, renewed_at: now }),
+        None if path.exists() => Ok(std::fs::remove_file(path)?),
+        None => Ok(()),
+    })
+}
+
+fn set_standby(holder: Option<String>) {
+    *STANDBY_HOLDER.write().unwrap() = holder;
+}
+
+/// Why trades are refused right now, None while this instance may trade
+pub fn trading_paused_reason() -> Option<String> {
+    STANDBY_HOLDER
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|holder| format!("Monitor-only: instance {} holds the trading lock", holder))
+}
+
+/// Switch trading on or off for a role change; returns whether this instance now trades and the
+/// detail for the operator
+fn apply(lock: &InstanceLock, logger: &Logger, transition: &LockTransition) -> (bool, String) {
+    match transition {
+        LockTransition::Acquired { previous } => {
+            set_standby(None);
+            let detail = match previous {
+                Some(previous) => format!("{} took over the trading lock from {}", lock.instance_id(), previous),
+                None => format!("{} holds the trading lock", lock.instance_id()),
+            };
+            logger.log(format!("✅ {}; trading enabled", detail).green().to_string());
+            (true, detail)
+        }
+        LockTransition::Lost { holder } => {
+            set_standby(Some(holder.clone()));
+            let detail = format!("{} holds the trading lock; {} is monitor-only", holder, lock.instance_id());
+            logger.log(format!("⏸️ {}", detail).yellow().bold().to_string());
+            (false, detail)
+        }
+    }
+}
+
+/// Claim or wait for the trading lease of `wallet`, unless INSTANCE_LOCK_ENABLED=false. The
+/// first check runs before this returns, so a standby never trades in between.
+pub fn start_instance_lock(
+    wallet: Pubkey,
+    takeover: bool,
+    cancel_token: CancellationToken,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let config = InstanceLockConfig::from_env();
+    if !config.enabled {
+        return None;
+    }
+
+    let logger = Logger::new("[INSTANCE-LOCK] => ".bright_magenta().to_string());
+    let renew_interval = config.renew_interval;
+    let path = config.lock_path(&wallet);
+    let lock = InstanceLock::new(config, path);
+    let telegram = crate::processor::telegram_alerts::init_from_env().ok().flatten();
+
+    // Stay monitor-only until the lock says otherwise
+    set_standby(Some("(unknown)".to_string()));
+    let mut first = None;
+    match lock.check(takeover) {
+        Ok(Some(transition)) => first = Some(apply(&lock, &logger, &transition)),
+        Ok(None) => {}
+        Err(e) => logger.log(format!("Failed to check the lock at {}: {}", lock.path().display(), e).red().to_string()),
+    }
+
+    Some(tokio::spawn(async move {
+        if let (Some(tg), Some((active, detail))) = (&telegram, &first) {
+            let _ = tg.alert_instance_lock(*active, detail).await;
+        }
+        let mut interval = tokio::time::interval(renew_interval);
+        interval.tick().await;
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            let transition = match lock.check(false) {
+                Ok(Some(transition)) => transition,
+                Ok(None) => continue,
+                Err(e) => {
+                    logger.log(format!("Failed to renew the lock at {}: {}", lock.path().display(), e).red().to_string());
+                    continue;
+                }
+            };
+            let (active, detail) = apply(&lock, &logger, &transition);
+            if let Some(tg) = &telegram {
+                let _ = tg.alert_instance_lock(active, &detail).await;
+            }
+        }
+        if lock.is_active() {
+            match lock.release() {
+                Ok(()) => logger.log("Released the trading lock.".yellow().to_string()),
+                Err(e) => logger.log(format!("Failed to release the trading lock: {}", e).red().to_string()),
+            }
+        }
+        logger.log("Instance lock shut down.".yellow().to_string());
+    }))
+}
@@ -5,14 +5,18 @@ use colored::Colorize;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use anchor_client::solana_sdk::{
-    signature::{Keypair, Signer}, // Add Signer trait import
     pubkey::Pubkey,
     transaction::VersionedTransaction,
 };
+// Quotes and unsigned swap transactions are read-only; signing and sending need trading
+#[cfg(feature = "trading")]
+use anchor_client::solana_sdk::signature::{Keypair, Signer}; // Add Signer trait import
 use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
 use tokio::time::Duration;
 
+use crate::common::cluster::Cluster;
 use crate::common::logger::Logger;
+use crate::library::circuit_breaker::{self, CircuitBreaker};
 
 const JUPITER_API_URL: &str = "https://lite-api.jup.ag/swap/v1";
 const JUPITER_SWAP_API_URL: &str = "https://lite-api.jup.ag/swap/v1";
@@ -126,6 +130,7 @@ struct SwapResponse {
 pub struct JupiterClient {
     client: Client,
     rpc_client: Arc<RpcClient>,
+    breaker: Arc<CircuitBreaker>,
     logger: Logger,
 }
 
@@ -139,6 +144,7 @@ impl JupiterClient {
         Self {
             client,
             rpc_client,
+            breaker: circuit_breaker::breaker("jupiter"),
             logger: Logger::new("[JUPITER] => ".magenta().to_string()),
         }
     }
@@ -150,6 +156,18 @@ impl JupiterClient {
         output_mint: &str,
         amount: u64,
         slippage_bps: u64,
+    ) -> Result<QuoteResponse> {
+        self.breaker
+            .call(self.fetch_quote(input_mint, output_mint, amount, slippage_bps))
+            .await
+    }
+
+    async fn fetch_quote(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        slippage_bps: u64,
     ) -> Result<QuoteResponse> {
         self.logger.log(format!("Getting Jupiter quote: {} -> {} (amount: {}, slippage: {}bps)", 
             input_mint, output_mint, amount, slippage_bps));
@@ -196,6 +214,16 @@ impl JupiterClient {
         &self,
         quote: QuoteResponse,
         user_public_key: &Pubkey,
+    ) -> Result<VersionedTransaction> {
+        self.breaker
+            .call(self.fetch_swap_transaction(quote, user_public_key))
+            .await
+    }
+
+    async fn fetch_swap_transaction(
+        &self,
+        quote: QuoteResponse,
+        user_public_key: &Pubkey,
     ) -> Result<VersionedTransaction> {
         self.logger.log(format!("Getting Jupiter swap transaction for user: {}", user_public_key));
 
@@ -243,6 +271,7 @@ impl JupiterClient {
     }
 
     /// Execute a token sell using Jupiter (complete flow)
+    #[cfg(feature = "trading")]
     pub async fn sell_token_with_jupiter(
         &self,
         token_mint: &str,
@@ -252,13 +281,32 @@ impl JupiterClient {
     ) -> Result<String> {
         self.logger.log(format!("Starting Jupiter sell for token {} (amount: {}, slippage: {}bps)", 
             token_mint, token_amount, slippage_bps));
+        self.swap_with_jupiter(token_mint, SOL_MINT, token_amount, slippage_bps, keypair).await
+    }
+
+    /// Swap `amount` raw units of `input_mint` into `output_mint` (native SOL is wrapped and
+    /// unwrapped as needed), signed by `keypair`
+    #[cfg(feature = "trading")]
+    pub async fn swap_with_jupiter(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        slippage_bps: u64,
+        keypair: &Keypair,
+    ) -> Result<String> {
+        crate::library::trading_lockdown::ensure_trading_enabled()?;
+        let cluster = Cluster::current();
+        if !cluster.supports_mainnet_services() {
+            return Err(anyhow!("Jupiter is not available on {}", cluster));
+        }
 
         // Get quote
         self.logger.log("Getting Jupiter quote...".to_string());
         let quote = self.get_quote(
-            token_mint,
-            SOL_MINT,
-            token_amount,
+            input_mint,
+            output_mint,
+            amount,
             slippage_bps,
         ).await?;
 
@@ -290,6 +338,17 @@ impl JupiterClient {
 
         // Send the transaction
         let signature = self.rpc_client.send_transaction(&transaction).await?;
+        crate::library::audit_log::record(
+            crate::library::audit_log::AuditAction::TradeSubmitted,
+            "jupiter",
+            serde_json::json!({
+                "signer": keypair.pubkey().to_string(),
+                "signatures": [signature.to_string()],
+                "input_mint": input_mint,
+                "output_mint": output_mint,
+                "amount": amount,
+            }),
+        );
 
         self.logger.log(format!("Jupiter sell transaction sent: {}", signature).green().to_string());
 
@@ -324,6 +383,7 @@ impl JupiterClient {
     }
     
     /// High-level function to sell a token using Jupiter API
+    #[cfg(feature = "trading")]
     pub async fn sell_token(
         &self,
         input_mint: &str,
@@ -331,6 +391,7 @@ impl JupiterClient {
         slippage_bps: u64,
         user_public_key: &Pubkey,
     ) -> Result<(String, f64)> { // Returns (signature, expected_sol_amount)
+        crate::library::trading_lockdown::ensure_trading_enabled()?;
         let sol_mint = "So11111111111111111111111111111111111111112";
         
         // Skip if it's already SOL
@@ -0,0 +1,217 @@
+/*!
+# Wallet Keystore
+
+Trading keypairs encrypted at rest, one JSON file per wallet in `KEYSTORE_DIR`. The secret key is
+sealed with XChaCha20-Poly1305 under a key derived from a passphrase with Argon2id, so a copied
+keystore is useless without the passphrase.
+
+`wallets new` generates keypairs (optionally grinding for a vanity prefix), stores them here and
+registers their addresses in `KEYSTORE_WALLETS` of the `.env` file. When `PRIVATE_KEY` is empty
+the bot signs with the first registered wallet, decrypted with `KEYSTORE_PASSPHRASE`.
+
+## Environment Variables
+
+- `KEYSTORE_DIR`: Directory of the encrypted keypair files (default: `keystore`)
+- `KEYSTORE_PASSPHRASE`: Passphrase the keypairs are encrypted with; asked for on stdin by
+  `wallets new` when unset
+- `KEYSTORE_WALLETS`: Registered wallet addresses, comma separated
+*/
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use anyhow::{anyhow, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const SALT_LEN: usize = 16;
+
+pub fn keystore_dir() -> PathBuf {
+    PathBuf::from(std::env::var("KEYSTORE_DIR").unwrap_or_else(|_| "keystore".to_string()))
+}
+
+/// Encrypted keypair as stored on disk
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeystoreEntry {
+    pub pubkey: String,
+    pub label: Option<String>,
+    pub created_at: i64,
+    /// Argon2id salt, base64
+    pub salt: String,
+    /// XChaCha20-Poly1305 nonce, base64
+    pub nonce: String,
+    /// The 64-byte keypair, encrypted, base64
+    pub ciphertext: String,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+pub fn encrypt(keypair: &Keypair, passphrase: &str, label: Option<String>) -> Result<KeystoreEntry> {
+    let mut salt = [0u8; SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, &salt)?.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, keypair.to_bytes().as_ref())
+        .map_err(|e| anyhow!("Encryption failed: {}", e))?;
+    Ok(KeystoreEntry {
+        pubkey: keypair.pubkey().to_string(),
+        label,
+        created_at: chrono::Utc::now().timestamp(),
+        salt: base64::encode(salt),
+        nonce: base64::encode(nonce),
+        ciphertext: base64::encode(ciphertext),
+    })
+}
+
+pub fn decrypt(entry: &KeystoreEntry, passphrase: &str) -> Result<Keypair> {
+    let salt = base64::decode(&entry.salt)?;
+    let nonce = base64::decode(&entry.nonce)?;
+    if nonce.len() != 24 {
+        return Err(anyhow!("Keystore entry {} has a malformed nonce", entry.pubkey));
+    }
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, &salt)?.into());
+    let bytes = cipher
+        .decrypt(XNonce::from_slice(&nonce), base64::decode(&entry.ciphertext)?.as_ref())
+        .map_err(|_| anyhow!("Wrong passphrase for keystore wallet {}", entry.pubkey))?;
+    let keypair = Keypair::from_bytes(&bytes).map_err(|e| anyhow!("Keystore wallet {} is corrupt: {}", entry.pubkey, e))?;
+    if keypair.pubkey().to_string() != entry.pubkey {
+        return Err(anyhow!("Keystore wallet {} decrypts to a different key", entry.pubkey));
+    }
+    Ok(keypair)
+}
+
+pub fn save(dir: &Path, entry: &KeystoreEntry) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.json", entry.pubkey));
+    if path.exists() {
+        return Err(anyhow!("{} already exists", path.display()));
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(entry)?)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(path)
+}
+
+pub fn load(dir: &Path, pubkey: &str) -> Result<KeystoreEntry> {
+    let path = dir.join(format!("{}.json", pubkey));
+    let content = std::fs::read_to_string(&path).map_err(|e| anyhow!("Cannot read {}: {}", path.display(), e))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Check a vanity prefix can occur in a base58 address
+pub fn validate_prefix(prefix: &str) -> Result<()> {
+    match prefix.chars().find(|c| !BASE58_ALPHABET.contains(*c)) {
+        Some(c) => Err(anyhow!("'{}' cannot appear in an address (base58 has no 0, O, I or l)", c)),
+        None => Ok(()),
+    }
+}
+
+/// Generate a keypair whose address starts with `prefix`, grinding on every core. Returns the
+/// keypair and the number of keypairs tried; gives up after `max_attempts`.
+pub fn generate(prefix: Option<&str>, ignore_case: bool, max_attempts: u64) -> Result<(Keypair, u64)> {
+    let prefix = match prefix.filter(|p| !p.is_empty()) {
+        Some(prefix) => {
+            validate_prefix(prefix)?;
+            if ignore_case { prefix.to_lowercase() } else { prefix.to_string() }
+        }
+        None => return Ok((Keypair::new(), 1)),
+    };
+    let matches = |address: &str| {
+        if ignore_case {
+            address.to_lowercase().starts_with(&prefix)
+        } else {
+            address.starts_with(&prefix)
+        }
+    };
+
+    let found = AtomicBool::new(false);
+    let attempts = AtomicU64::new(0);
+    let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let keypair = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| scope.spawn(|| {
+                while !found.load(Ordering::Relaxed) && attempts.fetch_add(1, Ordering::Relaxed) < max_attempts {
+                    let keypair = Keypair::new();
+                    if matches(&keypair.pubkey().to_string()) {
+                        found.store(true, Ordering::Relaxed);
+                        return Some(keypair);
+                    }
+                }
+                None
+            }))
+            .collect();
+        workers.into_iter().filter_map(|w| w.join().ok().flatten()).next()
+    });
+    let tried = attempts.load(Ordering::Relaxed).min(max_attempts);
+    keypair
+        .map(|keypair| (keypair, tried))
+        .ok_or_else(|| anyhow!("No address starting with '{}' in {} attempts", prefix, tried))
+}
+
+/// Expected keypairs to try for a prefix, to warn before long grinds
+pub fn expected_attempts(prefix: &str, ignore_case: bool) -> f64 {
+    let per_char: f64 = if ignore_case { 58.0 / 2.0 } else { 58.0 };
+    per_char.powi(prefix.chars().count() as i32)
+}
+
+/// Add `pubkey` to `KEYSTORE_WALLETS` in the env file, creating the line (or file) if needed
+pub fn register_wallet(env_path: &Path, pubkey: &Pubkey) -> Result<()> {
+    let content = std::fs::read_to_string(env_path).unwrap_or_default();
+    let address = pubkey.to_string();
+    let mut registered = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| match line.strip_prefix("KEYSTORE_WALLETS=") {
+            Some(value) if !registered => {
+                registered = true;
+                let mut wallets: Vec<&str> = value.split(',').map(str::trim).filter(|w| !w.is_empty()).collect();
+                if !wallets.contains(&address.as_str()) {
+                    wallets.push(&address);
+                }
+                format!("KEYSTORE_WALLETS={}", wallets.join(","))
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+    if !registered {
+        lines.push(format!("KEYSTORE_WALLETS={}", address));
+    }
+    std::fs::write(env_path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+/// Registered wallet addresses from `KEYSTORE_WALLETS`
+pub fn registered_wallets() -> Vec<String> {
+    std::env::var("KEYSTORE_WALLETS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|w| w.trim().to_string())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// The first registered wallet, decrypted with `KEYSTORE_PASSPHRASE`
+pub fn load_primary_wallet() -> Result<Keypair> {
+    let pubkey = registered_wallets()
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("No PRIVATE_KEY and no wallet in KEYSTORE_WALLETS"))?;
+    let passphrase = std::env::var("KEYSTORE_PASSPHRASE")
+        .map_err(|_| anyhow!("KEYSTORE_PASSPHRASE is needed to unlock keystore wallet {}", pubkey))?;
+    decrypt(&load(&keystore_dir(), &pubkey)?, &passphrase)
+}
@@ -0,0 +1,161 @@
+//! Memory accounting for long-running deployments.
+//!
+//! Every long-lived map, cache and queue reports its entry count, and an approximate footprint
+//! is derived from a per-entry estimate. When the estimate exceeds `MEMORY_BUDGET_MB`, caches are
+//! cleared of expired entries and the monitor evicts its least recently active tokens until the
+//! total is back under `MEMORY_EVICT_TARGET_PCT` of the budget. The hourly report carries the
+//! breakdown. Estimates are deliberately rough: they count entries, not heap bytes, so the
+//! resident set size of the process is shown next to them for comparison.
+
+use std::fmt::Write as _;
+
+use crate::common::cache::{LOOKUP_TABLE_CACHE, SUBSCRIBED_ACCOUNTS, TOKEN_ACCOUNT_CACHE, TOKEN_MINT_CACHE};
+use crate::common::price_history::PriceHistory;
+use crate::common::timeseries::TOKEN_TIMESERIES;
+use crate::processor::alert_outbox::AlertOutbox;
+#[cfg(feature = "trading")]
+use crate::processor::balance_tracker::ON_CHAIN_BALANCES;
+use crate::processor::risk_score::RISK_FACTORS;
+#[cfg(feature = "trading")]
+use crate::processor::token_program_monitor::{TOKEN_SUPPLY, TOKEN_TRANSFERS};
+
+/// One map, cache or queue and its approximate footprint
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryComponent {
+    pub name: String,
+    pub entries: usize,
+    pub approx_bytes: usize,
+}
+
+impl MemoryComponent {
+    pub fn new(name: &str, entries: usize, bytes_per_entry: usize) -> Self {
+        Self { name: name.to_string(), entries, approx_bytes: entries * bytes_per_entry }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MemoryBudget {
+    /// Budget for the estimated total, bytes; 0 disables enforcement
+    pub budget_bytes: usize,
+    /// Eviction stops once the estimate is below this share of the budget (%)
+    pub evict_target_pct: f64,
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self { budget_bytes: 512 * 1024 * 1024, evict_target_pct: 70.0 }
+    }
+}
+
+impl MemoryBudget {
+    /// MEMORY_BUDGET_MB (default 512, 0 disables) and MEMORY_EVICT_TARGET_PCT (default 70)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            budget_bytes: std::env::var("MEMORY_BUDGET_MB")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .map(|mb| mb * 1024 * 1024)
+                .unwrap_or(defaults.budget_bytes),
+            evict_target_pct: std::env::var("MEMORY_EVICT_TARGET_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.evict_target_pct),
+        }
+    }
+
+    pub fn is_exceeded(&self, used_bytes: usize) -> bool {
+        self.budget_bytes > 0 && used_bytes > self.budget_bytes
+    }
+
+    /// Estimated bytes eviction has to bring the total down to
+    pub fn target_bytes(&self) -> usize {
+        (self.budget_bytes as f64 * self.evict_target_pct / 100.0) as usize
+    }
+}
+
+/// Process-wide caches, maps and queues
+pub fn global_components() -> Vec<MemoryComponent> {
+    #[allow(unused_mut)]
+    let mut components = vec![
+        MemoryComponent::new("token account cache", TOKEN_ACCOUNT_CACHE.size(), 400),
+        MemoryComponent::new("mint cache", TOKEN_MINT_CACHE.size(), 400),
+        MemoryComponent::new("lookup table cache", LOOKUP_TABLE_CACHE.size(), 4 * 1024),
+        MemoryComponent::new("subscribed accounts", SUBSCRIBED_ACCOUNTS.size(), 512),
+        MemoryComponent::new("token timeseries", TOKEN_TIMESERIES.len(), 8 * 1024),
+        MemoryComponent::new("price history", PriceHistory::shared().len(), 16 * 1024),
+        MemoryComponent::new("risk factors", RISK_FACTORS.len(), 256),
+        MemoryComponent::new("alert outbox queue", AlertOutbox::shared().len(), 1024),
+        MemoryComponent::new("event export queue", crate::library::event_export::queue_depth(), 512),
+    ];
+    #[cfg(feature = "trading")]
+    components.extend([
+        MemoryComponent::new("token supply", TOKEN_SUPPLY.len(), 128),
+        MemoryComponent::new("token transfers", TOKEN_TRANSFERS.len(), 8 * 1024),
+        MemoryComponent::new("on-chain balances", ON_CHAIN_BALANCES.len(), 160),
+    ]);
+    components
+}
+
+/// Drop what the global caches can give up: expired entries and the timeseries and price history
+/// of tokens no longer in `keep`
+pub fn evict_global(keep: impl Fn(&str) -> bool) {
+    TOKEN_ACCOUNT_CACHE.clear_expired();
+    TOKEN_MINT_CACHE.clear_expired();
+    LOOKUP_TABLE_CACHE.clear_expired();
+    TOKEN_TIMESERIES.retain(|mint, _| keep(mint));
+    PriceHistory::shared().retain(&keep);
+    RISK_FACTORS.retain(|mint, _| keep(mint));
+    #[cfg(feature = "trading")]
+    TOKEN_TRANSFERS.retain(|mint, _| keep(mint));
+}
+
+/// Resident set size of this process, None where /proc is not available
+pub fn resident_bytes() -> Option<usize> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: usize = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(pages * 4096)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Memory section of the hourly report: total against the budget and the largest components
+pub fn format_memory_section(components: &[MemoryComponent], budget: &MemoryBudget, evicted: usize) -> String {
+    let total: usize = components.iter().map(|c| c.approx_bytes).sum();
+    let mut section = String::from("\n🧠 Memory:\n");
+    if budget.budget_bytes > 0 {
+        let _ = writeln!(
+            section,
+            "  • Estimated: {} of {} budget ({:.0}%)",
+            format_bytes(total),
+            format_bytes(budget.budget_bytes),
+            total as f64 / budget.budget_bytes as f64 * 100.0
+        );
+    } else {
+        let _ = writeln!(section, "  • Estimated: {} (no budget)", format_bytes(total));
+    }
+    if let Some(rss) = resident_bytes() {
+        let _ = writeln!(section, "  • Resident: {}", format_bytes(rss));
+    }
+    let mut largest: Vec<&MemoryComponent> = components.iter().filter(|c| c.entries > 0).collect();
+    largest.sort_by(|a, b| b.approx_bytes.cmp(&a.approx_bytes));
+    for component in largest.iter().take(5) {
+        let _ = writeln!(
+            section,
+            "  • {}: {} entries (~{})",
+            component.name,
+            component.entries,
+            format_bytes(component.approx_bytes)
+        );
+    }
+    if evicted > 0 {
+        let _ = writeln!(section, "  • Evicted since last report: {} tokens", evicted);
+    }
+    section
+}
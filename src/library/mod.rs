@@ -1,6 +1,37 @@
+#[cfg(feature = "trading")]
 pub mod blockhash_processor;
 pub mod cache_maintenance;
 pub mod rpc_client;
+#[cfg(feature = "trading")]
 pub mod zeroslot;
 pub mod jupiter_api;
 pub mod health_check;
+pub mod event_export;
+#[cfg(all(feature = "api", feature = "monitor"))]
+pub mod grpc_api;
+pub mod parser_fixtures;
+#[cfg(feature = "trading")]
+pub mod rpc_api;
+pub mod circuit_breaker;
+#[cfg(feature = "api")]
+pub mod status_server;
+#[cfg(feature = "api")]
+pub mod grafana_datasource;
+pub mod rolling;
+pub mod ohlcv;
+pub mod chart;
+pub mod transaction_source;
+pub mod account_batcher;
+pub mod account_subscription;
+pub mod geyser_subscription;
+pub mod memory_budget;
+pub mod slot_lag;
+pub mod heartbeat;
+#[cfg(feature = "trading")]
+pub mod keystore;
+pub mod instance_lock;
+#[cfg(feature = "trading")]
+pub mod priority_lane;
+pub mod single_flight;
+pub mod trading_lockdown;
+pub mod audit_log;
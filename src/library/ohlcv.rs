@@ -0,0 +1,93 @@
+//! OHLCV candles built from individual trades. `CandleSeries` keeps the most recent candles of one
+//! token at a fixed interval; a trade either updates the candle it falls in or opens the next one.
+//! Intervals without trades have no candle, so consecutive candles are not always adjacent.
+
+use std::collections::VecDeque;
+use chrono::{DateTime, Duration, Utc};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candle {
+    pub start: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// SOL traded within the candle
+    pub volume: f64,
+    pub trades: u32,
+}
+
+impl Candle {
+    fn new(start: DateTime<Utc>, price: f64, volume: f64) -> Self {
+        Self { start, open: price, high: price, low: price, close: price, volume, trades: 1 }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CandleSeries {
+    interval: Duration,
+    capacity: usize,
+    candles: VecDeque<Candle>,
+}
+
+impl CandleSeries {
+    pub fn new(interval: Duration, capacity: usize) -> Self {
+        Self { interval: interval.max(Duration::seconds(1)), capacity: capacity.max(1), candles: VecDeque::new() }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn candle_start(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let size = self.interval.num_milliseconds();
+        DateTime::from_timestamp_millis(at.timestamp_millis().div_euclid(size) * size).unwrap_or(at)
+    }
+
+    /// Add a trade; trades older than the current candle are ignored
+    pub fn update(&mut self, at: DateTime<Utc>, price: f64, volume: f64) {
+        if price <= 0.0 || !price.is_finite() {
+            return;
+        }
+        let start = self.candle_start(at);
+        match self.candles.back_mut() {
+            Some(candle) if candle.start == start => {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.close = price;
+                candle.volume += volume;
+                candle.trades += 1;
+            }
+            Some(candle) if candle.start > start => {}
+            _ => {
+                self.candles.push_back(Candle::new(start, price, volume));
+                while self.candles.len() > self.capacity {
+                    self.candles.pop_front();
+                }
+            }
+        }
+    }
+
+    /// The candle still forming, i.e. the newest one
+    pub fn current(&self) -> Option<&Candle> {
+        self.candles.back()
+    }
+
+    /// Up to `n` candles before the current one, oldest first
+    pub fn previous(&self, n: usize) -> Vec<&Candle> {
+        let completed = self.candles.len().saturating_sub(1);
+        self.candles.iter().take(completed).skip(completed.saturating_sub(n)).collect()
+    }
+
+    pub fn candles(&self) -> impl Iterator<Item = &Candle> {
+        self.candles.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.candles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candles.is_empty()
+    }
+}
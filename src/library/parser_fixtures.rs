@@ -0,0 +1,187 @@
+//! Parser test corpus: transactions stored as base64-encoded `SubscribeUpdateTransaction`
+//! protobufs, each with the normalized events the parser is expected to produce.
+//!
+//! Fixtures live in `tests/fixtures/transactions/<dex>/<signature>.json` and are checked by the
+//! golden tests in `tests/parser_golden.rs`. Real transactions are captured from the live stream
+//! by setting `PARSER_FIXTURE_DIR`: every parsed transaction is written there (up to
+//! `PARSER_FIXTURE_MAX_PER_DEX` per DEX) with the current parser output as its expected result,
+//! to be reviewed before it is added to the corpus. `PARSER_FIXTURE_ACCOUNT` narrows the capture
+//! to transactions touching one account, e.g. an aggregator program for routed swaps.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use anyhow::{anyhow, Result};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use yellowstone_grpc_proto::geyser::SubscribeUpdateTransaction;
+
+use crate::library::event_export::SwapEvent;
+use crate::library::transaction_source::account_keys;
+use crate::processor::transaction_parser::{
+    extract_liquidity_events, parse_swap_events, DexType, ParsedData,
+};
+
+lazy_static! {
+    static ref CAPTURE_DIR: Option<PathBuf> = std::env::var("PARSER_FIXTURE_DIR")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from);
+    static ref CAPTURE_MAX_PER_DEX: usize = std::env::var("PARSER_FIXTURE_MAX_PER_DEX")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(50);
+    static ref CAPTURE_ACCOUNT: Option<String> = std::env::var("PARSER_FIXTURE_ACCOUNT")
+        .ok()
+        .map(|account| account.trim().to_string())
+        .filter(|account| !account.is_empty());
+    static ref CAPTURED_PER_DEX: DashMap<String, AtomicUsize> = DashMap::new();
+}
+
+/// A transaction and the events the parser must produce for it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParserFixture {
+    pub signature: String,
+    pub slot: u64,
+    pub dex: String,
+    /// Where the transaction came from: "mainnet" for captures, "synthetic" for hand-built cases
+    pub source: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub transaction_base64: String,
+    /// Expected output in the `SwapEvent` wire format, in parse order
+    pub expected: Vec<serde_json::Value>,
+}
+
+impl ParserFixture {
+    pub fn transaction(&self) -> Result<SubscribeUpdateTransaction> {
+        decode_transaction(&self.transaction_base64)
+    }
+}
+
+pub fn encode_transaction(txn: &SubscribeUpdateTransaction) -> String {
+    base64::encode(txn.encode_to_vec())
+}
+
+pub fn decode_transaction(encoded: &str) -> Result<SubscribeUpdateTransaction> {
+    let bytes = base64::decode(encoded.trim())?;
+    Ok(SubscribeUpdateTransaction::decode(bytes.as_slice())?)
+}
+
+/// Everything the parser extracts from a transaction, normalized: swaps in event order, then
+/// liquidity events
+pub fn parse_for_fixture(txn: &SubscribeUpdateTransaction) -> Vec<ParsedData> {
+    let signer = match txn.transaction.as_ref()
+        .and_then(|info| info.transaction.as_ref())
+        .and_then(|tx| tx.message.as_ref())
+        .and_then(|message| message.account_keys.first())
+    {
+        Some(key) => bs58::encode(key).into_string(),
+        None => return Vec::new(),
+    };
+
+    let swaps = parse_swap_events(txn)
+        .into_iter()
+        .filter_map(|trade| ParsedData::from_trade_info(&trade, &signer));
+    let liquidity = extract_liquidity_events(txn)
+        .into_iter()
+        .filter_map(|event| ParsedData::from_liquidity_event(&event, &signer));
+    swaps.chain(liquidity).collect()
+}
+
+/// Parser output in the fixture's expected format. Raydium Launchpad trade events carry no
+/// timestamp (the parser stamps them with the receive time), so theirs is zeroed.
+pub fn fixture_output(txn: &SubscribeUpdateTransaction) -> Vec<serde_json::Value> {
+    parse_for_fixture(txn)
+        .iter()
+        .map(|data| {
+            let mut event = SwapEvent::from(data);
+            if data.dex_name == DexType::RaydiumLaunchpad.as_str() {
+                event.timestamp = 0;
+            }
+            event
+        })
+        .filter_map(|event| serde_json::to_value(event).ok())
+        .collect()
+}
+
+/// Compare parser output to a fixture's expectation, with a relative tolerance on numbers.
+/// Returns a description of the first difference.
+pub fn compare_output(expected: &[serde_json::Value], actual: &[serde_json::Value]) -> Result<()> {
+    if expected.len() != actual.len() {
+        return Err(anyhow!("expected {} events, parsed {}", expected.len(), actual.len()));
+    }
+    for (i, (e, a)) in expected.iter().zip(actual).enumerate() {
+        compare_value(e, a).map_err(|diff| anyhow!("event {}: {}", i, diff))?;
+    }
+    Ok(())
+}
+
+fn compare_value(expected: &serde_json::Value, actual: &serde_json::Value) -> Result<(), String> {
+    use serde_json::Value;
+    match (expected, actual) {
+        (Value::Number(e), Value::Number(a)) => {
+            let (e, a) = (e.as_f64().unwrap_or_default(), a.as_f64().unwrap_or_default());
+            if (e - a).abs() <= 1e-9 * e.abs().max(a.abs()).max(1.0) {
+                Ok(())
+            } else {
+                Err(format!("expected {}, got {}", e, a))
+            }
+        }
+        (Value::Object(e), Value::Object(a)) => {
+            for (key, e_value) in e {
+                let a_value = a.get(key).unwrap_or(&Value::Null);
+                compare_value(e_value, a_value).map_err(|diff| format!("{}: {}", key, diff))?;
+            }
+            match a.keys().find(|key| !e.contains_key(*key)) {
+                Some(key) => Err(format!("unexpected field {}", key)),
+                None => Ok(()),
+            }
+        }
+        (e, a) if e == a => Ok(()),
+        (e, a) => Err(format!("expected {}, got {}", e, a)),
+    }
+}
+
+/// Write a parsed transaction as a fixture candidate when PARSER_FIXTURE_DIR is set
+pub fn capture(txn: &SubscribeUpdateTransaction) {
+    let dir = match CAPTURE_DIR.as_ref() {
+        Some(dir) => dir,
+        None => return,
+    };
+    if let Some(account) = CAPTURE_ACCOUNT.as_ref() {
+        if !account_keys(txn).contains(account) {
+            return;
+        }
+    }
+
+    let parsed = parse_for_fixture(txn);
+    let first = match parsed.first() {
+        Some(first) => first,
+        None => return,
+    };
+    let dex = first.dex_name.to_lowercase();
+    let captured = CAPTURED_PER_DEX.entry(dex.clone()).or_insert_with(|| AtomicUsize::new(0));
+    if captured.fetch_add(1, Ordering::Relaxed) >= *CAPTURE_MAX_PER_DEX {
+        return;
+    }
+
+    let fixture = ParserFixture {
+        signature: first.signature.clone(),
+        slot: txn.slot,
+        dex: dex.clone(),
+        source: "mainnet".to_string(),
+        description: None,
+        transaction_base64: encode_transaction(txn),
+        expected: fixture_output(txn),
+    };
+    let dex_dir = dir.join(&dex);
+    let result = std::fs::create_dir_all(&dex_dir)
+        .map_err(anyhow::Error::from)
+        .and_then(|_| Ok(serde_json::to_string_pretty(&fixture)?))
+        .and_then(|json| Ok(std::fs::write(dex_dir.join(format!("{}.json", fixture.signature)), json)?));
+    if let Err(e) = result {
+        eprintln!("Failed to capture parser fixture {}: {}", fixture.signature, e);
+    }
+}
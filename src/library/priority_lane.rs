@@ -0,0 +1,228 @@
+//! Priority-lane sender: submits transactions through a staked connection (Helius Sender,
+//! Triton, or a self-run staked RPC) so they reach the leader over stake-weighted QoS instead of
+//! the public lanes.
+//!
+//! Each send carries an urgency. Transactions at or above `PRIORITY_LANE_MIN_URGENCY` go through
+//! the lane when `PRIORITY_LANE_URL` is set; everything else keeps the configured landing mode
+//! (ZeroSlot or normal RPC). Buys are `high`, emergency sells `critical` and regular sells
+//! `normal`. Providers that require a tip (Helius Sender) get a transfer of
+//! `PRIORITY_LANE_TIP_SOL` to one of `PRIORITY_LANE_TIP_ACCOUNTS`, taken from the provider's docs.
+
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signature, system_instruction};
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use rand::{seq::SliceRandom, thread_rng};
+use serde_json::{json, Value};
+
+use crate::error::{BotError, ClientError};
+use crate::library::circuit_breaker::{self, CircuitBreaker};
+
+lazy_static! {
+    static ref SHARED_LANE: Option<Arc<PriorityLaneClient>> = PriorityLaneClient::from_env().map(Arc::new);
+}
+
+/// How much landing fast matters for a transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Urgency {
+    Low,
+    Normal,
+    High,
+    Critical,
+}
+
+impl FromStr for Urgency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "low" => Ok(Urgency::Low),
+            "normal" => Ok(Urgency::Normal),
+            "high" => Ok(Urgency::High),
+            "critical" => Ok(Urgency::Critical),
+            _ => Err(format!("Invalid urgency: {}. Use 'low', 'normal', 'high' or 'critical'", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PriorityLaneProvider {
+    /// Helius Sender: requires a tip, `skipPreflight` and no RPC-side retries
+    HeliusSender,
+    Triton,
+    /// A user-run staked connection speaking the standard `sendTransaction` JSON-RPC
+    Custom,
+}
+
+impl FromStr for PriorityLaneProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "helius" | "helius_sender" => Ok(PriorityLaneProvider::HeliusSender),
+            "triton" => Ok(PriorityLaneProvider::Triton),
+            "custom" | "staked" => Ok(PriorityLaneProvider::Custom),
+            _ => Err(format!("Invalid priority lane provider: {}. Use 'helius', 'triton' or 'custom'", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PriorityLaneConfig {
+    pub provider: PriorityLaneProvider,
+    /// Endpoint URL, API key included as the provider expects it
+    pub url: String,
+    /// Transactions at least this urgent use the lane
+    pub min_urgency: Urgency,
+    pub tip_lamports: u64,
+    pub tip_accounts: Vec<Pubkey>,
+}
+
+impl PriorityLaneConfig {
+    pub fn new(provider: PriorityLaneProvider, url: &str) -> Self {
+        Self {
+            provider,
+            url: url.to_string(),
+            min_urgency: Urgency::High,
+            tip_lamports: 0,
+            tip_accounts: Vec::new(),
+        }
+    }
+
+    /// None when PRIORITY_LANE_URL is not set
+    pub fn from_env() -> Option<Result<Self>> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let url = var("PRIORITY_LANE_URL")?;
+        let parse = || -> Result<Self> {
+            let provider = var("PRIORITY_LANE_PROVIDER")
+                .map(|v| v.parse::<PriorityLaneProvider>())
+                .transpose()
+                .map_err(|e| anyhow!(e))?
+                .unwrap_or(PriorityLaneProvider::Custom);
+            let mut config = Self::new(provider, &url);
+            if let Some(urgency) = var("PRIORITY_LANE_MIN_URGENCY") {
+                config.min_urgency = urgency.parse().map_err(|e: String| anyhow!(e))?;
+            }
+            if let Some(tip) = var("PRIORITY_LANE_TIP_SOL") {
+                let tip: f64 = tip.parse().map_err(|_| anyhow!("Invalid PRIORITY_LANE_TIP_SOL: {}", tip))?;
+                config.tip_lamports = (tip * 1_000_000_000.0).round() as u64;
+            }
+            config.tip_accounts = var("PRIORITY_LANE_TIP_ACCOUNTS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|a| !a.is_empty())
+                .map(|a| Pubkey::from_str(a).map_err(|_| anyhow!("Invalid tip account in PRIORITY_LANE_TIP_ACCOUNTS: {}", a)))
+                .collect::<Result<_>>()?;
+            config.validate()?;
+            Ok(config)
+        };
+        Some(parse())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.tip_lamports > 0 && self.tip_accounts.is_empty() {
+            return Err(anyhow!("PRIORITY_LANE_TIP_SOL is set but PRIORITY_LANE_TIP_ACCOUNTS is empty"));
+        }
+        if self.provider == PriorityLaneProvider::HeliusSender && self.tip_lamports == 0 {
+            return Err(anyhow!("Helius Sender requires a tip: set PRIORITY_LANE_TIP_SOL and PRIORITY_LANE_TIP_ACCOUNTS"));
+        }
+        Ok(())
+    }
+
+    /// Whether a transaction of `urgency` goes through the lane
+    pub fn handles(&self, urgency: Urgency) -> bool {
+        urgency >= self.min_urgency
+    }
+
+    /// JSON-RPC `sendTransaction` body for a base64 wire transaction
+    pub fn request_body(&self, encoded_tx: &str) -> Value {
+        let mut options = json!({ "encoding": "base64", "skipPreflight": true });
+        if self.provider == PriorityLaneProvider::HeliusSender {
+            options["maxRetries"] = json!(0);
+        }
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendTransaction",
+            "params": [encoded_tx, options],
+        })
+    }
+}
+
+pub struct PriorityLaneClient {
+    config: PriorityLaneConfig,
+    client: reqwest::Client,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl PriorityLaneClient {
+    pub fn new(config: PriorityLaneConfig) -> Self {
+        Self { config, client: reqwest::Client::new(), breaker: circuit_breaker::breaker("priority_lane") }
+    }
+
+    /// None without PRIORITY_LANE_URL, or (with an error printed) when the lane is misconfigured
+    pub fn from_env() -> Option<Self> {
+        match PriorityLaneConfig::from_env()? {
+            Ok(config) => Some(Self::new(config)),
+            Err(e) => {
+                eprintln!("Priority lane disabled: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Process-wide lane, None when not configured
+    pub fn shared() -> Option<Arc<PriorityLaneClient>> {
+        SHARED_LANE.clone()
+    }
+
+    pub fn config(&self) -> &PriorityLaneConfig {
+        &self.config
+    }
+
+    pub fn handles(&self, urgency: Urgency) -> bool {
+        self.config.handles(urgency)
+    }
+
+    /// Tip transfer the provider requires, None when it takes no tip
+    pub fn tip_instruction(&self, payer: &Pubkey) -> Option<Instruction> {
+        if self.config.tip_lamports == 0 {
+            return None;
+        }
+        let account = self.config.tip_accounts.choose(&mut thread_rng())?;
+        Some(system_instruction::transfer(payer, account, self.config.tip_lamports))
+    }
+
+    /// Submit once; the staked connection forwards the transaction itself, so resending the same
+    /// signed transaction is left to the caller's retry policy
+    pub async fn send_transaction<T: serde::Serialize + Sync>(&self, transaction: &T) -> Result<Signature, BotError> {
+        self.breaker.call(self.try_send(transaction)).await
+    }
+
+    async fn try_send<T: serde::Serialize + Sync>(&self, transaction: &T) -> Result<Signature, BotError> {
+        let wire_transaction = bincode::serialize(transaction)
+            .map_err(|e| ClientError::Parse("Transaction serialization failed".to_string(), e.to_string()))?;
+        let body = self.config.request_body(&bs64::encode(&wire_transaction));
+
+        let response: Value = self.client
+            .post(&self.config.url)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| ClientError::Solana("Request failed".to_string(), e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| ClientError::Parse("Invalid JSON response".to_string(), e.to_string()))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(ClientError::Solana("RPC error".to_string(), error.to_string()).into());
+        }
+        let signature = response["result"].as_str()
+            .ok_or_else(|| ClientError::Parse("Invalid response format".to_string(), "Missing result field".to_string()))?;
+        Ok(Signature::from_str(signature)
+            .map_err(|e| ClientError::Parse("Invalid signature".to_string(), e.to_string()))?)
+    }
+}
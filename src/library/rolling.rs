@@ -0,0 +1,283 @@
+//! Windowed statistics over timestamped samples, shared by the strategies that need "over the
+//! last N minutes" figures: rolling volume, volume spikes, trade-size anomalies and trending
+//! volume acceleration.
+//!
+//! A `RollingWindow` is a ring buffer of `(time, value, weight)` samples. Samples older than the
+//! window are dropped as new ones arrive (or on `evict`), so every statistic covers the window
+//! only: sum, simple moving average, exponential moving average, standard deviation and
+//! volume-weighted average (`value` the price, `weight` the volume). `RollingStats` keeps one
+//! window per key.
+//!
+//! `VolumeBuckets` trades precision for bounded memory: buy and sell volume are added up per
+//! time bucket (a minute by default), so a token's 24h volume takes at most 1440 buckets however
+//! busy it is, and any window up to the span can be read back as a sliding total.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use chrono::{DateTime, Duration, Utc};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sample {
+    pub at: DateTime<Utc>,
+    pub value: f64,
+    pub weight: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct RollingWindow {
+    window: Duration,
+    /// Most samples kept regardless of age, 0 for no limit
+    capacity: usize,
+    samples: VecDeque<Sample>,
+}
+
+impl RollingWindow {
+    pub fn new(window: Duration) -> Self {
+        Self { window, capacity: 0, samples: VecDeque::new() }
+    }
+
+    /// Also keep no more than `capacity` samples, dropping the oldest
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    pub fn push(&mut self, at: DateTime<Utc>, value: f64) {
+        self.push_weighted(at, value, 1.0);
+    }
+
+    /// Add a sample; the window ends at the newest sample seen
+    pub fn push_weighted(&mut self, at: DateTime<Utc>, value: f64, weight: f64) {
+        if !value.is_finite() || !weight.is_finite() {
+            return;
+        }
+        // Samples arriving out of order are placed by time
+        let index = self.samples.iter().rposition(|s| s.at <= at).map_or(0, |i| i + 1);
+        self.samples.insert(index, Sample { at, value, weight });
+        if self.capacity > 0 {
+            while self.samples.len() > self.capacity {
+                self.samples.pop_front();
+            }
+        }
+        let newest = self.samples.back().map_or(at, |s| s.at);
+        self.evict(newest);
+    }
+
+    /// Drop samples that fell out of the window ending at `now`
+    pub fn evict(&mut self, now: DateTime<Utc>) {
+        let start = now - self.window;
+        while self.samples.front().is_some_and(|s| s.at <= start) {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &Sample> {
+        self.samples.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.samples.iter().map(|s| s.value).sum()
+    }
+
+    /// Sum of the samples after `from`
+    pub fn sum_since(&self, from: DateTime<Utc>) -> f64 {
+        self.samples.iter().filter(|s| s.at > from).map(|s| s.value).sum()
+    }
+
+    /// Sum of the samples after `from` up to and including `to`
+    pub fn sum_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+        self.samples.iter().filter(|s| s.at > from && s.at <= to).map(|s| s.value).sum()
+    }
+
+    /// Simple moving average, None while empty
+    pub fn mean(&self) -> Option<f64> {
+        (!self.samples.is_empty()).then(|| self.sum() / self.samples.len() as f64)
+    }
+
+    /// Population standard deviation, None while empty
+    pub fn stddev(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let variance = self.samples.iter().map(|s| (s.value - mean).powi(2)).sum::<f64>() / self.samples.len() as f64;
+        Some(variance.sqrt())
+    }
+
+    /// Exponential moving average with smoothing factor `alpha` (0-1], oldest sample first
+    pub fn ema(&self, alpha: f64) -> Option<f64> {
+        let alpha = alpha.clamp(f64::EPSILON, 1.0);
+        let mut samples = self.samples.iter();
+        let first = samples.next()?.value;
+        Some(samples.fold(first, |ema, s| alpha * s.value + (1.0 - alpha) * ema))
+    }
+
+    /// Weight-averaged value, e.g. the VWAP of prices weighted by volume; None without weight
+    pub fn vwap(&self) -> Option<f64> {
+        let weight: f64 = self.samples.iter().map(|s| s.weight).sum();
+        (weight > 0.0).then(|| self.samples.iter().map(|s| s.value * s.weight).sum::<f64>() / weight)
+    }
+
+    /// Standard deviations `value` lies from the mean; None with fewer than `min_samples`
+    /// samples or no spread
+    pub fn z_score(&self, value: f64, min_samples: usize) -> Option<f64> {
+        if self.samples.len() < min_samples.max(2) {
+            return None;
+        }
+        let (mean, stddev) = (self.mean()?, self.stddev()?);
+        (stddev > 0.0).then(|| (value - mean) / stddev)
+    }
+}
+
+/// One rolling window per key
+#[derive(Clone, Debug)]
+pub struct RollingStats<K> {
+    window: Duration,
+    capacity: usize,
+    windows: HashMap<K, RollingWindow>,
+}
+
+impl<K: Eq + Hash> RollingStats<K> {
+    pub fn new(window: Duration) -> Self {
+        Self { window, capacity: 0, windows: HashMap::new() }
+    }
+
+    /// Keep no more than `capacity` samples per key
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn push(&mut self, key: K, at: DateTime<Utc>, value: f64) {
+        self.push_weighted(key, at, value, 1.0);
+    }
+
+    pub fn push_weighted(&mut self, key: K, at: DateTime<Utc>, value: f64, weight: f64) {
+        let (window, capacity) = (self.window, self.capacity);
+        self.windows.entry(key)
+            .or_insert_with(|| RollingWindow::new(window).with_capacity(capacity))
+            .push_weighted(at, value, weight);
+    }
+
+    pub fn get(&self, key: &K) -> Option<&RollingWindow> {
+        self.windows.get(key)
+    }
+
+    /// Drop samples that fell out of the window and keys left without any
+    pub fn evict(&mut self, now: DateTime<Utc>) {
+        self.windows.retain(|_, window| {
+            window.evict(now);
+            !window.is_empty()
+        });
+    }
+
+    pub fn retain(&mut self, keep: impl Fn(&K) -> bool) {
+        self.windows.retain(|key, _| keep(key));
+    }
+
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+}
+
+/// Buy and sell volume over a window
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VolumeTotals {
+    pub buy: f64,
+    pub sell: f64,
+}
+
+impl VolumeTotals {
+    pub fn total(&self) -> f64 {
+        self.buy + self.sell
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct VolumeBucket {
+    start: DateTime<Utc>,
+    volume: VolumeTotals,
+}
+
+/// Buy and sell volume added up per time bucket over a fixed span
+#[derive(Clone, Debug)]
+pub struct VolumeBuckets {
+    bucket: Duration,
+    span: Duration,
+    buckets: VecDeque<VolumeBucket>,
+}
+
+impl VolumeBuckets {
+    pub fn new(bucket: Duration, span: Duration) -> Self {
+        let bucket = bucket.max(Duration::seconds(1));
+        Self { bucket, span: span.max(bucket), buckets: VecDeque::new() }
+    }
+
+    /// One-minute buckets over 24h
+    pub fn daily() -> Self {
+        Self::new(Duration::minutes(1), Duration::hours(24))
+    }
+
+    fn bucket_start(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let size = self.bucket.num_milliseconds();
+        let start = at.timestamp_millis().div_euclid(size) * size;
+        DateTime::from_timestamp_millis(start).unwrap_or(at)
+    }
+
+    pub fn record(&mut self, at: DateTime<Utc>, sol: f64, is_buy: bool) {
+        if !sol.is_finite() || sol <= 0.0 {
+            return;
+        }
+        let start = self.bucket_start(at);
+        let index = match self.buckets.iter().rposition(|b| b.start <= start) {
+            Some(i) if self.buckets[i].start == start => i,
+            found => {
+                let i = found.map_or(0, |i| i + 1);
+                self.buckets.insert(i, VolumeBucket { start, volume: VolumeTotals::default() });
+                i
+            }
+        };
+        let volume = &mut self.buckets[index].volume;
+        if is_buy { volume.buy += sol; } else { volume.sell += sol; }
+
+        let newest = self.buckets.back().map_or(start, |b| b.start);
+        self.evict(newest + self.bucket);
+    }
+
+    /// Drop buckets that ended before the span ending at `now`
+    pub fn evict(&mut self, now: DateTime<Utc>) {
+        let start = now - self.span;
+        while self.buckets.front().is_some_and(|b| b.start + self.bucket <= start) {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Volume of the buckets overlapping the `window` ending at `now`
+    pub fn totals(&self, now: DateTime<Utc>, window: Duration) -> VolumeTotals {
+        self.totals_between(now - window.min(self.span), now)
+    }
+
+    /// Volume of the buckets that ended after `from` and started at or before `to`
+    pub fn totals_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> VolumeTotals {
+        self.buckets.iter()
+            .filter(|b| b.start + self.bucket > from && b.start <= to)
+            .fold(VolumeTotals::default(), |acc, b| VolumeTotals { buy: acc.buy + b.volume.buy, sell: acc.sell + b.volume.sell })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+}
@@ -0,0 +1,202 @@
+//! Chain access used by the trading hot path, behind a trait so execution can run against a
+//! mock in tests instead of mainnet RPC and the ZeroSlot relay.
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use async_trait::async_trait;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::RpcTransactionConfig;
+use anchor_client::solana_sdk::{
+    commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey, signature::Signature,
+    transaction::{Transaction, VersionedTransaction},
+};
+use solana_transaction_status::{TransactionConfirmationStatus, UiTransactionEncoding};
+
+use crate::common::logger::Logger;
+use crate::error::{retry, BotError};
+use crate::library::blockhash_processor::BlockhashProcessor;
+use crate::library::circuit_breaker::{self, CircuitBreaker};
+use crate::library::zeroslot::ZeroSlotClient;
+
+/// How a confirmed transaction changed the wallet's balances
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BalanceChanges {
+    /// Block time, unix seconds
+    pub block_time: Option<i64>,
+    /// Network fee paid, priority fee included
+    pub fee_lamports: u64,
+    /// Change of the wallet's lamports, fee included
+    pub sol_change_lamports: i64,
+    /// Change of the wallet's UI token balance per mint (WSOL included)
+    pub token_changes: Vec<(String, f64)>,
+    /// Token accounts of the wallet the transaction opened (rent paid) and closed (rent refunded)
+    pub token_accounts_opened: u32,
+    pub token_accounts_closed: u32,
+}
+
+/// Landing status of a sent transaction
+#[derive(Clone, Debug, PartialEq)]
+pub enum SignatureStatus {
+    /// Not seen yet, or processed but not confirmed
+    Pending,
+    Confirmed,
+    Failed(String),
+}
+
+#[async_trait]
+pub trait RpcApi: Send + Sync {
+    /// Most recent blockhash, None when none has been observed yet
+    async fn latest_blockhash(&self) -> Option<Hash>;
+
+    /// Submit a signed transaction
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, BotError>;
+
+    /// Submit a signed v0 transaction, compiled through an address lookup table
+    async fn send_versioned_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, BotError>;
+
+    /// Submit a transaction that carries no relay tip (a plain SOL transfer) where it can land
+    async fn send_plain_transaction(&self, transaction: &Transaction) -> Result<Signature, BotError>;
+
+    async fn signature_status(&self, signature: &Signature) -> Result<SignatureStatus, BotError>;
+
+    /// UI balance of a token account, None when the account does not exist
+    async fn token_balance(&self, token_account: &Pubkey) -> Result<Option<f64>, BotError>;
+
+    /// Balance changes of `wallet` in a confirmed transaction
+    async fn balance_changes(&self, signature: &Signature, wallet: &Pubkey) -> Result<BalanceChanges, BotError>;
+}
+
+/// Mainnet implementation: blockhashes from the blockhash processor, sends through ZeroSlot,
+/// reads through the RPC node. Each remote goes through its own circuit breaker.
+pub struct SolanaRpc {
+    rpc_client: Arc<RpcClient>,
+    zeroslot_client: Arc<ZeroSlotClient>,
+    rpc_breaker: Arc<CircuitBreaker>,
+    zeroslot_breaker: Arc<CircuitBreaker>,
+}
+
+impl SolanaRpc {
+    pub fn new(rpc_client: Arc<RpcClient>, zeroslot_client: Arc<ZeroSlotClient>) -> Self {
+        Self {
+            rpc_client,
+            zeroslot_client,
+            rpc_breaker: circuit_breaker::breaker("rpc"),
+            zeroslot_breaker: circuit_breaker::breaker("zeroslot"),
+        }
+    }
+}
+
+#[async_trait]
+impl RpcApi for SolanaRpc {
+    async fn latest_blockhash(&self) -> Option<Hash> {
+        BlockhashProcessor::get_latest_blockhash().await
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, BotError> {
+        crate::library::trading_lockdown::ensure_trading_enabled()?;
+        self.zeroslot_breaker
+            .call(async { self.zeroslot_client.send_transaction(transaction).await.map_err(BotError::from) })
+            .await
+    }
+
+    async fn send_versioned_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, BotError> {
+        crate::library::trading_lockdown::ensure_trading_enabled()?;
+        self.zeroslot_breaker
+            .call(async { self.zeroslot_client.send_transaction(transaction).await.map_err(BotError::from) })
+            .await
+    }
+
+    /// ZeroSlot drops transactions without a tip to one of its accounts, so these go to the RPC node
+    async fn send_plain_transaction(&self, transaction: &Transaction) -> Result<Signature, BotError> {
+        crate::library::trading_lockdown::ensure_trading_enabled()?;
+        self.rpc_breaker
+            .call(async { self.rpc_client.send_transaction(transaction).await.map_err(BotError::from) })
+            .await
+    }
+
+    async fn signature_status(&self, signature: &Signature) -> Result<SignatureStatus, BotError> {
+        let statuses = self.rpc_breaker
+            .call(async { self.rpc_client.get_signature_statuses(&[*signature]).await.map_err(BotError::from) })
+            .await?;
+        Ok(match statuses.value.into_iter().next().flatten() {
+            Some(status) if status.err.is_some() => SignatureStatus::Failed(format!("{:?}", status.err)),
+            Some(status) if matches!(
+                status.confirmation_status,
+                Some(TransactionConfirmationStatus::Confirmed | TransactionConfirmationStatus::Finalized)
+            ) => SignatureStatus::Confirmed,
+            _ => SignatureStatus::Pending,
+        })
+    }
+
+    async fn token_balance(&self, token_account: &Pubkey) -> Result<Option<f64>, BotError> {
+        let account = self.rpc_breaker
+            .call(async { self.rpc_client.get_token_account(token_account).await.map_err(BotError::from) })
+            .await?;
+        match account {
+            Some(account) => {
+                let amount = f64::from_str(&account.token_amount.amount)
+                    .map_err(|e| BotError::InvalidInput(format!("token amount {}: {}", account.token_amount.amount, e)))?;
+                Ok(Some(amount / 10f64.powi(account.token_amount.decimals as i32)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn balance_changes(&self, signature: &Signature, wallet: &Pubkey) -> Result<BalanceChanges, BotError> {
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let encoded = self.rpc_breaker
+            .call(async { self.rpc_client.get_transaction_with_config(signature, config).await.map_err(BotError::from) })
+            .await?;
+        let meta = encoded.transaction.meta
+            .ok_or_else(|| BotError::InvalidInput(format!("{} has no status meta", signature)))?;
+        let transaction = encoded.transaction.transaction.decode()
+            .ok_or_else(|| BotError::InvalidInput(format!("{} could not be decoded", signature)))?;
+        let index = transaction.message.static_account_keys().iter()
+            .position(|key| key == wallet)
+            .ok_or_else(|| BotError::InvalidInput(format!("{} does not involve {}", signature, wallet)))?;
+        let lamports = |balances: &[u64]| balances.get(index).copied().unwrap_or_default() as i64;
+
+        let owner = wallet.to_string();
+        let mut token_changes: HashMap<String, f64> = HashMap::new();
+        // Wallet token accounts before and after, by account index: an account only in one of
+        // them was opened or closed by the transaction
+        let mut accounts: [HashSet<u8>; 2] = Default::default();
+        let pre = Option::<Vec<_>>::from(meta.pre_token_balances).unwrap_or_default();
+        let post = Option::<Vec<_>>::from(meta.post_token_balances).unwrap_or_default();
+        for (side, (balances, sign)) in [(pre, -1.0), (post, 1.0)].into_iter().enumerate() {
+            for balance in balances {
+                if Option::<String>::from(balance.owner).as_deref() != Some(owner.as_str()) {
+                    continue;
+                }
+                accounts[side].insert(balance.account_index);
+                *token_changes.entry(balance.mint).or_default() += sign * balance.ui_token_amount.ui_amount.unwrap_or_default();
+            }
+        }
+
+        Ok(BalanceChanges {
+            block_time: encoded.block_time,
+            fee_lamports: meta.fee,
+            sol_change_lamports: lamports(&meta.post_balances) - lamports(&meta.pre_balances),
+            token_changes: token_changes.into_iter().collect(),
+            token_accounts_opened: accounts[1].difference(&accounts[0]).count() as u32,
+            token_accounts_closed: accounts[0].difference(&accounts[1]).count() as u32,
+        })
+    }
+}
+
+/// Wait until `signature` is confirmed; an on-chain failure is returned at once, a transaction
+/// still pending once the retries run out as `BotError::NotConfirmed`
+pub async fn wait_for_confirmation(rpc_api: &dyn RpcApi, signature: &Signature, logger: &Logger) -> Result<(), BotError> {
+    retry("transaction confirmation", logger, |_| async {
+        match rpc_api.signature_status(signature).await? {
+            SignatureStatus::Confirmed => Ok(()),
+            SignatureStatus::Failed(err) => Err(BotError::Rejected(err)),
+            SignatureStatus::Pending => Err(BotError::NotConfirmed(signature.to_string())),
+        }
+    }).await
+}
@@ -0,0 +1,119 @@
+//! Runs one task per key at a time; concurrent callers for the same key share its result.
+//!
+//! The first caller for a key runs the task, callers arriving while it runs wait for it and get a
+//! clone of its result instead of starting their own. Once the task finishes the key is free again,
+//! so results are not cached here: a burst of enrichment requests for the same new mint turns into
+//! a single round of RPC calls, and a later request fetches fresh data. If the running caller is
+//! dropped before finishing, one of the waiters takes over.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tokio::sync::oneshot;
+
+/// Calls made and tasks actually run, to see how much work was shared
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FlightStats {
+    pub requests: u64,
+    pub runs: u64,
+}
+
+pub struct SingleFlight<K, V> {
+    /// Waiters of the task running for each key
+    flights: Mutex<HashMap<K, Vec<oneshot::Sender<V>>>>,
+    requests: AtomicU64,
+    runs: AtomicU64,
+}
+
+/// Frees the key if the running caller is dropped before finishing; dropping the waiters' senders
+/// without a result wakes them to take over
+struct Running<'a, K: Eq + Hash, V> {
+    flights: &'a Mutex<HashMap<K, Vec<oneshot::Sender<V>>>>,
+    key: &'a K,
+    finished: bool,
+}
+
+impl<K: Eq + Hash, V> Running<'_, K, V> {
+    /// Free the key, returning who waited for the result
+    fn finish(mut self) -> Vec<oneshot::Sender<V>> {
+        self.finished = true;
+        self.flights.lock().unwrap().remove(self.key).unwrap_or_default()
+    }
+}
+
+impl<K: Eq + Hash, V> Drop for Running<'_, K, V> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.flights.lock().unwrap().remove(self.key);
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> SingleFlight<K, V> {
+    pub fn new() -> Self {
+        Self { flights: Mutex::new(HashMap::new()), requests: AtomicU64::new(0), runs: AtomicU64::new(0) }
+    }
+
+    /// Result of `task` for `key`, run here unless a task for the key is already running
+    pub async fn run<F, Fut>(&self, key: K, task: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let mut task = Some(task);
+        loop {
+            let waiting = {
+                let mut flights = self.flights.lock().unwrap();
+                match flights.get_mut(&key) {
+                    Some(waiters) => {
+                        let (tx, rx) = oneshot::channel();
+                        waiters.push(tx);
+                        Some(rx)
+                    }
+                    None => {
+                        flights.insert(key.clone(), Vec::new());
+                        None
+                    }
+                }
+            };
+            match waiting {
+                Some(rx) => match rx.await {
+                    Ok(value) => return value,
+                    // The running caller was dropped: try to take over
+                    Err(_) => continue,
+                },
+                None => {
+                    let running = Running { flights: &self.flights, key: &key, finished: false };
+                    self.runs.fetch_add(1, Ordering::Relaxed);
+                    let task = task.take().expect("a caller runs its task at most once");
+                    let value = task().await;
+                    for waiter in running.finish() {
+                        let _ = waiter.send(value.clone());
+                    }
+                    return value;
+                }
+            }
+        }
+    }
+
+    /// Keys with a task running
+    pub fn in_flight(&self) -> usize {
+        self.flights.lock().unwrap().len()
+    }
+
+    pub fn stats(&self) -> FlightStats {
+        FlightStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            runs: self.runs.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Default for SingleFlight<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,292 @@
+/*!
+# Slot Lag Monitoring
+
+Compares the slots of stream updates (Yellowstone transactions and account updates) with the
+RPC's `getSlot`. Filtered streams can be quiet for minutes, so lag is measured per update: the
+slot an update carries against the slot the RPC is expected to be at when it arrives,
+extrapolated from the last `getSlot` at 400 ms per slot. When the latest update lags by more than
+the threshold, alerts are marked as delayed, buys are refused and the operator is notified; both
+resume once an update arrives within the recovery threshold, or once the late sample is a minute
+old without a newer one.
+
+## Environment Variables
+
+- `SLOT_LAG_ENABLED`: Poll `getSlot` and track stream lag (default: `true`)
+- `SLOT_LAG_THRESHOLD`: Slots behind the RPC before data counts as delayed (default: `20`)
+- `SLOT_LAG_RECOVER`: Slots behind at which delayed data counts as caught up again (default: `5`)
+- `SLOT_LAG_CHECK_SECS`: How often `getSlot` is polled (default: `5`)
+- `SLOT_LAG_STALE_SECS`: Treat the stream as delayed after this long without any update; `0`
+  disables, which suits streams filtered to a few quiet wallets (default: `0`)
+- `SLOT_LAG_PAUSE_SNIPING`: Refuse buys while data is delayed (default: `true`)
+*/
+
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use lazy_static::lazy_static;
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::common::logger::Logger;
+
+const SLOT_MS: i64 = 400;
+/// A lag sample older than this no longer describes the stream, so a quiet stream cannot stay
+/// delayed on the strength of one late update
+const LAG_SAMPLE_MAX_AGE_SECS: i64 = 60;
+
+lazy_static! {
+    static ref TRACKER: SlotLagTracker = SlotLagTracker::new(SlotLagConfig::from_env(), system_clock());
+}
+
+#[derive(Clone, Debug)]
+pub struct SlotLagConfig {
+    pub threshold_slots: u64,
+    pub recover_slots: u64,
+    pub check_interval: Duration,
+    /// 0 disables the no-updates check
+    pub stale_secs: u64,
+    pub pause_sniping: bool,
+}
+
+impl Default for SlotLagConfig {
+    fn default() -> Self {
+        Self {
+            threshold_slots: 20,
+            recover_slots: 5,
+            check_interval: Duration::from_secs(5),
+            stale_secs: 0,
+            pause_sniping: true,
+        }
+    }
+}
+
+impl SlotLagConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            threshold_slots: var("SLOT_LAG_THRESHOLD").and_then(|v| v.parse().ok()).unwrap_or(defaults.threshold_slots),
+            recover_slots: var("SLOT_LAG_RECOVER").and_then(|v| v.parse().ok()).unwrap_or(defaults.recover_slots),
+            check_interval: var("SLOT_LAG_CHECK_SECS")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.check_interval),
+            stale_secs: var("SLOT_LAG_STALE_SECS").and_then(|v| v.parse().ok()).unwrap_or(defaults.stale_secs),
+            pause_sniping: var("SLOT_LAG_PAUSE_SNIPING")
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(defaults.pause_sniping),
+        }
+    }
+}
+
+/// Change of the delayed state, reported to the operator
+#[derive(Clone, Debug, PartialEq)]
+pub enum LagTransition {
+    Delayed { lag_slots: u64, reason: String },
+    Recovered { lag_slots: u64, delayed_secs: i64 },
+}
+
+/// Snapshot for status endpoints and reports
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SlotLagStatus {
+    pub stream_slot: Option<u64>,
+    pub rpc_slot: Option<u64>,
+    /// Lag of the latest stream update, slots
+    pub lag_slots: Option<u64>,
+    pub seconds_since_update: Option<i64>,
+    pub delayed: bool,
+    pub delayed_episodes: u64,
+    /// Stream updates received since start
+    pub updates: u64,
+}
+
+#[derive(Default)]
+struct LagState {
+    rpc: Option<(u64, DateTime<Utc>)>,
+    stream: Option<(u64, DateTime<Utc>)>,
+    lag_slots: Option<u64>,
+    delayed_since: Option<DateTime<Utc>>,
+    episodes: u64,
+    updates: u64,
+}
+
+pub struct SlotLagTracker {
+    config: SlotLagConfig,
+    clock: Arc<dyn Clock>,
+    state: Mutex<LagState>,
+}
+
+impl SlotLagTracker {
+    pub fn new(config: SlotLagConfig, clock: Arc<dyn Clock>) -> Self {
+        Self { config, clock, state: Mutex::new(LagState::default()) }
+    }
+
+    pub fn config(&self) -> &SlotLagConfig {
+        &self.config
+    }
+
+    /// Slot the RPC should be at now, extrapolated from the last getSlot
+    fn expected_rpc_slot(&self, state: &LagState, now: DateTime<Utc>) -> Option<u64> {
+        state.rpc.map(|(slot, at)| slot + ((now - at).num_milliseconds().max(0) / SLOT_MS) as u64)
+    }
+
+    pub fn record_rpc_slot(&self, slot: u64) {
+        self.state.lock().unwrap().rpc = Some((slot, self.clock.now()));
+    }
+
+    /// A stream update arrived carrying `slot`
+    pub fn observe_stream_slot(&self, slot: u64) {
+        let now = self.clock.now();
+        let mut state = self.state.lock().unwrap();
+        state.updates += 1;
+        // Updates from several streams interleave; only newer slots say anything about lag
+        if state.stream.is_some_and(|(latest, _)| slot < latest) {
+            return;
+        }
+        state.lag_slots = self.expected_rpc_slot(&state, now).map(|expected| expected.saturating_sub(slot));
+        state.stream = Some((slot, now));
+    }
+
+    /// Re-evaluate the delayed state; returns the transition when it changed
+    pub fn evaluate(&self) -> Option<LagTransition> {
+        let now = self.clock.now();
+        let mut state = self.state.lock().unwrap();
+        let silent_secs = state.stream.map(|(_, at)| (now - at).num_seconds());
+        let stale = self.config.stale_secs > 0 && silent_secs.is_some_and(|s| s > self.config.stale_secs as i64);
+        let lag = match silent_secs {
+            Some(secs) if secs <= LAG_SAMPLE_MAX_AGE_SECS => state.lag_slots.unwrap_or(0),
+            _ => 0,
+        };
+
+        match state.delayed_since {
+            None if stale || lag > self.config.threshold_slots => {
+                state.delayed_since = Some(now);
+                state.episodes += 1;
+                let reason = if stale {
+                    format!("no stream updates for {}s", silent_secs.unwrap_or_default())
+                } else {
+                    format!("stream is {} slots behind RPC", lag)
+                };
+                Some(LagTransition::Delayed { lag_slots: lag, reason })
+            }
+            Some(since) if !stale && lag <= self.config.recover_slots => {
+                state.delayed_since = None;
+                Some(LagTransition::Recovered { lag_slots: lag, delayed_secs: (now - since).num_seconds() })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_delayed(&self) -> bool {
+        self.state.lock().unwrap().delayed_since.is_some()
+    }
+
+    pub fn status(&self) -> SlotLagStatus {
+        let now = self.clock.now();
+        let state = self.state.lock().unwrap();
+        SlotLagStatus {
+            stream_slot: state.stream.map(|(slot, _)| slot),
+            rpc_slot: self.expected_rpc_slot(&state, now),
+            lag_slots: state.lag_slots,
+            seconds_since_update: state.stream.map(|(_, at)| (now - at).num_seconds()),
+            delayed: state.delayed_since.is_some(),
+            delayed_episodes: state.episodes,
+            updates: state.updates,
+        }
+    }
+}
+
+/// Record the slot of a stream update on the process-wide tracker
+pub fn observe_stream_slot(slot: u64) {
+    TRACKER.observe_stream_slot(slot);
+}
+
+pub fn status() -> SlotLagStatus {
+    TRACKER.status()
+}
+
+/// Whether stream data is currently delayed
+pub fn is_delayed() -> bool {
+    TRACKER.is_delayed()
+}
+
+/// Why buys are refused right now, None while sniping may proceed
+pub fn sniping_paused_reason() -> Option<String> {
+    if !TRACKER.config().pause_sniping || !TRACKER.is_delayed() {
+        return None;
+    }
+    let lag = TRACKER.status().lag_slots.unwrap_or_default();
+    Some(format!("Sniping paused: stream data delayed ({} slots behind)", lag))
+}
+
+/// Banner put in front of alerts built from delayed data
+pub fn delay_notice() -> Option<String> {
+    if !TRACKER.is_delayed() {
+        return None;
+    }
+    let status = TRACKER.status();
+    Some(format!(
+        "⏳ **DELAYED DATA**: stream is {} slots (~{:.1}s) behind, prices and activity may be stale",
+        status.lag_slots.unwrap_or_default(),
+        status.lag_slots.unwrap_or_default() as f64 * SLOT_MS as f64 / 1000.0
+    ))
+}
+
+/// Poll getSlot and report delayed/recovered transitions, unless SLOT_LAG_ENABLED=false
+pub fn start_slot_lag_monitor(
+    rpc_client: Arc<RpcClient>,
+    cancel_token: CancellationToken,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let enabled = std::env::var("SLOT_LAG_ENABLED")
+        .ok()
+        .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+        .unwrap_or(true);
+    if !enabled {
+        return None;
+    }
+
+    let logger = Logger::new("[SLOT-LAG] => ".bright_yellow().to_string());
+    let telegram = crate::processor::telegram_alerts::init_from_env()
+        .ok()
+        .flatten();
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TRACKER.config().check_interval);
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            match rpc_client.get_slot_with_commitment(CommitmentConfig::processed()).await {
+                Ok(slot) => TRACKER.record_rpc_slot(slot),
+                Err(e) => {
+                    logger.log(format!("getSlot failed: {}", e).red().to_string());
+                    continue;
+                }
+            }
+            let transition = match TRACKER.evaluate() {
+                Some(transition) => transition,
+                None => continue,
+            };
+            let (detail, delayed) = match &transition {
+                LagTransition::Delayed { reason, .. } => {
+                    logger.log(format!("⚠️ Data delayed: {}; alerts flagged, buys paused", reason).red().bold().to_string());
+                    (reason.clone(), true)
+                }
+                LagTransition::Recovered { lag_slots, delayed_secs } => {
+                    let detail = format!("stream caught up ({} slots behind) after {}s", lag_slots, delayed_secs);
+                    logger.log(format!("✅ Data current again: {}", detail).green().to_string());
+                    (detail, false)
+                }
+            };
+            if let Some(tg) = &telegram {
+                let _ = tg.alert_data_delay(delayed, &detail).await;
+            }
+        }
+        logger.log("Slot lag monitor shut down.".yellow().to_string());
+    }))
+}
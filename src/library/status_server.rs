@@ -0,0 +1,297 @@
+//! Plain-HTTP status endpoints for probes and scrapers, served on STATUS_ADDR when set:
+//! `GET /healthz` returns service health, circuit breaker state and the market regime as JSON,
+//! `GET /metrics` the same in Prometheus text format.
+//!
+//! `POST /control/pause` and `POST /control/resume` stop and restart new buys (see
+//! `trading_control`). They need `Authorization: Bearer <CONTROL_TOKEN>` and are refused while
+//! CONTROL_TOKEN is unset.
+//!
+//! `GET /timeline/<mint>` returns the token's notable events as JSON (see `token_timeline`).
+//!
+//! `GET /`, `POST /search` and `POST /query` make the server a Grafana JSON datasource over the
+//! recorded token price and volume history (see `grafana_datasource`).
+
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use colored::Colorize;
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+
+use crate::common::logger::Logger;
+use crate::library::audit_log::{self, AuditAction};
+#[cfg(feature = "trading")]
+use crate::library::blockhash_processor::BlockhashProcessor;
+use crate::common::price_history::PriceHistory;
+use crate::library::circuit_breaker::{self, CircuitState};
+use crate::library::grafana_datasource;
+use crate::library::health_check::HealthCheckManager;
+use crate::library::slot_lag;
+#[cfg(feature = "trading")]
+use crate::processor::swap_deadline;
+use crate::processor::market_regime::MarketRegime;
+use crate::processor::token_timeline::{self, TokenTimeline};
+use crate::processor::trading_control::TradingControl;
+
+const MAX_REQUEST_BYTES: usize = 16 * 1024;
+
+/// Health report: "ok" when every breaker is closed, every checked service healthy and stream
+/// data is current, "degraded" otherwise. The process itself is up either way, so the status code stays 200.
+pub fn healthz_body() -> String {
+    let breakers = circuit_breaker::snapshots();
+    let services = HealthCheckManager::all_health_status();
+    let slot_lag = slot_lag::status();
+    let degraded = breakers.iter().any(|b| b.state != CircuitState::Closed)
+        || services.iter().any(|s| !s.is_healthy)
+        || slot_lag.delayed;
+
+    json!({
+        "status": if degraded { "degraded" } else { "ok" },
+        "breakers": breakers,
+        "slot_lag": slot_lag,
+        "buys_paused": TradingControl::shared().state(),
+        "market_regime": MarketRegime::shared().snapshot(),
+        "services": services.iter().map(|s| json!({
+            "name": s.service_name,
+            "healthy": s.is_healthy,
+            "response_time_ms": s.response_time_ms,
+            "consecutive_failures": s.consecutive_failures,
+            "seconds_since_check": s.last_check.elapsed().as_secs(),
+        })).collect::<Vec<_>>(),
+    })
+    .to_string()
+}
+
+/// Breaker and service health in Prometheus text exposition format
+pub fn metrics_body() -> String {
+    let mut out = String::new();
+    let breakers = circuit_breaker::snapshots();
+
+    out.push_str("# HELP circuit_breaker_state Circuit breaker state (0 closed, 1 half-open, 2 open)\n");
+    out.push_str("# TYPE circuit_breaker_state gauge\n");
+    for b in &breakers {
+        let _ = writeln!(out, "circuit_breaker_state{{service=\"{}\"}} {}", b.name, b.state.as_gauge());
+    }
+    out.push_str("# HELP circuit_breaker_failures_total Failed calls counted against the service\n");
+    out.push_str("# TYPE circuit_breaker_failures_total counter\n");
+    for b in &breakers {
+        let _ = writeln!(out, "circuit_breaker_failures_total{{service=\"{}\"}} {}", b.name, b.total_failures);
+    }
+    out.push_str("# HELP circuit_breaker_rejected_total Calls shed while the breaker was open\n");
+    out.push_str("# TYPE circuit_breaker_rejected_total counter\n");
+    for b in &breakers {
+        let _ = writeln!(out, "circuit_breaker_rejected_total{{service=\"{}\"}} {}", b.name, b.rejected);
+    }
+    out.push_str("# HELP circuit_breaker_opened_total Times the breaker tripped open\n");
+    out.push_str("# TYPE circuit_breaker_opened_total counter\n");
+    for b in &breakers {
+        let _ = writeln!(out, "circuit_breaker_opened_total{{service=\"{}\"}} {}", b.name, b.times_opened);
+    }
+
+    out.push_str("# HELP service_healthy Result of the last health check (1 healthy)\n");
+    out.push_str("# TYPE service_healthy gauge\n");
+    for s in HealthCheckManager::all_health_status() {
+        let _ = writeln!(out, "service_healthy{{service=\"{}\"}} {}", s.service_name, s.is_healthy as u8);
+    }
+
+    #[cfg(feature = "trading")]
+    {
+        let blockhash = BlockhashProcessor::stats();
+        out.push_str("# HELP blockhash_age_ms Age of the cached blockhash\n");
+        out.push_str("# TYPE blockhash_age_ms gauge\n");
+        if let Some(age) = blockhash.age_ms {
+            let _ = writeln!(out, "blockhash_age_ms {}", age);
+        }
+        out.push_str("# HELP blockhash_last_valid_block_height Last block height the cached blockhash is valid for\n");
+        out.push_str("# TYPE blockhash_last_valid_block_height gauge\n");
+        if let Some(height) = blockhash.last_valid_block_height {
+            let _ = writeln!(out, "blockhash_last_valid_block_height {}", height);
+        }
+        out.push_str("# HELP blockhash_refresh_ms Duration of the last blockhash refresh\n");
+        out.push_str("# TYPE blockhash_refresh_ms gauge\n");
+        let _ = writeln!(out, "blockhash_refresh_ms {}", blockhash.last_refresh_ms);
+        for (name, help, value) in [
+            ("blockhash_cache_hits_total", "Blockhash reads served from the cache", blockhash.hits),
+            ("blockhash_stale_reads_total", "Blockhash reads that found the cache stale or empty", blockhash.stale_reads),
+            ("blockhash_refreshes_total", "Successful background blockhash refreshes", blockhash.refreshes),
+            ("blockhash_refresh_failures_total", "Failed background blockhash refreshes", blockhash.refresh_failures),
+            ("blockhash_rpc_fallbacks_total", "Blockhashes fetched directly because the cache was stale", blockhash.rpc_fallbacks),
+        ] {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} counter\n{} {}", name, help, name, name, value);
+        }
+    }
+
+    let lag = slot_lag::status();
+    for (name, help, value) in [
+        ("stream_slot", "Slot of the latest stream update", lag.stream_slot),
+        ("rpc_slot", "Slot the RPC is estimated to be at", lag.rpc_slot),
+        ("stream_slot_lag", "Slots the latest stream update was behind the RPC", lag.lag_slots),
+    ] {
+        let _ = writeln!(out, "# HELP {} {}\n# TYPE {} gauge", name, help, name);
+        if let Some(value) = value {
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+    }
+    let _ = writeln!(out, "# HELP stream_data_delayed Stream data is delayed; alerts flagged, buys paused (1 delayed)");
+    let _ = writeln!(out, "# TYPE stream_data_delayed gauge\nstream_data_delayed {}", lag.delayed as u8);
+    let _ = writeln!(out, "# HELP stream_delayed_episodes_total Times stream data fell behind the threshold");
+    let _ = writeln!(out, "# TYPE stream_delayed_episodes_total counter\nstream_delayed_episodes_total {}", lag.delayed_episodes);
+    #[cfg(feature = "trading")]
+    {
+        let _ = writeln!(out, "# HELP stale_signals_total Buys abandoned because their signal passed the swap deadline");
+        let _ = writeln!(out, "# TYPE stale_signals_total counter\nstale_signals_total {}", swap_deadline::stale_signals());
+    }
+    let _ = writeln!(out, "# HELP buys_paused New buys paused by the operator (1 paused)");
+    let _ = writeln!(out, "# TYPE buys_paused gauge\nbuys_paused {}", TradingControl::shared().is_paused() as u8);
+    let _ = writeln!(out, "# HELP market_regime Market regime buys are sized for (1 hot, 0 neutral, -1 cold)");
+    let _ = writeln!(out, "# TYPE market_regime gauge\nmarket_regime {}", MarketRegime::shared().regime().gauge());
+    out
+}
+
+/// Whether the request carries `Authorization: Bearer <token>`
+fn bearer_matches(request: &str, token: &str) -> bool {
+    request.lines().any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("authorization") && value.trim() == format!("Bearer {}", token)
+        })
+    })
+}
+
+/// Pause or resume new buys; returns the status line and JSON body
+pub fn control_response(action: &str, request: &str, control_token: Option<&str>) -> (&'static str, String) {
+    match control_token.filter(|t| !t.is_empty()) {
+        None => return ("403 Forbidden", json!({"error": "control endpoints are disabled, set CONTROL_TOKEN"}).to_string()),
+        Some(token) if !bearer_matches(request, token) => {
+            return ("401 Unauthorized", json!({"error": "missing or wrong bearer token"}).to_string())
+        }
+        Some(_) => {}
+    }
+
+    let control = TradingControl::shared();
+    let changed = match action {
+        "pause" => {
+            let paused = control.pause("api", None);
+            if paused {
+                audit_log::record(AuditAction::Paused, "api", json!({ "state": control.state() }));
+            }
+            paused
+        }
+        "resume" => match control.resume() {
+            Some(lifted) => {
+                audit_log::record(AuditAction::Resumed, "api", json!({ "lifted": lifted }));
+                true
+            }
+            None => false,
+        },
+        _ => return ("404 Not Found", json!({"error": "unknown control action"}).to_string()),
+    };
+    ("200 OK", json!({"paused": control.state(), "changed": changed}).to_string())
+}
+
+/// Value of the Content-Length header, 0 when absent
+fn content_length(headers: &str) -> usize {
+    headers.lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim().eq_ignore_ascii_case("content-length").then(|| value.trim().parse().ok())?
+        })
+        .unwrap_or(0)
+}
+
+async fn handle_connection(mut stream: TcpStream) -> std::io::Result<()> {
+    let mut buf = vec![0u8; MAX_REQUEST_BYTES];
+    let mut len = 0;
+    let mut request_end = None;
+    while len < buf.len() {
+        let n = stream.read(&mut buf[len..]).await?;
+        if n == 0 {
+            break;
+        }
+        len += n;
+        if request_end.is_none() {
+            if let Some(pos) = buf[..len].windows(4).position(|w| w == b"\r\n\r\n") {
+                request_end = Some(pos + 4 + content_length(&String::from_utf8_lossy(&buf[..pos])));
+            }
+        }
+        if request_end.is_some_and(|end| len >= end) {
+            break;
+        }
+    }
+
+    let request = String::from_utf8_lossy(&buf[..len]);
+    let request_body = request.split_once("\r\n\r\n").map(|(_, body)| body).unwrap_or("");
+    let mut parts = request.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/healthz")) => ("200 OK", "application/json", healthz_body()),
+        (Some("GET"), Some("/metrics")) => ("200 OK", "text/plain; version=0.0.4", metrics_body()),
+        (Some("GET"), Some("/")) => ("200 OK", "text/plain", "ok\n".to_string()),
+        (Some("GET"), Some(path)) if path.starts_with("/timeline/") => {
+            let (status, body) = token_timeline::timeline_response(&path["/timeline/".len()..], &TokenTimeline::shared());
+            (status, "application/json", body)
+        }
+        (Some("POST"), Some("/search")) => {
+            ("200 OK", "application/json", grafana_datasource::search_body(request_body, &PriceHistory::shared()))
+        }
+        (Some("POST"), Some("/query")) => match grafana_datasource::query_body(request_body, &PriceHistory::shared()) {
+            Ok(body) => ("200 OK", "application/json", body),
+            Err(e) => ("400 Bad Request", "application/json", json!({"error": e}).to_string()),
+        },
+        (Some("POST"), Some(path)) if path.starts_with("/control/") => {
+            let (status, body) = control_response(
+                &path["/control/".len()..],
+                &request,
+                std::env::var("CONTROL_TOKEN").ok().as_deref(),
+            );
+            (status, "application/json", body)
+        }
+        (Some("GET"), Some(_)) => ("404 Not Found", "text/plain", "not found\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain", "method not allowed\n".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Serve /healthz, /metrics and the Grafana datasource on STATUS_ADDR (e.g. 127.0.0.1:9090) if configured
+pub async fn start_status_server(cancel_token: CancellationToken) -> Option<tokio::task::JoinHandle<()>> {
+    let logger = Logger::new("[STATUS] => ".bright_green().to_string());
+    let addr: SocketAddr = match std::env::var("STATUS_ADDR").ok().filter(|a| !a.is_empty())?.parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            logger.log(format!("Invalid STATUS_ADDR: {}", e).red().to_string());
+            return None;
+        }
+    };
+    let listener = match TcpListener::bind(addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            logger.log(format!("Failed to bind status server on {}: {}", addr, e).red().to_string());
+            return None;
+        }
+    };
+
+    logger.log(format!("Serving /healthz, /metrics and the Grafana datasource on {}", addr).green().to_string());
+    Some(tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        tokio::spawn(async move {
+                            let _ = handle_connection(stream).await;
+                        });
+                    }
+                    Err(e) => logger.log(format!("Status server accept error: {}", e).red().to_string()),
+                },
+            }
+        }
+    }))
+}
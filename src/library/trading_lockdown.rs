@@ -0,0 +1,87 @@
+/*!
+# Trading Lockdown
+
+Runtime read-only mode. With `TRADING_ENABLED=false` the process monitors and alerts but never
+signs or sends: the wallet is not loaded, the swap builders and transaction senders refuse to
+run, and `execute_buy` returns `BotError::TradingDisabled` before any other check. Builds
+without the `trading` feature are always locked down.
+
+`self_check` runs at startup and stops the process when a guard would let a trade through, or
+when `TRADING_ENABLED` holds a value that is neither true nor false. Such a value also locks
+trading down, so a typo never fails open.
+
+## Environment Variables
+
+- `TRADING_ENABLED`: Allow loading the wallet, building swaps and sending transactions; `true`,
+  `1`, `yes`, `on` or `false`, `0`, `no`, `off` (default: `true`)
+*/
+
+use lazy_static::lazy_static;
+
+use crate::error::BotError;
+
+lazy_static! {
+    static ref TRADING_SETTING: Result<bool, BotError> = parse_trading_enabled(std::env::var("TRADING_ENABLED").ok().as_deref());
+}
+
+/// Value of TRADING_ENABLED: unset or empty enables trading, anything not a recognised spelling
+/// of true or false is an error
+pub fn parse_trading_enabled(value: Option<&str>) -> Result<bool, BotError> {
+    let value = match value.map(|v| v.trim().to_lowercase()).filter(|v| !v.is_empty()) {
+        Some(value) => value,
+        None => return Ok(true),
+    };
+    match value.as_str() {
+        "true" | "1" | "yes" | "on" => Ok(true),
+        "false" | "0" | "no" | "off" => Ok(false),
+        _ => Err(BotError::InvalidConfig(format!("TRADING_ENABLED={} is neither true nor false", value))),
+    }
+}
+
+/// Whether this process may sign and send transactions
+pub fn trading_enabled() -> bool {
+    cfg!(feature = "trading") && matches!(*TRADING_SETTING, Ok(true))
+}
+
+/// Guard for code that signs or sends: `BotError::TradingDisabled` while locked down
+pub fn ensure_trading_enabled() -> Result<(), BotError> {
+    if trading_enabled() {
+        Ok(())
+    } else {
+        Err(BotError::TradingDisabled)
+    }
+}
+
+/// Verify the lockdown holds while trading is disabled: the guard, wallet import and treasury
+/// load must all refuse. Fails on an unreadable TRADING_ENABLED. Returns a description of the
+/// mode on success.
+pub fn self_check() -> Result<String, BotError> {
+    if let Err(e) = &*TRADING_SETTING {
+        return Err(e.clone());
+    }
+    if trading_enabled() {
+        return Ok("trading enabled".to_string());
+    }
+
+    if ensure_trading_enabled().is_ok() {
+        return Err(BotError::InvalidConfig("trading guard allows trades while TRADING_ENABLED=false".to_string()));
+    }
+    #[cfg(feature = "trading")]
+    match crate::common::config::import_wallet() {
+        Err(e) if BotError::classify(&e) == BotError::TradingDisabled => {}
+        Err(e) => {
+            return Err(BotError::InvalidConfig(format!("wallet import failed for another reason than the lockdown: {}", e)))
+        }
+        Ok(_) => return Err(BotError::InvalidConfig("wallet loaded while TRADING_ENABLED=false".to_string())),
+    }
+    #[cfg(feature = "trading")]
+    if crate::processor::sol_balance_monitor::treasury_available() {
+        return Err(BotError::InvalidConfig("treasury wallet unlocked while TRADING_ENABLED=false".to_string()));
+    }
+
+    Ok(if cfg!(feature = "trading") {
+        "read-only (TRADING_ENABLED=false): no wallet loaded, swaps and sends refused".to_string()
+    } else {
+        "read-only (built without trading)".to_string()
+    })
+}
@@ -0,0 +1,160 @@
+//! Conversion of RPC-shaped transactions (`getTransaction` responses, Helius raw webhooks) into
+//! the Yellowstone protobuf the parser consumes.
+
+use std::str::FromStr;
+use anyhow::{anyhow, Result};
+use anchor_client::solana_sdk::{message::VersionedMessage, pubkey::Pubkey, transaction::VersionedTransaction};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
+    UiInstruction, UiLoadedAddresses, UiMessage, UiTransactionStatusMeta, UiTransactionTokenBalance,
+};
+use yellowstone_grpc_proto::geyser::{SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo};
+use yellowstone_grpc_proto::prelude::{
+    CompiledInstruction, InnerInstruction, InnerInstructions, Message, MessageAddressTableLookup, MessageHeader,
+    TokenBalance, Transaction, TransactionError, TransactionStatusMeta, UiTokenAmount,
+};
+
+/// Convert a confirmed transaction in any binary or raw-JSON encoding
+pub fn from_encoded(encoded: &EncodedConfirmedTransactionWithStatusMeta) -> Result<SubscribeUpdateTransaction> {
+    let transaction = match &encoded.transaction.transaction {
+        EncodedTransaction::Json(ui) => match &ui.message {
+            UiMessage::Raw(raw) => Transaction {
+                signatures: ui.signatures.iter().map(|s| decode_bs58(s)).collect::<Result<_>>()?,
+                message: Some(Message {
+                    header: Some(MessageHeader {
+                        num_required_signatures: raw.header.num_required_signatures as u32,
+                        num_readonly_signed_accounts: raw.header.num_readonly_signed_accounts as u32,
+                        num_readonly_unsigned_accounts: raw.header.num_readonly_unsigned_accounts as u32,
+                    }),
+                    account_keys: raw.account_keys.iter().map(|k| decode_pubkey(k)).collect::<Result<_>>()?,
+                    recent_blockhash: decode_bs58(&raw.recent_blockhash)?,
+                    instructions: raw.instructions.iter().map(|ix| Ok(CompiledInstruction {
+                        program_id_index: ix.program_id_index as u32,
+                        accounts: ix.accounts.clone(),
+                        data: decode_bs58(&ix.data)?,
+                    })).collect::<Result<_>>()?,
+                    versioned: raw.address_table_lookups.is_some(),
+                    address_table_lookups: raw.address_table_lookups.iter().flatten().map(|lookup| Ok(MessageAddressTableLookup {
+                        account_key: decode_pubkey(&lookup.account_key)?,
+                        writable_indexes: lookup.writable_indexes.clone(),
+                        readonly_indexes: lookup.readonly_indexes.clone(),
+                    })).collect::<Result<_>>()?,
+                }),
+            },
+            UiMessage::Parsed(_) => return Err(anyhow!("jsonParsed transactions are not supported, use json or base64")),
+        },
+        other => from_versioned(&other.decode().ok_or_else(|| anyhow!("Undecodable transaction encoding"))?),
+    };
+
+    let signature = transaction.signatures.first().cloned().unwrap_or_default();
+    let meta = encoded.transaction.meta.as_ref().map(from_ui_meta).transpose()?;
+    Ok(SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature,
+            is_vote: false,
+            transaction: Some(transaction),
+            meta,
+            index: 0,
+        }),
+        slot: encoded.slot,
+    })
+}
+
+pub fn from_versioned(tx: &VersionedTransaction) -> Transaction {
+    let header = tx.message.header();
+    let (instructions, lookups) = match &tx.message {
+        VersionedMessage::Legacy(message) => (&message.instructions, Vec::new()),
+        VersionedMessage::V0(message) => (
+            &message.instructions,
+            message.address_table_lookups.iter().map(|lookup| MessageAddressTableLookup {
+                account_key: lookup.account_key.to_bytes().to_vec(),
+                writable_indexes: lookup.writable_indexes.clone(),
+                readonly_indexes: lookup.readonly_indexes.clone(),
+            }).collect(),
+        ),
+    };
+    Transaction {
+        signatures: tx.signatures.iter().map(|s| s.as_ref().to_vec()).collect(),
+        message: Some(Message {
+            header: Some(MessageHeader {
+                num_required_signatures: header.num_required_signatures as u32,
+                num_readonly_signed_accounts: header.num_readonly_signed_accounts as u32,
+                num_readonly_unsigned_accounts: header.num_readonly_unsigned_accounts as u32,
+            }),
+            account_keys: tx.message.static_account_keys().iter().map(|k| k.to_bytes().to_vec()).collect(),
+            recent_blockhash: tx.message.recent_blockhash().to_bytes().to_vec(),
+            instructions: instructions.iter().map(|ix| CompiledInstruction {
+                program_id_index: ix.program_id_index as u32,
+                accounts: ix.accounts.clone(),
+                data: ix.data.clone(),
+            }).collect(),
+            versioned: matches!(tx.message, VersionedMessage::V0(_)),
+            address_table_lookups: lookups,
+        }),
+    }
+}
+
+fn from_ui_meta(meta: &UiTransactionStatusMeta) -> Result<TransactionStatusMeta> {
+    let inner_instructions = Option::<&Vec<_>>::from(meta.inner_instructions.as_ref());
+    let log_messages = Option::<&Vec<String>>::from(meta.log_messages.as_ref());
+    let loaded: Option<&UiLoadedAddresses> = meta.loaded_addresses.as_ref().into();
+
+    Ok(TransactionStatusMeta {
+        err: meta.err.as_ref()
+            .map(|err| bincode::serialize(err).map(|err| TransactionError { err }))
+            .transpose()?,
+        fee: meta.fee,
+        pre_balances: meta.pre_balances.clone(),
+        post_balances: meta.post_balances.clone(),
+        inner_instructions: inner_instructions.into_iter().flatten().map(|inner| Ok(InnerInstructions {
+            index: inner.index as u32,
+            instructions: inner.instructions.iter().filter_map(|ix| match ix {
+                UiInstruction::Compiled(ix) => Some(ix),
+                UiInstruction::Parsed(_) => None,
+            }).map(|ix| Ok(InnerInstruction {
+                program_id_index: ix.program_id_index as u32,
+                accounts: ix.accounts.clone(),
+                data: decode_bs58(&ix.data)?,
+                stack_height: ix.stack_height,
+            })).collect::<Result<_>>()?,
+        })).collect::<Result<_>>()?,
+        inner_instructions_none: inner_instructions.is_none(),
+        log_messages: log_messages.cloned().unwrap_or_default(),
+        log_messages_none: log_messages.is_none(),
+        pre_token_balances: token_balances(&meta.pre_token_balances),
+        post_token_balances: token_balances(&meta.post_token_balances),
+        loaded_writable_addresses: loaded.map(|l| l.writable.as_slice()).unwrap_or_default()
+            .iter().map(|k| decode_pubkey(k)).collect::<Result<_>>()?,
+        loaded_readonly_addresses: loaded.map(|l| l.readonly.as_slice()).unwrap_or_default()
+            .iter().map(|k| decode_pubkey(k)).collect::<Result<_>>()?,
+        compute_units_consumed: Option::from(meta.compute_units_consumed.clone()),
+        ..Default::default()
+    })
+}
+
+fn token_balances(balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>) -> Vec<TokenBalance> {
+    Option::<&Vec<_>>::from(balances.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|balance| TokenBalance {
+            account_index: balance.account_index as u32,
+            mint: balance.mint.clone(),
+            ui_token_amount: Some(UiTokenAmount {
+                ui_amount: balance.ui_token_amount.ui_amount.unwrap_or_default(),
+                decimals: balance.ui_token_amount.decimals as u32,
+                amount: balance.ui_token_amount.amount.clone(),
+                ui_amount_string: balance.ui_token_amount.ui_amount_string.clone(),
+            }),
+            owner: Option::<&String>::from(balance.owner.as_ref()).cloned().unwrap_or_default(),
+            program_id: Option::<&String>::from(balance.program_id.as_ref()).cloned().unwrap_or_default(),
+        })
+        .collect()
+}
+
+fn decode_bs58(value: &str) -> Result<Vec<u8>> {
+    bs58::decode(value).into_vec().map_err(|e| anyhow!("Invalid base58 '{}': {}", value, e))
+}
+
+fn decode_pubkey(value: &str) -> Result<Vec<u8>> {
+    Ok(Pubkey::from_str(value).map_err(|e| anyhow!("Invalid pubkey '{}': {}", value, e))?.to_bytes().to_vec())
+}
@@ -0,0 +1,160 @@
+//! Helius webhook source: listens for "raw" webhook deliveries (JSON arrays of `getTransaction`
+//! shaped transactions) on HELIUS_WEBHOOK_ADDR. Account filtering is configured on the webhook in
+//! Helius; deliveries are filtered again locally against the subscription's accounts.
+
+use std::net::SocketAddr;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use yellowstone_grpc_proto::geyser::SubscribeUpdateTransaction;
+
+use super::{convert, SourceFilter, TransactionSource, TransactionStream};
+
+const CHANNEL_CAPACITY: usize = 1024;
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+pub struct HeliusWebhookSource {
+    listen_addr: String,
+    auth_header: Option<String>,
+}
+
+impl HeliusWebhookSource {
+    pub fn new(listen_addr: String, auth_header: Option<String>) -> Self {
+        Self { listen_addr, auth_header }
+    }
+}
+
+#[async_trait]
+impl TransactionSource for HeliusWebhookSource {
+    fn name(&self) -> &str {
+        "helius"
+    }
+
+    async fn subscribe(&self, filter: &SourceFilter) -> Result<TransactionStream> {
+        let addr: SocketAddr = self.listen_addr.parse()
+            .map_err(|e| anyhow!("Invalid HELIUS_WEBHOOK_ADDR {}: {}", self.listen_addr, e))?;
+        let listener = TcpListener::bind(addr).await
+            .map_err(|e| anyhow!("Failed to bind webhook listener on {}: {}", addr, e))?;
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let filter = filter.clone();
+        let auth_header = self.auth_header.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        let _ = tx.send(Err(anyhow!("Webhook listener failed: {}", e))).await;
+                        break;
+                    }
+                };
+                let delivered = match handle_delivery(stream, auth_header.as_deref()).await {
+                    Ok(delivered) => delivered,
+                    Err(e) => {
+                        eprintln!("Rejected Helius webhook delivery: {}", e);
+                        continue;
+                    }
+                };
+                for txn in delivered {
+                    if !filter.include_failed && is_failed(&txn) {
+                        continue;
+                    }
+                    if !filter.matches(&txn) {
+                        continue;
+                    }
+                    if tx.send(Ok(txn)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(Box::pin(ReceiverStream::new(rx)))
+    }
+}
+
+/// Read one webhook request, answer it, and return the transactions it carried
+async fn handle_delivery(mut stream: TcpStream, auth_header: Option<&str>) -> Result<Vec<SubscribeUpdateTransaction>> {
+    let mut buf = Vec::with_capacity(64 * 1024);
+    let mut chunk = [0u8; 16 * 1024];
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(anyhow!("connection closed before headers"));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buf.len() > 64 * 1024 {
+            return Err(anyhow!("headers too large"));
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let header = |name: &str| {
+        head.lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim().to_string())
+    };
+
+    if let Some(expected) = auth_header {
+        if header("authorization").as_deref() != Some(expected) {
+            respond(&mut stream, "401 Unauthorized").await;
+            return Err(anyhow!("bad Authorization header"));
+        }
+    }
+    let content_length: usize = header("content-length").and_then(|v| v.parse().ok()).unwrap_or(0);
+    if content_length > MAX_BODY_BYTES {
+        respond(&mut stream, "413 Payload Too Large").await;
+        return Err(anyhow!("body of {} bytes exceeds limit", content_length));
+    }
+    while buf.len() < header_end + content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let body = &buf[header_end..buf.len().min(header_end + content_length)];
+    let parsed: Result<Vec<EncodedConfirmedTransactionWithStatusMeta>, _> = serde_json::from_slice(body);
+    let delivered = match parsed {
+        Ok(delivered) => delivered,
+        Err(e) => {
+            respond(&mut stream, "400 Bad Request").await;
+            return Err(anyhow!("invalid payload: {}", e));
+        }
+    };
+    // Acknowledge before converting so Helius does not retry on our processing time
+    respond(&mut stream, "200 OK").await;
+
+    Ok(delivered
+        .iter()
+        .filter_map(|encoded| match convert::from_encoded(encoded) {
+            Ok(txn) => Some(txn),
+            Err(e) => {
+                eprintln!("Skipped webhook transaction at slot {}: {}", encoded.slot, e);
+                None
+            }
+        })
+        .collect())
+}
+
+async fn respond(stream: &mut TcpStream, status: &str) {
+    let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+fn is_failed(txn: &SubscribeUpdateTransaction) -> bool {
+    txn.transaction.as_ref()
+        .and_then(|info| info.meta.as_ref())
+        .map(|meta| meta.err.is_some())
+        .unwrap_or(false)
+}
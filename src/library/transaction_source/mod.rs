@@ -0,0 +1,194 @@
+//! Pluggable sources of transactions for the processor.
+//!
+//! Every source yields transactions as Yellowstone `SubscribeUpdateTransaction`s, the format the
+//! parser already consumes, so the processor does not care where they came from. Sources are
+//! picked with `TRANSACTION_SOURCES` (comma separated, e.g. `yellowstone,websocket`); when more
+//! than one is configured their streams are merged and deduplicated by signature. Live streams can
+//! be recorded to a file and replayed later with the `replay` source. What a subscription covers
+//! (DEX programs and account sets) comes from the monitoring scope, see [`scope`].
+
+pub mod convert;
+pub mod helius;
+pub mod replay;
+pub mod scope;
+pub mod websocket;
+pub mod yellowstone;
+
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::pin::Pin;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::stream::{self, Stream, StreamExt};
+use lru::LruCache;
+use yellowstone_grpc_proto::geyser::SubscribeUpdateTransaction;
+
+use crate::common::cluster::Cluster;
+
+pub use helius::HeliusWebhookSource;
+pub use replay::{FileReplaySource, RecordingSource};
+pub use scope::MonitorScope;
+pub use websocket::WebSocketSource;
+pub use yellowstone::YellowstoneSource;
+
+pub type TransactionStream = Pin<Box<dyn Stream<Item = Result<SubscribeUpdateTransaction>> + Send>>;
+
+/// Which transactions a source should deliver
+#[derive(Clone, Debug, Default)]
+pub struct SourceFilter {
+    /// Deliver transactions that reference any of these accounts
+    pub account_include: Vec<String>,
+    /// Drop transactions that reference any of these accounts
+    pub account_exclude: Vec<String>,
+    /// Only deliver transactions that reference all of these accounts
+    pub account_required: Vec<String>,
+    /// Deliver failed transactions too
+    pub include_failed: bool,
+}
+
+impl SourceFilter {
+    pub fn involving(accounts: Vec<String>) -> Self {
+        Self { account_include: accounts, ..Default::default() }
+    }
+
+    /// Whether a transaction passes the account lists. Sources that cannot filter server side
+    /// apply this locally.
+    pub fn matches(&self, txn: &SubscribeUpdateTransaction) -> bool {
+        let keys = account_keys(txn);
+        (self.account_include.is_empty() || self.account_include.iter().any(|a| keys.contains(a)))
+            && !self.account_exclude.iter().any(|a| keys.contains(a))
+            && self.account_required.iter().all(|a| keys.contains(a))
+    }
+}
+
+/// Static and lookup-table-loaded account keys of a transaction, base58 encoded
+pub fn account_keys(txn: &SubscribeUpdateTransaction) -> HashSet<String> {
+    let info = match txn.transaction.as_ref() {
+        Some(info) => info,
+        None => return HashSet::new(),
+    };
+    let static_keys = info.transaction.as_ref()
+        .and_then(|tx| tx.message.as_ref())
+        .map(|message| message.account_keys.iter())
+        .into_iter()
+        .flatten();
+    let loaded_keys = info.meta.as_ref()
+        .map(|meta| meta.loaded_writable_addresses.iter().chain(meta.loaded_readonly_addresses.iter()))
+        .into_iter()
+        .flatten();
+    static_keys
+        .chain(loaded_keys)
+        .map(|key| bs58::encode(key).into_string())
+        .collect()
+}
+
+#[async_trait]
+pub trait TransactionSource: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Open the source. The stream ends when the source disconnects; an `Err` item reports why.
+    async fn subscribe(&self, filter: &SourceFilter) -> Result<TransactionStream>;
+}
+
+/// Several sources merged into one stream; a transaction seen by more than one source is
+/// delivered once
+pub struct MergedSource {
+    sources: Vec<Box<dyn TransactionSource>>,
+}
+
+impl MergedSource {
+    pub fn new(sources: Vec<Box<dyn TransactionSource>>) -> Self {
+        Self { sources }
+    }
+}
+
+#[async_trait]
+impl TransactionSource for MergedSource {
+    fn name(&self) -> &str {
+        "merged"
+    }
+
+    async fn subscribe(&self, filter: &SourceFilter) -> Result<TransactionStream> {
+        let mut streams = Vec::with_capacity(self.sources.len());
+        for source in &self.sources {
+            streams.push(source.subscribe(filter).await?);
+        }
+
+        let mut seen: LruCache<Vec<u8>, ()> = LruCache::new(NonZeroUsize::new(10_000).unwrap());
+        let merged = stream::select_all(streams).filter(move |item| {
+            let fresh = match item {
+                Ok(txn) => match txn.transaction.as_ref() {
+                    Some(info) => seen.put(info.signature.clone(), ()).is_none(),
+                    None => true,
+                },
+                Err(_) => true,
+            };
+            futures::future::ready(fresh)
+        });
+        Ok(Box::pin(merged))
+    }
+}
+
+/// Connection settings shared by the source implementations
+#[derive(Clone, Debug)]
+pub struct SourceConfig {
+    pub yellowstone_grpc_http: String,
+    pub yellowstone_grpc_token: String,
+    pub rpc_http: String,
+    pub rpc_wss: String,
+    /// Listen address for Helius webhook deliveries
+    pub helius_webhook_addr: String,
+    /// Expected Authorization header on webhook deliveries, if set
+    pub helius_webhook_auth: Option<String>,
+}
+
+impl SourceConfig {
+    pub fn new(yellowstone_grpc_http: String, yellowstone_grpc_token: String) -> Self {
+        Self {
+            yellowstone_grpc_http,
+            yellowstone_grpc_token,
+            rpc_http: Cluster::current().rpc_http().unwrap_or_default(),
+            rpc_wss: Cluster::current().rpc_wss().unwrap_or_default(),
+            helius_webhook_addr: std::env::var("HELIUS_WEBHOOK_ADDR").unwrap_or_else(|_| "0.0.0.0:8787".to_string()),
+            helius_webhook_auth: std::env::var("HELIUS_WEBHOOK_AUTH").ok().filter(|v| !v.is_empty()),
+        }
+    }
+}
+
+/// Build one source by name
+pub fn source_by_name(name: &str, config: &SourceConfig) -> Result<Box<dyn TransactionSource>> {
+    match name.trim().to_lowercase().as_str() {
+        "yellowstone" | "grpc" => Ok(Box::new(YellowstoneSource::new(
+            config.yellowstone_grpc_http.clone(),
+            config.yellowstone_grpc_token.clone(),
+        ))),
+        "websocket" | "ws" => Ok(Box::new(WebSocketSource::new(config.rpc_wss.clone(), config.rpc_http.clone()))),
+        "helius" | "helius_webhook" => Ok(Box::new(HeliusWebhookSource::new(
+            config.helius_webhook_addr.clone(),
+            config.helius_webhook_auth.clone(),
+        ))),
+        "replay" => Ok(Box::new(FileReplaySource::from_env()?)),
+        other => Err(anyhow!("Unknown transaction source '{}'", other)),
+    }
+}
+
+/// The sources named in TRANSACTION_SOURCES (default: yellowstone), merged when more than one and
+/// recorded to TRANSACTION_RECORD_PATH when that is set
+pub fn from_env(config: &SourceConfig) -> Result<Box<dyn TransactionSource>> {
+    let names = std::env::var("TRANSACTION_SOURCES").unwrap_or_else(|_| "yellowstone".to_string());
+    let mut sources = names
+        .split(',')
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| source_by_name(name, config))
+        .collect::<Result<Vec<_>>>()?;
+    let source: Box<dyn TransactionSource> = match sources.len() {
+        0 => return Err(anyhow!("TRANSACTION_SOURCES is empty")),
+        1 => sources.remove(0),
+        _ => Box::new(MergedSource::new(sources)),
+    };
+    match std::env::var("TRANSACTION_RECORD_PATH").ok().filter(|p| !p.is_empty()) {
+        Some(path) => Ok(Box::new(RecordingSource::new(source, PathBuf::from(path)))),
+        None => Ok(source),
+    }
+}
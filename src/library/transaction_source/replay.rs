@@ -0,0 +1,173 @@
+//! Recording and replay of transaction streams.
+//!
+//! With `TRANSACTION_RECORD_PATH` set, every transaction delivered by the live sources is appended
+//! to that file as a JSON line (`slot`, `received_at_ms`, base64 protobuf). The `replay` source
+//! reads such a file back with the original spacing between transactions, divided by
+//! `REPLAY_SPEED` (`0` replays as fast as the processor consumes). Recorded transactions are
+//! replayed as-is, without re-applying the subscription filter.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use super::{SourceFilter, TransactionSource, TransactionStream};
+use crate::library::parser_fixtures::{decode_transaction, encode_transaction};
+
+/// One line of a recording
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedTransaction {
+    pub slot: u64,
+    /// When the transaction was received, unix milliseconds
+    pub received_at_ms: i64,
+    pub transaction_base64: String,
+}
+
+pub struct FileReplaySource {
+    path: PathBuf,
+    /// Playback speed multiplier; 0 disables the delays
+    speed: f64,
+    /// Start over at the end of the file instead of ending the stream
+    looped: bool,
+}
+
+impl FileReplaySource {
+    pub fn new(path: PathBuf, speed: f64, looped: bool) -> Self {
+        Self { path, speed: speed.max(0.0), looped }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        let path = std::env::var("REPLAY_FILE")
+            .ok()
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| anyhow!("REPLAY_FILE is required for the replay transaction source"))?;
+        let speed = std::env::var("REPLAY_SPEED")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(1.0);
+        let looped = std::env::var("REPLAY_LOOP")
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(false);
+        Ok(Self::new(PathBuf::from(path), speed, looped))
+    }
+
+    fn delay(&self, previous_ms: Option<i64>, current_ms: i64) -> Option<Duration> {
+        if self.speed == 0.0 {
+            return None;
+        }
+        let gap_ms = current_ms - previous_ms?;
+        (gap_ms > 0).then(|| Duration::from_millis(gap_ms as u64).div_f64(self.speed))
+    }
+}
+
+#[async_trait]
+impl TransactionSource for FileReplaySource {
+    fn name(&self) -> &str {
+        "replay"
+    }
+
+    async fn subscribe(&self, _filter: &SourceFilter) -> Result<TransactionStream> {
+        // Fail on a missing file now rather than as the first stream item
+        tokio::fs::metadata(&self.path)
+            .await
+            .map_err(|e| anyhow!("Cannot read replay file {}: {}", self.path.display(), e))?;
+
+        let source = Self::new(self.path.clone(), self.speed, self.looped);
+        let (tx, rx) = mpsc::channel(1024);
+        tokio::spawn(async move {
+            loop {
+                let file = match tokio::fs::File::open(&source.path).await {
+                    Ok(file) => file,
+                    Err(e) => {
+                        let _ = tx.send(Err(anyhow!("Cannot open replay file: {}", e))).await;
+                        return;
+                    }
+                };
+                let mut lines = BufReader::new(file).lines();
+                let mut previous_ms = None;
+                let mut line_number = 0;
+                while let Ok(Some(line)) = lines.next_line().await {
+                    line_number += 1;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let item = serde_json::from_str::<RecordedTransaction>(&line)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|recorded| Ok((recorded.received_at_ms, decode_transaction(&recorded.transaction_base64)?)));
+                    let (received_at_ms, txn) = match item {
+                        Ok(item) => item,
+                        Err(e) => {
+                            eprintln!("Skipping replay line {}: {}", line_number, e);
+                            continue;
+                        }
+                    };
+                    if let Some(delay) = source.delay(previous_ms, received_at_ms) {
+                        tokio::time::sleep(delay).await;
+                    }
+                    previous_ms = Some(received_at_ms);
+                    if tx.send(Ok(txn)).await.is_err() {
+                        return;
+                    }
+                }
+                if !source.looped {
+                    return;
+                }
+            }
+        });
+
+        Ok(Box::pin(ReceiverStream::new(rx)))
+    }
+}
+
+/// Wraps a source and appends everything it delivers to a recording file
+pub struct RecordingSource {
+    inner: Box<dyn TransactionSource>,
+    path: PathBuf,
+}
+
+impl RecordingSource {
+    pub fn new(inner: Box<dyn TransactionSource>, path: PathBuf) -> Self {
+        Self { inner, path }
+    }
+}
+
+#[async_trait]
+impl TransactionSource for RecordingSource {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn subscribe(&self, filter: &SourceFilter) -> Result<TransactionStream> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| anyhow!("Cannot open recording file {}: {}", self.path.display(), e))?;
+        let file = Arc::new(Mutex::new(file));
+
+        let stream = self.inner.subscribe(filter).await?;
+        let recorded = stream.inspect(move |item| {
+            if let Ok(txn) = item {
+                let line = RecordedTransaction {
+                    slot: txn.slot,
+                    received_at_ms: chrono::Utc::now().timestamp_millis(),
+                    transaction_base64: encode_transaction(txn),
+                };
+                if let Ok(json) = serde_json::to_string(&line) {
+                    if let Err(e) = writeln!(file.lock().unwrap(), "{}", json) {
+                        eprintln!("Failed to record transaction: {}", e);
+                    }
+                }
+            }
+        });
+        Ok(Box::pin(recorded))
+    }
+}
@@ -0,0 +1,126 @@
+//! Monitoring scope: which DEX programs and account sets a subscription covers.
+//!
+//! By default a subscription watches the target wallets only. `MONITOR_PROGRAMS` adds whole DEX
+//! programs (by name, resolved for the configured cluster, or by program id), and the
+//! `MONITOR_ACCOUNTS_*` lists add, exclude or require specific accounts. The scope is turned into
+//! a [`SourceFilter`], from which the Yellowstone source builds its subscribe request; the other
+//! sources apply the same lists locally. DEXes switched off with `ENABLED_DEXES` are never
+//! subscribed to, even when listed in `MONITOR_PROGRAMS`.
+
+use std::str::FromStr;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+
+use crate::common::cluster::DexProgram;
+
+use super::SourceFilter;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MonitorScope {
+    /// Programs whose transactions are all delivered
+    pub programs: Vec<Pubkey>,
+    /// Extra accounts whose transactions are delivered
+    pub accounts_include: Vec<String>,
+    /// Transactions touching any of these are dropped
+    pub accounts_exclude: Vec<String>,
+    /// Transactions must touch all of these
+    pub accounts_required: Vec<String>,
+    pub include_failed: bool,
+}
+
+/// A program by DEX name (`pumpfun`, `pumpswap`, `raydium_launchpad`) or by id
+pub fn parse_program(value: &str) -> Result<Pubkey, String> {
+    match DexProgram::from_name(value) {
+        Some(dex) => Ok(dex.id()),
+        None => Pubkey::from_str(value.trim())
+            .map_err(|_| format!("Unknown program '{}': use pumpfun, pumpswap, raydium_launchpad or a program id", value.trim())),
+    }
+}
+
+/// Comma separated account list; every entry has to be a valid pubkey
+pub fn parse_accounts(value: &str) -> Result<Vec<String>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            Pubkey::from_str(entry)
+                .map(|key| key.to_string())
+                .map_err(|_| format!("Invalid account '{}' in monitoring scope", entry))
+        })
+        .collect()
+}
+
+/// A DEX program switched off through `ENABLED_DEXES`; other programs are never disabled
+fn disabled_dex(program: &Pubkey) -> bool {
+    DexProgram::ALL.iter().any(|dex| dex.id() == *program && !dex.is_enabled())
+}
+
+fn accounts_var(name: &str) -> Result<Vec<String>, String> {
+    match std::env::var(name) {
+        Ok(value) => parse_accounts(&value).map_err(|e| format!("{}: {}", name, e)),
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+impl MonitorScope {
+    /// MONITOR_PROGRAMS, MONITOR_ACCOUNTS_INCLUDE, MONITOR_ACCOUNTS_EXCLUDE,
+    /// MONITOR_ACCOUNTS_REQUIRED (all comma separated, default empty) and MONITOR_INCLUDE_FAILED
+    /// (default false). DEX programs disabled through ENABLED_DEXES are left out. A typo is an
+    /// error rather than a silently wider or narrower subscription.
+    pub fn from_env() -> Result<Self, String> {
+        crate::common::cluster::validate_dex_toggles()?;
+        let programs = std::env::var("MONITOR_PROGRAMS")
+            .unwrap_or_default()
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(parse_program)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("MONITOR_PROGRAMS: {}", e))?
+            .into_iter()
+            .filter(|program| !disabled_dex(program))
+            .collect();
+        Ok(Self {
+            programs,
+            accounts_include: accounts_var("MONITOR_ACCOUNTS_INCLUDE")?,
+            accounts_exclude: accounts_var("MONITOR_ACCOUNTS_EXCLUDE")?,
+            accounts_required: accounts_var("MONITOR_ACCOUNTS_REQUIRED")?,
+            include_failed: std::env::var("MONITOR_INCLUDE_FAILED")
+                .ok()
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(false),
+        })
+    }
+
+    /// Filter covering `targets` plus everything in the scope, without duplicates
+    pub fn filter_for(&self, targets: &[String]) -> SourceFilter {
+        let mut account_include: Vec<String> = Vec::new();
+        let candidates = targets
+            .iter()
+            .cloned()
+            .chain(self.programs.iter().map(|p| p.to_string()))
+            .chain(self.accounts_include.iter().cloned());
+        for account in candidates {
+            if !account_include.contains(&account) {
+                account_include.push(account);
+            }
+        }
+        SourceFilter {
+            account_include,
+            account_exclude: self.accounts_exclude.clone(),
+            account_required: self.accounts_required.clone(),
+            include_failed: self.include_failed,
+        }
+    }
+
+    /// One line for the startup log
+    pub fn describe(&self) -> String {
+        format!(
+            "{} programs, {} extra accounts, {} excluded, {} required{}",
+            self.programs.len(),
+            self.accounts_include.len(),
+            self.accounts_exclude.len(),
+            self.accounts_required.len(),
+            if self.include_failed { ", failed included" } else { "" }
+        )
+    }
+}
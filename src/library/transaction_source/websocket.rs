@@ -0,0 +1,106 @@
+//! Standard RPC WebSocket source for setups without a Geyser endpoint: `logsSubscribe` for each
+//! watched account, then `getTransaction` for every signature it reports. Slower than
+//! Yellowstone (confirmed commitment plus a fetch per transaction) but works with any RPC node.
+
+use std::sync::Arc;
+use std::str::FromStr;
+use anyhow::{anyhow, Result};
+use anchor_client::solana_client::nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient};
+use anchor_client::solana_client::rpc_config::{RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use anchor_client::solana_sdk::{commitment_config::CommitmentConfig, signature::Signature};
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use solana_transaction_status::UiTransactionEncoding;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use super::{convert, SourceFilter, TransactionSource, TransactionStream};
+
+const CHANNEL_CAPACITY: usize = 1024;
+
+pub struct WebSocketSource {
+    ws_url: String,
+    rpc_http: String,
+}
+
+impl WebSocketSource {
+    pub fn new(ws_url: String, rpc_http: String) -> Self {
+        Self { ws_url, rpc_http }
+    }
+}
+
+#[async_trait]
+impl TransactionSource for WebSocketSource {
+    fn name(&self) -> &str {
+        "websocket"
+    }
+
+    async fn subscribe(&self, filter: &SourceFilter) -> Result<TransactionStream> {
+        if self.ws_url.is_empty() {
+            return Err(anyhow!("RPC_WSS is required for the websocket transaction source"));
+        }
+        if filter.account_include.is_empty() {
+            return Err(anyhow!("The websocket source needs at least one account to watch"));
+        }
+
+        // Subscriptions borrow the client, so both live in a task that forwards into a channel
+        let pubsub = PubsubClient::new(&self.ws_url)
+            .await
+            .map_err(|e| anyhow!("Failed to connect to {}: {}", self.ws_url, e))?;
+        let rpc = Arc::new(RpcClient::new_with_commitment(self.rpc_http.clone(), CommitmentConfig::confirmed()));
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let accounts = filter.account_include.clone();
+        let filter = filter.clone();
+
+        tokio::spawn(async move {
+            let mut subscriptions = Vec::with_capacity(accounts.len());
+            for account in &accounts {
+                let config = RpcTransactionLogsConfig { commitment: Some(CommitmentConfig::confirmed()) };
+                match pubsub.logs_subscribe(RpcTransactionLogsFilter::Mentions(vec![account.clone()]), config).await {
+                    Ok((stream, _unsubscribe)) => subscriptions.push(stream),
+                    Err(e) => {
+                        let _ = tx.send(Err(anyhow!("logsSubscribe for {} failed: {}", account, e))).await;
+                        return;
+                    }
+                }
+            }
+
+            let mut logs = stream::select_all(subscriptions);
+            while let Some(notification) = logs.next().await {
+                if notification.value.err.is_some() && !filter.include_failed {
+                    continue;
+                }
+                let signature = match Signature::from_str(&notification.value.signature) {
+                    Ok(signature) => signature,
+                    Err(_) => continue,
+                };
+                let config = RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                };
+                // A single failed fetch is not fatal for the stream
+                let fetched = rpc.get_transaction_with_config(&signature, config).await
+                    .map_err(anyhow::Error::from)
+                    .and_then(|encoded| convert::from_encoded(&encoded));
+                let txn = match fetched {
+                    Ok(txn) => txn,
+                    Err(e) => {
+                        eprintln!("WebSocket source skipped {}: {}", signature, e);
+                        continue;
+                    }
+                };
+                // logsSubscribe only knows "mentions"; exclusions and required accounts are local
+                if !filter.matches(&txn) {
+                    continue;
+                }
+                if tx.send(Ok(txn)).await.is_err() {
+                    break;
+                }
+            }
+            let _ = tx.send(Err(anyhow!("WebSocket log subscription ended"))).await;
+        });
+
+        Ok(Box::pin(ReceiverStream::new(rx)))
+    }
+}
@@ -0,0 +1,114 @@
+//! Yellowstone gRPC (Geyser) source: processed-commitment transactions straight from a Geyser
+//! plugin, with a keepalive ping every 30 seconds.
+
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::{SinkExt, StreamExt};
+use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient};
+use yellowstone_grpc_proto::geyser::{
+    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest, SubscribeRequestFilterTransactions,
+    SubscribeRequestPing,
+};
+
+use super::{SourceFilter, TransactionSource, TransactionStream};
+
+const SUBSCRIBE_ATTEMPTS: u32 = 3;
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+pub struct YellowstoneSource {
+    endpoint: String,
+    x_token: String,
+}
+
+impl YellowstoneSource {
+    pub fn new(endpoint: String, x_token: String) -> Self {
+        Self { endpoint, x_token }
+    }
+}
+
+/// Subscribe request for a filter: one transaction filter carrying the include, exclude and
+/// required account lists
+pub fn subscribe_request(filter: &SourceFilter) -> SubscribeRequest {
+    SubscribeRequest {
+        transactions: maplit::hashmap! {
+            "All".to_owned() => SubscribeRequestFilterTransactions {
+                vote: Some(false),
+                failed: Some(filter.include_failed),
+                signature: None,
+                account_include: filter.account_include.clone(),
+                account_exclude: filter.account_exclude.clone(),
+                account_required: filter.account_required.clone(),
+            }
+        },
+        commitment: Some(CommitmentLevel::Processed as i32),
+        ..Default::default()
+    }
+}
+
+#[async_trait]
+impl TransactionSource for YellowstoneSource {
+    fn name(&self) -> &str {
+        "yellowstone"
+    }
+
+    async fn subscribe(&self, filter: &SourceFilter) -> Result<TransactionStream> {
+        let mut client = GeyserGrpcClient::build_from_shared(self.endpoint.clone())
+            .map_err(|e| anyhow!("Failed to build client: {}", e))?
+            .x_token::<String>(Some(self.x_token.clone()))
+            .map_err(|e| anyhow!("Failed to set x_token: {}", e))?
+            .tls_config(ClientTlsConfig::new().with_native_roots())
+            .map_err(|e| anyhow!("Failed to set tls config: {}", e))?
+            .connect()
+            .await
+            .map_err(|e| anyhow!("Failed to connect: {}", e))?;
+
+        let mut attempt = 0;
+        let (mut subscribe_tx, stream) = loop {
+            match client.subscribe().await {
+                Ok(pair) => break pair,
+                Err(e) => {
+                    attempt += 1;
+                    if attempt >= SUBSCRIBE_ATTEMPTS {
+                        return Err(anyhow!("Failed to subscribe after {} attempts: {}", SUBSCRIBE_ATTEMPTS, e));
+                    }
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                }
+            }
+        };
+
+        let request = subscribe_request(filter);
+        subscribe_tx
+            .send(request)
+            .await
+            .map_err(|e| anyhow!("Failed to send subscribe request: {}", e))?;
+
+        // The ping task owns the request sink; it ends when the server side goes away
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PING_INTERVAL);
+            loop {
+                interval.tick().await;
+                let ping = SubscribeRequest {
+                    ping: Some(SubscribeRequestPing { id: 0 }),
+                    ..Default::default()
+                };
+                if subscribe_tx.send(ping).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        // Keep the client alive for as long as the stream is consumed
+        let transactions = stream.filter_map(move |update| {
+            let _client = &client;
+            futures::future::ready(match update {
+                Ok(update) => match update.update_oneof {
+                    Some(UpdateOneof::Transaction(txn)) => Some(Ok(txn)),
+                    _ => None,
+                },
+                Err(e) => Some(Err(anyhow!("Stream error: {:?}", e))),
+            })
+        });
+        Ok(Box::pin(transactions))
+    }
+}
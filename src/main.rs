@@ -17,6 +17,7 @@ use solana_vntr_sniper::{
     processor::{
         sniper_bot::{start_token_queue_monitoring, SniperConfig},
         swap::SwapProtocol,
+        trade_ledger,
     },
     library::{
         cache_maintenance, 
@@ -453,12 +454,244 @@ async fn close_all_token_accounts(config: &Config) -> Result<(), String> {
 
 
 
+/// `wallets new [--count N] [--prefix P] [--ignore-case] [--label L] [--max-attempts N]`:
+/// generate trading keypairs into the encrypted keystore and register them in .env
+fn wallets_new(args: &[String]) -> Result<(), String> {
+    use solana_vntr_sniper::library::audit_log::{self, AuditAction};
+    use solana_vntr_sniper::library::keystore;
+
+    let arg_value = |flag: &str| args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .cloned();
+    let count = arg_value("--count").and_then(|v| v.parse::<usize>().ok()).unwrap_or(1);
+    let prefix = arg_value("--prefix");
+    let ignore_case = args.contains(&"--ignore-case".to_string());
+    let label = arg_value("--label");
+    let max_attempts = arg_value("--max-attempts").and_then(|v| v.parse::<u64>().ok()).unwrap_or(u64::MAX);
+
+    if let Some(prefix) = &prefix {
+        keystore::validate_prefix(prefix).map_err(|e| e.to_string())?;
+        println!(
+            "Grinding for addresses starting with '{}' (~{:.0} keypairs each)...",
+            prefix, keystore::expected_attempts(prefix, ignore_case)
+        );
+    }
+
+    let passphrase = match std::env::var("KEYSTORE_PASSPHRASE") {
+        Ok(passphrase) if !passphrase.is_empty() => passphrase,
+        // Read without echo, twice, so a typo cannot lock the new wallets away
+        _ => {
+            let passphrase = rpassword::prompt_password("Keystore passphrase (KEYSTORE_PASSPHRASE is not set): ")
+                .map_err(|e| e.to_string())?;
+            let confirmation = rpassword::prompt_password("Repeat the passphrase: ").map_err(|e| e.to_string())?;
+            if passphrase != confirmation {
+                return Err("The passphrases do not match".to_string());
+            }
+            passphrase
+        }
+    };
+    if passphrase.len() < 8 {
+        return Err("The keystore passphrase must be at least 8 characters".to_string());
+    }
+
+    let dir = keystore::keystore_dir();
+    for _ in 0..count {
+        let (keypair, attempts) = keystore::generate(prefix.as_deref(), ignore_case, max_attempts)
+            .map_err(|e| e.to_string())?;
+        let entry = keystore::encrypt(&keypair, &passphrase, label.clone()).map_err(|e| e.to_string())?;
+        let path = keystore::save(&dir, &entry).map_err(|e| e.to_string())?;
+        keystore::register_wallet(std::path::Path::new(".env"), &keypair.pubkey()).map_err(|e| e.to_string())?;
+        audit_log::record(
+            AuditAction::KeyUsed,
+            "keystore",
+            serde_json::json!({ "pubkey": keypair.pubkey().to_string(), "use": "generated" }),
+        );
+        println!("{} {} ({} tried), saved to {}", "New wallet".green(), keypair.pubkey(), attempts, path.display());
+    }
+    Ok(())
+}
+
+/// `audit verify [PATH]`: check the audit log's hash chain
+fn audit_command(args: &[String]) -> Result<(), String> {
+    use solana_vntr_sniper::library::audit_log;
+
+    match args.first().map(String::as_str) {
+        Some("verify") => {
+            let path = args.get(1).cloned()
+                .or_else(|| std::env::var("AUDIT_LOG_PATH").ok().filter(|p| !p.is_empty()))
+                .unwrap_or_else(|| "audit_log.jsonl".to_string());
+            let entries = audit_log::verify(std::path::Path::new(&path)).map_err(|e| e.to_string())?;
+            println!("{} {} ({} entries)", "Audit chain intact:".green(), path, entries);
+            Ok(())
+        }
+        _ => Err("Usage: audit verify [PATH]".to_string()),
+    }
+}
+
+/// `instance status` / `instance takeover [INSTANCE_ID]`: show or hand over the trading lock
+fn instance_command(args: &[String]) -> Result<(), String> {
+    use solana_vntr_sniper::library::audit_log::{self, AuditAction};
+    use solana_vntr_sniper::library::instance_lock::{self, InstanceLockConfig};
+
+    // The lock is named after the wallet's address; no key is loaded for it
+    let config = InstanceLockConfig::from_env();
+    let path = match config.path.clone() {
+        Some(path) => path,
+        None => config.lock_path(&solana_vntr_sniper::common::config::wallet_pubkey().map_err(|e| e.to_string())?),
+    };
+    match args.first().map(String::as_str) {
+        Some("status") => {
+            match instance_lock::read_lease(&path) {
+                Some(lease) => println!(
+                    "{} holds the trading lock ({}), renewed {}s ago",
+                    lease.holder,
+                    path.display(),
+                    chrono::Utc::now().timestamp() - lease.renewed_at
+                ),
+                None => println!("No instance holds the trading lock ({})", path.display()),
+            }
+            Ok(())
+        }
+        Some("takeover") => {
+            let target = args.get(1).map(String::as_str);
+            instance_lock::hand_over(&path, target, chrono::Utc::now().timestamp()).map_err(|e| e.to_string())?;
+            audit_log::record(
+                AuditAction::LockHandedOver,
+                "cli",
+                serde_json::json!({ "lock": path.display().to_string(), "to": target }),
+            );
+            match target {
+                Some(id) => println!("Handed the trading lock to {}; the previous holder goes monitor-only on its next renewal", id),
+                None => println!("Released the trading lock; the first standby instance to renew takes it"),
+            }
+            Ok(())
+        }
+        _ => Err("Usage: instance status | instance takeover [INSTANCE_ID]".to_string()),
+    }
+}
+
+/// `score check [PATH]`: validate a risk weights file (RISK_WEIGHTS_PATH by default) and print
+/// the weights in effect, a starting point for a custom file
+fn score_command(args: &[String]) -> Result<(), String> {
+    use solana_vntr_sniper::processor::risk_score::{self, RiskWeights};
+
+    match args.first().map(String::as_str) {
+        Some("check") => {
+            let path = args.get(1).map(std::path::PathBuf::from).unwrap_or_else(risk_score::weights_path);
+            let weights = if path.exists() {
+                let weights = RiskWeights::load(&path).map_err(|e| e.to_string())?;
+                println!("{} {}", "Valid risk weights:".green(), path.display());
+                weights
+            } else {
+                println!("{} does not exist, the built-in weights are used:", path.display());
+                RiskWeights::default()
+            };
+            println!("{}", serde_json::to_string_pretty(&weights).map_err(|e| e.to_string())?);
+            Ok(())
+        }
+        _ => Err("Usage: score check [PATH]".to_string()),
+    }
+}
+
+/// The educational monitor publishing into SignalConfirmation, when SIGNAL_CONFIRMATION_MIN is set,
+/// with the task refreshing its trending ranks
+fn start_confirmation_monitor(
+    config: &Config,
+    cancel_token: CancellationToken,
+) -> Option<(Arc<solana_vntr_sniper::processor::educational_monitor::EducationalMonitor>, JoinHandle<()>)> {
+    if !solana_vntr_sniper::processor::signal_confirmation::SignalConfirmation::shared().config().enabled() {
+        return None;
+    }
+    let monitor = Arc::new(solana_vntr_sniper::processor::educational_monitor::EducationalMonitor::new(config.clone(), None));
+    let handle = tokio::spawn({
+        let monitor = monitor.clone();
+        async move {
+            let mut trending_timer = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                tokio::select! {
+                    _ = cancel_token.cancelled() => break,
+                    _ = trending_timer.tick() => {
+                        monitor.refresh_trending().await;
+                    }
+                }
+            }
+        }
+    });
+    Some((monitor, handle))
+}
+
+/// Writes out the audit log's queued entries when main returns, whichever path it takes
+struct AuditLogShutdown;
+
+impl Drop for AuditLogShutdown {
+    fn drop(&mut self) {
+        solana_vntr_sniper::library::audit_log::shutdown();
+    }
+}
+
+/// Exit with `code` once the audit log is written out; `std::process::exit` skips every drop
+fn exit(code: i32) -> ! {
+    solana_vntr_sniper::library::audit_log::shutdown();
+    std::process::exit(code)
+}
+
 #[tokio::main]
 async fn main() {
+    let _audit_log = AuditLogShutdown;
+
+    // Wallet generation does not need a configured bot
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("wallets") {
+        dotenv::dotenv().ok();
+        let result = match args.get(2).map(String::as_str) {
+            Some("new") => wallets_new(&args[3..]),
+            _ => Err("Usage: wallets new [--count N] [--prefix P] [--ignore-case] [--label L] [--max-attempts N]".to_string()),
+        };
+        if let Err(e) = result {
+            eprintln!("{}", e);
+            exit(1);
+        }
+        return;
+    }
+    if args.get(1).map(String::as_str) == Some("score") {
+        dotenv::dotenv().ok();
+        if let Err(e) = score_command(&args[2..]) {
+            eprintln!("{}", e);
+            exit(1);
+        }
+        return;
+    }
+    if args.get(1).map(String::as_str) == Some("audit") {
+        dotenv::dotenv().ok();
+        if let Err(e) = audit_command(&args[2..]) {
+            eprintln!("{}", e);
+            exit(1);
+        }
+        return;
+    }
+    if args.get(1).map(String::as_str) == Some("instance") {
+        dotenv::dotenv().ok();
+        if let Err(e) = instance_command(&args[2..]) {
+            eprintln!("{}", e);
+            exit(1);
+        }
+        return;
+    }
+
     /* Initial Settings */
     let config = Config::new().await;
     let config = config.lock().await;
 
+    // With TRADING_ENABLED=false nothing in this process may sign or send
+    match solana_vntr_sniper::library::trading_lockdown::self_check() {
+        Ok(mode) => println!("🔒 Trading mode: {}", mode),
+        Err(e) => {
+            eprintln!("Trading lockdown self-check failed: {}", e);
+            exit(1);
+        }
+    }
+
     /* Running Bot */
     let run_msg = RUN_MSG;
     println!("{}", run_msg);
@@ -537,15 +770,97 @@ async fn main() {
                     return;
                 }
             }
+        } else if args.contains(&"--export-trades".to_string()) {
+            println!("Exporting trades from the trade ledger...");
+
+            let arg_value = |flag: &str| args.iter()
+                .position(|a| a == flag)
+                .and_then(|i| args.get(i + 1))
+                .cloned();
+            let date = |flag: &str| arg_value(flag)
+                .and_then(|v| chrono::NaiveDate::parse_from_str(&v, "%Y-%m-%d").ok());
+            let trades_path = arg_value("--out").unwrap_or_else(|| "trades_export.csv".to_string());
+            let daily_path = arg_value("--daily-out").unwrap_or_else(|| "daily_pnl.csv".to_string());
+
+            match trade_ledger::export_files(date("--from"), date("--to"), &trades_path, &daily_path) {
+                Ok(count) => {
+                    println!("Exported {} trades to {} and daily PnL to {}", count, trades_path, daily_path);
+                    if let Some(html_path) = arg_value("--html-out") {
+                        let trades = trade_ledger::TradeLedger::shared().trades();
+                        let report = solana_vntr_sniper::processor::report_export::pnl_report(
+                            &trades, date("--from"), date("--to"), chrono::Utc::now(),
+                        );
+                        match std::fs::write(&html_path, report.to_html()) {
+                            Ok(()) => println!("Wrote the PnL report to {}", html_path),
+                            Err(e) => eprintln!("Failed to write the PnL report: {}", e),
+                        }
+                    }
+                    return;
+                },
+                Err(e) => {
+                    eprintln!("Failed to export trades: {}", e);
+                    return;
+                }
+            }
         }
     }
 
     // Initialize token account list
     initialize_token_account_list(&config).await;
+
+    // Settle orders a previous run left open, so nothing it sent is bought again
+    let settled = solana_vntr_sniper::processor::order_ledger::OrderLedger::shared()
+        .reconcile(config.app_state.rpc_api.as_ref(), &config.app_state.wallet.pubkey())
+        .await;
+    for order in settled {
+        println!("🧾 Order {} for {} settled as {:?} on startup", order.id, order.mint, order.state);
+    }
+
+    // Only the instance holding the trading lock for this wallet trades; --takeover claims it
+    let instance_lock_handle = solana_vntr_sniper::library::instance_lock::start_instance_lock(
+        config.app_state.wallet.pubkey(),
+        args.contains(&"--takeover".to_string()),
+        cancel_token.clone(),
+    );
     
     // Start cache maintenance service (clean up expired cache entries every 60 seconds)
     let cache_maintenance_handle = solana_vntr_sniper::library::cache_maintenance::start_cache_maintenance(60, cancel_token.clone()).await;
     println!("Cache maintenance service started");
+
+    // Start swap event export (Kafka/NATS) if EVENT_EXPORT_BACKEND is set
+    let event_export_handle = solana_vntr_sniper::library::event_export::start_event_export(cancel_token.clone()).await;
+
+    if let Some(reason) = solana_vntr_sniper::processor::trading_control::buys_paused_reason() {
+        println!("⏸️ {} (carried over from the last run); /resume to buy again", reason);
+    }
+
+    // /pause and /resume from the alert chat
+    let telegram_commands_handle = solana_vntr_sniper::processor::telegram_commands::start_telegram_commands(
+        None,
+        cancel_token.clone(),
+    ).await;
+
+    // Serve /healthz and /metrics if STATUS_ADDR is set
+    let status_server_handle = solana_vntr_sniper::library::status_server::start_status_server(cancel_token.clone()).await;
+
+    // Flag alerts and pause buys while the streams fall behind the chain
+    let slot_lag_handle = solana_vntr_sniper::library::slot_lag::start_slot_lag_monitor(
+        config.app_state.rpc_nonblocking_client.clone(),
+        cancel_token.clone(),
+    );
+
+    // Tell a quiet market from a broken stream when no alerts go out for hours
+    let heartbeat_handle = solana_vntr_sniper::library::heartbeat::start_heartbeat(cancel_token.clone());
+
+    // Merge community scam lists into the blacklist if SCAM_LIST_URLS is set
+    let scam_list_handle = solana_vntr_sniper::processor::scam_lists::start_scam_list_sync(cancel_token.clone());
+
+    // Keep the bot's address lookup table filled if LOOKUP_TABLE_ENABLED is set
+    let lookup_table_handle = solana_vntr_sniper::block_engine::lookup_table::start_lookup_table_maintenance(
+        config.app_state.rpc_nonblocking_client.clone(),
+        config.app_state.wallet.clone(),
+        cancel_token.clone(),
+    );
     
     // Selling instruction cache removed - no maintenance needed
 
@@ -636,6 +951,49 @@ async fn main() {
     ).await;
     println!("Risk management service started successfully");
 
+    // Track on-chain balances of the wallet's token accounts
+    let balance_tracker_handle = solana_vntr_sniper::processor::balance_tracker::start_balance_tracker(
+        config.yellowstone_grpc_http.clone(),
+        config.yellowstone_grpc_token.clone(),
+        Arc::new(config.app_state.clone()),
+        cancel_token.clone(),
+    ).await;
+
+    // Alert on low SOL in the trading wallets and refill them from the treasury
+    let sol_balance_handle = solana_vntr_sniper::processor::sol_balance_monitor::start_sol_balance_monitor(
+        Arc::new(config.app_state.clone()),
+        cancel_token.clone(),
+    );
+
+    // Keep the SOL/USD price current for stablecoin-quoted pools and the market regime's SOL trend
+    let sol_price_handle = (solana_vntr_sniper::common::quote_asset::stable_quotes_enabled()
+        || solana_vntr_sniper::processor::market_regime::MarketRegime::shared().config().enabled)
+        .then(|| solana_vntr_sniper::common::quote_asset::start_sol_price_refresh(cancel_token.clone()));
+
+    // Compound, sweep or convert realized profits on a schedule
+    let profit_policy_handle = solana_vntr_sniper::processor::profit_policy::start_profit_policy(
+        Arc::new(config.app_state.clone()),
+        cancel_token.clone(),
+    );
+
+    // Watch mint/burn/transfer activity on tracked tokens and target wallets' exchange deposits
+    let token_program_monitor_handle = solana_vntr_sniper::processor::token_program_monitor::start_token_program_monitor(
+        config.yellowstone_grpc_http.clone(),
+        config.yellowstone_grpc_token.clone(),
+        Arc::new(config.app_state.clone()),
+        target_addresses.clone(),
+        cancel_token.clone(),
+    ).await;
+
+    // Snipe pump.fun tokens as their bonding curve migrates to PumpSwap
+    let graduation_sniper_handle = solana_vntr_sniper::processor::graduation_sniper::start_graduation_sniper(
+        config.yellowstone_grpc_http.clone(),
+        config.yellowstone_grpc_token.clone(),
+        Arc::new(config.app_state.clone()),
+        Arc::new(config.swap_config.clone()),
+        cancel_token.clone(),
+    ).await;
+
     // Create copy trading config
     let sniper_config = SniperConfig {
         yellowstone_grpc_http: config.yellowstone_grpc_http.clone(),
@@ -682,6 +1040,46 @@ async fn main() {
         println!("✅ Shutdown signal sent to all tasks");
     });
 
+    // With SIGNAL_CONFIRMATION_MIN set, buys are confirmed by the educational monitor's signals
+    // and trending ranks, so the monitor runs here too, with its own alerts off
+    let confirmation_monitor = start_confirmation_monitor(&config, cancel_token.clone());
+    if confirmation_monitor.is_some() {
+        println!("✅ Signal confirmation monitor started");
+    }
+
+    // Optional config-driven pipeline (PIPELINE / PIPELINE_PATH) over the live stream
+    let pipeline_handle = {
+        let ctx = solana_vntr_sniper::processor::pipeline::PipelineContext {
+            sniper: Some(Arc::new(sniper_config.clone())),
+            telegram: solana_vntr_sniper::processor::telegram_alerts::init_from_env().ok().flatten().map(Arc::new),
+            rpc: Some(config.app_state.rpc_nonblocking_client.clone()),
+            monitor: confirmation_monitor.as_ref().map(|(monitor, _)| monitor.clone()),
+            ..Default::default()
+        };
+        let source_config = solana_vntr_sniper::library::transaction_source::SourceConfig::new(
+            config.yellowstone_grpc_http.clone(),
+            config.yellowstone_grpc_token.clone(),
+        );
+        let spec = solana_vntr_sniper::processor::pipeline::PipelineSpec::from_env();
+        let started = match (spec, &confirmation_monitor) {
+            (Ok(Some(spec)), _) => solana_vntr_sniper::processor::pipeline::start(&spec, ctx, source_config, cancel_token.clone()).map(Some),
+            // No pipeline configured: one feeding just the monitor keeps confirmation sources live
+            (Ok(None), Some(_)) => {
+                let spec = solana_vntr_sniper::processor::pipeline::PipelineSpec::preset("monitor").unwrap_or_default();
+                solana_vntr_sniper::processor::pipeline::start(&spec, ctx, source_config, cancel_token.clone()).map(Some)
+            }
+            (Ok(None), None) => Ok(None),
+            (Err(e), _) => Err(e),
+        };
+        match started {
+            Ok(handle) => handle,
+            Err(e) => {
+                eprintln!("❌ Pipeline not started: {:#}", e);
+                None
+            }
+        }
+    };
+
     let token_queue_monitoring_handle = tokio::spawn({
         let config = sniper_config.clone();
         let token = cancel_token.clone();
@@ -707,7 +1105,55 @@ async fn main() {
         handles.push(handle);
     }
     handles.push(cache_maintenance_handle);
+    if let Some(handle) = instance_lock_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = event_export_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = telegram_commands_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = status_server_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = slot_lag_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = heartbeat_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = scam_list_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = lookup_table_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = pipeline_handle {
+        handles.push(handle);
+    }
+    if let Some((_, handle)) = confirmation_monitor {
+        handles.push(handle);
+    }
     handles.push(risk_management_handle);
+    if let Some(handle) = balance_tracker_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = sol_balance_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = profit_policy_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = sol_price_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = token_program_monitor_handle {
+        handles.push(handle);
+    }
+    if let Some(handle) = graduation_sniper_handle {
+        handles.push(handle);
+    }
 
     for handle in handles {
         if let Err(e) = handle.await {
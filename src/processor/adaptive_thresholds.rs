@@ -0,0 +1,219 @@
+//! Alert thresholds that tune themselves to the market's pace. When a signal type fires more
+//! than `ADAPTIVE_ALERTS_PER_HOUR` times within an hour, its threshold is multiplied by
+//! `ADAPTIVE_THRESHOLD_FACTOR` for `ADAPTIVE_THRESHOLD_HOLD_MINS`; if it keeps firing that often
+//! under the raised threshold it is raised again, up to `ADAPTIVE_THRESHOLD_MAX_FACTOR` in all.
+//! Once the hold passes without another raise, the threshold returns to where it was. Every
+//! raise and return is noted for the next report, so a quiet stretch during a frenzy is
+//! explained.
+//!
+//! Disabled while `ADAPTIVE_ALERTS_PER_HOUR` is 0 (the default).
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use crate::common::clock::{system_clock, Clock};
+
+#[derive(Clone, Debug)]
+pub struct AdaptiveConfig {
+    /// Alerts of one signal type per hour above which its threshold is raised; 0 disables
+    pub max_per_hour: usize,
+    /// Multiplier applied per raise
+    pub factor: f64,
+    /// How long a raise holds after the last one
+    pub hold_secs: i64,
+    /// Largest combined multiplier
+    pub max_factor: f64,
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        Self { max_per_hour: 0, factor: 1.5, hold_secs: 3600, max_factor: 4.0 }
+    }
+}
+
+impl AdaptiveConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            max_per_hour: var("ADAPTIVE_ALERTS_PER_HOUR").and_then(|v| v.parse().ok()).unwrap_or(defaults.max_per_hour),
+            factor: var("ADAPTIVE_THRESHOLD_FACTOR")
+                .and_then(|v| v.parse().ok())
+                .filter(|f: &f64| *f > 1.0)
+                .unwrap_or(defaults.factor),
+            hold_secs: var("ADAPTIVE_THRESHOLD_HOLD_MINS")
+                .and_then(|v| v.parse::<i64>().ok())
+                .map(|mins| mins.max(1) * 60)
+                .unwrap_or(defaults.hold_secs),
+            max_factor: var("ADAPTIVE_THRESHOLD_MAX_FACTOR")
+                .and_then(|v| v.parse().ok())
+                .filter(|f: &f64| *f >= 1.0)
+                .unwrap_or(defaults.max_factor),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.max_per_hour > 0
+    }
+}
+
+/// A threshold change to apply: multiply the signal type's threshold by `factor`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Adjustment {
+    /// Raised by `factor`, `total` above its usual level now
+    Raised { signal_type: String, factor: f64, total: f64, alerts: usize },
+    /// Back to its usual level; `factor` undoes the raises
+    Restored { signal_type: String, factor: f64 },
+}
+
+impl Adjustment {
+    pub fn signal_type(&self) -> &str {
+        match self {
+            Adjustment::Raised { signal_type, .. } | Adjustment::Restored { signal_type, .. } => signal_type,
+        }
+    }
+
+    /// Multiplier to apply to the threshold
+    pub fn factor(&self) -> f64 {
+        match self {
+            Adjustment::Raised { factor, .. } | Adjustment::Restored { factor, .. } => *factor,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Adjustment::Raised { signal_type, total, alerts, .. } => {
+                format!("{}: {} alerts in the last hour, threshold raised to {:.2}x", signal_type, alerts, total)
+            }
+            Adjustment::Restored { signal_type, .. } => format!("{}: alert rate calmed down, threshold back to normal", signal_type),
+        }
+    }
+}
+
+struct Raise {
+    total: f64,
+    /// Unix seconds of the last raise
+    at: i64,
+}
+
+pub struct AdaptiveThresholds {
+    config: AdaptiveConfig,
+    /// Unix seconds of each signal type's alerts over the last hour
+    alerts: Mutex<HashMap<String, VecDeque<i64>>>,
+    raised: Mutex<HashMap<String, Raise>>,
+    /// Adjustments not reported yet
+    notes: Mutex<Vec<String>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl AdaptiveThresholds {
+    pub fn new(config: AdaptiveConfig) -> Self {
+        Self {
+            config,
+            alerts: Mutex::new(HashMap::new()),
+            raised: Mutex::new(HashMap::new()),
+            notes: Mutex::new(Vec::new()),
+            clock: system_clock(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(AdaptiveConfig::from_env())
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn config(&self) -> &AdaptiveConfig {
+        &self.config
+    }
+
+    /// Count an alert of `signal_type`; returns the raise it triggers, if any
+    pub fn record(&self, signal_type: &str) -> Option<Adjustment> {
+        if !self.config.enabled() {
+            return None;
+        }
+        let now = self.clock.now().timestamp();
+        let alerts = {
+            let mut alerts = self.alerts.lock().unwrap();
+            let recent = alerts.entry(signal_type.to_string()).or_default();
+            recent.push_back(now);
+            while recent.front().is_some_and(|at| now - at >= 3600) {
+                recent.pop_front();
+            }
+            if recent.len() <= self.config.max_per_hour {
+                return None;
+            }
+            // The next raise needs as many alerts again, under the raised threshold
+            let count = recent.len();
+            recent.clear();
+            count
+        };
+
+        let mut raised = self.raised.lock().unwrap();
+        let current = raised.get(signal_type).map_or(1.0, |raise| raise.total);
+        let total = (current * self.config.factor).min(self.config.max_factor);
+        if total <= current {
+            // Already at the cap: the frenzy goes on, so the raise holds longer
+            if let Some(raise) = raised.get_mut(signal_type) {
+                raise.at = now;
+            }
+            return None;
+        }
+        raised.insert(signal_type.to_string(), Raise { total, at: now });
+        let factor = total / current;
+        let adjustment = Adjustment::Raised { signal_type: signal_type.to_string(), factor, total, alerts };
+        self.notes.lock().unwrap().push(adjustment.describe());
+        Some(adjustment)
+    }
+
+    /// Raises whose hold has passed, each undoing its signal type's raises
+    pub fn expire(&self) -> Vec<Adjustment> {
+        let now = self.clock.now().timestamp();
+        let mut expired = Vec::new();
+        self.raised.lock().unwrap().retain(|signal_type, raise| {
+            if now - raise.at < self.config.hold_secs {
+                return true;
+            }
+            expired.push(Adjustment::Restored { signal_type: signal_type.clone(), factor: 1.0 / raise.total });
+            false
+        });
+        expired.sort_by(|a, b| a.signal_type().cmp(b.signal_type()));
+        self.notes.lock().unwrap().extend(expired.iter().map(Adjustment::describe));
+        expired
+    }
+
+    /// Current multiplier of each raised signal type, sorted by signal type
+    pub fn active(&self) -> Vec<(String, f64)> {
+        let mut active: Vec<(String, f64)> = self.raised.lock().unwrap()
+            .iter()
+            .map(|(signal_type, raise)| (signal_type.clone(), raise.total))
+            .collect();
+        active.sort_by(|a, b| a.0.cmp(&b.0));
+        active
+    }
+
+    /// Adjustments made since the last call, oldest first
+    pub fn take_notes(&self) -> Vec<String> {
+        std::mem::take(&mut *self.notes.lock().unwrap())
+    }
+}
+
+/// Report section listing the adjustments since the last report and the raises still in effect;
+/// empty when there is nothing to say
+pub fn format_adjustments(notes: &[String], active: &[(String, f64)]) -> String {
+    if notes.is_empty() && active.is_empty() {
+        return String::new();
+    }
+    let mut section = String::from("\n🎚️ Adaptive Thresholds:\n");
+    for note in notes {
+        section.push_str(&format!("  • {}\n", note));
+    }
+    if !active.is_empty() {
+        let raised: Vec<String> = active.iter().map(|(signal_type, total)| format!("{} {:.2}x", signal_type, total)).collect();
+        section.push_str(&format!("  Still raised: {}\n", raised.join(", ")));
+    }
+    section
+}
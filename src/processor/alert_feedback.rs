@@ -0,0 +1,291 @@
+/*!
+# Alert Feedback
+
+Opportunity alerts (graduation, price movement and the pattern alerts) carry "👍 Useful" /
+"👎 Noise" buttons. Ratings are tallied per signal type and per token category (bonding curve,
+AMM pool, stablecoin pair), shown by `/feedback` and kept in `ALERT_FEEDBACK_PATH`.
+
+Every `ALERT_FEEDBACK_MIN_VOTES` ratings of a signal type are judged together: when at least
+`ALERT_FEEDBACK_NOISE_PCT` of them were "noise", the monitor raises that signal's threshold by
+`ALERT_FEEDBACK_RAISE_PCT`, at most `ALERT_FEEDBACK_MAX_RAISES` times. Raises are persisted and
+reapplied on startup, so the thresholds stay where the feedback pushed them.
+
+## Environment Variables
+
+- `ALERT_FEEDBACK_ENABLED`: Show rating buttons on opportunity alerts (default: `true`)
+- `ALERT_FEEDBACK_PATH`: Where ratings are kept (default: `alert_feedback.json`)
+- `ALERT_FEEDBACK_MIN_VOTES`: Ratings judged together before a threshold is raised (default: `10`)
+- `ALERT_FEEDBACK_NOISE_PCT`: Share of "noise" ratings that raises the threshold (default: `70`)
+- `ALERT_FEEDBACK_RAISE_PCT`: How much each raise increases the threshold (default: `10`)
+- `ALERT_FEEDBACK_MAX_RAISES`: Raises per signal type at most (default: `5`)
+*/
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+lazy_static! {
+    static ref BUTTONS_ENABLED: bool = FeedbackConfig::from_env().enabled;
+}
+
+/// Callback data prefix of the rating buttons
+pub const RATE_CALLBACK_PREFIX: &str = "rate:";
+
+/// Signal types that can be rated, with the short code used in callback data
+const RATED_SIGNALS: &[(&str, &str)] = &[
+    ("gr", "Graduation"),
+    ("pm", "Price Movement"),
+    ("bp", "High Buy Pressure"),
+    ("lt", "Large Transaction"),
+    ("pr", "Potential Recovery"),
+];
+
+/// Votes remembered to ignore repeated ratings of the same alert; the set starts over once full
+const MAX_REMEMBERED_VOTES: usize = 10_000;
+
+/// Callback data of a rating button, None for signal types that cannot be rated
+pub fn rating_callback(signal_type: &str, mint: &Pubkey, useful: bool) -> Option<String> {
+    let (code, _) = RATED_SIGNALS.iter().find(|(_, name)| *name == signal_type)?;
+    Some(format!("{}{}:{}:{}", RATE_CALLBACK_PREFIX, if useful { "up" } else { "down" }, code, mint))
+}
+
+/// Whether alerts carry rating buttons (ALERT_FEEDBACK_ENABLED)
+pub fn rating_buttons_enabled() -> bool {
+    *BUTTONS_ENABLED
+}
+
+/// A rating button press
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rating {
+    pub signal_type: &'static str,
+    pub mint: Pubkey,
+    pub useful: bool,
+}
+
+impl Rating {
+    /// Parse the callback data of a rating button, None for anything else
+    pub fn parse(data: &str) -> Option<Self> {
+        let mut parts = data.strip_prefix(RATE_CALLBACK_PREFIX)?.split(':');
+        let useful = match parts.next()? {
+            "up" => true,
+            "down" => false,
+            _ => return None,
+        };
+        let code = parts.next()?;
+        let (_, signal_type) = RATED_SIGNALS.iter().find(|(c, _)| *c == code)?;
+        let mint = Pubkey::from_str(parts.next()?).ok()?;
+        Some(Self { signal_type, mint, useful })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FeedbackConfig {
+    pub enabled: bool,
+    pub path: Option<PathBuf>,
+    pub min_votes: u32,
+    pub noise_pct: f64,
+    pub raise_pct: f64,
+    pub max_raises: u32,
+}
+
+impl Default for FeedbackConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: None,
+            min_votes: 10,
+            noise_pct: 70.0,
+            raise_pct: 10.0,
+            max_raises: 5,
+        }
+    }
+}
+
+impl FeedbackConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            enabled: var("ALERT_FEEDBACK_ENABLED")
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(defaults.enabled),
+            path: Some(PathBuf::from(var("ALERT_FEEDBACK_PATH").unwrap_or_else(|| "alert_feedback.json".to_string())))
+                .filter(|p| !p.as_os_str().is_empty()),
+            min_votes: var("ALERT_FEEDBACK_MIN_VOTES").and_then(|v| v.parse().ok()).filter(|n| *n > 0).unwrap_or(defaults.min_votes),
+            noise_pct: var("ALERT_FEEDBACK_NOISE_PCT").and_then(|v| v.parse().ok()).unwrap_or(defaults.noise_pct),
+            raise_pct: var("ALERT_FEEDBACK_RAISE_PCT").and_then(|v| v.parse().ok()).unwrap_or(defaults.raise_pct),
+            max_raises: var("ALERT_FEEDBACK_MAX_RAISES").and_then(|v| v.parse().ok()).unwrap_or(defaults.max_raises),
+        }
+    }
+}
+
+/// Ratings of one signal type (or signal type and category)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeedbackTally {
+    pub useful: u32,
+    pub noise: u32,
+}
+
+impl FeedbackTally {
+    pub fn total(&self) -> u32 {
+        self.useful + self.noise
+    }
+
+    /// Share of "noise" ratings (%), None without ratings
+    pub fn noise_pct(&self) -> Option<f64> {
+        (self.total() > 0).then(|| self.noise as f64 / self.total() as f64 * 100.0)
+    }
+
+    fn add(&mut self, useful: bool) {
+        if useful {
+            self.useful += 1;
+        } else {
+            self.noise += 1;
+        }
+    }
+}
+
+/// Ratings as persisted
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct FeedbackState {
+    /// Signal type -> category -> ratings
+    tallies: BTreeMap<String, BTreeMap<String, FeedbackTally>>,
+    /// Ratings of each signal type not judged yet
+    pending: BTreeMap<String, FeedbackTally>,
+    /// Threshold raises applied per signal type
+    raises: BTreeMap<String, u32>,
+}
+
+/// What a rating led to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FeedbackOutcome {
+    /// The user already rated this alert
+    Duplicate,
+    Recorded,
+    /// The signal's threshold should be multiplied by `factor`; `raises` counts every raise so far
+    Raised { factor: f64, raises: u32 },
+}
+
+pub struct AlertFeedback {
+    config: FeedbackConfig,
+    state: Mutex<FeedbackState>,
+    /// (user, signal type, mint) already rated
+    votes: Mutex<HashSet<(u64, &'static str, Pubkey)>>,
+}
+
+impl AlertFeedback {
+    /// Feedback persisted at `config.path` (if any), reloading the ratings of previous runs
+    pub fn new(config: FeedbackConfig) -> Self {
+        let state = config.path.as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { config, state: Mutex::new(state), votes: Mutex::new(HashSet::new()) }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(FeedbackConfig::from_env())
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// How much the feedback has raised a signal's threshold so far (1.0 when untouched)
+    pub fn threshold_factor(&self, signal_type: &str) -> f64 {
+        let raises = self.state.lock().unwrap().raises.get(signal_type).copied().unwrap_or(0);
+        (1.0 + self.config.raise_pct / 100.0).powi(raises as i32)
+    }
+
+    /// Count `user`'s rating of an alert about a token in `category`
+    pub fn record(&self, user: u64, rating: &Rating, category: &str) -> FeedbackOutcome {
+        {
+            let mut votes = self.votes.lock().unwrap();
+            if votes.len() >= MAX_REMEMBERED_VOTES {
+                votes.clear();
+            }
+            if !votes.insert((user, rating.signal_type, rating.mint)) {
+                return FeedbackOutcome::Duplicate;
+            }
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.tallies.entry(rating.signal_type.to_string())
+            .or_default()
+            .entry(category.to_string())
+            .or_default()
+            .add(rating.useful);
+        let pending = state.pending.entry(rating.signal_type.to_string()).or_default();
+        pending.add(rating.useful);
+
+        let mut outcome = FeedbackOutcome::Recorded;
+        if pending.total() >= self.config.min_votes {
+            let noisy = pending.noise_pct().is_some_and(|pct| pct >= self.config.noise_pct);
+            *pending = FeedbackTally::default();
+            let raises = state.raises.entry(rating.signal_type.to_string()).or_default();
+            if noisy && *raises < self.config.max_raises {
+                *raises += 1;
+                outcome = FeedbackOutcome::Raised { factor: 1.0 + self.config.raise_pct / 100.0, raises: *raises };
+            }
+        }
+        self.save(&state);
+        outcome
+    }
+
+    /// Ratings per signal type, summed over categories
+    pub fn tally(&self, signal_type: &str) -> FeedbackTally {
+        self.state.lock().unwrap().tallies.get(signal_type)
+            .map(|categories| categories.values().fold(FeedbackTally::default(), |sum, t| FeedbackTally {
+                useful: sum.useful + t.useful,
+                noise: sum.noise + t.noise,
+            }))
+            .unwrap_or_default()
+    }
+
+    pub fn category_tally(&self, signal_type: &str, category: &str) -> FeedbackTally {
+        self.state.lock().unwrap().tallies.get(signal_type)
+            .and_then(|categories| categories.get(category).copied())
+            .unwrap_or_default()
+    }
+
+    /// Ratings per signal type and category, with the raises applied, for `/feedback`
+    pub fn summary(&self) -> String {
+        let state = self.state.lock().unwrap();
+        if state.tallies.is_empty() {
+            return "No alert has been rated yet.".to_string();
+        }
+        state.tallies.iter()
+            .map(|(signal_type, categories)| {
+                let raises = state.raises.get(signal_type).copied().unwrap_or(0);
+                let raised = if raises > 0 { format!(" (threshold raised {}x)", raises) } else { String::new() };
+                let lines = categories.iter()
+                    .map(|(category, tally)| format!(
+                        "   {}: 👍 {} / 👎 {} ({:.0}% noise)",
+                        category,
+                        tally.useful,
+                        tally.noise,
+                        tally.noise_pct().unwrap_or(0.0)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!("{}{}\n{}", signal_type, raised, lines)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn save(&self, state: &FeedbackState) {
+        if let Some(path) = &self.config.path {
+            let written = serde_json::to_string(state)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| Ok(std::fs::write(path, json)?));
+            if let Err(e) = written {
+                eprintln!("Failed to save alert feedback to {}: {}", path.display(), e);
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! Persistent outbox for Telegram alerts that could not be delivered.
+//!
+//! When a send fails transiently (Telegram down, rate limited, breaker open) the alert is queued
+//! here instead of being dropped, written to `ALERT_OUTBOX_PATH` as JSON lines so it survives a
+//! restart. Queued alerts are retried in order; ones older than `ALERT_OUTBOX_TTL_SECS` are
+//! discarded as stale, and the count of both is reported once connectivity returns.
+//!
+//! During an alert storm (Telegram rate limiting, see `alert_throttle`) a queued alert is merged
+//! into the newest queued alert of the same category while the combined message stays under
+//! Telegram's length limit, so a burst of one kind of alert goes out as a few messages.
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use teloxide::types::InlineKeyboardMarkup;
+
+/// Merged alerts stay below Telegram's 4096 character message limit
+const MAX_MERGED_CHARS: usize = 4000;
+const MERGE_SEPARATOR: &str = "\n\n━━━━━━━━━━\n\n";
+
+lazy_static! {
+    static ref SHARED_OUTBOX: Arc<AlertOutbox> = Arc::new(AlertOutbox::from_env());
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub category: String,
+    pub text: String,
+    #[serde(default)]
+    pub keyboard: Option<InlineKeyboardMarkup>,
+    /// Unix seconds
+    pub queued_at: i64,
+    /// Alerts merged into this entry
+    #[serde(default = "one_alert")]
+    pub alerts: usize,
+}
+
+fn one_alert() -> usize {
+    1
+}
+
+impl OutboxEntry {
+    pub fn new(category: &str, text: &str, keyboard: Option<InlineKeyboardMarkup>, queued_at: i64) -> Self {
+        Self { category: category.to_string(), text: text.to_string(), keyboard, queued_at, alerts: 1 }
+    }
+}
+
+/// What a drain of the outbox found: alerts still waiting and alerts discarded as stale since
+/// the last report
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OutboxBacklog {
+    pub pending: usize,
+    pub expired: usize,
+}
+
+impl OutboxBacklog {
+    pub fn is_empty(&self) -> bool {
+        self.pending == 0 && self.expired == 0
+    }
+
+    /// Notice sent ahead of the backlog once Telegram is reachable again
+    pub fn notice(&self) -> String {
+        let mut notice = "📬 **Alerts Delayed**\n\nTelegram was unreachable or rate limiting".to_string();
+        match self.pending {
+            0 => notice.push('.'),
+            1 => notice.push_str("; 1 alert was delayed and follows."),
+            n => notice.push_str(&format!("; {} alerts were delayed and follow.", n)),
+        }
+        if self.expired > 0 {
+            notice.push_str(&format!(
+                "\n{} stale alert{} expired before delivery and {} dropped.",
+                self.expired,
+                if self.expired == 1 { "" } else { "s" },
+                if self.expired == 1 { "was" } else { "were" },
+            ));
+        }
+        notice
+    }
+}
+
+#[derive(Default)]
+struct OutboxState {
+    entries: VecDeque<OutboxEntry>,
+    /// Expired since the last delivered notice
+    expired: usize,
+    /// The notice for the current backlog went out; a throttled drain resumes without another
+    notified: bool,
+}
+
+pub struct AlertOutbox {
+    path: Option<PathBuf>,
+    ttl: Duration,
+    max_entries: usize,
+    state: Mutex<OutboxState>,
+    /// Held while draining so concurrent senders never deliver the same entry twice
+    delivery: tokio::sync::Mutex<()>,
+}
+
+impl AlertOutbox {
+    /// Outbox persisted at `path` (if any), reloading entries left by a previous run
+    pub fn new(path: Option<PathBuf>, ttl: Duration, max_entries: usize) -> Self {
+        let entries = path.as_ref().map(load_entries).unwrap_or_default();
+        Self {
+            path,
+            ttl,
+            max_entries: max_entries.max(1),
+            state: Mutex::new(OutboxState { entries, expired: 0, notified: false }),
+            delivery: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn in_memory(ttl: Duration, max_entries: usize) -> Self {
+        Self::new(None, ttl, max_entries)
+    }
+
+    pub fn from_env() -> Self {
+        let path = std::env::var("ALERT_OUTBOX_PATH")
+            .unwrap_or_else(|_| "alert_outbox.jsonl".to_string());
+        let ttl_secs = std::env::var("ALERT_OUTBOX_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(3600);
+        let max_entries = std::env::var("ALERT_OUTBOX_MAX_ENTRIES")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(500);
+        Self::new(
+            Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty()),
+            Duration::seconds(ttl_secs),
+            max_entries,
+        )
+    }
+
+    /// Process-wide outbox shared by every alert system, so instances never clobber one file
+    pub fn shared() -> Arc<AlertOutbox> {
+        SHARED_OUTBOX.clone()
+    }
+
+    /// Queued messages; merged alerts count once
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Queued alerts, counting each merged one
+    pub fn alert_count(&self) -> usize {
+        self.state.lock().unwrap().entries.iter().map(|entry| entry.alerts).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queue an alert, merging it into the newest queued alert of its category when the result
+    /// fits in one message (alerts with buttons stay separate); drops the oldest when full
+    pub fn push(&self, entry: OutboxEntry) {
+        // The front entry may be on its way to Telegram right now
+        let delivering = self.delivery.try_lock().is_err();
+        let mut state = self.state.lock().unwrap();
+        let mergeable = state.entries.iter_mut()
+            .enumerate()
+            .rev()
+            .find(|(i, queued)| queued.category == entry.category && !(delivering && *i == 0))
+            .map(|(_, queued)| queued);
+        match mergeable {
+            Some(queued)
+                if queued.keyboard.is_none()
+                    && entry.keyboard.is_none()
+                    && queued.text.chars().count() + MERGE_SEPARATOR.chars().count() + entry.text.chars().count() <= MAX_MERGED_CHARS =>
+            {
+                queued.text.push_str(MERGE_SEPARATOR);
+                queued.text.push_str(&entry.text);
+                queued.alerts += entry.alerts;
+            }
+            _ => state.entries.push_back(entry),
+        }
+        while state.entries.len() > self.max_entries {
+            if let Some(dropped) = state.entries.pop_front() {
+                state.expired += dropped.alerts;
+            }
+        }
+        self.persist(&state);
+    }
+
+    /// Discard stale alerts and report what is left to deliver
+    pub fn backlog(&self, now: DateTime<Utc>) -> OutboxBacklog {
+        let mut state = self.state.lock().unwrap();
+        let cutoff = now.timestamp() - self.ttl.num_seconds();
+        let before: usize = state.entries.iter().map(|entry| entry.alerts).sum();
+        state.entries.retain(|entry| entry.queued_at > cutoff);
+        let pending: usize = state.entries.iter().map(|entry| entry.alerts).sum();
+        let expired = before - pending;
+        if expired > 0 {
+            state.expired += expired;
+            self.persist(&state);
+        }
+        OutboxBacklog { pending, expired: state.expired }
+    }
+
+    /// Exclusive right to drain the outbox, None while another sender is draining it
+    pub fn try_begin_delivery(&self) -> Option<tokio::sync::MutexGuard<'_, ()>> {
+        self.delivery.try_lock().ok()
+    }
+
+    /// The backlog notice was delivered; stop reporting the expired count
+    pub fn acknowledge_notice(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.expired = 0;
+        state.notified = true;
+    }
+
+    /// Whether the current backlog still needs its notice
+    pub fn needs_notice(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        !state.notified || state.expired > 0
+    }
+
+    pub fn front(&self) -> Option<OutboxEntry> {
+        self.state.lock().unwrap().entries.front().cloned()
+    }
+
+    pub fn pop_front(&self) -> Option<OutboxEntry> {
+        let mut state = self.state.lock().unwrap();
+        let entry = state.entries.pop_front();
+        if state.entries.is_empty() {
+            state.notified = false;
+        }
+        self.persist(&state);
+        entry
+    }
+
+    fn persist(&self, state: &OutboxState) {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return,
+        };
+        let result = std::fs::File::create(path).and_then(|mut file| {
+            for entry in &state.entries {
+                if let Ok(line) = serde_json::to_string(entry) {
+                    writeln!(file, "{}", line)?;
+                }
+            }
+            file.flush()
+        });
+        if let Err(e) = result {
+            eprintln!("Failed to persist alert outbox {}: {}", path.display(), e);
+        }
+    }
+}
+
+fn load_entries(path: &PathBuf) -> VecDeque<OutboxEntry> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return VecDeque::new(),
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str::<OutboxEntry>(&line).ok())
+        .collect()
+}
@@ -0,0 +1,141 @@
+//! Adaptive send rate for Telegram alerts.
+//!
+//! Telegram answers floods with 429 "Too Many Requests" and a retry-after. Instead of sleeping
+//! on the sender, a 429 widens the gap kept between messages (doubling up to
+//! `ALERT_MAX_INTERVAL_SECS`) and holds every sender off until the retry-after has passed;
+//! alerts raised meanwhile wait in the outbox, where alerts of the same category are merged.
+//! Once no 429 has been seen for `ALERT_THROTTLE_RECOVERY_SECS`, each delivered message halves
+//! the gap again, down to `ALERT_MIN_INTERVAL_MS`.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+
+use crate::common::clock::{system_clock, Clock};
+
+lazy_static! {
+    static ref SHARED_THROTTLE: Arc<AlertThrottle> = Arc::new(AlertThrottle::new(AlertThrottleConfig::from_env()));
+}
+
+/// Gap after the first 429 when no throttling was in effect
+const FIRST_BACKOFF: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug)]
+pub struct AlertThrottleConfig {
+    /// Gap kept between messages when Telegram is not pushing back
+    pub min_interval: Duration,
+    pub max_interval: Duration,
+    /// Time without a 429 before the gap starts shrinking
+    pub recovery: Duration,
+}
+
+impl Default for AlertThrottleConfig {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::ZERO,
+            max_interval: Duration::from_secs(30),
+            recovery: Duration::from_secs(60),
+        }
+    }
+}
+
+impl AlertThrottleConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            min_interval: var("ALERT_MIN_INTERVAL_MS")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.min_interval),
+            max_interval: var("ALERT_MAX_INTERVAL_SECS")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.max_interval),
+            recovery: var("ALERT_THROTTLE_RECOVERY_SECS")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.recovery),
+        }
+    }
+}
+
+#[derive(Default)]
+struct ThrottleState {
+    interval: Duration,
+    /// Nothing is sent before this
+    next_send: Option<DateTime<Utc>>,
+    last_limited: Option<DateTime<Utc>>,
+    rate_limits: u64,
+}
+
+pub struct AlertThrottle {
+    config: AlertThrottleConfig,
+    clock: Arc<dyn Clock>,
+    state: Mutex<ThrottleState>,
+}
+
+impl AlertThrottle {
+    pub fn new(config: AlertThrottleConfig) -> Self {
+        let state = ThrottleState { interval: config.min_interval, ..ThrottleState::default() };
+        Self { config, clock: system_clock(), state: Mutex::new(state) }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Process-wide throttle: every alert system talks to the same bot
+    pub fn shared() -> Arc<AlertThrottle> {
+        SHARED_THROTTLE.clone()
+    }
+
+    /// How long senders still have to wait, None when a message may go out now
+    pub fn wait(&self) -> Option<Duration> {
+        let now = self.clock.now();
+        self.state.lock().unwrap().next_send
+            .filter(|next| *next > now)
+            .and_then(|next| (next - now).to_std().ok())
+    }
+
+    pub fn ready(&self) -> bool {
+        self.wait().is_none()
+    }
+
+    /// Current gap between messages
+    pub fn interval(&self) -> Duration {
+        self.state.lock().unwrap().interval
+    }
+
+    /// 429s seen since startup
+    pub fn rate_limits(&self) -> u64 {
+        self.state.lock().unwrap().rate_limits
+    }
+
+    /// A message was delivered: keep the gap before the next one, shrinking it once Telegram
+    /// has stopped pushing back
+    pub fn record_sent(&self) {
+        let now = self.clock.now();
+        let mut state = self.state.lock().unwrap();
+        let recovered = state.last_limited
+            .map_or(true, |at| (now - at).to_std().is_ok_and(|since| since >= self.config.recovery));
+        if recovered && state.interval > self.config.min_interval {
+            let halved = state.interval / 2;
+            state.interval = if halved < Duration::from_millis(100) { self.config.min_interval } else { halved.max(self.config.min_interval) };
+        }
+        state.next_send = chrono::Duration::from_std(state.interval).ok().map(|gap| now + gap);
+    }
+
+    /// Telegram answered 429: widen the gap and hold senders off for the retry-after
+    pub fn record_rate_limited(&self, retry_after: Option<Duration>) {
+        let now = self.clock.now();
+        let mut state = self.state.lock().unwrap();
+        state.interval = (state.interval * 2).max(FIRST_BACKOFF).min(self.config.max_interval.max(FIRST_BACKOFF));
+        let hold = retry_after.unwrap_or_default().max(state.interval);
+        state.next_send = chrono::Duration::from_std(hold).ok().map(|hold| now + hold);
+        state.last_limited = Some(now);
+        state.rate_limits += 1;
+    }
+}
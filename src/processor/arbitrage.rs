@@ -0,0 +1,134 @@
+//! Cross-venue price spreads for tokens trading on more than one pool.
+//!
+//! Every parsed trade updates the last price of its venue (DEX plus pool). When a mint has recent
+//! prices on at least two venues, the spread between the cheapest and the most expensive one is
+//! tracked, and an alert fires when it widens past `ARB_SPREAD_ALERT_PCT`. The spread is gross:
+//! fees, price impact and the time between the two trades usually eat most of it, which is the
+//! point of showing it as an educational example.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Clone, Debug)]
+pub struct SpreadConfig {
+    /// Spread (%) between the cheapest and the priciest venue that triggers an alert
+    pub alert_pct: f64,
+    /// Prices older than this are left out of the spread
+    pub max_price_age: Duration,
+}
+
+impl Default for SpreadConfig {
+    fn default() -> Self {
+        Self {
+            alert_pct: 3.0,
+            max_price_age: Duration::seconds(60),
+        }
+    }
+}
+
+impl SpreadConfig {
+    /// ARB_SPREAD_ALERT_PCT (default 3.0) and ARB_PRICE_MAX_AGE_SECS (default 60)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            alert_pct: std::env::var("ARB_SPREAD_ALERT_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.alert_pct),
+            max_price_age: std::env::var("ARB_PRICE_MAX_AGE_SECS")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .map(Duration::seconds)
+                .unwrap_or(defaults.max_price_age),
+        }
+    }
+}
+
+/// Last traded price on one venue
+#[derive(Clone, Debug, PartialEq)]
+pub struct VenuePrice {
+    /// DEX name, with the pool when known
+    pub venue: String,
+    pub price: f64,
+    pub at: DateTime<Utc>,
+}
+
+/// Cheapest and priciest venue for a token at one moment
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spread {
+    pub low: VenuePrice,
+    pub high: VenuePrice,
+    pub spread_pct: f64,
+}
+
+/// Venue label of a trade: the DEX, plus a short pool id so two pools of one DEX stay apart
+pub fn venue_label(dex_name: &str, pool_id: Option<&str>) -> String {
+    match pool_id {
+        Some(pool) if !pool.is_empty() => format!("{} ({}…)", dex_name, pool.get(..6).unwrap_or(pool)),
+        _ => dex_name.to_string(),
+    }
+}
+
+pub struct SpreadTracker {
+    config: SpreadConfig,
+    prices: HashMap<Pubkey, Vec<VenuePrice>>,
+    latest: HashMap<Pubkey, Spread>,
+    /// Tokens whose spread is above the threshold and already alerted
+    alerted: HashMap<Pubkey, bool>,
+}
+
+impl SpreadTracker {
+    pub fn new(config: SpreadConfig) -> Self {
+        Self { config, prices: HashMap::new(), latest: HashMap::new(), alerted: HashMap::new() }
+    }
+
+    /// Record a trade price; returns the spread when it just widened past the alert threshold.
+    /// It is reported again only after narrowing back below the threshold.
+    pub fn record(&mut self, mint: Pubkey, venue: String, price: f64, now: DateTime<Utc>) -> Option<Spread> {
+        if price <= 0.0 || !price.is_finite() {
+            return None;
+        }
+        let cutoff = now - self.config.max_price_age;
+        let venues = self.prices.entry(mint).or_default();
+        venues.retain(|v| v.venue != venue && v.at >= cutoff);
+        venues.push(VenuePrice { venue, price, at: now });
+
+        if venues.len() < 2 {
+            self.latest.remove(&mint);
+            return None;
+        }
+        let low = venues.iter().min_by(|a, b| a.price.total_cmp(&b.price))?.clone();
+        let high = venues.iter().max_by(|a, b| a.price.total_cmp(&b.price))?.clone();
+        let spread = Spread { spread_pct: (high.price - low.price) / low.price * 100.0, low, high };
+        self.latest.insert(mint, spread.clone());
+
+        let alerted = self.alerted.entry(mint).or_insert(false);
+        if spread.spread_pct < self.config.alert_pct {
+            *alerted = false;
+            return None;
+        }
+        if *alerted {
+            return None;
+        }
+        *alerted = true;
+        Some(spread)
+    }
+
+    /// Last computed spread of a token, None while it trades on a single venue
+    pub fn spread(&self, mint: &Pubkey) -> Option<Spread> {
+        self.latest.get(mint).cloned()
+    }
+
+    /// Forget tokens that are no longer tracked
+    pub fn retain(&mut self, keep: impl Fn(&Pubkey) -> bool) {
+        self.prices.retain(|mint, _| keep(mint));
+        self.latest.retain(|mint, _| keep(mint));
+        self.alerted.retain(|mint, _| keep(mint));
+    }
+
+    /// Number of tokens with recorded venue prices
+    pub fn token_count(&self) -> usize {
+        self.prices.len()
+    }
+}
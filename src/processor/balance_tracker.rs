@@ -0,0 +1,296 @@
+/*!
+# Balance Tracker
+
+Subscribes to every token account owned by the trading wallet through Yellowstone account
+updates, so the bot always knows the real on-chain balance of each token (airdrops, dust,
+partial fills) instead of relying only on the amounts recorded at buy time.
+
+Each update is reconciled against `BOUGHT_TOKEN_LIST`. When the ledger and the chain disagree
+by more than the configured tolerance the mismatch is logged and alerted, and the ledger is
+corrected to the on-chain amount. A read-only process (`TRADING_ENABLED=false`) has no trading
+wallet, so the tracker does not start.
+
+## Environment Variables
+
+- `BALANCE_TRACKER_ENABLED`: Enable the account subscription (default: `true`)
+- `BALANCE_MISMATCH_TOLERANCE_PCT`: Allowed difference between ledger and chain before alerting (default: `1.0`)
+*/
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
+use colored::Colorize;
+use dashmap::DashMap;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::StateWithExtensionsOwned;
+use spl_token_2022::state::Mint;
+use tokio_util::sync::CancellationToken;
+use yellowstone_grpc_proto::geyser::{
+    subscribe_request_filter_accounts_filter::Filter as AccountsFilter,
+    subscribe_request_filter_accounts_filter_memcmp::Data as MemcmpData,
+    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
+    SubscribeRequestFilterAccounts, SubscribeRequestFilterAccountsFilter,
+    SubscribeRequestFilterAccountsFilterMemcmp,
+};
+
+use crate::common::cache::TOKEN_ACCOUNT_CACHE;
+use crate::common::config::AppState;
+use crate::common::logger::Logger;
+use crate::library::geyser_subscription::{run_reconnecting, GeyserSubscription};
+use crate::processor::sniper_bot::BOUGHT_TOKEN_LIST;
+use crate::processor::telegram_alerts::TelegramAlertSystem;
+
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PbqqZ1WHs1kAUjR";
+/// Offset of the owner field in the SPL token account layout
+const TOKEN_ACCOUNT_OWNER_OFFSET: u64 = 32;
+
+lazy_static::lazy_static! {
+    /// Latest on-chain balance per mint for the trading wallet
+    pub static ref ON_CHAIN_BALANCES: Arc<DashMap<String, OnChainBalance>> = Arc::new(DashMap::new());
+    static ref MINT_DECIMALS: DashMap<Pubkey, u8> = DashMap::new();
+}
+
+#[derive(Clone, Debug)]
+pub struct OnChainBalance {
+    pub token_account: Pubkey,
+    pub raw_amount: u64,
+    pub ui_amount: f64,
+    pub slot: u64,
+    pub updated_at: Instant,
+}
+
+/// Balance tracker configuration
+pub struct BalanceTrackerConfig {
+    pub yellowstone_grpc_http: String,
+    pub yellowstone_grpc_token: String,
+    pub app_state: Arc<AppState>,
+    pub mismatch_tolerance_pct: f64,
+}
+
+impl BalanceTrackerConfig {
+    pub fn new(
+        yellowstone_grpc_http: String,
+        yellowstone_grpc_token: String,
+        app_state: Arc<AppState>,
+    ) -> Self {
+        let mismatch_tolerance_pct = std::env::var("BALANCE_MISMATCH_TOLERANCE_PCT")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(1.0);
+
+        Self {
+            yellowstone_grpc_http,
+            yellowstone_grpc_token,
+            app_state,
+            mismatch_tolerance_pct,
+        }
+    }
+}
+
+/// Keeps the position ledger in sync with the wallet's token accounts
+pub struct BalanceTracker {
+    config: BalanceTrackerConfig,
+    telegram: Option<Arc<TelegramAlertSystem>>,
+    logger: Logger,
+}
+
+impl BalanceTracker {
+    pub fn new(config: BalanceTrackerConfig, telegram: Option<Arc<TelegramAlertSystem>>) -> Self {
+        Self {
+            config,
+            telegram,
+            logger: Logger::new("[BALANCE-TRACKER] => ".bright_green().to_string()),
+        }
+    }
+
+    /// Run the account subscription, reconnecting until cancelled
+    pub async fn start(&self, cancel_token: CancellationToken) -> Result<(), String> {
+        let wallet = self.config.app_state.wallet.try_pubkey()
+            .map_err(|e| format!("Failed to get wallet pubkey: {}", e))?;
+        self.logger.log(format!("Tracking token accounts owned by {}", wallet).green().to_string());
+
+        run_reconnecting("Account subscription", &self.logger, &cancel_token, || self.run_subscription(&wallet)).await;
+        self.logger.log("Balance tracker received shutdown signal.".yellow().to_string());
+        Ok(())
+    }
+
+    async fn run_subscription(&self, wallet: &Pubkey) -> Result<(), String> {
+        let mut subscription = GeyserSubscription::open(&self.config.yellowstone_grpc_http, &self.config.yellowstone_grpc_token).await?;
+
+        let owner_filter = SubscribeRequestFilterAccountsFilter {
+            filter: Some(AccountsFilter::Memcmp(SubscribeRequestFilterAccountsFilterMemcmp {
+                offset: TOKEN_ACCOUNT_OWNER_OFFSET,
+                data: Some(MemcmpData::Base58(wallet.to_string())),
+            })),
+        };
+        let subscription_request = SubscribeRequest {
+            accounts: maplit::hashmap! {
+                "WalletTokenAccounts".to_owned() => SubscribeRequestFilterAccounts {
+                    account: vec![],
+                    owner: vec![TOKEN_PROGRAM.to_string(), TOKEN_2022_PROGRAM.to_string()],
+                    filters: vec![owner_filter],
+                    nonempty_txn_signature: None,
+                }
+            },
+            commitment: Some(CommitmentLevel::Confirmed as i32),
+            ..Default::default()
+        };
+        subscription.update(subscription_request).await?;
+
+        loop {
+            if let UpdateOneof::Account(update) = subscription.next().await? {
+                if let Some(account) = update.account {
+                    self.handle_account_update(&account.pubkey, &account.data, update.slot).await;
+                }
+            }
+        }
+    }
+
+    async fn handle_account_update(&self, account_pubkey: &[u8], data: &[u8], slot: u64) {
+        // Base SPL token account layout: mint (32) | owner (32) | amount (8) | ...
+        if data.len() < 72 {
+            return;
+        }
+        let token_account = match Pubkey::try_from(account_pubkey) {
+            Ok(pubkey) => pubkey,
+            Err(_) => return,
+        };
+        let mint = match Pubkey::try_from(&data[0..32]) {
+            Ok(pubkey) => pubkey,
+            Err(_) => return,
+        };
+        let raw_amount = u64::from_le_bytes(data[64..72].try_into().unwrap_or_default());
+
+        // Keep the decoded account cached so the sell path reads the subscribed balance
+        match StateWithExtensionsOwned::<spl_token_2022::state::Account>::unpack(data.to_vec()) {
+            Ok(decoded) => TOKEN_ACCOUNT_CACHE.insert(token_account, decoded, None),
+            Err(_) => TOKEN_ACCOUNT_CACHE.remove(&token_account),
+        }
+
+        let decimals = match self.get_mint_decimals(&mint).await {
+            Some(decimals) => decimals,
+            None => {
+                self.logger.log(format!("Could not resolve decimals for mint {}", mint).yellow().to_string());
+                return;
+            }
+        };
+        let ui_amount = raw_amount as f64 / 10_f64.powi(decimals as i32);
+        let mint_str = mint.to_string();
+
+        let previous = ON_CHAIN_BALANCES.insert(mint_str.clone(), OnChainBalance {
+            token_account,
+            raw_amount,
+            ui_amount,
+            slot,
+            updated_at: Instant::now(),
+        });
+
+        self.reconcile(&mint_str, ui_amount, previous.map(|p| p.ui_amount)).await;
+    }
+
+    /// Compare the on-chain balance with the position ledger, alerting on corrections and new tokens
+    async fn reconcile(&self, mint: &str, on_chain: f64, previous_on_chain: Option<f64>) {
+        match reconcile_ledger(mint, on_chain, previous_on_chain, self.config.mismatch_tolerance_pct) {
+            Reconciliation::Corrected { ledger, diff_pct } => {
+                let message = format!(
+                    "Balance mismatch for {}: ledger {:.4}, on-chain {:.4} ({:.2}% off)",
+                    mint, ledger, on_chain, diff_pct
+                );
+                self.logger.log(format!("⚠️  {}", message).red().bold().to_string());
+                self.send_alert(&message).await;
+            }
+            Reconciliation::Untracked => {
+                let message = format!("Untracked token {} appeared in wallet: {:.4}", mint, on_chain);
+                self.logger.log(message.yellow().to_string());
+                self.send_alert(&message).await;
+            }
+            Reconciliation::InSync => {}
+        }
+    }
+
+    async fn send_alert(&self, message: &str) {
+        if let Some(telegram) = &self.telegram {
+            if let Err(e) = telegram.send_custom_alert("Balance Reconciliation", message).await {
+                self.logger.log(format!("Failed to send balance alert: {}", e).red().to_string());
+            }
+        }
+    }
+
+    async fn get_mint_decimals(&self, mint: &Pubkey) -> Option<u8> {
+        if let Some(decimals) = MINT_DECIMALS.get(mint) {
+            return Some(*decimals);
+        }
+
+        let account = self.config.app_state.account_batcher.get_account(*mint).await.ok()??;
+        let mint_state = StateWithExtensionsOwned::<Mint>::unpack(account.data).ok()?;
+        let decimals = mint_state.base.decimals;
+        MINT_DECIMALS.insert(*mint, decimals);
+        Some(decimals)
+    }
+}
+
+/// Outcome of reconciling one on-chain balance against `BOUGHT_TOKEN_LIST`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reconciliation {
+    /// Ledger within tolerance of the chain, or a token we don't hold and didn't just receive
+    InSync,
+    /// Ledger was off by `diff_pct` and has been corrected to the on-chain amount
+    Corrected { ledger: f64, diff_pct: f64 },
+    /// Tokens we never bought (airdrop or dust) appeared in the wallet
+    Untracked,
+}
+
+/// Compare the on-chain balance of `mint` with the position ledger and correct the ledger when
+/// they differ by more than `tolerance_pct`
+pub fn reconcile_ledger(mint: &str, on_chain: f64, previous_on_chain: Option<f64>, tolerance_pct: f64) -> Reconciliation {
+    match BOUGHT_TOKEN_LIST.get_mut(mint) {
+        Some(mut entry) => {
+            let ledger = entry.current_amount;
+            let diff = (on_chain - ledger).abs();
+            let diff_pct = if ledger > 0.0 { diff / ledger * 100.0 } else if on_chain > 0.0 { 100.0 } else { 0.0 };
+            if diff_pct <= tolerance_pct {
+                return Reconciliation::InSync;
+            }
+            entry.current_amount = on_chain;
+            Reconciliation::Corrected { ledger, diff_pct }
+        }
+        None if on_chain > 0.0 && previous_on_chain.unwrap_or(0.0) == 0.0 => Reconciliation::Untracked,
+        None => Reconciliation::InSync,
+    }
+}
+
+/// On-chain balance for a mint as last seen by the tracker
+pub fn get_on_chain_balance(mint: &str) -> Option<OnChainBalance> {
+    ON_CHAIN_BALANCES.get(mint).map(|entry| entry.clone())
+}
+
+/// Start the balance tracker service unless disabled via BALANCE_TRACKER_ENABLED=false or trading
+/// is locked down
+pub async fn start_balance_tracker(
+    yellowstone_grpc_http: String,
+    yellowstone_grpc_token: String,
+    app_state: Arc<AppState>,
+    cancel_token: CancellationToken,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let enabled = std::env::var("BALANCE_TRACKER_ENABLED")
+        .ok()
+        .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+        .unwrap_or(true);
+    if !enabled || !crate::library::trading_lockdown::trading_enabled() {
+        return None;
+    }
+
+    let telegram = crate::processor::telegram_alerts::init_from_env()
+        .ok()
+        .flatten()
+        .map(Arc::new);
+    let config = BalanceTrackerConfig::new(yellowstone_grpc_http, yellowstone_grpc_token, app_state);
+    let tracker = BalanceTracker::new(config, telegram);
+
+    Some(tokio::spawn(async move {
+        if let Err(e) = tracker.start(cancel_token).await {
+            eprintln!("Balance tracker error: {}", e);
+        }
+    }))
+}
@@ -0,0 +1,229 @@
+/*!
+# Momentum Breakout
+
+Signals when a token's price breaks above the high of its recent candles on volume. Trades are
+aggregated into OHLCV candles of `BREAKOUT_CANDLE_SECS`; a breakout is the forming candle closing
+above the highest high of the `lookback` candles before it while its volume is at least
+`volume_multiple` times their average volume. Each candle signals at most once.
+
+What counts as a breakout differs with the token's age (time since the monitor first saw it): a
+minutes-old launch swings wildly on a handful of candles, an older token needs a longer range and
+less volume to mean something. `BREAKOUT_BRACKETS` lists `max_age:lookback:volume_multiple`
+brackets, youngest first; the first bracket whose `max_age` the token is within applies, `*` matches
+any age. Ages use the `/mute` duration format (`90s`, `30m`, `6h`, `2d`).
+
+## Environment Variables
+
+- `BREAKOUT_CANDLE_SECS`: Candle interval in seconds (default: `60`)
+- `BREAKOUT_BRACKETS`: Parameters per token age bracket (default: `10m:5:3,1h:10:2,*:20:1.5`)
+*/
+
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::library::ohlcv::{Candle, CandleSeries};
+use crate::processor::mute_list::parse_duration;
+
+/// Breakout parameters for tokens up to `max_age` old
+#[derive(Clone, Debug, PartialEq)]
+pub struct AgeBracket {
+    /// None matches tokens of any age
+    pub max_age: Option<Duration>,
+    /// Completed candles whose high has to be broken
+    pub lookback: usize,
+    /// Candle volume needed as a multiple of the lookback candles' average
+    pub volume_multiple: f64,
+}
+
+impl AgeBracket {
+    /// Parse `max_age:lookback:volume_multiple`, e.g. `10m:5:3` or `*:20:1.5`
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().split(':');
+        let max_age = match parts.next()?.trim() {
+            "*" => None,
+            age => Some(parse_duration(age)?),
+        };
+        let lookback = parts.next()?.trim().parse().ok().filter(|n| *n > 0)?;
+        let volume_multiple = parts.next()?.trim().parse().ok().filter(|m: &f64| *m >= 0.0)?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { max_age, lookback, volume_multiple })
+    }
+
+    pub fn label(&self) -> String {
+        match self.max_age {
+            Some(age) if age.num_seconds() % 3600 == 0 => format!("≤{}h", age.num_hours()),
+            Some(age) if age.num_seconds() % 60 == 0 => format!("≤{}m", age.num_minutes()),
+            Some(age) => format!("≤{}s", age.num_seconds()),
+            None => "any".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BreakoutConfig {
+    pub candle: Duration,
+    /// Youngest first
+    pub brackets: Vec<AgeBracket>,
+}
+
+impl Default for BreakoutConfig {
+    fn default() -> Self {
+        Self {
+            candle: Duration::seconds(60),
+            brackets: vec![
+                AgeBracket { max_age: Some(Duration::minutes(10)), lookback: 5, volume_multiple: 3.0 },
+                AgeBracket { max_age: Some(Duration::hours(1)), lookback: 10, volume_multiple: 2.0 },
+                AgeBracket { max_age: None, lookback: 20, volume_multiple: 1.5 },
+            ],
+        }
+    }
+}
+
+impl BreakoutConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            candle: var("BREAKOUT_CANDLE_SECS")
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|s| *s > 0)
+                .map(Duration::seconds)
+                .unwrap_or(defaults.candle),
+            brackets: var("BREAKOUT_BRACKETS")
+                .and_then(|v| Self::parse_brackets(&v))
+                .unwrap_or(defaults.brackets),
+        }
+    }
+
+    /// Comma separated brackets; None if any of them is malformed
+    pub fn parse_brackets(text: &str) -> Option<Vec<AgeBracket>> {
+        let brackets: Option<Vec<AgeBracket>> = text.split(',')
+            .filter(|b| !b.trim().is_empty())
+            .map(AgeBracket::parse)
+            .collect();
+        brackets.filter(|b| !b.is_empty())
+    }
+
+    /// Bracket for a token of the given age
+    pub fn bracket(&self, age: Duration) -> Option<&AgeBracket> {
+        self.brackets.iter().find(|b| b.max_age.map_or(true, |max| age <= max))
+    }
+
+    fn max_lookback(&self) -> usize {
+        self.brackets.iter().map(|b| b.lookback).max().unwrap_or(0)
+    }
+}
+
+/// A candle closing above the recent range
+#[derive(Clone, Debug, PartialEq)]
+pub struct Breakout {
+    pub bracket: AgeBracket,
+    pub candle: Candle,
+    /// Highest high of the lookback candles
+    pub range_high: f64,
+    /// Average volume of the lookback candles
+    pub average_volume: f64,
+}
+
+impl Breakout {
+    pub fn breakout_pct(&self) -> f64 {
+        (self.candle.close / self.range_high - 1.0) * 100.0
+    }
+
+    pub fn volume_multiple(&self) -> f64 {
+        if self.average_volume > 0.0 {
+            self.candle.volume / self.average_volume
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "Closed {:.1}% above the {}-candle high on {:.1}x average volume ({:.2} SOL, age bracket {})",
+            self.breakout_pct(),
+            self.bracket.lookback,
+            self.volume_multiple(),
+            self.candle.volume,
+            self.bracket.label(),
+        )
+    }
+}
+
+struct TokenCandles {
+    series: CandleSeries,
+    /// Start of the last candle that signalled
+    signalled: Option<DateTime<Utc>>,
+}
+
+pub struct BreakoutDetector {
+    config: BreakoutConfig,
+    tokens: HashMap<Pubkey, TokenCandles>,
+}
+
+impl BreakoutDetector {
+    pub fn new(config: BreakoutConfig) -> Self {
+        Self { config, tokens: HashMap::new() }
+    }
+
+    pub fn config(&self) -> &BreakoutConfig {
+        &self.config
+    }
+
+    /// Add a trade of a token `age` old. `volume_factor` scales every bracket's volume multiple,
+    /// e.g. after the signal was rated as noise.
+    pub fn observe(
+        &mut self,
+        mint: Pubkey,
+        age: Duration,
+        at: DateTime<Utc>,
+        price: f64,
+        volume: f64,
+        volume_factor: f64,
+    ) -> Option<Breakout> {
+        let capacity = self.config.max_lookback() + 1;
+        let candle = self.config.candle;
+        let token = self.tokens.entry(mint).or_insert_with(|| TokenCandles {
+            series: CandleSeries::new(candle, capacity),
+            signalled: None,
+        });
+        token.series.update(at, price, volume);
+
+        let bracket = self.config.bracket(age)?;
+        let current = token.series.current()?;
+        if token.signalled == Some(current.start) {
+            return None;
+        }
+        let previous = token.series.previous(bracket.lookback);
+        if previous.len() < bracket.lookback {
+            return None;
+        }
+        let range_high = previous.iter().map(|c| c.high).fold(f64::MIN, f64::max);
+        let average_volume = previous.iter().map(|c| c.volume).sum::<f64>() / previous.len() as f64;
+        if current.close <= range_high || current.volume < average_volume * bracket.volume_multiple * volume_factor {
+            return None;
+        }
+        token.signalled = Some(current.start);
+        Some(Breakout {
+            bracket: bracket.clone(),
+            candle: current.clone(),
+            range_high,
+            average_volume,
+        })
+    }
+
+    pub fn candles(&self, mint: &Pubkey) -> Option<&CandleSeries> {
+        self.tokens.get(mint).map(|t| &t.series)
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(&Pubkey) -> bool) {
+        self.tokens.retain(|mint, _| keep(mint));
+    }
+
+    pub fn token_count(&self) -> usize {
+        self.tokens.len()
+    }
+}
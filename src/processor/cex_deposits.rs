@@ -0,0 +1,118 @@
+//! Known exchange addresses, for exit tracking.
+//!
+//! Exchanges credit deposits to per-user deposit addresses and sweep them into a few hot wallets.
+//! The hot wallets of the larger exchanges are built in and more can be added with
+//! `CEX_ADDRESSES` (`Label:address,...`). A wallet seen transferring into a hot wallet is learned
+//! as one of that exchange's deposit addresses, so a later transfer into it is recognized too.
+//!
+//! The token program monitor alerts when a token's creator or a tracked wallet sends holdings to
+//! an exchange: moving tokens to a CEX is how size gets sold off-chain, usually the exit.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use solana_sdk::pubkey::Pubkey;
+
+lazy_static! {
+    static ref SHARED_REGISTRY: Arc<CexRegistry> = Arc::new(CexRegistry::from_env());
+}
+
+/// Exchange hot wallets on Solana
+const BUILTIN_HOT_WALLETS: &[(&str, &str)] = &[
+    ("Binance", "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9"),
+    ("Binance", "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+    ("Coinbase", "H8sMJSCQxfKiFTCfDR3DUMLPwcRbM61LGFJ8N4dK3WjS"),
+    ("Coinbase", "2AQdpHJ2JpcEgPiATUXjQxA8QmafFegfQwSLWSprPicm"),
+    ("OKX", "5VCwKtCXgCJ6kit5FybXjvriW3xELsFDhYrPSqtJNmcD"),
+    ("Bybit", "AC5RDfQFmDS1deWZos921JfqscXdByf8BKHs5ACWjtW2"),
+    ("Kraken", "FWznbcNXWQuHTawe9RxvQ2LdCENssh12dsznf4RiouN5"),
+    ("KuCoin", "BmFdpraQhkiDQE6SnfG5omcA1VwzqfXrwtNYBwWTymy6"),
+    ("Gate.io", "u6PJ8DtQuPFnfmwHbGFULQ4u4EgjDiyYKjVEsynXq2w"),
+    ("MEXC", "ASTyfSima4LLAdDgoFGkgqoKowG1LZFDr9fAQrg7iaJZ"),
+    ("Bitget", "A77HErqtfN1hLLpvZ9pCtu66FEtM8BveoaKbbMoZ4RiR"),
+];
+
+/// Learned deposit addresses kept at most; the set starts over once full
+const MAX_DEPOSIT_ADDRESSES: usize = 50_000;
+
+/// An address that belongs to an exchange
+#[derive(Clone, Debug, PartialEq)]
+pub struct CexMatch {
+    pub exchange: String,
+    /// A learned per-user deposit address rather than a hot wallet
+    pub deposit_address: bool,
+}
+
+impl CexMatch {
+    pub fn describe(&self) -> String {
+        if self.deposit_address {
+            format!("{} (deposit address)", self.exchange)
+        } else {
+            format!("{} (hot wallet)", self.exchange)
+        }
+    }
+}
+
+/// `Label:address` pairs separated by commas; invalid entries are skipped
+pub fn parse_cex_addresses(text: &str) -> Vec<(String, String)> {
+    text.split(',')
+        .filter_map(|entry| {
+            let (label, address) = entry.split_once(':')?;
+            let (label, address) = (label.trim(), address.trim());
+            (!label.is_empty() && Pubkey::from_str(address).is_ok()).then(|| (label.to_string(), address.to_string()))
+        })
+        .collect()
+}
+
+pub struct CexRegistry {
+    hot_wallets: HashMap<String, String>,
+    /// Deposit address -> exchange
+    deposits: DashMap<String, String>,
+}
+
+impl CexRegistry {
+    /// Built-in hot wallets plus `extra` (label, address) pairs
+    pub fn new(extra: Vec<(String, String)>) -> Self {
+        let hot_wallets = BUILTIN_HOT_WALLETS.iter()
+            .map(|(label, address)| (address.to_string(), label.to_string()))
+            .chain(extra.into_iter().map(|(label, address)| (address, label)))
+            .collect();
+        Self { hot_wallets, deposits: DashMap::new() }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("CEX_ADDRESSES").map(|v| parse_cex_addresses(&v)).unwrap_or_default())
+    }
+
+    /// Process-wide registry
+    pub fn shared() -> Arc<CexRegistry> {
+        SHARED_REGISTRY.clone()
+    }
+
+    pub fn exchange_of(&self, address: &str) -> Option<CexMatch> {
+        if let Some(exchange) = self.hot_wallets.get(address) {
+            return Some(CexMatch { exchange: exchange.clone(), deposit_address: false });
+        }
+        self.deposits.get(address).map(|exchange| CexMatch { exchange: exchange.clone(), deposit_address: true })
+    }
+
+    /// Learn `from` as a deposit address when it sweeps into a hot wallet; returns the exchange
+    /// when it was newly learned
+    pub fn observe_transfer(&self, from: &str, to: &str) -> Option<String> {
+        let exchange = self.hot_wallets.get(to)?;
+        if self.hot_wallets.contains_key(from) || self.deposits.contains_key(from) {
+            return None;
+        }
+        if self.deposits.len() >= MAX_DEPOSIT_ADDRESSES {
+            self.deposits.clear();
+        }
+        self.deposits.insert(from.to_string(), exchange.clone());
+        Some(exchange.clone())
+    }
+
+    pub fn deposit_address_count(&self) -> usize {
+        self.deposits.len()
+    }
+}
@@ -0,0 +1,132 @@
+/*!
+# Copy-Trade Exit Mirroring
+
+Decides how much of a position to sell when a copy-trade target sells a token the bot also
+holds. In proportional mode the bot sells the same fraction of its position as the target sold
+of theirs, read from the target's token balances before and after the sell transaction.
+
+## Environment Variables
+
+- `COPY_EXIT_MODE`: How target sells are mirrored: `proportional`, `full` (sell everything) or
+  `none` (default: `proportional`)
+- `COPY_EXIT_TARGET_MODES`: Per-target overrides as `<wallet>:<mode>` pairs separated by commas
+- `COPY_EXIT_FULL_ABOVE_PCT`: A target selling at least this share of their position (%) is
+  mirrored as a full exit, so no dust is left behind (default: `90`)
+- `COPY_EXIT_MIN_PCT`: Target sells smaller than this share of their position (%) are not
+  mirrored (default: `5`)
+*/
+
+use std::collections::HashMap;
+use yellowstone_grpc_proto::prelude::TransactionStatusMeta;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CopyExitMode {
+    Proportional,
+    Full,
+    None,
+}
+
+impl CopyExitMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "proportional" => Some(Self::Proportional),
+            "full" => Some(Self::Full),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CopyExitConfig {
+    pub default_mode: CopyExitMode,
+    pub target_modes: HashMap<String, CopyExitMode>,
+    pub full_above_pct: f64,
+    pub min_pct: f64,
+}
+
+impl Default for CopyExitConfig {
+    fn default() -> Self {
+        Self {
+            default_mode: CopyExitMode::Proportional,
+            target_modes: HashMap::new(),
+            full_above_pct: 90.0,
+            min_pct: 5.0,
+        }
+    }
+}
+
+impl CopyExitConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            default_mode: var("COPY_EXIT_MODE")
+                .and_then(|v| CopyExitMode::parse(&v))
+                .unwrap_or(defaults.default_mode),
+            target_modes: var("COPY_EXIT_TARGET_MODES")
+                .map(|v| parse_target_modes(&v))
+                .unwrap_or(defaults.target_modes),
+            full_above_pct: var("COPY_EXIT_FULL_ABOVE_PCT").and_then(|v| v.parse().ok()).unwrap_or(defaults.full_above_pct),
+            min_pct: var("COPY_EXIT_MIN_PCT").and_then(|v| v.parse().ok()).unwrap_or(defaults.min_pct),
+        }
+    }
+
+    pub fn mode_for(&self, target: &str) -> CopyExitMode {
+        self.target_modes.get(target).copied().unwrap_or(self.default_mode)
+    }
+
+    /// Share of our position (0-1] to sell when `target` sold `sold_fraction` of theirs, None
+    /// when the sell is not mirrored. An unknown fraction falls back to a full exit.
+    pub fn sell_fraction(&self, target: &str, sold_fraction: Option<f64>) -> Option<f64> {
+        match self.mode_for(target) {
+            CopyExitMode::None => None,
+            CopyExitMode::Full => Some(1.0),
+            CopyExitMode::Proportional => {
+                let sold_pct = match sold_fraction {
+                    Some(fraction) => fraction.clamp(0.0, 1.0) * 100.0,
+                    None => return Some(1.0),
+                };
+                if sold_pct >= self.full_above_pct {
+                    Some(1.0)
+                } else if sold_pct < self.min_pct {
+                    None
+                } else {
+                    Some(sold_pct / 100.0)
+                }
+            }
+        }
+    }
+}
+
+/// `<wallet>:<mode>` pairs; malformed entries are skipped with a warning
+pub fn parse_target_modes(value: &str) -> HashMap<String, CopyExitMode> {
+    let mut modes = HashMap::new();
+    for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        match entry.split_once(':').and_then(|(wallet, mode)| Some((wallet.trim(), CopyExitMode::parse(mode)?))) {
+            Some((wallet, mode)) => {
+                modes.insert(wallet.to_string(), mode);
+            }
+            None => eprintln!("Ignoring COPY_EXIT_TARGET_MODES entry '{}'", entry),
+        }
+    }
+    modes
+}
+
+/// Share of `owner`'s `mint` balance the transaction sold, from its pre and post token balances
+pub fn sold_fraction(meta: &TransactionStatusMeta, owner: &str, mint: &str) -> Option<f64> {
+    let balance = |balances: &[yellowstone_grpc_proto::prelude::TokenBalance]| -> f64 {
+        balances
+            .iter()
+            .filter(|b| b.owner == owner && b.mint == mint)
+            .filter_map(|b| b.ui_token_amount.as_ref())
+            .filter_map(|amount| amount.amount.parse::<f64>().ok())
+            .sum()
+    };
+    let pre = balance(&meta.pre_token_balances);
+    let post = balance(&meta.post_token_balances);
+    if pre <= 0.0 {
+        return None;
+    }
+    Some(((pre - post) / pre).clamp(0.0, 1.0))
+}
@@ -0,0 +1,207 @@
+/*!
+# Copy Buy Sanity Window
+
+Before copying a target wallet's buy, optionally wait a few slots and check that the token is not
+an obvious trap: its risk score has to stay under a limit, and selling the target's fresh bag
+has to succeed in a simulation. This trades latency for safety, so it is off unless a delay is
+configured, globally or for specific targets.
+
+The sell is simulated as the target wallet (signature verification off), through a Jupiter swap
+transaction for the target's whole post-buy balance. Tokens with transfer restrictions, frozen
+accounts or sell-blocking programs fail the simulation. When no route or quote is available the
+check is inconclusive and the copy goes ahead.
+
+## Environment Variables
+
+- `COPY_DELAY_SLOTS`: Slots to wait before copying a buy, 0 copies immediately without checks
+  (default: `0`)
+- `COPY_DELAY_TARGETS`: Per-target delays as `<wallet>:<slots>` pairs separated by commas
+- `COPY_GUARD_MAX_RISK`: Buys of tokens scoring above this (0-100) are not copied (default: `60`)
+- `COPY_GUARD_SIMULATE_SELL`: Simulate a sell of the target's tokens before copying
+  (default: `true`)
+*/
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::RpcSimulateTransactionConfig;
+use anyhow::Result;
+use async_trait::async_trait;
+use solana_sdk::pubkey::Pubkey;
+use yellowstone_grpc_proto::prelude::TransactionStatusMeta;
+
+use crate::common::config::{threshold_or, Slots};
+use crate::library::jupiter_api::JupiterClient;
+use crate::processor::risk_score;
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+#[derive(Clone, Debug)]
+pub struct CopyGuardConfig {
+    pub default_delay_slots: u64,
+    pub target_delays: HashMap<String, u64>,
+    pub max_risk_score: f64,
+    pub simulate_sell: bool,
+    /// Wall-clock length of a slot used for the delay
+    pub slot_ms: u64,
+}
+
+impl Default for CopyGuardConfig {
+    fn default() -> Self {
+        Self {
+            default_delay_slots: 0,
+            target_delays: HashMap::new(),
+            max_risk_score: 60.0,
+            simulate_sell: true,
+            slot_ms: 400,
+        }
+    }
+}
+
+impl CopyGuardConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            default_delay_slots: threshold_or(
+                "COPY_DELAY_SLOTS",
+                Slots::new(defaults.default_delay_slots),
+                Slots::from_str,
+                Slots::new(0)..=Slots::new(1_500),
+            ).get(),
+            target_delays: var("COPY_DELAY_TARGETS").map(|v| parse_target_delays(&v)).unwrap_or(defaults.target_delays),
+            max_risk_score: var("COPY_GUARD_MAX_RISK").and_then(|v| v.parse().ok()).unwrap_or(defaults.max_risk_score),
+            simulate_sell: var("COPY_GUARD_SIMULATE_SELL")
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(defaults.simulate_sell),
+            slot_ms: defaults.slot_ms,
+        }
+    }
+
+    pub fn delay_for(&self, target: &str) -> u64 {
+        self.target_delays.get(target).copied().unwrap_or(self.default_delay_slots)
+    }
+}
+
+/// `<wallet>:<slots>` pairs; malformed entries are skipped with a warning
+pub fn parse_target_delays(value: &str) -> HashMap<String, u64> {
+    let mut delays = HashMap::new();
+    for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        match entry.split_once(':').and_then(|(wallet, slots)| Some((wallet.trim(), slots.trim().parse().ok()?))) {
+            Some((wallet, slots)) => {
+                delays.insert(wallet.to_string(), slots);
+            }
+            None => eprintln!("Ignoring COPY_DELAY_TARGETS entry '{}'", entry),
+        }
+    }
+    delays
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SellSimulation {
+    Sellable,
+    /// The simulated sell failed; the token is likely a trap
+    Failed(String),
+    /// No simulation could be built, e.g. no route yet
+    Inconclusive(String),
+}
+
+/// Simulates a holder selling a token
+#[async_trait]
+pub trait SellSimulator: Send + Sync {
+    async fn simulate_sell(&self, mint: &Pubkey, holder: &Pubkey, raw_amount: u64) -> SellSimulation;
+}
+
+pub struct JupiterSellSimulator {
+    rpc_client: Arc<RpcClient>,
+    jupiter: JupiterClient,
+}
+
+impl JupiterSellSimulator {
+    pub fn new(rpc_client: Arc<RpcClient>) -> Self {
+        Self { jupiter: JupiterClient::new(rpc_client.clone()), rpc_client }
+    }
+}
+
+#[async_trait]
+impl SellSimulator for JupiterSellSimulator {
+    async fn simulate_sell(&self, mint: &Pubkey, holder: &Pubkey, raw_amount: u64) -> SellSimulation {
+        let transaction = match self.jupiter.get_quote(&mint.to_string(), SOL_MINT, raw_amount, 1000).await {
+            Ok(quote) => match self.jupiter.get_swap_transaction(quote, holder).await {
+                Ok(transaction) => transaction,
+                Err(e) => return SellSimulation::Inconclusive(format!("no swap transaction: {}", e)),
+            },
+            Err(e) => return SellSimulation::Inconclusive(format!("no quote: {}", e)),
+        };
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            ..RpcSimulateTransactionConfig::default()
+        };
+        match self.rpc_client.simulate_transaction_with_config(&transaction, config).await {
+            Ok(response) => match response.value.err {
+                None => SellSimulation::Sellable,
+                Some(err) => {
+                    let last_log = response.value.logs.and_then(|logs| logs.last().cloned()).unwrap_or_default();
+                    SellSimulation::Failed(format!("{} {}", err, last_log).trim().to_string())
+                }
+            },
+            Err(e) => SellSimulation::Inconclusive(format!("simulation request failed: {}", e)),
+        }
+    }
+}
+
+pub struct CopyGuard {
+    config: CopyGuardConfig,
+    simulator: Arc<dyn SellSimulator>,
+}
+
+impl CopyGuard {
+    pub fn new(config: CopyGuardConfig, simulator: Arc<dyn SellSimulator>) -> Self {
+        Self { config, simulator }
+    }
+
+    pub fn from_env(rpc_client: Arc<RpcClient>) -> Self {
+        Self::new(CopyGuardConfig::from_env(), Arc::new(JupiterSellSimulator::new(rpc_client)))
+    }
+
+    /// Wait out the target's delay window, then check the token. Err carries the reason the buy
+    /// should not be copied. `target_balance` is the target's raw token balance after its buy.
+    pub async fn vet(&self, target: &Pubkey, mint: &Pubkey, target_balance: Option<u64>) -> Result<(), String> {
+        let slots = self.config.delay_for(&target.to_string());
+        if slots == 0 {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(slots * self.config.slot_ms)).await;
+
+        let score = risk_score::risk_score(&mint.to_string());
+        if score > self.config.max_risk_score {
+            return Err(format!("risk score {:.0} is above {:.0}", score, self.config.max_risk_score));
+        }
+
+        if self.config.simulate_sell {
+            match target_balance.filter(|amount| *amount > 0) {
+                Some(amount) => match self.simulator.simulate_sell(mint, target, amount).await {
+                    SellSimulation::Sellable => {}
+                    SellSimulation::Failed(reason) => return Err(format!("simulated sell failed: {}", reason)),
+                    SellSimulation::Inconclusive(reason) => {
+                        eprintln!("Sell simulation for {} inconclusive ({}), copying anyway", mint, reason)
+                    }
+                },
+                None => eprintln!("No target balance of {} to simulate a sell with, copying anyway", mint),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `owner`'s raw balance of `mint` after the transaction
+pub fn post_token_balance(meta: &TransactionStatusMeta, owner: &str, mint: &str) -> Option<u64> {
+    meta.post_token_balances
+        .iter()
+        .filter(|b| b.owner == owner && b.mint == mint)
+        .filter_map(|b| b.ui_token_amount.as_ref()?.amount.parse::<u64>().ok())
+        .reduce(|a, b| a + b)
+}
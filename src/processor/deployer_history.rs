@@ -0,0 +1,275 @@
+//! Launch history per creator, for vetting a deployer before touching their new launch.
+//!
+//! The first time a token with a known creator is seen, a launch line is appended to
+//! `DEPLOYER_HISTORY_PATH` (JSON lines); when its launch replay ends, an outcome line follows with
+//! how it ended and its peak as a multiple of the first traded price. `/deployer <address>` reads
+//! the file back, merging the lines of each mint, so the history spans restarts and tokens the
+//! monitor stopped tracking long ago. Only launches whose creator is still open (no outcome yet)
+//! are held in memory.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write as _};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::processor::launch_replay::{LaunchOutcome, LaunchSession};
+use crate::processor::transaction_parser::ParsedData;
+
+/// One line of the history file: a launch, or the outcome of one
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeployerRecord {
+    pub creator: String,
+    pub mint: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Unix seconds the token was first seen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launched_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_multiple: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<i64>,
+}
+
+impl DeployerRecord {
+    /// Fill the fields this record lacks from a later line of the same mint
+    fn merge(&mut self, other: DeployerRecord) {
+        self.name = self.name.take().or(other.name);
+        self.launched_at = match (self.launched_at, other.launched_at) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if other.outcome.is_some() {
+            self.outcome = other.outcome;
+            self.peak_multiple = other.peak_multiple;
+            self.ended_at = other.ended_at;
+        }
+    }
+
+    pub fn is_graduated(&self) -> bool {
+        self.outcome.as_deref() == Some(LaunchOutcome::Graduated.label())
+    }
+
+    pub fn is_rugged(&self) -> bool {
+        self.outcome.as_deref().is_some_and(|o| o.starts_with("RUGGED"))
+    }
+}
+
+/// Aggregates over a creator's launches
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeployerStats {
+    pub launches: usize,
+    pub graduated: usize,
+    pub died: usize,
+    pub rugged: usize,
+    /// No recorded outcome: still live, or ended too quietly for a replay
+    pub open: usize,
+    pub median_peak_multiple: Option<f64>,
+}
+
+impl DeployerStats {
+    pub fn from_launches(launches: &[DeployerRecord]) -> Self {
+        let graduated = launches.iter().filter(|l| l.is_graduated()).count();
+        let rugged = launches.iter().filter(|l| l.is_rugged()).count();
+        let ended = launches.iter().filter(|l| l.outcome.is_some()).count();
+        let mut peaks: Vec<f64> = launches.iter().filter_map(|l| l.peak_multiple).collect();
+        peaks.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let median_peak_multiple = match peaks.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(peaks[n / 2]),
+            n => Some((peaks[n / 2 - 1] + peaks[n / 2]) / 2.0),
+        };
+        Self {
+            launches: launches.len(),
+            graduated,
+            died: ended - graduated - rugged,
+            rugged,
+            open: launches.len() - ended,
+            median_peak_multiple,
+        }
+    }
+
+    /// Share of launches with an outcome that graduated (%)
+    pub fn graduation_pct(&self) -> Option<f64> {
+        let ended = self.launches - self.open;
+        (ended > 0).then(|| self.graduated as f64 / ended as f64 * 100.0)
+    }
+}
+
+pub struct DeployerHistory {
+    path: Option<PathBuf>,
+    /// Mint -> creator of launches without an outcome yet
+    open: Mutex<HashMap<Pubkey, String>>,
+}
+
+impl DeployerHistory {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self { path, open: Mutex::new(HashMap::new()) }
+    }
+
+    /// DEPLOYER_HISTORY_PATH (default `deployer_history.jsonl`, empty turns the history off)
+    pub fn from_env() -> Self {
+        let path = std::env::var("DEPLOYER_HISTORY_PATH").unwrap_or_else(|_| "deployer_history.jsonl".to_string());
+        Self::new(Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty()))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Record the launch of a token with a known creator the first time it is seen
+    pub fn record_launch(&self, parsed_data: &ParsedData, at: DateTime<Utc>) {
+        let creator = match (&self.path, parsed_data.coin_creator) {
+            (Some(_), Some(creator)) => creator.to_string(),
+            _ => return,
+        };
+        {
+            let mut open = self.open.lock().unwrap();
+            if open.contains_key(&parsed_data.token_mint) {
+                return;
+            }
+            open.insert(parsed_data.token_mint, creator.clone());
+        }
+        self.append(&DeployerRecord {
+            creator,
+            mint: parsed_data.token_mint.to_string(),
+            name: parsed_data.token_name.clone(),
+            launched_at: Some(at.timestamp()),
+            ..DeployerRecord::default()
+        });
+    }
+
+    /// Record how a launch ended, if its creator is known
+    pub fn record_outcome(&self, session: &LaunchSession, outcome: &LaunchOutcome, at: DateTime<Utc>) {
+        let creator = match self.open.lock().unwrap().remove(&session.mint) {
+            Some(creator) => creator,
+            None => return,
+        };
+        let peak_multiple = match (session.first_price(), session.peak_price) {
+            (Some(first), Some((peak, _))) if first > 0.0 => Some(peak / first),
+            _ => None,
+        };
+        self.append(&DeployerRecord {
+            creator,
+            mint: session.mint.to_string(),
+            outcome: Some(outcome.label().to_string()),
+            peak_multiple,
+            ended_at: Some(at.timestamp()),
+            ..DeployerRecord::default()
+        });
+    }
+
+    fn append(&self, record: &DeployerRecord) {
+        if let Some(path) = &self.path {
+            let written = serde_json::to_string(record)
+                .map_err(anyhow::Error::from)
+                .and_then(|line| {
+                    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+                    writeln!(file, "{}", line)?;
+                    Ok(())
+                });
+            if let Err(e) = written {
+                eprintln!("Failed to record deployer history in {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// Every recorded launch of `creator`, one per mint, oldest first
+    pub fn launches_of(&self, creator: &str) -> Vec<DeployerRecord> {
+        let file = match self.path.as_ref().and_then(|path| std::fs::File::open(path).ok()) {
+            Some(file) => file,
+            None => return Vec::new(),
+        };
+        let mut by_mint: HashMap<String, DeployerRecord> = HashMap::new();
+        for record in BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .filter(|line| line.contains(creator))
+            .filter_map(|line| serde_json::from_str::<DeployerRecord>(&line).ok())
+            .filter(|record| record.creator == creator)
+        {
+            match by_mint.get_mut(&record.mint) {
+                Some(known) => known.merge(record),
+                None => {
+                    by_mint.insert(record.mint.clone(), record);
+                }
+            }
+        }
+        let mut launches: Vec<DeployerRecord> = by_mint.into_values().collect();
+        launches.sort_by_key(|l| l.launched_at.unwrap_or(i64::MAX));
+        launches
+    }
+
+    /// Creator of a recorded launch
+    pub fn creator_of(&self, mint: &str) -> Option<String> {
+        if let Some((_, creator)) = self.open.lock().unwrap().iter().find(|(open, _)| open.to_string() == mint) {
+            return Some(creator.clone());
+        }
+        let file = self.path.as_ref().and_then(|path| std::fs::File::open(path).ok())?;
+        BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .filter(|line| line.contains(mint))
+            .filter_map(|line| serde_json::from_str::<DeployerRecord>(&line).ok())
+            .find(|record| record.mint == mint)
+            .map(|record| record.creator)
+    }
+
+    /// Forget open launches of tokens no longer tracked; their outcome will not be seen
+    pub fn retain(&self, keep: impl Fn(&Pubkey) -> bool) {
+        self.open.lock().unwrap().retain(|mint, _| keep(mint));
+    }
+
+    pub fn open_count(&self) -> usize {
+        self.open.lock().unwrap().len()
+    }
+}
+
+/// Reply to `/deployer`: aggregate stats, then the launches, newest first
+pub fn format_deployer(creator: &str, launches: &[DeployerRecord]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "🏗️ Deployer {}\n", creator);
+    if launches.is_empty() {
+        let _ = writeln!(out, "No launches recorded for this address.");
+        return out;
+    }
+    let stats = DeployerStats::from_launches(launches);
+    let _ = writeln!(
+        out,
+        "{} launches: {} graduated, {} died, {} rugged, {} live or unresolved",
+        stats.launches, stats.graduated, stats.died, stats.rugged, stats.open
+    );
+    if let Some(pct) = stats.graduation_pct() {
+        let _ = writeln!(out, "Graduation rate: {:.0}% of resolved launches", pct);
+    }
+    if let Some(peak) = stats.median_peak_multiple {
+        let _ = writeln!(out, "Median peak: {:.1}x the first price", peak);
+    }
+    if stats.rugged > 0 {
+        let _ = writeln!(out, "⚠️ Pulled liquidity on {} launch(es)", stats.rugged);
+    }
+    let _ = writeln!(out);
+    for launch in launches.iter().rev() {
+        let date = launch.launched_at
+            .and_then(|t| DateTime::from_timestamp(t, 0))
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "unknown date".to_string());
+        let peak = launch.peak_multiple.map(|p| format!(", peak {:.1}x", p)).unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "• {} {} — {}{} ({})",
+            launch.name.as_deref().unwrap_or("Unknown"),
+            launch.mint,
+            launch.outcome.as_deref().unwrap_or("LIVE / UNRESOLVED"),
+            peak,
+            date
+        );
+    }
+    out
+}
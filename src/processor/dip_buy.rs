@@ -0,0 +1,370 @@
+/*!
+# Dip-Buy Re-Entry
+
+Turns the focus drop idea into a strategy for tokens the bot is already done with: after the bot
+sold a token, or skipped a buy signal for it (e.g. at the counter limit), the token is watched.
+Every trade seen for it moves its peak price; once the price is `DIP_BUY_DROP_PCT` below that peak
+and the token's holder count is still within `DIP_BUY_MAX_HOLDER_DROP_PCT` of what it was when
+watching started, a re-entry buy is signalled. A dip that holders are leaving is a token dying,
+not a dip.
+
+Re-entries are spaced by `DIP_BUY_COOLDOWN_SECS` (also counted from the sell or skip), limited to
+`DIP_BUY_MAX_REENTRIES` per token, and a token is only watched for `DIP_BUY_WATCH_SECS`. After a
+re-entry the peak starts over from the entry price. Nothing is signalled while the token is held.
+Each signalled re-entry exempts one buy from the sniper's never-rebuy blacklist.
+
+## Environment Variables
+
+- `DIP_BUY_ENABLED`: Watch sold and skipped tokens for re-entries (default: `false`)
+- `DIP_BUY_DROP_PCT`: Drop from the peak (%) that signals a re-entry (default: `FOCUS_DROP_THRESHOLD_PCT`
+  as a percentage, `15`)
+- `DIP_BUY_MAX_HOLDER_DROP_PCT`: Largest fall in holder count (%) still taken as stable (default: `10`)
+- `DIP_BUY_COOLDOWN_SECS`: Time between re-entry attempts of a token (default: `300`)
+- `DIP_BUY_MAX_REENTRIES`: Re-entries per token (default: `2`)
+- `DIP_BUY_WATCH_SECS`: How long a token is watched after its sell or skip (default: `3600`)
+*/
+
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use anchor_client::solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+
+use crate::common::clock::{system_clock, Clock};
+use crate::common::config::{threshold_or, Percent};
+use crate::common::logger::Logger;
+
+lazy_static! {
+    static ref SHARED_DIP_BUY: Arc<DipBuy> = Arc::new(DipBuy::new(DipBuyConfig::from_env()));
+    static ref LOGGER: Logger = Logger::new("[DIP-BUY] => ".cyan().to_string());
+}
+
+/// Offset of the owner-independent amount in an SPL token account
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+/// Size of a classic SPL token account
+const TOKEN_ACCOUNT_LEN: u64 = 165;
+
+#[derive(Clone, Debug)]
+pub struct DipBuyConfig {
+    pub enabled: bool,
+    /// Drop from the peak (%) that signals a re-entry
+    pub drop_pct: f64,
+    /// Largest fall in holder count (%) still taken as stable
+    pub max_holder_drop_pct: f64,
+    pub cooldown: Duration,
+    pub max_reentries: u32,
+    /// How long a token is watched after its sell or skip
+    pub watch_for: Duration,
+}
+
+impl Default for DipBuyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            drop_pct: 15.0,
+            max_holder_drop_pct: 10.0,
+            cooldown: Duration::seconds(300),
+            max_reentries: 2,
+            watch_for: Duration::seconds(3600),
+        }
+    }
+}
+
+impl DipBuyConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        let secs = |name: &str| var(name).and_then(|v| v.parse::<i64>().ok()).filter(|s| *s >= 0).map(Duration::seconds);
+        let drop_range = Percent::from_points(0.01)..=Percent::from_points(100.0);
+        Self {
+            enabled: var("DIP_BUY_ENABLED")
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(defaults.enabled),
+            drop_pct: threshold_or(
+                "DIP_BUY_DROP_PCT",
+                threshold_or("FOCUS_DROP_THRESHOLD_PCT", Percent::from_points(defaults.drop_pct), Percent::parse_fraction, drop_range.clone()),
+                Percent::from_str,
+                drop_range,
+            ).points(),
+            max_holder_drop_pct: threshold_or(
+                "DIP_BUY_MAX_HOLDER_DROP_PCT",
+                Percent::from_points(defaults.max_holder_drop_pct),
+                Percent::from_str,
+                Percent::from_points(0.0)..=Percent::from_points(100.0),
+            ).points(),
+            cooldown: secs("DIP_BUY_COOLDOWN_SECS").unwrap_or(defaults.cooldown),
+            max_reentries: var("DIP_BUY_MAX_REENTRIES")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.max_reentries),
+            watch_for: secs("DIP_BUY_WATCH_SECS").unwrap_or(defaults.watch_for),
+        }
+    }
+}
+
+/// Why a token is watched for re-entries
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WatchReason {
+    Sold,
+    Skipped,
+}
+
+#[derive(Clone, Debug)]
+pub struct DipCandidate {
+    pub mint: String,
+    pub reason: WatchReason,
+    pub watched_at: DateTime<Utc>,
+    /// Highest price seen since watching started or the last re-entry
+    pub peak_price: f64,
+    /// Holder count when watching started, None until it was read
+    pub holders_baseline: Option<usize>,
+    pub reentries: u32,
+    /// Last re-entry attempt, passed or rejected
+    pub last_attempt: Option<DateTime<Utc>>,
+}
+
+/// A dip that still needs its holder check
+#[derive(Clone, Debug, PartialEq)]
+pub struct DipCheck {
+    pub mint: String,
+    pub peak_price: f64,
+    pub price: f64,
+    pub drop_pct: f64,
+}
+
+/// A confirmed re-entry
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReentrySignal {
+    pub mint: String,
+    pub peak_price: f64,
+    pub price: f64,
+    pub drop_pct: f64,
+    pub holders_before: usize,
+    pub holders_now: usize,
+    /// 1 for the first re-entry of the token
+    pub reentry: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DipDecision {
+    Reenter(ReentrySignal),
+    Rejected(String),
+}
+
+/// Number of wallets holding a token
+#[async_trait]
+pub trait HolderCount: Send + Sync {
+    async fn holder_count(&self, mint: &Pubkey) -> Result<usize>;
+}
+
+/// Holder count from the token accounts of the mint with a non-zero balance, classic SPL Token
+/// and Token-2022
+pub struct RpcHolderCount {
+    rpc_client: Arc<RpcClient>,
+}
+
+impl RpcHolderCount {
+    pub fn new(rpc_client: Arc<RpcClient>) -> Self {
+        Self { rpc_client }
+    }
+}
+
+#[async_trait]
+impl HolderCount for RpcHolderCount {
+    async fn holder_count(&self, mint: &Pubkey) -> Result<usize> {
+        let mut holders = 0;
+        for (program, size) in [(spl_token::id(), Some(TOKEN_ACCOUNT_LEN)), (spl_token_2022::id(), None)] {
+            let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new(0, MemcmpEncodedBytes::Base64(base64::encode(mint.to_bytes()))))];
+            if let Some(size) = size {
+                filters.push(RpcFilterType::DataSize(size));
+            }
+            let accounts = self.rpc_client.get_program_accounts_with_config(
+                &program,
+                RpcProgramAccountsConfig {
+                    filters: Some(filters),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        data_slice: Some(UiDataSliceConfig { offset: TOKEN_ACCOUNT_AMOUNT_OFFSET, length: 8 }),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ).await?;
+            holders += accounts.iter()
+                .filter(|(_, account)| account.data.len() == 8 && account.data.iter().any(|b| *b != 0))
+                .count();
+        }
+        Ok(holders)
+    }
+}
+
+pub struct DipBuy {
+    config: DipBuyConfig,
+    candidates: DashMap<String, DipCandidate>,
+    clock: Arc<dyn Clock>,
+}
+
+impl DipBuy {
+    pub fn new(config: DipBuyConfig) -> Self {
+        Self { config, candidates: DashMap::new(), clock: system_clock() }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn shared() -> Arc<DipBuy> {
+        SHARED_DIP_BUY.clone()
+    }
+
+    pub fn config(&self) -> &DipBuyConfig {
+        &self.config
+    }
+
+    /// Start watching a token; a token already watched keeps its re-entry count. Returns
+    /// whether it is watched.
+    pub fn watch(&self, mint: &str, reason: WatchReason) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+        let now = self.clock.now();
+        let mut candidate = self.candidates.entry(mint.to_string()).or_insert_with(|| DipCandidate {
+            mint: mint.to_string(),
+            reason,
+            watched_at: now,
+            peak_price: 0.0,
+            holders_baseline: None,
+            reentries: 0,
+            last_attempt: None,
+        });
+        if candidate.watched_at + self.config.watch_for <= now {
+            candidate.watched_at = now;
+            candidate.reason = reason;
+        }
+        true
+    }
+
+    /// Record the holder count the re-entry checks compare against
+    pub fn set_holders_baseline(&self, mint: &str, holders: usize) {
+        if let Some(mut candidate) = self.candidates.get_mut(mint) {
+            candidate.holders_baseline.get_or_insert(holders);
+        }
+    }
+
+    /// Price of a trade of a watched token. Returns a dip once it is deep enough, off cooldown and
+    /// re-entries remain; the attempt starts the cooldown. A `risk` multiplier below 1 (a cold
+    /// market regime) asks for a proportionally deeper dip.
+    pub fn observe(&self, mint: &str, price: f64, risk: f64) -> Option<DipCheck> {
+        if price <= 0.0 || !price.is_finite() {
+            return None;
+        }
+        let now = self.clock.now();
+        let mut candidate = self.candidates.get_mut(mint)?;
+        if candidate.watched_at + self.config.watch_for <= now || candidate.reentries >= self.config.max_reentries {
+            drop(candidate);
+            self.candidates.remove(mint);
+            return None;
+        }
+        candidate.peak_price = candidate.peak_price.max(price);
+
+        let drop_pct = (candidate.peak_price - price) / candidate.peak_price * 100.0;
+        let cooling_since = candidate.last_attempt.unwrap_or(candidate.watched_at);
+        if drop_pct < self.config.drop_pct / risk.clamp(0.01, 1.0) || cooling_since + self.config.cooldown > now {
+            return None;
+        }
+        candidate.last_attempt = Some(now);
+        Some(DipCheck { mint: mint.to_string(), peak_price: candidate.peak_price, price, drop_pct })
+    }
+
+    /// Check the holders behind a dip and count the re-entry when they stayed
+    pub async fn evaluate(&self, check: DipCheck, holders: &dyn HolderCount) -> DipDecision {
+        let mint = match Pubkey::from_str(&check.mint) {
+            Ok(mint) => mint,
+            Err(e) => return DipDecision::Rejected(format!("invalid mint: {}", e)),
+        };
+        let holders_now = match holders.holder_count(&mint).await {
+            Ok(count) => count,
+            Err(e) => return DipDecision::Rejected(format!("holder count unavailable: {}", e)),
+        };
+
+        let mut candidate = match self.candidates.get_mut(&check.mint) {
+            Some(candidate) => candidate,
+            None => return DipDecision::Rejected("no longer watched".to_string()),
+        };
+        let holders_before = match candidate.holders_baseline {
+            Some(before) => before,
+            None => {
+                candidate.holders_baseline = Some(holders_now);
+                return DipDecision::Rejected(format!("no holder baseline yet, {} holders now", holders_now));
+            }
+        };
+        let holder_drop_pct = if holders_before > 0 {
+            (holders_before as f64 - holders_now as f64) / holders_before as f64 * 100.0
+        } else {
+            0.0
+        };
+        if holder_drop_pct > self.config.max_holder_drop_pct {
+            return DipDecision::Rejected(format!(
+                "holders fell {:.1}% ({} -> {})", holder_drop_pct, holders_before, holders_now
+            ));
+        }
+
+        candidate.reentries += 1;
+        candidate.peak_price = check.price;
+        DipDecision::Reenter(ReentrySignal {
+            mint: check.mint,
+            peak_price: check.peak_price,
+            price: check.price,
+            drop_pct: check.drop_pct,
+            holders_before,
+            holders_now,
+            reentry: candidate.reentries,
+        })
+    }
+
+    pub fn get(&self, mint: &str) -> Option<DipCandidate> {
+        self.candidates.get(mint).map(|c| c.clone())
+    }
+
+    pub fn remove(&self, mint: &str) {
+        self.candidates.remove(mint);
+    }
+
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+}
+
+/// Watch a token for re-entries and read its holder baseline in the background
+pub fn start_watch(mint: &str, reason: WatchReason, rpc_client: Arc<RpcClient>) {
+    let dip_buy = DipBuy::shared();
+    if !dip_buy.watch(mint, reason) {
+        return;
+    }
+    let mint = mint.to_string();
+    tokio::spawn(async move {
+        let pubkey = match Pubkey::from_str(&mint) {
+            Ok(pubkey) => pubkey,
+            Err(_) => return,
+        };
+        match RpcHolderCount::new(rpc_client).holder_count(&pubkey).await {
+            Ok(holders) => {
+                dip_buy.set_holders_baseline(&mint, holders);
+                LOGGER.log(format!("Watching {} for re-entries ({:?}), {} holders", mint, reason, holders));
+            }
+            Err(e) => LOGGER.log(format!("Holder count of {} failed: {}", mint, e).yellow().to_string()),
+        }
+    });
+}
@@ -1,20 +1,201 @@
 use crate::processor::telegram_alerts::TelegramAlertSystem;
 use crate::processor::transaction_parser::{ParsedData, SwapType};
-use crate::common::config::Config;
+use crate::processor::signal_outcomes::{format_signal_accuracy, outcome_horizons, SignalDirection, SignalOutcome};
+use crate::processor::watchlist::WatchlistEntry;
+use crate::processor::liquidity_depth::{
+    format_depth_curve, parse_pool_id, price_impact_pct, LiquidityDepth, LiquidityDepthConfig, ReserveReader,
+};
+use crate::processor::arbitrage::{venue_label, Spread, SpreadConfig, SpreadTracker};
+use crate::processor::launch_replay::{format_timeline, LaunchRecorder, LaunchReplayConfig};
+use crate::processor::launch_stats::{format_launch_stats, LaunchStats};
+use crate::processor::insider_detection::InsiderDetector;
+use crate::processor::wallet_age::WalletAgeChecker;
+use crate::processor::first_buyers::{format_first_buyers, FirstBuyers};
+use crate::processor::deployer_history::{format_deployer, DeployerHistory};
+use crate::processor::script_filter::ScriptHook;
+use crate::processor::signal_bus::{Signal, SignalBus, SignalRecorder, TelegramSignals};
+use crate::processor::opportunity_signal::{MetricsSnapshot, OpportunityKind, OpportunitySignal};
+use crate::processor::token_timeline::{format_token_timeline, TokenTimeline};
+use crate::processor::token_audit::{format_audit, TokenAuditor};
+use crate::processor::wallet_groups::{format_wallet_groups, ParsedWallets, WalletGroups};
+use crate::processor::group_consensus::{ConsensusHit, GroupConsensus};
+use crate::processor::signal_confirmation::{self, SignalConfirmation};
+use crate::processor::risk_score;
+use crate::processor::wallet_positions::{WalletPosition, WalletPositions};
+use crate::processor::breakout::{BreakoutConfig, BreakoutDetector};
+use crate::processor::trending::{format_trending, TrendingConfig, TrendingToken, TrendingTracker};
+use crate::processor::alert_feedback::{AlertFeedback, FeedbackOutcome, Rating};
+use crate::processor::adaptive_thresholds::{format_adjustments, AdaptiveThresholds};
+use crate::common::quote_asset;
+use crate::processor::swap::SwapDirection;
+use crate::processor::transaction_parser::DexType;
+use crate::common::clock::{system_clock, Clock, EventClock};
+use crate::common::price_history::PriceHistory;
+use crate::common::config::{threshold_or, Config, Percent};
+use crate::library::audit_log::{self, AuditAction};
+use crate::library::memory_budget::{self, MemoryBudget, MemoryComponent};
+use crate::library::rolling::{RollingStats, VolumeBuckets, VolumeTotals};
 use anyhow::Result;
 use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use std::collections::HashMap;
-use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 
 /// Educational monitoring system that tracks tokens without trading
 /// This replaces the trading functionality with alert-only monitoring
 pub struct EducationalMonitor {
     config: Config,
+    /// Follow-ups and reports; detections go out through `signals`
     telegram: Option<Arc<TelegramAlertSystem>>,
+    signals: SignalBus,
+    /// Notable events per token, shared with the status server
+    timeline: Arc<TokenTimeline>,
     tracked_tokens: Arc<RwLock<HashMap<Pubkey, TokenMetrics>>>,
     tracked_wallets: Arc<RwLock<HashMap<Pubkey, WalletMetrics>>>,
+    thresholds: Arc<RwLock<MonitorThresholds>>,
+    alert_snapshots: Arc<RwLock<HashMap<Pubkey, AlertSnapshot>>>,
+    watching: Arc<RwLock<HashSet<Pubkey>>>,
+    signal_outcomes: Arc<RwLock<Vec<SignalOutcome>>>,
+    liquidity_depth: Arc<RwLock<LiquidityDepth>>,
+    spreads: Arc<RwLock<SpreadTracker>>,
+    launches: Arc<RwLock<LaunchRecorder>>,
+    /// Launch time, peak and first-hour survival of every launch of the last week
+    launch_stats: Arc<RwLock<LaunchStats>>,
+    insiders: Arc<InsiderDetector>,
+    wallet_ages: Arc<WalletAgeChecker>,
+    first_buyers: Arc<FirstBuyers>,
+    deployers: Arc<DeployerHistory>,
+    /// Reads any mint from the chain for `/audit`
+    auditor: Arc<TokenAuditor>,
+    /// Imported wallets with labels and groups; their buys are aggregated per group
+    wallet_groups: Arc<WalletGroups>,
+    /// GROUP_CONSENSUS_PATH rules: M of N wallets of a group buying the same token
+    consensus: Arc<GroupConsensus>,
+    /// ALERT_FILTER_SCRIPT: trades it rejects raise no alerts or signals
+    alert_filter: Arc<ScriptHook>,
+    trending: Arc<RwLock<TrendingTracker>>,
+    /// SOL size of each token's most recent trades over the last 24h
+    trade_sizes: Arc<RwLock<RollingStats<Pubkey>>>,
+    /// Buy and sell volume of each token per minute over the last 24h
+    volumes: Arc<RwLock<HashMap<Pubkey, VolumeBuckets>>>,
+    /// Average entry of each wallet in each token
+    positions: Arc<RwLock<WalletPositions>>,
+    breakouts: Arc<RwLock<BreakoutDetector>>,
+    feedback: Arc<AlertFeedback>,
+    /// Raises thresholds of signal types that fire too often (ADAPTIVE_ALERTS_PER_HOUR)
+    adaptive: Arc<AdaptiveThresholds>,
+    memory_budget: MemoryBudget,
+    /// Tokens evicted to stay within the memory budget since the last report
+    evicted_tokens: Arc<AtomicUsize>,
+    /// Runs on the block time of the trades processed, so windows and ages hold up under lag and replay
+    clock: Arc<EventClock>,
+}
+
+/// Thresholds used by pattern detection, adjustable at runtime
+#[derive(Clone, Debug, Serialize)]
+pub struct MonitorThresholds {
+    /// Price change from first seen (%) that triggers a price movement alert
+    pub price_change_pct: f64,
+    /// Buy/sell count ratio considered "high buy pressure"
+    pub buy_sell_ratio: f64,
+    /// Single transaction size (SOL) considered whale activity
+    pub whale_sol: f64,
+    /// Drawdown from first seen (%) after which buying pressure flags a recovery
+    pub recovery_drop_pct: f64,
+    /// Pump.fun bonding curve completion (%) that triggers the "about to graduate" alert
+    pub graduation_pct: f64,
+    /// Share of pool SOL withdrawn at once (%) that triggers a liquidity removal alert
+    pub liquidity_removal_pct: f64,
+    /// Standard deviations from the token's 24h trade size that make a trade unusual
+    pub trade_anomaly_z: f64,
+    /// Scales the volume multiple every breakout age bracket requires
+    pub breakout_volume_factor: f64,
+}
+
+impl Default for MonitorThresholds {
+    fn default() -> Self {
+        let percent = Percent::from_points(0.0)..=Percent::from_points(100.0);
+        Self {
+            price_change_pct: 20.0,
+            buy_sell_ratio: 3.0,
+            whale_sol: 10.0,
+            recovery_drop_pct: 30.0,
+            graduation_pct: threshold_or("BONDING_CURVE_ALERT_PCT", Percent::from_points(90.0), Percent::from_str, percent.clone()).points(),
+            liquidity_removal_pct: threshold_or("LIQUIDITY_REMOVAL_ALERT_PCT", Percent::from_points(10.0), Percent::from_str, percent).points(),
+            trade_anomaly_z: std::env::var("TRADE_ANOMALY_Z_SCORE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(4.0),
+            breakout_volume_factor: 1.0,
+        }
+    }
+}
+
+impl MonitorThresholds {
+    /// Multiply the threshold behind `signal_type` by `factor`, e.g. after users rated its alerts
+    /// as noise; the graduation threshold stays below 100%
+    pub fn raise(&mut self, signal_type: &str, factor: f64) {
+        match signal_type {
+            "Graduation" => self.graduation_pct = (self.graduation_pct * factor).min(99.0).max(self.graduation_pct),
+            "Price Movement" => self.price_change_pct *= factor,
+            "High Buy Pressure" => self.buy_sell_ratio *= factor,
+            "Large Transaction" => self.whale_sol *= factor,
+            "Potential Recovery" => self.recovery_drop_pct *= factor,
+            "Unusual Trade Size" => self.trade_anomaly_z *= factor,
+            "Momentum Breakout" => self.breakout_volume_factor *= factor,
+            _ => {}
+        }
+    }
+
+    /// Default thresholds with the raises earned from alert feedback in earlier runs
+    fn with_feedback(feedback: &AlertFeedback) -> Self {
+        let mut thresholds = Self::default();
+        for signal_type in ["Graduation", "Price Movement", "High Buy Pressure", "Large Transaction", "Potential Recovery", "Unusual Trade Size", "Momentum Breakout"] {
+            thresholds.raise(signal_type, feedback.threshold_factor(signal_type));
+        }
+        thresholds
+    }
+
+    /// `raise` at runtime, recording the old and new thresholds in the audit log under `source`
+    fn raise_audited(&mut self, signal_type: &str, factor: f64, source: &str) {
+        let old = serde_json::to_value(&*self).unwrap_or_default();
+        self.raise(signal_type, factor);
+        audit_log::record(
+            AuditAction::ConfigChanged,
+            source,
+            serde_json::json!({
+                "setting": "thresholds",
+                "signal_type": signal_type,
+                "factor": factor,
+                "old": old,
+                "new": serde_json::to_value(&*self).unwrap_or_default(),
+            }),
+        );
+    }
+}
+
+/// Trades kept per token for its trade size statistics
+const TRADE_SIZES_PER_TOKEN: usize = 10_000;
+/// Minutes of volume compared against the average for a volume spike
+const VOLUME_SPIKE_WINDOW_MIN: i64 = 5;
+/// Minutes before the spike window the average volume is taken over
+const VOLUME_SPIKE_BASELINE_MIN: i64 = 60;
+/// Trades of a token needed before one can be flagged as unusual
+const TRADE_ANOMALY_MIN_SAMPLES: usize = 30;
+
+/// Category a token's alert ratings are tallied under
+fn token_category(metrics: &TokenMetrics) -> &'static str {
+    if metrics.bonding_curve_progress.is_some() {
+        "bonding curve"
+    } else if quote_asset::for_mint(&metrics.address.to_string()).is_stable() {
+        "stablecoin pair"
+    } else {
+        "amm pool"
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -24,7 +205,16 @@ pub struct TokenMetrics {
     pub symbol: Option<String>,
     pub initial_price: Option<f64>,
     pub current_price: Option<f64>,
+    /// SOL traded over the last 24h
     pub volume_24h: f64,
+    /// SOL spent buying over the last 24h
+    pub buy_volume_24h: f64,
+    /// SOL received selling over the last 24h
+    pub sell_volume_24h: f64,
+    /// SOL traded over the last hour
+    pub volume_1h: f64,
+    /// SOL traded since the token was first seen
+    pub total_volume: f64,
     pub liquidity: f64,
     pub holder_count: usize,
     pub first_seen: chrono::DateTime<Utc>,
@@ -33,6 +223,79 @@ pub struct TokenMetrics {
     pub sell_count: u32,
     pub largest_buy_sol: f64,
     pub largest_sell_sol: f64,
+    /// Pump.fun bonding curve completion (%), None for other DEXes
+    pub bonding_curve_progress: Option<f64>,
+    /// Whether the "about to graduate" alert was already sent
+    pub graduation_alerted: bool,
+    /// Imported from a watchlist: tracked even without observed activity
+    pub pinned: bool,
+    /// Share of checked buyers that were freshly created wallets (%), None before any was checked
+    pub fresh_wallet_pct: Option<f64>,
+}
+
+/// Token state when its last alert was sent, the baseline for follow-ups
+#[derive(Clone, Debug)]
+pub struct AlertSnapshot {
+    pub price: Option<f64>,
+    pub volume: f64,
+    pub liquidity: f64,
+    pub buy_count: u32,
+    pub sell_count: u32,
+    pub at: chrono::DateTime<Utc>,
+}
+
+impl AlertSnapshot {
+    fn of(metrics: &TokenMetrics, at: DateTime<Utc>) -> Self {
+        Self {
+            price: metrics.current_price,
+            volume: metrics.total_volume,
+            liquidity: metrics.liquidity,
+            buy_count: metrics.buy_count,
+            sell_count: metrics.sell_count,
+            at,
+        }
+    }
+}
+
+/// Minutes after an alert at which watched tokens get a follow-up (FOLLOW_UP_INTERVALS_MIN)
+fn follow_up_intervals() -> Vec<i64> {
+    std::env::var("FOLLOW_UP_INTERVALS_MIN")
+        .ok()
+        .map(|v| v.split(',').filter_map(|m| m.trim().parse::<i64>().ok()).filter(|m| *m > 0).collect::<Vec<_>>())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| vec![15, 60, 240])
+}
+
+fn format_elapsed(minutes: i64) -> String {
+    if minutes >= 60 && minutes % 60 == 0 {
+        format!("+{}h", minutes / 60)
+    } else {
+        format!("+{}m", minutes)
+    }
+}
+
+/// What happened to a token since the alert baseline
+pub fn format_follow_up(baseline: &AlertSnapshot, current: &TokenMetrics) -> String {
+    let price_line = match (baseline.price, current.current_price) {
+        (Some(then), Some(now)) if then > 0.0 => format!(
+            "💱 Price: {:.10} → {:.10} ({:+.2}%)",
+            then, now, (now - then) / then * 100.0
+        ),
+        (_, Some(now)) => format!("💱 Price now: {:.10}", now),
+        _ => "💱 Price: no trades observed yet".to_string(),
+    };
+    format!(
+        "{}\n\
+        📊 Volume since alert: {:.2} SOL\n\
+        🔄 Trades since alert: {} buys / {} sells\n\
+        💧 Liquidity: {:.2} → {:.2} SOL",
+        price_line,
+        current.total_volume - baseline.volume,
+        current.buy_count.saturating_sub(baseline.buy_count),
+        current.sell_count.saturating_sub(baseline.sell_count),
+        baseline.liquidity,
+        current.liquidity,
+    )
 }
 
 #[derive(Clone, Debug)]
@@ -49,64 +312,868 @@ pub struct WalletMetrics {
 
 impl EducationalMonitor {
     pub fn new(config: Config, telegram: Option<Arc<TelegramAlertSystem>>) -> Self {
+        let insiders = InsiderDetector::from_env(config.app_state.rpc_nonblocking_client.clone());
+        let wallet_ages = WalletAgeChecker::from_env(config.app_state.rpc_nonblocking_client.clone());
+        let auditor = TokenAuditor::from_rpc(config.app_state.rpc_nonblocking_client.clone());
+        let feedback = AlertFeedback::from_env();
+        let signals = SignalBus::new();
+        let clock = Arc::new(EventClock::new(system_clock()));
+        if let Some(telegram) = &telegram {
+            signals.subscribe(Arc::new(TelegramSignals::new(telegram.clone())));
+        }
+        if let Some(recorder) = SignalRecorder::from_env() {
+            signals.subscribe(Arc::new(recorder));
+        }
+        let timeline = TokenTimeline::shared();
+        signals.subscribe(timeline.clone());
+        let confirmation = SignalConfirmation::shared();
+        if confirmation.config().enabled() {
+            signals.subscribe(confirmation);
+        }
         Self {
             config,
             telegram,
+            signals,
+            timeline,
             tracked_tokens: Arc::new(RwLock::new(HashMap::new())),
             tracked_wallets: Arc::new(RwLock::new(HashMap::new())),
+            thresholds: Arc::new(RwLock::new(MonitorThresholds::with_feedback(&feedback))),
+            alert_snapshots: Arc::new(RwLock::new(HashMap::new())),
+            watching: Arc::new(RwLock::new(HashSet::new())),
+            signal_outcomes: Arc::new(RwLock::new(Vec::new())),
+            liquidity_depth: Arc::new(RwLock::new(LiquidityDepth::new(LiquidityDepthConfig::from_env()))),
+            spreads: Arc::new(RwLock::new(SpreadTracker::new(SpreadConfig::from_env()))),
+            launches: Arc::new(RwLock::new(LaunchRecorder::new(LaunchReplayConfig::from_env()))),
+            launch_stats: Arc::new(RwLock::new(LaunchStats::new(LaunchReplayConfig::from_env().dead_drop_pct))),
+            insiders: Arc::new(insiders),
+            wallet_ages: Arc::new(wallet_ages),
+            first_buyers: Arc::new(FirstBuyers::from_env()),
+            deployers: Arc::new(DeployerHistory::from_env()),
+            auditor: Arc::new(auditor),
+            wallet_groups: Arc::new(WalletGroups::from_env()),
+            consensus: Arc::new(GroupConsensus::from_env()),
+            alert_filter: Arc::new(ScriptHook::from_env("ALERT_FILTER_SCRIPT")),
+            trending: Arc::new(RwLock::new(TrendingTracker::new(TrendingConfig::from_env()))),
+            trade_sizes: Arc::new(RwLock::new(RollingStats::new(chrono::Duration::hours(24)).with_capacity(TRADE_SIZES_PER_TOKEN))),
+            volumes: Arc::new(RwLock::new(HashMap::new())),
+            positions: Arc::new(RwLock::new(WalletPositions::new())),
+            breakouts: Arc::new(RwLock::new(BreakoutDetector::new(BreakoutConfig::from_env()))),
+            feedback: Arc::new(feedback),
+            adaptive: Arc::new(AdaptiveThresholds::from_env().with_clock(clock.clone())),
+            memory_budget: MemoryBudget::from_env(),
+            evicted_tokens: Arc::new(AtomicUsize::new(0)),
+            clock,
+        }
+    }
+
+    /// The bus detections are published on; subscribe to act on them
+    pub fn signals(&self) -> &SignalBus {
+        &self.signals
+    }
+
+    /// Use different liquidity depth thresholds than the environment's
+    pub fn with_liquidity_depth(mut self, config: LiquidityDepthConfig) -> Self {
+        self.liquidity_depth = Arc::new(RwLock::new(LiquidityDepth::new(config)));
+        self
+    }
+
+    /// Use different cross-venue spread thresholds than the environment's
+    pub fn with_spread_config(mut self, config: SpreadConfig) -> Self {
+        self.spreads = Arc::new(RwLock::new(SpreadTracker::new(config)));
+        self
+    }
+
+    /// Use different launch replay settings than the environment's
+    pub fn with_launch_replay(mut self, config: LaunchReplayConfig) -> Self {
+        self.launches = Arc::new(RwLock::new(LaunchRecorder::new(config)));
+        self
+    }
+
+    /// Use a different insider detector, e.g. one with a mock funding lookup
+    pub fn with_insider_detector(mut self, detector: InsiderDetector) -> Self {
+        self.insiders = Arc::new(detector);
+        self
+    }
+
+    /// Use a different buyer wallet age checker, e.g. one with a mock origin lookup
+    pub fn with_wallet_age_checker(mut self, checker: WalletAgeChecker) -> Self {
+        self.wallet_ages = Arc::new(checker);
+        self
+    }
+
+    /// Use a different first-buyers store, e.g. one that is not written to disk
+    pub fn with_first_buyers(mut self, first_buyers: FirstBuyers) -> Self {
+        self.first_buyers = Arc::new(first_buyers);
+        self
+    }
+
+    /// Filter alerts with this script instead of ALERT_FILTER_SCRIPT
+    pub fn with_alert_filter(mut self, filter: ScriptHook) -> Self {
+        self.alert_filter = Arc::new(filter);
+        self
+    }
+
+    /// Use a different deployer history, e.g. one in a temporary file
+    pub fn with_deployer_history(mut self, deployers: DeployerHistory) -> Self {
+        self.deployers = Arc::new(deployers);
+        self
+    }
+
+    /// Use a different token auditor, e.g. one reading a mock chain
+    pub fn with_auditor(mut self, auditor: TokenAuditor) -> Self {
+        self.auditor = Arc::new(auditor);
+        self
+    }
+
+    /// Use different wallet groups, e.g. ones that are not written to disk
+    pub fn with_wallet_groups(mut self, groups: WalletGroups) -> Self {
+        self.wallet_groups = Arc::new(groups);
+        self
+    }
+
+    /// Use different group consensus rules than GROUP_CONSENSUS_PATH's
+    pub fn with_group_consensus(mut self, consensus: GroupConsensus) -> Self {
+        self.consensus = Arc::new(consensus);
+        self
+    }
+
+    /// Use different trending settings than the environment's
+    pub fn with_trending(mut self, config: TrendingConfig) -> Self {
+        self.trending = Arc::new(RwLock::new(TrendingTracker::new(config)));
+        self
+    }
+
+    /// Use different alert feedback (e.g. unpersisted), resetting the thresholds to the defaults
+    /// plus its raises
+    pub fn with_alert_feedback(mut self, feedback: AlertFeedback) -> Self {
+        self.thresholds = Arc::new(RwLock::new(MonitorThresholds::with_feedback(&feedback)));
+        self.feedback = Arc::new(feedback);
+        self
+    }
+
+    /// Use a different memory budget than the environment's
+    pub fn with_memory_budget(mut self, budget: MemoryBudget) -> Self {
+        self.memory_budget = budget;
+        self
+    }
+
+    /// Latest cross-venue spread of a token, None while it trades on a single venue
+    pub async fn get_spread(&self, token_address: &Pubkey) -> Option<Spread> {
+        self.spreads.read().await.spread(token_address)
+    }
+
+    /// Use a different time source, e.g. a simulated clock for backtests; event time runs behind it
+    /// by the lag of the trades processed
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Arc::new(EventClock::new(clock));
+        self.adaptive = Arc::new(AdaptiveThresholds::new(self.adaptive.config().clone()).with_clock(self.clock.clone()));
+        self
+    }
+
+    /// Shared handle to the pattern detection thresholds
+    pub fn thresholds(&self) -> Arc<RwLock<MonitorThresholds>> {
+        self.thresholds.clone()
+    }
+
+    /// A wallet's position in a token as far as the monitor has seen it
+    pub async fn get_wallet_position(&self, wallet: &Pubkey, token_address: &Pubkey) -> Option<WalletPosition> {
+        self.positions.read().await.get(wallet, token_address).cloned()
+    }
+
+    /// Snapshot of a tracked token's metrics
+    pub async fn get_token(&self, token_address: &Pubkey) -> Option<TokenMetrics> {
+        self.tracked_tokens.read().await.get(token_address).cloned()
+    }
+
+    /// Count a user's 👍/👎 on an alert; when the signal's recent ratings are mostly noise its
+    /// threshold is raised
+    pub async fn rate_alert(&self, user: u64, rating: &Rating) -> FeedbackOutcome {
+        let category = self.tracked_tokens.read().await.get(&rating.mint).map(token_category).unwrap_or("untracked");
+        let outcome = self.feedback.record(user, rating, category);
+        if let FeedbackOutcome::Raised { factor, raises } = outcome {
+            let mut thresholds = self.thresholds.write().await;
+            thresholds.raise_audited(rating.signal_type, factor, "alert_feedback");
+            println!(
+                "📉 {} alerts were mostly rated noise: threshold raised ({}x so far), now {:?}",
+                rating.signal_type, raises, *thresholds
+            );
+        }
+        outcome
+    }
+
+    /// Ratings per signal type and category, for `/feedback`
+    pub fn feedback_summary(&self) -> String {
+        self.feedback.summary()
+    }
+
+    /// Re-rank trending tokens from the trades of the last windows; returns the top list
+    pub async fn refresh_trending(&self) -> Vec<TrendingToken> {
+        let mut trending = self.trending.write().await;
+        let top_n = trending.config().top_n;
+        trending.refresh(self.clock.now());
+        let top = trending.top(top_n);
+        let confirmation = SignalConfirmation::shared();
+        for token in &top {
+            confirmation.record(&token.mint.to_string(), signal_confirmation::TRENDING);
+        }
+        top
+    }
+
+    /// Best trending tokens of the last refresh, `TRENDING_TOP_N` when `limit` is None
+    pub async fn trending(&self, limit: Option<usize>) -> Vec<TrendingToken> {
+        let trending = self.trending.read().await;
+        trending.top(limit.unwrap_or(trending.config().top_n))
+    }
+
+    /// Snapshot of all tracked tokens, watchlist (pinned) tokens first
+    pub async fn list_tokens(&self) -> Vec<TokenMetrics> {
+        let mut tokens: Vec<TokenMetrics> = self.tracked_tokens.read().await.values().cloned().collect();
+        tokens.sort_by(|a, b| b.pinned.cmp(&a.pinned).then(a.first_seen.cmp(&b.first_seen)));
+        tokens
+    }
+
+    /// Pin watchlist tokens so they are tracked regardless of observed activity. Their pools are
+    /// polled for liquidity from now on, so a token nobody trades still gets updates.
+    /// Returns how many were newly added (already tracked tokens are just pinned).
+    pub async fn import_watchlist(&self, entries: Vec<(Pubkey, WatchlistEntry)>) -> usize {
+        let mut tokens = self.tracked_tokens.write().await;
+        let mut depth = self.liquidity_depth.write().await;
+        let mut added = 0;
+        for (mint, entry) in entries {
+            depth.pin_pool(mint, self.clock.now());
+            let metrics = tokens.entry(mint).or_insert_with(|| {
+                added += 1;
+                TokenMetrics {
+                    address: mint,
+                    name: None,
+                    symbol: None,
+                    initial_price: None,
+                    current_price: None,
+                    volume_24h: 0.0,
+                    buy_volume_24h: 0.0,
+                    sell_volume_24h: 0.0,
+                    volume_1h: 0.0,
+                    total_volume: 0.0,
+                    liquidity: 0.0,
+                    holder_count: 0,
+                    first_seen: self.clock.now(),
+                    last_updated: self.clock.now(),
+                    buy_count: 0,
+                    sell_count: 0,
+                    largest_buy_sol: 0.0,
+                    largest_sell_sol: 0.0,
+                    bonding_curve_progress: None,
+                    graduation_alerted: false,
+                    pinned: true,
+                    fresh_wallet_pct: None,
+                }
+            });
+            metrics.pinned = true;
+            if metrics.name.is_none() {
+                metrics.name = entry.name;
+            }
+            if metrics.symbol.is_none() {
+                metrics.symbol = entry.symbol;
+            }
+        }
+        added
+    }
+
+    /// Mark a token as watched after the user acknowledged an alert: follow-ups are sent at each
+    /// FOLLOW_UP_INTERVALS_MIN offset (default +15m, +1h, +4h) after the alert, comparing the token
+    /// with its state at that time. Returns false if the token is unknown or already watched.
+    pub async fn watch_token(&self, mint: Pubkey) -> bool {
+        let baseline = self.alert_snapshots.read().await.get(&mint).cloned();
+        let baseline = match baseline {
+            Some(snapshot) => snapshot,
+            None => match self.tracked_tokens.read().await.get(&mint) {
+                Some(metrics) => AlertSnapshot::of(metrics, self.clock.now()),
+                None => return false,
+            },
+        };
+        if !self.watching.write().await.insert(mint) {
+            return false;
+        }
+
+        let tokens = self.tracked_tokens.clone();
+        let watching = self.watching.clone();
+        let telegram = self.telegram.clone();
+        let clock = self.clock.clone();
+        tokio::spawn(async move {
+            for minutes in follow_up_intervals() {
+                let due = baseline.at + chrono::Duration::minutes(minutes);
+                tokio::time::sleep((due - clock.now()).to_std().unwrap_or_default()).await;
+
+                let current = tokens.read().await.get(&mint).cloned();
+                if let (Some(current), Some(telegram)) = (current, &telegram) {
+                    let summary = format_follow_up(&baseline, &current);
+                    if let Err(e) = telegram.send_follow_up(&mint, current.name.clone(), &format_elapsed(minutes), &summary).await {
+                        eprintln!("Failed to send follow-up for {}: {}", mint, e);
+                    }
+                }
+            }
+            watching.write().await.remove(&mint);
+        });
+        true
+    }
+
+    async fn record_alert_snapshot(&self, metrics: &TokenMetrics) {
+        self.alert_snapshots.write().await.insert(metrics.address, AlertSnapshot::of(metrics, self.clock.now()));
+    }
+
+    /// Record an opportunity alert and measure the token's price at each SIGNAL_OUTCOME_HORIZONS_MIN
+    /// offset (default +5m, +30m, +2h) to score whether acting on it would have been profitable.
+    /// A signal that keeps firing for the same token is only recorded once per measurement window.
+    async fn record_signal(&self, signal_type: &str, direction: SignalDirection, confidence: Option<f64>, metrics: &TokenMetrics) {
+        self.adapt_threshold(signal_type).await;
+        let price = match metrics.current_price {
+            Some(price) if price > 0.0 => price,
+            _ => return,
+        };
+        let window = chrono::Duration::minutes(outcome_horizons().into_iter().max().unwrap_or(0));
+
+        let now = self.clock.now();
+        let outcome = SignalOutcome::new(signal_type, metrics.address, direction, price, now).with_confidence(confidence);
+        {
+            let mut outcomes = self.signal_outcomes.write().await;
+            let pending = outcomes.iter().any(|o| {
+                o.mint == metrics.address && o.signal_type == signal_type && now - o.alerted_at < window
+            });
+            if pending {
+                return;
+            }
+            // Keep a little over the weekly report period
+            let cutoff = now - chrono::Duration::days(8);
+            outcomes.retain(|o| o.alerted_at >= cutoff);
+            outcomes.push(outcome.clone());
+        }
+
+        let tokens = self.tracked_tokens.clone();
+        let outcomes = self.signal_outcomes.clone();
+        let clock = self.clock.clone();
+        tokio::spawn(async move {
+            for (minutes, _) in &outcome.checkpoints {
+                let due = outcome.alerted_at + chrono::Duration::minutes(*minutes);
+                tokio::time::sleep((due - clock.now()).to_std().unwrap_or_default()).await;
+
+                let price = tokens.read().await.get(&outcome.mint).and_then(|t| t.current_price);
+                let mut outcomes = outcomes.write().await;
+                let recorded = outcomes.iter_mut().find(|o| {
+                    o.mint == outcome.mint && o.signal_type == outcome.signal_type && o.alerted_at == outcome.alerted_at
+                });
+                match recorded {
+                    Some(recorded) => {
+                        if let Some(checkpoint) = recorded.checkpoints.iter_mut().find(|(m, _)| m == minutes) {
+                            checkpoint.1 = price;
+                        }
+                    }
+                    None => break,
+                }
+            }
+        });
+    }
+
+    /// Snapshot the token's metrics and score an opportunity before it is published
+    /// Count an alert towards its signal type's hourly rate, raising the threshold when the type
+    /// fires too often and restoring the ones whose raise has run out. The graduation threshold
+    /// is left alone: it fires once per token and is capped below 100% rather than scaled
+    async fn adapt_threshold(&self, signal_type: &str) {
+        let mut adjustments = self.adaptive.expire();
+        if signal_type != "Graduation" {
+            adjustments.extend(self.adaptive.record(signal_type));
+        }
+        if adjustments.is_empty() {
+            return;
+        }
+        let mut thresholds = self.thresholds.write().await;
+        for adjustment in adjustments {
+            thresholds.raise_audited(adjustment.signal_type(), adjustment.factor(), "adaptive_thresholds");
+            println!("🎚️ {}", adjustment.describe());
         }
     }
 
+    async fn record_opportunity(&self, signal: &OpportunitySignal, metrics: &TokenMetrics) {
+        self.record_alert_snapshot(metrics).await;
+        self.record_signal(signal.kind.label(), signal.direction, Some(signal.confidence), metrics).await;
+    }
+
+    fn metrics_snapshot(&self, metrics: &TokenMetrics) -> MetricsSnapshot {
+        MetricsSnapshot {
+            price: metrics.current_price,
+            liquidity: metrics.liquidity,
+            volume_1h: metrics.volume_1h,
+            volume_24h: metrics.volume_24h,
+            buy_count: metrics.buy_count,
+            sell_count: metrics.sell_count,
+            bonding_curve_progress: metrics.bonding_curve_progress,
+            fresh_wallet_pct: metrics.fresh_wallet_pct,
+            age_minutes: (self.clock.now() - metrics.first_seen).num_minutes(),
+        }
+    }
+
+    /// Entry counts and estimated footprint of the monitor's own maps
+    pub async fn memory_components(&self) -> Vec<MemoryComponent> {
+        vec![
+            MemoryComponent::new("tracked tokens", self.tracked_tokens.read().await.len(), 512),
+            MemoryComponent::new("tracked wallets", self.tracked_wallets.read().await.len(), 384),
+            MemoryComponent::new("alert snapshots", self.alert_snapshots.read().await.len(), 96),
+            MemoryComponent::new("signal outcomes", self.signal_outcomes.read().await.len(), 192),
+            MemoryComponent::new("liquidity pools", self.liquidity_depth.read().await.pool_count(), 2 * 1024),
+            MemoryComponent::new("venue prices", self.spreads.read().await.token_count(), 384),
+            MemoryComponent::new("launch sessions", self.launches.read().await.session_count(), 16 * 1024),
+            MemoryComponent::new("launch records", self.launch_stats.read().await.len(), 96),
+            MemoryComponent::new("insider launches", self.insiders.launch_count().await, 256),
+            MemoryComponent::new("funding sources", self.insiders.funding_cache_size(), 96),
+            MemoryComponent::new("buyer ages", self.wallet_ages.token_count().await, 2 * 1024),
+            MemoryComponent::new("wallet origins", self.wallet_ages.origin_cache_size(), 112),
+            MemoryComponent::new("first buyer snapshots", self.first_buyers.len(), 2 * 1024),
+            MemoryComponent::new("open deployer launches", self.deployers.open_count(), 160),
+            MemoryComponent::new("group consensus windows", self.consensus.len(), 256),
+            MemoryComponent::new("token timelines", self.timeline.len(), 4 * 1024),
+            MemoryComponent::new("trending activity", self.trending.read().await.token_count(), 4 * 1024),
+            MemoryComponent::new("trade size windows", self.trade_sizes.read().await.len(), 16 * 1024),
+            MemoryComponent::new("volume buckets", self.volumes.read().await.len(), 8 * 1024),
+            MemoryComponent::new("wallet positions", self.positions.read().await.len(), 128),
+            MemoryComponent::new("breakout candles", self.breakouts.read().await.token_count(), 2 * 1024),
+        ]
+    }
+
+    async fn estimated_memory(&self) -> (Vec<MemoryComponent>, usize) {
+        let mut components = self.memory_components().await;
+        components.extend(memory_budget::global_components());
+        let total = components.iter().map(|c| c.approx_bytes).sum();
+        (components, total)
+    }
+
+    /// When the estimated footprint is over the memory budget, evict the least recently traded
+    /// tokens (watchlist, watched and tokens with pending signal outcomes are kept) together with
+    /// their per-token state, down to the eviction target. Returns how many tokens were evicted.
+    pub async fn enforce_memory_budget(&self) -> usize {
+        let (_, total) = self.estimated_memory().await;
+        if !self.memory_budget.is_exceeded(total) {
+            return 0;
+        }
+
+        let mut protected: HashSet<Pubkey> = self.watching.read().await.clone();
+        protected.extend(
+            self.signal_outcomes.read().await.iter()
+                .filter(|o| o.checkpoints.iter().any(|(_, price)| price.is_none()))
+                .map(|o| o.mint),
+        );
+
+        let evicted = {
+            let mut tokens = self.tracked_tokens.write().await;
+            let mut candidates: Vec<(Pubkey, DateTime<Utc>)> = tokens.values()
+                .filter(|t| !t.pinned && !protected.contains(&t.address))
+                .map(|t| (t.address, t.last_updated))
+                .collect();
+            candidates.sort_by_key(|(_, last_updated)| *last_updated);
+
+            // Each evicted token frees roughly its own entry plus the per-token state around it
+            let per_token: usize = 512 + 96 + 2 * 1024 + 384 + 8 * 1024 + 16 * 1024 + 4 * 1024 + 2 * 1024;
+            let excess = total.saturating_sub(self.memory_budget.target_bytes());
+            let count = excess.div_ceil(per_token).min(candidates.len());
+            for (mint, _) in candidates.iter().take(count) {
+                tokens.remove(mint);
+            }
+            count
+        };
+
+        let tokens: HashSet<Pubkey> = self.tracked_tokens.read().await.keys().copied().collect();
+        self.alert_snapshots.write().await.retain(|mint, _| tokens.contains(mint));
+        self.liquidity_depth.write().await.retain(|mint| tokens.contains(mint));
+        self.spreads.write().await.retain(|mint| tokens.contains(mint));
+        self.launches.write().await.retain(|mint| tokens.contains(mint));
+        self.deployers.retain(|mint| tokens.contains(mint));
+        self.consensus.prune();
+        self.insiders.retain(|mint| tokens.contains(mint)).await;
+        self.wallet_ages.retain(|mint| tokens.contains(mint)).await;
+        self.trending.write().await.retain(|mint| tokens.contains(mint));
+        self.trade_sizes.write().await.retain(|mint| tokens.contains(mint));
+        self.volumes.write().await.retain(|mint, _| tokens.contains(mint));
+        self.positions.write().await.retain(|mint| tokens.contains(mint));
+        self.breakouts.write().await.retain(|mint| tokens.contains(mint));
+        {
+            let mut wallets = self.tracked_wallets.write().await;
+            for wallet in wallets.values_mut() {
+                wallet.tokens_traded.retain(|mint| tokens.contains(mint));
+            }
+        }
+        let names: HashSet<String> = tokens.iter().map(|mint| mint.to_string()).collect();
+        memory_budget::evict_global(|mint| names.contains(mint));
+
+        self.evicted_tokens.fetch_add(evicted, Ordering::Relaxed);
+        evicted
+    }
+
+    /// Snapshot of all tracked wallets, most active first
+    pub async fn list_wallets(&self) -> Vec<WalletMetrics> {
+        let mut wallets: Vec<WalletMetrics> = self.tracked_wallets.read().await.values().cloned().collect();
+        wallets.sort_by(|a, b| (b.total_buys + b.total_sells).cmp(&(a.total_buys + a.total_sells)));
+        wallets
+    }
+
+    /// Send a replay of every launch that graduated or died since the last check. Returns how
+    /// many were reported.
+    pub async fn report_finished_launches(&self) -> usize {
+        let pull_pct = self.thresholds.read().await.liquidity_removal_pct;
+        let finished = self.launches.write().await.take_finished(pull_pct, self.clock.now());
+        self.launch_stats.write().await.prune(self.clock.now());
+        for (session, outcome) in &finished {
+            self.deployers.record_outcome(session, outcome, self.clock.now());
+            let timeline = format_timeline(session, outcome);
+            self.log_educational_analysis("LAUNCH_REPLAY", &timeline);
+            self.signals.publish(Signal::LaunchReplay {
+                mint: session.mint,
+                token_name: session.name.clone(),
+                timeline,
+            }).await;
+        }
+        finished.len()
+    }
+
     /// Process parsed data for educational monitoring (no trading)
     pub async fn process_for_education(&self, parsed_data: &ParsedData) -> Result<()> {
+        self.clock.observe(parsed_data.event_time());
+        self.liquidity_depth.write().await.observe_pool(
+            parsed_data.token_mint,
+            DexType::from_name(&parsed_data.dex_name),
+            parse_pool_id(parsed_data.pool_id.as_deref()),
+            self.clock.now(),
+        );
+        self.launches.write().await.record(parsed_data, self.clock.now());
+        self.launch_stats.write().await.record(parsed_data, self.clock.now());
+        self.deployers.record_launch(parsed_data, self.clock.now());
+        self.trending.write().await.record(parsed_data, self.clock.now());
+        let whale_sol = self.thresholds.read().await.whale_sol;
+        self.timeline.observe_trade(parsed_data, whale_sol);
+
+        let alerts = self.alert_filter.alert_allowed(parsed_data);
         match parsed_data.swap_type {
             SwapType::Buy => {
-                self.handle_buy_signal(parsed_data).await?;
+                self.check_insider_buy(parsed_data, alerts).await?;
+                self.handle_buy_signal(parsed_data, alerts).await?;
             },
             SwapType::Sell => {
-                self.handle_sell_signal(parsed_data).await?;
+                self.handle_sell_signal(parsed_data, alerts).await?;
+            },
+            SwapType::AddLiquidity | SwapType::RemoveLiquidity | SwapType::PoolCreate => {
+                // Liquidity events update pool liquidity and price but are not trades
+                self.handle_liquidity_event(parsed_data).await?;
+                self.update_token_metrics(parsed_data).await?;
+                return Ok(());
             },
-            _ => {}
         }
 
         // Update metrics
         self.update_token_metrics(parsed_data).await?;
         self.update_wallet_metrics(parsed_data).await?;
+        self.check_buyer_age(parsed_data).await;
+        self.record_first_buyer(parsed_data).await;
+        let group_activity = self.wallet_groups.observe(parsed_data);
+        let consensus = self.consensus.observe(parsed_data, &self.wallet_groups);
+        if !alerts {
+            return Ok(());
+        }
+
+        if let Some(activity) = group_activity {
+            self.signals.publish(Signal::GroupBuy(activity)).await;
+        }
+        self.check_group_consensus(parsed_data, consensus).await;
 
         // Check for patterns
         self.detect_patterns(parsed_data).await?;
+        self.check_price_impact(parsed_data).await?;
+        self.check_cross_venue_spread(parsed_data).await?;
+
+        Ok(())
+    }
+
+    /// Tag the launch and alert when an early buyer shares the creator's funding
+    async fn check_insider_buy(&self, parsed_data: &ParsedData, alerts: bool) -> Result<()> {
+        let finding = match self.insiders.observe(parsed_data).await {
+            Some(finding) => finding,
+            None => return Ok(()),
+        };
+        let token_name = match &parsed_data.token_name {
+            Some(name) => Some(name.clone()),
+            None => self.tracked_tokens.read().await.get(&parsed_data.token_mint).and_then(|t| t.name.clone()),
+        };
+        if alerts {
+            self.signals.publish(Signal::InsiderBuy { finding, token_name }).await;
+        }
+        Ok(())
+    }
+
+    /// Raise each group consensus the trade completed as a scored opportunity
+    async fn check_group_consensus(&self, parsed_data: &ParsedData, hits: Vec<ConsensusHit>) {
+        if hits.is_empty() {
+            return;
+        }
+        let mut signals = Vec::new();
+        {
+            let tokens = self.tracked_tokens.read().await;
+            for hit in hits {
+                let signal = match tokens.get(&parsed_data.token_mint) {
+                    Some(metrics) => {
+                        let signal = hit.to_opportunity(metrics.name.clone(), self.metrics_snapshot(metrics));
+                        self.record_opportunity(&signal, metrics).await;
+                        signal
+                    }
+                    None => hit.to_opportunity(parsed_data.token_name.clone(), MetricsSnapshot::default()),
+                };
+                signals.push(Signal::Opportunity(signal));
+            }
+        }
+        self.signals.publish_all(signals).await;
+    }
+
+    /// Classify a new buyer's wallet as fresh or aged and refresh the token's fresh-wallet share
+    async fn check_buyer_age(&self, parsed_data: &ParsedData) {
+        let stats = match self.wallet_ages.observe(parsed_data).await {
+            Some(stats) => stats,
+            None => return,
+        };
+        if let Some(metrics) = self.tracked_tokens.write().await.get_mut(&parsed_data.token_mint) {
+            metrics.fresh_wallet_pct = stats.fresh_pct();
+        }
+    }
+
+    /// Add a launch's early buyer to its first-buyers snapshot, with the wallet's age
+    async fn record_first_buyer(&self, parsed_data: &ParsedData) {
+        if !self.first_buyers.record(parsed_data) {
+            return;
+        }
+        let origin = self.wallet_ages.lookup(&parsed_data.signer).await;
+        if let Some(snapshot) = self.first_buyers.set_origin(&parsed_data.token_mint, &parsed_data.signer, origin) {
+            self.log_educational_analysis(
+                "FIRST_BUYERS",
+                &format_first_buyers(&snapshot, self.first_buyers.fresh_max_age_secs()),
+            );
+        }
+    }
+
+    /// First buyers recorded for a launch, for `/firstbuyers`
+    pub fn first_buyers(&self, mint: &Pubkey) -> Option<String> {
+        self.first_buyers.get(mint)
+            .map(|snapshot| format_first_buyers(&snapshot, self.first_buyers.fresh_max_age_secs()))
+    }
+
+    /// Compact metrics card of a tracked token, for `/stats`
+    pub async fn stats(&self, mint: &Pubkey) -> Option<String> {
+        let metrics = self.get_token(mint).await?;
+        let now = self.clock.now();
+        let mint_text = mint.to_string();
+        let change = |price: Option<f64>, since: Option<f64>| match (price, since) {
+            (Some(price), Some(since)) if since > 0.0 => format!("{:+.1}%", (price / since - 1.0) * 100.0),
+            _ => "n/a".to_string(),
+        };
+        let hour_ago = PriceHistory::shared()
+            .points(&mint_text, (now - chrono::Duration::hours(1)).timestamp_millis(), now.timestamp_millis())
+            .first()
+            .map(|point| point.price);
+        let age = now - metrics.first_seen;
+        let mut card = format!(
+            "📊 {} ({})\n\
+            💰 Price: {} | 1h {} | since first seen {}\n\
+            📈 Volume: 1h {:.2} SOL | 24h {:.2} SOL ({:.2} buy / {:.2} sell)\n\
+            🔁 Trades: {} buys / {} sells | largest buy {:.2} SOL\n\
+            💧 Liquidity: {:.2} SOL\n",
+            metrics.name.as_deref().unwrap_or("Unknown"),
+            mint_text,
+            metrics.current_price.map(|p| format!("{:.10}", p)).unwrap_or_else(|| "n/a".to_string()),
+            change(metrics.current_price, hour_ago),
+            change(metrics.current_price, metrics.initial_price),
+            metrics.volume_1h,
+            metrics.volume_24h,
+            metrics.buy_volume_24h,
+            metrics.sell_volume_24h,
+            metrics.buy_count,
+            metrics.sell_count,
+            metrics.largest_buy_sol,
+            metrics.liquidity,
+        );
+        if let Some(progress) = metrics.bonding_curve_progress {
+            card.push_str(&format!("🎓 Bonding curve: {:.1}%\n", progress));
+        }
+        if let Some(fresh) = metrics.fresh_wallet_pct {
+            card.push_str(&format!("🆕 Fresh buyers: {:.0}%\n", fresh));
+        }
+        card.push_str(&format!(
+            "⚠️ Risk score: {:.0}/100\n⏱️ Tracked {}h {}m, last trade {}s ago",
+            risk_score::risk_score(&mint_text),
+            age.num_hours(),
+            age.num_minutes() % 60,
+            (now - metrics.last_updated).num_seconds().max(0),
+        ));
+        Some(card)
+    }
+
+    /// Notable events of a token, for `/timeline`
+    pub fn timeline(&self, mint: &Pubkey) -> String {
+        format_token_timeline(mint, &self.timeline.events(mint))
+    }
+
+    /// Every recorded launch of a creator with its outcome, for `/deployer`
+    pub fn deployer(&self, creator: &Pubkey) -> String {
+        let creator = creator.to_string();
+        format_deployer(&creator, &self.deployers.launches_of(&creator))
+    }
 
+    /// Track imported wallets under their labels and groups; returns (added, updated)
+    pub fn import_wallets(&self, parsed: ParsedWallets) -> (usize, usize) {
+        self.wallet_groups.import(parsed.wallets)
+    }
+
+    /// Tracked wallets per group, for `/wallets`
+    pub fn wallet_groups(&self) -> String {
+        format_wallet_groups(&self.wallet_groups.groups())
+    }
+
+    /// Full risk audit of any mint, tracked or not, for `/audit`
+    pub async fn audit(&self, mint: &Pubkey) -> String {
+        match self.auditor.audit(mint, &self.deployers).await {
+            Ok(report) => format_audit(&report),
+            Err(e) => format!("❌ Audit of {} failed: {}", mint, e),
+        }
+    }
+
+    /// Track the trade's price against the token's other venues and alert on a wide spread
+    async fn check_cross_venue_spread(&self, parsed_data: &ParsedData) -> Result<()> {
+        let price = match parsed_data.token_price {
+            Some(price) => price,
+            None => return Ok(()),
+        };
+        let venue = venue_label(&parsed_data.dex_name, parsed_data.pool_id.as_deref());
+        let spread = self.spreads.write().await.record(parsed_data.token_mint, venue, price, self.clock.now());
+        let spread = match spread {
+            Some(spread) => spread,
+            None => return Ok(()),
+        };
+
+        let token_name = match &parsed_data.token_name {
+            Some(name) => Some(name.clone()),
+            None => self.tracked_tokens.read().await.get(&parsed_data.token_mint).and_then(|t| t.name.clone()),
+        };
+        self.signals.publish(Signal::ArbitrageSpread {
+            mint: parsed_data.token_mint,
+            token_name,
+            spread,
+        }).await;
         Ok(())
     }
 
+    /// Alert when a trade moves the price more than PRICE_IMPACT_ALERT_PCT, estimated against the
+    /// last polled reserves (or the pool liquidity reported with the trade)
+    async fn check_price_impact(&self, parsed_data: &ParsedData) -> Result<()> {
+        let (latest, threshold) = {
+            let depth = self.liquidity_depth.read().await;
+            (depth.latest(&parsed_data.token_mint), depth.config().price_impact_alert_pct)
+        };
+        let pool_sol = match latest.map(|r| r.sol).or(parsed_data.liquidity).filter(|sol| *sol > 0.0) {
+            Some(pool_sol) => pool_sol,
+            None => return Ok(()),
+        };
+        let amount_sol = parsed_data.sol_amount.unwrap_or(0.0);
+        let (direction, action) = match parsed_data.swap_type {
+            SwapType::Buy => (SwapDirection::Buy, "BUY"),
+            SwapType::Sell => (SwapDirection::Sell, "SELL"),
+            _ => return Ok(()),
+        };
+        let impact = price_impact_pct(pool_sol, amount_sol, direction);
+        if impact < threshold {
+            return Ok(());
+        }
+
+        self.signals.publish(Signal::PriceImpact {
+            wallet: parsed_data.signer,
+            action,
+            mint: parsed_data.token_mint,
+            token_name: parsed_data.token_name.clone(),
+            amount_sol,
+            pool_sol,
+            impact_pct: impact,
+            signature: Some(parsed_data.signature.clone()).filter(|sig| !sig.is_empty()),
+        }).await;
+        Ok(())
+    }
+
+    /// Read the reserves of the most recently traded pools, refresh token liquidity and alert on
+    /// sharp drops
+    pub async fn poll_liquidity(&self, reader: &dyn ReserveReader) {
+        let pools = {
+            let tokens = self.tracked_tokens.read().await;
+            let mut depth = self.liquidity_depth.write().await;
+            depth.retain(|mint| tokens.contains_key(mint));
+            depth.pools_to_poll()
+        };
+
+        // Read concurrently so the account reads of one round share a batch
+        let reads = futures::future::join_all(pools.iter().map(|pool| reader.read(pool))).await;
+        for (pool, read) in pools.into_iter().zip(reads) {
+            let reserves = match read {
+                Ok(Some(reserves)) => reserves,
+                Ok(None) => continue,
+                Err(e) => {
+                    eprintln!("Failed to read reserves for {}: {}", pool.mint, e);
+                    continue;
+                }
+            };
+            let now = self.clock.now();
+            let (drop, curve, window) = {
+                let mut depth = self.liquidity_depth.write().await;
+                let drop = depth.record(pool.mint, reserves, now);
+                (drop, depth.curve(reserves.sol), depth.config().drop_window)
+            };
+
+            let token_name = {
+                let mut tokens = self.tracked_tokens.write().await;
+                tokens.get_mut(&pool.mint).and_then(|metrics| {
+                    metrics.liquidity = reserves.sol;
+                    metrics.name.clone()
+                })
+            };
+
+            if let Some(drop) = drop {
+                self.signals.publish(Signal::LiquidityDrop {
+                    mint: pool.mint,
+                    token_name,
+                    drop,
+                    window_minutes: window.num_minutes(),
+                    depth_curve: format_depth_curve(&curve),
+                }).await;
+            }
+        }
+    }
+
     /// Handle buy signals for educational purposes
-    async fn handle_buy_signal(&self, parsed_data: &ParsedData) -> Result<()> {
+    async fn handle_buy_signal(&self, parsed_data: &ParsedData, alerts: bool) -> Result<()> {
         let token_address = parsed_data.token_mint;
         let wallet_address = parsed_data.signer;
         let amount_sol = parsed_data.sol_amount.unwrap_or(0.0);
+        let entry = self.positions.write().await.record(parsed_data);
 
-        // Send Telegram alert if configured
-        if let Some(telegram) = &self.telegram {
-            telegram.alert_wallet_activity(
-                &wallet_address,
-                "BUY",
-                &token_address,
-                parsed_data.token_name.clone(),
+        if alerts {
+            self.signals.publish(Signal::WalletActivity {
+                wallet: wallet_address,
+                action: "BUY",
+                mint: token_address,
+                token_name: parsed_data.token_name.clone(),
                 amount_sol,
-                None,
-            ).await?;
+                entry,
+                bonding_curve_progress: parsed_data.bonding_curve_progress,
+                signature: Some(parsed_data.signature.clone()).filter(|sig| !sig.is_empty()),
+            }).await;
 
-            // Check if this is a new token
-            let tokens = self.tracked_tokens.read().await;
-            if !tokens.contains_key(&token_address) {
-                drop(tokens); // Release read lock
-
-                telegram.alert_new_token(
-                    &token_address,
-                    parsed_data.token_name.clone(),
-                    parsed_data.liquidity.unwrap_or(0.0),
-                    &parsed_data.dex_name,
-                ).await?;
+            // Not tracked yet: this is the token's first trade
+            let is_new = !self.tracked_tokens.read().await.contains_key(&token_address);
+            if is_new {
+                self.signals.publish(Signal::NewToken {
+                    mint: token_address,
+                    token_name: parsed_data.token_name.clone(),
+                    liquidity: parsed_data.liquidity.unwrap_or(0.0),
+                    dex: parsed_data.dex_name.clone(),
+                }).await;
             }
         }
 
@@ -137,21 +1204,23 @@ impl EducationalMonitor {
     }
 
     /// Handle sell signals for educational purposes
-    async fn handle_sell_signal(&self, parsed_data: &ParsedData) -> Result<()> {
+    async fn handle_sell_signal(&self, parsed_data: &ParsedData, alerts: bool) -> Result<()> {
         let token_address = parsed_data.token_mint;
         let wallet_address = parsed_data.signer;
         let amount_sol = parsed_data.sol_amount.unwrap_or(0.0);
+        let entry = self.positions.write().await.record(parsed_data);
 
-        // Send Telegram alert if configured
-        if let Some(telegram) = &self.telegram {
-            telegram.alert_wallet_activity(
-                &wallet_address,
-                "SELL",
-                &token_address,
-                parsed_data.token_name.clone(),
+        if alerts {
+            self.signals.publish(Signal::WalletActivity {
+                wallet: wallet_address,
+                action: "SELL",
+                mint: token_address,
+                token_name: parsed_data.token_name.clone(),
                 amount_sol,
-                None,
-            ).await?;
+                entry,
+                bonding_curve_progress: parsed_data.bonding_curve_progress,
+                signature: Some(parsed_data.signature.clone()).filter(|sig| !sig.is_empty()),
+            }).await;
         }
 
         // Calculate hypothetical PnL for educational purposes
@@ -164,6 +1233,7 @@ impl EducationalMonitor {
                 Token: {} ({})\n\
                 Wallet: {}\n\
                 Amount: {} SOL\n\
+                Vs Average Entry: {}\n\
                 Hypothetical PnL: {:.2}%\n\
                 Analysis: This sell signal could indicate:\n\
                 - Profit taking\n\
@@ -174,6 +1244,7 @@ impl EducationalMonitor {
                 token_address,
                 wallet_address,
                 amount_sol,
+                entry.map(|e| format!("{:.2}x", e.multiple())).unwrap_or_else(|| "unknown".to_string()),
                 hypothetical_pnl
             )
         );
@@ -183,8 +1254,14 @@ impl EducationalMonitor {
 
     /// Update token metrics for educational tracking
     async fn update_token_metrics(&self, parsed_data: &ParsedData) -> Result<()> {
+        let (price_change_threshold, graduation_threshold) = {
+            let thresholds = self.thresholds.read().await;
+            (thresholds.price_change_pct, thresholds.graduation_pct)
+        };
         let mut tokens = self.tracked_tokens.write().await;
         let token_address = parsed_data.token_mint;
+        // Published once the token lock is released
+        let mut signals = Vec::new();
 
         let metrics = tokens.entry(token_address).or_insert_with(|| {
             TokenMetrics {
@@ -194,58 +1271,111 @@ impl EducationalMonitor {
                 initial_price: parsed_data.token_price,
                 current_price: parsed_data.token_price,
                 volume_24h: 0.0,
+                buy_volume_24h: 0.0,
+                sell_volume_24h: 0.0,
+                volume_1h: 0.0,
+                total_volume: 0.0,
                 liquidity: parsed_data.liquidity.unwrap_or(0.0),
                 holder_count: 0,
-                first_seen: Utc::now(),
-                last_updated: Utc::now(),
+                first_seen: self.clock.now(),
+                last_updated: self.clock.now(),
                 buy_count: 0,
                 sell_count: 0,
                 largest_buy_sol: 0.0,
                 largest_sell_sol: 0.0,
+                bonding_curve_progress: None,
+                graduation_alerted: false,
+                pinned: false,
+                fresh_wallet_pct: None,
             }
         });
 
         // Update metrics based on swap type
+        let amount_sol = parsed_data.sol_amount.unwrap_or(0.0);
         match parsed_data.swap_type {
             SwapType::Buy => {
                 metrics.buy_count += 1;
-                let amount_sol = parsed_data.sol_amount.unwrap_or(0.0);
                 if amount_sol > metrics.largest_buy_sol {
                     metrics.largest_buy_sol = amount_sol;
                 }
-                metrics.volume_24h += amount_sol;
             },
             SwapType::Sell => {
                 metrics.sell_count += 1;
-                let amount_sol = parsed_data.sol_amount.unwrap_or(0.0);
                 if amount_sol > metrics.largest_sell_sol {
                     metrics.largest_sell_sol = amount_sol;
                 }
-                metrics.volume_24h += amount_sol;
             },
             _ => {}
         }
+        {
+            let now = self.clock.now();
+            let mut volumes = self.volumes.write().await;
+            let buckets = volumes.entry(token_address).or_insert_with(VolumeBuckets::daily);
+            if let SwapType::Buy | SwapType::Sell = parsed_data.swap_type {
+                let is_buy = matches!(parsed_data.swap_type, SwapType::Buy);
+                metrics.total_volume += amount_sol;
+                buckets.record(now, amount_sol, is_buy);
+                self.trade_sizes.write().await.push(token_address, now, amount_sol);
+            }
+            // Recomputed on every event so trades older than the window roll off
+            let day = buckets.totals(now, chrono::Duration::hours(24));
+            metrics.volume_24h = day.total();
+            metrics.buy_volume_24h = day.buy;
+            metrics.sell_volume_24h = day.sell;
+            metrics.volume_1h = buckets.totals(now, chrono::Duration::hours(1)).total();
+        }
 
         metrics.current_price = parsed_data.token_price;
+        if let Some(price) = parsed_data.token_price {
+            let is_buy = matches!(parsed_data.swap_type, SwapType::Buy);
+            PriceHistory::shared().record(
+                &token_address.to_string(),
+                self.clock.now(),
+                price,
+                is_buy,
+                parsed_data.sol_amount.unwrap_or(0.0),
+            );
+        }
         metrics.liquidity = parsed_data.liquidity.unwrap_or(metrics.liquidity);
-        metrics.last_updated = Utc::now();
+        metrics.last_updated = self.clock.now();
+        if parsed_data.bonding_curve_progress.is_some() {
+            metrics.bonding_curve_progress = parsed_data.bonding_curve_progress;
+        }
+
+        // Alert once when the bonding curve nears completion (migration to PumpSwap)
+        if let Some(progress) = metrics.bonding_curve_progress {
+            if progress >= graduation_threshold && !metrics.graduation_alerted {
+                metrics.graduation_alerted = true;
+                self.record_alert_snapshot(metrics).await;
+                self.record_signal("Graduation", SignalDirection::Bullish, None, metrics).await;
+                signals.push(Signal::GraduationImminent {
+                    mint: token_address,
+                    token_name: metrics.name.clone(),
+                    progress,
+                });
+            }
+        }
 
         // Check for significant price movement
         if let (Some(initial), Some(current)) = (metrics.initial_price, metrics.current_price) {
             let change_pct = ((current - initial) / initial) * 100.0;
 
-            if change_pct.abs() > 20.0 {
-                if let Some(telegram) = &self.telegram {
-                    telegram.alert_price_movement(
-                        &token_address,
-                        metrics.name.clone(),
-                        initial,
-                        current,
-                        Some(metrics.volume_24h),
-                    ).await?;
-                }
+            if change_pct.abs() > price_change_threshold {
+                self.record_alert_snapshot(metrics).await;
+                let direction = if change_pct > 0.0 { SignalDirection::Bullish } else { SignalDirection::Bearish };
+                self.record_signal("Price Movement", direction, None, metrics).await;
+                signals.push(Signal::PriceMovement {
+                    mint: token_address,
+                    token_name: metrics.name.clone(),
+                    initial_price: initial,
+                    current_price: current,
+                    volume_24h: VolumeTotals { buy: metrics.buy_volume_24h, sell: metrics.sell_volume_24h },
+                    bonding_curve_progress: metrics.bonding_curve_progress,
+                });
             }
         }
+        drop(tokens);
+        self.signals.publish_all(signals).await;
 
         Ok(())
     }
@@ -286,52 +1416,156 @@ impl EducationalMonitor {
 
     /// Detect patterns for educational purposes
     async fn detect_patterns(&self, parsed_data: &ParsedData) -> Result<()> {
+        let thresholds = self.thresholds.read().await.clone();
         let tokens = self.tracked_tokens.read().await;
+        // Published once the token lock is released
+        let mut signals = Vec::new();
 
         if let Some(metrics) = tokens.get(&parsed_data.token_mint) {
+            let snapshot = self.metrics_snapshot(metrics);
+            let opportunity = |kind: OpportunityKind, direction: SignalDirection, summary: String| {
+                OpportunitySignal::new(kind, parsed_data.token_mint, metrics.name.clone(), direction, summary, snapshot.clone())
+            };
+            let amount_sol = parsed_data.sol_amount.unwrap_or(0.0);
+
             // Pattern 1: High buy/sell ratio
             if metrics.buy_count > 0 && metrics.sell_count > 0 {
                 let ratio = metrics.buy_count as f64 / metrics.sell_count as f64;
-                if ratio > 3.0 {
-                    if let Some(telegram) = &self.telegram {
-                        telegram.alert_sniper_opportunity(
-                            &parsed_data.token_mint,
-                            metrics.name.clone(),
-                            "High Buy Pressure",
-                            &format!("Buy/Sell Ratio: {:.2}:1 - Strong buying interest detected", ratio),
-                        ).await?;
-                    }
+                if ratio > thresholds.buy_sell_ratio {
+                    let signal = opportunity(
+                        OpportunityKind::HighBuyPressure,
+                        SignalDirection::Bullish,
+                        format!("Buy/Sell Ratio: {:.2}:1 - Strong buying interest detected", ratio),
+                    ).with_evidence("buy/sell ratio", ratio, Some(thresholds.buy_sell_ratio));
+                    self.record_opportunity(&signal, metrics).await;
+                    signals.push(Signal::Opportunity(signal));
                 }
             }
 
             // Pattern 2: Volume spike
-            if parsed_data.sol_amount.unwrap_or(0.0) > 10.0 {
-                if let Some(telegram) = &self.telegram {
-                    telegram.alert_sniper_opportunity(
-                        &parsed_data.token_mint,
-                        metrics.name.clone(),
-                        "Large Transaction",
-                        &format!("Transaction size: {} SOL - Whale activity detected",
-                            parsed_data.sol_amount.unwrap_or(0.0)),
-                    ).await?;
+            if amount_sol > thresholds.whale_sol {
+                let signal = opportunity(
+                    OpportunityKind::LargeTransaction,
+                    SignalDirection::Bullish,
+                    format!("Transaction size: {} SOL - Whale activity detected", amount_sol),
+                ).with_evidence("trade size (SOL)", amount_sol, Some(thresholds.whale_sol));
+                self.record_opportunity(&signal, metrics).await;
+                signals.push(Signal::Opportunity(signal));
+            }
+
+            // Pattern 2b: Volume of the last minutes against the token's recent average, and
+            // trades far outside its usual size
+            let now = self.clock.now();
+            let spike_start = now - chrono::Duration::minutes(VOLUME_SPIKE_WINDOW_MIN);
+            let baseline_start = spike_start - chrono::Duration::minutes(VOLUME_SPIKE_BASELINE_MIN);
+            let volume_spike = match self.volumes.read().await.get(&parsed_data.token_mint) {
+                Some(buckets) if metrics.first_seen <= baseline_start => {
+                    let periods = (VOLUME_SPIKE_BASELINE_MIN / VOLUME_SPIKE_WINDOW_MIN) as f64;
+                    Some((buckets.totals_between(spike_start, now), buckets.totals_between(baseline_start, spike_start).total() / periods))
                 }
+                _ => None,
+            };
+            let trade_z = self.trade_sizes.read().await.get(&parsed_data.token_mint)
+                .and_then(|window| window.z_score(amount_sol, TRADE_ANOMALY_MIN_SAMPLES));
+            if let Some((recent, average)) = volume_spike.filter(|(_, average)| *average > 0.0) {
+                signals.push(Signal::VolumeSpike {
+                    mint: parsed_data.token_mint,
+                    token_name: metrics.name.clone(),
+                    recent,
+                    average,
+                });
+            }
+            if let Some(z) = trade_z.filter(|z| *z >= thresholds.trade_anomaly_z) {
+                let direction = match parsed_data.swap_type {
+                    SwapType::Sell => SignalDirection::Bearish,
+                    _ => SignalDirection::Bullish,
+                };
+                let signal = opportunity(
+                    OpportunityKind::UnusualTradeSize,
+                    direction,
+                    format!("{:.2} SOL trade, {:.1} standard deviations above the token's 24h trade size", amount_sol, z),
+                ).with_evidence("z-score", z, Some(thresholds.trade_anomaly_z));
+                self.record_opportunity(&signal, metrics).await;
+                signals.push(Signal::Opportunity(signal));
+            }
+
+            // Pattern 2c: Close above the recent candles' high on volume
+            let breakout = match (parsed_data.token_price, &parsed_data.swap_type) {
+                (Some(price), SwapType::Buy | SwapType::Sell) => self.breakouts.write().await.observe(
+                    parsed_data.token_mint,
+                    now - metrics.first_seen,
+                    now,
+                    price,
+                    amount_sol,
+                    thresholds.breakout_volume_factor,
+                ),
+                _ => None,
+            };
+            if let Some(breakout) = breakout {
+                let signal = opportunity(OpportunityKind::MomentumBreakout, SignalDirection::Bullish, breakout.describe())
+                    .with_evidence(
+                        "volume multiple",
+                        breakout.volume_multiple(),
+                        Some(breakout.bracket.volume_multiple * thresholds.breakout_volume_factor),
+                    )
+                    .with_evidence("above range high (%)", breakout.breakout_pct(), None);
+                self.record_opportunity(&signal, metrics).await;
+                signals.push(Signal::Opportunity(signal));
             }
 
             // Pattern 3: Recovery after dip
             if let (Some(initial), Some(current)) = (metrics.initial_price, metrics.current_price) {
                 let drop_pct = ((initial - current) / initial) * 100.0;
-                if drop_pct > 30.0 && metrics.buy_count > metrics.sell_count {
-                    if let Some(telegram) = &self.telegram {
-                        telegram.alert_sniper_opportunity(
-                            &parsed_data.token_mint,
-                            metrics.name.clone(),
-                            "Potential Recovery",
-                            &format!("Token down {:.1}% but buying pressure increasing", drop_pct),
-                        ).await?;
-                    }
+                if drop_pct > thresholds.recovery_drop_pct && metrics.buy_count > metrics.sell_count {
+                    let signal = opportunity(
+                        OpportunityKind::PotentialRecovery,
+                        SignalDirection::Bullish,
+                        format!("Token down {:.1}% but buying pressure increasing", drop_pct),
+                    )
+                    .with_evidence("drawdown (%)", drop_pct, Some(thresholds.recovery_drop_pct))
+                    .with_evidence("buys per sell", metrics.buy_count as f64 / metrics.sell_count.max(1) as f64, Some(1.0));
+                    self.record_opportunity(&signal, metrics).await;
+                    signals.push(Signal::Opportunity(signal));
                 }
             }
         }
+        drop(tokens);
+        self.signals.publish_all(signals).await;
+
+        Ok(())
+    }
+
+    /// Alert on pool creation, large liquidity removals, and whale-sized deposits
+    async fn handle_liquidity_event(&self, parsed_data: &ParsedData) -> Result<()> {
+        let thresholds = self.thresholds.read().await.clone();
+        let amount_sol = parsed_data.sol_amount.unwrap_or(0.0);
+        let pool_sol = parsed_data.liquidity.unwrap_or(0.0);
+
+        let notable = match parsed_data.swap_type {
+            SwapType::PoolCreate => true,
+            SwapType::RemoveLiquidity => {
+                let before = pool_sol + amount_sol;
+                before > 0.0 && amount_sol / before * 100.0 >= thresholds.liquidity_removal_pct
+            }
+            SwapType::AddLiquidity => amount_sol > thresholds.whale_sol,
+            SwapType::Buy | SwapType::Sell => false,
+        };
+        if !notable {
+            return Ok(());
+        }
+
+        let token_name = match &parsed_data.token_name {
+            Some(name) => Some(name.clone()),
+            None => self.tracked_tokens.read().await.get(&parsed_data.token_mint).and_then(|t| t.name.clone()),
+        };
+        self.signals.publish(Signal::LiquidityEvent {
+            mint: parsed_data.token_mint,
+            token_name,
+            kind: parsed_data.swap_type.clone(),
+            dex: parsed_data.dex_name.clone(),
+            amount_sol,
+            pool_sol,
+        }).await;
 
         Ok(())
     }
@@ -370,7 +1604,7 @@ impl EducationalMonitor {
         // Token statistics
         report.push_str(&format!("📈 Tokens Monitored: {}\n", tokens.len()));
 
-        let total_volume: f64 = tokens.values().map(|t| t.volume_24h).sum();
+        let total_volume: f64 = tokens.values().map(|t| t.total_volume).sum();
         report.push_str(&format!("💰 Total Volume: {:.2} SOL\n", total_volume));
 
         // Top movers
@@ -392,6 +1626,13 @@ impl EducationalMonitor {
             report.push_str(&format!("  • {}: +{:.2}%\n", name, gain));
         }
 
+        let trending = self.trending(Some(5)).await;
+        if !trending.is_empty() {
+            report.push_str("\n🔥 Trending:\n");
+            report.push_str(&format_trending(&trending));
+            report.push('\n');
+        }
+
         // Wallet statistics
         report.push_str(&format!("\n👥 Wallets Tracked: {}\n", wallets.len()));
 
@@ -407,6 +1648,16 @@ impl EducationalMonitor {
             report.push_str(&format!("  • {}...: {} trades\n", &addr[..8], trades));
         }
 
+        drop(tokens);
+        drop(wallets);
+        let (components, _) = self.estimated_memory().await;
+        let evicted = self.evicted_tokens.swap(0, Ordering::Relaxed);
+        report.push_str(&memory_budget::format_memory_section(&components, &self.memory_budget, evicted));
+        for adjustment in self.adaptive.expire() {
+            self.thresholds.write().await.raise_audited(adjustment.signal_type(), adjustment.factor(), "adaptive_thresholds");
+        }
+        report.push_str(&format_adjustments(&self.adaptive.take_notes(), &self.adaptive.active()));
+
         report.push_str("\n📚 Educational Insights:\n");
         report.push_str("• High-activity wallets may be bots or experienced traders\n");
         report.push_str("• Volume spikes often precede price movements\n");
@@ -423,4 +1674,24 @@ impl EducationalMonitor {
 
         Ok(report)
     }
+
+    /// Generate the weekly report: tracking totals and how accurate each signal type was
+    pub async fn generate_weekly_report(&self) -> String {
+        let tokens = self.tracked_tokens.read().await;
+        let outcomes = self.signal_outcomes.read().await;
+
+        let mut report = String::new();
+        report.push_str("\n📅 WEEKLY SIGNAL REPORT\n");
+        report.push_str("=====================================\n\n");
+        report.push_str(&format!("📈 Tokens Monitored: {}\n", tokens.len()));
+        report.push_str(&format!("👥 Wallets Tracked: {}\n\n", self.tracked_wallets.read().await.len()));
+        report.push_str(&format_signal_accuracy(&outcomes, 7, self.clock.now()));
+        report.push('\n');
+        report.push_str(&format_launch_stats(&self.launch_stats.read().await.summary(7, self.clock.now())));
+
+        report.push_str("\n📚 A signal counts as profitable when the price moved its way by at least\n");
+        report.push_str("SIGNAL_PROFIT_THRESHOLD_PCT at that horizon. Past accuracy does not predict future results.\n");
+
+        report
+    }
 }
\ No newline at end of file
@@ -0,0 +1,168 @@
+//! Positions sized from what a buy actually delivered rather than what it was quoted.
+//!
+//! A buy's position is opened with quoted amounts: the SOL the bot meant to spend and the token
+//! amount of the copied trade. Once the buy's transaction is read back from chain (see
+//! `trade_ledger::record_confirmed`), the wallet's real token and SOL deltas replace them, so
+//! PnL and sells work from the tokens actually held. Whichever of the two comes first (the
+//! position or the confirmed trade) waits for the other.
+//!
+//! A fill that differs from its quote by more than `FILL_DEVIATION_ALERT_PCT` percent, in tokens
+//! or SOL, raises an alert: a partial fill, heavy slippage or a transfer fee taken on the way.
+//! `0` disables the alert; positions are corrected either way.
+
+use std::sync::Arc;
+use colored::Colorize;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+
+use crate::common::logger::Logger;
+use crate::processor::sniper_bot::{BoughtTokenInfo, BOUGHT_TOKEN_LIST};
+use crate::processor::trade_ledger::{LedgerTrade, TradeSide};
+
+lazy_static! {
+    static ref SHARED_FILL_CHECK: Arc<FillCheck> = Arc::new(FillCheck::new(FillConfig::from_env()));
+    static ref LOGGER: Logger = Logger::new("[FILL-CHECK] => ".cyan().to_string());
+}
+
+#[derive(Clone, Debug)]
+pub struct FillConfig {
+    /// Deviation from the quote that raises an alert, in percent; 0 disables
+    pub alert_deviation_pct: f64,
+}
+
+impl Default for FillConfig {
+    fn default() -> Self {
+        Self { alert_deviation_pct: 10.0 }
+    }
+}
+
+impl FillConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            alert_deviation_pct: std::env::var("FILL_DEVIATION_ALERT_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|pct: &f64| *pct >= 0.0)
+                .unwrap_or(defaults.alert_deviation_pct),
+        }
+    }
+}
+
+/// A buy's quoted amounts against what it delivered
+#[derive(Clone, Debug, PartialEq)]
+pub struct FillReport {
+    pub signature: String,
+    pub mint: String,
+    pub quoted_tokens: f64,
+    pub actual_tokens: f64,
+    pub quoted_sol: f64,
+    pub actual_sol: f64,
+}
+
+impl FillReport {
+    pub fn new(quoted_tokens: f64, quoted_sol: f64, trade: &LedgerTrade) -> Self {
+        Self {
+            signature: trade.signature.clone(),
+            mint: trade.mint.clone(),
+            quoted_tokens,
+            actual_tokens: trade.token_amount,
+            quoted_sol,
+            actual_sol: trade.sol_amount,
+        }
+    }
+
+    /// Tokens received against the quote, in percent (negative: fewer than quoted)
+    pub fn token_deviation_pct(&self) -> f64 {
+        deviation_pct(self.quoted_tokens, self.actual_tokens)
+    }
+
+    /// SOL spent against the quote, in percent (positive: more than quoted)
+    pub fn sol_deviation_pct(&self) -> f64 {
+        deviation_pct(self.quoted_sol, self.actual_sol)
+    }
+
+    /// Whether tokens or SOL are off by more than `max_pct` percent
+    pub fn deviates(&self, max_pct: f64) -> bool {
+        self.token_deviation_pct().abs() > max_pct || self.sol_deviation_pct().abs() > max_pct
+    }
+}
+
+/// Unknown (zero) quotes never deviate
+fn deviation_pct(quoted: f64, actual: f64) -> f64 {
+    if quoted <= 0.0 {
+        return 0.0;
+    }
+    (actual - quoted) / quoted * 100.0
+}
+
+pub struct FillCheck {
+    config: FillConfig,
+    /// Confirmed buys by mint, kept until the mint is sold so a position (re)opened after the
+    /// confirmation still gets its fill
+    fills: DashMap<String, LedgerTrade>,
+    /// Mints whose fill was reported, so a reopened position is not reported twice
+    reported: DashMap<String, ()>,
+}
+
+impl FillCheck {
+    pub fn new(config: FillConfig) -> Self {
+        Self { config, fills: DashMap::new(), reported: DashMap::new() }
+    }
+
+    pub fn shared() -> Arc<FillCheck> {
+        SHARED_FILL_CHECK.clone()
+    }
+
+    /// A trade was read back from chain: correct the open position of a buy (or keep the fill
+    /// until the position opens); a sell forgets the mint's fill
+    pub fn trade_confirmed(&self, trade: &LedgerTrade) -> Option<FillReport> {
+        if trade.side == TradeSide::Sell {
+            self.fills.remove(&trade.mint);
+            self.reported.remove(&trade.mint);
+            return None;
+        }
+        self.fills.insert(trade.mint.clone(), trade.clone());
+        BOUGHT_TOKEN_LIST.get_mut(&trade.mint).map(|mut position| apply_fill(&mut position, trade))
+    }
+
+    /// A position is being opened: apply its buy's fill if it was confirmed already
+    pub fn position_opened(&self, position: &mut BoughtTokenInfo) -> Option<FillReport> {
+        let trade = self.fills.get(&position.token_mint)?.clone();
+        Some(apply_fill(position, &trade))
+    }
+
+    /// Whether a fill is off its quote by enough to alert
+    pub fn should_alert(&self, report: &FillReport) -> bool {
+        self.config.alert_deviation_pct > 0.0 && report.deviates(self.config.alert_deviation_pct)
+    }
+
+    /// Log a corrected position and alert when its fill deviated, in the background; once per mint
+    pub fn report(&self, report: FillReport) {
+        if self.reported.insert(report.mint.clone(), ()).is_some() {
+            return;
+        }
+        LOGGER.log(format!(
+            "{} filled {:.2} tokens for {:.6} SOL (quoted {:.2} for {:.6}, {:+.1}% tokens)",
+            report.mint, report.actual_tokens, report.actual_sol, report.quoted_tokens, report.quoted_sol,
+            report.token_deviation_pct(),
+        ));
+        if !self.should_alert(&report) {
+            return;
+        }
+        LOGGER.log(format!("⚠️ Fill of {} deviates from its quote", report.mint).yellow().to_string());
+        tokio::spawn(async move {
+            if let Ok(Some(telegram)) = crate::processor::telegram_alerts::init_from_env() {
+                let _ = telegram.alert_fill_deviation(&report).await;
+            }
+        });
+    }
+}
+
+/// Replace a position's quoted amounts with its fill, returning the comparison
+fn apply_fill(position: &mut BoughtTokenInfo, trade: &LedgerTrade) -> FillReport {
+    let report = FillReport::new(position.current_amount, position.initial_amount, trade);
+    position.current_amount = trade.token_amount;
+    position.initial_amount = trade.sol_amount;
+    report
+}
@@ -0,0 +1,290 @@
+//! First buyers of each launch: the first `FIRST_BUYERS_COUNT` wallets to buy a new token, with
+//! what they spent and how old their wallets were, kept as evidence of bundled launches (many
+//! first buyers in the launch slot, fresh wallets sharing a funder).
+//!
+//! Only launches seen from the start count (a pump.fun token first seen below
+//! `launch_stats::MAX_LAUNCH_PROGRESS_PCT` curve completion). Repeat buys of a wallet are added
+//! to its entry while the snapshot is open. Complete snapshots are appended to
+//! `FIRST_BUYERS_PATH` (JSON lines) and stay available through `/firstbuyers <mint>` after a
+//! restart; the most recent ones are also kept in memory, together with incomplete ones.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write as _};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::processor::launch_stats::MAX_LAUNCH_PROGRESS_PCT;
+use crate::processor::transaction_parser::{ParsedData, SwapType};
+use crate::processor::wallet_age::WalletOrigin;
+
+/// Snapshots kept in memory; older complete ones are only in the file
+const MAX_SNAPSHOTS: usize = 2_000;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FirstBuyer {
+    pub wallet: String,
+    pub sol: f64,
+    pub tokens: f64,
+    /// Slot and unix time of the wallet's first buy
+    pub slot: u64,
+    pub bought_at: i64,
+    /// Whether the wallet's age was looked up
+    pub age_checked: bool,
+    /// Unix time of the wallet's first transaction; None when unknown or its history is long
+    pub created_at: Option<i64>,
+    pub funder: Option<String>,
+}
+
+impl FirstBuyer {
+    /// Seconds between the wallet's creation and its buy, when known
+    pub fn age_secs(&self) -> Option<i64> {
+        self.created_at.map(|created_at| (self.bought_at - created_at).max(0))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FirstBuyersSnapshot {
+    pub mint: String,
+    pub buyers: Vec<FirstBuyer>,
+    pub complete: bool,
+}
+
+impl FirstBuyersSnapshot {
+    /// Buyers in the slot of the first buy
+    pub fn same_slot_buyers(&self) -> usize {
+        match self.buyers.first() {
+            Some(first) => self.buyers.iter().filter(|b| b.slot == first.slot).count(),
+            None => 0,
+        }
+    }
+
+    /// The funder shared by most first buyers, with how many, when at least two share one
+    pub fn top_funder(&self) -> Option<(&str, usize)> {
+        let mut funders: HashMap<&str, usize> = HashMap::new();
+        for funder in self.buyers.iter().filter_map(|b| b.funder.as_deref()) {
+            *funders.entry(funder).or_default() += 1;
+        }
+        funders.into_iter().filter(|(_, count)| *count >= 2).max_by_key(|(_, count)| *count)
+    }
+}
+
+pub struct FirstBuyers {
+    path: Option<PathBuf>,
+    max_buyers: usize,
+    /// Fresh-wallet cut-off, as for the buyer wallet ages
+    fresh_max_age_secs: i64,
+    snapshots: Mutex<HashMap<Pubkey, FirstBuyersSnapshot>>,
+    /// Insertion order, to drop the oldest snapshots
+    order: Mutex<VecDeque<Pubkey>>,
+}
+
+impl FirstBuyers {
+    pub fn new(path: Option<PathBuf>, max_buyers: usize, fresh_max_age_secs: i64) -> Self {
+        Self {
+            path,
+            max_buyers: max_buyers.max(1),
+            fresh_max_age_secs,
+            snapshots: Mutex::new(HashMap::new()),
+            order: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// FIRST_BUYERS_PATH (default `first_buyers.jsonl`, empty keeps snapshots in memory only),
+    /// FIRST_BUYERS_COUNT (default 20) and WALLET_FRESH_MAX_AGE_MIN
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok();
+        let path = var("FIRST_BUYERS_PATH").unwrap_or_else(|| "first_buyers.jsonl".to_string());
+        Self::new(
+            Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty()),
+            var("FIRST_BUYERS_COUNT").and_then(|v| v.parse().ok()).unwrap_or(20),
+            var("WALLET_FRESH_MAX_AGE_MIN").and_then(|v| v.parse::<i64>().ok()).map(|m| m * 60).unwrap_or(60 * 60),
+        )
+    }
+
+    /// Add a buy to its token's open snapshot; returns whether it came from a new first buyer,
+    /// whose wallet age is then due through `set_origin`
+    pub fn record(&self, parsed_data: &ParsedData) -> bool {
+        if parsed_data.swap_type != SwapType::Buy {
+            return false;
+        }
+        let mut snapshots = self.snapshots.lock().unwrap();
+        let snapshot = match snapshots.get_mut(&parsed_data.token_mint) {
+            Some(snapshot) => snapshot,
+            None => {
+                if !parsed_data.bonding_curve_progress.is_some_and(|p| p < MAX_LAUNCH_PROGRESS_PCT) {
+                    return false;
+                }
+                self.track(parsed_data.token_mint, &mut snapshots);
+                snapshots.entry(parsed_data.token_mint).or_insert_with(|| FirstBuyersSnapshot {
+                    mint: parsed_data.token_mint.to_string(),
+                    buyers: Vec::new(),
+                    complete: false,
+                })
+            }
+        };
+        if snapshot.complete {
+            return false;
+        }
+        let wallet = parsed_data.signer.to_string();
+        let sol = parsed_data.sol_amount.unwrap_or(0.0);
+        let tokens = parsed_data.token_amount.unwrap_or(0.0);
+        if let Some(buyer) = snapshot.buyers.iter_mut().find(|b| b.wallet == wallet) {
+            buyer.sol += sol;
+            buyer.tokens += tokens;
+            return false;
+        }
+        if snapshot.buyers.len() >= self.max_buyers {
+            return false;
+        }
+        snapshot.buyers.push(FirstBuyer {
+            wallet,
+            sol,
+            tokens,
+            slot: parsed_data.slot,
+            bought_at: parsed_data.timestamp as i64,
+            age_checked: false,
+            created_at: None,
+            funder: None,
+        });
+        true
+    }
+
+    /// Remember a snapshot's mint, dropping the oldest snapshots over the limit
+    fn track(&self, mint: Pubkey, snapshots: &mut HashMap<Pubkey, FirstBuyersSnapshot>) {
+        let mut order = self.order.lock().unwrap();
+        order.push_back(mint);
+        while order.len() > MAX_SNAPSHOTS {
+            if let Some(oldest) = order.pop_front() {
+                snapshots.remove(&oldest);
+            }
+        }
+    }
+
+    /// Set a first buyer's wallet age (None when the lookup failed or is turned off). The
+    /// snapshot completes, and is written out, once all its buyers are in and checked; returns
+    /// it then.
+    pub fn set_origin(&self, mint: &Pubkey, wallet: &Pubkey, origin: Option<WalletOrigin>) -> Option<FirstBuyersSnapshot> {
+        let mut snapshots = self.snapshots.lock().unwrap();
+        let snapshot = snapshots.get_mut(mint)?;
+        let wallet = wallet.to_string();
+        if let Some(buyer) = snapshot.buyers.iter_mut().find(|b| b.wallet == wallet) {
+            buyer.age_checked = true;
+            if let Some(origin) = origin {
+                buyer.created_at = origin.created_at;
+                buyer.funder = origin.funder.map(|f| f.to_string());
+            }
+        }
+        if snapshot.complete || snapshot.buyers.len() < self.max_buyers || snapshot.buyers.iter().any(|b| !b.age_checked) {
+            return None;
+        }
+        snapshot.complete = true;
+        let snapshot = snapshot.clone();
+        drop(snapshots);
+        self.save(&snapshot);
+        Some(snapshot)
+    }
+
+    fn save(&self, snapshot: &FirstBuyersSnapshot) {
+        if let Some(path) = &self.path {
+            let written = serde_json::to_string(snapshot)
+                .map_err(anyhow::Error::from)
+                .and_then(|line| {
+                    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+                    writeln!(file, "{}", line)?;
+                    Ok(())
+                });
+            if let Err(e) = written {
+                eprintln!("Failed to store the first buyers of {} in {}: {}", snapshot.mint, path.display(), e);
+            }
+        }
+    }
+
+    /// Snapshot of a mint, from memory or else the last one stored for it
+    pub fn get(&self, mint: &Pubkey) -> Option<FirstBuyersSnapshot> {
+        if let Some(snapshot) = self.snapshots.lock().unwrap().get(mint) {
+            return Some(snapshot.clone());
+        }
+        let file = std::fs::File::open(self.path.as_ref()?).ok()?;
+        let mint = mint.to_string();
+        BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .filter(|line| line.contains(&mint))
+            .filter_map(|line| serde_json::from_str::<FirstBuyersSnapshot>(&line).ok())
+            .filter(|snapshot| snapshot.mint == mint)
+            .last()
+    }
+
+    pub fn fresh_max_age_secs(&self) -> i64 {
+        self.fresh_max_age_secs
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.lock().unwrap().is_empty()
+    }
+}
+
+fn short(address: &str) -> String {
+    if address.len() > 10 {
+        format!("{}…{}", &address[..4], &address[address.len() - 4..])
+    } else {
+        address.to_string()
+    }
+}
+
+fn format_age(buyer: &FirstBuyer, fresh_max_age_secs: i64) -> String {
+    match (buyer.age_checked, buyer.age_secs()) {
+        (false, _) => "age unknown".to_string(),
+        (true, Some(secs)) if secs <= fresh_max_age_secs => {
+            let age = if secs < 3600 { format!("{}m", secs / 60) } else { format!("{}h", secs / 3600) };
+            match &buyer.funder {
+                Some(funder) => format!("🆕 fresh, {} old, funded by {}", age, short(funder)),
+                None => format!("🆕 fresh, {} old", age),
+            }
+        }
+        (true, Some(secs)) => format!("{}d old", secs / 86_400),
+        (true, None) => "aged wallet".to_string(),
+    }
+}
+
+/// Reply to `/firstbuyers`: one line per buyer and the bundling signs
+pub fn format_first_buyers(snapshot: &FirstBuyersSnapshot, fresh_max_age_secs: i64) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "🥇 First {} buyers of {}{}\n",
+        snapshot.buyers.len(),
+        snapshot.mint,
+        if snapshot.complete { "" } else { " (so far)" }
+    );
+    let first_slot = snapshot.buyers.first().map_or(0, |b| b.slot);
+    for (i, buyer) in snapshot.buyers.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "{}. {} {:.3} SOL, slot +{}, {}",
+            i + 1,
+            short(&buyer.wallet),
+            buyer.sol,
+            buyer.slot.saturating_sub(first_slot),
+            format_age(buyer, fresh_max_age_secs)
+        );
+    }
+    let fresh = snapshot.buyers.iter()
+        .filter(|b| b.age_secs().is_some_and(|secs| secs <= fresh_max_age_secs))
+        .count();
+    let total_sol: f64 = snapshot.buyers.iter().map(|b| b.sol).sum();
+    let _ = writeln!(out, "\n{:.2} SOL bought in total", total_sol);
+    let _ = writeln!(out, "{} of {} bought in the launch slot", snapshot.same_slot_buyers(), snapshot.buyers.len());
+    let _ = writeln!(out, "{} fresh wallets", fresh);
+    if let Some((funder, count)) = snapshot.top_funder() {
+        let _ = writeln!(out, "⚠️ {} buyers funded by the same wallet {}", count, funder);
+    }
+    out
+}
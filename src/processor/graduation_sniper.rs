@@ -0,0 +1,498 @@
+/*!
+# Graduation Sniper
+
+Targets pump.fun tokens at the moment their bonding curve completes and liquidity migrates to a
+PumpSwap pool, buying within the first blocks of the new pool.
+
+- Streams pump.fun program transactions and tracks each token's curve progress from its trade
+  events. Tokens crossing `GRADUATION_ARM_PCT` are armed: the canonical PumpSwap pool address is
+  derived and the buy is pre-built against the reserves the pool is expected to open with.
+- When the pump.fun `migrate` instruction for an armed token is seen, the pool's opening reserves
+  are read from the migration transaction. The pre-built buy is sent as-is when they match the
+  expectation (within `GRADUATION_RESERVE_TOLERANCE_PCT`), otherwise it is rebuilt from them.
+- The buy is dropped if the stream is already more than `GRADUATION_MAX_SLOT_DELAY` slots past
+  the migration, so we never chase a pool that is no longer fresh.
+
+## Environment Variables
+
+- `GRADUATION_SNIPE_ENABLED`: Enable graduation sniping (default: `false`)
+- `GRADUATION_ARM_PCT`: Curve completion (%) at which a token is armed (default: `95`)
+- `GRADUATION_BUY_SOL`: SOL to spend per graduation buy, scaled down like other buys in a cold
+  market regime (default: the regular buy amount)
+- `GRADUATION_MAX_SLOT_DELAY`: Maximum slots after migration to still buy (default: `2`)
+- `GRADUATION_RESERVE_TOLERANCE_PCT`: Reserve drift accepted for the pre-built buy (default: `5`)
+- `GRADUATION_ARM_TTL_SECS`: Disarm tokens that have not migrated after this long (default: `1800`)
+*/
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use colored::Colorize;
+use dashmap::DashMap;
+use anchor_client::solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+use tokio_util::sync::CancellationToken;
+use yellowstone_grpc_proto::geyser::{
+    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
+    SubscribeRequestFilterTransactions, SubscribeUpdateTransaction,
+};
+
+use crate::common::cache::WALLET_TOKEN_ACCOUNTS;
+use crate::common::config::{threshold_or, AppState, Percent, Slots, SolAmount, SwapConfig};
+use crate::common::logger::Logger;
+use crate::library::geyser_subscription::{run_reconnecting, GeyserSubscription};
+use crate::common::quote_asset::QuoteAsset;
+use crate::common::cluster::DexProgram;
+use crate::dex::pump_fun::{self, PUMP_FUN_MIGRATE_IX_DISCRIMINATOR};
+use crate::dex::pump_swap::{self, PumpSwap, SOL_MINT};
+use crate::processor::market_regime;
+use crate::processor::sniper_bot::BOUGHT_TOKEN_LIST;
+use crate::processor::swap::{SwapDirection, SwapProtocol};
+use crate::processor::telegram_alerts::TelegramAlertSystem;
+use crate::processor::transaction_parser::{self, DexType, TradeInfoFromToken};
+
+lazy_static::lazy_static! {
+    /// Tokens close to completing their bonding curve, waiting for migration
+    pub static ref GRADUATION_CANDIDATES: Arc<DashMap<String, GraduationCandidate>> = Arc::new(DashMap::new());
+}
+
+/// A buy built ahead of the migration it targets
+#[derive(Clone)]
+pub struct PrebuiltBuy {
+    pub keypair: Arc<Keypair>,
+    pub instructions: Vec<Instruction>,
+    pub trade_info: TradeInfoFromToken,
+}
+
+#[derive(Clone)]
+pub struct GraduationCandidate {
+    pub mint: Pubkey,
+    pub coin_creator: String,
+    pub pool: Pubkey,
+    pub progress: f64,
+    pub armed_at: Instant,
+    pub prebuilt: Option<PrebuiltBuy>,
+}
+
+/// Graduation sniper configuration
+pub struct GraduationSniperConfig {
+    pub yellowstone_grpc_http: String,
+    pub yellowstone_grpc_token: String,
+    pub app_state: Arc<AppState>,
+    pub swap_config: Arc<SwapConfig>,
+    pub arm_pct: f64,
+    pub max_slot_delay: u64,
+    pub reserve_tolerance_pct: f64,
+    pub arm_ttl: Duration,
+}
+
+impl GraduationSniperConfig {
+    pub fn new(
+        yellowstone_grpc_http: String,
+        yellowstone_grpc_token: String,
+        app_state: Arc<AppState>,
+        swap_config: Arc<SwapConfig>,
+    ) -> Self {
+        let mut buy_config = (*swap_config).clone();
+        buy_config.swap_direction = SwapDirection::Buy;
+        buy_config.amount_in = threshold_or(
+            "GRADUATION_BUY_SOL",
+            SolAmount::from_sol(buy_config.amount_in),
+            SolAmount::from_str,
+            SolAmount::from_sol(0.0)..=SolAmount::from_sol(1_000.0),
+        ).sol();
+        let percent = Percent::from_points(0.0)..=Percent::from_points(100.0);
+
+        Self {
+            yellowstone_grpc_http,
+            yellowstone_grpc_token,
+            app_state,
+            swap_config: Arc::new(buy_config),
+            arm_pct: threshold_or("GRADUATION_ARM_PCT", Percent::from_points(95.0), Percent::from_str, percent.clone()).points(),
+            max_slot_delay: threshold_or("GRADUATION_MAX_SLOT_DELAY", Slots::new(2), Slots::from_str, Slots::new(0)..=Slots::new(150)).get(),
+            reserve_tolerance_pct: threshold_or("GRADUATION_RESERVE_TOLERANCE_PCT", Percent::from_points(5.0), Percent::from_str, percent).points(),
+            arm_ttl: Duration::from_secs(
+                std::env::var("GRADUATION_ARM_TTL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(1800),
+            ),
+        }
+    }
+}
+
+/// Find the mint of an armed token migrated by this transaction, if any
+pub fn find_migrated_mint(txn: &SubscribeUpdateTransaction) -> Option<Pubkey> {
+    let tx_info = txn.transaction.as_ref()?;
+    let message = tx_info.transaction.as_ref()?.message.as_ref()?;
+
+    let mut account_keys: Vec<&[u8]> = message.account_keys.iter().map(|k| k.as_slice()).collect();
+    if let Some(meta) = &tx_info.meta {
+        account_keys.extend(meta.loaded_writable_addresses.iter().map(|k| k.as_slice()));
+        account_keys.extend(meta.loaded_readonly_addresses.iter().map(|k| k.as_slice()));
+    }
+
+    let pump_program = DexProgram::PumpFun.id();
+    let is_migrate = |program_id_index: u32, data: &[u8]| {
+        account_keys.get(program_id_index as usize) == Some(&pump_program.as_ref())
+            && data.starts_with(PUMP_FUN_MIGRATE_IX_DISCRIMINATOR)
+    };
+    let armed_mint = |accounts: &[u8]| {
+        accounts.iter()
+            .filter_map(|i| account_keys.get(*i as usize))
+            .filter_map(|key| Pubkey::try_from(*key).ok())
+            .find(|key| GRADUATION_CANDIDATES.contains_key(&key.to_string()))
+    };
+
+    let outer = message.instructions.iter().map(|ix| (ix.program_id_index, &ix.accounts, &ix.data));
+    let inner = tx_info.meta.iter()
+        .flat_map(|meta| &meta.inner_instructions)
+        .flat_map(|inner| &inner.instructions)
+        .map(|ix| (ix.program_id_index, &ix.accounts, &ix.data));
+    outer.chain(inner)
+        .filter(|(program_id_index, _, data)| is_migrate(*program_id_index, data))
+        .find_map(|(_, accounts, _)| armed_mint(accounts))
+}
+
+/// Pool reserves (base token, quote SOL) after the migration, from the transaction's token balances
+fn pool_reserves_after(txn: &SubscribeUpdateTransaction, pool: &Pubkey, mint: &Pubkey) -> Option<(u64, u64)> {
+    let meta = txn.transaction.as_ref()?.meta.as_ref()?;
+    let pool = pool.to_string();
+    let balance_of = |balance_mint: &str| {
+        meta.post_token_balances.iter()
+            .find(|b| b.owner == pool && b.mint == balance_mint)
+            .and_then(|b| b.ui_token_amount.as_ref())
+            .and_then(|amount| amount.amount.parse::<u64>().ok())
+    };
+    Some((balance_of(&mint.to_string())?, balance_of(&SOL_MINT.to_string())?))
+}
+
+fn within_tolerance(actual: u64, expected: u64, tolerance_pct: f64) -> bool {
+    if expected == 0 {
+        return actual == 0;
+    }
+    (actual as f64 - expected as f64).abs() / expected as f64 * 100.0 <= tolerance_pct
+}
+
+/// SOL a graduation buy spends now: the configured amount, scaled down in a cold market regime
+fn buy_amount(config: &GraduationSniperConfig) -> f64 {
+    config.swap_config.amount_in * market_regime::risk_multiplier().min(1.0)
+}
+
+/// Trade info describing a buy on a freshly migrated pool with the given reserves
+fn pool_trade_info(candidate: &GraduationCandidate, base_reserve: u64, quote_reserve: u64, amount_in: f64, slot: u64) -> TradeInfoFromToken {
+    let amount_lamports = (amount_in * 1_000_000_000.0) as u64;
+    let tokens_out = PumpSwap::calculate_buy_token_amount(amount_lamports, quote_reserve, base_reserve);
+    TradeInfoFromToken {
+        dex_type: DexType::PumpSwap,
+        slot,
+        signature: String::new(),
+        pool_id: candidate.pool.to_string(),
+        mint: candidate.mint.to_string(),
+        timestamp: chrono::Utc::now().timestamp() as u64,
+        is_buy: true,
+        price: quote_reserve.saturating_mul(1_000_000_000) / base_reserve.max(1),
+        is_reverse_when_pump_swap: false,
+        coin_creator: Some(candidate.coin_creator.clone()),
+        sol_change: -amount_in,
+        // pump.fun tokens have 6 decimals
+        token_change: tokens_out as f64 / 1_000_000.0,
+        liquidity: quote_reserve as f64 / 1_000_000_000.0,
+        virtual_sol_reserves: quote_reserve,
+        virtual_token_reserves: base_reserve,
+        quote_asset: QuoteAsset::Sol,
+    }
+}
+
+pub struct GraduationSniper {
+    config: Arc<GraduationSniperConfig>,
+    telegram: Option<Arc<TelegramAlertSystem>>,
+    latest_slot: Arc<AtomicU64>,
+    logger: Logger,
+}
+
+impl GraduationSniper {
+    pub fn new(config: GraduationSniperConfig, telegram: Option<Arc<TelegramAlertSystem>>) -> Self {
+        Self {
+            config: Arc::new(config),
+            telegram,
+            latest_slot: Arc::new(AtomicU64::new(0)),
+            logger: Logger::new("[GRADUATION-SNIPER] => ".bright_green().to_string()),
+        }
+    }
+
+    /// Run the sniper, reconnecting until cancelled
+    pub async fn start(&self, cancel_token: CancellationToken) -> Result<(), String> {
+        self.logger.log(format!(
+            "Starting graduation sniper (arm at {:.1}%, buy {} SOL, max {} slots after migration)",
+            self.config.arm_pct, self.config.swap_config.amount_in, self.config.max_slot_delay
+        ).green().to_string());
+
+        run_reconnecting("Graduation subscription", &self.logger, &cancel_token, || self.run_subscription()).await;
+        self.logger.log("Graduation sniper received shutdown signal.".yellow().to_string());
+        Ok(())
+    }
+
+    async fn run_subscription(&self) -> Result<(), String> {
+        let mut subscription = GeyserSubscription::open(&self.config.yellowstone_grpc_http, &self.config.yellowstone_grpc_token).await?;
+        subscription.update(SubscribeRequest {
+            transactions: maplit::hashmap! {
+                "PumpFun".to_owned() => SubscribeRequestFilterTransactions {
+                    vote: Some(false),
+                    failed: Some(false),
+                    signature: None,
+                    account_include: vec![DexProgram::PumpFun.id().to_string()],
+                    account_exclude: vec![],
+                    account_required: Vec::<String>::new(),
+                }
+            },
+            commitment: Some(CommitmentLevel::Processed as i32),
+            ..Default::default()
+        }).await?;
+
+        let mut prune = tokio::time::interval(Duration::from_secs(30));
+
+        loop {
+            tokio::select! {
+                _ = prune.tick() => self.prune_candidates(),
+                update = subscription.next() => {
+                    if let UpdateOneof::Transaction(txn) = update? {
+                        self.handle_transaction(txn);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Arm tokens from the pump.fun trades in `txn`, or snipe an armed token it migrates
+    pub fn handle_transaction(&self, txn: SubscribeUpdateTransaction) {
+        self.latest_slot.fetch_max(txn.slot, Ordering::SeqCst);
+        crate::library::slot_lag::observe_stream_slot(txn.slot);
+
+        if !GRADUATION_CANDIDATES.is_empty() {
+            if let Some(mint) = find_migrated_mint(&txn) {
+                if let Some((_, candidate)) = GRADUATION_CANDIDATES.remove(&mint.to_string()) {
+                    self.spawn_snipe(candidate, txn);
+                }
+                return;
+            }
+        }
+
+        let events = txn.transaction.as_ref()
+            .and_then(|t| t.meta.as_ref())
+            .map(|meta| meta.inner_instructions.iter()
+                .flat_map(|inner| &inner.instructions)
+                .filter_map(|ix| transaction_parser::parse_transaction_data(&txn, &ix.data))
+                .filter(|trade| trade.dex_type == DexType::PumpFun)
+                .collect::<Vec<_>>())
+            .unwrap_or_default();
+        for trade in events {
+            self.observe_trade(trade);
+        }
+    }
+
+    /// Arm tokens whose curve crossed the arm threshold
+    fn observe_trade(&self, trade: TradeInfoFromToken) {
+        let progress = pump_fun::bonding_curve_progress(trade.virtual_token_reserves);
+        if let Some(mut candidate) = GRADUATION_CANDIDATES.get_mut(&trade.mint) {
+            candidate.progress = progress;
+            return;
+        }
+        if progress < self.config.arm_pct || BOUGHT_TOKEN_LIST.contains_key(&trade.mint) {
+            return;
+        }
+        let (mint, coin_creator) = match (Pubkey::from_str(&trade.mint), trade.coin_creator.clone()) {
+            (Ok(mint), Some(creator)) => (mint, creator),
+            _ => return,
+        };
+
+        let candidate = GraduationCandidate {
+            mint,
+            coin_creator,
+            pool: pump_swap::get_canonical_pool_pda(&mint),
+            progress,
+            armed_at: Instant::now(),
+            prebuilt: None,
+        };
+        self.logger.log(format!(
+            "🎓 Armed {} at {:.1}% curve completion (pool {})", mint, progress, candidate.pool
+        ).cyan().to_string());
+        GRADUATION_CANDIDATES.insert(trade.mint.clone(), candidate.clone());
+
+        // Pre-build the buy against the reserves the pool is expected to open with
+        let config = self.config.clone();
+        let logger = self.logger.clone();
+        tokio::spawn(async move {
+            let (base_reserve, quote_reserve) = pump_fun::expected_migration_reserves();
+            match build_buy(&config, &candidate, base_reserve, quote_reserve, 0).await {
+                Ok(prebuilt) => {
+                    if let Some(mut entry) = GRADUATION_CANDIDATES.get_mut(&trade.mint) {
+                        entry.prebuilt = Some(prebuilt);
+                        logger.log(format!("Pre-built graduation buy for {}", trade.mint));
+                    }
+                }
+                Err(e) => logger.log(format!("Failed to pre-build buy for {}: {}", trade.mint, e).yellow().to_string()),
+            }
+        });
+    }
+
+    fn spawn_snipe(&self, candidate: GraduationCandidate, txn: SubscribeUpdateTransaction) {
+        let config = self.config.clone();
+        let telegram = self.telegram.clone();
+        let latest_slot = self.latest_slot.clone();
+        let logger = self.logger.clone();
+
+        tokio::spawn(async move {
+            let mint = candidate.mint.to_string();
+            let migration_slot = txn.slot;
+            logger.log(format!("🚀 {} migrated to PumpSwap pool {} at slot {}", mint, candidate.pool, migration_slot).green().bold().to_string());
+
+            let (base_reserve, quote_reserve) = match pool_reserves_after(&txn, &candidate.pool, &candidate.mint) {
+                Some(reserves) => reserves,
+                None => {
+                    logger.log(format!("Migration of {} did not create the expected pool {}, skipping", mint, candidate.pool).yellow().to_string());
+                    forget_unbought_ata(&config, &candidate.mint);
+                    return;
+                }
+            };
+
+            let (expected_base, expected_quote) = pump_fun::expected_migration_reserves();
+            let amount_in = buy_amount(&config);
+            let prebuilt = candidate.prebuilt.clone().filter(|prebuilt| {
+                within_tolerance(base_reserve, expected_base, config.reserve_tolerance_pct)
+                    && within_tolerance(quote_reserve, expected_quote, config.reserve_tolerance_pct)
+                    && (prebuilt.trade_info.sol_change.abs() - amount_in).abs() < 1e-9
+            });
+            let buy = match prebuilt {
+                Some(mut prebuilt) => {
+                    prebuilt.trade_info = pool_trade_info(&candidate, base_reserve, quote_reserve, amount_in, migration_slot);
+                    prebuilt
+                }
+                None => {
+                    // Reserves drifted from the expectation, or the regime changed the size: rebuild
+                    forget_unbought_ata(&config, &candidate.mint);
+                    match build_buy(&config, &candidate, base_reserve, quote_reserve, migration_slot).await {
+                        Ok(buy) => buy,
+                        Err(e) => {
+                            logger.log(format!("Failed to build graduation buy for {}: {}", mint, e).red().to_string());
+                            return;
+                        }
+                    }
+                }
+            };
+
+            let slots_late = latest_slot.load(Ordering::SeqCst).saturating_sub(migration_slot);
+            if slots_late > config.max_slot_delay {
+                logger.log(format!("Skipping {}: {} slots past migration (max {})", mint, slots_late, config.max_slot_delay).yellow().to_string());
+                forget_unbought_ata(&config, &candidate.mint);
+                return;
+            }
+
+            let bought_sol = buy.trade_info.sol_change.abs();
+            let result = crate::processor::sniper_bot::execute_prebuilt_buy(
+                buy.trade_info,
+                buy.keypair,
+                buy.instructions,
+                config.app_state.clone(),
+                config.swap_config.clone(),
+                SwapProtocol::PumpSwap,
+            ).await;
+
+            let message = match &result {
+                Ok(()) => format!(
+                    "Bought {} SOL of {} on its new PumpSwap pool {} ({} slots after migration)",
+                    bought_sol, mint, candidate.pool, slots_late
+                ),
+                Err(e) => format!("Graduation buy of {} failed: {}", mint, e),
+            };
+            if result.is_ok() {
+                logger.log(format!("✅ {}", message).green().to_string());
+            } else {
+                logger.log(message.red().to_string());
+                forget_unbought_ata(&config, &candidate.mint);
+            }
+            if let Some(telegram) = telegram {
+                let _ = telegram.send_custom_alert("Graduation Snipe", &message).await;
+            }
+        });
+    }
+
+    fn prune_candidates(&self) {
+        let expired: Vec<GraduationCandidate> = GRADUATION_CANDIDATES.iter()
+            .filter(|entry| entry.armed_at.elapsed() > self.config.arm_ttl)
+            .map(|entry| entry.value().clone())
+            .collect();
+        for candidate in expired {
+            GRADUATION_CANDIDATES.remove(&candidate.mint.to_string());
+            forget_unbought_ata(&self.config, &candidate.mint);
+            self.logger.log(format!("Disarmed {}: no migration within {:?}", candidate.mint, self.config.arm_ttl));
+        }
+    }
+}
+
+async fn build_buy(
+    config: &GraduationSniperConfig,
+    candidate: &GraduationCandidate,
+    base_reserve: u64,
+    quote_reserve: u64,
+    slot: u64,
+) -> anyhow::Result<PrebuiltBuy> {
+    let mut buy_config = (*config.swap_config).clone();
+    buy_config.amount_in = buy_amount(config);
+    let trade_info = pool_trade_info(candidate, base_reserve, quote_reserve, buy_config.amount_in, slot);
+    let pump_swap = PumpSwap::new(
+        config.app_state.wallet.clone(),
+        Some(config.app_state.rpc_client.clone()),
+        Some(config.app_state.rpc_nonblocking_client.clone()),
+    );
+    let (keypair, instructions, _price) = pump_swap
+        .build_swap_from_parsed_data(&trade_info, buy_config)
+        .await?;
+    Ok(PrebuiltBuy { keypair, instructions, trade_info })
+}
+
+/// Building a buy marks our ATA for the mint as existing; undo that if the buy never happened
+fn forget_unbought_ata(config: &GraduationSniperConfig, mint: &Pubkey) {
+    if BOUGHT_TOKEN_LIST.contains_key(&mint.to_string()) {
+        return;
+    }
+    if let Ok(wallet) = config.app_state.wallet.try_pubkey() {
+        WALLET_TOKEN_ACCOUNTS.remove(&get_associated_token_address(&wallet, mint));
+    }
+}
+
+/// Start the graduation sniper if enabled via GRADUATION_SNIPE_ENABLED=true
+pub async fn start_graduation_sniper(
+    yellowstone_grpc_http: String,
+    yellowstone_grpc_token: String,
+    app_state: Arc<AppState>,
+    swap_config: Arc<SwapConfig>,
+    cancel_token: CancellationToken,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let enabled = std::env::var("GRADUATION_SNIPE_ENABLED")
+        .ok()
+        .and_then(|v| v.to_lowercase().parse::<bool>().ok())
+        .unwrap_or(false);
+    if !enabled {
+        return None;
+    }
+    // Watches pump.fun curves and buys in the PumpSwap pool they migrate to
+    if !DexProgram::PumpFun.is_enabled() || !DexProgram::PumpSwap.is_tradeable() {
+        eprintln!("Graduation sniper not started: needs pumpfun in ENABLED_DEXES and pumpswap in TRADE_DEXES");
+        return None;
+    }
+
+    let telegram = crate::processor::telegram_alerts::init_from_env()
+        .ok()
+        .flatten()
+        .map(Arc::new);
+    let config = GraduationSniperConfig::new(yellowstone_grpc_http, yellowstone_grpc_token, app_state, swap_config);
+    let sniper = GraduationSniper::new(config, telegram);
+
+    Some(tokio::spawn(async move {
+        if let Err(e) = sniper.start(cancel_token).await {
+            eprintln!("Graduation sniper error: {}", e);
+        }
+    }))
+}
@@ -0,0 +1,260 @@
+/*!
+# Group Consensus
+
+A buy signal that fires only when M out of the N wallets of a group buy the same token within a
+time window. One wallet buying says little; several independent wallets you trust converging on
+the same token is a much stronger signal, so a consensus is raised as a scored opportunity
+(`Group Consensus`) whose confidence grows with the number of wallets agreeing.
+
+Rules are read from the JSON file at `GROUP_CONSENSUS_PATH`:
+
+```json
+[
+  {"group": "KOLs", "min_wallets": 3, "window_secs": 600},
+  {"group": "Funds", "min_wallets": 2, "members": ["<wallet>", "<wallet>"]}
+]
+```
+
+A rule without `members` uses the wallets imported into that group (see `wallet_groups`); with
+`members`, exactly those wallets, labeled from the wallet groups when they are imported there.
+`window_secs` defaults to 300. A rule fires once per token and window.
+
+## Environment Variables
+
+- `GROUP_CONSENSUS_PATH`: JSON file of consensus rules (default: `group_consensus.json`; a missing
+  file means no rules)
+*/
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::processor::opportunity_signal::{MetricsSnapshot, OpportunityKind, OpportunitySignal};
+use crate::processor::signal_outcomes::SignalDirection;
+use crate::processor::transaction_parser::{ParsedData, SwapType};
+use crate::processor::wallet_groups::WalletGroups;
+
+fn default_window_secs() -> i64 {
+    300
+}
+
+/// M wallets of a group buying within `window_secs`
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConsensusRule {
+    pub group: String,
+    pub min_wallets: usize,
+    #[serde(default = "default_window_secs")]
+    pub window_secs: i64,
+    /// Explicit members; empty means the wallets imported into `group`
+    #[serde(default)]
+    pub members: Vec<String>,
+}
+
+impl ConsensusRule {
+    fn validate(&self) -> Result<()> {
+        if self.group.trim().is_empty() {
+            return Err(anyhow!("a consensus rule has no group"));
+        }
+        if self.min_wallets < 2 {
+            return Err(anyhow!("group '{}': min_wallets must be at least 2", self.group));
+        }
+        if self.window_secs <= 0 {
+            return Err(anyhow!("group '{}': window_secs must be positive", self.group));
+        }
+        if let Some(member) = self.members.iter().find(|m| Pubkey::from_str(m).is_err()) {
+            return Err(anyhow!("group '{}': invalid member {}", self.group, member));
+        }
+        if !self.members.is_empty() && self.members.len() < self.min_wallets {
+            return Err(anyhow!("group '{}': {} members cannot reach min_wallets {}", self.group, self.members.len(), self.min_wallets));
+        }
+        Ok(())
+    }
+}
+
+/// Parse and validate consensus rules
+pub fn parse_rules(json: &str) -> Result<Vec<ConsensusRule>> {
+    let rules: Vec<ConsensusRule> = serde_json::from_str(json)?;
+    for rule in &rules {
+        rule.validate()?;
+    }
+    Ok(rules)
+}
+
+/// Rules from `path`; none when the file does not exist
+pub fn load_rules(path: &Path) -> Result<Vec<ConsensusRule>> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => parse_rules(&json).map_err(|e| anyhow!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(anyhow!("{}: {}", path.display(), e)),
+    }
+}
+
+/// M of N wallets of a group bought the same token
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsensusHit {
+    pub group: String,
+    pub mint: Pubkey,
+    /// Label (or address) and SOL bought of each agreeing wallet, first buyer first
+    pub wallets: Vec<(String, f64)>,
+    /// N: wallets in the group
+    pub members: usize,
+    /// M: wallets the rule asks for
+    pub min_wallets: usize,
+    pub total_sol: f64,
+    /// Seconds from the first to the last of the buys
+    pub span_secs: i64,
+}
+
+impl ConsensusHit {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} of {} wallets in group '{}' bought within {}s ({:.2} SOL): {}",
+            self.wallets.len(),
+            self.members,
+            self.group,
+            self.span_secs,
+            self.total_sol,
+            self.wallets.iter().map(|(label, _)| label.as_str()).collect::<Vec<_>>().join(", ")
+        )
+    }
+
+    /// The consensus as a scored opportunity; measured against a single wallet buying, so
+    /// confidence grows with every wallet agreeing
+    pub fn to_opportunity(&self, token_name: Option<String>, snapshot: MetricsSnapshot) -> OpportunitySignal {
+        OpportunitySignal::new(OpportunityKind::GroupConsensus, self.mint, token_name, SignalDirection::Bullish, self.summary(), snapshot)
+            .with_evidence("group wallets buying", self.wallets.len() as f64, Some(1.0))
+            .with_evidence("group size", self.members as f64, None)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Buy {
+    at: i64,
+    wallet: Pubkey,
+    sol: f64,
+}
+
+pub struct GroupConsensus {
+    rules: Vec<ConsensusRule>,
+    /// Recent buys of members per (rule index, mint)
+    buys: Mutex<HashMap<(usize, Pubkey), Vec<Buy>>>,
+    /// Unix seconds a rule last fired on a mint
+    fired: Mutex<HashMap<(usize, Pubkey), i64>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl GroupConsensus {
+    pub fn new(rules: Vec<ConsensusRule>) -> Self {
+        Self { rules, buys: Mutex::new(HashMap::new()), fired: Mutex::new(HashMap::new()), clock: system_clock() }
+    }
+
+    /// Rules from GROUP_CONSENSUS_PATH; a broken file is reported and leaves no rules
+    pub fn from_env() -> Self {
+        let path = std::env::var("GROUP_CONSENSUS_PATH").unwrap_or_else(|_| "group_consensus.json".to_string());
+        if path.trim().is_empty() {
+            return Self::new(Vec::new());
+        }
+        match load_rules(Path::new(&path)) {
+            Ok(rules) => Self::new(rules),
+            Err(e) => {
+                eprintln!("Ignoring group consensus rules: {}", e);
+                Self::new(Vec::new())
+            }
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn rules(&self) -> &[ConsensusRule] {
+        &self.rules
+    }
+
+    /// Count a buy towards every rule its wallet is a member of; returns the rules it completes
+    pub fn observe(&self, parsed_data: &ParsedData, groups: &WalletGroups) -> Vec<ConsensusHit> {
+        if self.rules.is_empty() || !matches!(parsed_data.swap_type, SwapType::Buy) {
+            return Vec::new();
+        }
+        let signer = parsed_data.signer.to_string();
+        let signer_group = groups.group(&parsed_data.signer);
+        let now = self.clock.now().timestamp();
+        let mut hits = Vec::new();
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            let member = if rule.members.is_empty() {
+                signer_group.as_deref() == Some(rule.group.as_str())
+            } else {
+                rule.members.contains(&signer)
+            };
+            if !member {
+                continue;
+            }
+            let key = (index, parsed_data.token_mint);
+            let recent = {
+                let mut buys = self.buys.lock().unwrap();
+                let list = buys.entry(key).or_default();
+                list.retain(|b| now - b.at < rule.window_secs);
+                list.push(Buy { at: now, wallet: parsed_data.signer, sol: parsed_data.sol_amount.unwrap_or(0.0) });
+                list.clone()
+            };
+
+            let mut per_wallet: Vec<(Pubkey, f64)> = Vec::new();
+            for buy in &recent {
+                match per_wallet.iter_mut().find(|(wallet, _)| *wallet == buy.wallet) {
+                    Some((_, sol)) => *sol += buy.sol,
+                    None => per_wallet.push((buy.wallet, buy.sol)),
+                }
+            }
+            if per_wallet.len() < rule.min_wallets {
+                continue;
+            }
+            {
+                let mut fired = self.fired.lock().unwrap();
+                if fired.get(&key).is_some_and(|at| now - at < rule.window_secs) {
+                    continue;
+                }
+                fired.insert(key, now);
+            }
+
+            let members = if rule.members.is_empty() { groups.group_size(&rule.group) } else { rule.members.len() };
+            let wallets: Vec<(String, f64)> = per_wallet.iter()
+                .map(|(wallet, sol)| (groups.label(wallet).unwrap_or_else(|| wallet.to_string()), *sol))
+                .collect();
+            hits.push(ConsensusHit {
+                group: rule.group.clone(),
+                mint: parsed_data.token_mint,
+                total_sol: wallets.iter().map(|(_, sol)| sol).sum(),
+                wallets,
+                members,
+                min_wallets: rule.min_wallets,
+                span_secs: now - recent.first().map(|b| b.at).unwrap_or(now),
+            });
+        }
+        hits
+    }
+
+    /// Drop buy windows that have gone quiet
+    pub fn prune(&self) {
+        let now = self.clock.now().timestamp();
+        let window = |index: usize| self.rules.get(index).map(|r| r.window_secs).unwrap_or(0);
+        self.buys.lock().unwrap().retain(|(index, _), list| list.last().is_some_and(|b| now - b.at < window(*index)));
+        self.fired.lock().unwrap().retain(|(index, _), at| now - *at < window(*index));
+    }
+
+    /// Buy windows held in memory
+    pub fn len(&self) -> usize {
+        self.buys.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,287 @@
+/*!
+# Insider Buy Detection
+
+Flags launches where significant buys land within seconds of the launch from wallets funded by
+the creator, or by the same wallet that funded the creator. Such buyers saw the token before the
+public did; the launch is tagged as likely insider-driven in alerts and in the risk score.
+
+A wallet's funding source is the fee payer of the oldest transaction touching it, which for a
+fresh wallet is the transfer that created it. Wallets with a history longer than a few pages of
+signatures are treated as unfunded: established wallets are not what insider launches use.
+
+Only launches seen from their start are checked: the first observed trade has to be on the
+pump.fun bonding curve at `INSIDER_MAX_CURVE_PCT` or less, and it sets the launch time.
+
+## Environment Variables
+
+- `INSIDER_DETECTION_ENABLED`: Check early buyers' funding sources (default: `true`)
+- `INSIDER_WINDOW_SECS`: Buys this soon after the launch are checked (default: `10`)
+- `INSIDER_MIN_BUY_SOL`: Smaller buys are ignored (default: `0.5`)
+- `INSIDER_MAX_CURVE_PCT`: Bonding curve completion (%) up to which a first sighting counts as
+  the launch (default: `5`)
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use anchor_client::solana_client::rpc_config::RpcTransactionConfig;
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::signature::Signature;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::UiTransactionEncoding;
+use tokio::sync::Mutex;
+
+use crate::processor::risk_score;
+use crate::processor::transaction_parser::{ParsedData, SwapType};
+
+/// Signature pages (1000 each) walked back to find a wallet's first transaction
+const MAX_SIGNATURE_PAGES: usize = 3;
+
+#[derive(Clone, Debug)]
+pub struct InsiderConfig {
+    pub enabled: bool,
+    pub window_secs: u64,
+    pub min_buy_sol: f64,
+    pub max_curve_pct: f64,
+}
+
+impl Default for InsiderConfig {
+    fn default() -> Self {
+        Self { enabled: true, window_secs: 10, min_buy_sol: 0.5, max_curve_pct: 5.0 }
+    }
+}
+
+impl InsiderConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            enabled: var("INSIDER_DETECTION_ENABLED")
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(defaults.enabled),
+            window_secs: var("INSIDER_WINDOW_SECS").and_then(|v| v.parse().ok()).unwrap_or(defaults.window_secs),
+            min_buy_sol: var("INSIDER_MIN_BUY_SOL").and_then(|v| v.parse().ok()).unwrap_or(defaults.min_buy_sol),
+            max_curve_pct: var("INSIDER_MAX_CURVE_PCT").and_then(|v| v.parse().ok()).unwrap_or(defaults.max_curve_pct),
+        }
+    }
+}
+
+/// Who funded a wallet
+#[async_trait]
+pub trait FundingLookup: Send + Sync {
+    /// The wallet that paid for the oldest transaction touching `wallet`, None when the wallet
+    /// paid for it itself or its history is too long to walk
+    async fn funding_source(&self, wallet: &Pubkey) -> Result<Option<Pubkey>>;
+}
+
+pub struct RpcFundingLookup {
+    rpc_client: Arc<RpcClient>,
+}
+
+impl RpcFundingLookup {
+    pub fn new(rpc_client: Arc<RpcClient>) -> Self {
+        Self { rpc_client }
+    }
+}
+
+/// Oldest transaction touching `wallet` and its block time, None when its history is longer
+/// than `MAX_SIGNATURE_PAGES` or empty
+pub async fn oldest_transaction(rpc_client: &RpcClient, wallet: &Pubkey) -> Result<Option<(Signature, Option<i64>)>> {
+    let mut before = None;
+    let mut oldest = None;
+    for _ in 0..MAX_SIGNATURE_PAGES {
+        let page = rpc_client
+            .get_signatures_for_address_with_config(wallet, GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: None,
+                limit: Some(1000),
+                commitment: Some(CommitmentConfig::confirmed()),
+            })
+            .await?;
+        if let Some(last) = page.last() {
+            let signature = Signature::from_str(&last.signature)?;
+            oldest = Some((signature, last.block_time));
+            before = Some(signature);
+        }
+        if page.len() < 1000 {
+            return Ok(oldest);
+        }
+    }
+    Ok(None)
+}
+
+/// Fee payer of a transaction
+pub async fn fee_payer(rpc_client: &RpcClient, signature: &Signature) -> Result<Option<Pubkey>> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let encoded = rpc_client.get_transaction_with_config(signature, config).await?;
+    let transaction = encoded.transaction.transaction.decode()
+        .ok_or_else(|| anyhow!("{} could not be decoded", signature))?;
+    Ok(transaction.message.static_account_keys().first().copied())
+}
+
+#[async_trait]
+impl FundingLookup for RpcFundingLookup {
+    async fn funding_source(&self, wallet: &Pubkey) -> Result<Option<Pubkey>> {
+        let oldest = match oldest_transaction(&self.rpc_client, wallet).await? {
+            Some((signature, _)) => signature,
+            None => return Ok(None),
+        };
+        let payer = fee_payer(&self.rpc_client, &oldest).await?;
+        Ok(payer.filter(|payer| payer != wallet))
+    }
+}
+
+/// A buy from a wallet that shares the creator's funding
+#[derive(Clone, Debug, PartialEq)]
+pub struct InsiderFinding {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub sol: f64,
+    pub seconds_after_launch: u64,
+    /// The creator itself or the wallet that funded both
+    pub funder: Pubkey,
+    /// Insider wallets and SOL seen on this launch so far, this one included
+    pub insider_wallets: usize,
+    pub insider_sol: f64,
+}
+
+#[derive(Clone, Debug)]
+struct LaunchWatch {
+    launched_at: u64,
+    /// False when the token was first seen mid-life; nothing is checked then
+    fresh: bool,
+    creator: Option<Pubkey>,
+    insiders: HashSet<Pubkey>,
+    insider_sol: f64,
+}
+
+pub struct InsiderDetector {
+    config: InsiderConfig,
+    lookup: Arc<dyn FundingLookup>,
+    launches: Mutex<HashMap<Pubkey, LaunchWatch>>,
+    funding: DashMap<Pubkey, Option<Pubkey>>,
+}
+
+impl InsiderDetector {
+    pub fn new(config: InsiderConfig, lookup: Arc<dyn FundingLookup>) -> Self {
+        Self { config, lookup, launches: Mutex::new(HashMap::new()), funding: DashMap::new() }
+    }
+
+    pub fn from_env(rpc_client: Arc<RpcClient>) -> Self {
+        Self::new(InsiderConfig::from_env(), Arc::new(RpcFundingLookup::new(rpc_client)))
+    }
+
+    /// Cached funding source; lookup failures are not cached so a later buy can retry
+    async fn funding_source(&self, wallet: &Pubkey) -> Option<Pubkey> {
+        if let Some(known) = self.funding.get(wallet) {
+            return *known;
+        }
+        match self.lookup.funding_source(wallet).await {
+            Ok(source) => {
+                self.funding.insert(*wallet, source);
+                source
+            }
+            Err(e) => {
+                eprintln!("Funding lookup for {} failed: {}", wallet, e);
+                None
+            }
+        }
+    }
+
+    /// Check a trade; returns a finding when it is an insider buy
+    pub async fn observe(&self, parsed_data: &ParsedData) -> Option<InsiderFinding> {
+        if !self.config.enabled {
+            return None;
+        }
+        let (launched_at, creator) = {
+            let mut launches = self.launches.lock().await;
+            let watch = launches.entry(parsed_data.token_mint).or_insert_with(|| LaunchWatch {
+                launched_at: parsed_data.timestamp,
+                fresh: parsed_data.dex_name == "PumpFun"
+                    && parsed_data.bonding_curve_progress.is_some_and(|p| p <= self.config.max_curve_pct),
+                creator: None,
+                insiders: HashSet::new(),
+                insider_sol: 0.0,
+            });
+            if watch.creator.is_none() {
+                watch.creator = parsed_data.coin_creator;
+            }
+            if !watch.fresh {
+                return None;
+            }
+            (watch.launched_at, watch.creator)
+        };
+
+        let sol = parsed_data.sol_amount.unwrap_or(0.0);
+        let seconds_after_launch = parsed_data.timestamp.saturating_sub(launched_at);
+        let creator = creator?;
+        if parsed_data.swap_type != SwapType::Buy
+            || seconds_after_launch > self.config.window_secs
+            || sol < self.config.min_buy_sol
+            || parsed_data.signer == creator
+        {
+            return None;
+        }
+
+        let buyer_funder = self.funding_source(&parsed_data.signer).await?;
+        let funder = if buyer_funder == creator {
+            creator
+        } else if self.funding_source(&creator).await == Some(buyer_funder) {
+            buyer_funder
+        } else {
+            return None;
+        };
+
+        let (insider_wallets, insider_sol) = {
+            let mut launches = self.launches.lock().await;
+            let watch = launches.get_mut(&parsed_data.token_mint)?;
+            if watch.insiders.insert(parsed_data.signer) {
+                watch.insider_sol += sol;
+            }
+            (watch.insiders.len(), watch.insider_sol)
+        };
+        risk_score::update_factors(&parsed_data.token_mint.to_string(), |f| {
+            f.insider_wallets = insider_wallets as u32;
+            f.insider_sol = insider_sol;
+        });
+
+        Some(InsiderFinding {
+            mint: parsed_data.token_mint,
+            wallet: parsed_data.signer,
+            sol,
+            seconds_after_launch,
+            funder,
+            insider_wallets,
+            insider_sol,
+        })
+    }
+
+    pub async fn launch_count(&self) -> usize {
+        self.launches.lock().await.len()
+    }
+
+    pub fn funding_cache_size(&self) -> usize {
+        self.funding.len()
+    }
+
+    /// Drop launches no longer tracked; funding sources are cheap to look up again, so the
+    /// cache is cleared along with them
+    pub async fn retain(&self, keep: impl Fn(&Pubkey) -> bool) {
+        self.launches.lock().await.retain(|mint, _| keep(mint));
+        self.funding.clear();
+    }
+}
+
+/// Whether a launch was tagged as insider-driven
+pub fn is_insider_launch(mint: &Pubkey) -> bool {
+    risk_score::get_factors(&mint.to_string()).insider_wallets > 0
+}
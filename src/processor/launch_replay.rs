@@ -0,0 +1,325 @@
+//! Launch replays: the recorded life of a token told as a timeline once it is over.
+//!
+//! Every trade and liquidity event of a tracked token is kept in a session. A session ends when
+//! the token graduates (its pump.fun bonding curve completes, or a PumpSwap pool is created for a
+//! pump.fun launch) or dies (trading stopped for `LAUNCH_REPLAY_IDLE_MINS` after the price fell
+//! `LAUNCH_REPLAY_DEAD_DROP_PCT` from its peak, or liquidity was pulled). The finished session is
+//! turned into a post-mortem narrative: first buy, peak, largest sells and any liquidity pull.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use chrono::{DateTime, Duration, Utc};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::processor::transaction_parser::{ParsedData, SwapType};
+
+#[derive(Clone, Debug)]
+pub struct LaunchReplayConfig {
+    pub enabled: bool,
+    /// Drop from peak price (%) after which a quiet token counts as dead
+    pub dead_drop_pct: f64,
+    /// Minutes without trades before a token can count as dead
+    pub idle: Duration,
+    /// Sessions with fewer trades are dropped without a report
+    pub min_trades: usize,
+    /// Events kept per session; the earliest ones are kept, later trades only update the totals
+    pub max_events: usize,
+}
+
+impl Default for LaunchReplayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dead_drop_pct: 80.0,
+            idle: Duration::minutes(15),
+            min_trades: 5,
+            max_events: 500,
+        }
+    }
+}
+
+impl LaunchReplayConfig {
+    /// LAUNCH_REPLAY_ENABLED, LAUNCH_REPLAY_DEAD_DROP_PCT, LAUNCH_REPLAY_IDLE_MINS,
+    /// LAUNCH_REPLAY_MIN_TRADES and LAUNCH_REPLAY_MAX_EVENTS
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            enabled: var("LAUNCH_REPLAY_ENABLED")
+                .and_then(|v| v.to_lowercase().parse().ok())
+                .unwrap_or(defaults.enabled),
+            dead_drop_pct: var("LAUNCH_REPLAY_DEAD_DROP_PCT").and_then(|v| v.parse().ok()).unwrap_or(defaults.dead_drop_pct),
+            idle: var("LAUNCH_REPLAY_IDLE_MINS")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::minutes)
+                .unwrap_or(defaults.idle),
+            min_trades: var("LAUNCH_REPLAY_MIN_TRADES").and_then(|v| v.parse().ok()).unwrap_or(defaults.min_trades),
+            max_events: var("LAUNCH_REPLAY_MAX_EVENTS").and_then(|v| v.parse().ok()).unwrap_or(defaults.max_events),
+        }
+    }
+}
+
+/// One recorded trade or liquidity event
+#[derive(Clone, Debug)]
+pub struct LaunchEvent {
+    pub at: DateTime<Utc>,
+    pub swap_type: SwapType,
+    pub wallet: Pubkey,
+    pub sol: f64,
+    pub price: Option<f64>,
+    /// Pool SOL after the event, when known
+    pub liquidity: Option<f64>,
+}
+
+/// How a launch ended
+#[derive(Clone, Debug, PartialEq)]
+pub enum LaunchOutcome {
+    Graduated,
+    /// Price collapsed and trading stopped
+    Died,
+    /// Liquidity was withdrawn; the share of the pool removed (%)
+    LiquidityPulled(f64),
+}
+
+impl LaunchOutcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LaunchOutcome::Graduated => "GRADUATED",
+            LaunchOutcome::Died => "DIED",
+            LaunchOutcome::LiquidityPulled(_) => "RUGGED (liquidity pulled)",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LaunchSession {
+    pub mint: Pubkey,
+    pub name: Option<String>,
+    pub dex_name: String,
+    pub events: Vec<LaunchEvent>,
+    pub trades: usize,
+    pub volume_sol: f64,
+    pub peak_price: Option<(f64, DateTime<Utc>)>,
+    pub last_trade: DateTime<Utc>,
+    pub graduated: bool,
+    /// Largest single liquidity removal, as a share of the pool (%)
+    pub largest_pull_pct: f64,
+}
+
+impl LaunchSession {
+    fn new(parsed_data: &ParsedData, at: DateTime<Utc>) -> Self {
+        Self {
+            mint: parsed_data.token_mint,
+            name: parsed_data.token_name.clone(),
+            dex_name: parsed_data.dex_name.clone(),
+            events: Vec::new(),
+            trades: 0,
+            volume_sol: 0.0,
+            peak_price: None,
+            last_trade: at,
+            graduated: false,
+            largest_pull_pct: 0.0,
+        }
+    }
+
+    pub fn first_price(&self) -> Option<f64> {
+        self.events.iter().filter(|e| is_trade(&e.swap_type)).find_map(|e| e.price)
+    }
+
+    pub fn last_price(&self) -> Option<f64> {
+        self.events.iter().rev().filter(|e| is_trade(&e.swap_type)).find_map(|e| e.price)
+    }
+
+    /// How the session ended as of `now`, None while the token is still live
+    pub fn outcome(&self, config: &LaunchReplayConfig, liquidity_pull_pct: f64, now: DateTime<Utc>) -> Option<LaunchOutcome> {
+        if self.graduated {
+            return Some(LaunchOutcome::Graduated);
+        }
+        if now - self.last_trade < config.idle {
+            return None;
+        }
+        if self.largest_pull_pct >= liquidity_pull_pct {
+            return Some(LaunchOutcome::LiquidityPulled(self.largest_pull_pct));
+        }
+        match (self.peak_price, self.last_price()) {
+            (Some((peak, _)), Some(last)) if peak > 0.0 && (peak - last) / peak * 100.0 >= config.dead_drop_pct => {
+                Some(LaunchOutcome::Died)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn is_trade(swap_type: &SwapType) -> bool {
+    matches!(swap_type, SwapType::Buy | SwapType::Sell)
+}
+
+/// Sessions of every token currently being recorded
+pub struct LaunchRecorder {
+    config: LaunchReplayConfig,
+    sessions: HashMap<Pubkey, LaunchSession>,
+}
+
+impl LaunchRecorder {
+    pub fn new(config: LaunchReplayConfig) -> Self {
+        Self { config, sessions: HashMap::new() }
+    }
+
+    pub fn config(&self) -> &LaunchReplayConfig {
+        &self.config
+    }
+
+    pub fn session_count(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn session(&self, mint: &Pubkey) -> Option<&LaunchSession> {
+        self.sessions.get(mint)
+    }
+
+    pub fn record(&mut self, parsed_data: &ParsedData, at: DateTime<Utc>) {
+        if !self.config.enabled {
+            return;
+        }
+        let max_events = self.config.max_events;
+        let session = self.sessions
+            .entry(parsed_data.token_mint)
+            .or_insert_with(|| LaunchSession::new(parsed_data, at));
+        if session.name.is_none() {
+            session.name = parsed_data.token_name.clone();
+        }
+
+        let sol = parsed_data.sol_amount.unwrap_or(0.0);
+        match parsed_data.swap_type {
+            SwapType::Buy | SwapType::Sell => {
+                session.trades += 1;
+                session.volume_sol += sol;
+                session.last_trade = at;
+                if let Some(price) = parsed_data.token_price.filter(|p| *p > 0.0) {
+                    if session.peak_price.map_or(true, |(peak, _)| price > peak) {
+                        session.peak_price = Some((price, at));
+                    }
+                }
+            }
+            SwapType::RemoveLiquidity => {
+                let before = parsed_data.liquidity.unwrap_or(0.0) + sol;
+                if before > 0.0 {
+                    session.largest_pull_pct = session.largest_pull_pct.max(sol / before * 100.0);
+                }
+            }
+            // A PumpSwap pool for a pump.fun launch is its migration
+            SwapType::PoolCreate if parsed_data.dex_name == "PumpSwap" && session.dex_name == "PumpFun" => {
+                session.graduated = true;
+            }
+            SwapType::PoolCreate | SwapType::AddLiquidity => {}
+        }
+        if parsed_data.bonding_curve_progress.is_some_and(|p| p >= 100.0) {
+            session.graduated = true;
+        }
+
+        // Keep the opening of the launch and anything structurally important later on
+        let notable = !is_trade(&parsed_data.swap_type) || session.events.len() < max_events;
+        if notable {
+            if session.events.len() >= max_events {
+                if let Some(pos) = session.events.iter().rposition(|e| is_trade(&e.swap_type)) {
+                    session.events.remove(pos);
+                }
+            }
+            session.events.push(LaunchEvent {
+                at,
+                swap_type: parsed_data.swap_type.clone(),
+                wallet: parsed_data.signer,
+                sol,
+                price: parsed_data.token_price,
+                liquidity: parsed_data.liquidity,
+            });
+        }
+    }
+
+    /// Remove and return the sessions that ended; sessions too small to be worth a report are
+    /// dropped
+    pub fn take_finished(&mut self, liquidity_pull_pct: f64, now: DateTime<Utc>) -> Vec<(LaunchSession, LaunchOutcome)> {
+        let ended: Vec<(Pubkey, LaunchOutcome)> = self.sessions
+            .values()
+            .filter_map(|s| s.outcome(&self.config, liquidity_pull_pct, now).map(|o| (s.mint, o)))
+            .collect();
+        ended
+            .into_iter()
+            .filter_map(|(mint, outcome)| {
+                let session = self.sessions.remove(&mint)?;
+                (session.trades >= self.config.min_trades).then_some((session, outcome))
+            })
+            .collect()
+    }
+
+    pub fn retain(&mut self, keep: impl Fn(&Pubkey) -> bool) {
+        self.sessions.retain(|mint, _| keep(mint));
+    }
+}
+
+fn offset(start: DateTime<Utc>, at: DateTime<Utc>) -> String {
+    let secs = (at - start).num_seconds().max(0);
+    if secs >= 3600 {
+        format!("+{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    } else {
+        format!("+{}m{:02}s", secs / 60, secs % 60)
+    }
+}
+
+fn short(wallet: &Pubkey) -> String {
+    let s = wallet.to_string();
+    format!("{}…{}", &s[..4], &s[s.len() - 4..])
+}
+
+/// The post-mortem narrative of a finished launch
+pub fn format_timeline(session: &LaunchSession, outcome: &LaunchOutcome) -> String {
+    let start = session.events.first().map(|e| e.at).unwrap_or(session.last_trade);
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        "🏁 Outcome: {} after {} ({} trades, {:.2} SOL volume)",
+        outcome.label(),
+        offset(start, session.last_trade).trim_start_matches('+'),
+        session.trades,
+        session.volume_sol
+    );
+    text.push_str("\n🕰️ Timeline:\n");
+
+    if let Some(first) = session.events.iter().find(|e| e.swap_type == SwapType::Buy) {
+        let _ = writeln!(text, "  {} First buy: {} bought {:.2} SOL", offset(start, first.at), short(&first.wallet), first.sol);
+    }
+    if let (Some((peak, at)), Some(first_price)) = (session.peak_price, session.first_price()) {
+        let _ = writeln!(text, "  {} Peak: {:.2}x the first price", offset(start, at), peak / first_price);
+    }
+
+    let mut sells: Vec<&LaunchEvent> = session.events.iter().filter(|e| e.swap_type == SwapType::Sell).collect();
+    sells.sort_by(|a, b| b.sol.partial_cmp(&a.sol).unwrap_or(std::cmp::Ordering::Equal));
+    let mut largest: Vec<&LaunchEvent> = sells.into_iter().take(3).collect();
+    largest.sort_by_key(|e| e.at);
+    for sell in largest {
+        let _ = writeln!(text, "  {} Large sell: {} sold {:.2} SOL", offset(start, sell.at), short(&sell.wallet), sell.sol);
+    }
+
+    for pull in session.events.iter().filter(|e| e.swap_type == SwapType::RemoveLiquidity) {
+        let _ = writeln!(
+            text,
+            "  {} Liquidity removed: {:.2} SOL by {}{}",
+            offset(start, pull.at),
+            pull.sol,
+            short(&pull.wallet),
+            pull.liquidity.map(|l| format!(", {:.2} SOL left", l)).unwrap_or_default()
+        );
+    }
+
+    if let (Some(first), Some(last)) = (session.first_price(), session.last_price()) {
+        let _ = writeln!(text, "  {} Last trade: {:+.1}% vs the first price", offset(start, session.last_trade), (last - first) / first * 100.0);
+    }
+
+    text.push_str("\n📚 Lesson: ");
+    text.push_str(match outcome {
+        LaunchOutcome::Graduated => "graduation is a liquidity event, not a guarantee; compare how early buyers behaved around the migration.",
+        LaunchOutcome::Died => "most launches fade; note how quickly the largest sells followed the peak.",
+        LaunchOutcome::LiquidityPulled(_) => "a pool whose liquidity can be withdrawn at once can go to zero in a single transaction.",
+    });
+    text
+}
@@ -0,0 +1,192 @@
+//! Launch analytics across the monitored universe: when tokens launch, how long they take to
+//! peak and how many are still alive an hour later.
+//!
+//! A launch is a pump.fun token first seen on its bonding curve below
+//! `MAX_LAUNCH_PROGRESS_PCT` completion; tokens first seen later in life have no usable launch
+//! time and are not counted. Each launch keeps a small record (launch time and price, running
+//! peak, whether it survived the first hour) independent of the per-token tracking state, so the
+//! weekly figures cover tokens that were evicted or stopped trading long ago.
+//!
+//! A launch survived its first hour when it still traded after the hour mark, at a price not
+//! more than the launch replay's dead drop (`LAUNCH_REPLAY_DEAD_DROP_PCT`) below its peak so far.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use chrono::{DateTime, Duration, Timelike, Utc};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::processor::transaction_parser::{ParsedData, SwapType};
+
+/// Bonding curve completion (%) below which a first sighting counts as the launch
+pub const MAX_LAUNCH_PROGRESS_PCT: f64 = 10.0;
+/// Launch records kept, as long as the weekly report looks back
+const RETENTION_DAYS: i64 = 7;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LaunchRecord {
+    pub launched_at: DateTime<Utc>,
+    pub peak_price: f64,
+    pub peak_at: DateTime<Utc>,
+    /// None until the first trade after the first hour
+    pub survived_first_hour: Option<bool>,
+}
+
+/// Launch figures over a period
+#[derive(Clone, Debug, PartialEq)]
+pub struct LaunchSummary {
+    pub days: i64,
+    pub launches: usize,
+    /// Launches per UTC hour of day
+    pub by_hour: [usize; 24],
+    /// Over launches at least an hour old
+    pub median_time_to_peak: Option<Duration>,
+    pub survived: usize,
+    /// Launches at least an hour old
+    pub decided: usize,
+}
+
+impl LaunchSummary {
+    pub fn launches_per_hour(&self) -> f64 {
+        self.launches as f64 / (self.days.max(1) * 24) as f64
+    }
+
+    pub fn survival_pct(&self) -> Option<f64> {
+        (self.decided > 0).then(|| self.survived as f64 / self.decided as f64 * 100.0)
+    }
+
+    /// The `n` UTC hours with most launches, busiest first
+    pub fn busiest_hours(&self, n: usize) -> Vec<(u32, usize)> {
+        let mut hours: Vec<(u32, usize)> = self.by_hour.iter().enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(hour, count)| (hour as u32, *count))
+            .collect();
+        hours.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        hours.truncate(n);
+        hours
+    }
+}
+
+pub struct LaunchStats {
+    records: HashMap<Pubkey, LaunchRecord>,
+    /// Drop from peak (%) after which a launch counts as dead
+    dead_drop_pct: f64,
+}
+
+impl LaunchStats {
+    pub fn new(dead_drop_pct: f64) -> Self {
+        Self { records: HashMap::new(), dead_drop_pct }
+    }
+
+    pub fn record(&mut self, parsed_data: &ParsedData, at: DateTime<Utc>) {
+        if !matches!(parsed_data.swap_type, SwapType::Buy | SwapType::Sell) {
+            return;
+        }
+        let price = match parsed_data.token_price.filter(|p| *p > 0.0) {
+            Some(price) => price,
+            None => return,
+        };
+        let record = match self.records.get_mut(&parsed_data.token_mint) {
+            Some(record) => record,
+            None => {
+                if parsed_data.bonding_curve_progress.is_some_and(|p| p < MAX_LAUNCH_PROGRESS_PCT) {
+                    self.records.insert(parsed_data.token_mint, LaunchRecord {
+                        launched_at: at,
+                        peak_price: price,
+                        peak_at: at,
+                        survived_first_hour: None,
+                    });
+                }
+                return;
+            }
+        };
+        if record.survived_first_hour.is_none() && at >= record.launched_at + Duration::hours(1) {
+            record.survived_first_hour = Some(price >= record.peak_price * (1.0 - self.dead_drop_pct / 100.0));
+        }
+        if price > record.peak_price {
+            record.peak_price = price;
+            record.peak_at = at;
+        }
+    }
+
+    pub fn get(&self, mint: &Pubkey) -> Option<&LaunchRecord> {
+        self.records.get(mint)
+    }
+
+    /// Launches of the last `days`; launches over an hour old without a trade since count as dead
+    pub fn summary(&self, days: i64, now: DateTime<Utc>) -> LaunchSummary {
+        let since = now - Duration::days(days);
+        let hour_ago = now - Duration::hours(1);
+        let mut by_hour = [0usize; 24];
+        let mut times_to_peak = Vec::new();
+        let (mut launches, mut survived, mut decided) = (0, 0, 0);
+        for record in self.records.values().filter(|r| r.launched_at > since) {
+            launches += 1;
+            by_hour[record.launched_at.hour() as usize] += 1;
+            if record.launched_at <= hour_ago {
+                decided += 1;
+                if record.survived_first_hour == Some(true) {
+                    survived += 1;
+                }
+                times_to_peak.push(record.peak_at - record.launched_at);
+            }
+        }
+        times_to_peak.sort();
+        let median_time_to_peak = match times_to_peak.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(times_to_peak[n / 2]),
+            n => Some((times_to_peak[n / 2 - 1] + times_to_peak[n / 2]) / 2),
+        };
+        LaunchSummary { days, launches, by_hour, median_time_to_peak, survived, decided }
+    }
+
+    /// Forget launches older than the report period
+    pub fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::days(RETENTION_DAYS);
+        self.records.retain(|_, record| record.launched_at > cutoff);
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.num_seconds().max(0);
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {:02}m", secs / 3600, secs % 3600 / 60)
+    }
+}
+
+/// Launch section of the weekly report
+pub fn format_launch_stats(summary: &LaunchSummary) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "🚀 LAUNCHES (last {} days, pump.fun tokens seen from launch)", summary.days);
+    if summary.launches == 0 {
+        let _ = writeln!(out, "No launches recorded");
+        return out;
+    }
+    let _ = writeln!(out, "Launches: {} ({:.1} per hour)", summary.launches, summary.launches_per_hour());
+    let busiest: Vec<String> = summary.busiest_hours(3).iter()
+        .map(|(hour, count)| format!("{:02}:00 ({})", hour, count))
+        .collect();
+    let _ = writeln!(out, "Busiest launch hours (UTC): {}", busiest.join(", "));
+    match summary.median_time_to_peak {
+        Some(median) => { let _ = writeln!(out, "Median time to peak: {}", format_duration(median)); }
+        None => { let _ = writeln!(out, "Median time to peak: n/a (no launch older than an hour yet)"); }
+    }
+    match summary.survival_pct() {
+        Some(pct) => {
+            let _ = writeln!(out, "Survival after 1h: {:.1}% ({} of {})", pct, summary.survived, summary.decided);
+        }
+        None => { let _ = writeln!(out, "Survival after 1h: n/a"); }
+    }
+    out
+}
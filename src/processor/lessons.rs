@@ -0,0 +1,415 @@
+/*!
+# Lessons
+
+Optional explanations and quiz questions offered next to alerts. Graduation alerts carry a
+"📚 Learn" button about bonding curves, liquidity alerts one about impermanent loss and price
+impact alerts one about MEV; `/learn [topic]` opens the same lessons without an alert.
+
+A lesson is a short thread read one part at a time ("Next ▶") followed by quiz questions
+answered with inline buttons. Each user gets their own reading position, pending question and
+score, so several people in the alert chat can follow lessons at once; a user idle for
+`LESSON_IDLE_MIN` starts over.
+
+## Environment Variables
+
+- `LESSONS_ENABLED`: Offer lessons on alerts and answer `/learn` (default: `true`)
+- `LESSON_IDLE_MIN`: Minutes of inactivity after which a user's lesson state is dropped (default: `30`)
+*/
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use chrono::Duration;
+use lazy_static::lazy_static;
+
+use crate::common::clock::{system_clock, Clock};
+
+lazy_static! {
+    static ref SHARED_LESSONS: Arc<LessonSessions> = Arc::new(LessonSessions::new(LessonConfig::from_env()));
+}
+
+/// Callback data prefix of every lesson button
+pub const LESSON_CALLBACK_PREFIX: &str = "learn:";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Topic {
+    BondingCurve,
+    ImpermanentLoss,
+    Mev,
+}
+
+pub struct Question {
+    pub prompt: &'static str,
+    pub options: &'static [&'static str],
+    pub answer: usize,
+    pub explanation: &'static str,
+}
+
+const BONDING_CURVE_PARTS: &[&str] = &[
+    "A pump.fun token starts on a bonding curve: a contract that is the only buyer and seller. \
+    The price comes from a formula over the tokens the curve still holds, not from an order book.",
+    "The curve is constant-product over virtual reserves (about 30 SOL against 1.073B tokens at \
+    launch). Every buy takes tokens out and puts SOL in, so the next token costs more; sells walk \
+    back down the same curve.",
+    "Once about 85 real SOL sit in the curve it completes (\"graduates\"): curve trading stops and \
+    the liquidity migrates to a PumpSwap pool. Buyers near the end pay the highest curve prices, \
+    and the minutes around migration are usually the most volatile.",
+];
+
+const BONDING_CURVE_QUIZ: &[Question] = &[
+    Question {
+        prompt: "What sets the price of a token on a pump.fun bonding curve?",
+        options: &["An order book of bids and asks", "A formula over the curve's reserves", "The creator, at launch"],
+        answer: 1,
+        explanation: "The curve quotes every trade from its reserves; each buy moves the price up the curve.",
+    },
+    Question {
+        prompt: "What happens when a bonding curve completes?",
+        options: &["The token is delisted", "Its liquidity migrates to a PumpSwap pool", "The creator receives the SOL"],
+        answer: 1,
+        explanation: "Graduation moves the liquidity into an AMM pool, where trading continues without the curve.",
+    },
+];
+
+const IMPERMANENT_LOSS_PARTS: &[&str] = &[
+    "Liquidity providers deposit both sides of a pool, e.g. SOL and a token. The pool keeps x·y \
+    constant, so when the market price moves, arbitrageurs trade against the pool until its price \
+    matches.",
+    "That rebalancing leaves the LP with more of the asset that fell and less of the one that rose. \
+    Against simply holding, a 2x price move costs the LP about 5.7% and a 5x move about 25.5%.",
+    "The loss is only \"impermanent\" if the price comes back. Fees can offset it, but on a token \
+    that drops 90% the LP ends up holding mostly the token that collapsed. Liquidity removals in \
+    alerts are LPs walking away from that risk.",
+];
+
+const IMPERMANENT_LOSS_QUIZ: &[Question] = &[
+    Question {
+        prompt: "The token in your SOL/token pool doubles in price. Against holding, your position is…",
+        options: &["Worth the same", "About 5.7% worse", "About 50% better"],
+        answer: 1,
+        explanation: "At a 2x move an x·y=k position trails holding by 2√2/3 − 1 ≈ −5.7%.",
+    },
+    Question {
+        prompt: "Why does an LP end up holding more of the asset that fell?",
+        options: &[
+            "Arbitrageurs sell it into the pool until the prices match",
+            "The DEX pays fees in that asset",
+            "The creator refills the pool",
+        ],
+        answer: 0,
+        explanation: "Arbitrage moves the pool to the market price by selling the cheaper asset into it.",
+    },
+];
+
+const MEV_PARTS: &[&str] = &[
+    "MEV (maximal extractable value) is profit taken by choosing the order of transactions in a \
+    block. On Solana it is mostly captured through Jito bundles, which leaders include for a tip.",
+    "The classic case is the sandwich: a searcher sees a large swap with loose slippage, buys just \
+    before it and sells right after it. The victim fills at the worse price the searcher created, \
+    and the difference is the searcher's profit.",
+    "Large orders in shallow pools, the ones behind high price impact alerts, are the best targets. \
+    Tight slippage limits, smaller orders and private submission cap what a sandwich can take.",
+];
+
+const MEV_QUIZ: &[Question] = &[
+    Question {
+        prompt: "In a sandwich attack, when does the searcher buy?",
+        options: &["Right before the victim's buy", "Right after the victim's buy", "In the next block"],
+        answer: 0,
+        explanation: "Buying first pushes the price up; the victim buys higher and the searcher sells into it.",
+    },
+    Question {
+        prompt: "What limits what a sandwich can take from your swap?",
+        options: &["A higher priority fee", "A tight slippage tolerance", "Signing with a fresh wallet"],
+        answer: 1,
+        explanation: "A searcher can only push your fill down to your minimum output; tight slippage makes the swap fail instead.",
+    },
+];
+
+impl Topic {
+    pub const ALL: [Topic; 3] = [Topic::BondingCurve, Topic::ImpermanentLoss, Topic::Mev];
+
+    /// Short key used in callback data and `/learn`
+    pub fn key(&self) -> &'static str {
+        match self {
+            Topic::BondingCurve => "curve",
+            Topic::ImpermanentLoss => "il",
+            Topic::Mev => "mev",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Topic::BondingCurve => "Bonding curves",
+            Topic::ImpermanentLoss => "Impermanent loss",
+            Topic::Mev => "MEV and sandwiches",
+        }
+    }
+
+    pub fn parts(&self) -> &'static [&'static str] {
+        match self {
+            Topic::BondingCurve => BONDING_CURVE_PARTS,
+            Topic::ImpermanentLoss => IMPERMANENT_LOSS_PARTS,
+            Topic::Mev => MEV_PARTS,
+        }
+    }
+
+    pub fn quiz(&self) -> &'static [Question] {
+        match self {
+            Topic::BondingCurve => BONDING_CURVE_QUIZ,
+            Topic::ImpermanentLoss => IMPERMANENT_LOSS_QUIZ,
+            Topic::Mev => MEV_QUIZ,
+        }
+    }
+}
+
+impl FromStr for Topic {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace(['-', ' '], "_").as_str() {
+            "curve" | "bonding" | "bonding_curve" | "bonding_curves" => Ok(Topic::BondingCurve),
+            "il" | "impermanent_loss" => Ok(Topic::ImpermanentLoss),
+            "mev" | "sandwich" | "sandwiches" => Ok(Topic::Mev),
+            other => Err(anyhow::anyhow!("Unknown topic '{}', expected curve, il or mev", other)),
+        }
+    }
+}
+
+/// What a lesson button asks for
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LessonAction {
+    /// Offer the explanation or the quiz for a topic
+    Menu(Topic),
+    /// Start the explanation thread
+    Read(Topic),
+    /// Next part of the thread being read
+    Next,
+    /// Next quiz question of a topic
+    Quiz(Topic),
+    Answer { topic: Topic, question: usize, choice: usize },
+    /// Forget the user's lesson state
+    Stop,
+}
+
+impl LessonAction {
+    pub fn callback_data(&self) -> String {
+        let action = match self {
+            LessonAction::Menu(topic) => format!("menu:{}", topic.key()),
+            LessonAction::Read(topic) => format!("read:{}", topic.key()),
+            LessonAction::Next => "next".to_string(),
+            LessonAction::Quiz(topic) => format!("quiz:{}", topic.key()),
+            LessonAction::Answer { topic, question, choice } => format!("answer:{}:{}:{}", topic.key(), question, choice),
+            LessonAction::Stop => "stop".to_string(),
+        };
+        format!("{}{}", LESSON_CALLBACK_PREFIX, action)
+    }
+
+    /// Parse the callback data of a lesson button, None for anything else
+    pub fn parse(data: &str) -> Option<Self> {
+        let mut parts = data.strip_prefix(LESSON_CALLBACK_PREFIX)?.split(':');
+        let action = parts.next()?;
+        let topic = parts.next().and_then(|key| key.parse::<Topic>().ok());
+        let parsed = match action {
+            "menu" => LessonAction::Menu(topic?),
+            "read" => LessonAction::Read(topic?),
+            "next" => LessonAction::Next,
+            "quiz" => LessonAction::Quiz(topic?),
+            "answer" => {
+                let topic = topic?;
+                let question = parts.next()?.parse().ok()?;
+                let choice = parts.next()?.parse().ok()?;
+                LessonAction::Answer { topic, question, choice }
+            }
+            "stop" => LessonAction::Stop,
+            _ => return None,
+        };
+        Some(parsed)
+    }
+}
+
+/// Message to send back: text plus rows of (label, action) buttons
+#[derive(Clone, Debug, PartialEq)]
+pub struct LessonReply {
+    pub text: String,
+    pub buttons: Vec<Vec<(String, LessonAction)>>,
+}
+
+impl LessonReply {
+    fn new(text: String, buttons: Vec<Vec<(&str, LessonAction)>>) -> Self {
+        let buttons = buttons.into_iter()
+            .map(|row| row.into_iter().map(|(label, action)| (label.to_string(), action)).collect())
+            .collect();
+        Self { text, buttons }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LessonConfig {
+    pub enabled: bool,
+    pub idle_timeout: Duration,
+}
+
+impl Default for LessonConfig {
+    fn default() -> Self {
+        Self { enabled: true, idle_timeout: Duration::minutes(30) }
+    }
+}
+
+impl LessonConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            enabled: var("LESSONS_ENABLED")
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(defaults.enabled),
+            idle_timeout: var("LESSON_IDLE_MIN")
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|m| *m > 0)
+                .map(Duration::minutes)
+                .unwrap_or(defaults.idle_timeout),
+        }
+    }
+}
+
+/// One user's place in the lessons
+#[derive(Default)]
+struct Progress {
+    /// Topic being read and the part last sent
+    reading: Option<(Topic, usize)>,
+    /// Question asked and not answered yet
+    pending: Option<(Topic, usize)>,
+    /// Questions asked per topic, so each quiz tap asks the next one
+    asked: HashMap<Topic, usize>,
+    correct: u32,
+    answered: u32,
+    /// Unix seconds
+    last_active: i64,
+}
+
+pub struct LessonSessions {
+    config: LessonConfig,
+    clock: Arc<dyn Clock>,
+    /// Telegram user id -> progress
+    users: Mutex<HashMap<u64, Progress>>,
+}
+
+impl LessonSessions {
+    pub fn new(config: LessonConfig) -> Self {
+        Self { config, clock: system_clock(), users: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Process-wide sessions, shared by the alert buttons and the command handler
+    pub fn shared() -> Arc<LessonSessions> {
+        SHARED_LESSONS.clone()
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Users with lesson state
+    pub fn active_users(&self) -> usize {
+        self.users.lock().unwrap().len()
+    }
+
+    /// `/learn` without a topic: every topic as a button
+    pub fn topics(&self) -> LessonReply {
+        let buttons = Topic::ALL.iter().map(|topic| vec![(topic.title(), LessonAction::Menu(*topic))]).collect();
+        LessonReply::new("📚 Pick a topic to learn about:".to_string(), buttons)
+    }
+
+    /// Apply a lesson button pressed by `user`; None when the button is stale (an answered
+    /// question, or someone else's)
+    pub fn handle(&self, user: u64, action: LessonAction) -> Option<LessonReply> {
+        let now = self.clock.now().timestamp();
+        let idle_cutoff = now - self.config.idle_timeout.num_seconds();
+        let mut users = self.users.lock().unwrap();
+        users.retain(|_, progress| progress.last_active > idle_cutoff);
+
+        if action == LessonAction::Stop {
+            let text = match users.remove(&user) {
+                Some(progress) if progress.answered > 0 => format!("Lesson ended. Quiz score: {}/{}.", progress.correct, progress.answered),
+                _ => "Lesson ended.".to_string(),
+            };
+            return Some(LessonReply::new(text, Vec::new()));
+        }
+
+        let progress = users.entry(user).or_default();
+        progress.last_active = now;
+        let reply = match action {
+            LessonAction::Menu(topic) => LessonReply::new(
+                format!("📚 {}\n\nRead a short explanation or test yourself.", topic.title()),
+                vec![vec![("📖 Explain", LessonAction::Read(topic)), ("❓ Quiz", LessonAction::Quiz(topic))]],
+            ),
+            LessonAction::Read(topic) => {
+                progress.reading = Some((topic, 0));
+                part_reply(topic, 0)
+            }
+            LessonAction::Next => match progress.reading {
+                Some((topic, part)) if part + 1 < topic.parts().len() => {
+                    progress.reading = Some((topic, part + 1));
+                    part_reply(topic, part + 1)
+                }
+                Some((topic, _)) => LessonReply::new(
+                    format!("That was all about {}. Ready for the quiz?", topic.title().to_lowercase()),
+                    vec![vec![("❓ Quiz", LessonAction::Quiz(topic)), ("✖ Done", LessonAction::Stop)]],
+                ),
+                None => LessonReply::new("No explanation in progress. Tap 📚 Learn on an alert or send /learn.".to_string(), Vec::new()),
+            },
+            LessonAction::Quiz(topic) => {
+                let asked = progress.asked.entry(topic).or_default();
+                let index = *asked % topic.quiz().len();
+                *asked += 1;
+                progress.pending = Some((topic, index));
+                let question = &topic.quiz()[index];
+                let buttons = question.options.iter()
+                    .enumerate()
+                    .map(|(choice, option)| vec![(*option, LessonAction::Answer { topic, question: index, choice })])
+                    .collect();
+                LessonReply::new(format!("❓ {}", question.prompt), buttons)
+            }
+            LessonAction::Answer { topic, question, choice } => {
+                if progress.pending != Some((topic, question)) {
+                    return None;
+                }
+                let asked = topic.quiz().get(question)?;
+                progress.pending = None;
+                progress.answered += 1;
+                let verdict = if choice == asked.answer {
+                    progress.correct += 1;
+                    "✅ Correct!".to_string()
+                } else {
+                    format!("❌ Not quite: {}", asked.options[asked.answer])
+                };
+                LessonReply::new(
+                    format!("{}\n{}\n\nScore: {}/{}", verdict, asked.explanation, progress.correct, progress.answered),
+                    vec![vec![
+                        ("❓ Another", LessonAction::Quiz(topic)),
+                        ("📖 Explain", LessonAction::Read(topic)),
+                        ("✖ Done", LessonAction::Stop),
+                    ]],
+                )
+            }
+            LessonAction::Stop => unreachable!("handled above"),
+        };
+        Some(reply)
+    }
+}
+
+fn part_reply(topic: Topic, part: usize) -> LessonReply {
+    let parts = topic.parts();
+    let text = format!("📖 {} ({}/{})\n\n{}", topic.title(), part + 1, parts.len(), parts[part]);
+    let buttons = if part + 1 < parts.len() {
+        vec![vec![("Next ▶", LessonAction::Next), ("✖ Done", LessonAction::Stop)]]
+    } else {
+        vec![vec![("❓ Quiz", LessonAction::Quiz(topic)), ("✖ Done", LessonAction::Stop)]]
+    };
+    LessonReply::new(text, buttons)
+}
@@ -0,0 +1,383 @@
+/*!
+# Liquidity Depth
+
+Polls the pool reserves of tracked tokens and keeps a short history per token, so the monitor
+can tell how much SOL it takes to move the price and notice liquidity leaving a pool between
+trades (LP pulls, migrations, drained bonding curves).
+
+Depth is approximated with the constant product formula over the pool's SOL reserve: a buy of
+`s` SOL against `x` SOL of reserves moves the price by about `s / (x + s)`, a sell returning `s`
+SOL by about `s / x`. Pump.fun bonding curves are read through their virtual reserves, which
+follow the same formula; PumpSwap pools through their vault balances. Other DEXes only use the
+pool liquidity reported with each parsed trade.
+
+Watchlist tokens are pinned: their pool is followed from the moment they are imported, before
+any trade is seen, and polled ahead of traded pools. A pinned token with no trade yet is assumed
+to be on its pump.fun bonding curve, whose account the reads keep in the subscribed account
+cache.
+
+## Environment Variables
+
+- `LIQUIDITY_DEPTH_POLL_SECS`: Seconds between reserve polls, `0` disables polling (default: `30`)
+- `LIQUIDITY_DEPTH_MAX_POOLS`: Pools polled each round, pinned and most recently traded first (default: `50`)
+- `LIQUIDITY_DROP_ALERT_PCT`: Drop of pool SOL within the window that triggers an alert (default: `30.0`)
+- `LIQUIDITY_DROP_WINDOW_SECS`: Window the drop is measured over (default: `300`)
+- `PRICE_IMPACT_ALERT_PCT`: Price impact of a tracked wallet's trade that triggers an alert (default: `5.0`)
+- `LIQUIDITY_DEPTH_LEVELS`: Price impacts (%) shown in the depth curve (default: `1,2,5,10`)
+*/
+
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use borsh::from_slice;
+use chrono::{DateTime, Utc};
+use solana_program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address;
+use tokio_util::sync::CancellationToken;
+
+use crate::common::cache::SUBSCRIBED_ACCOUNTS;
+use crate::common::cluster::DexProgram;
+use crate::library::account_batcher::AccountBatcher;
+use crate::dex::pump_fun::{get_pda, BondingCurveAccount};
+#[cfg(feature = "monitor")]
+use crate::processor::educational_monitor::EducationalMonitor;
+use crate::processor::swap::SwapDirection;
+use crate::processor::transaction_parser::DexType;
+
+const WSOL_MINT: Pubkey = spl_token::native_mint::ID;
+
+/// Reserves of a pool at one point in time
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PoolReserves {
+    /// SOL side, in SOL
+    pub sol: f64,
+    /// Token side, in raw token units
+    pub tokens: f64,
+}
+
+/// SOL needed to move the price by `impact_pct` in each direction
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DepthLevel {
+    pub impact_pct: f64,
+    pub buy_sol: f64,
+    pub sell_sol: f64,
+}
+
+/// Approximate price impact (%) of a trade of `sol_amount` against `pool_sol` of reserves
+pub fn price_impact_pct(pool_sol: f64, sol_amount: f64, direction: SwapDirection) -> f64 {
+    if pool_sol <= 0.0 {
+        return 100.0;
+    }
+    let impact = match direction {
+        SwapDirection::Buy => sol_amount / (pool_sol + sol_amount),
+        SwapDirection::Sell => sol_amount / pool_sol,
+    };
+    (impact * 100.0).min(100.0)
+}
+
+/// Trade sizes reaching each price impact level, the inverse of `price_impact_pct`
+pub fn depth_curve(pool_sol: f64, levels: &[f64]) -> Vec<DepthLevel> {
+    levels.iter()
+        .filter(|pct| **pct > 0.0 && **pct < 100.0)
+        .map(|pct| {
+            let p = pct / 100.0;
+            DepthLevel {
+                impact_pct: *pct,
+                buy_sol: pool_sol * p / (1.0 - p),
+                sell_sol: pool_sol * p,
+            }
+        })
+        .collect()
+}
+
+/// Depth curve as alert lines
+pub fn format_depth_curve(curve: &[DepthLevel]) -> String {
+    curve.iter()
+        .map(|level| format!(
+            "• {:.0}% impact: buy {:.2} SOL / sell {:.2} SOL",
+            level.impact_pct, level.buy_sol, level.sell_sol
+        ))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Clone, Debug)]
+pub struct LiquidityDepthConfig {
+    pub poll_interval: Duration,
+    pub max_pools: usize,
+    pub drop_alert_pct: f64,
+    pub drop_window: chrono::Duration,
+    pub price_impact_alert_pct: f64,
+    pub levels: Vec<f64>,
+}
+
+impl Default for LiquidityDepthConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(30),
+            max_pools: 50,
+            drop_alert_pct: 30.0,
+            drop_window: chrono::Duration::seconds(300),
+            price_impact_alert_pct: 5.0,
+            levels: vec![1.0, 2.0, 5.0, 10.0],
+        }
+    }
+}
+
+impl LiquidityDepthConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            poll_interval: var("LIQUIDITY_DEPTH_POLL_SECS")
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.poll_interval),
+            max_pools: var("LIQUIDITY_DEPTH_MAX_POOLS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.max_pools),
+            drop_alert_pct: var("LIQUIDITY_DROP_ALERT_PCT")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.drop_alert_pct),
+            drop_window: var("LIQUIDITY_DROP_WINDOW_SECS")
+                .and_then(|v| v.parse::<i64>().ok())
+                .map(chrono::Duration::seconds)
+                .unwrap_or(defaults.drop_window),
+            price_impact_alert_pct: var("PRICE_IMPACT_ALERT_PCT")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.price_impact_alert_pct),
+            levels: var("LIQUIDITY_DEPTH_LEVELS")
+                .map(|v| v.split(',').filter_map(|l| l.trim().parse::<f64>().ok()).collect::<Vec<_>>())
+                .filter(|levels| !levels.is_empty())
+                .unwrap_or(defaults.levels),
+        }
+    }
+}
+
+/// Where a token trades, learned from the parsed trades
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackedPool {
+    pub mint: Pubkey,
+    pub dex: DexType,
+    pub pool_id: Option<Pubkey>,
+    pub last_trade: DateTime<Utc>,
+    /// Imported from a watchlist: polled every round whatever its last trade
+    pub pinned: bool,
+}
+
+/// A sharp fall of pool SOL within the drop window
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiquidityDrop {
+    pub from_sol: f64,
+    pub to_sol: f64,
+    pub drop_pct: f64,
+    pub since: DateTime<Utc>,
+}
+
+/// Reserve history of the pools of tracked tokens
+pub struct LiquidityDepth {
+    config: LiquidityDepthConfig,
+    pools: HashMap<Pubkey, TrackedPool>,
+    history: HashMap<Pubkey, VecDeque<(DateTime<Utc>, PoolReserves)>>,
+}
+
+impl LiquidityDepth {
+    pub fn new(config: LiquidityDepthConfig) -> Self {
+        Self { config, pools: HashMap::new(), history: HashMap::new() }
+    }
+
+    pub fn config(&self) -> &LiquidityDepthConfig {
+        &self.config
+    }
+
+    /// Remember the pool a token traded on
+    pub fn observe_pool(&mut self, mint: Pubkey, dex: DexType, pool_id: Option<Pubkey>, now: DateTime<Utc>) {
+        let pool = self.pools.entry(mint).or_insert(TrackedPool { mint, dex: dex.clone(), pool_id, last_trade: now, pinned: false });
+        pool.dex = dex;
+        pool.pool_id = pool_id.or(pool.pool_id);
+        pool.last_trade = now;
+    }
+
+    /// Follow the pool of a watchlist token. Until it trades, its pool is taken to be the
+    /// pump.fun bonding curve.
+    pub fn pin_pool(&mut self, mint: Pubkey, now: DateTime<Utc>) {
+        self.pools
+            .entry(mint)
+            .or_insert(TrackedPool { mint, dex: DexType::PumpFun, pool_id: None, last_trade: now, pinned: true })
+            .pinned = true;
+    }
+
+    /// Pools to poll this round: pinned pools, then the most recently traded
+    pub fn pools_to_poll(&self) -> Vec<TrackedPool> {
+        let mut pools: Vec<TrackedPool> = self.pools.values().cloned().collect();
+        pools.sort_by(|a, b| b.pinned.cmp(&a.pinned).then(b.last_trade.cmp(&a.last_trade)));
+        pools.truncate(self.config.max_pools);
+        pools
+    }
+
+    /// Add a reserve reading; returns the drop when pool SOL fell by at least the alert
+    /// threshold from its peak within the window. The history restarts after a drop so the
+    /// same fall is reported once.
+    pub fn record(&mut self, mint: Pubkey, reserves: PoolReserves, now: DateTime<Utc>) -> Option<LiquidityDrop> {
+        let history = self.history.entry(mint).or_default();
+        let cutoff = now - self.config.drop_window;
+        while history.front().map_or(false, |(at, _)| *at < cutoff) {
+            history.pop_front();
+        }
+
+        let peak = history.iter()
+            .max_by(|a, b| a.1.sol.total_cmp(&b.1.sol))
+            .map(|(at, peak)| (*at, peak.sol));
+        history.push_back((now, reserves));
+
+        let (since, from_sol) = peak?;
+        if from_sol <= 0.0 {
+            return None;
+        }
+        let drop_pct = (from_sol - reserves.sol) / from_sol * 100.0;
+        if drop_pct < self.config.drop_alert_pct {
+            return None;
+        }
+        history.retain(|(at, _)| *at == now);
+        Some(LiquidityDrop { from_sol, to_sol: reserves.sol, drop_pct, since })
+    }
+
+    /// Latest reserves read for a token
+    pub fn latest(&self, mint: &Pubkey) -> Option<PoolReserves> {
+        self.history.get(mint).and_then(|h| h.back()).map(|(_, reserves)| *reserves)
+    }
+
+    /// Depth curve at the configured levels for a pool holding `pool_sol`
+    pub fn curve(&self, pool_sol: f64) -> Vec<DepthLevel> {
+        depth_curve(pool_sol, &self.config.levels)
+    }
+
+    /// Forget tokens that are no longer tracked
+    pub fn retain(&mut self, keep: impl Fn(&Pubkey) -> bool) {
+        self.pools.retain(|mint, _| keep(mint));
+        self.history.retain(|mint, _| keep(mint));
+    }
+
+    /// Number of pools followed
+    pub fn pool_count(&self) -> usize {
+        self.pools.len()
+    }
+}
+
+/// Reads the current reserves of a pool
+#[async_trait]
+pub trait ReserveReader: Send + Sync {
+    /// None when the pool's DEX is not supported
+    async fn read(&self, pool: &TrackedPool) -> Result<Option<PoolReserves>>;
+}
+
+/// Reads reserves through the RPC node, batched with other account reads
+pub struct RpcReserveReader {
+    accounts: Arc<AccountBatcher>,
+}
+
+impl RpcReserveReader {
+    pub fn new(accounts: Arc<AccountBatcher>) -> Self {
+        Self { accounts }
+    }
+
+    /// Account data from the subscription cache when it is live, the RPC node otherwise
+    async fn account_data(&self, keys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        let now = std::time::Instant::now();
+        let mut data: Vec<Option<Vec<u8>>> = Vec::with_capacity(keys.len());
+        let mut missing = Vec::new();
+        for (index, key) in keys.iter().enumerate() {
+            SUBSCRIBED_ACCOUNTS.watch(key, now);
+            let cached = SUBSCRIBED_ACCOUNTS.get(key);
+            if cached.is_none() {
+                missing.push(index);
+            }
+            data.push(cached);
+        }
+        if missing.is_empty() {
+            return Ok(data);
+        }
+
+        let fetch: Vec<Pubkey> = missing.iter().map(|i| keys[*i]).collect();
+        let fetched = self.accounts.get_accounts(&fetch).await?;
+        for (index, account) in missing.into_iter().zip(fetched) {
+            if let Some(account) = account {
+                SUBSCRIBED_ACCOUNTS.store_fetched(&keys[index], account.data.clone());
+                data[index] = Some(account.data);
+            }
+        }
+        Ok(data)
+    }
+}
+
+#[async_trait]
+impl ReserveReader for RpcReserveReader {
+    async fn read(&self, pool: &TrackedPool) -> Result<Option<PoolReserves>> {
+        match pool.dex {
+            DexType::PumpFun => {
+                let bonding_curve = get_pda(&pool.mint, &DexProgram::PumpFun.id())?;
+                let data = self.account_data(&[bonding_curve]).await?.pop().flatten()
+                    .ok_or_else(|| anyhow!("Bonding curve {} not found", bonding_curve))?;
+                let curve = from_slice::<BondingCurveAccount>(&data)
+                    .map_err(|e| anyhow!("Invalid bonding curve account {}: {}", bonding_curve, e))?;
+                Ok(Some(PoolReserves {
+                    sol: curve.virtual_sol_reserves as f64 / 1e9,
+                    tokens: curve.virtual_token_reserves as f64,
+                }))
+            }
+            DexType::PumpSwap => {
+                let pool_id = match pool.pool_id {
+                    Some(pool_id) => pool_id,
+                    None => return Ok(None),
+                };
+                let vaults = [
+                    get_associated_token_address(&pool_id, &pool.mint),
+                    get_associated_token_address(&pool_id, &WSOL_MINT),
+                ];
+                let accounts = self.account_data(&vaults).await?;
+                let amount = |index: usize| -> Result<u64> {
+                    let data = accounts.get(index).cloned().flatten()
+                        .ok_or_else(|| anyhow!("Pool vault {} not found", vaults[index]))?;
+                    Ok(spl_token::state::Account::unpack(&data)?.amount)
+                };
+                Ok(Some(PoolReserves {
+                    sol: amount(1)? as f64 / 1e9,
+                    tokens: amount(0)? as f64,
+                }))
+            }
+            DexType::RaydiumLaunchpad | DexType::Unknown => Ok(None),
+        }
+    }
+}
+
+/// Parse a pool id reported by the parser
+pub fn parse_pool_id(pool_id: Option<&str>) -> Option<Pubkey> {
+    pool_id.and_then(|id| Pubkey::from_str(id).ok())
+}
+
+/// Start polling the pools of the monitor's tracked tokens unless LIQUIDITY_DEPTH_POLL_SECS=0
+#[cfg(feature = "monitor")]
+pub fn start_liquidity_depth_polling(
+    monitor: Arc<EducationalMonitor>,
+    reader: Arc<dyn ReserveReader>,
+    cancel_token: CancellationToken,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let poll_interval = LiquidityDepthConfig::from_env().poll_interval;
+    if poll_interval.is_zero() {
+        return None;
+    }
+
+    Some(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => return,
+                _ = interval.tick() => monitor.poll_liquidity(reader.as_ref()).await,
+            }
+        }
+    }))
+}
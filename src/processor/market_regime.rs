@@ -0,0 +1,280 @@
+/*!
+# Market Regime
+
+Classifies the market the bot is trading in from two aggregate signals over the last
+`REGIME_WINDOW_SECS`:
+
+- the SOL/USD trend: change between the oldest and newest SOL price samples in the window
+- the launch success rate: share of finished launches (see the launch replays) that graduated
+  rather than died, once at least `REGIME_MIN_LAUNCHES` finished
+
+A regime is cold when either signal is at or below its cold threshold, hot when at least one is at
+or above its hot threshold and neither is cold, neutral otherwise. In a cold regime buys are sized
+down by `REGIME_COLD_RISK_MULTIPLIER`, and entry strategies ask for proportionally more before they
+trigger: a larger focus trigger buy, a deeper dip for re-entries.
+
+## Environment Variables
+
+- `REGIME_ENABLED`: Classify the market and scale risk down when it is cold (default: `false`)
+- `REGIME_WINDOW_SECS`: Window both signals are measured over (default: `3600`)
+- `REGIME_HOT_SOL_TREND_PCT`: SOL/USD change (%) counted as hot (default: `3`)
+- `REGIME_COLD_SOL_TREND_PCT`: SOL/USD change (%) counted as cold (default: `-3`)
+- `REGIME_HOT_LAUNCH_SUCCESS_PCT`: Share of launches graduating (%) counted as hot (default: `5`)
+- `REGIME_COLD_LAUNCH_SUCCESS_PCT`: Share of launches graduating (%) counted as cold (default: `1`)
+- `REGIME_MIN_LAUNCHES`: Finished launches needed before their success rate counts (default: `20`)
+- `REGIME_COLD_RISK_MULTIPLIER`: Buy size multiplier in a cold regime (default: `0.5`)
+*/
+
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use lazy_static::lazy_static;
+use serde::Serialize;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::common::logger::Logger;
+use crate::library::rolling::RollingWindow;
+use crate::processor::launch_replay::{LaunchOutcome, LaunchRecorder, LaunchReplayConfig};
+use crate::processor::transaction_parser::ParsedData;
+
+lazy_static! {
+    static ref SHARED_REGIME: Arc<MarketRegime> = Arc::new(MarketRegime::new(RegimeConfig::from_env()));
+    static ref LOGGER: Logger = Logger::new("[MARKET-REGIME] => ".cyan().to_string());
+}
+
+/// How often launches that went quiet are checked for their outcome
+const LAUNCH_SWEEP_SECS: i64 = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Regime {
+    Hot,
+    Neutral,
+    Cold,
+}
+
+impl Regime {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Regime::Hot => "hot",
+            Regime::Neutral => "neutral",
+            Regime::Cold => "cold",
+        }
+    }
+
+    /// 1 hot, 0 neutral, -1 cold
+    pub fn gauge(&self) -> i8 {
+        match self {
+            Regime::Hot => 1,
+            Regime::Neutral => 0,
+            Regime::Cold => -1,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RegimeConfig {
+    pub enabled: bool,
+    pub window: Duration,
+    pub hot_sol_trend_pct: f64,
+    pub cold_sol_trend_pct: f64,
+    pub hot_launch_success_pct: f64,
+    pub cold_launch_success_pct: f64,
+    pub min_launches: usize,
+    /// Buy size multiplier in a cold regime
+    pub cold_risk_multiplier: f64,
+}
+
+impl Default for RegimeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window: Duration::hours(1),
+            hot_sol_trend_pct: 3.0,
+            cold_sol_trend_pct: -3.0,
+            hot_launch_success_pct: 5.0,
+            cold_launch_success_pct: 1.0,
+            min_launches: 20,
+            cold_risk_multiplier: 0.5,
+        }
+    }
+}
+
+impl RegimeConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        let pct = |name: &str, default: f64| var(name).and_then(|v| v.parse().ok()).unwrap_or(default);
+        Self {
+            enabled: var("REGIME_ENABLED")
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(defaults.enabled),
+            window: var("REGIME_WINDOW_SECS")
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|s| *s > 0)
+                .map(Duration::seconds)
+                .unwrap_or(defaults.window),
+            hot_sol_trend_pct: pct("REGIME_HOT_SOL_TREND_PCT", defaults.hot_sol_trend_pct),
+            cold_sol_trend_pct: pct("REGIME_COLD_SOL_TREND_PCT", defaults.cold_sol_trend_pct),
+            hot_launch_success_pct: pct("REGIME_HOT_LAUNCH_SUCCESS_PCT", defaults.hot_launch_success_pct),
+            cold_launch_success_pct: pct("REGIME_COLD_LAUNCH_SUCCESS_PCT", defaults.cold_launch_success_pct),
+            min_launches: var("REGIME_MIN_LAUNCHES")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.min_launches),
+            cold_risk_multiplier: var("REGIME_COLD_RISK_MULTIPLIER")
+                .and_then(|v| v.parse().ok())
+                .filter(|m: &f64| *m > 0.0 && *m <= 1.0)
+                .unwrap_or(defaults.cold_risk_multiplier),
+        }
+    }
+}
+
+/// The regime with the signals it was classified from
+#[derive(Clone, Debug, Serialize)]
+pub struct RegimeSnapshot {
+    pub regime: Regime,
+    /// None until the window holds two SOL prices
+    pub sol_trend_pct: Option<f64>,
+    /// None until enough launches finished
+    pub launch_success_pct: Option<f64>,
+    pub launches: usize,
+}
+
+pub struct MarketRegime {
+    config: RegimeConfig,
+    sol_prices: Mutex<RollingWindow>,
+    /// 1 per graduated launch, 0 per launch that died
+    outcomes: Mutex<RollingWindow>,
+    launches: Mutex<LaunchRecorder>,
+    last_sweep: Mutex<Option<DateTime<Utc>>>,
+    last_regime: Mutex<Regime>,
+    clock: Arc<dyn Clock>,
+}
+
+impl MarketRegime {
+    pub fn new(config: RegimeConfig) -> Self {
+        let window = config.window;
+        Self {
+            config,
+            sol_prices: Mutex::new(RollingWindow::new(window)),
+            outcomes: Mutex::new(RollingWindow::new(window)),
+            launches: Mutex::new(LaunchRecorder::new(LaunchReplayConfig::from_env())),
+            last_sweep: Mutex::new(None),
+            last_regime: Mutex::new(Regime::Neutral),
+            clock: system_clock(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn shared() -> Arc<MarketRegime> {
+        SHARED_REGIME.clone()
+    }
+
+    pub fn config(&self) -> &RegimeConfig {
+        &self.config
+    }
+
+    pub fn record_sol_price(&self, price: f64) {
+        if self.config.enabled && price.is_finite() && price > 0.0 {
+            self.sol_prices.lock().unwrap().push(self.clock.now(), price);
+        }
+    }
+
+    pub fn record_launch(&self, outcome: &LaunchOutcome) {
+        if self.config.enabled {
+            let graduated = matches!(outcome, LaunchOutcome::Graduated);
+            self.outcomes.lock().unwrap().push(self.clock.now(), if graduated { 1.0 } else { 0.0 });
+        }
+    }
+
+    /// Follow a trade or liquidity event to learn how its launch ends
+    pub fn observe(&self, parsed_data: &ParsedData) {
+        if !self.config.enabled {
+            return;
+        }
+        let now = self.clock.now();
+        let mut launches = self.launches.lock().unwrap();
+        launches.record(parsed_data, now);
+
+        let mut last_sweep = self.last_sweep.lock().unwrap();
+        if last_sweep.is_some_and(|at| at + Duration::seconds(LAUNCH_SWEEP_SECS) > now) {
+            return;
+        }
+        *last_sweep = Some(now);
+        let pull_pct = std::env::var("LIQUIDITY_REMOVAL_ALERT_PCT").ok().and_then(|v| v.parse().ok()).unwrap_or(10.0);
+        for (_, outcome) in launches.take_finished(pull_pct, now) {
+            self.record_launch(&outcome);
+        }
+    }
+
+    pub fn snapshot(&self) -> RegimeSnapshot {
+        let now = self.clock.now();
+        let sol_trend_pct = {
+            let mut prices = self.sol_prices.lock().unwrap();
+            prices.evict(now);
+            match (prices.samples().next(), prices.samples().last()) {
+                (Some(first), Some(last)) if prices.len() >= 2 => Some((last.value / first.value - 1.0) * 100.0),
+                _ => None,
+            }
+        };
+        let (launch_success_pct, launches) = {
+            let mut outcomes = self.outcomes.lock().unwrap();
+            outcomes.evict(now);
+            let rate = outcomes.mean()
+                .filter(|_| outcomes.len() >= self.config.min_launches.max(1))
+                .map(|rate| rate * 100.0);
+            (rate, outcomes.len())
+        };
+
+        let cold = sol_trend_pct.is_some_and(|t| t <= self.config.cold_sol_trend_pct)
+            || launch_success_pct.is_some_and(|r| r <= self.config.cold_launch_success_pct);
+        let hot = sol_trend_pct.is_some_and(|t| t >= self.config.hot_sol_trend_pct)
+            || launch_success_pct.is_some_and(|r| r >= self.config.hot_launch_success_pct);
+        let regime = if !self.config.enabled {
+            Regime::Neutral
+        } else if cold {
+            Regime::Cold
+        } else if hot {
+            Regime::Hot
+        } else {
+            Regime::Neutral
+        };
+        RegimeSnapshot { regime, sol_trend_pct, launch_success_pct, launches }
+    }
+
+    /// Current regime; changes are logged
+    pub fn regime(&self) -> Regime {
+        let snapshot = self.snapshot();
+        let mut last = self.last_regime.lock().unwrap();
+        if *last != snapshot.regime {
+            LOGGER.log(format!(
+                "Market regime {} -> {} (SOL trend {}, launch success {} of {} launches)",
+                last.label(),
+                snapshot.regime.label(),
+                snapshot.sol_trend_pct.map_or("n/a".to_string(), |t| format!("{:+.1}%", t)),
+                snapshot.launch_success_pct.map_or("n/a".to_string(), |r| format!("{:.1}%", r)),
+                snapshot.launches,
+            ).yellow().to_string());
+            *last = snapshot.regime;
+        }
+        snapshot.regime
+    }
+
+    /// Multiplier for buy sizes; below 1 only in a cold regime
+    pub fn risk_multiplier(&self) -> f64 {
+        match self.regime() {
+            Regime::Cold => self.config.cold_risk_multiplier,
+            Regime::Hot | Regime::Neutral => 1.0,
+        }
+    }
+}
+
+/// Risk multiplier of the shared regime
+pub fn risk_multiplier() -> f64 {
+    MarketRegime::shared().risk_multiplier()
+}
@@ -1,9 +1,83 @@
+#[cfg(feature = "trading")]
 pub mod sniper_bot;
 pub mod monitor;
+#[cfg(feature = "trading")]
 pub mod risk_management;
+#[cfg(feature = "trading")]
 pub mod selling_strategy;
 pub mod swap;
 pub mod transaction_parser;
+#[cfg(feature = "trading")]
 pub mod transaction_retry;
 pub mod telegram_alerts;
+#[cfg(feature = "monitor")]
 pub mod educational_monitor;
+#[cfg(feature = "trading")]
+pub mod balance_tracker;
+pub mod risk_score;
+#[cfg(feature = "trading")]
+pub mod token_program_monitor;
+#[cfg(feature = "trading")]
+pub mod graduation_sniper;
+#[cfg(feature = "monitor")]
+pub mod watchlist;
+#[cfg(all(feature = "telegram", feature = "monitor"))]
+pub mod telegram_commands;
+pub mod signal_outcomes;
+pub mod alert_outbox;
+#[cfg(feature = "trading")]
+pub mod trade_ledger;
+pub mod liquidity_depth;
+pub mod arbitrage;
+#[cfg(feature = "trading")]
+pub mod route_split;
+pub mod launch_replay;
+pub mod launch_stats;
+pub mod first_buyers;
+pub mod deployer_history;
+pub mod insider_detection;
+#[cfg(feature = "trading")]
+pub mod copy_exit;
+pub mod copy_guard;
+#[cfg(feature = "trading")]
+pub mod sol_balance_monitor;
+#[cfg(feature = "trading")]
+pub mod profit_policy;
+pub mod trading_control;
+pub mod alert_throttle;
+pub mod mute_list;
+pub mod trending;
+pub mod wallet_age;
+pub mod cex_deposits;
+pub mod lessons;
+pub mod alert_feedback;
+#[cfg(feature = "trading")]
+pub mod swap_deadline;
+#[cfg(feature = "trading")]
+pub mod snipe_limiter;
+#[cfg(feature = "trading")]
+pub mod order_ledger;
+#[cfg(feature = "trading")]
+pub mod fill_check;
+#[cfg(feature = "trading")]
+pub mod price_backfill;
+pub mod report_export;
+pub mod wallet_positions;
+#[cfg(feature = "trading")]
+pub mod dip_buy;
+pub mod breakout;
+pub mod market_regime;
+pub mod pipeline;
+pub mod wasm_strategy;
+pub mod script_filter;
+pub mod signal_bus;
+pub mod opportunity_signal;
+pub mod token_timeline;
+pub mod token_audit;
+pub mod wallet_groups;
+pub mod group_consensus;
+#[cfg(feature = "trading")]
+pub mod sell_route;
+pub mod scam_lists;
+pub mod signal_confirmation;
+pub mod adaptive_thresholds;
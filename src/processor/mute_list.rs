@@ -0,0 +1,162 @@
+//! Per-token mute list with expiry.
+//!
+//! `/mute <mint|creator> <duration>` (e.g. `6h`, `30m`, `2d`) silences every alert about a mint,
+//! or about any token launched by a creator, until the mute expires; `/unmute` lifts it early and
+//! `/mutes` lists the active ones. Muted tokens are also skipped by the buy strategies. Mutes are
+//! written to `MUTE_LIST_PATH` and survive restarts.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use chrono::Duration;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+use crate::common::clock::{system_clock, Clock};
+
+lazy_static! {
+    static ref SHARED_MUTES: Arc<MuteList> = Arc::new(MuteList::from_env());
+    /// Creator of each mint seen launching or trading, so creator mutes cover their tokens
+    static ref MINT_CREATORS: DashMap<String, String> = DashMap::new();
+}
+
+/// Longest mute accepted, so a typo cannot silence a token for years
+const MAX_MUTE_DAYS: i64 = 30;
+
+/// `90s`, `30m`, `6h`, `2d` (a bare number is minutes)
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim().to_lowercase();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (amount, unit) = text.split_at(split);
+    let amount: i64 = amount.parse().ok().filter(|a| *a > 0)?;
+    let duration = match unit {
+        "s" => Duration::seconds(amount),
+        "" | "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        _ => return None,
+    };
+    Some(duration.min(Duration::days(MAX_MUTE_DAYS)))
+}
+
+/// Muted mint or creator, as persisted
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Mute {
+    pub address: String,
+    /// Unix seconds
+    pub until: i64,
+}
+
+pub struct MuteList {
+    path: Option<PathBuf>,
+    clock: Arc<dyn Clock>,
+    /// Address -> unix seconds the mute ends
+    mutes: Mutex<HashMap<String, i64>>,
+}
+
+impl MuteList {
+    /// Mute list persisted at `path` (if any), reloading the mutes of previous runs
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let mutes = path.as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<Vec<Mute>>(&content).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mute| (mute.address, mute.until))
+            .collect();
+        Self { path, clock: system_clock(), mutes: Mutex::new(mutes) }
+    }
+
+    pub fn from_env() -> Self {
+        let path = std::env::var("MUTE_LIST_PATH").unwrap_or_else(|_| "mute_list.json".to_string());
+        Self::new(Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty()))
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Process-wide mute list
+    pub fn shared() -> Arc<MuteList> {
+        SHARED_MUTES.clone()
+    }
+
+    /// Mute `address` (a mint or a creator) for `duration`, extending a shorter mute; returns
+    /// when it ends (unix seconds)
+    pub fn mute(&self, address: &str, duration: Duration) -> i64 {
+        let until = (self.clock.now() + duration).timestamp();
+        let mut mutes = self.mutes.lock().unwrap();
+        let until = mutes.get(address).map_or(until, |current| until.max(*current));
+        mutes.insert(address.to_string(), until);
+        self.save(&mutes);
+        until
+    }
+
+    /// Lift a mute early; returns whether `address` was muted
+    pub fn unmute(&self, address: &str) -> bool {
+        let mut mutes = self.mutes.lock().unwrap();
+        let removed = mutes.remove(address).is_some();
+        if removed {
+            self.save(&mutes);
+        }
+        removed
+    }
+
+    pub fn is_muted(&self, address: &str) -> bool {
+        let now = self.clock.now().timestamp();
+        self.mutes.lock().unwrap().get(address).is_some_and(|until| *until > now)
+    }
+
+    /// Whether alerts and buys for `mint` are muted, directly or through its creator
+    pub fn is_token_muted(&self, mint: &str) -> bool {
+        self.is_muted(mint) || creator_of(mint).is_some_and(|creator| self.is_muted(&creator))
+    }
+
+    /// Active mutes, soonest to expire first; expired ones are dropped
+    pub fn active(&self) -> Vec<Mute> {
+        let now = self.clock.now().timestamp();
+        let mut mutes = self.mutes.lock().unwrap();
+        let before = mutes.len();
+        mutes.retain(|_, until| *until > now);
+        if mutes.len() != before {
+            self.save(&mutes);
+        }
+        let mut active: Vec<Mute> = mutes.iter()
+            .map(|(address, until)| Mute { address: address.clone(), until: *until })
+            .collect();
+        active.sort_by_key(|mute| mute.until);
+        active
+    }
+
+    fn save(&self, mutes: &HashMap<String, i64>) {
+        if let Some(path) = &self.path {
+            let list: Vec<Mute> = mutes.iter()
+                .map(|(address, until)| Mute { address: address.clone(), until: *until })
+                .collect();
+            let written = serde_json::to_string(&list)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| Ok(std::fs::write(path, json)?));
+            if let Err(e) = written {
+                eprintln!("Failed to save the mute list to {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Remember who created `mint`, for creator mutes
+pub fn remember_creator(mint: &str, creator: &str) {
+    if !creator.is_empty() {
+        MINT_CREATORS.insert(mint.to_string(), creator.to_string());
+    }
+}
+
+pub fn creator_of(mint: &str) -> Option<String> {
+    MINT_CREATORS.get(mint).map(|creator| creator.clone())
+}
+
+/// Whether alerts and buys for `mint` are muted on the process-wide list
+pub fn is_token_muted(mint: &str) -> bool {
+    SHARED_MUTES.is_token_muted(mint)
+}
@@ -0,0 +1,168 @@
+//! Structured opportunity signals: what pattern fired, how strongly, on what evidence and what to
+//! do about it.
+//!
+//! Confidence comes from how far each piece of evidence clears its threshold: evidence just at
+//! its threshold contributes 30%, twice the threshold 65%, and it approaches 100% from there. The
+//! recommended action follows from direction and confidence. Signals are rendered into the
+//! opportunity alert, written by the signal recorder and kept with their measured outcome, so
+//! accuracy can be compared across confidence levels.
+
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::processor::signal_outcomes::SignalDirection;
+
+/// Confidence from which a bullish signal suggests considering an entry
+pub const ENTRY_CONFIDENCE: f64 = 0.7;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpportunityKind {
+    HighBuyPressure,
+    LargeTransaction,
+    UnusualTradeSize,
+    MomentumBreakout,
+    PotentialRecovery,
+    GroupConsensus,
+}
+
+impl OpportunityKind {
+    /// Name used in alerts, ratings and signal accuracy
+    pub fn label(&self) -> &'static str {
+        match self {
+            OpportunityKind::HighBuyPressure => "High Buy Pressure",
+            OpportunityKind::LargeTransaction => "Large Transaction",
+            OpportunityKind::UnusualTradeSize => "Unusual Trade Size",
+            OpportunityKind::MomentumBreakout => "Momentum Breakout",
+            OpportunityKind::PotentialRecovery => "Potential Recovery",
+            OpportunityKind::GroupConsensus => "Group Consensus",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendedAction {
+    Watch,
+    ConsiderEntry,
+    Avoid,
+}
+
+impl RecommendedAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecommendedAction::Watch => "Watch",
+            RecommendedAction::ConsiderEntry => "Consider entry",
+            RecommendedAction::Avoid => "Avoid",
+        }
+    }
+}
+
+/// One measurement behind a signal, with the threshold it had to reach
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Evidence {
+    pub name: String,
+    pub value: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<f64>,
+}
+
+impl Evidence {
+    /// How many times over its threshold the value is, for evidence with a positive threshold
+    pub fn strength(&self) -> Option<f64> {
+        self.threshold.filter(|t| *t > 0.0).map(|t| self.value / t)
+    }
+
+    pub fn describe(&self) -> String {
+        match self.threshold {
+            Some(threshold) => format!("{}: {:.2} (threshold {:.2})", self.name, self.value, threshold),
+            None => format!("{}: {:.2}", self.name, self.value),
+        }
+    }
+}
+
+/// The token's metrics when the signal fired
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct MetricsSnapshot {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    pub liquidity: f64,
+    pub volume_1h: f64,
+    pub volume_24h: f64,
+    pub buy_count: u32,
+    pub sell_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bonding_curve_progress: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fresh_wallet_pct: Option<f64>,
+    /// Minutes since the token was first seen
+    pub age_minutes: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OpportunitySignal {
+    pub kind: OpportunityKind,
+    #[serde(skip)]
+    pub mint: Pubkey,
+    #[serde(skip)]
+    pub token_name: Option<String>,
+    pub direction: SignalDirection,
+    /// 0.0 to 1.0
+    pub confidence: f64,
+    pub action: RecommendedAction,
+    pub summary: String,
+    pub evidence: Vec<Evidence>,
+    pub snapshot: MetricsSnapshot,
+}
+
+impl OpportunitySignal {
+    pub fn new(
+        kind: OpportunityKind,
+        mint: Pubkey,
+        token_name: Option<String>,
+        direction: SignalDirection,
+        summary: String,
+        snapshot: MetricsSnapshot,
+    ) -> Self {
+        let mut signal = Self {
+            kind,
+            mint,
+            token_name,
+            direction,
+            confidence: 0.0,
+            action: RecommendedAction::Watch,
+            summary,
+            evidence: Vec::new(),
+            snapshot,
+        };
+        signal.score();
+        signal
+    }
+
+    /// Add a measurement and rescore
+    pub fn with_evidence(mut self, name: &str, value: f64, threshold: Option<f64>) -> Self {
+        self.evidence.push(Evidence { name: name.to_string(), value, threshold });
+        self.score();
+        self
+    }
+
+    fn score(&mut self) {
+        let strengths: Vec<f64> = self.evidence.iter().filter_map(|e| e.strength()).collect();
+        self.confidence = if strengths.is_empty() {
+            0.3
+        } else {
+            let per_evidence = strengths.iter().map(|s| 0.3 + 0.7 * (1.0 - 1.0 / s.max(1.0)));
+            per_evidence.sum::<f64>() / strengths.len() as f64
+        };
+        self.action = match self.direction {
+            SignalDirection::Bearish => RecommendedAction::Avoid,
+            SignalDirection::Bullish if self.confidence >= ENTRY_CONFIDENCE => RecommendedAction::ConsiderEntry,
+            SignalDirection::Bullish => RecommendedAction::Watch,
+        };
+    }
+
+    /// Evidence lines for alerts
+    pub fn format_evidence(&self) -> String {
+        self.evidence.iter().map(|e| format!("  • {}\n", e.describe())).collect()
+    }
+}
@@ -0,0 +1,329 @@
+//! Ledger of intended orders, written before anything is sent so a retry or a restart never buys
+//! the same token twice.
+//!
+//! Every buy is recorded under a client-generated order id as `pending` before submission, moves
+//! to `submitted` with the signatures sent, and ends `filled`, `failed` or `expired`. Each state
+//! change is appended to `ORDER_LEDGER_PATH` as a JSON line (the last line per id wins on load).
+//!
+//! Before a new buy of a mint, an open order for it is reconciled against the chain: a confirmed
+//! signature fills it, all signatures failing fails it, and once `ORDER_EXPIRY_SECS` have passed
+//! (longer than a blockhash lives, so nothing sent can still land) the wallet's token balance
+//! decides whether it filled. Until then the new buy is refused, as it is once the mint has a
+//! filled buy, unless the new buy is a dip re-entry not yet taken for the mint. Open orders left
+//! by a crash are reconciled the same way at startup.
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use spl_associated_token_account::{get_associated_token_address, get_associated_token_address_with_program_id};
+
+use crate::common::clock::{system_clock, Clock};
+use crate::error::BotError;
+use crate::library::rpc_api::{RpcApi, SignatureStatus};
+use crate::processor::trade_ledger::TradeSide;
+
+lazy_static! {
+    static ref SHARED_ORDERS: Arc<OrderLedger> = Arc::new(OrderLedger::from_env());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderState {
+    /// Recorded, not sent yet (or the send failed in a way that may still have landed)
+    Pending,
+    Submitted,
+    Filled,
+    Failed,
+    /// Never landed before its transactions could no longer be valid
+    Expired,
+}
+
+impl OrderState {
+    pub fn is_open(&self) -> bool {
+        matches!(self, OrderState::Pending | OrderState::Submitted)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Order {
+    pub id: String,
+    pub mint: String,
+    pub side: TradeSide,
+    /// Unix seconds
+    pub created_at: i64,
+    /// Unix seconds
+    pub updated_at: i64,
+    pub state: OrderState,
+    /// Every signature sent for the order
+    #[serde(default)]
+    pub signatures: Vec<String>,
+    /// Signatures that failed on chain
+    #[serde(default)]
+    pub failed_signatures: Vec<String>,
+    /// Signature that filled the order, or why it did not
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Dip re-entry number for a buy back into a mint already bought
+    #[serde(default)]
+    pub reentry: Option<u32>,
+}
+
+/// Why an order was refused
+#[derive(Clone, Debug, PartialEq)]
+pub enum DuplicateOrder {
+    /// An earlier order of the mint may still land
+    Open { id: String },
+    /// The mint has already been bought
+    AlreadyFilled { id: String },
+    /// The earlier order could not be checked against the chain
+    Unresolved { id: String, error: String },
+}
+
+impl std::fmt::Display for DuplicateOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DuplicateOrder::Open { id } => write!(f, "Order {} for this mint is still open", id),
+            DuplicateOrder::AlreadyFilled { id } => write!(f, "Order {} already bought this mint", id),
+            DuplicateOrder::Unresolved { id, error } => write!(f, "Order {} could not be reconciled: {}", id, error),
+        }
+    }
+}
+
+pub struct OrderLedger {
+    path: Option<PathBuf>,
+    expiry_secs: i64,
+    clock: Arc<dyn Clock>,
+    orders: Mutex<Vec<Order>>,
+}
+
+impl OrderLedger {
+    /// Ledger persisted at `path` (if any), reloading orders recorded by previous runs
+    pub fn new(path: Option<PathBuf>, expiry_secs: i64) -> Self {
+        let orders = path.as_ref().map(load_orders).unwrap_or_default();
+        Self { path, expiry_secs, clock: system_clock(), orders: Mutex::new(orders) }
+    }
+
+    pub fn in_memory(expiry_secs: i64) -> Self {
+        Self::new(None, expiry_secs)
+    }
+
+    pub fn from_env() -> Self {
+        let path = std::env::var("ORDER_LEDGER_PATH").unwrap_or_else(|_| "order_ledger.jsonl".to_string());
+        let expiry_secs = std::env::var("ORDER_EXPIRY_SECS").ok().and_then(|v| v.parse().ok()).unwrap_or(90);
+        Self::new(Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty()), expiry_secs)
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Process-wide ledger
+    pub fn shared() -> Arc<OrderLedger> {
+        SHARED_ORDERS.clone()
+    }
+
+    /// Record a new pending order for `mint`, after reconciling any open order of the same mint
+    /// and side; refused while that order may still land, or for a mint already bought unless
+    /// `reentry` is a dip re-entry later than the one that filled
+    pub async fn begin(
+        &self,
+        mint: &str,
+        side: TradeSide,
+        reentry: Option<u32>,
+        rpc: &dyn RpcApi,
+        wallet: &Pubkey,
+    ) -> Result<Order, DuplicateOrder> {
+        let open = self.find(|o| o.mint == mint && o.side == side && o.state.is_open());
+        if let Some(order) = open {
+            self.reconcile_order(&order, rpc, wallet).await
+                .map_err(|e| DuplicateOrder::Unresolved { id: order.id.clone(), error: e.to_string() })?;
+        }
+
+        let mut orders = self.orders.lock().unwrap();
+        let previous = orders.iter().rev().find(|o| o.mint == mint && o.side == side);
+        match previous {
+            Some(order) if order.state.is_open() => return Err(DuplicateOrder::Open { id: order.id.clone() }),
+            Some(order) if side == TradeSide::Buy && order.state == OrderState::Filled && reentry <= order.reentry => {
+                return Err(DuplicateOrder::AlreadyFilled { id: order.id.clone() })
+            }
+            _ => {}
+        }
+        let now = self.clock.now();
+        let order = Order {
+            id: format!("{:x}-{:08x}", now.timestamp_millis(), rand::random::<u32>()),
+            mint: mint.to_string(),
+            side,
+            created_at: now.timestamp(),
+            updated_at: now.timestamp(),
+            state: OrderState::Pending,
+            signatures: Vec::new(),
+            failed_signatures: Vec::new(),
+            reason: None,
+            reentry,
+        };
+        self.append(&order);
+        orders.push(order.clone());
+        Ok(order)
+    }
+
+    /// The order's transactions were sent
+    pub fn submitted(&self, id: &str, signatures: &[String]) {
+        self.update(|o| o.id == id, |order| {
+            order.signatures.extend(signatures.iter().cloned());
+            order.state = OrderState::Submitted;
+        });
+    }
+
+    /// Sending failed; an error that may have reached the network leaves the order pending until
+    /// it is reconciled
+    pub fn send_failed(&self, id: &str, error: &BotError) {
+        if error.is_transient() {
+            return;
+        }
+        self.update(|o| o.id == id, |order| {
+            order.state = OrderState::Failed;
+            order.reason = Some(error.to_string());
+        });
+    }
+
+    /// A signature of an open order confirmed
+    pub fn signature_landed(&self, signature: &str) {
+        self.update(|o| o.state.is_open() && o.signatures.iter().any(|s| s == signature), |order| {
+            order.state = OrderState::Filled;
+            order.reason = Some(signature.to_string());
+        });
+    }
+
+    /// A signature of an open order failed on chain; the order fails once it has no other
+    /// signature left that could land
+    pub fn signature_failed(&self, signature: &str, error: &str) {
+        self.update(|o| o.state.is_open() && o.signatures.iter().any(|s| s == signature), |order| {
+            if !order.failed_signatures.iter().any(|s| s == signature) {
+                order.failed_signatures.push(signature.to_string());
+            }
+            if order.signatures.iter().all(|s| order.failed_signatures.contains(s)) {
+                order.state = OrderState::Failed;
+                order.reason = Some(error.to_string());
+            }
+        });
+    }
+
+    /// Reconcile every open order, returning the ones that reached a final state
+    pub async fn reconcile(&self, rpc: &dyn RpcApi, wallet: &Pubkey) -> Vec<Order> {
+        let open: Vec<Order> = self.orders.lock().unwrap().iter().filter(|o| o.state.is_open()).cloned().collect();
+        let mut resolved = Vec::new();
+        for order in open {
+            match self.reconcile_order(&order, rpc, wallet).await {
+                Ok(Some(order)) => resolved.push(order),
+                Ok(None) => {}
+                Err(e) => eprintln!("Could not reconcile order {}: {}", order.id, e),
+            }
+        }
+        resolved
+    }
+
+    pub fn orders(&self) -> Vec<Order> {
+        self.orders.lock().unwrap().clone()
+    }
+
+    pub fn get(&self, id: &str) -> Option<Order> {
+        self.find(|o| o.id == id)
+    }
+
+    /// Settle an open order from the chain; Some with the final order when it settled
+    async fn reconcile_order(&self, order: &Order, rpc: &dyn RpcApi, wallet: &Pubkey) -> Result<Option<Order>, BotError> {
+        let mut failed = Vec::new();
+        for signature in &order.signatures {
+            let parsed = Signature::from_str(signature)
+                .map_err(|e| BotError::InvalidInput(format!("Invalid signature {}: {}", signature, e)))?;
+            match rpc.signature_status(&parsed).await? {
+                SignatureStatus::Confirmed => {
+                    return Ok(self.settle(&order.id, OrderState::Filled, signature.clone()));
+                }
+                SignatureStatus::Failed(err) => failed.push(format!("{}: {}", signature, err)),
+                SignatureStatus::Pending => {}
+            }
+        }
+        if !order.signatures.is_empty() && failed.len() == order.signatures.len() {
+            return Ok(self.settle(&order.id, OrderState::Failed, failed.join("; ")));
+        }
+        if self.clock.now().timestamp() - order.created_at <= self.expiry_secs {
+            return Ok(None);
+        }
+        // Nothing sent can land any more: the balance tells whether something sent did
+        if order.side == TradeSide::Buy && self.holds_tokens(&order.mint, rpc, wallet).await? {
+            return Ok(self.settle(&order.id, OrderState::Filled, "token balance found on reconcile".to_string()));
+        }
+        Ok(self.settle(&order.id, OrderState::Expired, "no fill before expiry".to_string()))
+    }
+
+    async fn holds_tokens(&self, mint: &str, rpc: &dyn RpcApi, wallet: &Pubkey) -> Result<bool, BotError> {
+        let mint = Pubkey::from_str(mint).map_err(|e| BotError::InvalidInput(format!("Invalid mint {}: {}", mint, e)))?;
+        let accounts = [
+            get_associated_token_address(wallet, &mint),
+            get_associated_token_address_with_program_id(wallet, &mint, &spl_token_2022::id()),
+        ];
+        for account in accounts {
+            if rpc.token_balance(&account).await?.unwrap_or(0.0) > 0.0 {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn settle(&self, id: &str, state: OrderState, reason: String) -> Option<Order> {
+        self.update(|o| o.id == id && o.state.is_open(), |order| {
+            order.state = state;
+            order.reason = Some(reason.clone());
+        })
+    }
+
+    fn find(&self, predicate: impl Fn(&Order) -> bool) -> Option<Order> {
+        self.orders.lock().unwrap().iter().rev().find(|o| predicate(o)).cloned()
+    }
+
+    /// Apply `change` to the latest order matching `predicate` and persist it
+    fn update(&self, predicate: impl Fn(&Order) -> bool, change: impl FnOnce(&mut Order)) -> Option<Order> {
+        let mut orders = self.orders.lock().unwrap();
+        let order = orders.iter_mut().rev().find(|o| predicate(o))?;
+        change(order);
+        order.updated_at = self.clock.now().timestamp();
+        let order = order.clone();
+        self.append(&order);
+        Some(order)
+    }
+
+    fn append(&self, order: &Order) {
+        if let Some(path) = &self.path {
+            let result = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| writeln!(file, "{}", serde_json::to_string(order).unwrap_or_default()));
+            if let Err(e) = result {
+                eprintln!("Failed to append to order ledger {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Orders from the ledger file, the last line of each id winning
+fn load_orders(path: &PathBuf) -> Vec<Order> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return Vec::new(),
+    };
+    let mut orders: Vec<Order> = Vec::new();
+    for order in BufReader::new(file).lines().map_while(|line| line.ok()).filter_map(|line| serde_json::from_str::<Order>(&line).ok()) {
+        match orders.iter_mut().find(|o| o.id == order.id) {
+            Some(existing) => *existing = order,
+            None => orders.push(order),
+        }
+    }
+    orders
+}
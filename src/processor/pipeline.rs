@@ -0,0 +1,740 @@
+/*!
+# Pipelines
+
+A pipeline is a deployment described in config rather than code: transaction sources, then
+filters every trade must pass, then the strategies that act on it, then the notifiers that
+deliver what the strategies report. Components are looked up by name in a
+[`ComponentRegistry`] when the pipeline is built at startup, so a pure monitor, a copy trader and
+a launch sniper are the same binary with different pipelines, and new components only need to
+be registered.
+
+A pipeline is either one of the presets (`monitor`, `copy_trader`, `launch_sniper`) or a JSON
+file:
+
+```json
+{
+  "name": "whale-watch",
+  "sources": ["yellowstone", "websocket"],
+  "filters": [{ "type": "dex", "dexes": ["PumpFun"] }, { "type": "min_sol", "sol": 5 }],
+  "strategies": [{ "type": "monitor" }, { "type": "alert", "title": "Whale trade" }],
+  "notifiers": [{ "type": "telegram" }, { "type": "log" }]
+}
+```
+
+Without `sources` the `TRANSACTION_SOURCES` setting is used. Built-in components:
+
+- Filters: `dex` (`dexes`), `swap_type` (`types`: buy, sell, add_liquidity, remove_liquidity,
+  pool_create), `min_sol` (`sol`), `signer` (`wallets`, default the copy trading targets; the
+  wallets are added to the subscription), `launch` (`max_progress_pct`, default 10), `not_muted`
+- Strategies: `monitor` (the educational monitor, `monitor` feature), `sniper` (copy, focus and
+  dip buys of the trading bot, `trading` feature), `alert` (one notice per trade, `title`), `wasm` (a sandboxed WebAssembly
+  strategy, `path`; see `wasm_strategy`)
+- Notifiers: `telegram`, `log`
+
+## Environment Variables
+
+- `PIPELINE`: Preset to run (default: none, the binary's built-in behavior)
+- `PIPELINE_PATH`: JSON pipeline file; takes precedence over `PIPELINE` (default: none)
+*/
+
+use std::collections::HashMap;
+#[cfg(feature = "trading")]
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::signature::Signature;
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use colored::Colorize;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tokio_util::sync::CancellationToken;
+use yellowstone_grpc_proto::geyser::SubscribeUpdateTransaction;
+
+use crate::common::logger::Logger;
+use crate::library::transaction_source::{self, MergedSource, MonitorScope, SourceConfig, TransactionSource};
+#[cfg(feature = "monitor")]
+use crate::processor::educational_monitor::EducationalMonitor;
+use crate::processor::launch_stats::MAX_LAUNCH_PROGRESS_PCT;
+use crate::processor::market_regime::MarketRegime;
+#[cfg(feature = "trading")]
+use crate::processor::sniper_bot::{self, SniperConfig};
+use crate::processor::telegram_alerts::TelegramAlertSystem;
+use crate::processor::transaction_parser::{
+    self, collect_swaps, extract_signer_from_transaction, ParsedData, SwapType, TradeInfoFromToken,
+};
+use crate::processor::wasm_strategy::{WasmLimits, WasmStrategy};
+
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// One component in a pipeline file: its registered name and its parameters
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ComponentSpec {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(flatten)]
+    pub params: Map<String, Value>,
+}
+
+impl ComponentSpec {
+    pub fn new(kind: &str) -> Self {
+        Self { kind: kind.to_string(), params: Map::new() }
+    }
+
+    pub fn with(mut self, key: &str, value: Value) -> Self {
+        self.params.insert(key.to_string(), value);
+        self
+    }
+
+    pub fn f64(&self, key: &str) -> Option<f64> {
+        self.params.get(key).and_then(Value::as_f64)
+    }
+
+    pub fn string(&self, key: &str) -> Option<String> {
+        self.params.get(key).and_then(Value::as_str).map(str::to_string)
+    }
+
+    /// A list parameter, given either as a JSON array or a comma separated string
+    pub fn strings(&self, key: &str) -> Option<Vec<String>> {
+        match self.params.get(key)? {
+            Value::Array(items) => Some(items.iter().filter_map(Value::as_str).map(str::to_string).collect()),
+            Value::String(list) => Some(list.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct PipelineSpec {
+    #[serde(default)]
+    pub name: String,
+    /// Transaction source names; empty uses TRANSACTION_SOURCES
+    #[serde(default)]
+    pub sources: Vec<String>,
+    #[serde(default)]
+    pub filters: Vec<ComponentSpec>,
+    #[serde(default)]
+    pub strategies: Vec<ComponentSpec>,
+    #[serde(default)]
+    pub notifiers: Vec<ComponentSpec>,
+}
+
+impl PipelineSpec {
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// The built-in deployments
+    pub fn preset(name: &str) -> Option<Self> {
+        let pipeline = |strategies: Vec<ComponentSpec>| Self {
+            name: name.to_string(),
+            strategies,
+            ..Self::default()
+        };
+        match name {
+            "monitor" => Some(pipeline(vec![ComponentSpec::new("monitor")])),
+            "copy_trader" => Some(pipeline(vec![ComponentSpec::new("sniper")])),
+            "launch_sniper" => Some(Self {
+                filters: vec![ComponentSpec::new("launch"), ComponentSpec::new("not_muted")],
+                notifiers: vec![ComponentSpec::new("telegram")],
+                ..pipeline(vec![ComponentSpec::new("sniper"), ComponentSpec::new("alert").with("title", "New launch".into())])
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn has_strategy(&self, kind: &str) -> bool {
+        self.strategies.iter().any(|s| s.kind == kind)
+    }
+
+    /// The pipeline in PIPELINE_PATH, else the PIPELINE preset; None when neither is set
+    pub fn from_env() -> Result<Option<Self>> {
+        if let Some(path) = std::env::var("PIPELINE_PATH").ok().filter(|p| !p.is_empty()) {
+            let json = std::fs::read_to_string(&path).with_context(|| format!("Failed to read pipeline {}", path))?;
+            let mut spec = Self::from_json(&json).with_context(|| format!("Invalid pipeline {}", path))?;
+            if spec.name.is_empty() {
+                spec.name = path;
+            }
+            return Ok(Some(spec));
+        }
+        match std::env::var("PIPELINE").ok().filter(|p| !p.is_empty()) {
+            Some(name) => Self::preset(&name)
+                .map(Some)
+                .ok_or_else(|| anyhow!("Unknown PIPELINE preset '{}' (expected monitor, copy_trader or launch_sniper)", name)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// A trade or liquidity event flowing through a pipeline
+#[derive(Clone, Debug)]
+pub struct PipelineEvent {
+    pub parsed: ParsedData,
+    /// Raw trade and transaction, for strategies that trade on them; absent for liquidity events
+    pub trade: Option<(TradeInfoFromToken, Arc<SubscribeUpdateTransaction>)>,
+}
+
+impl PipelineEvent {
+    pub fn from_parsed(parsed: ParsedData) -> Self {
+        Self { parsed, trade: None }
+    }
+}
+
+/// Something a strategy wants delivered
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notice {
+    pub title: String,
+    pub body: String,
+}
+
+pub trait EventFilter: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn accepts(&self, event: &PipelineEvent) -> bool;
+
+    /// Wallets the source subscription must include for this filter to see anything
+    fn wallets(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+#[async_trait]
+pub trait Strategy: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn handle(&self, event: &PipelineEvent) -> Result<Vec<Notice>>;
+}
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn notify(&self, notice: &Notice) -> Result<()>;
+}
+
+/// What the binary running the pipeline can offer its components
+#[derive(Clone, Default)]
+pub struct PipelineContext {
+    #[cfg(feature = "monitor")]
+    pub monitor: Option<Arc<EducationalMonitor>>,
+    #[cfg(feature = "trading")]
+    pub sniper: Option<Arc<SniperConfig>>,
+    pub telegram: Option<Arc<TelegramAlertSystem>>,
+    /// Used to load address lookup tables before parsing
+    pub rpc: Option<Arc<RpcClient>>,
+}
+
+type Factory<T> = Box<dyn Fn(&ComponentSpec, &PipelineContext) -> Result<Box<T>> + Send + Sync>;
+
+/// Component constructors by name
+#[derive(Default)]
+pub struct ComponentRegistry {
+    filters: HashMap<String, Factory<dyn EventFilter>>,
+    strategies: HashMap<String, Factory<dyn Strategy>>,
+    notifiers: HashMap<String, Factory<dyn Notifier>>,
+}
+
+impl ComponentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry with the built-in filters, strategies and notifiers
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register_filter("dex", |spec, _| {
+            let dexes = spec.strings("dexes").ok_or_else(|| anyhow!("dex filter needs 'dexes'"))?;
+            Ok(Box::new(DexFilter { dexes }))
+        });
+        registry.register_filter("swap_type", |spec, _| {
+            let types = spec.strings("types").ok_or_else(|| anyhow!("swap_type filter needs 'types'"))?;
+            let types = types.iter().map(|t| parse_swap_type(t)).collect::<Result<Vec<_>>>()?;
+            Ok(Box::new(SwapTypeFilter { types }))
+        });
+        registry.register_filter("min_sol", |spec, _| {
+            let sol = spec.f64("sol").ok_or_else(|| anyhow!("min_sol filter needs 'sol'"))?;
+            Ok(Box::new(MinSolFilter { sol }))
+        });
+        registry.register_filter("signer", |spec, ctx| {
+            let wallets = match spec.strings("wallets") {
+                Some(wallets) => wallets,
+                None => {
+                    #[cfg(feature = "trading")]
+                    let targets = ctx.sniper.as_ref().map(|sniper| sniper.target_addresses.clone());
+                    #[cfg(not(feature = "trading"))]
+                    let targets: Option<Vec<String>> = None;
+                    targets
+                        .or_else(|| std::env::var("COPY_TRADING_TARGET_ADDRESS").ok()
+                            .map(|v| v.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()))
+                        .unwrap_or_default()
+                }
+            };
+            if wallets.is_empty() {
+                return Err(anyhow!("signer filter needs 'wallets' or COPY_TRADING_TARGET_ADDRESS"));
+            }
+            Ok(Box::new(SignerFilter { wallets }))
+        });
+        registry.register_filter("launch", |spec, _| {
+            Ok(Box::new(LaunchFilter { max_progress_pct: spec.f64("max_progress_pct").unwrap_or(MAX_LAUNCH_PROGRESS_PCT) }))
+        });
+        registry.register_filter("not_muted", |_, _| Ok(Box::new(NotMutedFilter)));
+
+        #[cfg(feature = "monitor")]
+        registry.register_strategy("monitor", |_, ctx| {
+            let monitor = ctx.monitor.clone().ok_or_else(|| anyhow!("monitor strategy needs the educational monitor"))?;
+            Ok(Box::new(MonitorStrategy { monitor }))
+        });
+        #[cfg(feature = "trading")]
+        registry.register_strategy("sniper", |_, ctx| {
+            let config = ctx.sniper.clone().ok_or_else(|| anyhow!("sniper strategy needs the trading bot"))?;
+            Ok(Box::new(SniperStrategy { config }))
+        });
+        registry.register_strategy("alert", |spec, _| {
+            Ok(Box::new(AlertStrategy { title: spec.string("title").unwrap_or_else(|| "Pipeline alert".to_string()) }))
+        });
+        registry.register_strategy("wasm", |spec, _| {
+            let path = spec.string("path").ok_or_else(|| anyhow!("wasm strategy needs 'path'"))?;
+            let defaults = WasmLimits::default();
+            let limits = WasmLimits {
+                fuel: spec.f64("fuel").map(|fuel| fuel as u64).unwrap_or(defaults.fuel),
+                max_memory_bytes: spec.f64("max_memory_mb")
+                    .map(|mb| (mb * 1024.0 * 1024.0) as usize)
+                    .unwrap_or(defaults.max_memory_bytes),
+            };
+            Ok(Box::new(WasmStrategy::from_file(std::path::Path::new(&path), spec.string("name"), limits)?))
+        });
+
+        registry.register_notifier("telegram", |_, ctx| {
+            let telegram = ctx.telegram.clone().ok_or_else(|| anyhow!("telegram notifier needs Telegram alerts to be configured"))?;
+            Ok(Box::new(TelegramNotifier { telegram }))
+        });
+        registry.register_notifier("log", |_, _| {
+            Ok(Box::new(LogNotifier { logger: Logger::new("[PIPELINE] => ".magenta().to_string()) }))
+        });
+        registry
+    }
+
+    pub fn register_filter(
+        &mut self,
+        name: &str,
+        factory: impl Fn(&ComponentSpec, &PipelineContext) -> Result<Box<dyn EventFilter>> + Send + Sync + 'static,
+    ) {
+        self.filters.insert(name.to_string(), Box::new(factory));
+    }
+
+    pub fn register_strategy(
+        &mut self,
+        name: &str,
+        factory: impl Fn(&ComponentSpec, &PipelineContext) -> Result<Box<dyn Strategy>> + Send + Sync + 'static,
+    ) {
+        self.strategies.insert(name.to_string(), Box::new(factory));
+    }
+
+    pub fn register_notifier(
+        &mut self,
+        name: &str,
+        factory: impl Fn(&ComponentSpec, &PipelineContext) -> Result<Box<dyn Notifier>> + Send + Sync + 'static,
+    ) {
+        self.notifiers.insert(name.to_string(), Box::new(factory));
+    }
+}
+
+fn build_all<T: ?Sized>(
+    kind: &str,
+    specs: &[ComponentSpec],
+    factories: &HashMap<String, Factory<T>>,
+    ctx: &PipelineContext,
+) -> Result<Vec<Box<T>>> {
+    specs.iter()
+        .map(|spec| {
+            let factory = factories.get(&spec.kind).ok_or_else(|| anyhow!("Unknown {} '{}'", kind, spec.kind))?;
+            factory(spec, ctx).with_context(|| format!("Failed to build {} '{}'", kind, spec.kind))
+        })
+        .collect()
+}
+
+fn parse_swap_type(value: &str) -> Result<SwapType> {
+    match value.trim().to_lowercase().as_str() {
+        "buy" => Ok(SwapType::Buy),
+        "sell" => Ok(SwapType::Sell),
+        "add_liquidity" => Ok(SwapType::AddLiquidity),
+        "remove_liquidity" => Ok(SwapType::RemoveLiquidity),
+        "pool_create" => Ok(SwapType::PoolCreate),
+        other => Err(anyhow!("Unknown swap type '{}'", other)),
+    }
+}
+
+pub struct Pipeline {
+    name: String,
+    sources: Vec<String>,
+    filters: Vec<Box<dyn EventFilter>>,
+    strategies: Vec<Box<dyn Strategy>>,
+    notifiers: Vec<Box<dyn Notifier>>,
+    rpc: Option<Arc<RpcClient>>,
+    logger: Logger,
+}
+
+impl Pipeline {
+    /// Assemble the pipeline from registered components; an unknown name, bad parameters or a
+    /// component the binary cannot provide fails the whole pipeline
+    pub fn build(spec: &PipelineSpec, registry: &ComponentRegistry, ctx: &PipelineContext) -> Result<Self> {
+        let strategies = build_all("strategy", &spec.strategies, &registry.strategies, ctx)?;
+        if strategies.is_empty() {
+            return Err(anyhow!("Pipeline '{}' has no strategies", spec.name));
+        }
+        Ok(Self {
+            name: spec.name.clone(),
+            sources: spec.sources.clone(),
+            filters: build_all("filter", &spec.filters, &registry.filters, ctx)?,
+            strategies,
+            notifiers: build_all("notifier", &spec.notifiers, &registry.notifiers, ctx)?,
+            rpc: ctx.rpc.clone(),
+            logger: Logger::new(format!("[PIPELINE {}] => ", spec.name).magenta().to_string()),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// One line for the startup log
+    pub fn describe(&self) -> String {
+        let names = |names: Vec<&str>| if names.is_empty() { "-".to_string() } else { names.join(", ") };
+        format!(
+            "{}: sources [{}] → filters [{}] → strategies [{}] → notifiers [{}]",
+            self.name,
+            if self.sources.is_empty() { "TRANSACTION_SOURCES".to_string() } else { self.sources.join(", ") },
+            names(self.filters.iter().map(|f| f.name()).collect()),
+            names(self.strategies.iter().map(|s| s.name()).collect()),
+            names(self.notifiers.iter().map(|n| n.name()).collect()),
+        )
+    }
+
+    /// Run an event through the filters, the strategies and the notifiers. Returns whether it
+    /// passed the filters.
+    pub async fn handle(&self, event: &PipelineEvent) -> bool {
+        if !self.filters.iter().all(|filter| filter.accepts(event)) {
+            return false;
+        }
+        for strategy in &self.strategies {
+            let notices = match strategy.handle(event).await {
+                Ok(notices) => notices,
+                Err(e) => {
+                    self.logger.log(format!("{} failed on {}: {}", strategy.name(), event.parsed.token_mint, e).red().to_string());
+                    continue;
+                }
+            };
+            for notice in &notices {
+                for notifier in &self.notifiers {
+                    if let Err(e) = notifier.notify(notice).await {
+                        self.logger.log(format!("{} notifier failed: {}", notifier.name(), e).red().to_string());
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// The events of one streamed transaction: its trades and its liquidity events
+    async fn events(&self, txn: &SubscribeUpdateTransaction) -> Vec<PipelineEvent> {
+        let info = match txn.transaction.as_ref() {
+            Some(info) => info,
+            None => return Vec::new(),
+        };
+        let inner_instructions = info.meta.as_ref().map(|meta| meta.inner_instructions.clone()).unwrap_or_default();
+        let signer = match extract_signer_from_transaction(txn) {
+            Some(signer) if !inner_instructions.is_empty() => signer,
+            _ => return Vec::new(),
+        };
+        if let Some(rpc) = &self.rpc {
+            transaction_parser::prefetch_lookup_tables(rpc, txn).await;
+        }
+        let signature = Signature::try_from(info.signature.clone()).ok();
+        let shared_txn = Arc::new(txn.clone());
+        let mut events = Vec::new();
+        for mut trade in collect_swaps(txn, &inner_instructions) {
+            trade.slot = txn.slot;
+            if let Some(signature) = signature {
+                trade.signature = signature.to_string();
+            }
+            if let Some(parsed) = ParsedData::from_trade_info(&trade, &signer) {
+                events.push(PipelineEvent { parsed, trade: Some((trade, shared_txn.clone())) });
+            }
+        }
+        events.extend(
+            transaction_parser::extract_liquidity_events(txn)
+                .iter()
+                .filter_map(|event| ParsedData::from_liquidity_event(event, &signer))
+                .map(PipelineEvent::from_parsed),
+        );
+        events
+    }
+
+    /// Subscribe to the pipeline's sources and run every event through it until cancelled or
+    /// the stream ends
+    pub async fn run(self, source_config: SourceConfig, cancel_token: CancellationToken) -> Result<()> {
+        let source: Box<dyn TransactionSource> = match self.sources.len() {
+            0 => transaction_source::from_env(&source_config)?,
+            1 => transaction_source::source_by_name(&self.sources[0], &source_config)?,
+            _ => Box::new(MergedSource::new(
+                self.sources.iter().map(|name| transaction_source::source_by_name(name, &source_config)).collect::<Result<Vec<_>>>()?,
+            )),
+        };
+        let scope = MonitorScope::from_env().map_err(|e| anyhow!(e))?;
+        let wallets: Vec<String> = self.filters.iter().flat_map(|filter| filter.wallets()).collect();
+        let mut stream = source.subscribe(&scope.filter_for(&wallets)).await
+            .with_context(|| format!("Failed to subscribe to {} source", source.name()))?;
+        self.logger.log(format!("Running {}", self.describe()).green().to_string());
+
+        loop {
+            let txn = tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                item = stream.next() => match item {
+                    Some(Ok(txn)) => txn,
+                    Some(Err(e)) => {
+                        self.logger.log(format!("Stream error: {}", e).red().to_string());
+                        break;
+                    }
+                    None => {
+                        self.logger.log("Stream ended".yellow().to_string());
+                        break;
+                    }
+                },
+            };
+            crate::library::slot_lag::observe_stream_slot(txn.slot);
+            for event in self.events(&txn).await {
+                crate::library::event_export::publish(&event.parsed);
+                MarketRegime::shared().observe(&event.parsed);
+                self.handle(&event).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Build the pipeline configured in the environment, if any, and run it in the background
+pub fn start_from_env(
+    ctx: PipelineContext,
+    source_config: SourceConfig,
+    cancel_token: CancellationToken,
+) -> Result<Option<tokio::task::JoinHandle<()>>> {
+    match PipelineSpec::from_env()? {
+        Some(spec) => start(&spec, ctx, source_config, cancel_token).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Build `spec` and run it over the live stream
+pub fn start(
+    spec: &PipelineSpec,
+    ctx: PipelineContext,
+    source_config: SourceConfig,
+    cancel_token: CancellationToken,
+) -> Result<tokio::task::JoinHandle<()>> {
+    let pipeline = Pipeline::build(spec, &ComponentRegistry::with_builtins(), &ctx)?;
+    #[cfg(feature = "trading")]
+    if let Some(sniper) = &ctx.sniper {
+        if spec.has_strategy("sniper") {
+            sniper_bot::start_stream_trading(sniper);
+        }
+    }
+    Ok(tokio::spawn(async move {
+        let logger = pipeline.logger.clone();
+        if let Err(e) = pipeline.run(source_config, cancel_token).await {
+            logger.log(format!("Pipeline stopped: {}", e).red().to_string());
+        }
+    }))
+}
+
+struct DexFilter {
+    dexes: Vec<String>,
+}
+
+impl EventFilter for DexFilter {
+    fn name(&self) -> &str {
+        "dex"
+    }
+
+    fn accepts(&self, event: &PipelineEvent) -> bool {
+        self.dexes.iter().any(|dex| dex.eq_ignore_ascii_case(&event.parsed.dex_name))
+    }
+}
+
+struct SwapTypeFilter {
+    types: Vec<SwapType>,
+}
+
+impl EventFilter for SwapTypeFilter {
+    fn name(&self) -> &str {
+        "swap_type"
+    }
+
+    fn accepts(&self, event: &PipelineEvent) -> bool {
+        self.types.contains(&event.parsed.swap_type)
+    }
+}
+
+struct MinSolFilter {
+    sol: f64,
+}
+
+impl EventFilter for MinSolFilter {
+    fn name(&self) -> &str {
+        "min_sol"
+    }
+
+    fn accepts(&self, event: &PipelineEvent) -> bool {
+        event.parsed.sol_amount.is_some_and(|sol| sol >= self.sol)
+    }
+}
+
+struct SignerFilter {
+    wallets: Vec<String>,
+}
+
+impl EventFilter for SignerFilter {
+    fn name(&self) -> &str {
+        "signer"
+    }
+
+    fn accepts(&self, event: &PipelineEvent) -> bool {
+        let signer = event.parsed.signer.to_string();
+        self.wallets.iter().any(|wallet| *wallet == signer)
+    }
+
+    fn wallets(&self) -> Vec<String> {
+        self.wallets.clone()
+    }
+}
+
+/// Tokens still early on their bonding curve
+struct LaunchFilter {
+    max_progress_pct: f64,
+}
+
+impl EventFilter for LaunchFilter {
+    fn name(&self) -> &str {
+        "launch"
+    }
+
+    fn accepts(&self, event: &PipelineEvent) -> bool {
+        event.parsed.bonding_curve_progress.is_some_and(|p| p < self.max_progress_pct)
+    }
+}
+
+struct NotMutedFilter;
+
+impl EventFilter for NotMutedFilter {
+    fn name(&self) -> &str {
+        "not_muted"
+    }
+
+    fn accepts(&self, event: &PipelineEvent) -> bool {
+        !crate::processor::mute_list::is_token_muted(&event.parsed.token_mint.to_string())
+    }
+}
+
+#[cfg(feature = "monitor")]
+struct MonitorStrategy {
+    monitor: Arc<EducationalMonitor>,
+}
+
+#[cfg(feature = "monitor")]
+#[async_trait]
+impl Strategy for MonitorStrategy {
+    fn name(&self) -> &str {
+        "monitor"
+    }
+
+    async fn handle(&self, event: &PipelineEvent) -> Result<Vec<Notice>> {
+        self.monitor.process_for_education(&event.parsed).await?;
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(feature = "trading")]
+struct SniperStrategy {
+    config: Arc<SniperConfig>,
+}
+
+#[cfg(feature = "trading")]
+#[async_trait]
+impl Strategy for SniperStrategy {
+    fn name(&self) -> &str {
+        "sniper"
+    }
+
+    async fn handle(&self, event: &PipelineEvent) -> Result<Vec<Notice>> {
+        let (trade, txn) = match &event.trade {
+            Some((trade, txn)) if trade.mint != WSOL_MINT => (trade.clone(), txn.clone()),
+            _ => return Ok(Vec::new()),
+        };
+        let signature = Signature::from_str(&trade.signature).ok();
+        let config = self.config.clone();
+        // Buys wait on confirmations; the stream moves on meanwhile
+        tokio::spawn(async move {
+            let _ = sniper_bot::handle_stream_trade(trade, config, signature, &txn).await;
+        });
+        Ok(Vec::new())
+    }
+}
+
+/// A notice for every trade that reached it, for filter-only alerting pipelines
+struct AlertStrategy {
+    title: String,
+}
+
+#[async_trait]
+impl Strategy for AlertStrategy {
+    fn name(&self) -> &str {
+        "alert"
+    }
+
+    async fn handle(&self, event: &PipelineEvent) -> Result<Vec<Notice>> {
+        let parsed = &event.parsed;
+        Ok(vec![Notice {
+            title: self.title.clone(),
+            body: format!(
+                "{:?} of {} ({}) on {}: {:.3} SOL by {}",
+                parsed.swap_type,
+                parsed.token_name.as_deref().unwrap_or("Unknown"),
+                parsed.token_mint,
+                parsed.dex_name,
+                parsed.sol_amount.unwrap_or_default(),
+                parsed.signer
+            ),
+        }])
+    }
+}
+
+struct TelegramNotifier {
+    telegram: Arc<TelegramAlertSystem>,
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    async fn notify(&self, notice: &Notice) -> Result<()> {
+        self.telegram.send_custom_alert(&notice.title, &notice.body).await
+    }
+}
+
+struct LogNotifier {
+    logger: Logger,
+}
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    fn name(&self) -> &str {
+        "log"
+    }
+
+    async fn notify(&self, notice: &Notice) -> Result<()> {
+        self.logger.log(format!("{}: {}", notice.title, notice.body));
+        Ok(())
+    }
+}
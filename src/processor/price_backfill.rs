@@ -0,0 +1,214 @@
+/*!
+# Price Backfill
+
+A token usually starts being tracked mid-life, when a target wallet first trades it. Its
+`initial_price`, low and high would then be anchored at that arbitrary first sighting, and the
+drop measured from "initial" would say nothing about the token's recent history.
+
+When a token enters the focus list, its most recent trades are read back from chain (the mint's
+recent signatures, each transaction run through the regular swap parser) and the tracked metrics
+are rebuilt from them: the initial price becomes the oldest backfilled price, the low and high
+cover the backfilled range, and the price history and slot timeseries are seeded with it. Live
+updates that arrived meanwhile are kept; only trades older than the first live one are added.
+
+## Environment Variables
+
+- `PRICE_BACKFILL_ENABLED`: Backfill newly tracked tokens (default: `true`)
+- `PRICE_BACKFILL_TRADES`: Recent transactions of the mint read per token (default: `30`)
+*/
+
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use anchor_client::solana_client::rpc_config::RpcTransactionConfig;
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature};
+use anyhow::Result;
+use async_trait::async_trait;
+use colored::Colorize;
+use lazy_static::lazy_static;
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::common::logger::Logger;
+use crate::common::timeseries::{self, SlotSample};
+use crate::library::transaction_source::convert;
+use crate::processor::sniper_bot::{FocusTokenInfo, FOCUS_TOKEN_LIST};
+use crate::processor::transaction_parser::{self, TradeInfoFromToken};
+
+/// Prices kept in a focus token's history
+const PRICE_HISTORY_CAPACITY: usize = 100;
+
+lazy_static! {
+    static ref LOGGER: Logger = Logger::new("[PRICE-BACKFILL] => ".cyan().to_string());
+}
+
+#[derive(Clone, Debug)]
+pub struct BackfillConfig {
+    pub enabled: bool,
+    /// Recent transactions of the mint read
+    pub max_transactions: usize,
+}
+
+impl Default for BackfillConfig {
+    fn default() -> Self {
+        Self { enabled: true, max_transactions: 30 }
+    }
+}
+
+impl BackfillConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            enabled: var("PRICE_BACKFILL_ENABLED")
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(defaults.enabled),
+            max_transactions: var("PRICE_BACKFILL_TRADES")
+                .and_then(|v| v.parse().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(defaults.max_transactions),
+        }
+    }
+}
+
+/// Past trades of a token
+#[async_trait]
+pub trait TradeHistory: Send + Sync {
+    /// Trades of `mint` among its `limit` most recent transactions, in any order
+    async fn recent_trades(&self, mint: &Pubkey, limit: usize) -> Result<Vec<TradeInfoFromToken>>;
+}
+
+/// Trade history read through `getSignaturesForAddress` and `getTransaction`
+pub struct RpcTradeHistory {
+    rpc_client: Arc<RpcClient>,
+}
+
+impl RpcTradeHistory {
+    pub fn new(rpc_client: Arc<RpcClient>) -> Self {
+        Self { rpc_client }
+    }
+}
+
+#[async_trait]
+impl TradeHistory for RpcTradeHistory {
+    async fn recent_trades(&self, mint: &Pubkey, limit: usize) -> Result<Vec<TradeInfoFromToken>> {
+        let signatures = self.rpc_client
+            .get_signatures_for_address_with_config(mint, GetConfirmedSignaturesForAddress2Config {
+                before: None,
+                until: None,
+                limit: Some(limit),
+                commitment: Some(CommitmentConfig::confirmed()),
+            })
+            .await?;
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let mut trades = Vec::new();
+        for entry in signatures.iter().filter(|entry| entry.err.is_none()) {
+            let signature = Signature::from_str(&entry.signature)?;
+            // A transaction that cannot be read only leaves a gap in the history
+            let txn = match self.rpc_client.get_transaction_with_config(&signature, config).await {
+                Ok(encoded) => convert::from_encoded(&encoded),
+                Err(e) => Err(e.into()),
+            };
+            match txn {
+                Ok(txn) => trades.extend(transaction_parser::parse_swap_events(&txn)),
+                Err(e) => LOGGER.log(format!("Skipped {}: {}", signature, e)),
+            }
+        }
+        Ok(trades)
+    }
+}
+
+/// Price range of a token's backfilled trades
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceBackfill {
+    /// (slot, price) of each trade, oldest first
+    pub prices: Vec<(u64, f64)>,
+    pub first_price: f64,
+    pub lowest_price: f64,
+    pub highest_price: f64,
+}
+
+impl PriceBackfill {
+    /// Backfill from the priced trades of `mint` older than `before_slot`, None without any
+    pub fn from_trades(mint: &str, trades: &[TradeInfoFromToken], before_slot: Option<u64>) -> Option<Self> {
+        let mut priced: Vec<&TradeInfoFromToken> = trades.iter()
+            .filter(|trade| trade.mint == mint && trade.price > 0)
+            .filter(|trade| before_slot.map_or(true, |slot| trade.slot < slot))
+            .collect();
+        priced.sort_by_key(|trade| trade.slot);
+        let prices: Vec<(u64, f64)> = priced.iter().map(|trade| (trade.slot, trade.price as f64)).collect();
+        let first_price = prices.first()?.1;
+        Some(Self {
+            first_price,
+            lowest_price: prices.iter().map(|(_, price)| *price).fold(f64::INFINITY, f64::min),
+            highest_price: prices.iter().map(|(_, price)| *price).fold(0.0, f64::max),
+            prices,
+        })
+    }
+
+    /// Anchor a focus token at the backfilled history, keeping its live updates
+    pub fn apply_to_focus(&self, info: &mut FocusTokenInfo) {
+        info.initial_price = self.first_price;
+        info.lowest_price = info.lowest_price.min(self.lowest_price);
+        info.highest_price = info.highest_price.max(self.highest_price);
+        for (_, price) in self.prices.iter().rev() {
+            if info.price_history.len() >= PRICE_HISTORY_CAPACITY {
+                break;
+            }
+            info.price_history.push_front(*price);
+        }
+    }
+}
+
+/// Backfill a token that just entered the focus list, returning what was applied
+pub async fn backfill_focus_token(mint: &str, history: &dyn TradeHistory, config: &BackfillConfig) -> Result<Option<PriceBackfill>> {
+    let mint_pubkey = Pubkey::from_str(mint)?;
+    let trades = history.recent_trades(&mint_pubkey, config.max_transactions).await?;
+
+    let mut info = match FOCUS_TOKEN_LIST.get_mut(mint) {
+        Some(info) => info,
+        None => return Ok(None),
+    };
+    let first_live_slot = info.slot_price_history.front().map(|(slot, _)| *slot);
+    let backfill = match PriceBackfill::from_trades(mint, &trades, first_live_slot) {
+        Some(backfill) => backfill,
+        None => return Ok(None),
+    };
+    backfill.apply_to_focus(&mut info);
+    drop(info);
+
+    let samples = trades.iter()
+        .filter(|trade| trade.mint == mint && trade.price > 0)
+        .map(|trade| {
+            let sol = trade.sol_change.abs();
+            let (buy_volume, sell_volume) = if trade.is_buy { (sol, 0.0) } else { (0.0, sol) };
+            SlotSample { slot: trade.slot, price: trade.price as f64, buy_volume, sell_volume }
+        })
+        .collect();
+    timeseries::backfill_for_mint(mint, samples);
+    Ok(Some(backfill))
+}
+
+/// Backfill a newly tracked focus token in the background; None when backfills are disabled
+pub fn start_focus_backfill(mint: String, rpc_client: Arc<RpcClient>) -> Option<tokio::task::JoinHandle<()>> {
+    let config = BackfillConfig::from_env();
+    if !config.enabled {
+        return None;
+    }
+    Some(tokio::spawn(async move {
+        let history = RpcTradeHistory::new(rpc_client);
+        match backfill_focus_token(&mint, &history, &config).await {
+            Ok(Some(backfill)) => LOGGER.log(format!(
+                "{} anchored at {} backfilled trades: initial {:.2}, range {:.2} - {:.2}",
+                mint, backfill.prices.len(), backfill.first_price, backfill.lowest_price, backfill.highest_price
+            )),
+            Ok(None) => LOGGER.log(format!("No earlier trades of {} to backfill", mint)),
+            Err(e) => LOGGER.log(format!("Backfill of {} failed: {}", mint, e).red().to_string()),
+        }
+    }))
+}
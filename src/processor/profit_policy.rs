@@ -0,0 +1,378 @@
+/*!
+# Profit Policy
+
+Decides what happens to realized profits, on a schedule. Every `PROFIT_POLICY_INTERVAL_HOURS`
+the realized PnL of the trades closed since the previous run is read from the trade ledger, and
+`PROFIT_POLICY_PCT` percent of it is:
+
+- `compound`: left in the trading wallet, growing the balance later buys are sized from
+- `sweep`: transferred to the cold wallet `PROFIT_SWEEP_WALLET`
+- `usdc`: swapped to USDC through Jupiter, kept in the trading wallet
+
+Each run ends with a summary alert. Runs with less than `PROFIT_POLICY_MIN_SOL` of profit (or a
+loss) move nothing and keep the window open, so profits accumulate until they are worth a
+transaction. The end of the last completed window is kept in `PROFIT_POLICY_STATE_PATH`, so a
+restart neither repeats nor skips a window; on the very first start the window begins at startup
+and profits made before the policy was enabled are left alone. A sweep counts once its transfer
+is confirmed; one still unconfirmed closes the window anyway, so it is never sent twice.
+
+## Environment Variables
+
+- `PROFIT_POLICY_ENABLED`: Apply the policy (default: `false`)
+- `PROFIT_POLICY`: `compound`, `sweep` or `usdc` (default: `compound`)
+- `PROFIT_POLICY_PCT`: Percent of the realized profit the policy applies to (default: `50`)
+- `PROFIT_SWEEP_WALLET`: Cold wallet receiving swept profits, required by `sweep`
+- `PROFIT_POLICY_INTERVAL_HOURS`: Hours between runs (default: `24`)
+- `PROFIT_POLICY_MIN_SOL`: Smallest profit (SOL) worth acting on (default: `0.1`)
+- `PROFIT_POLICY_STATE_PATH`: File keeping the last run (default: `profit_policy.json`)
+*/
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::native_token::sol_to_lamports;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{Keypair, Signature, Signer};
+use anchor_client::solana_sdk::system_instruction;
+use anchor_client::solana_sdk::transaction::Transaction;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::common::config::AppState;
+use crate::common::logger::Logger;
+use crate::library::jupiter_api::JupiterClient;
+use crate::error::BotError;
+use crate::library::audit_log::{self, AuditAction};
+use crate::library::rpc_api::{wait_for_confirmation, RpcApi};
+use crate::processor::trade_ledger::{tax_rows, LedgerTrade, TradeLedger};
+
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const USDC_SLIPPAGE_BPS: u64 = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProfitPolicy {
+    Compound,
+    Sweep,
+    Usdc,
+}
+
+impl ProfitPolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "compound" => Some(Self::Compound),
+            "sweep" => Some(Self::Sweep),
+            "usdc" => Some(Self::Usdc),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Compound => "compound",
+            Self::Sweep => "sweep",
+            Self::Usdc => "usdc",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ProfitPolicyConfig {
+    pub policy: ProfitPolicy,
+    pub pct: f64,
+    pub sweep_wallet: Option<Pubkey>,
+    pub interval_hours: u64,
+    pub min_sol: f64,
+    /// None keeps the last run in memory only
+    pub state_path: Option<PathBuf>,
+}
+
+impl Default for ProfitPolicyConfig {
+    fn default() -> Self {
+        Self {
+            policy: ProfitPolicy::Compound,
+            pct: 50.0,
+            sweep_wallet: None,
+            interval_hours: 24,
+            min_sol: 0.1,
+            state_path: None,
+        }
+    }
+}
+
+impl ProfitPolicyConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            policy: var("PROFIT_POLICY").and_then(|v| ProfitPolicy::parse(&v)).unwrap_or(defaults.policy),
+            pct: var("PROFIT_POLICY_PCT").and_then(|v| v.parse().ok()).unwrap_or(defaults.pct),
+            sweep_wallet: var("PROFIT_SWEEP_WALLET").and_then(|v| Pubkey::from_str(v.trim()).ok()),
+            interval_hours: var("PROFIT_POLICY_INTERVAL_HOURS").and_then(|v| v.parse().ok()).unwrap_or(defaults.interval_hours),
+            min_sol: var("PROFIT_POLICY_MIN_SOL").and_then(|v| v.parse().ok()).unwrap_or(defaults.min_sol),
+            state_path: Some(PathBuf::from(var("PROFIT_POLICY_STATE_PATH").unwrap_or_else(|| "profit_policy.json".to_string()))),
+        }
+    }
+}
+
+/// Realized PnL (SOL) of the sells in `(since, until]`, and how many there were. Cost basis is
+/// taken over the whole ledger, so buys before the window still count.
+pub fn realized_profit(trades: &[LedgerTrade], since: i64, until: i64) -> (f64, usize) {
+    tax_rows(trades)
+        .iter()
+        .filter(|row| row.trade.timestamp > since && row.trade.timestamp <= until)
+        .filter_map(|row| row.realized_pnl_sol)
+        .fold((0.0, 0), |(pnl, count), trade_pnl| (pnl + trade_pnl, count + 1))
+}
+
+/// Moves profits out of the trading wallet. Both return the signature once it is confirmed; a
+/// `BotError::NotConfirmed` error means it was sent but may still land.
+#[async_trait]
+pub trait ProfitExecutor: Send + Sync {
+    async fn sweep(&self, to: &Pubkey, lamports: u64) -> Result<String>;
+    async fn convert_to_usdc(&self, lamports: u64) -> Result<String>;
+}
+
+/// Signs with the trading wallet: transfers go through the RPC node, conversions through Jupiter
+pub struct WalletProfitExecutor {
+    wallet: Arc<Keypair>,
+    rpc_api: Arc<dyn RpcApi>,
+    jupiter: JupiterClient,
+    logger: Logger,
+}
+
+impl WalletProfitExecutor {
+    pub fn new(wallet: Arc<Keypair>, rpc_api: Arc<dyn RpcApi>, rpc_client: Arc<RpcClient>) -> Self {
+        Self {
+            wallet,
+            rpc_api,
+            jupiter: JupiterClient::new(rpc_client),
+            logger: Logger::new("[PROFIT-POLICY] => ".bright_green().to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl ProfitExecutor for WalletProfitExecutor {
+    async fn sweep(&self, to: &Pubkey, lamports: u64) -> Result<String> {
+        let blockhash = self.rpc_api.latest_blockhash().await.ok_or_else(|| anyhow!("no recent blockhash"))?;
+        let transfer = system_instruction::transfer(&self.wallet.pubkey(), to, lamports);
+        let transaction = Transaction::new_signed_with_payer(&[transfer], Some(&self.wallet.pubkey()), &[&*self.wallet], blockhash);
+        // A plain transfer carries no tip, so it goes to the RPC node rather than a tip-gated relay
+        let signature = self.rpc_api.send_plain_transaction(&transaction).await?;
+        audit_log::record(
+            AuditAction::TradeSubmitted,
+            "profit_sweep",
+            serde_json::json!({
+                "signer": self.wallet.pubkey().to_string(),
+                "signatures": [signature.to_string()],
+                "to": to.to_string(),
+                "lamports": lamports,
+            }),
+        );
+        wait_for_confirmation(self.rpc_api.as_ref(), &signature, &self.logger).await?;
+        Ok(signature.to_string())
+    }
+
+    async fn convert_to_usdc(&self, lamports: u64) -> Result<String> {
+        let signature = self.jupiter.swap_with_jupiter(SOL_MINT, USDC_MINT, lamports, USDC_SLIPPAGE_BPS, &self.wallet).await?;
+        audit_log::record(
+            AuditAction::TradeSubmitted,
+            "profit_usdc",
+            serde_json::json!({ "signer": self.wallet.pubkey().to_string(), "signatures": [signature], "lamports": lamports }),
+        );
+        let sent = Signature::from_str(&signature)?;
+        wait_for_confirmation(self.rpc_api.as_ref(), &sent, &self.logger).await?;
+        Ok(signature)
+    }
+}
+
+/// What one run of the policy did
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfitSummary {
+    pub policy: ProfitPolicy,
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub sells: usize,
+    pub realized_pnl_sol: f64,
+    /// SOL the policy applied to (compounded, swept or converted)
+    pub applied_sol: f64,
+    pub signature: Option<String>,
+    /// Why nothing was applied, or why applying failed
+    pub note: Option<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct PolicyState {
+    /// Unix seconds at the end of the last completed window
+    last_run: i64,
+}
+
+pub struct ProfitPolicyEngine {
+    config: ProfitPolicyConfig,
+    executor: Arc<dyn ProfitExecutor>,
+    last_run: Mutex<Option<i64>>,
+    /// Last run in this process, including runs that carried the window over
+    last_check: Mutex<Option<i64>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl ProfitPolicyEngine {
+    pub fn new(config: ProfitPolicyConfig, executor: Arc<dyn ProfitExecutor>) -> Self {
+        let last_run = config.state_path.as_ref().and_then(|path| {
+            let content = std::fs::read_to_string(path).ok()?;
+            serde_json::from_str::<PolicyState>(&content).ok().map(|state| state.last_run)
+        });
+        Self { config, executor, last_run: Mutex::new(last_run), last_check: Mutex::new(None), clock: system_clock() }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn config(&self) -> &ProfitPolicyConfig {
+        &self.config
+    }
+
+    /// Start of the current window; the first call without a saved state opens it now
+    async fn window_start(&self) -> i64 {
+        let mut last_run = self.last_run.lock().await;
+        match *last_run {
+            Some(at) => at,
+            None => {
+                let now = self.clock.now().timestamp();
+                *last_run = Some(now);
+                self.save(now);
+                now
+            }
+        }
+    }
+
+    fn save(&self, last_run: i64) {
+        if let Some(path) = &self.config.state_path {
+            let written = serde_json::to_string(&PolicyState { last_run })
+                .map_err(anyhow::Error::from)
+                .and_then(|json| Ok(std::fs::write(path, json)?));
+            if let Err(e) = written {
+                eprintln!("Failed to save the profit policy state to {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// Whether a full interval has passed since the last run
+    pub async fn due(&self) -> bool {
+        let since = self.window_start().await.max(self.last_check.lock().await.unwrap_or(i64::MIN));
+        self.clock.now().timestamp() - since >= self.config.interval_hours as i64 * 3600
+    }
+
+    /// Apply the policy to the profits realized since the last run
+    pub async fn run(&self, trades: &[LedgerTrade]) -> ProfitSummary {
+        let since = self.window_start().await;
+        let now = self.clock.now();
+        *self.last_check.lock().await = Some(now.timestamp());
+        let (realized_pnl_sol, sells) = realized_profit(trades, since, now.timestamp());
+        let mut summary = ProfitSummary {
+            policy: self.config.policy,
+            since: DateTime::<Utc>::from_timestamp(since, 0).unwrap_or_default(),
+            until: now,
+            sells,
+            realized_pnl_sol,
+            applied_sol: 0.0,
+            signature: None,
+            note: None,
+        };
+
+        if realized_pnl_sol < self.config.min_sol {
+            summary.note = Some(format!(
+                "{:.4} SOL realized is under the {} SOL minimum, carried over",
+                realized_pnl_sol, self.config.min_sol
+            ));
+            return summary;
+        }
+        let amount_sol = realized_pnl_sol * self.config.pct.clamp(0.0, 100.0) / 100.0;
+        let result = match self.config.policy {
+            ProfitPolicy::Compound => Ok(None),
+            ProfitPolicy::Sweep => match &self.config.sweep_wallet {
+                Some(wallet) => self.executor.sweep(wallet, sol_to_lamports(amount_sol)).await.map(Some),
+                None => Err(anyhow!("PROFIT_SWEEP_WALLET is not set")),
+            },
+            ProfitPolicy::Usdc => self.executor.convert_to_usdc(sol_to_lamports(amount_sol)).await.map(Some),
+        };
+        match result {
+            Ok(signature) => {
+                summary.applied_sol = amount_sol;
+                summary.signature = signature;
+                *self.last_run.lock().await = Some(now.timestamp());
+                self.save(now.timestamp());
+            }
+            Err(e) => match BotError::classify(&e) {
+                // The transfer may still land: close the window so the next run cannot send it twice
+                BotError::NotConfirmed(signature) => {
+                    summary.note = Some(format!("{} not confirmed yet, check it before sweeping this window again", signature));
+                    summary.signature = Some(signature);
+                    *self.last_run.lock().await = Some(now.timestamp());
+                    self.save(now.timestamp());
+                }
+                _ => summary.note = Some(format!("{} failed, retrying next run: {}", self.config.policy.as_str(), e)),
+            },
+        }
+        summary
+    }
+}
+
+/// Start the profit policy if enabled via PROFIT_POLICY_ENABLED=true
+pub fn start_profit_policy(app_state: Arc<AppState>, cancel_token: CancellationToken) -> Option<tokio::task::JoinHandle<()>> {
+    let enabled = std::env::var("PROFIT_POLICY_ENABLED")
+        .ok()
+        .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+        .unwrap_or(false);
+    if !enabled {
+        return None;
+    }
+
+    let logger = Logger::new("[PROFIT-POLICY] => ".bright_green().to_string());
+    let config = ProfitPolicyConfig::from_env();
+    if config.policy == ProfitPolicy::Sweep && config.sweep_wallet.is_none() {
+        logger.log("PROFIT_POLICY=sweep needs a valid PROFIT_SWEEP_WALLET, profit policy disabled".red().to_string());
+        return None;
+    }
+    logger.log(format!(
+        "Applying the {} policy to {}% of realized profits every {}h",
+        config.policy.as_str(), config.pct, config.interval_hours
+    ));
+    let executor = Arc::new(WalletProfitExecutor::new(
+        app_state.wallet.clone(),
+        app_state.rpc_api.clone(),
+        app_state.rpc_nonblocking_client.clone(),
+    ));
+    let engine = ProfitPolicyEngine::new(config, executor);
+    let telegram = crate::processor::telegram_alerts::init_from_env().ok().flatten().map(Arc::new);
+
+    Some(tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::minutes(5).to_std().unwrap_or_default());
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = ticker.tick() => {
+                    // Only the instance holding the trading lock moves funds
+                    if crate::library::instance_lock::trading_paused_reason().is_some() || !engine.due().await {
+                        continue;
+                    }
+                    let summary = engine.run(&TradeLedger::shared().trades()).await;
+                    logger.log(format!("{:?}", summary).green().to_string());
+                    if let Some(telegram) = &telegram {
+                        let _ = telegram.alert_profit_summary(&summary).await;
+                    }
+                }
+            }
+        }
+    }))
+}
@@ -0,0 +1,319 @@
+/*!
+# Report Export
+
+Renders reports as standalone, styled HTML pages (tables, and bar charts as inline SVG, so the
+file opens anywhere without network access) saved under `REPORT_DIR`, and optionally converts
+them to PDF with an external command. Saved files can be attached to an email as they are, and
+are sent to the Telegram chat as documents after the plain-text report.
+
+The hourly and weekly educational reports are exported this way, as is the realized PnL report
+written by `--export-trades --html-out <path>`.
+
+PDF conversion runs `REPORT_PDF_COMMAND` with `{html}` and `{pdf}` replaced by the file paths,
+e.g. `wkhtmltopdf {html} {pdf}` or `chromium --headless --print-to-pdf={pdf} {html}`.
+
+## Environment Variables
+
+- `REPORT_EXPORT_ENABLED`: Save reports as HTML (default: `false`)
+- `REPORT_DIR`: Directory the reports are written to (default: `reports`)
+- `REPORT_PDF_COMMAND`: Command converting the HTML to PDF; empty skips PDF (default: empty)
+- `REPORT_TELEGRAM_DOCUMENTS`: Send saved reports to the Telegram chat (default: `true`)
+*/
+
+#[cfg(feature = "trading")]
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "trading")]
+use chrono::NaiveDate;
+
+use crate::processor::telegram_alerts::TelegramAlertSystem;
+#[cfg(feature = "trading")]
+use crate::processor::trade_ledger::{daily_pnl, LedgerTrade};
+
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 220.0;
+/// Room under the chart for the bar labels
+const LABEL_HEIGHT: f64 = 30.0;
+
+const STYLE: &str = "body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;margin:32px auto;max-width:760px;color:#1f2933}\
+h1{font-size:24px;margin-bottom:4px}h2{font-size:18px;margin-top:28px}.generated{color:#7b8794;font-size:13px}\
+p{white-space:pre-wrap;line-height:1.5}table{border-collapse:collapse;width:100%;font-size:14px}\
+th,td{border-bottom:1px solid #e4e7eb;padding:6px 8px;text-align:left}th{background:#f5f7fa}\
+td.num{text-align:right;font-variant-numeric:tabular-nums}svg text{font-size:11px;fill:#52606d}";
+
+#[derive(Clone, Debug)]
+pub struct ReportConfig {
+    pub enabled: bool,
+    pub dir: PathBuf,
+    /// Program and arguments, `{html}` and `{pdf}` replaced by the file paths
+    pub pdf_command: Option<String>,
+    pub telegram_documents: bool,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self { enabled: false, dir: PathBuf::from("reports"), pdf_command: None, telegram_documents: true }
+    }
+}
+
+impl ReportConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let flag = |name: &str, default: bool| var(name)
+            .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+            .unwrap_or(default);
+        Self {
+            enabled: flag("REPORT_EXPORT_ENABLED", defaults.enabled),
+            dir: var("REPORT_DIR").map(PathBuf::from).unwrap_or(defaults.dir),
+            pdf_command: var("REPORT_PDF_COMMAND"),
+            telegram_documents: flag("REPORT_TELEGRAM_DOCUMENTS", defaults.telegram_documents),
+        }
+    }
+}
+
+/// A part of a report
+#[derive(Clone, Debug, PartialEq)]
+pub enum Block {
+    Heading(String),
+    /// Preformatted text, line breaks kept
+    Text(String),
+    /// Cells that parse as numbers are right-aligned
+    Table { headers: Vec<String>, rows: Vec<Vec<String>> },
+    /// Labeled values; negative values are drawn below the axis
+    BarChart { title: String, bars: Vec<(String, f64)> },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub title: String,
+    pub generated_at: DateTime<Utc>,
+    pub blocks: Vec<Block>,
+}
+
+/// Files a report was saved to
+#[derive(Clone, Debug, PartialEq)]
+pub struct SavedReport {
+    pub html: PathBuf,
+    pub pdf: Option<PathBuf>,
+}
+
+impl Report {
+    pub fn new(title: &str, generated_at: DateTime<Utc>) -> Self {
+        Self { title: title.to_string(), generated_at, blocks: Vec::new() }
+    }
+
+    /// A plain-text report, one text block per paragraph
+    pub fn from_text(title: &str, generated_at: DateTime<Utc>, text: &str) -> Self {
+        let mut report = Self::new(title, generated_at);
+        for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+            report = report.text(paragraph);
+        }
+        report
+    }
+
+    pub fn heading(mut self, heading: &str) -> Self {
+        self.blocks.push(Block::Heading(heading.to_string()));
+        self
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.blocks.push(Block::Text(text.to_string()));
+        self
+    }
+
+    pub fn table(mut self, headers: &[&str], rows: Vec<Vec<String>>) -> Self {
+        self.blocks.push(Block::Table { headers: headers.iter().map(|h| h.to_string()).collect(), rows });
+        self
+    }
+
+    pub fn bar_chart(mut self, title: &str, bars: Vec<(String, f64)>) -> Self {
+        self.blocks.push(Block::BarChart { title: title.to_string(), bars });
+        self
+    }
+
+    /// Standalone HTML page
+    pub fn to_html(&self) -> String {
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n<div class=\"generated\">Generated {}</div>\n",
+            escape(&self.title),
+            STYLE,
+            escape(&self.title),
+            self.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        );
+        for block in &self.blocks {
+            match block {
+                Block::Heading(heading) => html.push_str(&format!("<h2>{}</h2>\n", escape(heading))),
+                Block::Text(text) => html.push_str(&format!("<p>{}</p>\n", escape(text))),
+                Block::Table { headers, rows } => html.push_str(&render_table(headers, rows)),
+                Block::BarChart { title, bars } => {
+                    html.push_str(&format!("<h2>{}</h2>\n", escape(title)));
+                    html.push_str(&render_bar_chart(bars));
+                }
+            }
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// File name without extension, e.g. `weekly-signal-report-2024-03-01-1200`
+    pub fn file_stem(&self) -> String {
+        let slug: String = self.title
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let slug = slug.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-");
+        format!("{}-{}", slug, self.generated_at.format("%Y-%m-%d-%H%M"))
+    }
+
+    /// Write the HTML (and PDF, when a command is configured) to `config.dir`
+    pub fn save(&self, config: &ReportConfig) -> Result<SavedReport> {
+        std::fs::create_dir_all(&config.dir)?;
+        let html = config.dir.join(format!("{}.html", self.file_stem()));
+        std::fs::write(&html, self.to_html())?;
+        let pdf = match &config.pdf_command {
+            Some(command) => {
+                let pdf = html.with_extension("pdf");
+                convert_to_pdf(command, &html, &pdf)?;
+                Some(pdf)
+            }
+            None => None,
+        };
+        Ok(SavedReport { html, pdf })
+    }
+}
+
+/// Run the PDF command on `html`, failing unless it exits successfully and wrote `pdf`
+fn convert_to_pdf(command: &str, html: &Path, pdf: &Path) -> Result<()> {
+    let args: Vec<String> = command
+        .split_whitespace()
+        .map(|arg| arg.replace("{html}", &html.to_string_lossy()).replace("{pdf}", &pdf.to_string_lossy()))
+        .collect();
+    let (program, args) = args.split_first().ok_or_else(|| anyhow!("REPORT_PDF_COMMAND is empty"))?;
+    let status = std::process::Command::new(program).args(args).status()?;
+    if !status.success() || !pdf.exists() {
+        return Err(anyhow!("{} did not produce {} ({})", program, pdf.display(), status));
+    }
+    Ok(())
+}
+
+fn render_table(headers: &[String], rows: &[Vec<String>]) -> String {
+    let mut html = String::from("<table>\n<tr>");
+    for header in headers {
+        html.push_str(&format!("<th>{}</th>", escape(header)));
+    }
+    html.push_str("</tr>\n");
+    for row in rows {
+        html.push_str("<tr>");
+        for cell in row {
+            let class = if cell.trim_start_matches('+').parse::<f64>().is_ok() { " class=\"num\"" } else { "" };
+            html.push_str(&format!("<td{}>{}</td>", class, escape(cell)));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+fn render_bar_chart(bars: &[(String, f64)]) -> String {
+    if bars.is_empty() {
+        return "<p>No data</p>\n".to_string();
+    }
+    let max = bars.iter().map(|(_, v)| v.max(0.0)).fold(0.0, f64::max);
+    let min = bars.iter().map(|(_, v)| v.min(0.0)).fold(0.0, f64::min);
+    let range = if max - min > 0.0 { max - min } else { 1.0 };
+    let zero_y = CHART_HEIGHT * max / range;
+    let slot = CHART_WIDTH / bars.len() as f64;
+    let bar_width = (slot * 0.7).max(1.0);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = CHART_WIDTH,
+        h = CHART_HEIGHT + LABEL_HEIGHT,
+    );
+    for (i, (label, value)) in bars.iter().enumerate() {
+        let height = value.abs() / range * CHART_HEIGHT;
+        let y = if *value >= 0.0 { zero_y - height } else { zero_y };
+        let x = i as f64 * slot + (slot - bar_width) / 2.0;
+        let color = if *value >= 0.0 { "#3ebd93" } else { "#ef4e4e" };
+        svg.push_str(&format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"><title>{}: {}</title></rect>\n",
+            x, y, bar_width, height, color, escape(label), value,
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n",
+            i as f64 * slot + slot / 2.0,
+            CHART_HEIGHT + LABEL_HEIGHT / 2.0 + 4.0,
+            escape(label),
+        ));
+    }
+    svg.push_str(&format!(
+        "<line x1=\"0\" y1=\"{y:.1}\" x2=\"{w}\" y2=\"{y:.1}\" stroke=\"#9aa5b1\"/>\n</svg>\n",
+        y = zero_y,
+        w = CHART_WIDTH,
+    ));
+    svg
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(feature = "trading")]
+/// Realized PnL report of ledger trades between `from` and `to` (inclusive, either open)
+pub fn pnl_report(trades: &[LedgerTrade], from: Option<NaiveDate>, to: Option<NaiveDate>, generated_at: DateTime<Utc>) -> Report {
+    let days: BTreeMap<NaiveDate, (f64, f64, usize)> = daily_pnl(trades)
+        .into_iter()
+        .filter(|(date, _)| from.map_or(true, |from| *date >= from) && to.map_or(true, |to| *date <= to))
+        .collect();
+    let (pnl, fees, count) = days.values().fold((0.0, 0.0, 0), |acc, day| (acc.0 + day.0, acc.1 + day.1, acc.2 + day.2));
+
+    Report::new("Realized PnL Report", generated_at)
+        .text(&format!(
+            "{} trades over {} days\nRealized PnL: {:+.4} SOL\nFees: {:.6} SOL",
+            count, days.len(), pnl, fees
+        ))
+        .bar_chart(
+            "Realized PnL per day (SOL)",
+            days.iter().map(|(date, day)| (date.format("%m-%d").to_string(), day.0)).collect(),
+        )
+        .heading("Daily breakdown")
+        .table(
+            &["Date", "Trades", "Fees (SOL)", "Realized PnL (SOL)"],
+            days.iter()
+                .map(|(date, (pnl, fees, count))| vec![
+                    date.to_string(),
+                    count.to_string(),
+                    format!("{:.6}", fees),
+                    format!("{:+.4}", pnl),
+                ])
+                .collect(),
+        )
+}
+
+/// Save a report when exports are enabled and send it to the chat as a document; failures are
+/// printed, the plain-text report has gone out already
+pub async fn export(report: &Report, telegram: Option<&TelegramAlertSystem>) -> Option<SavedReport> {
+    let config = ReportConfig::from_env();
+    if !config.enabled {
+        return None;
+    }
+    let saved = match report.save(&config) {
+        Ok(saved) => saved,
+        Err(e) => {
+            eprintln!("Failed to save report {}: {}", report.title, e);
+            return None;
+        }
+    };
+    if let (true, Some(telegram)) = (config.telegram_documents, telegram) {
+        let document = saved.pdf.as_ref().unwrap_or(&saved.html);
+        if let Err(e) = telegram.send_document(document, &report.title).await {
+            eprintln!("Failed to send report {}: {}", document.display(), e);
+        }
+    }
+    Some(saved)
+}
@@ -0,0 +1,267 @@
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use anyhow::{anyhow, Result};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+/// On-chain risk signals collected for a token
+#[derive(Clone, Debug, Default)]
+pub struct RiskFactors {
+    /// Mint instructions seen after we started tracking the token
+    pub unexpected_mints: u32,
+    /// Supply added by those mints, as % of the supply when tracking started
+    pub minted_pct: f64,
+    /// Supply burned since tracking started, as % of the supply when tracking started
+    pub burned_pct: f64,
+    /// FreezeAccount instructions issued by the mint's freeze authority
+    pub freeze_events: u32,
+    /// Pool SOL liquidity withdrawn since tracking started, as % of the pool at each withdrawal (summed)
+    pub liquidity_removed_pct: f64,
+    /// Early buyers funded by the creator or the creator's funder
+    pub insider_wallets: u32,
+    /// SOL those wallets bought with
+    pub insider_sol: f64,
+    /// Supply the creator moved to other wallets by plain transfer, as % of supply (summed)
+    pub creator_transferred_pct: f64,
+    /// Transfers from the creator into exchange addresses
+    pub creator_cex_deposits: u32,
+    pub last_updated: Option<Instant>,
+}
+
+impl RiskFactors {
+    /// Risk score from 0 (no red flags) to 100 (avoid), with the configured weights
+    pub fn score(&self) -> f64 {
+        self.score_with(&weights())
+    }
+
+    pub fn score_with(&self, weights: &RiskWeights) -> f64 {
+        self.explain(weights).iter().map(|c| c.points).sum::<f64>().clamp(0.0, 100.0)
+    }
+
+    /// Points each feature adds to the score, in model order (zero for features without a signal)
+    pub fn explain(&self, weights: &RiskWeights) -> Vec<Contribution> {
+        let count = |count: u32, first: f64, per_extra: f64, max_extra: u32| {
+            if count == 0 {
+                0.0
+            } else {
+                first + (count - 1).min(max_extra) as f64 * per_extra
+            }
+        };
+        vec![
+            // Any mint after launch means the authority is live and can dilute holders
+            Contribution {
+                feature: "mint_authority",
+                value: format!("{} mints after launch", self.unexpected_mints),
+                points: count(self.unexpected_mints, weights.mint_authority, weights.mint_authority_per_extra, weights.mint_authority_max_extra),
+            },
+            Contribution {
+                feature: "minted_supply",
+                value: format!("{:.1}% of supply minted", self.minted_pct),
+                points: (self.minted_pct * weights.minted_supply_per_pct).min(weights.minted_supply_cap),
+            },
+            // An active freeze authority can trap holders at any time
+            Contribution {
+                feature: "freeze_authority",
+                value: format!("{} freezes", self.freeze_events),
+                points: if self.freeze_events > 0 { weights.freeze_authority } else { 0.0 },
+            },
+            // Liquidity pulled from the pool makes exits expensive and often precedes a rug
+            Contribution {
+                feature: "liquidity_removed",
+                value: format!("{:.1}% of pool removed", self.liquidity_removed_pct),
+                points: (self.liquidity_removed_pct * weights.liquidity_removed_per_pct).min(weights.liquidity_removed_cap),
+            },
+            // Insiders holding early supply can dump on everyone who buys after them
+            Contribution {
+                feature: "insiders",
+                value: format!("{} insider wallets ({:.2} SOL)", self.insider_wallets, self.insider_sol),
+                points: count(self.insider_wallets, weights.insiders, weights.insiders_per_extra, weights.insiders_max_extra),
+            },
+            // Supply handed out by the creator off the curve usually ends up sold into buyers
+            Contribution {
+                feature: "creator_transfers",
+                value: format!("{:.1}% of supply transferred", self.creator_transferred_pct),
+                points: (self.creator_transferred_pct * weights.creator_transfers_per_pct).min(weights.creator_transfers_cap),
+            },
+            // A creator moving tokens to an exchange is getting ready to sell off-chain
+            Contribution {
+                feature: "cex_deposits",
+                value: format!("{} exchange deposits", self.creator_cex_deposits),
+                points: if self.creator_cex_deposits > 0 { weights.cex_deposits } else { 0.0 },
+            },
+        ]
+    }
+}
+
+/// What one feature adds to a token's risk score
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contribution {
+    /// Feature name, as in the weights file
+    pub feature: &'static str,
+    /// The observed value behind it
+    pub value: String,
+    pub points: f64,
+}
+
+/// Weights of the risk model, loaded from the JSON file at `RISK_WEIGHTS_PATH` (default
+/// `risk_weights.json`; built-in defaults when it does not exist). Fields left out of the file
+/// keep their default; unknown fields are rejected so a typo does not silently change nothing.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RiskWeights {
+    /// Points for the first mint after launch
+    pub mint_authority: f64,
+    /// Points for each further mint, counted up to `mint_authority_max_extra`
+    pub mint_authority_per_extra: f64,
+    pub mint_authority_max_extra: u32,
+    /// Points per % of supply minted after launch, up to `minted_supply_cap`
+    pub minted_supply_per_pct: f64,
+    pub minted_supply_cap: f64,
+    /// Points once the freeze authority has been used
+    pub freeze_authority: f64,
+    /// Points per % of pool liquidity removed, up to `liquidity_removed_cap`
+    pub liquidity_removed_per_pct: f64,
+    pub liquidity_removed_cap: f64,
+    /// Points for the first insider wallet, then per further one up to `insiders_max_extra`
+    pub insiders: f64,
+    pub insiders_per_extra: f64,
+    pub insiders_max_extra: u32,
+    /// Points per % of supply the creator transferred away, up to `creator_transfers_cap`
+    pub creator_transfers_per_pct: f64,
+    pub creator_transfers_cap: f64,
+    /// Points once the creator deposited to an exchange
+    pub cex_deposits: f64,
+}
+
+impl Default for RiskWeights {
+    fn default() -> Self {
+        Self {
+            mint_authority: 40.0,
+            mint_authority_per_extra: 10.0,
+            mint_authority_max_extra: 2,
+            minted_supply_per_pct: 1.0,
+            minted_supply_cap: 40.0,
+            freeze_authority: 50.0,
+            liquidity_removed_per_pct: 1.0,
+            liquidity_removed_cap: 50.0,
+            insiders: 30.0,
+            insiders_per_extra: 5.0,
+            insiders_max_extra: 4,
+            creator_transfers_per_pct: 1.0,
+            creator_transfers_cap: 30.0,
+            cex_deposits: 25.0,
+        }
+    }
+}
+
+impl RiskWeights {
+    /// Every weight must be a finite, non-negative number and every cap at most 100 (the score's
+    /// ceiling)
+    pub fn validate(&self) -> Result<()> {
+        let points = [
+            ("mint_authority", self.mint_authority),
+            ("mint_authority_per_extra", self.mint_authority_per_extra),
+            ("minted_supply_per_pct", self.minted_supply_per_pct),
+            ("minted_supply_cap", self.minted_supply_cap),
+            ("freeze_authority", self.freeze_authority),
+            ("liquidity_removed_per_pct", self.liquidity_removed_per_pct),
+            ("liquidity_removed_cap", self.liquidity_removed_cap),
+            ("insiders", self.insiders),
+            ("insiders_per_extra", self.insiders_per_extra),
+            ("creator_transfers_per_pct", self.creator_transfers_per_pct),
+            ("creator_transfers_cap", self.creator_transfers_cap),
+            ("cex_deposits", self.cex_deposits),
+        ];
+        let problems: Vec<String> = points.iter()
+            .filter_map(|(name, value)| {
+                if !value.is_finite() || *value < 0.0 {
+                    Some(format!("{} must be a non-negative number (got {})", name, value))
+                } else if name.ends_with("_cap") && *value > 100.0 {
+                    Some(format!("{} must be at most 100 (got {})", name, value))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid risk weights: {}", problems.join("; ")))
+        }
+    }
+
+    /// Parse and validate a weights file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let weights: Self = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Invalid risk weights in {}: {}", path.display(), e))?;
+        weights.validate()?;
+        Ok(weights)
+    }
+
+    /// Weights from `RISK_WEIGHTS_PATH`; the defaults when there is no file, or (with an error
+    /// printed) when it is invalid
+    pub fn from_env() -> Self {
+        let path = weights_path();
+        if !path.exists() {
+            return Self::default();
+        }
+        Self::load(&path).unwrap_or_else(|e| {
+            eprintln!("Using the default risk weights: {}", e);
+            Self::default()
+        })
+    }
+}
+
+/// RISK_WEIGHTS_PATH (default `risk_weights.json`)
+pub fn weights_path() -> PathBuf {
+    PathBuf::from(std::env::var("RISK_WEIGHTS_PATH").unwrap_or_else(|_| "risk_weights.json".to_string()))
+}
+
+/// Weights the process scores with
+pub fn weights() -> RiskWeights {
+    RISK_WEIGHTS.clone()
+}
+
+/// Per-feature breakdown of a token's score, for `/score_explain`
+pub fn format_explanation(mint: &str, factors: &RiskFactors, weights: &RiskWeights) -> String {
+    let contributions = factors.explain(weights);
+    let lines: Vec<String> = contributions.iter()
+        .filter(|c| c.points > 0.0)
+        .map(|c| format!("• {} ({}): +{:.1}", c.feature, c.value, c.points))
+        .collect();
+    let total: f64 = contributions.iter().map(|c| c.points).sum();
+    let mut message = format!("🧮 Risk score {:.0}/100 for {}\n\n", factors.score_with(weights), mint);
+    if lines.is_empty() {
+        message.push_str("No risk signal observed for this token.");
+    } else {
+        message.push_str(&lines.join("\n"));
+        if total > 100.0 {
+            message.push_str(&format!("\n\nFeatures add up to {:.1}, capped at 100.", total));
+        }
+    }
+    message
+}
+
+lazy_static! {
+    pub static ref RISK_FACTORS: DashMap<String, RiskFactors> = DashMap::new();
+    static ref RISK_WEIGHTS: RiskWeights = RiskWeights::from_env();
+}
+
+/// Apply an update to a token's risk factors
+pub fn update_factors<F: FnOnce(&mut RiskFactors)>(mint: &str, update: F) {
+    let mut entry = RISK_FACTORS.entry(mint.to_string()).or_default();
+    update(&mut entry);
+    entry.last_updated = Some(Instant::now());
+}
+
+/// Current risk factors for a token
+pub fn get_factors(mint: &str) -> RiskFactors {
+    RISK_FACTORS.get(mint).map(|f| f.clone()).unwrap_or_default()
+}
+
+/// Current risk score for a token (0 when nothing is known)
+pub fn risk_score(mint: &str) -> f64 {
+    RISK_FACTORS.get(mint).map(|f| f.score()).unwrap_or(0.0)
+}
@@ -0,0 +1,167 @@
+//! Splitting a large sell across several pools of the same token.
+//!
+//! Selling `d` tokens into a constant product pool `(x SOL, y tokens)` returns
+//! `x * d / (y + d)` before fees, so the marginal price falls as the order grows. Spreading the
+//! order over pools until their marginal prices are equal returns more SOL than dumping it into
+//! the deepest one. With fee factor `g = 1 - fee`, the marginal output of pool `i` after `d_i`
+//! tokens is `g * x_i * y_i / (y_i + d_i)^2`; setting all of them equal gives
+//! `d_i = sqrt(g_i * x_i * y_i) * s - y_i` with `s = (D + sum y_i) / sum sqrt(g_i * x_i * y_i)`
+//! over the pools that receive a positive share.
+
+use solana_sdk::pubkey::Pubkey;
+
+/// A pool the token can be sold into
+#[derive(Clone, Debug, PartialEq)]
+pub struct SellVenue {
+    pub pool_id: Pubkey,
+    /// SOL side, lamports
+    pub sol_reserve: u64,
+    /// Token side, raw units
+    pub token_reserve: u64,
+    /// Swap fee, basis points
+    pub fee_bps: u64,
+}
+
+impl SellVenue {
+    /// Lamports returned for `token_amount`, fee included
+    pub fn expected_sol_out(&self, token_amount: u64) -> u64 {
+        if token_amount == 0 || self.sol_reserve == 0 || self.token_reserve == 0 {
+            return 0;
+        }
+        let gross = self.sol_reserve as u128 * token_amount as u128 / (self.token_reserve as u128 + token_amount as u128);
+        (gross * (10_000 - self.fee_bps.min(10_000)) as u128 / 10_000) as u64
+    }
+
+    /// Price impact (%) of selling `token_amount` here
+    pub fn impact_pct(&self, token_amount: u64) -> f64 {
+        if self.token_reserve == 0 {
+            return 100.0;
+        }
+        token_amount as f64 / (self.token_reserve as f64 + token_amount as f64) * 100.0
+    }
+
+    fn weight(&self) -> f64 {
+        let g = 1.0 - self.fee_bps.min(10_000) as f64 / 10_000.0;
+        (g * self.sol_reserve as f64 * self.token_reserve as f64).sqrt()
+    }
+}
+
+/// Part of a split sell
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitLeg {
+    pub venue: SellVenue,
+    pub token_amount: u64,
+    pub expected_sol: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct SplitConfig {
+    pub enabled: bool,
+    /// Only split when the sell would move the best single pool by at least this much (%)
+    pub min_impact_pct: f64,
+    /// Legs smaller than this share of the order (%) are folded into the others
+    pub min_leg_pct: f64,
+    /// Upper bound on legs, keeping the transaction within size limits
+    pub max_legs: usize,
+}
+
+impl Default for SplitConfig {
+    fn default() -> Self {
+        Self { enabled: false, min_impact_pct: 3.0, min_leg_pct: 5.0, max_legs: 4 }
+    }
+}
+
+impl SplitConfig {
+    /// SELL_SPLIT_ENABLED (default false), SELL_SPLIT_MIN_IMPACT_PCT (3.0),
+    /// SELL_SPLIT_MIN_LEG_PCT (5.0) and SELL_SPLIT_MAX_LEGS (4)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            enabled: var("SELL_SPLIT_ENABLED").and_then(|v| v.parse().ok()).unwrap_or(defaults.enabled),
+            min_impact_pct: var("SELL_SPLIT_MIN_IMPACT_PCT").and_then(|v| v.parse().ok()).unwrap_or(defaults.min_impact_pct),
+            min_leg_pct: var("SELL_SPLIT_MIN_LEG_PCT").and_then(|v| v.parse().ok()).unwrap_or(defaults.min_leg_pct),
+            max_legs: var("SELL_SPLIT_MAX_LEGS").and_then(|v| v.parse().ok()).unwrap_or(defaults.max_legs).max(1),
+        }
+    }
+}
+
+/// Token amount per pool that equalizes marginal prices, largest leg first. Pools that would
+/// not receive a meaningful share (below `min_leg_pct`, or beyond `max_legs`) are left out.
+pub fn plan_split(token_amount: u64, venues: &[SellVenue], config: &SplitConfig) -> Vec<SplitLeg> {
+    let mut active: Vec<&SellVenue> = venues.iter()
+        .filter(|v| v.sol_reserve > 0 && v.token_reserve > 0)
+        .collect();
+    if token_amount == 0 || active.is_empty() {
+        return Vec::new();
+    }
+
+    loop {
+        let shares = allocate(token_amount as f64, &active);
+        let total = token_amount as f64;
+        let smallest = shares.iter()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, share)| (i, *share));
+        match smallest {
+            Some((i, share)) if active.len() > 1
+                && (share / total * 100.0 < config.min_leg_pct || active.len() > config.max_legs) =>
+            {
+                active.remove(i);
+            }
+            _ => return to_legs(token_amount, &active, &shares),
+        }
+    }
+}
+
+/// Water-filling allocation over the given pools; shares sum to `total`
+fn allocate(total: f64, venues: &[&SellVenue]) -> Vec<f64> {
+    let mut included: Vec<bool> = vec![true; venues.len()];
+    loop {
+        let weight_sum: f64 = venues.iter().zip(&included).filter(|(_, inc)| **inc).map(|(v, _)| v.weight()).sum();
+        let reserve_sum: f64 = venues.iter().zip(&included).filter(|(_, inc)| **inc).map(|(v, _)| v.token_reserve as f64).sum();
+        let scale = (total + reserve_sum) / weight_sum;
+        let shares: Vec<f64> = venues.iter()
+            .zip(&included)
+            .map(|(v, inc)| if *inc { v.weight() * scale - v.token_reserve as f64 } else { 0.0 })
+            .collect();
+        // A pool already cheaper than the others at zero size gets nothing
+        match shares.iter().zip(&included).position(|(share, inc)| *inc && *share < 0.0) {
+            Some(i) => included[i] = false,
+            None => return shares,
+        }
+    }
+}
+
+fn to_legs(token_amount: u64, venues: &[&SellVenue], shares: &[f64]) -> Vec<SplitLeg> {
+    let mut amounts: Vec<u64> = shares.iter().map(|s| s.max(0.0).floor() as u64).collect();
+    // Rounding remainder goes to the largest leg
+    let assigned: u64 = amounts.iter().sum();
+    if let Some(largest) = (0..amounts.len()).max_by_key(|i| amounts[*i]) {
+        amounts[largest] += token_amount.saturating_sub(assigned);
+    }
+
+    let mut legs: Vec<SplitLeg> = venues.iter()
+        .zip(amounts)
+        .filter(|(_, amount)| *amount > 0)
+        .map(|(venue, amount)| SplitLeg {
+            venue: (*venue).clone(),
+            token_amount: amount,
+            expected_sol: venue.expected_sol_out(amount),
+        })
+        .collect();
+    legs.sort_by(|a, b| b.token_amount.cmp(&a.token_amount));
+    legs
+}
+
+/// Whether splitting is worth it: more than one pool, and the whole order in the deepest pool
+/// would move its price at least `min_impact_pct`
+pub fn should_split(token_amount: u64, venues: &[SellVenue], config: &SplitConfig) -> bool {
+    if !config.enabled || venues.len() < 2 {
+        return false;
+    }
+    venues.iter()
+        .max_by_key(|v| v.sol_reserve)
+        .map(|deepest| deepest.impact_pct(token_amount) >= config.min_impact_pct)
+        .unwrap_or(false)
+}
@@ -0,0 +1,341 @@
+/*!
+# Community Scam Lists
+
+An optional job pulling known-scam token and creator lists from community-maintained endpoints
+and merging them into the local blacklist. Every entry keeps the names of the lists that flag
+it, so alerts can say which list flagged a token; buys of listed tokens, or of tokens launched
+by listed creators, are refused.
+
+Each sync replaces a list's entries with what it serves now, so an address a list drops is no
+longer flagged by it. A list that cannot be fetched or parsed keeps its previous entries. The
+blacklist is saved to `BLACKLIST_PATH` and reloaded on startup, so flags survive restarts and
+endpoint outages.
+
+Lists are served as JSON or plain text:
+- a JSON array of addresses, or of `{"address", "kind"}` objects (`mint` is accepted for
+  `address`, `kind` is `token` or `creator`);
+- one address per line, optionally prefixed with `token:` or `creator:`; `#` starts a comment.
+
+Addresses without a kind are tokens.
+
+## Environment Variables
+
+- `SCAM_LIST_URLS`: Lists as `<name>=<url>` pairs separated by commas; empty disables the sync
+- `SCAM_LIST_SYNC_MINS`: Minutes between syncs (default: `60`)
+- `BLACKLIST_PATH`: File the merged blacklist is kept in across restarts (default:
+  `blacklist.json`, empty keeps it in memory only)
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use colored::Colorize;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use tokio_util::sync::CancellationToken;
+
+use crate::common::logger::Logger;
+use crate::processor::mute_list;
+
+lazy_static! {
+    static ref SHARED_BLACKLIST: Arc<Blacklist> = Arc::new(Blacklist::from_env());
+}
+
+/// Longest a list endpoint gets to answer
+const FETCH_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    Token,
+    Creator,
+}
+
+impl EntryKind {
+    fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "token" | "mint" => Some(EntryKind::Token),
+            "creator" | "deployer" | "dev" => Some(EntryKind::Creator),
+            _ => None,
+        }
+    }
+}
+
+/// A blacklisted address and the lists that flag it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlacklistEntry {
+    pub address: String,
+    pub kind: EntryKind,
+    pub sources: Vec<String>,
+}
+
+impl BlacklistEntry {
+    /// e.g. `creator flagged by RugWatch, ScamDB`
+    pub fn describe(&self) -> String {
+        let kind = match self.kind {
+            EntryKind::Token => "token",
+            EntryKind::Creator => "creator",
+        };
+        format!("{} flagged by {}", kind, self.sources.join(", "))
+    }
+}
+
+/// A community list endpoint
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScamListSource {
+    pub name: String,
+    pub url: String,
+}
+
+/// `<name>=<url>` pairs separated by commas; malformed entries are skipped with a warning
+pub fn parse_list_urls(value: &str) -> Vec<ScamListSource> {
+    let mut sources = Vec::new();
+    for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        match entry.split_once('=').map(|(name, url)| (name.trim(), url.trim())) {
+            Some((name, url)) if !name.is_empty() && url.starts_with("http") => {
+                sources.push(ScamListSource { name: name.to_string(), url: url.to_string() });
+            }
+            _ => eprintln!("Ignoring SCAM_LIST_URLS entry '{}'", entry),
+        }
+    }
+    sources
+}
+
+/// Addresses served by a list. Err when the body is not a list, e.g. an error page
+pub fn parse_list(body: &str) -> Result<Vec<(String, EntryKind)>, String> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum JsonEntry {
+        Address(String),
+        Entry {
+            #[serde(alias = "mint")]
+            address: String,
+            #[serde(default, alias = "type")]
+            kind: Option<String>,
+        },
+    }
+
+    let trimmed = body.trim();
+    let raw: Vec<(String, Option<EntryKind>)> = if trimmed.starts_with('[') {
+        let entries: Vec<JsonEntry> = serde_json::from_str(trimmed).map_err(|e| format!("invalid JSON: {}", e))?;
+        entries.into_iter()
+            .map(|entry| match entry {
+                JsonEntry::Address(address) => (address, None),
+                JsonEntry::Entry { address, kind } => (address, kind.as_deref().and_then(EntryKind::parse)),
+            })
+            .collect()
+    } else {
+        trimmed.lines()
+            .map(|line| line.split('#').next().unwrap_or("").trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| match line.split_once(':') {
+                Some((kind, address)) => (address.trim().to_string(), EntryKind::parse(kind)),
+                None => (line.to_string(), None),
+            })
+            .collect()
+    };
+
+    let lines = raw.len();
+    let entries: Vec<(String, EntryKind)> = raw.into_iter()
+        .filter(|(address, _)| Pubkey::from_str(address).is_ok())
+        .map(|(address, kind)| (address, kind.unwrap_or(EntryKind::Token)))
+        .collect();
+    if entries.is_empty() && lines > 0 {
+        return Err(format!("no addresses among {} entries", lines));
+    }
+    Ok(entries)
+}
+
+/// What a sync changed for one list
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MergeStats {
+    pub listed: usize,
+    pub added: usize,
+    pub removed: usize,
+}
+
+pub struct Blacklist {
+    path: Option<PathBuf>,
+    entries: Mutex<HashMap<String, BlacklistEntry>>,
+}
+
+impl Blacklist {
+    /// Blacklist persisted at `path` (if any), reloading the entries of previous runs
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let entries = path.as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<Vec<BlacklistEntry>>(&content).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| (entry.address.clone(), entry))
+            .collect();
+        Self { path, entries: Mutex::new(entries) }
+    }
+
+    pub fn from_env() -> Self {
+        let path = std::env::var("BLACKLIST_PATH").unwrap_or_else(|_| "blacklist.json".to_string());
+        Self::new(Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty()))
+    }
+
+    /// Process-wide blacklist
+    pub fn shared() -> Arc<Blacklist> {
+        SHARED_BLACKLIST.clone()
+    }
+
+    /// Make `listed` the entries `source` flags, dropping the ones it no longer lists
+    pub fn merge(&self, source: &str, listed: &[(String, EntryKind)]) -> MergeStats {
+        let mut entries = self.entries.lock().unwrap();
+        let mut stats = MergeStats { listed: listed.len(), ..MergeStats::default() };
+        let addresses: HashSet<&str> = listed.iter().map(|(address, _)| address.as_str()).collect();
+
+        let before = entries.len();
+        entries.retain(|address, entry| {
+            if !addresses.contains(address.as_str()) {
+                entry.sources.retain(|s| s != source);
+            }
+            !entry.sources.is_empty()
+        });
+        stats.removed = before - entries.len();
+
+        for (address, kind) in listed {
+            let entry = entries.entry(address.clone()).or_insert_with(|| {
+                stats.added += 1;
+                BlacklistEntry { address: address.clone(), kind: *kind, sources: Vec::new() }
+            });
+            if !entry.sources.iter().any(|s| s == source) {
+                entry.sources.push(source.to_string());
+            }
+        }
+        self.save(&entries);
+        stats
+    }
+
+    pub fn get(&self, address: &str) -> Option<BlacklistEntry> {
+        self.entries.lock().unwrap().get(address).cloned()
+    }
+
+    /// The entry flagging `mint`, directly or through its creator
+    pub fn flag_for(&self, mint: &str) -> Option<BlacklistEntry> {
+        self.get(mint).or_else(|| {
+            mute_list::creator_of(mint)
+                .and_then(|creator| self.get(&creator))
+                .filter(|entry| entry.kind == EntryKind::Creator)
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn save(&self, entries: &HashMap<String, BlacklistEntry>) {
+        if let Some(path) = &self.path {
+            let mut list: Vec<&BlacklistEntry> = entries.values().collect();
+            list.sort_by(|a, b| a.address.cmp(&b.address));
+            let written = serde_json::to_string(&list)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| Ok(std::fs::write(path, json)?));
+            if let Err(e) = written {
+                eprintln!("Failed to save the blacklist to {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// The entry flagging `mint` on the process-wide blacklist
+pub fn flag_for(mint: &str) -> Option<BlacklistEntry> {
+    SHARED_BLACKLIST.flag_for(mint)
+}
+
+/// Fetches a list's body
+#[async_trait]
+pub trait ListFetcher: Send + Sync {
+    async fn fetch(&self, url: &str) -> Result<String>;
+}
+
+pub struct HttpListFetcher {
+    client: reqwest::Client,
+}
+
+impl Default for HttpListFetcher {
+    fn default() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl ListFetcher for HttpListFetcher {
+    async fn fetch(&self, url: &str) -> Result<String> {
+        let response = self.client.get(url).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("HTTP {}", response.status()));
+        }
+        Ok(response.text().await?)
+    }
+}
+
+/// Fetch every list and merge it into `blacklist`; a failed list keeps its entries
+pub async fn sync_lists(
+    blacklist: &Blacklist,
+    fetcher: &dyn ListFetcher,
+    sources: &[ScamListSource],
+) -> Vec<(String, Result<MergeStats, String>)> {
+    let mut results = Vec::new();
+    for source in sources {
+        let result = match fetcher.fetch(&source.url).await {
+            Ok(body) => parse_list(&body).map(|listed| blacklist.merge(&source.name, &listed)),
+            Err(e) => Err(e.to_string()),
+        };
+        results.push((source.name.clone(), result));
+    }
+    results
+}
+
+/// Sync the configured lists into the process-wide blacklist every `SCAM_LIST_SYNC_MINS`;
+/// None without `SCAM_LIST_URLS`
+pub fn start_scam_list_sync(cancel_token: CancellationToken) -> Option<tokio::task::JoinHandle<()>> {
+    let sources = parse_list_urls(&std::env::var("SCAM_LIST_URLS").unwrap_or_default());
+    if sources.is_empty() {
+        return None;
+    }
+    let every = std::env::var("SCAM_LIST_SYNC_MINS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(60)
+        .max(1);
+    let logger = Logger::new("[SCAM-LISTS] => ".bright_red().to_string());
+    Some(tokio::spawn(async move {
+        let fetcher = HttpListFetcher::default();
+        let blacklist = Blacklist::shared();
+        let mut interval = tokio::time::interval(Duration::from_secs(every * 60));
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            for (name, result) in sync_lists(&blacklist, &fetcher, &sources).await {
+                match result {
+                    Ok(stats) => logger.log(format!(
+                        "{}: {} listed, {} added, {} dropped",
+                        name, stats.listed, stats.added, stats.removed
+                    )),
+                    Err(e) => logger.log(format!("{}: sync failed, keeping its entries: {}", name, e).yellow().to_string()),
+                }
+            }
+            logger.log(format!("Blacklist holds {} addresses", blacklist.len()));
+        }
+        logger.log("Scam list sync shut down.".yellow().to_string());
+    }))
+}
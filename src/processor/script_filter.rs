@@ -0,0 +1,171 @@
+/*!
+# Script Filters
+
+Small Rhai expressions, evaluated per event, that decide whether an alert may fire or a buy may
+go ahead, e.g. `sol_amount > 5 && dex == "PumpFun" && risk_score < 40`. A script must evaluate
+to a boolean. Variables:
+
+- `sol_amount`, `token_amount`, `price`, `liquidity`, `progress` (bonding curve %): numbers,
+  `0.0` when unknown
+- `dex`, `side` (`buy`, `sell`, ...), `mint`, `signer`, `name`, `symbol`: strings, empty when
+  unknown
+- `slot`: integer
+- `risk_score`: the token's rug-risk score, 0 to 100 (see `risk_score`)
+
+Trades the alert filter rejects still update the monitor's metrics but raise no alerts or
+signals; a script error lets the alert through. The trade filter fails closed: a buy is refused
+when the script rejects it, errors or does not compile. In trade filters the triggering wallet
+is not known and `signer` holds the default address. Scripts run with an operation limit and
+without printing.
+
+## Environment Variables
+
+- `ALERT_FILTER_SCRIPT`: Expression deciding whether a monitor event may alert, or `@path` to a
+  `.rhai` file (default: none, every event may alert)
+- `TRADE_FILTER_SCRIPT`: Expression deciding whether a buy may go ahead, or `@path` (default:
+  none)
+*/
+
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use lazy_static::lazy_static;
+use rhai::{Engine, Scope, AST};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::logger::Logger;
+use crate::processor::risk_score;
+use crate::processor::transaction_parser::{ParsedData, SwapType, TradeInfoFromToken};
+
+/// Operations a script may take per event
+const MAX_OPERATIONS: u64 = 10_000;
+
+lazy_static! {
+    static ref TRADE_FILTER: ScriptHook = ScriptHook::from_env("TRADE_FILTER_SCRIPT");
+    static ref LOGGER: Logger = Logger::new("[SCRIPT-FILTER] => ".cyan().to_string());
+}
+
+/// A compiled filter script
+pub struct ScriptFilter {
+    source: String,
+    engine: Engine,
+    ast: AST,
+}
+
+impl ScriptFilter {
+    pub fn compile(source: &str) -> Result<Self> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.set_max_expr_depths(64, 32);
+        engine.on_print(|_| {});
+        engine.on_debug(|_, _, _| {});
+        let ast = engine.compile(source).map_err(|e| anyhow!("{}", e))?;
+        Ok(Self { source: source.to_string(), engine, ast })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the script lets the event through
+    pub fn allows(&self, parsed_data: &ParsedData) -> Result<bool> {
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        let side = match parsed_data.swap_type {
+            SwapType::Buy => "buy",
+            SwapType::Sell => "sell",
+            SwapType::AddLiquidity => "add_liquidity",
+            SwapType::RemoveLiquidity => "remove_liquidity",
+            SwapType::PoolCreate => "pool_create",
+        };
+        let mint = parsed_data.token_mint.to_string();
+        let mut scope = Scope::new();
+        scope.push_constant("sol_amount", parsed_data.sol_amount.unwrap_or_default());
+        scope.push_constant("token_amount", parsed_data.token_amount.unwrap_or_default());
+        scope.push_constant("price", parsed_data.token_price.unwrap_or_default());
+        scope.push_constant("liquidity", parsed_data.liquidity.unwrap_or_default());
+        scope.push_constant("progress", parsed_data.bonding_curve_progress.unwrap_or_default());
+        scope.push_constant("dex", parsed_data.dex_name.clone());
+        scope.push_constant("side", side.to_string());
+        scope.push_constant("signer", parsed_data.signer.to_string());
+        scope.push_constant("name", text(&parsed_data.token_name));
+        scope.push_constant("symbol", text(&parsed_data.token_symbol));
+        scope.push_constant("slot", parsed_data.slot as i64);
+        scope.push_constant("risk_score", risk_score::risk_score(&mint));
+        scope.push_constant("mint", mint);
+        self.engine
+            .eval_ast_with_scope::<bool>(&mut scope, &self.ast)
+            .map_err(|e| anyhow!("{}", e))
+    }
+}
+
+/// A configured hook: off, a compiled script, or a script that failed to compile
+pub enum ScriptHook {
+    Off,
+    Script(ScriptFilter),
+    Broken(String),
+}
+
+impl ScriptHook {
+    /// The script in `var`: an expression, or `@path` to a file; empty or unset is off
+    pub fn from_env(var: &str) -> Self {
+        let value = match std::env::var(var).ok().filter(|v| !v.trim().is_empty()) {
+            Some(value) => value,
+            None => return ScriptHook::Off,
+        };
+        let source = match value.strip_prefix('@') {
+            Some(path) => std::fs::read_to_string(path.trim()).with_context(|| format!("Failed to read {}", path.trim())),
+            None => Ok(value),
+        };
+        let hook = Self::from_source(source.and_then(|source| ScriptFilter::compile(&source)));
+        match &hook {
+            ScriptHook::Script(filter) => LOGGER.log(format!("{}: {}", var, filter.source().trim())),
+            ScriptHook::Broken(error) => LOGGER.log(format!("{} does not compile: {}", var, error).red().bold().to_string()),
+            ScriptHook::Off => {}
+        }
+        hook
+    }
+
+    pub fn from_source(filter: Result<ScriptFilter>) -> Self {
+        match filter {
+            Ok(filter) => ScriptHook::Script(filter),
+            Err(e) => ScriptHook::Broken(format!("{:#}", e)),
+        }
+    }
+
+    /// Alert filters fail open: only a script that evaluates to false suppresses the alert
+    pub fn alert_allowed(&self, parsed_data: &ParsedData) -> bool {
+        match self {
+            ScriptHook::Off | ScriptHook::Broken(_) => true,
+            ScriptHook::Script(filter) => match filter.allows(parsed_data) {
+                Ok(allowed) => allowed,
+                Err(e) => {
+                    LOGGER.log(format!("Alert filter failed on {}: {}", parsed_data.token_mint, e).yellow().to_string());
+                    true
+                }
+            },
+        }
+    }
+
+    /// Trade filters fail closed; returns why the trade is refused
+    pub fn trade_refusal(&self, parsed_data: &ParsedData) -> Option<String> {
+        match self {
+            ScriptHook::Off => None,
+            ScriptHook::Broken(error) => Some(format!("Trade filter script does not compile: {}", error)),
+            ScriptHook::Script(filter) => match filter.allows(parsed_data) {
+                Ok(true) => None,
+                Ok(false) => Some(format!("Rejected by trade filter `{}`", filter.source().trim())),
+                Err(e) => Some(format!("Trade filter failed: {}", e)),
+            },
+        }
+    }
+}
+
+/// Why TRADE_FILTER_SCRIPT refuses a buy of this trade, if it does
+pub fn trade_refusal(trade_info: &TradeInfoFromToken) -> Option<String> {
+    if matches!(*TRADE_FILTER, ScriptHook::Off) {
+        return None;
+    }
+    match ParsedData::from_trade_info(trade_info, &Pubkey::default().to_string()) {
+        Some(parsed_data) => TRADE_FILTER.trade_refusal(&parsed_data),
+        None => Some(format!("Trade filter cannot read trade of {}", trade_info.mint)),
+    }
+}
@@ -0,0 +1,264 @@
+/*!
+# Sell Route Verification
+
+Before a live buy, check that the token could be sold right away through the venue it would be
+bought on, and refuse the buy when it could not:
+
+- Mint: the mint exists and, for Token-2022 mints, has no transfer hook program, is not
+  non-transferable and does not create token accounts frozen.
+- Pump.fun: the bonding curve exists and is still trading. A completed curve sells on its
+  PumpSwap pool, which is checked instead.
+- PumpSwap and Raydium Launchpad: the pool is initialized and both of its vaults hold a balance
+  and are not frozen.
+
+All reads are plain account fetches, one `getMultipleAccounts` call per step. A check that cannot
+run (RPC error) is inconclusive: the buy goes ahead with a warning unless `SELL_ROUTE_STRICT`
+is set.
+
+## Environment Variables
+
+- `SELL_ROUTE_CHECK`: Verify the sell route before each live buy (default: `true`)
+- `SELL_ROUTE_STRICT`: Refuse buys whose sell route could not be verified (default: `false`)
+*/
+
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use spl_token_2022::extension::default_account_state::DefaultAccountState;
+use spl_token_2022::extension::non_transferable::NonTransferable;
+use spl_token_2022::extension::transfer_hook::TransferHook;
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use spl_token_2022::state::AccountState;
+
+use crate::common::cluster::DexProgram;
+use crate::dex::pump_fun::get_pda;
+use crate::dex::pump_swap::get_canonical_pool_pda;
+use crate::dex::raydium_launchpad::{get_pool_vault_pda, SOL_MINT};
+use crate::library::account_batcher::AccountFetcher;
+use crate::processor::token_audit::parse_bonding_curve;
+use crate::processor::transaction_parser::DexType;
+
+/// PumpSwap pool layout: pool_base_token_account @139, pool_quote_token_account @171
+const PUMP_SWAP_BASE_VAULT_OFFSET: usize = 139;
+const PUMP_SWAP_QUOTE_VAULT_OFFSET: usize = 171;
+
+#[derive(Clone, Debug)]
+pub struct SellRouteConfig {
+    pub enabled: bool,
+    /// Refuse buys whose route check was inconclusive
+    pub strict: bool,
+}
+
+impl Default for SellRouteConfig {
+    fn default() -> Self {
+        Self { enabled: true, strict: false }
+    }
+}
+
+impl SellRouteConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok().and_then(|v| bool::from_str(&v.to_lowercase()).ok());
+        Self {
+            enabled: var("SELL_ROUTE_CHECK").unwrap_or(defaults.enabled),
+            strict: var("SELL_ROUTE_STRICT").unwrap_or(defaults.strict),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RouteCheck {
+    /// The token can be sold through the named venue
+    Sellable(String),
+    /// The token cannot currently be sold
+    Blocked(String),
+    /// The route could not be checked
+    Inconclusive(String),
+}
+
+pub struct SellRouteGuard {
+    config: SellRouteConfig,
+    fetcher: Arc<dyn AccountFetcher>,
+}
+
+impl SellRouteGuard {
+    pub fn new(config: SellRouteConfig, fetcher: Arc<dyn AccountFetcher>) -> Self {
+        Self { config, fetcher }
+    }
+
+    pub fn from_env(rpc_client: Arc<RpcClient>) -> Self {
+        Self::new(SellRouteConfig::from_env(), rpc_client)
+    }
+
+    /// Err carries the reason the buy should not go ahead
+    pub async fn vet(&self, dex: &DexType, mint: &str, pool_id: &str) -> Result<(), String> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+        match self.check(dex, mint, pool_id).await {
+            RouteCheck::Sellable(_) => Ok(()),
+            RouteCheck::Blocked(reason) => Err(format!("no sell route: {}", reason)),
+            RouteCheck::Inconclusive(reason) if self.config.strict => {
+                Err(format!("sell route unverified: {}", reason))
+            }
+            RouteCheck::Inconclusive(reason) => {
+                eprintln!("Sell route of {} unverified ({}), buying anyway", mint, reason);
+                Ok(())
+            }
+        }
+    }
+
+    /// Whether `mint`, bought on `dex` (in `pool_id` for pool based venues), can be sold now
+    pub async fn check(&self, dex: &DexType, mint: &str, pool_id: &str) -> RouteCheck {
+        let mint = match Pubkey::from_str(mint) {
+            Ok(mint) => mint,
+            Err(_) => return RouteCheck::Inconclusive(format!("invalid mint {}", mint)),
+        };
+        let pool = Pubkey::from_str(pool_id).ok();
+        match dex {
+            DexType::PumpFun => self.check_pump_fun(&mint).await,
+            DexType::PumpSwap => {
+                self.check_pump_swap(&mint, &pool.unwrap_or_else(|| get_canonical_pool_pda(&mint))).await
+            }
+            DexType::RaydiumLaunchpad => match pool {
+                Some(pool) => self.check_launchpad(&mint, &pool).await,
+                None => RouteCheck::Inconclusive(format!("invalid pool {}", pool_id)),
+            },
+            DexType::Unknown => RouteCheck::Inconclusive("unknown DEX".to_string()),
+        }
+    }
+
+    async fn check_pump_fun(&self, mint: &Pubkey) -> RouteCheck {
+        let curve = match get_pda(mint, &DexProgram::PumpFun.id()) {
+            Ok(curve) => curve,
+            Err(e) => return RouteCheck::Inconclusive(e.to_string()),
+        };
+        let (mint_account, curve_account) = match self.fetch_pair(mint, &curve).await {
+            Ok(accounts) => accounts,
+            Err(check) => return check,
+        };
+        if let Some(reason) = mint_blocker(mint_account.as_ref()) {
+            return RouteCheck::Blocked(reason);
+        }
+        let state = match curve_account.as_ref().filter(|a| a.owner == DexProgram::PumpFun.id()) {
+            Some(account) => parse_bonding_curve(curve, &account.data),
+            None => return RouteCheck::Blocked(format!("bonding curve {} does not exist", curve)),
+        };
+        match state {
+            Some(state) if state.complete => {
+                // Completed curves no longer trade; sells go to the migrated pool
+                match self.check_pool(&DexProgram::PumpSwap, &get_canonical_pool_pda(mint), None).await {
+                    RouteCheck::Blocked(reason) => RouteCheck::Blocked(format!("bonding curve complete and {}", reason)),
+                    check => check,
+                }
+            }
+            Some(_) => RouteCheck::Sellable(format!("pump.fun curve {}", curve)),
+            None => RouteCheck::Blocked(format!("bonding curve {} is not initialized", curve)),
+        }
+    }
+
+    async fn check_pump_swap(&self, mint: &Pubkey, pool: &Pubkey) -> RouteCheck {
+        match self.check_mint(mint).await {
+            Some(check) => check,
+            None => self.check_pool(&DexProgram::PumpSwap, pool, None).await,
+        }
+    }
+
+    async fn check_launchpad(&self, mint: &Pubkey, pool: &Pubkey) -> RouteCheck {
+        let vaults = (get_pool_vault_pda(pool, mint), get_pool_vault_pda(pool, &SOL_MINT));
+        match self.check_mint(mint).await {
+            Some(check) => check,
+            None => self.check_pool(&DexProgram::RaydiumLaunchpad, pool, Some(vaults)).await,
+        }
+    }
+
+    /// A blocked or inconclusive check when the mint stands in the way of a sell
+    async fn check_mint(&self, mint: &Pubkey) -> Option<RouteCheck> {
+        match self.fetcher.get_multiple_accounts(&[*mint]).await {
+            Ok(accounts) => mint_blocker(accounts.into_iter().next().flatten().as_ref()).map(RouteCheck::Blocked),
+            Err(e) => Some(RouteCheck::Inconclusive(e.to_string())),
+        }
+    }
+
+    /// The pool is owned by `program` and its vaults are funded. Pools that store their vaults
+    /// (PumpSwap) are read for them; others pass the derived `vaults`.
+    async fn check_pool(&self, program: &DexProgram, pool: &Pubkey, vaults: Option<(Pubkey, Pubkey)>) -> RouteCheck {
+        let pool_account = match self.fetcher.get_multiple_accounts(&[*pool]).await {
+            Ok(accounts) => accounts.into_iter().next().flatten(),
+            Err(e) => return RouteCheck::Inconclusive(e.to_string()),
+        };
+        let pool_account = match pool_account.filter(|a| a.owner == program.id()) {
+            Some(account) => account,
+            None => return RouteCheck::Blocked(format!("pool {} is not initialized", pool)),
+        };
+        let key = |offset: usize| pool_account.data.get(offset..offset + 32).and_then(|k| Pubkey::try_from(k).ok());
+        let (base_vault, quote_vault) = match vaults {
+            Some(vaults) => vaults,
+            None => match (key(PUMP_SWAP_BASE_VAULT_OFFSET), key(PUMP_SWAP_QUOTE_VAULT_OFFSET)) {
+                (Some(base), Some(quote)) => (base, quote),
+                _ => return RouteCheck::Blocked(format!("pool {} is not initialized", pool)),
+            },
+        };
+        let (base, quote) = match self.fetch_pair(&base_vault, &quote_vault).await {
+            Ok(accounts) => accounts,
+            Err(check) => return check,
+        };
+        for (side, account) in [("base", base), ("quote", quote)] {
+            if let Some(reason) = vault_blocker(account.as_ref()) {
+                return RouteCheck::Blocked(format!("{} vault of pool {} {}", side, pool, reason));
+            }
+        }
+        RouteCheck::Sellable(format!("pool {}", pool))
+    }
+
+    async fn fetch_pair(&self, a: &Pubkey, b: &Pubkey) -> Result<(Option<Account>, Option<Account>), RouteCheck> {
+        let mut accounts = self.fetcher.get_multiple_accounts(&[*a, *b]).await
+            .map_err(|e| RouteCheck::Inconclusive(e.to_string()))?
+            .into_iter();
+        Ok((accounts.next().flatten(), accounts.next().flatten()))
+    }
+}
+
+/// Why a mint cannot be sold, if it cannot
+pub fn mint_blocker(account: Option<&Account>) -> Option<String> {
+    let account = match account {
+        Some(account) => account,
+        None => return Some("mint does not exist".to_string()),
+    };
+    if account.owner == spl_token::id() {
+        return None;
+    }
+    if account.owner != spl_token_2022::id() {
+        return Some(format!("mint is owned by {}, not a token program", account.owner));
+    }
+    let state = match StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data) {
+        Ok(state) => state,
+        Err(_) => return Some("mint is not initialized".to_string()),
+    };
+    if let Some(program) = state.get_extension::<TransferHook>().ok().and_then(|hook| Option::<Pubkey>::from(hook.program_id)) {
+        return Some(format!("transfers run hook program {}", program));
+    }
+    if state.get_extension::<NonTransferable>().is_ok() {
+        return Some("token is non-transferable".to_string());
+    }
+    if state.get_extension::<DefaultAccountState>().is_ok_and(|default| default.state == AccountState::Frozen as u8) {
+        return Some("new token accounts start frozen".to_string());
+    }
+    None
+}
+
+/// Why a pool vault cannot pay out a sell, if it cannot
+fn vault_blocker(account: Option<&Account>) -> Option<String> {
+    let account = match account {
+        Some(account) => account,
+        None => return Some("does not exist".to_string()),
+    };
+    // Classic token accounts share Token-2022's base layout
+    match StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data) {
+        Ok(state) if state.base.state == AccountState::Frozen => Some("is frozen".to_string()),
+        Ok(state) if state.base.amount == 0 => Some("is empty".to_string()),
+        Ok(_) => None,
+        Err(_) => Some("is not a token account".to_string()),
+    }
+}
@@ -4,7 +4,7 @@ use solana_sdk::signature::Signer;
 use std::collections::{HashSet, VecDeque};
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use std::time::Instant;
 use anyhow::{anyhow, Result};
 use anchor_client::solana_sdk::{hash::Hash, instruction::Instruction, pubkey::Pubkey, signature::{Keypair, Signature}};
 use colored::Colorize;
@@ -15,13 +15,18 @@ use dashmap::DashMap;
 use solana_program_pack::Pack;
 
 use crate::common::{
+    clock::{system_clock, Clock, EventClock},
     config::{AppState, SwapConfig},
     logger::Logger,
     cache::WALLET_TOKEN_ACCOUNTS,
 };
 use crate::processor::transaction_parser::{TradeInfoFromToken, DexType};
 use crate::common::timeseries as ts;
+use crate::common::price_history::PriceHistory;
 use crate::processor::swap::{SwapDirection, SwapProtocol, SwapInType};
+use crate::library::priority_lane::Urgency;
+use crate::processor::trade_ledger;
+use crate::dex::bonding_curve::CurveSnapshot;
 use crate::dex::pump_fun::Pump;
 use crate::dex::pump_swap::PumpSwap;
 
@@ -740,6 +745,8 @@ pub struct SellingEngine {
     config: SellingConfig,
     logger: Logger,
     token_manager: TokenManager,
+    /// Runs on the block time of the trades seen, so hold times are not stretched by lag
+    clock: Arc<EventClock>,
 }
 
 impl SellingEngine {
@@ -754,8 +761,15 @@ impl SellingEngine {
             config,
             logger: Logger::new("[SELLING-STRATEGY] => ".yellow().to_string()),
             token_manager: TokenManager::new(),
+            clock: Arc::new(EventClock::new(system_clock())),
         }
     }
+
+    /// Use a different time source for hold times and trade timestamps, e.g. in backtests
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Arc::new(EventClock::new(clock));
+        self
+    }
     
     /// Get a reference to the selling configuration
     pub fn get_config(&self) -> &SellingConfig {
@@ -856,10 +870,7 @@ impl SellingEngine {
                     market_cap: 0.0,
                     time_held: 0,
                     last_update: Instant::now(),
-                    buy_timestamp: std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs(),
+                    buy_timestamp: self.clock.unix_timestamp(),
                     amount_held: token_amount,
                     cost_basis: current_price * token_amount,
                     price_history: VecDeque::new(),
@@ -916,6 +927,7 @@ impl SellingEngine {
     /// Update metrics for a token based on parsed transaction data
     pub async fn update_metrics(&self, token_mint: &str, trade_info: &TradeInfoFromToken) -> Result<()> {
         let logger = Logger::new("[SELLING-STRATEGY] => ".magenta().to_string());
+        self.clock.observe(trade_info.event_time());
         
         // Extract data
         let sol_change = trade_info.sol_change;
@@ -933,12 +945,8 @@ impl SellingEngine {
         let ata = get_associated_token_address(&wallet_pubkey, &token_pubkey);
 
         // Get current token balance
-        let actual_token_balance = match self.app_state.rpc_nonblocking_client.get_token_account(&ata).await {
-            Ok(Some(account)) => {
-                let amount_value = account.token_amount.amount.parse::<f64>()
-                    .map_err(|e| anyhow!("Failed to parse token amount: {}", e))?;
-                amount_value / 10f64.powi(account.token_amount.decimals as i32)
-            },
+        let actual_token_balance = match self.app_state.rpc_api.token_balance(&ata).await {
+            Ok(Some(balance)) => balance,
             Ok(None) => 0.0,
             Err(_) => 0.0,
         };
@@ -946,13 +954,7 @@ impl SellingEngine {
         // Calculate price using the same logic as transaction_parser.rs
         let price = match trade_info.dex_type {
             DexType::PumpFun => {
-                // PumpFun: virtual_sol_reserves / virtual_token_reserves
-                if trade_info.virtual_token_reserves > 0 {
-                    (trade_info.virtual_sol_reserves as f64 * 1_000_000_000.0) / 
-                    (trade_info.virtual_token_reserves as f64) / 1_000_000_000.0
-                } else {
-                    0.0
-                }
+                CurveSnapshot::from_virtual(trade_info.virtual_sol_reserves, trade_info.virtual_token_reserves).price()
             },
             DexType::PumpSwap => {
                 // PumpSwap: use the price from trade_info (already calculated correctly)
@@ -1088,6 +1090,7 @@ impl SellingEngine {
         // Time-series cache: 20-slot rolling price and buy/sell volume
         let sol_volume = trade_info.sol_change.abs();
         ts::update_for_mint(token_mint, trade_info.slot, price, is_buy, sol_volume);
+        PriceHistory::shared().record(token_mint, self.clock.now(), price, is_buy, sol_volume);
         
         // Log current metrics
         let pnl = if entry.entry_price > 0.0 {
@@ -1115,10 +1118,7 @@ impl SellingEngine {
     
     /// Record a buy transaction for a token with enhanced metrics tracking
     pub async fn record_buy(&self, token_mint: &str, amount: f64, cost: f64, trade_info: &TradeInfoFromToken) -> Result<()> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        let timestamp = self.clock.observe(trade_info.event_time()).timestamp().max(0) as u64;
 
         // Get current price and liquidity
         let current_price = cost / amount;
@@ -1184,7 +1184,11 @@ impl SellingEngine {
         };
         
         // Calculate time held
-        let time_held = metrics.last_update.elapsed().as_secs();
+        let time_held = if metrics.buy_timestamp > 0 {
+            self.clock.unix_timestamp().saturating_sub(metrics.buy_timestamp)
+        } else {
+            metrics.last_update.elapsed().as_secs()
+        };
         
         // Calculate percentage gain from entry (PNL)
         let pnl = if metrics.entry_price > 0.0 {
@@ -1416,10 +1420,7 @@ impl SellingEngine {
         protocol: &str
     ) -> Result<()> {
         // Get current timestamp
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| anyhow!("Failed to get timestamp: {}", e))?
-            .as_secs();
+        let timestamp = self.clock.unix_timestamp();
         
         // Get entry price from metrics
         let entry_price = TOKEN_METRICS.get(mint)
@@ -1481,10 +1482,7 @@ impl SellingEngine {
         let protocol_to_use = metrics.protocol.clone();
         
         // Create timestamp
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|_| anyhow!("Failed to get current timestamp"))?
-            .as_secs();
+        let timestamp = self.clock.unix_timestamp();
         
         // Calculate token amount for selling
         let token_amount = metrics.amount_held;
@@ -1628,6 +1626,7 @@ impl SellingEngine {
             liquidity: pool_quote_token_reserves.unwrap_or(0) as f64 / 1_000_000_000.0,
             virtual_sol_reserves: pool_quote_token_reserves.unwrap_or(0),
             virtual_token_reserves: pool_base_token_reserves.unwrap_or(0),
+            quote_asset: crate::common::quote_asset::for_mint(&token_mint.to_string()),
         })
     }
 
@@ -1768,7 +1767,7 @@ impl SellingEngine {
             Duration::from_secs(60) // Standard timeout for regular sells
         };
         
-        let result = timeout(timeout_duration, self.execute_emergency_sell_internal(token_mint, is_whale_emergency, parsed_data, protocol)).await;
+        let result = timeout(timeout_duration, self.execute_emergency_sell_internal(token_mint, is_whale_emergency, parsed_data, protocol, 1.0)).await;
         
         match result {
             Ok(inner_result) => inner_result,
@@ -1778,9 +1777,33 @@ impl SellingEngine {
             }
         }
     }
+
+    /// Sell `fraction` (0-1] of the held balance through the emergency sell path, e.g. to mirror
+    /// a copy-trade target's partial exit. A fraction of 1 is a full emergency sell.
+    pub async fn unified_partial_sell(&self, token_mint: &str, fraction: f64, parsed_data: Option<&TradeInfoFromToken>, protocol: Option<SwapProtocol>) -> Result<String> {
+        use tokio::time::{timeout, Duration};
+
+        let fraction = fraction.clamp(0.0, 1.0);
+        if fraction <= 0.0 {
+            return Err(anyhow!("Nothing to sell: fraction is zero"));
+        }
+        match timeout(Duration::from_secs(60), self.execute_emergency_sell_internal(token_mint, false, parsed_data, protocol, fraction)).await {
+            Ok(inner_result) => inner_result,
+            Err(_timeout_err) => {
+                self.logger.log(format!("🚨 Partial sell timed out for token: {}", token_mint).red().bold().to_string());
+                Err(anyhow!("Partial sell operation timed out after 60s"))
+            }
+        }
+    }
     
-    /// Internal implementation of emergency sell without timeout wrapper
-    async fn execute_emergency_sell_internal(&self, token_mint: &str, is_whale_emergency: bool, parsed_data: Option<&TradeInfoFromToken>, protocol: Option<SwapProtocol>) -> Result<String> {
+    /// Internal implementation of emergency sell without timeout wrapper; sells `fraction` of the balance
+    async fn execute_emergency_sell_internal(&self, token_mint: &str, is_whale_emergency: bool, parsed_data: Option<&TradeInfoFromToken>, protocol: Option<SwapProtocol>, fraction: f64) -> Result<String> {
+        // Another instance holds the trading lock for this wallet
+        if let Some(reason) = crate::library::instance_lock::trading_paused_reason() {
+            self.logger.log(format!("⏸️ {}, skipping sell of {}", reason, token_mint).yellow().to_string());
+            return Err(anyhow!(reason));
+        }
+
         // Log the type of emergency sell
         if is_whale_emergency {
             self.logger.log(format!("🐋 WHALE EMERGENCY SELL triggered for token: {}", token_mint).red().bold().to_string());
@@ -1802,7 +1825,7 @@ impl SellingEngine {
             Ok(Some(account)) => {
                 let amount_value = account.token_amount.amount.parse::<f64>()
                     .map_err(|e| anyhow!("Failed to parse token amount: {}", e))?;
-                let decimal_amount = amount_value / 10f64.powi(account.token_amount.decimals as i32);
+                let decimal_amount = amount_value / 10f64.powi(account.token_amount.decimals as i32) * fraction;
                 self.logger.log(format!("Emergency selling {} tokens", decimal_amount).red().to_string());
                 decimal_amount
             },
@@ -1832,7 +1855,7 @@ impl SellingEngine {
         let mut emergency_config = (*self.swap_config).clone();
         emergency_config.swap_direction = SwapDirection::Sell;
         emergency_config.in_type = SwapInType::Pct; // Use percentage for emergency sells
-        emergency_config.amount_in = 1.0; // Sell 100% of tokens
+        emergency_config.amount_in = fraction; // 100% of tokens unless mirroring a partial exit
         // Use higher slippage for whale emergency sells for faster execution
         emergency_config.slippage = if is_whale_emergency { 1500 } else { 1000 }; // 15% vs 10% slippage
 
@@ -1860,6 +1883,7 @@ impl SellingEngine {
                 liquidity: data.liquidity,
                 virtual_sol_reserves: data.virtual_sol_reserves,
                 virtual_token_reserves: data.virtual_token_reserves,
+                quote_asset: data.quote_asset,
             }
         } else {
             // Create trade info from metrics (for execute_emergency_sell_via_engine replacement)
@@ -1908,8 +1932,9 @@ impl SellingEngine {
                         };
                         self.logger.log(format!("Generated emergency PumpFun sell instruction at price: {}", price));
                         // Execute with zeroslot for copy selling
-                        match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                            self.app_state.zeroslot_rpc_client.clone(),
+                        match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                            Urgency::Critical,
+                            self.app_state.rpc_api.clone(),
                             recent_blockhash,
                             &keypair,
                             instructions,
@@ -1958,8 +1983,9 @@ impl SellingEngine {
                         };
                         self.logger.log(format!("Generated emergency PumpSwap sell instruction at price: {}", price));
                         // Execute with zeroslot for copy selling
-                        match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                            self.app_state.zeroslot_rpc_client.clone(),
+                        match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                            Urgency::Critical,
+                            self.app_state.rpc_api.clone(),
                             recent_blockhash,
                             &keypair,
                             instructions,
@@ -2008,8 +2034,9 @@ impl SellingEngine {
                         };
                         self.logger.log(format!("Generated emergency Raydium sell instruction at price: {}", price));
                         // Execute with zeroslot for copy selling
-                        match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                            self.app_state.zeroslot_rpc_client.clone(),
+                        match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                            Urgency::Critical,
+                            self.app_state.rpc_api.clone(),
                             recent_blockhash,
                             &keypair,
                             instructions,
@@ -2056,8 +2083,9 @@ impl SellingEngine {
                             }
                         };
                         self.logger.log(format!("Generated emergency PumpFun sell instruction at price: {}", price));
-                        match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                            self.app_state.zeroslot_rpc_client.clone(),
+                        match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                            Urgency::Critical,
+                            self.app_state.rpc_api.clone(),
                             recent_blockhash,
                             &keypair,
                             instructions,
@@ -2097,7 +2125,7 @@ impl SellingEngine {
                 self.logger.log(format!("❌ DEX emergency sell failed: {}. Attempting Jupiter API fallback...", dex_error).yellow().to_string());
                 
                 // Try Jupiter API as fallback
-                match self.try_jupiter_fallback_sell(token_mint, token_amount).await {
+                match self.try_jupiter_fallback_sell(token_mint, token_amount, fraction).await {
                     Ok(jupiter_signature) => {
                         self.logger.log(format!("✅ Jupiter API fallback sell successful: {}", jupiter_signature).green().to_string());
                         Ok(jupiter_signature)
@@ -2118,7 +2146,7 @@ impl SellingEngine {
             // Record the emergency trade execution
             if let Err(e) = self.record_trade_execution(
                 token_mint,
-                "EMERGENCY_STOP_LOSS",
+                if fraction < 1.0 { "PARTIAL_EXIT" } else { "EMERGENCY_STOP_LOSS" },
                 token_amount,
                 protocol_str
             ).await {
@@ -2130,7 +2158,7 @@ impl SellingEngine {
     }
 
     /// Try Jupiter API as fallback when DEX selling fails
-    async fn try_jupiter_fallback_sell(&self, token_mint: &str, token_amount: f64) -> Result<String> {
+    async fn try_jupiter_fallback_sell(&self, token_mint: &str, token_amount: f64, fraction: f64) -> Result<String> {
         self.logger.log(format!("🌌 Attempting Jupiter API fallback sell for {} tokens of {}", token_amount, token_mint).cyan().to_string());
         
         // Create Jupiter client
@@ -2148,8 +2176,9 @@ impl SellingEngine {
         // Get current token balance in raw units for Jupiter
         let raw_token_amount = match self.app_state.rpc_nonblocking_client.get_token_account(&ata).await {
             Ok(Some(account)) => {
-                account.token_amount.amount.parse::<u64>()
-                    .map_err(|e| anyhow!("Failed to parse token amount: {}", e))?
+                let raw = account.token_amount.amount.parse::<u64>()
+                    .map_err(|e| anyhow!("Failed to parse token amount: {}", e))?;
+                if fraction < 1.0 { (raw as f64 * fraction) as u64 } else { raw }
             },
             Ok(None) => {
                 return Err(anyhow!("No token account found for mint: {}", token_mint));
@@ -2177,6 +2206,9 @@ impl SellingEngine {
         ).await {
             Ok(signature) => {
                 self.logger.log(format!("🌌 Jupiter API sell completed: {}", signature).green().to_string());
+                if let Ok(parsed) = Signature::from_str(&signature) {
+                    trade_ledger::record_confirmed(self.app_state.clone(), parsed);
+                }
                 Ok(signature)
             },
             Err(e) => {
@@ -2188,10 +2220,8 @@ impl SellingEngine {
 
     pub async fn check_time_conditions(&self, trade_info: &TradeInfoFromToken) -> Option<String> {
         // Get current timestamp
-        let current_timestamp = match SystemTime::now().duration_since(UNIX_EPOCH) {
-            Ok(n) => n.as_secs(),
-            Err(_) => return None,
-        };
+        self.clock.observe(trade_info.event_time());
+        let current_timestamp = self.clock.unix_timestamp();
         
         // Get metrics using DashMap's get() method
         let metrics = TOKEN_METRICS.get(&trade_info.mint)?;
@@ -0,0 +1,432 @@
+/*!
+# Signal Bus
+
+Detectors publish typed [`Signal`]s and consumers subscribe to the bus independently: Telegram
+alerting, the signal recorder, and anything else that wants to act on what the monitor detects
+(paper trading, a live sniper) without the detection code knowing about it.
+
+Every consumer sees every signal, in publish order. `publish` returns once all consumers have
+handled the signal; a consumer that fails is logged and neither stops the others nor fails the
+detector. Consumers that do slow work should hand it to a task of their own.
+
+## Environment Variables
+
+- `SIGNAL_LOG_PATH`: JSON lines file every published signal is appended to (default: empty, no
+  recorder)
+*/
+
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use anyhow::Result;
+use async_trait::async_trait;
+use colored::Colorize;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::common::logger::Logger;
+use crate::library::rolling::VolumeTotals;
+use crate::processor::arbitrage::Spread;
+use crate::processor::insider_detection::InsiderFinding;
+use crate::processor::liquidity_depth::LiquidityDrop;
+use crate::processor::opportunity_signal::OpportunitySignal;
+use crate::processor::signal_outcomes::SignalDirection;
+use crate::processor::telegram_alerts::TelegramAlertSystem;
+use crate::processor::transaction_parser::SwapType;
+use crate::processor::wallet_groups::GroupActivity;
+use crate::processor::wallet_positions::EntryComparison;
+
+/// Something a detector noticed
+#[derive(Clone, Debug)]
+pub enum Signal {
+    /// A buy or sell by any wallet
+    WalletActivity {
+        wallet: Pubkey,
+        action: &'static str,
+        mint: Pubkey,
+        token_name: Option<String>,
+        amount_sol: f64,
+        entry: Option<EntryComparison>,
+        bonding_curve_progress: Option<f64>,
+        signature: Option<String>,
+    },
+    /// The first buy of a token not tracked yet
+    NewToken {
+        mint: Pubkey,
+        token_name: Option<String>,
+        liquidity: f64,
+        dex: String,
+    },
+    InsiderBuy {
+        finding: InsiderFinding,
+        token_name: Option<String>,
+    },
+    /// Price moved past the threshold since the token was first seen
+    PriceMovement {
+        mint: Pubkey,
+        token_name: Option<String>,
+        initial_price: f64,
+        current_price: f64,
+        volume_24h: VolumeTotals,
+        bonding_curve_progress: Option<f64>,
+    },
+    GraduationImminent {
+        mint: Pubkey,
+        token_name: Option<String>,
+        progress: f64,
+    },
+    /// A trading pattern: buy pressure, whale trade, unusual size, breakout, recovery
+    Opportunity(OpportunitySignal),
+    VolumeSpike {
+        mint: Pubkey,
+        token_name: Option<String>,
+        recent: VolumeTotals,
+        average: f64,
+    },
+    PriceImpact {
+        wallet: Pubkey,
+        action: &'static str,
+        mint: Pubkey,
+        token_name: Option<String>,
+        amount_sol: f64,
+        pool_sol: f64,
+        impact_pct: f64,
+        signature: Option<String>,
+    },
+    ArbitrageSpread {
+        mint: Pubkey,
+        token_name: Option<String>,
+        spread: Spread,
+    },
+    /// Pool creation, a large liquidity removal or a whale-sized deposit
+    LiquidityEvent {
+        mint: Pubkey,
+        token_name: Option<String>,
+        kind: SwapType,
+        dex: String,
+        amount_sol: f64,
+        pool_sol: f64,
+    },
+    LiquidityDrop {
+        mint: Pubkey,
+        token_name: Option<String>,
+        drop: LiquidityDrop,
+        window_minutes: i64,
+        depth_curve: String,
+    },
+    /// A launch graduated or died; `timeline` is its replay
+    LaunchReplay {
+        mint: Pubkey,
+        token_name: Option<String>,
+        timeline: String,
+    },
+    /// Several wallets of one tracked group bought the same token
+    GroupBuy(GroupActivity),
+}
+
+impl Signal {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Signal::WalletActivity { .. } => "wallet_activity",
+            Signal::NewToken { .. } => "new_token",
+            Signal::InsiderBuy { .. } => "insider_buy",
+            Signal::PriceMovement { .. } => "price_movement",
+            Signal::GraduationImminent { .. } => "graduation_imminent",
+            Signal::Opportunity(_) => "opportunity",
+            Signal::VolumeSpike { .. } => "volume_spike",
+            Signal::PriceImpact { .. } => "price_impact",
+            Signal::ArbitrageSpread { .. } => "arbitrage_spread",
+            Signal::LiquidityEvent { .. } => "liquidity_event",
+            Signal::LiquidityDrop { .. } => "liquidity_drop",
+            Signal::LaunchReplay { .. } => "launch_replay",
+            Signal::GroupBuy(_) => "group_buy",
+        }
+    }
+
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            Signal::InsiderBuy { finding, .. } => finding.mint,
+            Signal::Opportunity(signal) => signal.mint,
+            Signal::GroupBuy(activity) => activity.mint,
+            Signal::WalletActivity { mint, .. }
+            | Signal::NewToken { mint, .. }
+            | Signal::PriceMovement { mint, .. }
+            | Signal::GraduationImminent { mint, .. }
+            | Signal::VolumeSpike { mint, .. }
+            | Signal::PriceImpact { mint, .. }
+            | Signal::ArbitrageSpread { mint, .. }
+            | Signal::LiquidityEvent { mint, .. }
+            | Signal::LiquidityDrop { mint, .. }
+            | Signal::LaunchReplay { mint, .. } => *mint,
+        }
+    }
+
+    /// The price direction the signal suggests, for signals that suggest one
+    pub fn direction(&self) -> Option<SignalDirection> {
+        match self {
+            Signal::Opportunity(signal) => Some(signal.direction),
+            Signal::GraduationImminent { .. } => Some(SignalDirection::Bullish),
+            Signal::PriceMovement { initial_price, current_price, .. } => Some(if current_price > initial_price {
+                SignalDirection::Bullish
+            } else {
+                SignalDirection::Bearish
+            }),
+            _ => None,
+        }
+    }
+
+    /// One line for logs and the recorder
+    pub fn describe(&self) -> String {
+        match self {
+            Signal::WalletActivity { wallet, action, amount_sol, .. } => format!("{} {} {:.3} SOL", wallet, action, amount_sol),
+            Signal::NewToken { liquidity, dex, .. } => format!("new on {} with {:.2} SOL liquidity", dex, liquidity),
+            Signal::InsiderBuy { finding, .. } => format!(
+                "insider {} bought {:.3} SOL {}s after launch ({} insider wallets, {:.3} SOL)",
+                finding.wallet, finding.sol, finding.seconds_after_launch, finding.insider_wallets, finding.insider_sol
+            ),
+            Signal::PriceMovement { initial_price, current_price, .. } => format!(
+                "price {:+.1}% since first seen",
+                (current_price - initial_price) / initial_price * 100.0
+            ),
+            Signal::GraduationImminent { progress, .. } => format!("bonding curve {:.1}% complete", progress),
+            Signal::Opportunity(signal) => format!(
+                "{}: {} ({:.0}% confidence, {})",
+                signal.kind.label(), signal.summary, signal.confidence * 100.0, signal.action.label()
+            ),
+            Signal::VolumeSpike { recent, average, .. } => format!("{:.2} SOL volume against a {:.2} SOL average", recent.total(), average),
+            Signal::PriceImpact { wallet, action, amount_sol, impact_pct, .. } => {
+                format!("{} {} {:.3} SOL moved the price {:.1}%", wallet, action, amount_sol, impact_pct)
+            }
+            Signal::ArbitrageSpread { spread, .. } => format!(
+                "{:.2}% spread between {} and {}",
+                spread.spread_pct, spread.low.venue, spread.high.venue
+            ),
+            Signal::LiquidityEvent { kind, dex, amount_sol, pool_sol, .. } => {
+                format!("{:?} of {:.2} SOL on {} ({:.2} SOL in pool)", kind, amount_sol, dex, pool_sol)
+            }
+            Signal::LiquidityDrop { drop, window_minutes, .. } => format!(
+                "pool SOL {:.2} -> {:.2} ({:.1}%) within {}m",
+                drop.from_sol, drop.to_sol, drop.drop_pct, window_minutes
+            ),
+            Signal::LaunchReplay { timeline, .. } => timeline.lines().next().unwrap_or_default().to_string(),
+            Signal::GroupBuy(activity) => format!("{} ({:.2} SOL)", activity.headline(), activity.total_sol),
+        }
+    }
+}
+
+/// Something that acts on signals
+#[async_trait]
+pub trait SignalConsumer: Send + Sync {
+    fn name(&self) -> &str;
+    async fn consume(&self, signal: &Signal) -> Result<()>;
+}
+
+/// Fans signals out to every subscribed consumer; clones share the same consumers
+#[derive(Clone)]
+pub struct SignalBus {
+    consumers: Arc<RwLock<Vec<Arc<dyn SignalConsumer>>>>,
+    published: Arc<AtomicU64>,
+    logger: Logger,
+}
+
+impl Default for SignalBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignalBus {
+    pub fn new() -> Self {
+        Self {
+            consumers: Arc::new(RwLock::new(Vec::new())),
+            published: Arc::new(AtomicU64::new(0)),
+            logger: Logger::new("[SIGNAL-BUS] => ".blue().to_string()),
+        }
+    }
+
+    pub fn subscribe(&self, consumer: Arc<dyn SignalConsumer>) {
+        self.consumers.write().unwrap().push(consumer);
+    }
+
+    /// Names of the subscribed consumers, in delivery order
+    pub fn consumers(&self) -> Vec<String> {
+        self.consumers.read().unwrap().iter().map(|c| c.name().to_string()).collect()
+    }
+
+    /// Signals published since start
+    pub fn published(&self) -> u64 {
+        self.published.load(Ordering::Relaxed)
+    }
+
+    /// Deliver a signal to every consumer
+    pub async fn publish(&self, signal: Signal) {
+        self.published.fetch_add(1, Ordering::Relaxed);
+        let consumers = self.consumers.read().unwrap().clone();
+        for consumer in consumers {
+            if let Err(e) = consumer.consume(&signal).await {
+                self.logger.log(format!(
+                    "{} failed on {} for {}: {}", consumer.name(), signal.kind(), signal.mint(), e
+                ).red().to_string());
+            }
+        }
+    }
+
+    pub async fn publish_all(&self, signals: Vec<Signal>) {
+        for signal in signals {
+            self.publish(signal).await;
+        }
+    }
+}
+
+/// Sends each signal as its Telegram alert
+pub struct TelegramSignals {
+    telegram: Arc<TelegramAlertSystem>,
+}
+
+impl TelegramSignals {
+    pub fn new(telegram: Arc<TelegramAlertSystem>) -> Self {
+        Self { telegram }
+    }
+}
+
+#[async_trait]
+impl SignalConsumer for TelegramSignals {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    async fn consume(&self, signal: &Signal) -> Result<()> {
+        let telegram = &self.telegram;
+        match signal.clone() {
+            Signal::WalletActivity { wallet, action, mint, token_name, amount_sol, entry, bonding_curve_progress, signature } => {
+                telegram.alert_wallet_activity(
+                    &wallet, action, &mint, token_name, amount_sol, None, entry, bonding_curve_progress, signature.as_deref(),
+                ).await
+            }
+            Signal::NewToken { mint, token_name, liquidity, dex } => {
+                telegram.alert_new_token(&mint, token_name, liquidity, &dex).await
+            }
+            Signal::InsiderBuy { finding, token_name } => telegram.alert_insider_buy(&finding, token_name).await,
+            Signal::PriceMovement { mint, token_name, initial_price, current_price, volume_24h, bonding_curve_progress } => {
+                telegram.alert_price_movement(
+                    &mint, token_name, initial_price, current_price, Some(volume_24h), bonding_curve_progress,
+                ).await
+            }
+            Signal::GraduationImminent { mint, token_name, progress } => {
+                telegram.alert_graduation_imminent(&mint, token_name, progress).await
+            }
+            Signal::Opportunity(signal) => telegram.alert_sniper_opportunity(&signal).await,
+            Signal::VolumeSpike { mint, token_name, recent, average } => {
+                telegram.alert_volume_spike(&mint, token_name, recent, average).await
+            }
+            Signal::PriceImpact { wallet, action, mint, token_name, amount_sol, pool_sol, impact_pct, signature } => {
+                telegram.alert_price_impact(
+                    &wallet, action, &mint, token_name, amount_sol, pool_sol, impact_pct, signature.as_deref(),
+                ).await
+            }
+            Signal::ArbitrageSpread { mint, token_name, spread } => {
+                telegram.alert_arbitrage_spread(
+                    &mint, token_name, &spread.low.venue, spread.low.price, &spread.high.venue, spread.high.price, spread.spread_pct,
+                ).await
+            }
+            Signal::LiquidityEvent { mint, token_name, kind, dex, amount_sol, pool_sol } => {
+                telegram.alert_liquidity_event(&mint, token_name, &kind, &dex, amount_sol, pool_sol).await
+            }
+            Signal::LiquidityDrop { mint, token_name, drop, window_minutes, depth_curve } => {
+                telegram.alert_liquidity_drop(
+                    &mint, token_name, drop.from_sol, drop.to_sol, drop.drop_pct, window_minutes, &depth_curve,
+                ).await
+            }
+            Signal::LaunchReplay { mint, token_name, timeline } => {
+                telegram.send_launch_replay(&mint, token_name, &timeline).await
+            }
+            Signal::GroupBuy(activity) => telegram.alert_group_buy(&activity).await,
+        }
+    }
+}
+
+/// One line of the signal log
+#[derive(Clone, Debug, Serialize)]
+struct SignalLine<'a> {
+    /// Unix seconds
+    at: i64,
+    kind: &'a str,
+    mint: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    direction: Option<&'a str>,
+    detail: String,
+    /// Type, confidence, evidence and recommended action of opportunity signals
+    #[serde(skip_serializing_if = "Option::is_none")]
+    opportunity: Option<&'a OpportunitySignal>,
+}
+
+/// Appends every signal to a JSON lines file
+pub struct SignalRecorder {
+    path: PathBuf,
+    clock: Arc<dyn Clock>,
+}
+
+impl SignalRecorder {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, clock: system_clock() }
+    }
+
+    /// SIGNAL_LOG_PATH, or none when unset or empty
+    pub fn from_env() -> Option<Self> {
+        std::env::var("SIGNAL_LOG_PATH").ok()
+            .filter(|path| !path.trim().is_empty())
+            .map(|path| Self::new(PathBuf::from(path)))
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+#[async_trait]
+impl SignalConsumer for SignalRecorder {
+    fn name(&self) -> &str {
+        "recorder"
+    }
+
+    async fn consume(&self, signal: &Signal) -> Result<()> {
+        let token_name = match signal {
+            Signal::WalletActivity { token_name, .. }
+            | Signal::NewToken { token_name, .. }
+            | Signal::InsiderBuy { token_name, .. }
+            | Signal::PriceMovement { token_name, .. }
+            | Signal::GraduationImminent { token_name, .. }
+            | Signal::VolumeSpike { token_name, .. }
+            | Signal::PriceImpact { token_name, .. }
+            | Signal::ArbitrageSpread { token_name, .. }
+            | Signal::LiquidityEvent { token_name, .. }
+            | Signal::LiquidityDrop { token_name, .. }
+            | Signal::LaunchReplay { token_name, .. } => token_name.as_deref(),
+            Signal::Opportunity(signal) => signal.token_name.as_deref(),
+            Signal::GroupBuy(activity) => activity.token_name.as_deref(),
+        };
+        let line = SignalLine {
+            at: self.clock.now().timestamp(),
+            kind: signal.kind(),
+            mint: signal.mint().to_string(),
+            token_name,
+            direction: signal.direction().map(|d| match d {
+                SignalDirection::Bullish => "bullish",
+                SignalDirection::Bearish => "bearish",
+            }),
+            detail: signal.describe(),
+            opportunity: match signal {
+                Signal::Opportunity(opportunity) => Some(opportunity),
+                _ => None,
+            },
+        };
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&line)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+/*!
+# Signal Confirmation
+
+A live buy goes ahead only when at least K independent signals agree on the token within a time
+window, so a single detector firing on noise does not trade. Independent means distinct sources:
+five buy-pressure alerts are one signal, a target wallet buy plus a trending rank plus an
+acceptable risk score are three.
+
+Sources counted:
+- the trigger of the buy itself (`target_buy` for a copied wallet buy, `graduation` for a
+  graduation snipe);
+- every bullish or neutral signal the monitor publishes, by kind (`new_token`, `insider_buy`,
+  `volume_spike`, `group_buy`, ...); opportunities count per pattern (`high_buy_pressure`,
+  `momentum_breakout`, `group_consensus`, ...). Bearish signals, liquidity drops and launch
+  replays never count;
+- `trending`, when the token ranks in the last trending refresh;
+- `acceptable_risk`, when the token's risk factors are known and its score is at most
+  `SIGNAL_CONFIRMATION_MAX_RISK`.
+
+Sources per binary:
+- `educational_monitor` never buys, but with `PIPELINE=monitor` publishes every monitor source;
+- the trading bot counts its triggers, and `acceptable_risk` once the token program monitor or
+  insider detection has scored the token. With `SIGNAL_CONFIRMATION_MIN` set it also runs the
+  educational monitor (alerts off) for the monitor signals and `trending`, fed by the `PIPELINE`
+  when that has a `monitor` strategy and by a pipeline of its own when no `PIPELINE` is set. A
+  `PIPELINE` without `monitor` leaves only the triggers and `acceptable_risk`, so keep
+  `SIGNAL_CONFIRMATION_MIN` at 2 or below there.
+
+## Environment Variables
+
+- `SIGNAL_CONFIRMATION_MIN`: Independent signals a live buy needs (default: `0`, no confirmation)
+- `SIGNAL_CONFIRMATION_WINDOW_SECS`: How recent a signal must be to count (default: `300`)
+- `SIGNAL_CONFIRMATION_SOURCES`: Comma-separated sources that count (default: empty, all of them)
+- `SIGNAL_CONFIRMATION_MAX_RISK`: Highest risk score counted as `acceptable_risk` (default: `50`)
+*/
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use anyhow::Result;
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::processor::risk_score;
+use crate::processor::signal_bus::{Signal, SignalConsumer};
+use crate::processor::signal_outcomes::SignalDirection;
+
+lazy_static! {
+    static ref SHARED_CONFIRMATION: Arc<SignalConfirmation> = Arc::new(SignalConfirmation::new(ConfirmationConfig::from_env()));
+}
+
+/// Tokens with recent signals kept before stale ones are swept
+const MAX_TRACKED_TOKENS: usize = 10_000;
+
+/// Trigger of a copied wallet buy
+pub const TARGET_BUY: &str = "target_buy";
+/// Trigger of a graduation snipe
+pub const GRADUATION: &str = "graduation";
+pub const TRENDING: &str = "trending";
+pub const ACCEPTABLE_RISK: &str = "acceptable_risk";
+
+#[derive(Clone, Debug)]
+pub struct ConfirmationConfig {
+    /// Independent signals a buy needs; 0 disables confirmation
+    pub min_signals: usize,
+    pub window_secs: i64,
+    /// Sources that count; empty counts every source
+    pub sources: Vec<String>,
+    pub max_risk: f64,
+}
+
+impl Default for ConfirmationConfig {
+    fn default() -> Self {
+        Self { min_signals: 0, window_secs: 300, sources: Vec::new(), max_risk: 50.0 }
+    }
+}
+
+impl ConfirmationConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            min_signals: var("SIGNAL_CONFIRMATION_MIN").and_then(|v| v.parse().ok()).unwrap_or(defaults.min_signals),
+            window_secs: var("SIGNAL_CONFIRMATION_WINDOW_SECS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.window_secs)
+                .max(1),
+            sources: var("SIGNAL_CONFIRMATION_SOURCES")
+                .map(|v| v.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()).collect())
+                .unwrap_or(defaults.sources),
+            max_risk: var("SIGNAL_CONFIRMATION_MAX_RISK").and_then(|v| v.parse().ok()).unwrap_or(defaults.max_risk),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.min_signals > 0
+    }
+
+    fn counts(&self, source: &str) -> bool {
+        self.sources.is_empty() || self.sources.iter().any(|s| s == source)
+    }
+}
+
+/// The source a published signal counts as, None for signals that never confirm a buy
+pub fn source_of(signal: &Signal) -> Option<String> {
+    if signal.direction() == Some(SignalDirection::Bearish) {
+        return None;
+    }
+    match signal {
+        Signal::LiquidityDrop { .. } | Signal::LaunchReplay { .. } => None,
+        Signal::Opportunity(opportunity) => Some(opportunity.kind.label().to_lowercase().replace(' ', "_")),
+        _ => Some(signal.kind().to_string()),
+    }
+}
+
+pub struct SignalConfirmation {
+    config: ConfirmationConfig,
+    /// Unix seconds each source last fired, per mint
+    seen: Mutex<HashMap<String, HashMap<String, i64>>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl SignalConfirmation {
+    pub fn new(config: ConfirmationConfig) -> Self {
+        Self { config, seen: Mutex::new(HashMap::new()), clock: system_clock() }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Process-wide confirmation state
+    pub fn shared() -> Arc<SignalConfirmation> {
+        SHARED_CONFIRMATION.clone()
+    }
+
+    pub fn config(&self) -> &ConfirmationConfig {
+        &self.config
+    }
+
+    /// Note that `source` fired for `mint` now
+    pub fn record(&self, mint: &str, source: &str) {
+        if !self.config.enabled() || !self.config.counts(source) {
+            return;
+        }
+        let now = self.clock.now().timestamp();
+        let mut seen = self.seen.lock().unwrap();
+        seen.entry(mint.to_string()).or_default().insert(source.to_string(), now);
+        if seen.len() > MAX_TRACKED_TOKENS {
+            let window = self.config.window_secs;
+            seen.retain(|_, sources| sources.values().any(|at| now - at < window));
+        }
+    }
+
+    /// Sources that fired for `mint` within the window, sorted
+    pub fn recent(&self, mint: &str) -> Vec<String> {
+        let now = self.clock.now().timestamp();
+        let mut sources: Vec<String> = self.seen.lock().unwrap()
+            .get(mint)
+            .map(|sources| {
+                sources.iter()
+                    .filter(|(_, at)| now - **at < self.config.window_secs)
+                    .map(|(source, _)| source.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        sources.sort();
+        sources
+    }
+
+    /// The independent signals confirming a buy of `mint` triggered by `trigger`, or why there
+    /// are too few. Always confirmed when disabled
+    pub fn confirm(&self, mint: &str, trigger: &str) -> Result<Vec<String>, String> {
+        if !self.config.enabled() {
+            return Ok(Vec::new());
+        }
+        self.record(mint, trigger);
+        if risk_score::RISK_FACTORS.contains_key(mint) && risk_score::risk_score(mint) <= self.config.max_risk {
+            self.record(mint, ACCEPTABLE_RISK);
+        }
+        let sources = self.recent(mint);
+        if sources.len() < self.config.min_signals {
+            return Err(format!(
+                "{} of {} signals within {}s ({})",
+                sources.len(),
+                self.config.min_signals,
+                self.config.window_secs,
+                if sources.is_empty() { "none".to_string() } else { sources.join(", ") }
+            ));
+        }
+        Ok(sources)
+    }
+}
+
+#[async_trait]
+impl SignalConsumer for SignalConfirmation {
+    fn name(&self) -> &str {
+        "signal_confirmation"
+    }
+
+    async fn consume(&self, signal: &Signal) -> Result<()> {
+        if let Some(source) = source_of(signal) {
+            self.record(&signal.mint().to_string(), &source);
+        }
+        Ok(())
+    }
+}
+
+/// Confirm a live buy of `mint` against the process-wide state
+pub fn confirm_buy(mint: &str, trigger: &str) -> Result<Vec<String>, String> {
+    SHARED_CONFIRMATION.confirm(mint, trigger)
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::processor::opportunity_signal::ENTRY_CONFIDENCE;
+
+/// Expected price direction behind a signal
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignalDirection {
+    Bullish,
+    Bearish,
+}
+
+/// An opportunity alert and the token price observed at each horizon after it
+#[derive(Clone, Debug)]
+pub struct SignalOutcome {
+    pub signal_type: String,
+    pub mint: Pubkey,
+    pub direction: SignalDirection,
+    pub alerted_at: DateTime<Utc>,
+    pub price_at_alert: f64,
+    /// Confidence of structured opportunity signals (0.0 to 1.0)
+    pub confidence: Option<f64>,
+    /// (minutes after alert, price then); None until the horizon is reached or if no price was known
+    pub checkpoints: Vec<(i64, Option<f64>)>,
+}
+
+impl SignalOutcome {
+    pub fn new(signal_type: &str, mint: Pubkey, direction: SignalDirection, price_at_alert: f64, alerted_at: DateTime<Utc>) -> Self {
+        Self {
+            signal_type: signal_type.to_string(),
+            mint,
+            direction,
+            alerted_at,
+            price_at_alert,
+            confidence: None,
+            checkpoints: outcome_horizons().into_iter().map(|m| (m, None)).collect(),
+        }
+    }
+
+    pub fn with_confidence(mut self, confidence: Option<f64>) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
+    /// Hypothetical return (%) of acting on the signal, measured at a horizon
+    pub fn return_at(&self, minutes: i64) -> Option<f64> {
+        let price = self.checkpoints.iter().find(|(m, _)| *m == minutes)?.1?;
+        if self.price_at_alert <= 0.0 {
+            return None;
+        }
+        let change = (price - self.price_at_alert) / self.price_at_alert * 100.0;
+        Some(match self.direction {
+            SignalDirection::Bullish => change,
+            SignalDirection::Bearish => -change,
+        })
+    }
+}
+
+/// Minutes after an alert at which the outcome is measured (SIGNAL_OUTCOME_HORIZONS_MIN)
+pub fn outcome_horizons() -> Vec<i64> {
+    std::env::var("SIGNAL_OUTCOME_HORIZONS_MIN")
+        .ok()
+        .map(|v| v.split(',').filter_map(|m| m.trim().parse::<i64>().ok()).filter(|m| *m > 0).collect::<Vec<_>>())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| vec![5, 30, 120])
+}
+
+/// Minimum return (%) for a signal to count as profitable, covering fees and slippage
+fn profit_threshold_pct() -> f64 {
+    std::env::var("SIGNAL_PROFIT_THRESHOLD_PCT")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(2.0)
+}
+
+/// Accuracy of one signal type at one horizon
+#[derive(Clone, Debug, Default)]
+pub struct SignalAccuracy {
+    pub measured: u32,
+    pub profitable: u32,
+    pub total_return_pct: f64,
+}
+
+impl SignalAccuracy {
+    pub fn accuracy_pct(&self) -> f64 {
+        if self.measured == 0 {
+            return 0.0;
+        }
+        self.profitable as f64 / self.measured as f64 * 100.0
+    }
+
+    pub fn average_return_pct(&self) -> f64 {
+        if self.measured == 0 {
+            return 0.0;
+        }
+        self.total_return_pct / self.measured as f64
+    }
+}
+
+/// Accuracy group of opportunity signals at or above ENTRY_CONFIDENCE, whatever their type
+pub const CONFIDENT_SIGNALS: &str = "High confidence";
+
+/// Accuracy per signal type and horizon for signals alerted since `since`
+pub fn aggregate_accuracy(outcomes: &[SignalOutcome], since: DateTime<Utc>) -> BTreeMap<String, BTreeMap<i64, SignalAccuracy>> {
+    let threshold = profit_threshold_pct();
+    let mut stats: BTreeMap<String, BTreeMap<i64, SignalAccuracy>> = BTreeMap::new();
+    for outcome in outcomes.iter().filter(|o| o.alerted_at >= since) {
+        // Confident signals are also scored together, to compare against each type overall
+        let confident = outcome.confidence.is_some_and(|c| c >= ENTRY_CONFIDENCE);
+        let groups = std::iter::once(outcome.signal_type.as_str()).chain(confident.then_some(CONFIDENT_SIGNALS));
+        for group in groups {
+            for (minutes, _) in &outcome.checkpoints {
+                if let Some(ret) = outcome.return_at(*minutes) {
+                    let entry = stats
+                        .entry(group.to_string())
+                        .or_default()
+                        .entry(*minutes)
+                        .or_default();
+                    entry.measured += 1;
+                    entry.total_return_pct += ret;
+                    if ret >= threshold {
+                        entry.profitable += 1;
+                    }
+                }
+            }
+        }
+    }
+    stats
+}
+
+/// Report section with signal accuracy over the `days` before `now`
+pub fn format_signal_accuracy(outcomes: &[SignalOutcome], days: i64, now: DateTime<Utc>) -> String {
+    let since = now - Duration::days(days);
+    let stats = aggregate_accuracy(outcomes, since);
+    let alerted = outcomes.iter().filter(|o| o.alerted_at >= since).count();
+
+    let mut section = format!("🎯 Signal Accuracy (last {} days, {} signals)\n", days, alerted);
+    if stats.is_empty() {
+        section.push_str("  • No signal outcomes measured yet\n");
+        return section;
+    }
+    for (signal_type, horizons) in &stats {
+        section.push_str(&format!("  • {}:\n", signal_type));
+        if signal_type == CONFIDENT_SIGNALS {
+            section.push_str(&format!("      (opportunity signals with at least {:.0}% confidence)\n", ENTRY_CONFIDENCE * 100.0));
+        }
+        for (minutes, accuracy) in horizons {
+            section.push_str(&format!(
+                "      +{}m: {:.0}% profitable ({}/{}), avg {:+.2}%\n",
+                minutes,
+                accuracy.accuracy_pct(),
+                accuracy.profitable,
+                accuracy.measured,
+                accuracy.average_return_pct(),
+            ));
+        }
+    }
+    section
+}
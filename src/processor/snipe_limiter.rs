@@ -0,0 +1,126 @@
+//! Admission control for buys: one buy per mint at a time, and at most `MAX_CONCURRENT_SNIPES`
+//! buys in flight across all mints.
+//!
+//! A second signal for a mint that is already being bought is refused straight away instead of
+//! waiting behind the first (which would double-buy once the first finished). Signals for other
+//! mints wait for a free slot for at most `SNIPE_QUEUE_TIMEOUT_MS`, then expire; a burst of
+//! signals therefore never stacks into a backlog of buys executed at long-gone prices.
+
+use std::sync::Arc;
+use std::time::Duration;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+lazy_static! {
+    static ref SHARED_LIMITER: Arc<SnipeLimiter> = Arc::new(SnipeLimiter::new(LimiterConfig::from_env()));
+}
+
+#[derive(Clone, Debug)]
+pub struct LimiterConfig {
+    pub max_concurrent: usize,
+    pub queue_timeout: Duration,
+}
+
+impl Default for LimiterConfig {
+    fn default() -> Self {
+        Self { max_concurrent: 3, queue_timeout: Duration::from_millis(2_000) }
+    }
+}
+
+impl LimiterConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            max_concurrent: var("MAX_CONCURRENT_SNIPES")
+                .and_then(|v| v.parse().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(defaults.max_concurrent),
+            queue_timeout: var("SNIPE_QUEUE_TIMEOUT_MS")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.queue_timeout),
+        }
+    }
+}
+
+/// Why a buy was not admitted
+#[derive(Clone, Debug, PartialEq)]
+pub enum SnipeRejected {
+    /// Another buy of the mint is in flight
+    MintBusy,
+    /// No slot freed up within the queue timeout
+    Expired { waited_ms: u64 },
+}
+
+impl std::fmt::Display for SnipeRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnipeRejected::MintBusy => write!(f, "A buy of this mint is already in flight"),
+            SnipeRejected::Expired { waited_ms } => {
+                write!(f, "Signal expired after waiting {} ms for a free snipe slot", waited_ms)
+            }
+        }
+    }
+}
+
+/// A mint marked in flight; unmarked when dropped, including when the buy waiting for a slot is
+/// cancelled
+struct MintClaim {
+    mint: String,
+    in_flight: Arc<DashMap<String, ()>>,
+}
+
+impl Drop for MintClaim {
+    fn drop(&mut self) {
+        self.in_flight.remove(&self.mint);
+    }
+}
+
+/// Held for the duration of a buy; releases the mint and the slot when dropped
+pub struct SnipeSlot {
+    _claim: MintClaim,
+    _permit: OwnedSemaphorePermit,
+}
+
+pub struct SnipeLimiter {
+    config: LimiterConfig,
+    semaphore: Arc<Semaphore>,
+    in_flight: Arc<DashMap<String, ()>>,
+}
+
+impl SnipeLimiter {
+    pub fn new(config: LimiterConfig) -> Self {
+        let semaphore = Arc::new(Semaphore::new(config.max_concurrent));
+        Self { config, semaphore, in_flight: Arc::new(DashMap::new()) }
+    }
+
+    pub fn shared() -> Arc<SnipeLimiter> {
+        SHARED_LIMITER.clone()
+    }
+
+    /// Claim `mint`, then wait (at most the queue timeout) for a free slot
+    pub async fn acquire(&self, mint: &str) -> Result<SnipeSlot, SnipeRejected> {
+        if self.in_flight.insert(mint.to_string(), ()).is_some() {
+            return Err(SnipeRejected::MintBusy);
+        }
+        let claim = MintClaim { mint: mint.to_string(), in_flight: self.in_flight.clone() };
+        let permit = tokio::time::timeout(self.config.queue_timeout, self.semaphore.clone().acquire_owned()).await;
+        match permit {
+            Ok(Ok(permit)) => Ok(SnipeSlot { _claim: claim, _permit: permit }),
+            // The semaphore is never closed; treat it like an expiry all the same
+            _ => Err(SnipeRejected::Expired { waited_ms: self.config.queue_timeout.as_millis() as u64 }),
+        }
+    }
+
+    /// Mints claimed right now, waiting or executing
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Buys executing right now
+    pub fn executing(&self) -> usize {
+        self.config.max_concurrent - self.semaphore.available_permits()
+    }
+}
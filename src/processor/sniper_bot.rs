@@ -270,6 +270,7 @@ pub async fn start_token_queue_monitoring(
                                 liquidity: token_data.liquidity_usd,
                                 virtual_sol_reserves: 0, // Not available - requires DEX pool state query
                                 virtual_token_reserves: 0, // Not available - requires DEX pool state query
+                                quote_asset: crate::common::quote_asset::for_mint(&token_data.address),
                             };
 
                             let app_state = Arc::new(config.app_state.clone());
@@ -374,21 +375,21 @@ use crate::common::config::import_env_var;
 use crate::processor::selling_strategy::{SellingEngine, SellingConfig};
 use anyhow::Result;
 
-/// Extract the signer (fee payer) from a yellowstone grpc transaction
-/// Returns the first signer which is typically the transaction fee payer
-fn extract_signer_from_transaction(txn: &SubscribeUpdateTransaction) -> Option<String> {
-    if let Some(transaction_info) = &txn.transaction {
-        if let Some(transaction) = &transaction_info.transaction {
-            if let Some(message) = &transaction.message {
-                // Get account keys - the first one is typically the signer/fee payer
-                if !message.account_keys.is_empty() {
-                    // Convert the first account key (signer) to string
-                    return Some(bs58::encode(&message.account_keys[0]).into_string());
-                }
-            }
+// Parsing helpers shared with the monitor-only pipeline
+pub use crate::processor::transaction_parser::{collect_swaps, extract_signer_from_transaction};
+
+/// Export the liquidity events (pool creation, deposits, withdrawals) of a transaction
+fn publish_liquidity_events(txn: &SubscribeUpdateTransaction) {
+    let signer = match extract_signer_from_transaction(txn) {
+        Some(signer) => signer,
+        None => return,
+    };
+    for event in transaction_parser::extract_liquidity_events(txn) {
+        if let Some(normalized) = transaction_parser::ParsedData::from_liquidity_event(&event, &signer) {
+            crate::library::event_export::publish(&normalized);
+            market_regime::MarketRegime::shared().observe(&normalized);
         }
     }
-    None
 }
 use anchor_client::solana_sdk::{pubkey::Pubkey, signature::Signature};
 use solana_sdk::signature::Signer;
@@ -405,22 +406,33 @@ use yellowstone_grpc_proto::geyser::{
     subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest, SubscribeRequestPing,
     SubscribeRequestFilterTransactions,  SubscribeUpdate, SubscribeUpdateTransaction,
 };
-use solana_transaction_status::TransactionConfirmationStatus;
+use crate::library::rpc_api::SignatureStatus;
+use crate::error::{retry, BotError};
+use crate::library::priority_lane::Urgency;
+use crate::block_engine::relay::RelayStats;
+use crate::processor::snipe_limiter::SnipeLimiter;
+use crate::processor::order_ledger::OrderLedger;
+use crate::processor::dip_buy;
+use crate::processor::market_regime;
+use crate::processor::swap_deadline;
+use crate::library::transaction_source::{self, TransactionSource};
 use crate::processor::transaction_parser;
 use crate::common::{
     config::{Config, AppState, SwapConfig},
     logger::Logger,
     cache::WALLET_TOKEN_ACCOUNTS,
     constants::WHALE_SELLING_AMOUNT_FOR_SELLING_TRIGGER,
+    trade_costs,
 };
 use crate::processor::swap::{SwapDirection, SwapProtocol, SwapInType};
 use crate::processor::transaction_parser::{DexType, TradeInfoFromToken};
 use crate::processor::selling_strategy::{TokenTrackingInfo as SellingTokenTrackingInfo, TokenMetrics};
 use crate::processor::transaction_retry;
+use crate::processor::trade_ledger::{self, TradeSide};
+use crate::processor::route_split;
+use crate::processor::copy_exit;
+use crate::processor::copy_guard;
 use dashmap::DashMap;
-use crate::dex::pump_fun::PUMP_FUN_PROGRAM;
-use crate::dex::pump_swap::PUMP_SWAP_PROGRAM;
-use crate::dex::raydium_launchpad::RAYDIUM_LAUNCHPAD_PROGRAM;
 use chrono::Timelike;
 
 // Enum for different selling actions
@@ -523,6 +535,15 @@ impl BoughtTokenInfo {
         self.trailing_stop_percentage = self.calculate_dynamic_trailing_stop();
     }
     
+    /// PnL percentage after priority fees, tips and ATA rent paid on this position
+    pub fn net_pnl_percentage(&self) -> f64 {
+        if self.initial_amount <= 0.0 {
+            return self.pnl_percentage;
+        }
+        let costs_sol = trade_costs::position_costs(&self.token_mint).net_sol();
+        self.pnl_percentage - (costs_sol / self.initial_amount) * 100.0
+    }
+    
     fn calculate_dynamic_trailing_stop(&self) -> f64 {
         match self.highest_pnl_percentage {
             pnl if pnl < 20.0 => 1.0,     // <20% PnL: 1% trailing stop (default)
@@ -618,6 +639,8 @@ lazy_static::lazy_static! {
     static ref SHOULD_CONTINUE_STREAMING: Arc<AtomicBool> = Arc::new(AtomicBool::new(true));
     // Add: Permanent blacklist for tokens that have been bought before (never rebuy)
     static ref BOUGHT_TOKENS_BLACKLIST: Arc<DashMap<String, u64>> = Arc::new(DashMap::new());
+    // Dip re-entries let past the blacklist for their next buy, by mint: the re-entry number
+    static ref REENTRY_EXEMPTIONS: Arc<DashMap<String, u32>> = Arc::new(DashMap::new());
     // SNIPER BOT: Focus token list for tracking target wallet purchases
     pub static ref FOCUS_TOKEN_LIST: Arc<DashMap<String, FocusTokenInfo>> = Arc::new(DashMap::new());
     // SNIPER BOT: Price monitoring tasks for focus tokens
@@ -750,130 +773,133 @@ pub async fn start_target_wallet_monitoring(config: SniperConfig) -> Result<(),
          start_enhanced_selling_monitor(app_state_clone, swap_config_clone).await;
      });
     
-    // Connect to Yellowstone gRPC
-    let mut client = GeyserGrpcClient::build_from_shared(config.yellowstone_grpc_http.clone())
-        .map_err(|e| format!("Failed to build client: {}", e))?
-        .x_token::<String>(Some(config.yellowstone_grpc_token.clone()))
-        .map_err(|e| format!("Failed to set x_token: {}", e))?
-        .tls_config(ClientTlsConfig::new().with_native_roots())
-        .map_err(|e| format!("Failed to set tls config: {}", e))?
-        .connect()
+    // Transactions come from the configured sources (TRANSACTION_SOURCES, default Yellowstone)
+    let source_config = transaction_source::SourceConfig::new(
+        config.yellowstone_grpc_http.clone(),
+        config.yellowstone_grpc_token.clone(),
+    );
+    let source = transaction_source::from_env(&source_config).map_err(|e| e.to_string())?;
+    let scope = transaction_source::MonitorScope::from_env()?;
+    logger.log(format!("Monitoring scope: {} target wallets, {}", config.target_addresses.len(), scope.describe()));
+    let filter = scope.filter_for(&config.target_addresses);
+    let mut stream = source
+        .subscribe(&filter)
         .await
-        .map_err(|e| format!("Failed to connect: {}", e))?;
+        .map_err(|e| format!("Failed to subscribe to {} source: {}", source.name(), e))?;
 
-    // Set up subscribe
-    let mut retry_count = 0;
-    const MAX_RETRIES: u32 = 3;
-    let (subscribe_tx, mut stream) = loop {
-        match client.subscribe().await {
-            Ok(pair) => break pair,
-            Err(e) => {
-                retry_count += 1;
-                if retry_count >= MAX_RETRIES {
-                    return Err(format!("Failed to subscribe after {} attempts: {}", MAX_RETRIES, e));
-                }
-                time::sleep(Duration::from_secs(5)).await;
-            }
-        }
-    };
-
-    // Convert to Arc to allow cloning across tasks
-    let subscribe_tx = Arc::new(tokio::sync::Mutex::new(subscribe_tx));
     // Enable buying
     BUYING_ENABLED.insert((), true);
 
-    // Create config for subscription
-    let target_addresses = config.target_addresses.clone();
-
-    // Set up subscription
-    let subscription_request = SubscribeRequest {
-        transactions: maplit::hashmap! {
-            "All".to_owned() => SubscribeRequestFilterTransactions {
-                vote: Some(false), // Exclude vote transactions
-                failed: Some(false), // Exclude failed transactions
-                signature: None,
-                account_include: target_addresses.clone(), // Only include transactions involving our targets
-                account_exclude: vec![], // Listen to all transactions
-                account_required: Vec::<String>::new(),
-            }
-        },
-        commitment: Some(CommitmentLevel::Processed as i32),
-        ..Default::default()
-    };
-    
-    subscribe_tx
-        .lock()
-        .await
-        .send(subscription_request)
-        .await
-        .map_err(|e| format!("Failed to send subscribe request: {}", e))?;
-    
     // Create Arc config for tasks
     let config = Arc::new(config);
 
-    // Spawn heartbeat task
-    let subscribe_tx_clone = subscribe_tx.clone();
-    
-    tokio::spawn(async move {
-        let mut interval = time::interval(Duration::from_secs(30));
-        
-        loop {
-            interval.tick().await;
-            if let Err(_e) = send_heartbeat_ping(&subscribe_tx_clone).await {
-                break;
-            }
-        }
-    });
-    
     // Main stream processing loop
     while SHOULD_CONTINUE_STREAMING.load(Ordering::SeqCst) {
         match stream.next().await {
-            Some(msg_result) => {
-                match msg_result {
-                    Ok(msg) => {
-                        if let Err(e) = process_message_for_target_monitoring(&msg, &subscribe_tx, config.clone(), &logger).await {
-                            logger.log(format!("Error processing message: {}", e).red().to_string());
-                        }
-                    },
-                    Err(e) => {
-                        logger.log(format!("Stream error: {:?}", e).red().to_string());
-                        // Check if it's a connection limit error
-                        if format!("{:?}", e).contains("Maximum connection count reached") {
-                            logger.log("🚫 Connection limit reached - this indicates a connection leak. Streams should be properly closed when tokens are sold.".red().bold().to_string());
-                        }
-                        // Try to reconnect
-                        break;
-                    },
+            Some(Ok(txn)) => {
+                crate::library::slot_lag::observe_stream_slot(txn.slot);
+                if let Err(e) = process_transaction_for_target_monitoring(&txn, config.clone(), &logger).await {
+                    logger.log(format!("Error processing message: {}", e).red().to_string());
                 }
             },
+            Some(Err(e)) => {
+                logger.log(format!("Stream error: {:?}", e).red().to_string());
+                // Check if it's a connection limit error
+                if format!("{:?}", e).contains("Maximum connection count reached") {
+                    logger.log("🚫 Connection limit reached - this indicates a connection leak. Streams should be properly closed when tokens are sold.".red().bold().to_string());
+                }
+                // Try to reconnect
+                break;
+            },
             None => {
                 logger.log("Stream ended".yellow().to_string());
                 break;
             }
         }
     }
-    
+
     if !SHOULD_CONTINUE_STREAMING.load(Ordering::SeqCst) {
-        // Explicitly drop the stream and client to close connections
+        // Explicitly drop the stream to close connections
         drop(stream);
-        drop(subscribe_tx);
-        drop(client);
-        
+
         return Ok(());
     }
-    
+
     // Here you would implement reconnection logic
     
     Ok(())
 }
+/// Shared state and the selling monitor stream trades need, for callers that feed trades to
+/// `handle_stream_trade` instead of running `start_target_wallet_monitoring` (pipelines)
+pub fn start_stream_trading(config: &SniperConfig) {
+    SHOULD_CONTINUE_STREAMING.store(true, Ordering::SeqCst);
+    init_global_state();
+    let app_state = Arc::new(config.app_state.clone());
+    let swap_config = Arc::new(config.swap_config.clone());
+    tokio::spawn(async move {
+        start_enhanced_selling_monitor(app_state, swap_config).await;
+    });
+}
 
+/// Copy, focus and dip-buy handling of one trade from a streamed transaction
+pub async fn handle_stream_trade(
+    trade_info: transaction_parser::TradeInfoFromToken,
+    config: Arc<SniperConfig>,
+    target_signature: Option<Signature>,
+    txn: &SubscribeUpdateTransaction,
+) -> Result<(), String> {
+    let logger = Logger::new("[SNIPER-BOT] => ".green().bold().to_string());
+    handle_sniper_bot_logic(trade_info, config, target_signature, txn, &logger).await
+}
 
+/// Abandon a buy whose signal went stale while the transaction was being prepared
+fn ensure_signal_fresh(trade_info: &transaction_parser::TradeInfoFromToken, logger: &Logger) -> Result<(), String> {
+    swap_deadline::check_signal(trade_info.slot, trade_info.timestamp).map_err(|stale| {
+        logger.log(format!("⌛ Abandoning buy of {}: {}", trade_info.mint, stale).yellow().to_string());
+        stale.to_string()
+    })
+}
 
+/// Record the buy in the order ledger, refused while an earlier order of the mint may still land
+/// or once the mint has been bought, even by a previous run, unless it is dip re-entry `reentry`
+async fn begin_buy_order(
+    trade_info: &transaction_parser::TradeInfoFromToken,
+    reentry: Option<u32>,
+    app_state: &AppState,
+    logger: &Logger,
+) -> Result<String, String> {
+    let order = OrderLedger::shared()
+        .begin(&trade_info.mint, TradeSide::Buy, reentry, app_state.rpc_api.as_ref(), &app_state.wallet.pubkey())
+        .await
+        .map_err(|duplicate| {
+            logger.log(format!("🧾 {}, skipping buy of {}", duplicate, trade_info.mint).yellow().to_string());
+            duplicate.to_string()
+        })?;
+    Ok(order.id)
+}
 
-
-
-
-
+/// Send the buy recorded as `order_id`, marking the order submitted, or failed when the send
+/// certainly did not reach the network
+async fn send_buy_order(
+    order_id: &str,
+    rpc_api: Arc<dyn crate::library::rpc_api::RpcApi>,
+    recent_blockhash: solana_sdk::hash::Hash,
+    keypair: &Keypair,
+    instructions: Vec<solana_sdk::instruction::Instruction>,
+    logger: &Logger,
+) -> Result<Vec<String>, BotError> {
+    let orders = OrderLedger::shared();
+    match crate::block_engine::tx::new_signed_and_send_buy(rpc_api, recent_blockhash, keypair, instructions, logger).await {
+        Ok(signatures) => {
+            orders.submitted(order_id, &signatures);
+            Ok(signatures)
+        }
+        Err(e) => {
+            orders.send_failed(order_id, &e);
+            Err(e)
+        }
+    }
+}
 
 /// Verify that a transaction was successful
 async fn verify_transaction(
@@ -887,67 +913,166 @@ async fn verify_transaction(
         Err(e) => return Err(format!("Invalid signature: {}", e)),
     };
     
-    // Verify transaction success with retries
-    let max_retries = 5;
-    for retry in 0..max_retries {
-        // Check transaction status
-        match app_state.rpc_nonblocking_client.get_signature_statuses(&[signature]).await {
-            Ok(result) => {
-                if let Some(status_opt) = result.value.get(0) {
-                    if let Some(status) = status_opt {
-                        if status.err.is_some() {
-                            // Transaction failed
-                            return Err(format!("Transaction failed: {:?}", status.err));
-                        } else if let Some(conf_status) = &status.confirmation_status {
-                            if matches!(conf_status, TransactionConfirmationStatus::Finalized | 
-                                                      TransactionConfirmationStatus::Confirmed) {
-                                return Ok(true);
-                            } else {
-                                logger.log(format!("Transaction not yet confirmed (status: {:?}), retrying...", 
-                                         conf_status).yellow().to_string());
-                            }
-                        } else {
-                        }
-                    } else {
-                    }
-                }
-            },
-            Err(e) => {
-                logger.log(format!("Failed to get transaction status: {}, retrying...", e).red().to_string());
-            }
+    // Pending and transient RPC failures are retried under their class policy; an on-chain
+    // failure is fatal and returned immediately
+    let status = retry("transaction verification", logger, |_| async {
+        match app_state.rpc_api.signature_status(&signature).await? {
+            SignatureStatus::Confirmed => Ok(()),
+            SignatureStatus::Failed(err) => Err(BotError::Rejected(err)),
+            SignatureStatus::Pending => Err(BotError::NotConfirmed(signature.to_string())),
         }
-        
-        if retry < max_retries - 1 {
-            // Wait before retrying
-            sleep(Duration::from_millis(500)).await;
-        } else {
-            return Err("Transaction verification timed out".to_string());
+    }).await;
+
+    match status {
+        Ok(()) => {
+            RelayStats::shared().record_landed(&signature);
+            OrderLedger::shared().signature_landed(signature_str);
+            trade_ledger::record_confirmed(app_state.clone(), signature);
+            Ok(true)
+        }
+        Err(BotError::Rejected(err)) => {
+            OrderLedger::shared().signature_failed(signature_str, &err);
+            Err(format!("Transaction failed: {}", err))
         }
+        Err(BotError::NotConfirmed(_)) => Err("Transaction verification timed out".to_string()),
+        Err(e) => Err(format!("Failed to get transaction status: {}", e)),
     }
-    
-    // If we get here, verification failed
-    Err("Transaction verification failed after retries".to_string())
 }
 
-/// Execute buy operation based on detected transaction
+/// Gates every live buy passes before anything is built or sent, whatever triggered it (a signal
+/// confirmation source such as `TARGET_BUY`). Returns the mint's snipe slot, to hold until the buy
+/// is tracked, the buy config sized for the market regime, and the dip re-entry number the buy
+/// used up, if any.
+async fn pre_buy_checks(
+    trade_info: &transaction_parser::TradeInfoFromToken,
+    trigger: &str,
+    app_state: &AppState,
+    swap_config: &SwapConfig,
+    logger: &Logger,
+) -> Result<(crate::processor::snipe_limiter::SnipeSlot, SwapConfig, Option<u32>), String> {
+    // One buy per mint at a time and a bounded number in flight
+    let snipe_slot = match SnipeLimiter::shared().acquire(&trade_info.mint).await {
+        Ok(slot) => slot,
+        Err(rejected) => {
+            logger.log(format!("🚦 {}, skipping buy of {}", rejected, trade_info.mint).yellow().to_string());
+            return Err(rejected.to_string());
+        }
+    };
+
+    // Check if this token is in the permanent blacklist (never rebuy), unless this is a dip re-entry
+    let reentry = REENTRY_EXEMPTIONS.remove(&trade_info.mint).map(|(_, reentry)| reentry);
+    if BOUGHT_TOKENS_BLACKLIST.contains_key(&trade_info.mint) {
+        match reentry {
+            Some(reentry) => logger.log(format!("📉 Token {} was bought before, buying again as dip re-entry #{}", trade_info.mint, reentry)),
+            None => {
+                logger.log(format!("🚫 Token {} is blacklisted (previously bought), skipping buy", trade_info.mint).yellow().to_string());
+                return Err("Token is blacklisted - previously bought".to_string());
+            }
+        }
+    }
+
+    // Muted via /mute, directly or through its creator
+    if crate::processor::mute_list::is_token_muted(&trade_info.mint) {
+        logger.log(format!("🔇 Token {} is muted, skipping buy", trade_info.mint).yellow().to_string());
+        return Err("Token is muted".to_string());
+    }
+
+    // Flagged by a community scam list, directly or through its creator
+    if let Some(entry) = crate::processor::scam_lists::flag_for(&trade_info.mint) {
+        logger.log(format!("🚩 Token {} is blacklisted ({}), skipping buy", trade_info.mint, entry.describe()).yellow().to_string());
+        return Err(format!("Token is blacklisted - {}", entry.describe()));
+    }
+
+    // Rejected by TRADE_FILTER_SCRIPT
+    if let Some(reason) = crate::processor::script_filter::trade_refusal(trade_info) {
+        logger.log(format!("📜 {}, skipping buy of {}", reason, trade_info.mint).yellow().to_string());
+        return Err(reason);
+    }
+
+    // Decisions on a lagging stream are made on stale prices
+    if let Some(reason) = crate::library::slot_lag::sniping_paused_reason() {
+        logger.log(format!("⏸️ {}, skipping buy of {}", reason, trade_info.mint).yellow().to_string());
+        return Err(reason);
+    }
+
+    // Another instance holds the trading lock for this wallet
+    if let Some(reason) = crate::library::instance_lock::trading_paused_reason() {
+        logger.log(format!("⏸️ {}, skipping buy of {}", reason, trade_info.mint).yellow().to_string());
+        return Err(reason);
+    }
+
+    // Paused by the operator (/pause or POST /control/pause)
+    if let Some(reason) = crate::processor::trading_control::buys_paused_reason() {
+        logger.log(format!("⏸️ {}, skipping buy of {}", reason, trade_info.mint).yellow().to_string());
+        return Err(reason);
+    }
+
+    // Trading scoped to other DEXes (TRADE_DEXES)
+    if !trade_info.dex_type.is_tradeable() {
+        logger.log(format!("🚫 Trading on {} is disabled, skipping buy of {}", trade_info.dex_type.as_str(), trade_info.mint).yellow().to_string());
+        return Err(format!("Trading on {} is disabled", trade_info.dex_type.as_str()));
+    }
+
+    // Fewer independent signals than SIGNAL_CONFIRMATION_MIN agree on the token
+    if let Err(reason) = crate::processor::signal_confirmation::confirm_buy(&trade_info.mint, trigger) {
+        logger.log(format!("🧮 Unconfirmed: {}, skipping buy of {}", reason, trade_info.mint).yellow().to_string());
+        return Err(format!("Unconfirmed: {}", reason));
+    }
+
+    // Tokens that cannot be sold right now are not bought
+    let sell_route = crate::processor::sell_route::SellRouteGuard::from_env(app_state.rpc_nonblocking_client.clone());
+    if let Err(reason) = sell_route.vet(&trade_info.dex_type, &trade_info.mint, &trade_info.pool_id).await {
+        logger.log(format!("🧱 {}, skipping buy of {}", reason, trade_info.mint).yellow().to_string());
+        return Err(reason);
+    }
+
+    let mut buy_config = swap_config.clone();
+    buy_config.swap_direction = SwapDirection::Buy;
+
+    // Smaller positions while the market is cold
+    let risk = market_regime::risk_multiplier();
+    if risk < 1.0 {
+        buy_config.amount_in *= risk;
+        logger.log(format!("🥶 Cold market regime, buying {} with {:.0}% of the usual size", trade_info.mint, risk * 100.0).yellow().to_string());
+    }
+
+    Ok((snipe_slot, buy_config, reentry))
+}
+
+/// Let the next buy of `mint` past the never-rebuy blacklist as dip re-entry number `reentry`
+/// (counted from 1). Refused past DIP_BUY_MAX_REENTRIES; the exemption is used up by that buy
+/// whether it succeeds or not.
+pub fn allow_reentry(mint: &str, reentry: u32) -> bool {
+    if reentry == 0 || reentry > dip_buy::DipBuy::shared().config().max_reentries {
+        return false;
+    }
+    REENTRY_EXEMPTIONS.insert(mint.to_string(), reentry);
+    true
+}
+
+/// Execute buy operation based on detected transaction. Refused with `BotError::TradingDisabled`
+/// before any other check while TRADING_ENABLED=false.
 pub async fn execute_buy(
     trade_info: transaction_parser::TradeInfoFromToken,
     app_state: Arc<AppState>,
     swap_config: Arc<SwapConfig>,
     protocol: SwapProtocol,
+) -> Result<(), BotError> {
+    crate::library::trading_lockdown::ensure_trading_enabled()?;
+    buy(trade_info, app_state, swap_config, protocol).await.map_err(|e| BotError::from_message(&e))
+}
+
+async fn buy(
+    trade_info: transaction_parser::TradeInfoFromToken,
+    app_state: Arc<AppState>,
+    swap_config: Arc<SwapConfig>,
+    protocol: SwapProtocol,
 ) -> Result<(), String> {
     let logger = Logger::new("[EXECUTE-BUY] => ".green().to_string());
     let start_time = Instant::now();
-    
-    // Check if this token is in the permanent blacklist (never rebuy)
-    if BOUGHT_TOKENS_BLACKLIST.contains_key(&trade_info.mint) {
-        logger.log(format!("🚫 Token {} is blacklisted (previously bought), skipping buy", trade_info.mint).yellow().to_string());
-        return Err("Token is blacklisted - previously bought".to_string());
-    }
-    
-    // Create a modified swap config based on the trade_info
-    let mut buy_config = (*swap_config).clone();
-    buy_config.swap_direction = SwapDirection::Buy;
+
+    // Held until the buy is tracked
+    let (_snipe_slot, buy_config, reentry) = pre_buy_checks(&trade_info, crate::processor::signal_confirmation::TARGET_BUY, &app_state, &swap_config, &logger).await?;
     
     // Store the amount_in before potential moves
     let amount_in = buy_config.amount_in;
@@ -1002,7 +1127,7 @@ pub async fn execute_buy(
                     logger.log(format!("copy transaction {}", trade_info.signature));
                     let start_time = Instant::now();
                     // Get real-time blockhash from processor
-                    let recent_blockhash = match crate::library::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
+                    let recent_blockhash = match app_state.rpc_api.latest_blockhash().await {
                         Some(hash) => hash,
                         None => {
                             logger.log("Failed to get real-time blockhash, skipping transaction".red().to_string());
@@ -1012,8 +1137,11 @@ pub async fn execute_buy(
                     println!("time taken for get_latest_blockhash: {:?}", start_time.elapsed());
                     println!("using zeroslot for buy transaction >>>>>>>>");
                     // Execute the transaction using zeroslot for buying
-                    match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                        app_state.zeroslot_rpc_client.clone(),
+                    ensure_signal_fresh(&trade_info, &logger)?;
+                    let order_id = begin_buy_order(&trade_info, reentry, &app_state, &logger).await?;
+                    match send_buy_order(
+                        &order_id,
+                        app_state.rpc_api.clone(),
                         recent_blockhash,
                         &keypair,
                         instructions,
@@ -1116,7 +1244,7 @@ pub async fn execute_buy(
                     logger.log(format!("copy transaction {}", trade_info.signature));
                     
                     // Get real-time blockhash from processor
-                    let recent_blockhash = match crate::library::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
+                    let recent_blockhash = match app_state.rpc_api.latest_blockhash().await {
                         Some(hash) => hash,
                         None => {
                             logger.log("Failed to get real-time blockhash, skipping transaction".red().to_string());
@@ -1126,8 +1254,11 @@ pub async fn execute_buy(
 
                     println!("using zeroslot for buy transaction >>>>>>>>");
                     // Execute the transaction using zeroslot for buying
-                    match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                        app_state.zeroslot_rpc_client.clone(),
+                    ensure_signal_fresh(&trade_info, &logger)?;
+                    let order_id = begin_buy_order(&trade_info, reentry, &app_state, &logger).await?;
+                    match send_buy_order(
+                        &order_id,
+                        app_state.rpc_api.clone(),
                         recent_blockhash,
                         &keypair,
                         instructions,
@@ -1222,7 +1353,7 @@ pub async fn execute_buy(
                 Ok((keypair, instructions, _price)) => {
                     
                     // Get real-time blockhash from processor
-                    let recent_blockhash = match crate::library::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
+                    let recent_blockhash = match app_state.rpc_api.latest_blockhash().await {
                         Some(hash) => hash,
                         None => {
                             logger.log("Failed to get real-time blockhash, skipping transaction".red().to_string());
@@ -1231,8 +1362,11 @@ pub async fn execute_buy(
                     };
                     
                     // Execute the transaction using zeroslot for buying
-                    match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                        app_state.zeroslot_rpc_client.clone(),
+                    ensure_signal_fresh(&trade_info, &logger)?;
+                    let order_id = begin_buy_order(&trade_info, reentry, &app_state, &logger).await?;
+                    match send_buy_order(
+                        &order_id,
+                        app_state.rpc_api.clone(),
                         recent_blockhash,
                         &keypair,
                         instructions,
@@ -1328,7 +1462,7 @@ pub async fn execute_buy(
                     logger.log(format!("copy transaction {}", trade_info.signature));
                     let start_time = Instant::now();
                     // Get real-time blockhash from processor
-                    let recent_blockhash = match crate::library::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
+                    let recent_blockhash = match app_state.rpc_api.latest_blockhash().await {
                         Some(hash) => hash,
                         None => {
                             logger.log("Failed to get real-time blockhash, skipping transaction".red().to_string());
@@ -1338,8 +1472,11 @@ pub async fn execute_buy(
                     println!("time taken for get_latest_blockhash: {:?}", start_time.elapsed());
                     println!("using zeroslot for buy transaction >>>>>>>>");
                     // Execute the transaction using zeroslot for buying
-                    match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                        app_state.zeroslot_rpc_client.clone(),
+                    ensure_signal_fresh(&trade_info, &logger)?;
+                    let order_id = begin_buy_order(&trade_info, reentry, &app_state, &logger).await?;
+                    match send_buy_order(
+                        &order_id,
+                        app_state.rpc_api.clone(),
                         recent_blockhash,
                         &keypair,
                         instructions,
@@ -1427,64 +1564,150 @@ pub async fn execute_buy(
     
     // Increment bought counter on success
     if result.is_ok() {
-        // Update counters and tracking
-        let bought_count = {
-            let mut entry = BOUGHT_TOKENS.entry(()).or_insert(0);
-            *entry += 1;
-            *entry
-        };
-        logger.log(format!("Total bought: {}", bought_count));
-        
-        // Add token to bought token list for comprehensive tracking
-        let bought_token_info = BoughtTokenInfo::new(
-            trade_info.mint.clone(),
-            trade_info.price, // Use price directly from TradeInfoFromToken (already scaled)
-            amount_in, // SOL amount spent (using stored value)
-            trade_info.token_change.abs(), // Token amount received
-            protocol.clone(),
-            trade_info.clone(),
-            std::env::var("SELLING_TIME").unwrap_or_else(|_| "300".to_string()).parse().unwrap_or(300),
-        );
-        
-        // Debug logging for token tracking
-        println!("DEBUG TRACKING: Adding token {} to BOUGHT_TOKEN_LIST with entry_price: {}", 
-            trade_info.mint, bought_token_info.entry_price);
-        
-        // Only add to tracking if entry_price is valid
-        if bought_token_info.entry_price > 0 {
-            BOUGHT_TOKEN_LIST.insert(trade_info.mint.clone(), bought_token_info);
-            
-            // Add to permanent blacklist (never rebuy this token)
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
-            BOUGHT_TOKENS_BLACKLIST.insert(trade_info.mint.clone(), timestamp);
-            logger.log(format!("🚫 Added {} to permanent blacklist", trade_info.mint));
-        } else {
-            println!("WARNING: Refusing to track token {} with entry_price = 0", trade_info.mint);
-        }
-        
-        // Token added to selling system via the selling_engine.update_metrics call above
-        
-        // Legacy tracking for compatibility
-        TOKEN_TRACKING.entry(trade_info.mint.clone()).or_insert(TokenTrackingInfo {
-            top_pnl: 0.0,
-            last_sell_time: Instant::now(),
-            completed_intervals: HashSet::new(),
-            sell_attempts: 0,
-            sell_success: 0,
-        });
-        
-        // Get active tokens list
-        let _active_tokens: Vec<String> = TOKEN_TRACKING.iter().map(|entry| entry.key().clone()).collect();
-        let _sold_count = SOLD_TOKENS.get(&()).map(|r| *r).unwrap_or(0);
-        
+        record_successful_buy(&trade_info, amount_in, &protocol, &logger);
     }
     
     result
 }
 
+/// Update counters, the bought token list and the blacklist after a confirmed buy
+fn record_successful_buy(
+    trade_info: &transaction_parser::TradeInfoFromToken,
+    amount_in: f64,
+    protocol: &SwapProtocol,
+    logger: &Logger,
+) {
+    // Update counters and tracking
+    let bought_count = {
+        let mut entry = BOUGHT_TOKENS.entry(()).or_insert(0);
+        *entry += 1;
+        *entry
+    };
+    logger.log(format!("Total bought: {}", bought_count));
+    
+    // Add token to bought token list for comprehensive tracking
+    let mut bought_token_info = BoughtTokenInfo::new(
+        trade_info.mint.clone(),
+        trade_info.price, // Use price directly from TradeInfoFromToken (already scaled)
+        amount_in, // SOL amount spent (using stored value)
+        trade_info.token_change.abs(), // Token amount received
+        protocol.clone(),
+        trade_info.clone(),
+        std::env::var("SELLING_TIME").unwrap_or_else(|_| "300".to_string()).parse().unwrap_or(300),
+    );
+    // Quoted amounts until the buy is read back from chain, or its fill if that happened already
+    let fills = crate::processor::fill_check::FillCheck::shared();
+    if let Some(report) = fills.position_opened(&mut bought_token_info) {
+        fills.report(report);
+    }
+    
+    // Debug logging for token tracking
+    println!("DEBUG TRACKING: Adding token {} to BOUGHT_TOKEN_LIST with entry_price: {}", 
+        trade_info.mint, bought_token_info.entry_price);
+    
+    // Only add to tracking if entry_price is valid
+    if bought_token_info.entry_price > 0 {
+        BOUGHT_TOKEN_LIST.insert(trade_info.mint.clone(), bought_token_info);
+        
+        // Add to permanent blacklist (never rebuy this token)
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        BOUGHT_TOKENS_BLACKLIST.insert(trade_info.mint.clone(), timestamp);
+        logger.log(format!("🚫 Added {} to permanent blacklist", trade_info.mint));
+    } else {
+        println!("WARNING: Refusing to track token {} with entry_price = 0", trade_info.mint);
+    }
+    
+    // Token added to selling system via the selling_engine.update_metrics call above
+    
+    // Legacy tracking for compatibility
+    TOKEN_TRACKING.entry(trade_info.mint.clone()).or_insert(TokenTrackingInfo {
+        top_pnl: 0.0,
+        last_sell_time: Instant::now(),
+        completed_intervals: HashSet::new(),
+        sell_attempts: 0,
+        sell_success: 0,
+    });
+    
+    // Get active tokens list
+    let _active_tokens: Vec<String> = TOKEN_TRACKING.iter().map(|entry| entry.key().clone()).collect();
+    let _sold_count = SOLD_TOKENS.get(&()).map(|r| *r).unwrap_or(0);
+}
+
+/// Send a buy whose instructions were built ahead of time (e.g. graduation sniping), then verify
+/// and track it like any other buy. `trade_info` must describe the pool state the buy was built for.
+pub async fn execute_prebuilt_buy(
+    trade_info: transaction_parser::TradeInfoFromToken,
+    keypair: Arc<Keypair>,
+    instructions: Vec<solana_sdk::instruction::Instruction>,
+    app_state: Arc<AppState>,
+    swap_config: Arc<SwapConfig>,
+    protocol: SwapProtocol,
+) -> Result<(), String> {
+    let logger = Logger::new("[EXECUTE-PREBUILT-BUY] => ".green().to_string());
+    let start_time = Instant::now();
+
+    // Held until the buy is tracked
+    let (_snipe_slot, buy_config, reentry) = pre_buy_checks(&trade_info, crate::processor::signal_confirmation::GRADUATION, &app_state, &swap_config, &logger).await?;
+    // The instructions carry their amount: a buy built for more than the market regime allows now is not sent
+    if trade_info.sol_change.abs() > buy_config.amount_in + 1e-9 {
+        logger.log(format!(
+            "🥶 Pre-built buy of {} SOL exceeds the {} SOL the market regime allows, skipping buy of {}",
+            trade_info.sol_change.abs(), buy_config.amount_in, trade_info.mint
+        ).yellow().to_string());
+        return Err(format!("Pre-built for {} SOL, at most {} SOL allowed", trade_info.sol_change.abs(), buy_config.amount_in));
+    }
+
+    let recent_blockhash = match app_state.rpc_api.latest_blockhash().await {
+        Some(hash) => hash,
+        None => {
+            logger.log("Failed to get real-time blockhash, skipping transaction".red().to_string());
+            return Err("Failed to get real-time blockhash".to_string());
+        }
+    };
+
+    ensure_signal_fresh(&trade_info, &logger)?;
+    let order_id = begin_buy_order(&trade_info, reentry, &app_state, &logger).await?;
+    let signatures = send_buy_order(
+        &order_id,
+        app_state.rpc_api.clone(),
+        recent_blockhash,
+        &keypair,
+        instructions,
+        &logger,
+    ).await.map_err(|e| format!("Transaction error: {}", e))?;
+    let signature = signatures.first().ok_or_else(|| "No transaction signature returned".to_string())?;
+    logger.log(format!("Buy transaction sent in {:?}: {}", start_time.elapsed(), signature));
+
+    let verified = verify_transaction(&signature.to_string(), app_state.clone(), &logger).await
+        .map_err(|e| format!("Transaction verification error: {}", e))?;
+    if !verified {
+        return Err("Buy transaction verification failed".to_string());
+    }
+    logger.log("Buy transaction verified successfully".to_string());
+
+    if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
+        let token_mint = Pubkey::from_str(&trade_info.mint)
+            .map_err(|_| "Invalid token mint".to_string())?;
+        WALLET_TOKEN_ACCOUNTS.insert(get_associated_token_address(&wallet_pubkey, &token_mint));
+    }
+
+    let selling_engine = crate::processor::selling_strategy::SellingEngine::new(
+        app_state.clone(),
+        Arc::new(buy_config.clone()),
+        crate::processor::selling_strategy::SellingConfig::default()
+    );
+    if let Err(e) = selling_engine.update_metrics(&trade_info.mint, &trade_info).await {
+        logger.log(format!("Warning: Failed to update token metrics after buy: {}", e).yellow().to_string());
+    }
+
+    record_successful_buy(&trade_info, buy_config.amount_in, &protocol, &logger);
+    logger.log(format!("✅ Buy transaction completed for token: {}", trade_info.mint).green().to_string());
+    Ok(())
+}
+
 /// Internal wallet monitoring function using second GRPC stream
 async fn start_wallet_monitoring_internal(app_state: Arc<AppState>) -> Result<(), String> {
     use futures_util::stream::StreamExt;
@@ -1635,8 +1858,9 @@ async fn execute_pumpfun_emergency_sell_with_zeroslot(
                 }
             };
             
-            match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
+            match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                Urgency::Critical,
+                app_state.rpc_api.clone(),
                 recent_blockhash,
                 &keypair,
                 instructions,
@@ -1686,8 +1910,9 @@ async fn execute_pumpswap_emergency_sell_with_zeroslot(
                 }
             };
             
-            match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
+            match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                Urgency::Critical,
+                app_state.rpc_api.clone(),
                 recent_blockhash,
                 &keypair,
                 instructions,
@@ -1762,6 +1987,9 @@ async fn execute_jupiter_emergency_sell(
     ).await {
         Ok(signature) => {
             logger.log(format!("🪐 Jupiter emergency sell successful: {}", signature).green().to_string());
+            if let Ok(signature) = Signature::from_str(&signature) {
+                trade_ledger::record_confirmed(app_state.clone(), signature);
+            }
             Ok(())
         },
         Err(e) => {
@@ -2039,7 +2267,14 @@ async fn execute_sell_all_enhanced(
             execute_pumpfun_sell_with_zeroslot(&trade_info, sell_config, app_state.clone(), &logger).await
         },
         SwapProtocol::PumpSwap => {
-            execute_pumpswap_sell_with_zeroslot(&trade_info, sell_config, app_state.clone(), &logger).await
+            match execute_pumpswap_split_sell(&token_pubkey, &ata, sell_config.slippage as u64, app_state.clone(), &logger).await {
+                Ok(true) => Ok(()),
+                Ok(false) => execute_pumpswap_sell_with_zeroslot(&trade_info, sell_config, app_state.clone(), &logger).await,
+                Err(e) => {
+                    logger.log(format!("Split sell failed, selling through a single pool: {}", e).yellow().to_string());
+                    execute_pumpswap_sell_with_zeroslot(&trade_info, sell_config, app_state.clone(), &logger).await
+                }
+            }
         },
         SwapProtocol::RaydiumLaunchpad => {
             execute_raydium_sell_with_zeroslot(&trade_info, sell_config, app_state.clone(), &logger).await
@@ -2211,6 +2446,7 @@ fn create_sell_trade_info_from_original(
         liquidity: original_trade_info.liquidity,
         virtual_sol_reserves: original_trade_info.virtual_sol_reserves,
         virtual_token_reserves: original_trade_info.virtual_token_reserves,
+        quote_asset: original_trade_info.quote_asset,
     }
 }
 
@@ -2240,8 +2476,9 @@ async fn execute_pumpfun_sell_with_zeroslot(
                 }
             };
             
-            match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
+            match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                Urgency::Normal,
+                app_state.rpc_api.clone(),
                 recent_blockhash,
                 &keypair,
                 instructions,
@@ -2267,6 +2504,70 @@ async fn execute_pumpfun_sell_with_zeroslot(
     }
 }
 
+/// Sell the whole balance across several PumpSwap pools when one pool alone would take too
+/// much impact. Returns Ok(false) when splitting is disabled or not worth it, so the caller
+/// falls back to the single pool sell.
+async fn execute_pumpswap_split_sell(
+    mint: &Pubkey,
+    ata: &Pubkey,
+    slippage_bps: u64,
+    app_state: Arc<AppState>,
+    logger: &Logger,
+) -> Result<bool, String> {
+    let config = route_split::SplitConfig::from_env();
+    if !config.enabled {
+        return Ok(false);
+    }
+
+    let raw_amount = match app_state.rpc_nonblocking_client.get_token_account(ata).await {
+        Ok(Some(account)) => account.token_amount.amount.parse::<u64>()
+            .map_err(|e| format!("Failed to parse token amount: {}", e))?,
+        Ok(None) => return Ok(false),
+        Err(e) => return Err(format!("Failed to get token account: {}", e)),
+    };
+
+    let pump_swap = crate::dex::pump_swap::PumpSwap::new(
+        app_state.wallet.clone(),
+        Some(app_state.rpc_client.clone()),
+        Some(app_state.rpc_nonblocking_client.clone()),
+    );
+    let pools = pump_swap.find_pools(mint).await.map_err(|e| format!("Failed to load pools: {}", e))?;
+    let venues: Vec<route_split::SellVenue> = pools.iter().map(|p| p.to_venue()).collect();
+    if !route_split::should_split(raw_amount, &venues, &config) {
+        return Ok(false);
+    }
+    let legs = route_split::plan_split(raw_amount, &venues, &config);
+    if legs.len() < 2 {
+        return Ok(false);
+    }
+
+    for leg in &legs {
+        logger.log(format!(
+            "Split leg: {} tokens into pool {} (expected {:.6} SOL, {:.2}% impact)",
+            leg.token_amount, leg.venue.pool_id, leg.expected_sol as f64 / 1e9, leg.venue.impact_pct(leg.token_amount),
+        ));
+    }
+
+    let (keypair, instructions) = pump_swap.build_split_sell(mint, &pools, &legs, slippage_bps, true)
+        .map_err(|e| format!("Failed to build split sell: {}", e))?;
+    let recent_blockhash = crate::library::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await
+        .ok_or_else(|| "Failed to get recent blockhash".to_string())?;
+    let signatures = crate::block_engine::tx::new_signed_and_send_by_urgency(
+        Urgency::Normal,
+        app_state.rpc_api.clone(),
+        recent_blockhash,
+        &keypair,
+        instructions,
+        logger,
+    ).await.map_err(|e| format!("Zeroslot transaction error: {}", e))?;
+    let signature = signatures.first().ok_or_else(|| "No transaction signature returned".to_string())?;
+    logger.log(format!("Split sell across {} pools sent: {}", legs.len(), signature));
+
+    verify_transaction(&signature.to_string(), app_state.clone(), logger).await
+        .map_err(|e| format!("Transaction verification error: {}", e))?;
+    Ok(true)
+}
+
 /// Execute PumpSwap sell with zeroslot
 async fn execute_pumpswap_sell_with_zeroslot(
     trade_info: &transaction_parser::TradeInfoFromToken,
@@ -2291,8 +2592,9 @@ async fn execute_pumpswap_sell_with_zeroslot(
                 }
             };
             
-            match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
+            match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                Urgency::Normal,
+                app_state.rpc_api.clone(),
                 recent_blockhash,
                 &keypair,
                 instructions,
@@ -2342,8 +2644,9 @@ async fn execute_raydium_emergency_sell_with_zeroslot(
                 }
             };
             
-            match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
+            match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                Urgency::Critical,
+                app_state.rpc_api.clone(),
                 recent_blockhash,
                 &keypair,
                 instructions,
@@ -2495,8 +2798,9 @@ async fn execute_raydium_sell_with_zeroslot(
                 }
             };
             
-            match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                app_state.zeroslot_rpc_client.clone(),
+            match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                Urgency::Normal,
+                app_state.rpc_api.clone(),
                 recent_blockhash,
                 &keypair,
                 instructions,
@@ -2586,6 +2890,11 @@ pub async fn execute_sell(
     let logger = Logger::new("[EXECUTE-SELL] => ".green().to_string());
     let start_time = Instant::now();
     
+    if let Some(reason) = crate::library::instance_lock::trading_paused_reason() {
+        logger.log(format!("⏸️ {}, skipping sell of {}", reason, token_mint).yellow().to_string());
+        return Err(reason);
+    }
+
     logger.log(format!("Selling token: {}", token_mint));
     
     // Protocol string for notifications
@@ -2618,6 +2927,7 @@ pub async fn execute_sell(
         liquidity: trade_info.liquidity,
         virtual_sol_reserves: trade_info.virtual_sol_reserves,
         virtual_token_reserves: trade_info.virtual_token_reserves,
+        quote_asset: trade_info.quote_asset,
     };
 
     // Create a modified swap config for selling
@@ -2765,6 +3075,7 @@ pub async fn execute_sell(
                         liquidity: 0.0,
                         virtual_sol_reserves: 0,
                         virtual_token_reserves: 0,
+                        quote_asset: crate::common::quote_asset::for_mint(&token_mint),
                     };
                     
                     // Build swap instructions for sell
@@ -2772,8 +3083,9 @@ pub async fn execute_sell(
                         Ok((keypair, instructions, price)) => {
                             logger.log(format!("Generated PumpFun sell instruction at price: {}", price));
                             // Execute the transaction
-                            match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                                app_state.zeroslot_rpc_client.clone(),
+                            match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                                Urgency::Normal,
+                                app_state.rpc_api.clone(),
                                 match crate::library::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
                                     Some(hash) => hash,
                                     None => {
@@ -2846,6 +3158,7 @@ pub async fn execute_sell(
                         liquidity: trade_info.liquidity,
                         virtual_sol_reserves: trade_info.virtual_sol_reserves,
                         virtual_token_reserves: trade_info.virtual_token_reserves,
+                        quote_asset: trade_info.quote_asset,
                     };
                     
                     // Build swap instructions for sell - use chunk_sell_config
@@ -2863,8 +3176,9 @@ pub async fn execute_sell(
                             logger.log(format!("copy transaction {}", trade_info_clone.signature));
                             
                             // Execute the transaction
-                            match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                                app_state.zeroslot_rpc_client.clone(),
+                            match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                                Urgency::Normal,
+                                app_state.rpc_api.clone(),
                                 recent_blockhash,
                                 &keypair,
                                 instructions,
@@ -2931,6 +3245,7 @@ pub async fn execute_sell(
                         liquidity: trade_info.liquidity,
                         virtual_sol_reserves: trade_info.virtual_sol_reserves,
                         virtual_token_reserves: trade_info.virtual_token_reserves,
+                        quote_asset: trade_info.quote_asset,
                     };
                     
                     match raydium.build_swap_from_parsed_data(&trade_info_clone, sell_config.clone()).await {
@@ -2944,8 +3259,9 @@ pub async fn execute_sell(
                             };
                             logger.log(format!("Generated Raydium sell instruction at price: {}", price));
                             
-                            match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                                app_state.zeroslot_rpc_client.clone(),
+                            match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                                Urgency::Normal,
+                                app_state.rpc_api.clone(),
                                 recent_blockhash,
                                 &keypair,
                                 instructions,
@@ -3011,13 +3327,15 @@ pub async fn execute_sell(
                         liquidity: 0.0,
                         virtual_sol_reserves: 0,
                         virtual_token_reserves: 0,
+                        quote_asset: crate::common::quote_asset::for_mint(&token_mint),
                     };
                     
                     match pump.build_swap_from_parsed_data(&trade_info_clone, sell_config.clone()).await {
                         Ok((keypair, instructions, price)) => {
                             logger.log(format!("Generated PumpFun sell instruction at price: {}", price));
-                            match crate::block_engine::tx::new_signed_and_send_zeroslot(
-                                app_state.zeroslot_rpc_client.clone(),
+                            match crate::block_engine::tx::new_signed_and_send_by_urgency(
+                                Urgency::Normal,
+                                app_state.rpc_api.clone(),
                                 match crate::library::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await {
                                     Some(hash) => hash,
                                     None => {
@@ -3147,6 +3465,7 @@ pub async fn execute_sell(
                     liquidity: 0.0,
                     virtual_sol_reserves: 0,
                     virtual_token_reserves: 0,
+                    quote_asset: crate::common::quote_asset::for_mint(&token_mint),
                 };
                 
                 // Build swap instructions for sell
@@ -3208,6 +3527,7 @@ pub async fn execute_sell(
                 liquidity: 0.0,
                 virtual_sol_reserves: 0,
                 virtual_token_reserves: 0,
+                quote_asset: crate::common::quote_asset::for_mint(&token_mint),
             };
                 
                 // Use the new retry mechanism with Jupiter fallback
@@ -3263,6 +3583,7 @@ pub async fn execute_sell(
                     liquidity: trade_info.liquidity,
                     virtual_sol_reserves: trade_info.virtual_sol_reserves,
                     virtual_token_reserves: trade_info.virtual_token_reserves,
+                    quote_asset: trade_info.quote_asset,
                 };
                 
                 // Use the new retry mechanism with Jupiter fallback
@@ -3321,6 +3642,7 @@ pub async fn execute_sell(
                     liquidity: 0.0,
                     virtual_sol_reserves: 0,
                     virtual_token_reserves: 0,
+                    quote_asset: crate::common::quote_asset::for_mint(&token_mint),
                 };
                 
                 // Use the new retry mechanism with Jupiter fallback
@@ -3401,60 +3723,60 @@ pub async fn execute_sell(
 }
 
 /// Process incoming stream messages
-async fn process_message_for_target_monitoring(
-    msg: &SubscribeUpdate,
-    _subscribe_tx: &Arc<tokio::sync::Mutex<impl Sink<SubscribeRequest, Error = impl std::fmt::Debug> + Unpin>>,
+async fn process_transaction_for_target_monitoring(
+    txn: &SubscribeUpdateTransaction,
     config: Arc<SniperConfig>,
     logger: &Logger,
 ) -> Result<(), String> {
-    // Handle ping messages
-    if let Some(UpdateOneof::Ping(_ping)) = &msg.update_oneof {
-        return Ok(());
-    }
-    
-    // Handle transaction messages
-    if let Some(UpdateOneof::Transaction(txn)) = &msg.update_oneof {
-        let target_signature = if let Some(transaction) = &txn.transaction {
-            match Signature::try_from(transaction.signature.clone()) {
-                Ok(signature) => Some(signature),
-                Err(e) => {
-                    logger.log(format!("Invalid signature: {:?}", e).red().to_string());
-                    return Err(format!("Invalid signature: {:?}", e));
-                }
+    let target_signature = if let Some(transaction) = &txn.transaction {
+        match Signature::try_from(transaction.signature.clone()) {
+            Ok(signature) => Some(signature),
+            Err(e) => {
+                logger.log(format!("Invalid signature: {:?}", e).red().to_string());
+                return Err(format!("Invalid signature: {:?}", e));
             }
-        } else {
-            None
-        };
-        
-        let inner_instructions = match &txn.transaction {
-            Some(txn_info) => match &txn_info.meta {
-                Some(meta) => meta.inner_instructions.clone(),
-                None => vec![],
-            },
+        }
+    } else {
+        None
+    };
+    
+    let inner_instructions = match &txn.transaction {
+        Some(txn_info) => match &txn_info.meta {
+            Some(meta) => meta.inner_instructions.clone(),
             None => vec![],
-        };
-
-        if !inner_instructions.is_empty() {
-            let cpi_log_data = inner_instructions
-                .iter()
-                .flat_map(|inner| &inner.instructions)
-                .find(|ix| ix.data.len() == 368 || ix.data.len() == 266 || ix.data.len() == 270  || ix.data.len() == 146 || ix.data.len() == 170 || ix.data.len() == 138 )
-                .map(|ix| ix.data.clone());
+        },
+        None => vec![],
+    };
 
-            if let Some(data) = cpi_log_data {
-                let config = config.clone();
-                let logger = logger.clone();
-                let txn = txn.clone();
-                tokio::spawn(async move {
-                    if let Some(parsed_data) = crate::processor::transaction_parser::parse_transaction_data(&txn, &data) {
-                        if parsed_data.mint != "So11111111111111111111111111111111111111112" {
-                            // SNIPER BOT: Handle target wallet transactions differently
-                            let _ = handle_sniper_bot_logic(parsed_data, config, target_signature, &txn, &logger).await;
-                        }
+    if !inner_instructions.is_empty() {
+        // Swaps are attributed through account keys, which may live in lookup tables
+        transaction_parser::prefetch_lookup_tables(&config.app_state.rpc_nonblocking_client, txn).await;
+        crate::library::parser_fixtures::capture(txn);
+        for parsed_data in collect_swaps(txn, &inner_instructions) {
+            let config = config.clone();
+            let logger = logger.clone();
+            let txn = txn.clone();
+            tokio::spawn(async move {
+                if let Some(signer) = extract_signer_from_transaction(&txn) {
+                    let mut exported = parsed_data.clone();
+                    exported.slot = txn.slot;
+                    if let Some(sig) = target_signature {
+                        exported.signature = sig.to_string();
                     }
-                });
-            }
+                    if let Some(normalized) = transaction_parser::ParsedData::from_trade_info(&exported, &signer) {
+                        crate::library::event_export::publish(&normalized);
+                        market_regime::MarketRegime::shared().observe(&normalized);
+                    }
+                }
+                if parsed_data.mint != "So11111111111111111111111111111111111111112" {
+                    // SNIPER BOT: Handle target wallet transactions differently
+                    let _ = handle_sniper_bot_logic(parsed_data, config, target_signature, &txn, &logger).await;
+                }
+            });
         }
+
+        // Liquidity events (pool creation, deposits, withdrawals) are exported alongside swaps
+        publish_liquidity_events(txn);
     }
     
     Ok(())  
@@ -3489,15 +3811,29 @@ async fn handle_sniper_bot_logic(
                 
                 // Handle buy transactions from target wallets
                 if parsed_data.is_buy {
-                    return handle_target_wallet_buy(parsed_data, config, logger, signer).await;
+                    let target_balance = txn.transaction.as_ref()
+                        .and_then(|info| info.meta.as_ref())
+                        .and_then(|meta| copy_guard::post_token_balance(meta, &signer, &parsed_data.mint));
+                    return handle_target_wallet_buy(parsed_data, config, logger, signer, target_balance).await;
                 } else {
                     // Handle sell transactions from target wallets
-                    return handle_target_wallet_sell(parsed_data, config, logger).await;
+                    let exit_fraction = match mirrored_exit_fraction(txn, &signer, &parsed_data.mint, logger) {
+                        Some(fraction) => fraction,
+                        None => return Ok(()),
+                    };
+                    return handle_target_wallet_sell(parsed_data, config, logger, exit_fraction).await;
                 }
             }
         }
     }
     
+    // Tokens the bot sold or skipped are watched for a dip to re-enter
+    if !BOUGHT_TOKEN_LIST.contains_key(&parsed_data.mint) {
+        if let Some(check) = dip_buy::DipBuy::shared().observe(&parsed_data.mint, parsed_data.price as f64, market_regime::risk_multiplier()) {
+            tokio::spawn(execute_dip_reentry(check, parsed_data.clone(), config.clone(), logger.clone()));
+        }
+    }
+
     // Handle other transactions (non-target wallets) - check if token is in focus list for volume-based buying
     handle_volume_based_buying(parsed_data, config, logger).await
 }
@@ -3508,8 +3844,20 @@ async fn handle_target_wallet_buy(
     config: Arc<SniperConfig>,
     logger: &Logger,
     whale_wallet: String,
+    target_balance: Option<u64>,
 ) -> Result<(), String> {
     let mint = parsed_data.mint.clone();
+
+    // Optional sanity window before a new token is copied
+    if !FOCUS_TOKEN_LIST.contains_key(&mint) {
+        if let (Ok(target), Ok(mint_pubkey)) = (Pubkey::from_str(&whale_wallet), Pubkey::from_str(&mint)) {
+            let guard = copy_guard::CopyGuard::from_env(config.app_state.rpc_nonblocking_client.clone());
+            if let Err(reason) = guard.vet(&target, &mint_pubkey, target_balance).await {
+                logger.log(format!("🛡️ Not copying {}'s buy of {}: {}", whale_wallet, mint, reason).yellow().to_string());
+                return Ok(());
+            }
+        }
+    }
     
     // Determine protocol based on instruction type
     let protocol = match parsed_data.dex_type {
@@ -3549,6 +3897,12 @@ async fn handle_target_wallet_buy(
         };
         
         FOCUS_TOKEN_LIST.insert(mint.clone(), focus_info);
+
+        // Anchor the initial price at the token's recent trades, not at this first sighting
+        crate::processor::price_backfill::start_focus_backfill(
+            mint.clone(),
+            config.app_state.rpc_nonblocking_client.clone(),
+        );
         
         // Start price monitoring for this token
         start_price_monitoring(mint.clone(), config.clone(), logger).await?;
@@ -3584,8 +3938,17 @@ async fn handle_target_wallet_sell(
     parsed_data: transaction_parser::TradeInfoFromToken,
     config: Arc<SniperConfig>,
     logger: &Logger,
+    exit_fraction: f64,
 ) -> Result<(), String> {
     let mint = parsed_data.mint.clone();
+
+    // A partial exit keeps the position and its monitoring
+    if exit_fraction < 1.0 {
+        if BOUGHT_TOKEN_LIST.contains_key(&mint) {
+            tokio::spawn(execute_partial_copy_sell(config.app_state.clone(), mint, exit_fraction, logger.clone()));
+        }
+        return Ok(());
+    }
     
     // Check if we own this token and execute emergency sell
     if let Some(_token_info) = BOUGHT_TOKEN_LIST.get(&mint) {
@@ -3644,6 +4007,53 @@ async fn handle_target_wallet_sell(
     Ok(())
 }
 
+/// Share of our position to sell when a target wallet sells `mint`, following COPY_EXIT_MODE;
+/// None when the target's sell is not mirrored
+fn mirrored_exit_fraction(txn: &SubscribeUpdateTransaction, target: &str, mint: &str, logger: &Logger) -> Option<f64> {
+    let sold = txn.transaction.as_ref()
+        .and_then(|info| info.meta.as_ref())
+        .and_then(|meta| copy_exit::sold_fraction(meta, target, mint));
+    let fraction = copy_exit::CopyExitConfig::from_env().sell_fraction(target, sold);
+    match (sold, fraction) {
+        (_, None) => logger.log(format!(
+            "🎯 Target {} sell of {} is not mirrored ({})",
+            target, mint, sold.map(|s| format!("sold {:.1}% of their position", s * 100.0)).unwrap_or("exit mode none".to_string())
+        ).blue().to_string()),
+        (Some(sold), Some(fraction)) => logger.log(format!(
+            "🎯 Target {} sold {:.1}% of their {} - mirroring {:.1}% of ours",
+            target, sold * 100.0, mint, fraction * 100.0
+        ).purple().to_string()),
+        (None, Some(_)) => {}
+    }
+    fraction
+}
+
+/// Sell `fraction` of a held token to mirror a target's partial exit; the position stays tracked
+async fn execute_partial_copy_sell(app_state: AppState, mint: String, fraction: f64, logger: Logger) {
+    let config = crate::common::config::Config::get().await;
+    let selling_engine = crate::processor::selling_strategy::SellingEngine::new(
+        app_state.into(),
+        Arc::new(config.swap_config.clone()),
+        crate::processor::selling_strategy::SellingConfig::set_from_env(),
+    );
+    drop(config);
+
+    match selling_engine.unified_partial_sell(&mint, fraction, None, None).await {
+        Ok(signature) => {
+            if let Some(mut token_info) = BOUGHT_TOKEN_LIST.get_mut(&mint) {
+                token_info.current_amount *= 1.0 - fraction;
+            }
+            logger.log(format!(
+                "✅ Mirrored partial exit: sold {:.1}% of {} with signature: {}",
+                fraction * 100.0, mint, signature
+            ).green().to_string());
+        },
+        Err(e) => {
+            logger.log(format!("❌ Failed to mirror partial exit for token {}: {}", mint, e).red().to_string());
+        }
+    }
+}
+
 /// SNIPER BOT: Handle volume-based buying decisions
 async fn handle_volume_based_buying(
     parsed_data: transaction_parser::TradeInfoFromToken,
@@ -3653,7 +4063,8 @@ async fn handle_volume_based_buying(
     let mint = parsed_data.mint.clone();
     // Read sniper trigger config once (avoid awaiting while holding map guard)
     let cfg_guard = crate::common::config::Config::get().await;
-    let trigger_size = cfg_guard.focus_trigger_sol.max(1.0);
+    // A cold market asks for a proportionally larger trigger buy
+    let trigger_size = cfg_guard.focus_trigger_sol.sol().max(1.0) / market_regime::risk_multiplier();
     
     // Check if token is in focus list
     if let Some(mut focus_info) = FOCUS_TOKEN_LIST.get_mut(&mint) {
@@ -3742,7 +4153,7 @@ async fn start_price_monitoring(
                     // TODO: In real implementation, fetch actual price from Jupiter API or DEX
                     // Read config before locking map
                     let cfg = crate::common::config::Config::get().await;
-                    let price_drop_threshold = cfg.focus_drop_threshold_pct; // fraction e.g. 0.15
+                    let price_drop_threshold = cfg.focus_drop_threshold.fraction();
                     drop(cfg);
                     if let Some(mut focus_info) = FOCUS_TOKEN_LIST.get_mut(&mint_clone) {
                         // Update last seen time
@@ -3806,9 +4217,10 @@ async fn execute_sniper_buy(
         return Ok(());
     }
     
-    // Check counter limit
+    // Check counter limit; a skipped signal can still be re-entered on a later dip
     let active_tokens_count = TOKEN_TRACKING.len();
     if active_tokens_count >= config.counter_limit as usize {
+        dip_buy::start_watch(&mint, dip_buy::WatchReason::Skipped, config.app_state.rpc_nonblocking_client.clone());
         return Ok(());
     }
     
@@ -3817,6 +4229,54 @@ async fn execute_sniper_buy(
         return Ok(());
     }
     
+    buy_and_start_selling(parsed_data, config, protocol, logger).await
+}
+
+/// SNIPER BOT: Re-enter a sold or skipped token once its dip passed the holder check
+async fn execute_dip_reentry(
+    check: dip_buy::DipCheck,
+    parsed_data: transaction_parser::TradeInfoFromToken,
+    config: Arc<SniperConfig>,
+    logger: Logger,
+) {
+    let holders = dip_buy::RpcHolderCount::new(config.app_state.rpc_nonblocking_client.clone());
+    let signal = match dip_buy::DipBuy::shared().evaluate(check, &holders).await {
+        dip_buy::DipDecision::Reenter(signal) => signal,
+        dip_buy::DipDecision::Rejected(reason) => {
+            logger.log(format!("📉 No re-entry into {}: {}", parsed_data.mint, reason).yellow().to_string());
+            return;
+        }
+    };
+    logger.log(format!(
+        "📉 DIP RE-ENTRY #{}: {} is {:.1}% below its peak, holders {} -> {}",
+        signal.reentry, signal.mint, signal.drop_pct, signal.holders_before, signal.holders_now
+    ).green().bold().to_string());
+
+    if BOUGHT_TOKEN_LIST.contains_key(&signal.mint) || TOKEN_TRACKING.len() >= config.counter_limit as usize {
+        return;
+    }
+    if !allow_reentry(&signal.mint, signal.reentry) {
+        logger.log(format!("📉 Re-entry #{} into {} is past DIP_BUY_MAX_REENTRIES", signal.reentry, signal.mint).yellow().to_string());
+        return;
+    }
+    let protocol = match parsed_data.dex_type {
+        transaction_parser::DexType::PumpSwap => SwapProtocol::PumpSwap,
+        transaction_parser::DexType::PumpFun => SwapProtocol::PumpFun,
+        transaction_parser::DexType::RaydiumLaunchpad => SwapProtocol::RaydiumLaunchpad,
+        _ => config.protocol_preference.clone(),
+    };
+    let _ = buy_and_start_selling(parsed_data, config, protocol, &logger).await;
+}
+
+/// SNIPER BOT: Buy and hand the position to the selling strategy
+async fn buy_and_start_selling(
+    parsed_data: transaction_parser::TradeInfoFromToken,
+    config: Arc<SniperConfig>,
+    protocol: SwapProtocol,
+    logger: &Logger,
+) -> Result<(), String> {
+    let mint = parsed_data.mint.clone();
+
     // Execute buy using existing logic
     match execute_buy(
         parsed_data.clone(),
@@ -3847,7 +4307,7 @@ async fn execute_sniper_buy(
         },
         Err(e) => {
             logger.log(format!("❌ Sniper buy failed for token {}: {}", mint, e).red().to_string());
-            Err(e)
+            Err(e.to_string())
         }
     }
 }
@@ -3874,8 +4334,12 @@ async fn handle_parsed_data_for_selling(
                     signer, parsed_data.mint, parsed_data.sol_change.abs()
                 ).purple().bold().to_string());
                 
-                // Check if we own this token
-                if let Some(mut token_info) = BOUGHT_TOKEN_LIST.get_mut(&parsed_data.mint) {
+                let exit_fraction = mirrored_exit_fraction(txn, &signer, &parsed_data.mint, logger);
+                if !BOUGHT_TOKEN_LIST.contains_key(&parsed_data.mint) {
+                    logger.log(format!("🎯 Target wallet selling {} but we don't own this token", parsed_data.mint).blue().to_string());
+                } else if let Some(fraction) = exit_fraction.filter(|fraction| *fraction < 1.0) {
+                    tokio::spawn(execute_partial_copy_sell(config.app_state.clone(), parsed_data.mint.clone(), fraction, logger.clone()));
+                } else if let (Some(_), Some(mut token_info)) = (exit_fraction, BOUGHT_TOKEN_LIST.get_mut(&parsed_data.mint)) {
                     logger.log(format!(
                         "🚨 We own token {} that target wallet is selling - executing IMMEDIATE COPY SELL",
                         parsed_data.mint
@@ -3916,8 +4380,6 @@ async fn handle_parsed_data_for_selling(
                     });
                     
                     logger.log(format!("🚀 Target wallet copy sell task spawned for token: {}, continuing main flow", parsed_data.mint).cyan().to_string());
-                } else {
-                    logger.log(format!("🎯 Target wallet selling {} but we don't own this token", parsed_data.mint).blue().to_string());
                 }
             }
         }
@@ -4407,26 +4869,33 @@ async fn process_selling(
                 }
             }
 
-            let cpi_log_data = inner_instructions
-            .iter()
-            .flat_map(|inner| &inner.instructions)
-            .find(|ix| ix.data.len() == 368 || ix.data.len() == 266 || ix.data.len() == 270  || ix.data.len() == 146 || ix.data.len() == 170 || ix.data.len() == 138)
-            .map(|ix| ix.data.clone());
-
-           
-            if let Some(data) = cpi_log_data {
+            // Swaps are attributed through account keys, which may live in lookup tables
+            transaction_parser::prefetch_lookup_tables(&config.app_state.rpc_nonblocking_client, txn).await;
+            crate::library::parser_fixtures::capture(txn);
+            for parsed_data in collect_swaps(txn, &inner_instructions) {
                 let config = config.clone();
                 let logger = logger.clone();
                 let txn = txn.clone();  // Clone the transaction data
                 let target_signature_clone = target_signature; // Clone the signature
                 tokio::spawn(async move {
-                    if let Some(parsed_data) = crate::processor::transaction_parser::parse_transaction_data(&txn, &data) {
-                        if parsed_data.mint != "So11111111111111111111111111111111111111112" {
-                        let _ =  handle_parsed_data_for_selling(parsed_data, config, &txn, target_signature_clone, &logger).await;
+                    if let Some(signer) = extract_signer_from_transaction(&txn) {
+                        let mut exported = parsed_data.clone();
+                        exported.slot = txn.slot;
+                        if let Some(sig) = target_signature {
+                            exported.signature = sig.to_string();
                         }
+                        if let Some(normalized) = transaction_parser::ParsedData::from_trade_info(&exported, &signer) {
+                            crate::library::event_export::publish(&normalized);
+                            market_regime::MarketRegime::shared().observe(&normalized);
+                        }
+                    }
+                    if parsed_data.mint != "So11111111111111111111111111111111111111112" {
+                    let _ =  handle_parsed_data_for_selling(parsed_data, config, &txn, target_signature_clone, &logger).await;
                     }
                 });
             }
+
+            publish_liquidity_events(txn);
             
         }
     }
@@ -4569,7 +5038,7 @@ async fn handle_parsed_data_for_buying(
             Err(e) => {
                 logger.log(format!("Error executing buy: {}", e).red().to_string());
                 
-                Err(e) // Return the error from execute_buy
+                Err(e.to_string()) // Return the error from execute_buy
             },
             Ok(_) => {      
                 logger.log(format!("Processing time for buy transaction: {:?}", start_time.elapsed()).blue().to_string());
@@ -4680,6 +5149,16 @@ async fn verify_sell_transaction_and_cleanup(
     if is_fully_sold {
         let mut removed_systems = Vec::new();
         
+        // Report the position result net of fees, tips and rent
+        if let Some(info) = BOUGHT_TOKEN_LIST.get(token_mint) {
+            let costs = trade_costs::position_costs(token_mint);
+            logger.log(format!(
+                "Position {} closed - gross PnL: {:.2}%, net PnL: {:.2}% (costs {:.6} SOL over {} txs)",
+                token_mint, info.pnl_percentage, info.net_pnl_percentage(), costs.net_sol(), costs.transactions
+            ).cyan().to_string());
+        }
+        trade_costs::close_position(token_mint);
+        
         // Remove from BOUGHT_TOKEN_LIST
         if BOUGHT_TOKEN_LIST.remove(token_mint).is_some() {
             removed_systems.push("BOUGHT_TOKEN_LIST");
@@ -0,0 +1,388 @@
+/*!
+# SOL Balance Monitor
+
+Checks the SOL balance of every wallet that pays for trades: the signing wallet, the wallets
+registered in the keystore and any listed in `SOL_BALANCE_WALLETS`. A wallet dropping below its
+minimum is alerted once, and alerted again when it recovers.
+
+With top-ups enabled, a low wallet is refilled up to `SOL_TOPUP_TARGET` by a signed transfer
+from the treasury wallet, sent through the RPC node (the transfer carries no relay tip) and
+reported once it is confirmed. Refills of one wallet are at least `TOPUP_COOLDOWN_SECS` apart (the
+previous transfer may not have landed yet), and the treasury sends at most
+`SOL_TOPUP_MAX_PER_DAY` SOL per UTC day. No refill is sent while trading is paused by the
+operator or another instance holds the trading lock; low wallets are still alerted.
+
+A read-only process (`TRADING_ENABLED=false`) loads no wallet, so the monitor does not start.
+
+## Environment Variables
+
+- `SOL_BALANCE_MONITOR_ENABLED`: Check wallet SOL balances (default: `true`)
+- `SOL_BALANCE_CHECK_SECS`: Interval between checks (default: `60`)
+- `SOL_BALANCE_MIN`: Balance (SOL) under which a wallet counts as low (default: `0.1`)
+- `SOL_BALANCE_WALLETS`: More wallets to watch as `<wallet>[:<min SOL>]`, comma separated
+- `SOL_TOPUP_ENABLED`: Refill low wallets from the treasury (default: `false`)
+- `SOL_TOPUP_TARGET`: Balance (SOL) a refill brings the wallet to (default: `0.5`)
+- `SOL_TOPUP_MAX_PER_DAY`: Most SOL the treasury sends per day (default: `2.0`)
+- `SOL_TREASURY_WALLET`: Keystore wallet that pays refills, unlocked with `KEYSTORE_PASSPHRASE`
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::native_token::{lamports_to_sol, sol_to_lamports};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::signature::{Keypair, Signer};
+use anchor_client::solana_sdk::system_instruction;
+use anchor_client::solana_sdk::transaction::Transaction;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use colored::Colorize;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::common::config::AppState;
+use crate::common::logger::Logger;
+use crate::error::BotError;
+use crate::library::audit_log::{self, AuditAction};
+use crate::library::keystore;
+use crate::library::rpc_api::{wait_for_confirmation, RpcApi};
+
+/// Minimum time between two refills of the same wallet
+const TOPUP_COOLDOWN_SECS: i64 = 300;
+
+#[derive(Clone, Debug)]
+pub struct SolBalanceConfig {
+    pub check_secs: u64,
+    pub min_sol: f64,
+    /// Extra wallets and their own minimum, if any
+    pub wallets: Vec<(Pubkey, Option<f64>)>,
+    pub topup_enabled: bool,
+    pub topup_target_sol: f64,
+    pub topup_max_per_day_sol: f64,
+}
+
+impl Default for SolBalanceConfig {
+    fn default() -> Self {
+        Self {
+            check_secs: 60,
+            min_sol: 0.1,
+            wallets: Vec::new(),
+            topup_enabled: false,
+            topup_target_sol: 0.5,
+            topup_max_per_day_sol: 2.0,
+        }
+    }
+}
+
+impl SolBalanceConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            check_secs: var("SOL_BALANCE_CHECK_SECS").and_then(|v| v.parse().ok()).unwrap_or(defaults.check_secs),
+            min_sol: var("SOL_BALANCE_MIN").and_then(|v| v.parse().ok()).unwrap_or(defaults.min_sol),
+            wallets: var("SOL_BALANCE_WALLETS").map(|v| parse_wallets(&v)).unwrap_or(defaults.wallets),
+            topup_enabled: var("SOL_TOPUP_ENABLED")
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(defaults.topup_enabled),
+            topup_target_sol: var("SOL_TOPUP_TARGET").and_then(|v| v.parse().ok()).unwrap_or(defaults.topup_target_sol),
+            topup_max_per_day_sol: var("SOL_TOPUP_MAX_PER_DAY").and_then(|v| v.parse().ok()).unwrap_or(defaults.topup_max_per_day_sol),
+        }
+    }
+}
+
+/// `<wallet>[:<min SOL>]` entries; malformed entries are skipped with a warning
+pub fn parse_wallets(value: &str) -> Vec<(Pubkey, Option<f64>)> {
+    let parse = |entry: &str| -> Option<(Pubkey, Option<f64>)> {
+        let (wallet, min) = match entry.split_once(':') {
+            Some((wallet, min)) => (wallet, Some(min.trim().parse::<f64>().ok()?)),
+            None => (entry, None),
+        };
+        Some((Pubkey::from_str(wallet.trim()).ok()?, min))
+    };
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .filter_map(|entry| {
+            let parsed = parse(entry);
+            if parsed.is_none() {
+                eprintln!("Ignoring SOL_BALANCE_WALLETS entry '{}'", entry);
+            }
+            parsed
+        })
+        .collect()
+}
+
+/// Reads wallet SOL balances
+#[async_trait]
+pub trait SolBalances: Send + Sync {
+    async fn lamports(&self, wallet: &Pubkey) -> Result<u64>;
+}
+
+#[async_trait]
+impl SolBalances for RpcClient {
+    async fn lamports(&self, wallet: &Pubkey) -> Result<u64> {
+        Ok(self.get_balance(wallet).await?)
+    }
+}
+
+/// What a check found for one wallet
+#[derive(Clone, Debug, PartialEq)]
+pub enum BalanceEvent {
+    Low { wallet: Pubkey, sol: f64, min_sol: f64 },
+    Refilled { wallet: Pubkey, sol: f64, amount_sol: f64, signature: String },
+    RefillFailed { wallet: Pubkey, sol: f64, reason: String },
+    Recovered { wallet: Pubkey, sol: f64 },
+}
+
+#[derive(Default)]
+struct MonitorState {
+    low: HashSet<Pubkey>,
+    last_refill: HashMap<Pubkey, DateTime<Utc>>,
+    day: Option<NaiveDate>,
+    sent_today_sol: f64,
+}
+
+pub struct SolBalanceMonitor {
+    config: SolBalanceConfig,
+    /// Watched wallets and their minimum balance (SOL)
+    wallets: Vec<(Pubkey, f64)>,
+    balances: Arc<dyn SolBalances>,
+    rpc_api: Arc<dyn RpcApi>,
+    treasury: Option<Arc<Keypair>>,
+    state: Mutex<MonitorState>,
+    clock: Arc<dyn Clock>,
+    /// Why refills are held back right now
+    paused: fn() -> Option<String>,
+}
+
+impl SolBalanceMonitor {
+    /// Watch `primary` (the signing wallet) and the configured wallets
+    pub fn new(
+        config: SolBalanceConfig,
+        primary: Vec<Pubkey>,
+        balances: Arc<dyn SolBalances>,
+        rpc_api: Arc<dyn RpcApi>,
+        treasury: Option<Arc<Keypair>>,
+    ) -> Self {
+        let mut wallets: Vec<(Pubkey, f64)> = Vec::new();
+        let listed = primary.into_iter().map(|w| (w, None)).chain(config.wallets.iter().copied());
+        for (wallet, min) in listed {
+            match wallets.iter_mut().find(|(known, _)| *known == wallet) {
+                Some(existing) => existing.1 = min.unwrap_or(existing.1),
+                None => wallets.push((wallet, min.unwrap_or(config.min_sol))),
+            }
+        }
+        Self {
+            config,
+            wallets,
+            balances,
+            rpc_api,
+            treasury,
+            state: Mutex::new(MonitorState::default()),
+            clock: system_clock(),
+            paused: refills_paused_reason,
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_pause_check(mut self, paused: fn() -> Option<String>) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    pub fn wallets(&self) -> &[(Pubkey, f64)] {
+        &self.wallets
+    }
+
+    /// Check every wallet once, refilling where allowed
+    pub async fn check(&self) -> Vec<BalanceEvent> {
+        let mut events = Vec::new();
+        for (wallet, min_sol) in &self.wallets {
+            let sol = match self.balances.lamports(wallet).await {
+                Ok(lamports) => lamports_to_sol(lamports),
+                Err(e) => {
+                    eprintln!("Failed to read the SOL balance of {}: {}", wallet, e);
+                    continue;
+                }
+            };
+
+            if sol >= *min_sol {
+                if self.state.lock().await.low.remove(wallet) {
+                    events.push(BalanceEvent::Recovered { wallet: *wallet, sol });
+                }
+                continue;
+            }
+            if self.state.lock().await.low.insert(*wallet) {
+                events.push(BalanceEvent::Low { wallet: *wallet, sol, min_sol: *min_sol });
+            }
+            if let Some(event) = self.refill(wallet, sol).await {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    async fn refill(&self, wallet: &Pubkey, sol: f64) -> Option<BalanceEvent> {
+        if !self.config.topup_enabled {
+            return None;
+        }
+        let treasury = self.treasury.as_ref()?;
+        if treasury.pubkey() == *wallet {
+            return None;
+        }
+        // Only the instance holding the trading lock moves funds, and none while paused
+        if (self.paused)().is_some() {
+            return None;
+        }
+        let now = self.clock.now();
+        let amount_sol = {
+            let mut state = self.state.lock().await;
+            if state.day != Some(now.date_naive()) {
+                state.day = Some(now.date_naive());
+                state.sent_today_sol = 0.0;
+            }
+            if state.last_refill.get(wallet).is_some_and(|at| (now - *at).num_seconds() < TOPUP_COOLDOWN_SECS) {
+                return None;
+            }
+            let wanted = self.config.topup_target_sol - sol;
+            let allowed = self.config.topup_max_per_day_sol - state.sent_today_sol;
+            if wanted <= 0.0 {
+                return None;
+            }
+            if allowed < wanted {
+                return Some(BalanceEvent::RefillFailed {
+                    wallet: *wallet,
+                    sol,
+                    reason: format!("daily top-up limit of {} SOL reached", self.config.topup_max_per_day_sol),
+                });
+            }
+            // Reserve the amount before sending so concurrent checks cannot exceed the limit
+            state.sent_today_sol += wanted;
+            state.last_refill.insert(*wallet, now);
+            wanted
+        };
+
+        match self.send_transfer(treasury, wallet, amount_sol).await {
+            Ok(signature) => Some(BalanceEvent::Refilled { wallet: *wallet, sol, amount_sol, signature }),
+            // Sent but not confirmed yet: it may still land, so the amount stays reserved and the
+            // cooldown keeps it from being sent twice
+            Err(BotError::NotConfirmed(signature)) => Some(BalanceEvent::RefillFailed {
+                wallet: *wallet,
+                sol,
+                reason: format!("transfer {} not confirmed yet", signature),
+            }),
+            Err(e) => {
+                let mut state = self.state.lock().await;
+                state.sent_today_sol -= amount_sol;
+                state.last_refill.remove(wallet);
+                Some(BalanceEvent::RefillFailed { wallet: *wallet, sol, reason: e.to_string() })
+            }
+        }
+    }
+
+    /// Send the transfer and wait for it to confirm
+    async fn send_transfer(&self, treasury: &Keypair, wallet: &Pubkey, amount_sol: f64) -> Result<String, BotError> {
+        let blockhash = self.rpc_api.latest_blockhash().await.ok_or(BotError::BlockhashUnavailable)?;
+        let transfer = system_instruction::transfer(&treasury.pubkey(), wallet, sol_to_lamports(amount_sol));
+        let transaction = Transaction::new_signed_with_payer(&[transfer], Some(&treasury.pubkey()), &[treasury], blockhash);
+        let signature = self.rpc_api.send_plain_transaction(&transaction).await?;
+        audit_log::record(
+            AuditAction::TradeSubmitted,
+            "sol_topup",
+            serde_json::json!({
+                "signer": treasury.pubkey().to_string(),
+                "signatures": [signature.to_string()],
+                "to": wallet.to_string(),
+                "amount_sol": amount_sol,
+            }),
+        );
+        let logger = Logger::new("[SOL-BALANCE] => ".bright_yellow().to_string());
+        wait_for_confirmation(self.rpc_api.as_ref(), &signature, &logger).await?;
+        Ok(signature.to_string())
+    }
+}
+
+/// Why refills are held back right now: another instance holds the trading lock, or the
+/// operator paused trading
+pub fn refills_paused_reason() -> Option<String> {
+    crate::library::instance_lock::trading_paused_reason().or_else(crate::processor::trading_control::buys_paused_reason)
+}
+
+/// Treasury keypair from the keystore, None when refills are not configured or trading is locked down
+fn load_treasury(logger: &Logger) -> Option<Arc<Keypair>> {
+    let address = std::env::var("SOL_TREASURY_WALLET").ok().filter(|a| !a.trim().is_empty())?;
+    if let Err(e) = crate::library::trading_lockdown::ensure_trading_enabled() {
+        logger.log(format!("{}, treasury not unlocked and top-ups disabled", e).yellow().to_string());
+        return None;
+    }
+    let passphrase = std::env::var("KEYSTORE_PASSPHRASE").unwrap_or_default();
+    match keystore::load(&keystore::keystore_dir(), address.trim()).and_then(|entry| keystore::decrypt(&entry, &passphrase)) {
+        Ok(keypair) => {
+            audit_log::record(AuditAction::KeyUsed, "treasury", serde_json::json!({ "pubkey": keypair.pubkey().to_string(), "use": "load" }));
+            Some(Arc::new(keypair))
+        }
+        Err(e) => {
+            logger.log(format!("Treasury wallet unavailable, top-ups disabled: {}", e).red().to_string());
+            None
+        }
+    }
+}
+
+/// Whether the treasury keypair can be unlocked for top-ups right now
+pub fn treasury_available() -> bool {
+    load_treasury(&Logger::new("[SOL-BALANCE] => ".bright_yellow().to_string())).is_some()
+}
+
+/// Start the SOL balance monitor unless disabled via SOL_BALANCE_MONITOR_ENABLED=false or trading
+/// is locked down
+pub fn start_sol_balance_monitor(app_state: Arc<AppState>, cancel_token: CancellationToken) -> Option<tokio::task::JoinHandle<()>> {
+    let enabled = std::env::var("SOL_BALANCE_MONITOR_ENABLED")
+        .ok()
+        .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+        .unwrap_or(true);
+    if !enabled || !crate::library::trading_lockdown::trading_enabled() {
+        return None;
+    }
+
+    let logger = Logger::new("[SOL-BALANCE] => ".bright_yellow().to_string());
+    let config = SolBalanceConfig::from_env();
+    let mut primary: Vec<Pubkey> = app_state.wallet.try_pubkey().into_iter().collect();
+    primary.extend(keystore::registered_wallets().iter().filter_map(|w| Pubkey::from_str(w).ok()));
+    let treasury = if config.topup_enabled { load_treasury(&logger) } else { None };
+    let interval = std::time::Duration::from_secs(config.check_secs.max(5));
+    let monitor = SolBalanceMonitor::new(
+        config,
+        primary,
+        app_state.rpc_nonblocking_client.clone(),
+        app_state.rpc_api.clone(),
+        treasury,
+    );
+    let telegram = crate::processor::telegram_alerts::init_from_env().ok().flatten().map(Arc::new);
+    logger.log(format!("Watching the SOL balance of {} wallets", monitor.wallets().len()));
+
+    Some(tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = ticker.tick() => {
+                    for event in monitor.check().await {
+                        logger.log(format!("{:?}", event).yellow().to_string());
+                        if let Some(telegram) = &telegram {
+                            let _ = telegram.alert_sol_balance(&event).await;
+                        }
+                    }
+                }
+            }
+        }
+    }))
+}
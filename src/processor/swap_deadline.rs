@@ -0,0 +1,136 @@
+/*!
+# Swap Deadline
+
+A buy copies a trade seen on the stream. By the time its transaction is built and about to be
+submitted the price may have moved on, so just before sending the signal's age is checked: when
+it is older than `SWAP_DEADLINE_SLOTS` slots (against the RPC slot extrapolated by the slot lag
+monitor) or `SWAP_DEADLINE_MS` milliseconds (against the trade's on-chain timestamp), the buy is
+abandoned and counted as a stale signal (`stale_signals_total` on `/metrics`).
+
+Sells are never abandoned: an old exit signal is still a reason to leave the position.
+
+## Environment Variables
+
+- `SWAP_DEADLINE_SLOTS`: Oldest signal bought, in slots; `0` disables (default: `10`)
+- `SWAP_DEADLINE_MS`: Oldest signal bought, in milliseconds; on-chain timestamps have second
+  precision, so keep this above `1000`; `0` disables (default: `5000`)
+*/
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use lazy_static::lazy_static;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::common::config::{threshold_or, Slots};
+use crate::library::slot_lag;
+
+lazy_static! {
+    static ref SHARED_DEADLINE: Arc<SwapDeadline> = Arc::new(SwapDeadline::new(DeadlineConfig::from_env()));
+}
+
+#[derive(Clone, Debug)]
+pub struct DeadlineConfig {
+    /// 0 disables
+    pub max_age_slots: u64,
+    /// 0 disables
+    pub max_age_ms: u64,
+}
+
+impl Default for DeadlineConfig {
+    fn default() -> Self {
+        Self { max_age_slots: 10, max_age_ms: 5_000 }
+    }
+}
+
+impl DeadlineConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            // A blockhash expires after 150 slots, a later deadline could never be hit
+            max_age_slots: threshold_or("SWAP_DEADLINE_SLOTS", Slots::new(defaults.max_age_slots), Slots::from_str, Slots::new(0)..=Slots::new(150)).get(),
+            max_age_ms: var("SWAP_DEADLINE_MS").and_then(|v| v.parse().ok()).unwrap_or(defaults.max_age_ms),
+        }
+    }
+}
+
+/// Why a signal was abandoned
+#[derive(Clone, Debug, PartialEq)]
+pub enum StaleSignal {
+    Slots { age: u64, max: u64 },
+    Millis { age: u64, max: u64 },
+}
+
+impl std::fmt::Display for StaleSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StaleSignal::Slots { age, max } => write!(f, "stale signal: {} slots old (deadline {} slots)", age, max),
+            StaleSignal::Millis { age, max } => write!(f, "stale signal: {} ms old (deadline {} ms)", age, max),
+        }
+    }
+}
+
+pub struct SwapDeadline {
+    config: DeadlineConfig,
+    clock: Arc<dyn Clock>,
+    stale: AtomicU64,
+}
+
+impl SwapDeadline {
+    pub fn new(config: DeadlineConfig) -> Self {
+        Self { config, clock: system_clock(), stale: AtomicU64::new(0) }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn shared() -> Arc<SwapDeadline> {
+        SHARED_DEADLINE.clone()
+    }
+
+    /// Check a signal from `signal_slot` with on-chain `signal_timestamp` (unix seconds) against
+    /// `current_slot`; unknown values (0 or None) are not checked. Stale signals are counted.
+    pub fn check(&self, signal_slot: u64, signal_timestamp: u64, current_slot: Option<u64>) -> Result<(), StaleSignal> {
+        let stale = self.evaluate(signal_slot, signal_timestamp, current_slot);
+        if stale.is_some() {
+            self.stale.fetch_add(1, Ordering::Relaxed);
+        }
+        stale.map_or(Ok(()), Err)
+    }
+
+    fn evaluate(&self, signal_slot: u64, signal_timestamp: u64, current_slot: Option<u64>) -> Option<StaleSignal> {
+        if self.config.max_age_slots > 0 && signal_slot > 0 {
+            if let Some(current) = current_slot {
+                let age = current.saturating_sub(signal_slot);
+                if age > self.config.max_age_slots {
+                    return Some(StaleSignal::Slots { age, max: self.config.max_age_slots });
+                }
+            }
+        }
+        if self.config.max_age_ms > 0 && signal_timestamp > 0 {
+            let age = (self.clock.now().timestamp_millis() - signal_timestamp as i64 * 1000).max(0) as u64;
+            if age > self.config.max_age_ms {
+                return Some(StaleSignal::Millis { age, max: self.config.max_age_ms });
+            }
+        }
+        None
+    }
+
+    /// Signals abandoned so far
+    pub fn stale_signals(&self) -> u64 {
+        self.stale.load(Ordering::Relaxed)
+    }
+}
+
+/// Check a buy signal against the process-wide deadline and the monitored RPC slot
+pub fn check_signal(signal_slot: u64, signal_timestamp: u64) -> Result<(), StaleSignal> {
+    SwapDeadline::shared().check(signal_slot, signal_timestamp, slot_lag::status().rpc_slot)
+}
+
+/// Stale signals abandoned by the process-wide deadline
+pub fn stale_signals() -> u64 {
+    SwapDeadline::shared().stale_signals()
+}
@@ -1,19 +1,233 @@
 use anyhow::Result;
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use colored::Colorize;
 use solana_sdk::pubkey::Pubkey;
+use std::path::Path;
 use std::sync::Arc;
-use teloxide::{prelude::*, Bot};
-use tokio::sync::RwLock;
+use teloxide::{
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, InputFile},
+    Bot,
+};
+use tokio::sync::{broadcast, RwLock};
+use tokio_util::sync::CancellationToken;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::common::explorer::{ExplorerLinks, TradeLinks};
+use crate::common::quote_asset;
+use crate::common::logger::Logger;
+use crate::error::{retry, BotError};
+use crate::library::circuit_breaker::{self, CircuitBreaker};
+use crate::library::rolling::VolumeTotals;
+use crate::processor::alert_feedback::{self, rating_callback};
+use crate::processor::alert_outbox::{AlertOutbox, OutboxEntry};
+use crate::processor::alert_throttle::{AlertThrottle, AlertThrottleConfig};
+#[cfg(feature = "trading")]
+use crate::processor::fill_check::FillReport;
+use crate::processor::insider_detection::{is_insider_launch, InsiderFinding};
+use crate::processor::lessons::{LessonAction, LessonSessions, Topic};
+use crate::processor::mute_list;
+use crate::processor::scam_lists;
+use crate::processor::opportunity_signal::OpportunitySignal;
+#[cfg(feature = "trading")]
+use crate::processor::profit_policy::{ProfitPolicy, ProfitSummary};
+#[cfg(feature = "trading")]
+use crate::processor::sol_balance_monitor::BalanceEvent;
+use crate::processor::transaction_parser::SwapType;
+use crate::processor::wallet_groups::GroupActivity;
+use crate::processor::wallet_positions::EntryComparison;
+
+lazy_static::lazy_static! {
+    /// Every alert emitted by the system, for API streaming and other in-process consumers
+    static ref ALERT_BROADCAST: broadcast::Sender<AlertRecord> = broadcast::channel(1024).0;
+}
+
+/// An alert as it was sent, tagged with its category
+#[derive(Clone, Debug)]
+pub struct AlertRecord {
+    pub category: String,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Subscribe to the live alert feed
+pub fn subscribe_alerts() -> broadcast::Receiver<AlertRecord> {
+    ALERT_BROADCAST.subscribe()
+}
+
+/// Callback data prefix of the "Watching" button; the token mint follows it
+pub const WATCH_CALLBACK_PREFIX: &str = "watch:";
+
+/// Inline button letting the user acknowledge an alert and request follow-ups on the token
+fn watch_keyboard(token_address: &Pubkey) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
+        "👀 Watching",
+        format!("{}{}", WATCH_CALLBACK_PREFIX, token_address),
+    )]])
+}
+
+/// The "Watching" button plus, when enabled, a row of one-tap trade links for the token
+fn opportunity_keyboard(token_address: &Pubkey, trade_links: Option<&TradeLinks>) -> InlineKeyboardMarkup {
+    let keyboard = watch_keyboard(token_address);
+    let buttons: Vec<InlineKeyboardButton> = trade_links
+        .map(|links| links.buttons(&token_address.to_string()))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(label, url)| reqwest::Url::parse(&url).ok().map(|url| InlineKeyboardButton::url(label, url)))
+        .collect();
+    if buttons.is_empty() {
+        keyboard
+    } else {
+        keyboard.append_row(buttons)
+    }
+}
+
+/// `keyboard` plus a "📚 Learn" button opening the lesson on `topic`, when lessons are enabled
+fn with_lesson(keyboard: InlineKeyboardMarkup, topic: Topic) -> InlineKeyboardMarkup {
+    if !LessonSessions::shared().enabled() {
+        return keyboard;
+    }
+    keyboard.append_row(vec![InlineKeyboardButton::callback(
+        format!("📚 Learn: {}", topic.title()),
+        LessonAction::Menu(topic).callback_data(),
+    )])
+}
+
+/// `keyboard` plus "👍 Useful" / "👎 Noise" buttons rating a `signal_type` alert, when enabled
+fn with_rating(keyboard: InlineKeyboardMarkup, signal_type: &str, token_address: &Pubkey) -> InlineKeyboardMarkup {
+    let (useful, noise) = match (
+        rating_callback(signal_type, token_address, true),
+        rating_callback(signal_type, token_address, false),
+    ) {
+        (Some(useful), Some(noise)) if alert_feedback::rating_buttons_enabled() => (useful, noise),
+        _ => return keyboard,
+    };
+    keyboard.append_row(vec![
+        InlineKeyboardButton::callback("👍 Useful", useful),
+        InlineKeyboardButton::callback("👎 Noise", noise),
+    ])
+}
+
+/// Warning line for launches tagged as insider-driven, empty otherwise
+fn insider_tag(token_address: &Pubkey) -> &'static str {
+    if is_insider_launch(token_address) {
+        "🕵️ **Likely insider-driven launch**: early buyers share the creator's funding\n"
+    } else {
+        ""
+    }
+}
+
+/// Line naming the community lists that flag a token or its creator, empty otherwise
+fn blacklist_tag(token_address: &Pubkey) -> String {
+    match scam_lists::flag_for(&token_address.to_string()) {
+        Some(entry) => format!("🚩 **Blacklisted**: {}\n", entry.describe()),
+        None => String::new(),
+    }
+}
+
+/// `sol` labeled in the asset the token trades against, e.g. `300.00 USDC (≈1.5 SOL)`
+fn quote_amount(token_address: &Pubkey, sol: f64) -> String {
+    quote_asset::for_mint(&token_address.to_string()).format_sol_amount(sol)
+}
+
+/// Alert line naming the stablecoin a token's pool is quoted in, empty for SOL pairs
+fn quote_tag(token_address: &Pubkey) -> String {
+    let quote = quote_asset::for_mint(&token_address.to_string());
+    if quote.is_stable() {
+        format!("💱 **Quote**: {} pair, amounts shown in SOL at ${:.2}/SOL\n", quote, quote_asset::sol_usd_price())
+    } else {
+        String::new()
+    }
+}
+
+/// Alert line for pump.fun bonding curve completion, empty for other DEXes
+fn format_curve_progress(progress: Option<f64>) -> String {
+    progress.map(|p| format!("🎓 **Bonding Curve**: {:.1}% complete\n", p)).unwrap_or_default()
+}
+
+/// Alert line putting a wallet's trade against its average entry, e.g. "selling at ~3.2x their
+/// average entry"
+fn format_entry_comparison(action: &str, entry: &EntryComparison) -> String {
+    let verb = if action == "BUY" { "buying" } else { "selling" };
+    format!(
+        "📐 **Vs Entry**: {} at ~{:.1}x their average entry ({:.10} over {} buy{})\n",
+        verb,
+        entry.multiple(),
+        entry.average_entry,
+        entry.buys,
+        if entry.buys == 1 { "" } else { "s" },
+    )
+}
+
+/// Volume with its buy/sell split, e.g. "12.50 SOL (buys 9.00 / sells 3.50)"
+pub fn format_volume(volume: &VolumeTotals) -> String {
+    format!("{:.2} SOL (buys {:.2} / sells {:.2})", volume.total(), volume.buy, volume.sell)
+}
+
+/// Destination of alert messages; Telegram in production, a recorder in tests
+#[async_trait]
+pub trait MessageSink: Send + Sync {
+    async fn send(&self, text: &str, keyboard: Option<InlineKeyboardMarkup>) -> Result<()>;
+
+    /// Send a file, e.g. a saved report
+    async fn send_document(&self, path: &Path, _caption: &str) -> Result<()> {
+        Err(anyhow::anyhow!("Cannot send {}: documents are not supported here", path.display()))
+    }
+}
+
+/// Sends alerts to a Telegram chat as Markdown
+pub struct TelegramSink {
+    bot: Bot,
+    chat_id: ChatId,
+}
+
+impl TelegramSink {
+    pub fn new(bot_token: String, chat_id: i64) -> Self {
+        Self {
+            bot: Bot::new(bot_token),
+            chat_id: ChatId(chat_id),
+        }
+    }
+}
+
+#[async_trait]
+impl MessageSink for TelegramSink {
+    async fn send(&self, text: &str, keyboard: Option<InlineKeyboardMarkup>) -> Result<()> {
+        let mut request = self.bot
+            .send_message(self.chat_id, text)
+            .parse_mode(teloxide::types::ParseMode::Markdown);
+        if let Some(keyboard) = keyboard {
+            request = request.reply_markup(keyboard);
+        }
+        request.send().await?;
+        Ok(())
+    }
+
+    async fn send_document(&self, path: &Path, caption: &str) -> Result<()> {
+        self.bot
+            .send_document(self.chat_id, InputFile::file(path.to_path_buf()))
+            .caption(caption)
+            .send()
+            .await?;
+        Ok(())
+    }
+}
 
 /// Educational Alert System for monitoring Solana tokens
 /// This module sends Telegram notifications for educational purposes only
 /// No actual trading is performed
 pub struct TelegramAlertSystem {
-    bot: Bot,
-    chat_id: ChatId,
+    sink: Arc<dyn MessageSink>,
+    breaker: Arc<CircuitBreaker>,
+    outbox: Arc<AlertOutbox>,
+    throttle: Arc<AlertThrottle>,
     enabled: bool,
     alert_settings: AlertSettings,
     rate_limiter: Arc<RwLock<RateLimiter>>,
+    clock: Arc<dyn Clock>,
+    links: ExplorerLinks,
+    trade_links: Option<TradeLinks>,
 }
 
 #[derive(Clone, Debug)]
@@ -55,18 +269,20 @@ impl Default for AlertSettings {
 struct RateLimiter {
     last_alert_times: std::collections::HashMap<String, DateTime<Utc>>,
     min_interval_seconds: i64,
+    clock: Arc<dyn Clock>,
 }
 
 impl RateLimiter {
-    fn new(min_interval_seconds: i64) -> Self {
+    fn new(min_interval_seconds: i64, clock: Arc<dyn Clock>) -> Self {
         Self {
             last_alert_times: std::collections::HashMap::new(),
             min_interval_seconds,
+            clock,
         }
     }
 
     fn can_send(&mut self, key: &str) -> bool {
-        let now = Utc::now();
+        let now = self.clock.now();
         if let Some(last_time) = self.last_alert_times.get(key) {
             if now.timestamp() - last_time.timestamp() < self.min_interval_seconds {
                 return false;
@@ -79,17 +295,67 @@ impl RateLimiter {
 
 impl TelegramAlertSystem {
     /// Create a new Telegram alert system for educational monitoring
+    /// Undeliverable alerts are queued in the shared persistent outbox.
     pub fn new(bot_token: String, chat_id: i64, enabled: bool) -> Result<Self> {
-        let bot = Bot::new(bot_token);
-        let chat_id = ChatId(chat_id);
+        Ok(Self::with_sink(Arc::new(TelegramSink::new(bot_token, chat_id)), enabled)
+            .with_outbox(AlertOutbox::shared())
+            .with_throttle(AlertThrottle::shared()))
+    }
 
-        Ok(Self {
-            bot,
-            chat_id,
+    /// Create an alert system delivering to any message sink, queueing undeliverable alerts
+    /// in memory
+    pub fn with_sink(sink: Arc<dyn MessageSink>, enabled: bool) -> Self {
+        let clock = system_clock();
+        Self {
+            sink,
+            breaker: circuit_breaker::breaker("telegram"),
+            outbox: Arc::new(AlertOutbox::in_memory(chrono::Duration::hours(1), 500)),
+            throttle: Arc::new(AlertThrottle::new(AlertThrottleConfig::default()).with_clock(clock.clone())),
             enabled,
             alert_settings: AlertSettings::default(),
-            rate_limiter: Arc::new(RwLock::new(RateLimiter::new(30))), // 30 seconds between similar alerts
-        })
+            rate_limiter: Arc::new(RwLock::new(RateLimiter::new(30, clock.clone()))), // 30 seconds between similar alerts
+            clock,
+            links: ExplorerLinks::from_env(),
+            trade_links: TradeLinks::from_env(),
+        }
+    }
+
+    /// Use a different time source for rate limiting, send pacing and alert timestamps
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.rate_limiter = Arc::new(RwLock::new(RateLimiter::new(30, clock.clone())));
+        self.throttle = Arc::new(AlertThrottle::new(AlertThrottleConfig::default()).with_clock(clock.clone()));
+        self.clock = clock;
+        self
+    }
+
+    /// Use a dedicated circuit breaker instead of the shared "telegram" one
+    pub fn with_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    pub fn with_outbox(mut self, outbox: Arc<AlertOutbox>) -> Self {
+        self.outbox = outbox;
+        self
+    }
+
+    /// Pace sends with this throttle instead of a private one
+    pub fn with_throttle(mut self, throttle: Arc<AlertThrottle>) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Link tokens, wallets and transactions with these settings instead of ALERT_EXPLORER
+    pub fn with_links(mut self, links: ExplorerLinks) -> Self {
+        self.links = links;
+        self
+    }
+
+    /// Show (or hide, with None) trade deep links on opportunity alerts regardless of
+    /// TRADE_DEEP_LINKS_ENABLED
+    pub fn with_trade_links(mut self, trade_links: Option<TradeLinks>) -> Self {
+        self.trade_links = trade_links;
+        self
     }
 
     /// Configure alert settings
@@ -104,7 +370,7 @@ impl TelegramAlertSystem {
         initial_liquidity: f64,
         dex: &str,
     ) -> Result<()> {
-        if !self.enabled || !self.alert_settings.alert_new_tokens {
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) || !self.alert_settings.alert_new_tokens {
             return Ok(());
         }
 
@@ -117,19 +383,25 @@ impl TelegramAlertSystem {
             "🚀 **NEW TOKEN DETECTED** (Educational Alert)\n\n\
             📍 **Token**: {}\n\
             📝 **Name**: {}\n\
-            💰 **Initial Liquidity**: {} SOL\n\
+            💰 **Initial Liquidity**: {}\n\
             🏪 **DEX**: {}\n\
-            🔗 **Address**: `{}`\n\n\
+            {}\
+            {}\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
             {}",
             token_name.as_ref().unwrap_or(&"Unknown".to_string()),
             token_name.unwrap_or("Unknown".to_string()),
-            initial_liquidity,
+            quote_amount(token_address, initial_liquidity),
             dex,
+            insider_tag(token_address),
+            blacklist_tag(token_address),
             token_address,
+            self.links.token(&token_address.to_string()),
             self.get_risk_warning()
         );
 
-        self.send_message(&message).await
+        self.send_message_with_keyboard("new_token", &message, Some(watch_keyboard(token_address))).await
     }
 
     /// Alert on target wallet activity (educational purposes only)
@@ -140,8 +412,11 @@ impl TelegramAlertSystem {
         token_name: Option<String>,
         amount_sol: f64,
         price: Option<f64>,
+        entry: Option<EntryComparison>,
+        bonding_curve_progress: Option<f64>,
+        signature: Option<&str>,
     ) -> Result<()> {
-        if !self.enabled || !self.alert_settings.alert_wallet_activity {
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) || !self.alert_settings.alert_wallet_activity {
             return Ok(());
         }
 
@@ -154,24 +429,32 @@ impl TelegramAlertSystem {
         let action_emoji = if action == "BUY" { "💚" } else { "💔" };
         let message = format!(
             "{} **WALLET ACTIVITY** (Educational Alert)\n\n\
-            👤 **Wallet**: `{}`\n\
+            👤 **Wallet**: {}\n\
             📊 **Action**: {}\n\
             🪙 **Token**: {}\n\
-            💵 **Amount**: {} SOL\n\
+            💵 **Amount**: {}\n\
+            {}\
             {}\
-            🔗 **Token Address**: `{}`\n\n\
+            {}\
+            🔗 **Token Address**: `{}`\n\
+            🔍 **Links**: {}\n\
+            {}\n\
             {}",
             action_emoji,
-            &wallet_address.to_string()[..8],
+            self.links.wallet(&wallet_address.to_string()),
             action,
             token_name.unwrap_or("Unknown".to_string()),
-            amount_sol,
+            quote_amount(token_address, amount_sol),
             price.map(|p| format!("💱 **Price**: ${:.6}\n", p)).unwrap_or_default(),
+            entry.map(|e| format_entry_comparison(action, &e)).unwrap_or_default(),
+            format_curve_progress(bonding_curve_progress),
             token_address,
+            self.links.token(&token_address.to_string()),
+            signature.map(|sig| format!("🧾 {}\n", self.links.transaction(sig))).unwrap_or_default(),
             self.get_educational_note(action)
         );
 
-        self.send_message(&message).await
+        self.send_message("wallet_activity", &message).await
     }
 
     /// Alert on significant price movements (educational purposes only)
@@ -180,9 +463,10 @@ impl TelegramAlertSystem {
         token_name: Option<String>,
         old_price: f64,
         new_price: f64,
-        volume_24h: Option<f64>,
+        volume_24h: Option<VolumeTotals>,
+        bonding_curve_progress: Option<f64>,
     ) -> Result<()> {
-        if !self.enabled || !self.alert_settings.alert_price_movements {
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) || !self.alert_settings.alert_price_movements {
             return Ok(());
         }
 
@@ -205,33 +489,273 @@ impl TelegramAlertSystem {
             💱 **New Price**: ${:.8}\n\
             📊 **Change**: {:.2}%\n\
             {}\
-            🔗 **Address**: `{}`\n\n\
+            {}\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
             {}",
             trend_emoji,
             token_name.unwrap_or("Unknown".to_string()),
             old_price,
             new_price,
             change_percentage,
-            volume_24h.map(|v| format!("📊 **24h Volume**: ${:.2}\n", v)).unwrap_or_default(),
+            volume_24h.map(|v| format!("📊 **24h Volume**: {}\n", format_volume(&v))).unwrap_or_default(),
+            format_curve_progress(bonding_curve_progress),
             token_address,
+            self.links.token(&token_address.to_string()),
             self.get_market_analysis_note(change_percentage)
         );
 
-        self.send_message(&message).await
+        let keyboard = with_rating(watch_keyboard(token_address), "Price Movement", token_address);
+        self.send_message_with_keyboard("price_movement", &message, Some(keyboard)).await
+    }
+
+    /// Alert when a pump.fun bonding curve is close to completion (educational purposes only)
+    pub async fn alert_graduation_imminent(&self,
+        token_address: &Pubkey,
+        token_name: Option<String>,
+        progress: f64,
+    ) -> Result<()> {
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) {
+            return Ok(());
+        }
+
+        let mut rate_limiter = self.rate_limiter.write().await;
+        if !rate_limiter.can_send(&format!("graduation_{}", token_address)) {
+            return Ok(());
+        }
+
+        let message = format!(
+            "🎓 **ABOUT TO GRADUATE** (Educational Alert)\n\n\
+            🪙 **Token**: {}\n\
+            📈 **Bonding Curve**: {:.1}% complete\n\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
+            📚 **Educational Note**: When the curve completes, liquidity migrates to PumpSwap.\n\
+            Migration often brings sharp volatility in both directions.\n\n\
+            {}",
+            token_name.unwrap_or("Unknown".to_string()),
+            progress,
+            token_address,
+            self.links.token(&token_address.to_string()),
+            self.get_risk_warning()
+        );
+
+        let keyboard = with_rating(watch_keyboard(token_address), "Graduation", token_address);
+        self.send_message_with_keyboard("graduation", &message, Some(with_lesson(keyboard, Topic::BondingCurve))).await
+    }
+
+    /// Alert on pool creation and notable liquidity changes (educational purposes only)
+    pub async fn alert_liquidity_event(&self,
+        token_address: &Pubkey,
+        token_name: Option<String>,
+        kind: &SwapType,
+        dex_name: &str,
+        amount_sol: f64,
+        pool_sol: f64,
+    ) -> Result<()> {
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) {
+            return Ok(());
+        }
+
+        let (headline, note) = match kind {
+            SwapType::PoolCreate => (
+                "🆕 **POOL CREATED**",
+                "A new pool makes the token tradable on another venue.\n\
+                Early pools are thin: small trades move the price a lot.",
+            ),
+            SwapType::RemoveLiquidity => (
+                "💧 **LIQUIDITY REMOVED**",
+                "Pulled liquidity makes exits more expensive and can precede a rug pull.",
+            ),
+            SwapType::AddLiquidity => (
+                "💦 **LIQUIDITY ADDED**",
+                "Deeper liquidity reduces slippage for larger trades.",
+            ),
+            SwapType::Buy | SwapType::Sell => return Ok(()),
+        };
+
+        let mut rate_limiter = self.rate_limiter.write().await;
+        if !rate_limiter.can_send(&format!("liquidity_{:?}_{}", kind, token_address)) {
+            return Ok(());
+        }
+
+        let message = format!(
+            "{} (Educational Alert)\n\n\
+            🪙 **Token**: {}\n\
+            🏦 **DEX**: {}\n\
+            💰 **Amount**: {:.2} SOL\n\
+            🌊 **Pool Liquidity**: {:.2} SOL\n\
+            {}\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
+            📚 **Educational Note**: {}\n\n\
+            {}",
+            headline,
+            token_name.unwrap_or("Unknown".to_string()),
+            dex_name,
+            amount_sol,
+            pool_sol,
+            quote_tag(token_address),
+            token_address,
+            self.links.token(&token_address.to_string()),
+            note,
+            self.get_risk_warning()
+        );
+
+        self.send_message_with_keyboard("liquidity", &message, Some(with_lesson(watch_keyboard(token_address), Topic::ImpermanentLoss))).await
+    }
+
+    /// Alert when pool SOL fell sharply between reserve polls (educational purposes only)
+    pub async fn alert_liquidity_drop(&self,
+        token_address: &Pubkey,
+        token_name: Option<String>,
+        from_sol: f64,
+        to_sol: f64,
+        drop_pct: f64,
+        window_minutes: i64,
+        depth_curve: &str,
+    ) -> Result<()> {
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) {
+            return Ok(());
+        }
+
+        let mut rate_limiter = self.rate_limiter.write().await;
+        if !rate_limiter.can_send(&format!("liquidity_drop_{}", token_address)) {
+            return Ok(());
+        }
+
+        let message = format!(
+            "🚰 **LIQUIDITY DROP** (Educational Alert)\n\n\
+            🪙 **Token**: {}\n\
+            🌊 **Pool Liquidity**: {:.2} → {:.2} SOL (-{:.1}% within {}m)\n\
+            📏 **Depth Now**:\n{}\n\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
+            📚 **Educational Note**: Thinner pools mean more slippage on exit.\n\
+            Sudden drops can come from LP withdrawals or a drained bonding curve.\n\n\
+            {}",
+            token_name.unwrap_or("Unknown".to_string()),
+            from_sol,
+            to_sol,
+            drop_pct,
+            window_minutes,
+            depth_curve,
+            token_address,
+            self.links.token(&token_address.to_string()),
+            self.get_risk_warning()
+        );
+
+        self.send_message_with_keyboard("liquidity_drop", &message, Some(watch_keyboard(token_address))).await
+    }
+
+    /// Alert when a tracked wallet's trade is large relative to pool depth (educational purposes only)
+    pub async fn alert_price_impact(&self,
+        wallet_address: &Pubkey,
+        action: &str, // "BUY" or "SELL"
+        token_address: &Pubkey,
+        token_name: Option<String>,
+        amount_sol: f64,
+        pool_sol: f64,
+        impact_pct: f64,
+        signature: Option<&str>,
+    ) -> Result<()> {
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) {
+            return Ok(());
+        }
+
+        let mut rate_limiter = self.rate_limiter.write().await;
+        if !rate_limiter.can_send(&format!("impact_{}_{}", wallet_address, token_address)) {
+            return Ok(());
+        }
+
+        let message = format!(
+            "🌊 **HIGH PRICE IMPACT** (Educational Alert)\n\n\
+            👤 **Wallet**: {}\n\
+            📊 **Action**: {}\n\
+            🪙 **Token**: {}\n\
+            💵 **Amount**: {:.4} SOL\n\
+            🏦 **Pool Liquidity**: {:.2} SOL\n\
+            💥 **Estimated Impact**: {:.2}%\n\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\
+            {}\n\
+            📚 **Educational Note**: A single order moving the price this much means the pool is\n\
+            shallow for its size; copying it would fill at a noticeably worse price.\n\n\
+            {}",
+            self.links.wallet(&wallet_address.to_string()),
+            action,
+            token_name.unwrap_or("Unknown".to_string()),
+            amount_sol,
+            pool_sol,
+            impact_pct,
+            token_address,
+            self.links.token(&token_address.to_string()),
+            signature.map(|sig| format!("🧾 {}\n", self.links.transaction(sig))).unwrap_or_default(),
+            self.get_risk_warning()
+        );
+
+        let keyboard = with_lesson(watch_keyboard(token_address), Topic::Mev);
+        self.send_message_with_keyboard("price_impact", &message, Some(keyboard)).await
+    }
+
+    /// Alert when the same token trades at noticeably different prices on two venues
+    /// (educational purposes only)
+    pub async fn alert_arbitrage_spread(&self,
+        token_address: &Pubkey,
+        token_name: Option<String>,
+        low_venue: &str,
+        low_price: f64,
+        high_venue: &str,
+        high_price: f64,
+        spread_pct: f64,
+    ) -> Result<()> {
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) {
+            return Ok(());
+        }
+
+        let mut rate_limiter = self.rate_limiter.write().await;
+        if !rate_limiter.can_send(&format!("spread_{}", token_address)) {
+            return Ok(());
+        }
+
+        let message = format!(
+            "⚖️ **CROSS-VENUE SPREAD** (Educational Alert)\n\n\
+            🪙 **Token**: {}\n\
+            🔻 **Cheapest**: {} at {:.10}\n\
+            🔺 **Priciest**: {} at {:.10}\n\
+            📐 **Spread**: {:.2}%\n\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
+            📚 **Educational Note**: Prices of one token drift apart when venues are not arbitraged\n\
+            instantly. Swap fees, price impact on both legs and landing latency usually consume most\n\
+            of a spread like this before it can be captured.\n\n\
+            {}",
+            token_name.unwrap_or("Unknown".to_string()),
+            low_venue,
+            low_price,
+            high_venue,
+            high_price,
+            spread_pct,
+            token_address,
+            self.links.token(&token_address.to_string()),
+            self.get_risk_warning()
+        );
+
+        self.send_message_with_keyboard("arbitrage_spread", &message, Some(watch_keyboard(token_address))).await
     }
 
     /// Alert on volume spikes (educational purposes only)
     pub async fn alert_volume_spike(&self,
         token_address: &Pubkey,
         token_name: Option<String>,
-        current_volume: f64,
+        current_volume: VolumeTotals,
         average_volume: f64,
     ) -> Result<()> {
-        if !self.enabled || !self.alert_settings.alert_volume_spikes {
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) || !self.alert_settings.alert_volume_spikes {
             return Ok(());
         }
 
-        let spike_multiplier = current_volume / average_volume;
+        let spike_multiplier = current_volume.total() / average_volume;
 
         if spike_multiplier < self.alert_settings.volume_spike_threshold {
             return Ok(());
@@ -245,34 +769,32 @@ impl TelegramAlertSystem {
         let message = format!(
             "📊 **VOLUME SPIKE** (Educational Alert)\n\n\
             🪙 **Token**: {}\n\
-            📈 **Current Volume**: ${:.2}\n\
-            📊 **Average Volume**: ${:.2}\n\
+            📈 **Current Volume**: {}\n\
+            📊 **Average Volume**: {:.2} SOL\n\
             🔥 **Spike**: {:.1}x average\n\
-            🔗 **Address**: `{}`\n\n\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
             📚 **Educational Note**: Volume spikes can indicate:\n\
             • Increased market interest\n\
             • Potential price movements\n\
             • News or events affecting the token\n\n\
             {}",
             token_name.unwrap_or("Unknown".to_string()),
-            current_volume,
+            format_volume(&current_volume),
             average_volume,
             spike_multiplier,
             token_address,
+            self.links.token(&token_address.to_string()),
             self.get_risk_warning()
         );
 
-        self.send_message(&message).await
+        self.send_message("volume_spike", &message).await
     }
 
     /// Alert on potential sniper opportunities (educational analysis only)
-    pub async fn alert_sniper_opportunity(&self,
-        token_address: &Pubkey,
-        token_name: Option<String>,
-        opportunity_type: &str,
-        details: &str,
-    ) -> Result<()> {
-        if !self.enabled || !self.alert_settings.alert_sniper_opportunities {
+    pub async fn alert_sniper_opportunity(&self, signal: &OpportunitySignal) -> Result<()> {
+        let token_address = &signal.mint;
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) || !self.alert_settings.alert_sniper_opportunities {
             return Ok(());
         }
 
@@ -286,24 +808,33 @@ impl TelegramAlertSystem {
             🪙 **Token**: {}\n\
             📍 **Pattern Type**: {}\n\
             📊 **Details**: {}\n\
-            🔗 **Address**: `{}`\n\n\
-            📚 **Educational Context**:\n\
-            This pattern suggests a potential market opportunity based on:\n\
-            • Historical price action\n\
-            • Volume analysis\n\
-            • Market sentiment indicators\n\n\
+            🎚️ **Confidence**: {:.0}%\n\
+            🧭 **Suggested**: {}\n\
+            🧾 **Evidence**:\n{}\
+            {}\
+            {}\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
             ⚠️ **IMPORTANT**: This is for educational purposes only!\n\
             • Real trading involves significant risk\n\
             • Past patterns don't guarantee future results\n\
             • Always do your own research\n\
             • Never invest more than you can afford to lose",
-            token_name.unwrap_or("Unknown".to_string()),
-            opportunity_type,
-            details,
-            token_address
+            signal.token_name.clone().unwrap_or("Unknown".to_string()),
+            signal.kind.label(),
+            signal.summary,
+            signal.confidence * 100.0,
+            signal.action.label(),
+            signal.format_evidence(),
+            insider_tag(token_address),
+            blacklist_tag(token_address),
+            token_address,
+            self.links.token(&token_address.to_string())
         );
 
-        self.send_message(&message).await
+        let keyboard = opportunity_keyboard(token_address, self.trade_links.as_ref());
+        let keyboard = with_rating(keyboard, signal.kind.label(), token_address);
+        self.send_message_with_keyboard("sniper_opportunity", &message, Some(keyboard)).await
     }
 
     /// Send daily summary (educational purposes)
@@ -316,25 +847,42 @@ impl TelegramAlertSystem {
             return Ok(());
         }
 
+        #[cfg(feature = "trading")]
+        let relay_stats = crate::block_engine::relay::format_relay_stats();
+        // Alerts-only builds send no transactions
+        #[cfg(not(feature = "trading"))]
+        let relay_stats = String::from("  none (built without trading)");
         let message = format!(
             "📊 **DAILY SUMMARY** (Educational Report)\n\n\
             📅 **Date**: {}\n\
             🔍 **Tokens Monitored**: {}\n\
             👥 **Wallet Activities**: {}\n\
             📈 **Significant Movements**: {}\n\n\
+            💸 **Infrastructure Costs**:\n{}\n\n\
+            🚀 **Relay Landed Rates**:\n{}\n\n\
             📚 **Market Insights**:\n\
             • Monitor multiple data points for better analysis\n\
             • Look for patterns across different tokens\n\
             • Consider market sentiment and external factors\n\n\
             {}",
-            Utc::now().format("%Y-%m-%d"),
+            self.clock.now().format("%Y-%m-%d"),
             tokens_monitored,
             wallet_activities,
             significant_movements,
+            crate::common::trade_costs::format_daily_costs(),
+            relay_stats,
             self.get_risk_warning()
         );
 
-        self.send_message(&message).await
+        self.send_message("daily_summary", &message).await
+    }
+
+    /// Send a file to the chat, e.g. a saved report
+    pub async fn send_document(&self, path: &Path, caption: &str) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        self.breaker.call(self.sink.send_document(path, caption)).await
     }
 
     /// Send a custom educational alert
@@ -352,19 +900,391 @@ impl TelegramAlertSystem {
             self.get_risk_warning()
         );
 
-        self.send_message(&message).await
+        self.send_message("custom", &message).await
     }
 
-    /// Internal method to send messages via Telegram
-    async fn send_message(&self, text: &str) -> Result<()> {
-        self.bot
-            .send_message(self.chat_id, text)
-            .parse_mode(teloxide::types::ParseMode::Markdown)
-            .send()
-            .await?;
+    /// Follow-up on a token the user marked as watching (educational feedback loop)
+    pub async fn send_follow_up(&self,
+        token_address: &Pubkey,
+        token_name: Option<String>,
+        elapsed_label: &str,
+        summary: &str,
+    ) -> Result<()> {
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) {
+            return Ok(());
+        }
+
+        let message = format!(
+            "🔁 **FOLLOW-UP {}** (Educational Review)\n\n\
+            🪙 **Token**: {}\n\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
+            {}\n\n\
+            📚 **Reflect**: Did the alert's signal play out? Comparing outcomes with signals \
+            is how you learn which patterns matter.",
+            elapsed_label,
+            token_name.unwrap_or("Unknown".to_string()),
+            token_address,
+            self.links.token(&token_address.to_string()),
+            summary
+        );
+
+        self.send_message("follow_up", &message).await
+    }
+
+    /// Alert on an early buy by a wallet sharing the creator's funding (educational purposes only)
+    pub async fn alert_insider_buy(&self, finding: &InsiderFinding, token_name: Option<String>) -> Result<()> {
+        if !self.enabled || mute_list::is_token_muted(&finding.mint.to_string()) {
+            return Ok(());
+        }
+
+        let mut rate_limiter = self.rate_limiter.write().await;
+        if !rate_limiter.can_send(&format!("insider_{}", finding.mint)) {
+            return Ok(());
+        }
+
+        let message = format!(
+            "🕵️ **LIKELY INSIDER LAUNCH** (Educational Alert)\n\n\
+            🪙 **Token**: {}\n\
+            👛 **Buyer**: `{}` bought {:.2} SOL {}s after launch\n\
+            💸 **Funded by**: `{}`, which also funded the creator\n\
+            👥 **Insider wallets so far**: {} ({:.2} SOL)\n\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
+            📚 **Educational Note**: Wallets funded from the creator's source buying before the \
+            launch is public often hold supply they intend to sell into later buyers.\n\n\
+            {}",
+            token_name.unwrap_or("Unknown".to_string()),
+            finding.wallet,
+            finding.sol,
+            finding.seconds_after_launch,
+            finding.funder,
+            finding.insider_wallets,
+            finding.insider_sol,
+            finding.mint,
+            self.links.token(&finding.mint.to_string()),
+            self.get_risk_warning()
+        );
+
+        self.send_message_with_keyboard("insider", &message, Some(watch_keyboard(&finding.mint))).await
+    }
+
+    /// Alert when several wallets of a tracked group buy the same token (educational purposes only)
+    pub async fn alert_group_buy(&self, activity: &GroupActivity) -> Result<()> {
+        if !self.enabled || mute_list::is_token_muted(&activity.mint.to_string()) {
+            return Ok(());
+        }
+
+        let mut rate_limiter = self.rate_limiter.write().await;
+        if !rate_limiter.can_send(&format!("group_{}_{}", activity.group, activity.mint)) {
+            return Ok(());
+        }
+
+        let buyers: String = activity.buyers.iter()
+            .map(|(label, sol)| format!("  • {}: {:.2} SOL\n", label, sol))
+            .collect();
+        let message = format!(
+            "👥 **GROUP BUYING** (Educational Alert)\n\n\
+            {}\n\
+            💰 **Total**: {:.2} SOL\n\
+            {}\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
+            📚 **Educational Note**: Wallets you grouped acting together can be a shared \
+            conviction or a coordinated pump; followers who buy after them often provide the exit.\n\n\
+            {}",
+            activity.headline(),
+            activity.total_sol,
+            buyers,
+            activity.mint,
+            self.links.token(&activity.mint.to_string()),
+            self.get_risk_warning()
+        );
+
+        self.send_message_with_keyboard("group_buy", &message, Some(watch_keyboard(&activity.mint))).await
+    }
+
+    /// Post-mortem of a finished launch: its timeline from first buy to the end
+    pub async fn send_launch_replay(&self,
+        token_address: &Pubkey,
+        token_name: Option<String>,
+        timeline: &str,
+    ) -> Result<()> {
+        if !self.enabled || mute_list::is_token_muted(&token_address.to_string()) {
+            return Ok(());
+        }
+
+        let message = format!(
+            "🎬 **LAUNCH REPLAY** (Educational Post-Mortem)\n\n\
+            🪙 **Token**: {}\n\
+            🔗 **Address**: `{}`\n\
+            🔍 **Links**: {}\n\n\
+            {}\n\n\
+            {}",
+            token_name.unwrap_or("Unknown".to_string()),
+            token_address,
+            self.links.token(&token_address.to_string()),
+            timeline,
+            self.get_risk_warning()
+        );
+
+        self.send_message("launch_replay", &message).await
+    }
+
+    /// Operator notice that stream data fell behind the chain, or caught up again
+    pub async fn alert_data_delay(&self, delayed: bool, detail: &str) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let message = if delayed {
+            format!(
+                "⏳ **DATA DELAYED**\n\n\
+                {}\n\n\
+                Alerts are flagged as delayed and new buys are paused until the stream catches up.",
+                detail
+            )
+        } else {
+            format!("✅ **DATA CURRENT AGAIN**\n\n{}\n\nAlerts and buys are back to normal.", detail)
+        };
+
+        self.send_message("slot_lag", &message).await
+    }
+
+    /// No alerts for a while: stream health, so a quiet market can be told from a broken bot
+    pub async fn send_heartbeat(&self, report: &str) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        self.send_message("heartbeat", &format!("💓 **HEARTBEAT**\n\n{}", report)).await
+    }
+
+    /// This instance took the trading lock, or dropped to monitor-only
+    pub async fn alert_instance_lock(&self, active: bool, detail: &str) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let message = if active {
+            format!("🔓 **TRADING LOCK ACQUIRED**\n\n{}\n\nThis instance now executes trades.", detail)
+        } else {
+            format!(
+                "🔒 **MONITOR-ONLY**\n\n\
+                {}\n\n\
+                Buys and sells are refused here while another instance trades this wallet.",
+                detail
+            )
+        };
+
+        self.send_message("instance_lock", &message).await
+    }
+
+    /// Low, refilled and recovered SOL balances of trading wallets
+    #[cfg(feature = "trading")]
+    pub async fn alert_sol_balance(&self, event: &BalanceEvent) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let message = match event {
+            BalanceEvent::Low { wallet, sol, min_sol } => format!(
+                "🪫 **LOW SOL BALANCE**\n\n\
+                👛 **Wallet**: `{}`\n\
+                💰 **Balance**: {:.4} SOL (minimum {:.4} SOL)\n\n\
+                Trades from this wallet may fail for lack of fees and rent.",
+                wallet, sol, min_sol
+            ),
+            BalanceEvent::Refilled { wallet, sol, amount_sol, signature } => format!(
+                "⛽ **WALLET TOPPED UP**\n\n\
+                👛 **Wallet**: `{}`\n\
+                💸 **Sent**: {:.4} SOL from the treasury (balance was {:.4} SOL)\n\
+                🔍 **Transaction**: {}",
+                wallet, amount_sol, sol, self.links.transaction(signature)
+            ),
+            BalanceEvent::RefillFailed { wallet, sol, reason } => format!(
+                "❌ **TOP-UP FAILED**\n\n\
+                👛 **Wallet**: `{}` at {:.4} SOL\n\
+                ⚠️ **Reason**: {}",
+                wallet, sol, reason
+            ),
+            BalanceEvent::Recovered { wallet, sol } => format!(
+                "🔋 **SOL BALANCE OK**\n\n👛 **Wallet**: `{}` is back at {:.4} SOL",
+                wallet, sol
+            ),
+        };
+
+        self.send_message("sol_balance", &message).await
+    }
+
+    /// Summary of a profit policy run
+    #[cfg(feature = "trading")]
+    pub async fn alert_profit_summary(&self, summary: &ProfitSummary) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let action = match (summary.applied_sol > 0.0, summary.policy) {
+            (false, _) => "Nothing applied".to_string(),
+            (true, ProfitPolicy::Compound) => format!("{:.4} SOL compounded into the trading balance", summary.applied_sol),
+            (true, ProfitPolicy::Sweep) => format!("{:.4} SOL swept to the cold wallet", summary.applied_sol),
+            (true, ProfitPolicy::Usdc) => format!("{:.4} SOL converted to USDC", summary.applied_sol),
+        };
+        let mut message = format!(
+            "🏦 **PROFIT POLICY: {}**\n\n\
+            🗓 **Window**: {} → {}\n\
+            📈 **Realized PnL**: {:+.4} SOL over {} sells\n\
+            💼 **Action**: {}",
+            summary.policy.as_str().to_uppercase(),
+            summary.since.format("%Y-%m-%d %H:%M"),
+            summary.until.format("%Y-%m-%d %H:%M UTC"),
+            summary.realized_pnl_sol,
+            summary.sells,
+            action
+        );
+        if let Some(signature) = &summary.signature {
+            message.push_str(&format!("\n🔍 **Transaction**: {}", self.links.transaction(signature)));
+        }
+        if let Some(note) = &summary.note {
+            message.push_str(&format!("\nℹ️ {}", note));
+        }
+
+        self.send_message("profit_policy", &message).await
+    }
+
+    /// A buy filled noticeably off its quote
+    #[cfg(feature = "trading")]
+    pub async fn alert_fill_deviation(&self, report: &FillReport) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let message = format!(
+            "📐 **FILL OFF QUOTE**\n\n\
+            🔗 **Token Address**: `{}`\n\
+            📦 **Tokens**: {:.2} received, {:.2} quoted ({:+.1}%)\n\
+            💰 **SOL**: {:.6} spent, {:.6} quoted ({:+.1}%)\n\
+            🔍 **Transaction**: {}\n\n\
+            The position now uses the amounts received.",
+            report.mint,
+            report.actual_tokens, report.quoted_tokens, report.token_deviation_pct(),
+            report.actual_sol, report.quoted_sol, report.sol_deviation_pct(),
+            self.links.transaction(&report.signature)
+        );
+
+        self.send_message("fill_deviation", &message).await
+    }
+
+    /// Internal method to send messages through the sink
+    async fn send_message(&self, category: &str, text: &str) -> Result<()> {
+        self.send_message_with_keyboard(category, text, None).await
+    }
+
+    async fn send_message_with_keyboard(
+        &self,
+        category: &str,
+        text: &str,
+        keyboard: Option<InlineKeyboardMarkup>,
+    ) -> Result<()> {
+        // Market alerts built from a lagging stream say so; operator notices do not need it
+        let operator_notice = matches!(category, "custom" | "slot_lag" | "heartbeat");
+        if !operator_notice {
+            crate::library::heartbeat::record_alert();
+        }
+        let delayed_text;
+        let text = match crate::library::slot_lag::delay_notice() {
+            Some(notice) if !operator_notice => {
+                delayed_text = format!("{}\n\n{}", notice, text);
+                delayed_text.as_str()
+            }
+            _ => text,
+        };
+
+        // No receivers is the normal case when no API client is connected
+        let _ = ALERT_BROADCAST.send(AlertRecord {
+            category: category.to_string(),
+            message: text.to_string(),
+            timestamp: self.clock.now(),
+        });
+
+        let logger = Logger::new("[TELEGRAM] => ".blue().to_string());
+        // Anything already queued goes out first so alerts arrive in order; while Telegram is
+        // rate limiting us the alert joins the queue instead
+        if self.throttle.ready() && (self.outbox.is_empty() || self.flush_outbox().await) {
+            match self.deliver(text, keyboard.clone(), &logger).await {
+                Ok(()) => return Ok(()),
+                Err(e) if !e.is_transient() => return Err(e.into()),
+                Err(e) => logger.log(format!("Queueing {} alert: {}", category, e).yellow().to_string()),
+            }
+        }
+        self.outbox.push(OutboxEntry::new(category, text, keyboard, self.clock.now().timestamp()));
         Ok(())
     }
 
+    /// One delivery attempt under the retry policy and circuit breaker; bad chat ids or markup
+    /// fail fast. A Telegram rate limit is not retried here: it slows the throttle down and the
+    /// alert waits in the outbox.
+    async fn deliver(&self, text: &str, keyboard: Option<InlineKeyboardMarkup>, logger: &Logger) -> Result<(), BotError> {
+        let result = retry("telegram send", logger, |_| {
+            let keyboard = keyboard.clone();
+            async move {
+                match self.breaker.call(self.sink.send(text, keyboard)).await.map_err(|e| BotError::classify(&e)) {
+                    Err(BotError::RateLimited { retry_after }) => {
+                        self.throttle.record_rate_limited(retry_after);
+                        logger.log(format!(
+                            "Telegram rate limit, sending one message every {:?}",
+                            self.throttle.interval()
+                        ).yellow().to_string());
+                        // Shed like an open breaker: transient, and not retried on the spot
+                        Err(BotError::CircuitOpen("telegram (rate limited)".to_string()))
+                    }
+                    other => other,
+                }
+            }
+        }).await;
+        if result.is_ok() {
+            self.throttle.record_sent();
+        }
+        result
+    }
+
+    /// Deliver queued alerts in order, preceded by a notice of how many were delayed or expired.
+    /// Stops at the first transient failure; returns whether the outbox was fully drained.
+    pub async fn flush_outbox(&self) -> bool {
+        let _delivery = match self.outbox.try_begin_delivery() {
+            Some(guard) => guard,
+            None => return false,
+        };
+        let backlog = self.outbox.backlog(self.clock.now());
+        if backlog.is_empty() {
+            return true;
+        }
+
+        let logger = Logger::new("[TELEGRAM] => ".blue().to_string());
+        if self.outbox.needs_notice() {
+            if !self.throttle.ready() {
+                return false;
+            }
+            match self.deliver(&backlog.notice(), None, &logger).await {
+                Err(e) if e.is_transient() => return false,
+                _ => self.outbox.acknowledge_notice(),
+            }
+        }
+        while let Some(entry) = self.outbox.front() {
+            if !self.throttle.ready() {
+                return false;
+            }
+            match self.deliver(&entry.text, entry.keyboard.clone(), &logger).await {
+                Ok(()) => {}
+                Err(e) if e.is_transient() => return false,
+                Err(e) => logger.log(format!("Dropping queued {} alert: {}", entry.category, e).red().to_string()),
+            }
+            self.outbox.pop_front();
+        }
+        logger.log(format!("Delivered {} delayed alerts", backlog.pending).green().to_string());
+        true
+    }
+
     /// Get risk warning text
     fn get_risk_warning(&self) -> &str {
         if self.alert_settings.include_risk_warnings {
@@ -432,4 +1352,34 @@ pub fn init_from_env() -> Result<Option<TelegramAlertSystem>> {
             Ok(None)
         }
     }
-}
\ No newline at end of file
+}
+
+/// Retry queued alerts every ALERT_OUTBOX_RETRY_SECS (default 30) until cancelled, so the
+/// backlog is delivered when Telegram comes back even if no new alert is raised. While Telegram
+/// is rate limiting, the backlog is drained as fast as the throttle allows instead.
+pub fn start_outbox_retry(
+    system: Arc<TelegramAlertSystem>,
+    cancel_token: CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    let retry_secs = std::env::var("ALERT_OUTBOX_RETRY_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(30)
+        .max(1);
+    tokio::spawn(async move {
+        loop {
+            let delay = match system.throttle.wait() {
+                Some(wait) if !system.outbox.is_empty() => wait,
+                _ => std::time::Duration::from_secs(retry_secs),
+            };
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = tokio::time::sleep(delay) => {
+                    if !system.outbox.is_empty() {
+                        system.flush_outbox().await;
+                    }
+                }
+            }
+        }
+    })
+}
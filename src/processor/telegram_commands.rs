@@ -0,0 +1,624 @@
+/*!
+# Telegram Commands
+
+Answers bot commands sent from the configured alert chat (`TELEGRAM_CHAT_ID`); messages from any
+other chat are ignored.
+
+- `/import_watchlist <mints>`: track the pasted mints (CSV or JSON). A `.csv`/`.json` document
+  sent with `/import_watchlist` as its caption is imported the same way.
+- `/export_watchlist [json|csv]`: reply with a file of every tracked token and its metrics.
+- `/import_wallets <wallets>`: track the pasted wallets (`address[,label[,group]]` CSV or JSON)
+  with a label and group each; a `.csv`/`.json` document sent with `/import_wallets` as its
+  caption is imported the same way. `/wallets` lists them per group (see `wallet_groups`).
+- `/trending [n]`: the tokens ranking highest on volume acceleration, unique buyers, holder
+  growth and price momentum (see `trending`).
+- `/mute <mint|creator> <duration>`, `/unmute <mint|creator>`, `/mutes`: silence alerts (and
+  buys) for one token or a creator's tokens for a while, e.g. `/mute <mint> 6h`.
+- `/score_explain <mint>`: the token's rug-risk score broken down per feature, with the weights
+  from `RISK_WEIGHTS_PATH`.
+- `/firstbuyers <mint>`: the first buyers of a launch with their amounts, slots and wallet ages,
+  and whether they look bundled (see `first_buyers`).
+- `/chart <mint> [window]`: a candlestick chart of the token's recorded price history, e.g.
+  `/chart <mint> 4h` (default `1h`, at most 7 days and as far back as `PRICE_HISTORY_POINTS` reach).
+- `/stats <mint>`: a compact metrics card of a tracked token: price changes, volume, trades,
+  liquidity, curve progress and risk score.
+- `/timeline <mint>`: the token's notable events in order: creation, first whale buy, liquidity
+  changes, alerts fired and state transitions (see `token_timeline`).
+- `/audit <mint>`: a full risk audit of any mint, tracked or not: authorities, liquidity,
+  holder concentration, deployer history and a simulated sell (see `token_audit`).
+- `/deployer <address>`: every recorded launch of a creator with its outcome, plus graduation
+  and rug counts, to vet a deployer before buying their new token (see `deployer_history`).
+- `/learn [curve|il|mev]`: a short explanation thread and quiz on bonding curves, impermanent
+  loss or MEV; the same lessons are offered by the "📚 Learn" button on some alerts (see `lessons`).
+- `/feedback`: how the "👍 Useful" / "👎 Noise" ratings on opportunity alerts add up per signal
+  type and token category; signals mostly rated noise get stricter thresholds (see `alert_feedback`).
+- `/pause [reason]`, `/resume`: stop or restart new buys of the trading bot; monitoring and the
+  selling of open positions continue, and the pause survives restarts.
+- The "👀 Watching" button on opportunity alerts schedules follow-ups on the token showing what
+  happened since the alert (see `FOLLOW_UP_INTERVALS_MIN`).
+
+## Environment Variables
+
+- `TELEGRAM_COMMANDS_ENABLED`: Answer commands when Telegram is configured (default: `true`)
+- `FOLLOW_UP_INTERVALS_MIN`: Follow-up offsets after an alert, in minutes (default: `15,60,240`)
+*/
+
+use std::str::FromStr;
+use std::sync::Arc;
+use colored::Colorize;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use teloxide::{
+    net::Download,
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, InputFile},
+    utils::command::BotCommands,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::common::logger::Logger;
+use crate::common::price_history::PriceHistory;
+use crate::library::audit_log::{self, AuditAction};
+use crate::library::chart;
+use crate::processor::alert_feedback::{FeedbackOutcome, Rating};
+use crate::processor::educational_monitor::EducationalMonitor;
+use crate::processor::lessons::{LessonAction, LessonReply, LessonSessions, Topic};
+use crate::processor::mute_list::{self, MuteList};
+use crate::processor::risk_score;
+use crate::processor::telegram_alerts::WATCH_CALLBACK_PREFIX;
+use crate::processor::trading_control::TradingControl;
+use crate::processor::trending::format_trending;
+use crate::processor::wallet_groups::parse_wallets;
+use crate::processor::watchlist::{export_watchlist, parse_watchlist, WatchlistFormat};
+
+/// Largest watchlist or wallet list document accepted for import
+const MAX_IMPORT_BYTES: u32 = 1024 * 1024;
+
+#[derive(BotCommands, Clone)]
+#[command(rename_rule = "snake_case", description = "Monitor commands:")]
+pub enum Command {
+    #[command(description = "show this help")]
+    Help,
+    #[command(description = "track mints from pasted CSV/JSON (or attach a file with this caption)")]
+    ImportWatchlist(String),
+    #[command(description = "export tracked tokens with metrics: /export_watchlist [json|csv]")]
+    ExportWatchlist(String),
+    #[command(description = "track wallets from pasted address,label,group CSV/JSON (or attach a file with this caption)")]
+    ImportWallets(String),
+    #[command(description = "tracked wallets per group")]
+    Wallets,
+    #[command(description = "top trending tokens: /trending [n]")]
+    Trending(String),
+    #[command(description = "silence a token or creator for a while: /mute <mint|creator> <6h|30m|2d>")]
+    Mute(String),
+    #[command(description = "lift a mute: /unmute <mint|creator>")]
+    Unmute(String),
+    #[command(description = "list active mutes")]
+    Mutes,
+    #[command(description = "risk score contribution per feature: /score_explain <mint>")]
+    ScoreExplain(String),
+    #[command(description = "first buyers of a launch with amounts and wallet ages: /firstbuyers <mint>")]
+    #[command(rename = "firstbuyers")]
+    FirstBuyers(String),
+    #[command(description = "every recorded launch of a creator with outcomes: /deployer <address>")]
+    Deployer(String),
+    #[command(description = "notable events of a token in order: /timeline <mint>")]
+    Timeline(String),
+    #[command(description = "candlestick chart of recorded prices: /chart <mint> [15m|1h|4h|1d]")]
+    Chart(String),
+    #[command(description = "compact metrics card of a tracked token: /stats <mint>")]
+    Stats(String),
+    #[command(description = "full risk audit of any mint: /audit <mint>")]
+    Audit(String),
+    #[command(description = "alert ratings per signal type and token category")]
+    Feedback,
+    #[command(description = "learn how bonding curves, impermanent loss or MEV work: /learn [curve|il|mev]")]
+    Learn(String),
+    #[command(description = "stop new buys, keep monitoring and selling: /pause [reason]")]
+    Pause(String),
+    #[command(description = "resume buying after /pause")]
+    Resume,
+}
+
+#[derive(Clone)]
+struct CommandContext {
+    /// None in the trading bot, which has no watchlist
+    monitor: Option<Arc<EducationalMonitor>>,
+    chat_id: ChatId,
+    logger: Logger,
+}
+
+async fn handle_command(bot: Bot, msg: Message, cmd: Command, ctx: CommandContext) -> ResponseResult<()> {
+    if msg.chat.id != ctx.chat_id {
+        return Ok(());
+    }
+
+    match cmd {
+        Command::Help => {
+            bot.send_message(msg.chat.id, Command::descriptions().to_string()).await?;
+        }
+        Command::ImportWatchlist(_) | Command::ExportWatchlist(_) | Command::Trending(_) | Command::Feedback
+        | Command::FirstBuyers(_) | Command::Deployer(_) | Command::Timeline(_) | Command::Stats(_)
+        | Command::Audit(_) | Command::ImportWallets(_) | Command::Wallets
+            if ctx.monitor.is_none() =>
+        {
+            bot.send_message(msg.chat.id, "Watchlist, wallet, trending, first buyer, deployer, timeline, stats, audit and feedback commands are only available in the monitor.").await?;
+        }
+        Command::Pause(_) | Command::Resume if ctx.monitor.is_some() => {
+            bot.send_message(msg.chat.id, "This monitor does not trade; /pause and /resume control the trading bot.").await?;
+        }
+        Command::ImportWatchlist(content) => {
+            if content.trim().is_empty() {
+                bot.send_message(msg.chat.id, "Paste mints after the command, or send a CSV/JSON file with /import_watchlist as caption.").await?;
+                return Ok(());
+            }
+            let reply = import(&ctx, &content).await;
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+        Command::ImportWallets(content) => {
+            if content.trim().is_empty() {
+                bot.send_message(msg.chat.id, "Paste wallets after the command (address,label,group per line), or send a CSV/JSON file with /import_wallets as caption.").await?;
+                return Ok(());
+            }
+            let reply = import_wallets(&ctx, &content);
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+        Command::Wallets => {
+            if let Some(monitor) = &ctx.monitor {
+                bot.send_message(msg.chat.id, monitor.wallet_groups()).await?;
+            }
+        }
+        Command::ExportWatchlist(format) => {
+            let format: WatchlistFormat = match format.parse() {
+                Ok(format) => format,
+                Err(e) => {
+                    bot.send_message(msg.chat.id, e.to_string()).await?;
+                    return Ok(());
+                }
+            };
+            let tokens = match &ctx.monitor {
+                Some(monitor) => monitor.list_tokens().await,
+                None => return Ok(()),
+            };
+            match export_watchlist(&tokens, format) {
+                Ok(content) => {
+                    let file_name = match format {
+                        WatchlistFormat::Csv => "watchlist.csv",
+                        WatchlistFormat::Json => "watchlist.json",
+                    };
+                    bot.send_document(msg.chat.id, InputFile::memory(content.into_bytes()).file_name(file_name))
+                        .caption(format!("{} tracked tokens", tokens.len()))
+                        .await?;
+                }
+                Err(e) => {
+                    bot.send_message(msg.chat.id, format!("Export failed: {}", e)).await?;
+                }
+            }
+        }
+        Command::Trending(limit) => {
+            let limit = limit.trim().parse::<usize>().ok().filter(|n| *n > 0);
+            let tokens = match &ctx.monitor {
+                Some(monitor) => monitor.trending(limit).await,
+                None => return Ok(()),
+            };
+            bot.send_message(msg.chat.id, format!("🔥 Trending tokens\n\n{}", format_trending(&tokens))).await?;
+        }
+        Command::Mute(args) => {
+            let mut parts = args.split_whitespace();
+            let address = parts.next().and_then(|a| Pubkey::from_str(a).ok());
+            let duration = parts.next().and_then(mute_list::parse_duration);
+            let reply = match (address, duration) {
+                (Some(address), Some(duration)) => {
+                    let until = MuteList::shared().mute(&address.to_string(), duration);
+                    audit_log::record(
+                        AuditAction::ConfigChanged,
+                        "telegram",
+                        json!({ "setting": "mute", "address": address.to_string(), "until": until }),
+                    );
+                    ctx.logger.log(format!("Muted {} until {}", address, until));
+                    format!(
+                        "🔇 Muted {} until {} UTC",
+                        address,
+                        chrono::DateTime::from_timestamp(until, 0).map(|t| t.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default()
+                    )
+                }
+                _ => "Usage: /mute <mint|creator> <duration>, e.g. /mute <mint> 6h (s, m, h or d)".to_string(),
+            };
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+        Command::Unmute(address) => {
+            let reply = if MuteList::shared().unmute(address.trim()) {
+                audit_log::record(AuditAction::ConfigChanged, "telegram", json!({ "setting": "unmute", "address": address.trim() }));
+                format!("🔔 Unmuted {}", address.trim())
+            } else {
+                format!("{} is not muted", address.trim())
+            };
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+        Command::Mutes => {
+            let mutes = MuteList::shared().active();
+            let reply = if mutes.is_empty() {
+                "No active mutes.".to_string()
+            } else {
+                let now = chrono::Utc::now().timestamp();
+                mutes.iter()
+                    .map(|mute| format!("🔇 {} ({} min left)", mute.address, (mute.until - now).max(0) / 60))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+        Command::ScoreExplain(mint) => {
+            let reply = match Pubkey::from_str(mint.trim()) {
+                Ok(mint) => {
+                    let mint = mint.to_string();
+                    risk_score::format_explanation(&mint, &risk_score::get_factors(&mint), &risk_score::weights())
+                }
+                Err(_) => "Usage: /score_explain <mint>".to_string(),
+            };
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+        Command::FirstBuyers(mint) => {
+            let monitor = match &ctx.monitor {
+                Some(monitor) => monitor,
+                None => return Ok(()),
+            };
+            let reply = match Pubkey::from_str(mint.trim()) {
+                Ok(mint) => monitor.first_buyers(&mint)
+                    .unwrap_or_else(|| format!("No first buyers recorded for {}; only launches seen from the start are captured.", mint)),
+                Err(_) => "Usage: /firstbuyers <mint>".to_string(),
+            };
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+        Command::Deployer(creator) => {
+            let monitor = match &ctx.monitor {
+                Some(monitor) => monitor,
+                None => return Ok(()),
+            };
+            let reply = match Pubkey::from_str(creator.trim()) {
+                Ok(creator) => monitor.deployer(&creator),
+                Err(_) => "Usage: /deployer <address>".to_string(),
+            };
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+        Command::Timeline(mint) => {
+            let monitor = match &ctx.monitor {
+                Some(monitor) => monitor,
+                None => return Ok(()),
+            };
+            let reply = match Pubkey::from_str(mint.trim()) {
+                Ok(mint) => monitor.timeline(&mint),
+                Err(_) => "Usage: /timeline <mint>".to_string(),
+            };
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+        Command::Chart(args) => {
+            let mut args = args.split_whitespace();
+            let mint = match args.next().map(Pubkey::from_str) {
+                Some(Ok(mint)) => mint,
+                _ => {
+                    bot.send_message(msg.chat.id, "Usage: /chart <mint> [15m|1h|4h|1d]").await?;
+                    return Ok(());
+                }
+            };
+            let window = match args.next() {
+                Some(window) => match mute_list::parse_duration(window) {
+                    Some(window) => window,
+                    None => {
+                        bot.send_message(msg.chat.id, "Usage: /chart <mint> [15m|1h|4h|1d]").await?;
+                        return Ok(());
+                    }
+                },
+                None => chrono::Duration::hours(1),
+            };
+            match chart::render_history_chart(&PriceHistory::shared(), &mint.to_string(), window, chrono::Utc::now()) {
+                Ok((png, caption)) => {
+                    bot.send_photo(msg.chat.id, InputFile::memory(png).file_name("chart.png")).caption(caption).await?;
+                }
+                Err(e) => {
+                    bot.send_message(msg.chat.id, e.to_string()).await?;
+                }
+            }
+        }
+        Command::Stats(mint) => {
+            let monitor = match &ctx.monitor {
+                Some(monitor) => monitor,
+                None => return Ok(()),
+            };
+            let reply = match Pubkey::from_str(mint.trim()) {
+                Ok(mint) => monitor.stats(&mint).await
+                    .unwrap_or_else(|| format!("{} is not tracked; stats cover tokens the monitor has seen trade.", mint)),
+                Err(_) => "Usage: /stats <mint>".to_string(),
+            };
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+        Command::Audit(mint) => {
+            let monitor = match &ctx.monitor {
+                Some(monitor) => monitor,
+                None => return Ok(()),
+            };
+            match Pubkey::from_str(mint.trim()) {
+                Ok(mint) => {
+                    bot.send_message(msg.chat.id, format!("🔍 Auditing {}…", mint)).await?;
+                    bot.send_message(msg.chat.id, monitor.audit(&mint).await).await?;
+                }
+                Err(_) => {
+                    bot.send_message(msg.chat.id, "Usage: /audit <mint>").await?;
+                }
+            }
+        }
+        Command::Feedback => {
+            let summary = match &ctx.monitor {
+                Some(monitor) => monitor.feedback_summary(),
+                None => return Ok(()),
+            };
+            bot.send_message(msg.chat.id, format!("🗳️ Alert feedback\n\n{}", summary)).await?;
+        }
+        Command::Learn(topic) => {
+            let lessons = LessonSessions::shared();
+            if !lessons.enabled() {
+                bot.send_message(msg.chat.id, "Lessons are turned off (LESSONS_ENABLED=false).").await?;
+                return Ok(());
+            }
+            let reply = if topic.trim().is_empty() {
+                Some(lessons.topics())
+            } else {
+                match (topic.parse::<Topic>(), msg.from()) {
+                    (Ok(topic), Some(user)) => lessons.handle(user.id.0, LessonAction::Menu(topic)),
+                    (Err(e), _) => Some(LessonReply { text: e.to_string(), buttons: Vec::new() }),
+                    (Ok(_), None) => None,
+                }
+            };
+            if let Some(reply) = reply {
+                send_lesson(&bot, msg.chat.id, reply).await?;
+            }
+        }
+        Command::Pause(reason) => {
+            let control = TradingControl::shared();
+            let reply = if control.pause("telegram", Some(reason)) {
+                audit_log::record(AuditAction::Paused, "telegram", json!({ "state": control.state() }));
+                ctx.logger.log(control.paused_reason().unwrap_or_default().yellow().to_string());
+                "⏸️ New buys paused. Monitoring and selling of open positions continue; /resume to buy again.".to_string()
+            } else {
+                format!("Already paused: {}", control.paused_reason().unwrap_or_default())
+            };
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+        Command::Resume => {
+            let reply = match TradingControl::shared().resume() {
+                Some(pause) => {
+                    audit_log::record(AuditAction::Resumed, "telegram", json!({ "lifted": pause }));
+                    let minutes = (chrono::Utc::now().timestamp() - pause.since) / 60;
+                    ctx.logger.log(format!("Buys resumed after {} min", minutes).green().to_string());
+                    format!("▶️ Buying resumed (paused for {} min).", minutes)
+                }
+                None => "Buys are not paused.".to_string(),
+            };
+            bot.send_message(msg.chat.id, reply).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Import a watchlist or wallet list document sent with `/import_watchlist` or `/import_wallets`
+/// as its caption
+async fn handle_document(bot: Bot, msg: Message, ctx: CommandContext) -> ResponseResult<()> {
+    if msg.chat.id != ctx.chat_id || ctx.monitor.is_none() {
+        return Ok(());
+    }
+    let document = match msg.document() {
+        Some(document) => document,
+        None => return Ok(()),
+    };
+    if document.file.size > MAX_IMPORT_BYTES {
+        bot.send_message(msg.chat.id, "Import file is too large (max 1 MB).").await?;
+        return Ok(());
+    }
+
+    let file = bot.get_file(document.file.id.clone()).await?;
+    let mut content = Vec::new();
+    if let Err(e) = bot.download_file(&file.path, &mut content).await {
+        ctx.logger.log(format!("Failed to download import file: {}", e).red().to_string());
+        bot.send_message(msg.chat.id, "Could not download the import file.").await?;
+        return Ok(());
+    }
+
+    let content = String::from_utf8_lossy(&content);
+    let reply = if msg.caption().is_some_and(|c| c.trim_start().starts_with("/import_wallets")) {
+        import_wallets(&ctx, &content)
+    } else {
+        import(&ctx, &content).await
+    };
+    bot.send_message(msg.chat.id, reply).await?;
+    Ok(())
+}
+
+/// "Watching" button pressed on an alert: schedule follow-ups for the token. Lesson and rating
+/// buttons are passed on to `handle_lesson` and `handle_rating`.
+async fn handle_callback(bot: Bot, query: CallbackQuery, ctx: CommandContext) -> ResponseResult<()> {
+    if let Some(action) = query.data.as_deref().and_then(LessonAction::parse) {
+        return handle_lesson(bot, query, action, ctx).await;
+    }
+    if let Some(rating) = query.data.as_deref().and_then(Rating::parse) {
+        return handle_rating(bot, query, rating, ctx).await;
+    }
+    let from_alert_chat = query.message.as_ref().map(|m| m.chat.id == ctx.chat_id).unwrap_or(false);
+    let mint = query.data.as_deref()
+        .and_then(|data| data.strip_prefix(WATCH_CALLBACK_PREFIX))
+        .and_then(|mint| Pubkey::from_str(mint).ok());
+    let mint = match mint {
+        Some(mint) if from_alert_chat => mint,
+        _ => {
+            bot.answer_callback_query(query.id).await?;
+            return Ok(());
+        }
+    };
+
+    let watching = match &ctx.monitor {
+        Some(monitor) => monitor.watch_token(mint).await,
+        None => false,
+    };
+    let reply = if watching {
+        ctx.logger.log(format!("Watching {} for follow-ups", mint));
+        "👀 Watching - follow-ups will show what happened since this alert"
+    } else {
+        "Already watching this token (or it is no longer tracked)"
+    };
+    bot.answer_callback_query(query.id).text(reply).await?;
+    Ok(())
+}
+
+/// "👍 Useful" / "👎 Noise" pressed on an alert: count the rating towards its signal type
+async fn handle_rating(bot: Bot, query: CallbackQuery, rating: Rating, ctx: CommandContext) -> ResponseResult<()> {
+    let from_alert_chat = query.message.as_ref().map(|m| m.chat.id == ctx.chat_id).unwrap_or(false);
+    let monitor = match &ctx.monitor {
+        Some(monitor) if from_alert_chat => monitor,
+        _ => {
+            bot.answer_callback_query(query.id).await?;
+            return Ok(());
+        }
+    };
+
+    let reply = match monitor.rate_alert(query.from.id.0, &rating).await {
+        FeedbackOutcome::Duplicate => "You already rated this alert".to_string(),
+        FeedbackOutcome::Recorded => "Thanks, rating recorded".to_string(),
+        FeedbackOutcome::Raised { raises, .. } => {
+            ctx.logger.log(format!("{} alerts rated mostly noise, threshold raised ({}x)", rating.signal_type, raises).yellow().to_string());
+            format!("Thanks - {} alerts were mostly rated noise, so they now need a stronger signal", rating.signal_type)
+        }
+    };
+    bot.answer_callback_query(query.id).text(reply).await?;
+    Ok(())
+}
+
+/// Lesson button pressed: send the next step of the pressing user's lesson
+async fn handle_lesson(bot: Bot, query: CallbackQuery, action: LessonAction, ctx: CommandContext) -> ResponseResult<()> {
+    let lessons = LessonSessions::shared();
+    let chat_id = match query.message.as_ref().map(|m| m.chat.id) {
+        Some(chat_id) if chat_id == ctx.chat_id && lessons.enabled() => chat_id,
+        _ => {
+            bot.answer_callback_query(query.id).await?;
+            return Ok(());
+        }
+    };
+
+    match lessons.handle(query.from.id.0, action) {
+        Some(reply) => {
+            bot.answer_callback_query(query.id).await?;
+            send_lesson(&bot, chat_id, reply).await?;
+        }
+        None => {
+            bot.answer_callback_query(query.id)
+                .text("This question was already answered or is someone else's; tap ❓ Quiz for your own")
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn send_lesson(bot: &Bot, chat_id: ChatId, reply: LessonReply) -> ResponseResult<()> {
+    if reply.buttons.is_empty() {
+        bot.send_message(chat_id, reply.text).await?;
+        return Ok(());
+    }
+    let keyboard = InlineKeyboardMarkup::new(reply.buttons.into_iter().map(|row| {
+        row.into_iter()
+            .map(|(label, action)| InlineKeyboardButton::callback(label, action.callback_data()))
+            .collect::<Vec<_>>()
+    }));
+    bot.send_message(chat_id, reply.text).reply_markup(keyboard).await?;
+    Ok(())
+}
+
+async fn import(ctx: &CommandContext, content: &str) -> String {
+    let parsed = parse_watchlist(content);
+    let total = parsed.entries.len();
+    let added = match &ctx.monitor {
+        Some(monitor) => monitor.import_watchlist(parsed.entries).await,
+        None => 0,
+    };
+    ctx.logger.log(format!("Imported watchlist: {} new, {} already tracked", added, total - added));
+
+    let mut reply = format!(
+        "📋 Watchlist imported\nNew tokens: {}\nAlready tracked (now pinned): {}",
+        added,
+        total - added
+    );
+    if !parsed.invalid.is_empty() {
+        reply.push_str(&format!("\nSkipped {} invalid entries: {}", parsed.invalid.len(), parsed.invalid.join(", ")));
+    }
+    reply
+}
+
+fn import_wallets(ctx: &CommandContext, content: &str) -> String {
+    let parsed = parse_wallets(content);
+    let invalid = parsed.invalid.clone();
+    let (added, updated) = match &ctx.monitor {
+        Some(monitor) => monitor.import_wallets(parsed),
+        None => (0, 0),
+    };
+    ctx.logger.log(format!("Imported wallets: {} new, {} updated", added, updated));
+
+    let mut reply = format!("👥 Wallets imported\nNew wallets: {}\nUpdated labels/groups: {}", added, updated);
+    if !invalid.is_empty() {
+        reply.push_str(&format!("\nSkipped {} invalid entries: {}", invalid.len(), invalid.join(", ")));
+    }
+    reply
+}
+
+/// Start answering Telegram commands if Telegram is configured and TELEGRAM_COMMANDS_ENABLED is not
+/// false. The monitor passes itself for the watchlist commands; the trading bot passes None.
+pub async fn start_telegram_commands(
+    monitor: Option<Arc<EducationalMonitor>>,
+    cancel_token: CancellationToken,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let enabled = std::env::var("TELEGRAM_COMMANDS_ENABLED")
+        .ok()
+        .and_then(|v| v.to_lowercase().parse::<bool>().ok())
+        .unwrap_or(true);
+    if !enabled {
+        return None;
+    }
+    let bot_token = std::env::var("TELEGRAM_BOT_TOKEN").ok()?;
+    let chat_id = std::env::var("TELEGRAM_CHAT_ID").ok()?.parse::<i64>().ok()?;
+
+    let bot = Bot::new(bot_token);
+    let ctx = CommandContext {
+        monitor,
+        chat_id: ChatId(chat_id),
+        logger: Logger::new("[TELEGRAM-COMMANDS] => ".bright_blue().to_string()),
+    };
+    if let Err(e) = bot.set_my_commands(Command::bot_commands()).await {
+        ctx.logger.log(format!("Failed to register bot commands: {}", e).yellow().to_string());
+    }
+
+    let handler = Update::filter_message()
+        .branch(dptree::entry().filter_command::<Command>().endpoint(handle_command))
+        .branch(
+            dptree::filter(|msg: Message| {
+                msg.document().is_some()
+                    && msg.caption()
+                        .map(|c| c.trim_start().starts_with("/import_watchlist") || c.trim_start().starts_with("/import_wallets"))
+                        .unwrap_or(false)
+            })
+            .endpoint(handle_document),
+        );
+    let handler = dptree::entry()
+        .branch(handler)
+        .branch(Update::filter_callback_query().endpoint(handle_callback));
+
+    let mut dispatcher = Dispatcher::builder(bot, handler)
+        .dependencies(dptree::deps![ctx])
+        .build();
+    let shutdown_token = dispatcher.shutdown_token();
+
+    Some(tokio::spawn(async move {
+        tokio::spawn(async move {
+            cancel_token.cancelled().await;
+            if let Ok(shutdown) = shutdown_token.shutdown() {
+                shutdown.await;
+            }
+        });
+        dispatcher.dispatch().await;
+    }))
+}
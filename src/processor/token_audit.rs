@@ -0,0 +1,382 @@
+/*!
+# Token Audit
+
+An on-demand audit of any mint for `/audit <mint>`, whether or not the monitor tracks it. It
+reads the chain directly and combines what it finds with what the monitor recorded:
+
+- Authorities: whether the mint and freeze authorities are still set.
+- Liquidity: pump.fun bonding curve progress or migration, and liquidity removed since tracking.
+- Holders: the largest holders and how concentrated the supply is.
+- Deployer: the creator's recorded launches and how they ended (see `deployer_history`).
+- Sellability: a simulated sell by the largest wallet holder (see `copy_guard`).
+
+Each check yields findings graded red (avoid), yellow (caution) or green; the verdict is the
+worst of them. A check that cannot run (RPC error, no route) is reported as such and does not
+fail the audit. The chain reads of an audit run concurrently, and audits of a mint requested
+while one is running wait for it instead of repeating its RPC calls.
+*/
+
+use std::fmt::Write as _;
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use solana_sdk::program_option::COption;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::StateWithExtensions;
+
+use crate::dex::pump_fun::{bonding_curve_progress, get_pda, PUMP_FUN_PROGRAM};
+use crate::library::single_flight::{FlightStats, SingleFlight};
+use crate::processor::copy_guard::{JupiterSellSimulator, SellSimulation, SellSimulator};
+use crate::processor::deployer_history::{DeployerHistory, DeployerStats};
+use crate::processor::risk_score;
+
+/// Largest holders listed in the report
+const LISTED_HOLDERS: usize = 10;
+/// A single wallet holding more than this share of supply (%) is a red flag
+const MAX_HOLDER_PCT: f64 = 20.0;
+/// The ten largest wallets holding more than this share of supply (%) calls for caution
+const MAX_TOP10_PCT: f64 = 50.0;
+/// Risk score (0-100) from which the recorded on-chain signals are a red flag
+const MAX_RISK_SCORE: f64 = 60.0;
+
+/// The mint account's state
+#[derive(Clone, Debug, PartialEq)]
+pub struct MintState {
+    pub token_2022: bool,
+    /// Raw supply
+    pub supply: u64,
+    pub decimals: u8,
+    pub mint_authority: Option<Pubkey>,
+    pub freeze_authority: Option<Pubkey>,
+}
+
+/// A token account among the largest holders
+#[derive(Clone, Debug, PartialEq)]
+pub struct Holder {
+    pub token_account: Pubkey,
+    pub owner: Pubkey,
+    /// Raw amount
+    pub amount: u64,
+}
+
+/// The token's pump.fun bonding curve
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurveState {
+    pub address: Pubkey,
+    pub progress: f64,
+    pub complete: bool,
+    pub creator: Option<Pubkey>,
+}
+
+/// Chain reads behind an audit
+#[async_trait]
+pub trait AuditSource: Send + Sync {
+    /// None when the account does not exist or is not a token mint
+    async fn mint_state(&self, mint: &Pubkey) -> Result<Option<MintState>>;
+
+    /// Largest token accounts of the mint, largest first
+    async fn largest_holders(&self, mint: &Pubkey) -> Result<Vec<Holder>>;
+
+    /// None for tokens not launched on pump.fun
+    async fn bonding_curve(&self, mint: &Pubkey) -> Result<Option<CurveState>>;
+}
+
+pub struct RpcAuditSource {
+    rpc_client: Arc<RpcClient>,
+}
+
+impl RpcAuditSource {
+    pub fn new(rpc_client: Arc<RpcClient>) -> Self {
+        Self { rpc_client }
+    }
+}
+
+#[async_trait]
+impl AuditSource for RpcAuditSource {
+    async fn mint_state(&self, mint: &Pubkey) -> Result<Option<MintState>> {
+        let account = match self.rpc_client.get_account_with_commitment(mint, CommitmentConfig::confirmed()).await?.value {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+        let token_2022 = account.owner == spl_token_2022::id();
+        if !token_2022 && account.owner != spl_token::id() {
+            return Ok(None);
+        }
+        // Classic mints share Token-2022's base layout, without extensions
+        let state = match StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data) {
+            Ok(state) => state.base,
+            Err(_) => return Ok(None),
+        };
+        let authority = |authority: COption<Pubkey>| Option::<Pubkey>::from(authority);
+        Ok(Some(MintState {
+            token_2022,
+            supply: state.supply,
+            decimals: state.decimals,
+            mint_authority: authority(state.mint_authority),
+            freeze_authority: authority(state.freeze_authority),
+        }))
+    }
+
+    async fn largest_holders(&self, mint: &Pubkey) -> Result<Vec<Holder>> {
+        let largest = self.rpc_client.get_token_largest_accounts(mint).await?;
+        let mut holders: Vec<Holder> = largest.iter()
+            .filter_map(|balance| Some(Holder {
+                token_account: Pubkey::from_str(&balance.address).ok()?,
+                owner: Pubkey::default(),
+                amount: balance.amount.amount.parse().ok()?,
+            }))
+            .collect();
+        let addresses: Vec<Pubkey> = holders.iter().map(|h| h.token_account).collect();
+        let accounts = self.rpc_client.get_multiple_accounts(&addresses).await?;
+        for (holder, account) in holders.iter_mut().zip(accounts) {
+            // The owner follows the mint in every token account layout
+            if let Some(owner) = account.and_then(|a| a.data.get(32..64).and_then(|o| Pubkey::try_from(o).ok())) {
+                holder.owner = owner;
+            }
+        }
+        Ok(holders)
+    }
+
+    async fn bonding_curve(&self, mint: &Pubkey) -> Result<Option<CurveState>> {
+        let address = get_pda(mint, &Pubkey::from_str(PUMP_FUN_PROGRAM)?)?;
+        let account = match self.rpc_client.get_account_with_commitment(&address, CommitmentConfig::confirmed()).await?.value {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+        Ok(parse_bonding_curve(address, &account.data))
+    }
+}
+
+/// Decode a bonding curve account: discriminator, virtual token and SOL reserves, real token and
+/// SOL reserves, total supply, completion flag and, on curves created since creator fees, the
+/// creator
+pub fn parse_bonding_curve(address: Pubkey, data: &[u8]) -> Option<CurveState> {
+    let virtual_token_reserves = u64::from_le_bytes(data.get(8..16)?.try_into().ok()?);
+    let complete = *data.get(48)? != 0;
+    let creator = data.get(49..81)
+        .and_then(|c| Pubkey::try_from(c).ok())
+        .filter(|c| *c != Pubkey::default());
+    Some(CurveState {
+        address,
+        progress: if complete { 100.0 } else { bonding_curve_progress(virtual_token_reserves) },
+        complete,
+        creator,
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Green,
+    Yellow,
+    Red,
+}
+
+impl Severity {
+    fn icon(&self) -> &'static str {
+        match self {
+            Severity::Green => "🟢",
+            Severity::Yellow => "🟡",
+            Severity::Red => "🔴",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    pub section: &'static str,
+    pub severity: Severity,
+    pub text: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct AuditReport {
+    pub mint: Pubkey,
+    pub mint_state: MintState,
+    pub curve: Option<CurveState>,
+    /// Largest holders with their share of supply (%)
+    pub holders: Vec<(Holder, f64)>,
+    pub creator: Option<String>,
+    pub deployer: Option<DeployerStats>,
+    pub sell: SellSimulation,
+    /// Score of the on-chain signals recorded while tracking; 0 for untracked tokens
+    pub risk_score: f64,
+    pub findings: Vec<Finding>,
+}
+
+impl AuditReport {
+    /// The worst finding
+    pub fn verdict(&self) -> Severity {
+        self.findings.iter().map(|f| f.severity).max().unwrap_or(Severity::Green)
+    }
+
+    pub fn red_flags(&self) -> usize {
+        self.findings.iter().filter(|f| f.severity == Severity::Red).count()
+    }
+}
+
+pub struct TokenAuditor {
+    source: Arc<dyn AuditSource>,
+    simulator: Arc<dyn SellSimulator>,
+    flights: SingleFlight<Pubkey, Result<AuditReport, String>>,
+}
+
+impl TokenAuditor {
+    pub fn new(source: Arc<dyn AuditSource>, simulator: Arc<dyn SellSimulator>) -> Self {
+        Self { source, simulator, flights: SingleFlight::new() }
+    }
+
+    pub fn from_rpc(rpc_client: Arc<RpcClient>) -> Self {
+        Self::new(Arc::new(RpcAuditSource::new(rpc_client.clone())), Arc::new(JupiterSellSimulator::new(rpc_client)))
+    }
+
+    /// Run every check on `mint`; fails only when the mint cannot be read or is not a token.
+    /// Audits of a mint requested while one is running share its report.
+    pub async fn audit(&self, mint: &Pubkey, deployers: &DeployerHistory) -> Result<AuditReport> {
+        self.flights
+            .run(*mint, || async { self.run_audit(mint, deployers).await.map_err(|e| e.to_string()) })
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+
+    /// Audits requested and audits actually run
+    pub fn stats(&self) -> FlightStats {
+        self.flights.stats()
+    }
+
+    async fn run_audit(&self, mint: &Pubkey, deployers: &DeployerHistory) -> Result<AuditReport> {
+        // The reads are independent, so they go out together
+        let (mint_state, curve, holders) = tokio::join!(
+            self.source.mint_state(mint),
+            self.source.bonding_curve(mint),
+            self.source.largest_holders(mint),
+        );
+        let mint_state = mint_state?.ok_or_else(|| anyhow!("{} is not a token mint", mint))?;
+        let mut findings = Vec::new();
+        let mut finding = |section: &'static str, severity: Severity, text: String| {
+            findings.push(Finding { section, severity, text });
+        };
+
+        match mint_state.mint_authority {
+            Some(authority) => finding("Authorities", Severity::Red, format!("Mint authority is live ({}): supply can be inflated", authority)),
+            None => finding("Authorities", Severity::Green, "Mint authority revoked".to_string()),
+        }
+        match mint_state.freeze_authority {
+            Some(authority) => finding("Authorities", Severity::Red, format!("Freeze authority is live ({}): holders can be frozen", authority)),
+            None => finding("Authorities", Severity::Green, "Freeze authority revoked".to_string()),
+        }
+
+        let curve = match curve {
+            Ok(curve) => curve,
+            Err(e) => {
+                finding("Liquidity", Severity::Yellow, format!("Bonding curve could not be read: {}", e));
+                None
+            }
+        };
+        match &curve {
+            Some(curve) if curve.complete => finding("Liquidity", Severity::Green, "Bonding curve complete, migrated to an AMM pool".to_string()),
+            Some(curve) => finding("Liquidity", Severity::Green, format!("On the pump.fun bonding curve, {:.1}% complete", curve.progress)),
+            None => finding("Liquidity", Severity::Yellow, "Not a pump.fun launch: LP lock or burn not verified".to_string()),
+        }
+        let factors = risk_score::get_factors(&mint.to_string());
+        if factors.liquidity_removed_pct > 0.0 {
+            finding("Liquidity", Severity::Red, format!("{:.1}% of pool liquidity removed while tracked", factors.liquidity_removed_pct));
+        }
+
+        let holders = match holders {
+            Ok(holders) => holders,
+            Err(e) => {
+                finding("Holders", Severity::Yellow, format!("Largest holders could not be read: {}", e));
+                Vec::new()
+            }
+        };
+        let share = |amount: u64| if mint_state.supply > 0 { amount as f64 / mint_state.supply as f64 * 100.0 } else { 0.0 };
+        let curve_address = curve.as_ref().map(|c| c.address);
+        // The curve holds unsold supply, not a wallet's bag
+        let wallets: Vec<&Holder> = holders.iter().filter(|h| Some(h.owner) != curve_address).collect();
+        if let Some(top) = wallets.first() {
+            let top_pct = share(top.amount);
+            let top10_pct: f64 = wallets.iter().take(10).map(|h| share(h.amount)).sum();
+            if top_pct > MAX_HOLDER_PCT {
+                finding("Holders", Severity::Red, format!("Largest wallet holds {:.1}% of supply", top_pct));
+            } else if top10_pct > MAX_TOP10_PCT {
+                finding("Holders", Severity::Yellow, format!("Top 10 wallets hold {:.1}% of supply", top10_pct));
+            } else {
+                finding("Holders", Severity::Green, format!("Top 10 wallets hold {:.1}% of supply", top10_pct));
+            }
+        }
+
+        let creator = curve.as_ref()
+            .and_then(|c| c.creator)
+            .map(|c| c.to_string())
+            .or_else(|| deployers.creator_of(&mint.to_string()));
+        let deployer = creator.as_ref().map(|creator| DeployerStats::from_launches(&deployers.launches_of(creator)));
+        match &deployer {
+            Some(stats) if stats.rugged > 0 => finding("Deployer", Severity::Red, format!("Creator pulled liquidity on {} of {} recorded launches", stats.rugged, stats.launches)),
+            Some(stats) if stats.launches > 0 => finding("Deployer", Severity::Green, format!("Creator has {} recorded launches, none rugged", stats.launches)),
+            Some(_) => finding("Deployer", Severity::Yellow, "No launches of this creator recorded".to_string()),
+            None => finding("Deployer", Severity::Yellow, "Creator unknown".to_string()),
+        }
+
+        // A wallet (an address on the curve) can sign; pools and PDAs cannot
+        let sell = match wallets.iter().find(|h| h.owner.is_on_curve() && h.amount > 0) {
+            Some(holder) => self.simulator.simulate_sell(mint, &holder.owner, holder.amount).await,
+            None => SellSimulation::Inconclusive("no wallet holder to simulate with".to_string()),
+        };
+        match &sell {
+            SellSimulation::Sellable => finding("Sellability", Severity::Green, "Simulated sell succeeded".to_string()),
+            SellSimulation::Failed(reason) => finding("Sellability", Severity::Red, format!("Simulated sell failed: {}", reason)),
+            SellSimulation::Inconclusive(reason) => finding("Sellability", Severity::Yellow, format!("Sell not simulated: {}", reason)),
+        }
+
+        let risk_score = factors.score();
+        if risk_score >= MAX_RISK_SCORE {
+            finding("Risk score", Severity::Red, format!("Recorded on-chain signals score {:.0}/100", risk_score));
+        }
+
+        let holders = holders.into_iter().take(LISTED_HOLDERS).map(|h| {
+            let pct = share(h.amount);
+            (h, pct)
+        }).collect();
+        Ok(AuditReport { mint: *mint, mint_state, curve, holders, creator, deployer, sell, risk_score, findings })
+    }
+}
+
+/// Reply to `/audit`
+pub fn format_audit(report: &AuditReport) -> String {
+    let mut out = String::new();
+    let verdict = match report.verdict() {
+        Severity::Red => format!("HIGH RISK ({} red flags)", report.red_flags()),
+        Severity::Yellow => "CAUTION".to_string(),
+        Severity::Green => "NO RED FLAGS FOUND".to_string(),
+    };
+    let _ = writeln!(out, "🔍 Audit of {}\n{} {}\n", report.mint, report.verdict().icon(), verdict);
+    let _ = writeln!(
+        out,
+        "{}, supply {:.0}, {} decimals",
+        if report.mint_state.token_2022 { "Token-2022" } else { "SPL Token" },
+        report.mint_state.supply as f64 / 10f64.powi(report.mint_state.decimals as i32),
+        report.mint_state.decimals
+    );
+    let mut section = "";
+    for finding in &report.findings {
+        if finding.section != section {
+            section = finding.section;
+            let _ = writeln!(out, "\n{}", section);
+        }
+        let _ = writeln!(out, "{} {}", finding.severity.icon(), finding.text);
+    }
+    if !report.holders.is_empty() {
+        let _ = writeln!(out, "\nLargest holders");
+        for (holder, pct) in &report.holders {
+            let label = if report.curve.as_ref().is_some_and(|c| c.address == holder.owner) { " (bonding curve)" } else { "" };
+            let _ = writeln!(out, "• {} {:.2}%{}", holder.owner, pct, label);
+        }
+    }
+    if let Some(creator) = &report.creator {
+        let _ = writeln!(out, "\nCreator: {} (/deployer for the launch history)", creator);
+    }
+    out
+}
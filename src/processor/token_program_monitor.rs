@@ -0,0 +1,667 @@
+/*!
+# Token Program Monitor
+
+Watches SPL Token / Token-2022 instructions that touch the tokens we hold or focus on, through a
+Yellowstone transaction subscription filtered on those mints. The subscription is refreshed as
+tokens are added to or removed from tracking.
+
+- `MintTo` / `Burn` (and their `Checked` variants) update the tracked circulating supply.
+  Large burns are alerted (often a marketed event), and any mint after tracking started is
+  alerted as critical since it usually means a live authority inflating supply. Both feed the
+  token's risk score.
+- `FreezeAccount` fires a critical alert: a live freeze authority typically makes the token
+  unsellable, and if our own token account is frozen the position cannot be exited.
+- Plain `Transfer` / `TransferChecked` of these mints in transactions that do not touch a DEX
+  (airdrops, team distributions, wallet-to-wallet shuffling) are kept in the token's transfer
+  feed. Transfers above `TRANSFER_ALERT_PCT` of supply are alerted, as is a wallet handing the
+  token out to `DISTRIBUTION_ALERT_RECIPIENTS` wallets; supply moved by the creator feeds the
+  risk score.
+- Transfers from a token's creator or a tracked wallet (`COPY_TRADING_TARGET_ADDRESS`, whose
+  transactions are subscribed too) into a known exchange address are alerted as an exit signal
+  (see `cex_deposits`).
+- PumpSwap liquidity events on these tokens: large withdrawals are alerted and feed the risk
+  score, and a new pool being created for a held token is reported.
+
+## Environment Variables
+
+- `TOKEN_MONITOR_ENABLED`: Enable the monitor (default: `true`)
+- `SUPPLY_BURN_ALERT_PCT`: Burn size, as % of supply, that triggers an alert (default: `1.0`)
+- `LIQUIDITY_REMOVAL_ALERT_PCT`: Share of pool SOL withdrawn at once that triggers an alert (default: `10.0`)
+- `TRANSFER_ALERT_PCT`: Plain transfer size, as % of supply, that triggers an alert (default: `1.0`)
+- `DISTRIBUTION_ALERT_RECIPIENTS`: Wallets one sender has transferred the token to before it is
+  alerted as a distribution (default: `10`)
+*/
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use colored::Colorize;
+use dashmap::DashMap;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address;
+use tokio_util::sync::CancellationToken;
+use yellowstone_grpc_proto::geyser::{
+    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
+    SubscribeRequestFilterTransactions, SubscribeUpdateTransaction,
+};
+
+use crate::common::config::AppState;
+use crate::common::logger::Logger;
+use crate::library::geyser_subscription::{run_reconnecting, GeyserSubscription};
+use crate::processor::cex_deposits::CexRegistry;
+use crate::processor::mute_list;
+use crate::processor::risk_score;
+use crate::processor::sniper_bot::{BOUGHT_TOKEN_LIST, FOCUS_TOKEN_LIST};
+use crate::processor::telegram_alerts::TelegramAlertSystem;
+use crate::processor::transaction_parser::{
+    dex_program_type, extract_liquidity_events, prefetch_lookup_tables, transaction_account_keys, LiquidityEventInfo,
+    SwapType,
+};
+
+// SPL token instruction tags (identical for Token-2022)
+const TRANSFER: u8 = 3;
+const MINT_TO: u8 = 7;
+const BURN: u8 = 8;
+const FREEZE_ACCOUNT: u8 = 10;
+const MINT_TO_CHECKED: u8 = 14;
+const TRANSFER_CHECKED: u8 = 12;
+const BURN_CHECKED: u8 = 15;
+
+/// Plain transfers kept per token for its feed
+const RECENT_TRANSFERS: usize = 50;
+
+const SUBSCRIPTION_REFRESH: Duration = Duration::from_secs(10);
+
+lazy_static::lazy_static! {
+    /// Supply tracked per mint since it entered monitoring
+    pub static ref TOKEN_SUPPLY: Arc<DashMap<String, SupplyInfo>> = Arc::new(DashMap::new());
+    /// Plain (non-swap) transfers per mint
+    pub static ref TOKEN_TRANSFERS: Arc<DashMap<String, TransferActivity>> = Arc::new(DashMap::new());
+}
+
+#[derive(Clone, Debug)]
+pub struct SupplyInfo {
+    pub initial_supply: u64,
+    pub supply: u64,
+    pub minted: u64,
+    pub burned: u64,
+    pub decimals: u8,
+    pub tracked_since: Instant,
+}
+
+/// A token program instruction decoded from a transaction
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenProgramEvent {
+    MintTo { mint: String, amount: u64 },
+    Burn { mint: String, amount: u64 },
+    Freeze { mint: String, account: String },
+    /// `from` and `to` are the owning wallets when the transaction's token balances name them,
+    /// the token accounts otherwise
+    Transfer { mint: String, from: String, to: String, amount: u64 },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransferRecord {
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Plain transfers of one token
+#[derive(Clone, Debug, Default)]
+pub struct TransferActivity {
+    /// Latest transfers, oldest first
+    pub recent: VecDeque<TransferRecord>,
+    pub transfers: u64,
+    pub amount: u64,
+    /// Distinct wallets each sender has transferred to
+    recipients: HashMap<String, HashSet<String>>,
+    /// Senders already alerted as distributing
+    distributors: HashSet<String>,
+}
+
+impl TransferActivity {
+    /// Record a transfer; returns how many wallets the sender has handed the token to when that
+    /// just reached `distribution_recipients`
+    pub fn record(&mut self, record: TransferRecord, distribution_recipients: usize) -> Option<usize> {
+        self.transfers += 1;
+        self.amount = self.amount.saturating_add(record.amount);
+        let recipients = self.recipients.entry(record.from.clone()).or_default();
+        recipients.insert(record.to.clone());
+        let count = recipients.len();
+        let from = record.from.clone();
+        self.recent.push_back(record);
+        while self.recent.len() > RECENT_TRANSFERS {
+            self.recent.pop_front();
+        }
+
+        if distribution_recipients > 0 && count >= distribution_recipients && self.distributors.insert(from) {
+            Some(count)
+        } else {
+            None
+        }
+    }
+
+    /// Distinct wallets `from` has transferred the token to
+    pub fn recipients_of(&self, from: &str) -> usize {
+        self.recipients.get(from).map_or(0, |recipients| recipients.len())
+    }
+}
+
+/// Latest plain transfers of a tracked token, oldest first
+pub fn recent_transfers(mint: &str) -> Vec<TransferRecord> {
+    TOKEN_TRANSFERS.get(mint).map(|activity| activity.recent.iter().cloned().collect()).unwrap_or_default()
+}
+
+/// Decode the token program instructions (top level and inner) of a transaction
+pub fn extract_token_events(txn: &SubscribeUpdateTransaction) -> Vec<TokenProgramEvent> {
+    let mut events = Vec::new();
+    let tx_info = match &txn.transaction {
+        Some(info) => info,
+        None => return events,
+    };
+    let message = match tx_info.transaction.as_ref().and_then(|t| t.message.as_ref()) {
+        Some(message) => message,
+        None => return events,
+    };
+
+    // Full account list: static keys followed by keys loaded from lookup tables
+    let account_keys = transaction_account_keys(txn);
+
+    let token_program = spl_token::id();
+    let token_2022_program = spl_token_2022::id();
+    let key_at = |index: usize| -> Option<String> {
+        account_keys.get(index).map(|k| k.to_string())
+    };
+
+    // Transfers inside swaps are trades, already reported by the DEX parsers
+    let swap = account_keys.iter().any(|key| dex_program_type(key).is_some());
+    // Mint and owner of each token account, from the balances the transaction reports
+    let token_accounts: HashMap<u32, (String, String)> = tx_info.meta.as_ref()
+        .map(|meta| {
+            meta.pre_token_balances.iter()
+                .chain(meta.post_token_balances.iter())
+                .map(|b| (b.account_index, (b.mint.clone(), b.owner.clone())))
+                .collect()
+        })
+        .unwrap_or_default();
+    let owner_or_account = |index: u8| -> Option<String> {
+        token_accounts.get(&(index as u32))
+            .map(|(_, owner)| owner.clone())
+            .filter(|owner| !owner.is_empty())
+            .or_else(|| key_at(index as usize))
+    };
+    let mut decode = |program_id_index: u32, accounts: &[u8], data: &[u8]| {
+        let program_id = match account_keys.get(program_id_index as usize) {
+            Some(key) => *key,
+            None => return,
+        };
+        if program_id.as_ref() != token_program.as_ref() && program_id.as_ref() != token_2022_program.as_ref() {
+            return;
+        }
+        if data.first() == Some(&FREEZE_ACCOUNT) {
+            // FreezeAccount: [account, mint, authority]
+            if let (Some(account), Some(mint)) = (
+                accounts.first().and_then(|i| key_at(*i as usize)),
+                accounts.get(1).and_then(|i| key_at(*i as usize)),
+            ) {
+                events.push(TokenProgramEvent::Freeze { mint, account });
+            }
+            return;
+        }
+        if data.len() < 9 {
+            return;
+        }
+        let amount = u64::from_le_bytes(data[1..9].try_into().unwrap_or_default());
+        // MintTo: [mint, destination, authority]; Burn: [account, mint, owner];
+        // Transfer: [source, destination, authority]; TransferChecked: [source, mint, destination, authority]
+        let event = match data[0] {
+            TRANSFER | TRANSFER_CHECKED if !swap => {
+                let checked = data[0] == TRANSFER_CHECKED;
+                let (source, destination) = (accounts.first().copied(), accounts.get(if checked { 2 } else { 1 }).copied());
+                let mint = if checked {
+                    accounts.get(1).and_then(|i| key_at(*i as usize))
+                } else {
+                    [source, destination].into_iter().flatten()
+                        .find_map(|i| token_accounts.get(&(i as u32)).map(|(mint, _)| mint.clone()))
+                };
+                match (mint, source.and_then(owner_or_account), destination.and_then(owner_or_account)) {
+                    (Some(mint), Some(from), Some(to)) if from != to => Some(TokenProgramEvent::Transfer { mint, from, to, amount }),
+                    _ => None,
+                }
+            }
+            MINT_TO | MINT_TO_CHECKED => accounts
+                .first()
+                .and_then(|i| key_at(*i as usize))
+                .map(|mint| TokenProgramEvent::MintTo { mint, amount }),
+            BURN | BURN_CHECKED => accounts
+                .get(1)
+                .and_then(|i| key_at(*i as usize))
+                .map(|mint| TokenProgramEvent::Burn { mint, amount }),
+            _ => None,
+        };
+        if let Some(event) = event {
+            events.push(event);
+        }
+    };
+
+    for ix in &message.instructions {
+        decode(ix.program_id_index, &ix.accounts, &ix.data);
+    }
+    if let Some(meta) = &tx_info.meta {
+        for inner in &meta.inner_instructions {
+            for ix in &inner.instructions {
+                decode(ix.program_id_index, &ix.accounts, &ix.data);
+            }
+        }
+    }
+
+    events
+}
+
+/// Token program monitor configuration
+pub struct TokenProgramMonitorConfig {
+    pub yellowstone_grpc_http: String,
+    pub yellowstone_grpc_token: String,
+    pub app_state: Arc<AppState>,
+    pub burn_alert_pct: f64,
+    pub liquidity_removal_alert_pct: f64,
+    pub transfer_alert_pct: f64,
+    pub distribution_alert_recipients: usize,
+    /// Wallets whose transfers to exchanges are alerted besides token creators
+    pub tracked_wallets: HashSet<String>,
+}
+
+impl TokenProgramMonitorConfig {
+    pub fn new(
+        yellowstone_grpc_http: String,
+        yellowstone_grpc_token: String,
+        app_state: Arc<AppState>,
+    ) -> Self {
+        let burn_alert_pct = std::env::var("SUPPLY_BURN_ALERT_PCT")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(1.0);
+        let liquidity_removal_alert_pct = std::env::var("LIQUIDITY_REMOVAL_ALERT_PCT")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(10.0);
+        let transfer_alert_pct = std::env::var("TRANSFER_ALERT_PCT")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(1.0);
+        let distribution_alert_recipients = std::env::var("DISTRIBUTION_ALERT_RECIPIENTS")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(10);
+
+        Self {
+            yellowstone_grpc_http,
+            yellowstone_grpc_token,
+            app_state,
+            burn_alert_pct,
+            liquidity_removal_alert_pct,
+            transfer_alert_pct,
+            distribution_alert_recipients,
+            tracked_wallets: HashSet::new(),
+        }
+    }
+}
+
+pub struct TokenProgramMonitor {
+    config: TokenProgramMonitorConfig,
+    telegram: Option<Arc<TelegramAlertSystem>>,
+    logger: Logger,
+}
+
+impl TokenProgramMonitor {
+    pub fn new(config: TokenProgramMonitorConfig, telegram: Option<Arc<TelegramAlertSystem>>) -> Self {
+        Self {
+            config,
+            telegram,
+            logger: Logger::new("[TOKEN-PROGRAM-MONITOR] => ".bright_magenta().to_string()),
+        }
+    }
+
+    /// Run the monitor, reconnecting until cancelled
+    pub async fn start(&self, cancel_token: CancellationToken) -> Result<(), String> {
+        self.logger.log("Starting token program monitor for tracked mints".green().to_string());
+
+        run_reconnecting("Token program subscription", &self.logger, &cancel_token, || self.run_subscription()).await;
+        self.logger.log("Token program monitor received shutdown signal.".yellow().to_string());
+        Ok(())
+    }
+
+    fn tracked_mints() -> HashSet<String> {
+        BOUGHT_TOKEN_LIST.iter().map(|e| e.key().clone())
+            .chain(FOCUS_TOKEN_LIST.iter().map(|e| e.key().clone()))
+            .collect()
+    }
+
+    fn subscription_request(&self, mints: &HashSet<String>) -> SubscribeRequest {
+        SubscribeRequest {
+            transactions: maplit::hashmap! {
+                "TrackedMints".to_owned() => SubscribeRequestFilterTransactions {
+                    vote: Some(false),
+                    failed: Some(false),
+                    signature: None,
+                    account_include: mints.iter().chain(self.config.tracked_wallets.iter()).cloned().collect(),
+                    account_exclude: vec![],
+                    account_required: Vec::<String>::new(),
+                }
+            },
+            commitment: Some(CommitmentLevel::Confirmed as i32),
+            ..Default::default()
+        }
+    }
+
+    async fn run_subscription(&self) -> Result<(), String> {
+        let mut subscription = GeyserSubscription::open(&self.config.yellowstone_grpc_http, &self.config.yellowstone_grpc_token).await?;
+
+        let mut subscribed: HashSet<String> = HashSet::new();
+        // Tracked wallets are watched even before any mint is
+        let mut requested = false;
+        let mut refresh = tokio::time::interval(SUBSCRIPTION_REFRESH);
+
+        loop {
+            tokio::select! {
+                _ = refresh.tick() => {
+                    // Re-sending a request on the same stream replaces the filters
+                    let mints = Self::tracked_mints();
+                    let watching = !mints.is_empty() || !self.config.tracked_wallets.is_empty();
+                    if (mints != subscribed || !requested) && watching {
+                        for mint in mints.difference(&subscribed) {
+                            self.start_tracking_supply(mint).await;
+                        }
+                        subscription.update(self.subscription_request(&mints)).await?;
+                        self.logger.log(format!("Watching token program activity for {} mints", mints.len()).cyan().to_string());
+                        subscribed = mints;
+                        requested = true;
+                    }
+                }
+                update = subscription.next() => {
+                    if let UpdateOneof::Transaction(txn) = update? {
+                        crate::library::slot_lag::observe_stream_slot(txn.slot);
+                        prefetch_lookup_tables(&self.config.app_state.rpc_nonblocking_client, &txn).await;
+                        for event in extract_token_events(&txn) {
+                            self.handle_event(event).await;
+                        }
+                        for event in extract_liquidity_events(&txn) {
+                            if subscribed.contains(&event.mint) {
+                                self.handle_liquidity_event(event).await;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn start_tracking_supply(&self, mint: &str) {
+        if TOKEN_SUPPLY.contains_key(mint) {
+            return;
+        }
+        let mint_pubkey = match Pubkey::from_str(mint) {
+            Ok(pubkey) => pubkey,
+            Err(_) => return,
+        };
+        match self.config.app_state.rpc_nonblocking_client.get_token_supply(&mint_pubkey).await {
+            Ok(supply) => {
+                let amount = supply.amount.parse::<u64>().unwrap_or(0);
+                TOKEN_SUPPLY.insert(mint.to_string(), SupplyInfo {
+                    initial_supply: amount,
+                    supply: amount,
+                    minted: 0,
+                    burned: 0,
+                    decimals: supply.decimals,
+                    tracked_since: Instant::now(),
+                });
+            }
+            Err(e) => {
+                self.logger.log(format!("Failed to fetch supply for {}: {}", mint, e).red().to_string());
+            }
+        }
+    }
+
+    async fn handle_event(&self, event: TokenProgramEvent) {
+        match event {
+            TokenProgramEvent::MintTo { mint, amount } => {
+                let (ui_amount, minted_pct) = match TOKEN_SUPPLY.get_mut(&mint) {
+                    Some(mut info) => {
+                        info.supply = info.supply.saturating_add(amount);
+                        info.minted = info.minted.saturating_add(amount);
+                        (
+                            amount as f64 / 10_f64.powi(info.decimals as i32),
+                            pct_of(info.minted, info.initial_supply),
+                        )
+                    }
+                    None => return,
+                };
+                risk_score::update_factors(&mint, |f| {
+                    f.unexpected_mints += 1;
+                    f.minted_pct = minted_pct;
+                });
+
+                let message = format!(
+                    "🚨 Unexpected mint on {}: {:.2} tokens minted ({:.2}% of tracked supply). Possible inflation attack. Risk score: {:.0}",
+                    mint, ui_amount, minted_pct, risk_score::risk_score(&mint)
+                );
+                self.logger.log(message.red().bold().to_string());
+                self.send_alert("Unexpected Token Mint", &message).await;
+            }
+            TokenProgramEvent::Burn { mint, amount } => {
+                let (ui_amount, burn_pct, burned_pct) = match TOKEN_SUPPLY.get_mut(&mint) {
+                    Some(mut info) => {
+                        let burn_pct = pct_of(amount, info.supply);
+                        info.supply = info.supply.saturating_sub(amount);
+                        info.burned = info.burned.saturating_add(amount);
+                        (
+                            amount as f64 / 10_f64.powi(info.decimals as i32),
+                            burn_pct,
+                            pct_of(info.burned, info.initial_supply),
+                        )
+                    }
+                    None => return,
+                };
+                risk_score::update_factors(&mint, |f| f.burned_pct = burned_pct);
+
+                if burn_pct >= self.config.burn_alert_pct {
+                    let message = format!(
+                        "🔥 Large burn on {}: {:.2} tokens ({:.2}% of supply, {:.2}% burned since tracking)",
+                        mint, ui_amount, burn_pct, burned_pct
+                    );
+                    self.logger.log(message.yellow().to_string());
+                    self.send_alert("Large Token Burn", &message).await;
+                }
+            }
+            TokenProgramEvent::Freeze { mint, account } => {
+                risk_score::update_factors(&mint, |f| f.freeze_events += 1);
+
+                let held = BOUGHT_TOKEN_LIST.contains_key(&mint);
+                let own_account = self.config.app_state.wallet.try_pubkey()
+                    .ok()
+                    .and_then(|wallet| Pubkey::from_str(&mint).ok().map(|m| get_associated_token_address(&wallet, &m)))
+                    .map(|ata| ata.to_string() == account)
+                    .unwrap_or(false);
+
+                let message = format!(
+                    "🧊 CRITICAL: freeze authority of {} froze account {}{}. The token is likely unsellable{}.",
+                    mint,
+                    account,
+                    if own_account { " (OUR token account)" } else { "" },
+                    if held { " - we currently hold this token" } else { "" }
+                );
+                self.logger.log(message.red().bold().to_string());
+                self.send_alert("Freeze Authority Used", &message).await;
+            }
+            TokenProgramEvent::Transfer { mint, from, to, amount } => {
+                self.check_exchange_deposit(&mint, &from, &to, amount).await;
+                let (ui_amount, supply_pct) = match TOKEN_SUPPLY.get(&mint) {
+                    Some(info) => (amount as f64 / 10_f64.powi(info.decimals as i32), pct_of(amount, info.supply)),
+                    None => return,
+                };
+                let from_creator = mute_list::creator_of(&mint).is_some_and(|creator| creator == from);
+                let record = TransferRecord { from: from.clone(), to: to.clone(), amount, at: chrono::Utc::now() };
+                let distributed_to = TOKEN_TRANSFERS.entry(mint.clone()).or_default()
+                    .record(record, self.config.distribution_alert_recipients);
+                if from_creator {
+                    risk_score::update_factors(&mint, |f| f.creator_transferred_pct += supply_pct);
+                }
+                let creator_tag = if from_creator { " (the creator)" } else { "" };
+
+                if let Some(recipients) = distributed_to {
+                    let message = format!(
+                        "📤 {}{} has transferred {} to {} wallets outside any swap: airdrop or team distribution. Risk score: {:.0}",
+                        from, creator_tag, mint, recipients, risk_score::risk_score(&mint)
+                    );
+                    self.logger.log(message.yellow().to_string());
+                    self.send_alert("Token Distribution", &message).await;
+                }
+                if supply_pct >= self.config.transfer_alert_pct {
+                    let message = format!(
+                        "🔀 Large transfer of {}: {:.2} tokens ({:.2}% of supply) from {}{} to {}. Risk score: {:.0}",
+                        mint, ui_amount, supply_pct, from, creator_tag, to, risk_score::risk_score(&mint)
+                    );
+                    self.logger.log(message.yellow().to_string());
+                    self.send_alert("Large Token Transfer", &message).await;
+                } else {
+                    self.logger.log(format!("Transfer of {}: {:.2} tokens from {}{} to {}", mint, ui_amount, from, creator_tag, to));
+                }
+            }
+        }
+    }
+
+    /// Alert when a creator or tracked wallet sends tokens to an exchange, and learn deposit
+    /// addresses from everyone else's sweeps into hot wallets
+    async fn check_exchange_deposit(&self, mint: &str, from: &str, to: &str, amount: u64) {
+        let registry = CexRegistry::shared();
+        let from_creator = mute_list::creator_of(mint).is_some_and(|creator| creator == from);
+        let tracked = self.config.tracked_wallets.contains(from);
+        if !from_creator && !tracked {
+            registry.observe_transfer(from, to);
+            return;
+        }
+        let exchange = match registry.exchange_of(to) {
+            Some(exchange) => exchange,
+            None => return,
+        };
+
+        let amount_text = match TOKEN_SUPPLY.get(mint) {
+            Some(info) => format!(
+                "{:.2} tokens ({:.2}% of supply)",
+                amount as f64 / 10_f64.powi(info.decimals as i32),
+                pct_of(amount, info.supply)
+            ),
+            None => format!("{} base units", amount),
+        };
+        if from_creator {
+            risk_score::update_factors(mint, |f| f.creator_cex_deposits += 1);
+        }
+        let message = format!(
+            "🏦 Exit signal: {} {} sent {} of {} to {} ({}){}. Risk score: {:.0}",
+            if from_creator { "the creator" } else { "tracked wallet" },
+            from,
+            amount_text,
+            mint,
+            exchange.describe(),
+            to,
+            if BOUGHT_TOKEN_LIST.contains_key(mint) { " - we currently hold this token" } else { "" },
+            risk_score::risk_score(mint)
+        );
+        self.logger.log(message.red().bold().to_string());
+        self.send_alert("Exchange Deposit", &message).await;
+    }
+
+    async fn handle_liquidity_event(&self, event: LiquidityEventInfo) {
+        let held = BOUGHT_TOKEN_LIST.contains_key(&event.mint);
+        match event.kind {
+            SwapType::RemoveLiquidity => {
+                let removed_pct = event.removed_pct();
+                risk_score::update_factors(&event.mint, |f| f.liquidity_removed_pct += removed_pct);
+                if removed_pct < self.config.liquidity_removal_alert_pct {
+                    return;
+                }
+
+                let message = format!(
+                    "💧 Liquidity removed from {} pool {}: {:.2} SOL ({:.1}% of the pool), {:.2} SOL left{}. Risk score: {:.0}",
+                    event.mint,
+                    event.pool_id,
+                    event.sol_amount,
+                    removed_pct,
+                    event.pool_sol_reserves,
+                    if held { " - we currently hold this token" } else { "" },
+                    risk_score::risk_score(&event.mint)
+                );
+                self.logger.log(message.red().bold().to_string());
+                self.send_alert("Liquidity Removed", &message).await;
+            }
+            SwapType::PoolCreate => {
+                let message = format!(
+                    "🆕 New {} pool {} for {} seeded with {:.2} SOL{}",
+                    event.dex_type.as_str(),
+                    event.pool_id,
+                    event.mint,
+                    event.sol_amount,
+                    if held { " - we currently hold this token" } else { "" }
+                );
+                self.logger.log(message.cyan().to_string());
+                self.send_alert("Pool Created", &message).await;
+            }
+            SwapType::AddLiquidity => {
+                self.logger.log(format!(
+                    "Liquidity added to {} pool {}: {:.2} SOL ({:.2} SOL in pool)",
+                    event.mint, event.pool_id, event.sol_amount, event.pool_sol_reserves
+                ));
+            }
+            SwapType::Buy | SwapType::Sell => {}
+        }
+    }
+
+    async fn send_alert(&self, title: &str, message: &str) {
+        if let Some(telegram) = &self.telegram {
+            if let Err(e) = telegram.send_custom_alert(title, message).await {
+                self.logger.log(format!("Failed to send alert: {}", e).red().to_string());
+            }
+        }
+    }
+}
+
+fn pct_of(amount: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    amount as f64 / total as f64 * 100.0
+}
+
+/// Start the token program monitor unless disabled via TOKEN_MONITOR_ENABLED=false
+pub async fn start_token_program_monitor(
+    yellowstone_grpc_http: String,
+    yellowstone_grpc_token: String,
+    app_state: Arc<AppState>,
+    tracked_wallets: Vec<String>,
+    cancel_token: CancellationToken,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let enabled = std::env::var("TOKEN_MONITOR_ENABLED")
+        .ok()
+        .and_then(|v| v.to_lowercase().parse::<bool>().ok())
+        .unwrap_or(true);
+    if !enabled {
+        return None;
+    }
+
+    let telegram = crate::processor::telegram_alerts::init_from_env()
+        .ok()
+        .flatten()
+        .map(Arc::new);
+    let mut config = TokenProgramMonitorConfig::new(yellowstone_grpc_http, yellowstone_grpc_token, app_state);
+    config.tracked_wallets = tracked_wallets.into_iter().collect();
+    let monitor = TokenProgramMonitor::new(config, telegram);
+
+    Some(tokio::spawn(async move {
+        if let Err(e) = monitor.start(cancel_token).await {
+            eprintln!("Token program monitor error: {}", e);
+        }
+    }))
+}
@@ -0,0 +1,290 @@
+/*!
+# Token Timeline
+
+An append-only timeline of the notable events of each token: when it was first seen or its pool
+created, its first whale buy, liquidity changes, every alert fired on it and state transitions
+such as an imminent graduation or the end of its launch. The monitor records creation and whale
+buys as it sees trades; everything else arrives through the signal bus.
+
+Read it with `/timeline <mint>` in Telegram or `GET /timeline/<mint>` on the status server.
+
+## Environment Variables
+
+- `TIMELINE_MAX_TOKENS`: Tokens kept; the one quiet the longest is dropped first (default: `5000`)
+- `TIMELINE_MAX_EVENTS`: Events kept per token; the oldest after the first go first (default: `200`)
+- `TIMELINE_REPEAT_SECS`: An event with the same title as one this recent on the token is not
+  added again, so an alert firing on every trade shows once (default: `300`)
+*/
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::processor::signal_bus::{Signal, SignalConsumer};
+use crate::processor::transaction_parser::{ParsedData, SwapType};
+
+/// Events listed by `/timeline`
+const MAX_LISTED_EVENTS: usize = 40;
+
+lazy_static! {
+    static ref SHARED_TIMELINE: Arc<TokenTimeline> = Arc::new(TokenTimeline::new(TimelineConfig::from_env()));
+}
+
+#[derive(Clone, Debug)]
+pub struct TimelineConfig {
+    pub max_tokens: usize,
+    pub max_events: usize,
+    pub repeat_secs: i64,
+}
+
+impl Default for TimelineConfig {
+    fn default() -> Self {
+        Self {
+            max_tokens: 5000,
+            max_events: 200,
+            repeat_secs: 300,
+        }
+    }
+}
+
+impl TimelineConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            max_tokens: var("TIMELINE_MAX_TOKENS")
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(defaults.max_tokens),
+            max_events: var("TIMELINE_MAX_EVENTS")
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|n| *n > 1)
+                .unwrap_or(defaults.max_events),
+            repeat_secs: var("TIMELINE_REPEAT_SECS")
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|s| *s >= 0)
+                .unwrap_or(defaults.repeat_secs),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineKind {
+    Created,
+    WhaleBuy,
+    Liquidity,
+    Alert,
+    State,
+}
+
+impl TimelineKind {
+    fn icon(&self) -> &'static str {
+        match self {
+            TimelineKind::Created => "🐣",
+            TimelineKind::WhaleBuy => "🐋",
+            TimelineKind::Liquidity => "💧",
+            TimelineKind::Alert => "🔔",
+            TimelineKind::State => "🔄",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TimelineEvent {
+    /// Unix seconds
+    pub at: i64,
+    pub kind: TimelineKind,
+    pub title: String,
+    pub detail: String,
+}
+
+#[derive(Default)]
+struct TokenEvents {
+    events: Vec<TimelineEvent>,
+    whale_seen: bool,
+}
+
+pub struct TokenTimeline {
+    config: TimelineConfig,
+    tokens: Mutex<HashMap<Pubkey, TokenEvents>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl TokenTimeline {
+    pub fn new(config: TimelineConfig) -> Self {
+        Self {
+            config,
+            tokens: Mutex::new(HashMap::new()),
+            clock: system_clock(),
+        }
+    }
+
+    pub fn shared() -> Arc<TokenTimeline> {
+        SHARED_TIMELINE.clone()
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Append an event to the token's timeline
+    pub fn record(&self, mint: Pubkey, kind: TimelineKind, title: &str, detail: String) {
+        let now = self.clock.now();
+        let mut tokens = self.tokens.lock().unwrap();
+        if !tokens.contains_key(&mint) && tokens.len() >= self.config.max_tokens {
+            let quietest = tokens.iter()
+                .min_by_key(|(_, token)| token.events.last().map(|e| e.at).unwrap_or(i64::MIN))
+                .map(|(mint, _)| *mint);
+            if let Some(quietest) = quietest {
+                tokens.remove(&quietest);
+            }
+        }
+        let token = tokens.entry(mint).or_default();
+        let repeated = token.events.iter().rev()
+            .take_while(|e| now.timestamp() - e.at < self.config.repeat_secs)
+            .any(|e| e.kind == kind && e.title == title);
+        if repeated {
+            return;
+        }
+        if token.events.len() >= self.config.max_events {
+            // The first event says how the token started; keep it
+            token.events.remove(1);
+        }
+        token.events.push(event_at(now, kind, title, detail));
+    }
+
+    /// Record a token's first sighting and its first buy of at least `whale_sol`
+    pub fn observe_trade(&self, parsed_data: &ParsedData, whale_sol: f64) {
+        let amount_sol = parsed_data.sol_amount.unwrap_or(0.0);
+        let (known, whale_seen) = {
+            let tokens = self.tokens.lock().unwrap();
+            match tokens.get(&parsed_data.token_mint) {
+                Some(token) => (true, token.whale_seen),
+                None => (false, false),
+            }
+        };
+        if !known {
+            let title = match parsed_data.swap_type {
+                SwapType::PoolCreate => "Pool created",
+                _ => "First seen",
+            };
+            let name = parsed_data.token_name.as_deref().unwrap_or("Unknown");
+            self.record(parsed_data.token_mint, TimelineKind::Created, title, format!("{} on {}", name, parsed_data.dex_name));
+        }
+        if matches!(parsed_data.swap_type, SwapType::Buy) && amount_sol >= whale_sol && !whale_seen {
+            if let Some(token) = self.tokens.lock().unwrap().get_mut(&parsed_data.token_mint) {
+                token.whale_seen = true;
+            }
+            self.record(
+                parsed_data.token_mint,
+                TimelineKind::WhaleBuy,
+                "First whale buy",
+                format!("{} bought {:.2} SOL", parsed_data.signer, amount_sol),
+            );
+        }
+    }
+
+    pub fn events(&self, mint: &Pubkey) -> Vec<TimelineEvent> {
+        self.tokens.lock().unwrap().get(mint).map(|token| token.events.clone()).unwrap_or_default()
+    }
+
+    /// Tokens with a timeline
+    pub fn len(&self) -> usize {
+        self.tokens.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn event_at(at: DateTime<Utc>, kind: TimelineKind, title: &str, detail: String) -> TimelineEvent {
+    TimelineEvent { at: at.timestamp(), kind, title: title.to_string(), detail }
+}
+
+/// Where a published signal lands on the timeline; wallet activity and new tokens are recorded
+/// from the trades themselves
+fn timeline_entry(signal: &Signal) -> Option<(TimelineKind, String)> {
+    let title = match signal {
+        Signal::WalletActivity { .. } | Signal::NewToken { .. } => return None,
+        Signal::GraduationImminent { .. } => return Some((TimelineKind::State, "About to graduate".to_string())),
+        Signal::LaunchReplay { .. } => return Some((TimelineKind::State, "Launch ended".to_string())),
+        Signal::LiquidityEvent { kind, .. } => {
+            let title = match kind {
+                SwapType::PoolCreate => "Pool created",
+                SwapType::RemoveLiquidity => "Liquidity removed",
+                _ => "Liquidity added",
+            };
+            return Some((TimelineKind::Liquidity, title.to_string()));
+        }
+        Signal::LiquidityDrop { .. } => return Some((TimelineKind::Liquidity, "Liquidity drop".to_string())),
+        Signal::Opportunity(opportunity) => opportunity.kind.label().to_string(),
+        Signal::InsiderBuy { .. } => "Insider buy".to_string(),
+        Signal::PriceMovement { .. } => "Price movement".to_string(),
+        Signal::VolumeSpike { .. } => "Volume spike".to_string(),
+        Signal::PriceImpact { .. } => "Price impact".to_string(),
+        Signal::ArbitrageSpread { .. } => "Cross-venue spread".to_string(),
+        Signal::GroupBuy(activity) => format!("Group '{}' buying", activity.group),
+    };
+    Some((TimelineKind::Alert, title))
+}
+
+#[async_trait]
+impl SignalConsumer for TokenTimeline {
+    fn name(&self) -> &str {
+        "timeline"
+    }
+
+    async fn consume(&self, signal: &Signal) -> Result<()> {
+        if let Some((kind, title)) = timeline_entry(signal) {
+            self.record(signal.mint(), kind, &title, signal.describe());
+        }
+        Ok(())
+    }
+}
+
+/// Reply to `/timeline <mint>`
+pub fn format_token_timeline(mint: &Pubkey, events: &[TimelineEvent]) -> String {
+    if events.is_empty() {
+        return format!("No timeline recorded for {}", mint);
+    }
+    let mut text = format!("🕰️ Timeline of {} ({} events)\n", mint, events.len());
+    let line = |text: &mut String, event: &TimelineEvent| {
+        let at = DateTime::from_timestamp(event.at, 0).map(|at| at.format("%m-%d %H:%M:%S").to_string()).unwrap_or_default();
+        let _ = write!(text, "\n{} {} {}: {}", at, event.kind.icon(), event.title, event.detail);
+    };
+    // The start and the latest events, to stay within a Telegram message
+    let skipped = events.len().saturating_sub(MAX_LISTED_EVENTS);
+    line(&mut text, &events[0]);
+    if skipped > 0 {
+        let _ = write!(text, "\n… {} earlier events", skipped);
+    }
+    for event in &events[1 + skipped..] {
+        line(&mut text, event);
+    }
+    text
+}
+
+/// Status and JSON body for `GET /timeline/<mint>`
+pub fn timeline_response(mint: &str, timeline: &TokenTimeline) -> (&'static str, String) {
+    let mint = match Pubkey::from_str(mint) {
+        Ok(mint) => mint,
+        Err(_) => return ("400 Bad Request", json!({"error": "invalid mint"}).to_string()),
+    };
+    let events = timeline.events(&mint);
+    if events.is_empty() {
+        return ("404 Not Found", json!({"error": "no timeline recorded for this mint"}).to_string());
+    }
+    ("200 OK", json!({"mint": mint.to_string(), "events": events}).to_string())
+}
@@ -0,0 +1,307 @@
+//! Persistent ledger of executed live trades, and the tax/PnL export built from it.
+//!
+//! Every buy or sell the bot confirms is read back from chain (the wallet's SOL and token balance
+//! changes) and appended to `TRADE_LEDGER_PATH` as JSON lines. The same read settles the
+//! transaction's infrastructure cost in `trade_costs`, with the rent of the token accounts it opened. `--export-trades` turns the ledger
+//! into a CSV of trades with cost basis and realized PnL, plus a CSV of realized PnL per day.
+//!
+//! Cost basis uses the average cost method per mint over the whole ledger: buy fees are added to
+//! the cost, sell fees are deducted from the proceeds. SOL amounts include Jito/ZeroSlot tips and
+//! token account rent, since both leave the wallet as part of the trade.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anchor_client::solana_sdk::{signature::Signature, signer::Signer};
+use chrono::{DateTime, NaiveDate, Utc};
+use colored::Colorize;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+use crate::common::config::AppState;
+use crate::common::logger::Logger;
+use crate::common::trade_costs;
+use crate::library::rpc_api::BalanceChanges;
+use crate::processor::dip_buy::{self, WatchReason};
+use crate::processor::fill_check::FillCheck;
+
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+/// A just-confirmed transaction can take a few seconds to become readable through getTransaction
+const FETCH_ATTEMPTS: u32 = 5;
+const FETCH_DELAY: Duration = Duration::from_secs(2);
+
+lazy_static! {
+    static ref SHARED_LEDGER: Arc<TradeLedger> = Arc::new(TradeLedger::from_env());
+    static ref LOGGER: Logger = Logger::new("[TRADE-LEDGER] => ".magenta().to_string());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+impl TradeSide {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeSide::Buy => "buy",
+            TradeSide::Sell => "sell",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LedgerTrade {
+    /// Unix seconds
+    pub timestamp: i64,
+    pub signature: String,
+    pub mint: String,
+    pub side: TradeSide,
+    pub token_amount: f64,
+    /// SOL paid (buy) or received (sell), network fee excluded
+    pub sol_amount: f64,
+    pub fee_sol: f64,
+}
+
+impl LedgerTrade {
+    /// The trade a transaction made, from the wallet's balance changes: the mint whose balance
+    /// moved the most is the one traded. None when no token (other than WSOL) changed.
+    pub fn from_balance_changes(signature: &str, changes: &BalanceChanges, now: i64) -> Option<Self> {
+        let (mint, token_change) = changes.token_changes.iter()
+            .filter(|(mint, change)| mint != WSOL_MINT && *change != 0.0)
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))?;
+        let wsol_change: f64 = changes.token_changes.iter()
+            .filter(|(mint, _)| mint == WSOL_MINT)
+            .map(|(_, change)| change)
+            .sum();
+        let fee_sol = changes.fee_lamports as f64 / 1e9;
+        // SOL that moved because of the trade, with the fee put back
+        let sol_change = changes.sol_change_lamports as f64 / 1e9 + wsol_change + fee_sol;
+        let side = if *token_change > 0.0 { TradeSide::Buy } else { TradeSide::Sell };
+
+        Some(Self {
+            timestamp: changes.block_time.unwrap_or(now),
+            signature: signature.to_string(),
+            mint: mint.clone(),
+            side,
+            token_amount: token_change.abs(),
+            sol_amount: sol_change.abs(),
+            fee_sol,
+        })
+    }
+}
+
+pub struct TradeLedger {
+    path: Option<PathBuf>,
+    trades: Mutex<Vec<LedgerTrade>>,
+}
+
+impl TradeLedger {
+    /// Ledger persisted at `path` (if any), reloading trades recorded by previous runs
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let trades = path.as_ref().map(load_trades).unwrap_or_default();
+        Self { path, trades: Mutex::new(trades) }
+    }
+
+    pub fn in_memory() -> Self {
+        Self::new(None)
+    }
+
+    pub fn from_env() -> Self {
+        let path = std::env::var("TRADE_LEDGER_PATH").unwrap_or_else(|_| "trade_ledger.jsonl".to_string());
+        Self::new(Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty()))
+    }
+
+    /// Process-wide ledger
+    pub fn shared() -> Arc<TradeLedger> {
+        SHARED_LEDGER.clone()
+    }
+
+    /// Append a trade; a signature already in the ledger is ignored. Returns whether it was added.
+    pub fn record(&self, trade: LedgerTrade) -> bool {
+        let mut trades = self.trades.lock().unwrap();
+        if trades.iter().any(|t| t.signature == trade.signature) {
+            return false;
+        }
+        if let Some(path) = &self.path {
+            let result = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| writeln!(file, "{}", serde_json::to_string(&trade).unwrap_or_default()));
+            if let Err(e) = result {
+                eprintln!("Failed to append to trade ledger {}: {}", path.display(), e);
+            }
+        }
+        trades.push(trade);
+        true
+    }
+
+    pub fn trades(&self) -> Vec<LedgerTrade> {
+        self.trades.lock().unwrap().clone()
+    }
+}
+
+fn load_trades(path: &PathBuf) -> Vec<LedgerTrade> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return Vec::new(),
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+/// Read a confirmed transaction back from chain and record the trade it made, correcting the
+/// position of a buy with its real amounts, in the background
+pub fn record_confirmed(app_state: Arc<AppState>, signature: Signature) {
+    tokio::spawn(async move {
+        let wallet = app_state.wallet.pubkey();
+        for attempt in 1..=FETCH_ATTEMPTS {
+            match app_state.rpc_api.balance_changes(&signature, &wallet).await {
+                Ok(changes) => {
+                    trade_costs::record_confirmed(&signature.to_string(), changes.token_accounts_opened, changes.token_accounts_closed);
+                    match LedgerTrade::from_balance_changes(&signature.to_string(), &changes, Utc::now().timestamp()) {
+                        Some(trade) => {
+                            let fills = FillCheck::shared();
+                            if let Some(report) = fills.trade_confirmed(&trade) {
+                                fills.report(report);
+                            }
+                            if trade.side == TradeSide::Sell {
+                                dip_buy::start_watch(&trade.mint, WatchReason::Sold, app_state.rpc_nonblocking_client.clone());
+                            }
+                            TradeLedger::shared().record(trade);
+                        }
+                        None => LOGGER.log(format!("{} moved no tokens, not a trade", signature)),
+                    }
+                    return;
+                }
+                Err(e) if attempt == FETCH_ATTEMPTS => {
+                    LOGGER.log(format!("Could not record trade {}: {}", signature, e).red().to_string());
+                }
+                Err(_) => tokio::time::sleep(FETCH_DELAY).await,
+            }
+        }
+    });
+}
+
+/// A trade with its tax figures
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaxRow {
+    pub trade: LedgerTrade,
+    /// Cost of the tokens bought (buy) or the average cost of the tokens sold (sell)
+    pub cost_basis_sol: f64,
+    /// Sell proceeds after fees; None for buys
+    pub proceeds_sol: Option<f64>,
+    pub realized_pnl_sol: Option<f64>,
+}
+
+/// Tax figures for every trade, in time order, using average cost per mint
+pub fn tax_rows(trades: &[LedgerTrade]) -> Vec<TaxRow> {
+    let mut ordered = trades.to_vec();
+    ordered.sort_by_key(|trade| trade.timestamp);
+
+    // Tokens held and their total cost, per mint
+    let mut holdings: HashMap<String, (f64, f64)> = HashMap::new();
+    ordered.into_iter()
+        .map(|trade| {
+            let (held, cost) = holdings.entry(trade.mint.clone()).or_insert((0.0, 0.0));
+            match trade.side {
+                TradeSide::Buy => {
+                    let cost_basis = trade.sol_amount + trade.fee_sol;
+                    *held += trade.token_amount;
+                    *cost += cost_basis;
+                    TaxRow { trade, cost_basis_sol: cost_basis, proceeds_sol: None, realized_pnl_sol: None }
+                }
+                TradeSide::Sell => {
+                    // Tokens bought before the ledger existed have no known cost
+                    let share = if *held > 0.0 { (trade.token_amount / *held).min(1.0) } else { 0.0 };
+                    let cost_basis = *cost * share;
+                    *held = (*held - trade.token_amount).max(0.0);
+                    *cost -= cost_basis;
+                    let proceeds = trade.sol_amount - trade.fee_sol;
+                    TaxRow {
+                        trade,
+                        cost_basis_sol: cost_basis,
+                        proceeds_sol: Some(proceeds),
+                        realized_pnl_sol: Some(proceeds - cost_basis),
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+fn trade_date(trade: &LedgerTrade) -> NaiveDate {
+    DateTime::<Utc>::from_timestamp(trade.timestamp, 0).unwrap_or_default().date_naive()
+}
+
+fn in_range(date: NaiveDate, from: Option<NaiveDate>, to: Option<NaiveDate>) -> bool {
+    from.map_or(true, |from| date >= from) && to.map_or(true, |to| date <= to)
+}
+
+/// Trades CSV for tax software. Cost basis is computed over the whole ledger, so rows outside
+/// the range still count towards the average cost of later sells.
+pub fn export_csv(trades: &[LedgerTrade], from: Option<NaiveDate>, to: Option<NaiveDate>) -> String {
+    let mut csv = String::from(
+        "timestamp_utc,signature,mint,side,token_amount,sol_amount,fee_sol,cost_basis_sol,proceeds_sol,realized_pnl_sol\n",
+    );
+    let optional = |value: Option<f64>| value.map(|v| format!("{:.9}", v)).unwrap_or_default();
+    for row in tax_rows(trades).into_iter().filter(|row| in_range(trade_date(&row.trade), from, to)) {
+        let trade = &row.trade;
+        csv.push_str(&format!(
+            "{},{},{},{},{},{:.9},{:.9},{:.9},{},{}\n",
+            DateTime::<Utc>::from_timestamp(trade.timestamp, 0).unwrap_or_default().format("%Y-%m-%dT%H:%M:%SZ"),
+            trade.signature,
+            trade.mint,
+            trade.side.as_str(),
+            trade.token_amount,
+            trade.sol_amount,
+            trade.fee_sol,
+            row.cost_basis_sol,
+            optional(row.proceeds_sol),
+            optional(row.realized_pnl_sol),
+        ));
+    }
+    csv
+}
+
+/// Realized PnL, fees and trade count per day
+pub fn daily_pnl(trades: &[LedgerTrade]) -> BTreeMap<NaiveDate, (f64, f64, usize)> {
+    let mut days: BTreeMap<NaiveDate, (f64, f64, usize)> = BTreeMap::new();
+    for row in tax_rows(trades) {
+        let day = days.entry(trade_date(&row.trade)).or_default();
+        day.0 += row.realized_pnl_sol.unwrap_or_default();
+        day.1 += row.trade.fee_sol;
+        day.2 += 1;
+    }
+    days
+}
+
+pub fn daily_pnl_csv(trades: &[LedgerTrade], from: Option<NaiveDate>, to: Option<NaiveDate>) -> String {
+    let mut csv = String::from("date,trades,fees_sol,realized_pnl_sol\n");
+    for (date, (pnl, fees, count)) in daily_pnl(trades).into_iter().filter(|(date, _)| in_range(*date, from, to)) {
+        csv.push_str(&format!("{},{},{:.9},{:.9}\n", date, count, fees, pnl));
+    }
+    csv
+}
+
+/// Write the trades and daily PnL CSVs for the shared ledger; returns the number of trades
+/// exported
+pub fn export_files(
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    trades_path: &str,
+    daily_path: &str,
+) -> std::io::Result<usize> {
+    let trades = TradeLedger::shared().trades();
+    let exported = trades.iter().filter(|trade| in_range(trade_date(trade), from, to)).count();
+    std::fs::write(trades_path, export_csv(&trades, from, to))?;
+    std::fs::write(daily_path, daily_pnl_csv(&trades, from, to))?;
+    Ok(exported)
+}
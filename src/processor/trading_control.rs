@@ -0,0 +1,113 @@
+//! Runtime pause switch for new buys.
+//!
+//! `/pause [reason]` in the alert chat or `POST /control/pause` on the status server stops new
+//! buys right away; monitoring, alerts and the selling of open positions carry on. `/resume` or
+//! `POST /control/resume` lifts the pause. The state is written to `TRADING_CONTROL_PATH`, so a
+//! bot restarted while paused comes back paused.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+use crate::common::clock::{system_clock, Clock};
+
+lazy_static! {
+    static ref SHARED_CONTROL: Arc<TradingControl> = Arc::new(TradingControl::from_env());
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PauseState {
+    /// Unix seconds
+    pub since: i64,
+    /// Where the pause came from, e.g. `telegram` or `api`
+    pub source: String,
+    pub reason: Option<String>,
+}
+
+pub struct TradingControl {
+    path: Option<PathBuf>,
+    clock: Arc<dyn Clock>,
+    paused: Mutex<Option<PauseState>>,
+}
+
+impl TradingControl {
+    /// Control persisted at `path` (if any), paused again if a previous run left it paused
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let paused = path.as_ref().and_then(|path| {
+            let content = std::fs::read_to_string(path).ok()?;
+            serde_json::from_str::<Option<PauseState>>(&content).ok().flatten()
+        });
+        Self { path, clock: system_clock(), paused: Mutex::new(paused) }
+    }
+
+    pub fn from_env() -> Self {
+        let path = std::env::var("TRADING_CONTROL_PATH").unwrap_or_else(|_| "trading_control.json".to_string());
+        Self::new(Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty()))
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Process-wide control
+    pub fn shared() -> Arc<TradingControl> {
+        SHARED_CONTROL.clone()
+    }
+
+    /// Pause new buys; returns false if they already were
+    pub fn pause(&self, source: &str, reason: Option<String>) -> bool {
+        let mut paused = self.paused.lock().unwrap();
+        if paused.is_some() {
+            return false;
+        }
+        *paused = Some(PauseState {
+            since: self.clock.now().timestamp(),
+            source: source.to_string(),
+            reason: reason.filter(|r| !r.trim().is_empty()).map(|r| r.trim().to_string()),
+        });
+        self.save(&paused);
+        true
+    }
+
+    /// Resume buying; returns the pause that was lifted, None if buys were not paused
+    pub fn resume(&self) -> Option<PauseState> {
+        let mut paused = self.paused.lock().unwrap();
+        let lifted = paused.take()?;
+        self.save(&paused);
+        Some(lifted)
+    }
+
+    pub fn state(&self) -> Option<PauseState> {
+        self.paused.lock().unwrap().clone()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.lock().unwrap().is_some()
+    }
+
+    /// Why buys are refused, None while buying may proceed
+    pub fn paused_reason(&self) -> Option<String> {
+        self.paused.lock().unwrap().as_ref().map(|state| match &state.reason {
+            Some(reason) => format!("Buys paused via {}: {}", state.source, reason),
+            None => format!("Buys paused via {}", state.source),
+        })
+    }
+
+    fn save(&self, paused: &Option<PauseState>) {
+        if let Some(path) = &self.path {
+            let written = serde_json::to_string(paused)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| Ok(std::fs::write(path, json)?));
+            if let Err(e) = written {
+                eprintln!("Failed to save the trading pause state to {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Why buys are refused by the operator right now, None while buying may proceed
+pub fn buys_paused_reason() -> Option<String> {
+    SHARED_CONTROL.paused_reason()
+}
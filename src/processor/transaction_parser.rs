@@ -6,8 +6,13 @@ use crate::common::logger::Logger;
 use lazy_static;
 use yellowstone_grpc_proto::geyser::SubscribeUpdateTransaction;
 use std::time::Instant;
-// Import PUMP_FUN_PROGRAM instead of PUMP_PROGRAM
-use crate::dex::pump_fun::PUMP_FUN_PROGRAM;
+use chrono::{DateTime, Utc};
+use crate::dex::bonding_curve::CurveSnapshot;
+use crate::common::cluster::DexProgram;
+use crate::common::cache::LOOKUP_TABLE_CACHE;
+use crate::common::quote_asset::{self, QuoteAsset};
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::address_lookup_table::state::AddressLookupTable;
 // Create a static logger for this module
 lazy_static::lazy_static! {
     static ref LOGGER: Logger = Logger::new("[PARSER] => ".blue().to_string());
@@ -38,12 +43,277 @@ pub struct TradeInfoFromToken {
     pub price: u64,
     pub is_reverse_when_pump_swap: bool,
     pub coin_creator: Option<String>,
+    /// SOL side of the trade; for stablecoin-quoted pools the quote amount converted to SOL
     pub sol_change: f64,
     pub token_change: f64,
     pub liquidity: f64,  // this is for filtering out small trades
+    /// Pool quote reserves in raw units of `quote_asset`
     pub virtual_sol_reserves: u64,
     pub virtual_token_reserves: u64,
+    pub quote_asset: QuoteAsset,
 }
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SwapType {
+    Buy,
+    Sell,
+    AddLiquidity,
+    RemoveLiquidity,
+    PoolCreate,
+}
+
+impl SwapType {
+    /// Whether this is a liquidity event rather than a trade
+    pub fn is_liquidity_event(&self) -> bool {
+        matches!(self, SwapType::AddLiquidity | SwapType::RemoveLiquidity | SwapType::PoolCreate)
+    }
+}
+
+// Anchor event CPI: EVENT_IX_TAG (LE) followed by the event discriminator
+const ANCHOR_EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+const PUMP_SWAP_CREATE_POOL_EVENT: [u8; 8] = [177, 49, 12, 210, 160, 118, 167, 116];
+const PUMP_SWAP_DEPOSIT_EVENT: [u8; 8] = [120, 248, 61, 83, 31, 142, 107, 144];
+const PUMP_SWAP_WITHDRAW_EVENT: [u8; 8] = [22, 9, 133, 26, 160, 44, 71, 192];
+const PUMP_SWAP_BUY_EVENT: [u8; 8] = [103, 244, 82, 31, 44, 245, 119, 119];
+const PUMP_SWAP_SELL_EVENT: [u8; 8] = [62, 47, 55, 10, 165, 3, 220, 42];
+
+// Position of the mints in the DEX swap instructions' account lists
+const PUMP_SWAP_BASE_MINT_ACCOUNT: usize = 3;
+const PUMP_SWAP_QUOTE_MINT_ACCOUNT: usize = 4;
+const RAYDIUM_LAUNCHPAD_BASE_MINT_ACCOUNT: usize = 9;
+const RAYDIUM_LAUNCHPAD_QUOTE_MINT_ACCOUNT: usize = 10;
+
+/// Liquidity added to, removed from, or seeding a pool
+#[derive(Clone, Debug)]
+pub struct LiquidityEventInfo {
+    pub dex_type: DexType,
+    pub kind: SwapType,
+    pub slot: u64,
+    pub signature: String,
+    pub pool_id: String,
+    pub mint: String,
+    pub timestamp: u64,
+    /// SOL side of the deposit/withdrawal (lamports converted to SOL)
+    pub sol_amount: f64,
+    /// Token side of the deposit/withdrawal (raw amount / 1e9, like swap amounts)
+    pub token_amount: f64,
+    /// Pool SOL reserves after the event
+    pub pool_sol_reserves: f64,
+    /// Pool token reserves after the event
+    pub pool_token_reserves: f64,
+    /// SOL amounts of stable-quoted pools are converted at the SOL price
+    pub quote_asset: QuoteAsset,
+}
+
+impl LiquidityEventInfo {
+    /// Share of the pool's SOL side removed by this event (%), 0 for non-removals
+    pub fn removed_pct(&self) -> f64 {
+        let before = self.pool_sol_reserves + self.sol_amount;
+        if self.kind != SwapType::RemoveLiquidity || before <= 0.0 {
+            return 0.0;
+        }
+        self.sol_amount / before * 100.0
+    }
+}
+
+/// Extract the signer (fee payer) from a yellowstone grpc transaction
+/// Returns the first signer which is typically the transaction fee payer
+pub fn extract_signer_from_transaction(txn: &SubscribeUpdateTransaction) -> Option<String> {
+    if let Some(transaction_info) = &txn.transaction {
+        if let Some(transaction) = &transaction_info.transaction {
+            if let Some(message) = &transaction.message {
+                // Get account keys - the first one is typically the signer/fee payer
+                if !message.account_keys.is_empty() {
+                    // Convert the first account key (signer) to string
+                    return Some(bs58::encode(&message.account_keys[0]).into_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// DEX trades in a transaction, one per traded mint. Trades are attributed through their event
+/// CPIs (so swaps routed via aggregators are seen); when none is attributed, fall back to the
+/// first inner instruction with a known event payload size.
+pub fn collect_swaps(
+    txn: &SubscribeUpdateTransaction,
+    inner_instructions: &[yellowstone_grpc_proto::prelude::InnerInstructions],
+) -> Vec<TradeInfoFromToken> {
+    let mut swaps = parse_swap_events(txn);
+    if swaps.is_empty() {
+        swaps.extend(
+            inner_instructions
+                .iter()
+                .flat_map(|inner| &inner.instructions)
+                .find(|ix| ix.data.len() == 368 || ix.data.len() == 266 || ix.data.len() == 270  || ix.data.len() == 146 || ix.data.len() == 170 || ix.data.len() == 138)
+                .and_then(|ix| parse_transaction_data(txn, &ix.data))
+                .filter(|swap| swap.dex_type.is_enabled()),
+        );
+    }
+    let mut seen = std::collections::HashSet::new();
+    swaps.retain(|swap| seen.insert(swap.mint.clone()));
+    swaps
+}
+
+/// Block time of a trade event stamped `timestamp`. Raydium Launchpad events carry no clock, so
+/// their timestamp is the time they were parsed and does not count
+fn block_time(dex_type: &DexType, timestamp: u64) -> Option<DateTime<Utc>> {
+    if timestamp == 0 || *dex_type == DexType::RaydiumLaunchpad {
+        return None;
+    }
+    DateTime::from_timestamp(timestamp as i64, 0)
+}
+
+/// Normalized view of a parsed swap, independent of the DEX it came from
+#[derive(Clone, Debug)]
+pub struct ParsedData {
+    pub signature: String,
+    pub slot: u64,
+    /// Block time (unix seconds) from the program's event; see `event_time`
+    pub timestamp: u64,
+    pub dex_name: String,
+    pub swap_type: SwapType,
+    pub token_mint: Pubkey,
+    pub signer: Pubkey,
+    pub pool_id: Option<String>,
+    pub sol_amount: Option<f64>,
+    pub token_amount: Option<f64>,
+    pub token_price: Option<f64>,
+    pub liquidity: Option<f64>,
+    pub token_name: Option<String>,
+    pub token_symbol: Option<String>,
+    /// Pump.fun bonding curve completion (%), None for other DEXes
+    pub bonding_curve_progress: Option<f64>,
+    /// Creator of the token, where the DEX event carries it
+    pub coin_creator: Option<Pubkey>,
+    /// Asset the pool quotes the token in; SOL amounts and prices are converted to SOL
+    pub quote_asset: QuoteAsset,
+}
+
+impl TradeInfoFromToken {
+    /// When the trade happened on chain, if the DEX event says
+    pub fn event_time(&self) -> Option<DateTime<Utc>> {
+        block_time(&self.dex_type, self.timestamp)
+    }
+}
+
+impl DexType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DexType::PumpSwap => "PumpSwap",
+            DexType::PumpFun => "PumpFun",
+            DexType::RaydiumLaunchpad => "RaydiumLaunchpad",
+            DexType::Unknown => "Unknown",
+        }
+    }
+
+    pub fn program(&self) -> Option<DexProgram> {
+        match self {
+            DexType::PumpSwap => Some(DexProgram::PumpSwap),
+            DexType::PumpFun => Some(DexProgram::PumpFun),
+            DexType::RaydiumLaunchpad => Some(DexProgram::RaydiumLaunchpad),
+            DexType::Unknown => None,
+        }
+    }
+
+    /// Whether trades on this DEX are parsed (`ENABLED_DEXES`); unknown DEXes are not gated
+    pub fn is_enabled(&self) -> bool {
+        self.program().map_or(true, |dex| dex.is_enabled())
+    }
+
+    /// Whether strategies may buy on this DEX (`TRADE_DEXES`); unknown DEXes are not gated
+    pub fn is_tradeable(&self) -> bool {
+        self.program().map_or(true, |dex| dex.is_tradeable())
+    }
+
+    /// Inverse of `as_str`, e.g. for the `dex_name` of parsed data
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "PumpSwap" => DexType::PumpSwap,
+            "PumpFun" => DexType::PumpFun,
+            "RaydiumLaunchpad" => DexType::RaydiumLaunchpad,
+            _ => DexType::Unknown,
+        }
+    }
+}
+
+impl ParsedData {
+    /// When the trade happened on chain, if the DEX event says
+    pub fn event_time(&self) -> Option<DateTime<Utc>> {
+        block_time(&DexType::from_name(&self.dex_name), self.timestamp)
+    }
+
+    /// Build the normalized record from raw trade info and the transaction signer
+    pub fn from_trade_info(trade_info: &TradeInfoFromToken, signer: &str) -> Option<Self> {
+        let token_mint = Pubkey::from_str(&trade_info.mint).ok()?;
+        let signer = Pubkey::from_str(signer).ok()?;
+        let token_price = if trade_info.price > 0 {
+            Some(trade_info.price as f64 / 1_000_000_000.0)
+        } else {
+            None
+        };
+
+        let bonding_curve_progress = match trade_info.dex_type {
+            DexType::PumpFun if trade_info.virtual_token_reserves > 0 => Some(
+                crate::dex::pump_fun::bonding_curve_progress(trade_info.virtual_token_reserves),
+            ),
+            _ => None,
+        };
+
+        Some(Self {
+            signature: trade_info.signature.clone(),
+            slot: trade_info.slot,
+            timestamp: trade_info.timestamp,
+            dex_name: trade_info.dex_type.as_str().to_string(),
+            swap_type: if trade_info.is_buy { SwapType::Buy } else { SwapType::Sell },
+            token_mint,
+            signer,
+            pool_id: if trade_info.pool_id.is_empty() { None } else { Some(trade_info.pool_id.clone()) },
+            sol_amount: Some(trade_info.sol_change.abs()),
+            token_amount: Some(trade_info.token_change.abs()),
+            token_price,
+            liquidity: Some(trade_info.liquidity),
+            token_name: None,
+            token_symbol: None,
+            bonding_curve_progress,
+            coin_creator: trade_info.coin_creator.as_deref().and_then(|c| Pubkey::from_str(c).ok()),
+            quote_asset: trade_info.quote_asset,
+        })
+    }
+
+    /// Build the normalized record for a liquidity event
+    pub fn from_liquidity_event(event: &LiquidityEventInfo, signer: &str) -> Option<Self> {
+        let token_mint = Pubkey::from_str(&event.mint).ok()?;
+        let signer = Pubkey::from_str(signer).ok()?;
+        let token_price = if event.pool_token_reserves > 0.0 {
+            Some(event.pool_sol_reserves / event.pool_token_reserves)
+        } else {
+            None
+        };
+
+        Some(Self {
+            signature: event.signature.clone(),
+            slot: event.slot,
+            timestamp: event.timestamp,
+            dex_name: event.dex_type.as_str().to_string(),
+            swap_type: event.kind.clone(),
+            token_mint,
+            signer,
+            pool_id: if event.pool_id.is_empty() { None } else { Some(event.pool_id.clone()) },
+            sol_amount: Some(event.sol_amount),
+            token_amount: Some(event.token_amount),
+            token_price,
+            liquidity: Some(event.pool_sol_reserves),
+            token_name: None,
+            token_symbol: None,
+            bonding_curve_progress: None,
+            coin_creator: None,
+            quote_asset: event.quote_asset,
+        })
+    }
+}
+
 /// Helper function to check if transaction contains MintTo instruction
 /// NOTE: This function is no longer used - we now process all transactions regardless of MintTo
 fn _has_mint_to_instruction(txn: &SubscribeUpdateTransaction) -> bool {
@@ -84,6 +354,21 @@ fn has_sell_instruction(txn: &SubscribeUpdateTransaction) -> bool {
 
 /// Parses the transaction data buffer into a TradeInfoFromToken struct
 pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -> Option<TradeInfoFromToken> {
+    parse_event_data(txn, buffer, &EventContext::default())
+}
+
+/// What the instruction that emitted an event tells us beyond the event payload
+#[derive(Clone, Debug, Default)]
+struct EventContext {
+    /// Traded (non-quote) mint from the emitting instruction's accounts
+    mint: Option<String>,
+    /// Quote asset of the pool, from the emitting instruction's accounts
+    quote: Option<QuoteAsset>,
+    /// Whether the PumpSwap event is a BuyEvent (vs SellEvent)
+    pump_swap_buy: Option<bool>,
+}
+
+fn parse_event_data(txn: &SubscribeUpdateTransaction, buffer: &[u8], context: &EventContext) -> Option<TradeInfoFromToken> {
     fn parse_public_key(buffer: &[u8], offset: usize) -> Option<String> {
         if offset + 32 > buffer.len() {
             return None;
@@ -113,28 +398,13 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
     ) -> String {
         
         let mut mint = String::new();
-        let mut is_reverse = false;
         
-        // Try to extract from token balances if txn is available
+        // Try to extract from token balances if txn is available: the first mint that is not a
+        // quote asset (WSOL, USDC, USDT) is the token
         if let Some(tx_inner) = &txn.transaction {
             if let Some(meta) = &tx_inner.meta {
-                // Check post token balances
-                if !meta.post_token_balances.is_empty() {
-                    mint = meta.post_token_balances[0].mint.clone();
-                    
-                    // Check if this is a reverse case (WSOL is the first mint)
-                if mint == "So11111111111111111111111111111111111111112" {
-                        // In reverse case, look for the second mint which should be the token
-                        if meta.post_token_balances.len() > 1 {
-                            mint = meta.post_token_balances[1].mint.clone();
-                            if mint == "So11111111111111111111111111111111111111112" {
-                                // In reverse case, look for the second mint which should be the token
-                                if meta.post_token_balances.len() > 2 {
-                                    mint = meta.post_token_balances[2].mint.clone();
-                                }
-                            }
-                        }
-                    }
+                if let Some(balance) = meta.post_token_balances.iter().take(3).find(|b| QuoteAsset::from_mint(&b.mint).is_none()) {
+                    mint = balance.mint.clone();
                 }
             }
         }
@@ -147,6 +417,14 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
         mint
     }
     
+    // Without the instruction accounts, a stablecoin in the token balances marks a stable-quoted pool
+    fn detect_quote_asset(txn: &SubscribeUpdateTransaction) -> QuoteAsset {
+        txn.transaction.as_ref()
+            .and_then(|tx| tx.meta.as_ref())
+            .and_then(|meta| meta.post_token_balances.iter().find_map(|b| QuoteAsset::from_mint(&b.mint).filter(QuoteAsset::is_stable)))
+            .unwrap_or_default()
+    }
+
     // Check for MintTo instruction in transaction logs
     // NOTE: MintTo checking has been removed - we now process all transactions
     let _has_mint_to = _has_mint_to_instruction(txn);
@@ -156,7 +434,7 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
 
         368 => {  // pump swap transaction - 368 bytes
             // Extract token mint and check for reverse case
-            let mint = extract_token_info(&txn);
+            let mint = context.mint.clone().unwrap_or_else(|| extract_token_info(&txn));
             let timestamp = parse_u64(buffer, 16)?;
             let base_amount_in_or_base_amount_out = parse_u64(buffer, 24)?;
             let min_quote_amount_out = parse_u64(buffer, 32)?;
@@ -173,6 +451,14 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
             let user_quote_amount_out = parse_u64(buffer, 120)?;
             let pool_id = parse_public_key(buffer, 128)?;
             let coin_creator = parse_public_key(buffer, 320)?;
+            let quote = context.quote.unwrap_or_else(|| detect_quote_asset(txn));
+            if quote.is_stable() && !quote_asset::stable_quotes_enabled() {
+                return None;
+            }
+            // Price, SOL change and liquidity of stable-quoted pools are in lamports at the SOL price
+            let pool_quote_token_reserves_raw = pool_quote_token_reserves;
+            let pool_quote_token_reserves = quote.raw_to_lamports(pool_quote_token_reserves);
+            let quote_amount_out = quote.raw_to_lamports(quote_amount_out);
             
             let (price , is_reverse_when_pump_swap) = if pool_base_token_reserves > 0 && pool_quote_token_reserves > 0 {
                 // Calculate price and determine if it's reverse case
@@ -192,10 +478,10 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
             
             let is_buy = if is_reverse_when_pump_swap {
                 // In reverse case, buy and sell are inverted (base_mint is WSOL)
-                has_sell_instruction(txn)
+                context.pump_swap_buy.map(|buy| !buy).unwrap_or_else(|| has_sell_instruction(txn))
             } else {
                 // Normal case (quote_mint is WSOL)
-                has_buy_instruction(txn)
+                context.pump_swap_buy.unwrap_or_else(|| has_buy_instruction(txn))
             };
             dex_log(format!("PumpSwap=========== {}: {} SOL (Price: {}) Reverse: {}", 
                 if is_buy { "BUY" } else { "SELL" },
@@ -252,14 +538,15 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
                 token_change,
                 liquidity,
                 // Map pool reserves to virtual reserves as requested
-                virtual_sol_reserves: pool_quote_token_reserves,  
+                virtual_sol_reserves: pool_quote_token_reserves_raw,  
                 virtual_token_reserves: pool_base_token_reserves,  
+                quote_asset: quote,
             })
         },
 
         270 => {  // pump swap migeration transaction - 270 bytes  
             // Extract token mint and check for reverse case
-            let mint = extract_token_info(&txn);
+            let mint = context.mint.clone().unwrap_or_else(|| extract_token_info(&txn));
             let timestamp = parse_u64(buffer, 16)?;
             let base_amount_in_or_base_amount_out = parse_u64(buffer, 24)?;
             let min_quote_amount_out = parse_u64(buffer, 32)?;
@@ -275,6 +562,14 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
             let quote_amount_out_without_lp_fee = parse_u64(buffer, 112)?;
             let user_quote_amount_out = parse_u64(buffer, 120)?;
             let pool_id = parse_public_key(buffer, 128)?;
+            let quote = context.quote.unwrap_or_else(|| detect_quote_asset(txn));
+            if quote.is_stable() && !quote_asset::stable_quotes_enabled() {
+                return None;
+            }
+            // Price, SOL change and liquidity of stable-quoted pools are in lamports at the SOL price
+            let pool_quote_token_reserves_raw = pool_quote_token_reserves;
+            let pool_quote_token_reserves = quote.raw_to_lamports(pool_quote_token_reserves);
+            let quote_amount_out = quote.raw_to_lamports(quote_amount_out);
             
             // Determine if this is a reverse case by checking if the mint is WSOL
             let is_reverse_when_pump_swap = mint == "So11111111111111111111111111111111111111112";
@@ -282,10 +577,10 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
             // Determine buy/sell based on reverse case and log messages
             let is_buy = if is_reverse_when_pump_swap {
                 // In reverse case, buy and sell are inverted (base_mint is WSOL)
-                has_sell_instruction(txn)
+                context.pump_swap_buy.map(|buy| !buy).unwrap_or_else(|| has_sell_instruction(txn))
             } else {
                 // Normal case (quote_mint is WSOL)
-                has_buy_instruction(txn)
+                context.pump_swap_buy.unwrap_or_else(|| has_buy_instruction(txn))
             };
             
             // Calculate price for PumpSwap
@@ -348,8 +643,9 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
                 token_change,
                 liquidity,
                 // Map pool reserves to virtual reserves as requested
-                virtual_sol_reserves: pool_quote_token_reserves,  
+                virtual_sol_reserves: pool_quote_token_reserves_raw,  
                 virtual_token_reserves: pool_base_token_reserves,  
+                quote_asset: quote,
             })
         },
 
@@ -365,12 +661,8 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
             let real_sol_reserves = parse_u64(buffer, 121)?;
             let real_token_reserves = parse_u64(buffer, 129)?;
             let creator = parse_public_key(buffer, 185)?;
-            // Calculate price for PumpFun: virtualSolReserves/virtualTokenReserves
-            let price = if virtual_token_reserves > 0 {
-                virtual_sol_reserves.saturating_mul(1_000_000_000) / virtual_token_reserves
-            } else {
-                0
-            };
+            // Price for PumpFun: virtualSolReserves/virtualTokenReserves
+            let price = CurveSnapshot::from_virtual(virtual_sol_reserves, virtual_token_reserves).scaled_price();
 
             // Pump fun don't have pool, just have bonding curve
             let liquidity = real_sol_reserves as f64 / 1_000_000_000.0;
@@ -403,6 +695,7 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
                 liquidity,
                 virtual_sol_reserves: virtual_sol_reserves,
                 virtual_token_reserves: virtual_token_reserves,
+                quote_asset: QuoteAsset::Sol,
             })
         },
         
@@ -419,12 +712,8 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
             let real_sol_reserves = parse_u64(buffer, 121)?;
             let real_token_reserves = parse_u64(buffer, 129)?;
             let creator = parse_public_key(buffer, 185)?;
-            // Calculate price for PumpFun: virtualSolReserves/virtualTokenReserves
-            let price = if virtual_token_reserves > 0 {
-                virtual_sol_reserves.saturating_mul(1_000_000_000) / virtual_token_reserves
-            } else {
-                0
-            };
+            // Price for PumpFun: virtualSolReserves/virtualTokenReserves
+            let price = CurveSnapshot::from_virtual(virtual_sol_reserves, virtual_token_reserves).scaled_price();
 
             // Pump fun don't have pool, just have bonding curve
             let liquidity = real_sol_reserves as f64 / 1_000_000_000.0;
@@ -457,6 +746,7 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
                 liquidity,
                 virtual_sol_reserves: virtual_sol_reserves,
                 virtual_token_reserves: virtual_token_reserves,
+                quote_asset: QuoteAsset::Sol,
             })
         },
         
@@ -473,12 +763,8 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
             let real_sol_reserves = parse_u64(buffer, 121)?;
             let real_token_reserves = parse_u64(buffer, 129)?;
             let creator = parse_public_key(buffer, 185)?;
-            // Calculate price for PumpFun: virtualSolReserves/virtualTokenReserves
-            let price = if virtual_token_reserves > 0 {
-                virtual_sol_reserves.saturating_mul(1_000_000_000) / virtual_token_reserves
-            } else {
-                0
-            };
+            // Price for PumpFun: virtualSolReserves/virtualTokenReserves
+            let price = CurveSnapshot::from_virtual(virtual_sol_reserves, virtual_token_reserves).scaled_price();
 
             // Pump fun don't have pool, just have bonding curve
             let liquidity = real_sol_reserves as f64 / 1_000_000_000.0;
@@ -511,6 +797,7 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
                 liquidity,
                 virtual_sol_reserves: virtual_sol_reserves,
                 virtual_token_reserves: virtual_token_reserves,
+                quote_asset: QuoteAsset::Sol,
             })
         },        
         
@@ -529,9 +816,19 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
             let is_buy = !trade_direction; // Invert: 0 = buy, 1 = sell
             
             // For Raydium Launchpad, we don't need reverse logic since it's never reverse
-            let mint = extract_token_info(&txn);
+            let mint = context.mint.clone().unwrap_or_else(|| extract_token_info(&txn));
             let is_reverse_when_pump_swap = false;
+            let quote = context.quote.unwrap_or_else(|| detect_quote_asset(txn));
+            if quote.is_stable() && !quote_asset::stable_quotes_enabled() {
+                return None;
+            }
+            // Price, SOL change and liquidity of stable-quoted pools are in lamports at the SOL price
+            let virtual_quote_reserve_raw = virtual_quote_reserve;
+            let virtual_quote_reserve = quote.raw_to_lamports(virtual_quote_reserve);
+            let real_quote_before = quote.raw_to_lamports(real_quote_before);
+            let real_quote_after = quote.raw_to_lamports(real_quote_after);
             
+            // The launchpad's trade event has no clock; `ParsedData::event_time` ignores this
             let timestamp = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -579,10 +876,423 @@ pub fn parse_transaction_data(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -
                 sol_change,
                 token_change,
                 liquidity: real_quote_after as f64 / 1_000_000_000.0,
-                virtual_sol_reserves: virtual_quote_reserve,
+                virtual_sol_reserves: virtual_quote_reserve_raw,
                 virtual_token_reserves: virtual_base_reserve,
+                quote_asset: quote,
             })
         },
         _ => None,
     }
 }
+
+/// Parses a PumpSwap CreatePool/Deposit/Withdraw event CPI into a liquidity event
+pub fn parse_liquidity_event(txn: &SubscribeUpdateTransaction, buffer: &[u8]) -> Option<LiquidityEventInfo> {
+    fn parse_public_key(buffer: &[u8], offset: usize) -> Option<String> {
+        buffer.get(offset..offset + 32).map(|b| bs58::encode(b).into_string())
+    }
+
+    fn parse_u64(buffer: &[u8], offset: usize) -> Option<u64> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(buffer.get(offset..offset + 8)?);
+        Some(u64::from_le_bytes(bytes))
+    }
+
+    if buffer.len() < 16 || buffer[..8] != ANCHOR_EVENT_IX_TAG {
+        return None;
+    }
+    let discriminator: [u8; 8] = buffer[8..16].try_into().ok()?;
+    let lamports = |v: u64| v as f64 / 1_000_000_000.0;
+
+    match discriminator {
+        PUMP_SWAP_CREATE_POOL_EVENT => {
+            let timestamp = parse_u64(buffer, 16)?;
+            let base_mint = parse_public_key(buffer, 58)?;
+            let quote_mint = parse_public_key(buffer, 90)?;
+            let base_amount_in = parse_u64(buffer, 124)?;
+            let quote_amount_in = parse_u64(buffer, 132)?;
+            let pool_base_amount = parse_u64(buffer, 140)?;
+            let pool_quote_amount = parse_u64(buffer, 148)?;
+            let pool_id = parse_public_key(buffer, 181)?;
+
+            // Pools are normally token/quote asset; a quote asset base mint is the reverse layout
+            let reverse = QuoteAsset::from_mint(&base_mint).is_some();
+            let quote = QuoteAsset::from_mint(if reverse { &base_mint } else { &quote_mint }).unwrap_or_default();
+            if quote.is_stable() && !quote_asset::stable_quotes_enabled() {
+                return None;
+            }
+            let (mint, sol_in, token_in, pool_sol, pool_token) = if reverse {
+                (quote_mint, base_amount_in, quote_amount_in, pool_base_amount, pool_quote_amount)
+            } else {
+                (base_mint, quote_amount_in, base_amount_in, pool_quote_amount, pool_base_amount)
+            };
+            let (sol_in, pool_sol) = (quote.raw_to_lamports(sol_in), quote.raw_to_lamports(pool_sol));
+            dex_log(format!("PumpSwap POOL CREATE: {} with {} SOL", mint, lamports(sol_in)).cyan().to_string());
+
+            Some(LiquidityEventInfo {
+                dex_type: DexType::PumpSwap,
+                kind: SwapType::PoolCreate,
+                slot: txn.slot,
+                signature: String::new(),
+                pool_id,
+                mint,
+                timestamp,
+                sol_amount: lamports(sol_in),
+                token_amount: lamports(token_in),
+                pool_sol_reserves: lamports(pool_sol),
+                pool_token_reserves: lamports(pool_token),
+                quote_asset: quote,
+            })
+        }
+        PUMP_SWAP_DEPOSIT_EVENT | PUMP_SWAP_WITHDRAW_EVENT => {
+            let is_deposit = discriminator == PUMP_SWAP_DEPOSIT_EVENT;
+            let timestamp = parse_u64(buffer, 16)?;
+            let pool_base_token_reserves = parse_u64(buffer, 64)?;
+            let pool_quote_token_reserves = parse_u64(buffer, 72)?;
+            let base_amount = parse_u64(buffer, 80)?;
+            let quote_amount = parse_u64(buffer, 88)?;
+            let pool_id = parse_public_key(buffer, 104)?;
+
+            // The event does not carry the mints; take them from the token balances
+            let balances = &txn.transaction.as_ref()?.meta.as_ref()?.post_token_balances;
+            let mint = balances.iter()
+                .map(|b| b.mint.clone())
+                .find(|m| QuoteAsset::from_mint(m).is_none())?;
+            let quote = balances.iter()
+                .find_map(|b| QuoteAsset::from_mint(&b.mint).filter(QuoteAsset::is_stable))
+                .unwrap_or_default();
+            if quote.is_stable() && !quote_asset::stable_quotes_enabled() {
+                return None;
+            }
+            // Event reserves are read before the deposit/withdrawal is applied
+            let (pool_base_after, pool_quote_after) = if is_deposit {
+                (pool_base_token_reserves.saturating_add(base_amount), pool_quote_token_reserves.saturating_add(quote_amount))
+            } else {
+                (pool_base_token_reserves.saturating_sub(base_amount), pool_quote_token_reserves.saturating_sub(quote_amount))
+            };
+            // Canonical PumpSwap pools are token (base) / quote asset (quote)
+            let (sol_amount, token_amount, pool_sol, pool_token) =
+                (quote.raw_to_lamports(quote_amount), base_amount, quote.raw_to_lamports(pool_quote_after), pool_base_after);
+            dex_log(format!(
+                "PumpSwap {}: {} SOL",
+                if is_deposit { "ADD LIQUIDITY" } else { "REMOVE LIQUIDITY" },
+                lamports(sol_amount)
+            ).cyan().to_string());
+
+            Some(LiquidityEventInfo {
+                dex_type: DexType::PumpSwap,
+                kind: if is_deposit { SwapType::AddLiquidity } else { SwapType::RemoveLiquidity },
+                slot: txn.slot,
+                signature: String::new(),
+                pool_id,
+                mint,
+                timestamp,
+                sol_amount: lamports(sol_amount),
+                token_amount: lamports(token_amount),
+                pool_sol_reserves: lamports(pool_sol),
+                pool_token_reserves: lamports(pool_token),
+                quote_asset: quote,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// All liquidity events emitted (as event CPIs) by a transaction, with signature and slot set
+pub fn extract_liquidity_events(txn: &SubscribeUpdateTransaction) -> Vec<LiquidityEventInfo> {
+    let tx_info = match &txn.transaction {
+        Some(info) => info,
+        None => return Vec::new(),
+    };
+    let signature = bs58::encode(&tx_info.signature).into_string();
+    tx_info.meta.iter()
+        .flat_map(|meta| &meta.inner_instructions)
+        .flat_map(|inner| &inner.instructions)
+        .filter_map(|ix| parse_liquidity_event(txn, &ix.data))
+        .filter(|event| event.dex_type.is_enabled())
+        .map(|mut event| {
+            event.signature = signature.clone();
+            quote_asset::remember(&event.mint, event.quote_asset);
+            event
+        })
+        .collect()
+}
+
+/// A DEX trade event CPI located in a transaction, with the instruction it was executed under
+#[derive(Clone, Debug)]
+pub struct DexEventCpi {
+    pub dex_type: DexType,
+    /// Index of the top-level instruction the swap ran under
+    pub outer_index: u32,
+    /// Program of that top-level instruction: the DEX itself, or an aggregator/proxy routing to it
+    pub outer_program: Option<Pubkey>,
+    pub data: Vec<u8>,
+    context: EventContext,
+}
+
+impl DexEventCpi {
+    /// Whether the swap was routed through another program rather than called directly
+    pub fn is_routed(&self) -> bool {
+        match &self.outer_program {
+            Some(program) => dex_program_type(program).as_ref() != Some(&self.dex_type),
+            None => false,
+        }
+    }
+}
+
+/// Account keys of a transaction in index order: static keys, then the writable and readonly
+/// addresses loaded from lookup tables.
+///
+/// Loaded addresses come from the transaction meta when it reports as many as the message looks
+/// up; otherwise they are resolved through `LOOKUP_TABLE_CACHE` (see `prefetch_lookup_tables`).
+/// If a table is not cached only the static keys are returned, so indices into lookup tables
+/// resolve to nothing rather than to the wrong account.
+pub fn transaction_account_keys(txn: &SubscribeUpdateTransaction) -> Vec<Pubkey> {
+    let tx_info = match &txn.transaction {
+        Some(info) => info,
+        None => return Vec::new(),
+    };
+    let message = match tx_info.transaction.as_ref().and_then(|tx| tx.message.as_ref()) {
+        Some(message) => message,
+        None => return Vec::new(),
+    };
+    let mut keys: Vec<Pubkey> = message.account_keys.iter()
+        .filter_map(|key| Pubkey::try_from(key.as_slice()).ok())
+        .collect();
+    if message.address_table_lookups.is_empty() {
+        return keys;
+    }
+
+    let lookups = &message.address_table_lookups;
+    let expected_writable: usize = lookups.iter().map(|l| l.writable_indexes.len()).sum();
+    let expected_readonly: usize = lookups.iter().map(|l| l.readonly_indexes.len()).sum();
+    if let Some(meta) = &tx_info.meta {
+        if meta.loaded_writable_addresses.len() == expected_writable
+            && meta.loaded_readonly_addresses.len() == expected_readonly
+        {
+            keys.extend(
+                meta.loaded_writable_addresses.iter()
+                    .chain(&meta.loaded_readonly_addresses)
+                    .filter_map(|key| Pubkey::try_from(key.as_slice()).ok()),
+            );
+            return keys;
+        }
+    }
+
+    // Resolve from cached tables: every table's writable addresses first, then the readonly ones
+    let mut writable = Vec::with_capacity(expected_writable);
+    let mut readonly = Vec::with_capacity(expected_readonly);
+    for lookup in lookups {
+        let table = match Pubkey::try_from(lookup.account_key.as_slice()).ok().and_then(|key| LOOKUP_TABLE_CACHE.get(&key)) {
+            Some(table) => table,
+            None => return keys,
+        };
+        for (indexes, resolved) in [(&lookup.writable_indexes, &mut writable), (&lookup.readonly_indexes, &mut readonly)] {
+            for index in indexes {
+                match table.get(*index as usize) {
+                    Some(address) => resolved.push(*address),
+                    None => return keys,
+                }
+            }
+        }
+    }
+    keys.extend(writable);
+    keys.extend(readonly);
+    keys
+}
+
+/// Lookup tables a transaction references that are not cached (or cached without an index it uses)
+pub fn missing_lookup_tables(txn: &SubscribeUpdateTransaction) -> Vec<Pubkey> {
+    let message = match txn.transaction.as_ref()
+        .and_then(|info| info.transaction.as_ref())
+        .and_then(|tx| tx.message.as_ref())
+    {
+        Some(message) => message,
+        None => return Vec::new(),
+    };
+    message.address_table_lookups.iter()
+        .filter_map(|lookup| {
+            let key = Pubkey::try_from(lookup.account_key.as_slice()).ok()?;
+            let covered = lookup.writable_indexes.iter()
+                .chain(&lookup.readonly_indexes)
+                .all(|index| LOOKUP_TABLE_CACHE.covers(&key, *index));
+            (!covered).then_some(key)
+        })
+        .collect()
+}
+
+/// Fetch and cache the lookup tables a transaction needs before it is parsed. Tables already
+/// cached are not refetched unless the transaction uses an index past their cached length
+/// (tables can be extended). Returns how many tables were loaded.
+pub async fn prefetch_lookup_tables(rpc_client: &RpcClient, txn: &SubscribeUpdateTransaction) -> usize {
+    let missing = missing_lookup_tables(txn);
+    if missing.is_empty() {
+        return 0;
+    }
+    let accounts = match rpc_client.get_multiple_accounts(&missing).await {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            LOGGER.log(format!("Failed to fetch address lookup tables: {}", e).red().to_string());
+            return 0;
+        }
+    };
+
+    let mut loaded = 0;
+    for (key, account) in missing.iter().zip(accounts) {
+        let account = match account {
+            Some(account) => account,
+            None => continue,
+        };
+        match AddressLookupTable::deserialize(&account.data) {
+            Ok(table) => {
+                LOOKUP_TABLE_CACHE.insert(*key, table.addresses.to_vec(), None);
+                loaded += 1;
+            }
+            Err(e) => LOGGER.log(format!("Invalid address lookup table {}: {}", key, e).red().to_string()),
+        }
+    }
+    loaded
+}
+
+/// DEX whose trade events we parse, by program id
+pub fn dex_program_type(program: &Pubkey) -> Option<DexType> {
+    if *program == DexProgram::PumpSwap.id() {
+        Some(DexType::PumpSwap)
+    } else if *program == DexProgram::RaydiumLaunchpad.id() {
+        Some(DexType::RaydiumLaunchpad)
+    } else if *program == DexProgram::PumpFun.id() {
+        Some(DexType::PumpFun)
+    } else {
+        None
+    }
+}
+
+/// Trade event payload sizes each DEX emits
+fn trade_event_lengths(dex_type: &DexType) -> &'static [usize] {
+    match dex_type {
+        DexType::PumpSwap => &[368, 270],
+        DexType::PumpFun => &[266],
+        DexType::RaydiumLaunchpad => &[146],
+        DexType::Unknown => &[],
+    }
+}
+
+/// Find every DEX trade event emitted in a transaction, at any CPI depth.
+///
+/// Events are attributed by the program that emitted them rather than by payload size alone, so
+/// swaps routed through aggregators or proxy programs are found, multi-hop routes yield one event
+/// per hop, and payloads of the right size from unrelated programs are ignored. The emitting
+/// instruction's accounts give the traded mint for each hop.
+pub fn find_dex_event_cpis(txn: &SubscribeUpdateTransaction) -> Vec<DexEventCpi> {
+    let mut events = Vec::new();
+    let tx_info = match &txn.transaction {
+        Some(info) => info,
+        None => return events,
+    };
+    let meta = match &tx_info.meta {
+        Some(meta) => meta,
+        None => return events,
+    };
+    let outer_instructions = tx_info.transaction.as_ref()
+        .and_then(|tx| tx.message.as_ref())
+        .map(|message| message.instructions.as_slice())
+        .unwrap_or_default();
+    let keys = transaction_account_keys(txn);
+    let key_at = |index: u32| keys.get(index as usize).copied();
+
+    for inner in &meta.inner_instructions {
+        let outer = outer_instructions.get(inner.index as usize);
+        let outer_program = outer.and_then(|ix| key_at(ix.program_id_index));
+
+        for (position, ix) in inner.instructions.iter().enumerate() {
+            let dex_type = match key_at(ix.program_id_index).as_ref().and_then(dex_program_type) {
+                Some(dex_type) => dex_type,
+                None => continue,
+            };
+            if ix.data.len() < 16
+                || ix.data[..8] != ANCHOR_EVENT_IX_TAG
+                || !trade_event_lengths(&dex_type).contains(&ix.data.len())
+            {
+                continue;
+            }
+
+            // The emitting instruction is the closest earlier call into the same program one
+            // level up the stack, or the top-level instruction when the DEX was called directly
+            let emitter_accounts: Option<&[u8]> = inner.instructions[..position].iter().rev()
+                .find(|candidate| {
+                    candidate.program_id_index == ix.program_id_index
+                        && candidate.data.get(..8) != Some(&ANCHOR_EVENT_IX_TAG[..])
+                        && match (candidate.stack_height, ix.stack_height) {
+                            (Some(caller), Some(event)) => caller + 1 == event,
+                            _ => true,
+                        }
+                })
+                .map(|candidate| candidate.accounts.as_slice())
+                .or_else(|| outer.filter(|o| o.program_id_index == ix.program_id_index).map(|o| o.accounts.as_slice()));
+            let account = |position: usize| {
+                emitter_accounts.and_then(|accounts| accounts.get(position)).and_then(|index| key_at(*index as u32))
+            };
+
+            let context = match dex_type {
+                DexType::PumpSwap => EventContext {
+                    mint: [account(PUMP_SWAP_BASE_MINT_ACCOUNT), account(PUMP_SWAP_QUOTE_MINT_ACCOUNT)]
+                        .into_iter()
+                        .flatten()
+                        .map(|mint| mint.to_string())
+                        .find(|mint| QuoteAsset::from_mint(mint).is_none()),
+                    quote: account(PUMP_SWAP_QUOTE_MINT_ACCOUNT).and_then(|mint| QuoteAsset::from_mint(&mint.to_string())),
+                    pump_swap_buy: match &ix.data[8..16] {
+                        d if d == PUMP_SWAP_BUY_EVENT => Some(true),
+                        d if d == PUMP_SWAP_SELL_EVENT => Some(false),
+                        _ => None,
+                    },
+                },
+                DexType::RaydiumLaunchpad => EventContext {
+                    mint: account(RAYDIUM_LAUNCHPAD_BASE_MINT_ACCOUNT).map(|mint| mint.to_string()),
+                    quote: account(RAYDIUM_LAUNCHPAD_QUOTE_MINT_ACCOUNT).and_then(|mint| QuoteAsset::from_mint(&mint.to_string())),
+                    pump_swap_buy: None,
+                },
+                _ => EventContext::default(),
+            };
+
+            events.push(DexEventCpi {
+                dex_type,
+                outer_index: inner.index,
+                outer_program,
+                data: ix.data.clone(),
+                context,
+            });
+        }
+    }
+    events
+}
+
+/// Parse every DEX trade in a transaction, including swaps executed through CPIs, with slot and
+/// signature set
+pub fn parse_swap_events(txn: &SubscribeUpdateTransaction) -> Vec<TradeInfoFromToken> {
+    let signature = txn.transaction.as_ref()
+        .map(|info| bs58::encode(&info.signature).into_string())
+        .unwrap_or_default();
+    find_dex_event_cpis(txn)
+        .into_iter()
+        .filter(|event| event.dex_type.is_enabled())
+        .filter_map(|event| {
+            let mut trade = parse_event_data(txn, &event.data, &event.context)?;
+            trade.dex_type = event.dex_type.clone();
+            trade.slot = txn.slot;
+            trade.signature = signature.clone();
+            quote_asset::remember(&trade.mint, trade.quote_asset);
+            if let Some(creator) = &trade.coin_creator {
+                crate::processor::mute_list::remember_creator(&trade.mint, creator);
+            }
+            if event.is_routed() {
+                dex_log(format!(
+                    "{} swap on {} routed via {}",
+                    trade.dex_type.as_str(),
+                    trade.mint,
+                    event.outer_program.map(|p| p.to_string()).unwrap_or_default()
+                ));
+            }
+            Some(trade)
+        })
+        .collect()
+}
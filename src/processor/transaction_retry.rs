@@ -21,12 +21,15 @@ use crate::common::{
 use crate::processor::swap::SwapDirection;
 use crate::library::jupiter_api::JupiterClient;
 use crate::processor::transaction_parser::TradeInfoFromToken;
+use crate::processor::trade_ledger;
 use crate::block_engine::tx;
+use crate::error::{BotError, RetryPolicy};
 
 /// Maximum number of retry attempts for selling transactions
 const MAX_RETRIES: u32 = 3;
 
-/// Delay between retry attempts
+/// Delay between retry attempts after a failed verification; send failures back off by their
+/// error class
 const RETRY_DELAY: Duration = Duration::from_secs(2);
 
 /// Timeout for transaction verification
@@ -65,6 +68,7 @@ pub async fn verify_transaction_with_retry(
                     if let Some(status) = status_opt {
                         if status.err.is_none() {
                             logger.log(format!("✅ Transaction verified successfully: {}", signature).green().to_string());
+                            trade_ledger::record_confirmed(app_state.clone(), *signature);
                             return Ok(true);
                         } else {
                             logger.log(format!("❌ Transaction failed with error: {:?}", status.err).red().to_string());
@@ -147,6 +151,7 @@ async fn execute_normal_sell_with_retry(
     let mut last_error = String::new();
 
     for attempt in 1..=MAX_RETRIES {
+        let mut retry_delay = RETRY_DELAY;
         logger.log(format!("🔄 Normal sell attempt {}/{} for token: {}", attempt, MAX_RETRIES, trade_info.mint).cyan().to_string());
 
         match execute_single_sell_attempt(trade_info, sell_config.clone(), app_state.clone(), logger).await {
@@ -175,14 +180,22 @@ async fn execute_normal_sell_with_retry(
                 }
             }
             Err(e) => {
+                let error = BotError::classify(&e);
                 last_error = e.to_string();
                 logger.log(format!("❌ Attempt {} failed: {}", attempt, last_error).yellow().to_string());
+                // Retrying a fatal failure (bad input, insufficient funds, rejected) cannot help;
+                // go straight to the Jupiter fallback
+                if !error.is_transient() {
+                    logger.log(format!("Not retrying fatal error: {}", error).red().to_string());
+                    return Err(anyhow!("Normal sell failed on attempt {}: {}", attempt, last_error));
+                }
+                retry_delay = RetryPolicy::for_error(&error).backoff(attempt);
             }
         }
 
         if attempt < MAX_RETRIES {
-            logger.log(format!("⏳ Waiting {:?} before retry...", RETRY_DELAY).yellow().to_string());
-            sleep(RETRY_DELAY).await;
+            logger.log(format!("⏳ Waiting {:?} before retry...", retry_delay).yellow().to_string());
+            sleep(retry_delay).await;
         }
     }
 
@@ -271,8 +284,9 @@ async fn execute_raydium_sell_attempt(
     let recent_blockhash = crate::library::blockhash_processor::BlockhashProcessor::get_latest_blockhash().await
         .ok_or_else(|| anyhow!("Failed to get recent blockhash"))?;
 
-    let signatures = crate::block_engine::tx::new_signed_and_send_zeroslot(
-        app_state.zeroslot_rpc_client.clone(),
+    let signatures = crate::block_engine::tx::new_signed_and_send_by_urgency(
+        crate::library::priority_lane::Urgency::Normal,
+        app_state.rpc_api.clone(),
         recent_blockhash,
         &keypair,
         instructions,
@@ -0,0 +1,300 @@
+//! Trending-token ranking.
+//!
+//! Every trade the monitor sees is kept for two ranking windows (`TRENDING_WINDOW_MIN`). Once a
+//! minute each token with at least `TRENDING_MIN_VOLUME_SOL` traded in the last window gets a
+//! composite score out of 100, built from four parts:
+//!
+//! - volume acceleration: volume of the last window against the window before (saturates at 4x)
+//! - unique buyers in the last window
+//! - holder growth: wallets that bought in for the first time minus wallets that sold out
+//! - price momentum over the last window (saturates at +100%, negative when falling)
+//!
+//! The best `TRENDING_TOP_N` are served by `/trending`, the gRPC API and the hourly report.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use chrono::{DateTime, Duration, Utc};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::library::rolling::RollingWindow;
+use crate::processor::transaction_parser::{ParsedData, SwapType};
+
+/// Share of the composite score given to volume acceleration, unique buyers, holder growth and
+/// price momentum
+const WEIGHTS: [f64; 4] = [0.30, 0.25, 0.25, 0.20];
+/// Acceleration that earns the full volume part
+const FULL_ACCELERATION: f64 = 4.0;
+/// Buyers (and net new holders) in one window that earn the full part
+const FULL_BUYERS: f64 = 25.0;
+/// Price change (%) over one window that earns the full momentum part
+const FULL_MOMENTUM_PCT: f64 = 100.0;
+
+#[derive(Clone, Debug)]
+pub struct TrendingConfig {
+    /// Length of the window each part is measured over
+    pub window: Duration,
+    /// Tokens served by `/trending` and the API
+    pub top_n: usize,
+    /// Tokens that traded less than this in the last window are not ranked
+    pub min_volume_sol: f64,
+}
+
+impl Default for TrendingConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::minutes(5),
+            top_n: 10,
+            min_volume_sol: 1.0,
+        }
+    }
+}
+
+impl TrendingConfig {
+    /// TRENDING_WINDOW_MIN (default 5), TRENDING_TOP_N (default 10) and TRENDING_MIN_VOLUME_SOL
+    /// (default 1.0)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            window: std::env::var("TRENDING_WINDOW_MIN")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|m| *m > 0)
+                .map(Duration::minutes)
+                .unwrap_or(defaults.window),
+            top_n: std::env::var("TRENDING_TOP_N")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(defaults.top_n),
+            min_volume_sol: std::env::var("TRENDING_MIN_VOLUME_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.min_volume_sol),
+        }
+    }
+}
+
+/// A token's place in the ranking at the last refresh
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrendingToken {
+    pub mint: Pubkey,
+    pub name: Option<String>,
+    /// Composite score, 100 at most
+    pub score: f64,
+    /// SOL traded in the last window
+    pub volume_sol: f64,
+    /// Last window's volume against the one before
+    pub volume_acceleration: f64,
+    pub unique_buyers: usize,
+    pub holder_growth: i64,
+    /// Price change over the last window, None without a price at both ends
+    pub price_change_pct: Option<f64>,
+}
+
+struct Trade {
+    at: DateTime<Utc>,
+    buyer: Option<Pubkey>,
+    price: Option<f64>,
+}
+
+/// One wallet's position in a token, as far as the monitor has seen it
+struct Position {
+    bought: f64,
+    sold: f64,
+    first_buy: DateTime<Utc>,
+    exited_at: Option<DateTime<Utc>>,
+}
+
+struct TokenActivity {
+    name: Option<String>,
+    /// SOL traded, over two ranking windows
+    volume: RollingWindow,
+    trades: VecDeque<Trade>,
+    positions: HashMap<Pubkey, Position>,
+}
+
+impl TokenActivity {
+    fn new(config: &TrendingConfig) -> Self {
+        Self {
+            name: None,
+            volume: RollingWindow::new(config.window * 2),
+            trades: VecDeque::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    fn score(&self, mint: Pubkey, config: &TrendingConfig, now: DateTime<Utc>) -> Option<TrendingToken> {
+        let start = now - config.window;
+        let previous_start = start - config.window;
+
+        let volume_sol = self.volume.sum_since(start);
+        if volume_sol < config.min_volume_sol || volume_sol <= 0.0 {
+            return None;
+        }
+        let previous_volume = self.volume.sum_between(previous_start, start);
+        // A token with no earlier volume accelerates from the eligibility floor
+        let volume_acceleration = volume_sol / previous_volume.max(config.min_volume_sol).max(f64::EPSILON);
+
+        let unique_buyers = self.trades.iter()
+            .filter(|t| t.at > start)
+            .filter_map(|t| t.buyer)
+            .collect::<HashSet<_>>()
+            .len();
+
+        let joined = self.positions.values().filter(|p| p.first_buy > start && p.exited_at.is_none()).count();
+        let left = self.positions.values()
+            .filter(|p| p.first_buy <= start && p.exited_at.is_some_and(|at| at > start))
+            .count();
+        let holder_growth = joined as i64 - left as i64;
+
+        let opening_price = self.trades.iter().rev()
+            .find(|t| t.at <= start && t.price.is_some())
+            .or_else(|| self.trades.iter().find(|t| t.at > start && t.price.is_some()))
+            .and_then(|t| t.price);
+        let latest_price = self.trades.iter().rev().find_map(|t| t.price);
+        let price_change_pct = match (opening_price, latest_price) {
+            (Some(open), Some(latest)) if open > 0.0 => Some((latest - open) / open * 100.0),
+            _ => None,
+        };
+
+        let parts = [
+            (volume_acceleration / FULL_ACCELERATION).min(1.0),
+            (unique_buyers as f64 / FULL_BUYERS).min(1.0),
+            (holder_growth as f64 / FULL_BUYERS).clamp(-1.0, 1.0),
+            (price_change_pct.unwrap_or(0.0) / FULL_MOMENTUM_PCT).clamp(-1.0, 1.0),
+        ];
+        let score = parts.iter().zip(WEIGHTS).map(|(part, weight)| part * weight).sum::<f64>() * 100.0;
+
+        Some(TrendingToken {
+            mint,
+            name: self.name.clone(),
+            score,
+            volume_sol,
+            volume_acceleration,
+            unique_buyers,
+            holder_growth,
+            price_change_pct,
+        })
+    }
+}
+
+pub struct TrendingTracker {
+    config: TrendingConfig,
+    tokens: HashMap<Pubkey, TokenActivity>,
+    ranking: Vec<TrendingToken>,
+    refreshed_at: Option<DateTime<Utc>>,
+}
+
+impl TrendingTracker {
+    pub fn new(config: TrendingConfig) -> Self {
+        Self { config, tokens: HashMap::new(), ranking: Vec::new(), refreshed_at: None }
+    }
+
+    pub fn config(&self) -> &TrendingConfig {
+        &self.config
+    }
+
+    /// Record a buy or sell; other events do not count towards trending
+    pub fn record(&mut self, parsed_data: &ParsedData, now: DateTime<Utc>) {
+        let is_buy = match parsed_data.swap_type {
+            SwapType::Buy => true,
+            SwapType::Sell => false,
+            _ => return,
+        };
+        let config = &self.config;
+        let activity = self.tokens.entry(parsed_data.token_mint).or_insert_with(|| TokenActivity::new(config));
+        if parsed_data.token_name.is_some() {
+            activity.name = parsed_data.token_name.clone();
+        }
+
+        let amount = parsed_data.token_amount.unwrap_or(0.0);
+        if is_buy {
+            let position = activity.positions.entry(parsed_data.signer).or_insert(Position {
+                bought: 0.0,
+                sold: 0.0,
+                first_buy: now,
+                exited_at: None,
+            });
+            position.bought += amount;
+            position.exited_at = None;
+        } else if let Some(position) = activity.positions.get_mut(&parsed_data.signer) {
+            position.sold += amount;
+            // Without a token amount the sell is taken as a full exit; dust left behind counts as out
+            if parsed_data.token_amount.is_none() || position.sold >= position.bought * 0.99 {
+                position.exited_at.get_or_insert(now);
+            }
+        }
+
+        activity.volume.push(now, parsed_data.sol_amount.unwrap_or(0.0));
+        activity.trades.push_back(Trade {
+            at: now,
+            buyer: is_buy.then_some(parsed_data.signer),
+            price: parsed_data.token_price.filter(|p| *p > 0.0 && p.is_finite()),
+        });
+    }
+
+    /// Re-score every token and rank them, best first. Trades older than two windows are dropped.
+    pub fn refresh(&mut self, now: DateTime<Utc>) -> &[TrendingToken] {
+        let cutoff = now - self.config.window * 2;
+        for activity in self.tokens.values_mut() {
+            activity.volume.evict(now);
+            // One trade before the window start is kept as the opening price
+            while activity.trades.len() > 1 && activity.trades[1].at <= cutoff {
+                activity.trades.pop_front();
+            }
+        }
+
+        let mut ranking: Vec<TrendingToken> = self.tokens.iter()
+            .filter_map(|(mint, activity)| activity.score(*mint, &self.config, now))
+            .collect();
+        ranking.sort_by(|a, b| b.score.total_cmp(&a.score));
+        self.ranking = ranking;
+        self.refreshed_at = Some(now);
+        &self.ranking
+    }
+
+    /// Best `n` tokens of the last refresh
+    pub fn top(&self, n: usize) -> Vec<TrendingToken> {
+        self.ranking.iter().take(n).cloned().collect()
+    }
+
+    pub fn refreshed_at(&self) -> Option<DateTime<Utc>> {
+        self.refreshed_at
+    }
+
+    /// Forget tokens that are no longer tracked
+    pub fn retain(&mut self, keep: impl Fn(&Pubkey) -> bool) {
+        self.tokens.retain(|mint, _| keep(mint));
+        self.ranking.retain(|token| keep(&token.mint));
+    }
+
+    /// Number of tokens with recorded activity
+    pub fn token_count(&self) -> usize {
+        self.tokens.len()
+    }
+}
+
+/// Ranked list for Telegram and reports
+pub fn format_trending(tokens: &[TrendingToken]) -> String {
+    if tokens.is_empty() {
+        return "No token is trending yet.".to_string();
+    }
+    tokens.iter()
+        .enumerate()
+        .map(|(i, token)| {
+            let name = token.name.clone().unwrap_or_else(|| token.mint.to_string()[..8].to_string());
+            let momentum = token.price_change_pct.map(|pct| format!(" | {:+.1}%", pct)).unwrap_or_default();
+            format!(
+                "{}. {} — score {:.0}\n   {:.2} SOL ({:.1}x) | {} buyers | {:+} holders{}",
+                i + 1,
+                name,
+                token.score,
+                token.volume_sol,
+                token.volume_acceleration,
+                token.unique_buyers,
+                token.holder_growth,
+                momentum,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
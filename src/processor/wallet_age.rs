@@ -0,0 +1,243 @@
+/*!
+# Buyer Wallet Age
+
+Classifies the buyers of each tracked token as fresh or aged. A wallet is fresh when its first
+transaction (the transfer that created it) landed at most `WALLET_FRESH_MAX_AGE_MIN` before
+its buy, and the fee payer of that transaction is recorded as its funding source. Wallets with a
+history longer than a few pages of signatures are aged.
+
+Bot-farmed launches are bought by batches of wallets created minutes earlier, usually by the
+same funder, so the fresh-wallet share of a token's buyers is kept in its analytics (see
+`TokenMetrics::fresh_wallet_pct`). Only the first `WALLET_AGE_MAX_BUYERS` buyers of a token are
+looked up, which is where farming shows and keeps the RPC cost bounded.
+
+## Environment Variables
+
+- `WALLET_AGE_CHECKS_ENABLED`: Look up buyer wallet ages (default: `true`)
+- `WALLET_FRESH_MAX_AGE_MIN`: Wallets created this recently before buying are fresh (default: `60`)
+- `WALLET_AGE_MAX_BUYERS`: Buyers checked per token (default: `50`)
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anyhow::Result;
+use async_trait::async_trait;
+use dashmap::DashMap;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::Mutex;
+
+use crate::processor::insider_detection::{fee_payer, oldest_transaction};
+use crate::processor::transaction_parser::{ParsedData, SwapType};
+
+#[derive(Clone, Debug)]
+pub struct WalletAgeConfig {
+    pub enabled: bool,
+    /// Seconds between a wallet's first transaction and its buy within which it counts as fresh
+    pub fresh_max_age_secs: i64,
+    pub max_buyers: usize,
+}
+
+impl Default for WalletAgeConfig {
+    fn default() -> Self {
+        Self { enabled: true, fresh_max_age_secs: 60 * 60, max_buyers: 50 }
+    }
+}
+
+impl WalletAgeConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            enabled: var("WALLET_AGE_CHECKS_ENABLED")
+                .and_then(|v| bool::from_str(&v.to_lowercase()).ok())
+                .unwrap_or(defaults.enabled),
+            fresh_max_age_secs: var("WALLET_FRESH_MAX_AGE_MIN")
+                .and_then(|v| v.parse::<i64>().ok())
+                .map(|minutes| minutes * 60)
+                .unwrap_or(defaults.fresh_max_age_secs),
+            max_buyers: var("WALLET_AGE_MAX_BUYERS").and_then(|v| v.parse().ok()).unwrap_or(defaults.max_buyers),
+        }
+    }
+}
+
+/// Where a wallet came from
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WalletOrigin {
+    /// Block time (unix seconds) of its first transaction, None when its history is too long to
+    /// walk or the time is unknown
+    pub created_at: Option<i64>,
+    /// Fee payer of that transaction, None when the wallet paid for it itself
+    pub funder: Option<Pubkey>,
+}
+
+#[async_trait]
+pub trait WalletOriginLookup: Send + Sync {
+    async fn origin(&self, wallet: &Pubkey) -> Result<WalletOrigin>;
+}
+
+pub struct RpcWalletOriginLookup {
+    rpc_client: Arc<RpcClient>,
+}
+
+impl RpcWalletOriginLookup {
+    pub fn new(rpc_client: Arc<RpcClient>) -> Self {
+        Self { rpc_client }
+    }
+}
+
+#[async_trait]
+impl WalletOriginLookup for RpcWalletOriginLookup {
+    async fn origin(&self, wallet: &Pubkey) -> Result<WalletOrigin> {
+        let (signature, created_at) = match oldest_transaction(&self.rpc_client, wallet).await? {
+            Some(oldest) => oldest,
+            None => return Ok(WalletOrigin { created_at: None, funder: None }),
+        };
+        // Only fresh wallets need a funder, and only a known creation time can make one fresh
+        let funder = match created_at {
+            Some(_) => fee_payer(&self.rpc_client, &signature).await?.filter(|payer| payer != wallet),
+            None => None,
+        };
+        Ok(WalletOrigin { created_at, funder })
+    }
+}
+
+/// Age of a buyer when it bought
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BuyerAge {
+    Fresh { age_secs: i64, funder: Option<Pubkey> },
+    Aged,
+}
+
+/// Ages of the buyers of one token checked so far
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BuyerAgeStats {
+    pub checked: usize,
+    pub fresh: usize,
+    /// Fresh buyers per funding wallet
+    pub funders: HashMap<Pubkey, usize>,
+}
+
+impl BuyerAgeStats {
+    /// Share of checked buyers that were fresh wallets (%), None before any was checked
+    pub fn fresh_pct(&self) -> Option<f64> {
+        (self.checked > 0).then(|| self.fresh as f64 / self.checked as f64 * 100.0)
+    }
+
+    /// Wallet that funded the most fresh buyers, with how many
+    pub fn top_funder(&self) -> Option<(Pubkey, usize)> {
+        self.funders.iter().max_by_key(|(_, count)| **count).map(|(funder, count)| (*funder, *count))
+    }
+}
+
+#[derive(Default)]
+struct TokenBuyers {
+    seen: HashSet<Pubkey>,
+    stats: BuyerAgeStats,
+}
+
+pub struct WalletAgeChecker {
+    config: WalletAgeConfig,
+    lookup: Arc<dyn WalletOriginLookup>,
+    tokens: Mutex<HashMap<Pubkey, TokenBuyers>>,
+    origins: DashMap<Pubkey, WalletOrigin>,
+}
+
+impl WalletAgeChecker {
+    pub fn new(config: WalletAgeConfig, lookup: Arc<dyn WalletOriginLookup>) -> Self {
+        Self { config, lookup, tokens: Mutex::new(HashMap::new()), origins: DashMap::new() }
+    }
+
+    pub fn from_env(rpc_client: Arc<RpcClient>) -> Self {
+        Self::new(WalletAgeConfig::from_env(), Arc::new(RpcWalletOriginLookup::new(rpc_client)))
+    }
+
+    /// Cached origin; lookup failures are not cached so a later buy can retry
+    async fn origin(&self, wallet: &Pubkey) -> Option<WalletOrigin> {
+        if let Some(known) = self.origins.get(wallet) {
+            return Some(*known);
+        }
+        match self.lookup.origin(wallet).await {
+            Ok(origin) => {
+                self.origins.insert(*wallet, origin);
+                Some(origin)
+            }
+            Err(e) => {
+                eprintln!("Wallet age lookup for {} failed: {}", wallet, e);
+                None
+            }
+        }
+    }
+
+    /// Check the buyer of a trade the first time it buys the token; returns the token's updated
+    /// stats when a buyer was classified
+    pub async fn observe(&self, parsed_data: &ParsedData) -> Option<BuyerAgeStats> {
+        if !self.config.enabled || parsed_data.swap_type != SwapType::Buy {
+            return None;
+        }
+        {
+            let tokens = self.tokens.lock().await;
+            if let Some(buyers) = tokens.get(&parsed_data.token_mint) {
+                if buyers.seen.contains(&parsed_data.signer) || buyers.stats.checked >= self.config.max_buyers {
+                    return None;
+                }
+            }
+        }
+
+        let origin = self.origin(&parsed_data.signer).await?;
+        let age = classify(&origin, parsed_data.timestamp as i64, self.config.fresh_max_age_secs);
+
+        let mut tokens = self.tokens.lock().await;
+        let buyers = tokens.entry(parsed_data.token_mint).or_default();
+        if !buyers.seen.insert(parsed_data.signer) {
+            return None;
+        }
+        buyers.stats.checked += 1;
+        if let BuyerAge::Fresh { funder, .. } = age {
+            buyers.stats.fresh += 1;
+            if let Some(funder) = funder {
+                *buyers.stats.funders.entry(funder).or_default() += 1;
+            }
+        }
+        Some(buyers.stats.clone())
+    }
+
+    /// Origin of any wallet through the same cache; None when checks are off or the lookup failed
+    pub async fn lookup(&self, wallet: &Pubkey) -> Option<WalletOrigin> {
+        if !self.config.enabled {
+            return None;
+        }
+        self.origin(wallet).await
+    }
+
+    pub async fn stats(&self, mint: &Pubkey) -> Option<BuyerAgeStats> {
+        self.tokens.lock().await.get(mint).map(|buyers| buyers.stats.clone())
+    }
+
+    pub async fn token_count(&self) -> usize {
+        self.tokens.lock().await.len()
+    }
+
+    pub fn origin_cache_size(&self) -> usize {
+        self.origins.len()
+    }
+
+    /// Drop tokens no longer tracked; wallet origins never change, so they are only cleared
+    /// along with them to free memory
+    pub async fn retain(&self, keep: impl Fn(&Pubkey) -> bool) {
+        self.tokens.lock().await.retain(|mint, _| keep(mint));
+        self.origins.clear();
+    }
+}
+
+/// Age of a wallet with `origin` when it bought at `bought_at` (unix seconds)
+pub fn classify(origin: &WalletOrigin, bought_at: i64, fresh_max_age_secs: i64) -> BuyerAge {
+    match origin.created_at {
+        Some(created_at) if bought_at - created_at <= fresh_max_age_secs => BuyerAge::Fresh {
+            age_secs: (bought_at - created_at).max(0),
+            funder: origin.funder,
+        },
+        _ => BuyerAge::Aged,
+    }
+}
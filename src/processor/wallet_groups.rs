@@ -0,0 +1,402 @@
+/*!
+# Wallet Groups
+
+Tracked wallets imported in bulk, each with a label and a group tag (e.g. `KOLs`, `Smart money`,
+`Team`). Import them with `/import_wallets`, pasting a list or attaching a CSV/JSON file, and
+list them with `/wallets`.
+
+Instead of one alert per wallet, buys are aggregated per group: once `WALLET_GROUP_MIN_WALLETS`
+different wallets of a group buy the same token within `WALLET_GROUP_WINDOW_SECS`, a single alert
+names them ("3 wallets in group 'KOLs' bought X in 5 minutes"). The group alerts again on that
+token only after another window has passed.
+
+CSV rows are `address[,label[,group]]`, with an optional header; JSON is an array of addresses or
+of `{"address", "label", "group"}` objects. Wallets without a group go to `default`; wallets
+without a label are labeled automatically after their group (`KOLs #4`). Importing a wallet
+again updates its label and group.
+
+## Environment Variables
+
+- `WALLET_GROUPS_PATH`: File the imported wallets are kept in across restarts (default:
+  `wallet_groups.json`, empty keeps them in memory only)
+- `WALLET_GROUP_WINDOW_SECS`: Window the buys of a group are aggregated over (default: `300`)
+- `WALLET_GROUP_MIN_WALLETS`: Wallets of a group buying the same token that raise an alert
+  (default: `3`)
+*/
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::processor::transaction_parser::{ParsedData, SwapType};
+
+/// Group of wallets imported without one
+pub const DEFAULT_GROUP: &str = "default";
+/// Wallets listed per group by `/wallets`
+const LISTED_WALLETS: usize = 10;
+
+#[derive(Clone, Debug)]
+pub struct WalletGroupsConfig {
+    pub path: Option<PathBuf>,
+    pub window_secs: i64,
+    pub min_wallets: usize,
+}
+
+impl Default for WalletGroupsConfig {
+    fn default() -> Self {
+        Self {
+            path: Some(PathBuf::from("wallet_groups.json")),
+            window_secs: 300,
+            min_wallets: 3,
+        }
+    }
+}
+
+impl WalletGroupsConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            path: match var("WALLET_GROUPS_PATH") {
+                Some(path) => Some(PathBuf::from(path)).filter(|p| !p.as_os_str().is_empty()),
+                None => defaults.path,
+            },
+            window_secs: var("WALLET_GROUP_WINDOW_SECS")
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|s| *s > 0)
+                .unwrap_or(defaults.window_secs),
+            min_wallets: var("WALLET_GROUP_MIN_WALLETS")
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(defaults.min_wallets),
+        }
+    }
+}
+
+/// A tracked wallet, as imported and persisted
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrackedWallet {
+    pub address: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub group: Option<String>,
+}
+
+/// Result of parsing a wallet list: valid wallets plus the entries that were rejected
+#[derive(Debug, Default)]
+pub struct ParsedWallets {
+    pub wallets: Vec<(Pubkey, TrackedWallet)>,
+    pub invalid: Vec<String>,
+}
+
+/// Parse a wallet list in JSON or CSV form, detecting the format from the content. Plain
+/// whitespace or comma separated addresses (e.g. pasted in a chat message) are also accepted.
+pub fn parse_wallets(content: &str) -> ParsedWallets {
+    let trimmed = content.trim();
+    let raw: Vec<TrackedWallet> = if trimmed.starts_with('[') {
+        match parse_json(trimmed) {
+            Ok(wallets) => wallets,
+            Err(e) => return ParsedWallets { wallets: Vec::new(), invalid: vec![format!("invalid JSON: {}", e)] },
+        }
+    } else {
+        parse_csv(trimmed)
+    };
+
+    let mut parsed = ParsedWallets::default();
+    for wallet in raw {
+        match Pubkey::from_str(wallet.address.trim()) {
+            Ok(address) if !parsed.wallets.iter().any(|(a, _)| *a == address) => parsed.wallets.push((address, wallet)),
+            Ok(_) => {}
+            Err(_) => parsed.invalid.push(wallet.address),
+        }
+    }
+    parsed
+}
+
+fn parse_json(content: &str) -> anyhow::Result<Vec<TrackedWallet>> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum JsonWallet {
+        Address(String),
+        Wallet(TrackedWallet),
+    }
+
+    let wallets: Vec<JsonWallet> = serde_json::from_str(content)?;
+    Ok(wallets
+        .into_iter()
+        .map(|w| match w {
+            JsonWallet::Address(address) => TrackedWallet { address, label: None, group: None },
+            JsonWallet::Wallet(wallet) => wallet,
+        })
+        .collect())
+}
+
+fn parse_csv(content: &str) -> Vec<TrackedWallet> {
+    let mut wallets = Vec::new();
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields[0].eq_ignore_ascii_case("address") || fields[0].eq_ignore_ascii_case("wallet") {
+            continue;
+        }
+        // A line of several addresses (no labels) is a pasted list rather than a CSV row
+        let pasted_list = (fields.len() > 1 && fields.iter().all(|f| Pubkey::from_str(f).is_ok()))
+            || (line.contains(' ') && !line.contains(','));
+        if pasted_list {
+            wallets.extend(
+                line.split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|a| !a.is_empty())
+                    .map(|address| TrackedWallet { address: address.to_string(), label: None, group: None }),
+            );
+            continue;
+        }
+        let field = |i: usize| fields.get(i).filter(|f| !f.is_empty()).map(|f| f.to_string());
+        wallets.push(TrackedWallet { address: fields[0].to_string(), label: field(1), group: field(2) });
+    }
+    wallets
+}
+
+/// Buys of one group in one token, raised once enough of its wallets bought within the window
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupActivity {
+    pub group: String,
+    pub mint: Pubkey,
+    pub token_name: Option<String>,
+    /// Label and SOL bought of each wallet, first buyer first
+    pub buyers: Vec<(String, f64)>,
+    pub total_sol: f64,
+    /// Seconds from the first to the last of the buys
+    pub span_secs: i64,
+}
+
+impl GroupActivity {
+    /// "3 wallets in group 'KOLs' bought X in 5 minutes"
+    pub fn headline(&self) -> String {
+        let span = if self.span_secs < 60 {
+            format!("{}s", self.span_secs)
+        } else {
+            format!("{} minutes", (self.span_secs + 59) / 60)
+        };
+        format!(
+            "{} wallets in group '{}' bought {} in {}",
+            self.buyers.len(),
+            self.group,
+            self.token_name.as_deref().unwrap_or("Unknown"),
+            span
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct GroupBuy {
+    at: i64,
+    wallet: Pubkey,
+    sol: f64,
+}
+
+pub struct WalletGroups {
+    config: WalletGroupsConfig,
+    wallets: Mutex<HashMap<Pubkey, TrackedWallet>>,
+    /// Recent buys per (group, mint)
+    buys: Mutex<HashMap<(String, Pubkey), Vec<GroupBuy>>>,
+    /// Unix seconds of the last alert per (group, mint)
+    alerted: Mutex<HashMap<(String, Pubkey), i64>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl WalletGroups {
+    /// Wallet groups persisted at the configured path (if any), reloading earlier imports
+    pub fn new(config: WalletGroupsConfig) -> Self {
+        let wallets = config.path.as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<Vec<TrackedWallet>>(&content).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|wallet| Some((Pubkey::from_str(&wallet.address).ok()?, wallet)))
+            .collect();
+        Self {
+            config,
+            wallets: Mutex::new(wallets),
+            buys: Mutex::new(HashMap::new()),
+            alerted: Mutex::new(HashMap::new()),
+            clock: system_clock(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(WalletGroupsConfig::from_env())
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Track `wallets`, filling in groups and labels they lack; returns (added, updated)
+    pub fn import(&self, wallets: Vec<(Pubkey, TrackedWallet)>) -> (usize, usize) {
+        let mut tracked = self.wallets.lock().unwrap();
+        let mut group_sizes: HashMap<String, usize> = HashMap::new();
+        for wallet in tracked.values() {
+            *group_sizes.entry(group_of(wallet).to_string()).or_default() += 1;
+        }
+        let (mut added, mut updated) = (0, 0);
+        for (address, mut wallet) in wallets {
+            let group = wallet.group.take()
+                .map(|g| g.trim().to_string())
+                .filter(|g| !g.is_empty())
+                .unwrap_or_else(|| DEFAULT_GROUP.to_string());
+            let label = match wallet.label.take().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()) {
+                Some(label) => label,
+                None => match tracked.get(&address).filter(|known| group_of(known) == group) {
+                    Some(known) => known.label.clone().unwrap_or_default(),
+                    None => {
+                        let size = group_sizes.entry(group.clone()).or_default();
+                        *size += 1;
+                        format!("{} #{}", group, size)
+                    }
+                },
+            };
+            let wallet = TrackedWallet { address: address.to_string(), label: Some(label), group: Some(group) };
+            match tracked.insert(address, wallet) {
+                Some(_) => updated += 1,
+                None => added += 1,
+            }
+        }
+        self.save(&tracked);
+        (added, updated)
+    }
+
+    pub fn get(&self, wallet: &Pubkey) -> Option<TrackedWallet> {
+        self.wallets.lock().unwrap().get(wallet).cloned()
+    }
+
+    /// Label of a tracked wallet
+    pub fn label(&self, wallet: &Pubkey) -> Option<String> {
+        self.get(wallet).and_then(|w| w.label)
+    }
+
+    /// Group of a tracked wallet
+    pub fn group(&self, wallet: &Pubkey) -> Option<String> {
+        self.get(wallet).map(|w| group_of(&w).to_string())
+    }
+
+    /// Tracked wallets in `group`
+    pub fn group_size(&self, group: &str) -> usize {
+        self.wallets.lock().unwrap().values().filter(|w| group_of(w) == group).count()
+    }
+
+    /// Tracked wallets
+    pub fn len(&self) -> usize {
+        self.wallets.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Tracked wallets per group, by group name
+    pub fn groups(&self) -> BTreeMap<String, Vec<TrackedWallet>> {
+        let mut groups: BTreeMap<String, Vec<TrackedWallet>> = BTreeMap::new();
+        for wallet in self.wallets.lock().unwrap().values() {
+            groups.entry(group_of(wallet).to_string()).or_default().push(wallet.clone());
+        }
+        for wallets in groups.values_mut() {
+            wallets.sort_by(|a, b| a.label.cmp(&b.label));
+        }
+        groups
+    }
+
+    /// Count a buy by a tracked wallet towards its group; returns the group's activity when it
+    /// reaches the alert threshold
+    pub fn observe(&self, parsed_data: &ParsedData) -> Option<GroupActivity> {
+        if !matches!(parsed_data.swap_type, SwapType::Buy) {
+            return None;
+        }
+        let group = group_of(&self.get(&parsed_data.signer)?).to_string();
+        let now = self.clock.now().timestamp();
+        let key = (group.clone(), parsed_data.token_mint);
+
+        let recent = {
+            let mut buys = self.buys.lock().unwrap();
+            // Drop windows gone quiet so the map does not grow with every token ever bought
+            buys.retain(|_, list| list.last().is_some_and(|b| now - b.at < self.config.window_secs));
+            let list = buys.entry(key.clone()).or_default();
+            list.retain(|b| now - b.at < self.config.window_secs);
+            list.push(GroupBuy { at: now, wallet: parsed_data.signer, sol: parsed_data.sol_amount.unwrap_or(0.0) });
+            list.clone()
+        };
+
+        let mut per_wallet: Vec<(Pubkey, f64)> = Vec::new();
+        for buy in &recent {
+            match per_wallet.iter_mut().find(|(wallet, _)| *wallet == buy.wallet) {
+                Some((_, sol)) => *sol += buy.sol,
+                None => per_wallet.push((buy.wallet, buy.sol)),
+            }
+        }
+        if per_wallet.len() < self.config.min_wallets {
+            return None;
+        }
+        {
+            let mut alerted = self.alerted.lock().unwrap();
+            alerted.retain(|_, at| now - *at < self.config.window_secs);
+            if alerted.contains_key(&key) {
+                return None;
+            }
+            alerted.insert(key, now);
+        }
+
+        let buyers: Vec<(String, f64)> = per_wallet.iter()
+            .map(|(wallet, sol)| (self.label(wallet).unwrap_or_else(|| wallet.to_string()), *sol))
+            .collect();
+        Some(GroupActivity {
+            group,
+            mint: parsed_data.token_mint,
+            token_name: parsed_data.token_name.clone(),
+            total_sol: buyers.iter().map(|(_, sol)| sol).sum(),
+            buyers,
+            span_secs: now - recent.first().map(|b| b.at).unwrap_or(now),
+        })
+    }
+
+    fn save(&self, wallets: &HashMap<Pubkey, TrackedWallet>) {
+        if let Some(path) = &self.config.path {
+            let mut list: Vec<&TrackedWallet> = wallets.values().collect();
+            list.sort_by(|a, b| (&a.group, &a.label).cmp(&(&b.group, &b.label)));
+            let written = serde_json::to_string_pretty(&list)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| Ok(std::fs::write(path, json)?));
+            if let Err(e) = written {
+                eprintln!("Failed to save the wallet groups to {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+fn group_of(wallet: &TrackedWallet) -> &str {
+    wallet.group.as_deref().unwrap_or(DEFAULT_GROUP)
+}
+
+/// Reply to `/wallets`
+pub fn format_wallet_groups(groups: &BTreeMap<String, Vec<TrackedWallet>>) -> String {
+    if groups.is_empty() {
+        return "No wallets tracked. Import some with /import_wallets.".to_string();
+    }
+    let total: usize = groups.values().map(Vec::len).sum();
+    let mut out = format!("👥 {} tracked wallets in {} groups\n", total, groups.len());
+    for (group, wallets) in groups {
+        let _ = write!(out, "\n{} ({})", group, wallets.len());
+        for wallet in wallets.iter().take(LISTED_WALLETS) {
+            let _ = write!(out, "\n• {}: {}", wallet.label.as_deref().unwrap_or("-"), wallet.address);
+        }
+        if wallets.len() > LISTED_WALLETS {
+            let _ = write!(out, "\n… and {} more", wallets.len() - LISTED_WALLETS);
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,125 @@
+/*!
+# Wallet Positions
+
+Average entry of each tracked wallet in each token, so an alert can put a trade in context:
+"selling at ~3.2x their average entry" says more than the SOL amount alone.
+
+The average entry is the SOL-weighted average price of the wallet's observed buys (a VWAP of its
+entries). Sells leave it unchanged and reduce the tokens held; once the wallet has sold what it
+was seen buying, the position is closed and its next buy starts a new average. Only trades the
+monitor observed count, so a wallet that bought before it was tracked has no entry until it buys
+again.
+*/
+
+use std::collections::HashMap;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::processor::transaction_parser::{ParsedData, SwapType};
+
+/// Share of the bought tokens that has to be sold for the position to count as closed
+const EXIT_SOLD_SHARE: f64 = 0.99;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WalletPosition {
+    /// SOL spent on the observed buys
+    pub sol_spent: f64,
+    /// Sum of buy price times SOL spent, for the weighted average
+    price_sol: f64,
+    pub buys: u32,
+    pub tokens_bought: f64,
+    pub tokens_sold: f64,
+    pub sol_received: f64,
+    pub sells: u32,
+}
+
+impl WalletPosition {
+    /// SOL-weighted average buy price, None before any priced buy
+    pub fn average_entry(&self) -> Option<f64> {
+        (self.sol_spent > 0.0).then(|| self.price_sol / self.sol_spent)
+    }
+
+    fn is_closed(&self) -> bool {
+        self.sells > 0 && self.tokens_bought > 0.0 && self.tokens_sold >= self.tokens_bought * EXIT_SOLD_SHARE
+    }
+}
+
+/// A trade's price against the wallet's average entry before it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EntryComparison {
+    pub average_entry: f64,
+    pub trade_price: f64,
+    /// Buys the average was built from
+    pub buys: u32,
+}
+
+impl EntryComparison {
+    /// Trade price as a multiple of the average entry, e.g. 3.2 for a sell at 3.2x
+    pub fn multiple(&self) -> f64 {
+        self.trade_price / self.average_entry
+    }
+}
+
+#[derive(Default)]
+pub struct WalletPositions {
+    positions: HashMap<(Pubkey, Pubkey), WalletPosition>,
+}
+
+impl WalletPositions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a buy or sell to the signer's position in the token. Returns the trade against the
+    /// average entry the wallet had before it, None without an earlier priced buy.
+    pub fn record(&mut self, parsed_data: &ParsedData) -> Option<EntryComparison> {
+        let is_buy = match parsed_data.swap_type {
+            SwapType::Buy => true,
+            SwapType::Sell => false,
+            _ => return None,
+        };
+        let price = parsed_data.token_price.filter(|p| *p > 0.0 && p.is_finite());
+        let sol = parsed_data.sol_amount.unwrap_or(0.0);
+        let tokens = parsed_data.token_amount.unwrap_or(0.0);
+
+        let key = (parsed_data.signer, parsed_data.token_mint);
+        let position = self.positions.entry(key).or_default();
+        let comparison = match (position.average_entry(), price) {
+            (Some(average_entry), Some(trade_price)) => Some(EntryComparison { average_entry, trade_price, buys: position.buys }),
+            _ => None,
+        };
+
+        if is_buy {
+            if position.is_closed() {
+                *position = WalletPosition::default();
+            }
+            if let Some(price) = price.filter(|_| sol > 0.0) {
+                position.sol_spent += sol;
+                position.price_sol += price * sol;
+            }
+            position.buys += 1;
+            position.tokens_bought += tokens;
+        } else {
+            position.sells += 1;
+            position.tokens_sold += tokens;
+            position.sol_received += sol;
+        }
+        comparison
+    }
+
+    pub fn get(&self, wallet: &Pubkey, mint: &Pubkey) -> Option<&WalletPosition> {
+        self.positions.get(&(*wallet, *mint))
+    }
+
+    /// Forget positions in tokens that are no longer tracked
+    pub fn retain(&mut self, keep: impl Fn(&Pubkey) -> bool) {
+        self.positions.retain(|(_, mint), _| keep(mint));
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
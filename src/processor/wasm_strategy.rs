@@ -0,0 +1,231 @@
+/*!
+# WASM Strategies
+
+Community strategies compiled to WebAssembly and loaded at runtime, without forking the crate
+or trusting native code. A strategy gets every event as JSON in the exported swap format
+(`SwapEvent`, see `event_export`) and answers with a JSON list of signals:
+
+```json
+[{ "signal": "buy", "mint": "<optional, defaults to the event's mint>", "reason": "whale entry", "confidence": 0.8 }]
+```
+
+`signal` is `buy`, `sell` or `alert`. A module must export:
+
+- `memory`
+- `alloc(len: i32) -> i32`: space for the event JSON
+- `on_event(ptr: i32, len: i32) -> i64`: the signals as `(ptr << 32) | len`, or `0` for none
+
+The host provides no imports, so a strategy cannot touch files, the network or the clock. Each
+call runs under a fuel budget and the instance under a memory cap; a strategy that traps is
+reinstantiated, and one that fails `MAX_CONSECUTIVE_FAILURES` times in a row is disabled.
+
+Strategies are added to a pipeline as `{ "type": "wasm", "path": "strategies/whale.wasm" }`
+with optional `name`, `fuel` (default 10,000,000) and `max_memory_mb` (default 16).
+*/
+
+use std::path::Path;
+use std::sync::Mutex;
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use colored::Colorize;
+use serde::Deserialize;
+use wasmtime::{Config, Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc};
+
+use crate::common::logger::Logger;
+use crate::library::event_export::SwapEvent;
+use crate::processor::pipeline::{Notice, PipelineEvent, Strategy};
+
+/// Largest signal list read back from a guest
+const MAX_OUTPUT_BYTES: usize = 64 * 1024;
+const MAX_CONSECUTIVE_FAILURES: u32 = 5;
+
+#[derive(Clone, Debug)]
+pub struct WasmLimits {
+    /// Fuel per event; roughly one unit per executed instruction
+    pub fuel: u64,
+    pub max_memory_bytes: usize,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            fuel: 10_000_000,
+            max_memory_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignalKind {
+    Buy,
+    Sell,
+    Alert,
+}
+
+impl SignalKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SignalKind::Buy => "BUY",
+            SignalKind::Sell => "SELL",
+            SignalKind::Alert => "ALERT",
+        }
+    }
+}
+
+/// A signal returned by a strategy
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct WasmSignal {
+    pub signal: SignalKind,
+    #[serde(default)]
+    pub mint: Option<String>,
+    #[serde(default)]
+    pub reason: String,
+    #[serde(default)]
+    pub confidence: Option<f64>,
+}
+
+struct GuestState {
+    limits: StoreLimits,
+}
+
+/// One live instance with its exports
+struct Guest {
+    store: Store<GuestState>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    on_event: TypedFunc<(i32, i32), i64>,
+}
+
+impl Guest {
+    fn instantiate(engine: &Engine, module: &Module, limits: &WasmLimits) -> Result<Self> {
+        let mut store = Store::new(engine, GuestState {
+            limits: StoreLimitsBuilder::new().memory_size(limits.max_memory_bytes).instances(1).build(),
+        });
+        store.limiter(|state| &mut state.limits);
+        // Nothing is linked: a module importing anything fails here
+        let instance: Instance = Linker::new(engine).instantiate(&mut store, module)?;
+        let memory = instance.get_memory(&mut store, "memory").ok_or_else(|| anyhow!("module exports no memory"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+        let on_event = instance.get_typed_func::<(i32, i32), i64>(&mut store, "on_event")?;
+        Ok(Self { store, memory, alloc, on_event })
+    }
+
+    fn call(&mut self, input: &[u8], fuel: u64) -> Result<Vec<WasmSignal>> {
+        self.store.set_fuel(fuel)?;
+        let len = i32::try_from(input.len()).map_err(|_| anyhow!("event too large"))?;
+        let ptr = self.alloc.call(&mut self.store, len)?;
+        self.memory.write(&mut self.store, ptr as u32 as usize, input)?;
+        let packed = self.on_event.call(&mut self.store, (ptr, len))? as u64;
+        if packed == 0 {
+            return Ok(Vec::new());
+        }
+        let (out_ptr, out_len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        if out_len > MAX_OUTPUT_BYTES {
+            return Err(anyhow!("{} bytes of signals, more than the {} allowed", out_len, MAX_OUTPUT_BYTES));
+        }
+        let mut output = vec![0u8; out_len];
+        self.memory.read(&self.store, out_ptr, &mut output)?;
+        serde_json::from_slice(&output).context("signals are not valid JSON")
+    }
+}
+
+struct Sandbox {
+    guest: Option<Guest>,
+    failures: u32,
+}
+
+pub struct WasmStrategy {
+    name: String,
+    engine: Engine,
+    module: Module,
+    limits: WasmLimits,
+    sandbox: Mutex<Sandbox>,
+    logger: Logger,
+}
+
+impl WasmStrategy {
+    /// Compile a strategy from a `.wasm` file (or `.wat` text)
+    pub fn from_file(path: &Path, name: Option<String>, limits: WasmLimits) -> Result<Self> {
+        let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let name = name.unwrap_or_else(|| {
+            path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_else(|| "wasm".to_string())
+        });
+        Self::from_bytes(&name, &bytes, limits)
+    }
+
+    pub fn from_bytes(name: &str, bytes: &[u8], limits: WasmLimits) -> Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let module = Module::new(&engine, bytes).with_context(|| format!("Failed to compile strategy {}", name))?;
+        let guest = Guest::instantiate(&engine, &module, &limits).with_context(|| format!("Failed to load strategy {}", name))?;
+        Ok(Self {
+            name: name.to_string(),
+            engine,
+            module,
+            limits,
+            sandbox: Mutex::new(Sandbox { guest: Some(guest), failures: 0 }),
+            logger: Logger::new(format!("[WASM {}] => ", name).magenta().to_string()),
+        })
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.sandbox.lock().unwrap().failures >= MAX_CONSECUTIVE_FAILURES
+    }
+
+    /// Run the strategy on one event; signals without a mint get the event's
+    pub fn evaluate(&self, event: &SwapEvent) -> Result<Vec<WasmSignal>> {
+        let input = serde_json::to_vec(event)?;
+        let mut sandbox = self.sandbox.lock().unwrap();
+        if sandbox.failures >= MAX_CONSECUTIVE_FAILURES {
+            return Ok(Vec::new());
+        }
+        let guest = match sandbox.guest.take() {
+            Some(guest) => Ok(guest),
+            None => Guest::instantiate(&self.engine, &self.module, &self.limits),
+        };
+        let result = guest.and_then(|mut guest| {
+            let signals = guest.call(&input, self.limits.fuel)?;
+            Ok((guest, signals))
+        });
+        match result {
+            Ok((guest, mut signals)) => {
+                sandbox.guest = Some(guest);
+                sandbox.failures = 0;
+                for signal in &mut signals {
+                    signal.mint.get_or_insert_with(|| event.mint.clone());
+                }
+                Ok(signals)
+            }
+            Err(e) => {
+                // A trap can leave the guest's memory half-written; the next event gets a fresh instance
+                sandbox.failures += 1;
+                if sandbox.failures == MAX_CONSECUTIVE_FAILURES {
+                    self.logger.log(format!("Disabled after {} consecutive failures", MAX_CONSECUTIVE_FAILURES).red().bold().to_string());
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for WasmStrategy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn handle(&self, event: &PipelineEvent) -> Result<Vec<Notice>> {
+        let signals = self.evaluate(&SwapEvent::from(&event.parsed))?;
+        Ok(signals.iter().map(|signal| Notice {
+            title: format!("{}: {} signal", self.name, signal.signal.label()),
+            body: format!(
+                "{}{}{}",
+                signal.mint.as_deref().unwrap_or_default(),
+                if signal.reason.is_empty() { String::new() } else { format!(" — {}", signal.reason) },
+                signal.confidence.map(|c| format!(" (confidence {:.0}%)", c * 100.0)).unwrap_or_default()
+            ),
+        }).collect())
+    }
+}
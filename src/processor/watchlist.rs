@@ -0,0 +1,202 @@
+use std::str::FromStr;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::processor::educational_monitor::TokenMetrics;
+
+/// A watchlist entry as imported; only `mint` is required
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WatchlistEntry {
+    pub mint: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub symbol: Option<String>,
+}
+
+/// A tracked token as exported, with its current metrics
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportedToken {
+    pub mint: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub pinned: bool,
+    pub initial_price: Option<f64>,
+    pub current_price: Option<f64>,
+    pub volume_24h: f64,
+    #[serde(default)]
+    pub buy_volume_24h: f64,
+    #[serde(default)]
+    pub sell_volume_24h: f64,
+    #[serde(default)]
+    pub volume_1h: f64,
+    pub liquidity: f64,
+    pub buy_count: u32,
+    pub sell_count: u32,
+    pub bonding_curve_progress: Option<f64>,
+    pub first_seen: i64,
+    pub last_updated: i64,
+    /// Share of checked buyers that were freshly created wallets (%)
+    #[serde(default)]
+    pub fresh_wallet_pct: Option<f64>,
+}
+
+impl From<&TokenMetrics> for ExportedToken {
+    fn from(metrics: &TokenMetrics) -> Self {
+        Self {
+            mint: metrics.address.to_string(),
+            name: metrics.name.clone(),
+            symbol: metrics.symbol.clone(),
+            pinned: metrics.pinned,
+            initial_price: metrics.initial_price,
+            current_price: metrics.current_price,
+            volume_24h: metrics.volume_24h,
+            buy_volume_24h: metrics.buy_volume_24h,
+            sell_volume_24h: metrics.sell_volume_24h,
+            volume_1h: metrics.volume_1h,
+            liquidity: metrics.liquidity,
+            buy_count: metrics.buy_count,
+            sell_count: metrics.sell_count,
+            bonding_curve_progress: metrics.bonding_curve_progress,
+            first_seen: metrics.first_seen.timestamp(),
+            last_updated: metrics.last_updated.timestamp(),
+            fresh_wallet_pct: metrics.fresh_wallet_pct,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WatchlistFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for WatchlistFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" | "" => Ok(Self::Json),
+            other => Err(anyhow!("unknown watchlist format '{}', expected csv or json", other)),
+        }
+    }
+}
+
+/// Result of parsing a watchlist: valid entries plus the lines/values that were rejected
+#[derive(Debug, Default)]
+pub struct ParsedWatchlist {
+    pub entries: Vec<(Pubkey, WatchlistEntry)>,
+    pub invalid: Vec<String>,
+}
+
+/// Parse a watchlist in JSON or CSV form, detecting the format from the content.
+///
+/// JSON may be an array of mint strings, an array of entry objects, or the export format.
+/// CSV takes `mint[,name[,symbol]]` per line with an optional header; plain whitespace or
+/// comma separated mints (e.g. pasted in a chat message) are also accepted.
+pub fn parse_watchlist(content: &str) -> ParsedWatchlist {
+    let trimmed = content.trim();
+    let raw: Vec<WatchlistEntry> = if trimmed.starts_with('[') {
+        match parse_json(trimmed) {
+            Ok(entries) => entries,
+            Err(e) => return ParsedWatchlist { entries: Vec::new(), invalid: vec![format!("invalid JSON: {}", e)] },
+        }
+    } else {
+        parse_csv(trimmed)
+    };
+
+    let mut parsed = ParsedWatchlist::default();
+    for entry in raw {
+        match Pubkey::from_str(entry.mint.trim()) {
+            Ok(mint) if !parsed.entries.iter().any(|(m, _)| *m == mint) => parsed.entries.push((mint, entry)),
+            Ok(_) => {}
+            Err(_) => parsed.invalid.push(entry.mint),
+        }
+    }
+    parsed
+}
+
+fn parse_json(content: &str) -> Result<Vec<WatchlistEntry>> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum JsonEntry {
+        Mint(String),
+        Entry(WatchlistEntry),
+    }
+
+    let entries: Vec<JsonEntry> = serde_json::from_str(content)?;
+    Ok(entries
+        .into_iter()
+        .map(|e| match e {
+            JsonEntry::Mint(mint) => WatchlistEntry { mint, name: None, symbol: None },
+            JsonEntry::Entry(entry) => entry,
+        })
+        .collect())
+}
+
+fn parse_csv(content: &str) -> Vec<WatchlistEntry> {
+    let mut entries = Vec::new();
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields[0].eq_ignore_ascii_case("mint") {
+            continue;
+        }
+        // A line of several mints (no names) is a pasted list rather than a CSV row
+        let pasted_list = (fields.len() > 1 && fields.iter().all(|f| Pubkey::from_str(f).is_ok()))
+            || (line.contains(' ') && !line.contains(','));
+        if pasted_list {
+            entries.extend(
+                line.split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|m| !m.is_empty())
+                    .map(|mint| WatchlistEntry { mint: mint.to_string(), name: None, symbol: None }),
+            );
+            continue;
+        }
+        let field = |i: usize| fields.get(i).filter(|f| !f.is_empty()).map(|f| f.to_string());
+        entries.push(WatchlistEntry {
+            mint: fields[0].to_string(),
+            name: field(1),
+            symbol: field(2),
+        });
+    }
+    entries
+}
+
+/// Serialize tracked tokens for export
+pub fn export_watchlist(tokens: &[TokenMetrics], format: WatchlistFormat) -> Result<String> {
+    let exported: Vec<ExportedToken> = tokens.iter().map(ExportedToken::from).collect();
+    match format {
+        WatchlistFormat::Json => Ok(serde_json::to_string_pretty(&exported)?),
+        WatchlistFormat::Csv => {
+            let opt = |v: Option<f64>| v.map(|v| v.to_string()).unwrap_or_default();
+            let mut csv = String::from(
+                "mint,name,symbol,pinned,initial_price,current_price,volume_24h,buy_volume_24h,sell_volume_24h,volume_1h,liquidity,buy_count,sell_count,bonding_curve_progress,first_seen,last_updated,fresh_wallet_pct\n",
+            );
+            for t in exported {
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                    t.mint,
+                    t.name.unwrap_or_default().replace(',', " "),
+                    t.symbol.unwrap_or_default().replace(',', " "),
+                    t.pinned,
+                    opt(t.initial_price),
+                    opt(t.current_price),
+                    t.volume_24h,
+                    t.buy_volume_24h,
+                    t.sell_volume_24h,
+                    t.volume_1h,
+                    t.liquidity,
+                    t.buy_count,
+                    t.sell_count,
+                    opt(t.bonding_curve_progress),
+                    t.first_seen,
+                    t.last_updated,
+                    opt(t.fresh_wallet_pct),
+                ));
+            }
+            Ok(csv)
+        }
+    }
+}
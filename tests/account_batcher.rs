@@ -0,0 +1,93 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_vntr_sniper::library::account_batcher::{AccountBatcher, AccountFetcher, BatchConfig};
+
+/// Serves accounts whose lamports are the first key byte, records every call
+#[derive(Default)]
+struct RecordingFetcher {
+    calls: Mutex<Vec<Vec<Pubkey>>>,
+    missing: Vec<Pubkey>,
+    fail: bool,
+}
+
+#[async_trait]
+impl AccountFetcher for RecordingFetcher {
+    async fn get_multiple_accounts(&self, keys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        self.calls.lock().unwrap().push(keys.to_vec());
+        if self.fail {
+            return Err(anyhow!("rpc down"));
+        }
+        Ok(keys.iter()
+            .map(|key| (!self.missing.contains(key)).then(|| Account {
+                lamports: key.to_bytes()[0] as u64,
+                ..Default::default()
+            }))
+            .collect())
+    }
+}
+
+fn batcher(fetcher: Arc<RecordingFetcher>, window_ms: u64, max_batch: usize) -> AccountBatcher {
+    AccountBatcher::new(fetcher, BatchConfig { window: Duration::from_millis(window_ms), max_batch })
+}
+
+#[tokio::test]
+async fn concurrent_reads_share_one_call() {
+    let fetcher = Arc::new(RecordingFetcher::default());
+    let batcher = batcher(fetcher.clone(), 20, 100);
+    let keys: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+
+    let accounts = futures::future::join_all(keys.iter().map(|key| batcher.get_account(*key))).await;
+
+    for (key, account) in keys.iter().zip(accounts) {
+        assert_eq!(account.unwrap().unwrap().lamports, key.to_bytes()[0] as u64);
+    }
+    assert_eq!(fetcher.calls.lock().unwrap().len(), 1);
+    let stats = batcher.stats();
+    assert_eq!((stats.requests, stats.rpc_calls), (10, 1));
+}
+
+#[tokio::test]
+async fn duplicate_keys_are_fetched_once() {
+    let fetcher = Arc::new(RecordingFetcher::default());
+    let batcher = batcher(fetcher.clone(), 20, 100);
+    let key = Pubkey::new_unique();
+
+    let (a, b) = tokio::join!(batcher.get_account(key), batcher.get_account(key));
+
+    assert_eq!(a.unwrap(), b.unwrap());
+    assert_eq!(fetcher.calls.lock().unwrap().as_slice(), &[vec![key]]);
+}
+
+#[tokio::test]
+async fn full_batch_is_fetched_before_the_window() {
+    let fetcher = Arc::new(RecordingFetcher::default());
+    let batcher = batcher(fetcher.clone(), 60_000, 3);
+    let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+    let accounts = tokio::time::timeout(Duration::from_secs(5), batcher.get_accounts(&keys)).await
+        .expect("full batch waited for the window")
+        .unwrap();
+
+    assert_eq!(accounts.len(), 3);
+    assert_eq!(fetcher.calls.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn missing_accounts_and_errors_reach_every_caller() {
+    let missing = Pubkey::new_unique();
+    let fetcher = Arc::new(RecordingFetcher { missing: vec![missing], ..Default::default() });
+    let batcher = batcher(fetcher, 5, 100);
+    assert_eq!(batcher.get_account(missing).await.unwrap(), None);
+
+    let failing = batcher_with_failure();
+    let (a, b) = tokio::join!(failing.get_account(Pubkey::new_unique()), failing.get_account(Pubkey::new_unique()));
+    assert!(a.unwrap_err().to_string().contains("rpc down"));
+    assert!(b.is_err());
+}
+
+fn batcher_with_failure() -> AccountBatcher {
+    batcher(Arc::new(RecordingFetcher { fail: true, ..Default::default() }), 5, 100)
+}
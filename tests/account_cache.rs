@@ -0,0 +1,69 @@
+use std::time::{Duration, Instant};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::cache::SubscribedAccountCache;
+
+#[test]
+fn data_is_served_only_while_the_subscription_is_live() {
+    let cache = SubscribedAccountCache::new(10, Duration::from_secs(600));
+    let key = Pubkey::new_unique();
+    let now = Instant::now();
+    assert!(cache.watch(&key, now));
+
+    // Not subscribed yet: an RPC read is not kept
+    cache.store_fetched(&key, vec![1]);
+    assert_eq!(cache.get(&key), None);
+
+    cache.mark_live(&cache.wanted(now));
+    cache.store_fetched(&key, vec![1]);
+    assert_eq!(cache.get(&key), Some(vec![1]));
+
+    cache.invalidate_all();
+    assert_eq!(cache.get(&key), None);
+}
+
+#[test]
+fn updates_replace_fetched_data_and_older_slots_are_ignored() {
+    let cache = SubscribedAccountCache::new(10, Duration::from_secs(600));
+    let key = Pubkey::new_unique();
+    cache.watch(&key, Instant::now());
+    cache.mark_live(&[key]);
+
+    cache.store_fetched(&key, vec![1]);
+    cache.apply_update(&key, vec![2], 100);
+    assert_eq!(cache.get(&key), Some(vec![2]));
+
+    cache.apply_update(&key, vec![3], 99);
+    cache.store_fetched(&key, vec![4]);
+    assert_eq!(cache.get(&key), Some(vec![2]));
+}
+
+#[test]
+fn newly_subscribed_accounts_drop_data_from_before() {
+    let cache = SubscribedAccountCache::new(10, Duration::from_secs(600));
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let now = Instant::now();
+    cache.watch(&a, now);
+    cache.mark_live(&[a]);
+    cache.apply_update(&a, vec![1], 10);
+
+    cache.watch(&b, now);
+    cache.apply_update(&b, vec![2], 10);
+    cache.mark_live(&[a, b]);
+
+    assert_eq!(cache.get(&a), Some(vec![1]));
+    assert_eq!(cache.get(&b), None);
+}
+
+#[test]
+fn idle_accounts_are_unsubscribed_and_capacity_is_bounded() {
+    let cache = SubscribedAccountCache::new(2, Duration::from_secs(60));
+    let now = Instant::now();
+    let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    assert!(cache.watch(&a, now));
+    assert!(cache.watch(&b, now + Duration::from_secs(50)));
+    assert!(!cache.watch(&c, now));
+
+    assert_eq!(cache.wanted(now + Duration::from_secs(90)), vec![b]);
+    assert!(cache.watch(&c, now + Duration::from_secs(90)));
+    assert_eq!(cache.size(), 2);
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use chrono::Duration;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::adaptive_thresholds::{
+    format_adjustments, AdaptiveConfig, AdaptiveThresholds, Adjustment,
+};
+
+fn adaptive(max_per_hour: usize) -> (AdaptiveThresholds, Arc<SimulatedClock>) {
+    let clock = Arc::new(SimulatedClock::at_unix(1_700_000_000));
+    let config = AdaptiveConfig { max_per_hour, factor: 2.0, hold_secs: 1800, max_factor: 4.0 };
+    (AdaptiveThresholds::new(config).with_clock(clock.clone()), clock)
+}
+
+fn fire(adaptive: &AdaptiveThresholds, clock: &SimulatedClock, times: usize) -> Vec<Adjustment> {
+    (0..times)
+        .filter_map(|_| {
+            clock.advance(Duration::seconds(10));
+            adaptive.record("Large Transaction")
+        })
+        .collect()
+}
+
+#[test]
+fn disabled_thresholds_never_move() {
+    let (adaptive, clock) = adaptive(0);
+    assert!(fire(&adaptive, &clock, 100).is_empty());
+    assert!(adaptive.active().is_empty());
+}
+
+#[test]
+fn a_busy_signal_type_is_raised_up_to_the_cap() {
+    let (adaptive, clock) = adaptive(5);
+    assert!(fire(&adaptive, &clock, 5).is_empty());
+
+    let raised = fire(&adaptive, &clock, 1);
+    assert_eq!(raised, vec![Adjustment::Raised { signal_type: "Large Transaction".to_string(), factor: 2.0, total: 2.0, alerts: 6 }]);
+
+    // Another burst raises it again, then the cap holds
+    assert_eq!(fire(&adaptive, &clock, 6)[0].factor(), 2.0);
+    assert!(fire(&adaptive, &clock, 6).is_empty());
+    assert_eq!(adaptive.active(), vec![("Large Transaction".to_string(), 4.0)]);
+    assert!(adaptive.record("Price Movement").is_none());
+}
+
+#[test]
+fn alerts_spread_over_more_than_an_hour_do_not_raise() {
+    let (adaptive, clock) = adaptive(5);
+    for _ in 0..20 {
+        clock.advance(Duration::minutes(15));
+        assert!(adaptive.record("Large Transaction").is_none());
+    }
+}
+
+#[test]
+fn raises_run_out_and_are_reported_once() {
+    let (adaptive, clock) = adaptive(2);
+    fire(&adaptive, &clock, 3);
+    clock.advance(Duration::minutes(29));
+    assert!(adaptive.expire().is_empty());
+
+    clock.advance(Duration::minutes(2));
+    let restored = adaptive.expire();
+    assert_eq!(restored, vec![Adjustment::Restored { signal_type: "Large Transaction".to_string(), factor: 0.5 }]);
+    assert!(adaptive.active().is_empty());
+
+    let notes = adaptive.take_notes();
+    assert_eq!(notes.len(), 2);
+    assert!(notes[0].contains("raised to 2.00x"));
+    assert!(notes[1].contains("back to normal"));
+    assert!(adaptive.take_notes().is_empty());
+    assert!(format_adjustments(&notes, &[]).contains("Adaptive Thresholds"));
+    assert_eq!(format_adjustments(&[], &[]), "");
+}
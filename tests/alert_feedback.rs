@@ -0,0 +1,104 @@
+mod common;
+
+use std::sync::Arc;
+use common::{config, MockRpc};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::library::audit_log::{read_entries, AuditAction};
+use solana_vntr_sniper::processor::alert_feedback::{
+    rating_callback, AlertFeedback, FeedbackConfig, FeedbackOutcome, FeedbackTally, Rating,
+};
+use solana_vntr_sniper::processor::educational_monitor::{EducationalMonitor, MonitorThresholds};
+
+fn rating(signal_type: &'static str, useful: bool) -> Rating {
+    Rating { signal_type, mint: Pubkey::new_unique(), useful }
+}
+
+#[test]
+fn rating_buttons_round_trip() {
+    let mint = Pubkey::new_unique();
+    let data = rating_callback("Large Transaction", &mint, false).unwrap();
+    assert!(data.len() <= 64, "Telegram limits callback data to 64 bytes");
+    assert_eq!(Rating::parse(&data), Some(Rating { signal_type: "Large Transaction", mint, useful: false }));
+
+    assert_eq!(rating_callback("Exchange Deposit", &mint, true), None);
+    assert_eq!(Rating::parse("rate:up:zz:So11111111111111111111111111111111111111112"), None);
+    assert_eq!(Rating::parse("learn:next"), None);
+}
+
+#[test]
+fn repeated_ratings_of_an_alert_count_once() {
+    let feedback = AlertFeedback::new(FeedbackConfig::default());
+    let vote = rating("Price Movement", true);
+
+    assert_eq!(feedback.record(1, &vote, "amm pool"), FeedbackOutcome::Recorded);
+    assert_eq!(feedback.record(1, &vote, "amm pool"), FeedbackOutcome::Duplicate);
+    assert_eq!(feedback.record(2, &vote, "amm pool"), FeedbackOutcome::Recorded);
+    assert_eq!(feedback.record(2, &Rating { useful: false, ..vote.clone() }, "amm pool"), FeedbackOutcome::Duplicate);
+
+    assert_eq!(feedback.tally("Price Movement"), FeedbackTally { useful: 2, noise: 0 });
+    assert_eq!(feedback.category_tally("Price Movement", "amm pool").total(), 2);
+    assert_eq!(feedback.category_tally("Price Movement", "bonding curve").total(), 0);
+}
+
+#[test]
+fn mostly_noise_raises_the_threshold_up_to_the_limit() {
+    let feedback = AlertFeedback::new(FeedbackConfig {
+        min_votes: 4,
+        noise_pct: 75.0,
+        raise_pct: 10.0,
+        max_raises: 1,
+        ..Default::default()
+    });
+
+    // Half noise is not enough
+    for useful in [true, false, true, false] {
+        assert!(!matches!(feedback.record(1, &rating("Large Transaction", useful), "amm pool"), FeedbackOutcome::Raised { .. }));
+    }
+    assert_eq!(feedback.threshold_factor("Large Transaction"), 1.0);
+
+    let outcomes: Vec<_> = [false, false, true, false].into_iter()
+        .map(|useful| feedback.record(1, &rating("Large Transaction", useful), "bonding curve"))
+        .collect();
+    assert_eq!(outcomes[3], FeedbackOutcome::Raised { factor: 1.1, raises: 1 });
+    assert!((feedback.threshold_factor("Large Transaction") - 1.1).abs() < 1e-9);
+
+    // Capped at max_raises
+    for _ in 0..4 {
+        assert_eq!(feedback.record(1, &rating("Large Transaction", false), "amm pool"), FeedbackOutcome::Recorded);
+    }
+    assert_eq!(feedback.threshold_factor("Graduation"), 1.0);
+}
+
+#[tokio::test]
+async fn noisy_ratings_raise_the_monitor_threshold() {
+    // The only test in this binary writing the process-wide audit log
+    let audit_path = std::env::temp_dir().join(format!("audit_log_{}.jsonl", Pubkey::new_unique()));
+    std::env::set_var("AUDIT_LOG_PATH", &audit_path);
+    let feedback = AlertFeedback::new(FeedbackConfig { min_votes: 2, ..Default::default() });
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), None)
+        .with_alert_feedback(feedback);
+    let default_whale = MonitorThresholds::default().whale_sol;
+
+    monitor.rate_alert(1, &rating("Large Transaction", false)).await;
+    assert_eq!(monitor.thresholds().read().await.whale_sol, default_whale);
+    let outcome = monitor.rate_alert(2, &rating("Large Transaction", false)).await;
+
+    assert!(matches!(outcome, FeedbackOutcome::Raised { raises: 1, .. }));
+    assert!((monitor.thresholds().read().await.whale_sol - default_whale * 1.1).abs() < 1e-9);
+    assert!(monitor.feedback_summary().contains("untracked: 👍 0 / 👎 2"));
+
+    // The raise is audited with the old and new thresholds
+    let mut audited = None;
+    for _ in 0..100 {
+        audited = read_entries(&audit_path).ok().and_then(|entries| entries.into_iter().find(|e| e.action == AuditAction::ConfigChanged));
+        if audited.is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    let audited = audited.expect("threshold raise audited");
+    assert_eq!(audited.source, "alert_feedback");
+    assert_eq!(audited.details["signal_type"], "Large Transaction");
+    assert_eq!(audited.details["old"]["whale_sol"], default_whale);
+    let _ = std::fs::remove_file(&audit_path);
+}
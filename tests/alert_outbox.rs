@@ -0,0 +1,98 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+use common::FlakySink;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::library::circuit_breaker::CircuitBreaker;
+use solana_vntr_sniper::processor::alert_outbox::{AlertOutbox, OutboxEntry};
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+
+fn alerts(sink: Arc<FlakySink>, clock: Arc<SimulatedClock>, ttl_secs: i64) -> TelegramAlertSystem {
+    TelegramAlertSystem::with_sink(sink, true)
+        .with_clock(clock.clone())
+        .with_breaker(Arc::new(CircuitBreaker::new("telegram", 1, Duration::from_secs(30), clock)))
+        .with_outbox(Arc::new(AlertOutbox::in_memory(chrono::Duration::seconds(ttl_secs), 100)))
+}
+
+#[tokio::test]
+async fn alerts_are_queued_while_down_and_delivered_in_order_after_a_notice() {
+    let sink = Arc::new(FlakySink::down());
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let telegram = alerts(sink.clone(), clock.clone(), 3600);
+
+    telegram.send_custom_alert("First", "queued").await.unwrap();
+    telegram.send_custom_alert("Second", "queued").await.unwrap();
+    assert!(sink.texts().is_empty());
+
+    sink.set_down(false);
+    clock.advance(chrono::Duration::seconds(31));
+    assert!(telegram.flush_outbox().await);
+
+    let texts = sink.texts();
+    assert_eq!(texts.len(), 3, "{:?}", texts);
+    assert!(texts[0].contains("2 alerts were delayed"), "{}", texts[0]);
+    assert!(texts[1].contains("First"));
+    assert!(texts[2].contains("Second"));
+}
+
+#[tokio::test]
+async fn stale_alerts_expire_and_are_counted_in_the_notice() {
+    let sink = Arc::new(FlakySink::down());
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let telegram = alerts(sink.clone(), clock.clone(), 60);
+
+    telegram.send_custom_alert("Old", "stale by the time Telegram is back").await.unwrap();
+
+    sink.set_down(false);
+    clock.advance(chrono::Duration::minutes(10));
+    assert!(telegram.flush_outbox().await);
+
+    let texts = sink.texts();
+    assert_eq!(texts.len(), 1, "{:?}", texts);
+    assert!(texts[0].contains("1 stale alert expired"), "{}", texts[0]);
+}
+
+#[test]
+fn queued_alerts_of_a_category_are_merged() {
+    let outbox = AlertOutbox::in_memory(chrono::Duration::hours(1), 100);
+    outbox.push(OutboxEntry::new("volume", "first spike", None, 1_750_000_000));
+    outbox.push(OutboxEntry::new("price", "price move", None, 1_750_000_001));
+    outbox.push(OutboxEntry::new("volume", "second spike", None, 1_750_000_002));
+    assert_eq!(outbox.len(), 2);
+    assert_eq!(outbox.alert_count(), 3);
+    let front = outbox.front().unwrap();
+    assert!(front.text.contains("first spike") && front.text.contains("second spike"));
+
+    // Too long to share a message
+    outbox.push(OutboxEntry::new("volume", &"x".repeat(3_990), None, 1_750_000_003));
+    assert_eq!(outbox.len(), 3);
+    assert_eq!(outbox.backlog(chrono::DateTime::from_timestamp(1_750_000_010, 0).unwrap()).pending, 4);
+}
+
+#[tokio::test]
+async fn rate_limited_alerts_wait_in_the_outbox_and_go_out_paced() {
+    let sink = Arc::new(FlakySink::down());
+    sink.set_down(false);
+    sink.set_rate_limited(true);
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let outbox = Arc::new(AlertOutbox::in_memory(chrono::Duration::hours(1), 100));
+    let telegram = alerts(sink.clone(), clock.clone(), 3600).with_outbox(outbox.clone());
+
+    telegram.send_custom_alert("First", "one").await.unwrap();
+    // Telegram accepts again, but the throttle still holds this one back
+    sink.set_rate_limited(false);
+    telegram.send_custom_alert("Second", "two").await.unwrap();
+    assert!(sink.texts().is_empty());
+    assert_eq!((outbox.len(), outbox.alert_count()), (1, 2));
+
+    clock.advance(chrono::Duration::seconds(31));
+    assert!(!telegram.flush_outbox().await, "the gap after the notice holds the backlog");
+    clock.advance(chrono::Duration::seconds(1));
+    assert!(telegram.flush_outbox().await);
+
+    let texts = sink.texts();
+    assert_eq!(texts.len(), 2, "{:?}", texts);
+    assert!(texts[0].contains("2 alerts were delayed"), "{}", texts[0]);
+    assert!(texts[1].contains("First") && texts[1].contains("Second"));
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::alert_throttle::{AlertThrottle, AlertThrottleConfig};
+
+fn throttle(clock: &Arc<SimulatedClock>) -> AlertThrottle {
+    AlertThrottle::new(AlertThrottleConfig { max_interval: Duration::from_secs(8), ..AlertThrottleConfig::default() })
+        .with_clock(clock.clone())
+}
+
+#[test]
+fn sends_are_unpaced_until_telegram_pushes_back() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let throttle = throttle(&clock);
+    throttle.record_sent();
+    throttle.record_sent();
+    assert!(throttle.ready());
+    assert_eq!(throttle.interval(), Duration::ZERO);
+}
+
+#[test]
+fn rate_limits_widen_the_gap_and_honour_retry_after() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let throttle = throttle(&clock);
+
+    throttle.record_rate_limited(Some(Duration::from_secs(5)));
+    assert_eq!(throttle.interval(), Duration::from_secs(1));
+    assert_eq!(throttle.wait(), Some(Duration::from_secs(5)));
+
+    for _ in 0..5 {
+        throttle.record_rate_limited(None);
+    }
+    assert_eq!(throttle.interval(), Duration::from_secs(8));
+    assert_eq!(throttle.rate_limits(), 6);
+
+    clock.advance(chrono::Duration::seconds(8));
+    assert!(throttle.ready());
+    throttle.record_sent();
+    assert_eq!(throttle.wait(), Some(Duration::from_secs(8)));
+}
+
+#[test]
+fn the_gap_shrinks_once_rate_limits_stop() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let throttle = throttle(&clock);
+    throttle.record_rate_limited(None);
+    throttle.record_rate_limited(None);
+    assert_eq!(throttle.interval(), Duration::from_secs(2));
+
+    // Still inside the recovery window: the gap holds
+    clock.advance(chrono::Duration::seconds(30));
+    throttle.record_sent();
+    assert_eq!(throttle.interval(), Duration::from_secs(2));
+
+    clock.advance(chrono::Duration::seconds(31));
+    throttle.record_sent();
+    assert_eq!(throttle.interval(), Duration::from_secs(1));
+    for _ in 0..4 {
+        throttle.record_sent();
+    }
+    assert_eq!(throttle.interval(), Duration::ZERO);
+    assert!(throttle.ready());
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use common::{config, parsed_trade, MockRpc, RecordingSink};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::arbitrage::{venue_label, SpreadConfig, SpreadTracker};
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+
+fn at(seconds: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(1_750_000_000 + seconds, 0).unwrap()
+}
+
+fn tracker() -> SpreadTracker {
+    SpreadTracker::new(SpreadConfig { alert_pct: 3.0, max_price_age: Duration::seconds(60) })
+}
+
+#[test]
+fn single_venue_has_no_spread() {
+    let mut spreads = tracker();
+    let mint = Pubkey::new_unique();
+
+    assert_eq!(spreads.record(mint, "PumpSwap".into(), 1.0, at(0)), None);
+    assert_eq!(spreads.record(mint, "PumpSwap".into(), 1.5, at(1)), None);
+    assert_eq!(spreads.spread(&mint), None);
+}
+
+#[test]
+fn wide_spread_alerts_once_until_it_narrows() {
+    let mut spreads = tracker();
+    let mint = Pubkey::new_unique();
+
+    assert_eq!(spreads.record(mint, "PumpSwap".into(), 1.00, at(0)), None);
+    let spread = spreads.record(mint, "RaydiumLaunchpad".into(), 1.05, at(1)).unwrap();
+    assert_eq!((spread.low.venue.as_str(), spread.high.venue.as_str()), ("PumpSwap", "RaydiumLaunchpad"));
+    assert!((spread.spread_pct - 5.0).abs() < 1e-9);
+
+    assert_eq!(spreads.record(mint, "PumpSwap".into(), 0.99, at(2)), None);
+    assert_eq!(spreads.record(mint, "PumpSwap".into(), 1.04, at(3)), None);
+    assert!(spreads.spread(&mint).unwrap().spread_pct < 3.0);
+    assert!(spreads.record(mint, "PumpSwap".into(), 0.98, at(4)).is_some());
+}
+
+#[test]
+fn stale_venue_prices_are_ignored() {
+    let mut spreads = tracker();
+    let mint = Pubkey::new_unique();
+
+    spreads.record(mint, "PumpSwap".into(), 1.0, at(0));
+    assert_eq!(spreads.record(mint, "RaydiumLaunchpad".into(), 2.0, at(120)), None);
+    assert_eq!(spreads.spread(&mint), None);
+}
+
+#[test]
+fn pools_of_one_dex_are_separate_venues() {
+    assert_eq!(venue_label("PumpSwap", None), "PumpSwap");
+    assert_ne!(
+        venue_label("PumpSwap", Some("Pool1111111111")),
+        venue_label("PumpSwap", Some("Pool2222222222"))
+    );
+}
+
+#[tokio::test]
+async fn monitor_alerts_on_cross_venue_spread() {
+    let sink = Arc::new(RecordingSink::default());
+    let telegram = TelegramAlertSystem::with_sink(sink.clone(), true);
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), Some(Arc::new(telegram)))
+        .with_spread_config(SpreadConfig { alert_pct: 3.0, max_price_age: Duration::seconds(60) });
+    let mint = Pubkey::new_unique();
+
+    for (dex, price) in [("PumpSwap", 0.00005), ("RaydiumLaunchpad", 0.000055)] {
+        monitor.process_for_education(&parsed_trade(mint)
+            .dex(dex)
+            .tokens(2_000.0)
+            .price(price)
+            .liquidity(100.0)
+            .name("Test")
+            .symbol("TST")
+            .build()).await.unwrap();
+    }
+
+    let texts = sink.texts();
+    let alert = texts.iter().find(|t| t.contains("CROSS-VENUE SPREAD")).expect("no spread alert");
+    assert!(alert.contains("10.00%"), "{}", alert);
+    assert!((monitor.get_spread(&mint).await.unwrap().spread_pct - 10.0).abs() < 1e-6);
+}
@@ -0,0 +1,122 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use chrono::Duration;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::library::audit_log::{read_entries, verify, AuditAction, AuditLog};
+
+fn log_path() -> PathBuf {
+    std::env::temp_dir().join(format!("audit_log_{}.jsonl", Pubkey::new_unique()))
+}
+
+fn write_sample(path: &PathBuf) {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let log = AuditLog::new(Some(path.clone())).with_clock(clock.clone());
+    log.record(AuditAction::KeyUsed, "private_key", json!({ "pubkey": "wallet", "use": "load" }));
+    clock.advance(Duration::seconds(5));
+    log.record(AuditAction::Paused, "telegram", json!({ "reason": "rug wave" }));
+    clock.advance(Duration::seconds(5));
+    log.record(AuditAction::TradeSubmitted, "zeroslot", json!({ "signer": "wallet", "signatures": ["sig"] }));
+}
+
+#[test]
+fn entries_are_chained_in_order() {
+    let path = log_path();
+    write_sample(&path);
+
+    let entries = read_entries(&path).unwrap();
+    assert_eq!(entries.iter().map(|e| e.seq).collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(entries[0].prev_hash, "");
+    assert_eq!(entries[1].prev_hash, entries[0].hash);
+    assert_eq!(entries[2].prev_hash, entries[1].hash);
+    assert_eq!(entries[1].at, 1_750_000_005_000);
+    assert_eq!(entries[2].action, AuditAction::TradeSubmitted);
+    assert_eq!(verify(&path), Ok(3));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn a_restarted_log_continues_the_chain() {
+    let path = log_path();
+    write_sample(&path);
+
+    let entry = AuditLog::new(Some(path.clone())).record(AuditAction::Resumed, "api", json!({}));
+
+    assert_eq!(entry.seq, 3);
+    assert_eq!(entry.prev_hash, read_entries(&path).unwrap()[2].hash);
+    assert_eq!(verify(&path), Ok(4));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn edited_lines_break_the_chain() {
+    let path = log_path();
+    write_sample(&path);
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, content.replace("rug wave", "routine")).unwrap();
+
+    let broken = verify(&path).unwrap_err();
+    assert_eq!(broken.line, 2);
+    assert!(broken.reason.contains("hash"), "{}", broken);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn dropped_lines_break_the_chain() {
+    let path = log_path();
+    write_sample(&path);
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let kept: Vec<&str> = content.lines().enumerate().filter(|(i, _)| *i != 1).map(|(_, line)| line).collect();
+    std::fs::write(&path, kept.join("\n")).unwrap();
+
+    assert_eq!(verify(&path).unwrap_err().line, 2);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn closing_writes_out_a_log_that_is_never_dropped() {
+    let path = log_path();
+    let log: &'static AuditLog = Box::leak(Box::new(AuditLog::new(Some(path.clone()))));
+    log.record(AuditAction::KeyUsed, "keystore", json!({ "pubkey": "wallet", "use": "generated" }));
+
+    log.close();
+    assert_eq!(verify(&path), Ok(1));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn a_log_without_a_path_writes_nothing() {
+    let log = AuditLog::new(None);
+    let first = log.record(AuditAction::ConfigChanged, "grpc", json!({ "setting": "thresholds" }));
+    let second = log.record(AuditAction::ConfigChanged, "grpc", json!({ "setting": "thresholds" }));
+    assert_eq!(second.prev_hash, first.hash);
+    assert_eq!(first.compute_hash(), first.hash);
+}
+
+#[test]
+fn a_cut_off_last_line_is_recorded_and_the_chain_continues() {
+    let path = log_path();
+    write_sample(&path);
+    let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+    std::io::Write::write_all(&mut file, br#"{"seq":3,"at":17500"#).unwrap();
+    assert_eq!(verify(&path).unwrap_err().line, 4);
+
+    let entry = AuditLog::new(Some(path.clone())).record(AuditAction::Resumed, "api", json!({}));
+
+    // Line 4 is the cut-off write, line 5 the recovery entry naming it
+    assert_eq!(entry.seq, 4);
+    assert_eq!(verify(&path), Ok(5));
+    let content = std::fs::read_to_string(&path).unwrap();
+    let recovered: serde_json::Value = serde_json::from_str(content.lines().nth(4).unwrap()).unwrap();
+    assert_eq!(recovered["action"], "log_recovered");
+    assert_eq!(recovered["details"]["lines"], json!([4]));
+
+    // A later restart does not record the same break again
+    let entry = AuditLog::new(Some(path.clone())).record(AuditAction::Paused, "api", json!({}));
+    assert_eq!(entry.seq, 5);
+    assert_eq!(verify(&path), Ok(6));
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,86 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::quote_asset::QuoteAsset;
+use solana_vntr_sniper::processor::balance_tracker::{reconcile_ledger, Reconciliation};
+use solana_vntr_sniper::processor::sniper_bot::{BoughtTokenInfo, BOUGHT_TOKEN_LIST};
+use solana_vntr_sniper::processor::swap::SwapProtocol;
+use solana_vntr_sniper::processor::transaction_parser::{DexType, TradeInfoFromToken};
+
+/// Track a position of `amount` tokens, returning its mint
+fn hold(amount: f64) -> String {
+    let mint = Pubkey::new_unique().to_string();
+    let trade_info = TradeInfoFromToken {
+        dex_type: DexType::PumpFun,
+        slot: 350_000_000,
+        signature: "target".to_string(),
+        pool_id: String::new(),
+        mint: mint.clone(),
+        timestamp: 1_750_000_000,
+        is_buy: true,
+        price: 50,
+        is_reverse_when_pump_swap: false,
+        coin_creator: None,
+        sol_change: 0.5,
+        token_change: amount,
+        liquidity: 10.0,
+        virtual_sol_reserves: 40_000_000_000,
+        virtual_token_reserves: 800_000_000_000_000,
+        quote_asset: QuoteAsset::Sol,
+    };
+    let position = BoughtTokenInfo::new(mint.clone(), 50, 0.01, amount, SwapProtocol::PumpFun, trade_info, 300);
+    BOUGHT_TOKEN_LIST.insert(mint.clone(), position);
+    mint
+}
+
+fn ledger_amount(mint: &str) -> f64 {
+    BOUGHT_TOKEN_LIST.get(mint).unwrap().current_amount
+}
+
+#[test]
+fn differences_within_tolerance_leave_the_ledger_alone() {
+    let mint = hold(10_000.0);
+
+    assert_eq!(reconcile_ledger(&mint, 9_950.0, None, 1.0), Reconciliation::InSync);
+    assert_eq!(ledger_amount(&mint), 10_000.0);
+    BOUGHT_TOKEN_LIST.remove(&mint);
+}
+
+#[test]
+fn a_mismatch_corrects_the_ledger_to_the_chain() {
+    let mint = hold(10_000.0);
+
+    // A partial fill: only 8,000 tokens arrived
+    assert_eq!(
+        reconcile_ledger(&mint, 8_000.0, None, 1.0),
+        Reconciliation::Corrected { ledger: 10_000.0, diff_pct: 20.0 }
+    );
+    assert_eq!(ledger_amount(&mint), 8_000.0);
+
+    // Once corrected the next update is in sync
+    assert_eq!(reconcile_ledger(&mint, 8_000.0, Some(8_000.0), 1.0), Reconciliation::InSync);
+    BOUGHT_TOKEN_LIST.remove(&mint);
+}
+
+#[test]
+fn tokens_on_an_empty_ledger_entry_are_fully_off() {
+    let mint = hold(0.0);
+
+    assert_eq!(
+        reconcile_ledger(&mint, 5.0, None, 1.0),
+        Reconciliation::Corrected { ledger: 0.0, diff_pct: 100.0 }
+    );
+    assert_eq!(reconcile_ledger(&mint, 0.0, Some(5.0), 1.0), Reconciliation::Corrected { ledger: 5.0, diff_pct: 100.0 });
+    assert_eq!(reconcile_ledger(&mint, 0.0, Some(0.0), 1.0), Reconciliation::InSync);
+    BOUGHT_TOKEN_LIST.remove(&mint);
+}
+
+#[test]
+fn untracked_tokens_are_reported_when_they_first_appear() {
+    let mint = Pubkey::new_unique().to_string();
+
+    assert_eq!(reconcile_ledger(&mint, 42.0, None, 1.0), Reconciliation::Untracked);
+    assert_eq!(reconcile_ledger(&mint, 42.0, Some(0.0), 1.0), Reconciliation::Untracked);
+    // Already reported, or gone again
+    assert_eq!(reconcile_ledger(&mint, 50.0, Some(42.0), 1.0), Reconciliation::InSync);
+    assert_eq!(reconcile_ledger(&mint, 0.0, Some(42.0), 1.0), Reconciliation::InSync);
+    assert!(!BOUGHT_TOKEN_LIST.contains_key(&mint));
+}
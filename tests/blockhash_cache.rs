@@ -0,0 +1,45 @@
+use std::time::{Duration, Instant};
+use solana_sdk::hash::Hash;
+use solana_vntr_sniper::library::blockhash_processor::BlockhashCache;
+
+#[test]
+fn fresh_blockhash_is_served_with_its_height() {
+    let cache = BlockhashCache::new(Duration::from_secs(10));
+    let now = Instant::now();
+    let hash = Hash::new_unique();
+    cache.store(hash, 300_000_150, now);
+
+    let cached = cache.get(now + Duration::from_secs(2)).unwrap();
+    assert_eq!((cached.hash, cached.last_valid_block_height), (hash, 300_000_150));
+
+    let stats = cache.stats(now + Duration::from_secs(2));
+    assert_eq!((stats.hits, stats.stale_reads), (1, 0));
+    assert_eq!(stats.age_ms, Some(2_000));
+    assert_eq!(stats.last_valid_block_height, Some(300_000_150));
+}
+
+#[test]
+fn stale_or_missing_blockhash_is_not_served() {
+    let cache = BlockhashCache::new(Duration::from_secs(10));
+    let now = Instant::now();
+    assert_eq!(cache.get(now), None);
+
+    cache.store(Hash::new_unique(), 1, now);
+    assert_eq!(cache.get(now + Duration::from_secs(11)), None);
+
+    let stats = cache.stats(now + Duration::from_secs(11));
+    assert_eq!((stats.hits, stats.stale_reads), (0, 2));
+}
+
+#[test]
+fn refresh_counters_are_reported() {
+    let cache = BlockhashCache::new(Duration::from_secs(10));
+    cache.record_refresh(Duration::from_millis(42));
+    cache.record_refresh_failure();
+    cache.record_rpc_fallback();
+
+    let stats = cache.stats(Instant::now());
+    assert_eq!((stats.refreshes, stats.refresh_failures, stats.rpc_fallbacks), (1, 1, 1));
+    assert_eq!(stats.last_refresh_ms, 42);
+    assert_eq!(stats.age_ms, None);
+}
@@ -0,0 +1,111 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use solana_vntr_sniper::dex::bonding_curve::{CurveSnapshot, PUMP_FUN_FEE_BPS};
+use solana_vntr_sniper::dex::pump_fun::expected_migration_reserves;
+
+const CASES: usize = 2_000;
+
+/// A curve somewhere between launch and completion
+fn random_curve(rng: &mut StdRng) -> CurveSnapshot {
+    let initial = CurveSnapshot::initial();
+    let bought = rng.gen_range(0..initial.real_token_reserves - 1_000_000);
+    initial.buy_exact_tokens(bought.max(1), 0).unwrap().after
+}
+
+fn invariant(curve: &CurveSnapshot) -> u128 {
+    curve.virtual_sol_reserves as u128 * curve.virtual_token_reserves as u128
+}
+
+#[test]
+fn a_fresh_curve_matches_the_launch_parameters() {
+    let curve = CurveSnapshot::initial();
+    assert_eq!(curve.real_sol_reserves, 0);
+    assert_eq!(curve.progress(), 0.0);
+    assert_eq!(curve.scaled_price(), 27_958);
+    assert!(!curve.complete);
+}
+
+#[test]
+fn buying_the_whole_curve_raises_the_migration_sol() {
+    let curve = CurveSnapshot::initial();
+    let quote = curve.buy_exact_tokens(curve.real_token_reserves, PUMP_FUN_FEE_BPS).unwrap();
+
+    assert!(quote.after.complete);
+    assert_eq!(quote.after.progress(), 100.0);
+    let (_, migration_sol) = expected_migration_reserves();
+    assert!(quote.after.real_sol_reserves.abs_diff(migration_sol) <= 1, "{} vs {}", quote.after.real_sol_reserves, migration_sol);
+    assert!(quote.after.buy_with_sol(1_000_000_000, PUMP_FUN_FEE_BPS).is_none(), "a complete curve does not trade");
+    assert!(curve.buy_exact_tokens(curve.real_token_reserves + 1, PUMP_FUN_FEE_BPS).is_none());
+}
+
+#[test]
+fn a_buy_never_costs_more_than_the_sol_offered() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..CASES {
+        let curve = random_curve(&mut rng);
+        let lamports = rng.gen_range(1_000..50_000_000_000u64);
+        let quote = match curve.buy_with_sol(lamports, PUMP_FUN_FEE_BPS) {
+            Some(quote) => quote,
+            None => continue,
+        };
+        assert!(quote.sol <= lamports, "{:?} for {} lamports", quote, lamports);
+        // Nearly all of it is spent unless the curve ran out of tokens
+        if !quote.after.complete {
+            let one_more = curve.buy_exact_tokens(quote.tokens + 1, PUMP_FUN_FEE_BPS).unwrap();
+            assert!(one_more.sol > lamports);
+        }
+    }
+}
+
+#[test]
+fn trades_never_shrink_the_invariant_and_move_the_price_the_right_way() {
+    let mut rng = StdRng::seed_from_u64(2);
+    for _ in 0..CASES {
+        let curve = random_curve(&mut rng);
+        let tokens = rng.gen_range(1..curve.real_token_reserves.max(2));
+
+        if let Some(buy) = curve.buy_exact_tokens(tokens, PUMP_FUN_FEE_BPS) {
+            assert!(invariant(&buy.after) >= invariant(&curve));
+            assert!(buy.after.price() > curve.price());
+            assert_eq!(buy.sol - buy.fee, buy.after.real_sol_reserves - curve.real_sol_reserves);
+        }
+        if let Some(sell) = curve.sell(tokens, PUMP_FUN_FEE_BPS) {
+            assert!(invariant(&sell.after) >= invariant(&curve));
+            assert!(sell.after.price() <= curve.price());
+            assert!(sell.average_price() <= curve.price());
+        }
+    }
+}
+
+#[test]
+fn a_round_trip_loses_at_least_the_fees() {
+    let mut rng = StdRng::seed_from_u64(3);
+    for _ in 0..CASES {
+        let curve = random_curve(&mut rng);
+        let lamports = rng.gen_range(10_000..10_000_000_000u64);
+        let buy = match curve.buy_with_sol(lamports, PUMP_FUN_FEE_BPS) {
+            Some(buy) => buy,
+            None => continue,
+        };
+        let sell = buy.after.sell(buy.tokens, PUMP_FUN_FEE_BPS).unwrap();
+
+        assert!(sell.sol + buy.fee + sell.fee <= buy.sol, "bought for {} sold for {}", buy.sol, sell.sol);
+        assert!(sell.after.virtual_sol_reserves >= curve.virtual_sol_reserves);
+    }
+}
+
+#[test]
+fn bigger_buys_get_more_tokens_at_a_worse_price() {
+    let mut rng = StdRng::seed_from_u64(4);
+    for _ in 0..CASES {
+        let curve = random_curve(&mut rng);
+        let small = rng.gen_range(1_000_000..1_000_000_000u64);
+        let (a, b) = match (curve.buy_with_sol(small, PUMP_FUN_FEE_BPS), curve.buy_with_sol(small * 3, PUMP_FUN_FEE_BPS)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => continue,
+        };
+        assert!(b.tokens >= a.tokens);
+        assert!(b.average_price() >= a.average_price());
+        assert!(b.impact_pct() >= a.impact_pct());
+    }
+}
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Duration, Utc};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::library::ohlcv::CandleSeries;
+use solana_vntr_sniper::processor::breakout::{AgeBracket, BreakoutConfig, BreakoutDetector};
+
+/// Start of a minute plus `seconds`
+fn at(seconds: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(1_749_999_960 + seconds, 0).unwrap()
+}
+
+fn detector(lookback: usize, volume_multiple: f64) -> BreakoutDetector {
+    BreakoutDetector::new(BreakoutConfig {
+        candle: Duration::seconds(60),
+        brackets: vec![AgeBracket { max_age: None, lookback, volume_multiple }],
+    })
+}
+
+#[test]
+fn candles_aggregate_trades_per_interval() {
+    let mut series = CandleSeries::new(Duration::seconds(60), 3);
+    series.update(at(1), 1.0, 0.5);
+    series.update(at(20), 1.4, 1.0);
+    series.update(at(40), 0.8, 0.5);
+    series.update(at(59), 1.1, 1.0);
+    series.update(at(61), 1.2, 2.0);
+
+    let candles: Vec<_> = series.candles().collect();
+    assert_eq!(candles.len(), 2);
+    assert_eq!((candles[0].open, candles[0].high, candles[0].low, candles[0].close), (1.0, 1.4, 0.8, 1.1));
+    assert_eq!(candles[0].volume, 3.0);
+    assert_eq!(candles[0].trades, 4);
+    assert_eq!(candles[1].start, at(60));
+    assert_eq!(series.previous(5).len(), 1);
+
+    // Late trades for an earlier candle are dropped; only `capacity` candles are kept
+    series.update(at(30), 9.0, 9.0);
+    assert_eq!(series.candles().next().unwrap().high, 1.4);
+    series.update(at(120), 1.0, 1.0);
+    series.update(at(180), 1.0, 1.0);
+    assert_eq!(series.len(), 3);
+    assert_eq!(series.candles().next().unwrap().start, at(60));
+}
+
+#[test]
+fn breakout_needs_a_close_above_the_range_and_volume() {
+    let mint = Pubkey::new_unique();
+    let mut breakouts = detector(3, 2.0);
+    let age = Duration::hours(2);
+    for minute in 0..3 {
+        assert!(breakouts.observe(mint, age, at(minute * 60), 1.0 + minute as f64 * 0.1, 1.0, 1.0).is_none());
+    }
+    // Above the 1.2 high but on average volume
+    assert!(breakouts.observe(mint, age, at(180), 1.3, 1.0, 1.0).is_none());
+
+    // Volume catches up within the same candle
+    let breakout = breakouts.observe(mint, age, at(200), 1.35, 1.5, 1.0).unwrap();
+    assert_eq!(breakout.range_high, 1.2);
+    assert_eq!(breakout.average_volume, 1.0);
+    assert_eq!(breakout.candle.volume, 2.5);
+    assert!(breakout.describe().starts_with("Closed 12.5% above the 3-candle high on 2.5x average volume"));
+
+    // Once per candle
+    assert!(breakouts.observe(mint, age, at(210), 1.5, 5.0, 1.0).is_none());
+    // A raised volume factor asks for more
+    assert!(breakouts.observe(mint, age, at(240), 2.0, 3.0, 2.0).is_none());
+}
+
+#[test]
+fn brackets_are_picked_by_token_age() {
+    let config = BreakoutConfig {
+        candle: Duration::seconds(60),
+        brackets: BreakoutConfig::parse_brackets("10m:2:3, 1h:10:2, *:20:1.5").unwrap(),
+    };
+    assert_eq!(config.bracket(Duration::minutes(5)).unwrap().lookback, 2);
+    assert_eq!(config.bracket(Duration::minutes(10)).unwrap().lookback, 2);
+    assert_eq!(config.bracket(Duration::minutes(30)).unwrap().lookback, 10);
+    assert_eq!(config.bracket(Duration::days(3)).unwrap().max_age, None);
+    assert!(BreakoutConfig::parse_brackets("10m:2").is_none());
+    assert!(BreakoutConfig::parse_brackets("soon:2:3").is_none());
+
+    // A young token breaks out after two candles, the same trades of an older one do not
+    let mut breakouts = BreakoutDetector::new(config);
+    let (young, old) = (Pubkey::new_unique(), Pubkey::new_unique());
+    for (mint, age) in [(young, Duration::minutes(3)), (old, Duration::minutes(30))] {
+        breakouts.observe(mint, age, at(0), 1.0, 1.0, 1.0);
+        breakouts.observe(mint, age, at(60), 1.0, 1.0, 1.0);
+    }
+    assert!(breakouts.observe(young, Duration::minutes(5), at(120), 1.2, 3.0, 1.0).is_some());
+    assert!(breakouts.observe(old, Duration::minutes(32), at(120), 1.2, 3.0, 1.0).is_none());
+    assert_eq!(breakouts.token_count(), 2);
+}
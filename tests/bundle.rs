@@ -0,0 +1,91 @@
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_vntr_sniper::block_engine::bundle::{BundleBuilder, JitoConfig};
+use solana_vntr_sniper::block_engine::guard::MinOutGuard;
+use solana_vntr_sniper::common::cluster::DexProgram;
+use solana_vntr_sniper::dex::pump_fun::PUMP_BUY_METHOD;
+
+fn pump_buy(owner: &Pubkey, mint: &Pubkey, token_account: &Pubkey, amount: u64) -> Instruction {
+    let mut accounts: Vec<AccountMeta> = (0..14).map(|_| AccountMeta::new(Pubkey::new_unique(), false)).collect();
+    accounts[2] = AccountMeta::new_readonly(*mint, false);
+    accounts[5] = AccountMeta::new(*token_account, false);
+    accounts[6] = AccountMeta::new(*owner, true);
+    accounts[8] = AccountMeta::new_readonly(spl_token::id(), false);
+    Instruction::new_with_bincode(
+        DexProgram::PumpFun.id(),
+        &(PUMP_BUY_METHOD, amount, 2_000_000u64),
+        accounts,
+    )
+}
+
+#[test]
+fn guarded_bundle_tips_in_its_last_transaction() {
+    let payer = Keypair::new();
+    let (mint, token_account, tip_account) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let buy = vec![pump_buy(&payer.pubkey(), &mint, &token_account, 1_000)];
+
+    let bundle = BundleBuilder::new(&payer, Hash::new_unique())
+        .buy(buy.clone())
+        .guard(MinOutGuard::from_pump_fun_buy(&buy))
+        .tip(tip_account, 1_000_000)
+        .build()
+        .unwrap();
+
+    assert_eq!(bundle.transactions.len(), 2);
+    assert_eq!(bundle.transactions[0].message.instructions().len(), 1);
+    let last = &bundle.transactions[1];
+    assert_eq!(last.message.instructions().len(), 2);
+    let tip = &last.message.instructions()[1];
+    assert_eq!(last.message.static_account_keys()[tip.accounts[1] as usize], tip_account);
+    assert_eq!(bincode::deserialize::<SystemInstruction>(&tip.data).unwrap(), SystemInstruction::Transfer { lamports: 1_000_000 });
+    assert!(bundle.transactions.iter().all(|tx| tx.verify_with_results().iter().all(|ok| *ok)));
+    assert_eq!(bundle.signatures()[0], bundle.transactions[0].signatures[0]);
+}
+
+#[test]
+fn unguarded_bundle_is_a_single_tipped_transaction() {
+    let payer = Keypair::new();
+    let buy = vec![Instruction::new_with_bytes(Pubkey::new_unique(), &[7], vec![])];
+    let bundle = BundleBuilder::new(&payer, Hash::new_unique())
+        .buy(buy)
+        .tip(Pubkey::new_unique(), 10_000)
+        .build()
+        .unwrap();
+    assert_eq!(bundle.transactions.len(), 1);
+    assert_eq!(bundle.transactions[0].message.instructions().len(), 2);
+}
+
+#[test]
+fn bundles_need_a_buy_a_tip_and_the_payers_guard() {
+    let payer = Keypair::new();
+    let buy = vec![Instruction::new_with_bytes(Pubkey::new_unique(), &[7], vec![])];
+    assert!(BundleBuilder::new(&payer, Hash::new_unique()).tip(Pubkey::new_unique(), 1).build().is_err());
+    assert!(BundleBuilder::new(&payer, Hash::new_unique()).buy(buy.clone()).build().is_err());
+
+    let someone_else = Pubkey::new_unique();
+    let foreign_guard = MinOutGuard::from_pump_fun_buy(&[pump_buy(&someone_else, &Pubkey::new_unique(), &Pubkey::new_unique(), 1)]);
+    let error = BundleBuilder::new(&payer, Hash::new_unique())
+        .buy(buy)
+        .guard(foreign_guard)
+        .tip(Pubkey::new_unique(), 1)
+        .build()
+        .unwrap_err();
+    assert!(error.to_string().contains("payer"));
+}
+
+#[test]
+fn send_bundle_body_is_base64_json_rpc() {
+    let config = JitoConfig {
+        url: JitoConfig::DEFAULT_URL.to_string(),
+        auth_uuid: None,
+        tip_lamports: 1_000_000,
+        tip_accounts: Vec::new(),
+    };
+    let body = config.send_bundle_body(&["AQID".to_string(), "BAUG".to_string()]);
+    assert_eq!(body["method"], "sendBundle");
+    assert_eq!(body["params"][0][1], "BAUG");
+    assert_eq!(body["params"][1]["encoding"], "base64");
+}
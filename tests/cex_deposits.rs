@@ -0,0 +1,31 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::cex_deposits::{parse_cex_addresses, CexMatch, CexRegistry};
+
+const BINANCE_HOT_WALLET: &str = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9";
+
+#[test]
+fn extra_addresses_are_parsed_from_label_pairs() {
+    let address = Pubkey::new_unique().to_string();
+    let parsed = parse_cex_addresses(&format!(" MyCEX : {} ,broken,NoKey:nope,:{}", address, address));
+    assert_eq!(parsed, vec![("MyCEX".to_string(), address.clone())]);
+
+    let registry = CexRegistry::new(parsed);
+    assert_eq!(registry.exchange_of(&address), Some(CexMatch { exchange: "MyCEX".to_string(), deposit_address: false }));
+    assert_eq!(registry.exchange_of(BINANCE_HOT_WALLET).unwrap().exchange, "Binance");
+    assert_eq!(registry.exchange_of(&Pubkey::new_unique().to_string()), None);
+}
+
+#[test]
+fn wallets_sweeping_into_a_hot_wallet_are_learned_as_deposit_addresses() {
+    let registry = CexRegistry::new(Vec::new());
+    let deposit = Pubkey::new_unique().to_string();
+
+    assert_eq!(registry.observe_transfer(&deposit, &Pubkey::new_unique().to_string()), None);
+    assert_eq!(registry.observe_transfer(&deposit, BINANCE_HOT_WALLET), Some("Binance".to_string()));
+    assert_eq!(registry.observe_transfer(&deposit, BINANCE_HOT_WALLET), None, "already known");
+
+    let matched = registry.exchange_of(&deposit).unwrap();
+    assert!(matched.deposit_address);
+    assert_eq!(matched.describe(), "Binance (deposit address)");
+    assert_eq!(registry.deposit_address_count(), 1);
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Duration, Utc};
+use solana_vntr_sniper::common::price_history::{PriceHistory, PricePoint};
+use solana_vntr_sniper::library::chart::{candle_interval, candles_from_points, format_window, render_candles, render_history_chart};
+
+fn point(seconds: i64, price: f64, volume: f64) -> PricePoint {
+    PricePoint { timestamp_ms: (1_749_999_960 + seconds) * 1000, price, buy_volume: volume, sell_volume: 0.0 }
+}
+
+#[test]
+fn buckets_aggregate_into_candles() {
+    let points = vec![point(0, 1.0, 1.0), point(10, 1.5, 2.0), point(20, 0.8, 0.5), point(60, 1.2, 1.0)];
+
+    let candles = candles_from_points(&points, Duration::minutes(1));
+
+    assert_eq!(candles.len(), 2);
+    assert_eq!((candles[0].open, candles[0].high, candles[0].low, candles[0].close), (1.0, 1.5, 0.8, 0.8));
+    assert_eq!(candles[0].volume, 3.5);
+    assert_eq!(candles[1].open, 1.2);
+}
+
+#[test]
+fn intervals_aim_for_sixty_candles_but_stay_above_the_resolution() {
+    assert_eq!(candle_interval(Duration::hours(1), Duration::seconds(10)), Duration::minutes(1));
+    assert_eq!(candle_interval(Duration::minutes(5), Duration::seconds(10)), Duration::seconds(10));
+    assert_eq!(format_window(Duration::hours(4)), "4h");
+    assert_eq!(format_window(Duration::days(1)), "1d");
+    assert_eq!(format_window(Duration::minutes(15)), "15m");
+}
+
+#[test]
+fn candles_render_to_png() {
+    let points: Vec<PricePoint> = (0..30).map(|i| point(i * 60, 1.0 + (i % 7) as f64 * 0.1, i as f64)).collect();
+    let candles = candles_from_points(&points, Duration::minutes(1));
+
+    let png = render_candles(&candles, 400, 300).unwrap();
+
+    assert!(png.starts_with(b"\x89PNG"));
+    assert!(render_candles(&[], 400, 300).is_err());
+}
+
+#[test]
+fn history_charts_need_recorded_prices() {
+    let history = PriceHistory::new(10, 360);
+    let now = DateTime::<Utc>::from_timestamp(1_750_003_600, 0).unwrap();
+    assert!(render_history_chart(&history, "mint", Duration::hours(1), now).is_err());
+
+    for i in 0..120 {
+        history.record("mint", now - Duration::seconds(3600 - i * 30), 1.0 + i as f64 * 0.01, i % 3 != 0, 0.5);
+    }
+    let (png, caption) = render_history_chart(&history, "mint", Duration::hours(1), now).unwrap();
+
+    assert!(png.starts_with(b"\x89PNG"));
+    assert!(caption.contains("(1h)"), "{}", caption);
+    assert!(caption.contains("candles of 1m"), "{}", caption);
+}
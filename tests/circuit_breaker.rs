@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use std::time::Duration;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::error::BotError;
+use solana_vntr_sniper::library::circuit_breaker::{CircuitBreaker, CircuitState};
+
+async fn timeout() -> Result<(), BotError> {
+    Err(BotError::RpcTimeout("getSignatureStatuses".to_string()))
+}
+
+#[tokio::test]
+async fn opens_after_consecutive_failures_and_sheds_calls() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let breaker = CircuitBreaker::new("rpc", 3, Duration::from_secs(30), clock.clone());
+
+    for _ in 0..3 {
+        assert!(breaker.call(timeout()).await.is_err());
+    }
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    let shed = breaker.call(async { Ok::<_, BotError>(()) }).await;
+    assert_eq!(shed, Err(BotError::CircuitOpen("rpc".to_string())));
+    let snapshot = breaker.snapshot();
+    assert_eq!(snapshot.rejected, 1);
+    assert_eq!(snapshot.times_opened, 1);
+}
+
+#[tokio::test]
+async fn recovery_probe_closes_or_reopens_the_breaker() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let breaker = CircuitBreaker::new("telegram", 1, Duration::from_secs(30), clock.clone());
+
+    assert!(breaker.call(timeout()).await.is_err());
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    // Failed probe re-opens for another period
+    clock.advance(chrono::Duration::seconds(30));
+    assert!(breaker.call(timeout()).await.is_err());
+    assert_eq!(breaker.state(), CircuitState::Open);
+    assert!(!breaker.try_acquire());
+
+    // Successful probe closes it
+    clock.advance(chrono::Duration::seconds(30));
+    assert!(breaker.call(async { Ok::<_, BotError>(()) }).await.is_ok());
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[tokio::test]
+async fn fatal_errors_do_not_count_against_the_service() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let breaker = CircuitBreaker::new("zeroslot", 1, Duration::from_secs(30), clock);
+
+    let rejected = breaker.call(async { Err::<(), _>(BotError::Rejected("Custom(6002)".to_string())) }).await;
+    assert!(rejected.is_err());
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
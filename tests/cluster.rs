@@ -0,0 +1,40 @@
+use solana_vntr_sniper::common::cluster::{parse_dex_list, Cluster, DexProgram};
+
+#[test]
+fn cluster_names_parse() {
+    assert_eq!("mainnet-beta".parse::<Cluster>(), Ok(Cluster::Mainnet));
+    assert_eq!(" Devnet ".parse::<Cluster>(), Ok(Cluster::Devnet));
+    assert!("localnet".parse::<Cluster>().is_err());
+}
+
+#[test]
+fn explorer_links_carry_the_cluster() {
+    assert_eq!(Cluster::Mainnet.tx_url("sig"), "https://solscan.io/tx/sig");
+    assert_eq!(Cluster::Devnet.token_url("mint"), "https://solscan.io/token/mint?cluster=devnet");
+    assert_eq!(Cluster::Testnet.account_url("addr"), "https://solscan.io/account/addr?cluster=testnet");
+}
+
+#[test]
+fn only_the_launchpad_moves_on_devnet() {
+    for program in [DexProgram::PumpFun, DexProgram::PumpSwap] {
+        assert_eq!(program.default_id(Cluster::Devnet), program.default_id(Cluster::Mainnet));
+    }
+    assert_ne!(
+        DexProgram::RaydiumLaunchpad.default_id(Cluster::Devnet),
+        DexProgram::RaydiumLaunchpad.default_id(Cluster::Mainnet)
+    );
+}
+
+#[test]
+fn dex_lists_parse_by_name_without_duplicates() {
+    assert_eq!(DexProgram::from_name("Raydium-Launchpad"), Some(DexProgram::RaydiumLaunchpad));
+    assert_eq!(
+        parse_dex_list(" pumpfun, PumpSwap ,pump "),
+        Ok(vec![DexProgram::PumpFun, DexProgram::PumpSwap])
+    );
+    assert!(parse_dex_list("pumpfun,raydium").is_err());
+    assert!(parse_dex_list(" , ").is_err());
+    for dex in DexProgram::ALL {
+        assert_eq!(DexProgram::from_name(dex.name()), Some(dex));
+    }
+}
@@ -0,0 +1,322 @@
+//! Mock chain and alert layers, and test trade builders, shared by the integration tests.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use anyhow::Result;
+use async_trait::async_trait;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+use solana_vntr_sniper::common::config::{AppState, Config, Percent, SolAmount, SwapConfig, TransactionLandingMode};
+use solana_vntr_sniper::common::quote_asset::QuoteAsset;
+use solana_vntr_sniper::error::BotError;
+use solana_vntr_sniper::library::account_batcher::{AccountBatcher, BatchConfig};
+use solana_vntr_sniper::library::rpc_api::{BalanceChanges, RpcApi, SignatureStatus};
+use solana_vntr_sniper::library::zeroslot::ZeroSlotClient;
+use solana_vntr_sniper::processor::swap::{SwapDirection, SwapInType, SwapProtocol};
+use solana_vntr_sniper::processor::telegram_alerts::MessageSink;
+use solana_vntr_sniper::processor::transaction_parser::{ParsedData, SwapType};
+use teloxide::types::InlineKeyboardMarkup;
+
+/// Nothing listens here: the real clients in a test `AppState` fail fast if anything bypasses the mock
+pub const UNREACHABLE_RPC: &str = "http://127.0.0.1:1";
+
+/// Scripted RPC: every send succeeds and lands with `status`
+pub struct MockRpc {
+    pub blockhash: Option<Hash>,
+    pub status: SignatureStatus,
+    pub token_balance: Option<f64>,
+    pub balance_changes: BalanceChanges,
+    pub sent: Mutex<Vec<Transaction>>,
+    /// v0 transactions, compiled through the lookup table
+    pub sent_versioned: Mutex<Vec<VersionedTransaction>>,
+    /// Signatures of the sends that went through `send_plain_transaction`
+    pub plain_sent: Mutex<Vec<Signature>>,
+}
+
+impl MockRpc {
+    pub fn confirming() -> Self {
+        Self {
+            blockhash: Some(Hash::new_unique()),
+            status: SignatureStatus::Confirmed,
+            token_balance: None,
+            balance_changes: BalanceChanges::default(),
+            sent: Mutex::new(Vec::new()),
+            sent_versioned: Mutex::new(Vec::new()),
+            plain_sent: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn with_status(status: SignatureStatus) -> Self {
+        Self { status, ..Self::confirming() }
+    }
+
+    pub fn without_blockhash() -> Self {
+        Self { blockhash: None, ..Self::confirming() }
+    }
+
+    pub fn sent_count(&self) -> usize {
+        self.sent.lock().unwrap().len() + self.sent_versioned.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl RpcApi for MockRpc {
+    async fn latest_blockhash(&self) -> Option<Hash> {
+        self.blockhash
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, BotError> {
+        let signature = transaction.signatures.first().copied()
+            .ok_or_else(|| BotError::InvalidInput("unsigned transaction".to_string()))?;
+        self.sent.lock().unwrap().push(transaction.clone());
+        Ok(signature)
+    }
+
+    async fn send_versioned_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, BotError> {
+        let signature = transaction.signatures.first().copied()
+            .ok_or_else(|| BotError::InvalidInput("unsigned transaction".to_string()))?;
+        self.sent_versioned.lock().unwrap().push(transaction.clone());
+        Ok(signature)
+    }
+
+    async fn send_plain_transaction(&self, transaction: &Transaction) -> Result<Signature, BotError> {
+        let signature = self.send_transaction(transaction).await?;
+        self.plain_sent.lock().unwrap().push(signature);
+        Ok(signature)
+    }
+
+    async fn signature_status(&self, _signature: &Signature) -> Result<SignatureStatus, BotError> {
+        Ok(self.status.clone())
+    }
+
+    async fn token_balance(&self, _token_account: &Pubkey) -> Result<Option<f64>, BotError> {
+        Ok(self.token_balance)
+    }
+
+    async fn balance_changes(&self, _signature: &Signature, _wallet: &Pubkey) -> Result<BalanceChanges, BotError> {
+        Ok(self.balance_changes.clone())
+    }
+}
+
+/// Records every alert instead of delivering it
+#[derive(Default)]
+pub struct RecordingSink {
+    pub messages: Mutex<Vec<(String, Option<InlineKeyboardMarkup>)>>,
+    pub documents: Mutex<Vec<(PathBuf, String)>>,
+}
+
+impl RecordingSink {
+    pub fn texts(&self) -> Vec<String> {
+        self.messages.lock().unwrap().iter().map(|(text, _)| text.clone()).collect()
+    }
+
+    pub fn keyboards(&self) -> Vec<Option<InlineKeyboardMarkup>> {
+        self.messages.lock().unwrap().iter().map(|(_, keyboard)| keyboard.clone()).collect()
+    }
+}
+
+#[async_trait]
+impl MessageSink for RecordingSink {
+    async fn send(&self, text: &str, keyboard: Option<InlineKeyboardMarkup>) -> Result<()> {
+        self.messages.lock().unwrap().push((text.to_string(), keyboard));
+        Ok(())
+    }
+
+    async fn send_document(&self, path: &Path, caption: &str) -> Result<()> {
+        self.documents.lock().unwrap().push((path.to_path_buf(), caption.to_string()));
+        Ok(())
+    }
+}
+
+/// Records alerts while up; fails every send with a connection error while down
+#[derive(Default)]
+pub struct FlakySink {
+    pub down: AtomicBool,
+    /// Answer with Telegram's 429 instead of delivering
+    pub rate_limited: AtomicBool,
+    pub messages: Mutex<Vec<String>>,
+}
+
+impl FlakySink {
+    pub fn down() -> Self {
+        Self { down: AtomicBool::new(true), rate_limited: AtomicBool::new(false), messages: Mutex::new(Vec::new()) }
+    }
+
+    pub fn set_down(&self, down: bool) {
+        self.down.store(down, Ordering::SeqCst);
+    }
+
+    pub fn set_rate_limited(&self, rate_limited: bool) {
+        self.rate_limited.store(rate_limited, Ordering::SeqCst);
+    }
+
+    pub fn texts(&self) -> Vec<String> {
+        self.messages.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl MessageSink for FlakySink {
+    async fn send(&self, text: &str, _keyboard: Option<InlineKeyboardMarkup>) -> Result<()> {
+        if self.down.load(Ordering::SeqCst) {
+            return Err(anyhow::anyhow!("error sending request: connection refused"));
+        }
+        if self.rate_limited.load(Ordering::SeqCst) {
+            return Err(anyhow::anyhow!("Too Many Requests: retry after 5"));
+        }
+        self.messages.lock().unwrap().push(text.to_string());
+        Ok(())
+    }
+}
+
+pub fn app_state(rpc_api: Arc<dyn RpcApi>) -> AppState {
+    let rpc_nonblocking_client = Arc::new(anchor_client::solana_client::nonblocking::rpc_client::RpcClient::new(UNREACHABLE_RPC.to_string()));
+    AppState {
+        rpc_client: Arc::new(anchor_client::solana_client::rpc_client::RpcClient::new(UNREACHABLE_RPC.to_string())),
+        rpc_nonblocking_client: rpc_nonblocking_client.clone(),
+        zeroslot_rpc_client: Arc::new(ZeroSlotClient::new(UNREACHABLE_RPC)),
+        rpc_api,
+        account_batcher: Arc::new(AccountBatcher::new(rpc_nonblocking_client, BatchConfig::default())),
+        wallet: Arc::new(Keypair::new()),
+        protocol_preference: SwapProtocol::PumpFun,
+    }
+}
+
+pub fn buy_config(amount_in: f64) -> SwapConfig {
+    SwapConfig {
+        swap_direction: SwapDirection::Buy,
+        in_type: SwapInType::Qty,
+        amount_in,
+        slippage: 1000,
+    }
+}
+
+pub fn config(rpc_api: Arc<dyn RpcApi>) -> Config {
+    Config {
+        yellowstone_grpc_http: String::new(),
+        yellowstone_grpc_token: String::new(),
+        app_state: app_state(rpc_api),
+        swap_config: buy_config(0.01),
+        counter_limit: 0,
+        transaction_landing_mode: TransactionLandingMode::Zeroslot,
+        copy_selling_limit: 1.0,
+        selling_unit_price: 0,
+        selling_unit_limit: 0,
+        zero_slot_tip_value: 0.0,
+        focus_drop_threshold: Percent::from_points(15.0),
+        focus_trigger_sol: SolAmount::from_sol(1.0),
+    }
+}
+
+/// A parsed trade for tests: a 0.1 SOL pump.fun buy of `mint` at 0.00005 SOL by a fresh wallet,
+/// with 10 SOL of liquidity. The token amount follows from the SOL amount and price unless set.
+pub fn parsed_trade(mint: Pubkey) -> ParsedTrade {
+    ParsedTrade {
+        data: ParsedData {
+            signature: Pubkey::new_unique().to_string(),
+            slot: 350_000_000,
+            timestamp: 1_750_000_000,
+            dex_name: "PumpFun".to_string(),
+            swap_type: SwapType::Buy,
+            token_mint: mint,
+            signer: Pubkey::new_unique(),
+            pool_id: None,
+            sol_amount: Some(0.1),
+            token_amount: None,
+            token_price: Some(0.00005),
+            liquidity: Some(10.0),
+            token_name: None,
+            token_symbol: None,
+            bonding_curve_progress: None,
+            coin_creator: None,
+            quote_asset: QuoteAsset::Sol,
+        },
+    }
+}
+
+pub struct ParsedTrade {
+    data: ParsedData,
+}
+
+impl ParsedTrade {
+    pub fn swap_type(mut self, swap_type: SwapType) -> Self {
+        self.data.swap_type = swap_type;
+        self
+    }
+
+    pub fn dex(mut self, dex_name: &str) -> Self {
+        self.data.dex_name = dex_name.to_string();
+        self
+    }
+
+    pub fn signer(mut self, signer: Pubkey) -> Self {
+        self.data.signer = signer;
+        self
+    }
+
+    pub fn slot(mut self, slot: u64) -> Self {
+        self.data.slot = slot;
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: u64) -> Self {
+        self.data.timestamp = timestamp;
+        self
+    }
+
+    pub fn sol(mut self, sol: f64) -> Self {
+        self.data.sol_amount = Some(sol);
+        self
+    }
+
+    pub fn tokens(mut self, tokens: f64) -> Self {
+        self.data.token_amount = Some(tokens);
+        self
+    }
+
+    pub fn price(mut self, price: f64) -> Self {
+        self.data.token_price = Some(price);
+        self
+    }
+
+    pub fn liquidity(mut self, liquidity: impl Into<Option<f64>>) -> Self {
+        self.data.liquidity = liquidity.into();
+        self
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.data.token_name = Some(name.to_string());
+        self
+    }
+
+    pub fn symbol(mut self, symbol: &str) -> Self {
+        self.data.token_symbol = Some(symbol.to_string());
+        self
+    }
+
+    pub fn progress(mut self, progress: impl Into<Option<f64>>) -> Self {
+        self.data.bonding_curve_progress = progress.into();
+        self
+    }
+
+    pub fn creator(mut self, creator: Pubkey) -> Self {
+        self.data.coin_creator = Some(creator);
+        self
+    }
+
+    pub fn pool(mut self, pool_id: &str) -> Self {
+        self.data.pool_id = Some(pool_id.to_string());
+        self
+    }
+
+    pub fn build(mut self) -> ParsedData {
+        if self.data.token_amount.is_none() {
+            self.data.token_amount = self.data.sol_amount.zip(self.data.token_price).map(|(sol, price)| sol / price);
+        }
+        self.data
+    }
+}
@@ -0,0 +1,61 @@
+use solana_vntr_sniper::processor::copy_exit::{parse_target_modes, sold_fraction, CopyExitConfig, CopyExitMode};
+use yellowstone_grpc_proto::prelude::{TokenBalance, TransactionStatusMeta, UiTokenAmount};
+
+const TARGET: &str = "TargetWa11et1111111111111111111111111111111";
+const MINT: &str = "Mint111111111111111111111111111111111111111";
+
+fn balance(owner: &str, mint: &str, amount: u64) -> TokenBalance {
+    TokenBalance {
+        account_index: 1,
+        mint: mint.to_string(),
+        ui_token_amount: Some(UiTokenAmount {
+            ui_amount: amount as f64 / 1e6,
+            decimals: 6,
+            amount: amount.to_string(),
+            ui_amount_string: (amount as f64 / 1e6).to_string(),
+        }),
+        owner: owner.to_string(),
+        program_id: String::new(),
+    }
+}
+
+fn meta(pre: u64, post: u64) -> TransactionStatusMeta {
+    TransactionStatusMeta {
+        pre_token_balances: vec![balance(TARGET, MINT, pre), balance("Other", MINT, 5_000_000)],
+        post_token_balances: vec![balance(TARGET, MINT, post), balance("Other", MINT, 9_000_000)],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn the_targets_sold_share_comes_from_its_own_balances() {
+    assert_eq!(sold_fraction(&meta(4_000_000, 3_000_000), TARGET, MINT), Some(0.25));
+    assert_eq!(sold_fraction(&meta(4_000_000, 0), TARGET, MINT), Some(1.0));
+    // A balance that appears only after the transaction was never sold from
+    assert_eq!(sold_fraction(&meta(0, 1_000_000), TARGET, MINT), None);
+    assert_eq!(sold_fraction(&meta(4_000_000, 0), TARGET, "OtherMint"), None);
+}
+
+#[test]
+fn proportional_exits_mirror_the_targets_share() {
+    let config = CopyExitConfig::default();
+    assert_eq!(config.sell_fraction(TARGET, Some(0.25)), Some(0.25));
+    // Near-complete exits sell everything, tiny trims are ignored
+    assert_eq!(config.sell_fraction(TARGET, Some(0.95)), Some(1.0));
+    assert_eq!(config.sell_fraction(TARGET, Some(0.02)), None);
+    // Without balances the exit cannot be sized and falls back to a full sell
+    assert_eq!(config.sell_fraction(TARGET, None), Some(1.0));
+}
+
+#[test]
+fn targets_can_override_the_exit_mode() {
+    let config = CopyExitConfig {
+        target_modes: parse_target_modes(&format!("{}:full, Quiet:none, Broken", TARGET)),
+        ..CopyExitConfig::default()
+    };
+    assert_eq!(config.target_modes.len(), 2);
+    assert_eq!(config.mode_for(TARGET), CopyExitMode::Full);
+    assert_eq!(config.sell_fraction(TARGET, Some(0.25)), Some(1.0));
+    assert_eq!(config.sell_fraction("Quiet", Some(1.0)), None);
+    assert_eq!(config.mode_for("Anyone"), CopyExitMode::Proportional);
+}
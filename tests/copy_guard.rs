@@ -0,0 +1,86 @@
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::copy_guard::{
+    parse_target_delays, CopyGuard, CopyGuardConfig, SellSimulation, SellSimulator,
+};
+use solana_vntr_sniper::processor::risk_score;
+
+/// Returns a fixed outcome and records the sells it was asked to simulate
+struct MockSimulator {
+    outcome: SellSimulation,
+    calls: Mutex<Vec<(Pubkey, u64)>>,
+}
+
+impl MockSimulator {
+    fn new(outcome: SellSimulation) -> Arc<Self> {
+        Arc::new(Self { outcome, calls: Mutex::new(Vec::new()) })
+    }
+}
+
+#[async_trait]
+impl SellSimulator for MockSimulator {
+    async fn simulate_sell(&self, mint: &Pubkey, _holder: &Pubkey, raw_amount: u64) -> SellSimulation {
+        self.calls.lock().unwrap().push((*mint, raw_amount));
+        self.outcome.clone()
+    }
+}
+
+fn config(delay_slots: u64) -> CopyGuardConfig {
+    CopyGuardConfig { default_delay_slots: delay_slots, slot_ms: 1, ..CopyGuardConfig::default() }
+}
+
+#[tokio::test]
+async fn without_a_delay_buys_are_copied_unchecked() {
+    let simulator = MockSimulator::new(SellSimulation::Failed("frozen".to_string()));
+    let guard = CopyGuard::new(config(0), simulator.clone());
+    assert_eq!(guard.vet(&Pubkey::new_unique(), &Pubkey::new_unique(), Some(1_000)).await, Ok(()));
+    assert!(simulator.calls.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn a_failed_sell_simulation_blocks_the_copy() {
+    let simulator = MockSimulator::new(SellSimulation::Failed("custom program error: 0x1771".to_string()));
+    let guard = CopyGuard::new(config(2), simulator.clone());
+    let mint = Pubkey::new_unique();
+    let result = guard.vet(&Pubkey::new_unique(), &mint, Some(5_000_000)).await;
+    assert!(result.unwrap_err().contains("0x1771"));
+    assert_eq!(*simulator.calls.lock().unwrap(), vec![(mint, 5_000_000)]);
+}
+
+#[tokio::test]
+async fn sellable_and_inconclusive_tokens_are_copied() {
+    let guard = CopyGuard::new(config(1), MockSimulator::new(SellSimulation::Sellable));
+    assert_eq!(guard.vet(&Pubkey::new_unique(), &Pubkey::new_unique(), Some(1_000)).await, Ok(()));
+
+    let guard = CopyGuard::new(config(1), MockSimulator::new(SellSimulation::Inconclusive("no route".to_string())));
+    assert_eq!(guard.vet(&Pubkey::new_unique(), &Pubkey::new_unique(), Some(1_000)).await, Ok(()));
+}
+
+#[tokio::test]
+async fn risky_tokens_are_not_copied() {
+    let simulator = MockSimulator::new(SellSimulation::Sellable);
+    let guard = CopyGuard::new(config(1), simulator.clone());
+    let mint = Pubkey::new_unique();
+    risk_score::update_factors(&mint.to_string(), |f| f.freeze_events = 1);
+    assert!(guard.vet(&Pubkey::new_unique(), &mint, Some(1_000)).await.unwrap_err().contains("risk score"));
+    // The cheap check runs first, no simulation needed
+    assert!(simulator.calls.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn delays_can_be_set_per_target() {
+    let (slow, fast) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let config = CopyGuardConfig {
+        target_delays: parse_target_delays(&format!("{}:2, {}:0, broken", slow, fast)),
+        ..config(0)
+    };
+    assert_eq!(config.delay_for(&slow.to_string()), 2);
+    assert_eq!(config.delay_for(&fast.to_string()), 0);
+    assert_eq!(config.delay_for("someone else"), 0);
+
+    let simulator = MockSimulator::new(SellSimulation::Failed("frozen".to_string()));
+    let guard = CopyGuard::new(config, simulator);
+    assert!(guard.vet(&slow, &Pubkey::new_unique(), Some(1_000)).await.is_err());
+    assert!(guard.vet(&fast, &Pubkey::new_unique(), Some(1_000)).await.is_ok());
+}
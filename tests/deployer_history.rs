@@ -0,0 +1,84 @@
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use common::parsed_trade;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::deployer_history::{format_deployer, DeployerHistory, DeployerStats};
+use solana_vntr_sniper::processor::launch_replay::{LaunchOutcome, LaunchRecorder, LaunchReplayConfig};
+use solana_vntr_sniper::processor::transaction_parser::{ParsedData, SwapType};
+
+fn trade(mint: Pubkey, creator: Pubkey, swap_type: SwapType, price: f64) -> ParsedData {
+    parsed_trade(mint)
+        .swap_type(swap_type)
+        .sol(1.0)
+        .price(price)
+        .name("Serial")
+        .symbol("SRL")
+        .progress(20.0)
+        .creator(creator)
+        .build()
+}
+
+fn start() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_750_000_000, 0).unwrap()
+}
+
+#[test]
+fn a_creators_launches_are_listed_with_outcomes_across_restarts() {
+    let path = std::env::temp_dir().join(format!("deployer_history_{}.jsonl", Pubkey::new_unique()));
+    let history = DeployerHistory::new(Some(path.clone()));
+    let mut recorder = LaunchRecorder::new(LaunchReplayConfig::default());
+    let (creator, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (dead, live, unrelated) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+    // A pump to 4x that collapses, from the creator
+    let prices = [1.0, 2.0, 4.0, 1.0, 0.5, 0.1];
+    for (i, price) in prices.iter().enumerate() {
+        let swap_type = if i < 3 { SwapType::Buy } else { SwapType::Sell };
+        let parsed = trade(dead, creator, swap_type, *price);
+        let at = start() + Duration::minutes(i as i64);
+        history.record_launch(&parsed, at);
+        recorder.record(&parsed, at);
+    }
+    history.record_launch(&trade(live, creator, SwapType::Buy, 1.0), start() + Duration::hours(2));
+    history.record_launch(&trade(unrelated, other, SwapType::Buy, 1.0), start());
+    assert_eq!(history.open_count(), 3);
+
+    let finished = recorder.take_finished(10.0, start() + Duration::minutes(30));
+    assert_eq!(finished.len(), 1);
+    let (session, outcome) = &finished[0];
+    assert_eq!(*outcome, LaunchOutcome::Died);
+    history.record_outcome(session, outcome, start() + Duration::minutes(30));
+    assert_eq!(history.open_count(), 2);
+
+    // A fresh instance reads the same file
+    let launches = DeployerHistory::new(Some(path.clone())).launches_of(&creator.to_string());
+    assert_eq!(launches.len(), 2);
+    assert_eq!(launches[0].mint, dead.to_string());
+    assert_eq!(launches[0].launched_at, Some(start().timestamp()));
+    assert_eq!(launches[0].outcome.as_deref(), Some("DIED"));
+    assert_eq!(launches[0].peak_multiple, Some(4.0));
+    assert_eq!(launches[1].outcome, None);
+
+    let stats = DeployerStats::from_launches(&launches);
+    assert_eq!((stats.launches, stats.graduated, stats.died, stats.rugged, stats.open), (2, 0, 1, 0, 1));
+    assert_eq!(stats.graduation_pct(), Some(0.0));
+
+    let text = format_deployer(&creator.to_string(), &launches);
+    assert!(text.contains("2 launches: 0 graduated, 1 died, 0 rugged, 1 live or unresolved"));
+    assert!(text.contains(&format!("Serial {} — DIED, peak 4.0x", dead)));
+    assert!(text.contains(&format!("Serial {} — LIVE / UNRESOLVED", live)));
+    assert!(!text.contains(&unrelated.to_string()));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn unknown_creators_and_a_disabled_history_record_nothing() {
+    let history = DeployerHistory::new(None);
+    history.record_launch(&trade(Pubkey::new_unique(), Pubkey::new_unique(), SwapType::Buy, 1.0), start());
+    assert_eq!(history.open_count(), 0);
+
+    let text = format_deployer("Creator111", &[]);
+    assert!(text.contains("No launches recorded"));
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Duration;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::dip_buy::{DipBuy, DipBuyConfig, DipDecision, HolderCount, WatchReason};
+
+struct FixedHolders(usize);
+
+#[async_trait]
+impl HolderCount for FixedHolders {
+    async fn holder_count(&self, _mint: &Pubkey) -> Result<usize> {
+        Ok(self.0)
+    }
+}
+
+fn dip_buy(clock: Arc<SimulatedClock>) -> DipBuy {
+    let config = DipBuyConfig {
+        enabled: true,
+        drop_pct: 30.0,
+        max_holder_drop_pct: 10.0,
+        cooldown: Duration::seconds(60),
+        max_reentries: 2,
+        watch_for: Duration::hours(1),
+    };
+    DipBuy::new(config).with_clock(clock)
+}
+
+#[tokio::test]
+async fn a_dip_with_stable_holders_signals_a_reentry() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let dips = dip_buy(clock.clone());
+    let mint = Pubkey::new_unique().to_string();
+    assert!(dips.watch(&mint, WatchReason::Sold));
+    dips.set_holders_baseline(&mint, 200);
+
+    dips.observe(&mint, 100.0, 1.0);
+    // Deep enough but still cooling down from the sell
+    assert_eq!(dips.observe(&mint, 60.0, 1.0), None);
+    clock.advance(Duration::seconds(61));
+    let check = dips.observe(&mint, 65.0, 1.0).unwrap();
+    assert_eq!(check.peak_price, 100.0);
+    assert_eq!(check.drop_pct, 35.0);
+
+    match dips.evaluate(check, &FixedHolders(190)).await {
+        DipDecision::Reenter(signal) => {
+            assert_eq!(signal.reentry, 1);
+            assert_eq!((signal.holders_before, signal.holders_now), (200, 190));
+        }
+        other => panic!("expected a re-entry, got {:?}", other),
+    }
+    // The peak starts over from the entry
+    assert_eq!(dips.get(&mint).unwrap().peak_price, 65.0);
+}
+
+#[tokio::test]
+async fn holders_leaving_reject_the_dip() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let dips = dip_buy(clock.clone());
+    let mint = Pubkey::new_unique().to_string();
+    dips.watch(&mint, WatchReason::Skipped);
+    dips.observe(&mint, 100.0, 1.0);
+    clock.advance(Duration::seconds(61));
+
+    // Without a baseline the first check only records one
+    let check = dips.observe(&mint, 50.0, 1.0).unwrap();
+    assert!(matches!(dips.evaluate(check, &FixedHolders(100)).await, DipDecision::Rejected(_)));
+    assert_eq!(dips.get(&mint).unwrap().holders_baseline, Some(100));
+
+    clock.advance(Duration::seconds(61));
+    let check = dips.observe(&mint, 50.0, 1.0).unwrap();
+    match dips.evaluate(check, &FixedHolders(80)).await {
+        DipDecision::Rejected(reason) => assert!(reason.contains("holders fell 20.0%"), "{}", reason),
+        other => panic!("expected a rejection, got {:?}", other),
+    }
+    assert_eq!(dips.get(&mint).unwrap().reentries, 0);
+}
+
+#[tokio::test]
+async fn reentries_are_capped_and_watching_expires() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let dips = dip_buy(clock.clone());
+    let mint = Pubkey::new_unique().to_string();
+    dips.watch(&mint, WatchReason::Sold);
+    dips.set_holders_baseline(&mint, 50);
+
+    let mut price = 100.0;
+    for _ in 0..2 {
+        dips.observe(&mint, price, 1.0);
+        clock.advance(Duration::seconds(61));
+        price *= 0.5;
+        let check = dips.observe(&mint, price, 1.0).unwrap();
+        assert!(matches!(dips.evaluate(check, &FixedHolders(50)).await, DipDecision::Reenter(_)));
+    }
+    clock.advance(Duration::seconds(61));
+    assert_eq!(dips.observe(&mint, price * 0.1, 1.0), None);
+    assert!(dips.get(&mint).is_none());
+
+    let other = Pubkey::new_unique().to_string();
+    dips.watch(&other, WatchReason::Sold);
+    clock.advance(Duration::hours(2));
+    assert_eq!(dips.observe(&other, 1.0, 1.0), None);
+    assert!(dips.is_empty());
+
+    let disabled = DipBuy::new(DipBuyConfig::default());
+    assert!(!disabled.watch(&other, WatchReason::Sold));
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use std::sync::Arc;
+use common::{config, parsed_trade, MockRpc, RecordingSink};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::educational_monitor::{format_follow_up, AlertSnapshot, EducationalMonitor, TokenMetrics};
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+use solana_vntr_sniper::processor::transaction_parser::{ParsedData, SwapType};
+
+fn trade(mint: Pubkey, signer: Pubkey, swap_type: SwapType, sol: f64) -> ParsedData {
+    parsed_trade(mint)
+        .swap_type(swap_type)
+        .signer(signer)
+        .sol(sol)
+        .name("Test")
+        .symbol("TST")
+        .progress(40.0)
+        .build()
+}
+
+fn metrics(price: Option<f64>, total_volume: f64, liquidity: f64, buy_count: u32, sell_count: u32) -> TokenMetrics {
+    let now = chrono::Utc::now();
+    TokenMetrics {
+        address: Pubkey::new_unique(),
+        name: None,
+        symbol: None,
+        initial_price: price,
+        current_price: price,
+        volume_24h: total_volume,
+        buy_volume_24h: 0.0,
+        sell_volume_24h: 0.0,
+        volume_1h: 0.0,
+        total_volume,
+        liquidity,
+        holder_count: 0,
+        first_seen: now,
+        last_updated: now,
+        buy_count,
+        sell_count,
+        largest_buy_sol: 0.0,
+        largest_sell_sol: 0.0,
+        bonding_curve_progress: None,
+        graduation_alerted: false,
+        pinned: false,
+        fresh_wallet_pct: None,
+    }
+}
+
+fn snapshot(price: Option<f64>, volume: f64, liquidity: f64, buy_count: u32, sell_count: u32) -> AlertSnapshot {
+    AlertSnapshot { price, volume, liquidity, buy_count, sell_count, at: chrono::Utc::now() }
+}
+
+fn monitor(sink: Arc<RecordingSink>) -> EducationalMonitor {
+    let telegram = TelegramAlertSystem::with_sink(sink, true);
+    EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), Some(Arc::new(telegram)))
+}
+
+#[tokio::test]
+async fn first_buy_of_a_token_alerts_wallet_activity_and_new_token() {
+    let sink = Arc::new(RecordingSink::default());
+    let monitor = monitor(sink.clone());
+
+    monitor.process_for_education(&trade(Pubkey::new_unique(), Pubkey::new_unique(), SwapType::Buy, 1.0)).await.unwrap();
+
+    let texts = sink.texts();
+    assert!(texts.iter().any(|t| t.contains("WALLET ACTIVITY") && t.contains("BUY")), "{:?}", texts);
+    assert!(texts.iter().any(|t| t.contains("NEW TOKEN")), "{:?}", texts);
+}
+
+#[tokio::test]
+async fn repeated_activity_is_rate_limited() {
+    let sink = Arc::new(RecordingSink::default());
+    let monitor = monitor(sink.clone());
+    let (mint, wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    monitor.process_for_education(&trade(mint, wallet, SwapType::Buy, 1.0)).await.unwrap();
+    monitor.process_for_education(&trade(mint, wallet, SwapType::Buy, 1.0)).await.unwrap();
+
+    let activity = sink.texts().iter().filter(|t| t.contains("WALLET ACTIVITY")).count();
+    assert_eq!(activity, 1);
+}
+
+#[tokio::test]
+async fn disabled_alerts_send_nothing() {
+    let sink = Arc::new(RecordingSink::default());
+    let telegram = TelegramAlertSystem::with_sink(sink.clone(), false);
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), Some(Arc::new(telegram)));
+
+    monitor.process_for_education(&trade(Pubkey::new_unique(), Pubkey::new_unique(), SwapType::Sell, 25.0)).await.unwrap();
+
+    assert!(sink.texts().is_empty());
+}
+
+#[tokio::test]
+async fn rate_limit_expires_on_the_injected_clock() {
+    let sink = Arc::new(RecordingSink::default());
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let telegram = TelegramAlertSystem::with_sink(sink.clone(), true).with_clock(clock.clone());
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), Some(Arc::new(telegram)))
+        .with_clock(clock.clone());
+    let (mint, wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    monitor.process_for_education(&trade(mint, wallet, SwapType::Buy, 1.0)).await.unwrap();
+    clock.advance(chrono::Duration::seconds(10));
+    monitor.process_for_education(&trade(mint, wallet, SwapType::Buy, 1.0)).await.unwrap();
+    clock.advance(chrono::Duration::seconds(30));
+    monitor.process_for_education(&trade(mint, wallet, SwapType::Buy, 1.0)).await.unwrap();
+
+    let activity = sink.texts().iter().filter(|t| t.contains("WALLET ACTIVITY")).count();
+    assert_eq!(activity, 2);
+}
+
+#[test]
+fn follow_up_reports_changes_since_the_alert() {
+    let baseline = snapshot(Some(0.0001), 10.0, 20.0, 5, 2);
+    let current = metrics(Some(0.00015), 14.5, 25.0, 9, 3);
+
+    let text = format_follow_up(&baseline, &current);
+    assert!(text.contains("💱 Price: 0.0001000000 → 0.0001500000 (+50.00%)"), "{}", text);
+    assert!(text.contains("Volume since alert: 4.50 SOL"), "{}", text);
+    assert!(text.contains("Trades since alert: 4 buys / 1 sells"), "{}", text);
+    assert!(text.contains("Liquidity: 20.00 → 25.00 SOL"), "{}", text);
+}
+
+#[test]
+fn follow_up_without_a_baseline_price_shows_the_current_one() {
+    let text = format_follow_up(&snapshot(None, 0.0, 0.0, 0, 0), &metrics(Some(0.0002), 1.0, 0.0, 1, 0));
+    assert!(text.contains("💱 Price now: 0.0002000000"), "{}", text);
+
+    let text = format_follow_up(&snapshot(Some(0.0), 0.0, 0.0, 0, 0), &metrics(Some(0.0002), 1.0, 0.0, 1, 0));
+    assert!(text.contains("💱 Price now:"), "{}", text);
+
+    let text = format_follow_up(&snapshot(None, 0.0, 0.0, 0, 0), &metrics(None, 0.0, 0.0, 0, 0));
+    assert!(text.contains("no trades observed yet"), "{}", text);
+}
+
+#[test]
+fn follow_up_trade_counts_never_go_negative() {
+    // Counters reset (e.g. metrics rebuilt) after the alert
+    let text = format_follow_up(&snapshot(Some(1.0), 5.0, 1.0, 10, 10), &metrics(Some(1.0), 6.0, 1.0, 2, 1));
+    assert!(text.contains("Trades since alert: 0 buys / 0 sells"), "{}", text);
+}
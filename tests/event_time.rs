@@ -0,0 +1,80 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use common::{config, parsed_trade, MockRpc};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::{Clock, EventClock, SimulatedClock};
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::transaction_parser::ParsedData;
+
+const BLOCK_TIME: i64 = 1_750_000_000;
+
+fn at(timestamp: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(timestamp, 0).unwrap()
+}
+
+fn trade(mint: Pubkey, dex_name: &str, timestamp: u64) -> ParsedData {
+    parsed_trade(mint)
+        .timestamp(timestamp)
+        .dex(dex_name)
+        .sol(0.5)
+        .name("Test")
+        .symbol("TST")
+        .progress(40.0)
+        .build()
+}
+
+#[test]
+fn event_time_comes_from_the_block_when_the_dex_stamps_it() {
+    let mint = Pubkey::new_unique();
+    assert_eq!(trade(mint, "PumpFun", BLOCK_TIME as u64).event_time(), Some(at(BLOCK_TIME)));
+    assert_eq!(trade(mint, "PumpFun", 0).event_time(), None);
+    assert_eq!(trade(mint, "RaydiumLaunchpad", BLOCK_TIME as u64).event_time(), None);
+}
+
+#[test]
+fn the_event_clock_runs_behind_by_the_lag() {
+    let inner = Arc::new(SimulatedClock::at_unix(BLOCK_TIME + 90));
+    let clock = EventClock::new(inner.clone());
+    assert_eq!(clock.now(), at(BLOCK_TIME + 90));
+
+    assert_eq!(clock.observe(Some(at(BLOCK_TIME))), at(BLOCK_TIME));
+    assert_eq!(clock.lag(), Duration::seconds(90));
+    inner.advance(Duration::seconds(30));
+    assert_eq!(clock.now(), at(BLOCK_TIME + 30));
+    assert_eq!(clock.observe(None), at(BLOCK_TIME + 30));
+}
+
+#[test]
+fn older_events_do_not_move_the_clock_back() {
+    let inner = Arc::new(SimulatedClock::at_unix(BLOCK_TIME + 60));
+    let clock = EventClock::new(inner);
+    clock.observe(Some(at(BLOCK_TIME + 50)));
+    assert_eq!(clock.observe(Some(at(BLOCK_TIME))), at(BLOCK_TIME));
+    assert_eq!(clock.now(), at(BLOCK_TIME + 50));
+}
+
+#[test]
+fn future_block_times_count_as_now() {
+    let inner = Arc::new(SimulatedClock::at_unix(BLOCK_TIME));
+    let clock = EventClock::new(inner);
+    clock.observe(Some(at(BLOCK_TIME - 20)));
+    assert_eq!(clock.observe(Some(at(BLOCK_TIME + 5))), at(BLOCK_TIME));
+    assert_eq!(clock.lag(), Duration::zero());
+}
+
+#[tokio::test]
+async fn a_lagging_monitor_dates_tokens_by_their_trades() {
+    let clock = Arc::new(SimulatedClock::at_unix(BLOCK_TIME + 600));
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), None).with_clock(clock.clone());
+    let mint = Pubkey::new_unique();
+
+    monitor.process_for_education(&trade(mint, "PumpFun", BLOCK_TIME as u64)).await.unwrap();
+    clock.advance(Duration::seconds(5));
+    monitor.process_for_education(&trade(mint, "PumpFun", BLOCK_TIME as u64 + 120)).await.unwrap();
+
+    let token = monitor.get_token(&mint).await.unwrap();
+    assert_eq!(token.first_seen, at(BLOCK_TIME));
+    assert_eq!(token.last_updated, at(BLOCK_TIME + 120));
+}
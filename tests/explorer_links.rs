@@ -0,0 +1,93 @@
+mod common;
+
+use std::sync::Arc;
+use common::RecordingSink;
+use solana_sdk::pubkey::Pubkey;
+use teloxide::types::InlineKeyboardButtonKind;
+use solana_vntr_sniper::common::cluster::Cluster;
+use solana_vntr_sniper::common::explorer::{Explorer, ExplorerLinks, TradeLinks};
+use solana_vntr_sniper::processor::opportunity_signal::{MetricsSnapshot, OpportunityKind, OpportunitySignal};
+use solana_vntr_sniper::processor::signal_outcomes::SignalDirection;
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+
+#[test]
+fn token_links_put_the_preferred_explorer_first() {
+    let links = ExplorerLinks::new(Explorer::DexScreener, Cluster::Mainnet);
+    assert_eq!(
+        links.token("Mint"),
+        "[DexScreener](https://dexscreener.com/solana/Mint) | \
+        [Solscan](https://solscan.io/token/Mint) | \
+        [Birdeye](https://birdeye.so/token/Mint?chain=solana) | \
+        [Pump.fun](https://pump.fun/coin/Mint)"
+    );
+}
+
+#[test]
+fn mainnet_only_sites_are_dropped_off_mainnet() {
+    let links = ExplorerLinks::new(Explorer::Birdeye, Cluster::Devnet);
+    assert_eq!(links.token("Mint"), "[Solscan](https://solscan.io/token/Mint?cluster=devnet)");
+    assert_eq!(links.wallet("WalletAddress123"), "[WalletAd](https://solscan.io/account/WalletAddress123?cluster=devnet)");
+}
+
+#[test]
+fn transactions_fall_back_to_solscan() {
+    let links = ExplorerLinks::new(Explorer::PumpFun, Cluster::Mainnet);
+    assert_eq!(links.transaction("Sig"), "[View on Solscan](https://solscan.io/tx/Sig)");
+}
+
+#[tokio::test]
+async fn wallet_activity_alerts_link_wallet_token_and_transaction() {
+    let sink = Arc::new(RecordingSink::default());
+    let alerts = TelegramAlertSystem::with_sink(sink.clone(), true)
+        .with_links(ExplorerLinks::new(Explorer::Solscan, Cluster::Mainnet));
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+
+    alerts.alert_wallet_activity(&wallet, "BUY", &mint, None, 1.0, None, None, None, Some("Sig")).await.unwrap();
+
+    let text = &sink.texts()[0];
+    assert!(text.contains(&format!("https://solscan.io/account/{}", wallet)), "{}", text);
+    assert!(text.contains(&format!("https://birdeye.so/token/{}", mint)), "{}", text);
+    assert!(text.contains("https://solscan.io/tx/Sig"), "{}", text);
+}
+
+fn button_urls(sink: &RecordingSink) -> Vec<String> {
+    sink.keyboards()[0]
+        .as_ref()
+        .map(|keyboard| {
+            keyboard.inline_keyboard.iter()
+                .flatten()
+                .filter_map(|button| match &button.kind {
+                    InlineKeyboardButtonKind::Url(url) => Some(url.to_string()),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn opportunity_alerts_carry_trade_links_only_when_enabled() {
+    let mint = Pubkey::new_unique();
+    let signal = OpportunitySignal::new(
+        OpportunityKind::LargeTransaction,
+        mint,
+        None,
+        SignalDirection::Bullish,
+        "details".to_string(),
+        MetricsSnapshot::default(),
+    );
+
+    let sink = Arc::new(RecordingSink::default());
+    let alerts = TelegramAlertSystem::with_sink(sink.clone(), true).with_trade_links(None);
+    alerts.alert_sniper_opportunity(&signal).await.unwrap();
+    assert!(button_urls(&sink).is_empty());
+
+    let sink = Arc::new(RecordingSink::default());
+    let alerts = TelegramAlertSystem::with_sink(sink.clone(), true).with_trade_links(Some(TradeLinks::new(0.25)));
+    alerts.alert_sniper_opportunity(&signal).await.unwrap();
+    let urls = button_urls(&sink);
+    assert_eq!(urls.len(), 2, "{:?}", urls);
+    assert!(urls[0].starts_with(&format!("https://jup.ag/swap/SOL-{}", mint)), "{:?}", urls);
+    assert!(urls[0].contains("inAmount=0.25"), "{:?}", urls);
+}
@@ -0,0 +1,85 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::quote_asset::QuoteAsset;
+use solana_vntr_sniper::processor::fill_check::{FillCheck, FillConfig, FillReport};
+use solana_vntr_sniper::processor::sniper_bot::{BoughtTokenInfo, BOUGHT_TOKEN_LIST};
+use solana_vntr_sniper::processor::swap::SwapProtocol;
+use solana_vntr_sniper::processor::trade_ledger::{LedgerTrade, TradeSide};
+use solana_vntr_sniper::processor::transaction_parser::{DexType, TradeInfoFromToken};
+
+fn trade(mint: &str, side: TradeSide, token_amount: f64, sol_amount: f64) -> LedgerTrade {
+    LedgerTrade {
+        timestamp: 1_750_000_000,
+        signature: format!("sig-{}", mint),
+        mint: mint.to_string(),
+        side,
+        token_amount,
+        sol_amount,
+        fee_sol: 0.000005,
+    }
+}
+
+/// Position quoted at 10,000 tokens for 0.01 SOL
+fn position(mint: &str) -> BoughtTokenInfo {
+    let trade_info = TradeInfoFromToken {
+        dex_type: DexType::PumpFun,
+        slot: 350_000_000,
+        signature: "target".to_string(),
+        pool_id: String::new(),
+        mint: mint.to_string(),
+        timestamp: 1_750_000_000,
+        is_buy: true,
+        price: 50,
+        is_reverse_when_pump_swap: false,
+        coin_creator: None,
+        sol_change: 0.5,
+        token_change: 10_000.0,
+        liquidity: 10.0,
+        virtual_sol_reserves: 40_000_000_000,
+        virtual_token_reserves: 800_000_000_000_000,
+        quote_asset: QuoteAsset::Sol,
+    };
+    BoughtTokenInfo::new(mint.to_string(), 50, 0.01, 10_000.0, SwapProtocol::PumpFun, trade_info, 300)
+}
+
+#[test]
+fn deviation_is_measured_against_the_quote() {
+    let report = FillReport::new(10_000.0, 0.01, &trade("Mint111", TradeSide::Buy, 8_500.0, 0.0101));
+    assert!((report.token_deviation_pct() + 15.0).abs() < 1e-9);
+    assert!((report.sol_deviation_pct() - 1.0).abs() < 1e-9);
+    assert!(report.deviates(10.0));
+    assert!(!report.deviates(20.0));
+
+    let checks = FillCheck::new(FillConfig { alert_deviation_pct: 10.0 });
+    assert!(checks.should_alert(&report));
+    assert!(!FillCheck::new(FillConfig { alert_deviation_pct: 0.0 }).should_alert(&report));
+}
+
+#[test]
+fn fill_confirmed_before_the_position_opens_is_applied_on_open() {
+    let checks = FillCheck::new(FillConfig::default());
+    let mint = Pubkey::new_unique().to_string();
+
+    assert_eq!(checks.trade_confirmed(&trade(&mint, TradeSide::Buy, 9_000.0, 0.0102)), None);
+
+    let mut opened = position(&mint);
+    let report = checks.position_opened(&mut opened).unwrap();
+    assert_eq!(report.quoted_tokens, 10_000.0);
+    assert_eq!(opened.current_amount, 9_000.0);
+    assert_eq!(opened.initial_amount, 0.0102);
+}
+
+#[test]
+fn fill_confirmed_after_the_position_opened_corrects_it() {
+    let checks = FillCheck::new(FillConfig::default());
+    let mint = Pubkey::new_unique().to_string();
+    BOUGHT_TOKEN_LIST.insert(mint.clone(), position(&mint));
+
+    let report = checks.trade_confirmed(&trade(&mint, TradeSide::Buy, 4_000.0, 0.01)).unwrap();
+    assert!((report.token_deviation_pct() + 60.0).abs() < 1e-9);
+    assert_eq!(BOUGHT_TOKEN_LIST.get(&mint).unwrap().current_amount, 4_000.0);
+
+    // Once sold, a later position of the mint starts from its quote again
+    checks.trade_confirmed(&trade(&mint, TradeSide::Sell, 4_000.0, 0.02));
+    assert_eq!(checks.position_opened(&mut position(&mint)), None);
+    BOUGHT_TOKEN_LIST.remove(&mint);
+}
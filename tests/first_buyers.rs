@@ -0,0 +1,73 @@
+mod common;
+
+use common::parsed_trade;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::first_buyers::{format_first_buyers, FirstBuyers};
+use solana_vntr_sniper::processor::transaction_parser::ParsedData;
+use solana_vntr_sniper::processor::wallet_age::WalletOrigin;
+
+const NOW: i64 = 1_750_000_000;
+
+fn buy(mint: Pubkey, signer: Pubkey, slot: u64, sol: f64, progress: f64) -> ParsedData {
+    parsed_trade(mint)
+        .slot(slot)
+        .timestamp(NOW as u64)
+        .signer(signer)
+        .sol(sol)
+        .price(0.000001)
+        .liquidity(30.0)
+        .progress(progress)
+        .build()
+}
+
+#[test]
+fn first_buyers_of_a_launch_are_captured_and_stored_once_complete() {
+    let path = std::env::temp_dir().join(format!("first_buyers_{}.jsonl", Pubkey::new_unique()));
+    let store = FirstBuyers::new(Some(path.clone()), 3, 3600);
+    let (mint, funder) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let wallets: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+
+    // Two bundled fresh wallets in the launch slot, a repeat buy, then an aged wallet
+    assert!(store.record(&buy(mint, wallets[0], 100, 1.0, 0.5)));
+    assert!(store.record(&buy(mint, wallets[1], 100, 2.0, 1.0)));
+    assert!(!store.record(&buy(mint, wallets[0], 101, 0.5, 1.5)));
+    let fresh = WalletOrigin { created_at: Some(NOW - 300), funder: Some(funder) };
+    assert!(store.set_origin(&mint, &wallets[0], Some(fresh)).is_none());
+    assert!(store.set_origin(&mint, &wallets[1], Some(fresh)).is_none());
+    assert!(store.record(&buy(mint, wallets[2], 103, 0.1, 2.0)));
+    let snapshot = store.set_origin(&mint, &wallets[2], Some(WalletOrigin { created_at: None, funder: None })).unwrap();
+    assert!(snapshot.complete);
+    assert_eq!(snapshot.buyers[0].sol, 1.5);
+    assert_eq!(snapshot.same_slot_buyers(), 2);
+    assert_eq!(snapshot.top_funder(), Some((funder.to_string().as_str(), 2)));
+
+    // Full: later buyers are not added
+    assert!(!store.record(&buy(mint, wallets[3], 104, 5.0, 3.0)));
+
+    let text = format_first_buyers(&snapshot, 3600);
+    assert!(text.contains("First 3 buyers of"));
+    assert!(text.contains("1.500 SOL, slot +0, 🆕 fresh, 5m old, funded by"));
+    assert!(text.contains("0.100 SOL, slot +3, aged wallet"));
+    assert!(text.contains("2 of 3 bought in the launch slot"));
+    assert!(text.contains("⚠️ 2 buyers funded by the same wallet"));
+
+    // Read back from the file by a new store
+    let reloaded = FirstBuyers::new(Some(path.clone()), 3, 3600);
+    assert_eq!(reloaded.get(&mint), Some(snapshot));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn tokens_first_seen_mid_curve_are_not_launches() {
+    let store = FirstBuyers::new(None, 20, 3600);
+    let mint = Pubkey::new_unique();
+    assert!(!store.record(&buy(mint, Pubkey::new_unique(), 100, 1.0, 45.0)));
+    assert!(store.get(&mint).is_none());
+
+    let launch = Pubkey::new_unique();
+    assert!(store.record(&buy(launch, Pubkey::new_unique(), 100, 1.0, 0.1)));
+    let snapshot = store.get(&launch).unwrap();
+    assert!(!snapshot.complete);
+    assert!(format_first_buyers(&snapshot, 3600).contains("(so far)"));
+    assert!(format_first_buyers(&snapshot, 3600).contains("age unknown"));
+}
@@ -0,0 +1,196 @@
+//! Graduation sniping end to end: a pump.fun trade near curve completion arms the token and
+//! pre-builds its buy, the `migrate` instruction sends it through the shared buy gates.
+
+mod common;
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use common::{app_state, buy_config, MockRpc};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_vntr_sniper::common::quote_asset::QuoteAsset;
+use solana_vntr_sniper::dex::pump_fun::{self, PUMP_FUN_MIGRATE_IX_DISCRIMINATOR, PUMP_FUN_PROGRAM};
+use solana_vntr_sniper::dex::pump_swap::{self, SOL_MINT};
+use solana_vntr_sniper::processor::graduation_sniper::{GraduationSniper, GraduationSniperConfig, GRADUATION_CANDIDATES};
+use solana_vntr_sniper::processor::sniper_bot::{execute_prebuilt_buy, BOUGHT_TOKEN_LIST};
+use solana_vntr_sniper::processor::swap::SwapProtocol;
+use solana_vntr_sniper::processor::transaction_parser::{DexType, TradeInfoFromToken};
+use yellowstone_grpc_proto::prelude::{
+    CompiledInstruction, InnerInstruction, InnerInstructions, Message, SubscribeUpdateTransaction,
+    SubscribeUpdateTransactionInfo, TokenBalance, Transaction, TransactionStatusMeta, UiTokenAmount,
+};
+
+const ANCHOR_EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+const PUMP_FUN_TRADE_EVENT: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+/// Virtual token reserves of a curve about 98% complete
+const NEARLY_COMPLETE_RESERVES: u64 = 295_000_000_000_000;
+const SLOT: u64 = 350_000_000;
+
+fn put_u64(buf: &mut [u8], offset: usize, value: u64) {
+    buf[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+fn put_key(buf: &mut [u8], offset: usize, key: &Pubkey) {
+    buf[offset..offset + 32].copy_from_slice(key.as_ref());
+}
+
+/// pump.fun TradeEvent (266 bytes) buying `mint` on a nearly complete curve
+fn trade_event(mint: &Pubkey, creator: &Pubkey) -> Vec<u8> {
+    let mut data = vec![0u8; 266];
+    data[..8].copy_from_slice(&ANCHOR_EVENT_IX_TAG);
+    data[8..16].copy_from_slice(&PUMP_FUN_TRADE_EVENT);
+    put_key(&mut data, 16, mint);
+    put_u64(&mut data, 48, 1_000_000_000);
+    put_u64(&mut data, 56, 10_000_000_000);
+    data[64] = 1;
+    put_u64(&mut data, 97, 1_750_000_000);
+    put_u64(&mut data, 105, 84_000_000_000);
+    put_u64(&mut data, 113, NEARLY_COMPLETE_RESERVES);
+    put_u64(&mut data, 121, 10_000_000_000);
+    put_key(&mut data, 185, creator);
+    data
+}
+
+fn transaction(
+    account_keys: Vec<Pubkey>,
+    instructions: Vec<CompiledInstruction>,
+    inner_instructions: Vec<InnerInstructions>,
+    post_token_balances: Vec<TokenBalance>,
+) -> SubscribeUpdateTransaction {
+    SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: vec![7u8; 64],
+            is_vote: false,
+            transaction: Some(Transaction {
+                signatures: vec![vec![7u8; 64]],
+                message: Some(Message {
+                    account_keys: account_keys.iter().map(|k| k.to_bytes().to_vec()).collect(),
+                    instructions,
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(TransactionStatusMeta { inner_instructions, post_token_balances, ..Default::default() }),
+            index: 0,
+        }),
+        slot: SLOT,
+    }
+}
+
+/// A buy on the curve, emitting its trade event through a self-CPI
+fn curve_buy(mint: &Pubkey, creator: &Pubkey) -> SubscribeUpdateTransaction {
+    let pump_fun = Pubkey::from_str(PUMP_FUN_PROGRAM).unwrap();
+    let event = InnerInstruction { program_id_index: 1, accounts: vec![], data: trade_event(mint, creator), stack_height: Some(2) };
+    transaction(
+        vec![Pubkey::new_unique(), pump_fun, *mint],
+        vec![CompiledInstruction { program_id_index: 1, accounts: vec![0, 2], data: vec![102, 6, 61, 18, 1, 218, 235, 234] }],
+        vec![InnerInstructions { index: 0, instructions: vec![event] }],
+        vec![],
+    )
+}
+
+/// The `migrate` of `mint`, leaving the canonical pool with `base` tokens and `quote` lamports
+fn migration(mint: &Pubkey, base: u64, quote: u64) -> SubscribeUpdateTransaction {
+    let pump_fun = Pubkey::from_str(PUMP_FUN_PROGRAM).unwrap();
+    let pool = pump_swap::get_canonical_pool_pda(mint);
+    let balance = |account_index: u32, balance_mint: String, amount: u64| TokenBalance {
+        account_index,
+        mint: balance_mint,
+        owner: pool.to_string(),
+        ui_token_amount: Some(UiTokenAmount { amount: amount.to_string(), ..Default::default() }),
+        ..Default::default()
+    };
+    transaction(
+        vec![Pubkey::new_unique(), pump_fun, *mint, pool],
+        vec![CompiledInstruction { program_id_index: 1, accounts: vec![0, 2, 3], data: PUMP_FUN_MIGRATE_IX_DISCRIMINATOR.to_vec() }],
+        vec![],
+        vec![balance(3, mint.to_string(), base), balance(3, SOL_MINT.to_string(), quote)],
+    )
+}
+
+async fn eventually(what: &str, condition: impl Fn() -> bool) {
+    for _ in 0..200 {
+        if condition() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+    panic!("timed out waiting until {}", what);
+}
+
+#[tokio::test]
+async fn an_armed_token_is_bought_when_it_migrates() {
+    std::env::set_var("ZERO_SLOT_TIP_VALUE", "0.001");
+    let rpc = Arc::new(MockRpc::confirming());
+    let state = Arc::new(app_state(rpc.clone()));
+    let config = GraduationSniperConfig::new(String::new(), String::new(), state, Arc::new(buy_config(0.01)));
+    let sniper = GraduationSniper::new(config, None);
+    let mint = Pubkey::new_unique();
+
+    sniper.handle_transaction(curve_buy(&mint, &Pubkey::new_unique()));
+    assert!(GRADUATION_CANDIDATES.contains_key(&mint.to_string()));
+    eventually("the buy is pre-built", || {
+        GRADUATION_CANDIDATES.get(&mint.to_string()).map_or(false, |c| c.prebuilt.is_some())
+    }).await;
+    assert_eq!(rpc.sent_count(), 0);
+
+    let (base, quote) = pump_fun::expected_migration_reserves();
+    sniper.handle_transaction(migration(&mint, base, quote));
+
+    eventually("the graduation buy is tracked", || BOUGHT_TOKEN_LIST.contains_key(&mint.to_string())).await;
+    assert!(!GRADUATION_CANDIDATES.contains_key(&mint.to_string()));
+    assert_eq!(rpc.sent_count(), 1);
+    let sent = rpc.sent.lock().unwrap()[0].clone();
+    assert!(sent.message.account_keys.contains(&pump_swap::get_canonical_pool_pda(&mint)));
+}
+
+#[tokio::test]
+async fn unarmed_tokens_are_not_bought_on_migration() {
+    let rpc = Arc::new(MockRpc::confirming());
+    let config = GraduationSniperConfig::new(String::new(), String::new(), Arc::new(app_state(rpc.clone())), Arc::new(buy_config(0.01)));
+    let sniper = GraduationSniper::new(config, None);
+    let mint = Pubkey::new_unique();
+
+    let (base, quote) = pump_fun::expected_migration_reserves();
+    sniper.handle_transaction(migration(&mint, base, quote));
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    assert_eq!(rpc.sent_count(), 0);
+    assert!(!BOUGHT_TOKEN_LIST.contains_key(&mint.to_string()));
+}
+
+#[tokio::test]
+async fn a_prebuilt_buy_larger_than_the_buy_size_is_not_sent() {
+    let rpc = Arc::new(MockRpc::confirming());
+    let mint = Pubkey::new_unique();
+    let trade_info = TradeInfoFromToken {
+        dex_type: DexType::PumpSwap,
+        slot: SLOT,
+        signature: String::new(),
+        pool_id: pump_swap::get_canonical_pool_pda(&mint).to_string(),
+        mint: mint.to_string(),
+        timestamp: chrono::Utc::now().timestamp() as u64,
+        is_buy: true,
+        price: 400,
+        is_reverse_when_pump_swap: false,
+        coin_creator: Some(Pubkey::new_unique().to_string()),
+        sol_change: -0.5,
+        token_change: 1_000_000.0,
+        liquidity: 85.0,
+        virtual_sol_reserves: 85_000_000_000,
+        virtual_token_reserves: 206_900_000_000_000,
+        quote_asset: QuoteAsset::Sol,
+    };
+
+    let result = execute_prebuilt_buy(
+        trade_info,
+        Arc::new(Keypair::new()),
+        vec![],
+        Arc::new(app_state(rpc.clone())),
+        Arc::new(buy_config(0.01)),
+        SwapProtocol::PumpSwap,
+    ).await;
+
+    assert!(result.unwrap_err().contains("Pre-built for 0.5 SOL"));
+    assert_eq!(rpc.sent_count(), 0);
+}
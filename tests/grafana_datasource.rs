@@ -0,0 +1,77 @@
+use chrono::DateTime;
+use serde_json::{json, Value};
+use solana_vntr_sniper::common::price_history::{PriceHistory, PricePoint};
+use solana_vntr_sniper::library::grafana_datasource::{query_body, search_body};
+
+/// 2024-03-01T12:00:00Z
+const START: i64 = 1_709_294_400;
+
+fn at(secs: i64) -> DateTime<chrono::Utc> {
+    DateTime::from_timestamp(START + secs, 0).unwrap()
+}
+
+fn query(targets: &[&str], max_points: u64) -> String {
+    json!({
+        "range": {"from": "2024-03-01T12:00:00.000Z", "to": "2024-03-01T13:00:00.000Z"},
+        "maxDataPoints": max_points,
+        "targets": targets.iter().map(|t| json!({"target": t, "refId": "A", "type": "timeserie"})).collect::<Vec<_>>(),
+    })
+    .to_string()
+}
+
+#[test]
+fn trades_are_bucketed_and_capped() {
+    let history = PriceHistory::new(10, 2);
+    history.record("Mint111", at(1), 1.0, true, 0.5);
+    history.record("Mint111", at(9), 1.2, false, 0.25);
+    history.record("Mint111", at(12), 1.4, true, 1.0);
+    // Late trade of an already closed bucket
+    history.record("Mint111", at(5), 9.0, true, 9.0);
+    history.record("Mint111", at(25), 1.1, true, 2.0);
+
+    let points = history.points("Mint111", 0, i64::MAX);
+    assert_eq!(points, vec![
+        PricePoint { timestamp_ms: (START + 10) * 1000, price: 1.4, buy_volume: 1.0, sell_volume: 0.0 },
+        PricePoint { timestamp_ms: (START + 20) * 1000, price: 1.1, buy_volume: 2.0, sell_volume: 0.0 },
+    ]);
+
+    let disabled = PriceHistory::new(10, 0);
+    disabled.record("Mint111", at(1), 1.0, true, 0.5);
+    assert!(disabled.is_empty());
+}
+
+#[test]
+fn search_lists_the_metrics_of_each_token() {
+    let history = PriceHistory::new(10, 10);
+    history.record("Bbb", at(0), 1.0, true, 0.5);
+    history.record("Aaa", at(0), 2.0, true, 0.5);
+
+    let all: Vec<String> = serde_json::from_str(&search_body("{\"target\": \"\"}", &history)).unwrap();
+    assert_eq!(all.len(), 8);
+    assert_eq!(all[0], "Aaa:price");
+
+    let volumes: Vec<String> = serde_json::from_str(&search_body("{\"target\": \"bbb:sell\"}", &history)).unwrap();
+    assert_eq!(volumes, vec!["Bbb:sell_volume"]);
+}
+
+#[test]
+fn query_returns_datapoints_within_the_range() {
+    let history = PriceHistory::new(10, 100);
+    history.record("Mint111", at(-60), 0.5, true, 1.0);
+    for i in 0..4 {
+        history.record("Mint111", at(i * 10), 1.0 + i as f64, i % 2 == 0, 1.0);
+    }
+
+    let response: Value = serde_json::from_str(&query_body(&query(&["Mint111:price", "Mint111:volume", "Mint111:bogus"], 0), &history).unwrap()).unwrap();
+    let series = response.as_array().unwrap();
+    assert_eq!(series.len(), 2);
+    assert_eq!(series[0]["target"], "Mint111:price");
+    assert_eq!(series[0]["datapoints"][0], json!([1.0, START * 1000]));
+    assert_eq!(series[0]["datapoints"].as_array().unwrap().len(), 4);
+
+    let merged: Value = serde_json::from_str(&query_body(&query(&["Mint111:volume", "Mint111:price"], 2), &history).unwrap()).unwrap();
+    assert_eq!(merged[0]["datapoints"], json!([[2.0, (START + 10) * 1000], [2.0, (START + 30) * 1000]]));
+    assert_eq!(merged[1]["datapoints"], json!([[2.0, (START + 10) * 1000], [4.0, (START + 30) * 1000]]));
+
+    assert!(query_body("{\"targets\": []}", &history).is_err());
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use std::sync::Arc;
+use common::{config, parsed_trade, MockRpc, RecordingSink};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::group_consensus::{parse_rules, ConsensusRule, GroupConsensus};
+use solana_vntr_sniper::processor::opportunity_signal::{MetricsSnapshot, OpportunityKind, RecommendedAction};
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+use solana_vntr_sniper::processor::transaction_parser::ParsedData;
+use solana_vntr_sniper::processor::wallet_groups::{parse_wallets, WalletGroups, WalletGroupsConfig};
+
+fn buy(wallet: Pubkey, mint: Pubkey, sol: f64) -> ParsedData {
+    parsed_trade(mint)
+        .signer(wallet)
+        .sol(sol)
+        .name("Consensus")
+        .symbol("CNS")
+        .progress(30.0)
+        .build()
+}
+
+/// A group of `n` imported wallets, never persisted; the group-buy alert stays out of the way
+fn imported(group: &str, n: usize) -> (WalletGroups, Vec<Pubkey>) {
+    let groups = WalletGroups::new(WalletGroupsConfig { path: None, window_secs: 300, min_wallets: 100 });
+    let wallets: Vec<Pubkey> = (0..n).map(|_| Pubkey::new_unique()).collect();
+    let csv: String = wallets.iter().map(|w| format!("{},,{}\n", w, group)).collect();
+    groups.import(parse_wallets(&csv).wallets);
+    (groups, wallets)
+}
+
+fn rule(group: &str, min_wallets: usize, window_secs: i64) -> ConsensusRule {
+    ConsensusRule { group: group.to_string(), min_wallets, window_secs, members: Vec::new() }
+}
+
+#[test]
+fn rules_parse_with_defaults_and_are_validated() {
+    let member = Pubkey::new_unique();
+    let rules = parse_rules(&format!(
+        r#"[{{"group": "KOLs", "min_wallets": 3}}, {{"group": "Funds", "min_wallets": 2, "window_secs": 60, "members": ["{}", "{}"]}}]"#,
+        member, Pubkey::new_unique()
+    )).unwrap();
+    assert_eq!(rules[0].window_secs, 300);
+    assert!(rules[0].members.is_empty());
+    assert_eq!(rules[1].members[0], member.to_string());
+
+    assert!(parse_rules(r#"[{"group": "KOLs", "min_wallets": 1}]"#).is_err());
+    assert!(parse_rules(r#"[{"group": "KOLs", "min_wallets": 3, "members": ["nope"]}]"#).is_err());
+    assert!(parse_rules(&format!(r#"[{{"group": "KOLs", "min_wallets": 3, "members": ["{}"]}}]"#, member)).is_err());
+    assert!(parse_rules(r#"[{"group": "KOLs", "min_wallets": 3, "threshold": 2}]"#).is_err());
+}
+
+#[test]
+fn fires_when_m_of_n_members_buy_within_the_window() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let (groups, kols) = imported("KOLs", 5);
+    let consensus = GroupConsensus::new(vec![rule("KOLs", 3, 600)]).with_clock(clock.clone());
+    let mint = Pubkey::new_unique();
+
+    assert!(consensus.observe(&buy(kols[0], mint, 1.0), &groups).is_empty());
+    assert!(consensus.observe(&buy(Pubkey::new_unique(), mint, 5.0), &groups).is_empty());
+    clock.advance(chrono::Duration::seconds(120));
+    assert!(consensus.observe(&buy(kols[1], mint, 2.0), &groups).is_empty());
+    clock.advance(chrono::Duration::seconds(120));
+    let hits = consensus.observe(&buy(kols[2], mint, 1.5), &groups);
+
+    assert_eq!(hits.len(), 1);
+    let hit = &hits[0];
+    assert_eq!((hit.wallets.len(), hit.members, hit.min_wallets), (3, 5, 3));
+    assert_eq!(hit.total_sol, 4.5);
+    assert_eq!(hit.span_secs, 240);
+    assert!(hit.summary().starts_with("3 of 5 wallets in group 'KOLs' bought within 240s"), "{}", hit.summary());
+    // Once per window
+    assert!(consensus.observe(&buy(kols[3], mint, 1.0), &groups).is_empty());
+}
+
+#[test]
+fn buys_spread_wider_than_the_window_do_not_agree() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let (groups, kols) = imported("KOLs", 3);
+    let consensus = GroupConsensus::new(vec![rule("KOLs", 2, 60)]).with_clock(clock.clone());
+    let mint = Pubkey::new_unique();
+
+    consensus.observe(&buy(kols[0], mint, 1.0), &groups);
+    clock.advance(chrono::Duration::seconds(61));
+    assert!(consensus.observe(&buy(kols[1], mint, 1.0), &groups).is_empty());
+    assert_eq!(consensus.observe(&buy(kols[2], mint, 1.0), &groups).len(), 1);
+}
+
+#[test]
+fn explicit_members_override_the_imported_group() {
+    let (groups, kols) = imported("KOLs", 2);
+    let outsider = Pubkey::new_unique();
+    let consensus = GroupConsensus::new(vec![ConsensusRule {
+        group: "Desk".to_string(),
+        min_wallets: 2,
+        window_secs: 300,
+        members: vec![kols[0].to_string(), outsider.to_string()],
+    }]);
+    let mint = Pubkey::new_unique();
+
+    assert!(consensus.observe(&buy(kols[1], mint, 1.0), &groups).is_empty());
+    assert!(consensus.observe(&buy(kols[0], mint, 1.0), &groups).is_empty());
+    let hits = consensus.observe(&buy(outsider, mint, 1.0), &groups);
+
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].members, 2);
+    assert_eq!(hits[0].wallets[0].0, "KOLs #1");
+    assert_eq!(hits[0].wallets[1].0, outsider.to_string());
+}
+
+#[test]
+fn more_wallets_agreeing_means_more_confidence() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let (groups, kols) = imported("KOLs", 6);
+    let consensus = GroupConsensus::new(vec![rule("KOLs", 2, 600), rule("KOLs", 5, 600)]).with_clock(clock);
+    let mint = Pubkey::new_unique();
+
+    let mut hits = Vec::new();
+    for wallet in &kols[..5] {
+        hits.extend(consensus.observe(&buy(*wallet, mint, 1.0), &groups));
+    }
+    let pair = hits[0].to_opportunity(None, MetricsSnapshot::default());
+    let five = hits[1].to_opportunity(None, MetricsSnapshot::default());
+
+    assert_eq!(pair.kind, OpportunityKind::GroupConsensus);
+    assert!(five.confidence > pair.confidence);
+    assert_eq!(five.action, RecommendedAction::ConsiderEntry);
+}
+
+#[tokio::test]
+async fn the_monitor_raises_consensus_as_an_opportunity() {
+    let sink = Arc::new(RecordingSink::default());
+    let telegram = TelegramAlertSystem::with_sink(sink.clone(), true);
+    let (groups, kols) = imported("KOLs", 4);
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), Some(Arc::new(telegram)))
+        .with_wallet_groups(groups)
+        .with_group_consensus(GroupConsensus::new(vec![rule("KOLs", 3, 600)]));
+    let mint = Pubkey::new_unique();
+
+    for wallet in &kols[..3] {
+        monitor.process_for_education(&buy(*wallet, mint, 0.5)).await.unwrap();
+    }
+
+    let texts = sink.texts();
+    let alert = texts.iter().find(|t| t.contains("Group Consensus")).expect("consensus alert");
+    assert!(alert.contains("3 of 4 wallets in group 'KOLs'"), "{}", alert);
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use std::sync::Arc;
+use common::{config, MockRpc};
+use solana_vntr_sniper::library::grpc_api::pb::monitor_service_server::MonitorService;
+use solana_vntr_sniper::library::grpc_api::{pb, MonitorGrpcService};
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use tonic::{Code, Request};
+
+fn service(api_token: Option<&str>) -> MonitorGrpcService {
+    // Threshold changes are audited; keep them out of the working directory
+    std::env::set_var("AUDIT_LOG_PATH", "");
+    let monitor = Arc::new(EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), None));
+    MonitorGrpcService::new(monitor, api_token.map(str::to_string))
+}
+
+fn thresholds() -> pb::Thresholds {
+    pb::Thresholds {
+        price_change_pct: 15.0,
+        buy_sell_ratio: 3.0,
+        whale_sol: 20.0,
+        recovery_drop_pct: 40.0,
+        graduation_pct: 90.0,
+        liquidity_removal_pct: 50.0,
+    }
+}
+
+fn with_token<T>(message: T, token: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert("x-api-token", token.parse().unwrap());
+    request
+}
+
+#[tokio::test]
+async fn control_calls_are_refused_without_a_configured_token() {
+    let service = service(None);
+
+    let thresholds = service.set_thresholds(Request::new(thresholds())).await.unwrap_err();
+    let import = service.import_watchlist(Request::new(pb::ImportWatchlistRequest { content: String::new() })).await.unwrap_err();
+
+    assert_eq!(thresholds.code(), Code::PermissionDenied);
+    assert_eq!(import.code(), Code::PermissionDenied);
+}
+
+#[tokio::test]
+async fn control_calls_need_the_matching_token() {
+    let service = service(Some("secret"));
+
+    assert_eq!(service.set_thresholds(with_token(thresholds(), "wrong")).await.unwrap_err().code(), Code::Unauthenticated);
+    let applied = service.set_thresholds(with_token(thresholds(), "secret")).await.unwrap().into_inner();
+    assert_eq!(applied, thresholds());
+}
+
+#[tokio::test]
+async fn non_finite_thresholds_are_rejected() {
+    let service = service(Some("secret"));
+
+    for bad in [f64::NAN, f64::INFINITY, -1.0] {
+        let request = with_token(pb::Thresholds { whale_sol: bad, ..thresholds() }, "secret");
+        assert_eq!(service.set_thresholds(request).await.unwrap_err().code(), Code::InvalidArgument, "{}", bad);
+    }
+    let request = with_token(pb::Thresholds { price_change_pct: f64::NAN, ..thresholds() }, "secret");
+    assert_eq!(service.set_thresholds(request).await.unwrap_err().code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn omitted_graduation_and_liquidity_thresholds_are_kept() {
+    let service = service(Some("secret"));
+    service.set_thresholds(with_token(thresholds(), "secret")).await.unwrap();
+
+    // A client built before these fields existed sends them as 0
+    let request = with_token(pb::Thresholds { whale_sol: 30.0, graduation_pct: 0.0, liquidity_removal_pct: 0.0, ..thresholds() }, "secret");
+    let applied = service.set_thresholds(request).await.unwrap().into_inner();
+
+    assert_eq!(applied.whale_sol, 30.0);
+    assert_eq!(applied.graduation_pct, 90.0);
+    assert_eq!(applied.liquidity_removal_pct, 50.0);
+}
@@ -0,0 +1,74 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::block_engine::guard::MinOutGuard;
+use solana_vntr_sniper::common::cluster::DexProgram;
+use solana_vntr_sniper::dex::pump_fun::PUMP_BUY_METHOD;
+use solana_vntr_sniper::dex::pump_swap::{BUY_DISCRIMINATOR, SELL_DISCRIMINATOR};
+
+fn accounts(count: usize, set: &[(usize, Pubkey)]) -> Vec<AccountMeta> {
+    let mut accounts: Vec<AccountMeta> = (0..count).map(|_| AccountMeta::new(Pubkey::new_unique(), false)).collect();
+    for (index, key) in set {
+        accounts[*index] = AccountMeta::new(*key, false);
+    }
+    accounts
+}
+
+fn pump_swap_ix(discriminator: [u8; 8], owner: Pubkey, mint: Pubkey, token_account: Pubkey, amount: u64) -> Instruction {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&5_000_000u64.to_le_bytes());
+    Instruction {
+        program_id: DexProgram::PumpSwap.id(),
+        accounts: accounts(21, &[(1, owner), (3, mint), (5, token_account), (11, spl_token::id())]),
+        data,
+    }
+}
+
+#[test]
+fn pump_fun_buys_expect_the_tokens_they_ask_for() {
+    let (owner, mint, token_account) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let buy = Instruction::new_with_bincode(
+        DexProgram::PumpFun.id(),
+        &(PUMP_BUY_METHOD, 350_000u64, 2_000_000u64),
+        accounts(14, &[(2, mint), (5, token_account), (6, owner), (8, spl_token_2022::id())]),
+    );
+    let memo = Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], vec![]);
+    let guard = MinOutGuard::for_swap(&[memo.clone(), buy]).unwrap();
+
+    assert_eq!(guard.min_amount, 350_000);
+    assert_eq!((guard.owner, guard.mint, guard.token_account), (owner, mint, token_account));
+    assert_eq!(MinOutGuard::for_swap(&[memo]), None);
+
+    // transferChecked from the token account to itself, signed by the owner
+    let ix = guard.instruction().unwrap();
+    assert_eq!(ix.program_id, spl_token_2022::id());
+    assert_eq!(ix.accounts[0].pubkey, token_account);
+    assert_eq!(ix.accounts[1].pubkey, mint);
+    assert_eq!(ix.accounts[2].pubkey, token_account);
+    assert!(ix.accounts[3].pubkey == owner && ix.accounts[3].is_signer);
+}
+
+#[test]
+fn pump_swap_buys_are_guarded_and_sells_are_not() {
+    let (owner, mint, token_account) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let guard = MinOutGuard::for_swap(&[pump_swap_ix(BUY_DISCRIMINATOR, owner, mint, token_account, 42)]).unwrap();
+    assert_eq!(guard.min_amount, 42);
+    assert_eq!(guard.decimals, None);
+    assert_eq!(guard.instruction().unwrap().program_id, spl_token::id());
+
+    assert_eq!(MinOutGuard::for_swap(&[pump_swap_ix(SELL_DISCRIMINATOR, owner, mint, token_account, 42)]), None);
+}
+
+#[test]
+fn token_2022_guards_need_decimals() {
+    let guard = MinOutGuard {
+        owner: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        token_account: Pubkey::new_unique(),
+        token_program: spl_token_2022::id(),
+        decimals: None,
+        min_amount: 1,
+    };
+    assert!(guard.instruction().is_err());
+    assert!(MinOutGuard { decimals: Some(9), ..guard }.instruction().is_ok());
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use chrono::Duration;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::library::heartbeat::{Heartbeat, HeartbeatConfig};
+use solana_vntr_sniper::library::slot_lag::SlotLagStatus;
+
+fn stream(updates: u64, seconds_since_update: i64) -> SlotLagStatus {
+    SlotLagStatus {
+        stream_slot: Some(1_000),
+        rpc_slot: Some(1_002),
+        lag_slots: Some(2),
+        seconds_since_update: Some(seconds_since_update),
+        updates,
+        ..SlotLagStatus::default()
+    }
+}
+
+#[test]
+fn a_heartbeat_is_sent_after_hours_without_alerts_and_repeats_while_quiet() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_700_000_000));
+    let heartbeat = Heartbeat::new(HeartbeatConfig::default(), clock.clone());
+    let quiet_after = Duration::hours(3);
+
+    clock.advance(Duration::hours(1));
+    heartbeat.record_alert(100);
+    clock.advance(Duration::hours(2));
+    assert_eq!(heartbeat.check(&stream(400, 5)), None);
+
+    // Three hours after the alert; the stream kept delivering
+    clock.advance(Duration::hours(1));
+    let report = heartbeat.check(&stream(900, 5)).unwrap();
+    assert_eq!(report.quiet_for, Duration::hours(3));
+    assert_eq!(report.stream_updates, 800);
+    assert_eq!(report.alerts_sent, 1);
+    assert!(report.stream_flowing(quiet_after));
+    let text = report.describe(quiet_after);
+    assert!(text.contains("No alerts for 3h 0m"));
+    assert!(text.contains("Last stream update: 5s ago"));
+    assert!(text.contains("Stream lag: 2 slots"));
+    assert!(text.contains("the market is just quiet"));
+
+    // Not again until another full period passed
+    clock.advance(Duration::hours(1));
+    assert_eq!(heartbeat.check(&stream(1_000, 5)), None);
+    clock.advance(Duration::hours(2));
+    let report = heartbeat.check(&stream(1_000, 3 * 3600)).unwrap();
+    assert_eq!(report.quiet_for, Duration::hours(6));
+    assert!(!report.stream_flowing(quiet_after));
+    assert!(report.describe(quiet_after).contains("stalled or behind"));
+}
+
+#[test]
+fn a_stream_without_updates_is_reported_as_possibly_disconnected() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_700_000_000));
+    let heartbeat = Heartbeat::new(HeartbeatConfig { quiet_after: Some(Duration::hours(1)) }, clock.clone());
+    clock.advance(Duration::hours(1));
+    let report = heartbeat.check(&SlotLagStatus::default()).unwrap();
+    assert_eq!(report.last_alert, None);
+    let text = report.describe(Duration::hours(1));
+    assert!(text.contains("Last stream update: never"));
+    assert!(text.contains("may be disconnected"));
+}
+
+#[test]
+fn disabled_heartbeats_never_fire() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_700_000_000));
+    let heartbeat = Heartbeat::new(HeartbeatConfig { quiet_after: None }, clock.clone());
+    clock.advance(Duration::days(1));
+    assert_eq!(heartbeat.check(&stream(0, 0)), None);
+}
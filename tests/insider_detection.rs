@@ -0,0 +1,129 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use common::{config, parsed_trade, MockRpc, RecordingSink};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::insider_detection::{
+    is_insider_launch, FundingLookup, InsiderConfig, InsiderDetector,
+};
+use solana_vntr_sniper::processor::risk_score;
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+use solana_vntr_sniper::processor::transaction_parser::ParsedData;
+
+/// Funding sources from a fixed table
+struct MockFunding(HashMap<Pubkey, Pubkey>);
+
+#[async_trait]
+impl FundingLookup for MockFunding {
+    async fn funding_source(&self, wallet: &Pubkey) -> Result<Option<Pubkey>> {
+        Ok(self.0.get(wallet).copied())
+    }
+}
+
+const LAUNCH: u64 = 1_750_000_000;
+
+fn trade(mint: Pubkey, creator: Pubkey, signer: Pubkey, sol: f64, secs_after_launch: u64) -> ParsedData {
+    parsed_trade(mint)
+        .timestamp(LAUNCH + secs_after_launch)
+        .signer(signer)
+        .sol(sol)
+        .price(0.000001)
+        .liquidity(30.0)
+        .name("Insider")
+        .symbol("INS")
+        .progress(1.0)
+        .creator(creator)
+        .build()
+}
+
+fn detector(funding: &[(Pubkey, Pubkey)]) -> InsiderDetector {
+    InsiderDetector::new(InsiderConfig::default(), Arc::new(MockFunding(funding.iter().copied().collect())))
+}
+
+#[tokio::test]
+async fn buyers_funded_by_the_creator_are_flagged() {
+    let (mint, creator, buyer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let detector = detector(&[(buyer, creator)]);
+
+    assert_eq!(detector.observe(&trade(mint, creator, creator, 1.0, 0)).await, None);
+    let finding = detector.observe(&trade(mint, creator, buyer, 2.0, 3)).await.expect("insider buy");
+    assert_eq!(finding.funder, creator);
+    assert_eq!(finding.seconds_after_launch, 3);
+    assert_eq!(finding.insider_wallets, 1);
+    assert!(is_insider_launch(&mint));
+}
+
+#[tokio::test]
+async fn buyers_sharing_the_creators_funder_are_flagged() {
+    let (mint, creator, funder) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let detector = detector(&[(creator, funder), (first, funder), (second, funder)]);
+
+    detector.observe(&trade(mint, creator, creator, 1.0, 0)).await;
+    detector.observe(&trade(mint, creator, first, 1.0, 2)).await.expect("first insider");
+    let finding = detector.observe(&trade(mint, creator, second, 1.5, 4)).await.expect("second insider");
+    assert_eq!(finding.funder, funder);
+    assert_eq!(finding.insider_wallets, 2);
+    assert!((finding.insider_sol - 2.5).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn late_small_and_unrelated_buys_are_ignored() {
+    let (mint, creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (late, small, stranger) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let detector = detector(&[(late, creator), (small, creator), (stranger, Pubkey::new_unique())]);
+
+    detector.observe(&trade(mint, creator, creator, 1.0, 0)).await;
+    assert_eq!(detector.observe(&trade(mint, creator, late, 2.0, 30)).await, None);
+    assert_eq!(detector.observe(&trade(mint, creator, small, 0.1, 2)).await, None);
+    assert_eq!(detector.observe(&trade(mint, creator, stranger, 2.0, 2)).await, None);
+    assert!(!is_insider_launch(&mint));
+}
+
+#[tokio::test]
+async fn tokens_first_seen_mid_curve_are_not_checked() {
+    let (mint, creator, buyer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let detector = detector(&[(buyer, creator)]);
+
+    let mut first = trade(mint, creator, Pubkey::new_unique(), 1.0, 0);
+    first.bonding_curve_progress = Some(40.0);
+    detector.observe(&first).await;
+    assert_eq!(detector.observe(&trade(mint, creator, buyer, 2.0, 1)).await, None);
+}
+
+#[tokio::test]
+async fn insider_buys_raise_the_risk_score() {
+    let (mint, creator, buyer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let detector = detector(&[(buyer, creator)]);
+    let before = risk_score::risk_score(&mint.to_string());
+
+    detector.observe(&trade(mint, creator, creator, 1.0, 0)).await;
+    detector.observe(&trade(mint, creator, buyer, 2.0, 1)).await;
+    assert_eq!(risk_score::get_factors(&mint.to_string()).insider_wallets, 1);
+    assert!(risk_score::risk_score(&mint.to_string()) > before);
+}
+
+#[tokio::test]
+async fn the_monitor_alerts_on_insider_buys() {
+    let sink = Arc::new(RecordingSink::default());
+    let clock = Arc::new(SimulatedClock::at_unix(LAUNCH as i64));
+    let telegram = TelegramAlertSystem::with_sink(sink.clone(), true).with_clock(clock.clone());
+    let (mint, creator, buyer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), Some(Arc::new(telegram)))
+        .with_clock(clock)
+        .with_insider_detector(detector(&[(buyer, creator)]));
+
+    monitor.process_for_education(&trade(mint, creator, creator, 1.0, 0)).await.unwrap();
+    monitor.process_for_education(&trade(mint, creator, buyer, 2.0, 2)).await.unwrap();
+    let texts = sink.texts();
+    assert!(
+        texts.iter().any(|t| t.contains("LIKELY INSIDER LAUNCH") && t.contains(&buyer.to_string())),
+        "{:?}",
+        texts
+    );
+}
@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Barrier};
+use chrono::Duration;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::library::instance_lock::{
+    hand_over, read_lease, InstanceLock, InstanceLockConfig, LockTransition,
+};
+
+const START: i64 = 1_750_000_000;
+
+fn lock_path() -> PathBuf {
+    std::env::temp_dir().join(format!("instance_lock_{}.json", Pubkey::new_unique()))
+}
+
+fn instance(id: &str, path: &PathBuf, clock: &Arc<SimulatedClock>) -> InstanceLock {
+    let config = InstanceLockConfig { instance_id: id.to_string(), ..InstanceLockConfig::default() };
+    InstanceLock::new(config, path.clone()).with_clock(clock.clone())
+}
+
+#[test]
+fn the_second_instance_runs_monitor_only() {
+    let path = lock_path();
+    let clock = Arc::new(SimulatedClock::at_unix(START));
+    let first = instance("a", &path, &clock);
+    let second = instance("b", &path, &clock);
+
+    assert_eq!(first.check(false).unwrap(), Some(LockTransition::Acquired { previous: None }));
+    assert_eq!(second.check(false).unwrap(), Some(LockTransition::Lost { holder: "a".to_string() }));
+
+    // Renewals keep the roles without reporting anything
+    clock.advance(Duration::seconds(15));
+    assert_eq!(first.check(false).unwrap(), None);
+    assert_eq!(second.check(false).unwrap(), None);
+    assert!(first.is_active() && !second.is_active());
+    assert_eq!(read_lease(&path).unwrap().renewed_at, START + 15);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn an_abandoned_lease_is_taken_over() {
+    let path = lock_path();
+    let clock = Arc::new(SimulatedClock::at_unix(START));
+    let first = instance("a", &path, &clock);
+    let second = instance("b", &path, &clock);
+    first.check(false).unwrap();
+    second.check(false).unwrap();
+
+    // The holder stops renewing
+    clock.advance(Duration::seconds(61));
+    assert_eq!(second.check(false).unwrap(), Some(LockTransition::Acquired { previous: Some("a".to_string()) }));
+    assert_eq!(first.check(false).unwrap(), Some(LockTransition::Lost { holder: "b".to_string() }));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn takeover_moves_trading_to_another_instance() {
+    let path = lock_path();
+    let clock = Arc::new(SimulatedClock::at_unix(START));
+    let first = instance("a", &path, &clock);
+    let second = instance("b", &path, &clock);
+    first.check(false).unwrap();
+    second.check(false).unwrap();
+
+    assert_eq!(second.check(true).unwrap(), Some(LockTransition::Acquired { previous: Some("a".to_string()) }));
+    assert!(matches!(first.check(false).unwrap(), Some(LockTransition::Lost { .. })));
+
+    // Handing the lease back by name
+    hand_over(&path, Some("a"), START).unwrap();
+    assert!(matches!(first.check(false).unwrap(), Some(LockTransition::Acquired { .. })));
+    assert!(matches!(second.check(false).unwrap(), Some(LockTransition::Lost { .. })));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn a_released_lease_goes_to_the_next_standby() {
+    let path = lock_path();
+    let clock = Arc::new(SimulatedClock::at_unix(START));
+    let first = instance("a", &path, &clock);
+    let second = instance("b", &path, &clock);
+    first.check(false).unwrap();
+    second.check(false).unwrap();
+
+    first.release().unwrap();
+    assert!(read_lease(&path).is_none());
+    assert!(matches!(second.check(false).unwrap(), Some(LockTransition::Acquired { previous: None })));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn only_one_of_two_concurrent_takers_gets_a_free_lease() {
+    for _ in 0..50 {
+        let path = lock_path();
+        let clock = Arc::new(SimulatedClock::at_unix(START));
+        let barrier = Arc::new(Barrier::new(2));
+        let takers: Vec<_> = ["a", "b"]
+            .into_iter()
+            .map(|id| {
+                let lock = instance(id, &path, &clock);
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    lock.check(false).unwrap();
+                    lock
+                })
+            })
+            .collect();
+        let locks: Vec<InstanceLock> = takers.into_iter().map(|t| t.join().unwrap()).collect();
+
+        assert_eq!(locks.iter().filter(|l| l.is_active()).count(), 1);
+        let holder = locks.iter().find(|l| l.is_active()).unwrap().instance_id().to_string();
+        assert_eq!(read_lease(&path).unwrap().holder, holder);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(path.with_extension("json.guard"));
+    }
+}
@@ -0,0 +1,65 @@
+use solana_sdk::signature::{Keypair, Signer};
+use solana_vntr_sniper::library::keystore::{self, decrypt, encrypt, generate, register_wallet, validate_prefix};
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("keystore-test-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn keypairs_round_trip_only_with_the_right_passphrase() {
+    let keypair = Keypair::new();
+    let entry = encrypt(&keypair, "correct horse", Some("main".to_string())).unwrap();
+    assert_eq!(entry.pubkey, keypair.pubkey().to_string());
+    assert!(!entry.ciphertext.contains(&bs58::encode(keypair.to_bytes()).into_string()));
+
+    assert_eq!(decrypt(&entry, "correct horse").unwrap().pubkey(), keypair.pubkey());
+    assert!(decrypt(&entry, "wrong horse").is_err());
+}
+
+#[test]
+fn saved_entries_load_back_and_are_not_overwritten() {
+    let dir = temp_dir("save");
+    let keypair = Keypair::new();
+    let entry = encrypt(&keypair, "passphrase", None).unwrap();
+    keystore::save(&dir, &entry).unwrap();
+    assert!(keystore::save(&dir, &entry).is_err());
+
+    let loaded = keystore::load(&dir, &keypair.pubkey().to_string()).unwrap();
+    assert_eq!(decrypt(&loaded, "passphrase").unwrap().pubkey(), keypair.pubkey());
+}
+
+#[test]
+fn vanity_addresses_start_with_the_prefix() {
+    let (keypair, attempts) = generate(Some("A"), false, 1_000_000).unwrap();
+    assert!(keypair.pubkey().to_string().starts_with('A'));
+    assert!(attempts >= 1);
+
+    let (keypair, _) = generate(Some("z"), true, 1_000_000).unwrap();
+    assert!(keypair.pubkey().to_string().to_lowercase().starts_with('z'));
+
+    assert!(validate_prefix("0x").is_err());
+    assert!(generate(Some("Il"), false, 10).is_err());
+    // A prefix too long to find within the attempt budget gives up
+    assert!(generate(Some("AAAAAAAA"), false, 100).is_err());
+}
+
+#[test]
+fn new_wallets_are_registered_in_the_env_file() {
+    let env = temp_dir("register").join(".env");
+    std::fs::write(&env, "PRIVATE_KEY=\nKEYSTORE_WALLETS=\nRPC_HTTP=http://localhost\n").unwrap();
+    let (first, second) = (Keypair::new().pubkey(), Keypair::new().pubkey());
+
+    register_wallet(&env, &first).unwrap();
+    register_wallet(&env, &second).unwrap();
+    register_wallet(&env, &first).unwrap();
+    let content = std::fs::read_to_string(&env).unwrap();
+    assert!(content.contains(&format!("KEYSTORE_WALLETS={},{}\n", first, second)), "{}", content);
+    assert!(content.contains("RPC_HTTP=http://localhost"));
+
+    let fresh = env.with_file_name("fresh.env");
+    register_wallet(&fresh, &first).unwrap();
+    assert_eq!(std::fs::read_to_string(&fresh).unwrap(), format!("KEYSTORE_WALLETS={}\n", first));
+}
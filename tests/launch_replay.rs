@@ -0,0 +1,118 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use common::{config, parsed_trade, MockRpc, RecordingSink};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::launch_replay::{format_timeline, LaunchOutcome, LaunchRecorder, LaunchReplayConfig};
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+use solana_vntr_sniper::processor::transaction_parser::{ParsedData, SwapType};
+
+fn event(mint: Pubkey, signer: Pubkey, swap_type: SwapType, sol: f64, price: f64) -> ParsedData {
+    parsed_trade(mint)
+        .swap_type(swap_type)
+        .signer(signer)
+        .sol(sol)
+        .price(price)
+        .name("Replay")
+        .symbol("RPL")
+        .progress(40.0)
+        .build()
+}
+
+fn start() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_750_000_000, 0).unwrap()
+}
+
+/// Pump to 4x, three sells back down to a tenth of the first price
+fn record_pump_and_dump(recorder: &mut LaunchRecorder, mint: Pubkey) {
+    let at = start();
+    let prices = [1.0, 2.0, 4.0, 3.0, 1.0, 0.1];
+    for (i, price) in prices.iter().enumerate() {
+        let swap_type = if i < 3 { SwapType::Buy } else { SwapType::Sell };
+        let sol = if i < 3 { 1.0 } else { 2.0 * i as f64 };
+        recorder.record(&event(mint, Pubkey::new_unique(), swap_type, sol, *price), at + Duration::minutes(i as i64));
+    }
+}
+
+#[test]
+fn a_collapsed_launch_ends_once_trading_stops() {
+    let mut recorder = LaunchRecorder::new(LaunchReplayConfig::default());
+    let mint = Pubkey::new_unique();
+    record_pump_and_dump(&mut recorder, mint);
+
+    assert!(recorder.take_finished(10.0, start() + Duration::minutes(10)).is_empty());
+    let finished = recorder.take_finished(10.0, start() + Duration::minutes(21));
+    assert_eq!(finished.len(), 1);
+    assert_eq!(finished[0].1, LaunchOutcome::Died);
+    assert_eq!(recorder.session_count(), 0);
+
+    let timeline = format_timeline(&finished[0].0, &finished[0].1);
+    assert!(timeline.contains("DIED"), "{}", timeline);
+    assert!(timeline.contains("First buy"), "{}", timeline);
+    assert!(timeline.contains("Peak: 4.00x"), "{}", timeline);
+    assert_eq!(timeline.matches("Large sell").count(), 3, "{}", timeline);
+    assert!(timeline.contains("-90.0%"), "{}", timeline);
+}
+
+#[test]
+fn a_pumpswap_pool_for_a_pump_fun_launch_is_a_graduation() {
+    let mut recorder = LaunchRecorder::new(LaunchReplayConfig::default());
+    let mint = Pubkey::new_unique();
+    record_pump_and_dump(&mut recorder, mint);
+    let mut migration = event(mint, Pubkey::new_unique(), SwapType::PoolCreate, 80.0, 0.1);
+    migration.dex_name = "PumpSwap".to_string();
+    recorder.record(&migration, start() + Duration::minutes(6));
+
+    let finished = recorder.take_finished(10.0, start() + Duration::minutes(6));
+    assert_eq!(finished.len(), 1);
+    assert_eq!(finished[0].1, LaunchOutcome::Graduated);
+}
+
+#[test]
+fn liquidity_pulls_are_reported_as_rugs() {
+    let mut recorder = LaunchRecorder::new(LaunchReplayConfig::default());
+    let mint = Pubkey::new_unique();
+    for i in 0..5 {
+        recorder.record(&event(mint, Pubkey::new_unique(), SwapType::Buy, 1.0, 1.0), start() + Duration::minutes(i));
+    }
+    let mut pull = event(mint, Pubkey::new_unique(), SwapType::RemoveLiquidity, 9.0, 1.0);
+    pull.liquidity = Some(1.0);
+    recorder.record(&pull, start() + Duration::minutes(5));
+
+    let finished = recorder.take_finished(50.0, start() + Duration::minutes(30));
+    assert_eq!(finished[0].1, LaunchOutcome::LiquidityPulled(90.0));
+    assert!(format_timeline(&finished[0].0, &finished[0].1).contains("Liquidity removed: 9.00 SOL"));
+}
+
+#[test]
+fn small_launches_end_without_a_report() {
+    let mut recorder = LaunchRecorder::new(LaunchReplayConfig { min_trades: 10, ..LaunchReplayConfig::default() });
+    record_pump_and_dump(&mut recorder, Pubkey::new_unique());
+    assert!(recorder.take_finished(10.0, start() + Duration::hours(1)).is_empty());
+    assert_eq!(recorder.session_count(), 0);
+}
+
+#[tokio::test]
+async fn the_monitor_sends_replays_of_finished_launches() {
+    let sink = Arc::new(RecordingSink::default());
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let telegram = TelegramAlertSystem::with_sink(sink.clone(), true).with_clock(clock.clone());
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), Some(Arc::new(telegram)))
+        .with_clock(clock.clone())
+        .with_launch_replay(LaunchReplayConfig::default());
+
+    let mint = Pubkey::new_unique();
+    for (i, price) in [1.0, 3.0, 2.0, 0.5, 0.1].iter().enumerate() {
+        let swap_type = if i < 2 { SwapType::Buy } else { SwapType::Sell };
+        monitor.process_for_education(&event(mint, Pubkey::new_unique(), swap_type, 0.5, *price)).await.unwrap();
+        clock.advance(Duration::minutes(1));
+    }
+    assert_eq!(monitor.report_finished_launches().await, 0);
+
+    clock.advance(Duration::minutes(20));
+    assert_eq!(monitor.report_finished_launches().await, 1);
+    assert!(sink.texts().iter().any(|t| t.contains("LAUNCH REPLAY") && t.contains("DIED")), "{:?}", sink.texts());
+}
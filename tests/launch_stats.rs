@@ -0,0 +1,73 @@
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use common::parsed_trade;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::launch_stats::{format_launch_stats, LaunchStats};
+use solana_vntr_sniper::processor::transaction_parser::ParsedData;
+
+fn trade(mint: Pubkey, price: f64, progress: Option<f64>) -> ParsedData {
+    parsed_trade(mint)
+        .sol(1.0)
+        .price(price)
+        .progress(progress)
+        .build()
+}
+
+/// 15:06:40 UTC
+fn start() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_750_000_000, 0).unwrap()
+}
+
+#[test]
+fn launches_are_counted_from_their_first_sighting_on_the_curve() {
+    let mut stats = LaunchStats::new(80.0);
+    let (survivor, dead, quiet, midlife) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+    // Peaks after 10 minutes and still trades near it after the hour
+    stats.record(&trade(survivor, 1.0, Some(1.0)), start());
+    stats.record(&trade(survivor, 3.0, Some(20.0)), start() + Duration::minutes(10));
+    stats.record(&trade(survivor, 2.0, Some(15.0)), start() + Duration::minutes(70));
+    // Peaks after 4 minutes and trades at a tenth of it after the hour
+    stats.record(&trade(dead, 1.0, Some(2.0)), start() + Duration::hours(1));
+    stats.record(&trade(dead, 2.0, Some(5.0)), start() + Duration::minutes(64));
+    stats.record(&trade(dead, 0.2, Some(1.0)), start() + Duration::minutes(125));
+    // Never trades again
+    stats.record(&trade(quiet, 1.0, Some(0.5)), start() + Duration::hours(1));
+    // First seen far along its curve, or on a pool: not a launch
+    stats.record(&trade(midlife, 1.0, Some(60.0)), start());
+    stats.record(&trade(Pubkey::new_unique(), 1.0, None), start());
+
+    assert_eq!(stats.len(), 3);
+    assert_eq!(stats.get(&survivor).unwrap().survived_first_hour, Some(true));
+    assert_eq!(stats.get(&dead).unwrap().survived_first_hour, Some(false));
+
+    let summary = stats.summary(7, start() + Duration::hours(3));
+    assert_eq!(summary.launches, 3);
+    assert_eq!(summary.busiest_hours(3), vec![(16, 2), (15, 1)]);
+    assert_eq!((summary.survived, summary.decided), (1, 3));
+    // 10m, 4m and 0s to peak
+    assert_eq!(summary.median_time_to_peak, Some(Duration::minutes(4)));
+
+    let text = format_launch_stats(&summary);
+    assert!(text.contains("Launches: 3 (0.0 per hour)"));
+    assert!(text.contains("Busiest launch hours (UTC): 16:00 (2), 15:00 (1)"));
+    assert!(text.contains("Median time to peak: 4m 00s"));
+    assert!(text.contains("Survival after 1h: 33.3% (1 of 3)"));
+}
+
+#[test]
+fn young_launches_wait_for_their_first_hour_and_old_ones_are_pruned() {
+    let mut stats = LaunchStats::new(80.0);
+    let mint = Pubkey::new_unique();
+    stats.record(&trade(mint, 1.0, Some(1.0)), start());
+
+    let summary = stats.summary(7, start() + Duration::minutes(30));
+    assert_eq!((summary.launches, summary.decided), (1, 0));
+    assert_eq!(summary.median_time_to_peak, None);
+    assert!(format_launch_stats(&summary).contains("Survival after 1h: n/a"));
+
+    stats.prune(start() + Duration::days(7));
+    assert!(stats.is_empty());
+    assert!(format_launch_stats(&stats.summary(7, start())).contains("No launches recorded"));
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::lessons::{LessonAction, LessonConfig, LessonSessions, Topic};
+
+#[test]
+fn callback_data_round_trips() {
+    let actions = [
+        LessonAction::Menu(Topic::BondingCurve),
+        LessonAction::Read(Topic::ImpermanentLoss),
+        LessonAction::Next,
+        LessonAction::Quiz(Topic::Mev),
+        LessonAction::Answer { topic: Topic::Mev, question: 1, choice: 2 },
+        LessonAction::Stop,
+    ];
+    for action in actions {
+        let data = action.callback_data();
+        assert!(data.len() <= 64, "Telegram limits callback data to 64 bytes");
+        assert_eq!(LessonAction::parse(&data), Some(action));
+    }
+    assert_eq!(LessonAction::parse("watch:So11111111111111111111111111111111111111112"), None);
+    assert_eq!(LessonAction::parse("learn:menu:nonsense"), None);
+}
+
+#[test]
+fn explanation_thread_is_read_one_part_at_a_time() {
+    let lessons = LessonSessions::new(LessonConfig::default());
+    let parts = Topic::BondingCurve.parts().len();
+
+    let first = lessons.handle(1, LessonAction::Read(Topic::BondingCurve)).unwrap();
+    assert!(first.text.contains(&format!("(1/{})", parts)));
+    assert_eq!(first.buttons[0][0].1, LessonAction::Next);
+
+    let mut last = first;
+    for _ in 1..parts {
+        last = lessons.handle(1, LessonAction::Next).unwrap();
+    }
+    assert!(last.text.contains(&format!("({}/{})", parts, parts)));
+    assert_eq!(last.buttons[0][0].1, LessonAction::Quiz(Topic::BondingCurve));
+
+    // Another user has not started reading
+    let other = lessons.handle(2, LessonAction::Next).unwrap();
+    assert!(other.text.starts_with("No explanation in progress"));
+}
+
+#[test]
+fn quiz_answers_are_scored_per_user_and_only_once() {
+    let lessons = LessonSessions::new(LessonConfig::default());
+    let question = lessons.handle(1, LessonAction::Quiz(Topic::Mev)).unwrap();
+    assert_eq!(question.buttons.len(), Topic::Mev.quiz()[0].options.len());
+
+    let correct = LessonAction::Answer { topic: Topic::Mev, question: 0, choice: Topic::Mev.quiz()[0].answer };
+    // Someone else pressing the first user's buttons changes nothing
+    assert_eq!(lessons.handle(2, correct), None);
+
+    let result = lessons.handle(1, correct).unwrap();
+    assert!(result.text.starts_with("✅ Correct!"));
+    assert!(result.text.contains("Score: 1/1"));
+    assert_eq!(lessons.handle(1, correct), None);
+
+    // The next quiz tap asks the next question
+    let next = lessons.handle(1, LessonAction::Quiz(Topic::Mev)).unwrap();
+    assert!(next.text.contains(Topic::Mev.quiz()[1].prompt));
+    let wrong = lessons.handle(1, LessonAction::Answer { topic: Topic::Mev, question: 1, choice: 0 }).unwrap();
+    assert!(wrong.text.starts_with("❌"));
+    assert!(wrong.text.contains("Score: 1/2"));
+
+    let stopped = lessons.handle(1, LessonAction::Stop).unwrap();
+    assert!(stopped.text.contains("1/2"));
+}
+
+#[test]
+fn idle_users_start_over() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let lessons = LessonSessions::new(LessonConfig { idle_timeout: chrono::Duration::minutes(30), ..Default::default() })
+        .with_clock(clock.clone());
+
+    lessons.handle(1, LessonAction::Read(Topic::ImpermanentLoss)).unwrap();
+    assert_eq!(lessons.active_users(), 1);
+
+    clock.advance(chrono::Duration::minutes(31));
+    let reply = lessons.handle(1, LessonAction::Next).unwrap();
+    assert!(reply.text.starts_with("No explanation in progress"));
+    assert_eq!(lessons.active_users(), 1);
+}
+
+#[test]
+fn topics_parse_from_short_and_long_names() {
+    assert_eq!("curve".parse::<Topic>().unwrap(), Topic::BondingCurve);
+    assert_eq!("Impermanent loss".parse::<Topic>().unwrap(), Topic::ImpermanentLoss);
+    assert_eq!("MEV".parse::<Topic>().unwrap(), Topic::Mev);
+    assert!("yield farming".parse::<Topic>().is_err());
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use common::{config, parsed_trade, MockRpc, RecordingSink};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::liquidity_depth::{
+    depth_curve, price_impact_pct, LiquidityDepth, LiquidityDepthConfig, PoolReserves, ReserveReader, TrackedPool,
+};
+use solana_vntr_sniper::processor::swap::SwapDirection;
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+use solana_vntr_sniper::processor::transaction_parser::{DexType, ParsedData, SwapType};
+use solana_vntr_sniper::processor::watchlist::WatchlistEntry;
+
+fn at(seconds: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(1_750_000_000 + seconds, 0).unwrap()
+}
+
+fn reserves(sol: f64) -> PoolReserves {
+    PoolReserves { sol, tokens: sol * 1_000_000.0 }
+}
+
+/// Serves whatever pool SOL the test set last
+struct FixedReader {
+    sol: Mutex<f64>,
+}
+
+#[async_trait]
+impl ReserveReader for FixedReader {
+    async fn read(&self, _pool: &TrackedPool) -> Result<Option<PoolReserves>> {
+        Ok(Some(reserves(*self.sol.lock().unwrap())))
+    }
+}
+
+fn trade(mint: Pubkey, swap_type: SwapType, sol: f64, liquidity: f64) -> ParsedData {
+    parsed_trade(mint)
+        .swap_type(swap_type)
+        .sol(sol)
+        .liquidity(liquidity)
+        .name("Test")
+        .symbol("TST")
+        .build()
+}
+
+#[test]
+fn impact_follows_the_constant_product() {
+    assert!((price_impact_pct(100.0, 10.0, SwapDirection::Buy) - 100.0 / 11.0).abs() < 1e-9);
+    assert!((price_impact_pct(100.0, 10.0, SwapDirection::Sell) - 10.0).abs() < 1e-9);
+    assert_eq!(price_impact_pct(0.0, 1.0, SwapDirection::Buy), 100.0);
+}
+
+#[test]
+fn depth_curve_inverts_the_impact() {
+    for level in depth_curve(80.0, &[1.0, 5.0, 10.0]) {
+        assert!((price_impact_pct(80.0, level.buy_sol, SwapDirection::Buy) - level.impact_pct).abs() < 1e-9);
+        assert!((price_impact_pct(80.0, level.sell_sol, SwapDirection::Sell) - level.impact_pct).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn drop_from_the_window_peak_is_reported_once() {
+    let config = LiquidityDepthConfig { drop_alert_pct: 30.0, drop_window: Duration::seconds(300), ..Default::default() };
+    let mut depth = LiquidityDepth::new(config);
+    let mint = Pubkey::new_unique();
+
+    assert_eq!(depth.record(mint, reserves(100.0), at(0)), None);
+    assert_eq!(depth.record(mint, reserves(90.0), at(30)), None);
+    let drop = depth.record(mint, reserves(60.0), at(60)).unwrap();
+    assert_eq!((drop.from_sol, drop.to_sol, drop.since), (100.0, 60.0, at(0)));
+    assert!((drop.drop_pct - 40.0).abs() < 1e-9);
+
+    assert_eq!(depth.record(mint, reserves(59.0), at(90)), None);
+    assert_eq!(depth.latest(&mint), Some(reserves(59.0)));
+}
+
+#[test]
+fn slow_drain_outside_the_window_is_not_a_drop() {
+    let config = LiquidityDepthConfig { drop_alert_pct: 30.0, drop_window: Duration::seconds(300), ..Default::default() };
+    let mut depth = LiquidityDepth::new(config);
+    let mint = Pubkey::new_unique();
+
+    for (step, sol) in [100.0, 85.0, 72.0, 61.0, 52.0].into_iter().enumerate() {
+        assert_eq!(depth.record(mint, reserves(sol), at(step as i64 * 301)), None);
+    }
+}
+
+#[tokio::test]
+async fn polled_liquidity_drop_is_alerted_with_the_depth_curve() {
+    let sink = Arc::new(RecordingSink::default());
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let telegram = TelegramAlertSystem::with_sink(sink.clone(), true).with_clock(clock.clone());
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), Some(Arc::new(telegram)))
+        .with_clock(clock.clone())
+        .with_liquidity_depth(LiquidityDepthConfig { price_impact_alert_pct: 100.0, ..Default::default() });
+    let mint = Pubkey::new_unique();
+    monitor.process_for_education(&trade(mint, SwapType::Buy, 0.1, 50.0)).await.unwrap();
+
+    let reader = FixedReader { sol: Mutex::new(50.0) };
+    monitor.poll_liquidity(&reader).await;
+    clock.advance(Duration::seconds(30));
+    *reader.sol.lock().unwrap() = 20.0;
+    monitor.poll_liquidity(&reader).await;
+
+    let texts = sink.texts();
+    let alert = texts.iter().find(|t| t.contains("LIQUIDITY DROP")).expect("no liquidity drop alert");
+    assert!(alert.contains("50.00 → 20.00 SOL"), "{}", alert);
+    assert!(alert.contains("5% impact"), "{}", alert);
+    assert_eq!(monitor.get_token(&mint).await.unwrap().liquidity, 20.0);
+}
+
+#[tokio::test]
+async fn large_order_against_a_thin_pool_alerts_price_impact() {
+    let sink = Arc::new(RecordingSink::default());
+    let telegram = TelegramAlertSystem::with_sink(sink.clone(), true);
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), Some(Arc::new(telegram)))
+        .with_liquidity_depth(LiquidityDepthConfig { price_impact_alert_pct: 5.0, ..Default::default() });
+
+    monitor.process_for_education(&trade(Pubkey::new_unique(), SwapType::Buy, 0.1, 50.0)).await.unwrap();
+    assert!(!sink.texts().iter().any(|t| t.contains("HIGH PRICE IMPACT")));
+
+    monitor.process_for_education(&trade(Pubkey::new_unique(), SwapType::Sell, 5.0, 50.0)).await.unwrap();
+    let texts = sink.texts();
+    let alert = texts.iter().find(|t| t.contains("HIGH PRICE IMPACT")).expect("no price impact alert");
+    assert!(alert.contains("10.00%") && alert.contains("SELL"), "{}", alert);
+}
+
+#[test]
+fn pinned_pools_are_polled_ahead_of_traded_ones() {
+    let mut depth = LiquidityDepth::new(LiquidityDepthConfig { max_pools: 2, ..Default::default() });
+    let pinned = Pubkey::new_unique();
+    depth.pin_pool(pinned, at(0));
+    for seconds in 1..=3 {
+        depth.observe_pool(Pubkey::new_unique(), DexType::PumpSwap, Some(Pubkey::new_unique()), at(seconds));
+    }
+
+    let pools = depth.pools_to_poll();
+    assert_eq!(pools.len(), 2);
+    assert_eq!(pools[0].mint, pinned);
+    assert_eq!(pools[0].dex, DexType::PumpFun);
+    assert_eq!(pools[1].last_trade, at(3));
+
+    // A trade reveals the real pool without unpinning it
+    let pool_id = Pubkey::new_unique();
+    depth.observe_pool(pinned, DexType::PumpSwap, Some(pool_id), at(4));
+    let pool = depth.pools_to_poll().into_iter().find(|p| p.mint == pinned).unwrap();
+    assert!(pool.pinned);
+    assert_eq!((pool.dex, pool.pool_id), (DexType::PumpSwap, Some(pool_id)));
+}
+
+#[tokio::test]
+async fn imported_watchlist_tokens_get_liquidity_without_trades() {
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), None);
+    let mint = Pubkey::new_unique();
+    let entry = WatchlistEntry { mint: mint.to_string(), name: Some("Pinned".to_string()), symbol: None };
+    assert_eq!(monitor.import_watchlist(vec![(mint, entry)]).await, 1);
+
+    monitor.poll_liquidity(&FixedReader { sol: Mutex::new(42.0) }).await;
+
+    let metrics = monitor.get_token(&mint).await.unwrap();
+    assert!(metrics.pinned);
+    assert_eq!(metrics.liquidity, 42.0);
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use common::MockRpc;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::{AddressLookupTableAccount, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_vntr_sniper::block_engine::bundle::BundleBuilder;
+use solana_vntr_sniper::block_engine::lookup_table::{
+    compile_message, core_accounts, plan_extension, AccountUsage, LookupTableManager, EXTEND_CHUNK, MAX_ADDRESSES,
+};
+use solana_vntr_sniper::block_engine::tx::new_signed_and_send_by_urgency;
+use solana_vntr_sniper::common::cluster::DexProgram;
+use solana_vntr_sniper::common::logger::Logger;
+use solana_vntr_sniper::library::priority_lane::Urgency;
+
+fn swap(owner: &Pubkey, accounts: &[Pubkey]) -> Instruction {
+    let mut metas: Vec<AccountMeta> = accounts.iter().map(|a| AccountMeta::new(*a, false)).collect();
+    metas.push(AccountMeta::new(*owner, true));
+    Instruction::new_with_bytes(DexProgram::PumpFun.id(), &[1, 2, 3], metas)
+}
+
+fn keys(n: usize) -> Vec<Pubkey> {
+    (0..n).map(|_| Pubkey::new_unique()).collect()
+}
+
+#[test]
+fn core_accounts_fit_in_one_table() {
+    let accounts = core_accounts();
+    assert!(accounts.len() <= MAX_ADDRESSES);
+    assert_eq!(accounts.iter().collect::<HashSet<_>>().len(), accounts.len());
+    assert!(accounts.contains(&DexProgram::PumpSwap.id()));
+}
+
+#[test]
+fn extensions_add_only_missing_addresses_until_the_table_is_full() {
+    let table = keys(10);
+    let mut wanted = table[..5].to_vec();
+    wanted.extend(keys(45));
+    wanted.push(wanted[6]);
+
+    let chunks = plan_extension(&table, &wanted);
+    let added: Vec<Pubkey> = chunks.concat();
+    assert_eq!(added.len(), 45);
+    assert!(chunks.iter().all(|chunk| chunk.len() <= EXTEND_CHUNK));
+    assert!(added.iter().all(|a| !table.contains(a)));
+
+    let full = keys(MAX_ADDRESSES - 3);
+    assert_eq!(plan_extension(&full, &keys(10)).concat().len(), 3);
+    assert!(plan_extension(&table, &table).is_empty());
+}
+
+#[test]
+fn frequent_accounts_exclude_signers() {
+    let owner = Pubkey::new_unique();
+    let (vault, rare) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let usage = AccountUsage::default();
+    for _ in 0..3 {
+        usage.record(&[swap(&owner, &[vault, vault])]);
+    }
+    usage.record(&[swap(&owner, &[rare])]);
+
+    let frequent = usage.frequent(3);
+    assert!(frequent.contains(&vault));
+    assert!(frequent.contains(&DexProgram::PumpFun.id()));
+    assert!(!frequent.contains(&rare));
+    assert!(!frequent.contains(&owner));
+}
+
+#[test]
+fn messages_through_a_table_are_smaller() {
+    let payer = Pubkey::new_unique();
+    let accounts = keys(12);
+    let instructions = [swap(&payer, &accounts)];
+    let table = AddressLookupTableAccount { key: Pubkey::new_unique(), addresses: accounts.clone() };
+
+    let legacy = compile_message(&payer, &instructions, Hash::new_unique(), None).unwrap();
+    let v0 = compile_message(&payer, &instructions, Hash::new_unique(), Some(&table)).unwrap();
+    assert!(matches!(legacy, VersionedMessage::Legacy(_)));
+    assert!(matches!(v0, VersionedMessage::V0(_)));
+    assert!(v0.static_account_keys().len() < legacy.static_account_keys().len());
+    assert!(bincode::serialize(&v0).unwrap().len() + 12 * 20 < bincode::serialize(&legacy).unwrap().len());
+}
+
+#[test]
+fn bundles_reference_the_table() {
+    let payer = Keypair::new();
+    let accounts = keys(8);
+    let table = AddressLookupTableAccount { key: Pubkey::new_unique(), addresses: accounts.clone() };
+    let bundle = BundleBuilder::new(&payer, Hash::new_unique())
+        .buy(vec![swap(&payer.pubkey(), &accounts)])
+        .tip(Pubkey::new_unique(), 10_000)
+        .lookup_table(Some(table.clone()))
+        .build()
+        .unwrap();
+
+    let transaction = &bundle.transactions[0];
+    match &transaction.message {
+        VersionedMessage::V0(message) => assert_eq!(message.address_table_lookups[0].account_key, table.key),
+        other => panic!("expected a v0 message, got {:?}", other),
+    }
+    assert!(transaction.verify_with_results().iter().all(|ok| *ok));
+}
+
+#[tokio::test]
+async fn oversized_sends_go_out_as_one_v0_transaction_before_splitting() {
+    std::env::set_var("LOOKUP_TABLE_ENABLED", "true");
+    std::env::set_var("LOOKUP_TABLE_PATH", "");
+    std::env::set_var("ZERO_SLOT_TIP_VALUE", "0.001");
+    let owner = Keypair::new();
+    let rpc = Arc::new(MockRpc::confirming());
+    let logger = Logger::new("[TEST] => ".to_string());
+
+    // Eight swaps of six accounts each do not fit a legacy transaction
+    let instructions: Vec<Instruction> = (0..8).map(|_| swap(&owner.pubkey(), &keys(6))).collect();
+    let addresses = instructions.iter()
+        .flat_map(|ix| ix.accounts.iter().map(|meta| meta.pubkey))
+        .filter(|key| *key != owner.pubkey())
+        .collect();
+    let table = AddressLookupTableAccount { key: Pubkey::new_unique(), addresses };
+    LookupTableManager::shared().expect("lookup table enabled").set_current(table.clone());
+
+    let signatures = new_signed_and_send_by_urgency(Urgency::Normal, rpc.clone(), Hash::new_unique(), &owner, instructions, &logger)
+        .await
+        .unwrap();
+    assert_eq!(signatures.len(), 1);
+    assert!(rpc.sent.lock().unwrap().is_empty());
+    let sent = rpc.sent_versioned.lock().unwrap()[0].clone();
+    match sent.message {
+        VersionedMessage::V0(message) => assert_eq!(message.address_table_lookups[0].account_key, table.key),
+        VersionedMessage::Legacy(_) => panic!("expected a v0 transaction"),
+    }
+
+    // What fits a legacy transaction is still sent as one
+    new_signed_and_send_by_urgency(Urgency::Normal, rpc.clone(), Hash::new_unique(), &owner, vec![swap(&owner.pubkey(), &keys(2))], &logger)
+        .await
+        .unwrap();
+    assert_eq!(rpc.sent.lock().unwrap().len(), 1);
+    assert_eq!(rpc.sent_versioned.lock().unwrap().len(), 1);
+}
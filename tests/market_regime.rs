@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use chrono::Duration;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::launch_replay::LaunchOutcome;
+use solana_vntr_sniper::processor::market_regime::{MarketRegime, Regime, RegimeConfig};
+
+fn regime(clock: Arc<SimulatedClock>) -> MarketRegime {
+    MarketRegime::new(RegimeConfig { enabled: true, min_launches: 10, ..RegimeConfig::default() })
+        .with_clock(clock)
+}
+
+#[test]
+fn sol_trend_over_the_window_sets_the_regime() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let market = regime(clock.clone());
+    market.record_sol_price(150.0);
+    assert_eq!(market.snapshot().sol_trend_pct, None);
+    assert_eq!(market.regime(), Regime::Neutral);
+
+    clock.advance(Duration::minutes(30));
+    market.record_sol_price(144.0);
+    assert_eq!(market.regime(), Regime::Cold);
+    assert_eq!(market.risk_multiplier(), 0.5);
+
+    // The falling sample leaves the window, the recovery from there is hot
+    clock.advance(Duration::minutes(31));
+    market.record_sol_price(150.0);
+    let snapshot = market.snapshot();
+    assert!((snapshot.sol_trend_pct.unwrap() - 4.1667).abs() < 0.001);
+    assert_eq!(snapshot.regime, Regime::Hot);
+    assert_eq!(market.risk_multiplier(), 1.0);
+}
+
+#[test]
+fn launch_success_rate_counts_once_enough_launches_finished() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let market = regime(clock.clone());
+    for _ in 0..9 {
+        market.record_launch(&LaunchOutcome::Died);
+    }
+    assert_eq!(market.snapshot().launch_success_pct, None);
+    assert_eq!(market.regime(), Regime::Neutral);
+
+    market.record_launch(&LaunchOutcome::LiquidityPulled(90.0));
+    assert_eq!(market.snapshot().launch_success_pct, Some(0.0));
+    assert_eq!(market.regime(), Regime::Cold);
+
+    for _ in 0..2 {
+        market.record_launch(&LaunchOutcome::Graduated);
+    }
+    let snapshot = market.snapshot();
+    assert!((snapshot.launch_success_pct.unwrap() - 16.667).abs() < 0.001);
+    assert_eq!(snapshot.launches, 12);
+    assert_eq!(snapshot.regime, Regime::Hot);
+
+    // A cold SOL trend outweighs hot launches
+    market.record_sol_price(150.0);
+    clock.advance(Duration::minutes(10));
+    market.record_sol_price(140.0);
+    assert_eq!(market.regime(), Regime::Cold);
+}
+
+#[test]
+fn disabled_regime_stays_neutral() {
+    let market = MarketRegime::new(RegimeConfig::default())
+        .with_clock(Arc::new(SimulatedClock::at_unix(1_750_000_000)));
+    market.record_sol_price(150.0);
+    market.record_sol_price(100.0);
+    assert_eq!(market.regime(), Regime::Neutral);
+    assert_eq!(market.risk_multiplier(), 1.0);
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::Duration;
+use common::{config, parsed_trade, MockRpc};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::library::memory_budget::{format_memory_section, MemoryBudget, MemoryComponent};
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::transaction_parser::ParsedData;
+use solana_vntr_sniper::processor::watchlist::WatchlistEntry;
+
+fn trade(mint: Pubkey) -> ParsedData {
+    parsed_trade(mint)
+        .name("Test")
+        .symbol("TST")
+        .build()
+}
+
+async fn monitor_with(budget: MemoryBudget) -> (EducationalMonitor, Pubkey, Vec<Pubkey>) {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), None)
+        .with_clock(clock.clone())
+        .with_memory_budget(budget);
+
+    let pinned = Pubkey::new_unique();
+    monitor.import_watchlist(vec![(pinned, WatchlistEntry { mint: pinned.to_string(), name: None, symbol: None })]).await;
+
+    let mut traded = Vec::new();
+    for _ in 0..6 {
+        let mint = Pubkey::new_unique();
+        monitor.process_for_education(&trade(mint)).await.unwrap();
+        traded.push(mint);
+        clock.advance(Duration::seconds(60));
+    }
+    (monitor, pinned, traded)
+}
+
+#[tokio::test]
+async fn within_budget_nothing_is_evicted() {
+    let (monitor, _, traded) = monitor_with(MemoryBudget::default()).await;
+
+    assert_eq!(monitor.enforce_memory_budget().await, 0);
+    assert_eq!(monitor.list_tokens().await.len(), traded.len() + 1);
+}
+
+#[tokio::test]
+async fn over_budget_evicts_the_least_recently_traded_tokens_first() {
+    let (monitor, pinned, traded) = monitor_with(MemoryBudget { budget_bytes: 1, evict_target_pct: 0.0 }).await;
+
+    let evicted = monitor.enforce_memory_budget().await;
+    assert!(evicted > 0);
+    assert!(monitor.get_token(&pinned).await.is_some(), "watchlist token was evicted");
+    for (i, mint) in traded.iter().enumerate() {
+        assert_eq!(monitor.get_token(mint).await.is_none(), i < evicted, "token {} evicted out of order", i);
+    }
+
+    let report = monitor.generate_educational_report().await.unwrap();
+    assert!(report.contains("🧠 Memory:"), "{}", report);
+    assert!(report.contains(&format!("Evicted since last report: {} tokens", evicted)), "{}", report);
+}
+
+#[test]
+fn memory_section_lists_the_largest_components() {
+    let components = vec![
+        MemoryComponent::new("tracked tokens", 1_000, 512),
+        MemoryComponent::new("mint cache", 10, 400),
+        MemoryComponent::new("empty", 0, 400),
+    ];
+    let budget = MemoryBudget { budget_bytes: 1024 * 1024, evict_target_pct: 70.0 };
+    let section = format_memory_section(&components, &budget, 0);
+
+    assert!(section.contains("of 1.0 MB budget"), "{}", section);
+    assert!(section.find("tracked tokens").unwrap() < section.find("mint cache").unwrap());
+    assert!(!section.contains("empty") && !section.contains("Evicted"));
+    assert!(budget.is_exceeded(2 * 1024 * 1024) && !MemoryBudget { budget_bytes: 0, ..budget }.is_exceeded(usize::MAX));
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+use chrono::Duration;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::mute_list::{parse_duration, remember_creator, MuteList};
+
+#[test]
+fn durations_accept_units_and_are_capped() {
+    assert_eq!(parse_duration("90s"), Some(Duration::seconds(90)));
+    assert_eq!(parse_duration("30"), Some(Duration::minutes(30)));
+    assert_eq!(parse_duration("6H"), Some(Duration::hours(6)));
+    assert_eq!(parse_duration("2d"), Some(Duration::days(2)));
+    assert_eq!(parse_duration("365d"), Some(Duration::days(30)));
+    assert_eq!(parse_duration("0m"), None);
+    assert_eq!(parse_duration("6w"), None);
+    assert_eq!(parse_duration("soon"), None);
+}
+
+#[test]
+fn a_mute_expires() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let mutes = MuteList::new(None).with_clock(clock.clone());
+    let mint = Pubkey::new_unique().to_string();
+
+    assert_eq!(mutes.mute(&mint, Duration::hours(1)), 1_750_003_600);
+    // A shorter mute does not cut the running one short
+    assert_eq!(mutes.mute(&mint, Duration::minutes(5)), 1_750_003_600);
+    assert!(mutes.is_token_muted(&mint));
+
+    clock.advance(Duration::minutes(61));
+    assert!(!mutes.is_token_muted(&mint));
+    assert!(mutes.active().is_empty());
+}
+
+#[test]
+fn muting_a_creator_covers_its_tokens() {
+    let mutes = MuteList::new(None);
+    let creator = Pubkey::new_unique().to_string();
+    let mint = Pubkey::new_unique().to_string();
+    remember_creator(&mint, &creator);
+
+    mutes.mute(&creator, Duration::hours(6));
+    assert!(mutes.is_token_muted(&mint));
+    assert!(!mutes.is_token_muted(&Pubkey::new_unique().to_string()));
+    assert!(mutes.unmute(&creator));
+    assert!(!mutes.is_token_muted(&mint));
+}
+
+#[test]
+fn mutes_survive_a_restart() {
+    let path = std::env::temp_dir().join(format!("mute_list_{}.json", Pubkey::new_unique()));
+    let mint = Pubkey::new_unique().to_string();
+    MuteList::new(Some(path.clone())).mute(&mint, Duration::days(1));
+
+    let restarted = MuteList::new(Some(path.clone()));
+    assert!(restarted.is_muted(&mint));
+    assert_eq!(restarted.active().len(), 1);
+    let _ = std::fs::remove_file(path);
+}
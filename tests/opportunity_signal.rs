@@ -0,0 +1,70 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::{DateTime, Duration};
+use common::RecordingSink;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::opportunity_signal::{MetricsSnapshot, OpportunityKind, OpportunitySignal, RecommendedAction};
+use solana_vntr_sniper::processor::signal_outcomes::{aggregate_accuracy, SignalDirection, SignalOutcome, CONFIDENT_SIGNALS};
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+
+fn signal(kind: OpportunityKind, direction: SignalDirection) -> OpportunitySignal {
+    OpportunitySignal::new(kind, Pubkey::new_unique(), Some("Scored".to_string()), direction, "summary".to_string(), MetricsSnapshot::default())
+}
+
+#[test]
+fn confidence_grows_with_how_far_evidence_clears_its_threshold() {
+    let at_threshold = signal(OpportunityKind::LargeTransaction, SignalDirection::Bullish)
+        .with_evidence("trade size (SOL)", 10.0, Some(10.0));
+    assert!((at_threshold.confidence - 0.3).abs() < 1e-9);
+    assert_eq!(at_threshold.action, RecommendedAction::Watch);
+
+    let doubled = signal(OpportunityKind::LargeTransaction, SignalDirection::Bullish)
+        .with_evidence("trade size (SOL)", 20.0, Some(10.0));
+    assert!((doubled.confidence - 0.65).abs() < 1e-9);
+
+    let strong = signal(OpportunityKind::LargeTransaction, SignalDirection::Bullish)
+        .with_evidence("trade size (SOL)", 50.0, Some(10.0))
+        .with_evidence("context", 3.0, None);
+    assert!(strong.confidence > 0.8);
+    assert_eq!(strong.action, RecommendedAction::ConsiderEntry);
+    assert_eq!(strong.evidence.len(), 2);
+
+    let bearish = signal(OpportunityKind::UnusualTradeSize, SignalDirection::Bearish)
+        .with_evidence("z-score", 9.0, Some(3.0));
+    assert_eq!(bearish.action, RecommendedAction::Avoid);
+}
+
+#[tokio::test]
+async fn alerts_show_confidence_action_and_evidence() {
+    let sink = Arc::new(RecordingSink::default());
+    let alerts = TelegramAlertSystem::with_sink(sink.clone(), true);
+    let signal = signal(OpportunityKind::HighBuyPressure, SignalDirection::Bullish)
+        .with_evidence("buy/sell ratio", 6.0, Some(3.0));
+
+    alerts.alert_sniper_opportunity(&signal).await.unwrap();
+
+    let texts = sink.texts();
+    assert!(texts[0].contains("High Buy Pressure"), "{}", texts[0]);
+    assert!(texts[0].contains("65%"), "{}", texts[0]);
+    assert!(texts[0].contains("Watch"), "{}", texts[0]);
+    assert!(texts[0].contains("buy/sell ratio: 6.00 (threshold 3.00)"), "{}", texts[0]);
+}
+
+#[test]
+fn confident_signals_are_scored_as_their_own_group() {
+    let at = DateTime::from_timestamp(1_750_000_000, 0).unwrap();
+    let mint = Pubkey::new_unique();
+    let measured = |confidence: Option<f64>, price: f64| {
+        let mut outcome = SignalOutcome::new("Large Transaction", mint, SignalDirection::Bullish, 1.0, at)
+            .with_confidence(confidence);
+        outcome.checkpoints = vec![(5, Some(price))];
+        outcome
+    };
+    let outcomes = vec![measured(Some(0.9), 1.2), measured(Some(0.4), 0.9), measured(None, 1.1)];
+
+    let stats = aggregate_accuracy(&outcomes, at - Duration::hours(1));
+    assert_eq!(stats["Large Transaction"][&5].measured, 3);
+    let confident = &stats[CONFIDENT_SIGNALS][&5];
+    assert_eq!((confident.measured, confident.profitable), (1, 1));
+}
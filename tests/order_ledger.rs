@@ -0,0 +1,129 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::Duration;
+use common::MockRpc;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::library::rpc_api::SignatureStatus;
+use solana_vntr_sniper::processor::order_ledger::{DuplicateOrder, OrderLedger, OrderState};
+use solana_vntr_sniper::processor::trade_ledger::TradeSide;
+
+const NOW: i64 = 1_750_000_000;
+const EXPIRY_SECS: i64 = 90;
+
+fn ledger(clock: &Arc<SimulatedClock>) -> OrderLedger {
+    OrderLedger::in_memory(EXPIRY_SECS).with_clock(clock.clone())
+}
+
+#[tokio::test]
+async fn retry_waits_for_an_open_order_to_expire() {
+    let clock = Arc::new(SimulatedClock::at_unix(NOW));
+    let orders = ledger(&clock);
+    let rpc = MockRpc::with_status(SignatureStatus::Pending);
+    let (mint, wallet) = (Pubkey::new_unique().to_string(), Pubkey::new_unique());
+
+    let first = orders.begin(&mint, TradeSide::Buy, None, &rpc, &wallet).await.unwrap();
+    orders.submitted(&first.id, &[Signature::new_unique().to_string()]);
+
+    let retry = orders.begin(&mint, TradeSide::Buy, None, &rpc, &wallet).await;
+    assert_eq!(retry, Err(DuplicateOrder::Open { id: first.id.clone() }));
+
+    // Past expiry nothing sent can land; without tokens in the wallet it never did
+    clock.advance(Duration::seconds(EXPIRY_SECS + 1));
+    let retry = orders.begin(&mint, TradeSide::Buy, None, &rpc, &wallet).await.unwrap();
+    assert_ne!(retry.id, first.id);
+    assert_eq!(orders.get(&first.id).unwrap().state, OrderState::Expired);
+}
+
+#[tokio::test]
+async fn confirmed_order_blocks_a_rebuy() {
+    let clock = Arc::new(SimulatedClock::at_unix(NOW));
+    let orders = ledger(&clock);
+    let rpc = MockRpc::confirming();
+    let (mint, wallet) = (Pubkey::new_unique().to_string(), Pubkey::new_unique());
+
+    let first = orders.begin(&mint, TradeSide::Buy, None, &rpc, &wallet).await.unwrap();
+    let signature = Signature::new_unique().to_string();
+    orders.submitted(&first.id, &[signature.clone()]);
+
+    // The confirmation was never seen by the bot, the chain has it
+    let retry = orders.begin(&mint, TradeSide::Buy, None, &rpc, &wallet).await;
+    assert_eq!(retry, Err(DuplicateOrder::AlreadyFilled { id: first.id.clone() }));
+    let filled = orders.get(&first.id).unwrap();
+    assert_eq!(filled.state, OrderState::Filled);
+    assert_eq!(filled.reason, Some(signature));
+}
+
+#[tokio::test]
+async fn dip_reentry_passes_a_filled_order_but_not_an_open_one() {
+    let clock = Arc::new(SimulatedClock::at_unix(NOW));
+    let orders = ledger(&clock);
+    let rpc = MockRpc::with_status(SignatureStatus::Pending);
+    let (mint, wallet) = (Pubkey::new_unique().to_string(), Pubkey::new_unique());
+
+    let first = orders.begin(&mint, TradeSide::Buy, None, &rpc, &wallet).await.unwrap();
+    let signature = Signature::new_unique().to_string();
+    orders.submitted(&first.id, &[signature.clone()]);
+    orders.signature_landed(&signature);
+
+    let reentry = orders.begin(&mint, TradeSide::Buy, Some(1), &rpc, &wallet).await.unwrap();
+    assert_eq!(reentry.reentry, Some(1));
+    let signature = Signature::new_unique().to_string();
+    orders.submitted(&reentry.id, &[signature.clone()]);
+
+    // A re-entry still waits for the open order of the mint
+    let retry = orders.begin(&mint, TradeSide::Buy, Some(2), &rpc, &wallet).await;
+    assert_eq!(retry, Err(DuplicateOrder::Open { id: reentry.id.clone() }));
+
+    // Once filled, the same re-entry is not bought twice; the next one is
+    orders.signature_landed(&signature);
+    let retry = orders.begin(&mint, TradeSide::Buy, Some(1), &rpc, &wallet).await;
+    assert_eq!(retry, Err(DuplicateOrder::AlreadyFilled { id: reentry.id.clone() }));
+    assert!(orders.begin(&mint, TradeSide::Buy, Some(2), &rpc, &wallet).await.is_ok());
+}
+
+#[tokio::test]
+async fn failed_order_allows_a_retry() {
+    let clock = Arc::new(SimulatedClock::at_unix(NOW));
+    let orders = ledger(&clock);
+    let rpc = MockRpc::with_status(SignatureStatus::Pending);
+    let (mint, wallet) = (Pubkey::new_unique().to_string(), Pubkey::new_unique());
+
+    let first = orders.begin(&mint, TradeSide::Buy, None, &rpc, &wallet).await.unwrap();
+    let signature = Signature::new_unique().to_string();
+    orders.submitted(&first.id, &[signature.clone()]);
+    orders.signature_failed(&signature, "InstructionError(2, Custom(6002))");
+
+    assert_eq!(orders.get(&first.id).unwrap().state, OrderState::Failed);
+    assert!(orders.begin(&mint, TradeSide::Buy, None, &rpc, &wallet).await.is_ok());
+}
+
+#[tokio::test]
+async fn order_left_open_by_a_crash_is_settled_from_the_balance() {
+    let path = std::env::temp_dir().join(format!("order_ledger_{}.jsonl", Pubkey::new_unique()));
+    let clock = Arc::new(SimulatedClock::at_unix(NOW));
+    let (mint, wallet) = (Pubkey::new_unique().to_string(), Pubkey::new_unique());
+    let rpc = MockRpc { token_balance: Some(1_000.0), ..MockRpc::with_status(SignatureStatus::Pending) };
+
+    let id = {
+        let orders = OrderLedger::new(Some(path.clone()), EXPIRY_SECS).with_clock(clock.clone());
+        let order = orders.begin(&mint, TradeSide::Buy, None, &rpc, &wallet).await.unwrap();
+        orders.submitted(&order.id, &[Signature::new_unique().to_string()]);
+        order.id
+    };
+
+    // Restarted after the order expired: the tokens in the wallet show it landed
+    clock.advance(Duration::seconds(EXPIRY_SECS + 1));
+    let orders = OrderLedger::new(Some(path.clone()), EXPIRY_SECS).with_clock(clock.clone());
+    assert_eq!(orders.get(&id).unwrap().state, OrderState::Submitted);
+    let settled = orders.reconcile(&rpc, &wallet).await;
+    assert_eq!(settled.len(), 1);
+    assert_eq!(settled[0].state, OrderState::Filled);
+    assert_eq!(
+        orders.begin(&mint, TradeSide::Buy, None, &rpc, &wallet).await,
+        Err(DuplicateOrder::AlreadyFilled { id }),
+    );
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,103 @@
+//! Golden tests for the transaction parser.
+//!
+//! Every fixture under `tests/fixtures/transactions/<dex>/` is decoded and parsed, and the output
+//! must match the fixture's `expected` events. After an intended parser change, regenerate the
+//! expectations with `UPDATE_GOLDEN=1 cargo test --test parser_golden` and review the diff.
+
+use std::path::{Path, PathBuf};
+use solana_vntr_sniper::library::parser_fixtures::{compare_output, fixture_output, ParserFixture};
+use solana_vntr_sniper::library::transaction_source::account_keys;
+
+const JUPITER_V6: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+
+fn fixture_paths() -> Vec<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/transactions");
+    let mut paths = Vec::new();
+    for dex_dir in std::fs::read_dir(&root).expect("fixtures directory").flatten() {
+        if !dex_dir.path().is_dir() {
+            continue;
+        }
+        for entry in std::fs::read_dir(dex_dir.path()).expect("dex fixtures directory").flatten() {
+            if entry.path().extension().map_or(false, |ext| ext == "json") {
+                paths.push(entry.path());
+            }
+        }
+    }
+    paths.sort();
+    paths
+}
+
+#[test]
+fn parser_matches_golden_fixtures() {
+    let update = std::env::var("UPDATE_GOLDEN").map_or(false, |v| v == "1");
+    let paths = fixture_paths();
+    assert!(!paths.is_empty(), "no parser fixtures found");
+
+    let mut failures = Vec::new();
+    for path in &paths {
+        let json = std::fs::read_to_string(path).unwrap();
+        let mut fixture: ParserFixture = serde_json::from_str(&json)
+            .unwrap_or_else(|e| panic!("{}: invalid fixture: {}", path.display(), e));
+        let txn = fixture.transaction()
+            .unwrap_or_else(|e| panic!("{}: undecodable transaction: {}", path.display(), e));
+        let actual = fixture_output(&txn);
+
+        if update {
+            fixture.expected = actual;
+            std::fs::write(path, serde_json::to_string_pretty(&fixture).unwrap() + "\n").unwrap();
+        } else if let Err(diff) = compare_output(&fixture.expected, &actual) {
+            failures.push(format!("{}: {}", path.display(), diff));
+        }
+    }
+    assert!(failures.is_empty(), "parser output differs from fixtures:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn fixtures_are_filed_under_their_dex() {
+    for path in fixture_paths() {
+        let fixture: ParserFixture = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let dir = path.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str()).unwrap();
+        assert_eq!(fixture.dex, dir, "{}", path.display());
+        assert_eq!(path.file_stem().and_then(|s| s.to_str()), Some(fixture.signature.as_str()), "{}", path.display());
+    }
+}
+
+/// Every DEX directory must hold at least REQUIRE_MAINNET_FIXTURES (default 10) mainnet captures.
+/// Ignored until the corpus is captured, so the gap shows in every test run instead of passing.
+#[test]
+#[ignore = "mainnet corpus not captured yet, see tests/fixtures/transactions/README.md"]
+fn enough_mainnet_captures() {
+    let required: usize = std::env::var("REQUIRE_MAINNET_FIXTURES").ok().and_then(|v| v.parse().ok()).unwrap_or(10);
+    let mut per_dex: std::collections::BTreeMap<String, usize> =
+        ["pumpfun", "pumpswap", "raydiumlaunchpad"].iter().map(|dex| (dex.to_string(), 0)).collect();
+    for path in fixture_paths() {
+        let fixture: ParserFixture = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        *per_dex.entry(fixture.dex.clone()).or_default() += (fixture.source == "mainnet") as usize;
+    }
+    let short: Vec<String> = per_dex.iter()
+        .filter(|(_, count)| **count < required)
+        .map(|(dex, count)| format!("{}: {} of {}", dex, count, required))
+        .collect();
+    assert!(short.is_empty(), "not enough mainnet fixtures (see tests/fixtures/transactions/README.md): {}", short.join(", "));
+}
+
+/// Routed swaps need captures of their own: a single-venue and a multi-hop Jupiter route, each a
+/// mainnet transaction. Ignored until they are captured.
+#[test]
+#[ignore = "routed mainnet captures not recorded yet, see tests/fixtures/transactions/README.md"]
+fn routed_swaps_have_mainnet_captures() {
+    let mut single = 0;
+    let mut multi_hop = 0;
+    for path in fixture_paths() {
+        let fixture: ParserFixture = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        if fixture.source != "mainnet" || !account_keys(&fixture.transaction().unwrap()).contains(JUPITER_V6) {
+            continue;
+        }
+        match fixture.expected.len() {
+            0 => {}
+            1 => single += 1,
+            _ => multi_hop += 1,
+        }
+    }
+    assert!(single > 0 && multi_hop > 0, "routed mainnet captures: {} single-venue, {} multi-hop", single, multi_hop);
+}
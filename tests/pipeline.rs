@@ -0,0 +1,121 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+use anyhow::Result;
+use async_trait::async_trait;
+use common::parsed_trade;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::pipeline::{
+    ComponentRegistry, Notice, Notifier, Pipeline, PipelineContext, PipelineEvent, PipelineSpec, Strategy,
+};
+use solana_vntr_sniper::processor::transaction_parser::SwapType;
+
+fn trade(dex: &str, swap_type: SwapType, sol: f64, progress: f64) -> PipelineEvent {
+    PipelineEvent::from_parsed(parsed_trade(Pubkey::new_unique())
+        .dex(dex)
+        .swap_type(swap_type)
+        .sol(sol)
+        .price(0.000001)
+        .liquidity(30.0)
+        .name("Piped")
+        .progress(progress)
+        .build())
+}
+
+struct Recording {
+    seen: Arc<Mutex<Vec<Pubkey>>>,
+}
+
+#[async_trait]
+impl Strategy for Recording {
+    fn name(&self) -> &str {
+        "recording"
+    }
+
+    async fn handle(&self, event: &PipelineEvent) -> Result<Vec<Notice>> {
+        self.seen.lock().unwrap().push(event.parsed.token_mint);
+        Ok(Vec::new())
+    }
+}
+
+struct Collecting {
+    notices: Arc<Mutex<Vec<Notice>>>,
+}
+
+#[async_trait]
+impl Notifier for Collecting {
+    fn name(&self) -> &str {
+        "collecting"
+    }
+
+    async fn notify(&self, notice: &Notice) -> Result<()> {
+        self.notices.lock().unwrap().push(notice.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn a_pipeline_from_config_runs_filtered_events_through_registered_components() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let notices = Arc::new(Mutex::new(Vec::new()));
+    let mut registry = ComponentRegistry::with_builtins();
+    let recorded = seen.clone();
+    registry.register_strategy("recording", move |_, _| Ok(Box::new(Recording { seen: recorded.clone() })));
+    let collected = notices.clone();
+    registry.register_notifier("collecting", move |_, _| Ok(Box::new(Collecting { notices: collected.clone() })));
+
+    let spec = PipelineSpec::from_json(r#"{
+        "name": "whale-watch",
+        "filters": [
+            { "type": "dex", "dexes": "PumpFun, PumpSwap" },
+            { "type": "swap_type", "types": ["buy"] },
+            { "type": "min_sol", "sol": 5 }
+        ],
+        "strategies": [{ "type": "recording" }, { "type": "alert", "title": "Whale buy" }],
+        "notifiers": [{ "type": "collecting" }]
+    }"#).unwrap();
+    let pipeline = Pipeline::build(&spec, &registry, &PipelineContext::default()).unwrap();
+    assert_eq!(
+        pipeline.describe(),
+        "whale-watch: sources [TRANSACTION_SOURCES] → filters [dex, swap_type, min_sol] → strategies [recording, alert] → notifiers [collecting]"
+    );
+
+    let whale = trade("PumpSwap", SwapType::Buy, 12.0, 100.0);
+    assert!(pipeline.handle(&whale).await);
+    assert!(!pipeline.handle(&trade("PumpSwap", SwapType::Buy, 1.0, 100.0)).await);
+    assert!(!pipeline.handle(&trade("PumpSwap", SwapType::Sell, 12.0, 100.0)).await);
+    assert!(!pipeline.handle(&trade("RaydiumLaunchpad", SwapType::Buy, 12.0, 100.0)).await);
+
+    assert_eq!(*seen.lock().unwrap(), vec![whale.parsed.token_mint]);
+    let notices = notices.lock().unwrap();
+    assert_eq!(notices.len(), 1);
+    assert_eq!(notices[0].title, "Whale buy");
+    assert!(notices[0].body.contains("Buy of Piped"));
+    assert!(notices[0].body.contains("12.000 SOL"));
+}
+
+#[test]
+fn presets_need_the_components_their_binary_provides() {
+    let registry = ComponentRegistry::with_builtins();
+    let ctx = PipelineContext::default();
+    for preset in ["monitor", "copy_trader", "launch_sniper"] {
+        let spec = PipelineSpec::preset(preset).unwrap();
+        let error = Pipeline::build(&spec, &registry, &ctx).err().unwrap();
+        assert!(format!("{:#}", error).contains("needs"), "{}: {:#}", preset, error);
+    }
+    assert!(PipelineSpec::preset("arbitrage").is_none());
+}
+
+#[test]
+fn unknown_components_and_bad_parameters_fail_the_build() {
+    let registry = ComponentRegistry::with_builtins();
+    let ctx = PipelineContext::default();
+    let build = |json: &str| Pipeline::build(&PipelineSpec::from_json(json).unwrap(), &registry, &ctx).err().map(|e| format!("{:#}", e));
+
+    assert_eq!(build(r#"{ "name": "empty" }"#).unwrap(), "Pipeline 'empty' has no strategies");
+    assert!(build(r#"{ "strategies": [{ "type": "martingale" }] }"#).unwrap().contains("Unknown strategy 'martingale'"));
+    assert!(build(r#"{ "strategies": [{ "type": "alert" }], "filters": [{ "type": "min_sol" }] }"#).unwrap().contains("needs 'sol'"));
+    assert!(build(r#"{ "strategies": [{ "type": "alert" }], "filters": [{ "type": "swap_type", "types": ["hodl"] }] }"#)
+        .unwrap().contains("Unknown swap type 'hodl'"));
+    assert!(build(r#"{ "strategies": [{ "type": "alert" }], "filters": [{ "type": "launch" }], "notifiers": [{ "type": "log" }] }"#).is_none());
+}
@@ -0,0 +1,96 @@
+use solana_sdk::compute_budget::{self, ComputeBudgetInstruction};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::AddressLookupTableAccount;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::block_engine::lookup_table::compile_message;
+use solana_vntr_sniper::block_engine::preflight::{
+    check, requested_units, split, Violation, MAX_COMPUTE_UNIT_LIMIT,
+};
+
+fn budget() -> Vec<Instruction> {
+    vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+        ComputeBudgetInstruction::set_compute_unit_price(20_000),
+    ]
+}
+
+/// An instruction touching `n` accounts of its own
+fn wide(n: usize) -> Instruction {
+    let accounts = (0..n).map(|_| AccountMeta::new(Pubkey::new_unique(), false)).collect();
+    Instruction::new_with_bytes(Pubkey::new_unique(), &[1; 8], accounts)
+}
+
+fn accounts_of(instructions: &[Instruction]) -> Vec<Pubkey> {
+    instructions.iter().flat_map(|ix| ix.accounts.iter().map(|meta| meta.pubkey)).collect()
+}
+
+#[test]
+fn small_transactions_pass_unchanged() {
+    let payer = Pubkey::new_unique();
+    let instructions: Vec<Instruction> = budget().into_iter().chain([wide(5)]).collect();
+    let groups = split(&payer, instructions.clone(), &[], Hash::new_unique(), None).unwrap();
+    assert_eq!(groups, vec![instructions]);
+}
+
+#[test]
+fn compute_unit_limits_are_lowered_to_the_maximum() {
+    let payer = Pubkey::new_unique();
+    let instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(5_000_000), wide(2)];
+    let groups = split(&payer, instructions, &[], Hash::new_unique(), None).unwrap();
+    assert_eq!(requested_units(&groups[0]), Some(MAX_COMPUTE_UNIT_LIMIT));
+}
+
+#[test]
+fn oversized_transactions_split_with_the_swap_last() {
+    let payer = Pubkey::new_unique();
+    let setup: Vec<Instruction> = (0..8).map(|_| wide(6)).collect();
+    let swap = wide(4);
+    let instructions: Vec<Instruction> = budget().into_iter().chain(setup).chain([swap.clone()]).collect();
+
+    let groups = split(&payer, instructions, &[], Hash::new_unique(), None).unwrap();
+    assert!(groups.len() > 1);
+    assert_eq!(groups.last().unwrap().last(), Some(&swap));
+    assert_eq!(groups.iter().map(|g| g.len() - 2).sum::<usize>(), 9);
+    for group in &groups {
+        assert!(group[..2].iter().all(|ix| ix.program_id == compute_budget::id()));
+        let message = compile_message(&payer, group, Hash::new_unique(), None).unwrap();
+        assert_eq!(check(&message), Ok(()));
+    }
+}
+
+#[test]
+fn additions_are_counted_against_every_part() {
+    let payer = Pubkey::new_unique();
+    let instructions: Vec<Instruction> = (0..4).map(|_| wide(6)).collect();
+    assert_eq!(split(&payer, instructions.clone(), &[], Hash::new_unique(), None).unwrap().len(), 1);
+    let additions = [wide(10)];
+    assert!(split(&payer, instructions, &additions, Hash::new_unique(), None).unwrap().len() > 1);
+}
+
+#[test]
+fn a_lookup_table_avoids_the_split() {
+    let payer = Pubkey::new_unique();
+    let instructions: Vec<Instruction> = (0..8).map(|_| wide(6)).collect();
+    let table = AddressLookupTableAccount { key: Pubkey::new_unique(), addresses: accounts_of(&instructions) };
+    assert_eq!(split(&payer, instructions, &[], Hash::new_unique(), Some(&table)).unwrap().len(), 1);
+}
+
+#[test]
+fn account_locks_are_checked_through_the_table() {
+    let payer = Pubkey::new_unique();
+    let instructions: Vec<Instruction> = (0..7).map(|_| wide(10)).collect();
+    let table = AddressLookupTableAccount { key: Pubkey::new_unique(), addresses: accounts_of(&instructions) };
+    let message = compile_message(&payer, &instructions, Hash::new_unique(), Some(&table)).unwrap();
+    assert!(matches!(check(&message), Err(Violation::TooManyAccounts { .. })));
+    assert_eq!(split(&payer, instructions, &[], Hash::new_unique(), Some(&table)).unwrap().len(), 2);
+}
+
+#[test]
+fn an_instruction_too_large_on_its_own_is_reported() {
+    let payer = Pubkey::new_unique();
+    let huge = Instruction::new_with_bytes(Pubkey::new_unique(), &[0; 1300], vec![]);
+    let error = split(&payer, vec![wide(2), huge], &[], Hash::new_unique(), None).unwrap_err();
+    assert!(matches!(&error, Violation::Unsplittable { index: 1, .. }), "{:?}", error);
+    assert!(error.to_string().contains("bytes"));
+}
@@ -0,0 +1,106 @@
+use std::collections::{HashSet, VecDeque};
+use std::time::Instant;
+use anyhow::Result;
+use async_trait::async_trait;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::quote_asset::QuoteAsset;
+use solana_vntr_sniper::common::timeseries::{SlotSample, TokenTimeseries};
+use solana_vntr_sniper::processor::price_backfill::{backfill_focus_token, BackfillConfig, PriceBackfill, TradeHistory};
+use solana_vntr_sniper::processor::sniper_bot::{FocusTokenInfo, FOCUS_TOKEN_LIST};
+use solana_vntr_sniper::processor::swap::SwapProtocol;
+use solana_vntr_sniper::processor::transaction_parser::{DexType, TradeInfoFromToken};
+
+fn trade(mint: &str, slot: u64, price: u64) -> TradeInfoFromToken {
+    TradeInfoFromToken {
+        dex_type: DexType::PumpFun,
+        slot,
+        signature: format!("sig-{}", slot),
+        pool_id: String::new(),
+        mint: mint.to_string(),
+        timestamp: 1_750_000_000,
+        is_buy: true,
+        price,
+        is_reverse_when_pump_swap: false,
+        coin_creator: None,
+        sol_change: 0.5,
+        token_change: 10_000.0,
+        liquidity: 10.0,
+        virtual_sol_reserves: 40_000_000_000,
+        virtual_token_reserves: 800_000_000_000_000,
+        quote_asset: QuoteAsset::Sol,
+    }
+}
+
+/// Focus token first seen at `slot` and `price`
+fn focus(mint: &str, slot: u64, price: f64) -> FocusTokenInfo {
+    FocusTokenInfo {
+        mint: mint.to_string(),
+        initial_price: price,
+        current_price: price,
+        lowest_price: price,
+        highest_price: price,
+        price_dropped: false,
+        buy_count: 0,
+        sell_count: 0,
+        trade_cycles: 0,
+        protocol: SwapProtocol::PumpFun,
+        added_timestamp: Instant::now(),
+        last_price_update: Instant::now(),
+        price_history: VecDeque::from(vec![price]),
+        slot_price_history: VecDeque::from(vec![(slot, price)]),
+        two_slot_drop_active: false,
+        whale_wallets: HashSet::new(),
+        total_trades: 0,
+    }
+}
+
+struct FixedHistory(Vec<TradeInfoFromToken>);
+
+#[async_trait]
+impl TradeHistory for FixedHistory {
+    async fn recent_trades(&self, _mint: &Pubkey, _limit: usize) -> Result<Vec<TradeInfoFromToken>> {
+        Ok(self.0.clone())
+    }
+}
+
+#[test]
+fn backfill_covers_trades_before_the_first_sighting() {
+    let trades = vec![trade("Mint111", 105, 90), trade("Mint111", 100, 120), trade("Mint111", 110, 80), trade("Other", 101, 1)];
+
+    let backfill = PriceBackfill::from_trades("Mint111", &trades, Some(110)).unwrap();
+    assert_eq!(backfill.prices, vec![(100, 120.0), (105, 90.0)]);
+    assert_eq!(backfill.first_price, 120.0);
+    assert_eq!(backfill.lowest_price, 90.0);
+    assert_eq!(backfill.highest_price, 120.0);
+
+    assert_eq!(PriceBackfill::from_trades("Mint111", &trades, Some(100)), None);
+}
+
+#[tokio::test]
+async fn focus_token_is_anchored_at_its_recent_trades() {
+    let mint = Pubkey::new_unique().to_string();
+    FOCUS_TOKEN_LIST.insert(mint.clone(), focus(&mint, 200, 60.0));
+    let history = FixedHistory(vec![trade(&mint, 190, 100), trade(&mint, 195, 50), trade(&mint, 200, 60)]);
+
+    let backfill = backfill_focus_token(&mint, &history, &BackfillConfig::default()).await.unwrap().unwrap();
+    assert_eq!(backfill.prices.len(), 2);
+
+    let info = FOCUS_TOKEN_LIST.get(&mint).unwrap().clone();
+    assert_eq!(info.initial_price, 100.0);
+    assert_eq!(info.lowest_price, 50.0);
+    assert_eq!(info.highest_price, 100.0);
+    assert_eq!(info.current_price, 60.0);
+    assert_eq!(info.price_history, VecDeque::from(vec![100.0, 50.0, 60.0]));
+    FOCUS_TOKEN_LIST.remove(&mint);
+}
+
+#[test]
+fn timeseries_backfill_goes_before_live_samples() {
+    let mut series = TokenTimeseries::new(3);
+    series.update(50, 10.0, true, 1.0);
+    let sample = |slot, price| SlotSample { slot, price, buy_volume: 0.5, sell_volume: 0.0 };
+    series.backfill(vec![sample(48, 12.0), sample(40, 9.0), sample(48, 11.0), sample(60, 1.0)]);
+
+    let slots: Vec<(u64, f64, f64)> = series.samples().map(|s| (s.slot, s.price, s.buy_volume)).collect();
+    assert_eq!(slots, vec![(40, 9.0, 0.5), (48, 11.0, 1.0), (50, 10.0, 1.0)]);
+}
@@ -0,0 +1,72 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_vntr_sniper::library::priority_lane::{
+    PriorityLaneClient, PriorityLaneConfig, PriorityLaneProvider, Urgency,
+};
+
+#[test]
+fn urgencies_are_ordered_and_parsed() {
+    assert!(Urgency::Low < Urgency::Normal && Urgency::Normal < Urgency::High && Urgency::High < Urgency::Critical);
+    assert_eq!("Critical".parse::<Urgency>().unwrap(), Urgency::Critical);
+    assert!("asap".parse::<Urgency>().is_err());
+    assert_eq!("helius".parse::<PriorityLaneProvider>().unwrap(), PriorityLaneProvider::HeliusSender);
+}
+
+#[test]
+fn only_urgent_enough_transactions_take_the_lane() {
+    let config = PriorityLaneConfig::new(PriorityLaneProvider::Triton, "https://lane.example");
+    assert!(!config.handles(Urgency::Normal));
+    assert!(config.handles(Urgency::High));
+    assert!(config.handles(Urgency::Critical));
+
+    let everything = PriorityLaneConfig { min_urgency: Urgency::Low, ..config };
+    assert!(everything.handles(Urgency::Low));
+}
+
+#[test]
+fn tips_are_validated() {
+    let custom = PriorityLaneConfig::new(PriorityLaneProvider::Custom, "https://lane.example");
+    assert!(custom.validate().is_ok());
+
+    let helius = PriorityLaneConfig::new(PriorityLaneProvider::HeliusSender, "https://sender.example");
+    assert!(helius.validate().is_err(), "Helius Sender needs a tip");
+
+    let tip_nowhere = PriorityLaneConfig { tip_lamports: 1_000_000, ..helius.clone() };
+    assert!(tip_nowhere.validate().is_err(), "a tip needs accounts to go to");
+
+    let tipped = PriorityLaneConfig { tip_accounts: vec![Pubkey::new_unique()], ..tip_nowhere };
+    assert!(tipped.validate().is_ok());
+}
+
+#[test]
+fn helius_requests_disable_rpc_retries() {
+    let helius = PriorityLaneConfig::new(PriorityLaneProvider::HeliusSender, "https://sender.example");
+    let body = helius.request_body("AQID");
+    assert_eq!(body["method"], "sendTransaction");
+    assert_eq!(body["params"][0], "AQID");
+    assert_eq!(body["params"][1]["skipPreflight"], true);
+    assert_eq!(body["params"][1]["maxRetries"], 0);
+
+    let custom = PriorityLaneConfig::new(PriorityLaneProvider::Custom, "https://lane.example");
+    assert!(custom.request_body("AQID")["params"][1].get("maxRetries").is_none());
+}
+
+#[test]
+fn tip_goes_to_a_configured_account() {
+    let payer = Pubkey::new_unique();
+    let accounts = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    let lane = PriorityLaneClient::new(PriorityLaneConfig {
+        tip_lamports: 1_000_000,
+        tip_accounts: accounts.clone(),
+        ..PriorityLaneConfig::new(PriorityLaneProvider::HeliusSender, "https://sender.example")
+    });
+
+    let tip = lane.tip_instruction(&payer).unwrap();
+    assert_eq!(tip.accounts[0].pubkey, payer);
+    assert!(accounts.contains(&tip.accounts[1].pubkey));
+    let transfer: SystemInstruction = bincode::deserialize(&tip.data).unwrap();
+    assert_eq!(transfer, SystemInstruction::Transfer { lamports: 1_000_000 });
+
+    let untipped = PriorityLaneClient::new(PriorityLaneConfig::new(PriorityLaneProvider::Triton, "https://lane.example"));
+    assert!(untipped.tip_instruction(&payer).is_none());
+}
@@ -0,0 +1,229 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::Duration;
+use common::{MockRpc, UNREACHABLE_RPC};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::error::BotError;
+use solana_vntr_sniper::library::rpc_api::SignatureStatus;
+use solana_vntr_sniper::processor::profit_policy::{
+    realized_profit, ProfitExecutor, ProfitPolicy, ProfitPolicyConfig, ProfitPolicyEngine, WalletProfitExecutor,
+};
+use solana_vntr_sniper::processor::trade_ledger::{LedgerTrade, TradeSide};
+
+const START: i64 = 1_750_000_000;
+const HOUR: i64 = 3_600;
+
+/// Records what the policy moved
+#[derive(Default)]
+struct MockExecutor {
+    fail: bool,
+    unconfirmed: bool,
+    swept: Mutex<Vec<(Pubkey, u64)>>,
+    converted: Mutex<Vec<u64>>,
+}
+
+#[async_trait]
+impl ProfitExecutor for MockExecutor {
+    async fn sweep(&self, to: &Pubkey, lamports: u64) -> Result<String> {
+        if self.fail {
+            return Err(anyhow!("no recent blockhash"));
+        }
+        if self.unconfirmed {
+            return Err(BotError::NotConfirmed("sweep-sig".to_string()).into());
+        }
+        self.swept.lock().unwrap().push((*to, lamports));
+        Ok("sweep-sig".to_string())
+    }
+
+    async fn convert_to_usdc(&self, lamports: u64) -> Result<String> {
+        if self.unconfirmed {
+            return Err(BotError::NotConfirmed("swap-sig".to_string()).into());
+        }
+        self.converted.lock().unwrap().push(lamports);
+        Ok("swap-sig".to_string())
+    }
+}
+
+fn trade(timestamp: i64, side: TradeSide, sol_amount: f64) -> LedgerTrade {
+    LedgerTrade {
+        timestamp,
+        signature: format!("{}-{}", side.as_str(), timestamp),
+        mint: "Mint111".to_string(),
+        side,
+        token_amount: 1_000.0,
+        sol_amount,
+        fee_sol: 0.0,
+    }
+}
+
+fn config(policy: ProfitPolicy) -> ProfitPolicyConfig {
+    ProfitPolicyConfig { policy, sweep_wallet: Some(Pubkey::new_unique()), ..ProfitPolicyConfig::default() }
+}
+
+#[test]
+fn only_sells_inside_the_window_count() {
+    let trades = vec![
+        trade(START - 2 * HOUR, TradeSide::Buy, 1.0),
+        trade(START - HOUR, TradeSide::Sell, 1.5),
+        trade(START + HOUR, TradeSide::Buy, 1.0),
+        trade(START + 2 * HOUR, TradeSide::Sell, 1.2),
+    ];
+    let (pnl, sells) = realized_profit(&trades, START, START + 3 * HOUR);
+    assert_eq!(sells, 1);
+    assert!((pnl - 0.2).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn profits_are_swept_once_an_interval_has_passed() {
+    let executor = Arc::new(MockExecutor::default());
+    let clock = Arc::new(SimulatedClock::at_unix(START));
+    let config = config(ProfitPolicy::Sweep);
+    let cold_wallet = config.sweep_wallet.unwrap();
+    let engine = ProfitPolicyEngine::new(config, executor.clone()).with_clock(clock.clone());
+    assert!(!engine.due().await);
+
+    let trades = vec![trade(START + HOUR, TradeSide::Buy, 1.0), trade(START + 2 * HOUR, TradeSide::Sell, 2.0)];
+    clock.advance(Duration::hours(24));
+    assert!(engine.due().await);
+    let summary = engine.run(&trades).await;
+    assert_eq!(summary.sells, 1);
+    assert!((summary.applied_sol - 0.5).abs() < 1e-9);
+    assert_eq!(summary.signature.as_deref(), Some("sweep-sig"));
+    assert_eq!(*executor.swept.lock().unwrap(), vec![(cold_wallet, 500_000_000)]);
+
+    // The next window starts where this one ended
+    assert!(!engine.due().await);
+    clock.advance(Duration::hours(24));
+    let summary = engine.run(&trades).await;
+    assert_eq!(summary.sells, 0);
+    assert_eq!(executor.swept.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn small_profits_are_carried_over() {
+    let executor = Arc::new(MockExecutor::default());
+    let clock = Arc::new(SimulatedClock::at_unix(START));
+    let engine = ProfitPolicyEngine::new(config(ProfitPolicy::Usdc), executor.clone()).with_clock(clock.clone());
+    assert!(!engine.due().await);
+
+    let mut trades = vec![trade(START + HOUR, TradeSide::Buy, 1.0), trade(START + 2 * HOUR, TradeSide::Sell, 1.0625)];
+    clock.advance(Duration::hours(24));
+    let summary = engine.run(&trades).await;
+    assert_eq!(summary.applied_sol, 0.0);
+    assert!(summary.note.unwrap().contains("carried over"));
+    assert!(!engine.due().await);
+
+    // The second window still includes the first sell
+    trades.push(trade(START + 30 * HOUR, TradeSide::Buy, 1.0));
+    trades.push(trade(START + 31 * HOUR, TradeSide::Sell, 1.1875));
+    clock.advance(Duration::hours(24));
+    let summary = engine.run(&trades).await;
+    assert_eq!(summary.sells, 2);
+    assert_eq!(*executor.converted.lock().unwrap(), vec![125_000_000]);
+}
+
+#[tokio::test]
+async fn compounding_moves_nothing() {
+    let executor = Arc::new(MockExecutor::default());
+    let clock = Arc::new(SimulatedClock::at_unix(START));
+    let engine = ProfitPolicyEngine::new(config(ProfitPolicy::Compound), executor.clone()).with_clock(clock.clone());
+    engine.due().await;
+
+    clock.advance(Duration::hours(24));
+    let summary = engine.run(&[trade(START + HOUR, TradeSide::Buy, 1.0), trade(START + 2 * HOUR, TradeSide::Sell, 2.0)]).await;
+    assert!((summary.applied_sol - 0.5).abs() < 1e-9);
+    assert_eq!(summary.signature, None);
+    assert!(executor.swept.lock().unwrap().is_empty() && executor.converted.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn a_failed_sweep_is_retried_and_the_window_survives_a_restart() {
+    let path = std::env::temp_dir().join(format!("profit_policy_{}.json", Pubkey::new_unique()));
+    let config = ProfitPolicyConfig { state_path: Some(path.clone()), ..config(ProfitPolicy::Sweep) };
+    let trades = vec![trade(START + HOUR, TradeSide::Buy, 1.0), trade(START + 2 * HOUR, TradeSide::Sell, 2.0)];
+    let clock = Arc::new(SimulatedClock::at_unix(START));
+
+    let failing = Arc::new(MockExecutor { fail: true, ..MockExecutor::default() });
+    let engine = ProfitPolicyEngine::new(config.clone(), failing).with_clock(clock.clone());
+    engine.due().await;
+    clock.advance(Duration::hours(24));
+    let summary = engine.run(&trades).await;
+    assert_eq!(summary.applied_sol, 0.0);
+    assert!(summary.note.unwrap().contains("no recent blockhash"));
+
+    // After a restart the unswept window is still open
+    let executor = Arc::new(MockExecutor::default());
+    let engine = ProfitPolicyEngine::new(config, executor.clone()).with_clock(clock.clone());
+    assert!(engine.due().await);
+    assert_eq!(engine.run(&trades).await.sells, 1);
+    assert_eq!(executor.swept.lock().unwrap().len(), 1);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn an_unconfirmed_sweep_closes_the_window_without_claiming_it() {
+    let clock = Arc::new(SimulatedClock::at_unix(START));
+    let executor = Arc::new(MockExecutor { unconfirmed: true, ..MockExecutor::default() });
+    let engine = ProfitPolicyEngine::new(config(ProfitPolicy::Sweep), executor).with_clock(clock.clone());
+    let trades = vec![trade(START + HOUR, TradeSide::Buy, 1.0), trade(START + 2 * HOUR, TradeSide::Sell, 2.0)];
+    engine.due().await;
+    clock.advance(Duration::hours(24));
+
+    let summary = engine.run(&trades).await;
+
+    assert_eq!(summary.applied_sol, 0.0);
+    assert_eq!(summary.signature.as_deref(), Some("sweep-sig"));
+    assert!(summary.note.unwrap().contains("not confirmed"));
+    // The transfer may still land, so the same profits are not swept again
+    clock.advance(Duration::hours(24));
+    assert_eq!(engine.run(&trades).await.sells, 0);
+}
+
+#[tokio::test]
+async fn an_unconfirmed_conversion_is_not_converted_again() {
+    let clock = Arc::new(SimulatedClock::at_unix(START));
+    let executor = Arc::new(MockExecutor { unconfirmed: true, ..MockExecutor::default() });
+    let engine = ProfitPolicyEngine::new(config(ProfitPolicy::Usdc), executor).with_clock(clock.clone());
+    let trades = vec![trade(START + HOUR, TradeSide::Buy, 1.0), trade(START + 2 * HOUR, TradeSide::Sell, 2.0)];
+    engine.due().await;
+    clock.advance(Duration::hours(24));
+
+    let summary = engine.run(&trades).await;
+
+    assert_eq!(summary.applied_sol, 0.0);
+    assert_eq!(summary.signature.as_deref(), Some("swap-sig"));
+    clock.advance(Duration::hours(24));
+    assert_eq!(engine.run(&trades).await.sells, 0);
+}
+
+#[tokio::test]
+async fn the_wallet_executor_sweeps_through_the_rpc_node() {
+    let wallet = Arc::new(Keypair::new());
+    let cold_wallet = Pubkey::new_unique();
+    let rpc = Arc::new(MockRpc::confirming());
+    let executor = WalletProfitExecutor::new(wallet.clone(), rpc.clone(), Arc::new(RpcClient::new(UNREACHABLE_RPC.to_string())));
+
+    let signature = executor.sweep(&cold_wallet, 500_000_000).await.unwrap();
+
+    let sent = rpc.sent.lock().unwrap()[0].clone();
+    assert_eq!(rpc.plain_sent.lock().unwrap().as_slice(), &[sent.signatures[0]]);
+    assert_eq!(signature, sent.signatures[0].to_string());
+    assert_eq!(sent.message.account_keys[0], wallet.pubkey());
+    assert!(sent.message.account_keys.contains(&cold_wallet));
+}
+
+#[tokio::test]
+async fn a_sweep_failing_on_chain_is_an_error() {
+    let rpc = Arc::new(MockRpc::with_status(SignatureStatus::Failed("InsufficientFundsForFee".to_string())));
+    let executor = WalletProfitExecutor::new(Arc::new(Keypair::new()), rpc, Arc::new(RpcClient::new(UNREACHABLE_RPC.to_string())));
+
+    let error = executor.sweep(&Pubkey::new_unique(), 500_000_000).await.unwrap_err();
+
+    assert!(matches!(BotError::classify(&error), BotError::Rejected(_)), "{}", error);
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_vntr_sniper::block_engine::relay::{
+    parse_submit_response, BloxrouteRelay, NextBlockRelay, Relay, RelayConfig, RelayRouter, RelayStats, Route,
+};
+
+fn tipped_config() -> RelayConfig {
+    RelayConfig { tip_lamports: 1_000_000, tip_accounts: vec![Pubkey::new_unique()], ..RelayConfig::new("https://relay.example/", "secret") }
+}
+
+#[test]
+fn relays_require_a_tip_and_tip_accounts() {
+    let untipped = RelayConfig::new("https://relay.example", "secret");
+    assert!(untipped.validate("NEXTBLOCK").unwrap_err().to_string().contains("NEXTBLOCK requires a tip"));
+
+    let no_accounts = RelayConfig { tip_lamports: 1_000_000, ..untipped };
+    assert!(no_accounts.validate("BLOXROUTE").unwrap_err().to_string().contains("BLOXROUTE_TIP_ACCOUNTS is empty"));
+    assert!(tipped_config().validate("BLOXROUTE").is_ok());
+    assert_eq!(tipped_config().url, "https://relay.example");
+}
+
+#[test]
+fn each_relay_tips_its_own_accounts() {
+    let config = tipped_config();
+    let relay = NextBlockRelay::new(config.clone());
+    let payer = Pubkey::new_unique();
+
+    let tip = relay.tip_instruction(&payer).unwrap();
+    assert_eq!(tip.accounts[1].pubkey, config.tip_accounts[0]);
+    assert_eq!(bincode::deserialize::<SystemInstruction>(&tip.data).unwrap(), SystemInstruction::Transfer { lamports: 1_000_000 });
+    assert_eq!(relay.tip_lamports(), 1_000_000);
+}
+
+#[test]
+fn request_bodies_follow_each_api() {
+    let bloxroute = BloxrouteRelay::new(tipped_config(), true).request_body("AQID");
+    assert_eq!(bloxroute, json!({
+        "transaction": { "content": "AQID" },
+        "frontRunningProtection": true,
+        "useStakedRPCs": true,
+    }));
+    let nextblock = NextBlockRelay::new(tipped_config()).request_body("AQID");
+    assert_eq!(nextblock["transaction"]["content"], "AQID");
+
+    let signature = Signature::new_unique();
+    assert_eq!(parse_submit_response(&json!({ "signature": signature.to_string() })).unwrap(), signature);
+    assert!(parse_submit_response(&json!({ "error": "unauthorized" })).is_err());
+    assert!(parse_submit_response(&json!({})).is_err());
+}
+
+#[test]
+fn routes_rotate_per_transaction() {
+    assert_eq!(RelayRouter::new(Vec::new()).next_route().name(), "zeroslot");
+
+    let router = RelayRouter::new(vec![
+        Route::ZeroSlot,
+        Route::Relay(Arc::new(NextBlockRelay::new(tipped_config()))),
+    ]);
+    let names: Vec<_> = (0..4).map(|_| router.next_route().name()).collect();
+    assert_eq!(names, ["zeroslot", "nextblock", "zeroslot", "nextblock"]);
+}
+
+#[test]
+fn landed_rates_are_compared_per_route() {
+    let stats = RelayStats::new();
+    let sent: Vec<_> = (0..4).map(|_| Signature::new_unique()).collect();
+    stats.record_sent("bloxroute", sent[0]);
+    stats.record_sent("bloxroute", sent[1]);
+    stats.record_sent("nextblock", sent[2]);
+    stats.record_sent("nextblock", sent[3]);
+
+    stats.record_landed(&sent[0]);
+    stats.record_landed(&sent[2]);
+    stats.record_landed(&sent[3]);
+    // Confirming twice, or confirming an untracked send, counts nothing
+    stats.record_landed(&sent[3]);
+    stats.record_landed(&Signature::new_unique());
+
+    assert_eq!(stats.counts("bloxroute"), (2, 1));
+    assert_eq!(stats.counts("nextblock"), (2, 2));
+    assert_eq!(
+        stats.format(),
+        "nextblock: 2/2 landed (100.0%)\nbloxroute: 1/2 landed (50.0%)"
+    );
+    assert_eq!(RelayStats::new().format(), "No transaction sent yet");
+}
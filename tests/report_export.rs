@@ -0,0 +1,88 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::{DateTime, NaiveDate};
+use common::RecordingSink;
+use solana_vntr_sniper::processor::report_export::{pnl_report, Block, Report, ReportConfig};
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+use solana_vntr_sniper::processor::trade_ledger::{LedgerTrade, TradeSide};
+
+/// 2024-03-01T12:00:00Z
+const DAY_ONE: i64 = 1_709_294_400;
+const DAY: i64 = 86_400;
+
+fn trade(signature: &str, timestamp: i64, side: TradeSide, sol_amount: f64) -> LedgerTrade {
+    LedgerTrade {
+        timestamp,
+        signature: signature.to_string(),
+        mint: "Mint111".to_string(),
+        side,
+        token_amount: 1_000.0,
+        sol_amount,
+        fee_sol: 0.001,
+    }
+}
+
+fn at(timestamp: i64) -> DateTime<chrono::Utc> {
+    DateTime::from_timestamp(timestamp, 0).unwrap()
+}
+
+#[test]
+fn text_report_keeps_its_paragraphs_and_is_escaped() {
+    let report = Report::from_text("Weekly Signal Report", at(DAY_ONE), "\nTokens: 5\nWallets: 2\n\n<b>not markup</b>\n");
+    assert_eq!(report.blocks, vec![
+        Block::Text("Tokens: 5\nWallets: 2".to_string()),
+        Block::Text("<b>not markup</b>".to_string()),
+    ]);
+    assert_eq!(report.file_stem(), "weekly-signal-report-2024-03-01-1200");
+
+    let html = report.to_html();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<p>Tokens: 5\nWallets: 2</p>"));
+    assert!(html.contains("&lt;b&gt;not markup&lt;/b&gt;"));
+}
+
+#[test]
+fn pnl_report_has_a_chart_and_a_daily_table() {
+    let trades = vec![
+        trade("b1", DAY_ONE, TradeSide::Buy, 1.0),
+        trade("s1", DAY_ONE + 60, TradeSide::Sell, 1.5),
+        trade("b2", DAY_ONE + DAY, TradeSide::Buy, 1.0),
+        trade("s2", DAY_ONE + DAY + 60, TradeSide::Sell, 0.5),
+        trade("b3", DAY_ONE + 3 * DAY, TradeSide::Buy, 1.0),
+    ];
+    let to = NaiveDate::from_ymd_opt(2024, 3, 2);
+
+    let report = pnl_report(&trades, None, to, at(DAY_ONE + 5 * DAY));
+    let bars = report.blocks.iter().find_map(|block| match block {
+        Block::BarChart { bars, .. } => Some(bars.clone()),
+        _ => None,
+    }).unwrap();
+    assert_eq!(bars.len(), 2);
+    assert_eq!(bars[0].0, "03-01");
+    assert!(bars[0].1 > 0.0 && bars[1].1 < 0.0);
+
+    let html = report.to_html();
+    assert!(html.contains("<svg"));
+    assert_eq!(html.matches("<rect").count(), 2);
+    assert!(html.contains("<td>2024-03-02</td>"));
+    assert!(!html.contains("2024-03-04"));
+}
+
+#[tokio::test]
+async fn saved_report_is_sent_as_a_document() {
+    let dir = std::env::temp_dir().join(format!("reports_{}", DAY_ONE + std::process::id() as i64));
+    let config = ReportConfig { enabled: true, dir: dir.clone(), pdf_command: None, telegram_documents: true };
+    let report = Report::new("Daily Report", at(DAY_ONE)).table(&["Token", "Trades"], vec![vec!["Mint111".to_string(), "3".to_string()]]);
+
+    let saved = report.save(&config).unwrap();
+    assert_eq!(saved.html, dir.join("daily-report-2024-03-01-1200.html"));
+    assert_eq!(saved.pdf, None);
+    assert!(std::fs::read_to_string(&saved.html).unwrap().contains("<td class=\"num\">3</td>"));
+
+    let sink = Arc::new(RecordingSink::default());
+    let telegram = TelegramAlertSystem::with_sink(sink.clone(), true);
+    telegram.send_document(&saved.html, &report.title).await.unwrap();
+    assert_eq!(sink.documents.lock().unwrap().clone(), vec![(saved.html.clone(), "Daily Report".to_string())]);
+    let _ = std::fs::remove_dir_all(dir);
+}
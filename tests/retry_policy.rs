@@ -0,0 +1,49 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use solana_vntr_sniper::common::logger::Logger;
+use solana_vntr_sniper::error::{retry, BotError, ErrorClass, RetryPolicy};
+
+#[test]
+fn errors_are_classified_from_their_messages() {
+    assert_eq!(BotError::from_message("Blockhash not found").class(), ErrorClass::Transient);
+    assert_eq!(BotError::from_message("operation timed out").class(), ErrorClass::Transient);
+    assert_eq!(
+        BotError::from_message("HTTP status 429 Too Many Requests"),
+        BotError::RateLimited { retry_after: None }
+    );
+    assert_eq!(
+        BotError::from_message("Transfer: insufficient lamports 100, need 200"),
+        BotError::InsufficientFunds
+    );
+    assert_eq!(BotError::InvalidKeypair("short".to_string()).class(), ErrorClass::Fatal);
+}
+
+#[test]
+fn backoff_grows_to_the_policy_cap() {
+    let policy = RetryPolicy::for_error(&BotError::RpcTimeout("getSignatureStatuses".to_string()));
+    assert_eq!(policy.backoff(1), Duration::from_millis(250));
+    assert_eq!(policy.backoff(2), Duration::from_millis(500));
+    assert_eq!(policy.backoff(5), Duration::from_secs(2));
+    assert_eq!(RetryPolicy::for_error(&BotError::InsufficientFunds).max_attempts, 1);
+}
+
+#[tokio::test]
+async fn transient_failures_are_retried_and_fatal_ones_are_not() {
+    let logger = Logger::new("[TEST] => ".to_string());
+
+    let attempts = AtomicU32::new(0);
+    let result = retry("blockhash", &logger, |attempt| {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        async move { if attempt < 3 { Err(BotError::BlockhashExpired) } else { Ok(attempt) } }
+    }).await;
+    assert_eq!(result, Ok(3));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+    let attempts = AtomicU32::new(0);
+    let result: Result<(), BotError> = retry("send", &logger, |_| {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        async { Err(BotError::Rejected("Custom(6002)".to_string())) }
+    }).await;
+    assert_eq!(result, Err(BotError::Rejected("Custom(6002)".to_string())));
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
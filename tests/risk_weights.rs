@@ -0,0 +1,94 @@
+use solana_vntr_sniper::processor::risk_score::{format_explanation, get_factors, update_factors, RiskFactors, RiskWeights};
+
+fn write_weights(name: &str, content: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("risk_weights_{}_{}.json", name, std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn default_weights_score_as_before() {
+    let weights = RiskWeights::default();
+    let factors = RiskFactors {
+        unexpected_mints: 2,
+        minted_pct: 15.0,
+        insider_wallets: 3,
+        ..RiskFactors::default()
+    };
+    // 40 + 10 for two mints, 15 for the minted supply, 30 + 2 * 5 for three insiders
+    assert_eq!(factors.score_with(&weights), 100.0);
+    assert_eq!(RiskFactors { freeze_events: 1, ..RiskFactors::default() }.score_with(&weights), 50.0);
+    assert_eq!(RiskFactors::default().score_with(&weights), 0.0);
+}
+
+#[test]
+fn any_freeze_adds_the_flat_freeze_weight() {
+    let weights = RiskWeights::default();
+    let freezes = |freeze_events: u32| RiskFactors { freeze_events, ..RiskFactors::default() };
+
+    // One frozen account already proves the authority is used; more freezes add nothing
+    assert_eq!(freezes(1).score_with(&weights), weights.freeze_authority);
+    assert_eq!(freezes(5).score_with(&weights), weights.freeze_authority);
+
+    let custom = RiskWeights { freeze_authority: 80.0, ..RiskWeights::default() };
+    assert_eq!(freezes(1).score_with(&custom), 80.0);
+}
+
+#[test]
+fn recorded_freezes_stack_with_other_signals_up_to_the_cap() {
+    let mint = "FreezeTest1111";
+    update_factors(mint, |f| f.freeze_events += 1);
+    update_factors(mint, |f| f.freeze_events += 1);
+    update_factors(mint, |f| f.unexpected_mints += 1);
+
+    let factors = get_factors(mint);
+    assert_eq!(factors.freeze_events, 2);
+    // 50 for the freezes, 40 for the unexpected mint
+    assert_eq!(factors.score_with(&RiskWeights::default()), 90.0);
+
+    update_factors(mint, |f| f.insider_wallets += 1);
+    assert_eq!(get_factors(mint).score_with(&RiskWeights::default()), 100.0);
+}
+
+#[test]
+fn explanation_lists_each_feature_contribution() {
+    let factors = RiskFactors { liquidity_removed_pct: 20.0, creator_cex_deposits: 1, ..RiskFactors::default() };
+    let weights = RiskWeights { cex_deposits: 10.0, ..RiskWeights::default() };
+
+    let contributions = factors.explain(&weights);
+    let points = |feature: &str| contributions.iter().find(|c| c.feature == feature).unwrap().points;
+    assert_eq!(points("liquidity_removed"), 20.0);
+    assert_eq!(points("cex_deposits"), 10.0);
+    assert_eq!(points("freeze_authority"), 0.0);
+
+    let text = format_explanation("Mint111", &factors, &weights);
+    assert!(text.contains("Risk score 30/100"));
+    assert!(text.contains("liquidity_removed (20.0% of pool removed): +20.0"));
+    assert!(!text.contains("freeze_authority"));
+}
+
+#[test]
+fn weights_file_overrides_only_the_fields_it_sets() {
+    let path = write_weights("partial", r#"{ "freeze_authority": 80, "insiders": 10 }"#);
+    let weights = RiskWeights::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(weights.freeze_authority, 80.0);
+    assert_eq!(weights.insiders, 10.0);
+    assert_eq!(weights.mint_authority, RiskWeights::default().mint_authority);
+}
+
+#[test]
+fn invalid_weights_files_are_rejected() {
+    let typo = write_weights("typo", r#"{ "freez_authority": 80 }"#);
+    let negative = write_weights("negative", r#"{ "cex_deposits": -5, "minted_supply_cap": 250 }"#);
+
+    let typo_error = RiskWeights::load(&typo).unwrap_err().to_string();
+    let negative_error = RiskWeights::load(&negative).unwrap_err().to_string();
+    std::fs::remove_file(&typo).ok();
+    std::fs::remove_file(&negative).ok();
+
+    assert!(typo_error.contains("freez_authority"));
+    assert!(negative_error.contains("cex_deposits must be a non-negative number"));
+    assert!(negative_error.contains("minted_supply_cap must be at most 100"));
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use common::{config, parsed_trade, MockRpc};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::library::rolling::{RollingStats, RollingWindow, VolumeBuckets, VolumeTotals};
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::transaction_parser::{ParsedData, SwapType};
+
+fn at(seconds: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(1_750_000_000 + seconds, 0).unwrap()
+}
+
+fn trade(mint: Pubkey, swap_type: SwapType, sol: f64) -> ParsedData {
+    parsed_trade(mint)
+        .dex("PumpSwap")
+        .swap_type(swap_type)
+        .sol(sol)
+        .price(0.001)
+        .liquidity(50.0)
+        .name("Roll")
+        .symbol("RLL")
+        .build()
+}
+
+#[test]
+fn statistics_cover_the_window_only() {
+    let mut window = RollingWindow::new(Duration::seconds(60));
+    window.push(at(0), 100.0);
+    window.push(at(30), 2.0);
+    window.push(at(40), 4.0);
+    window.push(at(50), 6.0);
+    // Out of order, still inside the window
+    window.push(at(35), 8.0);
+    window.push(at(61), 10.0);
+
+    assert_eq!(window.len(), 5);
+    assert_eq!(window.sum(), 30.0);
+    assert_eq!(window.sum_since(at(40)), 16.0);
+    assert_eq!(window.sum_between(at(30), at(50)), 18.0);
+    assert_eq!(window.mean(), Some(6.0));
+    assert!((window.stddev().unwrap() - 8.0_f64.sqrt()).abs() < 1e-9);
+    assert_eq!(window.ema(1.0), Some(10.0));
+    assert_eq!(window.ema(0.5), Some(7.625));
+
+    window.evict(at(120));
+    assert!(window.is_empty());
+    assert_eq!(window.mean(), None);
+}
+
+#[test]
+fn vwap_weights_prices_by_volume_and_z_score_needs_enough_samples() {
+    let mut window = RollingWindow::new(Duration::hours(1)).with_capacity(3);
+    window.push_weighted(at(0), 1.0, 9.0);
+    window.push_weighted(at(1), 2.0, 1.0);
+    assert_eq!(window.vwap(), Some(1.1));
+    window.push_weighted(at(2), 3.0, 0.0);
+    window.push_weighted(at(3), 4.0, 0.0);
+    assert_eq!(window.len(), 3);
+    assert_eq!(window.vwap(), Some(2.0));
+
+    assert_eq!(window.z_score(3.0, 5), None);
+    assert_eq!(window.z_score(3.0, 3), Some(0.0));
+
+    let mut stats = RollingStats::new(Duration::seconds(10));
+    stats.push("a", at(0), 1.0);
+    stats.push("b", at(8), 1.0);
+    stats.evict(at(12));
+    assert_eq!(stats.len(), 1);
+    assert!(stats.get(&"a").is_none());
+}
+
+#[tokio::test]
+async fn monitor_volume_rolls_off_after_a_day() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), None).with_clock(clock.clone());
+    let mint = Pubkey::new_unique();
+
+    monitor.process_for_education(&trade(mint, SwapType::Buy, 2.0)).await.unwrap();
+    clock.advance(Duration::hours(23));
+    monitor.process_for_education(&trade(mint, SwapType::Sell, 3.0)).await.unwrap();
+    let token = monitor.get_token(&mint).await.unwrap();
+    assert_eq!((token.volume_24h, token.buy_volume_24h, token.sell_volume_24h), (5.0, 2.0, 3.0));
+    assert_eq!(token.volume_1h, 3.0);
+
+    clock.advance(Duration::hours(2));
+    monitor.process_for_education(&trade(mint, SwapType::Buy, 1.0)).await.unwrap();
+    let token = monitor.get_token(&mint).await.unwrap();
+    assert_eq!((token.volume_24h, token.buy_volume_24h, token.sell_volume_24h), (4.0, 1.0, 3.0));
+    assert_eq!(token.volume_1h, 1.0);
+    assert_eq!(token.total_volume, 6.0);
+}
+
+#[test]
+fn volume_buckets_slide_by_the_minute() {
+    // at(0) is 40s into a minute
+    let mut volumes = VolumeBuckets::new(Duration::minutes(1), Duration::hours(1));
+    volumes.record(at(0), 1.0, true);
+    volumes.record(at(30), 2.0, false);
+    volumes.record(at(39), 0.5, true);
+    volumes.record(at(-300), 4.0, true);
+
+    assert_eq!(volumes.totals(at(40), Duration::minutes(1)), VolumeTotals { buy: 1.5, sell: 2.0 });
+    assert_eq!(volumes.totals(at(40), Duration::hours(1)), VolumeTotals { buy: 5.5, sell: 2.0 });
+    assert_eq!(volumes.totals(at(40), Duration::hours(1)).total(), 7.5);
+    assert_eq!(volumes.totals_between(at(-400), at(-100)), VolumeTotals { buy: 4.0, sell: 0.0 });
+
+    // A bucket stops counting once it ended before the window
+    assert_eq!(volumes.totals(at(3_630), Duration::hours(1)), VolumeTotals { buy: 0.5, sell: 2.0 });
+    volumes.evict(at(3_700));
+    assert!(volumes.is_empty());
+}
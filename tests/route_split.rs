@@ -0,0 +1,70 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::route_split::{plan_split, should_split, SellVenue, SplitConfig};
+
+fn venue(sol: u64, tokens: u64) -> SellVenue {
+    SellVenue { pool_id: Pubkey::new_unique(), sol_reserve: sol, token_reserve: tokens, fee_bps: 25 }
+}
+
+fn enabled() -> SplitConfig {
+    SplitConfig { enabled: true, ..Default::default() }
+}
+
+fn marginal_price(venue: &SellVenue, sold: u64) -> f64 {
+    let y = venue.token_reserve as f64 + sold as f64;
+    venue.sol_reserve as f64 * venue.token_reserve as f64 / (y * y)
+}
+
+#[test]
+fn equal_pools_share_the_order_evenly() {
+    let pools = vec![venue(100_000_000_000, 1_000_000_000_000), venue(100_000_000_000, 1_000_000_000_000)];
+    let legs = plan_split(200_000_000_000, &pools, &enabled());
+
+    assert_eq!(legs.len(), 2);
+    assert_eq!(legs.iter().map(|l| l.token_amount).sum::<u64>(), 200_000_000_000);
+    assert!(legs[0].token_amount.abs_diff(legs[1].token_amount) <= 1);
+}
+
+#[test]
+fn split_equalizes_marginal_prices_and_beats_a_single_pool() {
+    let pools = vec![venue(300_000_000_000, 3_000_000_000_000), venue(100_000_000_000, 1_200_000_000_000)];
+    let amount = 500_000_000_000;
+    let legs = plan_split(amount, &pools, &enabled());
+
+    assert_eq!(legs.len(), 2);
+    assert_eq!(legs[0].venue, pools[0]);
+    let (a, b) = (marginal_price(&legs[0].venue, legs[0].token_amount), marginal_price(&legs[1].venue, legs[1].token_amount));
+    assert!((a - b).abs() / a < 1e-6, "{} vs {}", a, b);
+
+    let split_out: u64 = legs.iter().map(|l| l.expected_sol).sum();
+    assert!(split_out > pools[0].expected_sol_out(amount));
+}
+
+#[test]
+fn pools_priced_below_the_rest_get_nothing() {
+    // The second pool's price is far below where the first ends up
+    let pools = vec![venue(100_000_000_000, 1_000_000_000_000), venue(1_000_000_000, 100_000_000_000)];
+    let legs = plan_split(50_000_000_000, &pools, &enabled());
+
+    assert_eq!(legs.len(), 1);
+    assert_eq!((legs[0].venue.pool_id, legs[0].token_amount), (pools[0].pool_id, 50_000_000_000));
+}
+
+#[test]
+fn leg_count_is_capped() {
+    let pools: Vec<SellVenue> = (0..6).map(|_| venue(50_000_000_000, 500_000_000_000)).collect();
+    let config = SplitConfig { max_legs: 3, ..enabled() };
+    let legs = plan_split(600_000_000_000, &pools, &config);
+
+    assert_eq!(legs.len(), 3);
+    assert_eq!(legs.iter().map(|l| l.token_amount).sum::<u64>(), 600_000_000_000);
+}
+
+#[test]
+fn small_sells_are_not_split() {
+    let pools = vec![venue(100_000_000_000, 1_000_000_000_000), venue(80_000_000_000, 800_000_000_000)];
+
+    assert!(!should_split(10_000_000_000, &pools, &enabled()));
+    assert!(should_split(100_000_000_000, &pools, &enabled()));
+    assert!(!should_split(100_000_000_000, &pools, &SplitConfig::default()));
+    assert!(!should_split(100_000_000_000, &pools[..1], &enabled()));
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::mute_list;
+use solana_vntr_sniper::processor::scam_lists::{
+    parse_list, parse_list_urls, sync_lists, Blacklist, EntryKind, ListFetcher, MergeStats, ScamListSource,
+};
+
+/// Serves fixed bodies per URL; unknown URLs fail
+struct StaticLists(HashMap<String, String>);
+
+#[async_trait]
+impl ListFetcher for StaticLists {
+    async fn fetch(&self, url: &str) -> Result<String> {
+        self.0.get(url).cloned().ok_or_else(|| anyhow!("HTTP 503"))
+    }
+}
+
+fn source(name: &str) -> ScamListSource {
+    ScamListSource { name: name.to_string(), url: format!("https://lists.example/{}", name) }
+}
+
+fn addresses(n: usize) -> Vec<String> {
+    (0..n).map(|_| Pubkey::new_unique().to_string()).collect()
+}
+
+#[test]
+fn list_urls_are_named_pairs() {
+    let sources = parse_list_urls("RugWatch=https://rugwatch.example/list.json, broken, ScamDB=https://scamdb.example/tokens.txt");
+    assert_eq!(sources.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), vec!["RugWatch", "ScamDB"]);
+    assert_eq!(sources[1].url, "https://scamdb.example/tokens.txt");
+}
+
+#[test]
+fn lists_parse_from_json_and_text() {
+    let a = addresses(3);
+    let json = format!(r#"["{}", {{"mint": "{}"}}, {{"address": "{}", "kind": "creator"}}, "not-an-address"]"#, a[0], a[1], a[2]);
+    assert_eq!(
+        parse_list(&json).unwrap(),
+        vec![(a[0].clone(), EntryKind::Token), (a[1].clone(), EntryKind::Token), (a[2].clone(), EntryKind::Creator)]
+    );
+
+    let text = format!("# community list\n{}\ncreator:{}  # rugged 3 launches\n\ntoken: {}\n", a[0], a[1], a[2]);
+    assert_eq!(
+        parse_list(&text).unwrap(),
+        vec![(a[0].clone(), EntryKind::Token), (a[1].clone(), EntryKind::Creator), (a[2].clone(), EntryKind::Token)]
+    );
+
+    assert_eq!(parse_list("[]").unwrap(), vec![]);
+    assert!(parse_list("<html>Service Unavailable</html>").is_err());
+}
+
+#[test]
+fn merges_keep_the_lists_that_flag_each_address() {
+    let blacklist = Blacklist::new(None);
+    let a = addresses(3);
+    let tokens = |list: &[&String]| list.iter().map(|a| (a.to_string(), EntryKind::Token)).collect::<Vec<_>>();
+
+    blacklist.merge("RugWatch", &tokens(&[&a[0], &a[1]]));
+    assert_eq!(blacklist.merge("ScamDB", &tokens(&[&a[1], &a[2]])), MergeStats { listed: 2, added: 1, removed: 0 });
+    assert_eq!(blacklist.get(&a[1]).unwrap().sources, vec!["RugWatch", "ScamDB"]);
+    assert_eq!(blacklist.get(&a[1]).unwrap().describe(), "token flagged by RugWatch, ScamDB");
+
+    // RugWatch drops a[0] and a[1]: a[0] is gone, a[1] is still flagged by ScamDB
+    assert_eq!(blacklist.merge("RugWatch", &[]), MergeStats { listed: 0, added: 0, removed: 1 });
+    assert!(blacklist.get(&a[0]).is_none());
+    assert_eq!(blacklist.get(&a[1]).unwrap().sources, vec!["ScamDB"]);
+    assert_eq!(blacklist.len(), 2);
+}
+
+#[test]
+fn tokens_are_flagged_through_their_creator() {
+    let blacklist = Blacklist::new(None);
+    let (mint, creator) = (Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string());
+    blacklist.merge("RugWatch", &[(creator.clone(), EntryKind::Creator)]);
+    assert!(blacklist.flag_for(&mint).is_none());
+
+    mute_list::remember_creator(&mint, &creator);
+    let entry = blacklist.flag_for(&mint).unwrap();
+    assert_eq!(entry.address, creator);
+    assert_eq!(entry.describe(), "creator flagged by RugWatch");
+}
+
+#[test]
+fn the_blacklist_survives_restarts() {
+    let path = std::env::temp_dir().join(format!("blacklist_{}.json", Pubkey::new_unique()));
+    let token = Pubkey::new_unique().to_string();
+    Blacklist::new(Some(path.clone())).merge("ScamDB", &[(token.clone(), EntryKind::Token)]);
+
+    let reloaded = Blacklist::new(Some(path.clone()));
+    assert_eq!(reloaded.get(&token).unwrap().sources, vec!["ScamDB"]);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn a_list_that_fails_keeps_its_entries() {
+    let blacklist = Blacklist::new(None);
+    let (rug, scam) = (source("RugWatch"), source("ScamDB"));
+    let a = addresses(2);
+    let lists = StaticLists(HashMap::from([
+        (rug.url.clone(), a[0].clone()),
+        (scam.url.clone(), format!("[\"{}\"]", a[1])),
+    ]));
+    let results = sync_lists(&blacklist, &lists, &[rug.clone(), scam.clone()]).await;
+    assert!(results.iter().all(|(_, result)| result.is_ok()));
+    assert_eq!(blacklist.len(), 2);
+
+    // RugWatch goes down and ScamDB serves an error page
+    let lists = StaticLists(HashMap::from([(scam.url.clone(), "Bad gateway".to_string())]));
+    let results = sync_lists(&blacklist, &lists, &[rug, scam]).await;
+    assert!(results.iter().all(|(_, result)| result.is_err()));
+    assert_eq!(blacklist.get(&a[0]).unwrap().sources, vec!["RugWatch"]);
+    assert_eq!(blacklist.get(&a[1]).unwrap().sources, vec!["ScamDB"]);
+}
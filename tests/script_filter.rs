@@ -0,0 +1,62 @@
+mod common;
+
+use common::parsed_trade;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::script_filter::{ScriptFilter, ScriptHook};
+use solana_vntr_sniper::processor::transaction_parser::{ParsedData, SwapType};
+
+fn trade(dex: &str, swap_type: SwapType, sol_amount: f64) -> ParsedData {
+    parsed_trade(Pubkey::new_unique())
+        .dex(dex)
+        .swap_type(swap_type)
+        .sol(sol_amount)
+        .price(0.000001)
+        .liquidity(None)
+        .name("Scripted")
+        .progress(35.0)
+        .build()
+}
+
+#[test]
+fn expressions_see_the_event() {
+    let filter = ScriptFilter::compile(r#"sol_amount > 5.0 && dex == "PumpFun" && risk_score < 40.0"#).unwrap();
+    assert!(filter.allows(&trade("PumpFun", SwapType::Buy, 8.0)).unwrap());
+    assert!(!filter.allows(&trade("PumpFun", SwapType::Buy, 2.0)).unwrap());
+    assert!(!filter.allows(&trade("PumpSwap", SwapType::Buy, 8.0)).unwrap());
+
+    let filter = ScriptFilter::compile(r#"side == "sell" && progress >= 30.0 && symbol == "" && liquidity == 0.0"#).unwrap();
+    assert!(filter.allows(&trade("PumpFun", SwapType::Sell, 1.0)).unwrap());
+    assert!(!filter.allows(&trade("PumpFun", SwapType::Buy, 1.0)).unwrap());
+}
+
+#[test]
+fn a_script_must_yield_a_boolean() {
+    let filter = ScriptFilter::compile("sol_amount * 2.0").unwrap();
+    assert!(filter.allows(&trade("PumpFun", SwapType::Buy, 1.0)).is_err());
+    assert!(ScriptFilter::compile("sol_amount >").is_err());
+}
+
+#[test]
+fn runaway_scripts_are_stopped() {
+    let filter = ScriptFilter::compile("let n = 0; loop { n += 1; } true").unwrap();
+    assert!(filter.allows(&trade("PumpFun", SwapType::Buy, 1.0)).is_err());
+}
+
+#[test]
+fn alerts_fail_open_and_trades_fail_closed() {
+    let event = trade("PumpFun", SwapType::Buy, 1.0);
+    let broken = ScriptHook::from_source(ScriptFilter::compile("sol_amount >"));
+    assert!(broken.alert_allowed(&event));
+    assert!(broken.trade_refusal(&event).unwrap().contains("does not compile"));
+
+    let failing = ScriptHook::from_source(ScriptFilter::compile("undefined_variable > 1"));
+    assert!(failing.alert_allowed(&event));
+    assert!(failing.trade_refusal(&event).is_some());
+
+    let rejecting = ScriptHook::from_source(ScriptFilter::compile("sol_amount > 5.0"));
+    assert!(!rejecting.alert_allowed(&event));
+    assert!(rejecting.trade_refusal(&event).unwrap().contains("sol_amount > 5.0"));
+
+    assert!(ScriptHook::Off.alert_allowed(&event));
+    assert!(ScriptHook::Off.trade_refusal(&event).is_none());
+}
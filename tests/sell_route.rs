@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use solana_program_pack::Pack;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use spl_token_2022::extension::transfer_hook::TransferHook;
+use spl_token_2022::extension::{BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut};
+use spl_token_2022::state::{AccountState, Mint};
+use solana_vntr_sniper::common::cluster::DexProgram;
+use solana_vntr_sniper::dex::pump_fun::get_pda;
+use solana_vntr_sniper::dex::pump_swap::get_canonical_pool_pda;
+use solana_vntr_sniper::library::account_batcher::AccountFetcher;
+use solana_vntr_sniper::processor::sell_route::{RouteCheck, SellRouteConfig, SellRouteGuard};
+use solana_vntr_sniper::processor::transaction_parser::DexType;
+
+/// Serves a fixed set of accounts
+#[derive(Default)]
+struct Chain {
+    accounts: HashMap<Pubkey, Account>,
+    down: bool,
+}
+
+impl Chain {
+    fn with(mut self, key: Pubkey, account: Account) -> Self {
+        self.accounts.insert(key, account);
+        self
+    }
+}
+
+#[async_trait]
+impl AccountFetcher for Chain {
+    async fn get_multiple_accounts(&self, keys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        if self.down {
+            return Err(anyhow!("rpc down"));
+        }
+        Ok(keys.iter().map(|key| self.accounts.get(key).cloned()).collect())
+    }
+}
+
+fn classic_mint() -> Account {
+    let mut data = vec![0; Mint::LEN];
+    Mint { decimals: 6, supply: 1_000_000, is_initialized: true, ..Default::default() }.pack_into_slice(&mut data);
+    Account { owner: spl_token::id(), data, ..Default::default() }
+}
+
+fn hooked_mint(hook_program: Pubkey) -> Account {
+    let len = ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferHook]).unwrap();
+    let mut data = vec![0; len];
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+    let hook = state.init_extension::<TransferHook>(true).unwrap();
+    hook.program_id = Some(hook_program).try_into().unwrap();
+    state.base = Mint { decimals: 6, supply: 1_000_000, is_initialized: true, ..Default::default() };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    Account { owner: spl_token_2022::id(), data, ..Default::default() }
+}
+
+fn curve(complete: bool) -> Account {
+    let mut data = vec![0; 81];
+    data[8..16].copy_from_slice(&900_000_000_000_000u64.to_le_bytes());
+    data[48] = complete as u8;
+    Account { owner: DexProgram::PumpFun.id(), data, ..Default::default() }
+}
+
+fn pump_swap_pool(base_vault: Pubkey, quote_vault: Pubkey) -> Account {
+    let mut data = vec![0; 300];
+    data[139..171].copy_from_slice(base_vault.as_ref());
+    data[171..203].copy_from_slice(quote_vault.as_ref());
+    Account { owner: DexProgram::PumpSwap.id(), data, ..Default::default() }
+}
+
+fn vault(amount: u64, state: AccountState) -> Account {
+    let mut data = vec![0; spl_token_2022::state::Account::LEN];
+    spl_token_2022::state::Account { amount, state, ..Default::default() }.pack_into_slice(&mut data);
+    Account { owner: spl_token::id(), data, ..Default::default() }
+}
+
+fn guard(chain: Chain, strict: bool) -> SellRouteGuard {
+    SellRouteGuard::new(SellRouteConfig { enabled: true, strict }, Arc::new(chain))
+}
+
+/// A mint with a funded PumpSwap pool
+fn pool_chain(base: Account, quote: Account) -> (Chain, Pubkey, Pubkey) {
+    let (mint, pool) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (base_vault, quote_vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let chain = Chain::default()
+        .with(mint, classic_mint())
+        .with(pool, pump_swap_pool(base_vault, quote_vault))
+        .with(base_vault, base)
+        .with(quote_vault, quote);
+    (chain, mint, pool)
+}
+
+#[tokio::test]
+async fn a_trading_curve_is_sellable() {
+    let mint = Pubkey::new_unique();
+    let chain = Chain::default()
+        .with(mint, classic_mint())
+        .with(get_pda(&mint, &DexProgram::PumpFun.id()).unwrap(), curve(false));
+
+    let check = guard(chain, false).check(&DexType::PumpFun, &mint.to_string(), "").await;
+    assert!(matches!(check, RouteCheck::Sellable(_)), "{:?}", check);
+}
+
+#[tokio::test]
+async fn a_complete_curve_needs_its_migrated_pool() {
+    let mint = Pubkey::new_unique();
+    let curve_address = get_pda(&mint, &DexProgram::PumpFun.id()).unwrap();
+    let chain = Chain::default().with(mint, classic_mint()).with(curve_address, curve(true));
+    let check = guard(chain, false).check(&DexType::PumpFun, &mint.to_string(), "").await;
+    assert!(matches!(&check, RouteCheck::Blocked(reason) if reason.contains("complete")), "{:?}", check);
+
+    let (base_vault, quote_vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let chain = Chain::default()
+        .with(mint, classic_mint())
+        .with(curve_address, curve(true))
+        .with(get_canonical_pool_pda(&mint), pump_swap_pool(base_vault, quote_vault))
+        .with(base_vault, vault(1_000, AccountState::Initialized))
+        .with(quote_vault, vault(1_000, AccountState::Initialized));
+    let check = guard(chain, false).check(&DexType::PumpFun, &mint.to_string(), "").await;
+    assert!(matches!(check, RouteCheck::Sellable(_)), "{:?}", check);
+}
+
+#[tokio::test]
+async fn missing_curves_and_pools_block_the_buy() {
+    let mint = Pubkey::new_unique();
+    let chain = Chain::default().with(mint, classic_mint());
+    let check = guard(chain, false).check(&DexType::PumpFun, &mint.to_string(), "").await;
+    assert!(matches!(check, RouteCheck::Blocked(_)), "{:?}", check);
+
+    let chain = Chain::default().with(mint, classic_mint());
+    let result = guard(chain, false).vet(&DexType::PumpSwap, &mint.to_string(), &Pubkey::new_unique().to_string()).await;
+    assert!(result.unwrap_err().contains("not initialized"));
+}
+
+#[tokio::test]
+async fn a_pool_needs_funded_unfrozen_vaults() {
+    let (chain, mint, pool) = pool_chain(vault(1_000, AccountState::Initialized), vault(5_000, AccountState::Initialized));
+    assert_eq!(guard(chain, false).vet(&DexType::PumpSwap, &mint.to_string(), &pool.to_string()).await, Ok(()));
+
+    let (chain, mint, pool) = pool_chain(vault(1_000, AccountState::Initialized), vault(0, AccountState::Initialized));
+    let result = guard(chain, false).vet(&DexType::PumpSwap, &mint.to_string(), &pool.to_string()).await;
+    assert!(result.unwrap_err().contains("quote vault"));
+
+    let (chain, mint, pool) = pool_chain(vault(1_000, AccountState::Frozen), vault(5_000, AccountState::Initialized));
+    let result = guard(chain, false).vet(&DexType::PumpSwap, &mint.to_string(), &pool.to_string()).await;
+    assert!(result.unwrap_err().contains("frozen"));
+}
+
+#[tokio::test]
+async fn a_transfer_hook_blocks_the_buy() {
+    let hook_program = Pubkey::new_unique();
+    let (chain, mint, pool) = pool_chain(vault(1_000, AccountState::Initialized), vault(5_000, AccountState::Initialized));
+    let chain = chain.with(mint, hooked_mint(hook_program));
+
+    let result = guard(chain, false).vet(&DexType::PumpSwap, &mint.to_string(), &pool.to_string()).await;
+    assert!(result.unwrap_err().contains(&hook_program.to_string()));
+}
+
+#[tokio::test]
+async fn unverifiable_routes_are_refused_only_when_strict() {
+    let mint = Pubkey::new_unique().to_string();
+    let down = || Chain { down: true, ..Chain::default() };
+
+    assert_eq!(guard(down(), false).vet(&DexType::PumpFun, &mint, "").await, Ok(()));
+    assert!(guard(down(), true).vet(&DexType::PumpFun, &mint, "").await.unwrap_err().contains("rpc down"));
+
+    let disabled = SellRouteGuard::new(SellRouteConfig { enabled: false, strict: true }, Arc::new(down()));
+    assert_eq!(disabled.vet(&DexType::PumpFun, &mint, "").await, Ok(()));
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use common::{config, parsed_trade, MockRpc, RecordingSink};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::opportunity_signal::{MetricsSnapshot, OpportunityKind, OpportunitySignal};
+use solana_vntr_sniper::processor::signal_bus::{Signal, SignalBus, SignalConsumer, SignalRecorder};
+use solana_vntr_sniper::processor::signal_outcomes::SignalDirection;
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+use solana_vntr_sniper::processor::transaction_parser::{ParsedData, SwapType};
+
+fn trade(mint: Pubkey, swap_type: SwapType, sol: f64) -> ParsedData {
+    parsed_trade(mint)
+        .swap_type(swap_type)
+        .sol(sol)
+        .name("Bus")
+        .symbol("BUS")
+        .progress(40.0)
+        .build()
+}
+
+fn opportunity(mint: Pubkey) -> Signal {
+    Signal::Opportunity(OpportunitySignal::new(
+        OpportunityKind::LargeTransaction,
+        mint,
+        Some("Bus".to_string()),
+        SignalDirection::Bullish,
+        "Transaction size: 25 SOL - Whale activity detected".to_string(),
+        MetricsSnapshot::default(),
+    ).with_evidence("trade size (SOL)", 25.0, Some(10.0)))
+}
+
+#[derive(Default)]
+struct Collector {
+    kinds: Mutex<Vec<&'static str>>,
+}
+
+#[async_trait]
+impl SignalConsumer for Collector {
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    async fn consume(&self, signal: &Signal) -> Result<()> {
+        self.kinds.lock().unwrap().push(signal.kind());
+        Ok(())
+    }
+}
+
+struct Failing;
+
+#[async_trait]
+impl SignalConsumer for Failing {
+    fn name(&self) -> &str {
+        "failing"
+    }
+
+    async fn consume(&self, _signal: &Signal) -> Result<()> {
+        Err(anyhow!("consumer is down"))
+    }
+}
+
+#[tokio::test]
+async fn consumers_subscribe_to_the_monitors_detections() {
+    let sink = Arc::new(RecordingSink::default());
+    let telegram = TelegramAlertSystem::with_sink(sink.clone(), true);
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), Some(Arc::new(telegram)));
+    let collector = Arc::new(Collector::default());
+    monitor.signals().subscribe(collector.clone());
+    assert_eq!(monitor.signals().consumers(), vec!["telegram", "collector"]);
+
+    monitor.process_for_education(&trade(Pubkey::new_unique(), SwapType::Buy, 25.0)).await.unwrap();
+
+    let kinds = collector.kinds.lock().unwrap().clone();
+    assert!(kinds.contains(&"wallet_activity"), "{:?}", kinds);
+    assert!(kinds.contains(&"new_token"), "{:?}", kinds);
+    assert!(kinds.contains(&"opportunity"), "{:?}", kinds);
+    assert!(sink.texts().iter().any(|t| t.contains("WALLET ACTIVITY")));
+}
+
+#[tokio::test]
+async fn detection_runs_without_telegram() {
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), None);
+    let collector = Arc::new(Collector::default());
+    monitor.signals().subscribe(collector.clone());
+
+    monitor.process_for_education(&trade(Pubkey::new_unique(), SwapType::Sell, 0.1)).await.unwrap();
+
+    assert_eq!(collector.kinds.lock().unwrap().clone(), vec!["wallet_activity"]);
+}
+
+#[tokio::test]
+async fn a_failing_consumer_does_not_stop_the_others() {
+    let bus = SignalBus::new();
+    let collector = Arc::new(Collector::default());
+    bus.subscribe(Arc::new(Failing));
+    bus.subscribe(collector.clone());
+
+    bus.publish(opportunity(Pubkey::new_unique())).await;
+    bus.publish(opportunity(Pubkey::new_unique())).await;
+
+    assert_eq!(collector.kinds.lock().unwrap().len(), 2);
+    assert_eq!(bus.published(), 2);
+}
+
+#[tokio::test]
+async fn the_recorder_appends_one_line_per_signal() {
+    let path = std::env::temp_dir().join(format!("signals_{}.jsonl", Pubkey::new_unique()));
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let bus = SignalBus::new();
+    bus.subscribe(Arc::new(SignalRecorder::new(path.clone()).with_clock(clock)));
+    let mint = Pubkey::new_unique();
+
+    bus.publish(opportunity(mint)).await;
+    bus.publish(Signal::GraduationImminent { mint, token_name: None, progress: 96.5 }).await;
+
+    let lines: Vec<serde_json::Value> = std::fs::read_to_string(&path).unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["at"], 1_750_000_000);
+    assert_eq!(lines[0]["kind"], "opportunity");
+    assert_eq!(lines[0]["mint"], mint.to_string());
+    assert_eq!(lines[0]["direction"], "bullish");
+    assert!(lines[0]["detail"].as_str().unwrap().starts_with("Large Transaction"));
+    assert_eq!(lines[0]["opportunity"]["kind"], "large_transaction");
+    assert_eq!(lines[0]["opportunity"]["evidence"][0]["threshold"], 10.0);
+    assert!(lines[1].get("token_name").is_none());
+    let _ = std::fs::remove_file(path);
+}
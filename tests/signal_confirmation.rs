@@ -0,0 +1,95 @@
+use std::sync::Arc;
+use chrono::Duration;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::library::rolling::VolumeTotals;
+use solana_vntr_sniper::processor::risk_score;
+use solana_vntr_sniper::processor::signal_bus::{Signal, SignalConsumer};
+use solana_vntr_sniper::processor::signal_confirmation::{
+    source_of, ConfirmationConfig, SignalConfirmation, ACCEPTABLE_RISK, TARGET_BUY, TRENDING,
+};
+
+fn confirmation(config: ConfirmationConfig) -> (SignalConfirmation, Arc<SimulatedClock>) {
+    let clock = Arc::new(SimulatedClock::at_unix(1_700_000_000));
+    (SignalConfirmation::new(config).with_clock(clock.clone()), clock)
+}
+
+fn needing(min_signals: usize) -> ConfirmationConfig {
+    ConfirmationConfig { min_signals, ..ConfirmationConfig::default() }
+}
+
+fn price_movement(mint: Pubkey, current_price: f64) -> Signal {
+    Signal::PriceMovement {
+        mint,
+        token_name: None,
+        initial_price: 1.0,
+        current_price,
+        volume_24h: VolumeTotals::default(),
+        bonding_curve_progress: None,
+    }
+}
+
+#[test]
+fn disabled_confirmation_lets_every_buy_through() {
+    let (confirmation, _) = confirmation(needing(0));
+    assert_eq!(confirmation.confirm(&Pubkey::new_unique().to_string(), TARGET_BUY), Ok(vec![]));
+}
+
+#[test]
+fn buys_need_k_distinct_sources() {
+    let (confirmation, _) = confirmation(needing(3));
+    let mint = Pubkey::new_unique().to_string();
+
+    confirmation.record(&mint, TRENDING);
+    confirmation.record(&mint, TRENDING);
+    let error = confirmation.confirm(&mint, TARGET_BUY).unwrap_err();
+    assert!(error.starts_with("2 of 3 signals"), "{}", error);
+    assert!(error.contains("target_buy, trending"));
+
+    confirmation.record(&mint, "volume_spike");
+    assert_eq!(confirmation.confirm(&mint, TARGET_BUY).unwrap(), vec!["target_buy", "trending", "volume_spike"]);
+}
+
+#[test]
+fn signals_expire_with_the_window() {
+    let (confirmation, clock) = confirmation(ConfirmationConfig { window_secs: 60, ..needing(2) });
+    let mint = Pubkey::new_unique().to_string();
+    confirmation.record(&mint, TRENDING);
+    clock.advance(Duration::seconds(61));
+    assert!(confirmation.confirm(&mint, TARGET_BUY).is_err());
+    assert_eq!(confirmation.recent(&mint), vec!["target_buy"]);
+}
+
+#[test]
+fn only_configured_sources_count() {
+    let sources = vec![TARGET_BUY.to_string(), TRENDING.to_string()];
+    let (confirmation, _) = confirmation(ConfirmationConfig { sources, ..needing(2) });
+    let mint = Pubkey::new_unique().to_string();
+    confirmation.record(&mint, "new_token");
+    assert!(confirmation.confirm(&mint, TARGET_BUY).is_err());
+    confirmation.record(&mint, TRENDING);
+    assert!(confirmation.confirm(&mint, TARGET_BUY).is_ok());
+}
+
+#[test]
+fn a_known_low_risk_score_counts_as_a_signal() {
+    let (confirmation, _) = confirmation(needing(2));
+    let (risky, safe) = (Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string());
+    assert!(confirmation.confirm(&risky, TARGET_BUY).is_err());
+
+    risk_score::update_factors(&safe, |_| {});
+    assert_eq!(confirmation.confirm(&safe, TARGET_BUY).unwrap(), vec![ACCEPTABLE_RISK, TARGET_BUY]);
+}
+
+#[tokio::test]
+async fn published_signals_count_unless_bearish() {
+    let (confirmation, _) = confirmation(needing(2));
+    let mint = Pubkey::new_unique();
+
+    assert_eq!(source_of(&price_movement(mint, 0.5)), None);
+    confirmation.consume(&price_movement(mint, 0.5)).await.unwrap();
+    assert!(confirmation.confirm(&mint.to_string(), TARGET_BUY).is_err());
+
+    confirmation.consume(&Signal::GraduationImminent { mint, token_name: None, progress: 92.0 }).await.unwrap();
+    assert_eq!(confirmation.confirm(&mint.to_string(), TARGET_BUY).unwrap(), vec!["graduation_imminent", "target_buy"]);
+}
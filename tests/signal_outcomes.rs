@@ -0,0 +1,81 @@
+use chrono::{Duration, TimeZone, Utc};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::signal_outcomes::{aggregate_accuracy, SignalDirection, SignalOutcome, CONFIDENT_SIGNALS};
+
+/// An alert at 1.0 followed by the given (minutes, price) checkpoints
+fn outcome(signal_type: &str, direction: SignalDirection, alerted_at: chrono::DateTime<Utc>, checkpoints: &[(i64, Option<f64>)]) -> SignalOutcome {
+    let mut outcome = SignalOutcome::new(signal_type, Pubkey::new_unique(), direction, 1.0, alerted_at);
+    outcome.checkpoints = checkpoints.to_vec();
+    outcome
+}
+
+fn now() -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap()
+}
+
+#[test]
+fn accuracy_counts_returns_above_the_profit_threshold() {
+    let outcomes = vec![
+        outcome("Volume Spike", SignalDirection::Bullish, now(), &[(5, Some(1.10)), (30, Some(0.90))]),
+        outcome("Volume Spike", SignalDirection::Bullish, now(), &[(5, Some(1.01)), (30, None)]),
+    ];
+
+    let stats = aggregate_accuracy(&outcomes, now() - Duration::days(1));
+    let five = &stats["Volume Spike"][&5];
+    assert_eq!(five.measured, 2);
+    // +1% does not cover the default 2% threshold
+    assert_eq!(five.profitable, 1);
+    assert_eq!(five.accuracy_pct(), 50.0);
+    assert!((five.average_return_pct() - 5.5).abs() < 1e-9);
+
+    // Horizons without a price are not measured
+    let thirty = &stats["Volume Spike"][&30];
+    assert_eq!(thirty.measured, 1);
+    assert_eq!(thirty.profitable, 0);
+}
+
+#[test]
+fn bearish_signals_profit_when_the_price_falls() {
+    let outcomes = vec![outcome("Whale Dump", SignalDirection::Bearish, now(), &[(5, Some(0.80))])];
+
+    let accuracy = &aggregate_accuracy(&outcomes, now())["Whale Dump"][&5];
+    assert_eq!(accuracy.profitable, 1);
+    assert!((accuracy.total_return_pct - 20.0).abs() < 1e-9);
+}
+
+#[test]
+fn signals_before_the_window_are_left_out() {
+    let outcomes = vec![
+        outcome("Volume Spike", SignalDirection::Bullish, now() - Duration::days(8), &[(5, Some(1.5))]),
+        outcome("Breakout", SignalDirection::Bullish, now(), &[(5, None)]),
+    ];
+
+    let stats = aggregate_accuracy(&outcomes, now() - Duration::days(7));
+    assert!(!stats.contains_key("Volume Spike"));
+    // Alerted in the window but nothing measured yet
+    assert!(!stats.contains_key("Breakout"));
+}
+
+#[test]
+fn confident_signals_are_also_scored_together() {
+    let outcomes = vec![
+        outcome("Opportunity", SignalDirection::Bullish, now(), &[(5, Some(1.2))]).with_confidence(Some(0.9)),
+        outcome("Momentum", SignalDirection::Bullish, now(), &[(5, Some(0.9))]).with_confidence(Some(0.75)),
+        outcome("Opportunity", SignalDirection::Bullish, now(), &[(5, Some(1.5))]).with_confidence(Some(0.3)),
+    ];
+
+    let stats = aggregate_accuracy(&outcomes, now());
+    assert_eq!(stats["Opportunity"][&5].measured, 2);
+    assert_eq!(stats["Momentum"][&5].measured, 1);
+    let confident = &stats[CONFIDENT_SIGNALS][&5];
+    assert_eq!(confident.measured, 2);
+    assert_eq!(confident.profitable, 1);
+}
+
+#[test]
+fn alerts_without_a_price_are_never_measured() {
+    let mut unpriced = outcome("Volume Spike", SignalDirection::Bullish, now(), &[(5, Some(1.2))]);
+    unpriced.price_at_alert = 0.0;
+
+    assert!(aggregate_accuracy(&[unpriced], now()).is_empty());
+}
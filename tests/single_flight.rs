@@ -0,0 +1,72 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use solana_vntr_sniper::library::single_flight::{FlightStats, SingleFlight};
+
+/// A task that takes a while and counts how often it ran
+async fn slow(runs: &AtomicUsize, value: u64) -> u64 {
+    runs.fetch_add(1, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    value
+}
+
+#[tokio::test]
+async fn concurrent_callers_of_a_key_share_one_run() {
+    let flights = SingleFlight::new();
+    let runs = &AtomicUsize::new(0);
+
+    let results = futures::future::join_all((0..8).map(|i| flights.run("mint", move || slow(runs, i)))).await;
+
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    assert!(results.iter().all(|r| *r == results[0]));
+    assert_eq!(flights.stats(), FlightStats { requests: 8, runs: 1 });
+    assert_eq!(flights.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn different_keys_run_independently() {
+    let flights = SingleFlight::new();
+    let runs = AtomicUsize::new(0);
+
+    let (a, b) = tokio::join!(flights.run("a", || slow(&runs, 1)), flights.run("b", || slow(&runs, 2)));
+
+    assert_eq!((a, b), (1, 2));
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn results_are_not_cached_once_the_run_ends() {
+    let flights = SingleFlight::new();
+    let runs = AtomicUsize::new(0);
+
+    assert_eq!(flights.run("mint", || slow(&runs, 1)).await, 1);
+    assert_eq!(flights.run("mint", || slow(&runs, 2)).await, 2);
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn a_waiter_takes_over_when_the_running_caller_is_dropped() {
+    let flights = Arc::new(SingleFlight::new());
+    let runs = Arc::new(AtomicUsize::new(0));
+
+    let leader = {
+        let (flights, runs) = (flights.clone(), runs.clone());
+        tokio::spawn(async move {
+            flights.run("mint", move || async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                1
+            }).await
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let waiter = {
+        let (flights, runs) = (flights.clone(), runs.clone());
+        tokio::spawn(async move { flights.run("mint", move || async move { slow(&runs, 2).await }).await })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    leader.abort();
+
+    assert_eq!(tokio::time::timeout(Duration::from_secs(5), waiter).await.unwrap().unwrap(), 2);
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use chrono::Duration;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::library::slot_lag::{LagTransition, SlotLagConfig, SlotLagTracker};
+
+fn tracker(config: SlotLagConfig) -> (SlotLagTracker, Arc<SimulatedClock>) {
+    let clock = Arc::new(SimulatedClock::at_unix(1_700_000_000));
+    (SlotLagTracker::new(config, clock.clone()), clock)
+}
+
+fn tracker_with_stale(stale_secs: u64) -> (SlotLagTracker, Arc<SimulatedClock>) {
+    tracker(SlotLagConfig { stale_secs, ..SlotLagConfig::default() })
+}
+
+#[test]
+fn lagging_updates_delay_data_until_the_stream_catches_up() {
+    let (tracker, clock) = tracker(SlotLagConfig::default());
+    tracker.record_rpc_slot(1_000);
+    tracker.observe_stream_slot(999);
+    assert_eq!(tracker.evaluate(), None);
+
+    // Four seconds later the RPC is ten slots further than the update that just arrived
+    clock.advance(Duration::seconds(4));
+    tracker.observe_stream_slot(1_000);
+    assert_eq!(tracker.status().lag_slots, Some(10));
+    assert_eq!(tracker.evaluate(), None);
+
+    tracker.record_rpc_slot(1_040);
+    tracker.observe_stream_slot(1_015);
+    match tracker.evaluate() {
+        Some(LagTransition::Delayed { lag_slots, .. }) => assert_eq!(lag_slots, 25),
+        other => panic!("expected delayed, got {:?}", other),
+    }
+    assert!(tracker.is_delayed());
+    // Still behind but under the threshold: no flapping back
+    tracker.observe_stream_slot(1_030);
+    assert_eq!(tracker.evaluate(), None);
+
+    clock.advance(Duration::seconds(6));
+    tracker.observe_stream_slot(1_053);
+    match tracker.evaluate() {
+        Some(LagTransition::Recovered { lag_slots, delayed_secs }) => {
+            assert_eq!(lag_slots, 2);
+            assert_eq!(delayed_secs, 6);
+        }
+        other => panic!("expected recovered, got {:?}", other),
+    }
+    assert!(!tracker.is_delayed());
+    assert_eq!(tracker.status().delayed_episodes, 1);
+}
+
+#[test]
+fn older_slots_from_another_stream_do_not_count_as_lag() {
+    let (tracker, _clock) = tracker(SlotLagConfig::default());
+    tracker.record_rpc_slot(500);
+    tracker.observe_stream_slot(500);
+    tracker.observe_stream_slot(450);
+    assert_eq!(tracker.status().stream_slot, Some(500));
+    assert_eq!(tracker.evaluate(), None);
+}
+
+#[test]
+fn a_quiet_stream_recovers_and_only_counts_as_stale_when_enabled() {
+    let (tracker, clock) = tracker(SlotLagConfig::default());
+    tracker.record_rpc_slot(100);
+    tracker.observe_stream_slot(50);
+    assert!(matches!(tracker.evaluate(), Some(LagTransition::Delayed { .. })));
+
+    // No newer update for two minutes: the late sample no longer describes the stream
+    clock.advance(Duration::seconds(120));
+    assert!(matches!(tracker.evaluate(), Some(LagTransition::Recovered { .. })));
+
+    let (stale_tracker, clock) = tracker_with_stale(30);
+    stale_tracker.record_rpc_slot(100);
+    stale_tracker.observe_stream_slot(100);
+    clock.advance(Duration::seconds(31));
+    match stale_tracker.evaluate() {
+        Some(LagTransition::Delayed { reason, .. }) => assert!(reason.contains("no stream updates")),
+        other => panic!("expected stale delay, got {:?}", other),
+    }
+}
+
+#[test]
+fn no_rpc_slot_means_no_lag_estimate() {
+    let (tracker, _clock) = tracker(SlotLagConfig::default());
+    tracker.observe_stream_slot(10);
+    assert_eq!(tracker.status().lag_slots, None);
+    assert_eq!(tracker.evaluate(), None);
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use solana_vntr_sniper::processor::snipe_limiter::{LimiterConfig, SnipeLimiter, SnipeRejected};
+
+fn limiter(max_concurrent: usize, queue_timeout_ms: u64) -> SnipeLimiter {
+    SnipeLimiter::new(LimiterConfig { max_concurrent, queue_timeout: Duration::from_millis(queue_timeout_ms) })
+}
+
+#[tokio::test]
+async fn a_mint_is_bought_once_at_a_time() {
+    let limiter = limiter(3, 50);
+    let first = limiter.acquire("MintA").await.unwrap();
+    assert_eq!(limiter.acquire("MintA").await.err(), Some(SnipeRejected::MintBusy));
+    assert!(limiter.acquire("MintB").await.is_ok());
+
+    drop(first);
+    assert!(limiter.acquire("MintA").await.is_ok());
+}
+
+#[tokio::test]
+async fn queued_signals_expire_instead_of_stacking() {
+    let limiter = limiter(1, 20);
+    let _busy = limiter.acquire("MintA").await.unwrap();
+    assert_eq!(limiter.executing(), 1);
+
+    assert_eq!(limiter.acquire("MintB").await.err(), Some(SnipeRejected::Expired { waited_ms: 20 }));
+    // The expired signal released its mint
+    assert_eq!(limiter.in_flight(), 1);
+}
+
+#[tokio::test]
+async fn a_waiting_signal_runs_once_a_slot_frees_up() {
+    let limiter = std::sync::Arc::new(limiter(1, 1_000));
+    let busy = limiter.acquire("MintA").await.unwrap();
+
+    let waiting = {
+        let limiter = limiter.clone();
+        tokio::spawn(async move { limiter.acquire("MintB").await.map(|_| ()) })
+    };
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(limiter.in_flight(), 2);
+    drop(busy);
+
+    assert_eq!(waiting.await.unwrap(), Ok(()));
+    assert_eq!(limiter.in_flight(), 0);
+    assert_eq!(limiter.executing(), 0);
+}
+
+#[tokio::test]
+async fn a_cancelled_wait_releases_its_mint() {
+    let limiter = limiter(1, 1_000);
+    let _busy = limiter.acquire("MintA").await.unwrap();
+
+    // The caller gives up (e.g. a select! or timeout) while the signal waits for a slot
+    assert!(tokio::time::timeout(Duration::from_millis(20), limiter.acquire("MintB")).await.is_err());
+    assert_eq!(limiter.in_flight(), 1);
+}
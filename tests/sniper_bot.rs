@@ -1,40 +1,120 @@
-use super::*;
-use solana_vntr_sniper::processor::sniper_bot::*;
-use solana_vntr_sniper::common::config::{Config, AppState, SwapConfig};
-use solana_vntr_sniper::processor::swap::{SwapDirection, SwapProtocol, SwapInType};
-use solana_vntr_sniper::processor::transaction_parser::{DexType, TradeInfoFromToken};
-use std::sync::Arc;
+mod common;
 
-#[tokio::test]
-async fn test_execute_buy_does_not_panic() {
-    // This is a basic test to ensure that the execute_buy function can be called without panicking.
-    // It does not actually execute a buy transaction on the blockchain.
-
-    let config = Config::new().await;
-    let app_state = Arc::new(config.lock().await.app_state.clone());
-    let swap_config = Arc::new(config.lock().await.swap_config.clone());
+use std::sync::Arc;
+use common::{app_state, buy_config, MockRpc};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::quote_asset::QuoteAsset;
+use solana_vntr_sniper::error::BotError;
+use solana_vntr_sniper::library::rpc_api::SignatureStatus;
+use solana_vntr_sniper::processor::sniper_bot::{allow_reentry, execute_buy, BOUGHT_TOKEN_LIST};
+use solana_vntr_sniper::processor::swap::SwapProtocol;
+use solana_vntr_sniper::processor::transaction_parser::{DexType, TradeInfoFromToken};
 
-    let trade_info = TradeInfoFromToken {
+fn pump_fun_buy(mint: &Pubkey) -> TradeInfoFromToken {
+    TradeInfoFromToken {
         dex_type: DexType::PumpFun,
-        slot: 0,
-        signature: "".to_string(),
-        pool_id: "".to_string(),
-        mint: "So11111111111111111111111111111111111111112".to_string(),
-        timestamp: 0,
+        slot: 350_000_000,
+        signature: "target".to_string(),
+        pool_id: String::new(),
+        mint: mint.to_string(),
+        timestamp: 1_750_000_000,
         is_buy: true,
-        price: 0,
+        price: 50,
         is_reverse_when_pump_swap: false,
-        coin_creator: "".to_string(),
-        sol_change: 0.0,
-        token_change: 0.0,
-        liquidity: 0.0,
-        virtual_sol_reserves: 0,
-        virtual_token_reserves: 0,
-    };
-
-    let result = execute_buy(trade_info, app_state, swap_config, SwapProtocol::PumpFun).await;
-
-    // We don't care about the result, we just want to make sure it doesn't panic.
-    // In a real test, we would mock the dependencies and assert the result.
-    assert!(result.is_err());
+        coin_creator: Some(Pubkey::new_unique().to_string()),
+        sol_change: 0.5,
+        token_change: 10_000.0,
+        liquidity: 10.0,
+        virtual_sol_reserves: 40_000_000_000,
+        virtual_token_reserves: 800_000_000_000_000,
+        quote_asset: QuoteAsset::Sol,
+    }
+}
+
+fn set_tip() {
+    std::env::set_var("ZERO_SLOT_TIP_VALUE", "0.001");
+}
+
+#[tokio::test]
+async fn confirmed_buy_is_sent_once_and_tracked() {
+    set_tip();
+    let rpc = Arc::new(MockRpc::confirming());
+    let mint = Pubkey::new_unique();
+
+    let result = execute_buy(pump_fun_buy(&mint), Arc::new(app_state(rpc.clone())), Arc::new(buy_config(0.01)), SwapProtocol::PumpFun).await;
+
+    assert_eq!(result, Ok(()));
+    assert_eq!(rpc.sent_count(), 1);
+    assert!(BOUGHT_TOKEN_LIST.contains_key(&mint.to_string()));
+}
+
+#[tokio::test]
+async fn bought_token_is_not_bought_again() {
+    set_tip();
+    let rpc = Arc::new(MockRpc::confirming());
+    let mint = Pubkey::new_unique();
+    let state = Arc::new(app_state(rpc.clone()));
+
+    execute_buy(pump_fun_buy(&mint), state.clone(), Arc::new(buy_config(0.01)), SwapProtocol::PumpFun).await.unwrap();
+    let second = execute_buy(pump_fun_buy(&mint), state, Arc::new(buy_config(0.01)), SwapProtocol::PumpFun).await;
+
+    assert!(second.unwrap_err().to_string().contains("blacklisted"));
+    assert_eq!(rpc.sent_count(), 1);
+}
+
+#[tokio::test]
+async fn failed_landing_is_reported_and_not_tracked() {
+    set_tip();
+    let rpc = Arc::new(MockRpc::with_status(SignatureStatus::Failed("InstructionError(2, Custom(6002))".to_string())));
+    let mint = Pubkey::new_unique();
+
+    let result = execute_buy(pump_fun_buy(&mint), Arc::new(app_state(rpc.clone())), Arc::new(buy_config(0.01)), SwapProtocol::PumpFun).await;
+
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("Custom(6002)"), "{}", error);
+    assert_eq!(rpc.sent_count(), 1);
+    assert!(!BOUGHT_TOKEN_LIST.contains_key(&mint.to_string()));
+}
+
+#[tokio::test]
+async fn buy_without_blockhash_sends_nothing() {
+    set_tip();
+    let rpc = Arc::new(MockRpc::without_blockhash());
+
+    let result = execute_buy(pump_fun_buy(&Pubkey::new_unique()), Arc::new(app_state(rpc.clone())), Arc::new(buy_config(0.01)), SwapProtocol::PumpFun).await;
+
+    assert_eq!(result, Err(BotError::Other("Failed to get real-time blockhash".to_string())));
+    assert_eq!(rpc.sent_count(), 0);
+}
+
+#[tokio::test]
+async fn a_sold_token_is_bought_again_as_a_dip_reentry() {
+    set_tip();
+    let rpc = Arc::new(MockRpc::confirming());
+    let mint = Pubkey::new_unique();
+    let state = Arc::new(app_state(rpc.clone()));
+    execute_buy(pump_fun_buy(&mint), state.clone(), Arc::new(buy_config(0.01)), SwapProtocol::PumpFun).await.unwrap();
+    // Sold: the position is gone but the mint stays blacklisted
+    BOUGHT_TOKEN_LIST.remove(&mint.to_string());
+
+    assert!(allow_reentry(&mint.to_string(), 1));
+    let reentry = execute_buy(pump_fun_buy(&mint), state.clone(), Arc::new(buy_config(0.01)), SwapProtocol::PumpFun).await;
+
+    assert_eq!(reentry, Ok(()));
+    assert_eq!(rpc.sent_count(), 2);
+    assert!(BOUGHT_TOKEN_LIST.contains_key(&mint.to_string()));
+
+    // The exemption covered that one buy only
+    BOUGHT_TOKEN_LIST.remove(&mint.to_string());
+    let again = execute_buy(pump_fun_buy(&mint), state, Arc::new(buy_config(0.01)), SwapProtocol::PumpFun).await;
+    assert!(again.unwrap_err().to_string().contains("blacklisted"));
+    assert_eq!(rpc.sent_count(), 2);
+}
+
+#[test]
+fn reentries_past_the_cap_are_not_exempted() {
+    let mint = Pubkey::new_unique().to_string();
+    assert!(!allow_reentry(&mint, 0));
+    assert!(allow_reentry(&mint, 2));
+    assert!(!allow_reentry(&mint, 3));
 }
@@ -0,0 +1,171 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::Duration;
+use common::MockRpc;
+use solana_vntr_sniper::library::rpc_api::SignatureStatus;
+use solana_sdk::native_token::sol_to_lamports;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::sol_balance_monitor::{
+    parse_wallets, BalanceEvent, SolBalanceConfig, SolBalanceMonitor, SolBalances,
+};
+
+/// Balances set by the test, in SOL
+#[derive(Default)]
+struct MockBalances(Mutex<HashMap<Pubkey, f64>>);
+
+impl MockBalances {
+    fn set(&self, wallet: Pubkey, sol: f64) {
+        self.0.lock().unwrap().insert(wallet, sol);
+    }
+}
+
+#[async_trait]
+impl SolBalances for MockBalances {
+    async fn lamports(&self, wallet: &Pubkey) -> Result<u64> {
+        self.0.lock().unwrap().get(wallet).map(|sol| sol_to_lamports(*sol)).ok_or_else(|| anyhow!("unknown wallet"))
+    }
+}
+
+fn topups() -> SolBalanceConfig {
+    SolBalanceConfig { topup_enabled: true, topup_target_sol: 0.5, topup_max_per_day_sol: 1.0, ..SolBalanceConfig::default() }
+}
+
+#[tokio::test]
+async fn low_wallets_are_alerted_once_and_again_on_recovery() {
+    let wallet = Pubkey::new_unique();
+    let balances = Arc::new(MockBalances::default());
+    let monitor = SolBalanceMonitor::new(
+        SolBalanceConfig::default(),
+        vec![wallet],
+        balances.clone(),
+        Arc::new(MockRpc::confirming()),
+        None,
+    );
+
+    balances.set(wallet, 0.05);
+    assert!(matches!(monitor.check().await.as_slice(), [BalanceEvent::Low { .. }]));
+    assert!(monitor.check().await.is_empty());
+
+    balances.set(wallet, 0.3);
+    assert!(matches!(monitor.check().await.as_slice(), [BalanceEvent::Recovered { .. }]));
+    assert!(monitor.check().await.is_empty());
+}
+
+#[tokio::test]
+async fn listed_wallets_can_have_their_own_minimum() {
+    let (primary, fee_payer) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let config = SolBalanceConfig {
+        wallets: parse_wallets(&format!("{}:1.5, not-a-wallet, {}", fee_payer, primary)),
+        ..SolBalanceConfig::default()
+    };
+    assert_eq!(config.wallets.len(), 2);
+    let balances = Arc::new(MockBalances::default());
+    let monitor = SolBalanceMonitor::new(config, vec![primary], balances.clone(), Arc::new(MockRpc::confirming()), None);
+    assert_eq!(monitor.wallets(), &[(primary, 0.1), (fee_payer, 1.5)]);
+
+    balances.set(primary, 1.0);
+    balances.set(fee_payer, 1.0);
+    let events = monitor.check().await;
+    assert_eq!(events, vec![BalanceEvent::Low { wallet: fee_payer, sol: 1.0, min_sol: 1.5 }]);
+}
+
+#[tokio::test]
+async fn low_wallets_are_topped_up_from_the_treasury() {
+    let wallet = Pubkey::new_unique();
+    let treasury = Arc::new(Keypair::new());
+    let balances = Arc::new(MockBalances::default());
+    let rpc = Arc::new(MockRpc::confirming());
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let monitor = SolBalanceMonitor::new(topups(), vec![wallet, treasury.pubkey()], balances.clone(), rpc.clone(), Some(treasury.clone()))
+        .with_clock(clock.clone());
+
+    balances.set(wallet, 0.05);
+    balances.set(treasury.pubkey(), 10.0);
+    let events = monitor.check().await;
+    match events.as_slice() {
+        [BalanceEvent::Low { .. }, BalanceEvent::Refilled { amount_sol, .. }] => assert!((amount_sol - 0.45).abs() < 1e-9),
+        other => panic!("expected a refill, got {:?}", other),
+    }
+    let sent = rpc.sent.lock().unwrap()[0].clone();
+    assert_eq!(rpc.plain_sent.lock().unwrap().as_slice(), &[sent.signatures[0]]);
+    assert_eq!(sent.message.account_keys[0], treasury.pubkey());
+    assert!(sent.message.account_keys.contains(&wallet));
+
+    // The transfer has not landed yet: no second refill within the cooldown
+    assert!(monitor.check().await.is_empty());
+    assert_eq!(rpc.sent_count(), 1);
+}
+
+#[tokio::test]
+async fn no_refill_is_sent_while_trading_is_paused() {
+    let wallet = Pubkey::new_unique();
+    let balances = Arc::new(MockBalances::default());
+    let rpc = Arc::new(MockRpc::confirming());
+    let monitor = SolBalanceMonitor::new(topups(), vec![wallet], balances.clone(), rpc.clone(), Some(Arc::new(Keypair::new())))
+        .with_pause_check(|| Some("Buys paused via telegram".to_string()));
+
+    balances.set(wallet, 0.0);
+    assert!(matches!(monitor.check().await.as_slice(), [BalanceEvent::Low { .. }]));
+    assert!(monitor.check().await.is_empty());
+    assert_eq!(rpc.sent_count(), 0);
+}
+
+#[tokio::test]
+async fn refills_stop_at_the_daily_limit() {
+    let wallet = Pubkey::new_unique();
+    let treasury = Arc::new(Keypair::new());
+    let balances = Arc::new(MockBalances::default());
+    let rpc = Arc::new(MockRpc::confirming());
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let monitor = SolBalanceMonitor::new(topups(), vec![wallet], balances.clone(), rpc.clone(), Some(treasury))
+        .with_clock(clock.clone());
+
+    balances.set(wallet, 0.0);
+    monitor.check().await;
+    clock.advance(Duration::minutes(10));
+    monitor.check().await;
+    clock.advance(Duration::minutes(10));
+    let events = monitor.check().await;
+    assert_eq!(rpc.sent_count(), 2);
+    assert!(matches!(events.as_slice(), [BalanceEvent::RefillFailed { reason, .. }] if reason.contains("daily")));
+
+    // A new day resets the limit
+    clock.advance(Duration::days(1));
+    assert!(matches!(monitor.check().await.as_slice(), [BalanceEvent::Refilled { .. }]));
+}
+
+#[tokio::test]
+async fn without_a_blockhash_the_refill_fails_and_can_be_retried() {
+    let wallet = Pubkey::new_unique();
+    let balances = Arc::new(MockBalances::default());
+    let rpc = Arc::new(MockRpc::without_blockhash());
+    let monitor = SolBalanceMonitor::new(topups(), vec![wallet], balances.clone(), rpc.clone(), Some(Arc::new(Keypair::new())));
+
+    balances.set(wallet, 0.0);
+    assert!(matches!(monitor.check().await.as_slice(), [BalanceEvent::Low { .. }, BalanceEvent::RefillFailed { .. }]));
+    assert!(matches!(monitor.check().await.as_slice(), [BalanceEvent::RefillFailed { .. }]));
+    assert_eq!(rpc.sent_count(), 0);
+}
+
+#[tokio::test]
+async fn a_refill_failing_on_chain_is_not_reported_as_sent() {
+    let wallet = Pubkey::new_unique();
+    let balances = Arc::new(MockBalances::default());
+    let rpc = Arc::new(MockRpc::with_status(SignatureStatus::Failed("InsufficientFundsForFee".to_string())));
+    let monitor = SolBalanceMonitor::new(topups(), vec![wallet], balances.clone(), rpc.clone(), Some(Arc::new(Keypair::new())));
+
+    balances.set(wallet, 0.0);
+    match monitor.check().await.as_slice() {
+        [BalanceEvent::Low { .. }, BalanceEvent::RefillFailed { reason, .. }] => assert!(reason.contains("InsufficientFundsForFee"), "{}", reason),
+        other => panic!("expected a failed refill, got {:?}", other),
+    }
+    // Nothing landed, so the refill may be tried again right away
+    assert!(matches!(monitor.check().await.as_slice(), [BalanceEvent::RefillFailed { .. }]));
+    assert_eq!(rpc.sent_count(), 2);
+}
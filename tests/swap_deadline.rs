@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::swap_deadline::{DeadlineConfig, StaleSignal, SwapDeadline};
+
+const NOW: i64 = 1_750_000_000;
+
+fn deadline(max_age_slots: u64, max_age_ms: u64) -> SwapDeadline {
+    SwapDeadline::new(DeadlineConfig { max_age_slots, max_age_ms })
+        .with_clock(Arc::new(SimulatedClock::at_unix(NOW)))
+}
+
+#[test]
+fn signals_older_than_the_slot_deadline_are_abandoned() {
+    let deadline = deadline(10, 0);
+    assert_eq!(deadline.check(1_000, 0, Some(1_010)), Ok(()));
+    assert_eq!(deadline.check(1_000, 0, Some(1_011)), Err(StaleSignal::Slots { age: 11, max: 10 }));
+    // Without a known current slot the slot deadline cannot be checked
+    assert_eq!(deadline.check(1_000, 0, None), Ok(()));
+    assert_eq!(deadline.stale_signals(), 1);
+}
+
+#[test]
+fn signals_older_than_the_time_deadline_are_abandoned() {
+    let deadline = deadline(0, 3_000);
+    assert_eq!(deadline.check(1_000, (NOW - 2) as u64, Some(5_000)), Ok(()));
+    assert_eq!(
+        deadline.check(1_000, (NOW - 4) as u64, Some(5_000)),
+        Err(StaleSignal::Millis { age: 4_000, max: 3_000 })
+    );
+    // A timestamp slightly ahead of the local clock is not negative age
+    assert_eq!(deadline.check(1_000, (NOW + 1) as u64, None), Ok(()));
+    assert_eq!(deadline.stale_signals(), 1);
+}
+
+#[test]
+fn disabled_deadlines_and_unknown_signal_times_pass() {
+    let disabled = deadline(0, 0);
+    assert_eq!(disabled.check(1, 1, Some(1_000_000)), Ok(()));
+
+    let deadline = deadline(10, 3_000);
+    assert_eq!(deadline.check(0, 0, Some(1_000_000)), Ok(()));
+    assert_eq!(deadline.stale_signals(), 0);
+    assert!(StaleSignal::Slots { age: 11, max: 10 }.to_string().contains("11 slots old"));
+}
@@ -0,0 +1,46 @@
+use std::str::FromStr;
+use solana_vntr_sniper::common::config::{parse_threshold, Percent, Slots, SolAmount};
+
+#[test]
+fn percents_parse_as_points_with_or_without_a_sign() {
+    assert_eq!(Percent::from_str("15").unwrap(), Percent::from_points(15.0));
+    assert_eq!(Percent::from_str(" 15% ").unwrap().fraction(), 0.15);
+    assert!(Percent::from_str("-5").is_err());
+    assert!(Percent::from_str("NaN").is_err());
+    assert!(Percent::from_str("lots").is_err());
+}
+
+#[test]
+fn fraction_settings_reject_bare_values_above_one() {
+    assert_eq!(Percent::parse_fraction("0.3").unwrap().points(), 30.0);
+    assert_eq!(Percent::parse_fraction("30%").unwrap().points(), 30.0);
+    let err = Percent::parse_fraction("30").unwrap_err();
+    assert!(err.contains("write 30%"), "{}", err);
+}
+
+#[test]
+fn sol_amounts_take_sol_or_lamports() {
+    assert_eq!(SolAmount::from_str("1.5").unwrap().sol(), 1.5);
+    assert_eq!(SolAmount::from_str("1.5 SOL").unwrap().lamports(), 1_500_000_000);
+    assert_eq!(SolAmount::from_str("250000000 lamports").unwrap().sol(), 0.25);
+    assert!(SolAmount::from_str("0.5 lamports").is_err());
+}
+
+#[test]
+fn slots_are_whole_numbers() {
+    assert_eq!(Slots::from_str("10").unwrap(), Slots::new(10));
+    assert_eq!(Slots::from_str("10 slots").unwrap().get(), 10);
+    assert!(Slots::from_str("1.5").is_err());
+}
+
+#[test]
+fn out_of_range_thresholds_name_the_setting_and_the_range() {
+    let range = Percent::from_points(0.0)..=Percent::from_points(100.0);
+    assert_eq!(parse_threshold("BONDING_CURVE_ALERT_PCT", "90", Percent::from_str, range.clone()).unwrap().points(), 90.0);
+
+    let err = parse_threshold("BONDING_CURVE_ALERT_PCT", "150", Percent::from_str, range).unwrap_err();
+    assert_eq!(err.to_string(), "BONDING_CURVE_ALERT_PCT=150: must be between 0% and 100%");
+
+    let err = parse_threshold("SWAP_DEADLINE_SLOTS", "soon", Slots::from_str, Slots::new(0)..=Slots::new(150)).unwrap_err();
+    assert_eq!(err.reason, "soon is not a whole number of slots");
+}
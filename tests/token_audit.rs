@@ -0,0 +1,217 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_vntr_sniper::processor::copy_guard::{SellSimulation, SellSimulator};
+use solana_vntr_sniper::processor::deployer_history::{DeployerHistory, DeployerRecord};
+use solana_vntr_sniper::processor::token_audit::{
+    format_audit, parse_bonding_curve, AuditSource, CurveState, Holder, MintState, Severity, TokenAuditor,
+};
+
+const SUPPLY: u64 = 1_000_000_000_000_000;
+
+struct MockChain {
+    mint: Option<MintState>,
+    holders: Result<Vec<Holder>, String>,
+    curve: Option<CurveState>,
+}
+
+#[async_trait]
+impl AuditSource for MockChain {
+    async fn mint_state(&self, _mint: &Pubkey) -> Result<Option<MintState>> {
+        Ok(self.mint.clone())
+    }
+
+    async fn largest_holders(&self, _mint: &Pubkey) -> Result<Vec<Holder>> {
+        self.holders.clone().map_err(|e| anyhow!(e))
+    }
+
+    async fn bonding_curve(&self, _mint: &Pubkey) -> Result<Option<CurveState>> {
+        Ok(self.curve.clone())
+    }
+}
+
+/// A slow chain that counts its reads
+#[derive(Default)]
+struct CountingChain {
+    reads: AtomicUsize,
+}
+
+#[async_trait]
+impl AuditSource for CountingChain {
+    async fn mint_state(&self, _mint: &Pubkey) -> Result<Option<MintState>> {
+        self.reads.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        Ok(Some(revoked_mint()))
+    }
+
+    async fn largest_holders(&self, _mint: &Pubkey) -> Result<Vec<Holder>> {
+        self.reads.fetch_add(1, Ordering::SeqCst);
+        Ok(vec![wallet_holder(2.0)])
+    }
+
+    async fn bonding_curve(&self, _mint: &Pubkey) -> Result<Option<CurveState>> {
+        self.reads.fetch_add(1, Ordering::SeqCst);
+        Ok(None)
+    }
+}
+
+struct FixedSell(SellSimulation);
+
+#[async_trait]
+impl SellSimulator for FixedSell {
+    async fn simulate_sell(&self, _mint: &Pubkey, _holder: &Pubkey, _raw_amount: u64) -> SellSimulation {
+        self.0.clone()
+    }
+}
+
+fn revoked_mint() -> MintState {
+    MintState { token_2022: false, supply: SUPPLY, decimals: 6, mint_authority: None, freeze_authority: None }
+}
+
+fn wallet_holder(pct: f64) -> Holder {
+    Holder {
+        token_account: Pubkey::new_unique(),
+        owner: Keypair::new().pubkey(),
+        amount: (SUPPLY as f64 * pct / 100.0) as u64,
+    }
+}
+
+fn curve(creator: Option<Pubkey>) -> CurveState {
+    CurveState { address: Pubkey::new_unique(), progress: 42.0, complete: false, creator }
+}
+
+fn auditor(chain: MockChain, sell: SellSimulation) -> TokenAuditor {
+    TokenAuditor::new(Arc::new(chain), Arc::new(FixedSell(sell)))
+}
+
+fn no_history() -> DeployerHistory {
+    DeployerHistory::new(None)
+}
+
+#[tokio::test]
+async fn a_clean_pump_fun_token_has_no_red_flags() {
+    let curve = curve(None);
+    let holders = vec![
+        Holder { token_account: Pubkey::new_unique(), owner: curve.address, amount: SUPPLY / 2 },
+        wallet_holder(3.0),
+        wallet_holder(2.0),
+    ];
+    let chain = MockChain { mint: Some(revoked_mint()), holders: Ok(holders), curve: Some(curve) };
+
+    let report = auditor(chain, SellSimulation::Sellable).audit(&Pubkey::new_unique(), &no_history()).await.unwrap();
+
+    assert_eq!(report.red_flags(), 0, "{:?}", report.findings);
+    // The curve's unsold supply does not count as a holder, the unknown creator does
+    assert_eq!(report.verdict(), Severity::Yellow);
+    let text = format_audit(&report);
+    assert!(text.contains("Top 10 wallets hold 5.0% of supply"), "{}", text);
+    assert!(text.contains("(bonding curve)"), "{}", text);
+    assert!(text.contains("Simulated sell succeeded"), "{}", text);
+}
+
+#[tokio::test]
+async fn live_authorities_concentration_and_failed_sells_are_red_flags() {
+    let mint = MintState { mint_authority: Some(Pubkey::new_unique()), freeze_authority: Some(Pubkey::new_unique()), ..revoked_mint() };
+    let chain = MockChain { mint: Some(mint), holders: Ok(vec![wallet_holder(35.0)]), curve: None };
+
+    let report = auditor(chain, SellSimulation::Failed("custom program error: 0x11".to_string()))
+        .audit(&Pubkey::new_unique(), &no_history())
+        .await
+        .unwrap();
+
+    assert_eq!(report.verdict(), Severity::Red);
+    assert_eq!(report.red_flags(), 4, "{:?}", report.findings);
+    let text = format_audit(&report);
+    assert!(text.starts_with("🔍 Audit of"));
+    assert!(text.contains("HIGH RISK (4 red flags)"), "{}", text);
+    assert!(text.contains("Mint authority is live"), "{}", text);
+    assert!(text.contains("Largest wallet holds 35.0% of supply"), "{}", text);
+    assert!(text.contains("0x11"), "{}", text);
+}
+
+#[tokio::test]
+async fn the_deployers_recorded_rugs_count_against_the_token() {
+    let path = std::env::temp_dir().join(format!("audit_deployers_{}.jsonl", Pubkey::new_unique()));
+    let creator = Pubkey::new_unique();
+    let lines: Vec<String> = ["RUGGED (liquidity pulled)", "GRADUATED"].iter().map(|outcome| {
+        serde_json::to_string(&DeployerRecord {
+            creator: creator.to_string(),
+            mint: Pubkey::new_unique().to_string(),
+            outcome: Some(outcome.to_string()),
+            ..DeployerRecord::default()
+        }).unwrap()
+    }).collect();
+    std::fs::write(&path, lines.join("\n")).unwrap();
+    let chain = MockChain { mint: Some(revoked_mint()), holders: Ok(vec![wallet_holder(1.0)]), curve: Some(curve(Some(creator))) };
+
+    let report = auditor(chain, SellSimulation::Sellable).audit(&Pubkey::new_unique(), &DeployerHistory::new(Some(path.clone()))).await.unwrap();
+
+    assert_eq!(report.creator, Some(creator.to_string()));
+    assert_eq!(report.deployer.as_ref().map(|d| d.rugged), Some(1));
+    assert!(format_audit(&report).contains("Creator pulled liquidity on 1 of 2 recorded launches"));
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn checks_that_cannot_run_are_reported_not_fatal() {
+    let chain = MockChain { mint: Some(revoked_mint()), holders: Err("rate limited".to_string()), curve: None };
+
+    let report = auditor(chain, SellSimulation::Sellable).audit(&Pubkey::new_unique(), &no_history()).await.unwrap();
+
+    let text = format_audit(&report);
+    assert!(text.contains("Largest holders could not be read: rate limited"), "{}", text);
+    assert!(text.contains("Sell not simulated: no wallet holder"), "{}", text);
+}
+
+#[tokio::test]
+async fn accounts_that_are_not_mints_fail_the_audit() {
+    let chain = MockChain { mint: None, holders: Ok(Vec::new()), curve: None };
+
+    let err = auditor(chain, SellSimulation::Sellable).audit(&Pubkey::new_unique(), &no_history()).await.unwrap_err();
+
+    assert!(err.to_string().contains("is not a token mint"));
+}
+
+#[tokio::test]
+async fn concurrent_audits_of_a_mint_share_one_run() {
+    let chain = Arc::new(CountingChain::default());
+    let auditor = TokenAuditor::new(chain.clone(), Arc::new(FixedSell(SellSimulation::Sellable)));
+    let (mint, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let history = no_history();
+
+    let (a, b, c, d) = tokio::join!(
+        auditor.audit(&mint, &history),
+        auditor.audit(&mint, &history),
+        auditor.audit(&mint, &history),
+        auditor.audit(&other, &history),
+    );
+
+    assert_eq!(format_audit(&a.unwrap()), format_audit(&b.unwrap()));
+    assert!(c.is_ok() && d.is_ok());
+    assert_eq!(chain.reads.load(Ordering::SeqCst), 6, "one round of reads per mint");
+    assert_eq!((auditor.stats().requests, auditor.stats().runs), (4, 2));
+}
+
+#[test]
+fn bonding_curve_accounts_decode_with_and_without_a_creator() {
+    let creator = Pubkey::new_unique();
+    let mut data = vec![0u8; 8];
+    for value in [1_073_000_000_000_000u64, 30_000_000_000, 793_100_000_000_000, 0, SUPPLY] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.push(0);
+    let address = Pubkey::new_unique();
+
+    let legacy = parse_bonding_curve(address, &data).unwrap();
+    assert_eq!((legacy.progress, legacy.complete, legacy.creator), (0.0, false, None));
+
+    data[48] = 1;
+    data.extend_from_slice(creator.as_ref());
+    let current = parse_bonding_curve(address, &data).unwrap();
+    assert_eq!((current.progress, current.complete, current.creator), (100.0, true, Some(creator)));
+    assert!(parse_bonding_curve(address, &data[..20]).is_none());
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::Duration;
+use common::{config, parsed_trade, MockRpc};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::token_timeline::{timeline_response, TimelineConfig, TimelineKind, TokenTimeline};
+use solana_vntr_sniper::processor::transaction_parser::{ParsedData, SwapType};
+
+fn trade(mint: Pubkey, swap_type: SwapType, sol: f64) -> ParsedData {
+    parsed_trade(mint)
+        .swap_type(swap_type)
+        .sol(sol)
+        .liquidity(100.0)
+        .name("Chrono")
+        .symbol("CHR")
+        .progress(40.0)
+        .build()
+}
+
+#[tokio::test]
+async fn the_monitor_records_creation_whales_and_alerts() {
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), None);
+    let mint = Pubkey::new_unique();
+
+    monitor.process_for_education(&trade(mint, SwapType::Buy, 0.5)).await.unwrap();
+    monitor.process_for_education(&trade(mint, SwapType::Buy, 25.0)).await.unwrap();
+    monitor.process_for_education(&trade(mint, SwapType::Buy, 30.0)).await.unwrap();
+
+    let events = TokenTimeline::shared().events(&mint);
+    let titles: Vec<&str> = events.iter().map(|e| e.title.as_str()).collect();
+    assert_eq!(events[0].kind, TimelineKind::Created);
+    assert_eq!(events[0].detail, "Chrono on PumpFun");
+    assert_eq!(events.iter().filter(|e| e.kind == TimelineKind::WhaleBuy).count(), 1, "{:?}", titles);
+    // Fired on both whale buys, listed once
+    assert_eq!(titles.iter().filter(|t| **t == "Large Transaction").count(), 1, "{:?}", titles);
+    assert!(monitor.timeline(&mint).contains("First whale buy"));
+}
+
+#[test]
+fn events_are_capped_keeping_the_first() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let config = TimelineConfig { max_tokens: 2, max_events: 3, repeat_secs: 300 };
+    let timeline = TokenTimeline::new(config).with_clock(clock.clone());
+    let (mint, other, third) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+    timeline.record(mint, TimelineKind::Created, "First seen", String::new());
+    for title in ["Volume spike", "Price movement", "Liquidity drop"] {
+        clock.advance(Duration::seconds(10));
+        timeline.record(mint, TimelineKind::Alert, title, String::new());
+    }
+    // Repeated within the window
+    timeline.record(mint, TimelineKind::Alert, "Liquidity drop", String::new());
+    let titles: Vec<String> = timeline.events(&mint).into_iter().map(|e| e.title).collect();
+    assert_eq!(titles, vec!["First seen", "Price movement", "Liquidity drop"]);
+
+    clock.advance(Duration::minutes(10));
+    timeline.record(other, TimelineKind::Created, "First seen", String::new());
+    clock.advance(Duration::minutes(1));
+    timeline.record(mint, TimelineKind::Alert, "Liquidity drop", String::new());
+    assert_eq!(timeline.events(&mint).len(), 3);
+
+    // The token quiet the longest makes room
+    timeline.record(third, TimelineKind::Created, "First seen", String::new());
+    assert_eq!(timeline.len(), 2);
+    assert!(timeline.events(&other).is_empty());
+}
+
+#[test]
+fn the_api_answers_with_json() {
+    let timeline = TokenTimeline::new(TimelineConfig::default());
+    let mint = Pubkey::new_unique();
+    timeline.record(mint, TimelineKind::State, "About to graduate", "bonding curve 95.0% complete".to_string());
+
+    let (status, body) = timeline_response(&mint.to_string(), &timeline);
+    assert_eq!(status, "200 OK");
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["events"][0]["kind"], "state");
+    assert_eq!(body["events"][0]["title"], "About to graduate");
+
+    assert_eq!(timeline_response(&Pubkey::new_unique().to_string(), &timeline).0, "404 Not Found");
+    assert_eq!(timeline_response("not-a-mint", &timeline).0, "400 Bad Request");
+}
@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::dex::pump_swap::PUMP_SWAP_PROGRAM;
+use solana_vntr_sniper::processor::risk_score::RiskFactors;
+use solana_vntr_sniper::processor::token_program_monitor::{
+    extract_token_events, TokenProgramEvent, TransferActivity, TransferRecord,
+};
+use yellowstone_grpc_proto::prelude::{
+    CompiledInstruction, Message, SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo, TokenBalance,
+    Transaction, TransactionStatusMeta,
+};
+
+fn key(seed: u8) -> Pubkey {
+    Pubkey::new_from_array([seed; 32])
+}
+
+fn balance(account_index: u32, mint: &Pubkey, owner: &Pubkey) -> TokenBalance {
+    TokenBalance {
+        account_index,
+        mint: mint.to_string(),
+        owner: owner.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Wallet `key(1)` moving tokens from its account `key(2)` to `key(3)`, owned by `key(4)`
+fn transfer_transaction(mint: &Pubkey, data: Vec<u8>, accounts: Vec<u8>, extra_keys: Vec<Pubkey>) -> SubscribeUpdateTransaction {
+    // The token program crate may pin another Pubkey version
+    let token_program = Pubkey::new_from_array(spl_token::id().to_bytes());
+    let mut account_keys = vec![key(1), key(2), key(3), token_program, *mint];
+    account_keys.extend(extra_keys);
+    SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: vec![7u8; 64],
+            is_vote: false,
+            transaction: Some(Transaction {
+                signatures: vec![vec![7u8; 64]],
+                message: Some(Message {
+                    account_keys: account_keys.iter().map(|k| k.to_bytes().to_vec()).collect(),
+                    instructions: vec![CompiledInstruction { program_id_index: 3, accounts, data }],
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(TransactionStatusMeta {
+                pre_token_balances: vec![balance(1, mint, &key(1))],
+                post_token_balances: vec![balance(1, mint, &key(1)), balance(2, mint, &key(4))],
+                ..Default::default()
+            }),
+            index: 0,
+        }),
+        slot: 350_000_000,
+    }
+}
+
+fn transfer_data(tag: u8, amount: u64) -> Vec<u8> {
+    let mut data = vec![tag];
+    data.extend_from_slice(&amount.to_le_bytes());
+    if tag == 12 {
+        data.push(6);
+    }
+    data
+}
+
+#[test]
+fn plain_transfers_resolve_mint_and_owners() {
+    let mint = key(9);
+    let expected = TokenProgramEvent::Transfer {
+        mint: mint.to_string(),
+        from: key(1).to_string(),
+        to: key(4).to_string(),
+        amount: 5_000_000,
+    };
+
+    // Transfer names no mint: it comes from the token balances
+    let plain = transfer_transaction(&mint, transfer_data(3, 5_000_000), vec![1, 2, 0], vec![]);
+    assert_eq!(extract_token_events(&plain), vec![expected.clone()]);
+
+    let checked = transfer_transaction(&mint, transfer_data(12, 5_000_000), vec![1, 4, 2, 0], vec![]);
+    assert_eq!(extract_token_events(&checked), vec![expected]);
+}
+
+#[test]
+fn transfers_inside_swaps_are_left_to_the_dex_parsers() {
+    let mint = key(9);
+    let swap = transfer_transaction(&mint, transfer_data(3, 5_000_000), vec![1, 2, 0], vec![PUMP_SWAP_PROGRAM]);
+    assert!(extract_token_events(&swap).is_empty());
+}
+
+fn record(from: &Pubkey, to: &Pubkey) -> TransferRecord {
+    TransferRecord { from: from.to_string(), to: to.to_string(), amount: 1_000, at: DateTime::<Utc>::from_timestamp(1_750_000_000, 0).unwrap() }
+}
+
+#[test]
+fn a_wallet_handing_out_the_token_is_reported_once() {
+    let mut activity = TransferActivity::default();
+    let (team, repeat) = (key(20), key(21));
+
+    assert_eq!(activity.record(record(&team, &repeat), 3), None);
+    assert_eq!(activity.record(record(&team, &repeat), 3), None, "the same recipient counts once");
+    assert_eq!(activity.record(record(&team, &key(22)), 3), None);
+    assert_eq!(activity.record(record(&team, &key(23)), 3), Some(3));
+    assert_eq!(activity.record(record(&team, &key(24)), 3), None);
+    assert_eq!(activity.recipients_of(&team.to_string()), 4);
+
+    for _ in 0..60 {
+        activity.record(record(&key(30), &key(31)), 3);
+    }
+    assert_eq!((activity.transfers, activity.recent.len()), (65, 50));
+}
+
+#[test]
+fn creator_transfers_raise_the_risk_score() {
+    let factors = RiskFactors { creator_transferred_pct: 12.0, ..RiskFactors::default() };
+    assert_eq!(factors.score(), 12.0);
+    let factors = RiskFactors { creator_transferred_pct: 80.0, ..RiskFactors::default() };
+    assert_eq!(factors.score(), 30.0);
+}
@@ -0,0 +1,66 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::trade_costs::{self, TransactionCost, BASE_FEE_LAMPORTS, TOKEN_ACCOUNT_RENT_LAMPORTS};
+
+fn signature() -> String {
+    Pubkey::new_unique().to_string()
+}
+
+#[test]
+fn rent_is_only_paid_for_accounts_the_transaction_opened() {
+    // A create_idempotent on an existing account opens nothing
+    let mut cost = TransactionCost::sent(200_000, 0, 0);
+    cost.settle(0, 0);
+    assert_eq!(cost.ata_rent, 0);
+    assert_eq!(cost.net_lamports(), BASE_FEE_LAMPORTS as i64);
+
+    cost.settle(1, 0);
+    assert_eq!(cost.ata_rent, TOKEN_ACCOUNT_RENT_LAMPORTS);
+}
+
+#[test]
+fn costs_are_recorded_once_confirmed() {
+    let sig = signature();
+    trade_costs::record_sent(&[sig.clone()], &[], TransactionCost::sent(0, 0, 0));
+
+    let cost = trade_costs::record_confirmed(&sig, 0, 1).unwrap();
+    assert_eq!(cost.rent_refund, TOKEN_ACCOUNT_RENT_LAMPORTS);
+    assert_eq!(cost.net_lamports(), BASE_FEE_LAMPORTS as i64 - TOKEN_ACCOUNT_RENT_LAMPORTS as i64);
+
+    // Confirmed twice, recorded once
+    assert!(trade_costs::record_confirmed(&sig, 0, 1).is_none());
+}
+
+#[test]
+fn unsent_transactions_record_nothing() {
+    assert!(trade_costs::record_confirmed(&signature(), 1, 0).is_none());
+}
+
+#[test]
+fn a_bundle_is_recorded_once_whichever_transaction_confirms() {
+    let bundle = vec![signature(), signature()];
+    trade_costs::record_sent(&bundle, &[], TransactionCost::sent(0, 0, 0));
+
+    assert!(trade_costs::record_confirmed(&bundle[1], 0, 0).is_some());
+    assert!(trade_costs::record_confirmed(&bundle[0], 0, 0).is_none());
+}
+
+#[test]
+fn priority_fee_is_unit_limit_times_micro_lamport_price() {
+    // 200k units at 1,000,000 micro-lamports (1 lamport) per unit
+    let cost = TransactionCost::sent(200_000, 1_000_000, 0);
+    assert_eq!(cost.priority_fee, 200_000);
+
+    // Sub-lamport totals round down
+    assert_eq!(TransactionCost::sent(1_000, 999, 0).priority_fee, 0);
+    // An overflowing price saturates instead of wrapping
+    assert_eq!(TransactionCost::sent(u32::MAX, u64::MAX, 0).priority_fee, u64::MAX / 1_000_000);
+}
+
+#[test]
+fn net_cost_adds_fees_tip_and_rent_less_refunds() {
+    let mut cost = TransactionCost::sent(100_000, 50_000, 1_000_000);
+    cost.settle(2, 1);
+
+    let expected = BASE_FEE_LAMPORTS + 5_000 + 1_000_000 + 2 * TOKEN_ACCOUNT_RENT_LAMPORTS - TOKEN_ACCOUNT_RENT_LAMPORTS;
+    assert_eq!(cost.net_lamports(), expected as i64);
+}
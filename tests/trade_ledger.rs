@@ -0,0 +1,128 @@
+use chrono::NaiveDate;
+use solana_vntr_sniper::library::rpc_api::BalanceChanges;
+use solana_vntr_sniper::processor::trade_ledger::{
+    daily_pnl, export_csv, tax_rows, LedgerTrade, TradeLedger, TradeSide,
+};
+
+const WSOL: &str = "So11111111111111111111111111111111111111112";
+/// 2024-03-01T12:00:00Z
+const DAY_ONE: i64 = 1_709_294_400;
+const DAY: i64 = 86_400;
+
+fn trade(signature: &str, timestamp: i64, side: TradeSide, token_amount: f64, sol_amount: f64) -> LedgerTrade {
+    LedgerTrade {
+        timestamp,
+        signature: signature.to_string(),
+        mint: "Mint111".to_string(),
+        side,
+        token_amount,
+        sol_amount,
+        fee_sol: 0.001,
+    }
+}
+
+#[test]
+fn buy_is_read_from_balance_changes() {
+    let changes = BalanceChanges {
+        block_time: Some(DAY_ONE),
+        fee_lamports: 5_000,
+        // 0.5 SOL spent plus the fee
+        sol_change_lamports: -500_005_000,
+        token_changes: vec![("Mint111".to_string(), 1_000.0), (WSOL.to_string(), 0.0)],
+        ..Default::default()
+    };
+
+    let trade = LedgerTrade::from_balance_changes("sig", &changes, 0).unwrap();
+    assert_eq!(trade.side, TradeSide::Buy);
+    assert_eq!(trade.timestamp, DAY_ONE);
+    assert_eq!(trade.token_amount, 1_000.0);
+    assert!((trade.sol_amount - 0.5).abs() < 1e-9);
+    assert!((trade.fee_sol - 0.000005).abs() < 1e-12);
+}
+
+#[test]
+fn sell_through_wsol_counts_the_unwrapped_sol() {
+    let changes = BalanceChanges {
+        block_time: None,
+        fee_lamports: 5_000,
+        sol_change_lamports: -5_000,
+        token_changes: vec![("Mint111".to_string(), -400.0), (WSOL.to_string(), 0.3)],
+        ..Default::default()
+    };
+
+    let trade = LedgerTrade::from_balance_changes("sig", &changes, 42).unwrap();
+    assert_eq!(trade.side, TradeSide::Sell);
+    assert_eq!(trade.timestamp, 42);
+    assert!((trade.sol_amount - 0.3).abs() < 1e-9);
+}
+
+#[test]
+fn transactions_without_token_moves_are_not_trades() {
+    let changes = BalanceChanges {
+        sol_change_lamports: -5_000,
+        fee_lamports: 5_000,
+        ..Default::default()
+    };
+    assert!(LedgerTrade::from_balance_changes("sig", &changes, 0).is_none());
+}
+
+#[test]
+fn ledger_ignores_duplicate_signatures() {
+    let ledger = TradeLedger::in_memory();
+    assert!(ledger.record(trade("a", DAY_ONE, TradeSide::Buy, 100.0, 1.0)));
+    assert!(!ledger.record(trade("a", DAY_ONE, TradeSide::Buy, 100.0, 1.0)));
+    assert_eq!(ledger.trades().len(), 1);
+}
+
+#[test]
+fn sells_use_average_cost_basis() {
+    let trades = vec![
+        trade("buy1", DAY_ONE, TradeSide::Buy, 100.0, 0.999),
+        trade("buy2", DAY_ONE + 60, TradeSide::Buy, 100.0, 2.999),
+        trade("sell", DAY_ONE + 120, TradeSide::Sell, 100.0, 3.001),
+    ];
+
+    let rows = tax_rows(&trades);
+    assert!((rows[0].cost_basis_sol - 1.0).abs() < 1e-9);
+    assert_eq!(rows[0].realized_pnl_sol, None);
+    // Half of the 4 SOL total cost
+    assert!((rows[2].cost_basis_sol - 2.0).abs() < 1e-9);
+    assert!((rows[2].proceeds_sol.unwrap() - 3.0).abs() < 1e-9);
+    assert!((rows[2].realized_pnl_sol.unwrap() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn csv_export_filters_by_date_but_keeps_earlier_cost() {
+    let trades = vec![
+        trade("buy", DAY_ONE, TradeSide::Buy, 100.0, 0.999),
+        trade("sell", DAY_ONE + DAY, TradeSide::Sell, 100.0, 2.001),
+    ];
+    let day_two = NaiveDate::from_ymd_opt(2024, 3, 2);
+
+    let csv = export_csv(&trades, day_two, day_two);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("timestamp_utc,signature,mint,side"));
+    assert_eq!(
+        lines[1],
+        "2024-03-02T12:00:00Z,sell,Mint111,sell,100,2.001000000,0.001000000,1.000000000,2.000000000,1.000000000"
+    );
+}
+
+#[test]
+fn daily_pnl_sums_realized_gains_per_day() {
+    let trades = vec![
+        trade("buy", DAY_ONE, TradeSide::Buy, 100.0, 0.999),
+        trade("sell1", DAY_ONE + DAY, TradeSide::Sell, 50.0, 1.001),
+        trade("sell2", DAY_ONE + DAY + 60, TradeSide::Sell, 50.0, 0.251),
+    ];
+
+    let days = daily_pnl(&trades);
+    let (first_pnl, _, first_count) = days[&NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()];
+    assert_eq!((first_pnl, first_count), (0.0, 1));
+    let (second_pnl, fees, second_count) = days[&NaiveDate::from_ymd_opt(2024, 3, 2).unwrap()];
+    assert_eq!(second_count, 2);
+    // (1.0 - 0.5) + (0.25 - 0.5)
+    assert!((second_pnl - 0.25).abs() < 1e-9);
+    assert!((fees - 0.002).abs() < 1e-12);
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::library::status_server::control_response;
+use solana_vntr_sniper::processor::trading_control::TradingControl;
+
+#[test]
+fn pausing_twice_keeps_the_first_pause() {
+    let control = TradingControl::new(None).with_clock(Arc::new(SimulatedClock::at_unix(1_750_000_000)));
+    assert!(control.paused_reason().is_none());
+
+    assert!(control.pause("telegram", Some(" rug wave ".to_string())));
+    assert!(!control.pause("api", None));
+    assert_eq!(control.paused_reason().as_deref(), Some("Buys paused via telegram: rug wave"));
+    assert_eq!(control.state().unwrap().since, 1_750_000_000);
+
+    assert_eq!(control.resume().unwrap().source, "telegram");
+    assert!(control.resume().is_none());
+    assert!(!control.is_paused());
+}
+
+#[test]
+fn a_pause_survives_a_restart() {
+    let path = std::env::temp_dir().join(format!("trading_control_{}.json", Pubkey::new_unique()));
+    let control = TradingControl::new(Some(path.clone()));
+    control.pause("api", None);
+
+    let restarted = TradingControl::new(Some(path.clone()));
+    assert_eq!(restarted.paused_reason().as_deref(), Some("Buys paused via api"));
+    restarted.resume();
+    assert!(!TradingControl::new(Some(path.clone())).is_paused());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn control_endpoints_need_the_bearer_token() {
+    let request = "POST /control/pause HTTP/1.1\r\nHost: localhost\r\n\r\n";
+    assert_eq!(control_response("pause", request, None).0, "403 Forbidden");
+    assert_eq!(control_response("pause", request, Some("secret")).0, "401 Unauthorized");
+
+    let wrong = "POST /control/pause HTTP/1.1\r\nAuthorization: Bearer nope\r\n\r\n";
+    assert_eq!(control_response("pause", wrong, Some("secret")).0, "401 Unauthorized");
+}
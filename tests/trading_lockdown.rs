@@ -0,0 +1,105 @@
+mod common;
+
+use std::sync::Arc;
+use common::{app_state, buy_config, MockRpc};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_vntr_sniper::common::config::import_wallet;
+use solana_vntr_sniper::common::quote_asset::QuoteAsset;
+use solana_vntr_sniper::dex::pump_swap::PumpSwap;
+use solana_vntr_sniper::error::{BotError, ErrorClass};
+use solana_vntr_sniper::library::trading_lockdown::{self, ensure_trading_enabled, parse_trading_enabled, trading_enabled};
+use solana_vntr_sniper::processor::sniper_bot::{execute_buy, BOUGHT_TOKEN_LIST};
+use solana_vntr_sniper::processor::swap::SwapProtocol;
+use solana_vntr_sniper::processor::transaction_parser::{DexType, TradeInfoFromToken};
+
+/// Every test in this binary runs read-only; the flag is read once per process
+fn lock_down() {
+    std::env::set_var("TRADING_ENABLED", "false");
+}
+
+fn buy(dex_type: DexType, mint: &Pubkey) -> TradeInfoFromToken {
+    TradeInfoFromToken {
+        dex_type,
+        slot: 350_000_000,
+        signature: "target".to_string(),
+        pool_id: String::new(),
+        mint: mint.to_string(),
+        timestamp: 1_750_000_000,
+        is_buy: true,
+        price: 50,
+        is_reverse_when_pump_swap: false,
+        coin_creator: Some(Pubkey::new_unique().to_string()),
+        sol_change: 0.5,
+        token_change: 10_000.0,
+        liquidity: 10.0,
+        virtual_sol_reserves: 40_000_000_000,
+        virtual_token_reserves: 800_000_000_000_000,
+        quote_asset: QuoteAsset::Sol,
+    }
+}
+
+#[test]
+fn the_guard_refuses_with_a_fatal_error() {
+    lock_down();
+    assert!(!trading_enabled());
+    assert_eq!(ensure_trading_enabled(), Err(BotError::TradingDisabled));
+    assert_eq!(BotError::TradingDisabled.class(), ErrorClass::Fatal);
+}
+
+#[test]
+fn the_usual_spellings_are_understood() {
+    for value in ["false", "0", "no", "off", " OFF "] {
+        assert_eq!(parse_trading_enabled(Some(value)), Ok(false), "{}", value);
+    }
+    for value in ["true", "1", "yes", "On"] {
+        assert_eq!(parse_trading_enabled(Some(value)), Ok(true), "{}", value);
+    }
+    assert_eq!(parse_trading_enabled(None), Ok(true));
+    assert_eq!(parse_trading_enabled(Some("")), Ok(true));
+}
+
+#[test]
+fn garbage_does_not_enable_trading() {
+    for value in ["flase", "disabled", "2"] {
+        assert!(matches!(parse_trading_enabled(Some(value)), Err(BotError::InvalidConfig(_))), "{}", value);
+    }
+}
+
+#[test]
+fn no_wallet_is_loaded() {
+    lock_down();
+    std::env::set_var("PRIVATE_KEY", bs58::encode(Keypair::new().to_bytes()).into_string());
+    let error = import_wallet().unwrap_err();
+    assert_eq!(BotError::classify(&error), BotError::TradingDisabled);
+}
+
+#[test]
+fn the_self_check_passes_while_locked_down() {
+    lock_down();
+    let mode = trading_lockdown::self_check().unwrap();
+    assert!(mode.starts_with("read-only"), "{}", mode);
+}
+
+#[tokio::test]
+async fn buys_are_refused_before_anything_is_sent() {
+    lock_down();
+    let rpc = Arc::new(MockRpc::confirming());
+    let mint = Pubkey::new_unique();
+
+    let result = execute_buy(buy(DexType::PumpFun, &mint), Arc::new(app_state(rpc.clone())), Arc::new(buy_config(0.01)), SwapProtocol::PumpFun).await;
+
+    assert_eq!(result, Err(BotError::TradingDisabled));
+    assert_eq!(rpc.sent_count(), 0);
+    assert!(!BOUGHT_TOKEN_LIST.contains_key(&mint.to_string()));
+}
+
+#[tokio::test]
+async fn swap_builders_refuse() {
+    lock_down();
+    let swap = PumpSwap::new(Arc::new(Keypair::new()), None, None);
+
+    let error = swap.build_swap_from_parsed_data(&buy(DexType::PumpSwap, &Pubkey::new_unique()), buy_config(0.01)).await.unwrap_err();
+
+    assert_eq!(BotError::classify(&error), BotError::TradingDisabled);
+}
@@ -0,0 +1,306 @@
+//! CPI-aware parsing of routed swaps.
+//!
+//! The transactions below follow the shape of mainnet transactions: real program ids, the DEX
+//! instruction account layouts, and event CPI payloads laid out as the programs emit them
+//! (anchor event tag + event discriminator + fields). Signatures, wallets and mints are fixed
+//! test keys.
+
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::cache::LOOKUP_TABLE_CACHE;
+use solana_vntr_sniper::common::quote_asset::{self, QuoteAsset, USDC_MINT};
+use solana_vntr_sniper::dex::pump_fun::PUMP_FUN_PROGRAM;
+use solana_vntr_sniper::dex::pump_swap::{PUMP_SWAP_PROGRAM, SOL_MINT};
+use solana_vntr_sniper::processor::transaction_parser::{
+    find_dex_event_cpis, missing_lookup_tables, parse_swap_events, transaction_account_keys, DexType,
+};
+use std::str::FromStr;
+use yellowstone_grpc_proto::prelude::{
+    CompiledInstruction, InnerInstruction, InnerInstructions, Message, MessageAddressTableLookup,
+    SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo, Transaction, TransactionStatusMeta,
+};
+
+const JUPITER_V6: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+const ANCHOR_EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+const PUMP_SWAP_BUY_EVENT: [u8; 8] = [103, 244, 82, 31, 44, 245, 119, 119];
+const PUMP_SWAP_SELL_EVENT: [u8; 8] = [62, 47, 55, 10, 165, 3, 220, 42];
+const PUMP_FUN_TRADE_EVENT: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+
+fn key(seed: u8) -> Pubkey {
+    Pubkey::new_from_array([seed; 32])
+}
+
+fn put_u64(buf: &mut [u8], offset: usize, value: u64) {
+    buf[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+fn put_key(buf: &mut [u8], offset: usize, key: &Pubkey) {
+    buf[offset..offset + 32].copy_from_slice(key.as_ref());
+}
+
+/// PumpSwap BuyEvent/SellEvent (368 bytes)
+fn pump_swap_event(buy: bool, base_amount: u64, quote_amount: u64, pool_base: u64, pool_quote: u64, pool: &Pubkey) -> Vec<u8> {
+    let mut data = vec![0u8; 368];
+    data[..8].copy_from_slice(&ANCHOR_EVENT_IX_TAG);
+    data[8..16].copy_from_slice(if buy { &PUMP_SWAP_BUY_EVENT } else { &PUMP_SWAP_SELL_EVENT });
+    put_u64(&mut data, 16, 1_750_000_000);
+    put_u64(&mut data, 24, base_amount);
+    put_u64(&mut data, 56, pool_base);
+    put_u64(&mut data, 64, pool_quote);
+    put_u64(&mut data, 72, quote_amount);
+    put_key(&mut data, 128, pool);
+    put_key(&mut data, 320, &key(90));
+    data
+}
+
+/// pump.fun TradeEvent (266 bytes)
+fn pump_fun_trade_event(mint: &Pubkey, buy: bool, sol_amount: u64, token_amount: u64) -> Vec<u8> {
+    let mut data = vec![0u8; 266];
+    data[..8].copy_from_slice(&ANCHOR_EVENT_IX_TAG);
+    data[8..16].copy_from_slice(&PUMP_FUN_TRADE_EVENT);
+    put_key(&mut data, 16, mint);
+    put_u64(&mut data, 48, sol_amount);
+    put_u64(&mut data, 56, token_amount);
+    data[64] = buy as u8;
+    put_u64(&mut data, 97, 1_750_000_000);
+    put_u64(&mut data, 105, 40_000_000_000);
+    put_u64(&mut data, 113, 800_000_000_000_000);
+    put_u64(&mut data, 121, 10_000_000_000);
+    put_key(&mut data, 185, &key(91));
+    data
+}
+
+fn inner(program_id_index: u32, accounts: Vec<u8>, data: Vec<u8>, stack_height: u32) -> InnerInstruction {
+    InnerInstruction { program_id_index, accounts, data, stack_height: Some(stack_height) }
+}
+
+fn transaction(
+    account_keys: Vec<Pubkey>,
+    loaded_readonly: Vec<Pubkey>,
+    instructions: Vec<CompiledInstruction>,
+    inner_instructions: Vec<InnerInstructions>,
+) -> SubscribeUpdateTransaction {
+    SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: vec![7u8; 64],
+            is_vote: false,
+            transaction: Some(Transaction {
+                signatures: vec![vec![7u8; 64]],
+                message: Some(Message {
+                    account_keys: account_keys.iter().map(|k| k.to_bytes().to_vec()).collect(),
+                    instructions,
+                    versioned: !loaded_readonly.is_empty(),
+                    address_table_lookups: if loaded_readonly.is_empty() {
+                        vec![]
+                    } else {
+                        vec![MessageAddressTableLookup {
+                            account_key: key(201).to_bytes().to_vec(),
+                            writable_indexes: vec![],
+                            readonly_indexes: (0..loaded_readonly.len() as u8).collect(),
+                        }]
+                    },
+                    ..Default::default()
+                }),
+            }),
+            meta: Some(TransactionStatusMeta {
+                inner_instructions,
+                loaded_readonly_addresses: loaded_readonly.iter().map(|k| k.to_bytes().to_vec()).collect(),
+                ..Default::default()
+            }),
+            index: 0,
+        }),
+        slot: 350_000_000,
+    }
+}
+
+/// Jupiter route buying a PumpSwap token: the swap and its event are CPIs under the router, and
+/// the PumpSwap program and mints only appear through the address lookup table
+fn jupiter_routed_pump_swap_buy(mint: &Pubkey, pool: &Pubkey) -> SubscribeUpdateTransaction {
+    jupiter_routed_pump_swap_buy_against(mint, pool, &SOL_MINT, 1_000_000_000, 40_000_000_000)
+}
+
+fn jupiter_routed_pump_swap_buy_against(
+    mint: &Pubkey,
+    pool: &Pubkey,
+    quote_mint: &Pubkey,
+    quote_amount: u64,
+    pool_quote: u64,
+) -> SubscribeUpdateTransaction {
+    let jupiter = Pubkey::from_str(JUPITER_V6).unwrap();
+    // 0 signer, 1 jupiter, 2 pool | loaded: 3 pump swap, 4 global config, 5 mint, 6 quote mint
+    let keys = vec![key(1), jupiter, *pool];
+    let loaded = vec![PUMP_SWAP_PROGRAM, key(4), *mint, *quote_mint];
+
+    let swap_ix = inner(3, vec![2, 0, 4, 5, 6], vec![102, 6, 61, 18, 1, 218, 235, 234], 2);
+    let token_transfer = inner(1, vec![], vec![3, 0, 0, 0, 0, 0, 0, 0, 0], 3);
+    let event = inner(3, vec![], pump_swap_event(true, 5_000_000_000_000, quote_amount, 200_000_000_000_000, pool_quote, pool), 3);
+
+    transaction(
+        keys,
+        loaded,
+        vec![CompiledInstruction { program_id_index: 1, accounts: vec![0, 2, 3], data: vec![229, 23, 203, 151] }],
+        vec![InnerInstructions { index: 0, instructions: vec![swap_ix, token_transfer, event] }],
+    )
+}
+
+#[test]
+fn routed_pump_swap_buy_is_attributed_to_pump_swap() {
+    let mint = key(50);
+    let pool = key(60);
+    let txn = jupiter_routed_pump_swap_buy(&mint, &pool);
+
+    let events = find_dex_event_cpis(&txn);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].dex_type, DexType::PumpSwap);
+    assert!(events[0].is_routed());
+    assert_eq!(events[0].outer_program, Some(Pubkey::from_str(JUPITER_V6).unwrap()));
+
+    let swaps = parse_swap_events(&txn);
+    assert_eq!(swaps.len(), 1);
+    let swap = &swaps[0];
+    assert_eq!(swap.dex_type, DexType::PumpSwap);
+    assert_eq!(swap.mint, mint.to_string());
+    assert_eq!(swap.pool_id, pool.to_string());
+    assert!(swap.is_buy);
+    assert_eq!(swap.slot, 350_000_000);
+    assert!(!swap.signature.is_empty());
+    assert!((swap.sol_change + 1.0).abs() < 1e-9);
+    assert!((swap.token_change - 5_000.0).abs() < 1e-9);
+}
+
+#[test]
+fn usdc_quoted_pump_swap_buy_is_converted_to_sol() {
+    quote_asset::set_sol_usd_price(200.0);
+    let mint = key(51);
+    let pool = key(61);
+    // 200 USDC into a pool holding 8,000 USDC
+    let txn = jupiter_routed_pump_swap_buy_against(&mint, &pool, &USDC_MINT, 200_000_000, 8_000_000_000);
+
+    let swaps = parse_swap_events(&txn);
+    assert_eq!(swaps.len(), 1);
+    let swap = &swaps[0];
+    assert_eq!(swap.mint, mint.to_string());
+    assert_eq!(swap.quote_asset, QuoteAsset::Usdc);
+    assert!((swap.sol_change + 1.0).abs() < 1e-9);
+    assert!((swap.liquidity - 40.0).abs() < 1e-9);
+    assert_eq!(swap.virtual_sol_reserves, 8_000_000_000);
+
+    // Later lookups by mint alone know the pair
+    assert_eq!(quote_asset::for_mint(&mint.to_string()), QuoteAsset::Usdc);
+    assert_eq!(quote_asset::for_mint(&key(52).to_string()), QuoteAsset::Sol);
+    assert_eq!(QuoteAsset::Usdc.format_sol_amount(1.0), "200.00 USDC (≈1.0000 SOL)");
+}
+
+#[test]
+fn loaded_addresses_extend_account_keys() {
+    let txn = jupiter_routed_pump_swap_buy(&key(50), &key(60));
+    let keys = transaction_account_keys(&txn);
+    assert_eq!(keys.len(), 7);
+    assert_eq!(keys[3], PUMP_SWAP_PROGRAM);
+    assert_eq!(keys[6], SOL_MINT);
+}
+
+#[test]
+fn multi_hop_route_yields_one_trade_per_hop() {
+    let jupiter = Pubkey::from_str(JUPITER_V6).unwrap();
+    let pump_fun = Pubkey::from_str(PUMP_FUN_PROGRAM).unwrap();
+    let token_a = key(51);
+    let token_b = key(52);
+    let pool_a = key(61);
+    // 0 signer, 1 jupiter, 2 pump swap, 3 pump.fun, 4 pool a, 5 global config, 6 token a, 7 wsol
+    let keys = vec![key(1), jupiter, PUMP_SWAP_PROGRAM, pump_fun, pool_a, key(5), token_a, SOL_MINT];
+
+    let sell_a = inner(2, vec![4, 0, 5, 6, 7], vec![51, 230, 133, 164, 1, 97, 89, 11], 2);
+    let sell_a_event = inner(2, vec![], pump_swap_event(false, 2_000_000_000_000, 500_000_000, 100_000_000_000_000, 20_000_000_000, &pool_a), 3);
+    let buy_b = inner(3, vec![], vec![102, 6, 61, 18, 1, 218, 235, 234], 2);
+    let buy_b_event = inner(3, vec![], pump_fun_trade_event(&token_b, true, 490_000_000, 9_000_000_000_000), 3);
+
+    let txn = transaction(
+        keys,
+        vec![],
+        vec![CompiledInstruction { program_id_index: 1, accounts: vec![0, 2, 3], data: vec![229, 23, 203, 151] }],
+        vec![InnerInstructions { index: 0, instructions: vec![sell_a, sell_a_event, buy_b, buy_b_event] }],
+    );
+
+    let swaps = parse_swap_events(&txn);
+    assert_eq!(swaps.len(), 2);
+    assert_eq!(swaps[0].dex_type, DexType::PumpSwap);
+    assert_eq!(swaps[0].mint, token_a.to_string());
+    assert!(!swaps[0].is_buy);
+    assert_eq!(swaps[1].dex_type, DexType::PumpFun);
+    assert_eq!(swaps[1].mint, token_b.to_string());
+    assert!(swaps[1].is_buy);
+}
+
+#[test]
+fn direct_pump_fun_trade_is_not_routed() {
+    let pump_fun = Pubkey::from_str(PUMP_FUN_PROGRAM).unwrap();
+    let mint = key(53);
+    // 0 signer, 1 pump.fun
+    let keys = vec![key(1), pump_fun];
+    let event = inner(1, vec![], pump_fun_trade_event(&mint, false, 250_000_000, 1_000_000_000_000), 2);
+
+    let txn = transaction(
+        keys,
+        vec![],
+        vec![CompiledInstruction { program_id_index: 1, accounts: vec![0], data: vec![51, 230, 133, 164, 1, 97, 89, 11] }],
+        vec![InnerInstructions { index: 0, instructions: vec![event] }],
+    );
+
+    let events = find_dex_event_cpis(&txn);
+    assert_eq!(events.len(), 1);
+    assert!(!events[0].is_routed());
+
+    let swaps = parse_swap_events(&txn);
+    assert_eq!(swaps.len(), 1);
+    assert_eq!(swaps[0].mint, mint.to_string());
+    assert!(!swaps[0].is_buy);
+}
+
+#[test]
+fn event_sized_payload_from_unknown_program_is_ignored() {
+    let unknown = key(99);
+    // 0 signer, 1 unknown program
+    let keys = vec![key(1), unknown];
+    let decoy = inner(1, vec![], pump_fun_trade_event(&key(54), true, 1, 1), 2);
+
+    let txn = transaction(
+        keys,
+        vec![],
+        vec![CompiledInstruction { program_id_index: 1, accounts: vec![0], data: vec![] }],
+        vec![InnerInstructions { index: 0, instructions: vec![decoy] }],
+    );
+
+    assert!(find_dex_event_cpis(&txn).is_empty());
+    assert!(parse_swap_events(&txn).is_empty());
+}
+
+#[test]
+fn lookup_table_addresses_resolve_from_cache_when_meta_lacks_them() {
+    let table = key(200);
+    let mint = key(55);
+    let pool = key(65);
+    let mut txn = jupiter_routed_pump_swap_buy(&mint, &pool);
+    {
+        let info = txn.transaction.as_mut().unwrap();
+        // Meta without loaded addresses; the message looks up [pool swap program, global config, mint, wsol]
+        info.meta.as_mut().unwrap().loaded_readonly_addresses.clear();
+        info.transaction.as_mut().unwrap().message.as_mut().unwrap().address_table_lookups = vec![MessageAddressTableLookup {
+            account_key: table.to_bytes().to_vec(),
+            writable_indexes: vec![],
+            readonly_indexes: vec![0, 1, 2, 3],
+        }];
+    }
+
+    // Unresolvable lookups leave only the static keys, so no swap is misattributed
+    assert_eq!(missing_lookup_tables(&txn), vec![table]);
+    assert_eq!(transaction_account_keys(&txn).len(), 3);
+    assert!(parse_swap_events(&txn).is_empty());
+
+    LOOKUP_TABLE_CACHE.insert(table, vec![PUMP_SWAP_PROGRAM, key(4), mint, SOL_MINT], None);
+    assert!(missing_lookup_tables(&txn).is_empty());
+    assert_eq!(transaction_account_keys(&txn)[5], mint);
+
+    let swaps = parse_swap_events(&txn);
+    assert_eq!(swaps.len(), 1);
+    assert_eq!(swaps[0].mint, mint.to_string());
+}
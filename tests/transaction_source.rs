@@ -0,0 +1,184 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use solana_sdk::message::{Message, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_vntr_sniper::common::cluster::DexProgram;
+use solana_vntr_sniper::library::transaction_source::{
+    convert, scope, yellowstone, FileReplaySource, MergedSource, MonitorScope, RecordingSource, SourceFilter,
+    TransactionSource, TransactionStream,
+};
+use yellowstone_grpc_proto::geyser::{SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo};
+use yellowstone_grpc_proto::prelude::{Message as ProtoMessage, Transaction as ProtoTransaction};
+
+/// Replays a fixed list of transactions
+struct StaticSource(Vec<SubscribeUpdateTransaction>);
+
+#[async_trait]
+impl TransactionSource for StaticSource {
+    fn name(&self) -> &str {
+        "static"
+    }
+
+    async fn subscribe(&self, _filter: &SourceFilter) -> Result<TransactionStream> {
+        Ok(Box::pin(stream::iter(self.0.clone().into_iter().map(Ok))))
+    }
+}
+
+fn txn(signature: u8, slot: u64) -> SubscribeUpdateTransaction {
+    SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: vec![signature; 64],
+            ..Default::default()
+        }),
+        slot,
+    }
+}
+
+#[tokio::test]
+async fn merged_sources_deliver_each_signature_once() {
+    let merged = MergedSource::new(vec![
+        Box::new(StaticSource(vec![txn(1, 10), txn(2, 11)])),
+        Box::new(StaticSource(vec![txn(2, 11), txn(3, 12)])),
+    ]);
+
+    let stream = merged.subscribe(&SourceFilter::default()).await.unwrap();
+    let mut slots: Vec<u64> = stream.map(|item| item.unwrap().slot).collect().await;
+    slots.sort();
+    assert_eq!(slots, vec![10, 11, 12]);
+}
+
+#[test]
+fn versioned_transactions_convert_to_the_stream_format() {
+    let payer = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let message = Message::new(&[system_instruction::transfer(&payer.pubkey(), &recipient, 1)], Some(&payer.pubkey()));
+    let tx = VersionedTransaction::try_new(VersionedMessage::Legacy(message), &[&payer]).unwrap();
+
+    let converted = convert::from_versioned(&tx);
+    let message = converted.message.unwrap();
+    assert_eq!(converted.signatures[0], tx.signatures[0].as_ref().to_vec());
+    assert_eq!(message.account_keys[0], payer.pubkey().to_bytes().to_vec());
+    assert_eq!(message.instructions.len(), 1);
+    assert!(!message.versioned);
+}
+
+#[tokio::test]
+async fn recorded_streams_replay_in_order_and_skip_corrupt_lines() {
+    let path = std::env::temp_dir().join(format!("replay-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let recorder = RecordingSource::new(Box::new(StaticSource(vec![txn(1, 10), txn(2, 11), txn(3, 12)])), path.clone());
+    let live: Vec<u64> = recorder.subscribe(&SourceFilter::default()).await.unwrap()
+        .map(|item| item.unwrap().slot)
+        .collect()
+        .await;
+    assert_eq!(live, vec![10, 11, 12]);
+
+    let mut recording = std::fs::read_to_string(&path).unwrap();
+    recording.push_str("not json\n");
+    std::fs::write(&path, recording).unwrap();
+
+    let replay = FileReplaySource::new(path.clone(), 0.0, false);
+    let replayed: Vec<(u64, Vec<u8>)> = replay.subscribe(&SourceFilter::default()).await.unwrap()
+        .map(|item| {
+            let txn = item.unwrap();
+            (txn.slot, txn.transaction.unwrap().signature)
+        })
+        .collect()
+        .await;
+    assert_eq!(replayed, vec![(10, vec![1; 64]), (11, vec![2; 64]), (12, vec![3; 64])]);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn replay_of_a_missing_file_fails_on_subscribe() {
+    let replay = FileReplaySource::new(std::env::temp_dir().join("no-such-recording.jsonl"), 1.0, false);
+    assert!(replay.subscribe(&SourceFilter::default()).await.is_err());
+}
+
+fn txn_touching(accounts: &[Pubkey]) -> SubscribeUpdateTransaction {
+    SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: vec![9; 64],
+            transaction: Some(ProtoTransaction {
+                message: Some(ProtoMessage {
+                    account_keys: accounts.iter().map(|k| k.to_bytes().to_vec()).collect(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        slot: 1,
+    }
+}
+
+#[test]
+fn dex_names_and_program_ids_resolve_and_typos_are_rejected() {
+    assert_eq!(scope::parse_program("pumpfun").unwrap(), DexProgram::PumpFun.id());
+    assert_eq!(scope::parse_program(" PumpSwap ").unwrap(), DexProgram::PumpSwap.id());
+    assert_eq!(scope::parse_program("raydium_launchpad").unwrap(), DexProgram::RaydiumLaunchpad.id());
+    let custom = Pubkey::new_unique();
+    assert_eq!(scope::parse_program(&custom.to_string()).unwrap(), custom);
+    assert!(scope::parse_program("pumpfunn").is_err());
+    assert!(scope::parse_accounts("not-a-key").is_err());
+    assert_eq!(scope::parse_accounts(" , ").unwrap(), Vec::<String>::new());
+}
+
+#[test]
+fn an_empty_scope_watches_only_the_targets() {
+    let targets = vec![Pubkey::new_unique().to_string()];
+    let filter = MonitorScope::default().filter_for(&targets);
+    assert_eq!(filter.account_include, targets);
+    assert!(filter.account_exclude.is_empty());
+    assert!(filter.account_required.is_empty());
+    assert!(!filter.include_failed);
+}
+
+#[test]
+fn scope_generates_the_yellowstone_request() {
+    let target = Pubkey::new_unique().to_string();
+    let excluded = Pubkey::new_unique().to_string();
+    let required = Pubkey::new_unique().to_string();
+    let scope = MonitorScope {
+        programs: vec![DexProgram::PumpSwap.id()],
+        accounts_include: vec![target.clone()],
+        accounts_exclude: vec![excluded.clone()],
+        accounts_required: vec![required.clone()],
+        include_failed: true,
+    };
+
+    let request = yellowstone::subscribe_request(&scope.filter_for(&[target.clone()]));
+    assert_eq!(request.transactions.len(), 1);
+    let filter = request.transactions.values().next().unwrap();
+    // The target listed twice is only subscribed once
+    assert_eq!(filter.account_include, vec![target, DexProgram::PumpSwap.id().to_string()]);
+    assert_eq!(filter.account_exclude, vec![excluded]);
+    assert_eq!(filter.account_required, vec![required]);
+    assert_eq!(filter.failed, Some(true));
+    assert_eq!(filter.vote, Some(false));
+}
+
+#[test]
+fn local_matching_applies_include_exclude_and_required() {
+    let program = Pubkey::new_unique();
+    let bot = Pubkey::new_unique();
+    let quote = Pubkey::new_unique();
+    let filter = SourceFilter {
+        account_include: vec![program.to_string()],
+        account_exclude: vec![bot.to_string()],
+        account_required: vec![quote.to_string()],
+        include_failed: false,
+    };
+
+    assert!(filter.matches(&txn_touching(&[program, quote])));
+    assert!(!filter.matches(&txn_touching(&[program])));
+    assert!(!filter.matches(&txn_touching(&[program, quote, bot])));
+    assert!(!filter.matches(&txn_touching(&[Pubkey::new_unique(), quote])));
+    assert!(SourceFilter::default().matches(&txn_touching(&[Pubkey::new_unique()])));
+}
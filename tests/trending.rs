@@ -0,0 +1,108 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use common::{config, parsed_trade, MockRpc};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::transaction_parser::{ParsedData, SwapType};
+use solana_vntr_sniper::processor::trending::{format_trending, TrendingConfig, TrendingTracker};
+
+fn trade(mint: Pubkey, signer: Pubkey, swap_type: SwapType, sol: f64, price: f64) -> ParsedData {
+    parsed_trade(mint)
+        .dex("PumpSwap")
+        .swap_type(swap_type)
+        .signer(signer)
+        .sol(sol)
+        .price(price)
+        .liquidity(50.0)
+        .name("Trend")
+        .symbol("TRD")
+        .build()
+}
+
+fn at(seconds: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(1_750_000_000 + seconds, 0).unwrap()
+}
+
+fn tracker() -> TrendingTracker {
+    TrendingTracker::new(TrendingConfig { window: Duration::minutes(5), top_n: 10, min_volume_sol: 1.0 })
+}
+
+#[test]
+fn an_accelerating_token_outranks_a_steady_one() {
+    let mut trending = tracker();
+    let (hot, steady, whale) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+    trending.record(&trade(hot, Pubkey::new_unique(), SwapType::Buy, 1.0, 1.0), at(60));
+    trending.record(&trade(hot, Pubkey::new_unique(), SwapType::Buy, 1.0, 1.0), at(120));
+    trending.record(&trade(steady, whale, SwapType::Buy, 2.0, 1.0), at(60));
+    trending.record(&trade(steady, whale, SwapType::Buy, 2.0, 1.0), at(360));
+    for i in 0..10 {
+        trending.record(&trade(hot, Pubkey::new_unique(), SwapType::Buy, 1.0, 1.0 + 0.05 * (i + 1) as f64), at(360 + i * 10));
+    }
+
+    let ranking = trending.refresh(at(600)).to_vec();
+    assert_eq!(ranking.iter().map(|t| t.mint).collect::<Vec<_>>(), vec![hot, steady]);
+
+    let top = &ranking[0];
+    assert!((top.volume_sol - 10.0).abs() < 1e-9);
+    assert!((top.volume_acceleration - 5.0).abs() < 1e-9);
+    assert_eq!((top.unique_buyers, top.holder_growth), (10, 10));
+    assert!((top.price_change_pct.unwrap() - 50.0).abs() < 1e-9);
+    // Full acceleration, 10/25 buyers and holders, half the momentum
+    assert!((top.score - 60.0).abs() < 1e-9);
+    assert!((ranking[1].score - 8.5).abs() < 1e-9);
+}
+
+#[test]
+fn holders_selling_out_count_against_growth() {
+    let mut trending = tracker();
+    let (mint, quiet) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let early: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    for wallet in &early {
+        trending.record(&trade(mint, *wallet, SwapType::Buy, 1.0, 0.01), at(60));
+    }
+    trending.record(&trade(mint, early[0], SwapType::Sell, 1.0, 0.01), at(360));
+    trending.record(&trade(mint, early[1], SwapType::Sell, 1.0, 0.01), at(370));
+    trending.record(&trade(mint, Pubkey::new_unique(), SwapType::Buy, 2.0, 0.01), at(380));
+    trending.record(&trade(quiet, Pubkey::new_unique(), SwapType::Buy, 0.5, 0.01), at(380));
+
+    let ranking = trending.refresh(at(600)).to_vec();
+    assert_eq!(ranking.len(), 1, "tokens under the volume floor are not ranked");
+    assert_eq!((ranking[0].unique_buyers, ranking[0].holder_growth), (1, -1));
+}
+
+#[test]
+fn tokens_drop_out_once_trading_stops() {
+    let mut trending = tracker();
+    let mint = Pubkey::new_unique();
+    trending.record(&trade(mint, Pubkey::new_unique(), SwapType::Buy, 3.0, 1.0), at(60));
+    assert_eq!(trending.refresh(at(120)).len(), 1);
+
+    assert!(trending.refresh(at(1800)).is_empty());
+    assert!(trending.top(10).is_empty());
+    assert_eq!(format_trending(&[]), "No token is trending yet.");
+}
+
+#[tokio::test]
+async fn the_monitor_ranks_and_reports_trending_tokens() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), None)
+        .with_clock(clock.clone())
+        .with_trending(TrendingConfig::default());
+
+    let mint = Pubkey::new_unique();
+    for _ in 0..5 {
+        monitor.process_for_education(&trade(mint, Pubkey::new_unique(), SwapType::Buy, 1.0, 1.0)).await.unwrap();
+        clock.advance(Duration::seconds(10));
+    }
+    assert!(monitor.trending(None).await.is_empty(), "nothing is ranked before the first refresh");
+
+    let top = monitor.refresh_trending().await;
+    assert_eq!(top[0].mint, mint);
+    assert_eq!(top[0].unique_buyers, 5);
+    let report = monitor.generate_educational_report().await.unwrap();
+    assert!(report.contains("🔥 Trending:\n1. Trend — score"));
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use common::{config, parsed_trade, MockRpc};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::transaction_parser::{ParsedData, SwapType};
+use solana_vntr_sniper::processor::wallet_age::{
+    classify, BuyerAge, WalletAgeChecker, WalletAgeConfig, WalletOrigin, WalletOriginLookup,
+};
+use solana_vntr_sniper::processor::watchlist::{export_watchlist, WatchlistFormat};
+
+const NOW: i64 = 1_750_000_000;
+
+/// Origins from a fixed table; unknown wallets have a history too long to walk
+#[derive(Default)]
+struct MockOrigins {
+    origins: HashMap<Pubkey, WalletOrigin>,
+    lookups: AtomicUsize,
+}
+
+#[async_trait]
+impl WalletOriginLookup for MockOrigins {
+    async fn origin(&self, wallet: &Pubkey) -> Result<WalletOrigin> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        Ok(self.origins.get(wallet).copied().unwrap_or(WalletOrigin { created_at: None, funder: None }))
+    }
+}
+
+fn trade(mint: Pubkey, signer: Pubkey, swap_type: SwapType) -> ParsedData {
+    parsed_trade(mint)
+        .timestamp(NOW as u64)
+        .swap_type(swap_type)
+        .signer(signer)
+        .sol(0.2)
+        .price(0.000001)
+        .liquidity(30.0)
+        .name("Farmed")
+        .symbol("FRM")
+        .progress(10.0)
+        .build()
+}
+
+fn fresh(funder: Pubkey, minutes_ago: i64) -> WalletOrigin {
+    WalletOrigin { created_at: Some(NOW - minutes_ago * 60), funder: Some(funder) }
+}
+
+#[test]
+fn wallets_created_within_the_limit_are_fresh() {
+    let funder = Pubkey::new_unique();
+    assert_eq!(
+        classify(&fresh(funder, 3), NOW, 3600),
+        BuyerAge::Fresh { age_secs: 180, funder: Some(funder) }
+    );
+    assert_eq!(classify(&fresh(funder, 61), NOW, 3600), BuyerAge::Aged);
+    assert_eq!(classify(&WalletOrigin { created_at: None, funder: None }, NOW, 3600), BuyerAge::Aged);
+}
+
+#[tokio::test]
+async fn fresh_wallet_share_and_shared_funder_are_tracked() {
+    let (mint, farm) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let farmed: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let lookup = Arc::new(MockOrigins {
+        origins: farmed.iter().map(|wallet| (*wallet, fresh(farm, 2))).collect(),
+        ..MockOrigins::default()
+    });
+    let checker = WalletAgeChecker::new(WalletAgeConfig::default(), lookup.clone());
+
+    for wallet in &farmed {
+        checker.observe(&trade(mint, *wallet, SwapType::Buy)).await.unwrap();
+    }
+    let stats = checker.observe(&trade(mint, Pubkey::new_unique(), SwapType::Buy)).await.unwrap();
+    assert_eq!((stats.checked, stats.fresh), (4, 3));
+    assert_eq!(stats.fresh_pct(), Some(75.0));
+    assert_eq!(stats.top_funder(), Some((farm, 3)));
+
+    // Repeat buys and sells are not looked up again
+    assert!(checker.observe(&trade(mint, farmed[0], SwapType::Buy)).await.is_none());
+    assert!(checker.observe(&trade(mint, farmed[1], SwapType::Sell)).await.is_none());
+    assert_eq!(lookup.lookups.load(Ordering::SeqCst), 4);
+
+    // A wallet buying another token is classified from the cache
+    let other = Pubkey::new_unique();
+    assert_eq!(checker.observe(&trade(other, farmed[0], SwapType::Buy)).await.unwrap().fresh, 1);
+    assert_eq!(lookup.lookups.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn only_the_first_buyers_are_checked() {
+    let mint = Pubkey::new_unique();
+    let config = WalletAgeConfig { max_buyers: 2, ..WalletAgeConfig::default() };
+    let checker = WalletAgeChecker::new(config, Arc::new(MockOrigins::default()));
+
+    for _ in 0..2 {
+        assert!(checker.observe(&trade(mint, Pubkey::new_unique(), SwapType::Buy)).await.is_some());
+    }
+    assert!(checker.observe(&trade(mint, Pubkey::new_unique(), SwapType::Buy)).await.is_none());
+    assert_eq!(checker.stats(&mint).await.unwrap().fresh_pct(), Some(0.0));
+}
+
+#[tokio::test]
+async fn the_fresh_wallet_share_is_part_of_token_analytics() {
+    let (mint, farm, farmed) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let lookup = MockOrigins { origins: HashMap::from([(farmed, fresh(farm, 5))]), ..MockOrigins::default() };
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), None)
+        .with_wallet_age_checker(WalletAgeChecker::new(WalletAgeConfig::default(), Arc::new(lookup)));
+
+    monitor.process_for_education(&trade(mint, farmed, SwapType::Buy)).await.unwrap();
+    monitor.process_for_education(&trade(mint, Pubkey::new_unique(), SwapType::Buy)).await.unwrap();
+
+    let metrics = monitor.get_token(&mint).await.unwrap();
+    assert_eq!(metrics.fresh_wallet_pct, Some(50.0));
+    let csv = export_watchlist(&[metrics], WatchlistFormat::Csv).unwrap();
+    assert!(csv.lines().next().unwrap().ends_with(",fresh_wallet_pct"));
+    assert!(csv.lines().nth(1).unwrap().ends_with(",50"));
+}
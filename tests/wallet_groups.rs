@@ -0,0 +1,124 @@
+mod common;
+
+use std::sync::Arc;
+use common::{config, parsed_trade, MockRpc, RecordingSink};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::clock::SimulatedClock;
+use solana_vntr_sniper::processor::educational_monitor::EducationalMonitor;
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+use solana_vntr_sniper::processor::transaction_parser::ParsedData;
+use solana_vntr_sniper::processor::wallet_groups::{parse_wallets, WalletGroups, WalletGroupsConfig};
+
+fn groups(clock: Arc<SimulatedClock>) -> WalletGroups {
+    WalletGroups::new(WalletGroupsConfig { path: None, window_secs: 300, min_wallets: 3 }).with_clock(clock)
+}
+
+fn buy(wallet: Pubkey, mint: Pubkey, sol: f64) -> ParsedData {
+    parsed_trade(mint)
+        .signer(wallet)
+        .sol(sol)
+        .name("Groupie")
+        .symbol("GRP")
+        .progress(30.0)
+        .build()
+}
+
+fn wallets(n: usize) -> Vec<Pubkey> {
+    (0..n).map(|_| Pubkey::new_unique()).collect()
+}
+
+#[test]
+fn csv_json_and_pasted_lists_parse() {
+    let [a, b, c] = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+    let csv = parse_wallets(&format!("address,label,group\n{},Ansem,KOLs\n{},,KOLs\nnot-a-wallet,x,y\n", a, b));
+    assert_eq!(csv.wallets.len(), 2);
+    assert_eq!(csv.wallets[0].1.label.as_deref(), Some("Ansem"));
+    assert_eq!(csv.wallets[1].1.group.as_deref(), Some("KOLs"));
+    assert_eq!(csv.invalid, vec!["not-a-wallet"]);
+
+    let json = parse_wallets(&format!(r#"["{}", {{"address": "{}", "group": "Team"}}]"#, a, b));
+    assert_eq!(json.wallets.len(), 2);
+    assert_eq!(json.wallets[1].1.group.as_deref(), Some("Team"));
+
+    let pasted = parse_wallets(&format!("{} {}\n{}, {}", a, b, c, a));
+    assert_eq!(pasted.wallets.len(), 3);
+}
+
+#[test]
+fn unlabeled_wallets_are_labeled_after_their_group() {
+    let groups = groups(Arc::new(SimulatedClock::at_unix(1_750_000_000)));
+    let [a, b, c] = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+    let parsed = parse_wallets(&format!("{},,KOLs\n{},Whale,KOLs\n{}\n", a, b, c));
+    assert_eq!(groups.import(parsed.wallets), (3, 0));
+    assert_eq!(groups.label(&a).as_deref(), Some("KOLs #1"));
+    assert_eq!(groups.label(&b).as_deref(), Some("Whale"));
+    assert_eq!(groups.get(&c).unwrap().group.as_deref(), Some("default"));
+
+    // Re-importing keeps the label unless a new one is given
+    assert_eq!(groups.import(parse_wallets(&format!("{},,KOLs", a)).wallets), (0, 1));
+    assert_eq!(groups.label(&a).as_deref(), Some("KOLs #1"));
+    assert_eq!(groups.groups().get("KOLs").map(Vec::len), Some(2));
+}
+
+#[test]
+fn a_group_alerts_once_enough_wallets_buy_within_the_window() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let groups = groups(clock.clone());
+    let kols = wallets(4);
+    let csv: String = kols.iter().map(|w| format!("{},,KOLs\n", w)).collect();
+    groups.import(parse_wallets(&csv).wallets);
+    let mint = Pubkey::new_unique();
+
+    assert!(groups.observe(&buy(kols[0], mint, 1.0)).is_none());
+    clock.advance(chrono::Duration::seconds(60));
+    assert!(groups.observe(&buy(kols[0], mint, 0.5)).is_none(), "the same wallet twice is one wallet");
+    assert!(groups.observe(&buy(Pubkey::new_unique(), mint, 9.0)).is_none(), "untracked wallets do not count");
+    clock.advance(chrono::Duration::seconds(180));
+    assert!(groups.observe(&buy(kols[1], mint, 2.0)).is_none(), "two wallets are below the threshold");
+
+    let activity = groups.observe(&buy(kols[2], mint, 3.0)).unwrap();
+    assert_eq!(activity.group, "KOLs");
+    assert_eq!(activity.buyers, vec![("KOLs #1".to_string(), 1.5), ("KOLs #2".to_string(), 2.0), ("KOLs #3".to_string(), 3.0)]);
+    assert_eq!(activity.total_sol, 6.5);
+    assert_eq!(activity.headline(), "3 wallets in group 'KOLs' bought Groupie in 4 minutes");
+
+    assert!(groups.observe(&buy(kols[3], mint, 1.0)).is_none(), "one alert per window");
+}
+
+#[test]
+fn buys_outside_the_window_do_not_add_up() {
+    let clock = Arc::new(SimulatedClock::at_unix(1_750_000_000));
+    let groups = groups(clock.clone());
+    let kols = wallets(3);
+    groups.import(parse_wallets(&kols.iter().map(|w| format!("{},,KOLs\n", w)).collect::<String>()).wallets);
+    let mint = Pubkey::new_unique();
+
+    groups.observe(&buy(kols[0], mint, 1.0));
+    groups.observe(&buy(kols[1], mint, 1.0));
+    clock.advance(chrono::Duration::seconds(301));
+    assert!(groups.observe(&buy(kols[2], mint, 1.0)).is_none());
+}
+
+#[tokio::test]
+async fn the_monitor_alerts_group_buys() {
+    let sink = Arc::new(RecordingSink::default());
+    let telegram = TelegramAlertSystem::with_sink(sink.clone(), true);
+    let kols = wallets(3);
+    let monitor = EducationalMonitor::new(config(Arc::new(MockRpc::confirming())), Some(Arc::new(telegram)))
+        .with_wallet_groups(WalletGroups::new(WalletGroupsConfig { path: None, window_secs: 300, min_wallets: 3 }));
+    let csv: String = kols.iter().enumerate().map(|(i, w)| format!("{},KOL {},KOLs\n", w, i)).collect();
+    assert_eq!(monitor.import_wallets(parse_wallets(&csv)), (3, 0));
+    let mint = Pubkey::new_unique();
+
+    for wallet in &kols {
+        monitor.process_for_education(&buy(*wallet, mint, 0.5)).await.unwrap();
+    }
+
+    let texts = sink.texts();
+    let alert = texts.iter().find(|t| t.contains("GROUP BUYING")).expect("group alert");
+    assert!(alert.contains("3 wallets in group 'KOLs' bought Groupie"), "{}", alert);
+    assert!(alert.contains("KOL 2: 0.50 SOL"), "{}", alert);
+    assert!(monitor.wallet_groups().contains("KOLs (3)"));
+}
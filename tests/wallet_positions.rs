@@ -0,0 +1,66 @@
+mod common;
+
+use std::sync::Arc;
+use common::{parsed_trade, RecordingSink};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::telegram_alerts::TelegramAlertSystem;
+use solana_vntr_sniper::processor::transaction_parser::{ParsedData, SwapType};
+use solana_vntr_sniper::processor::wallet_positions::{EntryComparison, WalletPositions};
+
+fn trade(wallet: Pubkey, mint: Pubkey, swap_type: SwapType, sol: f64, price: f64) -> ParsedData {
+    parsed_trade(mint)
+        .swap_type(swap_type)
+        .signer(wallet)
+        .sol(sol)
+        .price(price)
+        .liquidity(50.0)
+        .build()
+}
+
+#[test]
+fn average_entry_is_weighted_by_sol_spent() {
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut positions = WalletPositions::new();
+
+    assert_eq!(positions.record(&trade(wallet, mint, SwapType::Buy, 1.0, 1.0)), None);
+    let second = positions.record(&trade(wallet, mint, SwapType::Buy, 3.0, 2.0)).unwrap();
+    assert_eq!(second.multiple(), 2.0);
+    assert_eq!(positions.get(&wallet, &mint).unwrap().average_entry(), Some(1.75));
+
+    let sell = positions.record(&trade(wallet, mint, SwapType::Sell, 1.0, 5.6)).unwrap();
+    assert_eq!(sell, EntryComparison { average_entry: 1.75, trade_price: 5.6, buys: 2 });
+    assert!((sell.multiple() - 3.2).abs() < 1e-9);
+
+    // Another wallet in the same token has its own entry
+    assert_eq!(positions.record(&trade(Pubkey::new_unique(), mint, SwapType::Sell, 1.0, 5.6)), None);
+}
+
+#[test]
+fn a_closed_position_starts_a_new_average() {
+    let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut positions = WalletPositions::new();
+    positions.record(&trade(wallet, mint, SwapType::Buy, 2.0, 1.0));
+    // Sells all 2 tokens bought
+    positions.record(&trade(wallet, mint, SwapType::Sell, 6.0, 3.0));
+
+    let rebuy = positions.record(&trade(wallet, mint, SwapType::Buy, 1.0, 4.0)).unwrap();
+    assert_eq!(rebuy.average_entry, 1.0);
+    assert_eq!(positions.get(&wallet, &mint).unwrap().average_entry(), Some(4.0));
+    assert_eq!(positions.get(&wallet, &mint).unwrap().buys, 1);
+
+    positions.retain(|m| *m != mint);
+    assert!(positions.is_empty());
+}
+
+#[tokio::test]
+async fn sell_alert_compares_the_price_to_the_average_entry() {
+    let sink = Arc::new(RecordingSink::default());
+    let alerts = TelegramAlertSystem::with_sink(sink.clone(), true);
+    let entry = EntryComparison { average_entry: 0.5, trade_price: 1.6, buys: 3 };
+
+    alerts.alert_wallet_activity(&Pubkey::new_unique(), "SELL", &Pubkey::new_unique(), None, 2.0, None, Some(entry), None, None).await.unwrap();
+
+    let text = &sink.texts()[0];
+    assert!(text.contains("selling at ~3.2x their average entry"), "{}", text);
+    assert!(text.contains("over 3 buys"), "{}", text);
+}
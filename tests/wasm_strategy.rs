@@ -0,0 +1,90 @@
+mod common;
+
+use common::parsed_trade;
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::library::event_export::SwapEvent;
+use solana_vntr_sniper::processor::pipeline::{PipelineEvent, Strategy};
+use solana_vntr_sniper::processor::transaction_parser::ParsedData;
+use solana_vntr_sniper::processor::wasm_strategy::{SignalKind, WasmLimits, WasmStrategy};
+
+/// Signals a buy for events over 600 bytes of JSON, nothing otherwise
+const THRESHOLD_GUEST: &str = r#"
+(module
+  (memory (export "memory") 1)
+  (data (i32.const 1024) "[{\"signal\":\"buy\",\"reason\":\"big buy\",\"confidence\":0.9}]")
+  (func (export "alloc") (param $len i32) (result i32) (i32.const 4096))
+  (func (export "on_event") (param $ptr i32) (param $len i32) (result i64)
+    (if (result i64) (i32.gt_u (local.get $len) (i32.const 600))
+      (then (i64.or (i64.shl (i64.const 1024) (i64.const 32)) (i64.const 54)))
+      (else (i64.const 0)))))
+"#;
+
+const LOOPING_GUEST: &str = r#"
+(module
+  (memory (export "memory") 1)
+  (func (export "alloc") (param $len i32) (result i32) (i32.const 4096))
+  (func (export "on_event") (param $ptr i32) (param $len i32) (result i64)
+    (loop $forever (br $forever))
+    (i64.const 0)))
+"#;
+
+fn event(name_len: usize) -> ParsedData {
+    parsed_trade(Pubkey::new_unique())
+        .pool(&"x".repeat(name_len))
+        .sol(3.0)
+        .price(0.000001)
+        .liquidity(30.0)
+        .progress(5.0)
+        .build()
+}
+
+#[tokio::test]
+async fn a_sandboxed_strategy_turns_events_into_signals() {
+    let strategy = WasmStrategy::from_bytes("threshold", THRESHOLD_GUEST.as_bytes(), WasmLimits::default()).unwrap();
+
+    assert!(strategy.evaluate(&SwapEvent::from(&event(0))).unwrap().is_empty());
+
+    let big = event(400);
+    let signals = strategy.evaluate(&SwapEvent::from(&big)).unwrap();
+    assert_eq!(signals.len(), 1);
+    assert_eq!(signals[0].signal, SignalKind::Buy);
+    assert_eq!(signals[0].mint, Some(big.token_mint.to_string()));
+    assert_eq!(signals[0].confidence, Some(0.9));
+
+    let notices = strategy.handle(&PipelineEvent::from_parsed(big.clone())).await.unwrap();
+    assert_eq!(notices[0].title, "threshold: BUY signal");
+    assert_eq!(notices[0].body, format!("{} — big buy (confidence 90%)", big.token_mint));
+}
+
+#[test]
+fn a_runaway_strategy_runs_out_of_fuel_and_is_disabled() {
+    let limits = WasmLimits { fuel: 100_000, ..WasmLimits::default() };
+    let strategy = WasmStrategy::from_bytes("looping", LOOPING_GUEST.as_bytes(), limits).unwrap();
+    for _ in 0..5 {
+        assert!(strategy.evaluate(&SwapEvent::from(&event(0))).is_err());
+    }
+    assert!(strategy.is_disabled());
+    assert!(strategy.evaluate(&SwapEvent::from(&event(0))).unwrap().is_empty());
+}
+
+#[test]
+fn modules_with_imports_or_missing_exports_are_refused() {
+    let importing = r#"(module (import "env" "now" (func)) (memory (export "memory") 1))"#;
+    assert!(WasmStrategy::from_bytes("importing", importing.as_bytes(), WasmLimits::default()).is_err());
+    let incomplete = r#"(module (memory (export "memory") 1))"#;
+    assert!(WasmStrategy::from_bytes("incomplete", incomplete.as_bytes(), WasmLimits::default()).is_err());
+}
+
+#[test]
+fn invalid_signal_output_is_an_error() {
+    let guest = r#"
+    (module
+      (memory (export "memory") 1)
+      (data (i32.const 1024) "[{\"signal\":\"hodl\"}]")
+      (func (export "alloc") (param $len i32) (result i32) (i32.const 4096))
+      (func (export "on_event") (param $ptr i32) (param $len i32) (result i64)
+        (i64.or (i64.shl (i64.const 1024) (i64.const 32)) (i64.const 19))))
+    "#;
+    let strategy = WasmStrategy::from_bytes("bad", guest.as_bytes(), WasmLimits::default()).unwrap();
+    assert!(strategy.evaluate(&SwapEvent::from(&event(0))).is_err());
+}
@@ -0,0 +1,82 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::processor::watchlist::parse_watchlist;
+
+fn mints(count: usize) -> Vec<String> {
+    (0..count).map(|_| Pubkey::new_unique().to_string()).collect()
+}
+
+#[test]
+fn csv_rows_keep_names_and_skip_headers_comments_and_blanks() {
+    let m = mints(3);
+    let content = format!(
+        "mint,name,symbol\n# tokens to watch\n\n{},Dog Coin,DOG\n  {} , , CAT \n{}\n",
+        m[0], m[1], m[2]
+    );
+
+    let parsed = parse_watchlist(&content);
+    assert!(parsed.invalid.is_empty(), "{:?}", parsed.invalid);
+    assert_eq!(parsed.entries.len(), 3);
+    assert_eq!(parsed.entries[0].1.name.as_deref(), Some("Dog Coin"));
+    assert_eq!(parsed.entries[0].1.symbol.as_deref(), Some("DOG"));
+    // Empty fields are no name rather than an empty one
+    assert_eq!(parsed.entries[1].0.to_string(), m[1]);
+    assert_eq!(parsed.entries[1].1.name, None);
+    assert_eq!(parsed.entries[1].1.symbol.as_deref(), Some("CAT"));
+    assert_eq!(parsed.entries[2].1.name, None);
+}
+
+#[test]
+fn pasted_lists_of_mints_are_split_on_commas_and_whitespace() {
+    let m = mints(4);
+    let parsed = parse_watchlist(&format!("{}, {}\n{}   {}", m[0], m[1], m[2], m[3]));
+
+    let parsed_mints: Vec<String> = parsed.entries.iter().map(|(mint, _)| mint.to_string()).collect();
+    assert_eq!(parsed_mints, m);
+    assert!(parsed.entries.iter().all(|(_, entry)| entry.name.is_none()));
+}
+
+#[test]
+fn invalid_and_duplicate_mints_are_separated_out() {
+    let m = mints(1);
+    let parsed = parse_watchlist(&format!("{},First\nnot-a-mint,Bad\n{},Again", m[0], m[0]));
+
+    assert_eq!(parsed.entries.len(), 1);
+    assert_eq!(parsed.entries[0].1.name.as_deref(), Some("First"));
+    assert_eq!(parsed.invalid, vec!["not-a-mint".to_string()]);
+}
+
+#[test]
+fn json_accepts_mint_strings_entries_and_exports() {
+    let m = mints(3);
+    let content = format!(
+        r#"[
+            "{}",
+            {{ "mint": "{}", "symbol": "DOG" }},
+            {{ "mint": "{}", "name": "Cat", "pinned": true, "volume_24h": 12.5 }}
+        ]"#,
+        m[0], m[1], m[2]
+    );
+
+    let parsed = parse_watchlist(&content);
+    assert!(parsed.invalid.is_empty(), "{:?}", parsed.invalid);
+    assert_eq!(parsed.entries.len(), 3);
+    assert_eq!(parsed.entries[0].1.symbol, None);
+    assert_eq!(parsed.entries[1].1.symbol.as_deref(), Some("DOG"));
+    assert_eq!(parsed.entries[2].1.name.as_deref(), Some("Cat"));
+}
+
+#[test]
+fn malformed_json_is_reported_instead_of_parsed_as_csv() {
+    let parsed = parse_watchlist(r#"[ "unterminated"#);
+
+    assert!(parsed.entries.is_empty());
+    assert_eq!(parsed.invalid.len(), 1);
+    assert!(parsed.invalid[0].starts_with("invalid JSON"), "{:?}", parsed.invalid);
+}
+
+#[test]
+fn empty_content_parses_to_nothing() {
+    let parsed = parse_watchlist("  \n\n");
+    assert!(parsed.entries.is_empty());
+    assert!(parsed.invalid.is_empty());
+}